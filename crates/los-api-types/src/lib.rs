@@ -0,0 +1,268 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - REST API TYPES
+//
+// Request and response bodies of the los-node REST API. los-node parses and
+// builds these, and los-client sends and reads them, so the two cannot drift.
+//
+// Amounts are CIL (u128) unless the field name says LOS. `*_los` fields are
+// display strings only; never parse them back into amounts.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ─────────────────────────────────────────────────────────────────
+// Requests
+// ─────────────────────────────────────────────────────────────────
+
+/// POST /send
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SendRequest {
+    /// Sender address (if empty, use node's address)
+    pub from: Option<String>,
+    pub target: String,
+    /// Whole LOS (ignored when `amount_cil` is set)
+    pub amount: u128,
+    /// Amount already in CIL (skips ×CIL_PER_LOS). Used by client-signed blocks.
+    pub amount_cil: Option<u128>,
+    /// Client-provided signature (if present, validate instead of signing)
+    pub signature: Option<String>,
+    /// Sender's public key (hex-encoded, REQUIRED for signature verification)
+    pub public_key: Option<String>,
+    /// Previous block hash (for client-side signing)
+    pub previous: Option<String>,
+    /// PoW nonce (if client pre-computed)
+    pub work: Option<u64>,
+    /// Client timestamp (used when client_signed to match signing_hash)
+    pub timestamp: Option<u64>,
+    /// Client fee (used when client_signed to match signing_hash)
+    pub fee: Option<u128>,
+    /// Time lock: not applied before this Unix time (signed when set)
+    pub valid_after: Option<u64>,
+    /// Expiry: unreceived after this Unix time → sender may Reclaim (signed when set)
+    pub expires_at: Option<u64>,
+}
+
+/// POST /deploy-contract
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeployContractRequest {
+    pub owner: String,
+    /// base64 encoded WASM
+    pub bytecode: String,
+    pub initial_state: Option<BTreeMap<String, String>>,
+    /// Initial CIL funding for contract
+    pub amount_cil: Option<u128>,
+    /// Client-signed: Dilithium5 sig
+    pub signature: Option<String>,
+    /// Client-signed: deployer's pubkey (hex)
+    pub public_key: Option<String>,
+    /// Client-signed: previous block hash
+    pub previous: Option<String>,
+    /// Client-signed: PoW nonce
+    pub work: Option<u64>,
+    /// Client-signed: block timestamp
+    pub timestamp: Option<u64>,
+    /// Client-signed: fee in CIL
+    pub fee: Option<u128>,
+    /// Strip custom sections + linker exports before hashing
+    pub optimize: Option<bool>,
+    /// 32-byte hex salt: deploy at blake3(owner ‖ salt ‖ code_hash) (signed in the link)
+    pub salt: Option<String>,
+}
+
+/// POST /call-contract
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallContractRequest {
+    pub contract_address: String,
+    pub function: String,
+    pub args: Vec<String>,
+    pub gas_limit: Option<u64>,
+    /// Caller address (if empty, use node's address). Client-signed with a
+    /// session key: the account that granted it
+    pub caller: Option<String>,
+    /// CIL to send to contract (msg.value)
+    pub amount_cil: Option<u128>,
+    /// Client-signed: Dilithium5 sig
+    pub signature: Option<String>,
+    /// Client-signed: caller's pubkey (hex)
+    pub public_key: Option<String>,
+    /// Client-signed: previous block hash
+    pub previous: Option<String>,
+    /// Client-signed: PoW nonce
+    pub work: Option<u64>,
+    /// Client-signed: block timestamp
+    pub timestamp: Option<u64>,
+    /// Client-signed: fee in CIL
+    pub fee: Option<u128>,
+}
+
+/// POST /dry-run-contract
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunContractRequest {
+    pub contract_address: String,
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub gas_limit: Option<u64>,
+    /// Caller address (if empty, use node's address)
+    pub caller: Option<String>,
+    /// CIL attached to the call (msg_value)
+    pub amount_cil: Option<u128>,
+    /// Record host calls + per-function instruction counts
+    #[serde(default)]
+    pub trace: bool,
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Responses
+// ─────────────────────────────────────────────────────────────────
+
+/// GET /balance/:address
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BalanceResponse {
+    pub address: String,
+    pub balance: String,
+    pub balance_los: String,
+    pub balance_cil: u128,
+    /// balance_cil as a string, for JSON readers limited to f64
+    pub balance_cil_str: String,
+    pub head: String,
+    pub block_count: u64,
+}
+
+/// Timestamp range the node accepts for the next block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampWindow {
+    pub suggested: u64,
+    pub min: u64,
+    pub max: u64,
+}
+
+/// GET /account/:address/next — everything a wallet needs to build its next block
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountNextResponse {
+    pub status: String,
+    pub address: String,
+    pub chain_id: u64,
+    /// `previous` of the next block: the ledger head, extended by sends awaiting consensus
+    pub previous: String,
+    pub head: String,
+    pub block_count: u64,
+    pub pending_sends: u64,
+    pub balance_cil: u128,
+    pub balance_los: String,
+    /// Balance minus amount + fee of the pending sends
+    pub spendable_cil: u128,
+    pub spendable_los: String,
+    pub timestamp: TimestampWindow,
+    pub pow_difficulty_bits: u32,
+    pub min_fee_cil: u128,
+}
+
+/// GET /fee-estimate/:address
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimateResponse {
+    pub address: String,
+    pub base_fee_cil: u128,
+    pub base_fee_los: String,
+    pub estimated_fee_cil: u128,
+    pub estimated_fee_los: String,
+    pub fee_multiplier: u64,
+    pub fee_multiplier_bps: u64,
+}
+
+/// Answer of POST /send, /deploy-contract and /call-contract. Only the
+/// fields shared by those endpoints are typed; others are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_paid_cil: Option<u128>,
+    /// Time-locked send admitted to the mempool, applied at `valid_after`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub scheduled: bool,
+}
+
+/// Error body of every endpoint: `{"status": "error", "code": 400, "msg": "..."}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub status: String,
+    /// HTTP status; missing on older errors, which are served as 400
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u16>,
+    #[serde(default)]
+    pub msg: String,
+}
+
+impl ApiError {
+    /// Error body of a JSON answer, if it is one
+    pub fn from_body(body: &serde_json::Value) -> Option<Self> {
+        if body.get("status").and_then(|s| s.as_str()) != Some("error") {
+            return None;
+        }
+        serde_json::from_value(body.clone()).ok()
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "HTTP {}: {}", code, self.msg),
+            None => write!(f, "{}", self.msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_request_optional_fields() {
+        let req: SendRequest =
+            serde_json::from_str(r#"{"target": "LOSabc", "amount": 5}"#).unwrap();
+        assert_eq!(req.amount, 5);
+        assert!(req.signature.is_none() && req.valid_after.is_none());
+    }
+
+    #[test]
+    fn test_amounts_above_u64() {
+        let b: BalanceResponse = serde_json::from_str(
+            r#"{"address": "LOSabc", "balance": "2000000000", "balance_los": "2000000000",
+                "balance_cil": 200000000000000000000, "balance_cil_str": "200000000000000000000",
+                "head": "0", "block_count": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(b.balance_cil, 200_000_000_000_000_000_000);
+        assert!(serde_json::to_string(&b)
+            .unwrap()
+            .contains("\"balance_cil\":200000000000000000000"));
+    }
+
+    #[test]
+    fn test_submit_response_ignores_other_fields() {
+        let body = serde_json::json!({
+            "status": "success",
+            "tx_hash": "ab",
+            "fee_paid_cil": 100_000,
+            "initial_power": 3
+        });
+        let r: SubmitResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(r.tx_hash.as_deref(), Some("ab"));
+        assert_eq!(r.fee_paid_cil, Some(100_000));
+        assert!(!r.scheduled);
+    }
+
+    #[test]
+    fn test_api_error_from_body() {
+        let err = ApiError::from_body(&serde_json::json!({
+            "status": "error", "code": 429, "msg": "slow down"
+        }))
+        .unwrap();
+        assert_eq!(err.code, Some(429));
+        assert_eq!(err.to_string(), "HTTP 429: slow down");
+        assert!(ApiError::from_body(&serde_json::json!({"status": "success"})).is_none());
+    }
+}
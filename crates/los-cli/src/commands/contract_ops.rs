@@ -100,6 +100,8 @@ pub async fn deploy_contract(
         work: 0,
         timestamp,
         fee: MIN_DEPLOY_FEE_CIL,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     // 5. PoW
@@ -195,6 +197,8 @@ pub async fn call_contract(
         work: 0,
         timestamp,
         fee,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     // 4. PoW
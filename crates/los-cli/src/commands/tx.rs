@@ -1,7 +1,8 @@
 use crate::commands::common::load_wallet_keypair;
 use crate::{print_error, print_info, print_success, TxCommands};
 use colored::*;
-use los_core::{Block, BlockType, CIL_PER_LOS, MIN_POW_DIFFICULTY_BITS};
+use los_core::units::Los;
+use los_core::{Block, BlockType, MIN_POW_DIFFICULTY_BITS};
 use std::path::Path;
 
 pub async fn handle(
@@ -46,7 +47,7 @@ pub(crate) fn compute_pow(block: &mut Block) {
 
 async fn send_tx(
     to: &str,
-    amount: u64,
+    amount: Los,
     from_wallet: &str,
     rpc: &str,
     config_dir: &Path,
@@ -96,9 +97,7 @@ async fn send_tx(
         _ => 100_000, // Default base fee
     };
 
-    let amount_cil = (amount as u128)
-        .checked_mul(CIL_PER_LOS)
-        .ok_or("Amount overflow")?;
+    let amount_cil = amount.cil().0;
 
     if balance_cil < amount_cil {
         print_error(&format!(
@@ -124,6 +123,8 @@ async fn send_tx(
         work: 0,
         timestamp,
         fee: fee_cil, // Include proper fee from fee-estimate
+        valid_after: 0,
+        expires_at: 0,
     };
 
     // 4. Compute PoW (anti-spam)
@@ -144,7 +145,7 @@ async fn send_tx(
     let payload = serde_json::json!({
         "from": sender_addr,
         "target": to,
-        "amount": amount,
+        "amount": amount.whole(),
         "amount_cil": amount_cil,
         "previous": block.previous,
         "signature": block.signature,
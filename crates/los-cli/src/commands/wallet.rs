@@ -24,6 +24,9 @@ pub async fn handle(
         WalletCommands::Balance { address } => show_balance(&address, rpc).await?,
         WalletCommands::Export { name, output } => export_wallet(&name, config_dir, &output)?,
         WalletCommands::Import { input, name } => import_wallet(&input, config_dir, &name)?,
+        WalletCommands::Migrate { to, watch } => {
+            migrate_wallet(to.as_deref(), watch, rpc, config_dir).await?
+        }
     }
     Ok(())
 }
@@ -95,6 +98,118 @@ fn create_new_wallet(name: &str, config_dir: &Path) -> Result<(), Box<dyn std::e
     Ok(())
 }
 
+/// Sweep an Ed25519 (testnet desktop wallet) account to a Dilithium5 key.
+/// Both keys sign the Migrate block (los_core::key_migration).
+async fn migrate_wallet(
+    to_wallet: Option<&str>,
+    watch: Option<u64>,
+    rpc: &str,
+    config_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let seed_hex = match std::env::var("LOS_MIGRATION_SEED") {
+        Ok(s) if !s.is_empty() => s,
+        _ => rpassword::prompt_password("Enter wallet BIP39 seed (hex): ")?,
+    };
+    let seed = hex::decode(seed_hex.trim()).map_err(|_| "Seed must be hex")?;
+    if seed.len() < 32 {
+        return Err("Seed must be at least 32 bytes".into());
+    }
+    let ed = los_crypto::ed25519_keypair_from_seed(&seed)
+        .map_err(|e| format!("Ed25519 key derivation failed: {}", e))?;
+    let account = los_crypto::public_key_to_address(&ed.public_key);
+
+    // Target: an existing CLI wallet, or the Dilithium5 key of the same seed
+    // (the address the wallet app shows once Dilithium5 is available)
+    let target = match to_wallet {
+        Some(name) => crate::commands::common::load_wallet_keypair(name, config_dir)?.1,
+        None => {
+            print_info("Deriving Dilithium5 keypair from seed...");
+            los_crypto::generate_keypair_from_seed(&seed)
+        }
+    };
+    let new_address = los_crypto::public_key_to_address(&target.public_key);
+    println!("{} {}", "From (Ed25519):".bold(), account.dimmed());
+    println!("{} {}", "To (Dilithium5):".bold(), new_address.green());
+
+    let client = reqwest::Client::new();
+    loop {
+        let account_data: serde_json::Value = client
+            .get(format!("{}/balance/{}", rpc, account))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let previous = account_data["head"].as_str().unwrap_or("0").to_string();
+        let balance_cil: u128 = account_data["balance_cil_str"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| account_data["balance_cil"].as_u64().unwrap_or(0) as u128);
+        let fee = los_core::key_migration::MIN_MIGRATION_FEE_CIL;
+
+        if balance_cil > fee {
+            let link = los_core::key_migration::sign_migration_link(
+                &account,
+                &previous,
+                &target.public_key,
+                &target.secret_key,
+            )?;
+            let mut block = los_core::Block {
+                account: account.clone(),
+                previous,
+                block_type: los_core::BlockType::Migrate,
+                amount: balance_cil - fee,
+                link,
+                signature: String::new(),
+                public_key: hex::encode(&ed.public_key),
+                work: 0,
+                timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)?
+                    .as_secs(),
+                fee,
+                valid_after: 0,
+                expires_at: 0,
+            };
+            print_info("Computing Proof-of-Work...");
+            crate::commands::tx::compute_pow(&mut block);
+            let sig = los_crypto::sign_ed25519(block.signing_hash().as_bytes(), &ed.secret_key)
+                .map_err(|e| format!("Signing failed: {}", e))?;
+            block.signature = hex::encode(sig);
+
+            let resp: serde_json::Value = client
+                .post(format!("{}/migrate-account", rpc))
+                .json(&block)
+                .send()
+                .await?
+                .json()
+                .await?;
+            if resp["status"].as_str() == Some("success") {
+                print_success(&format!(
+                    "Migrated {}.{:011} LOS",
+                    block.amount / CIL_PER_LOS,
+                    block.amount % CIL_PER_LOS
+                ));
+                println!(
+                    "  {} {}",
+                    "Block Hash:".bold(),
+                    resp["block_hash"].as_str().unwrap_or("unknown").green()
+                );
+            } else {
+                print_error(&format!(
+                    "Migration failed: {}",
+                    resp["msg"].as_str().unwrap_or("Unknown error")
+                ));
+            }
+        } else if watch.is_none() {
+            print_info("Nothing to migrate: balance does not cover the fee.");
+        }
+
+        match watch {
+            Some(secs) => tokio::time::sleep(std::time::Duration::from_secs(secs.max(1))).await,
+            None => return Ok(()),
+        }
+    }
+}
+
 fn list_wallets(config_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let wallet_dir = config_dir.join("wallets");
 
@@ -107,6 +107,18 @@ enum WalletCommands {
         #[arg(short, long)]
         name: String,
     },
+
+    /// Sweep a testnet Ed25519 wallet to its Dilithium5 address.
+    /// The BIP39 seed (hex) is read from LOS_MIGRATION_SEED or prompted.
+    Migrate {
+        /// Sweep to this CLI wallet instead of the seed's own Dilithium5 key
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Keep running and sweep funds that arrive later (poll interval, seconds)
+        #[arg(long)]
+        watch: Option<u64>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -168,9 +180,9 @@ enum TxCommands {
         #[arg(short, long)]
         to: String,
 
-        /// Amount in LOS
+        /// Amount in LOS (up to 11 decimals, e.g. 12.5)
         #[arg(short, long)]
-        amount: u64,
+        amount: los_core::units::Los,
 
         /// Sender wallet name
         #[arg(short, long)]
@@ -685,6 +697,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_wallet_migrate() {
+        let cli = Cli::try_parse_from(["los-cli", "wallet", "migrate", "--watch", "30"]);
+        match cli.unwrap().command {
+            Commands::Wallet {
+                action: WalletCommands::Migrate { to, watch },
+            } => assert_eq!((to, watch), (None, Some(30))),
+            _ => panic!("Expected Wallet::Migrate"),
+        }
+    }
+
     #[test]
     fn test_cli_validator_stake() {
         let cli = Cli::try_parse_from([
@@ -725,7 +748,7 @@ mod tests {
                 action: TxCommands::Send { to, amount, from },
             } => {
                 assert_eq!(to, "LOSabc");
-                assert_eq!(amount, 50);
+                assert_eq!(amount, los_core::units::Los::from_whole(50).unwrap());
                 assert_eq!(from, "w1");
             }
             _ => panic!("Expected Tx::Send"),
@@ -897,6 +920,8 @@ mod tests {
             work: 0,
             timestamp: 1700000000,
             fee: 100_000,
+            valid_after: 0,
+            expires_at: 0,
         };
 
         commands::tx::compute_pow(&mut block);
@@ -921,6 +946,8 @@ mod tests {
             work: 0,
             timestamp: 1700000000,
             fee: 100_000,
+            valid_after: 0,
+            expires_at: 0,
         };
 
         // With a random nonce of 0, this is likely invalid (but not guaranteed)
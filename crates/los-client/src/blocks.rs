@@ -0,0 +1,143 @@
+// Client-side block construction: fill a block from GET /account/:addr/next,
+// solve its anti-spam PoW, sign it, and turn it into the request body the
+// node expects. The node recomputes signing_hash() from the request fields,
+// so every field that goes into the hash must reach it unchanged.
+
+use los_api_types::{AccountNextResponse, SendRequest};
+use los_core::{Block, BlockType};
+use los_crypto::KeyPair;
+
+/// Unsigned Send block continuing the chain described by `next`
+pub fn build_send(
+    next: &AccountNextResponse,
+    public_key: &[u8],
+    to: &str,
+    amount_cil: u128,
+    fee_cil: u128,
+) -> Block {
+    Block {
+        account: next.address.clone(),
+        previous: next.previous.clone(),
+        block_type: BlockType::Send,
+        amount: amount_cil,
+        link: to.to_string(),
+        signature: String::new(),
+        public_key: hex::encode(public_key),
+        work: 0,
+        timestamp: next.timestamp.suggested,
+        fee: fee_cil.max(next.min_fee_cil),
+        valid_after: 0,
+        expires_at: 0,
+    }
+}
+
+/// Number of leading zero bits of a hex hash
+fn leading_zero_bits(hash_hex: &str) -> u32 {
+    let mut zero_bits = 0u32;
+    for byte in hex::decode(hash_hex).unwrap_or_default() {
+        if byte == 0 {
+            zero_bits += 8;
+        } else {
+            zero_bits += byte.leading_zeros();
+            break;
+        }
+    }
+    zero_bits
+}
+
+/// Search `block.work` until signing_hash() has `difficulty_bits` leading zero bits.
+/// Must run before signing: the nonce is part of the signed hash.
+pub fn solve_pow(block: &mut Block, difficulty_bits: u32) {
+    let mut nonce = 0u64;
+    loop {
+        block.work = nonce;
+        if leading_zero_bits(&block.signing_hash()) >= difficulty_bits {
+            return;
+        }
+        nonce = nonce.wrapping_add(1);
+    }
+}
+
+/// Sign `block` with a Dilithium5 key
+pub fn sign_block(block: &mut Block, keypair: &KeyPair) -> Result<(), String> {
+    let signature = los_crypto::sign_message(block.signing_hash().as_bytes(), &keypair.secret_key)
+        .map_err(|e| format!("Signing failed: {}", e))?;
+    block.signature = hex::encode(signature);
+    Ok(())
+}
+
+/// POST /send body carrying a signed Send block
+pub fn send_request(block: &Block) -> SendRequest {
+    let amount_los = block.amount / los_core::CIL_PER_LOS;
+    SendRequest {
+        from: Some(block.account.clone()),
+        target: block.link.clone(),
+        amount: amount_los,
+        amount_cil: Some(block.amount),
+        signature: Some(block.signature.clone()),
+        public_key: Some(block.public_key.clone()),
+        previous: Some(block.previous.clone()),
+        work: Some(block.work),
+        timestamp: Some(block.timestamp),
+        fee: Some(block.fee),
+        valid_after: (block.valid_after > 0).then_some(block.valid_after),
+        expires_at: (block.expires_at > 0).then_some(block.expires_at),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_api_types::TimestampWindow;
+
+    fn next() -> AccountNextResponse {
+        AccountNextResponse {
+            status: "success".to_string(),
+            address: "LOSsender".to_string(),
+            previous: "ab".repeat(32),
+            timestamp: TimestampWindow {
+                suggested: 1_771_000_000,
+                min: 1_770_999_000,
+                max: 1_771_000_300,
+            },
+            pow_difficulty_bits: 8,
+            min_fee_cil: los_core::BASE_FEE_CIL,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_send_uses_next_and_min_fee() {
+        let b = build_send(&next(), &[1, 2], "LOSdest", 7, 1);
+        assert_eq!(b.previous, "ab".repeat(32));
+        assert_eq!(b.timestamp, 1_771_000_000);
+        assert_eq!(b.fee, los_core::BASE_FEE_CIL);
+        assert_eq!(b.public_key, "0102");
+    }
+
+    #[test]
+    fn test_solve_pow() {
+        let mut b = build_send(&next(), &[1, 2], "LOSdest", 7, 0);
+        solve_pow(&mut b, 8);
+        assert!(leading_zero_bits(&b.signing_hash()) >= 8);
+    }
+
+    #[test]
+    fn test_send_request_roundtrips_signed_fields() {
+        let mut b = build_send(
+            &next(),
+            &[1, 2],
+            "LOSdest",
+            3 * los_core::CIL_PER_LOS + 1,
+            0,
+        );
+        b.valid_after = 1_771_000_500;
+        b.signature = "cd".to_string();
+        let req = send_request(&b);
+        assert_eq!(req.amount, 3);
+        assert_eq!(req.amount_cil, Some(3 * los_core::CIL_PER_LOS + 1));
+        assert_eq!(req.valid_after, Some(1_771_000_500));
+        assert_eq!(req.expires_at, None);
+        assert_eq!(req.previous.as_deref(), Some(b.previous.as_str()));
+    }
+}
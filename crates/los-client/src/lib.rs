@@ -0,0 +1,367 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - RUST CLIENT
+//
+// Typed client for the los-node REST API, for exchanges and services that
+// integrate in Rust. Bodies are the los-api-types structs los-node itself
+// parses and builds.
+//
+//   let client = Client::new("http://127.0.0.1:3030")?;
+//   let hash = client.send(&keypair, "LOS...", 5 * CIL_PER_LOS).await?;
+//
+// Requests that fail on the transport, with 429 or with 5xx are retried with
+// exponential backoff (RetryPolicy). Submissions are retried too: a resent
+// signed block reuses its `previous`, so it can never apply twice.
+//
+// Blocks are signed locally (blocks.rs); the secret key never leaves the
+// process. The chain id in signing_hash() comes from los-core's build, so
+// link los-core with the `mainnet` feature to sign mainnet blocks.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+pub mod blocks;
+
+pub use los_api_types as types;
+
+use los_api_types::{
+    AccountNextResponse, ApiError, BalanceResponse, CallContractRequest, DeployContractRequest,
+    DryRunContractRequest, FeeEstimateResponse, SendRequest, SubmitResponse,
+};
+use los_crypto::KeyPair;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Connection, timeout or TLS failure
+    Transport(String),
+    /// The node answered with an error body
+    Api(ApiError),
+    /// The answer did not match the expected type
+    Decode(String),
+    /// Client-side failure (bad URL, signing)
+    Local(String),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Transport(e) => write!(f, "Transport error: {}", e),
+            ClientError::Api(e) => write!(f, "Node error: {}", e),
+            ClientError::Decode(e) => write!(f, "Unexpected response: {}", e),
+            ClientError::Local(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl ClientError {
+    /// Worth another attempt: the node may be restarting or rate limiting us
+    fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport(_) => true,
+            ClientError::Api(e) => matches!(e.code, Some(429) | Some(500..=599)),
+            ClientError::Decode(_) | ClientError::Local(_) => false,
+        }
+    }
+}
+
+/// Exponential backoff between attempts: base, 2×base, 4×base … capped at max_delay
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first (1 = no retry)
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// No retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before attempt `attempt` + 1 (attempt counts from 1)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// REST client of one los-node
+#[derive(Clone)]
+pub struct Client {
+    base_url: String,
+    http: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl Client {
+    /// Client of the node at `base_url` (e.g. `http://127.0.0.1:3030`), 30s timeout
+    pub fn new(base_url: &str) -> Result<Self, ClientError> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ClientError::Local(format!("HTTP client error: {}", e)))?;
+        Ok(Self::with_http_client(base_url, http))
+    }
+
+    /// Client reusing a configured reqwest client (proxy, TLS, timeouts)
+    pub fn with_http_client(base_url: &str, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Queries
+    // ─────────────────────────────────────────────────────────────
+
+    /// GET /balance/:address
+    pub async fn balance(&self, address: &str) -> Result<BalanceResponse, ClientError> {
+        self.get(&format!("/balance/{}", address)).await
+    }
+
+    /// GET /account/:address/next
+    pub async fn account_next(&self, address: &str) -> Result<AccountNextResponse, ClientError> {
+        self.get(&format!("/account/{}/next", address)).await
+    }
+
+    /// GET /fee-estimate/:address
+    pub async fn fee_estimate(&self, address: &str) -> Result<FeeEstimateResponse, ClientError> {
+        self.get(&format!("/fee-estimate/{}", address)).await
+    }
+
+    /// GET on any endpoint without a typed answer yet
+    pub async fn get_json(&self, path: &str) -> Result<serde_json::Value, ClientError> {
+        self.get(path).await
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Submissions
+    // ─────────────────────────────────────────────────────────────
+
+    /// POST /send
+    pub async fn submit_send(&self, req: &SendRequest) -> Result<SubmitResponse, ClientError> {
+        self.post("/send", req).await
+    }
+
+    /// POST /deploy-contract
+    pub async fn deploy_contract(
+        &self,
+        req: &DeployContractRequest,
+    ) -> Result<SubmitResponse, ClientError> {
+        self.post("/deploy-contract", req).await
+    }
+
+    /// POST /call-contract
+    pub async fn call_contract(
+        &self,
+        req: &CallContractRequest,
+    ) -> Result<SubmitResponse, ClientError> {
+        self.post("/call-contract", req).await
+    }
+
+    /// POST /dry-run-contract (read-only, nothing is signed or applied)
+    pub async fn dry_run_contract(
+        &self,
+        req: &DryRunContractRequest,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.post("/dry-run-contract", req).await
+    }
+
+    /// Build, mine, sign and submit a Send of `amount_cil` to `to`.
+    /// Returns the block hash.
+    pub async fn send(
+        &self,
+        keypair: &KeyPair,
+        to: &str,
+        amount_cil: u128,
+    ) -> Result<String, ClientError> {
+        let from = los_crypto::public_key_to_address(&keypair.public_key);
+        let next = self.account_next(&from).await?;
+        let fee = self.fee_estimate(&from).await?.estimated_fee_cil;
+
+        let mut block = blocks::build_send(&next, &keypair.public_key, to, amount_cil, fee);
+        let bits = next.pow_difficulty_bits;
+        let mut block = tokio::task::spawn_blocking(move || {
+            blocks::solve_pow(&mut block, bits);
+            block
+        })
+        .await
+        .map_err(|e| ClientError::Local(format!("PoW task failed: {}", e)))?;
+        blocks::sign_block(&mut block, keypair).map_err(ClientError::Local)?;
+
+        let resp = self.submit_send(&blocks::send_request(&block)).await?;
+        resp.tx_hash
+            .ok_or_else(|| ClientError::Decode("answer has no tx_hash".to_string()))
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Transport
+    // ─────────────────────────────────────────────────────────────
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.with_retries(|| self.http.get(&url)).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ClientError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.with_retries(|| self.http.post(&url).json(body)).await
+    }
+
+    async fn with_retries<T: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let mut attempt = 1;
+        loop {
+            match Self::execute(request()).await {
+                Err(e) if e.is_retryable() && attempt < self.retry.max_attempts => {
+                    tokio::time::sleep(self.retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn execute<T: DeserializeOwned>(req: reqwest::RequestBuilder) -> Result<T, ClientError> {
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+        let status = resp.status().as_u16();
+        let body = resp
+            .bytes()
+            .await
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+        parse_body(status, &body)
+    }
+}
+
+/// Typed answer of a response body, or the node's error.
+///
+/// Decodes from the raw bytes: amounts above u64 do not survive a detour
+/// through serde_json::Value without its arbitrary_precision feature.
+fn parse_body<T: DeserializeOwned>(http_status: u16, body: &[u8]) -> Result<T, ClientError> {
+    let value: Option<serde_json::Value> = serde_json::from_slice(body).ok();
+    if let Some(mut err) = value.as_ref().and_then(ApiError::from_body) {
+        err.code.get_or_insert(http_status);
+        return Err(ClientError::Api(err));
+    }
+    if !(200..300).contains(&http_status) {
+        // Non-JSON error page (proxy, overloaded node)
+        return Err(ClientError::Api(ApiError {
+            status: "error".to_string(),
+            code: Some(http_status),
+            msg: String::from_utf8_lossy(body).chars().take(200).collect(),
+        }));
+    }
+    serde_json::from_slice(body).map_err(|e| ClientError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        let p = RetryPolicy::default();
+        assert_eq!(p.delay(1), Duration::from_millis(250));
+        assert_eq!(p.delay(2), Duration::from_millis(500));
+        assert_eq!(p.delay(3), Duration::from_secs(1));
+        assert_eq!(p.delay(10), Duration::from_secs(5));
+        assert_eq!(p.delay(60), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let api = |code| {
+            ClientError::Api(ApiError {
+                status: "error".to_string(),
+                code: Some(code),
+                msg: String::new(),
+            })
+        };
+        assert!(api(429).is_retryable());
+        assert!(api(503).is_retryable());
+        assert!(!api(400).is_retryable());
+        assert!(ClientError::Transport("reset".to_string()).is_retryable());
+        assert!(!ClientError::Decode("x".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_parse_body() {
+        let ok: FeeEstimateResponse = parse_body(
+            200,
+            br#"{"address": "LOSa", "base_fee_cil": 100000, "base_fee_los": "0.000001",
+                "estimated_fee_cil": 100000, "estimated_fee_los": "0.000001",
+                "fee_multiplier": 1, "fee_multiplier_bps": 10000}"#,
+        )
+        .unwrap();
+        assert_eq!(ok.estimated_fee_cil, 100_000);
+
+        // An error body keeps its own code, else takes the HTTP one
+        let err = parse_body::<FeeEstimateResponse>(
+            400,
+            br#"{"status": "error", "msg": "Invalid address format"}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ClientError::Api(ref e) if e.code == Some(400)));
+        let err = parse_body::<FeeEstimateResponse>(
+            400,
+            br#"{"status": "error", "code": 413, "msg": "too large"}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ClientError::Api(ref e) if e.code == Some(413)));
+        let err = parse_body::<FeeEstimateResponse>(502, b"<html>Bad Gateway</html>").unwrap_err();
+        assert!(matches!(err, ClientError::Api(ref e) if e.code == Some(502)));
+
+        let err = parse_body::<FeeEstimateResponse>(200, br#"{"foo": 1}"#).unwrap_err();
+        assert!(matches!(err, ClientError::Decode(_)));
+    }
+
+    #[test]
+    fn test_parse_body_amount_above_u64() {
+        let b: BalanceResponse = parse_body(
+            200,
+            br#"{"address": "LOSa", "balance": "2000000000", "balance_los": "2000000000",
+                "balance_cil": 200000000000000000000, "balance_cil_str": "200000000000000000000",
+                "head": "0", "block_count": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(b.balance_cil, 200_000_000_000_000_000_000);
+    }
+}
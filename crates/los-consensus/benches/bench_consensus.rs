@@ -17,7 +17,7 @@ use los_consensus::voting::{calculate_voting_power, ValidatorVote, VotingSystem}
 fn bench_calculate_voting_power(c: &mut Criterion) {
     let cil_per_los: u128 = 100_000_000_000;
     let stakes = vec![
-        ("1_LOS", 1 * cil_per_los),
+        ("1_LOS", cil_per_los),
         ("1000_LOS", 1_000 * cil_per_los),
         ("100000_LOS", 100_000 * cil_per_los),
         ("1M_LOS", 1_000_000 * cil_per_los),
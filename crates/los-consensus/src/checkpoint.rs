@@ -9,16 +9,40 @@
 // 2. Store checkpoints in persistent DB (sled)
 // 3. On sync: validate forks against latest checkpoint
 // 4. Reject any blocks before last checkpoint (finality guarantee)
+//
+// Two quorum-finalized checkpoints at one height that name different blocks
+// or state roots are recorded as a CheckpointConflict. While one is open the
+// manager finalizes nothing else; an operator resolves it (resolve_conflict)
+// and every resolution is kept as an audit record.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::Path;
 
 /// Checkpoint interval (every 1,000 blocks)
 pub const CHECKPOINT_INTERVAL: u64 = 1000;
 
+/// Domain tag of the combined checkpoint state root
+const FULL_STATE_ROOT_TAG: &[u8] = b"LOS-STATE-ROOT-V2";
+
+/// Combine the component roots into the root a checkpoint commits to.
+///
+/// SHA3-256 over a domain tag and the length-prefixed component roots, so
+/// no two distinct (accounts, vm, rewards) triples collide by concatenation.
+/// A validator whose VM or reward pool diverged produces a different root
+/// even when every account balance matches.
+pub fn full_state_root(accounts_root: &str, vm_root: &str, rewards_root: &str) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(FULL_STATE_ROOT_TAG);
+    for part in [accounts_root, vm_root, rewards_root] {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
 /// Signature verification function type.
 /// Parameters: (message, signature_bytes, public_key_bytes) → is_valid.
 pub type SignatureVerifier = dyn Fn(&[u8], &[u8], &[u8]) -> bool;
@@ -50,7 +74,8 @@ pub struct FinalityCheckpoint {
     /// Total validators active at checkpoint
     pub validator_count: u32,
 
-    /// Merkle root of all accounts at this height (state snapshot)
+    /// Full state root at this height: accounts, VM and reward pool combined
+    /// with `full_state_root()`. Co-signers compare it with their own.
     pub state_root: String,
 
     /// Signature count — DERIVED from `signatures.len()` for new checkpoints.
@@ -131,9 +156,7 @@ impl FinalityCheckpoint {
     /// approximation ceil(67% * n) which can differ by 1 at certain n values.
     /// For n=1, requires 1 sig (bootstrap). For n=4, f=1 → requires 3.
     pub fn verify_quorum(&self) -> bool {
-        let n = self.validator_count as u64;
-        let f = n.saturating_sub(1) / 3;
-        let required_sigs = if n <= 1 { 1 } else { (2 * f + 1) as u32 };
+        let required_sigs = required_signatures(self.validator_count as u64) as u32;
 
         // SECURITY: Derive actual count from signatures when present.
         // Deduplicates by validator address to prevent double-counting.
@@ -188,6 +211,20 @@ impl FinalityCheckpoint {
         valid_count
     }
 
+    /// Quorum counted over the staked validator set only: 2f+1 of
+    /// `validators.len()` distinct signers from that set. Signers outside
+    /// the set never count; signatures are checked by `verify_signatures`.
+    pub fn has_validator_quorum(&self, validators: &BTreeSet<String>) -> bool {
+        let signers: HashSet<&str> = self
+            .signatures
+            .iter()
+            .map(|s| s.validator_address.as_str())
+            .filter(|addr| validators.contains(*addr))
+            .collect();
+        !validators.is_empty()
+            && signers.len() as u64 >= required_signatures(validators.len() as u64)
+    }
+
     /// Check if checkpoint is valid (interval aligned)
     pub fn is_valid_interval(&self) -> bool {
         self.height.is_multiple_of(CHECKPOINT_INTERVAL)
@@ -201,6 +238,76 @@ pub struct CheckpointManager {
 
     /// Latest checkpoint height
     latest_checkpoint_height: u64,
+
+    /// Unresolved conflicts (finality is halted while > 0)
+    open_conflicts: usize,
+}
+
+/// Two quorum-finalized checkpoints at the same height naming different
+/// blocks or state roots: a bug, or validators signing both sides.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CheckpointConflict {
+    pub height: u64,
+    /// The checkpoint this node had finalized first
+    pub stored: FinalityCheckpoint,
+    /// The quorum-signed checkpoint that contradicts it
+    pub conflicting: FinalityCheckpoint,
+    /// Unix time the conflict was detected
+    pub detected_at: u64,
+}
+
+impl CheckpointConflict {
+    /// Validators that signed both checkpoints
+    pub fn double_signers(&self) -> Vec<String> {
+        let stored: HashSet<&str> = self
+            .stored
+            .signatures
+            .iter()
+            .map(|s| s.validator_address.as_str())
+            .collect();
+        let mut both: Vec<String> = self
+            .conflicting
+            .signatures
+            .iter()
+            .filter(|s| stored.contains(s.validator_address.as_str()))
+            .map(|s| s.validator_address.clone())
+            .collect();
+        both.sort();
+        both.dedup();
+        both
+    }
+}
+
+/// Operator decision on a conflict (audit record, never pruned)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConflictResolution {
+    pub height: u64,
+    pub canonical_id: String,
+    pub rejected_id: String,
+    pub operator: String,
+    pub reason: String,
+    /// The canonical checkpoint replaced the one this node had stored
+    pub replaced_local: bool,
+    /// Checkpoints above `height` dropped because they built on the
+    /// rejected branch
+    pub removed_heights: Vec<u64>,
+    pub resolved_at: u64,
+}
+
+/// BFT quorum 2f+1 for `n` validators, f = (n-1)/3 (1 for n <= 1)
+fn required_signatures(n: u64) -> u64 {
+    if n <= 1 {
+        1
+    } else {
+        2 * (n.saturating_sub(1) / 3) + 1
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 impl CheckpointManager {
@@ -255,10 +362,12 @@ impl CheckpointManager {
                 u64::from_le_bytes(arr)
             })
             .unwrap_or(0);
+        let open_conflicts = db.scan_prefix(b"conflict_").count();
 
         Ok(Self {
             db,
             latest_checkpoint_height,
+            open_conflicts,
         })
     }
 
@@ -272,10 +381,32 @@ impl CheckpointManager {
             || msg.contains("EAGAIN")
     }
 
-    /// Store checkpoint in database (immutable)
+    /// Store checkpoint in database (immutable). A different checkpoint at
+    /// an already finalized height is refused; only
+    /// `store_checkpoint_among` can record it as a conflict.
     pub fn store_checkpoint(
         &mut self,
         checkpoint: FinalityCheckpoint,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.store(checkpoint, None)
+    }
+
+    /// Store a checkpoint that must carry a quorum of `validators` (the
+    /// staked validator set). A conflicting checkpoint halts finality only
+    /// when both it and the stored one carry such a quorum, so signatures
+    /// from non-validators can never stop finality.
+    pub fn store_checkpoint_among(
+        &mut self,
+        checkpoint: FinalityCheckpoint,
+        validators: &BTreeSet<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.store(checkpoint, Some(validators))
+    }
+
+    fn store(
+        &mut self,
+        checkpoint: FinalityCheckpoint,
+        validators: Option<&BTreeSet<String>>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Validate checkpoint
         if !checkpoint.is_valid_interval() {
@@ -293,6 +424,46 @@ impl CheckpointManager {
             )
             .into());
         }
+        if let Some(validators) = validators {
+            if !checkpoint.has_validator_quorum(validators) {
+                return Err(format!(
+                    "Insufficient staked validator signatures at height {} ({} validators)",
+                    checkpoint.height,
+                    validators.len()
+                )
+                .into());
+            }
+        }
+
+        // A second quorum at this height for another block/root is never
+        // stored over the first: record it and halt finality
+        if let Some(existing) = self.get_checkpoint(checkpoint.height)? {
+            if existing.calculate_id() != checkpoint.calculate_id() {
+                let height = checkpoint.height;
+                let both_staked = validators.is_some_and(|v| existing.has_validator_quorum(v));
+                if !both_staked {
+                    return Err(format!(
+                        "Refusing to replace finalized checkpoint at height {}: not a conflict between two staked validator quorums",
+                        height
+                    )
+                    .into());
+                }
+                self.record_conflict(existing, checkpoint)?;
+                return Err(format!(
+                    "Conflicting finalized checkpoint at height {} — finality halted",
+                    height
+                )
+                .into());
+            }
+        }
+        if self.has_conflicts() {
+            let heights: Vec<u64> = self.get_conflicts()?.iter().map(|c| c.height).collect();
+            return Err(format!(
+                "Finality halted: unresolved checkpoint conflict at height {:?}",
+                heights
+            )
+            .into());
+        }
 
         // Serialize checkpoint
         let checkpoint_bytes = bincode::serialize(&checkpoint)?;
@@ -418,6 +589,18 @@ impl CheckpointManager {
         Ok(checkpoints)
     }
 
+    /// Get up to `limit` checkpoints with height >= `from_height`, ascending (for paging)
+    pub fn get_checkpoints_range(
+        &self,
+        from_height: u64,
+        limit: usize,
+    ) -> Result<Vec<FinalityCheckpoint>, Box<dyn std::error::Error>> {
+        let mut checkpoints = self.get_all_checkpoints()?;
+        checkpoints.retain(|cp| cp.height >= from_height);
+        checkpoints.truncate(limit);
+        Ok(checkpoints)
+    }
+
     /// Get checkpoint count
     pub fn get_checkpoint_count(&self) -> usize {
         self.db.scan_prefix(b"checkpoint_").count()
@@ -452,6 +635,131 @@ impl CheckpointManager {
         Ok(removed)
     }
 
+    /// Persist a conflict (the first one seen per height is kept)
+    fn record_conflict(
+        &mut self,
+        stored: FinalityCheckpoint,
+        conflicting: FinalityCheckpoint,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let key = format!("conflict_{:020}", stored.height);
+        if self.db.contains_key(key.as_bytes())? {
+            return Ok(());
+        }
+        let conflict = CheckpointConflict {
+            height: stored.height,
+            stored,
+            conflicting,
+            detected_at: unix_now(),
+        };
+        self.db
+            .insert(key.as_bytes(), bincode::serialize(&conflict)?)?;
+        self.db.flush()?;
+        self.open_conflicts += 1;
+        Ok(())
+    }
+
+    /// Whether an unresolved conflict halts finality
+    pub fn has_conflicts(&self) -> bool {
+        self.open_conflicts > 0
+    }
+
+    /// Unresolved conflicts, lowest height first
+    pub fn get_conflicts(&self) -> Result<Vec<CheckpointConflict>, Box<dyn std::error::Error>> {
+        let mut conflicts = Vec::new();
+        for item in self.db.scan_prefix(b"conflict_") {
+            let (_, value) = item?;
+            conflicts.push(bincode::deserialize(&value)?);
+        }
+        Ok(conflicts)
+    }
+
+    /// Resolve the conflict at `height` in favour of the checkpoint whose
+    /// id is `canonical_id`.
+    ///
+    /// If that is the conflicting checkpoint, it replaces the stored one and
+    /// every checkpoint above `height` is dropped (it was finalized on top
+    /// of the rejected branch). The decision is stored as an audit record.
+    pub fn resolve_conflict(
+        &mut self,
+        height: u64,
+        canonical_id: &str,
+        operator: &str,
+        reason: &str,
+    ) -> Result<ConflictResolution, Box<dyn std::error::Error>> {
+        let key = format!("conflict_{:020}", height);
+        let bytes = self
+            .db
+            .get(key.as_bytes())?
+            .ok_or_else(|| format!("No open checkpoint conflict at height {}", height))?;
+        let conflict: CheckpointConflict = bincode::deserialize(&bytes)?;
+        let (stored_id, conflicting_id) = (
+            conflict.stored.calculate_id(),
+            conflict.conflicting.calculate_id(),
+        );
+        let replaced_local = if canonical_id == stored_id {
+            false
+        } else if canonical_id == conflicting_id {
+            true
+        } else {
+            return Err(format!(
+                "Checkpoint {} is not part of the conflict at height {}",
+                canonical_id, height
+            )
+            .into());
+        };
+
+        let mut removed_heights = Vec::new();
+        if replaced_local {
+            for cp in self.get_all_checkpoints()? {
+                if cp.height > height {
+                    self.db
+                        .remove(format!("checkpoint_{}", cp.height).as_bytes())?;
+                    removed_heights.push(cp.height);
+                }
+            }
+            self.db.insert(
+                format!("checkpoint_{}", height).as_bytes(),
+                bincode::serialize(&conflict.conflicting)?,
+            )?;
+            self.latest_checkpoint_height = height;
+            self.db
+                .insert(b"latest_checkpoint_height", &height.to_le_bytes())?;
+        }
+
+        let resolution = ConflictResolution {
+            height,
+            canonical_id: canonical_id.to_string(),
+            rejected_id: if replaced_local {
+                stored_id
+            } else {
+                conflicting_id
+            },
+            operator: operator.to_string(),
+            reason: reason.to_string(),
+            replaced_local,
+            removed_heights,
+            resolved_at: unix_now(),
+        };
+        self.db.insert(
+            format!("resolution_{:020}_{}", height, resolution.resolved_at).as_bytes(),
+            bincode::serialize(&resolution)?,
+        )?;
+        self.db.remove(key.as_bytes())?;
+        self.db.flush()?;
+        self.open_conflicts = self.open_conflicts.saturating_sub(1);
+        Ok(resolution)
+    }
+
+    /// Every resolution made on this node, oldest height first
+    pub fn get_resolutions(&self) -> Result<Vec<ConflictResolution>, Box<dyn std::error::Error>> {
+        let mut resolutions = Vec::new();
+        for item in self.db.scan_prefix(b"resolution_") {
+            let (_, value) = item?;
+            resolutions.push(bincode::deserialize(&value)?);
+        }
+        Ok(resolutions)
+    }
+
     /// Get statistics
     pub fn get_statistics(&self) -> CheckpointStats {
         CheckpointStats {
@@ -559,6 +867,21 @@ mod tests {
         assert!(checkpoint.is_valid_interval());
     }
 
+    #[test]
+    fn test_full_state_root_covers_every_component() {
+        let root = full_state_root("accounts", "vm", "rewards");
+        assert_eq!(root, full_state_root("accounts", "vm", "rewards"));
+        assert_eq!(root.len(), 64);
+        assert_ne!(root, full_state_root("accounts", "vm2", "rewards"));
+        assert_ne!(root, full_state_root("accounts", "vm", "rewards2"));
+        assert_ne!(root, full_state_root("accounts2", "vm", "rewards"));
+        // Length prefixes: shifting a boundary changes the root
+        assert_ne!(
+            full_state_root("ab", "c", "d"),
+            full_state_root("a", "bc", "d")
+        );
+    }
+
     #[test]
     fn test_checkpoint_id_consistency() {
         let checkpoint = FinalityCheckpoint::new(
@@ -614,6 +937,26 @@ mod tests {
         assert!(!checkpoint.verify_quorum()); // 4/10 = 40% < 67%
     }
 
+    #[test]
+    fn test_validator_quorum_ignores_unstaked_signers() {
+        let validators: BTreeSet<String> = (0..4).map(|i| format!("LOS_validator_{}", i)).collect();
+        // 3 of 4 staked validators: 2f+1 = 3
+        let cp = FinalityCheckpoint::new(1000, "b".to_string(), 4, "r".to_string(), fake_sigs(3));
+        assert!(cp.has_validator_quorum(&validators));
+
+        // Two staked signers plus any number of outsiders is not a quorum,
+        // even though verify_quorum() only counts distinct addresses
+        let mut sigs = fake_sigs(2);
+        sigs.extend((0..5).map(|i| CheckpointSignature {
+            validator_address: format!("LOS_sybil_{}", i),
+            signature: vec![0xAA; 64],
+        }));
+        let forged = FinalityCheckpoint::new(1000, "b".to_string(), 4, "r".to_string(), sigs);
+        assert!(forged.verify_quorum());
+        assert!(!forged.has_validator_quorum(&validators));
+        assert!(!cp.has_validator_quorum(&BTreeSet::new()));
+    }
+
     #[test]
     fn test_checkpoint_interval_validation() {
         let checkpoint1 = FinalityCheckpoint::new(
@@ -816,6 +1159,33 @@ mod tests {
         assert_eq!(checkpoints[2].height, 3000);
     }
 
+    #[test]
+    fn test_get_checkpoints_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("checkpoints_test");
+        let mut manager = CheckpointManager::new(&db_path).unwrap();
+
+        for i in 1..=12 {
+            let checkpoint = FinalityCheckpoint::new(
+                i * 1000,
+                format!("block_hash_{}", i * 1000),
+                10,
+                "state_root".to_string(),
+                fake_sigs(7),
+            );
+            manager.store_checkpoint(checkpoint).unwrap();
+        }
+
+        // Heights are ordered numerically, not by sled key ("checkpoint_10000" < "checkpoint_2000")
+        let page = manager.get_checkpoints_range(2000, 3).unwrap();
+        let heights: Vec<u64> = page.iter().map(|cp| cp.height).collect();
+        assert_eq!(heights, vec![2000, 3000, 4000]);
+
+        let tail = manager.get_checkpoints_range(11_500, 10).unwrap();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].height, 12_000);
+    }
+
     #[test]
     fn test_prune_old_checkpoints() {
         let temp_dir = TempDir::new().unwrap();
@@ -925,4 +1295,76 @@ mod tests {
         // verify_quorum falls back to signature_count for legacy checkpoints
         assert!(legacy.verify_quorum()); // 7/10 = 70% >= 67%
     }
+
+    #[test]
+    fn test_conflicting_checkpoint_halts_until_resolved() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = CheckpointManager::new(temp_dir.path().join("cp")).unwrap();
+        let at = |height: u64, hash: &str| {
+            FinalityCheckpoint::new(
+                height,
+                hash.to_string(),
+                4,
+                "root".to_string(),
+                fake_sigs(3),
+            )
+        };
+        let validators: BTreeSet<String> = (0..4).map(|i| format!("LOS_validator_{}", i)).collect();
+        manager.store_checkpoint(at(1000, "a")).unwrap();
+        manager.store_checkpoint(at(2000, "a2")).unwrap();
+        // Re-storing the same checkpoint is not a conflict
+        manager.store_checkpoint(at(2000, "a2")).unwrap();
+
+        // Without the validator set, or signed by non-validators, a
+        // contradicting checkpoint is refused but does not halt finality
+        assert!(manager.store_checkpoint(at(1000, "b")).is_err());
+        let sybils = (0..3)
+            .map(|i| CheckpointSignature {
+                validator_address: format!("LOS_sybil_{}", i),
+                signature: vec![0xAA; 64],
+            })
+            .collect();
+        let forged = FinalityCheckpoint::new(1000, "b".to_string(), 4, "root".to_string(), sybils);
+        assert!(manager.store_checkpoint_among(forged, &validators).is_err());
+        assert!(!manager.has_conflicts());
+
+        let err = manager
+            .store_checkpoint_among(at(1000, "b"), &validators)
+            .unwrap_err();
+        assert!(err.to_string().contains("Conflicting"));
+        assert!(manager.has_conflicts());
+        assert_eq!(
+            manager.get_checkpoint(1000).unwrap().unwrap().block_hash,
+            "a"
+        );
+        let conflict = manager.get_conflicts().unwrap().remove(0);
+        assert_eq!(conflict.double_signers().len(), 3);
+        // Nothing else finalizes while the conflict is open
+        assert!(manager.store_checkpoint(at(3000, "a3")).is_err());
+
+        // Survives a restart
+        drop(manager);
+        let mut manager = CheckpointManager::new(temp_dir.path().join("cp")).unwrap();
+        assert!(manager.has_conflicts());
+        assert!(manager
+            .resolve_conflict(1000, "not-an-id", "ops", "")
+            .is_err());
+
+        let canonical = at(1000, "b").calculate_id();
+        let resolution = manager
+            .resolve_conflict(1000, &canonical, "ops", "b has the honest majority")
+            .unwrap();
+        assert!(resolution.replaced_local);
+        assert_eq!(resolution.removed_heights, vec![2000]);
+        assert_eq!(resolution.rejected_id, at(1000, "a").calculate_id());
+        assert!(!manager.has_conflicts());
+        assert_eq!(manager.latest_finalized_height(), 1000);
+        assert_eq!(
+            manager.get_checkpoint(1000).unwrap().unwrap().block_hash,
+            "b"
+        );
+        assert!(manager.get_checkpoint(2000).unwrap().is_none());
+        assert_eq!(manager.get_resolutions().unwrap(), vec![resolution]);
+        manager.store_checkpoint(at(2000, "b2")).unwrap();
+    }
 }
@@ -5,4 +5,5 @@
 pub mod abft;
 pub mod checkpoint;
 pub mod slashing;
+pub mod vote_batch;
 pub mod voting;
@@ -0,0 +1,320 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - VOTE AGGREGATION
+//
+// Every CONFIRM_RES carries its own ~4.6 KB Dilithium5 signature + ~2.6 KB
+// public key, so N validators voting on M sends produce N×M signed messages.
+// Validators instead queue their YES votes and, once per batch interval,
+// seal them into ONE signed envelope:
+//
+//   VOTE_BATCH:{"voter":"LOS…","timestamp":…,"votes":[{tx_hash,requester},…],
+//               "signature":"…","public_key":"…"}
+//
+// One signature covers every vote in the envelope. Crypto is injected by the
+// caller (sign/verify closures) so this crate stays signature-scheme agnostic.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Gossip prefix for batched vote envelopes
+pub const VOTE_BATCH_PREFIX: &str = "VOTE_BATCH:";
+/// Domain separator for the envelope signing payload
+pub const VOTE_BATCH_DOMAIN: &str = "LOS_VOTE_BATCH_V1";
+/// How often queued votes are sealed and gossiped (vote heartbeat)
+pub const VOTE_BATCH_INTERVAL_MS: u64 = 500;
+/// Max votes per envelope (larger queues are split across envelopes)
+pub const MAX_VOTES_PER_BATCH: usize = 256;
+/// Envelopes older than this are rejected (replay protection)
+pub const MAX_ENVELOPE_AGE_MS: u128 = 5 * 60 * 1000;
+/// Clock skew tolerated for envelopes dated ahead of the local clock
+/// (a future timestamp would otherwise stretch the replay window)
+pub const MAX_ENVELOPE_FUTURE_SKEW_MS: u128 = 30 * 1000;
+
+/// A single YES vote for a pending Send
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BatchedVote {
+    pub tx_hash: String,
+    /// Account that initiated the Send (the CONFIRM_REQ requester)
+    pub requester: String,
+}
+
+/// Signed envelope carrying many votes from one validator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoteEnvelope {
+    pub voter: String,
+    /// Milliseconds since UNIX epoch when the envelope was sealed
+    pub timestamp: u128,
+    pub votes: Vec<BatchedVote>,
+    /// Hex-encoded signature over `signing_payload()`
+    pub signature: String,
+    /// Hex-encoded voter public key
+    pub public_key: String,
+}
+
+impl VoteEnvelope {
+    /// Deterministic payload covered by the signature.
+    /// Votes are sorted, so the payload does not depend on queue order.
+    pub fn signing_payload(voter: &str, timestamp: u128, votes: &[BatchedVote]) -> String {
+        let mut sorted: Vec<&BatchedVote> = votes.iter().collect();
+        sorted.sort();
+        let mut payload = format!("{}|{}|{}", VOTE_BATCH_DOMAIN, voter, timestamp);
+        for v in sorted {
+            payload.push('|');
+            payload.push_str(&v.tx_hash);
+            payload.push(':');
+            payload.push_str(&v.requester);
+        }
+        payload
+    }
+
+    /// Seal `votes` into a signed envelope. `sign` returns the raw signature bytes.
+    pub fn seal<F>(
+        voter: &str,
+        timestamp: u128,
+        votes: Vec<BatchedVote>,
+        public_key: &[u8],
+        sign: F,
+    ) -> Result<Self, String>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        if votes.is_empty() {
+            return Err("Cannot seal an empty vote batch".to_string());
+        }
+        let payload = Self::signing_payload(voter, timestamp, &votes);
+        let sig = sign(payload.as_bytes())?;
+        Ok(Self {
+            voter: voter.to_string(),
+            timestamp,
+            votes,
+            signature: hex::encode(&sig),
+            public_key: hex::encode(public_key),
+        })
+    }
+
+    /// Encode as a gossip message: VOTE_BATCH:{json}
+    pub fn to_message(&self) -> String {
+        format!(
+            "{}{}",
+            VOTE_BATCH_PREFIX,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    /// Decode a VOTE_BATCH: gossip message
+    pub fn from_message(msg: &str) -> Result<Self, String> {
+        let json = msg
+            .strip_prefix(VOTE_BATCH_PREFIX)
+            .ok_or("Not a VOTE_BATCH message")?;
+        serde_json::from_str(json).map_err(|e| format!("Malformed vote envelope: {}", e))
+    }
+
+    /// Validate structure, freshness, key→address binding and signature.
+    ///
+    /// - `verify(msg, sig, pk)` checks the signature
+    /// - `derive_address(pk)` maps the public key to a LOS address
+    ///
+    /// Duplicate votes inside one envelope are rejected (a voter cannot
+    /// count twice for the same send by repeating it).
+    pub fn verify<V, D>(&self, now_ms: u128, verify: V, derive_address: D) -> Result<(), String>
+    where
+        V: Fn(&[u8], &[u8], &[u8]) -> bool,
+        D: Fn(&[u8]) -> String,
+    {
+        if self.votes.is_empty() {
+            return Err("Empty vote envelope".to_string());
+        }
+        if self.votes.len() > MAX_VOTES_PER_BATCH {
+            return Err(format!(
+                "Vote envelope too large: {} > {}",
+                self.votes.len(),
+                MAX_VOTES_PER_BATCH
+            ));
+        }
+        if now_ms.saturating_sub(self.timestamp) > MAX_ENVELOPE_AGE_MS {
+            return Err("Vote envelope expired".to_string());
+        }
+        if self.timestamp > now_ms.saturating_add(MAX_ENVELOPE_FUTURE_SKEW_MS) {
+            return Err("Vote envelope timestamp is in the future".to_string());
+        }
+        let unique: BTreeSet<&BatchedVote> = self.votes.iter().collect();
+        if unique.len() != self.votes.len() {
+            return Err("Duplicate vote inside envelope".to_string());
+        }
+        let pk = hex::decode(&self.public_key).map_err(|_| "Invalid public key hex")?;
+        let sig = hex::decode(&self.signature).map_err(|_| "Invalid signature hex")?;
+        if derive_address(&pk) != self.voter {
+            return Err("Public key does not match voter address".to_string());
+        }
+        let payload = Self::signing_payload(&self.voter, self.timestamp, &self.votes);
+        if !verify(payload.as_bytes(), &sig, &pk) {
+            return Err("Invalid envelope signature".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Queue of outgoing votes, drained once per batch interval.
+/// Keyed by tx_hash so re-voting the same send before a flush is a no-op.
+#[derive(Debug, Default)]
+pub struct VoteBatcher {
+    pending: BTreeMap<String, BatchedVote>,
+}
+
+impl VoteBatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a vote for the next flush.
+    pub fn queue(&mut self, tx_hash: &str, requester: &str) {
+        self.pending
+            .entry(tx_hash.to_string())
+            .or_insert_with(|| BatchedVote {
+                tx_hash: tx_hash.to_string(),
+                requester: requester.to_string(),
+            });
+    }
+
+    /// Take up to MAX_VOTES_PER_BATCH queued votes (deterministic order).
+    pub fn drain(&mut self) -> Vec<BatchedVote> {
+        let keys: Vec<String> = self
+            .pending
+            .keys()
+            .take(MAX_VOTES_PER_BATCH)
+            .cloned()
+            .collect();
+        keys.iter().filter_map(|k| self.pending.remove(k)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Toy "signature" for tests: sig = reversed(msg) ‖ pk
+    fn toy_sign(pk: &[u8]) -> impl Fn(&[u8]) -> Result<Vec<u8>, String> + '_ {
+        move |msg| {
+            let mut s: Vec<u8> = msg.iter().rev().copied().collect();
+            s.extend_from_slice(pk);
+            Ok(s)
+        }
+    }
+
+    fn toy_verify(msg: &[u8], sig: &[u8], pk: &[u8]) -> bool {
+        let mut expected: Vec<u8> = msg.iter().rev().copied().collect();
+        expected.extend_from_slice(pk);
+        expected == sig
+    }
+
+    fn toy_address(pk: &[u8]) -> String {
+        format!("LOS{}", hex::encode(pk))
+    }
+
+    fn votes(n: usize) -> Vec<BatchedVote> {
+        (0..n)
+            .map(|i| BatchedVote {
+                tx_hash: format!("{:064x}", i),
+                requester: "LOSsender".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seal_and_verify_roundtrip() {
+        let pk = b"pk1";
+        let env = VoteEnvelope::seal(&toy_address(pk), 1_000, votes(3), pk, toy_sign(pk)).unwrap();
+        let decoded = VoteEnvelope::from_message(&env.to_message()).unwrap();
+        assert_eq!(decoded, env);
+        assert!(decoded.verify(2_000, toy_verify, toy_address).is_ok());
+    }
+
+    #[test]
+    fn test_payload_independent_of_vote_order() {
+        let mut v = votes(4);
+        let a = VoteEnvelope::signing_payload("LOSv", 1, &v);
+        v.reverse();
+        assert_eq!(a, VoteEnvelope::signing_payload("LOSv", 1, &v));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let pk = b"pk1";
+        let mut env =
+            VoteEnvelope::seal(&toy_address(pk), 1_000, votes(2), pk, toy_sign(pk)).unwrap();
+        env.votes.push(BatchedVote {
+            tx_hash: "ff".repeat(32),
+            requester: "LOSx".to_string(),
+        });
+        assert!(env.verify(1_000, toy_verify, toy_address).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_voter_and_stale() {
+        let pk = b"pk1";
+        let env = VoteEnvelope::seal("LOSother", 1_000, votes(1), pk, toy_sign(pk)).unwrap();
+        assert!(env.verify(1_000, toy_verify, toy_address).is_err());
+
+        let env = VoteEnvelope::seal(&toy_address(pk), 1_000, votes(1), pk, toy_sign(pk)).unwrap();
+        assert!(env
+            .verify(1_000 + MAX_ENVELOPE_AGE_MS + 1, toy_verify, toy_address)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_future_timestamp() {
+        let pk = b"pk1";
+        let sealed_at = 1_000 + MAX_ENVELOPE_FUTURE_SKEW_MS;
+        let env =
+            VoteEnvelope::seal(&toy_address(pk), sealed_at, votes(1), pk, toy_sign(pk)).unwrap();
+        // Within the skew: accepted
+        assert!(env.verify(1_000, toy_verify, toy_address).is_ok());
+        // Further ahead: rejected, so it cannot outlive MAX_ENVELOPE_AGE_MS
+        let err = env.verify(999, toy_verify, toy_address).unwrap_err();
+        assert!(err.contains("future"), "{err}");
+    }
+
+    #[test]
+    fn test_verify_rejects_duplicate_votes() {
+        let pk = b"pk1";
+        let mut dup = votes(1);
+        dup.push(dup[0].clone());
+        let env = VoteEnvelope::seal(&toy_address(pk), 1_000, dup, pk, toy_sign(pk)).unwrap();
+        assert!(env.verify(1_000, toy_verify, toy_address).is_err());
+    }
+
+    #[test]
+    fn test_batcher_dedup_and_drain() {
+        let mut b = VoteBatcher::new();
+        b.queue("h1", "LOSa");
+        b.queue("h1", "LOSa");
+        b.queue("h2", "LOSb");
+        assert_eq!(b.len(), 2);
+        let drained = b.drain();
+        assert_eq!(drained.len(), 2);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_batcher_splits_large_queue() {
+        let mut b = VoteBatcher::new();
+        for i in 0..=MAX_VOTES_PER_BATCH {
+            b.queue(&format!("h{}", i), "LOSa");
+        }
+        assert_eq!(b.drain().len(), MAX_VOTES_PER_BATCH);
+        assert_eq!(b.len(), 1);
+    }
+
+    #[test]
+    fn test_seal_rejects_empty() {
+        assert!(VoteEnvelope::seal("LOSv", 1, vec![], b"pk", |_| Ok(vec![])).is_err());
+    }
+}
@@ -266,11 +266,9 @@ impl VotingSystem {
             0
         };
 
-        let concentration_ratio_bps = if total_voting_power > 0 {
-            ((max_voting_power * 10_000) / total_voting_power) as u32
-        } else {
-            0
-        };
+        let concentration_ratio_bps = (max_voting_power * 10_000)
+            .checked_div(total_voting_power)
+            .unwrap_or(0) as u32;
 
         VotingPowerSummary {
             total_validators,
@@ -303,11 +301,9 @@ impl VotingSystem {
             .map(|v| v.voting_power)
             .sum();
 
-        let percentage_bps: u32 = if total_voting_power > 0 {
-            ((votes_for * 10_000) / total_voting_power) as u32
-        } else {
-            0
-        };
+        let percentage_bps: u32 = (votes_for * 10_000)
+            .checked_div(total_voting_power)
+            .unwrap_or(0) as u32;
 
         let consensus_reached = percentage_bps > 5_000; // Strictly > 50%
 
@@ -345,17 +341,13 @@ impl VotingSystem {
             .max()
             .unwrap_or(0);
 
-        let whale_concentration_bps = if whale_total_power > 0 {
-            ((max_whale * 10_000) / whale_total_power) as u32
-        } else {
-            0
-        };
+        let whale_concentration_bps = (max_whale * 10_000)
+            .checked_div(whale_total_power)
+            .unwrap_or(0) as u32;
 
-        let distributed_concentration_bps = if distributed_total_power > 0 {
-            ((max_distributed * 10_000) / distributed_total_power) as u32
-        } else {
-            0
-        };
+        let distributed_concentration_bps = (max_distributed * 10_000)
+            .checked_div(distributed_total_power)
+            .unwrap_or(0) as u32;
 
         let improvement_bps = if whale_concentration_bps > 0 {
             ((whale_concentration_bps as u64).saturating_sub(distributed_concentration_bps as u64)
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! DEX AMM contract WASM exports. The logic is `los_contracts::dex_amm`.
+
+#![no_std]
+#![no_main]
+
+extern crate los_sdk;
+
+use los_contracts::dex_amm;
+
+// Name/version metadata, read by the node at deploy (GET /contract/:addr)
+los_sdk::contract_info!("dex_amm", env!("CARGO_PKG_VERSION"));
+
+#[no_mangle]
+pub extern "C" fn init() -> i32 {
+    dex_amm::init()
+}
+
+#[no_mangle]
+pub extern "C" fn create_pool() -> i32 {
+    dex_amm::create_pool()
+}
+
+#[no_mangle]
+pub extern "C" fn add_liquidity() -> i32 {
+    dex_amm::add_liquidity()
+}
+
+#[no_mangle]
+pub extern "C" fn remove_liquidity() -> i32 {
+    dex_amm::remove_liquidity()
+}
+
+#[no_mangle]
+pub extern "C" fn swap() -> i32 {
+    dex_amm::swap()
+}
+
+#[no_mangle]
+pub extern "C" fn get_pool() -> i32 {
+    dex_amm::get_pool()
+}
+
+#[no_mangle]
+pub extern "C" fn quote() -> i32 {
+    dex_amm::quote()
+}
+
+#[no_mangle]
+pub extern "C" fn get_position() -> i32 {
+    dex_amm::get_position()
+}
+
+#[no_mangle]
+pub extern "C" fn list_pools() -> i32 {
+    dex_amm::list_pools()
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! Testnet faucet contract WASM exports. The logic is `los_contracts::faucet`.
+
+#![no_std]
+#![no_main]
+
+extern crate los_sdk;
+
+use los_contracts::faucet;
+
+// Name/version metadata, read by the node at deploy (GET /contract/:addr)
+los_sdk::contract_info!("faucet", env!("CARGO_PKG_VERSION"));
+
+#[no_mangle]
+pub extern "C" fn init() -> i32 {
+    faucet::init()
+}
+
+#[no_mangle]
+pub extern "C" fn drip() -> i32 {
+    faucet::drip()
+}
+
+#[no_mangle]
+pub extern "C" fn refill() -> i32 {
+    faucet::refill()
+}
+
+#[no_mangle]
+pub extern "C" fn set_policy() -> i32 {
+    faucet::set_policy()
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw() -> i32 {
+    faucet::withdraw()
+}
+
+#[no_mangle]
+pub extern "C" fn get_info() -> i32 {
+    faucet::get_info()
+}
+
+#[no_mangle]
+pub extern "C" fn next_claim() -> i32 {
+    faucet::next_claim()
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! HTLC contract WASM exports. The logic is `los_contracts::htlc`.
+
+#![no_std]
+#![no_main]
+
+extern crate los_sdk;
+
+use los_contracts::htlc;
+
+// Name/version metadata, read by the node at deploy (GET /contract/:addr)
+los_sdk::contract_info!("htlc", env!("CARGO_PKG_VERSION"));
+
+#[no_mangle]
+pub extern "C" fn lock() -> i32 {
+    htlc::lock()
+}
+
+#[no_mangle]
+pub extern "C" fn claim() -> i32 {
+    htlc::claim()
+}
+
+#[no_mangle]
+pub extern "C" fn refund() -> i32 {
+    htlc::refund()
+}
+
+#[no_mangle]
+pub extern "C" fn get_swap() -> i32 {
+    htlc::get_swap()
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! Merkle airdrop contract WASM exports. The logic is `los_contracts::merkle_distributor`.
+
+#![no_std]
+#![no_main]
+
+extern crate los_sdk;
+
+use los_contracts::merkle_distributor;
+
+// Name/version metadata, read by the node at deploy (GET /contract/:addr)
+los_sdk::contract_info!("merkle_distributor", env!("CARGO_PKG_VERSION"));
+
+#[no_mangle]
+pub extern "C" fn init() -> i32 {
+    merkle_distributor::init()
+}
+
+#[no_mangle]
+pub extern "C" fn fund() -> i32 {
+    merkle_distributor::fund()
+}
+
+#[no_mangle]
+pub extern "C" fn claim() -> i32 {
+    merkle_distributor::claim()
+}
+
+#[no_mangle]
+pub extern "C" fn is_claimed() -> i32 {
+    merkle_distributor::is_claimed()
+}
+
+#[no_mangle]
+pub extern "C" fn reclaim() -> i32 {
+    merkle_distributor::reclaim()
+}
+
+#[no_mangle]
+pub extern "C" fn get_info() -> i32 {
+    merkle_distributor::get_info()
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! USP-01 token contract WASM exports. The logic is `los_contracts::usp01_token`.
+
+#![no_std]
+#![no_main]
+
+extern crate los_sdk;
+
+use los_contracts::usp01_token;
+
+// Name/version metadata, read by the node at deploy (GET /contract/:addr)
+los_sdk::contract_info!("usp01_token", env!("CARGO_PKG_VERSION"));
+
+#[no_mangle]
+pub extern "C" fn init() -> i32 {
+    usp01_token::init()
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() -> i32 {
+    usp01_token::transfer()
+}
+
+#[no_mangle]
+pub extern "C" fn approve() -> i32 {
+    usp01_token::approve()
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() -> i32 {
+    usp01_token::transfer_from()
+}
+
+#[no_mangle]
+pub extern "C" fn burn() -> i32 {
+    usp01_token::burn()
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() -> i32 {
+    usp01_token::balance_of()
+}
+
+#[no_mangle]
+pub extern "C" fn allowance_of() -> i32 {
+    usp01_token::allowance_of()
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() -> i32 {
+    usp01_token::total_supply()
+}
+
+#[no_mangle]
+pub extern "C" fn token_info() -> i32 {
+    usp01_token::token_info()
+}
+
+#[no_mangle]
+pub extern "C" fn wrap_mint() -> i32 {
+    usp01_token::wrap_mint()
+}
+
+#[no_mangle]
+pub extern "C" fn wrap_burn() -> i32 {
+    usp01_token::wrap_burn()
+}
+
+#[no_mangle]
+pub extern "C" fn htlc_lock() -> i32 {
+    usp01_token::htlc_lock()
+}
+
+#[no_mangle]
+pub extern "C" fn htlc_claim() -> i32 {
+    usp01_token::htlc_claim()
+}
+
+#[no_mangle]
+pub extern "C" fn htlc_refund() -> i32 {
+    usp01_token::htlc_refund()
+}
+
+#[no_mangle]
+pub extern "C" fn htlc_get() -> i32 {
+    usp01_token::htlc_get()
+}
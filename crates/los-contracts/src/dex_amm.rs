@@ -43,15 +43,14 @@
 //! ## Compilation
 //! ```bash
 //! cargo build --target wasm32-unknown-unknown --release \
-//!     -p los-contract-examples --bin dex_amm_wasm --features sdk
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin dex_amm
 //! ```
 
-#![no_std]
-#![no_main]
-
-extern crate alloc;
-extern crate los_sdk;
-
+use crate::{
+    compute_output, deduct_fee, fail, get_state_str, get_state_u128, get_state_u64, isqrt,
+    json_escape, make_pool_id, ok, ok_data, parse_u128, parse_u64, pro_rata, set_state_u128,
+    set_state_u64, u128_to_str,
+};
 use alloc::format;
 use alloc::string::String;
 use los_sdk::*;
@@ -62,202 +61,17 @@ use los_sdk::*;
 
 /// Default swap fee: 30 bps = 0.3%
 const DEFAULT_FEE_BPS: u128 = 30;
-/// Basis point denominator
-const BPS_DENOMINATOR: u128 = 10_000;
 /// Minimum liquidity locked forever (prevent price manipulation)
 const MINIMUM_LIQUIDITY: u128 = 1_000;
 /// Max fee: 1000 bps = 10%
 const MAX_FEE_BPS: u128 = 1_000;
-/// Precision multiplier for overflow-safe calculations
+/// Fixed-point scale for reported prices (`*_price_scaled`)
 const PRECISION: u128 = 1_000_000_000_000;
 
-// ─────────────────────────────────────────────────────────────
-// HELPERS
-// ─────────────────────────────────────────────────────────────
-
-/// Parse a decimal string to u128. Returns 0 on failure.
-fn parse_u128(s: &str) -> u128 {
-    let mut result: u128 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u128) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
-
-/// Convert u128 to decimal string without std.
-fn u128_to_str(val: u128) -> String {
-    if val == 0 {
-        return String::from("0");
-    }
-    let mut buf = [0u8; 40];
-    let mut pos = buf.len();
-    let mut v = val;
-    while v > 0 {
-        pos -= 1;
-        buf[pos] = b'0' + (v % 10) as u8;
-        v /= 10;
-    }
-    let bytes = &buf[pos..];
-    // All bytes are proven ASCII digits — infallible conversion.
-    String::from_utf8(alloc::vec::Vec::from(bytes)).unwrap_or_default()
-}
-
-/// Parse u64 from decimal string.
-fn parse_u64(s: &str) -> u64 {
-    let mut result: u64 = 0;
-    for b in s.as_bytes() {
-        if *b >= b'0' && *b <= b'9' {
-            result = match result.checked_mul(10) {
-                Some(v) => v,
-                None => return 0,
-            };
-            result = match result.checked_add((*b - b'0') as u64) {
-                Some(v) => v,
-                None => return 0,
-            };
-        } else {
-            return 0;
-        }
-    }
-    result
-}
-
-/// Escape a string for JSON output.
-fn json_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len() + 8);
-    for c in s.chars() {
-        match c {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            _ => out.push(c),
-        }
-    }
-    out
-}
-
-/// Return failure JSON.
-fn fail(msg: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":false,\"message\":\"{}\"}}",
-        json_escape(msg)
-    );
-    set_return_str(&resp);
-    1
-}
-
-/// Return success JSON.
-fn ok(msg: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":true,\"message\":\"{}\"}}",
-        json_escape(msg)
-    );
-    set_return_str(&resp);
-    0
-}
-
-/// Return success JSON with data.
-fn ok_data(msg: &str, data: &str) -> i32 {
-    let resp = format!(
-        "{{\"success\":true,\"message\":\"{}\",\"data\":{}}}",
-        json_escape(msg),
-        data
-    );
-    set_return_str(&resp);
-    0
-}
-
-// ─────────────────────────────────────────────────────────────
-// INTEGER MATH (NO f32/f64)
-// ─────────────────────────────────────────────────────────────
-
-/// Integer square root — Newton's method. Returns floor(√n).
-fn isqrt(n: u128) -> u128 {
-    if n == 0 {
-        return 0;
-    }
-    let mut x = n;
-    let mut y = x.div_ceil(2); // safe: no overflow for u128::MAX
-    while y < x {
-        x = y;
-        y = (x + n / x) / 2;
-    }
-    x
-}
-
-/// Compute swap output using constant product formula.
-/// `amount_out = (amount_in * reserve_out) / (reserve_in + amount_in)`
-fn compute_output(amount_in: u128, reserve_in: u128, reserve_out: u128) -> u128 {
-    if reserve_in == 0 || reserve_out == 0 || amount_in == 0 {
-        return 0;
-    }
-    match (
-        amount_in.checked_mul(reserve_out),
-        reserve_in.checked_add(amount_in),
-    ) {
-        (Some(num), Some(den)) if den > 0 => num / den,
-        _ => {
-            // Overflow fallback: scaled division
-            let ratio_scaled =
-                (amount_in as u128 * PRECISION) / reserve_in.saturating_add(amount_in);
-            (ratio_scaled * reserve_out) / PRECISION
-        }
-    }
-}
-
-/// Deduct fee from input amount. Returns (after_fee, fee).
-fn deduct_fee(amount: u128, fee_bps: u128) -> (u128, u128) {
-    // checked_mul to prevent WASM trap on large amounts * fee_bps
-    let fee = amount.checked_mul(fee_bps)
-        .map(|v| v / BPS_DENOMINATOR)
-        .unwrap_or_else(|| (amount / BPS_DENOMINATOR).saturating_mul(fee_bps));
-    (amount.saturating_sub(fee), fee)
-}
-
 // ─────────────────────────────────────────────────────────────
 // STATE HELPERS
 // ─────────────────────────────────────────────────────────────
 
-fn get_state_str(key: &str) -> String {
-    state::get_str(key).unwrap_or_default()
-}
-
-fn get_state_u128(key: &str) -> u128 {
-    parse_u128(&get_state_str(key))
-}
-
-fn get_state_u64(key: &str) -> u64 {
-    parse_u64(&get_state_str(key))
-}
-
-fn set_state_u128(key: &str, val: u128) {
-    state::set_str(key, &u128_to_str(val));
-}
-
-fn set_state_u64(key: &str, val: u64) {
-    state::set_str(key, &u128_to_str(val as u128));
-}
-
-/// Generate deterministic pool ID from token pair (sorted).
-fn make_pool_id(token_a: &str, token_b: &str) -> String {
-    if token_a < token_b {
-        format!("POOL:{}:{}", token_a, token_b)
-    } else {
-        format!("POOL:{}:{}", token_b, token_a)
-    }
-}
-
 /// Check if a pool exists.
 fn pool_exists(pool_id: &str) -> bool {
     let key = format!("pool:{}:token_a", pool_id);
@@ -269,7 +83,6 @@ fn pool_exists(pool_id: &str) -> bool {
 // ─────────────────────────────────────────────────────────────
 
 /// Initialize the DEX contract.
-#[no_mangle]
 pub extern "C" fn init() -> i32 {
     if get_state_str("dex:init") == "1" {
         return fail("DEX already initialized");
@@ -279,13 +92,15 @@ pub extern "C" fn init() -> i32 {
     state::set_str("dex:owner", &who);
     set_state_u64("dex:pool_count", 0);
 
-    event::emit("DexInit", &format!("{{\"owner\":\"{}\"}}", json_escape(&who)));
+    event::emit(
+        "DexInit",
+        &format!("{{\"owner\":\"{}\"}}", json_escape(&who)),
+    );
     ok("DEX initialized")
 }
 
 /// Create a new liquidity pool for a token pair.
 /// Args: token_a, token_b, amount_a, amount_b [, fee_bps]
-#[no_mangle]
 pub extern "C" fn create_pool() -> i32 {
     if get_state_str("dex:init") != "1" {
         return fail("DEX not initialized");
@@ -388,7 +203,6 @@ pub extern "C" fn create_pool() -> i32 {
 
 /// Add liquidity to an existing pool.
 /// Args: pool_id, amount_a, amount_b, min_lp_tokens
-#[no_mangle]
 pub extern "C" fn add_liquidity() -> i32 {
     if get_state_str("dex:init") != "1" {
         return fail("DEX not initialized");
@@ -428,22 +242,8 @@ pub extern "C" fn add_liquidity() -> i32 {
     }
 
     // LP = min(amount_a * total_lp / reserve_a, amount_b * total_lp / reserve_b)
-    // Use checked_mul to prevent WASM trap on u128 overflow
-    let lp_from_a = match amount_a.checked_mul(total_lp) {
-        Some(v) => v / reserve_a,
-        None => {
-            // Overflow fallback: scale down to avoid trap
-            let ratio = (amount_a / reserve_a).max(1);
-            ratio.saturating_mul(total_lp)
-        }
-    };
-    let lp_from_b = match amount_b.checked_mul(total_lp) {
-        Some(v) => v / reserve_b,
-        None => {
-            let ratio = (amount_b / reserve_b).max(1);
-            ratio.saturating_mul(total_lp)
-        }
-    };
+    let lp_from_a = pro_rata(amount_a, total_lp, reserve_a);
+    let lp_from_b = pro_rata(amount_b, total_lp, reserve_b);
     let lp_tokens = if lp_from_a < lp_from_b {
         lp_from_a
     } else {
@@ -458,16 +258,23 @@ pub extern "C" fn add_liquidity() -> i32 {
         ));
     }
 
-    // Calculate actual amounts used (proportional) — checked to prevent WASM trap
-    let actual_a = lp_tokens.checked_mul(reserve_a).map(|v| v / total_lp)
-        .unwrap_or_else(|| (lp_tokens / total_lp).saturating_mul(reserve_a));
-    let actual_b = lp_tokens.checked_mul(reserve_b).map(|v| v / total_lp)
-        .unwrap_or_else(|| (lp_tokens / total_lp).saturating_mul(reserve_b));
+    // Calculate actual amounts used (proportional)
+    let actual_a = pro_rata(lp_tokens, reserve_a, total_lp);
+    let actual_b = pro_rata(lp_tokens, reserve_b, total_lp);
 
     // Update reserves — checked_add to prevent overflow
-    set_state_u128(&format!("{}:reserve_a", prefix), reserve_a.saturating_add(actual_a));
-    set_state_u128(&format!("{}:reserve_b", prefix), reserve_b.saturating_add(actual_b));
-    set_state_u128(&format!("{}:total_lp", prefix), total_lp.saturating_add(lp_tokens));
+    set_state_u128(
+        &format!("{}:reserve_a", prefix),
+        reserve_a.saturating_add(actual_a),
+    );
+    set_state_u128(
+        &format!("{}:reserve_b", prefix),
+        reserve_b.saturating_add(actual_b),
+    );
+    set_state_u128(
+        &format!("{}:total_lp", prefix),
+        total_lp.saturating_add(lp_tokens),
+    );
 
     // Update LP shares
     let who = caller();
@@ -488,7 +295,10 @@ pub extern "C" fn add_liquidity() -> i32 {
     );
 
     ok_data(
-        &format!("Added liquidity: {} LP tokens minted", u128_to_str(lp_tokens)),
+        &format!(
+            "Added liquidity: {} LP tokens minted",
+            u128_to_str(lp_tokens)
+        ),
         &format!(
             "{{\"lp_tokens\":\"{}\",\"amount_a_used\":\"{}\",\"amount_b_used\":\"{}\"}}",
             u128_to_str(lp_tokens),
@@ -500,7 +310,6 @@ pub extern "C" fn add_liquidity() -> i32 {
 
 /// Remove liquidity from a pool.
 /// Args: pool_id, lp_amount, min_amount_a, min_amount_b
-#[no_mangle]
 pub extern "C" fn remove_liquidity() -> i32 {
     if get_state_str("dex:init") != "1" {
         return fail("DEX not initialized");
@@ -547,11 +356,9 @@ pub extern "C" fn remove_liquidity() -> i32 {
         return fail("Pool has no liquidity");
     }
 
-    // Proportional token amounts — checked to prevent WASM trap
-    let amount_a = lp_amount.checked_mul(reserve_a).map(|v| v / total_lp)
-        .unwrap_or_else(|| (lp_amount / total_lp).saturating_mul(reserve_a));
-    let amount_b = lp_amount.checked_mul(reserve_b).map(|v| v / total_lp)
-        .unwrap_or_else(|| (lp_amount / total_lp).saturating_mul(reserve_b));
+    // Proportional token amounts
+    let amount_a = pro_rata(lp_amount, reserve_a, total_lp);
+    let amount_b = pro_rata(lp_amount, reserve_b, total_lp);
 
     // Slippage protection
     if amount_a < min_amount_a || amount_b < min_amount_b {
@@ -605,7 +412,6 @@ pub extern "C" fn remove_liquidity() -> i32 {
 
 /// Swap tokens via constant product AMM.
 /// Args: pool_id, token_in, amount_in, min_amount_out, deadline
-#[no_mangle]
 pub extern "C" fn swap() -> i32 {
     if get_state_str("dex:init") != "1" {
         return fail("DEX not initialized");
@@ -696,10 +502,16 @@ pub extern "C" fn swap() -> i32 {
     // Use saturating_add for input side (prevent overflow trap)
     // Subtraction is safe: amount_out < reserve_out is verified above
     if is_a_to_b {
-        set_state_u128(&format!("{}:reserve_a", prefix), reserve_a.saturating_add(amount_in));
+        set_state_u128(
+            &format!("{}:reserve_a", prefix),
+            reserve_a.saturating_add(amount_in),
+        );
         set_state_u128(&format!("{}:reserve_b", prefix), reserve_b - amount_out);
     } else {
-        set_state_u128(&format!("{}:reserve_b", prefix), reserve_b.saturating_add(amount_in));
+        set_state_u128(
+            &format!("{}:reserve_b", prefix),
+            reserve_b.saturating_add(amount_in),
+        );
         set_state_u128(&format!("{}:reserve_a", prefix), reserve_a - amount_out);
     }
 
@@ -721,11 +533,7 @@ pub extern "C" fn swap() -> i32 {
     );
 
     // Price impact (bps)
-    let impact_bps = if reserve_out > 0 {
-        (amount_out * BPS_DENOMINATOR) / reserve_out
-    } else {
-        0
-    };
+    let impact_bps = math::ratio_bps(amount_out, reserve_out).unwrap_or(0);
 
     ok_data(
         &format!(
@@ -746,7 +554,6 @@ pub extern "C" fn swap() -> i32 {
 
 /// Get pool info (read-only).
 /// Args: pool_id
-#[no_mangle]
 pub extern "C" fn get_pool() -> i32 {
     let pool_id = match arg(0) {
         Some(v) if !v.is_empty() => v,
@@ -769,7 +576,7 @@ pub extern "C" fn get_pool() -> i32 {
 
     // Spot price: price_b = reserve_a * PRECISION / reserve_b (A per B)
     let spot_price_scaled = if reserve_b > 0 {
-        reserve_a * PRECISION / reserve_b
+        math::mul_div(reserve_a, PRECISION, reserve_b).unwrap_or(u128::MAX)
     } else {
         0
     };
@@ -794,7 +601,6 @@ pub extern "C" fn get_pool() -> i32 {
 
 /// Get a swap quote without executing (read-only).
 /// Args: pool_id, token_in, amount_in
-#[no_mangle]
 pub extern "C" fn quote() -> i32 {
     let pool_id = match arg(0) {
         Some(v) if !v.is_empty() => v,
@@ -821,6 +627,12 @@ pub extern "C" fn quote() -> i32 {
     let fee_bps = get_state_u128(&format!("{}:fee_bps", prefix));
 
     let is_a_to_b = token_in == pool_token_a;
+    if !is_a_to_b && token_in != pool_token_b {
+        return fail(&format!(
+            "Token {} is not in pool (expected {} or {})",
+            token_in, pool_token_a, pool_token_b
+        ));
+    }
     let (reserve_in, reserve_out) = if is_a_to_b {
         (reserve_a, reserve_b)
     } else {
@@ -832,17 +644,17 @@ pub extern "C" fn quote() -> i32 {
 
     // Price impact
     let spot_price_scaled = if reserve_in > 0 {
-        reserve_out * PRECISION / reserve_in
+        math::mul_div(reserve_out, PRECISION, reserve_in).unwrap_or(u128::MAX)
     } else {
         0
     };
     let exec_price_scaled = if amount_in > 0 {
-        amount_out * PRECISION / amount_in
+        math::mul_div(amount_out, PRECISION, amount_in).unwrap_or(u128::MAX)
     } else {
         0
     };
-    let impact_bps = if spot_price_scaled > 0 && spot_price_scaled > exec_price_scaled {
-        ((spot_price_scaled - exec_price_scaled) * BPS_DENOMINATOR) / spot_price_scaled
+    let impact_bps = if spot_price_scaled > exec_price_scaled {
+        math::ratio_bps(spot_price_scaled - exec_price_scaled, spot_price_scaled).unwrap_or(0)
     } else {
         0
     };
@@ -865,7 +677,6 @@ pub extern "C" fn quote() -> i32 {
 
 /// Get caller's LP position in a pool (read-only).
 /// Args: pool_id
-#[no_mangle]
 pub extern "C" fn get_position() -> i32 {
     let pool_id = match arg(0) {
         Some(v) if !v.is_empty() => v,
@@ -886,23 +697,14 @@ pub extern "C" fn get_position() -> i32 {
     let total_lp = get_state_u128(&format!("{}:total_lp", prefix));
 
     let (amount_a, amount_b) = if total_lp > 0 && shares > 0 {
-        // Checked mul to prevent WASM trap on u128 overflow
-        let a = shares.checked_mul(reserve_a).map(|v| v / total_lp)
-            .unwrap_or_else(|| (shares / total_lp).saturating_mul(reserve_a));
-        let b = shares.checked_mul(reserve_b).map(|v| v / total_lp)
-            .unwrap_or_else(|| (shares / total_lp).saturating_mul(reserve_b));
+        let a = pro_rata(shares, reserve_a, total_lp);
+        let b = pro_rata(shares, reserve_b, total_lp);
         (a, b)
     } else {
         (0, 0)
     };
 
-    let share_pct_bps = if total_lp > 0 {
-        shares.checked_mul(BPS_DENOMINATOR)
-            .map(|v| v / total_lp)
-            .unwrap_or_else(|| (shares / total_lp).saturating_mul(BPS_DENOMINATOR))
-    } else {
-        0
-    };
+    let share_pct_bps = math::ratio_bps(shares, total_lp).unwrap_or(0);
 
     ok_data(
         "Position found",
@@ -918,7 +720,6 @@ pub extern "C" fn get_position() -> i32 {
 }
 
 /// List all pools (read-only).
-#[no_mangle]
 pub extern "C" fn list_pools() -> i32 {
     let count = get_state_u64("dex:pool_count");
     if count == 0 {
@@ -958,3 +759,92 @@ pub extern "C" fn list_pools() -> i32 {
 
     ok_data(&format!("{} pools", count), &data)
 }
+
+// ─────────────────────────────────────────────────────────────
+// TESTS — the deployed entry points, run natively on MockHost
+// ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_testing::MockHost;
+
+    const LP: &str = "LOSprovider";
+    const TRADER: &str = "LOStrader";
+
+    /// DEX with one LOS/LOSToken pool (1M : 2M, default fee). Returns the pool id.
+    fn dex_with_pool(host: &mut MockHost) -> String {
+        assert!(host.call(&[], init).is_success());
+        let res = host.call(&["LOS", "LOSToken", "1000000", "2000000"], create_pool);
+        assert!(res.is_success(), "{}", res.return_str());
+        make_pool_id("LOS", "LOSToken")
+    }
+
+    #[test]
+    fn test_quote_matches_swap() {
+        let mut host = MockHost::new("LOSConDex").with_caller(LP);
+        let pool = dex_with_pool(&mut host);
+        let (after_fee, _) = deduct_fee(10_000, DEFAULT_FEE_BPS);
+        let expected = compute_output(after_fee, 1_000_000, 2_000_000);
+
+        let quoted = host.call(&[&pool, "LOS", "10000"], quote);
+        assert!(quoted
+            .return_str()
+            .contains(&format!("\"amount_out\":\"{}\"", expected)));
+        let unknown = host.call(&[&pool, "LOSOther", "10000"], quote);
+        assert!(unknown.return_str().contains("is not in pool"));
+
+        // Slippage and deadline protection
+        let min_out = (expected + 1).to_string();
+        assert!(!host
+            .call(&[&pool, "LOS", "10000", &min_out, "0"], swap)
+            .is_success());
+        let past = (host.timestamp() - 1).to_string();
+        assert!(!host
+            .call(&[&pool, "LOS", "10000", "0", &past], swap)
+            .is_success());
+
+        let res = host.call_as(
+            TRADER,
+            &[&pool, "LOS", "10000", &expected.to_string(), "0"],
+            swap,
+        );
+        assert!(res.is_success(), "{}", res.return_str());
+        let prefix = format!("pool:{}", pool);
+        assert_eq!(
+            host.state_str(&format!("{}:reserve_a", prefix)).as_deref(),
+            Some("1010000")
+        );
+        assert_eq!(
+            host.state_str(&format!("{}:reserve_b", prefix)),
+            Some((2_000_000 - expected).to_string())
+        );
+    }
+
+    #[test]
+    fn test_liquidity_roundtrip_keeps_minimum_locked() {
+        let mut host = MockHost::new("LOSConDex").with_caller(LP);
+        let pool = dex_with_pool(&mut host);
+        let lp_key = format!("lp:{}:{}", pool, LP);
+        let shares = parse_u128(&host.state_str(&lp_key).unwrap_or_default());
+        assert_eq!(shares, isqrt(1_000_000 * 2_000_000) - MINIMUM_LIQUIDITY);
+
+        assert!(host
+            .call(&[&pool, "100000", "200000", "0"], add_liquidity)
+            .is_success());
+        let shares = parse_u128(&host.state_str(&lp_key).unwrap_or_default());
+        let too_many = (shares + 1).to_string();
+        assert!(!host
+            .call(&[&pool, &too_many, "0", "0"], remove_liquidity)
+            .is_success());
+        let res = host.call(&[&pool, &shares.to_string(), "0", "0"], remove_liquidity);
+        assert!(res.is_success(), "{}", res.return_str());
+
+        // Only the locked MINIMUM_LIQUIDITY share of the reserves remains
+        let prefix = format!("pool:{}", pool);
+        let total_lp = parse_u128(&host.state_str(&format!("{}:total_lp", prefix)).unwrap());
+        assert_eq!(total_lp, MINIMUM_LIQUIDITY);
+        let reserve_a = parse_u128(&host.state_str(&format!("{}:reserve_a", prefix)).unwrap());
+        assert!(reserve_a > 0 && reserve_a < 1_000);
+    }
+}
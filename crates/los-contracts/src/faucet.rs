@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! # Testnet Faucet Contract (WASM)
+//!
+//! Deployable `#![no_std]` WASM smart contract that hands out test CIL.
+//! Testnets deploy and fund it; the node itself has no faucet, so faucet
+//! policy (drip size, cooldown) changes with a contract call instead of a
+//! node release, and mainnet binaries carry no faucet code at all.
+//!
+//! ## Flow
+//! 1. The operator deploys the contract and calls
+//!    `init(drip_cil, cooldown_secs)` with CIL attached; they become owner.
+//! 2. Anyone calls `drip(recipient)`. The contract sends `drip_cil` to the
+//!    recipient (the caller if omitted) unless that address received a drip
+//!    less than `cooldown_secs` ago. On testnets the call can be node-signed,
+//!    so an empty wallet can request funds.
+//! 3. The owner tops the faucet up with `refill` (CIL attached), changes the
+//!    policy with `set_policy`, or takes CIL back with `withdraw`.
+//!
+//! ## State Layout
+//! - `faucet:owner`         → Owner (init caller)
+//! - `faucet:drip`          → CIL per drip (decimal string)
+//! - `faucet:cooldown`      → Seconds between drips to one address (decimal)
+//! - `faucet:claims`        → Drips paid (decimal string)
+//! - `faucet:total`         → CIL paid out (decimal string)
+//! - `last:{address}`       → Block timestamp of the address's last drip
+//!
+//! ## Exported Functions
+//! | Function     | Args                       | Who   |
+//! |--------------|----------------------------|-------|
+//! | `init`       | drip_cil, cooldown_secs    | once  |
+//! | `drip`       | recipient (optional)       | any   |
+//! | `refill`     | — (CIL attached)           | owner |
+//! | `set_policy` | drip_cil, cooldown_secs    | owner |
+//! | `withdraw`   | amount_cil                 | owner |
+//! | `get_info`   | —                          | any   |
+//! | `next_claim` | address                    | any   |
+//!
+//! ## Compilation
+//! ```bash
+//! cargo build --target wasm32-unknown-unknown --release \
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin faucet
+//! ```
+
+use crate::{
+    fail, faucet_wait_secs, get_state_str, get_state_u128, get_state_u64, json_escape, ok_data,
+    parse_u128, parse_u64, set_state_u128, u128_to_str,
+};
+use alloc::format;
+use alloc::string::String;
+use los_sdk::*;
+
+// ─────────────────────────────────────────────────────────────
+// STATE HELPERS
+// ─────────────────────────────────────────────────────────────
+
+fn last_key(address: &str) -> String {
+    format!("last:{}", address)
+}
+
+/// Err unless the caller is the owner
+fn require_owner() -> Result<String, i32> {
+    let owner = get_state_str("faucet:owner");
+    if owner.is_empty() {
+        return Err(fail("Faucet not initialized"));
+    }
+    if caller() != owner {
+        return Err(fail("Only the owner may do this"));
+    }
+    Ok(owner)
+}
+
+/// (drip_cil, cooldown_secs) from args 0 and 1
+fn policy_args() -> Result<(u128, u64), i32> {
+    let drip = arg(0).map(|v| parse_u128(&v)).unwrap_or(0);
+    if drip == 0 {
+        return Err(fail("drip_cil must be a positive integer"));
+    }
+    let cooldown = match arg(1) {
+        Some(v) if !v.is_empty() && v.bytes().all(|b| b.is_ascii_digit()) => parse_u64(&v),
+        _ => return Err(fail("cooldown_secs must be a non-negative integer")),
+    };
+    Ok((drip, cooldown))
+}
+
+fn info_json() -> String {
+    format!(
+        "{{\"owner\":\"{}\",\"drip_cil\":\"{}\",\"cooldown_secs\":\"{}\",\"balance\":\"{}\",\"claims\":\"{}\",\"total_dripped\":\"{}\"}}",
+        json_escape(&get_state_str("faucet:owner")),
+        u128_to_str(get_state_u128("faucet:drip")),
+        get_state_u64("faucet:cooldown"),
+        u128_to_str(balance()),
+        get_state_u64("faucet:claims"),
+        u128_to_str(get_state_u128("faucet:total")),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────
+// ENTRY POINTS
+// ─────────────────────────────────────────────────────────────
+
+/// Set the policy and owner (once). CIL attached funds the faucet.
+/// Args: drip_cil, cooldown_secs
+pub extern "C" fn init() -> i32 {
+    if !get_state_str("faucet:owner").is_empty() {
+        return fail("Already initialized");
+    }
+    let owner = caller();
+    if owner.is_empty() {
+        return fail("Caller unknown");
+    }
+    let (drip, cooldown) = match policy_args() {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
+    state::set_str("faucet:owner", &owner);
+    set_state_u128("faucet:drip", drip);
+    state::set_str("faucet:cooldown", &format!("{}", cooldown));
+    event::emit(
+        "FaucetInit",
+        &format!(
+            "{{\"owner\":\"{}\",\"drip_cil\":\"{}\",\"cooldown_secs\":\"{}\"}}",
+            json_escape(&owner),
+            u128_to_str(drip),
+            cooldown
+        ),
+    );
+    ok_data("Faucet initialized", &info_json())
+}
+
+/// Send one drip to `recipient` (default: the caller).
+/// Args: recipient (optional)
+pub extern "C" fn drip() -> i32 {
+    let drip = get_state_u128("faucet:drip");
+    if drip == 0 {
+        return fail("Faucet not initialized");
+    }
+    let recipient = match arg(0) {
+        Some(v) if !v.is_empty() => v,
+        _ => caller(),
+    };
+    if !recipient.starts_with("LOS") {
+        return fail("Recipient must be a LOS address");
+    }
+    if recipient == self_address() {
+        return fail("The faucet cannot drip to itself");
+    }
+
+    let now = timestamp();
+    let cooldown = get_state_u64("faucet:cooldown");
+    let wait = faucet_wait_secs(get_state_u64(&last_key(&recipient)), cooldown, now);
+    if wait > 0 {
+        return fail(&format!("Cooldown active: try again in {} seconds", wait));
+    }
+    if balance() < drip {
+        return fail("Faucet is empty; ask the owner to refill it");
+    }
+    if let Err(e) = transfer(&recipient, drip) {
+        return fail(e);
+    }
+    state::set_str(&last_key(&recipient), &format!("{}", now));
+    state::set_str(
+        "faucet:claims",
+        &format!("{}", get_state_u64("faucet:claims").saturating_add(1)),
+    );
+    set_state_u128(
+        "faucet:total",
+        get_state_u128("faucet:total").saturating_add(drip),
+    );
+
+    let _ = event::emit_indexed(
+        "FaucetDrip",
+        &[&recipient],
+        &format!("{{\"amount\":\"{}\"}}", u128_to_str(drip)),
+    );
+    ok_data(
+        &format!("Sent {} CIL", u128_to_str(drip)),
+        &format!(
+            "{{\"recipient\":\"{}\",\"amount\":\"{}\",\"next_claim\":\"{}\"}}",
+            json_escape(&recipient),
+            u128_to_str(drip),
+            now.saturating_add(cooldown)
+        ),
+    )
+}
+
+/// Add the CIL attached to this call (owner only; otherwise it is refunded).
+pub extern "C" fn refill() -> i32 {
+    let owner = match require_owner() {
+        Ok(o) => o,
+        Err(code) => return code,
+    };
+    let amount = msg_value();
+    if amount == 0 {
+        return fail("No CIL attached (send amount_cil with the call)");
+    }
+    event::emit(
+        "FaucetRefill",
+        &format!(
+            "{{\"by\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&owner),
+            u128_to_str(amount)
+        ),
+    );
+    ok_data(&format!("Added {} CIL", u128_to_str(amount)), &info_json())
+}
+
+/// Change drip size and cooldown (owner only).
+/// Args: drip_cil, cooldown_secs
+pub extern "C" fn set_policy() -> i32 {
+    if let Err(code) = require_owner() {
+        return code;
+    }
+    let (drip, cooldown) = match policy_args() {
+        Ok(p) => p,
+        Err(code) => return code,
+    };
+    set_state_u128("faucet:drip", drip);
+    state::set_str("faucet:cooldown", &format!("{}", cooldown));
+    event::emit(
+        "FaucetPolicy",
+        &format!(
+            "{{\"drip_cil\":\"{}\",\"cooldown_secs\":\"{}\"}}",
+            u128_to_str(drip),
+            cooldown
+        ),
+    );
+    ok_data("Policy updated", &info_json())
+}
+
+/// Send CIL from the faucet back to the owner (owner only).
+/// Args: amount_cil
+pub extern "C" fn withdraw() -> i32 {
+    let owner = match require_owner() {
+        Ok(o) => o,
+        Err(code) => return code,
+    };
+    let amount = arg(0).map(|v| parse_u128(&v)).unwrap_or(0);
+    if amount == 0 || amount > balance() {
+        return fail("amount_cil must be positive and at most the faucet balance");
+    }
+    if let Err(e) = transfer(&owner, amount) {
+        return fail(e);
+    }
+    ok_data(
+        &format!("Withdrew {} CIL", u128_to_str(amount)),
+        &info_json(),
+    )
+}
+
+/// Policy, owner, balance and totals (read-only).
+pub extern "C" fn get_info() -> i32 {
+    ok_data("Faucet", &info_json())
+}
+
+/// Seconds until `address` may receive a drip, 0 = now (read-only).
+/// Args: address
+pub extern "C" fn next_claim() -> i32 {
+    let address = match arg(0) {
+        Some(v) if !v.is_empty() => v,
+        _ => return fail("Missing address"),
+    };
+    let wait = faucet_wait_secs(
+        get_state_u64(&last_key(&address)),
+        get_state_u64("faucet:cooldown"),
+        timestamp(),
+    );
+    ok_data(
+        "Next claim",
+        &format!(
+            "{{\"address\":\"{}\",\"wait_secs\":\"{}\"}}",
+            json_escape(&address),
+            wait
+        ),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────
+// TESTS — the deployed entry points, run natively on MockHost
+// ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_testing::MockHost;
+
+    const OWNER: &str = "LOSowner";
+    const ALICE: &str = "LOSalice";
+
+    /// 1000 CIL faucet paying 100 CIL per hour per address
+    fn funded_faucet() -> MockHost {
+        let mut host = MockHost::new("LOSConFaucet").with_caller(OWNER);
+        let res = host.call_with_value(1_000, &["100", "3600"], init);
+        assert!(res.is_success(), "{}", res.return_str());
+        host
+    }
+
+    #[test]
+    fn test_drip_enforces_cooldown() {
+        let mut host = funded_faucet();
+        let res = host.call_as(ALICE, &[], drip);
+        assert!(res.is_success(), "{}", res.return_str());
+        assert_eq!(res.transfers, vec![(ALICE.to_string(), 100)]);
+
+        let again = host.call_as(ALICE, &[], drip);
+        assert!(!again.is_success());
+        assert!(again.return_str().contains("Cooldown active"));
+        // Someone else may still claim, and Alice may after an hour
+        assert!(host.call_as(ALICE, &["LOSbob"], drip).is_success());
+        host.advance_time(3_600);
+        assert!(host.call_as(ALICE, &[], drip).is_success());
+
+        assert_eq!(host.balance(), 700);
+        assert_eq!(host.state_str("faucet:claims").as_deref(), Some("3"));
+        assert_eq!(host.state_str("faucet:total").as_deref(), Some("300"));
+    }
+
+    #[test]
+    fn test_admin_is_owner_only() {
+        let mut host = funded_faucet();
+        assert!(!host.call_as(ALICE, &["50"], withdraw).is_success());
+        assert!(!host.call_as(ALICE, &["1", "0"], set_policy).is_success());
+
+        let res = host.call(&["50"], withdraw);
+        assert_eq!(res.transfers, vec![(OWNER.to_string(), 50)]);
+        assert!(host.call(&["500", "0"], set_policy).is_success());
+        assert!(host.call_as(ALICE, &[], drip).is_success());
+        let empty = host.call_as(ALICE, &[], drip);
+        assert!(empty.return_str().contains("Faucet is empty"));
+        assert!(host.call_with_value(600, &[], refill).is_success());
+        assert!(host.call_as(ALICE, &[], drip).is_success());
+        assert_eq!(host.balance(), 550);
+    }
+}
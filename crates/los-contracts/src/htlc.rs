@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! # HTLC Contract (WASM)
+//!
+//! Deployable `#![no_std]` WASM smart contract implementing Hash Time-Locked
+//! Contracts for native CIL on the Unauthority (LOS) blockchain — the
+//! building block for trustless cross-chain atomic swaps and OTC trades.
+//!
+//! ## Flow
+//! 1. Alice picks a secret `s` and publishes `hashlock = SHA3-256(s)` (hex).
+//! 2. Alice calls `lock(receiver=Bob, hashlock, timelock)` with CIL attached
+//!    (`amount_cil` on `/call-contract`).
+//! 3. Bob locks his side on the other chain against the same hashlock.
+//! 4. Alice claims Bob's funds there, revealing `s`.
+//! 5. Anyone calls `claim(id, s)` here before `timelock`; CIL goes to Bob.
+//!    The preimage is stored and emitted so either side can read it.
+//! 6. If nobody claims before `timelock`, anyone may call `refund(id)` and
+//!    the CIL returns to Alice.
+//!
+//! ## Deposits
+//! The UVM has no per-call "value" host function. The contract tracks the
+//! CIL owed to open swaps in `htlc:locked_total`; the amount of a new lock is
+//! `balance() - locked_total`, i.e. exactly the CIL attached to this call.
+//! A `lock` that fails validation refunds that surplus to the caller.
+//!
+//! ## State Layout
+//! - `htlc:count`               → Swaps created (decimal string, id nonce)
+//! - `htlc:locked_total`        → CIL held for open swaps (decimal string)
+//! - `swap:{id}:sender`         → Locker (refund recipient)
+//! - `swap:{id}:receiver`       → Claim recipient
+//! - `swap:{id}:amount`         → Locked CIL (decimal string)
+//! - `swap:{id}:hashlock`       → SHA3-256 of the preimage (64 hex chars)
+//! - `swap:{id}:timelock`       → Expiry, UNIX seconds (decimal string)
+//! - `swap:{id}:status`         → "open" | "claimed" | "refunded"
+//! - `swap:{id}:preimage`       → Revealed preimage (hex), set on claim
+//!
+//! ## Exported Functions
+//! | Function   | Args                                 |
+//! |------------|--------------------------------------|
+//! | `lock`     | receiver, hashlock, timelock         |
+//! | `claim`    | id, preimage (hex)                   |
+//! | `refund`   | id                                   |
+//! | `get_swap` | id                                   |
+//!
+//! ## Compilation
+//! ```bash
+//! cargo build --target wasm32-unknown-unknown --release \
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin htlc
+//! ```
+
+use crate::{
+    fail, get_state_str, get_state_u128, get_state_u64, hex_decode, hex_encode, htlc_hashlock,
+    htlc_id, json_escape, ok_data, parse_u64, set_state_u128, set_state_u64, u128_to_str,
+    HTLC_MAX_PREIMAGE_BYTES,
+};
+use alloc::format;
+use alloc::string::String;
+use los_sdk::*;
+
+// ─────────────────────────────────────────────────────────────
+// STATE HELPERS
+// ─────────────────────────────────────────────────────────────
+
+fn swap_key(id: &str, field: &str) -> String {
+    format!("swap:{}:{}", id, field)
+}
+
+/// JSON view of one swap (None if it does not exist).
+fn swap_json(id: &str) -> Option<String> {
+    let status = get_state_str(&swap_key(id, "status"));
+    if status.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{{\"id\":\"{}\",\"sender\":\"{}\",\"receiver\":\"{}\",\"amount\":\"{}\",\"hashlock\":\"{}\",\"timelock\":\"{}\",\"status\":\"{}\",\"preimage\":\"{}\"}}",
+        json_escape(id),
+        json_escape(&get_state_str(&swap_key(id, "sender"))),
+        json_escape(&get_state_str(&swap_key(id, "receiver"))),
+        u128_to_str(get_state_u128(&swap_key(id, "amount"))),
+        get_state_str(&swap_key(id, "hashlock")),
+        get_state_u64(&swap_key(id, "timelock")),
+        status,
+        get_state_str(&swap_key(id, "preimage")),
+    ))
+}
+
+/// Release an open swap's CIL to `recipient` and mark it `status`.
+fn settle(id: &str, recipient: &str, status: &str) -> Result<u128, &'static str> {
+    let amount = get_state_u128(&swap_key(id, "amount"));
+    transfer(recipient, amount)?;
+    let locked = get_state_u128("htlc:locked_total");
+    set_state_u128("htlc:locked_total", locked.saturating_sub(amount));
+    state::set_str(&swap_key(id, "status"), status);
+    Ok(amount)
+}
+
+// ─────────────────────────────────────────────────────────────
+// ENTRY POINTS
+// ─────────────────────────────────────────────────────────────
+
+/// Lock the CIL attached to this call.
+/// Args: receiver, hashlock (SHA3-256 hex), timelock (UNIX seconds)
+pub extern "C" fn lock() -> i32 {
+    let sender = caller();
+    let locked_total = get_state_u128("htlc:locked_total");
+    let deposit = balance().saturating_sub(locked_total);
+
+    // Validate everything before touching state; on failure hand the
+    // attached CIL back so it cannot be captured by the next lock.
+    let reject = |msg: &str| -> i32 {
+        if deposit > 0 && !sender.is_empty() {
+            let _ = transfer(&sender, deposit);
+        }
+        fail(msg)
+    };
+
+    let receiver = match arg(0) {
+        Some(v) if !v.is_empty() => v,
+        _ => return reject("Missing receiver"),
+    };
+    let hashlock = match arg(1) {
+        Some(v) if v.len() == 64 && hex_decode(&v).is_some() => v.to_ascii_lowercase(),
+        _ => return reject("hashlock must be 64 hex chars (SHA3-256)"),
+    };
+    let timelock = match arg(2) {
+        Some(v) => parse_u64(&v),
+        None => return reject("Missing timelock"),
+    };
+    if timelock <= timestamp() {
+        return reject("timelock must be in the future");
+    }
+    if deposit == 0 {
+        return reject("No CIL attached (send amount_cil with the call)");
+    }
+
+    let nonce = get_state_u64("htlc:count");
+    let id = htlc_id(&sender, &receiver, &hashlock, timelock, nonce);
+    state::set_str(&swap_key(&id, "sender"), &sender);
+    state::set_str(&swap_key(&id, "receiver"), &receiver);
+    set_state_u128(&swap_key(&id, "amount"), deposit);
+    state::set_str(&swap_key(&id, "hashlock"), &hashlock);
+    set_state_u64(&swap_key(&id, "timelock"), timelock);
+    state::set_str(&swap_key(&id, "status"), "open");
+    set_state_u64("htlc:count", nonce + 1);
+    set_state_u128("htlc:locked_total", locked_total.saturating_add(deposit));
+
+    event::emit(
+        "HtlcLocked",
+        &format!(
+            "{{\"id\":\"{}\",\"sender\":\"{}\",\"receiver\":\"{}\",\"amount\":\"{}\",\"hashlock\":\"{}\",\"timelock\":\"{}\"}}",
+            id,
+            json_escape(&sender),
+            json_escape(&receiver),
+            u128_to_str(deposit),
+            hashlock,
+            timelock,
+        ),
+    );
+    ok_data(
+        &format!("Locked {} CIL", u128_to_str(deposit)),
+        &format!("{{\"id\":\"{}\"}}", id),
+    )
+}
+
+/// Claim with the preimage before the timelock. Anyone may submit it;
+/// funds always go to the receiver.
+/// Args: id, preimage (hex)
+pub extern "C" fn claim() -> i32 {
+    let id = match arg(0) {
+        Some(v) if !v.is_empty() => v,
+        _ => return fail("Missing id"),
+    };
+    let preimage = match arg(1).as_deref().and_then(hex_decode) {
+        Some(p) if !p.is_empty() && p.len() <= HTLC_MAX_PREIMAGE_BYTES => p,
+        _ => return fail("preimage must be 1-256 bytes of hex"),
+    };
+    if get_state_str(&swap_key(&id, "status")) != "open" {
+        return fail("Swap not found or not open");
+    }
+    if timestamp() >= get_state_u64(&swap_key(&id, "timelock")) {
+        return fail("Swap expired; use refund");
+    }
+    if htlc_hashlock(&preimage) != get_state_str(&swap_key(&id, "hashlock")) {
+        return fail("Preimage does not match hashlock");
+    }
+
+    let receiver = get_state_str(&swap_key(&id, "receiver"));
+    let amount = match settle(&id, &receiver, "claimed") {
+        Ok(a) => a,
+        Err(e) => return fail(e),
+    };
+    let preimage_hex = hex_encode(&preimage);
+    state::set_str(&swap_key(&id, "preimage"), &preimage_hex);
+
+    event::emit(
+        "HtlcClaimed",
+        &format!(
+            "{{\"id\":\"{}\",\"receiver\":\"{}\",\"amount\":\"{}\",\"preimage\":\"{}\"}}",
+            json_escape(&id),
+            json_escape(&receiver),
+            u128_to_str(amount),
+            preimage_hex,
+        ),
+    );
+    ok_data(
+        "Claimed",
+        &format!(
+            "{{\"id\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&id),
+            u128_to_str(amount)
+        ),
+    )
+}
+
+/// Return the CIL to the sender once the timelock has passed. Anyone may call.
+/// Args: id
+pub extern "C" fn refund() -> i32 {
+    let id = match arg(0) {
+        Some(v) if !v.is_empty() => v,
+        _ => return fail("Missing id"),
+    };
+    if get_state_str(&swap_key(&id, "status")) != "open" {
+        return fail("Swap not found or not open");
+    }
+    let timelock = get_state_u64(&swap_key(&id, "timelock"));
+    if timestamp() < timelock {
+        return fail(&format!("Swap locked until {}", timelock));
+    }
+
+    let sender = get_state_str(&swap_key(&id, "sender"));
+    let amount = match settle(&id, &sender, "refunded") {
+        Ok(a) => a,
+        Err(e) => return fail(e),
+    };
+
+    event::emit(
+        "HtlcRefunded",
+        &format!(
+            "{{\"id\":\"{}\",\"sender\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&id),
+            json_escape(&sender),
+            u128_to_str(amount),
+        ),
+    );
+    ok_data(
+        "Refunded",
+        &format!(
+            "{{\"id\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&id),
+            u128_to_str(amount)
+        ),
+    )
+}
+
+/// Read a swap (read-only).
+/// Args: id
+pub extern "C" fn get_swap() -> i32 {
+    let id = arg(0).unwrap_or_default();
+    match swap_json(&id) {
+        Some(json) => ok_data("Swap", &json),
+        None => fail("Swap not found"),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// TESTS — the deployed entry points, run natively on MockHost
+// ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_testing::MockHost;
+
+    const ALICE: &str = "LOSalice";
+    const BOB: &str = "LOSbob";
+    const PREIMAGE: &[u8] = b"atomic swap secret";
+
+    /// Alice locks 500 CIL for Bob, claimable for 600 seconds. Returns the id.
+    fn lock_for_bob(host: &mut MockHost) -> String {
+        let hashlock = htlc_hashlock(PREIMAGE);
+        let timelock = host.timestamp() + 600;
+        let res = host.call_with_value(500, &[BOB, &hashlock, &timelock.to_string()], lock);
+        assert!(res.is_success(), "{}", res.return_str());
+        htlc_id(ALICE, BOB, &hashlock, timelock, 0)
+    }
+
+    #[test]
+    fn test_claim_pays_receiver_before_timelock() {
+        let mut host = MockHost::new("LOSConHtlc").with_caller(ALICE);
+        let id = lock_for_bob(&mut host);
+        assert_eq!(host.state_str("htlc:count").as_deref(), Some("1"));
+        // Decimal, like every numeric state value (read back with parse_u64)
+        let timelock = (host.timestamp() + 600).to_string();
+        assert_eq!(host.state_str(&swap_key(&id, "timelock")), Some(timelock));
+
+        let wrong = host.call(&[&id, &hex_encode(b"guess")], claim);
+        assert!(wrong.return_str().contains("does not match"));
+        let res = host.call(&[&id, &hex_encode(PREIMAGE)], claim);
+        assert!(res.is_success(), "{}", res.return_str());
+        assert_eq!(res.transfers, vec![(BOB.to_string(), 500)]);
+        assert_eq!(
+            host.state_str(&swap_key(&id, "status")).as_deref(),
+            Some("claimed")
+        );
+        assert_eq!(host.state_u128("htlc:locked_total"), 0);
+        assert!(!host.call(&[&id], refund).is_success());
+    }
+
+    #[test]
+    fn test_refund_only_after_timelock() {
+        let mut host = MockHost::new("LOSConHtlc").with_caller(ALICE);
+        let id = lock_for_bob(&mut host);
+        assert!(!host.call(&[&id], refund).is_success());
+
+        host.advance_time(600);
+        let expired = host.call(&[&id, &hex_encode(PREIMAGE)], claim);
+        assert!(expired.return_str().contains("expired"));
+        let res = host.call_as(BOB, &[&id], refund);
+        assert_eq!(res.transfers, vec![(ALICE.to_string(), 500)]);
+        assert_eq!(host.balance(), 0);
+    }
+}
@@ -3,12 +3,20 @@
 //! This crate contains the production `#![no_std]` WASM smart contracts
 //! for the Unauthority (LOS) blockchain.
 //!
+//! Each contract's logic lives in a library module (`los_contracts::faucet`,
+//! …). The binaries in `src/bin/` only export those entry points under
+//! `#[no_mangle]` names, so the code tested natively against
+//! `los_testing::MockHost` is the code that gets deployed.
+//!
 //! ## Contracts
 //!
 //! | Contract       | Binary         | Description                                        |
 //! |----------------|----------------|----------------------------------------------------|
 //! | USP-01 Token   | `usp01_token`  | Native Fungible Token Standard (ERC-20 equivalent) |
 //! | DEX AMM        | `dex_amm`      | Constant Product AMM (x·y=k) decentralized exchange|
+//! | HTLC           | `htlc`         | Hash time-locked CIL escrow for atomic swaps       |
+//! | Faucet         | `faucet`       | Testnet CIL faucet with per-address cooldown       |
+//! | Merkle Airdrop | `merkle_distributor` | CIL airdrop claimed with blake3 merkle proofs |
 //!
 //! ## Compilation
 //!
@@ -21,8 +29,20 @@
 //! # Build individual contract
 //! cargo build --target wasm32-unknown-unknown --release --manifest-path crates/los-contracts/Cargo.toml --bin usp01_token
 //! cargo build --target wasm32-unknown-unknown --release --manifest-path crates/los-contracts/Cargo.toml --bin dex_amm
+//! cargo build --target wasm32-unknown-unknown --release --manifest-path crates/los-contracts/Cargo.toml --bin htlc
+//! cargo build --target wasm32-unknown-unknown --release --manifest-path crates/los-contracts/Cargo.toml --bin faucet
+//! cargo build --target wasm32-unknown-unknown --release --manifest-path crates/los-contracts/Cargo.toml --bin merkle_distributor
+//! ```
+//!
+//! ## Testing
+//!
+//! ```bash
+//! cargo test --manifest-path crates/los-contracts/Cargo.toml --lib
 //! ```
 //!
+//! Pure helpers are unit-tested directly; every contract is also driven
+//! end-to-end through `MockHost` (state, attached CIL, transfers, events).
+//!
 //! ## Architecture
 //!
 //! All contracts use `los-sdk` for host function interaction:
@@ -35,12 +55,29 @@
 //! (not LE bytes) to avoid `String::from_utf8_lossy` corruption
 //! in `Contract.state: BTreeMap<String, String>`.
 
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod dex_amm;
+pub mod faucet;
+pub mod htlc;
+pub mod merkle_distributor;
+pub mod usp01_token;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use los_sdk::math::{apply_bps_fee, mul_div, BPS_DENOMINATOR};
+use los_sdk::{set_return_str, state};
+use sha3::{Digest, Sha3_256};
+
 // ─────────────────────────────────────────────────────────────────
-// Shared pure helper functions (tested natively, duplicated in bins)
+// Shared pure helper functions
 // ─────────────────────────────────────────────────────────────────
-// These helpers mirror the logic inside usp01_token.rs and dex_amm.rs.
-// Unit tests below verify correctness of all pure arithmetic, string
-// conversion, and JSON formatting used by both WASM contracts.
+// Used by the contract modules (usp01_token, dex_amm, htlc, faucet,
+// merkle_distributor) and by off-chain tooling. Unit tests below verify
+// all pure arithmetic, string conversion and hashing.
 // ─────────────────────────────────────────────────────────────────
 
 /// Parse a decimal string to u128 with overflow protection.
@@ -101,7 +138,7 @@ pub fn u128_to_str(val: u128) -> String {
     String::from_utf8(buf[pos..].to_vec()).unwrap_or_default()
 }
 
-/// Escape a string for JSON output (double-quote, backslash, newline).
+/// Escape a string for JSON output (double-quote, backslash, newline, CR, tab).
 pub fn json_escape(s: &str) -> String {
     let mut out = String::with_capacity(s.len() + 8);
     for c in s.chars() {
@@ -109,6 +146,8 @@ pub fn json_escape(s: &str) -> String {
             '"' => out.push_str("\\\""),
             '\\' => out.push_str("\\\\"),
             '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
             _ => out.push(c),
         }
     }
@@ -132,29 +171,29 @@ pub fn isqrt(n: u128) -> u128 {
 
 /// Constant product swap output: `out = (in * reserve_out) / (reserve_in + in)`.
 /// Returns 0 if any input is zero to prevent division by zero.
+/// Uses `los_sdk::math::mul_div`, so large reserves never overflow.
 pub fn compute_output(amount_in: u128, reserve_in: u128, reserve_out: u128) -> u128 {
     if reserve_in == 0 || reserve_out == 0 || amount_in == 0 {
         return 0;
     }
-    const PRECISION: u128 = 1_000_000_000_000;
-    match (
-        amount_in.checked_mul(reserve_out),
-        reserve_in.checked_add(amount_in),
-    ) {
-        (Some(num), Some(den)) if den > 0 => num / den,
-        _ => {
-            // Overflow fallback: scaled division
-            let ratio_scaled = (amount_in * PRECISION) / reserve_in.saturating_add(amount_in);
-            (ratio_scaled * reserve_out) / PRECISION
-        }
+    match reserve_in.checked_add(amount_in) {
+        Some(den) => mul_div(amount_in, reserve_out, den).unwrap_or(0),
+        // reserve_in + amount_in > u128::MAX → out < reserve_out; halve both
+        // terms of the ratio (relative error ≤ 2^-127)
+        None => mul_div(amount_in / 2, reserve_out, reserve_in / 2 + amount_in / 2).unwrap_or(0),
     }
 }
 
 /// Deduct fee from input amount. Returns (amount_after_fee, fee_amount).
+/// Fees above 100% are clamped to the whole amount.
 pub fn deduct_fee(amount: u128, fee_bps: u128) -> (u128, u128) {
-    const BPS_DENOMINATOR: u128 = 10_000;
-    let fee = amount * fee_bps / BPS_DENOMINATOR;
-    (amount - fee, fee)
+    apply_bps_fee(amount, fee_bps.min(BPS_DENOMINATOR)).unwrap_or((0, amount))
+}
+
+/// Pro-rata share: `amount * part / total` (LP mint/burn, position value).
+/// Returns 0 when `total` is zero.
+pub fn pro_rata(amount: u128, part: u128, total: u128) -> u128 {
+    mul_div(amount, part, total).unwrap_or(0)
 }
 
 /// Generate deterministic pool ID from token pair (sorted alphabetically).
@@ -176,6 +215,230 @@ pub fn allow_key(owner: &str, spender: &str) -> String {
     format!("allow:{}:{}", owner, spender)
 }
 
+/// Lowercase hex encoding (hashlocks, preimages, HTLC ids).
+pub fn hex_encode(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(HEX[(b >> 4) as usize] as char);
+        out.push(HEX[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Decode hex (either case). None on odd length or non-hex characters.
+pub fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    let bytes = s.as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    bytes
+        .chunks(2)
+        .map(|p| Some((nibble(p[0])? << 4) | nibble(p[1])?))
+        .collect()
+}
+
+/// Longest preimage `htlc` and USP-01 `htlc_claim` accept (bytes)
+pub const HTLC_MAX_PREIMAGE_BYTES: usize = 256;
+
+/// HTLC hashlock for a preimage: hex(SHA3-256(preimage)).
+pub fn htlc_hashlock(preimage: &[u8]) -> String {
+    hex_encode(&Sha3_256::digest(preimage))
+}
+
+/// Deterministic HTLC id shared by `htlc` and USP-01 `htlc_lock`:
+/// SHA3-256("LOS-HTLC-V1" ‖ len-prefixed sender, receiver, hashlock ‖ timelock ‖ nonce).
+pub fn htlc_id(sender: &str, receiver: &str, hashlock: &str, timelock: u64, nonce: u64) -> String {
+    let mut h = Sha3_256::new();
+    h.update(b"LOS-HTLC-V1");
+    for field in [sender, receiver, hashlock] {
+        h.update((field.len() as u32).to_le_bytes());
+        h.update(field.as_bytes());
+    }
+    h.update(timelock.to_le_bytes());
+    h.update(nonce.to_le_bytes());
+    hex_encode(&h.finalize())
+}
+
+/// Seconds until an address whose last faucet drip was at `last` may claim
+/// again (0 = now). `last == 0` means it never claimed.
+pub fn faucet_wait_secs(last: u64, cooldown: u64, now: u64) -> u64 {
+    if last == 0 {
+        return 0;
+    }
+    last.saturating_add(cooldown).saturating_sub(now)
+}
+
+/// Airdrop leaf: blake3(0x00 ‖ index ‖ len-prefixed account ‖ amount).
+/// Off-chain tree builders must hash entries exactly like this.
+pub fn merkle_leaf(index: u64, account: &str, amount: u128) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(&[0x00]);
+    h.update(&index.to_le_bytes());
+    h.update(&(account.len() as u32).to_le_bytes());
+    h.update(account.as_bytes());
+    h.update(&amount.to_le_bytes());
+    *h.finalize().as_bytes()
+}
+
+/// Inner node: blake3(0x01 ‖ min(a, b) ‖ max(a, b)). Sorting the pair
+/// means proofs need no left/right flags.
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut h = blake3::Hasher::new();
+    h.update(&[0x01]);
+    h.update(lo);
+    h.update(hi);
+    *h.finalize().as_bytes()
+}
+
+/// Root of `leaves` (an odd node is carried up unchanged). Zero for no leaves.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|p| {
+                if p.len() == 2 {
+                    merkle_parent(&p[0], &p[1])
+                } else {
+                    p[0]
+                }
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Sibling hashes from leaf `index` up to the root (None if out of range).
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = index;
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(idx ^ 1) {
+            proof.push(*sibling);
+        }
+        level = level
+            .chunks(2)
+            .map(|p| {
+                if p.len() == 2 {
+                    merkle_parent(&p[0], &p[1])
+                } else {
+                    p[0]
+                }
+            })
+            .collect();
+        idx /= 2;
+    }
+    Some(proof)
+}
+
+/// Whether `proof` leads from `leaf` to `root`.
+pub fn merkle_verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_parent(&node, sibling))
+        == *root
+}
+
+/// Longest proof `claim` accepts (2^32 recipients)
+pub const MAX_PROOF_DEPTH: usize = 32;
+
+/// Parse a proof given as concatenated hex hashes (64 characters each).
+/// None on bad hex, a partial hash or more than MAX_PROOF_DEPTH hashes.
+pub fn parse_proof(hex: &str) -> Option<Vec<[u8; 32]>> {
+    let bytes = hex_decode(hex)?;
+    if !bytes.len().is_multiple_of(32) || bytes.len() / 32 > MAX_PROOF_DEPTH {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks(32)
+            .map(|c| {
+                let mut h = [0u8; 32];
+                h.copy_from_slice(c);
+                h
+            })
+            .collect(),
+    )
+}
+
+/// Claim bitmap position of `index`: (state word, bit mask). One state key
+/// holds 128 claims, so 100k recipients need under 800 keys.
+pub fn claim_bit(index: u64) -> (u64, u128) {
+    (index / 128, 1u128 << (index % 128))
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Contract-side helpers (host calls)
+// ─────────────────────────────────────────────────────────────────
+// Response and state helpers of the contracts that answer with
+// `{"success", "message", "data"}`. USP-01 keeps its own `msg` format.
+// ─────────────────────────────────────────────────────────────────
+
+/// Return failure JSON.
+pub(crate) fn fail(msg: &str) -> i32 {
+    set_return_str(&format!(
+        "{{\"success\":false,\"message\":\"{}\"}}",
+        json_escape(msg)
+    ));
+    1
+}
+
+/// Return success JSON.
+pub(crate) fn ok(msg: &str) -> i32 {
+    set_return_str(&format!(
+        "{{\"success\":true,\"message\":\"{}\"}}",
+        json_escape(msg)
+    ));
+    0
+}
+
+/// Return success JSON with data.
+pub(crate) fn ok_data(msg: &str, data: &str) -> i32 {
+    set_return_str(&format!(
+        "{{\"success\":true,\"message\":\"{}\",\"data\":{}}}",
+        json_escape(msg),
+        data
+    ));
+    0
+}
+
+pub(crate) fn get_state_str(key: &str) -> String {
+    state::get_str(key).unwrap_or_default()
+}
+
+pub(crate) fn get_state_u128(key: &str) -> u128 {
+    parse_u128(&get_state_str(key))
+}
+
+pub(crate) fn get_state_u64(key: &str) -> u64 {
+    parse_u64(&get_state_str(key))
+}
+
+pub(crate) fn set_state_u128(key: &str, val: u128) {
+    state::set_str(key, &u128_to_str(val));
+}
+
+pub(crate) fn set_state_u64(key: &str, val: u64) {
+    state::set_str(key, &u128_to_str(val as u128));
+}
+
 // ─────────────────────────────────────────────────────────────────
 // UNIT TESTS — verifies all pure logic used by WASM contracts
 // ─────────────────────────────────────────────────────────────────
@@ -198,7 +461,10 @@ mod tests {
 
     #[test]
     fn test_parse_u128_large() {
-        assert_eq!(parse_u128("340282366920938463463374607431768211455"), u128::MAX);
+        assert_eq!(
+            parse_u128("340282366920938463463374607431768211455"),
+            u128::MAX
+        );
     }
 
     #[test]
@@ -274,7 +540,10 @@ mod tests {
 
     #[test]
     fn test_u128_to_str_max() {
-        assert_eq!(u128_to_str(u128::MAX), "340282366920938463463374607431768211455");
+        assert_eq!(
+            u128_to_str(u128::MAX),
+            "340282366920938463463374607431768211455"
+        );
     }
 
     #[test]
@@ -317,6 +586,11 @@ mod tests {
         assert_eq!(json_escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
     }
 
+    #[test]
+    fn test_json_escape_control_chars() {
+        assert_eq!(json_escape("a\rb\tc"), "a\\rb\\tc");
+    }
+
     #[test]
     fn test_json_escape_empty() {
         assert_eq!(json_escape(""), "");
@@ -388,7 +662,11 @@ mod tests {
         let amount_a: u128 = 500;
         let amount_b: u128 = 500;
         let lp = isqrt(amount_a * amount_b); // sqrt(250000) = 500
-        assert!(lp <= 1000, "LP tokens {} should be <= MINIMUM_LIQUIDITY", lp);
+        assert!(
+            lp <= 1000,
+            "LP tokens {} should be <= MINIMUM_LIQUIDITY",
+            lp
+        );
     }
 
     // ── compute_output ──────────────────────────────────────────
@@ -435,7 +713,7 @@ mod tests {
     fn test_compute_output_large_reserves() {
         // DEX-scale: billions of CIL in reserves
         let out = compute_output(
-            1_000_000_000_000, // 10 LOS input
+            1_000_000_000_000,       // 10 LOS input
             100_000_000_000_000_000, // 1M LOS reserve
             50_000_000_000_000_000,  // 500K LOS reserve
         );
@@ -444,6 +722,40 @@ mod tests {
         assert!(out < 50_000_000_000_000_000);
     }
 
+    #[test]
+    fn test_compute_output_overflowing_product_is_exact() {
+        // amount_in * reserve_out overflows u128 — old code fell back to a
+        // lossy scaled division; mul_div keeps full precision
+        let reserve = 1u128 << 100;
+        let out = compute_output(1 << 60, reserve, reserve);
+        let expected = mul_div(1 << 60, reserve, reserve + (1 << 60)).unwrap();
+        assert_eq!(out, expected);
+        // 2^60 / (1 + 2^-40) ≈ 2^60 - 2^20
+        assert_eq!(out, (1u128 << 60) - (1 << 20));
+    }
+
+    #[test]
+    fn test_compute_output_overflowing_denominator() {
+        let out = compute_output(u128::MAX / 2 + 2, u128::MAX / 2 + 2, 1_000);
+        assert!((499..=500).contains(&out), "out {}", out);
+        assert!(compute_output(u128::MAX, u128::MAX, u128::MAX) < u128::MAX);
+    }
+
+    // ── pro_rata ────────────────────────────────────────────────
+
+    #[test]
+    fn test_pro_rata_basic_and_zero_total() {
+        assert_eq!(pro_rata(100, 1_000, 10_000), 10);
+        assert_eq!(pro_rata(100, 1_000, 0), 0);
+        assert_eq!(pro_rata(0, 1_000, 10_000), 0);
+    }
+
+    #[test]
+    fn test_pro_rata_large_values() {
+        // shares * reserve overflows u128 but the share fits
+        assert_eq!(pro_rata(1 << 100, 1 << 100, 1 << 90), 1 << 110);
+    }
+
     // ── deduct_fee ──────────────────────────────────────────────
 
     #[test]
@@ -487,13 +799,28 @@ mod tests {
         assert_eq!(after, 100);
     }
 
+    #[test]
+    fn test_deduct_fee_large_amount_no_overflow() {
+        let (after, fee) = deduct_fee(u128::MAX, 30);
+        assert_eq!(after + fee, u128::MAX);
+        assert_eq!(fee, mul_div(u128::MAX, 30, 10_000).unwrap());
+    }
+
+    #[test]
+    fn test_deduct_fee_over_100_percent_clamped() {
+        assert_eq!(deduct_fee(500, 20_000), (0, 500));
+    }
+
     // ── make_pool_id ────────────────────────────────────────────
 
     #[test]
     fn test_make_pool_id_sorted() {
         let id1 = make_pool_id("LOS", "TOKEN_A");
         let id2 = make_pool_id("TOKEN_A", "LOS");
-        assert_eq!(id1, id2, "Pool ID must be deterministic regardless of order");
+        assert_eq!(
+            id1, id2,
+            "Pool ID must be deterministic regardless of order"
+        );
     }
 
     #[test]
@@ -538,7 +865,12 @@ mod tests {
         let out = compute_output(after_fee, reserve_in, reserve_out);
 
         // Output should be less than input (slippage + fee)
-        assert!(out < amount_in, "Output {} should be < input {}", out, amount_in);
+        assert!(
+            out < amount_in,
+            "Output {} should be < input {}",
+            out,
+            amount_in
+        );
         assert!(out > 0, "Output should be positive");
 
         // Step 3: verify LP tokens for pool creation
@@ -567,4 +899,127 @@ mod tests {
             assert_eq!(back, val, "Roundtrip failed for {}", val);
         }
     }
+
+    // ── HTLC helpers ────────────────────────────────────────────
+
+    #[test]
+    fn test_htlc_hashlock_sha3_vector() {
+        // SHA3-256("") — NIST test vector
+        assert_eq!(
+            htlc_hashlock(b""),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(htlc_hashlock(b"secret").len(), 64);
+    }
+
+    #[test]
+    fn test_hex_roundtrip_and_rejects() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        assert_eq!(hex_encode(&bytes), "0001abff");
+        assert_eq!(hex_decode("0001ABff").unwrap(), bytes.to_vec());
+        assert!(hex_decode("abc").is_none());
+        assert!(hex_decode("zz").is_none());
+        assert_eq!(hex_decode("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_htlc_id_deterministic_and_field_bound() {
+        let lock = htlc_hashlock(b"secret");
+        let id = htlc_id("LOSWalice", "LOSWbob", &lock, 1_800_000_000, 0);
+        assert_eq!(id.len(), 64);
+        assert_eq!(id, htlc_id("LOSWalice", "LOSWbob", &lock, 1_800_000_000, 0));
+        assert_ne!(id, htlc_id("LOSWalice", "LOSWbob", &lock, 1_800_000_000, 1));
+        assert_ne!(id, htlc_id("LOSWalice", "LOSWbob", &lock, 1_800_000_001, 0));
+        // Length prefixes keep field boundaries unambiguous
+        assert_ne!(
+            htlc_id("ab", "c", &lock, 1, 0),
+            htlc_id("a", "bc", &lock, 1, 0)
+        );
+    }
+
+    #[test]
+    fn test_faucet_wait_secs() {
+        assert_eq!(faucet_wait_secs(0, 3_600, 1_000), 0); // never claimed
+        assert_eq!(faucet_wait_secs(1_000, 3_600, 1_000), 3_600);
+        assert_eq!(faucet_wait_secs(1_000, 3_600, 4_599), 1);
+        assert_eq!(faucet_wait_secs(1_000, 3_600, 4_600), 0);
+        assert_eq!(faucet_wait_secs(1_000, 0, 1_000), 0); // no cooldown
+        assert_eq!(faucet_wait_secs(u64::MAX - 1, u64::MAX, 5), u64::MAX - 5);
+    }
+
+    // ── Merkle distributor ──────────────────────────────────────
+
+    fn airdrop(n: u64) -> Vec<[u8; 32]> {
+        (0..n)
+            .map(|i| merkle_leaf(i, &format!("LOSWuser{}", i), 1_000 + i as u128))
+            .collect()
+    }
+
+    #[test]
+    fn test_merkle_every_proof_verifies() {
+        for n in [1u64, 2, 3, 7, 64, 1_000] {
+            let leaves = airdrop(n);
+            let root = merkle_root(&leaves);
+            for i in 0..n as usize {
+                let proof = merkle_proof(&leaves, i).unwrap();
+                assert!(proof.len() <= MAX_PROOF_DEPTH);
+                assert!(merkle_verify(&root, leaves[i], &proof), "n={} i={}", n, i);
+            }
+        }
+        assert!(merkle_proof(&airdrop(3), 3).is_none());
+    }
+
+    #[test]
+    fn test_merkle_rejects_tampered_claims() {
+        let leaves = airdrop(100);
+        let root = merkle_root(&leaves);
+        let proof = merkle_proof(&leaves, 42).unwrap();
+        assert!(merkle_verify(
+            &root,
+            merkle_leaf(42, "LOSWuser42", 1_042),
+            &proof
+        ));
+        // Wrong amount, account or index
+        assert!(!merkle_verify(
+            &root,
+            merkle_leaf(42, "LOSWuser42", 1_043),
+            &proof
+        ));
+        assert!(!merkle_verify(
+            &root,
+            merkle_leaf(42, "LOSWuser43", 1_042),
+            &proof
+        ));
+        assert!(!merkle_verify(
+            &root,
+            merkle_leaf(43, "LOSWuser42", 1_042),
+            &proof
+        ));
+        // A proof for another index does not carry over
+        assert!(!merkle_verify(&root, leaves[41], &proof));
+    }
+
+    #[test]
+    fn test_parse_proof() {
+        let leaves = airdrop(5);
+        let proof = merkle_proof(&leaves, 4).unwrap();
+        let hex: String = proof.iter().map(|h| hex_encode(h)).collect();
+        assert_eq!(parse_proof(&hex).unwrap(), proof);
+        assert_eq!(parse_proof("").unwrap(), Vec::<[u8; 32]>::new());
+        assert!(parse_proof(&hex[..hex.len() - 2]).is_none());
+        assert!(parse_proof(&"00".repeat(32 * (MAX_PROOF_DEPTH + 1))).is_none());
+        assert!(parse_proof("zz").is_none());
+    }
+
+    #[test]
+    fn test_claim_bitmap() {
+        assert_eq!(claim_bit(0), (0, 1));
+        assert_eq!(claim_bit(127), (0, 1 << 127));
+        assert_eq!(claim_bit(128), (1, 1));
+        assert_eq!(claim_bit(99_999).0, 781);
+        // The word survives the decimal string encoding used in state
+        let (_, mask) = claim_bit(127);
+        let word = mask | claim_bit(3).1;
+        assert_eq!(parse_u128(&u128_to_str(word)), word);
+    }
 }
@@ -0,0 +1,413 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+//! # Merkle Distributor Contract (WASM)
+//!
+//! Deployable `#![no_std]` WASM smart contract for CIL airdrops to large
+//! recipient lists. Instead of storing every (address, amount) pair, the
+//! owner commits a single blake3 merkle root; each recipient claims with a
+//! proof that the contract checks on-chain with `host_blake3`.
+//!
+//! ## Flow
+//! 1. Off-chain, the operator numbers the recipients 0..n and builds the tree
+//!    (`los_contracts::merkle_leaf`, `merkle_root`, `merkle_proof`).
+//! 2. The operator calls `init(merkle_root, reclaim_after)` with the total
+//!    airdrop attached (`amount_cil`); they become owner. More CIL can be
+//!    added with `fund`.
+//! 3. Anyone calls `claim(index, account, amount, proof)` for an entry; the
+//!    CIL goes to `account`. Each index pays out once.
+//! 4. After `reclaim_after` (UNIX seconds, `0` = never) the owner may take
+//!    back whatever was not claimed with `reclaim`.
+//!
+//! ## Hashing
+//! - leaf = blake3(0x00 ‖ index u64 LE ‖ len(account) u32 LE ‖ account ‖ amount u128 LE)
+//! - node = blake3(0x01 ‖ min(a, b) ‖ max(a, b)); an odd node is carried up
+//! - `proof` = sibling hashes from leaf to root, concatenated hex (64 chars each)
+//!
+//! ## State Layout
+//! Claims are tracked in a bitmap, 128 indexes per key, so 100k recipients
+//! take under 800 state entries.
+//! - `md:owner`         → Owner (init caller)
+//! - `md:root`          → Merkle root (64 hex chars)
+//! - `md:reclaim_after` → UNIX seconds after which `reclaim` works (0 = never)
+//! - `md:claims`        → Claims paid (decimal string)
+//! - `md:claimed_total` → CIL paid out (decimal string)
+//! - `claimed:{word}`   → Bitmap word `index / 128` (decimal u128)
+//!
+//! ## Exported Functions
+//! | Function     | Args                             | Who   |
+//! |--------------|----------------------------------|-------|
+//! | `init`       | merkle_root, reclaim_after       | once  |
+//! | `fund`       | — (CIL attached)                 | any   |
+//! | `claim`      | index, account, amount, proof    | any   |
+//! | `is_claimed` | index                            | any   |
+//! | `reclaim`    | —                                | owner |
+//! | `get_info`   | —                                | any   |
+//!
+//! ## Compilation
+//! ```bash
+//! cargo build --target wasm32-unknown-unknown --release \
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin merkle_distributor
+//! ```
+
+use crate::{
+    claim_bit, fail, get_state_str, get_state_u128, get_state_u64, hex_decode, hex_encode,
+    json_escape, ok_data, parse_proof, parse_u128, parse_u64, set_state_u128, u128_to_str,
+};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use los_sdk::*;
+
+// ─────────────────────────────────────────────────────────────
+// HELPERS
+// ─────────────────────────────────────────────────────────────
+
+/// 32-byte hash from 64 hex characters
+fn parse_hash(s: &str) -> Option<[u8; 32]> {
+    let bytes = hex_decode(s)?;
+    let mut h = [0u8; 32];
+    if bytes.len() != 32 {
+        return None;
+    }
+    h.copy_from_slice(&bytes);
+    Some(h)
+}
+
+fn merkle_leaf(index: u64, account: &str, amount: u128) -> [u8; 32] {
+    let mut data = Vec::with_capacity(1 + 8 + 4 + account.len() + 16);
+    data.push(0x00);
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&(account.len() as u32).to_le_bytes());
+    data.extend_from_slice(account.as_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    crypto::blake3(&data)
+}
+
+fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = [0u8; 65];
+    data[0] = 0x01;
+    data[1..33].copy_from_slice(lo);
+    data[33..].copy_from_slice(hi);
+    crypto::blake3(&data)
+}
+
+// ─────────────────────────────────────────────────────────────
+// STATE HELPERS
+// ─────────────────────────────────────────────────────────────
+
+fn word_key(word: u64) -> String {
+    format!("claimed:{}", word)
+}
+
+fn is_claimed_index(index: u64) -> bool {
+    let (word, bit) = claim_bit(index);
+    get_state_u128(&word_key(word)) & bit != 0
+}
+
+fn set_claimed(index: u64) {
+    let (word, bit) = claim_bit(index);
+    let key = word_key(word);
+    set_state_u128(&key, get_state_u128(&key) | bit);
+}
+
+/// Decimal u64 argument (rejects empty and non-digit input)
+fn u64_arg(idx: u32, name: &str) -> Result<u64, i32> {
+    match arg(idx) {
+        Some(v) if !v.is_empty() && v.len() <= 20 && v.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok(parse_u64(&v))
+        }
+        _ => Err(fail(&format!("{} must be a non-negative integer", name))),
+    }
+}
+
+fn info_json() -> String {
+    format!(
+        "{{\"owner\":\"{}\",\"merkle_root\":\"{}\",\"reclaim_after\":\"{}\",\"balance\":\"{}\",\"claims\":\"{}\",\"claimed_total\":\"{}\"}}",
+        json_escape(&get_state_str("md:owner")),
+        get_state_str("md:root"),
+        get_state_u64("md:reclaim_after"),
+        u128_to_str(balance()),
+        get_state_u64("md:claims"),
+        u128_to_str(get_state_u128("md:claimed_total")),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────
+// ENTRY POINTS
+// ─────────────────────────────────────────────────────────────
+
+/// Commit the merkle root and set the owner (once). CIL attached funds the
+/// airdrop.
+/// Args: merkle_root (64 hex chars), reclaim_after (UNIX seconds, 0 = never)
+pub extern "C" fn init() -> i32 {
+    if !get_state_str("md:owner").is_empty() {
+        return fail("Already initialized");
+    }
+    let owner = caller();
+    if owner.is_empty() {
+        return fail("Caller unknown");
+    }
+    let root = match arg(0).as_deref().and_then(parse_hash) {
+        Some(r) => r,
+        None => return fail("merkle_root must be 64 hex characters"),
+    };
+    let reclaim_after = match u64_arg(1, "reclaim_after") {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    if reclaim_after != 0 && reclaim_after <= timestamp() {
+        return fail("reclaim_after must be in the future (or 0 for never)");
+    }
+    let root_hex = hex_encode(&root);
+    state::set_str("md:owner", &owner);
+    state::set_str("md:root", &root_hex);
+    state::set_str("md:reclaim_after", &format!("{}", reclaim_after));
+    event::emit(
+        "AirdropInit",
+        &format!(
+            "{{\"owner\":\"{}\",\"merkle_root\":\"{}\",\"reclaim_after\":\"{}\",\"funded\":\"{}\"}}",
+            json_escape(&owner),
+            root_hex,
+            reclaim_after,
+            u128_to_str(msg_value())
+        ),
+    );
+    ok_data("Airdrop initialized", &info_json())
+}
+
+/// Add the CIL attached to this call.
+pub extern "C" fn fund() -> i32 {
+    if get_state_str("md:root").is_empty() {
+        return fail("Airdrop not initialized");
+    }
+    let amount = msg_value();
+    if amount == 0 {
+        return fail("No CIL attached (send amount_cil with the call)");
+    }
+    event::emit(
+        "AirdropFunded",
+        &format!(
+            "{{\"by\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&caller()),
+            u128_to_str(amount)
+        ),
+    );
+    ok_data(&format!("Added {} CIL", u128_to_str(amount)), &info_json())
+}
+
+/// Pay entry `index` to `account` after checking its proof. Anyone may
+/// submit a claim; the CIL always goes to `account`.
+/// Args: index, account, amount, proof (concatenated hex sibling hashes)
+pub extern "C" fn claim() -> i32 {
+    let root = match parse_hash(&get_state_str("md:root")) {
+        Some(r) => r,
+        None => return fail("Airdrop not initialized"),
+    };
+    let index = match u64_arg(0, "index") {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    let account = match arg(1) {
+        Some(v) if v.starts_with("LOS") => v,
+        _ => return fail("account must be a LOS address"),
+    };
+    let amount = arg(2).map(|v| parse_u128(&v)).unwrap_or(0);
+    if amount == 0 {
+        return fail("amount must be a positive integer");
+    }
+    let proof = match parse_proof(&arg(3).unwrap_or_default()) {
+        Some(p) => p,
+        None => return fail("Malformed proof"),
+    };
+    if is_claimed_index(index) {
+        return fail("Already claimed");
+    }
+    let computed = proof
+        .iter()
+        .fold(merkle_leaf(index, &account, amount), |node, sibling| {
+            merkle_parent(&node, sibling)
+        });
+    if computed != root {
+        return fail("Invalid proof");
+    }
+    if balance() < amount {
+        return fail("Airdrop is underfunded; ask the owner to fund it");
+    }
+
+    // Mark before paying: a failing transfer aborts the whole call anyway
+    set_claimed(index);
+    if let Err(e) = transfer(&account, amount) {
+        return fail(e);
+    }
+    state::set_str(
+        "md:claims",
+        &format!("{}", get_state_u64("md:claims").saturating_add(1)),
+    );
+    set_state_u128(
+        "md:claimed_total",
+        get_state_u128("md:claimed_total").saturating_add(amount),
+    );
+    let _ = event::emit_indexed(
+        "Claimed",
+        &[&account],
+        &format!(
+            "{{\"index\":\"{}\",\"amount\":\"{}\"}}",
+            index,
+            u128_to_str(amount)
+        ),
+    );
+    ok_data(
+        &format!("Sent {} CIL", u128_to_str(amount)),
+        &format!(
+            "{{\"index\":\"{}\",\"account\":\"{}\",\"amount\":\"{}\"}}",
+            index,
+            json_escape(&account),
+            u128_to_str(amount)
+        ),
+    )
+}
+
+/// Whether entry `index` was claimed (read-only).
+/// Args: index
+pub extern "C" fn is_claimed() -> i32 {
+    let index = match u64_arg(0, "index") {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    ok_data(
+        "Claim status",
+        &format!(
+            "{{\"index\":\"{}\",\"claimed\":{}}}",
+            index,
+            is_claimed_index(index)
+        ),
+    )
+}
+
+/// Send the unclaimed balance to the owner once `reclaim_after` has passed
+/// (owner only).
+pub extern "C" fn reclaim() -> i32 {
+    let owner = get_state_str("md:owner");
+    if owner.is_empty() {
+        return fail("Airdrop not initialized");
+    }
+    if caller() != owner {
+        return fail("Only the owner may do this");
+    }
+    let reclaim_after = get_state_u64("md:reclaim_after");
+    if reclaim_after == 0 {
+        return fail("This airdrop cannot be reclaimed");
+    }
+    if timestamp() < reclaim_after {
+        return fail(&format!(
+            "Claim window open: reclaim possible after {}",
+            reclaim_after
+        ));
+    }
+    let amount = balance();
+    if amount == 0 {
+        return fail("Nothing left to reclaim");
+    }
+    if let Err(e) = transfer(&owner, amount) {
+        return fail(e);
+    }
+    event::emit(
+        "AirdropReclaimed",
+        &format!(
+            "{{\"owner\":\"{}\",\"amount\":\"{}\"}}",
+            json_escape(&owner),
+            u128_to_str(amount)
+        ),
+    );
+    ok_data(
+        &format!("Reclaimed {} CIL", u128_to_str(amount)),
+        &info_json(),
+    )
+}
+
+/// Owner, root, balance and totals (read-only).
+pub extern "C" fn get_info() -> i32 {
+    ok_data("Merkle distributor", &info_json())
+}
+
+// ─────────────────────────────────────────────────────────────
+// TESTS — the deployed entry points, run natively on MockHost
+// ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_testing::MockHost;
+
+    const OWNER: &str = "LOSowner";
+    const RECIPIENTS: [(&str, u128); 3] = [("LOSalice", 100), ("LOSbob", 250), ("LOScarol", 50)];
+
+    /// Airdrop of RECIPIENTS built with the off-chain helpers, funded with
+    /// exactly their total and reclaimable after a day
+    fn airdrop() -> (MockHost, Vec<[u8; 32]>) {
+        let leaves: Vec<[u8; 32]> = RECIPIENTS
+            .iter()
+            .enumerate()
+            .map(|(i, (account, amount))| crate::merkle_leaf(i as u64, account, *amount))
+            .collect();
+        let mut host = MockHost::new("LOSConAirdrop").with_caller(OWNER);
+        let root = hex_encode(&crate::merkle_root(&leaves));
+        let reclaim_after = (host.timestamp() + 86_400).to_string();
+        let res = host.call_with_value(400, &[&root, &reclaim_after], init);
+        assert!(res.is_success(), "{}", res.return_str());
+        (host, leaves)
+    }
+
+    fn claim_args(leaves: &[[u8; 32]], index: usize, amount: u128) -> Vec<String> {
+        let proof = crate::merkle_proof(leaves, index).unwrap();
+        let proof_hex: String = proof.iter().map(|h| hex_encode(h)).collect();
+        let account = RECIPIENTS[index].0;
+        vec![
+            index.to_string(),
+            account.into(),
+            amount.to_string(),
+            proof_hex,
+        ]
+    }
+
+    fn call_claim(host: &mut MockHost, args: &[String]) -> los_testing::CallResult {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        host.call(&args, claim)
+    }
+
+    #[test]
+    fn test_off_chain_tree_verifies_on_chain() {
+        let (mut host, leaves) = airdrop();
+        // Host blake3 (contract) and the blake3 crate (tree builder) agree
+        let res = call_claim(&mut host, &claim_args(&leaves, 1, 250));
+        assert!(res.is_success(), "{}", res.return_str());
+        assert_eq!(res.transfers, vec![("LOSbob".to_string(), 250)]);
+
+        let again = call_claim(&mut host, &claim_args(&leaves, 1, 250));
+        assert!(again.return_str().contains("Already claimed"));
+        let inflated = call_claim(&mut host, &claim_args(&leaves, 0, 101));
+        assert!(inflated.return_str().contains("Invalid proof"));
+        assert!(call_claim(&mut host, &claim_args(&leaves, 0, 100)).is_success());
+        assert!(host
+            .call(&["2"], is_claimed)
+            .return_str()
+            .contains("\"claimed\":false"));
+        assert!(host
+            .call(&["0"], is_claimed)
+            .return_str()
+            .contains("\"claimed\":true"));
+    }
+
+    #[test]
+    fn test_reclaim_after_window() {
+        let (mut host, leaves) = airdrop();
+        assert!(call_claim(&mut host, &claim_args(&leaves, 2, 50)).is_success());
+        assert!(!host.call(&[], reclaim).is_success());
+
+        host.advance_time(86_400);
+        assert!(!host.call_as("LOSalice", &[], reclaim).is_success());
+        let res = host.call(&[], reclaim);
+        assert_eq!(res.transfers, vec![(OWNER.to_string(), 350)]);
+        assert_eq!(host.balance(), 0);
+    }
+}
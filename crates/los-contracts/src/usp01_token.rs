@@ -25,6 +25,8 @@
 //! - `usp01:owner`               → Token creator address
 //! - `bal:{address}`             → Balance (u128 LE bytes)
 //! - `allow:{owner}:{spender}`   → Allowance (u128 LE bytes)
+//! - `htlc:count`                → Token HTLCs created (id nonce)
+//! - `htlc:{id}:{field}`         → Token HTLC (sender, receiver, amount, hashlock, timelock, status, preimage)
 //!
 //! ## Exported Functions
 //! | Function         | Args                                              |
@@ -42,19 +44,29 @@
 //! | `token_info`     | (none)                                             |
 //! | `wrap_mint`      | to, amount, proof                                  |
 //! | `wrap_burn`      | amount, destination                                |
+//! | `htlc_lock`      | receiver, amount, hashlock, timelock               |
+//! | `htlc_claim`     | id, preimage (hex)                                 |
+//! | `htlc_refund`    | id                                                 |
+//! | `htlc_get`       | id                                                 |
+//! | `contract_info`  | (none)                                             |
+//!
+//! ## Hash Time-Locked Transfers
+//! `htlc_lock` moves tokens from the caller into escrow inside this contract
+//! against a SHA3-256 hashlock. `htlc_claim` with the preimage before the
+//! timelock releases them to the receiver. After expiry, `htlc_refund`
+//! returns them to the sender. Escrowed tokens stay in `total_supply`. The
+//! native-CIL counterpart is the `htlc` contract (same id scheme and events).
 //!
 //! ## Compilation
 //! ```bash
 //! cargo build --target wasm32-unknown-unknown --release \
-//!     -p los-contract-examples --bin usp01_token --features sdk
+//!     --manifest-path crates/los-contracts/Cargo.toml --bin usp01_token
 //! ```
 
-#![no_std]
-#![no_main]
-
-extern crate alloc;
-extern crate los_sdk;
-
+use crate::{
+    allow_key, bal_key, hex_decode, hex_encode, htlc_hashlock, htlc_id, json_escape, parse_u128,
+    set_state_u64, u128_to_str, HTLC_MAX_PREIMAGE_BYTES,
+};
 use alloc::format;
 use alloc::string::String;
 use los_sdk::*;
@@ -63,52 +75,6 @@ use los_sdk::*;
 // HELPERS
 // ─────────────────────────────────────────────────────────────
 
-/// Parse a u128 from a decimal string. Returns 0 on failure.
-fn parse_u128(s: &str) -> u128 {
-    let mut result: u128 = 0;
-    for b in s.as_bytes() {
-        if *b < b'0' || *b > b'9' {
-            return 0;
-        }
-        let digit = (*b - b'0') as u128;
-        result = match result.checked_mul(10) {
-            Some(v) => v,
-            None => return 0,
-        };
-        result = match result.checked_add(digit) {
-            Some(v) => v,
-            None => return 0,
-        };
-    }
-    result
-}
-
-/// Return a u128 as decimal string.
-fn u128_to_str(val: u128) -> String {
-    if val == 0 {
-        return String::from("0");
-    }
-    let mut digits = alloc::vec::Vec::new();
-    let mut v = val;
-    while v > 0 {
-        digits.push(b'0' + (v % 10) as u8);
-        v /= 10;
-    }
-    digits.reverse();
-    // All bytes are proven ASCII digits — infallible conversion.
-    String::from_utf8(digits).unwrap_or_default()
-}
-
-/// Build balance state key.
-fn bal_key(addr: &str) -> String {
-    format!("bal:{}", addr)
-}
-
-/// Build allowance state key.
-fn allow_key(owner: &str, spender: &str) -> String {
-    format!("allow:{}:{}", owner, spender)
-}
-
 /// Get balance for an address (stored as decimal string).
 fn get_balance(addr: &str) -> u128 {
     parse_u128(&state::get_str(&bal_key(addr)).unwrap_or_default())
@@ -141,7 +107,7 @@ fn set_total_supply(val: u128) {
 
 /// Check if contract is initialized.
 fn is_initialized() -> bool {
-    state::get_str("usp01:init").map_or(false, |v| v == "1")
+    state::get_str("usp01:init").is_some_and(|v| v == "1")
 }
 
 /// Fail with JSON error response.
@@ -150,34 +116,12 @@ fn fail(msg: &str) -> i32 {
     1
 }
 
-/// Succeed with JSON success response.
-fn ok(msg: &str) -> i32 {
-    set_return_str(&format!(r#"{{"success":true,"msg":"{}"}}"#, msg));
-    0
-}
-
 /// Succeed with JSON data response.
 fn ok_data(data: &str) -> i32 {
     set_return_str(&format!(r#"{{"success":true,"data":{}}}"#, data));
     0
 }
 
-/// Escape a string for JSON (minimal — handles quotes and backslashes).
-fn json_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '"' => out.push_str("\\\""),
-            '\\' => out.push_str("\\\\"),
-            '\n' => out.push_str("\\n"),
-            '\r' => out.push_str("\\r"),
-            '\t' => out.push_str("\\t"),
-            _ => out.push(c),
-        }
-    }
-    out
-}
-
 // ─────────────────────────────────────────────────────────────
 // INIT — Called once at deployment
 // ─────────────────────────────────────────────────────────────
@@ -193,7 +137,6 @@ fn json_escape(s: &str) -> String {
 ///   5: wrapped_origin (string, optional)
 ///   6: max_supply (u128 decimal string, optional, default "0")
 ///   7: bridge_operator (address, optional)
-#[no_mangle]
 pub extern "C" fn init() -> i32 {
     if is_initialized() {
         return fail("Already initialized");
@@ -301,7 +244,6 @@ pub extern "C" fn init() -> i32 {
 /// Args:
 ///   0: to (recipient address)
 ///   1: amount (u128 decimal string)
-#[no_mangle]
 pub extern "C" fn transfer() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -374,7 +316,6 @@ pub extern "C" fn transfer() -> i32 {
 /// Args:
 ///   0: spender (address)
 ///   1: amount (u128 decimal string, 0 to revoke)
-#[no_mangle]
 pub extern "C" fn approve() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -427,7 +368,6 @@ pub extern "C" fn approve() -> i32 {
 ///   0: from (owner address)
 ///   1: to (recipient address)
 ///   2: amount (u128 decimal string)
-#[no_mangle]
 pub extern "C" fn transfer_from() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -483,10 +423,7 @@ pub extern "C" fn transfer_from() -> i32 {
     set_balance(&to, new_to);
 
     // Reduce allowance
-    let new_allowance = match allowance.checked_sub(amount) {
-        Some(v) => v,
-        None => 0,
-    };
+    let new_allowance = allowance.saturating_sub(amount);
     set_allowance(&from, &spender, new_allowance);
 
     // Emit transfer event
@@ -519,7 +456,6 @@ pub extern "C" fn transfer_from() -> i32 {
 ///
 /// Args:
 ///   0: amount (u128 decimal string)
-#[no_mangle]
 pub extern "C" fn burn() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -580,7 +516,6 @@ pub extern "C" fn burn() -> i32 {
 ///
 /// Args:
 ///   0: account (address)
-#[no_mangle]
 pub extern "C" fn balance_of() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -608,7 +543,6 @@ pub extern "C" fn balance_of() -> i32 {
 /// Args:
 ///   0: owner (address)
 ///   1: spender (address)
-#[no_mangle]
 pub extern "C" fn allowance_of() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -637,7 +571,6 @@ pub extern "C" fn allowance_of() -> i32 {
 // ─────────────────────────────────────────────────────────────
 
 /// Return current total supply.
-#[no_mangle]
 pub extern "C" fn total_supply() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -652,7 +585,6 @@ pub extern "C" fn total_supply() -> i32 {
 // ─────────────────────────────────────────────────────────────
 
 /// Return complete token metadata.
-#[no_mangle]
 pub extern "C" fn token_info() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -695,7 +627,6 @@ pub extern "C" fn token_info() -> i32 {
 ///   0: to (recipient address)
 ///   1: amount (u128 decimal string)
 ///   2: proof (deposit proof from source chain)
-#[no_mangle]
 pub extern "C" fn wrap_mint() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -781,7 +712,6 @@ pub extern "C" fn wrap_mint() -> i32 {
 /// Args:
 ///   0: amount (u128 decimal string)
 ///   1: destination (address on the source chain)
-#[no_mangle]
 pub extern "C" fn wrap_burn() -> i32 {
     if !is_initialized() {
         return fail("Contract not initialized");
@@ -844,3 +774,283 @@ pub extern "C" fn wrap_burn() -> i32 {
     ));
     0
 }
+
+// ─────────────────────────────────────────────────────────────
+// HTLC — Hash time-locked token transfers (atomic swaps)
+// ─────────────────────────────────────────────────────────────
+
+fn htlc_key(id: &str, field: &str) -> String {
+    format!("htlc:{}:{}", id, field)
+}
+
+fn htlc_get_str(id: &str, field: &str) -> String {
+    state::get_str(&htlc_key(id, field)).unwrap_or_default()
+}
+
+fn htlc_get_u64(id: &str, field: &str) -> u64 {
+    htlc_get_str(id, field).parse().unwrap_or(0)
+}
+
+/// Credit escrowed tokens to `to` and close the HTLC with `status`.
+fn htlc_release(id: &str, to: &str, status: &str) -> Result<u128, &'static str> {
+    let amount = parse_u128(&htlc_get_str(id, "amount"));
+    let new_bal = get_balance(to)
+        .checked_add(amount)
+        .ok_or("arithmetic overflow")?;
+    set_balance(to, new_bal);
+    state::set_str(&htlc_key(id, "status"), status);
+    Ok(amount)
+}
+
+/// Escrow tokens against a SHA3-256 hashlock.
+///
+/// Args:
+///   0: receiver (address)
+///   1: amount (u128 decimal string)
+///   2: hashlock (64 hex chars, SHA3-256 of the preimage)
+///   3: timelock (UNIX seconds, must be in the future)
+pub extern "C" fn htlc_lock() -> i32 {
+    if !is_initialized() {
+        return fail("Contract not initialized");
+    }
+    let receiver = match arg(0) {
+        Some(r) if !r.is_empty() => r,
+        _ => return fail("receiver address required"),
+    };
+    let amount = parse_u128(&arg(1).unwrap_or_default());
+    if amount == 0 {
+        return fail("amount must be > 0");
+    }
+    let hashlock = match arg(2) {
+        Some(h) if h.len() == 64 && hex_decode(&h).is_some() => h.to_ascii_lowercase(),
+        _ => return fail("hashlock must be 64 hex chars (SHA3-256)"),
+    };
+    let timelock: u64 = arg(3).and_then(|t| t.parse().ok()).unwrap_or(0);
+    if timelock <= timestamp() {
+        return fail("timelock must be in the future");
+    }
+
+    let from = caller();
+    if from.is_empty() {
+        return fail("caller address not available");
+    }
+    let bal = get_balance(&from);
+    if bal < amount {
+        return fail("insufficient balance");
+    }
+
+    let nonce: u64 = state::get_str("htlc:count")
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+    let id = htlc_id(&from, &receiver, &hashlock, timelock, nonce);
+    set_balance(&from, bal - amount);
+    state::set_str(&htlc_key(&id, "sender"), &from);
+    state::set_str(&htlc_key(&id, "receiver"), &receiver);
+    state::set_str(&htlc_key(&id, "amount"), &u128_to_str(amount));
+    state::set_str(&htlc_key(&id, "hashlock"), &hashlock);
+    set_state_u64(&htlc_key(&id, "timelock"), timelock);
+    state::set_str(&htlc_key(&id, "status"), "open");
+    set_state_u64("htlc:count", nonce + 1);
+
+    event::emit(
+        "USP01:HtlcLocked",
+        &format!(
+            r#"{{"id":"{}","sender":"{}","receiver":"{}","amount":"{}","hashlock":"{}","timelock":"{}"}}"#,
+            id,
+            json_escape(&from),
+            json_escape(&receiver),
+            u128_to_str(amount),
+            hashlock,
+            timelock
+        ),
+    );
+    set_return_str(&format!(
+        r#"{{"success":true,"id":"{}","amount":"{}"}}"#,
+        id,
+        u128_to_str(amount)
+    ));
+    0
+}
+
+/// Release escrowed tokens to the receiver with the preimage (before expiry).
+/// Anyone may submit the preimage.
+///
+/// Args:
+///   0: id
+///   1: preimage (hex)
+pub extern "C" fn htlc_claim() -> i32 {
+    let id = arg(0).unwrap_or_default();
+    let preimage = match arg(1).as_deref().and_then(hex_decode) {
+        Some(p) if !p.is_empty() && p.len() <= HTLC_MAX_PREIMAGE_BYTES => p,
+        _ => return fail("preimage must be 1-256 bytes of hex"),
+    };
+    if htlc_get_str(&id, "status") != "open" {
+        return fail("HTLC not found or not open");
+    }
+    if timestamp() >= htlc_get_u64(&id, "timelock") {
+        return fail("HTLC expired; use htlc_refund");
+    }
+    if htlc_hashlock(&preimage) != htlc_get_str(&id, "hashlock") {
+        return fail("preimage does not match hashlock");
+    }
+
+    let receiver = htlc_get_str(&id, "receiver");
+    let amount = match htlc_release(&id, &receiver, "claimed") {
+        Ok(a) => a,
+        Err(e) => return fail(e),
+    };
+    let preimage_hex = hex_encode(&preimage);
+    state::set_str(&htlc_key(&id, "preimage"), &preimage_hex);
+
+    event::emit(
+        "USP01:HtlcClaimed",
+        &format!(
+            r#"{{"id":"{}","receiver":"{}","amount":"{}","preimage":"{}"}}"#,
+            json_escape(&id),
+            json_escape(&receiver),
+            u128_to_str(amount),
+            preimage_hex
+        ),
+    );
+    set_return_str(&format!(
+        r#"{{"success":true,"id":"{}","amount":"{}"}}"#,
+        json_escape(&id),
+        u128_to_str(amount)
+    ));
+    0
+}
+
+/// Return escrowed tokens to the sender after the timelock. Anyone may call.
+///
+/// Args:
+///   0: id
+pub extern "C" fn htlc_refund() -> i32 {
+    let id = arg(0).unwrap_or_default();
+    if htlc_get_str(&id, "status") != "open" {
+        return fail("HTLC not found or not open");
+    }
+    if timestamp() < htlc_get_u64(&id, "timelock") {
+        return fail("HTLC still locked");
+    }
+
+    let sender = htlc_get_str(&id, "sender");
+    let amount = match htlc_release(&id, &sender, "refunded") {
+        Ok(a) => a,
+        Err(e) => return fail(e),
+    };
+
+    event::emit(
+        "USP01:HtlcRefunded",
+        &format!(
+            r#"{{"id":"{}","sender":"{}","amount":"{}"}}"#,
+            json_escape(&id),
+            json_escape(&sender),
+            u128_to_str(amount)
+        ),
+    );
+    set_return_str(&format!(
+        r#"{{"success":true,"id":"{}","amount":"{}"}}"#,
+        json_escape(&id),
+        u128_to_str(amount)
+    ));
+    0
+}
+
+/// Read a token HTLC (read-only).
+///
+/// Args:
+///   0: id
+pub extern "C" fn htlc_get() -> i32 {
+    let id = arg(0).unwrap_or_default();
+    let status = htlc_get_str(&id, "status");
+    if status.is_empty() {
+        return fail("HTLC not found");
+    }
+    ok_data(&format!(
+        r#"{{"id":"{}","sender":"{}","receiver":"{}","amount":"{}","hashlock":"{}","timelock":"{}","status":"{}","preimage":"{}"}}"#,
+        json_escape(&id),
+        json_escape(&htlc_get_str(&id, "sender")),
+        json_escape(&htlc_get_str(&id, "receiver")),
+        htlc_get_str(&id, "amount"),
+        htlc_get_str(&id, "hashlock"),
+        htlc_get_u64(&id, "timelock"),
+        status,
+        htlc_get_str(&id, "preimage")
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────
+// TESTS — the deployed entry points, run natively on MockHost
+// ─────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_testing::MockHost;
+
+    const ALICE: &str = "LOSalice";
+    const BOB: &str = "LOSbob";
+    const CAROL: &str = "LOScarol";
+
+    /// Token with 1M units, all owned by Alice
+    fn token() -> MockHost {
+        let mut host = MockHost::new("LOSConToken").with_caller(ALICE);
+        let res = host.call(&["Test Token", "TST", "6", "1000000"], init);
+        assert!(res.is_success(), "{}", res.return_str());
+        host
+    }
+
+    fn balance(host: &MockHost, addr: &str) -> u128 {
+        parse_u128(&host.state_str(&bal_key(addr)).unwrap_or_default())
+    }
+
+    #[test]
+    fn test_transfer_and_allowance() {
+        let mut host = token();
+        assert!(host.call(&[BOB, "250000"], transfer).is_success());
+        assert!(!host.call_as(BOB, &[CAROL, "250001"], transfer).is_success());
+        assert!(host.call(&[CAROL, "1000"], approve).is_success());
+
+        assert!(!host
+            .call_as(CAROL, &[ALICE, CAROL, "1001"], transfer_from)
+            .is_success());
+        let res = host.call_as(CAROL, &[ALICE, CAROL, "600"], transfer_from);
+        assert!(res.is_success(), "{}", res.return_str());
+        assert_eq!(
+            host.state_str(&allow_key(ALICE, CAROL)).as_deref(),
+            Some("400")
+        );
+        assert_eq!(balance(&host, ALICE), 749_400);
+        assert_eq!(balance(&host, BOB), 250_000);
+        assert_eq!(balance(&host, CAROL), 600);
+        let res = host.call(&[BOB], balance_of);
+        assert!(res.return_str().contains("\"balance\":\"250000\""));
+    }
+
+    #[test]
+    fn test_htlc_claim_and_refund() {
+        let mut host = token();
+        let hashlock = htlc_hashlock(b"secret");
+        let timelock = (host.timestamp() + 600).to_string();
+        for _ in 0..2 {
+            let res = host.call(&[BOB, "1000", &hashlock, &timelock], htlc_lock);
+            assert!(res.is_success(), "{}", res.return_str());
+        }
+        let timelock = host.timestamp() + 600;
+        let first = htlc_id(ALICE, BOB, &hashlock, timelock, 0);
+        let second = htlc_id(ALICE, BOB, &hashlock, timelock, 1);
+        assert_eq!(balance(&host, ALICE), 998_000);
+
+        let res = host.call(&[&first, &hex_encode(b"secret")], htlc_claim);
+        assert!(res.is_success(), "{}", res.return_str());
+        assert_eq!(balance(&host, BOB), 1_000);
+
+        assert!(!host.call(&[&second], htlc_refund).is_success());
+        host.advance_time(600);
+        assert!(!host
+            .call(&[&second, &hex_encode(b"secret")], htlc_claim)
+            .is_success());
+        assert!(host.call(&[&second], htlc_refund).is_success());
+        assert_eq!(balance(&host, ALICE), 999_000);
+    }
+}
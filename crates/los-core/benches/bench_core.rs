@@ -26,6 +26,8 @@ fn bench_signing_hash(c: &mut Criterion) {
         work: 12345,
         timestamp: 1_700_000_000,
         fee: 100_000,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     c.bench_function("block/signing_hash", |b| {
@@ -45,6 +47,8 @@ fn bench_calculate_hash(c: &mut Criterion) {
         work: 12345,
         timestamp: 1_700_000_000,
         fee: 100_000,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     c.bench_function("block/calculate_hash", |b| {
@@ -128,7 +132,7 @@ fn bench_difficulty_adjustment(c: &mut Criterion) {
             for i in 0..50 {
                 state.current_epoch_miners.insert(format!("LOSminer{}", i));
             }
-            black_box(state.advance_epoch(1));
+            state.advance_epoch(black_box(1));
         })
     });
 }
@@ -0,0 +1,353 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — RUNTIME CHAIN SPECIFICATION
+//
+// One binary runs mainnet, testnet or a custom test network. The network's
+// parameters (chain_id, epoch lengths, reward schedule, mint caps, signature
+// policy) live in a ChainSpec. The node installs it once at startup, before it
+// reads any of them.
+//
+// Integrity rules (enforced by ChainSpec::resolve and install):
+//   1. The mainnet spec is EMBEDDED. A genesis file may restate it, but any
+//      difference is rejected, so a typo cannot enable Ed25519 or VM test
+//      stubs, or shorten epochs on mainnet.
+//   2. chain_id 1 and the name "mainnet" belong to the embedded spec only.
+//   3. Binaries built with `--features mainnet` refuse every other spec.
+//   4. The spec can be installed once. If anything reads the spec before
+//      install, the build default is locked in and a different install fails.
+//
+// Nodes that never call install get the build default: mainnet with
+// `--features mainnet`, otherwise testnet. That is the behaviour before
+// runtime specs.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::CIL_PER_LOS;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+pub const MAINNET_CHAIN_ID: u64 = 1;
+pub const TESTNET_CHAIN_ID: u64 = 2;
+
+/// Which signatures a network accepts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SignaturePolicy {
+    /// Accept Ed25519 (32-byte key / 64-byte signature) in addition to Dilithium5
+    pub allow_ed25519: bool,
+    /// Reject node-signed sends/calls: every block must be signed by its owner
+    pub require_client_signatures: bool,
+}
+
+/// Validator reward payout model
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RewardPayout {
+    /// The epoch leader mints one REWARD Mint block per validator
+    #[default]
+    Push,
+    /// Rewards accrue in the reward pool; validators mint them with Claim blocks
+    Claim,
+}
+
+/// Network parameters selected at runtime
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainSpec {
+    pub network: String,
+    /// Included in every block signing hash and mining proof (replay protection)
+    pub chain_id: u64,
+    /// Validator reward epoch
+    pub reward_epoch_secs: u64,
+    /// Full epochs a new validator waits before it earns rewards
+    #[serde(default = "default_reward_probation_epochs")]
+    pub reward_probation_epochs: u64,
+    /// Epochs between halvings of the validator reward rate
+    #[serde(default = "default_reward_halving_interval_epochs")]
+    pub reward_halving_interval_epochs: u64,
+    /// Validator reward budget per epoch before halvings
+    #[serde(default = "default_reward_rate_initial_cil")]
+    pub reward_rate_initial_cil: u128,
+    /// PoW mining epoch
+    pub mining_epoch_secs: u64,
+    pub mining_halving_interval_epochs: u64,
+    pub initial_mining_difficulty_bits: u32,
+    /// Mining emission per epoch before halvings
+    pub mining_reward_per_epoch_cil: u128,
+    /// Largest single non-system Mint block
+    pub max_mint_per_block_cil: u128,
+    pub signature_policy: SignaturePolicy,
+    /// VM mock dispatch and stub exchange oracles (contract testing only)
+    pub allow_test_stubs: bool,
+    /// Accounts that may pause any contract (`__pause`), besides its owner
+    #[serde(default)]
+    pub emergency_guardians: Vec<String>,
+    /// Block timestamp from which REWARD/FEE_REWARD mints must carry a
+    /// validator quorum certificate (see reward_cosign.rs)
+    #[serde(default = "default_reward_cosign_activation")]
+    pub reward_cosign_activation_secs: u64,
+    /// How certified validator rewards reach validators (see reward_claims.rs)
+    #[serde(default)]
+    pub reward_payout: RewardPayout,
+}
+
+fn default_reward_probation_epochs() -> u64 {
+    crate::REWARD_PROBATION_EPOCHS
+}
+
+fn default_reward_halving_interval_epochs() -> u64 {
+    crate::REWARD_HALVING_INTERVAL_EPOCHS
+}
+
+fn default_reward_rate_initial_cil() -> u128 {
+    crate::REWARD_RATE_INITIAL_CIL
+}
+
+fn default_reward_cosign_activation() -> u64 {
+    crate::reward_cosign::REWARD_COSIGN_ACTIVATION_SECS
+}
+
+impl ChainSpec {
+    /// The embedded mainnet spec. IMMUTABLE without a coordinated upgrade.
+    pub fn mainnet() -> Self {
+        Self {
+            network: "mainnet".to_string(),
+            chain_id: MAINNET_CHAIN_ID,
+            reward_epoch_secs: crate::REWARD_EPOCH_SECS,
+            reward_probation_epochs: crate::REWARD_PROBATION_EPOCHS,
+            reward_halving_interval_epochs: crate::REWARD_HALVING_INTERVAL_EPOCHS,
+            reward_rate_initial_cil: crate::REWARD_RATE_INITIAL_CIL,
+            mining_epoch_secs: crate::pow_mint::MINING_EPOCH_SECS,
+            mining_halving_interval_epochs: crate::pow_mint::MINING_HALVING_INTERVAL_EPOCHS,
+            initial_mining_difficulty_bits: crate::pow_mint::INITIAL_MINING_DIFFICULTY_BITS,
+            mining_reward_per_epoch_cil: crate::pow_mint::MINING_REWARD_PER_EPOCH_CIL,
+            max_mint_per_block_cil: 1_000 * CIL_PER_LOS,
+            signature_policy: SignaturePolicy {
+                allow_ed25519: false,
+                require_client_signatures: true,
+            },
+            allow_test_stubs: false,
+            emergency_guardians: Vec::new(),
+            reward_cosign_activation_secs: crate::reward_cosign::REWARD_COSIGN_ACTIVATION_SECS,
+            reward_payout: RewardPayout::Push,
+        }
+    }
+
+    /// Public testnet defaults (short epochs, relaxed signature policy)
+    pub fn testnet() -> Self {
+        Self {
+            network: "testnet".to_string(),
+            chain_id: TESTNET_CHAIN_ID,
+            reward_epoch_secs: crate::TESTNET_REWARD_EPOCH_SECS,
+            reward_probation_epochs: crate::REWARD_PROBATION_EPOCHS,
+            reward_halving_interval_epochs: crate::REWARD_HALVING_INTERVAL_EPOCHS,
+            reward_rate_initial_cil: crate::REWARD_RATE_INITIAL_CIL,
+            mining_epoch_secs: crate::pow_mint::TESTNET_MINING_EPOCH_SECS,
+            mining_halving_interval_epochs: crate::pow_mint::TESTNET_MINING_HALVING_INTERVAL_EPOCHS,
+            initial_mining_difficulty_bits: crate::pow_mint::TESTNET_INITIAL_MINING_DIFFICULTY_BITS,
+            mining_reward_per_epoch_cil: crate::pow_mint::MINING_REWARD_PER_EPOCH_CIL,
+            max_mint_per_block_cil: 1_000 * CIL_PER_LOS,
+            signature_policy: SignaturePolicy {
+                allow_ed25519: true,
+                require_client_signatures: false,
+            },
+            allow_test_stubs: true,
+            emergency_guardians: Vec::new(),
+            reward_cosign_activation_secs: crate::reward_cosign::REWARD_COSIGN_ACTIVATION_SECS,
+            reward_payout: RewardPayout::Push,
+        }
+    }
+
+    /// Spec this binary uses when none is installed
+    pub fn build_default() -> Self {
+        if cfg!(feature = "mainnet") {
+            Self::mainnet()
+        } else {
+            Self::testnet()
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        self.chain_id == MAINNET_CHAIN_ID
+    }
+
+    /// Pick the spec for a node started with (or without) `--mainnet`.
+    /// `declared` is the optional `chain_spec` object from the genesis file.
+    pub fn resolve(mainnet: bool, declared: Option<&serde_json::Value>) -> Result<Self, String> {
+        let spec = match declared {
+            None if mainnet => Self::mainnet(),
+            None => Self::testnet(),
+            Some(value) => serde_json::from_value::<ChainSpec>(value.clone())
+                .map_err(|e| format!("Invalid chain_spec: {}", e))?,
+        };
+        spec.check_integrity()?;
+        if mainnet != spec.is_mainnet() {
+            return Err(format!(
+                "chain_spec '{}' (chain_id {}) does not match --mainnet={}",
+                spec.network, spec.chain_id, mainnet
+            ));
+        }
+        Ok(spec)
+    }
+
+    /// Rules every spec must satisfy (see module header)
+    pub fn check_integrity(&self) -> Result<(), String> {
+        let claims_mainnet = self.chain_id == MAINNET_CHAIN_ID || self.network == "mainnet";
+        if claims_mainnet && *self != Self::mainnet() {
+            return Err(
+                "chain_spec differs from the embedded mainnet spec — mainnet parameters cannot be overridden"
+                    .to_string(),
+            );
+        }
+        if cfg!(feature = "mainnet") && !claims_mainnet {
+            return Err(format!(
+                "This binary was built with --features mainnet and cannot run '{}'",
+                self.network
+            ));
+        }
+        if self.chain_id == 0 {
+            return Err("chain_id must be non-zero".to_string());
+        }
+        if self.reward_epoch_secs == 0
+            || self.mining_epoch_secs == 0
+            || self.mining_halving_interval_epochs == 0
+            || self.reward_halving_interval_epochs == 0
+        {
+            return Err("Epoch lengths and halving intervals must be non-zero".to_string());
+        }
+        if self.reward_rate_initial_cil > crate::VALIDATOR_REWARD_POOL_CIL {
+            return Err(format!(
+                "reward_rate_initial_cil exceeds the validator reward pool ({} CIL)",
+                crate::VALIDATOR_REWARD_POOL_CIL
+            ));
+        }
+        if !(crate::pow_mint::MIN_MINING_DIFFICULTY_BITS
+            ..=crate::pow_mint::MAX_MINING_DIFFICULTY_BITS)
+            .contains(&self.initial_mining_difficulty_bits)
+        {
+            return Err(format!(
+                "initial_mining_difficulty_bits must be {}..={}",
+                crate::pow_mint::MIN_MINING_DIFFICULTY_BITS,
+                crate::pow_mint::MAX_MINING_DIFFICULTY_BITS
+            ));
+        }
+        Ok(())
+    }
+}
+
+static ACTIVE: OnceLock<ChainSpec> = OnceLock::new();
+
+/// The running network's spec (build default until `install` is called)
+pub fn active() -> &'static ChainSpec {
+    ACTIVE.get_or_init(ChainSpec::build_default)
+}
+
+/// Install the spec for this process. Must run before anything reads it;
+/// re-installing the identical spec is a no-op.
+pub fn install(spec: ChainSpec) -> Result<(), String> {
+    spec.check_integrity()?;
+    if !spec.signature_policy.allow_ed25519 {
+        los_crypto::disable_ed25519();
+    }
+    let current = ACTIVE.get_or_init(|| spec.clone());
+    if *current != spec {
+        return Err(format!(
+            "Chain spec already fixed to '{}' (chain_id {}) before install",
+            current.network, current.chain_id
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mainnet_cannot_be_relaxed() {
+        let mut relaxed = serde_json::to_value(ChainSpec::mainnet()).unwrap();
+        relaxed["signature_policy"]["allow_ed25519"] = serde_json::json!(true);
+        assert!(ChainSpec::resolve(true, Some(&relaxed)).is_err());
+
+        // A testnet spec may not borrow chain_id 1
+        let mut fake = ChainSpec::testnet();
+        fake.chain_id = MAINNET_CHAIN_ID;
+        assert!(fake.check_integrity().is_err());
+
+        // Restating the embedded spec verbatim is accepted
+        let exact = serde_json::to_value(ChainSpec::mainnet()).unwrap();
+        if !cfg!(feature = "mainnet") {
+            assert!(ChainSpec::resolve(false, Some(&exact)).is_err());
+        }
+        assert_eq!(
+            ChainSpec::resolve(true, Some(&exact)).unwrap(),
+            ChainSpec::mainnet()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_custom_testnet_spec() {
+        let mut value = serde_json::to_value(ChainSpec::testnet()).unwrap();
+        value["network"] = serde_json::json!("devnet-7");
+        value["chain_id"] = serde_json::json!(77);
+        value["mining_epoch_secs"] = serde_json::json!(30);
+        let spec = ChainSpec::resolve(false, Some(&value)).unwrap();
+        assert_eq!(spec.chain_id, 77);
+        assert_eq!(spec.mining_epoch_secs, 30);
+        assert!(!spec.is_mainnet());
+
+        value["mining_epoch_secs"] = serde_json::json!(0);
+        assert!(ChainSpec::resolve(false, Some(&value)).is_err());
+        value["mining_epoch_secs"] = serde_json::json!(30);
+        value["reward_payout"] = serde_json::json!("claim");
+        assert_eq!(
+            ChainSpec::resolve(false, Some(&value))
+                .unwrap()
+                .reward_payout,
+            RewardPayout::Claim
+        );
+        value["unknown_knob"] = serde_json::json!(true);
+        assert!(ChainSpec::resolve(false, Some(&value)).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_reward_schedule_fields() {
+        // Genesis files written before these fields get the fixed schedule
+        let mut value = serde_json::to_value(ChainSpec::testnet()).unwrap();
+        for field in [
+            "reward_probation_epochs",
+            "reward_halving_interval_epochs",
+            "reward_rate_initial_cil",
+        ] {
+            value.as_object_mut().unwrap().remove(field);
+        }
+        assert_eq!(
+            ChainSpec::resolve(false, Some(&value)).unwrap(),
+            ChainSpec::testnet()
+        );
+
+        // A private network with 10-second epochs
+        value["network"] = serde_json::json!("ci-net");
+        value["chain_id"] = serde_json::json!(901);
+        value["reward_epoch_secs"] = serde_json::json!(10);
+        value["reward_probation_epochs"] = serde_json::json!(0);
+        value["reward_halving_interval_epochs"] = serde_json::json!(3);
+        value["reward_rate_initial_cil"] = serde_json::json!(10 * CIL_PER_LOS as u64);
+        let spec = ChainSpec::resolve(false, Some(&value)).unwrap();
+        assert_eq!(spec.reward_epoch_secs, 10);
+        assert_eq!(spec.reward_probation_epochs, 0);
+        assert_eq!(spec.reward_halving_interval_epochs, 3);
+        assert_eq!(spec.reward_rate_initial_cil, 10 * CIL_PER_LOS);
+
+        value["reward_halving_interval_epochs"] = serde_json::json!(0);
+        assert!(ChainSpec::resolve(false, Some(&value)).is_err());
+        value["reward_halving_interval_epochs"] = serde_json::json!(3);
+        value["reward_rate_initial_cil"] = serde_json::json!(u64::MAX);
+        assert!(ChainSpec::resolve(false, Some(&value)).is_err());
+
+        // Mainnet keeps its schedule
+        let mut mainnet = serde_json::to_value(ChainSpec::mainnet()).unwrap();
+        mainnet["reward_probation_epochs"] = serde_json::json!(0);
+        assert!(ChainSpec::resolve(true, Some(&mainnet)).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CONSENSUS CLOCK
+//
+// Block timestamp validation must not depend on one machine's wall clock:
+// a validator whose clock runs 6 minutes fast would accept blocks that every
+// other node rejects. Ledger::process_block therefore validates against a
+// consensus time instead of SystemTime::now():
+//
+//   1. Network-adjusted time: local time plus the median offset reported by
+//      registered validators (signed VALIDATOR_HEARTBEAT timestamps). The node
+//      itself counts as one sample with offset 0. Peer offsets are used only
+//      once MIN_PEER_SAMPLES validators have reported. Offsets larger than
+//      MAX_PEER_OFFSET_SECS are ignored.
+//   2. Floor: consensus time never drops below the median of the last
+//      CHECKPOINT_WINDOW finalized checkpoint timestamps. Validators signed
+//      those, so a node with a clock far behind still accepts what the
+//      network finalized.
+//
+// Ledger::process_block_at takes the consensus time explicitly (replay,
+// tests, fuzzing). Ledger::process_block uses the process-wide clock below.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+/// Finalized checkpoints used for the median-time floor
+pub const CHECKPOINT_WINDOW: usize = 11;
+/// Validators needed before their offsets adjust local time
+pub const MIN_PEER_SAMPLES: usize = 3;
+/// Peer offsets beyond this are treated as broken clocks and ignored
+pub const MAX_PEER_OFFSET_SECS: i64 = 600;
+/// Bound on tracked validators (memory cap against address spam)
+pub const MAX_PEER_SAMPLES: usize = 1_024;
+
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusClock {
+    /// validator address → (peer timestamp − local receive time)
+    peer_offsets: BTreeMap<String, i64>,
+    /// Timestamps of the most recent finalized checkpoints, oldest first
+    checkpoint_times: VecDeque<u64>,
+}
+
+fn median_i64(values: &mut [i64]) -> i64 {
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+impl ConsensusClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a validator's (signature-verified) timestamp received at `local_now`
+    pub fn record_peer_time(&mut self, validator: &str, peer_ts: u64, local_now: u64) {
+        let offset = peer_ts as i64 - local_now as i64;
+        if offset.abs() > MAX_PEER_OFFSET_SECS {
+            return;
+        }
+        if self.peer_offsets.len() >= MAX_PEER_SAMPLES && !self.peer_offsets.contains_key(validator)
+        {
+            return;
+        }
+        self.peer_offsets.insert(validator.to_string(), offset);
+    }
+
+    /// Forget a validator (unregistered or slashed)
+    pub fn remove_peer(&mut self, validator: &str) {
+        self.peer_offsets.remove(validator);
+    }
+
+    /// Replace the checkpoint window with the given timestamps (any order)
+    pub fn set_checkpoint_times(&mut self, mut times: Vec<u64>) {
+        times.sort_unstable();
+        let skip = times.len().saturating_sub(CHECKPOINT_WINDOW);
+        self.checkpoint_times = times.into_iter().skip(skip).collect();
+    }
+
+    /// Median of the recent finalized checkpoint timestamps
+    pub fn median_time_past(&self) -> Option<u64> {
+        if self.checkpoint_times.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.checkpoint_times.iter().copied().collect();
+        sorted.sort_unstable();
+        Some(sorted[sorted.len() / 2])
+    }
+
+    /// Median peer offset including our own clock (0), if enough validators reported
+    pub fn network_offset(&self) -> i64 {
+        if self.peer_offsets.len() < MIN_PEER_SAMPLES {
+            return 0;
+        }
+        let mut offsets: Vec<i64> = self.peer_offsets.values().copied().collect();
+        offsets.push(0);
+        median_i64(&mut offsets)
+    }
+
+    /// Consensus time for a given local wall-clock reading
+    pub fn now_at(&self, local_now: u64) -> u64 {
+        let adjusted = local_now.saturating_add_signed(self.network_offset());
+        adjusted.max(self.median_time_past().unwrap_or(0))
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peer_offsets.len()
+    }
+}
+
+fn local_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+static GLOBAL: OnceLock<Mutex<ConsensusClock>> = OnceLock::new();
+
+fn global() -> &'static Mutex<ConsensusClock> {
+    GLOBAL.get_or_init(|| Mutex::new(ConsensusClock::new()))
+}
+
+fn with_global<R>(f: impl FnOnce(&mut ConsensusClock) -> R) -> R {
+    let mut guard = global()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut guard)
+}
+
+/// Current consensus time of this process (seconds)
+pub fn now() -> u64 {
+    let local = local_now();
+    with_global(|c| c.now_at(local))
+}
+
+/// Feed a verified validator heartbeat timestamp into the process-wide clock
+pub fn record_peer_time(validator: &str, peer_ts: u64) {
+    let local = local_now();
+    with_global(|c| c.record_peer_time(validator, peer_ts, local));
+}
+
+/// Replace the process-wide checkpoint window
+pub fn set_checkpoint_times(times: Vec<u64>) {
+    with_global(|c| c.set_checkpoint_times(times));
+}
+
+/// Snapshot for monitoring: (consensus_now, network_offset, peers, median_time_past)
+pub fn status() -> (u64, i64, usize, Option<u64>) {
+    let local = local_now();
+    with_global(|c| {
+        (
+            c.now_at(local),
+            c.network_offset(),
+            c.peer_count(),
+            c.median_time_past(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skewed_node_follows_validator_median() {
+        let mut fast = ConsensusClock::new();
+        let local = 1_700_000_000;
+        // Our clock is 400s fast: every peer reports 400s behind us
+        for (i, off) in [-400i64, -401, -399, -402].iter().enumerate() {
+            fast.record_peer_time(&format!("LOSv{}", i), (local as i64 + off) as u64, local);
+        }
+        // Median of {-402,-401,-400,-399,0} = -400
+        assert_eq!(fast.now_at(local), local - 400);
+
+        // Too few samples: no adjustment
+        let mut lonely = ConsensusClock::new();
+        lonely.record_peer_time("LOSv0", local - 400, local);
+        assert_eq!(lonely.now_at(local), local);
+
+        // Absurd offsets are ignored
+        let mut liar = ConsensusClock::new();
+        for i in 0..5 {
+            liar.record_peer_time(&format!("LOSx{}", i), local + 86_400, local);
+        }
+        assert_eq!(liar.peer_count(), 0);
+        assert_eq!(liar.now_at(local), local);
+    }
+
+    #[test]
+    fn test_checkpoint_median_is_floor() {
+        let mut slow = ConsensusClock::new();
+        let times: Vec<u64> = (0..20).map(|i| 1_700_000_000 + i * 60).collect();
+        slow.set_checkpoint_times(times);
+        // Window keeps the last 11 (indices 9..20), median = index 14
+        assert_eq!(slow.median_time_past(), Some(1_700_000_000 + 14 * 60));
+        // Local clock a day behind is lifted to the floor
+        assert_eq!(slow.now_at(1_699_913_600), 1_700_000_000 + 14 * 60);
+        // Ahead of the floor: unchanged
+        assert_eq!(slow.now_at(1_800_000_000), 1_800_000_000);
+    }
+}
@@ -0,0 +1,161 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CONTRACT GAS CEILINGS
+//
+// A ContractCall pays for its gas limit up front: gas_limit = fee / GAS_PRICE_CIL.
+// Two consensus caps bound how much validator CPU a caller can buy:
+//
+//   - MAX_GAS_PER_CALL: a block whose fee buys more gas than this is invalid
+//     (the VM refuses larger limits too, so a call can never run into the
+//     wall-clock timeout on metering alone)
+//   - MAX_ACCOUNT_GAS_PER_EPOCH: gas limits paid by one account within one
+//     reward epoch (block.timestamp / reward_epoch_secs) add up to at most this
+//
+// The budget is charged with the gas limit (what the fee paid for), not the
+// gas used, so every node computes it from the block alone. Usage is kept on
+// the Ledger and rebuilt from ContractCall blocks on load.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{
+    chain_spec, Block, BlockType, Ledger, GAS_PRICE_CIL, MAX_ACCOUNT_GAS_PER_EPOCH,
+    MAX_GAS_PER_CALL,
+};
+use serde::{Deserialize, Serialize};
+
+/// Gas limits paid by one account in its latest epoch
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasUsage {
+    pub epoch: u64,
+    pub used: u64,
+}
+
+/// Gas limit a ContractCall block pays for
+pub fn call_gas_limit(block: &Block) -> u64 {
+    u64::try_from(block.fee / GAS_PRICE_CIL.max(1)).unwrap_or(u64::MAX)
+}
+
+/// Budget epoch of a timestamp (the active chain's reward epoch)
+pub fn gas_epoch(timestamp: u64) -> u64 {
+    timestamp / chain_spec::active().reward_epoch_secs.max(1)
+}
+
+impl Ledger {
+    /// Gas limits `account` has paid in the epoch containing `timestamp`
+    pub fn account_gas_used(&self, account: &str, timestamp: u64) -> u64 {
+        match self.contract_gas_used.get(account) {
+            Some(u) if u.epoch >= gas_epoch(timestamp) => u.used,
+            _ => 0,
+        }
+    }
+
+    /// Validate a ContractCall's gas limit against both caps; returns it
+    pub fn check_contract_gas(&self, block: &Block) -> Result<u64, String> {
+        let gas = call_gas_limit(block);
+        if gas > MAX_GAS_PER_CALL {
+            return Err(format!(
+                "Gas limit too high: fee buys {} gas > per-call cap {}",
+                gas, MAX_GAS_PER_CALL
+            ));
+        }
+        let used = self.account_gas_used(&block.account, block.timestamp);
+        if used.saturating_add(gas) > MAX_ACCOUNT_GAS_PER_EPOCH {
+            return Err(format!(
+                "Gas budget exhausted: {} + {} gas > {} per account per epoch",
+                used, gas, MAX_ACCOUNT_GAS_PER_EPOCH
+            ));
+        }
+        Ok(gas)
+    }
+
+    /// Charge an accepted call's gas limit to its account
+    pub(crate) fn charge_contract_gas(&mut self, account: &str, timestamp: u64, gas: u64) {
+        let epoch = gas_epoch(timestamp);
+        let usage = self
+            .contract_gas_used
+            .entry(account.to_string())
+            .or_default();
+        // A timestamp in an older epoch than the last charge counts towards
+        // the newer one (never resets a budget backwards)
+        if epoch > usage.epoch {
+            *usage = GasUsage { epoch, used: 0 };
+        }
+        usage.used = usage.used.saturating_add(gas);
+    }
+
+    /// Recompute gas usage from ContractCall blocks (after loading a ledger)
+    pub fn rebuild_gas_usage(&mut self) {
+        let mut calls: Vec<(String, u64, u64)> = self
+            .blocks
+            .values()
+            .filter(|b| b.block_type == BlockType::ContractCall)
+            .map(|b| (b.account.clone(), b.timestamp, call_gas_limit(b)))
+            .collect();
+        calls.sort();
+        self.contract_gas_used.clear();
+        for (account, timestamp, gas) in calls {
+            self.charge_contract_gas(&account, timestamp, gas);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(account: &str, timestamp: u64, gas: u64) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::ContractCall,
+            amount: 0,
+            link: "CALL:LOSConX:f".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp,
+            fee: gas as u128 * GAS_PRICE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_per_call_cap_and_epoch_budget() {
+        let mut ledger = Ledger::new();
+        let epoch_secs = chain_spec::active().reward_epoch_secs;
+        let t0 = 1_771_000_000 / epoch_secs * epoch_secs;
+
+        assert!(ledger
+            .check_contract_gas(&call("LOSa", t0, MAX_GAS_PER_CALL + 1))
+            .unwrap_err()
+            .contains("per-call cap"));
+        assert_eq!(
+            ledger.check_contract_gas(&call("LOSa", t0, MAX_GAS_PER_CALL)),
+            Ok(MAX_GAS_PER_CALL)
+        );
+
+        // Fill the budget, then the next call in the same epoch is refused
+        ledger.charge_contract_gas("LOSa", t0, MAX_ACCOUNT_GAS_PER_EPOCH - 10);
+        assert!(ledger.check_contract_gas(&call("LOSa", t0 + 1, 10)).is_ok());
+        assert!(ledger
+            .check_contract_gas(&call("LOSa", t0 + 1, 11))
+            .unwrap_err()
+            .contains("Gas budget exhausted"));
+        // Other accounts and the next epoch are unaffected
+        assert!(ledger.check_contract_gas(&call("LOSb", t0, 11)).is_ok());
+        assert!(ledger
+            .check_contract_gas(&call("LOSa", t0 + epoch_secs, 11))
+            .is_ok());
+
+        // Rebuild from blocks matches incremental charging
+        for (i, (acct, ts, gas)) in [("LOSa", t0, 5), ("LOSa", t0 + 1, 7), ("LOSb", t0, 3)]
+            .into_iter()
+            .enumerate()
+        {
+            ledger.blocks.insert(format!("h{}", i), call(acct, ts, gas));
+        }
+        ledger.rebuild_gas_usage();
+        assert_eq!(ledger.account_gas_used("LOSa", t0), 12);
+        assert_eq!(ledger.account_gas_used("LOSb", t0), 3);
+        assert_eq!(ledger.account_gas_used("LOSa", t0 + epoch_secs), 0);
+    }
+}
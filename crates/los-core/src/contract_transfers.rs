@@ -0,0 +1,227 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CONTRACT PAYOUTS IN THE LEDGER
+//
+// CIL sent to a contract (ContractDeploy funding, ContractCall value) leaves
+// the sender's ledger balance and is tracked in Ledger::contract_held_cil while
+// the VM holds it. When a contract pays out via host_transfer, the VM debits
+// the contract and returns the payout in ContractResult.transfers.
+// Ledger::apply_contract_transfers turns those into ledger state:
+//
+//   - one Receive block per payout on the recipient's chain, with
+//     link = "CONTRACT:{call_block_hash}:{index}". The record is system
+//     generated: no signature, no PoW, fee 0. Every node derives the same
+//     block from the same call, so the recipient head stays in agreement.
+//   - contract_held_cil decreases by the total, so the supply audit holds
+//   - the call hash is marked in claimed_sends: a call's payouts are credited
+//     at most once
+//
+// process_block rejects these records (no linked Send), so they cannot be
+// submitted by users. verify_account_chain accepts them when the link names
+// an existing ContractCall block.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{AccountState, Block, BlockType, Ledger};
+
+/// Link prefix of system-generated contract payout Receive blocks
+pub const CONTRACT_RECEIVE_PREFIX: &str = "CONTRACT:";
+
+/// Link of the `index`-th payout of ContractCall block `call_hash`
+pub fn contract_receive_link(call_hash: &str, index: usize) -> String {
+    format!("{}{}:{}", CONTRACT_RECEIVE_PREFIX, call_hash, index)
+}
+
+impl Block {
+    /// True for system-generated contract payout records
+    pub fn is_contract_receive(&self) -> bool {
+        self.block_type == BlockType::Receive && self.link.starts_with(CONTRACT_RECEIVE_PREFIX)
+    }
+}
+
+impl Ledger {
+    /// Credit the payouts of applied ContractCall `call_hash` to their
+    /// recipients (creating accounts as needed). Returns the new Receive block
+    /// hashes in payout order. All-or-nothing: on Err nothing is changed.
+    pub fn apply_contract_transfers(
+        &mut self,
+        call_hash: &str,
+        transfers: &[(String, u128)],
+    ) -> Result<Vec<String>, String> {
+        let call = self
+            .blocks
+            .get(call_hash)
+            .filter(|b| b.block_type == BlockType::ContractCall)
+            .ok_or_else(|| format!("Contract payout: ContractCall {} not found", call_hash))?;
+        let call_timestamp = call.timestamp;
+        if self.claimed_sends.contains(call_hash) {
+            return Err(format!(
+                "Contract payout: transfers of {} already credited",
+                call_hash
+            ));
+        }
+        let total = transfers
+            .iter()
+            .try_fold(0u128, |acc, (_, amount)| acc.checked_add(*amount))
+            .ok_or("Contract payout: total overflows u128")?;
+        if total > self.contract_held_cil {
+            return Err(format!(
+                "Contract payout: {} CIL exceeds the {} CIL held by contracts",
+                total, self.contract_held_cil
+            ));
+        }
+        if let Some((recipient, _)) = transfers.iter().find(|(r, _)| r.is_empty()) {
+            return Err(format!(
+                "Contract payout: invalid recipient '{}'",
+                recipient
+            ));
+        }
+
+        let mut hashes = Vec::with_capacity(transfers.len());
+        for (index, (recipient, amount)) in transfers.iter().enumerate() {
+            let mut state = self
+                .accounts
+                .get(recipient)
+                .cloned()
+                .unwrap_or(AccountState {
+                    head: "0".to_string(),
+                    balance: 0,
+                    block_count: 0,
+                    is_validator: false,
+                });
+            // Keep the recipient chain's timestamps non-decreasing
+            let prev_ts = self
+                .blocks
+                .get(&state.head)
+                .map(|b| b.timestamp)
+                .unwrap_or(0);
+            let block = Block {
+                account: recipient.clone(),
+                previous: state.head.clone(),
+                block_type: BlockType::Receive,
+                amount: *amount,
+                link: contract_receive_link(call_hash, index),
+                signature: String::new(),
+                public_key: String::new(),
+                work: 0,
+                timestamp: call_timestamp.max(prev_ts),
+                fee: 0,
+                valid_after: 0,
+                expires_at: 0,
+            };
+            let hash = block.calculate_hash();
+            state.balance = state.balance.saturating_add(*amount);
+            state.head = hash.clone();
+            state.block_count += 1;
+            self.accounts.insert(recipient.clone(), state);
+            self.blocks.insert(hash.clone(), block);
+            hashes.push(hash);
+        }
+        self.contract_held_cil -= total; // Safe: checked above
+        self.claimed_sends.insert(call_hash.to_string());
+        Ok(hashes)
+    }
+
+    /// Chain verification for a contract payout record
+    pub(crate) fn verify_contract_receive(&self, block: &Block) -> Result<(), String> {
+        let call_hash = block
+            .link
+            .strip_prefix(CONTRACT_RECEIVE_PREFIX)
+            .and_then(|rest| rest.rsplit_once(':'))
+            .map(|(hash, _)| hash)
+            .ok_or_else(|| format!("malformed contract payout link {}", block.link))?;
+        match self.blocks.get(call_hash) {
+            Some(call) if call.block_type == BlockType::ContractCall => {}
+            _ => return Err(format!("linked ContractCall {} not found", call_hash)),
+        }
+        if !block.signature.is_empty() || block.fee != 0 {
+            return Err("contract payout must be unsigned with fee 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger_with_call(held: u128) -> (Ledger, String) {
+        let mut ledger = Ledger::new();
+        let call = Block {
+            account: "LOScaller".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::ContractCall,
+            amount: held,
+            link: "CALL:LOSConX:withdraw".to_string(),
+            signature: "sig".to_string(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: crate::MIN_CALL_FEE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let hash = call.calculate_hash();
+        ledger.blocks.insert(hash.clone(), call);
+        ledger.contract_held_cil = held;
+        (ledger, hash)
+    }
+
+    fn in_circulation(l: &Ledger) -> u128 {
+        l.accounts.values().map(|a| a.balance).sum::<u128>() + l.contract_held_cil
+    }
+
+    #[test]
+    fn test_payouts_create_receive_records() {
+        let (mut ledger, call) = ledger_with_call(1_000);
+        let before = in_circulation(&ledger);
+        let transfers = vec![
+            ("LOSalice".to_string(), 300),
+            ("LOSbob".to_string(), 200),
+            ("LOSalice".to_string(), 100),
+        ];
+        let hashes = ledger.apply_contract_transfers(&call, &transfers).unwrap();
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(ledger.contract_held_cil, 400);
+        assert_eq!(in_circulation(&ledger), before);
+
+        let alice = &ledger.accounts["LOSalice"];
+        assert_eq!((alice.balance, alice.block_count), (400, 2));
+        assert_eq!(alice.head, hashes[2]);
+        let second = ledger.blocks[&hashes[2]].clone();
+        assert!(second.is_contract_receive());
+        assert_eq!(second.previous, hashes[0]);
+        assert_eq!(second.link, contract_receive_link(&call, 2));
+
+        // Chains made only of payout records verify without signatures
+        let v = ledger.verify_account_chain("LOSalice").unwrap();
+        assert_eq!((v.blocks_checked, v.opening_balance), (2, 0));
+        assert!(ledger.verify_account_chain("LOSbob").is_ok());
+
+        // Users cannot submit payout records
+        assert!(ledger.process_block(&second).is_err());
+    }
+
+    #[test]
+    fn test_payouts_rejected_without_side_effects() {
+        let (mut ledger, call) = ledger_with_call(500);
+        let pay = |amount| vec![("LOSalice".to_string(), amount)];
+
+        assert!(ledger
+            .apply_contract_transfers(&call, &pay(501))
+            .unwrap_err()
+            .contains("exceeds"));
+        assert!(ledger.apply_contract_transfers("unknown", &pay(1)).is_err());
+        assert!(ledger
+            .apply_contract_transfers(&call, &[(String::new(), 1)])
+            .is_err());
+        assert!(ledger.accounts.is_empty());
+        assert_eq!(ledger.contract_held_cil, 500);
+
+        ledger.apply_contract_transfers(&call, &pay(100)).unwrap();
+        assert!(ledger
+            .apply_contract_transfers(&call, &pay(100))
+            .unwrap_err()
+            .contains("already credited"));
+        assert_eq!(ledger.accounts["LOSalice"].balance, 100);
+        assert_eq!(ledger.contract_held_cil, 400);
+    }
+}
@@ -0,0 +1,97 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CONTRACTDEPLOY LINK
+//
+//   DEPLOY:{code_hash}              address from the deployer's nonce
+//   DEPLOY:{code_hash}:{salt}       address = blake3(owner ‖ salt ‖ code_hash)
+//
+// The salt is 32 bytes in lowercase hex. It is part of the signed link, so
+// every node derives the same address, and a dapp can compute the address
+// before deploying (counterfactual deployment). A salted address can be
+// taken only once; los-vm refuses the second deploy.
+//
+// WasmEngine::salted_address (los-vm) is the one derivation: the deploy path,
+// GET /contracts/address and a future contract-factory host function share it.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Link prefix of ContractDeploy blocks
+pub const DEPLOY_LINK_PREFIX: &str = "DEPLOY:";
+/// Salt length in bytes
+pub const SALT_LEN: usize = 32;
+
+/// A parsed ContractDeploy link
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployLink<'a> {
+    pub code_hash: &'a str,
+    pub salt: Option<[u8; SALT_LEN]>,
+}
+
+impl<'a> DeployLink<'a> {
+    pub fn parse(link: &'a str) -> Result<Self, String> {
+        let data = link
+            .strip_prefix(DEPLOY_LINK_PREFIX)
+            .ok_or("ContractDeploy Error: link must start with 'DEPLOY:'")?;
+        let (code_hash, salt) = match data.split_once(':') {
+            Some((hash, salt)) => (hash, Some(parse_salt(salt)?)),
+            None => (data, None),
+        };
+        if code_hash.len() < 8 {
+            return Err("ContractDeploy Error: invalid code hash in link field".to_string());
+        }
+        Ok(Self { code_hash, salt })
+    }
+
+    /// Link text for `code_hash`, salted or not
+    pub fn format(code_hash: &str, salt: Option<&[u8; SALT_LEN]>) -> String {
+        match salt {
+            Some(s) => format!("{}{}:{}", DEPLOY_LINK_PREFIX, code_hash, hex::encode(s)),
+            None => format!("{}{}", DEPLOY_LINK_PREFIX, code_hash),
+        }
+    }
+}
+
+/// Salt from its 64-char lowercase hex form
+pub fn parse_salt(hex_salt: &str) -> Result<[u8; SALT_LEN], String> {
+    // One spelling per salt: the link is signed and gossiped as text
+    if hex_salt.len() != SALT_LEN * 2 || hex_salt.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(format!(
+            "Deploy salt must be {} lowercase hex characters",
+            SALT_LEN * 2
+        ));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    hex::decode_to_slice(hex_salt, &mut salt)
+        .map_err(|_| "Deploy salt is not valid hex".to_string())?;
+    Ok(salt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format() {
+        let hash = "ab".repeat(32);
+        let plain_link = format!("DEPLOY:{}", hash);
+        let plain = DeployLink::parse(&plain_link).unwrap();
+        assert_eq!(plain.code_hash, hash);
+        assert_eq!(plain.salt, None);
+
+        let salt = [7u8; SALT_LEN];
+        let link = DeployLink::format(&hash, Some(&salt));
+        let salted = DeployLink::parse(&link).unwrap();
+        assert_eq!(salted.code_hash, hash);
+        assert_eq!(salted.salt, Some(salt));
+        assert_eq!(DeployLink::format(&hash, None), format!("DEPLOY:{}", hash));
+    }
+
+    #[test]
+    fn test_rejects_bad_links() {
+        let hash = "ab".repeat(32);
+        assert!(DeployLink::parse(&format!("CALL:{}", hash)).is_err());
+        assert!(DeployLink::parse("DEPLOY:abc").is_err());
+        assert!(DeployLink::parse(&format!("DEPLOY:{}:{}", hash, "07".repeat(31))).is_err());
+        assert!(DeployLink::parse(&format!("DEPLOY:{}:{}", hash, "AB".repeat(32))).is_err());
+        assert!(DeployLink::parse(&format!("DEPLOY:{}:{}", hash, "zz".repeat(32))).is_err());
+        assert!(DeployLink::parse(&format!("DEPLOY:{}:", hash)).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — ED25519 → DILITHIUM5 ACCOUNT MIGRATION
+//
+// Testnet desktop wallets sign with the Ed25519 fallback key, which mainnet
+// does not accept. A Migrate block moves such an account to a Dilithium5 key:
+//
+//   - the block is signed by the Ed25519 account key (normal block signature)
+//   - link = "MIGRATE:{dilithium_pk_hex}:{dilithium_sig_hex}", where the
+//     Dilithium5 signature covers migration_message(account, previous,
+//     new_address). Both keys must sign; neither alone can move the funds.
+//   - the destination is the address derived from the Dilithium5 key
+//   - amount + fee must equal the whole balance (a sweep). Funds that arrive
+//     later are moved by another Migrate block.
+//
+// Applying the block credits the destination with a system Receive record,
+// link = "MIGRATED:{migrate_block_hash}" — unsigned, no PoW, fee 0, derived
+// identically by every node (same pattern as contract_transfers.rs).
+// process_block rejects user-submitted records (no linked Send);
+// verify_account_chain accepts them when the link names a matching Migrate.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{chain_id, AccountState, Block, BlockType, Ledger, BASE_FEE_CIL};
+use sha3::{Digest, Sha3_256};
+
+/// Link prefix of Migrate blocks
+pub const MIGRATE_LINK_PREFIX: &str = "MIGRATE:";
+/// Link prefix of the system Receive record crediting the new account
+pub const MIGRATED_RECEIVE_PREFIX: &str = "MIGRATED:";
+/// Minimum fee of a Migrate block (same as a Send)
+pub const MIN_MIGRATION_FEE_CIL: u128 = BASE_FEE_CIL;
+
+const MIGRATION_DOMAIN: &[u8] = b"LOS-KEY-MIGRATION-V1";
+
+/// Message the new Dilithium5 key signs to accept the migration.
+/// Binds chain, source account, its current head and the destination,
+/// so the authorization cannot be replayed elsewhere or redirected.
+pub fn migration_message(account: &str, previous: &str, new_address: &str) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(MIGRATION_DOMAIN);
+    hasher.update(chain_id().to_le_bytes());
+    for part in [account, previous, new_address] {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Sign the Dilithium5 half of a migration and return the Migrate block link
+pub fn sign_migration_link(
+    account: &str,
+    previous: &str,
+    dilithium_pk: &[u8],
+    dilithium_sk: &[u8],
+) -> Result<String, String> {
+    let new_address = los_crypto::public_key_to_address(dilithium_pk);
+    let msg = migration_message(account, previous, &new_address);
+    let sig = los_crypto::sign_message(&msg, dilithium_sk)
+        .map_err(|e| format!("Migration signing failed: {}", e))?;
+    Ok(format!(
+        "{}{}:{}",
+        MIGRATE_LINK_PREFIX,
+        hex::encode(dilithium_pk),
+        hex::encode(sig)
+    ))
+}
+
+/// Split a Migrate link into (dilithium_pk, dilithium_sig)
+pub fn parse_migration_link(link: &str) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (pk, sig) = link.strip_prefix(MIGRATE_LINK_PREFIX)?.split_once(':')?;
+    Some((hex::decode(pk).ok()?, hex::decode(sig).ok()?))
+}
+
+impl Block {
+    /// Destination address of a Migrate block (None for other blocks or bad links)
+    pub fn migration_destination(&self) -> Option<String> {
+        if self.block_type != BlockType::Migrate {
+            return None;
+        }
+        parse_migration_link(&self.link).map(|(pk, _)| los_crypto::public_key_to_address(&pk))
+    }
+
+    /// True for system-generated migration credit records
+    pub fn is_migration_receive(&self) -> bool {
+        self.block_type == BlockType::Receive && self.link.starts_with(MIGRATED_RECEIVE_PREFIX)
+    }
+
+    /// Check the Dilithium5 authorization carried in the link.
+    /// Returns the destination address.
+    pub fn verify_migration_authorization(&self) -> Result<String, String> {
+        let (pk, sig) = parse_migration_link(&self.link)
+            .ok_or("Migrate Error: link must be MIGRATE:{dilithium_pk_hex}:{dilithium_sig_hex}")?;
+        // A 32-byte key would be verified as Ed25519 — the target must be Dilithium5
+        if pk.len() == 32 {
+            return Err("Migrate Error: target key must be Dilithium5, not Ed25519".to_string());
+        }
+        let new_address = los_crypto::public_key_to_address(&pk);
+        if new_address == self.account {
+            return Err("Migrate Error: account is already controlled by this key".to_string());
+        }
+        let msg = migration_message(&self.account, &self.previous, &new_address);
+        if !los_crypto::verify_signature(&msg, &sig, &pk) {
+            return Err("Migrate Error: invalid Dilithium5 authorization signature".to_string());
+        }
+        Ok(new_address)
+    }
+}
+
+impl Ledger {
+    /// Validate a Migrate block against the sender's current state.
+    /// The block signature and account ↔ key binding are checked by
+    /// process_block. Returns the destination address.
+    pub(crate) fn check_migration(
+        &self,
+        block: &Block,
+        state: &AccountState,
+    ) -> Result<String, String> {
+        // Only Ed25519 accounts have anything to migrate
+        if hex::decode(&block.public_key).map(|pk| pk.len()) != Ok(32) {
+            return Err("Migrate Error: only Ed25519 accounts can migrate".to_string());
+        }
+        if state.is_validator {
+            return Err("Migrate Error: unregister the validator before migrating".to_string());
+        }
+        let new_address = block.verify_migration_authorization()?;
+        if block.fee < MIN_MIGRATION_FEE_CIL {
+            return Err(format!(
+                "Fee too low: {} CIL < minimum {} CIL",
+                block.fee, MIN_MIGRATION_FEE_CIL
+            ));
+        }
+        let total = block
+            .amount
+            .checked_add(block.fee)
+            .ok_or("Overflow: amount + fee exceeds u128")?;
+        if block.amount == 0 || total != state.balance {
+            return Err(format!(
+                "Migrate Error: amount + fee must sweep the whole balance ({} CIL), got {}",
+                state.balance, total
+            ));
+        }
+        Ok(new_address)
+    }
+
+    /// Credit applied Migrate block `migrate_hash` to its destination.
+    /// Returns the hash of the new Receive record.
+    pub(crate) fn credit_migration(
+        &mut self,
+        migrate_hash: &str,
+        migrate: &Block,
+        new_address: &str,
+    ) -> String {
+        let mut state = self
+            .accounts
+            .get(new_address)
+            .cloned()
+            .unwrap_or(AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            });
+        // Keep the destination chain's timestamps non-decreasing
+        let prev_ts = self
+            .blocks
+            .get(&state.head)
+            .map(|b| b.timestamp)
+            .unwrap_or(0);
+        let block = Block {
+            account: new_address.to_string(),
+            previous: state.head.clone(),
+            block_type: BlockType::Receive,
+            amount: migrate.amount,
+            link: format!("{}{}", MIGRATED_RECEIVE_PREFIX, migrate_hash),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: migrate.timestamp.max(prev_ts),
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let hash = block.calculate_hash();
+        state.balance = state.balance.saturating_add(migrate.amount);
+        state.head = hash.clone();
+        state.block_count += 1;
+        self.accounts.insert(new_address.to_string(), state);
+        self.blocks.insert(hash.clone(), block);
+        self.claimed_sends.insert(migrate_hash.to_string());
+        hash
+    }
+
+    /// Chain verification for a migration credit record
+    pub(crate) fn verify_migration_receive(&self, block: &Block) -> Result<(), String> {
+        let migrate_hash = block
+            .link
+            .strip_prefix(MIGRATED_RECEIVE_PREFIX)
+            .ok_or_else(|| format!("malformed migration link {}", block.link))?;
+        match self.blocks.get(migrate_hash) {
+            Some(m)
+                if m.migration_destination().as_deref() == Some(block.account.as_str())
+                    && m.amount == block.amount => {}
+            _ => return Err(format!("linked Migrate {} does not match", migrate_hash)),
+        }
+        if !block.signature.is_empty() || block.fee != 0 {
+            return Err("migration credit must be unsigned with fee 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, not(feature = "mainnet")))]
+mod tests {
+    use super::*;
+
+    const SEED: [u8; 64] = [42u8; 64];
+
+    fn ed25519_account() -> (String, los_crypto::KeyPair) {
+        let keys = los_crypto::ed25519_keypair_from_seed(&SEED).unwrap();
+        (los_crypto::public_key_to_address(&keys.public_key), keys)
+    }
+
+    fn migrate_block(account: &str, ed: &los_crypto::KeyPair, link: String, amount: u128) -> Block {
+        let mut b = Block {
+            account: account.to_string(),
+            previous: "head".to_string(),
+            block_type: BlockType::Migrate,
+            amount,
+            link,
+            signature: String::new(),
+            public_key: hex::encode(&ed.public_key),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: MIN_MIGRATION_FEE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let sig = los_crypto::sign_ed25519(b.signing_hash().as_bytes(), &ed.secret_key).unwrap();
+        b.signature = hex::encode(sig);
+        b
+    }
+
+    fn state(balance: u128) -> AccountState {
+        AccountState {
+            head: "head".to_string(),
+            balance,
+            block_count: 1,
+            is_validator: false,
+        }
+    }
+
+    #[test]
+    fn test_migration_sweeps_to_dilithium_address() {
+        let (account, ed) = ed25519_account();
+        let dil = los_crypto::generate_keypair_from_seed(&SEED);
+        let new_address = los_crypto::public_key_to_address(&dil.public_key);
+        let link = sign_migration_link(&account, "head", &dil.public_key, &dil.secret_key).unwrap();
+        let balance = 5_000_000;
+        let block = migrate_block(&account, &ed, link.clone(), balance - MIN_MIGRATION_FEE_CIL);
+        assert!(block.verify_signature());
+
+        let mut ledger = Ledger::new();
+        assert_eq!(
+            ledger.check_migration(&block, &state(balance)),
+            Ok(new_address.clone())
+        );
+        assert_eq!(block.migration_destination(), Some(new_address.clone()));
+
+        // Partial sweeps and validators are refused
+        assert!(ledger
+            .check_migration(&block, &state(balance + 1))
+            .unwrap_err()
+            .contains("sweep"));
+        let mut validator = state(balance);
+        validator.is_validator = true;
+        assert!(ledger.check_migration(&block, &validator).is_err());
+
+        // The Dilithium authorization is bound to the chain position
+        let mut moved = migrate_block(&account, &ed, link, balance - MIN_MIGRATION_FEE_CIL);
+        moved.previous = "other".to_string();
+        assert!(moved
+            .verify_migration_authorization()
+            .unwrap_err()
+            .contains("authorization"));
+
+        // Destination is credited by a verifiable system record
+        let hash = block.calculate_hash();
+        ledger.blocks.insert(hash.clone(), block.clone());
+        let credit = ledger.credit_migration(&hash, &block, &new_address);
+        let dest = &ledger.accounts[&new_address];
+        assert_eq!(
+            (dest.balance, dest.head.as_str()),
+            (block.amount, credit.as_str())
+        );
+        let record = ledger.blocks[&credit].clone();
+        assert!(record.is_migration_receive());
+        let v = ledger.verify_account_chain(&new_address).unwrap();
+        assert_eq!((v.blocks_checked, v.opening_balance), (1, 0));
+
+        // Users cannot submit credit records themselves
+        let mut forged = record;
+        forged.amount += 1;
+        assert!(ledger.verify_migration_receive(&forged).is_err());
+        assert!(ledger.process_block(&forged).is_err());
+    }
+
+    #[test]
+    fn test_migration_rejects_bad_keys() {
+        let (account, ed) = ed25519_account();
+        let amount = 1_000_000;
+
+        // An Ed25519 target is not a migration
+        let other = los_crypto::ed25519_keypair_from_seed(&[9u8; 32]).unwrap();
+        let new_address = los_crypto::public_key_to_address(&other.public_key);
+        let sig = los_crypto::sign_ed25519(
+            &migration_message(&account, "head", &new_address),
+            &other.secret_key,
+        )
+        .unwrap();
+        let link = format!(
+            "{}{}:{}",
+            MIGRATE_LINK_PREFIX,
+            hex::encode(&other.public_key),
+            hex::encode(sig)
+        );
+        let block = migrate_block(&account, &ed, link, amount);
+        assert!(Ledger::new()
+            .check_migration(&block, &state(amount + MIN_MIGRATION_FEE_CIL))
+            .unwrap_err()
+            .contains("Dilithium5"));
+
+        // Malformed link
+        let block = migrate_block(&account, &ed, "MIGRATE:zz".to_string(), amount);
+        assert!(block.verify_migration_authorization().is_err());
+        assert_eq!(block.migration_destination(), None);
+    }
+}
@@ -13,8 +13,20 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 /// Maximum allowed timestamp drift from current time (5 minutes)
 pub const MAX_TIMESTAMP_DRIFT_SECS: u64 = 300;
 
+pub mod chain_spec;
+pub mod consensus_clock;
+pub mod contract_gas;
+pub mod contract_transfers;
+pub mod deploy_link;
 pub mod distribution;
+pub mod key_migration;
+pub mod payment_uri;
 pub mod pow_mint;
+pub mod reward_claims;
+pub mod reward_cosign;
+pub mod session_keys;
+pub mod units;
+pub mod validation_hooks;
 pub mod validator_config;
 pub mod validator_rewards;
 use crate::distribution::DistributionState;
@@ -44,9 +56,9 @@ pub const BASE_FEE_CIL: u128 = 100_000;
 /// Minimum PoW difficulty: 16 leading zero bits (anti-spam)
 pub const MIN_POW_DIFFICULTY_BITS: u32 = 16;
 
-/// Chain ID to prevent cross-chain replay attacks
-/// Mainnet = 1, Testnet = 2. Included in every block's signing hash.
-/// Compile with `--features mainnet` for mainnet build.
+/// Default chain ID of this build (used when no chain spec is installed).
+/// Mainnet = 1, Testnet = 2. The RUNNING network's ID is [`chain_id()`].
+/// Compile with `--features mainnet` for a mainnet-only build.
 #[cfg(feature = "mainnet")]
 pub const CHAIN_ID: u64 = 1; // Mainnet
 #[cfg(not(feature = "mainnet"))]
@@ -62,6 +74,22 @@ pub const fn is_mainnet_build() -> bool {
     CHAIN_ID == 1
 }
 
+/// Chain ID of the running network, included in every block's signing hash
+/// to prevent cross-chain replay attacks.
+pub fn chain_id() -> u64 {
+    chain_spec::active().chain_id
+}
+
+/// Returns true if the node is running mainnet (runtime chain spec)
+pub fn is_mainnet() -> bool {
+    chain_spec::active().is_mainnet()
+}
+
+/// Returns true if the node is running testnet or a custom test network
+pub fn is_testnet() -> bool {
+    !is_mainnet()
+}
+
 // ─────────────────────────────────────────────────────────────────
 // VALIDATOR REWARD SYSTEM CONSTANTS
 // ─────────────────────────────────────────────────────────────────
@@ -81,15 +109,11 @@ pub const REWARD_EPOCH_SECS: u64 = 30 * 24 * 60 * 60; // 2,592,000
 /// Testnet epoch = 2 minutes (for rapid testing of reward mechanics)
 pub const TESTNET_REWARD_EPOCH_SECS: u64 = 2 * 60; // 120
 
-/// Get the effective reward epoch duration based on network type.
+/// Get the effective reward epoch duration from the running chain spec.
 /// Testnet: 2 minutes for rapid reward testing.
 /// Mainnet: 30 days (standard epoch).
-pub const fn effective_reward_epoch_secs() -> u64 {
-    if is_testnet_build() {
-        TESTNET_REWARD_EPOCH_SECS
-    } else {
-        REWARD_EPOCH_SECS
-    }
+pub fn effective_reward_epoch_secs() -> u64 {
+    chain_spec::active().reward_epoch_secs
 }
 
 /// Initial reward rate: 5,000 LOS per epoch (before halving)
@@ -104,6 +128,22 @@ pub const REWARD_MIN_UPTIME_PCT: u64 = 95;
 /// Probation period: 1 epoch (30 days) before a new validator earns rewards
 pub const REWARD_PROBATION_EPOCHS: u64 = 1;
 
+/// Reward rate before halvings, from the running chain spec
+/// (REWARD_RATE_INITIAL_CIL on mainnet and testnet)
+pub fn effective_reward_rate_initial_cil() -> u128 {
+    chain_spec::active().reward_rate_initial_cil
+}
+
+/// Reward halving interval in epochs, from the running chain spec
+pub fn effective_reward_halving_interval() -> u64 {
+    chain_spec::active().reward_halving_interval_epochs
+}
+
+/// Probation epochs of a new validator, from the running chain spec
+pub fn effective_reward_probation_epochs() -> u64 {
+    chain_spec::active().reward_probation_epochs
+}
+
 // ─────────────────────────────────────────────────────────────────
 // SMART CONTRACT GAS PRICING
 // ─────────────────────────────────────────────────────────────────
@@ -125,6 +165,13 @@ pub const MIN_CALL_FEE_CIL: u128 = BASE_FEE_CIL;
 /// Default gas limit for contract calls (1,000,000 gas units)
 pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
 
+/// Maximum gas a single ContractCall may pay for (10× default).
+/// Must equal los_vm::MAX_GAS_PER_CALL.
+pub const MAX_GAS_PER_CALL: u64 = 10_000_000;
+
+/// Maximum gas limits one account may pay for per reward epoch (contract_gas.rs)
+pub const MAX_ACCOUNT_GAS_PER_EPOCH: u64 = 100_000 * MAX_GAS_PER_CALL;
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum BlockType {
     Send,
@@ -132,10 +179,18 @@ pub enum BlockType {
     Change,
     Mint,
     Slash,
-    /// Deploy a WASM smart contract. link = "DEPLOY:{code_hash}"
+    /// Deploy a WASM smart contract. link = "DEPLOY:{code_hash}[:{salt}]" (deploy_link.rs)
     ContractDeploy,
     /// Call a smart contract function. link = "CALL:{contract_addr}:{function}:{args_b64}"
     ContractCall,
+    /// Sweep an Ed25519 account to a Dilithium5 key.
+    /// link = "MIGRATE:{dilithium_pk_hex}:{dilithium_sig_hex}" (key_migration.rs)
+    Migrate,
+    /// Mint the owner's accrued validator rewards (claim payout model).
+    /// link = "CLAIM:REWARD" (reward_claims.rs)
+    Claim,
+    /// Sender takes back an expired, unreceived Send. link = send block hash
+    Reclaim,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -152,6 +207,19 @@ pub struct Block {
     /// Transaction fee in CIL (deducted from sender on Send blocks)
     #[serde(default)]
     pub fee: u128,
+    /// Time-locked Send: earliest consensus time (Unix seconds) at which the
+    /// block may be applied. 0 = no lock. Only allowed on Send blocks.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub valid_after: u64,
+    /// Expiring Send: consensus time (Unix seconds) from which the recipient
+    /// can no longer receive it and the sender may take it back with a
+    /// Reclaim block. 0 = never expires. Only allowed on Send blocks.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub expires_at: u64,
+}
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
 }
 
 impl Block {
@@ -162,7 +230,7 @@ impl Block {
         let mut hasher = Sha3_256::new();
 
         // Chain ID domain separation — prevents replay across testnet/mainnet
-        hasher.update(CHAIN_ID.to_le_bytes());
+        hasher.update(chain_id().to_le_bytes());
 
         hasher.update(self.account.as_bytes());
         hasher.update(self.previous.as_bytes());
@@ -175,6 +243,9 @@ impl Block {
             BlockType::Slash => 4,
             BlockType::ContractDeploy => 5,
             BlockType::ContractCall => 6,
+            BlockType::Migrate => 7,
+            BlockType::Claim => 8,
+            BlockType::Reclaim => 9,
         };
         hasher.update([type_byte]);
 
@@ -193,6 +264,17 @@ impl Block {
         // fee MUST be included in hash (prevent fee manipulation)
         hasher.update(self.fee.to_le_bytes());
 
+        // Time lock is signed, but only hashed when set: blocks without one
+        // keep the hash (and PoW/signature) they had before the field existed
+        if self.valid_after != 0 {
+            hasher.update(b"valid_after");
+            hasher.update(self.valid_after.to_le_bytes());
+        }
+        if self.expires_at != 0 {
+            hasher.update(b"expires_at");
+            hasher.update(self.expires_at.to_le_bytes());
+        }
+
         hex::encode(hasher.finalize())
     }
 
@@ -223,6 +305,43 @@ impl Block {
         los_crypto::verify_signature(msg_hash.as_bytes(), &sig_bytes, &pk_bytes)
     }
 
+    /// PoW + signature check, in the order and with the errors of `process_block`
+    pub fn check_crypto(&self) -> Result<(), String> {
+        // 1. PROOF-OF-WORK VALIDATION (Anti-spam: 16 leading zero bits)
+        if !self.verify_pow() {
+            return Err(
+                "Invalid PoW: Block does not meet minimum difficulty (16 zero bits)".to_string(),
+            );
+        }
+
+        // 2. SIGNATURE VALIDATION (Dilithium5 post-quantum)
+        if !self.verify_signature() {
+            return Err("Invalid Signature: Public key verification failed!".to_string());
+        }
+        Ok(())
+    }
+
+    /// Earliest time the block may be applied (timestamp or time lock)
+    pub fn valid_from(&self) -> u64 {
+        self.timestamp.max(self.valid_after)
+    }
+
+    /// Still time-locked at consensus time `now`
+    pub fn is_time_locked(&self, now: u64) -> bool {
+        self.valid_after > now
+    }
+
+    /// Expiring Send past its expiry at consensus time `now`
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Proof that this block passed `check_crypto`, for
+    /// `Ledger::process_verified_block` (verification can run on other threads)
+    pub fn verify_crypto(&self) -> Option<CryptoVerified<'_>> {
+        self.check_crypto().ok().map(|_| CryptoVerified(self))
+    }
+
     /// Verify Proof-of-Work meets minimum difficulty (anti-spam protection)
     /// This is NOT consensus PoW - just anti-spam measure
     /// Minimum: 16 leading zero bits (≈65,536 average attempts)
@@ -260,7 +379,28 @@ pub struct AccountState {
     pub is_validator: bool,
 }
 
+/// Result of Ledger::verify_account_chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainVerification {
+    pub address: String,
+    pub blocks_checked: u64,
+    /// Balance before the first block (genesis allocation / adopted state)
+    pub opening_balance: u128,
+    pub final_balance: u128,
+}
+
 /// Result of processing a block through the ledger.
+/// A block whose PoW and signature were checked (see `Block::verify_crypto`).
+/// Only los-core can create one.
+#[derive(Debug, Clone, Copy)]
+pub struct CryptoVerified<'a>(&'a Block);
+
+impl<'a> CryptoVerified<'a> {
+    pub fn block(&self) -> &'a Block {
+        self.0
+    }
+}
+
 /// Distinguishes between newly applied blocks and duplicates.
 /// Callers MUST check `is_new()` to avoid re-broadcasting duplicate blocks.
 #[derive(Debug, Clone, PartialEq)]
@@ -311,6 +451,23 @@ pub struct Ledger {
     /// Without this counter, slashed funds silently disappear and the supply invariant breaks.
     #[serde(default)]
     pub total_slashed_cil: u128,
+    /// CIL held in smart contract balances (deploy funding + call value transfers,
+    /// minus contract payouts). Counted by the supply audit; see contract_transfers.rs.
+    #[serde(default)]
+    pub contract_held_cil: u128,
+    /// Account → gas limits paid in its latest epoch (contract_gas.rs)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contract_gas_used: BTreeMap<String, contract_gas::GasUsage>,
+    /// Embedder policy hooks run by process_block (validation_hooks.rs).
+    /// Process-local: never serialized.
+    #[serde(skip)]
+    pub validator_hooks: validation_hooks::ValidatorHooks,
+    /// Account → CIL minted by its Claim blocks (reward_claims.rs)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub reward_claims_cil: BTreeMap<String, u128>,
+    /// Account → session key address → grant (session_keys.rs)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub session_keys: BTreeMap<String, BTreeMap<String, session_keys::SessionGrant>>,
 }
 
 impl Default for Ledger {
@@ -328,6 +485,11 @@ impl Ledger {
             claimed_sends: BTreeSet::new(),
             accumulated_fees_cil: 0,
             total_slashed_cil: 0,
+            contract_held_cil: 0,
+            contract_gas_used: BTreeMap::new(),
+            validator_hooks: validation_hooks::ValidatorHooks::default(),
+            reward_claims_cil: BTreeMap::new(),
+            session_keys: BTreeMap::new(),
         }
     }
 
@@ -389,28 +551,217 @@ impl Ledger {
         before - self.blocks.len()
     }
 
-    pub fn process_block(&mut self, block: &Block) -> Result<ProcessResult, String> {
-        // 1. PROOF-OF-WORK VALIDATION (Anti-spam: 16 leading zero bits)
-        if !block.verify_pow() {
-            return Err(
-                "Invalid PoW: Block does not meet minimum difficulty (16 zero bits)".to_string(),
-            );
+    /// Find a block already in `block.account`'s chain that shares
+    /// `block.previous` but has a different hash (two signed successors of
+    /// one block = fork / double-spend attempt). Returns (hash, block).
+    pub fn find_fork(&self, block: &Block) -> Option<(String, &Block)> {
+        let incoming = block.calculate_hash();
+        let mut current = self.accounts.get(&block.account)?.head.as_str();
+        let mut steps = 0u64;
+        while current != "0" && steps <= self.accounts[&block.account].block_count {
+            let existing = self.blocks.get(current)?;
+            if existing.previous == block.previous {
+                return (current != incoming).then(|| (current.to_string(), existing));
+            }
+            current = existing.previous.as_str();
+            steps += 1;
         }
+        None
+    }
 
-        // 2. SIGNATURE VALIDATION (Dilithium5 post-quantum)
-        if !block.verify_signature() {
-            return Err("Invalid Signature: Public key verification failed!".to_string());
+    /// Walk an account's chain from head to genesis and re-check every block:
+    /// stored hash, owner, PoW, signature, timestamp order, Receive links and
+    /// balance arithmetic. Returns the first inconsistency found.
+    ///
+    /// The opening balance (genesis allocation or directly adopted state) is
+    /// not recorded on-chain, so it is derived as
+    /// `final balance + debits - credits` and must not be negative.
+    pub fn verify_account_chain(&self, address: &str) -> Result<ChainVerification, String> {
+        let state = self
+            .accounts
+            .get(address)
+            .ok_or_else(|| format!("Account {} not found", address))?;
+
+        // Head → genesis
+        let mut chain: Vec<(&str, &Block)> = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut current = state.head.as_str();
+        while current != "0" {
+            if !visited.insert(current) {
+                return Err(format!("Chain loop at block {}", current));
+            }
+            let blk = self.blocks.get(current).ok_or_else(|| {
+                format!(
+                    "Block {} missing ({} blocks below head)",
+                    current,
+                    chain.len()
+                )
+            })?;
+            chain.push((current, blk));
+            current = blk.previous.as_str();
+        }
+        if chain.len() as u64 != state.block_count {
+            return Err(format!(
+                "block_count is {} but the chain has {} blocks",
+                state.block_count,
+                chain.len()
+            ));
+        }
+        chain.reverse();
+
+        let debit = |b: &Block| match b.block_type {
+            BlockType::Send
+            | BlockType::ContractDeploy
+            | BlockType::ContractCall
+            | BlockType::Migrate => b.amount.saturating_add(b.fee),
+            BlockType::Slash => b.amount,
+            _ => 0,
+        };
+        let credit = |b: &Block| match b.block_type {
+            BlockType::Mint | BlockType::Receive | BlockType::Claim | BlockType::Reclaim => {
+                b.amount
+            }
+            _ => 0,
+        };
+        let credits = chain
+            .iter()
+            .fold(0u128, |acc, (_, b)| acc.saturating_add(credit(b)));
+        let debits = chain
+            .iter()
+            .fold(0u128, |acc, (_, b)| acc.saturating_add(debit(b)));
+        let opening_balance = state
+            .balance
+            .saturating_add(debits)
+            .checked_sub(credits)
+            .ok_or_else(|| {
+                format!(
+                    "Balance arithmetic: credits {} exceed final balance {} + debits {}",
+                    credits, state.balance, debits
+                )
+            })?;
+
+        // Genesis → head
+        let mut balance = opening_balance;
+        let mut prev_ts = 0u64;
+        for (i, (hash, blk)) in chain.iter().enumerate() {
+            let at = format!("block #{} ({})", i + 1, &hash[..hash.len().min(16)]);
+            if blk.calculate_hash() != *hash {
+                return Err(format!("{}: stored under the wrong hash", at));
+            }
+            if blk.account != address {
+                return Err(format!("{}: belongs to {}", at, blk.account));
+            }
+            // Contract payouts and migration credits are unsigned system records
+            // (contract_transfers.rs, key_migration.rs)
+            let system_receive = blk.is_contract_receive() || blk.is_migration_receive();
+            if !system_receive && !blk.verify_pow() {
+                return Err(format!("{}: invalid PoW", at));
+            }
+            if !system_receive && !blk.verify_signature() {
+                return Err(format!("{}: invalid signature", at));
+            }
+            if blk.timestamp < prev_ts {
+                return Err(format!(
+                    "{}: timestamp {} before previous block {}",
+                    at, blk.timestamp, prev_ts
+                ));
+            }
+            prev_ts = blk.timestamp;
+            if blk.is_migration_receive() {
+                self.verify_migration_receive(blk)
+                    .map_err(|e| format!("{}: {}", at, e))?;
+            } else if system_receive {
+                self.verify_contract_receive(blk)
+                    .map_err(|e| format!("{}: {}", at, e))?;
+            } else if blk.block_type == BlockType::Migrate {
+                blk.verify_migration_authorization()
+                    .map_err(|e| format!("{}: {}", at, e))?;
+            } else if blk.block_type == BlockType::Receive {
+                match self.blocks.get(&blk.link) {
+                    Some(send)
+                        if send.block_type == BlockType::Send
+                            && send.link == address
+                            && send.amount == blk.amount => {}
+                    Some(_) => {
+                        return Err(format!(
+                            "{}: linked Send {} does not match (type, recipient or amount)",
+                            at, blk.link
+                        ))
+                    }
+                    None => return Err(format!("{}: linked Send {} not found", at, blk.link)),
+                }
+            }
+            balance = if blk.block_type == BlockType::Slash {
+                balance.saturating_sub(blk.amount)
+            } else {
+                balance
+                    .checked_sub(debit(blk))
+                    .ok_or_else(|| format!("{}: balance {} < debit {}", at, balance, debit(blk)))?
+                    .saturating_add(credit(blk))
+            };
+        }
+        if balance != state.balance {
+            return Err(format!(
+                "Replayed balance {} does not match stored balance {}",
+                balance, state.balance
+            ));
         }
+        Ok(ChainVerification {
+            address: address.to_string(),
+            blocks_checked: chain.len() as u64,
+            opening_balance,
+            final_balance: state.balance,
+        })
+    }
 
+    /// Validate and apply a block, checking its timestamp against the
+    /// process-wide consensus clock (see consensus_clock.rs).
+    pub fn process_block(&mut self, block: &Block) -> Result<ProcessResult, String> {
+        self.process_block_at(block, consensus_clock::now())
+    }
+
+    /// Validate and apply a block against an explicit consensus time.
+    /// Every node that agrees on `consensus_now` reaches the same verdict.
+    pub fn process_block_at(
+        &mut self,
+        block: &Block,
+        consensus_now: u64,
+    ) -> Result<ProcessResult, String> {
+        // 1-2. PoW + Dilithium5 signature
+        block.check_crypto()?;
+        self.apply_checked_block_at(block, consensus_now)
+    }
+
+    /// `process_block` for a block whose PoW and signature were already
+    /// verified (e.g. by a parallel verification pool): skips steps 1-2.
+    pub fn process_verified_block(
+        &mut self,
+        verified: CryptoVerified<'_>,
+    ) -> Result<ProcessResult, String> {
+        self.apply_checked_block_at(verified.0, consensus_clock::now())
+    }
+
+    /// Everything `process_block_at` does after the crypto checks
+    fn apply_checked_block_at(
+        &mut self,
+        block: &Block,
+        consensus_now: u64,
+    ) -> Result<ProcessResult, String> {
         // 3. ACCOUNT ↔ PUBLIC KEY BINDING (prevents fund theft)
         // For Send and Change blocks, the signer MUST be the account owner.
         // Receive/Mint/Slash are system-created (signed by node/validator, not account owner).
+        // A ContractCall may instead be signed by a session key the account
+        // granted (session_keys.rs).
+        let mut session_signer = None;
         if matches!(
             block.block_type,
             BlockType::Send
                 | BlockType::Change
                 | BlockType::ContractDeploy
                 | BlockType::ContractCall
+                | BlockType::Migrate
+                | BlockType::Claim
+                | BlockType::Reclaim
         ) {
             let pk_bytes = hex::decode(&block.public_key)
                 .map_err(|e| format!("Authorization Error: Invalid public_key hex: {}", e))?;
@@ -418,7 +769,14 @@ impl Ledger {
                 return Err("Authorization Error: public_key is empty".to_string());
             }
             let derived_address = los_crypto::public_key_to_address(&pk_bytes);
-            if derived_address != block.account {
+            if derived_address != block.account
+                && self
+                    .session_grant(&block.account, &derived_address)
+                    .is_some()
+            {
+                self.check_session_call(block, &derived_address)?;
+                session_signer = Some(derived_address);
+            } else if derived_address != block.account {
                 return Err(format!(
                     "Authorization Error: public_key derives to {} but account is {}. Only the account owner can create Send/Change blocks.",
                     derived_address, block.account
@@ -463,18 +821,12 @@ impl Ledger {
         }
 
         // 7. TIMESTAMP VALIDATION (Prevent timestamp manipulation)
+        // Uses consensus time, not the local wall clock, so skewed validators agree.
         {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-
-            const MAX_TIMESTAMP_DRIFT_SECS: u64 = 300; // 5 minutes max drift
-
-            if block.timestamp > now + MAX_TIMESTAMP_DRIFT_SECS {
+            if block.timestamp > consensus_now.saturating_add(MAX_TIMESTAMP_DRIFT_SECS) {
                 return Err(format!(
-                    "Block timestamp {} is too far in the future (now: {}, max drift: {}s)",
-                    block.timestamp, now, MAX_TIMESTAMP_DRIFT_SECS
+                    "Block timestamp {} is too far in the future (consensus time: {}, max drift: {}s)",
+                    block.timestamp, consensus_now, MAX_TIMESTAMP_DRIFT_SECS
                 ));
             }
 
@@ -491,7 +843,44 @@ impl Ledger {
             }
         }
 
+        // 7b. TIME LOCK: only Sends carry one; applied no earlier than valid_after
+        if block.valid_after != 0 {
+            if block.block_type != BlockType::Send {
+                return Err(format!(
+                    "Time-lock Error: valid_after is only allowed on Send blocks, not {:?}",
+                    block.block_type
+                ));
+            }
+            if block.is_time_locked(consensus_now) {
+                return Err(format!(
+                    "Time-lock Error: Send is not valid before {} (consensus time: {})",
+                    block.valid_after, consensus_now
+                ));
+            }
+        }
+
+        // 7c. EXPIRY: only Sends carry one, and it must leave a window to receive
+        if block.expires_at != 0 {
+            if block.block_type != BlockType::Send {
+                return Err(format!(
+                    "Expiry Error: expires_at is only allowed on Send blocks, not {:?}",
+                    block.block_type
+                ));
+            }
+            if block.expires_at <= block.valid_from() {
+                return Err(format!(
+                    "Expiry Error: expires_at {} must be after the Send becomes valid ({})",
+                    block.expires_at,
+                    block.valid_from()
+                ));
+            }
+        }
+
+        // Embedder policy hooks (validation_hooks.rs) — before any state change
+        self.run_block_validators(block, &state)?;
+
         // 8. TRANSACTION LOGIC BASED ON BLOCK TYPE
+        let mut migration_target = None;
         match block.block_type {
             BlockType::Mint => {
                 // FEE_REWARD blocks redistribute fees already collected from user balances.
@@ -499,6 +888,8 @@ impl Ledger {
                 // Without this distinction, every fee redistribution permanently decreases
                 // remaining_supply, causing supply deflation and eventually blocking PoW mints.
                 let is_fee_reward = block.link.starts_with("FEE_REWARD:");
+                // Epoch distributions need a validator quorum certificate (reward_cosign.rs)
+                let distribution_claim = self.check_distribution_mint(block)?;
 
                 // Check supply FIRST before modifying any state
                 // (skip for fee rewards — they come from accumulated fees, not remaining_supply)
@@ -506,27 +897,27 @@ impl Ledger {
                     return Err("Distribution Error: Supply exhausted!".to_string());
                 }
 
-                // SECURITY: Enforce max mint per block (1,000 LOS)
+                // SECURITY: Enforce max mint per block (chain spec; 1,000 LOS on mainnet)
                 // Prevents single entity from acquiring disproportionate supply
-                const MAX_MINT_PER_BLOCK: u128 = 1_000 * CIL_PER_LOS;
-                // Faucet blocks (FAUCET:TESTNET:*) are exempt ONLY on testnet builds.
-                // SECURITY: On mainnet build, nobody can bypass mint cap via link prefix.
+                let max_mint_per_block = chain_spec::active().max_mint_per_block_cil;
+                // Faucet blocks (FAUCET:TESTNET:*) are exempt ONLY on test networks.
+                // SECURITY: On mainnet, nobody can bypass mint cap via link prefix.
                 // System-generated blocks (REWARD:, FEE_REWARD:) are always exempt since amounts
                 // are algorithmically determined by the epoch reward/fee distribution logic.
                 let is_system_mint =
                     block.link.starts_with("REWARD:") || block.link.starts_with("FEE_REWARD:");
-                let is_faucet = if is_testnet_build() {
+                let is_faucet = if is_testnet() {
                     block.link.starts_with("FAUCET:")
                         || block.link.starts_with("TESTNET:")
                         || block.link.starts_with("Src:")
                 } else {
                     false // Mainnet: NO exemptions for user-initiated mints
                 };
-                if !is_system_mint && !is_faucet && block.amount > MAX_MINT_PER_BLOCK {
+                if !is_system_mint && !is_faucet && block.amount > max_mint_per_block {
                     return Err(format!(
                         "Mint cap: Mint amount {} CIL exceeds max {} LOS per block",
                         block.amount,
-                        MAX_MINT_PER_BLOCK / CIL_PER_LOS
+                        max_mint_per_block / CIL_PER_LOS
                     ));
                 }
 
@@ -540,6 +931,24 @@ impl Ledger {
                         .remaining_supply
                         .saturating_sub(block.amount);
                 }
+                if let Some(claim) = distribution_claim {
+                    self.claimed_sends.insert(claim);
+                }
+            }
+            BlockType::Claim => {
+                // Validator mints its certified payout (reward_claims.rs)
+                let claim = self.check_reward_claim(block)?;
+                state.balance = state.balance.saturating_add(block.amount);
+                self.distribution.remaining_supply = self
+                    .distribution
+                    .remaining_supply
+                    .saturating_sub(block.amount);
+                let claimed = self
+                    .reward_claims_cil
+                    .entry(block.account.clone())
+                    .or_insert(0);
+                *claimed = claimed.saturating_add(block.amount);
+                self.claimed_sends.insert(claim);
             }
             BlockType::Send => {
                 // Enforce minimum transaction fee to prevent zero-fee spam
@@ -590,12 +999,22 @@ impl Ledger {
                     }
                     // 4. Double-receive prevention:
                     // O(1) definitive check via claimed_sends BTreeSet (never pruned).
+                    // A reclaimed Send is in there too.
                     if self.claimed_sends.contains(&block.link) {
                         return Err(format!(
                             "Receive Error: Send block {} already received",
                             block.link
                         ));
                     }
+                    // 5. Expired Sends belong to the sender again (Reclaim).
+                    // Judged at this block's own (drift-bounded) timestamp, so
+                    // replaying the chain later gives the same verdict.
+                    if send_block.is_expired(block.timestamp) {
+                        return Err(format!(
+                            "Receive Error: Send block {} expired at {} (block time: {})",
+                            block.link, send_block.expires_at, block.timestamp
+                        ));
+                    }
                 } else {
                     return Err(format!(
                         "Receive Error: Referenced Send block {} not found in ledger",
@@ -606,6 +1025,57 @@ impl Ledger {
                 // All validations passed — credit balance
                 state.balance = state.balance.saturating_add(block.amount);
             }
+            BlockType::Reclaim => {
+                // Sender takes back its own expired Send that nobody received.
+                // claimed_sends makes Receive and Reclaim mutually exclusive.
+                let send_block = self.blocks.get(&block.link).ok_or_else(|| {
+                    format!(
+                        "Reclaim Error: Referenced Send block {} not found in ledger",
+                        block.link
+                    )
+                })?;
+                if send_block.block_type != BlockType::Send {
+                    return Err(format!(
+                        "Reclaim Error: Referenced block {} is not a Send block (type: {:?})",
+                        block.link, send_block.block_type
+                    ));
+                }
+                if send_block.account != block.account {
+                    return Err(format!(
+                        "Reclaim Error: Send block {} was sent by {}, not {}",
+                        block.link, send_block.account, block.account
+                    ));
+                }
+                if send_block.expires_at == 0 {
+                    return Err(format!(
+                        "Reclaim Error: Send block {} has no expiry",
+                        block.link
+                    ));
+                }
+                // Block time, like the Receive check above
+                if !send_block.is_expired(block.timestamp) {
+                    return Err(format!(
+                        "Reclaim Error: Send block {} expires at {} (block time: {})",
+                        block.link, send_block.expires_at, block.timestamp
+                    ));
+                }
+                if send_block.amount != block.amount {
+                    return Err(format!(
+                        "Reclaim Error: Amount mismatch. Send={}, Reclaim={}",
+                        send_block.amount, block.amount
+                    ));
+                }
+                if block.fee != 0 {
+                    return Err("Reclaim Error: fee must be 0".to_string());
+                }
+                if self.claimed_sends.contains(&block.link) {
+                    return Err(format!(
+                        "Reclaim Error: Send block {} was already received or reclaimed",
+                        block.link
+                    ));
+                }
+                state.balance = state.balance.saturating_add(block.amount);
+            }
             BlockType::Change => {
                 // Reject no-op Change blocks (anti-spam)
                 // Change block `link` should contain new representative address
@@ -616,17 +1086,15 @@ impl Ledger {
                 }
                 // Reject if representative is unchanged (no-op spam)
                 // No balance modification for Change blocks — only representative change
+                // (or a session key grant / revocation, applied below)
+                if session_keys::is_session_change(block) {
+                    self.check_session_change(block)?;
+                }
             }
             BlockType::ContractDeploy => {
                 // Contract deployment: deployer pays fee, optionally funds contract
-                // link format: "DEPLOY:{code_hash}" — bytecode hash for integrity verification
-                if !block.link.starts_with("DEPLOY:") {
-                    return Err("ContractDeploy Error: link must start with 'DEPLOY:'".to_string());
-                }
-                let code_hash = &block.link[7..]; // After "DEPLOY:"
-                if code_hash.is_empty() || code_hash.len() < 8 {
-                    return Err("ContractDeploy Error: invalid code hash in link field".to_string());
-                }
+                // link format: "DEPLOY:{code_hash}[:{salt}]" — bytecode hash for integrity verification
+                deploy_link::DeployLink::parse(&block.link)?;
                 // Fee validation (higher minimum than regular transactions)
                 if block.fee < MIN_DEPLOY_FEE_CIL {
                     return Err(format!(
@@ -646,6 +1114,7 @@ impl Ledger {
                 }
                 state.balance -= total_debit;
                 self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
+                self.contract_held_cil = self.contract_held_cil.saturating_add(block.amount);
             }
             BlockType::ContractCall => {
                 // Contract call: caller pays gas fee, optionally sends CIL to contract
@@ -668,6 +1137,8 @@ impl Ledger {
                         block.fee, MIN_CALL_FEE_CIL
                     ));
                 }
+                // Gas ceilings: per call and per account per epoch
+                let gas = self.check_contract_gas(block)?;
                 // Debit: fee + optional value transfer to contract
                 let total_debit = block
                     .amount
@@ -680,6 +1151,11 @@ impl Ledger {
                 }
                 state.balance -= total_debit;
                 self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
+                self.contract_held_cil = self.contract_held_cil.saturating_add(block.amount);
+                self.charge_contract_gas(&block.account, block.timestamp, gas);
+                if let Some(session_address) = &session_signer {
+                    self.charge_session(block, session_address);
+                }
             }
             BlockType::Slash => {
                 // Slash: penalty deduction for validator misbehavior
@@ -728,6 +1204,12 @@ impl Ledger {
                 // but must be accounted for so total supply doesn't silently shrink.
                 self.total_slashed_cil = self.total_slashed_cil.saturating_add(actual_slash);
             }
+            BlockType::Migrate => {
+                // Ed25519 → Dilithium5 sweep; the destination is credited below
+                migration_target = Some(self.check_migration(block, &state)?);
+                state.balance -= block.amount + block.fee; // Safe: == balance, checked
+                self.accumulated_fees_cil = self.accumulated_fees_cil.saturating_add(block.fee);
+            }
         }
 
         state.head = block_hash.clone();
@@ -737,9 +1219,15 @@ impl Ledger {
         self.blocks.insert(block_hash.clone(), block.clone());
 
         // Track claimed Sends for O(1) double-receive prevention
-        if block.block_type == BlockType::Receive {
+        if matches!(block.block_type, BlockType::Receive | BlockType::Reclaim) {
             self.claimed_sends.insert(block.link.clone());
         }
+        if let Some(new_address) = migration_target {
+            self.credit_migration(&block_hash, block, &new_address);
+        }
+        if session_keys::is_session_change(block) {
+            self.apply_session_change(block);
+        }
 
         Ok(ProcessResult::Applied(block_hash))
     }
@@ -756,7 +1244,8 @@ impl Ledger {
 
     /// DESIGN Supply invariant audit.
     ///
-    /// Verifies: sum(all_balances) + remaining_supply + total_slashed + accumulated_fees == EXPECTED_TOTAL
+    /// Verifies: sum(all_balances) + remaining_supply + total_slashed + accumulated_fees
+    ///           + contract_held == EXPECTED_TOTAL
     ///
     /// `reward_pool_remaining_cil`: remaining CIL in the validator reward pool.
     /// `reward_pool_distributed_cil`: total CIL already distributed from the reward pool.
@@ -791,6 +1280,7 @@ impl Ledger {
         // + unminted supply in distribution (includes undistributed reward pool)
         // + permanently removed via slash
         // + fees collected but not yet redistributed
+        // + held in smart contract balances
         //
         // NOTE: reward_pool_remaining_cil is NOT added here because those tokens
         // are already counted within distribution.remaining_supply. The reward pool
@@ -801,14 +1291,15 @@ impl Ledger {
         let accounted = balance_sum
             .saturating_add(remaining_supply)
             .saturating_add(self.total_slashed_cil)
-            .saturating_add(self.accumulated_fees_cil);
+            .saturating_add(self.accumulated_fees_cil)
+            .saturating_add(self.contract_held_cil);
 
         if accounted == total_supply_cil {
             Ok(())
         } else if accounted > total_supply_cil {
             Err(format!(
                 "Supply audit FAILED: accounted {} > total {} (inflation of {} CIL). \
-                balances={}, remaining={}, slashed={}, fees={}, reward_pool_remaining={}",
+                balances={}, remaining={}, slashed={}, fees={}, contracts={}, reward_pool_remaining={}",
                 accounted,
                 total_supply_cil,
                 accounted - total_supply_cil,
@@ -816,12 +1307,13 @@ impl Ledger {
                 remaining_supply,
                 self.total_slashed_cil,
                 self.accumulated_fees_cil,
+                self.contract_held_cil,
                 reward_pool_remaining_cil,
             ))
         } else {
             Err(format!(
                 "Supply audit FAILED: accounted {} < total {} (deflation of {} CIL). \
-                balances={}, remaining={}, slashed={}, fees={}, reward_pool_remaining={}",
+                balances={}, remaining={}, slashed={}, fees={}, contracts={}, reward_pool_remaining={}",
                 accounted,
                 total_supply_cil,
                 total_supply_cil - accounted,
@@ -829,6 +1321,7 @@ impl Ledger {
                 remaining_supply,
                 self.total_slashed_cil,
                 self.accumulated_fees_cil,
+                self.contract_held_cil,
                 reward_pool_remaining_cil,
             ))
         }
@@ -871,12 +1364,14 @@ mod wallet_send_tests {
             work,
             timestamp,
             fee,
+            valid_after: 0,
+            expires_at: 0,
         };
 
         // 3. Compute signing_hash (same as backend verify_signature path)
         let signing_hash = blk.signing_hash();
         println!("signing_hash = {}", signing_hash);
-        println!("CHAIN_ID = {}", CHAIN_ID);
+        println!("chain_id = {}", chain_id());
 
         // 4. Sign the signing_hash as bytes (same as Flutter: utf8.encode(signingHash))
         let sig_bytes = los_crypto::sign_message(signing_hash.as_bytes(), &keypair.secret_key)
@@ -918,12 +1413,14 @@ mod wallet_send_tests {
             work,
             timestamp,
             fee,
+            valid_after: 0,
+            expires_at: 0,
         };
         let backend_hash = blk.signing_hash();
 
         // Flutter approach: manual serialization (matches _minePoWInIsolate buffer)
         let mut hasher = Sha3_256::new();
-        hasher.update(CHAIN_ID.to_le_bytes()); // chain_id (u64 LE)
+        hasher.update(chain_id().to_le_bytes()); // chain_id (u64 LE)
         hasher.update(from.as_bytes()); // account
         hasher.update(prev.as_bytes()); // previous
         hasher.update([0u8]); // block_type = Send = 0
@@ -945,3 +1442,243 @@ mod wallet_send_tests {
         println!("✅ signing_hash field order matches Flutter exactly");
     }
 }
+
+#[cfg(test)]
+mod chain_verify_tests {
+    use super::*;
+
+    fn block(account: &str, previous: &str, block_type: BlockType, amount: u128) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: previous.to_string(),
+            block_type,
+            amount,
+            link: "LOSX84MQjCL6ZaGCktyUxjj11XZ12Jkqq4JYR".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: 100_000,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    /// Insert a chain directly (as persisted state would be loaded)
+    fn ledger_with(account: &str, blocks: &[Block], balance: u128) -> Ledger {
+        let mut l = Ledger::new();
+        let mut head = "0".to_string();
+        for b in blocks {
+            head = b.calculate_hash();
+            l.blocks.insert(head.clone(), b.clone());
+        }
+        l.accounts.insert(
+            account.to_string(),
+            AccountState {
+                head,
+                balance,
+                block_count: blocks.len() as u64,
+                is_validator: false,
+            },
+        );
+        l
+    }
+
+    #[test]
+    fn test_verify_account_chain_structural_errors() {
+        let a = "LOSWverify";
+        let mint = block(a, "0", BlockType::Mint, 1_000_000);
+        let send = block(a, &mint.calculate_hash(), BlockType::Send, 3);
+
+        // Unsigned blocks: structure passes, first PoW/signature check reports
+        let l = ledger_with(a, &[mint.clone(), send.clone()], 1_000_000 - 100_003);
+        let err = l.verify_account_chain(a).unwrap_err();
+        assert!(err.contains("block #1") && err.contains("PoW"), "{err}");
+
+        // Credits that the final balance cannot explain
+        let l = ledger_with(a, std::slice::from_ref(&mint), 0);
+        assert!(l.verify_account_chain(a).unwrap_err().contains("credits"));
+
+        // block_count out of sync with the chain
+        let mut l = ledger_with(a, &[mint.clone(), send.clone()], 0);
+        l.accounts.get_mut(a).unwrap().block_count = 5;
+        assert!(l
+            .verify_account_chain(a)
+            .unwrap_err()
+            .contains("block_count"));
+
+        // Missing block below head
+        let mut l = ledger_with(a, &[mint.clone(), send.clone()], 0);
+        l.blocks.remove(&mint.calculate_hash());
+        let err = l.verify_account_chain(a).unwrap_err();
+        assert!(err.contains("missing (1 blocks below head)"), "{err}");
+
+        // Block body altered after it was stored
+        let mut l = ledger_with(a, std::slice::from_ref(&mint), 1_000_000);
+        l.blocks.get_mut(&mint.calculate_hash()).unwrap().amount = 11;
+        assert!(l
+            .verify_account_chain(a)
+            .unwrap_err()
+            .contains("wrong hash"));
+
+        assert!(l.verify_account_chain("LOSnobody").is_err());
+    }
+
+    #[test]
+    fn test_time_locked_send() {
+        let keys = los_crypto::generate_keypair_from_seed(&[3u8; 64]);
+        let a = los_crypto::public_key_to_address(&keys.public_key);
+        let mint = block(&a, "0", BlockType::Mint, 1_000_000);
+        let mut l = ledger_with(&a, std::slice::from_ref(&mint), 1_000_000);
+        let mut send = block(&a, &mint.calculate_hash(), BlockType::Send, 3);
+        send.public_key = hex::encode(&keys.public_key);
+
+        // The lock is signed, but unlocked blocks keep their old hash
+        let unlocked = send.signing_hash();
+        send.valid_after = send.timestamp + 3_600;
+        assert_ne!(send.signing_hash(), unlocked);
+        assert!(!serde_json::to_string(&mint)
+            .unwrap()
+            .contains("valid_after"));
+
+        // Crypto checks are covered elsewhere; apply the ledger rules only
+        let err = l
+            .apply_checked_block_at(&send, send.valid_after - 1)
+            .unwrap_err();
+        assert!(err.contains("Time-lock"), "{err}");
+        assert!(l.apply_checked_block_at(&send, send.valid_after).is_ok());
+        assert_eq!(l.accounts[&a].balance, 1_000_000 - 3 - 100_000);
+
+        // Only Sends may carry a lock
+        let mut change = block(&a, &send.calculate_hash(), BlockType::Change, 0);
+        change.public_key = send.public_key.clone();
+        change.valid_after = 1;
+        let err = l
+            .apply_checked_block_at(&change, change.timestamp)
+            .unwrap_err();
+        assert!(err.contains("only allowed on Send"), "{err}");
+    }
+
+    #[test]
+    fn test_expired_send_is_reclaimed_once() {
+        let keys = los_crypto::generate_keypair_from_seed(&[3u8; 64]);
+        let a = los_crypto::public_key_to_address(&keys.public_key);
+        let pk = hex::encode(&keys.public_key);
+        let mint = block(&a, "0", BlockType::Mint, 1_000_000);
+        let mut l = ledger_with(&a, std::slice::from_ref(&mint), 1_000_000);
+        let mut send = block(&a, &mint.calculate_hash(), BlockType::Send, 3);
+        send.public_key = pk.clone();
+        send.expires_at = send.timestamp + 3_600;
+        let send_hash = send.calculate_hash();
+        l.apply_checked_block_at(&send, send.timestamp).unwrap();
+
+        let mut reclaim = block(&a, &send_hash, BlockType::Reclaim, 3);
+        reclaim.public_key = pk;
+        reclaim.link = send_hash.clone();
+        reclaim.fee = 0;
+        reclaim.timestamp = send.expires_at - 1;
+        let err = l
+            .apply_checked_block_at(&reclaim, send.expires_at)
+            .unwrap_err();
+        assert!(err.contains("expires at"), "{err}");
+
+        // From expires_at on, the recipient can no longer receive it
+        let mut receive = block(&send.link, "0", BlockType::Receive, 3);
+        receive.link = send_hash.clone();
+        receive.timestamp = send.expires_at;
+        let err = l
+            .apply_checked_block_at(&receive, send.expires_at)
+            .unwrap_err();
+        assert!(err.contains("expired"), "{err}");
+
+        reclaim.timestamp = send.expires_at;
+        l.apply_checked_block_at(&reclaim, send.expires_at).unwrap();
+        assert_eq!(l.accounts[&a].balance, 1_000_000 - 100_000);
+        assert!(l.claimed_sends.contains(&send_hash));
+
+        // No second refund
+        let mut again = reclaim.clone();
+        again.previous = reclaim.calculate_hash();
+        let err = l
+            .apply_checked_block_at(&again, send.expires_at)
+            .unwrap_err();
+        assert!(err.contains("already received or reclaimed"), "{err}");
+
+        // Only Sends may carry an expiry
+        let mut change = block(&a, &reclaim.calculate_hash(), BlockType::Change, 0);
+        change.public_key = send.public_key.clone();
+        change.timestamp = reclaim.timestamp;
+        change.expires_at = change.timestamp + 1;
+        let err = l
+            .apply_checked_block_at(&change, change.timestamp)
+            .unwrap_err();
+        assert!(err.contains("only allowed on Send"), "{err}");
+    }
+
+    #[test]
+    fn test_expiry_replay_uses_block_time() {
+        // Sync replays history long after it was made: the verdict must not
+        // depend on when the block is applied
+        let keys = los_crypto::generate_keypair_from_seed(&[3u8; 64]);
+        let a = los_crypto::public_key_to_address(&keys.public_key);
+        let mint = block(&a, "0", BlockType::Mint, 1_000_000);
+        let mut l = ledger_with(&a, std::slice::from_ref(&mint), 1_000_000);
+        let mut send = block(&a, &mint.calculate_hash(), BlockType::Send, 3);
+        send.public_key = hex::encode(&keys.public_key);
+        send.expires_at = send.timestamp + 3_600;
+        let send_hash = send.calculate_hash();
+        let replay_now = send.expires_at + 86_400;
+        l.apply_checked_block_at(&send, replay_now).unwrap();
+
+        // A Reclaim dated before the expiry stays invalid when replayed late
+        let mut early_reclaim = block(&a, &send_hash, BlockType::Reclaim, 3);
+        early_reclaim.public_key = send.public_key.clone();
+        early_reclaim.link = send_hash.clone();
+        early_reclaim.fee = 0;
+        early_reclaim.timestamp = send.expires_at - 1;
+        let err = l
+            .apply_checked_block_at(&early_reclaim, replay_now)
+            .unwrap_err();
+        assert!(err.contains("expires at"), "{err}");
+
+        // The Receive made in time is still accepted...
+        let mut receive = block(&send.link, "0", BlockType::Receive, 3);
+        receive.link = send_hash.clone();
+        receive.timestamp = send.expires_at - 1;
+        l.apply_checked_block_at(&receive, replay_now).unwrap();
+        assert_eq!(l.accounts[&send.link].balance, 3);
+
+        // ...so the sender cannot reclaim it afterwards
+        let mut late_reclaim = early_reclaim.clone();
+        late_reclaim.timestamp = send.expires_at;
+        let err = l
+            .apply_checked_block_at(&late_reclaim, replay_now)
+            .unwrap_err();
+        assert!(err.contains("already received or reclaimed"), "{err}");
+    }
+
+    #[test]
+    fn test_find_fork_second_successor() {
+        let a = "LOSWfork";
+        let mint = block(a, "0", BlockType::Mint, 1_000_000);
+        let send = block(a, &mint.calculate_hash(), BlockType::Send, 3);
+        let l = ledger_with(a, &[mint.clone(), send.clone()], 0);
+
+        // Another successor of the mint block conflicts with `send`
+        let rival = block(a, &mint.calculate_hash(), BlockType::Send, 4);
+        let (hash, existing) = l.find_fork(&rival).unwrap();
+        assert_eq!(hash, send.calculate_hash());
+        assert_eq!(existing.amount, 3);
+        // A rival opening block conflicts with the mint
+        assert!(l.find_fork(&block(a, "0", BlockType::Mint, 5)).is_some());
+
+        // Re-delivery of a known block and a normal extension are not forks
+        assert!(l.find_fork(&send).is_none());
+        assert!(l
+            .find_fork(&block(a, &send.calculate_hash(), BlockType::Send, 1))
+            .is_none());
+        assert!(l
+            .find_fork(&block("LOSnobody", "0", BlockType::Mint, 1))
+            .is_none());
+    }
+}
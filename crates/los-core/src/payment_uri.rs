@@ -0,0 +1,233 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — PAYMENT REQUEST URIs
+//
+//   los:<address>?amount=<LOS>&label=<text>&message=<text>&contract=<addr>
+//
+// - amount is decimal LOS (at most 11 fractional digits, the CIL precision).
+//   With `contract`, it is the token amount in the token's base units.
+// - label / message are percent-encoded UTF-8
+// - contract names a USP-01 token: pay in that token instead of LOS
+// - unknown parameters are ignored, except `req-*` ones, which a wallet
+//   must understand to pay correctly — those make the URI invalid (BIP21)
+//
+// Wallets, the CLI and the node's invoice endpoint (POST /invoice) share
+// this parser/generator so that a URI means the same thing everywhere.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::units::{Cil, Los, Rounding};
+use serde::{Deserialize, Serialize};
+
+/// URI scheme (without the colon)
+pub const PAYMENT_URI_SCHEME: &str = "los";
+
+/// A parsed payment request
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentRequest {
+    pub address: String,
+    /// CIL for LOS payments, token base units when `contract` is set
+    pub amount: Option<u128>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    /// USP-01 token contract to pay with
+    pub contract: Option<String>,
+}
+
+impl PaymentRequest {
+    pub fn new(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Render as `los:<address>?...` (parameters in a fixed order)
+    pub fn to_uri(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(amount) = self.amount {
+            params.push(format!(
+                "amount={}",
+                if self.contract.is_some() {
+                    amount.to_string()
+                } else {
+                    format_los(amount)
+                }
+            ));
+        }
+        for (key, value) in [
+            ("label", &self.label),
+            ("message", &self.message),
+            ("contract", &self.contract),
+        ] {
+            if let Some(v) = value {
+                params.push(format!("{}={}", key, percent_encode(v)));
+            }
+        }
+        let mut uri = format!("{}:{}", PAYMENT_URI_SCHEME, self.address);
+        if !params.is_empty() {
+            uri.push('?');
+            uri.push_str(&params.join("&"));
+        }
+        uri
+    }
+
+    /// Parse a payment URI. The address must be a valid LOS address.
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .trim()
+            .split_once(':')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(PAYMENT_URI_SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or_else(|| format!("Payment URI must start with '{}:'", PAYMENT_URI_SCHEME))?;
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let address = address.trim_start_matches("//");
+        if !los_crypto::validate_address(address) {
+            return Err(format!("Invalid address '{}'", address));
+        }
+
+        let mut req = Self::new(address);
+        let mut raw_amount = None;
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value)?;
+            let slot = match key {
+                "amount" => &mut raw_amount,
+                "label" => &mut req.label,
+                "message" => &mut req.message,
+                "contract" => &mut req.contract,
+                k if k.starts_with("req-") => {
+                    return Err(format!("Unsupported required parameter '{}'", k))
+                }
+                _ => continue,
+            };
+            if slot.replace(value).is_some() {
+                return Err(format!("Duplicate parameter '{}'", key));
+            }
+        }
+        if let Some(contract) = &req.contract {
+            if !los_crypto::validate_address(contract) {
+                return Err(format!("Invalid contract address '{}'", contract));
+            }
+        }
+        req.amount = match raw_amount {
+            Some(a) if req.contract.is_some() => Some(
+                a.parse::<u128>()
+                    .map_err(|_| format!("Invalid token amount '{}'", a))?,
+            ),
+            Some(a) => Some(parse_los(&a)?),
+            None => None,
+        };
+        Ok(req)
+    }
+}
+
+/// Strict decimal LOS → CIL (rejects signs, exponents and excess precision)
+pub fn parse_los(s: &str) -> Result<u128, String> {
+    Los::parse(s, Rounding::Exact).map(|los| los.cil().0)
+}
+
+/// CIL → shortest decimal LOS ("1.5", "0.00000000001", "42")
+pub fn format_los(cil: u128) -> String {
+    Cil(cil).to_los().to_string()
+}
+
+/// RFC 3986 percent-encoding (unreserved characters pass through)
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+fn percent_decode(s: &str) -> Result<String, String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = s
+                    .get(i + 1..i + 3)
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                    .ok_or_else(|| format!("Bad percent-encoding in '{}'", s))?;
+                out.push(hex);
+                i += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).map_err(|_| format!("Parameter is not UTF-8: '{}'", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CIL_PER_LOS;
+
+    const ADDR: &str = "LOSX7dStdPkS9U4MFCmDQfpmvrbMa5WAZfQX1";
+
+    #[test]
+    fn test_uri_roundtrip() {
+        let req = PaymentRequest {
+            address: ADDR.to_string(),
+            amount: Some(150_000_000_000 + 1),
+            label: Some("Coffee & Co".to_string()),
+            message: Some("Order #42 ☕".to_string()),
+            contract: None,
+        };
+        let uri = req.to_uri();
+        assert_eq!(
+            uri,
+            format!(
+                "los:{}?amount=1.50000000001&label=Coffee%20%26%20Co&message=Order%20%2342%20%E2%98%95",
+                ADDR
+            )
+        );
+        assert_eq!(PaymentRequest::parse(&uri).unwrap(), req);
+
+        let bare = PaymentRequest::parse(&format!("LOS:{}", ADDR)).unwrap();
+        assert_eq!(bare, PaymentRequest::new(ADDR));
+        assert_eq!(bare.to_uri(), format!("los:{}", ADDR));
+
+        // Token payments carry base units; unknown optional params are ignored
+        let token = PaymentRequest::parse(&format!(
+            "los:{}?contract={}&amount=2500&foo=bar&label=a+b",
+            ADDR, ADDR
+        ))
+        .unwrap();
+        assert_eq!(token.amount, Some(2500));
+        assert_eq!(token.label.as_deref(), Some("a b"));
+    }
+
+    #[test]
+    fn test_uri_rejects_invalid() {
+        for bad in [
+            format!("bitcoin:{}", ADDR),
+            "los:LOSnotanaddress".to_string(),
+            format!("los:{}?amount=1.000000000001", ADDR),
+            format!("los:{}?amount=-1", ADDR),
+            format!("los:{}?amount=1e5", ADDR),
+            format!("los:{}?amount=1&amount=2", ADDR),
+            format!("los:{}?req-expiry=60", ADDR),
+            format!("los:{}?label=%ZZ", ADDR),
+            format!("los:{}?contract=nope", ADDR),
+        ] {
+            assert!(PaymentRequest::parse(&bad).is_err(), "{}", bad);
+        }
+        assert_eq!(parse_los("0.00000000001"), Ok(1));
+        assert_eq!(format_los(42 * CIL_PER_LOS), "42");
+        assert!(parse_los(&u128::MAX.to_string()).is_err());
+    }
+}
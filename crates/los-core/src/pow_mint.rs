@@ -35,13 +35,9 @@ pub const MINING_EPOCH_SECS: u64 = 3600; // 1 hour
 /// Short for rapid testing of mining mechanics.
 pub const TESTNET_MINING_EPOCH_SECS: u64 = 120; // 2 minutes
 
-/// Get the effective mining epoch duration based on network type.
-pub const fn effective_mining_epoch_secs() -> u64 {
-    if crate::is_testnet_build() {
-        TESTNET_MINING_EPOCH_SECS
-    } else {
-        MINING_EPOCH_SECS
-    }
+/// Get the effective mining epoch duration from the running chain spec.
+pub fn effective_mining_epoch_secs() -> u64 {
+    crate::chain_spec::active().mining_epoch_secs
 }
 
 /// Initial mining reward per epoch: 100 LOS (split among all miners).
@@ -59,13 +55,9 @@ pub const MINING_HALVING_INTERVAL_EPOCHS: u64 = 8_760;
 /// Testnet halving interval: 10 epochs (≈20 minutes with 2-min testnet epochs).
 pub const TESTNET_MINING_HALVING_INTERVAL_EPOCHS: u64 = 10;
 
-/// Get the effective mining halving interval based on network type.
-pub const fn effective_mining_halving_interval() -> u64 {
-    if crate::is_testnet_build() {
-        TESTNET_MINING_HALVING_INTERVAL_EPOCHS
-    } else {
-        MINING_HALVING_INTERVAL_EPOCHS
-    }
+/// Get the effective mining halving interval from the running chain spec.
+pub fn effective_mining_halving_interval() -> u64 {
+    crate::chain_spec::active().mining_halving_interval_epochs
 }
 
 /// Initial mining difficulty: 20 leading zero bits.
@@ -76,13 +68,9 @@ pub const INITIAL_MINING_DIFFICULTY_BITS: u32 = 20;
 /// Testnet initial difficulty: 16 bits (easier for quick testing).
 pub const TESTNET_INITIAL_MINING_DIFFICULTY_BITS: u32 = 16;
 
-/// Get the initial mining difficulty for the current network type.
-pub const fn initial_mining_difficulty() -> u32 {
-    if crate::is_testnet_build() {
-        TESTNET_INITIAL_MINING_DIFFICULTY_BITS
-    } else {
-        INITIAL_MINING_DIFFICULTY_BITS
-    }
+/// Get the initial mining difficulty from the running chain spec.
+pub fn initial_mining_difficulty() -> u32 {
+    crate::chain_spec::active().initial_mining_difficulty_bits
 }
 
 /// Minimum mining difficulty (floor).
@@ -161,6 +149,9 @@ pub struct MiningState {
     pub prev_epoch_miners_count: u32,
     /// Genesis timestamp (used to calculate epoch number).
     pub genesis_timestamp: u64,
+    /// Pinned difficulty that epoch adjustment never changes (local dev chains only).
+    #[serde(default)]
+    pub fixed_difficulty: Option<u32>,
 }
 
 impl MiningState {
@@ -172,9 +163,17 @@ impl MiningState {
             current_epoch: 0,
             prev_epoch_miners_count: 0,
             genesis_timestamp,
+            fixed_difficulty: None,
         }
     }
 
+    /// Pin the difficulty (bypasses MIN_MINING_DIFFICULTY_BITS and epoch adjustment).
+    /// Used by `los-node --dev`, where mining should succeed on the first hash.
+    pub fn pin_difficulty(&mut self, bits: u32) {
+        self.fixed_difficulty = Some(bits);
+        self.difficulty_bits = bits;
+    }
+
     /// Calculate the current epoch number from the current time.
     pub fn epoch_from_time(&self, now_secs: u64) -> u64 {
         if now_secs <= self.genesis_timestamp {
@@ -200,7 +199,7 @@ impl MiningState {
         if halvings >= 64 {
             return 0; // Prevent overflow in shift
         }
-        MINING_REWARD_PER_EPOCH_CIL >> halvings
+        crate::chain_spec::active().mining_reward_per_epoch_cil >> halvings
     }
 
     /// Advance to a new epoch: adjust difficulty and reset miners.
@@ -214,7 +213,9 @@ impl MiningState {
         let miners = self.current_epoch_miners.len() as u32;
         self.prev_epoch_miners_count = miners;
 
-        if miners > TARGET_MINERS_PER_EPOCH * 2 {
+        if let Some(bits) = self.fixed_difficulty {
+            self.difficulty_bits = bits;
+        } else if miners > TARGET_MINERS_PER_EPOCH * 2 {
             // Way too many miners → increase difficulty (harder)
             let adjustment = ((miners / TARGET_MINERS_PER_EPOCH).ilog2() + 1)
                 .min(MAX_DIFFICULTY_ADJUSTMENT_BITS);
@@ -318,8 +319,8 @@ impl MiningState {
             return Err("Public supply exhausted".to_string());
         }
 
-        // Also cap at MAX_MINT_PER_BLOCK (1,000 LOS) to comply with consensus rule
-        let max_mint = 1_000 * CIL_PER_LOS;
+        // Also cap at the chain spec's max mint per block to comply with consensus rule
+        let max_mint = crate::chain_spec::active().max_mint_per_block_cil;
         let final_reward = final_reward.min(max_mint);
 
         // 6. Register this miner
@@ -339,7 +340,7 @@ impl MiningState {
             remaining_supply_cil,
             epoch_remaining_secs: self.epoch_remaining_secs(now_secs),
             miners_this_epoch: self.current_epoch_miners.len() as u32,
-            chain_id: crate::CHAIN_ID,
+            chain_id: crate::chain_id(),
         }
     }
 }
@@ -355,7 +356,7 @@ pub fn compute_mining_hash(address: &str, epoch: u64, nonce: u64) -> [u8; 32] {
     // Domain separator to prevent collision with block hashes
     hasher.update(b"LOS_MINE_V1");
     // Chain ID prevents cross-network proof replay
-    hasher.update(crate::CHAIN_ID.to_le_bytes());
+    hasher.update(crate::chain_id().to_le_bytes());
     // Address binds proof to owner — front-run resistant
     hasher.update(address.as_bytes());
     // Epoch binds proof to time window
@@ -573,6 +574,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pinned_difficulty_survives_epochs() {
+        let mut state = MiningState::new(1_000_000);
+        state.pin_difficulty(0);
+        for i in 0..30 {
+            state
+                .current_epoch_miners
+                .insert(format!("LOS_miner_{}", i));
+        }
+        state.advance_epoch(1);
+        assert_eq!(state.difficulty_bits, 0);
+        state.advance_epoch(2);
+        assert_eq!(state.difficulty_bits, 0);
+        assert!(verify_mining_hash("LOS_any", 2, 0, state.difficulty_bits));
+    }
+
     #[test]
     fn test_verify_proof_basic() {
         let genesis = 1_000_000u64;
@@ -729,7 +746,7 @@ mod tests {
         assert_eq!(info.difficulty_bits, initial_mining_difficulty());
         assert_eq!(info.reward_per_epoch_cil, MINING_REWARD_PER_EPOCH_CIL);
         assert_eq!(info.remaining_supply_cil, supply);
-        assert_eq!(info.chain_id, crate::CHAIN_ID);
+        assert_eq!(info.chain_id, crate::chain_id());
     }
 
     #[test]
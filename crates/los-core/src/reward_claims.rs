@@ -0,0 +1,265 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CLAIM-BASED VALIDATOR REWARDS
+//
+// ChainSpec::reward_payout selects how certified epoch rewards are paid:
+//
+//   push  (default, mainnet) — the epoch leader mints one REWARD:EPOCH: Mint
+//         block per validator once the distribution is co-signed
+//         (reward_cosign.rs). Every epoch ends with a burst of Mint blocks.
+//   claim — the leader gossips the certificate instead:
+//           REWARD_ACCRUE:{certificate json}
+//         Every node verifies it against the validator set, credits the
+//         payouts to per-validator accrual accounts in ValidatorRewardPool and
+//         keeps the certificate. A validator mints its share of an epoch
+//         whenever it chooses, with a Claim block it signs itself:
+//           block_type = Claim, amount = its certified payout,
+//           link = "CLAIM:REWARD:{certificate json}"
+//
+// The ledger checks a Claim like a REWARD mint (reward_cosign.rs): the
+// certificate must carry a validator quorum and pay exactly `amount` to the
+// claiming account, and each payout is minted once — by Mint or by Claim —
+// through its claimed_sends key. Every replay reaches the same verdict
+// without the node's pool. Claims deduct remaining_supply like reward mints;
+// fee redistribution is always pushed.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::chain_spec::{self, RewardPayout};
+use crate::reward_cosign::{
+    parse_distribution_link, payout_claim_key, DistributionCertificate, DistributionKind,
+    EpochDistribution,
+};
+use crate::{Block, BlockType, Ledger};
+
+/// Link prefix of every Claim block, followed by ':' and the certificate
+pub const CLAIM_LINK: &str = "CLAIM:REWARD";
+/// Gossip prefix: REWARD_ACCRUE:{DistributionCertificate json}
+pub const ACCRUE_PREFIX: &str = "REWARD_ACCRUE:";
+
+/// Claim block link minting a payout of `certificate`
+pub fn claim_link(certificate: &DistributionCertificate) -> String {
+    format!(
+        "{}:{}",
+        CLAIM_LINK,
+        serde_json::to_string(certificate).unwrap_or_default()
+    )
+}
+
+impl Ledger {
+    /// Validate a Claim block. Returns the claimed_sends key to mark once
+    /// the block is applied.
+    pub(crate) fn check_reward_claim(&self, block: &Block) -> Result<String, String> {
+        if chain_spec::active().reward_payout != RewardPayout::Claim {
+            return Err(
+                "Claim Error: this network pushes validator rewards (reward_payout = push)"
+                    .to_string(),
+            );
+        }
+        self.check_claim_certificate(block)
+    }
+
+    /// Claim rules that hold under any payout model
+    fn check_claim_certificate(&self, block: &Block) -> Result<String, String> {
+        let certificate = block
+            .link
+            .strip_prefix(CLAIM_LINK)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| format!("Claim Error: link must be '{}:{{certificate}}'", CLAIM_LINK))?;
+        if block.amount == 0 || block.fee != 0 {
+            return Err("Claim Error: amount must be non-zero and fee zero".to_string());
+        }
+        let cert: DistributionCertificate = serde_json::from_str(certificate)
+            .map_err(|e| format!("Claim Error: invalid certificate: {}", e))?;
+        let dist = &cert.distribution;
+        if dist.kind != DistributionKind::Reward {
+            return Err("Claim Error: only reward distributions are claimed".to_string());
+        }
+        if dist.payouts.get(&block.account) != Some(&block.amount) {
+            return Err(format!(
+                "Claim Error: {} CIL to {} is not in the certified payouts",
+                block.amount, block.account
+            ));
+        }
+        let claim = payout_claim_key(dist, &block.account);
+        if self.claimed_sends.contains(&claim) {
+            return Err("Claim Error: payout already claimed".to_string());
+        }
+        cert.verify(&self.reward_validator_set())
+            .map_err(|e| format!("Claim Error: {}", e))?;
+        if self.distribution.remaining_supply < block.amount {
+            return Err("Distribution Error: Supply exhausted!".to_string());
+        }
+        Ok(claim)
+    }
+
+    /// Whether `account`'s payout of `distribution` was already minted
+    pub fn is_payout_claimed(&self, distribution: &EpochDistribution, account: &str) -> bool {
+        self.claimed_sends
+            .contains(&payout_claim_key(distribution, account))
+    }
+
+    /// Total CIL `address` minted with Claim blocks
+    pub fn reward_claimed_cil(&self, address: &str) -> u128 {
+        self.reward_claims_cil.get(address).copied().unwrap_or(0)
+    }
+
+    /// Recompute claimed totals from Claim blocks, and the claimed_sends
+    /// keys of certified payouts (after loading a ledger)
+    pub fn rebuild_reward_claims(&mut self) {
+        self.reward_claims_cil.clear();
+        let mut paid = Vec::new();
+        for block in self.blocks.values() {
+            let certificate = match block.block_type {
+                BlockType::Claim => {
+                    let claimed = self
+                        .reward_claims_cil
+                        .entry(block.account.clone())
+                        .or_insert(0);
+                    *claimed = claimed.saturating_add(block.amount);
+                    block
+                        .link
+                        .strip_prefix(CLAIM_LINK)
+                        .and_then(|rest| rest.strip_prefix(':'))
+                }
+                BlockType::Mint => parse_distribution_link(&block.link).map(|(_, _, c)| c),
+                _ => None,
+            };
+            if let Some(cert) =
+                certificate.and_then(|c| serde_json::from_str::<DistributionCertificate>(c).ok())
+            {
+                paid.push(payout_claim_key(&cert.distribution, &block.account));
+            }
+        }
+        self.claimed_sends.extend(paid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reward_cosign::CoSignature;
+    use crate::validator_rewards::ValidatorRewardPool;
+    use crate::{AccountState, MIN_VALIDATOR_STAKE_CIL};
+
+    fn claim(account: &str, amount: u128, link: String) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Claim,
+            amount,
+            link,
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    fn rewards(epoch: u64, payouts: &[(&str, u128)]) -> DistributionCertificate {
+        DistributionCertificate {
+            distribution: EpochDistribution::new(
+                DistributionKind::Reward,
+                epoch,
+                payouts.iter().map(|(a, v)| (a.to_string(), *v)),
+            ),
+            signatures: vec![],
+        }
+    }
+
+    #[test]
+    fn test_accrual_is_once_per_epoch() {
+        let mut pool = ValidatorRewardPool::with_balance(0, 1_000);
+        pool.register_validator("LOSa", false, 1);
+        pool.register_validator("LOSb", false, 1);
+        let root = pool.state_root();
+
+        assert!(pool.accrue_distribution(&rewards(3, &[("LOSa", 10), ("LOSb", 5)])));
+        assert!(!pool.accrue_distribution(&rewards(3, &[("LOSa", 10)])));
+        assert!(!pool.accrue_distribution(&rewards(2, &[("LOSa", 10)])));
+        let mut fees = rewards(4, &[("LOSa", 7)]);
+        fees.distribution.kind = DistributionKind::Fee;
+        assert!(!pool.accrue_distribution(&fees));
+        assert!(pool.accrue_distribution(&rewards(4, &[("LOSa", 1)])));
+
+        assert_eq!(pool.claimable_cil("LOSa", 0), 11);
+        assert_eq!(pool.claimable_cil("LOSa", 4), 7);
+        assert_eq!(pool.claimable_cil("LOSb", 9), 0);
+        assert_eq!(pool.claimable_cil("LOSnone", 0), 0);
+        // Accruals are agreed state
+        assert_ne!(pool.state_root(), root);
+
+        // Each accrued epoch keeps the certificate a Claim carries
+        let payouts: Vec<_> = pool
+            .unclaimed_payouts("LOSa", |d| d.epoch == 3)
+            .map(|(epoch, amount, _)| (epoch, amount))
+            .collect();
+        assert_eq!(payouts, vec![(4, 1)]);
+        assert_eq!(pool.unclaimed_payouts("LOSb", |_| false).count(), 1);
+    }
+
+    #[test]
+    fn test_claim_needs_certified_unclaimed_payout() {
+        let key = los_crypto::generate_keypair_from_seed(&[7; 64]);
+        let validator = los_crypto::public_key_to_address(&key.public_key);
+        let mut ledger = Ledger::new();
+        ledger.accounts.insert(
+            validator.clone(),
+            AccountState {
+                head: "0".to_string(),
+                balance: MIN_VALIDATOR_STAKE_CIL,
+                block_count: 0,
+                is_validator: true,
+            },
+        );
+        let mut cert = rewards(5, &[(validator.as_str(), 100)]);
+        let block = |c: &DistributionCertificate, amount| claim(&validator, amount, claim_link(c));
+
+        // The link must carry a certificate signed by a validator quorum
+        let bare = claim(&validator, 100, CLAIM_LINK.to_string());
+        assert!(ledger.check_claim_certificate(&bare).is_err());
+        assert!(ledger
+            .check_claim_certificate(&block(&cert, 100))
+            .unwrap_err()
+            .contains("0 valid"));
+        let signature = CoSignature::sign(
+            &cert.distribution,
+            &validator,
+            &key.public_key,
+            &key.secret_key,
+        )
+        .unwrap();
+        cert.add_signature(signature);
+        let key = ledger.check_claim_certificate(&block(&cert, 100)).unwrap();
+
+        // Only the certified amount, to the certified account
+        assert!(ledger.check_claim_certificate(&block(&cert, 101)).is_err());
+        assert!(ledger.check_claim_certificate(&block(&cert, 99)).is_err());
+        let mut other = block(&cert, 100);
+        other.account = "LOSother".to_string();
+        assert!(ledger.check_claim_certificate(&other).is_err());
+
+        // Each payout is claimed once, also after a reload
+        let mut claimed = ledger.clone();
+        claimed.blocks.insert("h1".to_string(), block(&cert, 100));
+        claimed.rebuild_reward_claims();
+        assert!(claimed.claimed_sends.contains(&key));
+        assert!(claimed.is_payout_claimed(&cert.distribution, &validator));
+        assert_eq!(claimed.reward_claimed_cil(&validator), 100);
+        assert!(claimed
+            .check_claim_certificate(&block(&cert, 100))
+            .unwrap_err()
+            .contains("already claimed"));
+    }
+
+    #[test]
+    fn test_claims_rejected_on_push_networks() {
+        assert_eq!(chain_spec::active().reward_payout, RewardPayout::Push);
+        let ledger = Ledger::new();
+        let err = ledger
+            .check_reward_claim(&claim("LOSa", 1, CLAIM_LINK.to_string()))
+            .unwrap_err();
+        assert!(err.contains("reward_payout = push"), "{}", err);
+    }
+}
@@ -0,0 +1,614 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — CO-SIGNED EPOCH DISTRIBUTIONS
+//
+// Validator rewards and fee redistribution are minted by the epoch leader.
+// Without a check, any leader (or anyone signing a Mint) could pay itself
+// from the pool. Every distribution is therefore co-signed:
+//
+//   1. The leader computes the epoch's payouts (an EpochDistribution), signs
+//      its digest and gossips REWARD_PROPOSE:{certificate}
+//   2. Validators re-check the payouts against their own pool and ledger
+//      (check_proposal) and gossip REWARD_SIGN:{digest}:{CoSignature}
+//   3. Once 2f+1 of the validator set signed, the leader mints one block per
+//      payout, the certificate embedded in the link:
+//        REWARD:EPOCH:{epoch}:{certificate json}
+//        FEE_REWARD:EPOCH:{epoch}:{certificate json}
+//
+// process_block rejects REWARD:/FEE_REWARD: mints without a valid certificate
+// once the block timestamp reaches ChainSpec::reward_cosign_activation_secs.
+// Each (distribution, recipient) pair is marked in claimed_sends, so a
+// certificate pays each recipient once. Older uncertified mints stay valid
+// for replay, but only on an existing account chain: a backdated block cannot
+// open a fresh account.
+//
+// The reward rate and halving schedule remain chain constants; the quorum
+// governs who is paid and how much within them.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::validator_rewards::{
+    reward_rate_for_epoch, ValidatorRewardPool, FEE_REWARD_LINK_PREFIX,
+};
+use crate::{chain_id, Block, Ledger, MIN_VALIDATOR_STAKE_CIL};
+
+/// 2026-12-01 00:00 UTC — reward mints from then on need a certificate
+pub const REWARD_COSIGN_ACTIVATION_SECS: u64 = 1_796_083_200;
+/// Link prefix of validator reward Mint blocks ("REWARD:EPOCH:{epoch}")
+pub const REWARD_LINK_PREFIX: &str = "REWARD:EPOCH:";
+/// Gossip prefix: leader's proposal (certificate holding its own signature)
+pub const PROPOSE_PREFIX: &str = "REWARD_PROPOSE:";
+/// Gossip prefix: REWARD_SIGN:{digest}:{CoSignature json}
+pub const SIGN_PREFIX: &str = "REWARD_SIGN:";
+/// Signing domain, keeps distribution signatures from doubling as anything else
+pub const DISTRIBUTION_DOMAIN: &[u8] = b"LOS-EPOCH-DISTRIBUTION-V1";
+/// claimed_sends key prefix of paid-out (distribution, recipient) pairs
+const CLAIM_PREFIX: &str = "DIST:";
+
+/// Which pool an epoch distribution pays from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DistributionKind {
+    /// Validator reward pool (REWARD:EPOCH:)
+    Reward,
+    /// Accumulated transaction fees (FEE_REWARD:EPOCH:)
+    Fee,
+}
+
+impl DistributionKind {
+    pub fn link_prefix(self) -> &'static str {
+        match self {
+            DistributionKind::Reward => REWARD_LINK_PREFIX,
+            DistributionKind::Fee => FEE_REWARD_LINK_PREFIX,
+        }
+    }
+}
+
+/// Payouts of one epoch distribution: recipient → CIL
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EpochDistribution {
+    pub kind: DistributionKind,
+    pub epoch: u64,
+    /// BTreeMap: one payout per recipient, canonical order
+    pub payouts: BTreeMap<String, u128>,
+}
+
+impl EpochDistribution {
+    pub fn new(
+        kind: DistributionKind,
+        epoch: u64,
+        payouts: impl IntoIterator<Item = (String, u128)>,
+    ) -> Self {
+        Self {
+            kind,
+            epoch,
+            payouts: payouts.into_iter().collect(),
+        }
+    }
+
+    /// Sum of all payouts (None on overflow)
+    pub fn total(&self) -> Option<u128> {
+        self.payouts
+            .values()
+            .try_fold(0u128, |acc, amount| acc.checked_add(*amount))
+    }
+
+    /// Bytes every co-signer signs (chain ID bound, like block signing hashes)
+    pub fn signing_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(64 + self.payouts.len() * 64);
+        data.extend_from_slice(DISTRIBUTION_DOMAIN);
+        data.extend_from_slice(&chain_id().to_le_bytes());
+        data.push(match self.kind {
+            DistributionKind::Reward => 0,
+            DistributionKind::Fee => 1,
+        });
+        data.extend_from_slice(&self.epoch.to_le_bytes());
+        data.extend_from_slice(&(self.payouts.len() as u64).to_le_bytes());
+        for (recipient, amount) in &self.payouts {
+            data.extend_from_slice(&(recipient.len() as u64).to_le_bytes());
+            data.extend_from_slice(recipient.as_bytes());
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        data
+    }
+
+    /// SHA3-256 of `signing_data()`, hex — identifies the distribution in gossip
+    pub fn digest(&self) -> String {
+        hex::encode(Sha3_256::digest(self.signing_data()))
+    }
+
+    /// Sanity checks a validator runs before co-signing a leader's proposal
+    pub fn check_proposal(
+        &self,
+        ledger: &Ledger,
+        pool: &ValidatorRewardPool,
+    ) -> Result<(), String> {
+        if self.payouts.is_empty() || self.payouts.values().any(|a| *a == 0) {
+            return Err("Distribution has no payouts or a zero payout".to_string());
+        }
+        // The leader proposes right after the epoch ends; we may or may not
+        // have advanced our own epoch counter yet.
+        if self.epoch > pool.current_epoch || self.epoch + 1 < pool.current_epoch {
+            return Err(format!(
+                "Distribution epoch {} is not the epoch just completed (current {})",
+                self.epoch, pool.current_epoch
+            ));
+        }
+        let total = self.total().ok_or("Distribution total overflows u128")?;
+        match self.kind {
+            DistributionKind::Reward => {
+                if let Some(r) = self
+                    .payouts
+                    .keys()
+                    .find(|r| !pool.validators.contains_key(*r))
+                {
+                    return Err(format!(
+                        "Reward recipient {} is not a registered validator",
+                        r
+                    ));
+                }
+                let budget = reward_rate_for_epoch(self.epoch)
+                    .min(pool.remaining_cil)
+                    .min(ledger.distribution.remaining_supply);
+                if total > budget {
+                    return Err(format!(
+                        "Rewards of {} CIL exceed the epoch budget of {} CIL",
+                        total, budget
+                    ));
+                }
+            }
+            DistributionKind::Fee => {
+                let validators = ledger.reward_validator_set();
+                if let Some(r) = self.payouts.keys().find(|r| !validators.contains(*r)) {
+                    return Err(format!("Fee recipient {} is not a staked validator", r));
+                }
+                if total > ledger.accumulated_fees_cil {
+                    return Err(format!(
+                        "Fee payouts of {} CIL exceed the {} CIL collected",
+                        total, ledger.accumulated_fees_cil
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One validator's signature over an EpochDistribution digest
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CoSignature {
+    pub validator: String,
+    /// Hex public key (must derive to `validator`)
+    pub public_key: String,
+    /// Hex signature over `EpochDistribution::signing_data()`
+    pub signature: String,
+}
+
+impl CoSignature {
+    pub fn sign(
+        distribution: &EpochDistribution,
+        validator: &str,
+        public_key: &[u8],
+        secret_key: &[u8],
+    ) -> Result<Self, String> {
+        Self::sign_with(distribution, validator, public_key, |data| {
+            los_crypto::sign_message(data, secret_key).map_err(|e| format!("{:?}", e))
+        })
+    }
+
+    /// Like `sign`, with the signature produced by `sign` (e.g. a remote signer)
+    pub fn sign_with<F>(
+        distribution: &EpochDistribution,
+        validator: &str,
+        public_key: &[u8],
+        sign: F,
+    ) -> Result<Self, String>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        let signature = sign(&distribution.signing_data())
+            .map_err(|e| format!("Cannot sign distribution: {}", e))?;
+        Ok(Self {
+            validator: validator.to_string(),
+            public_key: hex::encode(public_key),
+            signature: hex::encode(signature),
+        })
+    }
+
+    /// Key derives to the signer address and the signature is valid
+    pub fn verify(&self, distribution: &EpochDistribution) -> bool {
+        let (Ok(pk), Ok(sig)) = (hex::decode(&self.public_key), hex::decode(&self.signature))
+        else {
+            return false;
+        };
+        !pk.is_empty()
+            && los_crypto::public_key_to_address(&pk) == self.validator
+            && los_crypto::verify_signature(&distribution.signing_data(), &sig, &pk)
+    }
+}
+
+/// A distribution plus the validator signatures that approve it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistributionCertificate {
+    pub distribution: EpochDistribution,
+    pub signatures: Vec<CoSignature>,
+}
+
+/// Signatures needed out of `validators`: BFT 2f+1 with f = (n-1)/3,
+/// the same rule as finality checkpoints (1 for a single validator)
+pub fn quorum(validators: usize) -> usize {
+    let f = validators.saturating_sub(1) / 3;
+    2 * f + 1
+}
+
+impl DistributionCertificate {
+    /// Add a signature (ignored when the validator already signed).
+    /// Returns true if it was new.
+    pub fn add_signature(&mut self, signature: CoSignature) -> bool {
+        if self
+            .signatures
+            .iter()
+            .any(|s| s.validator == signature.validator)
+        {
+            return false;
+        }
+        self.signatures.push(signature);
+        true
+    }
+
+    /// Number of distinct members of `validators` with a valid signature
+    pub fn valid_signers(&self, validators: &BTreeSet<String>) -> usize {
+        let mut seen = HashSet::new();
+        self.signatures
+            .iter()
+            .filter(|s| validators.contains(&s.validator) && seen.insert(s.validator.as_str()))
+            .filter(|s| s.verify(&self.distribution))
+            .count()
+    }
+
+    /// Enough of `validators` signed
+    pub fn verify(&self, validators: &BTreeSet<String>) -> Result<(), String> {
+        let required = quorum(validators.len());
+        let valid = self.valid_signers(validators);
+        if valid < required {
+            return Err(format!(
+                "Distribution certificate has {} valid validator signatures, {} required",
+                valid, required
+            ));
+        }
+        Ok(())
+    }
+
+    /// Mint block link paying out of this certificate
+    pub fn link(&self) -> String {
+        format!(
+            "{}{}:{}",
+            self.distribution.kind.link_prefix(),
+            self.distribution.epoch,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Split a distribution Mint link into kind, epoch and certificate JSON
+/// (None for non-distribution links, empty certificate for legacy links)
+pub fn parse_distribution_link(link: &str) -> Option<(DistributionKind, &str, &str)> {
+    let (kind, rest) = if let Some(rest) = link.strip_prefix(REWARD_LINK_PREFIX) {
+        (DistributionKind::Reward, rest)
+    } else {
+        (
+            DistributionKind::Fee,
+            link.strip_prefix(FEE_REWARD_LINK_PREFIX)?,
+        )
+    };
+    let (epoch, certificate) = rest.split_once(':').unwrap_or((rest, ""));
+    Some((kind, epoch, certificate))
+}
+
+/// claimed_sends key marking `account`'s payout of `distribution` as paid,
+/// whether by a distribution Mint or by a Claim block (reward_claims.rs)
+pub(crate) fn payout_claim_key(distribution: &EpochDistribution, account: &str) -> String {
+    format!("{}{}:{}", CLAIM_PREFIX, distribution.digest(), account)
+}
+
+impl Ledger {
+    /// Accounts whose signatures count toward a distribution (or
+    /// checkpoint) quorum
+    pub fn reward_validator_set(&self) -> BTreeSet<String> {
+        self.accounts
+            .keys()
+            .filter(|addr| self.is_staked_validator(addr))
+            .cloned()
+            .collect()
+    }
+
+    /// Registered validator holding at least MIN_VALIDATOR_STAKE_CIL
+    pub fn is_staked_validator(&self, address: &str) -> bool {
+        self.accounts
+            .get(address)
+            .is_some_and(|s| s.is_validator && s.balance >= MIN_VALIDATOR_STAKE_CIL)
+    }
+
+    /// Validate a REWARD:/FEE_REWARD: Mint against its certificate.
+    /// Returns the claimed_sends key to mark once the block is applied
+    /// (None for pre-activation blocks, which carry no certificate).
+    pub(crate) fn check_distribution_mint(&self, block: &Block) -> Result<Option<String>, String> {
+        let Some((kind, epoch, certificate)) = parse_distribution_link(&block.link) else {
+            return Ok(None);
+        };
+        let epoch: u64 = epoch.parse().map_err(|_| {
+            format!(
+                "Distribution Error: invalid epoch in {}",
+                kind.link_prefix()
+            )
+        })?;
+        let activation = crate::chain_spec::active().reward_cosign_activation_secs;
+        if certificate.is_empty() {
+            if block.timestamp >= activation {
+                return Err(
+                    "Distribution Error: epoch distribution mint without validator certificate"
+                        .to_string(),
+                );
+            }
+            if block.previous == "0" {
+                return Err(
+                    "Distribution Error: uncertified distribution cannot open an account"
+                        .to_string(),
+                );
+            }
+            return Ok(None);
+        }
+
+        let cert: DistributionCertificate = serde_json::from_str(certificate)
+            .map_err(|e| format!("Distribution Error: invalid certificate: {}", e))?;
+        let dist = &cert.distribution;
+        if dist.kind != kind || dist.epoch != epoch {
+            return Err("Distribution Error: certificate is for another distribution".to_string());
+        }
+        if dist.payouts.get(&block.account) != Some(&block.amount) {
+            return Err(format!(
+                "Distribution Error: {} CIL to {} is not in the certified payouts",
+                block.amount, block.account
+            ));
+        }
+        let claim = payout_claim_key(dist, &block.account);
+        if self.claimed_sends.contains(&claim) {
+            return Err("Distribution Error: payout already minted".to_string());
+        }
+        cert.verify(&self.reward_validator_set())
+            .map_err(|e| format!("Distribution Error: {}", e))?;
+        Ok(Some(claim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountState, BlockType};
+
+    fn keypair(seed: u8) -> los_crypto::KeyPair {
+        los_crypto::generate_keypair_from_seed(&[seed; 64])
+    }
+
+    fn address(kp: &los_crypto::KeyPair) -> String {
+        los_crypto::public_key_to_address(&kp.public_key)
+    }
+
+    /// Ledger with the given accounts staked as validators
+    fn ledger_with(validators: &[String]) -> Ledger {
+        let mut l = Ledger::new();
+        for v in validators {
+            l.accounts.insert(
+                v.clone(),
+                AccountState {
+                    head: "0".to_string(),
+                    balance: MIN_VALIDATOR_STAKE_CIL,
+                    block_count: 0,
+                    is_validator: true,
+                },
+            );
+        }
+        l
+    }
+
+    fn mint(account: &str, amount: u128, link: String, timestamp: u64) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Mint,
+            amount,
+            link,
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_quorum_matches_checkpoint_rule() {
+        assert_eq!(quorum(0), 1);
+        assert_eq!(quorum(1), 1);
+        assert_eq!(quorum(3), 1);
+        assert_eq!(quorum(4), 3);
+        assert_eq!(quorum(7), 5);
+        assert_eq!(quorum(10), 7);
+    }
+
+    #[test]
+    fn test_digest_binds_every_payout() {
+        let d = EpochDistribution::new(
+            DistributionKind::Reward,
+            4,
+            [("LOSa".to_string(), 10), ("LOSb".to_string(), 20)],
+        );
+        assert_eq!(d.total(), Some(30));
+        let mut other = d.clone();
+        other.payouts.insert("LOSb".to_string(), 21);
+        assert_ne!(d.digest(), other.digest());
+        let mut fee = d.clone();
+        fee.kind = DistributionKind::Fee;
+        assert_ne!(d.digest(), fee.digest());
+        let mut later = d.clone();
+        later.epoch = 5;
+        assert_ne!(d.digest(), later.digest());
+
+        assert_eq!(
+            parse_distribution_link("REWARD:EPOCH:4"),
+            Some((DistributionKind::Reward, "4", ""))
+        );
+        assert_eq!(
+            parse_distribution_link("FEE_REWARD:EPOCH:4:{\"x\":1}"),
+            Some((DistributionKind::Fee, "4", "{\"x\":1}"))
+        );
+        assert_eq!(parse_distribution_link("MINE:4"), None);
+    }
+
+    #[test]
+    fn test_mint_needs_quorum_certificate() {
+        let keys: Vec<_> = (1..=4).map(keypair).collect();
+        let validators: Vec<String> = keys.iter().map(address).collect();
+        let ledger = ledger_with(&validators);
+        let recipient = validators[0].clone();
+        let dist = EpochDistribution::new(DistributionKind::Reward, 2, [(recipient.clone(), 500)]);
+        let mut cert = DistributionCertificate {
+            distribution: dist.clone(),
+            signatures: vec![],
+        };
+        let sign = |i: usize| {
+            CoSignature::sign(
+                &dist,
+                &validators[i],
+                &keys[i].public_key,
+                &keys[i].secret_key,
+            )
+            .unwrap()
+        };
+        let after = REWARD_COSIGN_ACTIVATION_SECS;
+
+        // Legacy links: fine before activation on an existing chain only
+        let mut legacy = mint(&recipient, 500, "REWARD:EPOCH:2".to_string(), after - 1);
+        assert!(ledger
+            .check_distribution_mint(&legacy)
+            .unwrap_err()
+            .contains("open an account"));
+        legacy.previous = "abc".to_string();
+        assert_eq!(ledger.check_distribution_mint(&legacy), Ok(None));
+        legacy.timestamp = after;
+        assert!(ledger
+            .check_distribution_mint(&legacy)
+            .unwrap_err()
+            .contains("without validator"));
+
+        // 4 validators → 3 signatures; a repeated signer counts once
+        assert!(cert.add_signature(sign(0)));
+        assert!(!cert.add_signature(sign(0)));
+        cert.add_signature(sign(1));
+        let block = |c: &DistributionCertificate, amount| mint(&recipient, amount, c.link(), after);
+        assert!(ledger
+            .check_distribution_mint(&block(&cert, 500))
+            .unwrap_err()
+            .contains("2 valid"));
+        cert.add_signature(sign(2));
+        let claim = ledger
+            .check_distribution_mint(&block(&cert, 500))
+            .unwrap()
+            .unwrap();
+
+        // Amount or recipient outside the certified payouts
+        assert!(ledger.check_distribution_mint(&block(&cert, 501)).is_err());
+        let mut other = block(&cert, 500);
+        other.account = validators[1].clone();
+        assert!(ledger.check_distribution_mint(&other).is_err());
+
+        // Link prefix must match the certified kind and epoch
+        let mut wrong = block(&cert, 500);
+        wrong.link = wrong.link.replacen("REWARD:EPOCH:2:", "REWARD:EPOCH:3:", 1);
+        assert!(ledger
+            .check_distribution_mint(&wrong)
+            .unwrap_err()
+            .contains("another"));
+
+        // Each payout is minted once
+        let mut paid = ledger.clone();
+        paid.claimed_sends.insert(claim);
+        assert!(paid
+            .check_distribution_mint(&block(&cert, 500))
+            .unwrap_err()
+            .contains("already"));
+
+        // Signatures from outside the validator set or over other data do not count
+        let outsider = keypair(9);
+        let mut forged = cert.clone();
+        forged.signatures = vec![
+            sign(0),
+            CoSignature::sign(
+                &dist,
+                &address(&outsider),
+                &outsider.public_key,
+                &outsider.secret_key,
+            )
+            .unwrap(),
+            CoSignature {
+                signature: sign(2).signature,
+                ..sign(1)
+            },
+        ];
+        assert_eq!(forged.valid_signers(&ledger.reward_validator_set()), 1);
+    }
+
+    #[test]
+    fn test_proposal_checks() {
+        let validators = vec!["LOSv1".to_string(), "LOSv2".to_string()];
+        let mut ledger = ledger_with(&validators);
+        ledger.accumulated_fees_cil = 1_000;
+        let mut pool = ValidatorRewardPool::new(0);
+        for v in &validators {
+            pool.register_validator(v, false, MIN_VALIDATOR_STAKE_CIL);
+        }
+        pool.current_epoch = 5;
+
+        let reward = |epoch, payouts: Vec<(&str, u128)>| {
+            EpochDistribution::new(
+                DistributionKind::Reward,
+                epoch,
+                payouts.into_iter().map(|(a, v)| (a.to_string(), v)),
+            )
+        };
+        assert!(reward(5, vec![("LOSv1", 100)])
+            .check_proposal(&ledger, &pool)
+            .is_ok());
+        assert!(reward(4, vec![("LOSv1", 100)])
+            .check_proposal(&ledger, &pool)
+            .is_ok());
+        assert!(reward(3, vec![("LOSv1", 100)])
+            .check_proposal(&ledger, &pool)
+            .is_err());
+        assert!(reward(6, vec![("LOSv1", 100)])
+            .check_proposal(&ledger, &pool)
+            .is_err());
+        assert!(reward(5, vec![("LOSx", 100)])
+            .check_proposal(&ledger, &pool)
+            .is_err());
+        assert!(reward(5, vec![("LOSv1", 0)])
+            .check_proposal(&ledger, &pool)
+            .is_err());
+        let over = reward_rate_for_epoch(5) + 1;
+        assert!(reward(5, vec![("LOSv1", over)])
+            .check_proposal(&ledger, &pool)
+            .is_err());
+
+        let fee = |amount| {
+            EpochDistribution::new(DistributionKind::Fee, 5, [("LOSv2".to_string(), amount)])
+        };
+        assert!(fee(1_000).check_proposal(&ledger, &pool).is_ok());
+        assert!(fee(1_001).check_proposal(&ledger, &pool).is_err());
+        ledger.accounts.get_mut("LOSv2").unwrap().balance = MIN_VALIDATOR_STAKE_CIL - 1;
+        assert!(fee(1).check_proposal(&ledger, &pool).is_err());
+    }
+}
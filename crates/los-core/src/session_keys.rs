@@ -0,0 +1,527 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — SESSION KEYS
+//
+// A dApp (a game, say) should not need the main Dilithium5 key for every
+// move. An account grants a session key limited authority:
+//
+//   - grant: a Change block signed by the account key,
+//     link = "SESSION_GRANT:{session_pk_hex}:{expires_at}:{max_amount_cil}:{contract}"
+//   - revoke: a Change block signed by the account key,
+//     link = "SESSION_REVOKE:{session_address}"
+//
+// While a grant is live the ledger accepts ContractCall blocks for `account`
+// whose public_key is the session key, provided that
+//   - the call targets the granted contract,
+//   - the call block's timestamp is before expires_at, and
+//   - value + fee of all calls under the grant stay within max_amount_cil.
+// Everything else (Send, Change, deploys, other contracts, revoking) still
+// needs the account key. Granting the same key again replaces the grant and
+// resets its budget. Expired grants are dropped on the account's next grant.
+// Expiry is always judged at the block's own (drift-bounded) timestamp, never
+// the local clock, so replaying the chain rebuilds the same grants.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{Block, BlockType, Ledger};
+use serde::{Deserialize, Serialize};
+
+/// Link prefix of a grant Change block
+pub const SESSION_GRANT_PREFIX: &str = "SESSION_GRANT:";
+/// Link prefix of a revocation Change block
+pub const SESSION_REVOKE_PREFIX: &str = "SESSION_REVOKE:";
+/// Live grants per account (each one is ledger state)
+pub const MAX_SESSION_KEYS_PER_ACCOUNT: usize = 16;
+
+/// A live session key grant
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionGrant {
+    /// Session public key, hex
+    pub public_key: String,
+    /// Block time (Unix seconds) from which the key is no longer accepted
+    pub expires_at: u64,
+    /// Budget for value + fees over all calls under this grant
+    pub max_amount_cil: u128,
+    /// The one contract the key may call
+    pub contract: String,
+    /// Value + fees spent so far
+    pub spent_cil: u128,
+}
+
+impl SessionGrant {
+    pub fn remaining_cil(&self) -> u128 {
+        self.max_amount_cil.saturating_sub(self.spent_cil)
+    }
+}
+
+/// Link of a grant Change block
+pub fn grant_link(
+    session_pk: &[u8],
+    expires_at: u64,
+    max_amount_cil: u128,
+    contract: &str,
+) -> String {
+    format!(
+        "{}{}:{}:{}:{}",
+        SESSION_GRANT_PREFIX,
+        hex::encode(session_pk),
+        expires_at,
+        max_amount_cil,
+        contract
+    )
+}
+
+/// Link of a revocation Change block
+pub fn revoke_link(session_address: &str) -> String {
+    format!("{}{}", SESSION_REVOKE_PREFIX, session_address)
+}
+
+/// Parse a grant link into an unspent grant; returns (session address, grant)
+pub fn parse_grant_link(link: &str) -> Result<(String, SessionGrant), String> {
+    let parts: Vec<&str> = link
+        .strip_prefix(SESSION_GRANT_PREFIX)
+        .ok_or("Session Error: not a SESSION_GRANT link")?
+        .splitn(4, ':')
+        .collect();
+    let [pk_hex, expires_at, max_amount, contract] = parts[..] else {
+        return Err(
+            "Session Error: link must be SESSION_GRANT:{pk_hex}:{expires_at}:{max_amount_cil}:{contract}"
+                .to_string(),
+        );
+    };
+    let pk = hex::decode(pk_hex).map_err(|e| format!("Session Error: invalid key hex: {}", e))?;
+    if pk.is_empty() {
+        return Err("Session Error: session key is empty".to_string());
+    }
+    let expires_at = expires_at
+        .parse()
+        .map_err(|_| "Session Error: invalid expires_at".to_string())?;
+    let max_amount_cil = max_amount
+        .parse()
+        .map_err(|_| "Session Error: invalid max_amount_cil".to_string())?;
+    if contract.is_empty() {
+        return Err("Session Error: grant must name a contract".to_string());
+    }
+    Ok((
+        los_crypto::public_key_to_address(&pk),
+        SessionGrant {
+            public_key: pk_hex.to_lowercase(),
+            expires_at,
+            max_amount_cil,
+            contract: contract.to_string(),
+            spent_cil: 0,
+        },
+    ))
+}
+
+/// Contract a ContractCall link targets ("CALL:{contract}:...")
+fn call_contract(link: &str) -> Option<&str> {
+    link.strip_prefix("CALL:")?.split(':').next()
+}
+
+impl Ledger {
+    /// Live or expired grant of `session_address` on `account`
+    pub fn session_grant(&self, account: &str, session_address: &str) -> Option<&SessionGrant> {
+        self.session_keys.get(account)?.get(session_address)
+    }
+
+    /// Check a ContractCall signed by session key `session_address` on
+    /// behalf of `block.account`. Account ↔ key binding is replaced by this.
+    pub(crate) fn check_session_call(
+        &self,
+        block: &Block,
+        session_address: &str,
+    ) -> Result<(), String> {
+        if block.block_type != BlockType::ContractCall {
+            return Err(format!(
+                "Authorization Error: session keys can only sign ContractCall blocks, not {:?}",
+                block.block_type
+            ));
+        }
+        let grant = self
+            .session_grant(&block.account, session_address)
+            .ok_or_else(|| {
+                format!(
+                    "Authorization Error: public_key derives to {} but account is {}, and it holds no session grant",
+                    session_address, block.account
+                )
+            })?;
+        if block.timestamp >= grant.expires_at {
+            return Err(format!(
+                "Session Error: grant expired at {} (block time: {})",
+                grant.expires_at, block.timestamp
+            ));
+        }
+        if call_contract(&block.link) != Some(grant.contract.as_str()) {
+            return Err(format!(
+                "Session Error: session key may only call {}",
+                grant.contract
+            ));
+        }
+        let cost = block
+            .amount
+            .checked_add(block.fee)
+            .ok_or("Overflow: amount + fee exceeds u128")?;
+        if cost > grant.remaining_cil() {
+            return Err(format!(
+                "Session Error: value + fee {} CIL exceeds the remaining session budget {} CIL",
+                cost,
+                grant.remaining_cil()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Record value + fee of an applied session call against its grant
+    pub(crate) fn charge_session(&mut self, block: &Block, session_address: &str) {
+        if let Some(grant) = self
+            .session_keys
+            .get_mut(&block.account)
+            .and_then(|grants| grants.get_mut(session_address))
+        {
+            grant.spent_cil = grant
+                .spent_cil
+                .saturating_add(block.amount.saturating_add(block.fee));
+        }
+    }
+
+    /// Validate a grant or revocation Change block (signed by the account
+    /// key — process_block checked that). Nothing is applied.
+    pub(crate) fn check_session_change(&self, block: &Block) -> Result<(), String> {
+        if let Some(session_address) = block.link.strip_prefix(SESSION_REVOKE_PREFIX) {
+            if self
+                .session_grant(&block.account, session_address)
+                .is_none()
+            {
+                return Err(format!(
+                    "Session Error: {} holds no session grant on {}",
+                    session_address, block.account
+                ));
+            }
+            return Ok(());
+        }
+        let (session_address, grant) = parse_grant_link(&block.link)?;
+        if session_address == block.account {
+            return Err("Session Error: the account key cannot be its own session key".to_string());
+        }
+        if grant.expires_at <= block.timestamp {
+            return Err(format!(
+                "Session Error: expires_at {} is not after the block time {}",
+                grant.expires_at, block.timestamp
+            ));
+        }
+        let live = self.session_keys.get(&block.account).map_or(0, |grants| {
+            grants
+                .iter()
+                .filter(|(addr, g)| **addr != session_address && g.expires_at > block.timestamp)
+                .count()
+        });
+        if live >= MAX_SESSION_KEYS_PER_ACCOUNT {
+            return Err(format!(
+                "Session Error: account already has {} live session keys",
+                MAX_SESSION_KEYS_PER_ACCOUNT
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apply a grant or revocation checked by `check_session_change`
+    pub(crate) fn apply_session_change(&mut self, block: &Block) {
+        if let Some(session_address) = block.link.strip_prefix(SESSION_REVOKE_PREFIX) {
+            if let Some(grants) = self.session_keys.get_mut(&block.account) {
+                grants.remove(session_address);
+                if grants.is_empty() {
+                    self.session_keys.remove(&block.account);
+                }
+            }
+            return;
+        }
+        if let Ok((session_address, grant)) = parse_grant_link(&block.link) {
+            let grants = self.session_keys.entry(block.account.clone()).or_default();
+            grants.retain(|_, g| g.expires_at > block.timestamp);
+            grants.insert(session_address, grant);
+        }
+    }
+
+    /// Recompute grants and their spending by replaying each account chain
+    /// (after loading or merging a ledger)
+    pub fn rebuild_session_keys(&mut self) {
+        self.session_keys.clear();
+        let heads: Vec<(String, String)> = self
+            .accounts
+            .iter()
+            .map(|(addr, state)| (addr.clone(), state.head.clone()))
+            .collect();
+        for (account, head) in heads {
+            // Head → genesis; a broken chain replays the part that is present
+            let mut chain: Vec<String> = Vec::new();
+            let mut current = head;
+            while current != "0" && chain.len() < self.blocks.len() {
+                let Some(blk) = self.blocks.get(&current) else {
+                    break;
+                };
+                let previous = blk.previous.clone();
+                chain.push(std::mem::replace(&mut current, previous));
+            }
+            for hash in chain.iter().rev() {
+                let blk = match self.blocks.get(hash) {
+                    Some(b) if is_session_change(b) || b.block_type == BlockType::ContractCall => {
+                        b.clone()
+                    }
+                    _ => continue,
+                };
+                if is_session_change(&blk) {
+                    self.apply_session_change(&blk);
+                    continue;
+                }
+                let Ok(pk) = hex::decode(&blk.public_key) else {
+                    continue;
+                };
+                let signer = los_crypto::public_key_to_address(&pk);
+                if signer != account {
+                    self.charge_session(&blk, &signer);
+                }
+            }
+        }
+    }
+}
+
+/// True for Change blocks that grant or revoke a session key
+pub fn is_session_change(block: &Block) -> bool {
+    block.block_type == BlockType::Change
+        && (block.link.starts_with(SESSION_GRANT_PREFIX)
+            || block.link.starts_with(SESSION_REVOKE_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountState, MIN_CALL_FEE_CIL};
+
+    const NOW: u64 = 1_771_000_000;
+    const GAME: &str = "LOSConGame";
+
+    fn block(account: &str, block_type: BlockType, link: String, pk: &[u8]) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type,
+            amount: 0,
+            link,
+            signature: String::new(),
+            public_key: hex::encode(pk),
+            work: 0,
+            timestamp: NOW,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    /// Apply without PoW/signature (the checks under test come after them)
+    fn apply(ledger: &mut Ledger, mut b: Block) -> Result<(), String> {
+        b.previous = ledger.accounts[&b.account].head.clone();
+        ledger.apply_checked_block_at(&b, NOW).map(|_| ())
+    }
+
+    fn call(account: &str, pk: &[u8], contract: &str, amount: u128) -> Block {
+        let mut b = block(
+            account,
+            BlockType::ContractCall,
+            format!("CALL:{}:move", contract),
+            pk,
+        );
+        b.amount = amount;
+        b.fee = MIN_CALL_FEE_CIL;
+        b
+    }
+
+    #[test]
+    fn test_grant_link_roundtrip() {
+        let link = grant_link(&[1, 2, 3], NOW + 60, 500, GAME);
+        let (addr, grant) = parse_grant_link(&link).unwrap();
+        assert_eq!(addr, los_crypto::public_key_to_address(&[1, 2, 3]));
+        assert_eq!(
+            (
+                grant.expires_at,
+                grant.max_amount_cil,
+                grant.contract.as_str()
+            ),
+            (NOW + 60, 500, GAME)
+        );
+        assert!(parse_grant_link("SESSION_GRANT:0102:1:2").is_err());
+        assert!(parse_grant_link("SESSION_GRANT:zz:1:2:LOSCon").is_err());
+        assert!(parse_grant_link("SESSION_GRANT:0102:1:2:").is_err());
+    }
+
+    #[test]
+    fn test_session_key_calls_within_grant() {
+        let main = los_crypto::generate_keypair_from_seed(&[11u8; 64]);
+        let session = los_crypto::generate_keypair_from_seed(&[12u8; 64]);
+        let account = los_crypto::public_key_to_address(&main.public_key);
+        let session_addr = los_crypto::public_key_to_address(&session.public_key);
+        let mut ledger = Ledger::new();
+        ledger.accounts.insert(
+            account.clone(),
+            AccountState {
+                head: "0".to_string(),
+                balance: 100 * MIN_CALL_FEE_CIL,
+                block_count: 0,
+                is_validator: false,
+            },
+        );
+
+        // No grant yet: the session key is just a foreign key
+        let first = call(&account, &session.public_key, GAME, 0);
+        assert!(apply(&mut ledger, first.clone())
+            .unwrap_err()
+            .contains("Authorization Error"));
+
+        let budget = 3 * MIN_CALL_FEE_CIL;
+        let grant = block(
+            &account,
+            BlockType::Change,
+            grant_link(&session.public_key, NOW + 600, budget, GAME),
+            &main.public_key,
+        );
+        apply(&mut ledger, grant).unwrap();
+        assert_eq!(
+            ledger
+                .session_grant(&account, &session_addr)
+                .unwrap()
+                .spent_cil,
+            0
+        );
+
+        // Within the grant
+        apply(&mut ledger, first).unwrap();
+        assert_eq!(
+            ledger
+                .session_grant(&account, &session_addr)
+                .unwrap()
+                .spent_cil,
+            MIN_CALL_FEE_CIL
+        );
+        // Grant and spending are rebuilt from the chain alone
+        let applied = ledger.session_keys.clone();
+        ledger.rebuild_session_keys();
+        assert_eq!(ledger.session_keys, applied);
+
+        // Other contract, other block type, over budget
+        assert!(apply(
+            &mut ledger,
+            call(&account, &session.public_key, "LOSConBank", 0)
+        )
+        .unwrap_err()
+        .contains("may only call"));
+        let mut send = block(
+            &account,
+            BlockType::Send,
+            session_addr.clone(),
+            &session.public_key,
+        );
+        send.amount = 1;
+        send.fee = MIN_CALL_FEE_CIL;
+        assert!(apply(&mut ledger, send)
+            .unwrap_err()
+            .contains("only sign ContractCall"));
+        assert!(apply(
+            &mut ledger,
+            call(&account, &session.public_key, GAME, 2 * MIN_CALL_FEE_CIL)
+        )
+        .unwrap_err()
+        .contains("budget"));
+
+        // The session key cannot revoke (or grant); the account key can
+        let revoke = block(
+            &account,
+            BlockType::Change,
+            revoke_link(&session_addr),
+            &session.public_key,
+        );
+        assert!(apply(&mut ledger, revoke).is_err());
+        let revoke = block(
+            &account,
+            BlockType::Change,
+            revoke_link(&session_addr),
+            &main.public_key,
+        );
+        apply(&mut ledger, revoke.clone()).unwrap();
+        assert!(ledger.session_grant(&account, &session_addr).is_none());
+        assert!(apply(&mut ledger, call(&account, &session.public_key, GAME, 0)).is_err());
+        // Revoking twice is refused
+        assert!(apply(&mut ledger, revoke)
+            .unwrap_err()
+            .contains("no session grant"));
+    }
+
+    #[test]
+    fn test_expired_grant_and_limits() {
+        let main = los_crypto::generate_keypair_from_seed(&[13u8; 64]);
+        let account = los_crypto::public_key_to_address(&main.public_key);
+        let mut ledger = Ledger::new();
+        ledger.accounts.insert(
+            account.clone(),
+            AccountState {
+                head: "0".to_string(),
+                balance: 10 * MIN_CALL_FEE_CIL,
+                block_count: 0,
+                is_validator: false,
+            },
+        );
+        let grant = |pk: &[u8], expires_at: u64| {
+            block(
+                &account,
+                BlockType::Change,
+                grant_link(pk, expires_at, 1, GAME),
+                &main.public_key,
+            )
+        };
+
+        // Already expired, or the account key itself
+        assert!(apply(&mut ledger, grant(&[7], NOW)).is_err());
+        assert!(apply(&mut ledger, grant(&main.public_key, NOW + 60))
+            .unwrap_err()
+            .contains("own session key"));
+
+        for i in 0..MAX_SESSION_KEYS_PER_ACCOUNT as u8 {
+            apply(&mut ledger, grant(&[i], NOW + 60)).unwrap();
+        }
+        assert!(apply(&mut ledger, grant(&[200], NOW + 60))
+            .unwrap_err()
+            .contains("live session keys"));
+        // Re-granting a key replaces it instead of adding one
+        apply(&mut ledger, grant(&[0], NOW + 120)).unwrap();
+
+        // A grant past its expiry no longer signs anything
+        let session_addr = los_crypto::public_key_to_address(&[1]);
+        let mut late = call(&account, &[1], GAME, 0);
+        late.fee = 0;
+        late.timestamp = NOW + 60;
+        assert!(ledger
+            .check_session_call(&late, &session_addr)
+            .unwrap_err()
+            .contains("expired"));
+        // A call made in time is still valid when replayed much later
+        late.timestamp = NOW + 59;
+        assert!(ledger.check_session_call(&late, &session_addr).is_ok());
+
+        // Pruning on the next grant uses that block's time as well: the
+        // 15 grants expiring at NOW + 60 survive a grant dated NOW + 59,
+        // whatever the local clock says when it is applied
+        let mut next = grant(&[100], NOW + 600);
+        next.timestamp = NOW + 59;
+        next.previous = ledger.accounts[&account].head.clone();
+        assert!(ledger
+            .apply_checked_block_at(&next, NOW + 86_400)
+            .unwrap_err()
+            .contains("live session keys"));
+        next.timestamp = NOW + 60;
+        ledger.apply_checked_block_at(&next, NOW + 86_400).unwrap();
+        let grants = &ledger.session_keys[&account];
+        assert_eq!(grants.len(), 2);
+        assert!(grants.contains_key(&los_crypto::public_key_to_address(&[0])));
+        // Replaying the chain prunes the same way
+        let applied = ledger.session_keys.clone();
+        ledger.rebuild_session_keys();
+        assert_eq!(ledger.session_keys, applied);
+    }
+}
@@ -0,0 +1,350 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — AMOUNT UNITS
+//
+// Every amount on chain is CIL (u128); 1 LOS = 10^11 CIL. Wallets, the CLI
+// and the REST API convert to LOS for people, and every hand-rolled
+// conversion (integer division, f64, 8-digit padding) was a bug waiting to
+// happen. This module is the one conversion path:
+//
+// - Cil: an integer CIL amount (serde: plain number)
+// - Los: a decimal LOS amount with exactly 11 fractional digits. It is kept
+//   as its CIL count, so Cil → Los → Cil never loses precision
+//   (serde: fixed-point string, "1.50000000000")
+// - Los::parse reads user input ("1", "1.5", " 0.00000000001 ") with an
+//   explicit Rounding for digits beyond the 11th
+//
+// REST responses carry both forms side by side: `<field>_cil` (number) and
+// `<field>_los` (fixed-point string).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::CIL_PER_LOS;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Fractional digits of a LOS amount (CIL_PER_LOS = 10^LOS_DECIMALS)
+pub const LOS_DECIMALS: usize = 11;
+
+/// Longest accepted decimal string (u128 has 39 digits)
+const MAX_INPUT_LEN: usize = 64;
+
+/// What to do with digits beyond LOS_DECIMALS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Reject unless the extra digits are all zero
+    Exact,
+    /// Truncate toward zero
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round to nearest, ties to even (banker's rounding)
+    HalfEven,
+}
+
+/// Integer amount in CIL
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Cil(pub u128);
+
+impl Cil {
+    pub const ZERO: Cil = Cil(0);
+
+    pub fn to_los(self) -> Los {
+        Los(self.0)
+    }
+
+    pub fn checked_add(self, other: Cil) -> Option<Cil> {
+        self.0.checked_add(other.0).map(Cil)
+    }
+
+    pub fn checked_sub(self, other: Cil) -> Option<Cil> {
+        self.0.checked_sub(other.0).map(Cil)
+    }
+
+    pub fn saturating_sub(self, other: Cil) -> Cil {
+        Cil(self.0.saturating_sub(other.0))
+    }
+}
+
+impl From<u128> for Cil {
+    fn from(cil: u128) -> Self {
+        Cil(cil)
+    }
+}
+
+impl From<Cil> for u128 {
+    fn from(cil: Cil) -> Self {
+        cil.0
+    }
+}
+
+impl From<Los> for Cil {
+    fn from(los: Los) -> Self {
+        Cil(los.0)
+    }
+}
+
+impl fmt::Display for Cil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Cil {
+    type Err = String;
+
+    /// Plain decimal digits only (no sign, separators or fraction)
+    fn from_str(s: &str) -> Result<Self, String> {
+        let t = s.trim();
+        if t.is_empty() || !t.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid CIL amount '{}'", s));
+        }
+        t.parse()
+            .map(Cil)
+            .map_err(|_| format!("CIL amount '{}' out of range", s))
+    }
+}
+
+/// Decimal amount in LOS, exact to 1 CIL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Los(u128);
+
+impl Los {
+    pub const ZERO: Los = Los(0);
+
+    /// `los` whole LOS; None on overflow
+    pub fn from_whole(los: u128) -> Option<Los> {
+        los.checked_mul(CIL_PER_LOS).map(Los)
+    }
+
+    pub fn cil(self) -> Cil {
+        Cil(self.0)
+    }
+
+    /// Whole-LOS part (what integer division used to return)
+    pub fn whole(self) -> u128 {
+        self.0 / CIL_PER_LOS
+    }
+
+    /// Fractional part in CIL
+    pub fn frac_cil(self) -> u128 {
+        self.0 % CIL_PER_LOS
+    }
+
+    /// Parse a user decimal ("12", "12.5", "0.00000000001"). Leading and
+    /// trailing whitespace is ignored; signs, exponents and separators are
+    /// rejected. Digits beyond the 11th are handled by `rounding`.
+    pub fn parse(s: &str, rounding: Rounding) -> Result<Los, String> {
+        let invalid = || format!("Invalid LOS amount '{}'", s);
+        let t = s.trim();
+        if t.len() > MAX_INPUT_LEN {
+            return Err(invalid());
+        }
+        let (whole, frac) = t.split_once('.').unwrap_or((t, ""));
+        let digits = |d: &str| d.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !digits(whole) || !digits(frac) {
+            return Err(invalid());
+        }
+        let (kept, extra) = frac.split_at(frac.len().min(LOS_DECIMALS));
+        let whole: u128 = whole.parse().map_err(|_| invalid())?;
+        let kept: u128 = format!("{:0<width$}", kept, width = LOS_DECIMALS)
+            .parse()
+            .map_err(|_| invalid())?;
+        let cil = whole
+            .checked_mul(CIL_PER_LOS)
+            .and_then(|w| w.checked_add(kept))
+            .ok_or_else(invalid)?;
+
+        let extra = extra.trim_end_matches('0');
+        if extra.is_empty() {
+            return Ok(Los(cil));
+        }
+        let round_up = match rounding {
+            Rounding::Exact => {
+                return Err(format!(
+                    "LOS amount '{}' has more than {} decimal places",
+                    s, LOS_DECIMALS
+                ))
+            }
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::HalfEven => match extra.as_bytes()[0] {
+                b'6'..=b'9' => true,
+                b'5' if extra.len() > 1 => true,
+                b'5' => cil % 2 == 1,
+                _ => false,
+            },
+        };
+        if round_up {
+            cil.checked_add(1).map(Los).ok_or_else(invalid)
+        } else {
+            Ok(Los(cil))
+        }
+    }
+
+    /// All 11 fractional digits: "1.50000000000"
+    pub fn to_fixed(self) -> String {
+        format!(
+            "{}.{:0width$}",
+            self.whole(),
+            self.frac_cil(),
+            width = LOS_DECIMALS
+        )
+    }
+
+    /// Rounded to `decimals` (≤ 11) fractional digits for display
+    /// ("1.2346" for 1.23456 with 4 and HalfEven). Exact is treated as Down.
+    pub fn to_rounded(self, decimals: usize, rounding: Rounding) -> String {
+        let decimals = decimals.min(LOS_DECIMALS);
+        let step = 10u128.pow((LOS_DECIMALS - decimals) as u32);
+        let (units, rest) = (self.0 / step, self.0 % step);
+        let round_up = match rounding {
+            Rounding::Exact | Rounding::Down => false,
+            Rounding::Up => rest > 0,
+            Rounding::HalfEven => rest * 2 > step || (rest * 2 == step && units % 2 == 1),
+        };
+        let units = units.saturating_add(round_up as u128);
+        let scale = 10u128.pow(decimals as u32);
+        if decimals == 0 {
+            return units.to_string();
+        }
+        format!(
+            "{}.{:0width$}",
+            units / scale,
+            units % scale,
+            width = decimals
+        )
+    }
+
+    /// Display-only approximation for interfaces that need a float
+    /// (`double balance_los` in los.proto). Never convert back.
+    pub fn to_f64_lossy(self) -> f64 {
+        self.whole() as f64 + self.frac_cil() as f64 / CIL_PER_LOS as f64
+    }
+}
+
+impl From<Cil> for Los {
+    fn from(cil: Cil) -> Self {
+        cil.to_los()
+    }
+}
+
+impl fmt::Display for Los {
+    /// Shortest exact form: "1.5", "0.00000000001", "42"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frac = self.frac_cil();
+        if frac == 0 {
+            return write!(f, "{}", self.whole());
+        }
+        let frac = format!("{:0width$}", frac, width = LOS_DECIMALS);
+        write!(f, "{}.{}", self.whole(), frac.trim_end_matches('0'))
+    }
+}
+
+impl FromStr for Los {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Los::parse(s, Rounding::Exact)
+    }
+}
+
+impl Serialize for Los {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_fixed())
+    }
+}
+
+impl<'de> Deserialize<'de> for Los {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Los::parse(&s, Rounding::Exact).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Precise fixed-point LOS string of a CIL amount ("0.50000000000")
+pub fn cil_to_los_string(cil: u128) -> String {
+    Cil(cil).to_los().to_fixed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format_round_trip() {
+        let los = Los::parse(" 1.5 ", Rounding::Exact).unwrap();
+        assert_eq!(los.cil(), Cil(150_000_000_000));
+        assert_eq!(los.to_string(), "1.5");
+        assert_eq!(los.to_fixed(), "1.50000000000");
+        assert_eq!(Cil(1).to_los().to_string(), "0.00000000001");
+        assert_eq!("42".parse::<Los>().unwrap(), Los::from_whole(42).unwrap());
+        assert_eq!(
+            "1.000000000000000".parse::<Los>().unwrap(),
+            Los::from_whole(1).unwrap()
+        );
+        assert_eq!(
+            Cil(u128::MAX)
+                .to_los()
+                .to_fixed()
+                .parse::<Los>()
+                .unwrap()
+                .cil(),
+            Cil(u128::MAX)
+        );
+        assert_eq!(Los::from_whole(7).unwrap().to_f64_lossy(), 7.0);
+
+        for bad in ["", ".5", "-1", "+1", "1e5", "1,000", "1.2.3", "0x10", "١"] {
+            assert!(Los::parse(bad, Rounding::Down).is_err(), "{}", bad);
+        }
+        assert!(Los::parse(&u128::MAX.to_string(), Rounding::Down).is_err());
+        assert!("12.5".parse::<Cil>().is_err());
+        assert_eq!("  7 ".parse::<Cil>(), Ok(Cil(7)));
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let cil = |s: &str, r| Los::parse(s, r).map(|l| l.cil().0);
+        assert!(cil("0.000000000015", Rounding::Exact).is_err());
+        assert_eq!(cil("0.000000000019", Rounding::Down), Ok(1));
+        assert_eq!(cil("0.000000000011", Rounding::Up), Ok(2));
+        // Ties go to the even CIL, anything past the tie rounds up
+        assert_eq!(cil("0.000000000015", Rounding::HalfEven), Ok(2));
+        assert_eq!(cil("0.000000000025", Rounding::HalfEven), Ok(2));
+        assert_eq!(cil("0.0000000000251", Rounding::HalfEven), Ok(3));
+        assert_eq!(cil("0.000000000024", Rounding::HalfEven), Ok(2));
+
+        let los = Los::parse("1.23455", Rounding::Exact).unwrap();
+        assert_eq!(los.to_rounded(4, Rounding::HalfEven), "1.2346");
+        assert_eq!(los.to_rounded(4, Rounding::Down), "1.2345");
+        assert_eq!(los.to_rounded(0, Rounding::Up), "2");
+        assert_eq!(
+            Los::parse("1.23445", Rounding::Exact)
+                .unwrap()
+                .to_rounded(4, Rounding::HalfEven),
+            "1.2344"
+        );
+    }
+
+    #[test]
+    fn test_serde_forms() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Balance {
+            balance_cil: Cil,
+            balance_los: Los,
+        }
+        let b = Balance {
+            balance_cil: Cil(250_000_000_001),
+            balance_los: Cil(250_000_000_001).to_los(),
+        };
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(
+            json,
+            r#"{"balance_cil":250000000001,"balance_los":"2.50000000001"}"#
+        );
+        assert_eq!(serde_json::from_str::<Balance>(&json).unwrap(), b);
+        assert!(serde_json::from_str::<Los>(r#""0.000000000001""#).is_err());
+    }
+}
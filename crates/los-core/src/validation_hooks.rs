@@ -0,0 +1,191 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) — PLUGGABLE BLOCK VALIDATION HOOKS
+//
+// Embedders (private deployments, exchanges running their own ledger) can add
+// policy without forking los-core: minimum amounts, address blocklists, ...
+//
+//   ledger.add_block_validator(Arc::new(MyPolicy));
+//
+// Ledger::process_block runs the hooks in registration order after its own
+// checks (PoW, signature, key binding, chain sequence, timestamp) and before
+// any state changes. The first Err rejects the block with
+// "Policy Error [<hook name>]: ...". Hooks only ever reject — they cannot
+// accept a block the built-in rules refuse.
+//
+// Hooks are process-local: they are not serialized with the ledger and must
+// be registered again after loading one. Validators on a shared network must
+// run identical hooks, otherwise they disagree on which blocks are valid.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::{AccountState, Block, Ledger};
+use std::sync::Arc;
+
+/// Custom block policy. Implementations must be deterministic.
+pub trait BlockValidator: Send + Sync {
+    /// Name shown in rejection messages
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    /// Accept (Ok) or reject (Err) `block`. `account` is the sender's state
+    /// before the block; `ledger` is the state the block would apply to.
+    fn validate(
+        &self,
+        block: &Block,
+        account: &AccountState,
+        ledger: &Ledger,
+    ) -> Result<(), String> {
+        let _ = (block, account, ledger);
+        Ok(())
+    }
+}
+
+/// Accepts every block (the default behaviour of a ledger without hooks)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopValidator;
+
+impl BlockValidator for NoopValidator {
+    fn name(&self) -> &str {
+        "noop"
+    }
+}
+
+/// Registered hooks of a ledger. Skipped by serde; clones share the hooks.
+#[derive(Clone, Default)]
+pub struct ValidatorHooks(Vec<Arc<dyn BlockValidator>>);
+
+impl std::fmt::Debug for ValidatorHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|h| h.name()))
+            .finish()
+    }
+}
+
+impl Ledger {
+    /// Append a hook to the chain run by process_block
+    pub fn add_block_validator(&mut self, hook: Arc<dyn BlockValidator>) {
+        self.validator_hooks.0.push(hook);
+    }
+
+    /// Remove all hooks
+    pub fn clear_block_validators(&mut self) {
+        self.validator_hooks.0.clear();
+    }
+
+    /// Names of the registered hooks, in run order
+    pub fn block_validator_names(&self) -> Vec<String> {
+        self.validator_hooks
+            .0
+            .iter()
+            .map(|h| h.name().to_string())
+            .collect()
+    }
+
+    /// Run the hook chain; the first rejection wins
+    pub(crate) fn run_block_validators(
+        &self,
+        block: &Block,
+        account: &AccountState,
+    ) -> Result<(), String> {
+        for hook in &self.validator_hooks.0 {
+            hook.validate(block, account, self)
+                .map_err(|e| format!("Policy Error [{}]: {}", hook.name(), e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockType;
+    use std::collections::BTreeSet;
+
+    struct MinAmount(u128);
+    impl BlockValidator for MinAmount {
+        fn name(&self) -> &str {
+            "min-amount"
+        }
+        fn validate(&self, block: &Block, _: &AccountState, _: &Ledger) -> Result<(), String> {
+            if block.block_type == BlockType::Send && block.amount < self.0 {
+                return Err(format!("send below {} CIL", self.0));
+            }
+            Ok(())
+        }
+    }
+
+    struct Blocklist(BTreeSet<String>);
+    impl BlockValidator for Blocklist {
+        fn name(&self) -> &str {
+            "blocklist"
+        }
+        fn validate(&self, block: &Block, _: &AccountState, _: &Ledger) -> Result<(), String> {
+            if self.0.contains(&block.account) || self.0.contains(&block.link) {
+                return Err("address is blocked".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    fn send(account: &str, to: &str, amount: u128) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Send,
+            amount,
+            link: to.to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: 100_000,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_hooks_run_in_order_and_first_rejection_wins() {
+        let mut ledger = Ledger::new();
+        let state = AccountState {
+            head: "0".to_string(),
+            balance: 1_000_000,
+            block_count: 0,
+            is_validator: false,
+        };
+        assert!(ledger
+            .run_block_validators(&send("LOSa", "LOSb", 1), &state)
+            .is_ok());
+
+        ledger.add_block_validator(Arc::new(NoopValidator));
+        ledger.add_block_validator(Arc::new(MinAmount(10)));
+        ledger.add_block_validator(Arc::new(Blocklist(
+            ["LOSbad".to_string()].into_iter().collect(),
+        )));
+        assert_eq!(
+            ledger.block_validator_names(),
+            vec!["noop", "min-amount", "blocklist"]
+        );
+
+        assert!(ledger
+            .run_block_validators(&send("LOSa", "LOSb", 10), &state)
+            .is_ok());
+        let err = ledger
+            .run_block_validators(&send("LOSa", "LOSbad", 1), &state)
+            .unwrap_err();
+        assert_eq!(err, "Policy Error [min-amount]: send below 10 CIL");
+        assert!(ledger
+            .run_block_validators(&send("LOSa", "LOSbad", 10), &state)
+            .unwrap_err()
+            .contains("[blocklist]"));
+
+        // Clones share hooks; serialization drops them
+        assert_eq!(ledger.clone().block_validator_names().len(), 3);
+        let json = serde_json::to_string(&ledger).unwrap();
+        let loaded: Ledger = serde_json::from_str(&json).unwrap();
+        assert!(loaded.block_validator_names().is_empty());
+        ledger.clear_block_validators();
+        assert!(ledger.block_validator_names().is_empty());
+    }
+}
@@ -6,6 +6,9 @@
 // Weight:      Linear stake (1 CIL = 1 unit of reward weight)
 // Eligibility: 1000 LOS min stake, 95% uptime, 30-day probation passed
 // Lifespan:    Pool lasts ~16-20 years (asymptotic halving)
+// Exhaustion:  The epoch that empties the pool pays out all of it (rounding
+//              dust included); once the pool is empty or the halved rate
+//              reaches zero, validators earn redistributed fees only
 //
 // Changed from √stake to linear weight.
 // √stake incentivizes Sybil attacks (splitting stake into multiple
@@ -15,10 +18,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::reward_cosign::{DistributionCertificate, DistributionKind, EpochDistribution};
 use crate::{
-    effective_reward_epoch_secs, MIN_VALIDATOR_STAKE_CIL, REWARD_HALVING_INTERVAL_EPOCHS,
-    REWARD_MIN_UPTIME_PCT, REWARD_PROBATION_EPOCHS, REWARD_RATE_INITIAL_CIL,
-    VALIDATOR_REWARD_POOL_CIL,
+    effective_reward_epoch_secs, effective_reward_halving_interval,
+    effective_reward_probation_epochs, effective_reward_rate_initial_cil, Block, BlockType,
+    MIN_VALIDATOR_STAKE_CIL, REWARD_MIN_UPTIME_PCT, VALIDATOR_REWARD_POOL_CIL,
 };
 
 /// Per-validator reward tracking state.
@@ -84,6 +88,11 @@ impl ValidatorRewardState {
         current.max(self.last_epoch_uptime_pct)
     }
 
+    /// Epochs left before this validator leaves probation (0 = past it)
+    pub fn probation_epochs_left(&self, current_epoch: u64) -> u64 {
+        (self.join_epoch + effective_reward_probation_epochs()).saturating_sub(current_epoch)
+    }
+
     /// Returns true if this validator is eligible for rewards this epoch.
     /// Requirements:
     /// 1. NOT a genesis bootstrap validator (genesis nodes donate rewards to public)
@@ -104,7 +113,7 @@ impl ValidatorRewardState {
         // A validator joining at epoch N is eligible starting at epoch N + PROBATION_EPOCHS.
         // This applies to ALL validators equally — genesis and non-genesis.
         // Epoch 0 is the bootstrap epoch; no validator earns rewards in their join epoch.
-        if current_epoch < self.join_epoch + REWARD_PROBATION_EPOCHS {
+        if current_epoch < self.join_epoch + effective_reward_probation_epochs() {
             return false;
         }
         // Use display_uptime_pct() which returns max(current_epoch, last_epoch).
@@ -128,6 +137,107 @@ impl ValidatorRewardState {
     }
 }
 
+/// Link prefix of fee redistribution Mint blocks ("FEE_REWARD:EPOCH:{epoch}")
+pub const FEE_REWARD_LINK_PREFIX: &str = "FEE_REWARD:EPOCH:";
+/// Epochs of fee distribution history kept (oldest dropped first)
+pub const MAX_FEE_HISTORY_EPOCHS: usize = 1_024;
+
+/// Epoch of a FEE_REWARD Mint block (None for any other block).
+/// Accepts both "FEE_REWARD:EPOCH:{epoch}" and the co-signed
+/// "FEE_REWARD:EPOCH:{epoch}:{certificate}" (see reward_cosign.rs).
+pub fn fee_reward_epoch(block: &Block) -> Option<u64> {
+    if block.block_type != BlockType::Mint {
+        return None;
+    }
+    block
+        .link
+        .strip_prefix(FEE_REWARD_LINK_PREFIX)
+        .map(|rest| rest.split_once(':').map_or(rest, |(epoch, _)| epoch))
+        .and_then(|e| e.parse().ok())
+}
+
+/// REWARD Mint link prefix; the epoch follows ("REWARD:EPOCH:{epoch}")
+pub const REWARD_LINK_PREFIX: &str = "REWARD:EPOCH:";
+
+/// Epoch of a pool REWARD Mint block (None for any other block, including
+/// FEE_REWARD mints). Accepts the co-signed form like [`fee_reward_epoch`].
+pub fn reward_epoch(block: &Block) -> Option<u64> {
+    if block.block_type != BlockType::Mint {
+        return None;
+    }
+    block
+        .link
+        .strip_prefix(REWARD_LINK_PREFIX)
+        .map(|rest| rest.split_once(':').map_or(rest, |(epoch, _)| epoch))
+        .and_then(|e| e.parse().ok())
+}
+
+/// Split `budget` pro rata over `weights`: share_i = budget × w_i / Σw,
+/// rounded down. Zero shares are left out; the rounding remainder is not
+/// assigned. Used for pool rewards and for fee redistribution.
+pub fn pro_rata_shares(budget: u128, weights: &[(String, u128)]) -> Vec<(String, u128)> {
+    let total_weight: u128 = weights
+        .iter()
+        .fold(0u128, |acc, (_, w)| acc.saturating_add(*w));
+    if budget == 0 || total_weight == 0 {
+        return Vec::new();
+    }
+    weights
+        .iter()
+        .map(|(addr, weight)| {
+            // On overflow, divide first (less precise, but never zero for
+            // non-zero inputs) instead of silently dropping the share
+            let share = match budget.checked_mul(*weight) {
+                Some(prod) => prod / total_weight,
+                None => {
+                    (budget / total_weight) * (*weight)
+                        + (budget % total_weight) * (*weight) / total_weight
+                }
+            };
+            (addr.clone(), share)
+        })
+        .filter(|(_, share)| *share > 0)
+        .collect()
+}
+
+/// Where validator income comes from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardPhase {
+    /// Pool rewards on the halving schedule, plus redistributed fees
+    Emission,
+    /// Pool empty (or halved down to zero): redistributed fees only
+    FeeOnly,
+}
+
+/// Reward budget of `epoch` (with halving).
+/// After n halvings: rate = initial_rate >> n
+pub fn reward_rate_for_epoch(epoch: u64) -> u128 {
+    let halvings = epoch / effective_reward_halving_interval();
+    if halvings >= 128 {
+        return 0; // Effectively zero after 128 halvings
+    }
+    effective_reward_rate_initial_cil() >> halvings
+}
+
+/// One validator's share of an epoch's transaction fees.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeeShare {
+    pub validator: String,
+    pub amount_cil: u128,
+    /// FEE_REWARD Mint block that credited the share
+    pub block_hash: String,
+}
+
+/// Transaction fees redistributed for one epoch.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeeDistributionRecord {
+    pub epoch: u64,
+    pub total_fees_cil: u128,
+    /// Sorted by validator address
+    pub shares: Vec<FeeShare>,
+}
+
 /// Global reward pool and epoch tracking state.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ValidatorRewardPool {
@@ -148,6 +258,23 @@ pub struct ValidatorRewardPool {
     /// Defaults to effective_reward_epoch_secs() if not present (backwards-compatible).
     #[serde(default = "default_epoch_duration")]
     pub epoch_duration_secs: u64,
+    /// Fee redistribution per epoch, recorded from applied FEE_REWARD blocks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fee_history: BTreeMap<u64, FeeDistributionRecord>,
+    /// Claim payout model: accrual account per validator — certified rewards
+    /// credited so far (CIL, cumulative). Kept apart from `validators` so an
+    /// exit does not forfeit them; claimable = accrued − claimed (reward_claims.rs).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accruals: BTreeMap<String, u128>,
+    /// Latest epoch whose certified reward distribution was accrued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accrued_epoch: Option<u64>,
+    /// Certificates of the accrued epochs, the proof a Claim block carries
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accrued_certificates: BTreeMap<u64, DistributionCertificate>,
+    /// First epoch paid from fees only (set once, see `enter_fee_only`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_only_since_epoch: Option<u64>,
 }
 
 fn default_epoch_duration() -> u64 {
@@ -166,6 +293,11 @@ impl ValidatorRewardPool {
             total_distributed_cil: 0,
             validators: BTreeMap::new(),
             epoch_duration_secs: effective_reward_epoch_secs(),
+            fee_history: BTreeMap::new(),
+            accruals: BTreeMap::new(),
+            last_accrued_epoch: None,
+            accrued_certificates: BTreeMap::new(),
+            fee_only_since_epoch: None,
         }
     }
 
@@ -179,6 +311,11 @@ impl ValidatorRewardPool {
             total_distributed_cil: 0,
             validators: BTreeMap::new(),
             epoch_duration_secs: effective_reward_epoch_secs(),
+            fee_history: BTreeMap::new(),
+            accruals: BTreeMap::new(),
+            last_accrued_epoch: None,
+            accrued_certificates: BTreeMap::new(),
+            fee_only_since_epoch: None,
         }
     }
 
@@ -226,14 +363,36 @@ impl ValidatorRewardPool {
     }
 
     /// Calculate the reward rate for the current epoch (with halving).
-    /// Rate halves every `REWARD_HALVING_INTERVAL_EPOCHS` epochs.
+    /// Rate halves every `reward_halving_interval_epochs` (chain spec) epochs.
     /// After n halvings: rate = initial_rate >> n
     pub fn epoch_reward_rate(&self) -> u128 {
-        let halvings = self.current_epoch / REWARD_HALVING_INTERVAL_EPOCHS;
-        if halvings >= 128 {
-            return 0; // Effectively zero after 128 halvings
+        reward_rate_for_epoch(self.current_epoch)
+    }
+
+    /// Emission until the pool is empty or the current rate has halved to
+    /// zero, fee-only from then on (neither can recover)
+    pub fn phase(&self) -> RewardPhase {
+        if self.fee_only_since_epoch.is_some()
+            || self.remaining_cil == 0
+            || self.epoch_reward_rate() == 0
+        {
+            RewardPhase::FeeOnly
+        } else {
+            RewardPhase::Emission
+        }
+    }
+
+    /// Record the switch to fee-only rewards, starting at `epoch`, if the
+    /// pool is empty or the rate of `epoch` is zero. Returns true on the
+    /// transition (once per pool).
+    pub fn enter_fee_only(&mut self, epoch: u64) -> bool {
+        if self.fee_only_since_epoch.is_some()
+            || (self.remaining_cil > 0 && reward_rate_for_epoch(epoch) > 0)
+        {
+            return false;
         }
-        REWARD_RATE_INITIAL_CIL >> halvings
+        self.fee_only_since_epoch = Some(epoch);
+        true
     }
 
     /// Check if the current epoch has ended (based on timestamp).
@@ -278,7 +437,8 @@ impl ValidatorRewardPool {
         let skip = epochs_behind - 1;
         self.current_epoch += skip;
         self.epoch_start_timestamp += skip * self.epoch_duration_secs;
-        self.halvings_occurred = self.current_epoch / REWARD_HALVING_INTERVAL_EPOCHS;
+        self.halvings_occurred = self.current_epoch / effective_reward_halving_interval();
+        self.enter_fee_only(self.current_epoch);
         // Reset heartbeats since nobody was online
         for state in self.validators.values_mut() {
             state.heartbeats_current_epoch = 0;
@@ -298,11 +458,10 @@ impl ValidatorRewardPool {
     /// When called at epoch START (after advance_epoch reset counters),
     /// all validators have heartbeats=0 so they get the full expected count.
     pub fn set_expected_heartbeats(&mut self, heartbeat_interval_secs: u64) {
-        let full_expected = if heartbeat_interval_secs > 0 {
-            self.epoch_duration_secs / heartbeat_interval_secs
-        } else {
-            0
-        };
+        let full_expected = self
+            .epoch_duration_secs
+            .checked_div(heartbeat_interval_secs)
+            .unwrap_or(0);
         for state in self.validators.values_mut() {
             if state.heartbeats_current_epoch == 0 {
                 // Epoch start or no heartbeats yet: set full expected
@@ -329,6 +488,11 @@ impl ValidatorRewardPool {
     /// The caller is responsible for crediting these amounts to the ledger.
     ///
     /// After distribution, advances to the next epoch and resets heartbeat counters.
+    ///
+    /// The final partial epoch (pool below the epoch rate) pays out the whole
+    /// remaining pool: the rounding remainder goes to the heaviest eligible
+    /// validator, so the pool ends at exactly zero and the next epoch is
+    /// fee-only (see [`RewardPhase`]).
     pub fn distribute_epoch_rewards(&mut self) -> Vec<(String, u128)> {
         let epoch_rate = self.epoch_reward_rate();
         if epoch_rate == 0 || self.remaining_cil == 0 {
@@ -338,6 +502,7 @@ impl ValidatorRewardPool {
 
         // Cap at remaining pool balance
         let budget = epoch_rate.min(self.remaining_cil);
+        let final_epoch = budget == self.remaining_cil;
 
         // Collect eligible validators and their linear stake weights
         let eligible: Vec<(String, u128)> = self
@@ -354,34 +519,21 @@ impl ValidatorRewardPool {
             return vec![];
         }
 
-        let total_weight: u128 = eligible.iter().map(|(_, w)| w).sum();
-        if total_weight == 0 {
-            self.advance_epoch();
-            return vec![];
-        }
-
         // Proportional distribution: reward_i = budget × (weight_i / total_weight)
-        let mut rewards: Vec<(String, u128)> = Vec::new();
-        let mut actually_distributed: u128 = 0;
-
-        for (addr, weight) in &eligible {
-            // Use u128 multiplication then divide to avoid overflow:
-            // reward = (budget * weight) / total_weight
-            // On overflow, use divide-before-multiply fallback
-            // instead of returning 0 (which would silently lose validator rewards).
-            let reward = match budget.checked_mul(*weight) {
-                Some(prod) => prod / total_weight,
-                None => {
-                    // Overflow: divide first (less precise, but never zero for non-zero inputs)
-                    (budget / total_weight) * (*weight)
-                        + (budget % total_weight) * (*weight) / total_weight
-                }
-            };
-
-            if reward > 0 {
-                rewards.push((addr.clone(), reward));
-                actually_distributed += reward;
+        let mut rewards = pro_rata_shares(budget, &eligible);
+        let mut actually_distributed: u128 = rewards.iter().map(|(_, r)| r).sum();
+
+        if final_epoch && actually_distributed < budget {
+            // Heaviest validator, lowest address on ties (eligible is sorted)
+            let dust = budget - actually_distributed;
+            let heaviest = eligible
+                .iter()
+                .fold(&eligible[0], |best, v| if v.1 > best.1 { v } else { best });
+            match rewards.iter_mut().find(|(addr, _)| *addr == heaviest.0) {
+                Some((_, reward)) => *reward += dust,
+                None => rewards.push((heaviest.0.clone(), dust)),
             }
+            actually_distributed = budget;
         }
 
         // Deduct from pool
@@ -399,11 +551,13 @@ impl ValidatorRewardPool {
         rewards
     }
 
-    /// Advance to the next epoch: increment counter, reset heartbeats, update halvings.
+    /// Advance to the next epoch: increment counter, reset heartbeats, update
+    /// halvings, switch to fee-only if nothing is left to emit.
     fn advance_epoch(&mut self) {
         self.current_epoch += 1;
         self.epoch_start_timestamp += self.epoch_duration_secs;
-        self.halvings_occurred = self.current_epoch / REWARD_HALVING_INTERVAL_EPOCHS;
+        self.halvings_occurred = self.current_epoch / effective_reward_halving_interval();
+        self.enter_fee_only(self.current_epoch);
 
         // Save last epoch uptime before resetting counters
         for state in self.validators.values_mut() {
@@ -432,6 +586,102 @@ impl ValidatorRewardPool {
         }
     }
 
+    /// Account for an applied REWARD or FEE_REWARD Mint block received from
+    /// the leader. Only REWARD mints come out of the pool; a mint that empties
+    /// it switches to fee-only from the following epoch. Returns true on that
+    /// transition.
+    pub fn sync_reward_mint(&mut self, block: &Block) -> bool {
+        if let Some(epoch) = reward_epoch(block) {
+            self.sync_reward_from_gossip(&block.account, block.amount);
+            return self.enter_fee_only(epoch + 1);
+        }
+        self.record_fee_reward(block);
+        false
+    }
+
+    /// Record an applied FEE_REWARD Mint block in the fee history.
+    /// Returns false for other blocks and for blocks already recorded.
+    pub fn record_fee_reward(&mut self, block: &Block) -> bool {
+        let Some(epoch) = fee_reward_epoch(block) else {
+            return false;
+        };
+        let block_hash = block.calculate_hash();
+        let record = self
+            .fee_history
+            .entry(epoch)
+            .or_insert_with(|| FeeDistributionRecord {
+                epoch,
+                total_fees_cil: 0,
+                shares: Vec::new(),
+            });
+        if record.shares.iter().any(|s| s.block_hash == block_hash) {
+            return false;
+        }
+        record.total_fees_cil = record.total_fees_cil.saturating_add(block.amount);
+        record.shares.push(FeeShare {
+            validator: block.account.clone(),
+            amount_cil: block.amount,
+            block_hash,
+        });
+        record
+            .shares
+            .sort_by(|a, b| (&a.validator, &a.block_hash).cmp(&(&b.validator, &b.block_hash)));
+        while self.fee_history.len() > MAX_FEE_HISTORY_EPOCHS {
+            self.fee_history.pop_first();
+        }
+        true
+    }
+
+    /// Credit a certified reward distribution to the validators' accrual
+    /// accounts (claim payout model). Each epoch accrues once, in order;
+    /// returns false for fee distributions and for epochs already accrued.
+    /// Pool totals are not touched — callers sync them like reward mints.
+    pub fn accrue_distribution(&mut self, certificate: &DistributionCertificate) -> bool {
+        let distribution = &certificate.distribution;
+        if distribution.kind != DistributionKind::Reward
+            || self
+                .last_accrued_epoch
+                .is_some_and(|e| distribution.epoch <= e)
+        {
+            return false;
+        }
+        for (addr, amount) in &distribution.payouts {
+            let accrued = self.accruals.entry(addr.clone()).or_insert(0);
+            *accrued = accrued.saturating_add(*amount);
+        }
+        self.last_accrued_epoch = Some(distribution.epoch);
+        self.accrued_certificates
+            .insert(distribution.epoch, certificate.clone());
+        true
+    }
+
+    /// Accrued payouts to `address` not yet minted, as (epoch, CIL, certificate)
+    pub fn unclaimed_payouts<'a>(
+        &'a self,
+        address: &'a str,
+        is_claimed: impl Fn(&EpochDistribution) -> bool + 'a,
+    ) -> impl Iterator<Item = (u64, u128, &'a DistributionCertificate)> + 'a {
+        self.accrued_certificates
+            .iter()
+            .filter(move |(_, cert)| !is_claimed(&cert.distribution))
+            .filter_map(move |(epoch, cert)| {
+                let amount = *cert.distribution.payouts.get(address)?;
+                Some((*epoch, amount, cert))
+            })
+    }
+
+    /// Accrued rewards `address` can still claim, given what it already claimed
+    pub fn claimable_cil(&self, address: &str, claimed_cil: u128) -> u128 {
+        self.accruals
+            .get(address)
+            .map_or(0, |accrued| accrued.saturating_sub(claimed_cil))
+    }
+
+    /// Fees redistributed in `epoch` (None if none were recorded)
+    pub fn fee_distribution(&self, epoch: u64) -> Option<&FeeDistributionRecord> {
+        self.fee_history.get(&epoch)
+    }
+
     /// Unregister a validator from reward tracking (voluntary exit or auto-unregister).
     /// Returns true if the validator was found and removed.
     pub fn unregister_validator(&mut self, address: &str) -> bool {
@@ -451,6 +701,34 @@ impl ValidatorRewardPool {
         self.validators.get(address)
     }
 
+    /// Deterministic root of the reward state all validators agree on.
+    ///
+    /// Covers the pool balance, total distributed, each validator's
+    /// cumulative rewards and the claim-model accruals — values that only
+    /// move when reward blocks or certified accruals are applied. Heartbeat counters and the epoch clock are node-local
+    /// observations and are left out, so honest validators never disagree
+    /// on this root because of network timing.
+    pub fn state_root(&self) -> String {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.remaining_cil.to_le_bytes());
+        hasher.update(self.total_distributed_cil.to_le_bytes());
+        // BTreeMap iterates in sorted key order — deterministic
+        for (addr, state) in &self.validators {
+            hasher.update((addr.len() as u64).to_le_bytes());
+            hasher.update(addr.as_bytes());
+            hasher.update(state.cumulative_rewards_cil.to_le_bytes());
+        }
+        // Empty on push-model networks, leaving their root unchanged
+        for (addr, accrued) in &self.accruals {
+            hasher.update(b"ACCRUAL");
+            hasher.update((addr.len() as u64).to_le_bytes());
+            hasher.update(addr.as_bytes());
+            hasher.update(accrued.to_le_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
     /// Summary stats for the reward pool.
     pub fn pool_summary(&self) -> RewardPoolSummary {
         let eligible_count = self
@@ -468,13 +746,13 @@ impl ValidatorRewardPool {
             halvings_occurred: self.halvings_occurred,
             total_validators,
             eligible_validators: eligible_count,
-            pool_exhaustion_bps: if VALIDATOR_REWARD_POOL_CIL > 0 {
-                // Basis points (10000 = 100%) — pure integer math
-                ((VALIDATOR_REWARD_POOL_CIL - self.remaining_cil) * 10_000
-                    / VALIDATOR_REWARD_POOL_CIL) as u64
-            } else {
-                0
-            },
+            // Basis points (10000 = 100%) — pure integer math
+            pool_exhaustion_bps: (VALIDATOR_REWARD_POOL_CIL.saturating_sub(self.remaining_cil)
+                * 10_000)
+                .checked_div(VALIDATOR_REWARD_POOL_CIL)
+                .unwrap_or(0) as u64,
+            phase: self.phase(),
+            fee_only_since_epoch: self.fee_only_since_epoch,
         }
     }
 }
@@ -491,6 +769,9 @@ pub struct RewardPoolSummary {
     pub eligible_validators: u64,
     /// Pool exhaustion in basis points (10000 = 100%), pure integer
     pub pool_exhaustion_bps: u64,
+    pub phase: RewardPhase,
+    /// First fee-only epoch (None while emitting)
+    pub fee_only_since_epoch: Option<u64>,
 }
 
 // ─────────────────────────────────────────────────────────────────
@@ -535,6 +816,51 @@ mod tests {
         assert_eq!(isqrt(1_000_000), 1000);
     }
 
+    #[test]
+    fn test_fee_history_records_fee_reward_blocks() {
+        let mut pool = ValidatorRewardPool::new(GENESIS_TS);
+        let fee_block = |account: &str, amount: u128, link: &str| Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Mint,
+            amount,
+            link: link.to_string(),
+            signature: "sig".to_string(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: GENESIS_TS,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let bob = fee_block("LOSbob", 300, "FEE_REWARD:EPOCH:7");
+        assert!(pool.record_fee_reward(&bob));
+        assert!(pool.record_fee_reward(&fee_block("LOSalice", 700, "FEE_REWARD:EPOCH:7")));
+        // Same block seen again via sync is not double-counted
+        assert!(!pool.record_fee_reward(&bob));
+        // Validator rewards and other mints are not fee redistribution
+        assert!(!pool.record_fee_reward(&fee_block("LOSbob", 5, "REWARD:EPOCH:7")));
+        assert!(!pool.record_fee_reward(&fee_block("LOSbob", 5, "FEE_REWARD:EPOCH:x")));
+        // Co-signed links carry the certificate after the epoch
+        assert!(pool.record_fee_reward(&fee_block("LOScarol", 5, "FEE_REWARD:EPOCH:7:{}")));
+
+        let record = pool.fee_distribution(7).unwrap();
+        assert_eq!(record.total_fees_cil, 1_005);
+        assert_eq!(record.shares[0].validator, "LOSalice");
+        assert_eq!(record.shares[1].block_hash, bob.calculate_hash());
+        assert!(pool.fee_distribution(8).is_none());
+
+        for epoch in 0..MAX_FEE_HISTORY_EPOCHS as u64 + 10 {
+            pool.record_fee_reward(&fee_block(
+                "LOSbob",
+                1,
+                &format!("FEE_REWARD:EPOCH:{}", epoch + 100),
+            ));
+        }
+        assert_eq!(pool.fee_history.len(), MAX_FEE_HISTORY_EPOCHS);
+        assert!(pool.fee_distribution(7).is_none());
+    }
+
     #[test]
     fn test_pool_creation() {
         let pool = ValidatorRewardPool::new(GENESIS_TS);
@@ -786,6 +1112,112 @@ mod tests {
         assert!(total <= 1_000 * CIL_PER_LOS);
     }
 
+    #[test]
+    fn test_final_epoch_drains_pool_to_zero() {
+        // 1000 CIL + 1 over three equal validators: 333 each, 2 CIL of dust
+        let mut pool = ValidatorRewardPool::with_balance(GENESIS_TS, 1_000);
+        for addr in ["LOSc", "LOSa", "LOSb"] {
+            pool.register_validator(addr, false, 1_000 * CIL_PER_LOS);
+        }
+        pool.current_epoch = 2;
+        pool.set_expected_heartbeats(60);
+        for v in pool.validators.values_mut() {
+            v.heartbeats_current_epoch = v.expected_heartbeats;
+        }
+        assert_eq!(pool.phase(), RewardPhase::Emission);
+
+        let rewards = pool.distribute_epoch_rewards();
+        let total: u128 = rewards.iter().map(|(_, r)| r).sum();
+        assert_eq!(total, 1_000);
+        // Dust goes to the lowest address among the equally heavy
+        assert!(rewards.contains(&("LOSa".to_string(), 334)));
+        assert_eq!(pool.remaining_cil, 0);
+        assert_eq!(pool.total_distributed_cil, 1_000);
+
+        // Next epoch pays fees only, and nothing more comes out of the pool
+        assert_eq!(pool.fee_only_since_epoch, Some(3));
+        assert_eq!(pool.phase(), RewardPhase::FeeOnly);
+        assert!(pool.distribute_epoch_rewards().is_empty());
+        assert_eq!(pool.fee_only_since_epoch, Some(3));
+        assert_eq!(pool.pool_summary().phase, RewardPhase::FeeOnly);
+    }
+
+    #[test]
+    fn test_zero_rate_enters_fee_only() {
+        let mut pool = ValidatorRewardPool::new(GENESIS_TS);
+        assert!(!pool.enter_fee_only(0));
+        assert_eq!(pool.phase(), RewardPhase::Emission);
+
+        // 128 halvings shift the rate to zero with CIL still in the pool
+        let epoch = 128 * effective_reward_halving_interval();
+        assert_eq!(reward_rate_for_epoch(epoch), 0);
+        assert!(pool.enter_fee_only(epoch));
+        assert!(!pool.enter_fee_only(epoch + 1));
+        assert_eq!(pool.fee_only_since_epoch, Some(epoch));
+        assert!(pool.remaining_cil > 0);
+        assert_eq!(pool.phase(), RewardPhase::FeeOnly);
+    }
+
+    #[test]
+    fn test_sync_reward_mint() {
+        let mint = |amount: u128, link: &str| Block {
+            account: "LOSval".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Mint,
+            amount,
+            link: link.to_string(),
+            signature: "sig".to_string(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: GENESIS_TS,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let mut pool = ValidatorRewardPool::with_balance(GENESIS_TS, 1_000);
+        pool.register_validator("LOSval", false, 1_000 * CIL_PER_LOS);
+
+        // Fee mints are recorded but never come out of the pool
+        assert!(!pool.sync_reward_mint(&mint(500, "FEE_REWARD:EPOCH:4")));
+        assert_eq!(pool.remaining_cil, 1_000);
+        assert!(pool.fee_distribution(4).is_some());
+
+        assert!(!pool.sync_reward_mint(&mint(600, "REWARD:EPOCH:4")));
+        assert_eq!(pool.remaining_cil, 400);
+        // The mint that empties the pool switches the next epoch to fee-only
+        assert!(pool.sync_reward_mint(&mint(400, "REWARD:EPOCH:4:{}")));
+        assert_eq!(pool.fee_only_since_epoch, Some(5));
+        assert_eq!(
+            pool.validator_info("LOSval")
+                .unwrap()
+                .cumulative_rewards_cil,
+            1_000
+        );
+    }
+
+    #[test]
+    fn test_pro_rata_shares() {
+        let weights = |ws: &[u128]| -> Vec<(String, u128)> {
+            ws.iter()
+                .enumerate()
+                .map(|(i, w)| (format!("LOS{}", i), *w))
+                .collect()
+        };
+        assert!(pro_rata_shares(0, &weights(&[1, 2])).is_empty());
+        assert!(pro_rata_shares(100, &weights(&[0, 0])).is_empty());
+        assert!(pro_rata_shares(100, &[]).is_empty());
+        assert_eq!(
+            pro_rata_shares(100, &weights(&[1, 3])),
+            vec![("LOS0".to_string(), 25), ("LOS1".to_string(), 75)]
+        );
+        // Shares that round down to zero are left out
+        assert_eq!(pro_rata_shares(2, &weights(&[1, 1_000])).len(), 1);
+        // budget × weight overflows u128 but the split still adds up
+        let big = pro_rata_shares(u128::MAX / 2, &weights(&[u128::MAX / 4, u128::MAX / 4]));
+        assert_eq!(big.len(), 2);
+        assert!(big[0].1 >= u128::MAX / 4 - 1);
+    }
+
     #[test]
     fn test_epoch_timing() {
         let pool = ValidatorRewardPool::new(GENESIS_TS);
@@ -850,6 +1282,25 @@ mod tests {
         assert_eq!(summary.epoch_reward_rate_cil, 5_000 * CIL_PER_LOS);
     }
 
+    #[test]
+    fn test_state_root_ignores_local_observations() {
+        let mut pool = ValidatorRewardPool::new(GENESIS_TS);
+        pool.register_validator("LOSval1", false, 2000 * CIL_PER_LOS);
+        let root = pool.state_root();
+
+        // Heartbeats and the epoch clock differ between honest nodes
+        pool.record_heartbeat("LOSval1");
+        pool.current_epoch += 1;
+        assert_eq!(pool.state_root(), root);
+
+        // Applied rewards do not
+        pool.validators
+            .get_mut("LOSval1")
+            .unwrap()
+            .cumulative_rewards_cil += 1;
+        assert_ne!(pool.state_root(), root);
+    }
+
     #[test]
     fn test_minimum_stake_requirement() {
         let mut pool = ValidatorRewardPool::new(GENESIS_TS);
@@ -11,9 +11,7 @@
 use los_core::pow_mint::{
     compute_mining_hash, count_leading_zero_bits, verify_mining_hash, MiningState,
 };
-use los_core::{
-    Block, BlockType, Ledger, BASE_FEE_CIL, CIL_PER_LOS, MIN_POW_DIFFICULTY_BITS, TOTAL_SUPPLY_CIL,
-};
+use los_core::{Block, BlockType, Ledger, BASE_FEE_CIL, CIL_PER_LOS, TOTAL_SUPPLY_CIL};
 use proptest::prelude::*;
 
 // ─────────────────────────────────────────────────────────────────
@@ -69,6 +67,8 @@ fn arb_block() -> impl Strategy<Value = Block> {
                     work,
                     timestamp,
                     fee,
+                    valid_after: 0,
+                    expires_at: 0,
                 }
             },
         )
@@ -127,6 +127,8 @@ proptest! {
             work: 0,
             timestamp: 1_700_000_000,
             fee: 0,
+            valid_after: 0,
+            expires_at: 0,
         };
         let block2 = Block { amount: amount2, ..block1.clone() };
         prop_assert_ne!(block1.signing_hash(), block2.signing_hash());
@@ -249,7 +251,7 @@ proptest! {
     #[test]
     fn prop_state_root_deterministic(
         balances in proptest::collection::vec(
-            (1u128..=1_000_000 * CIL_PER_LOS),
+            1u128..=1_000_000 * CIL_PER_LOS,
             1..10
         ),
     ) {
@@ -10,8 +10,8 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use los_crypto::{
-    generate_keypair, generate_keypair_from_seed, public_key_to_address, sign_message,
-    validate_address, verify_signature,
+    clear_public_key_cache, generate_keypair, generate_keypair_from_seed, public_key_to_address,
+    sign_message, validate_address, verify_signature,
 };
 
 // ─────────────────────────────────────────────────────────────────
@@ -75,9 +75,36 @@ fn bench_verify(c: &mut Criterion) {
 
 fn bench_address_derivation(c: &mut Criterion) {
     let kp = generate_keypair();
-    c.bench_function("crypto/public_key_to_address", |b| {
+    let mut group = c.benchmark_group("crypto/public_key_to_address");
+    group.bench_function("cached", |b| {
         b.iter(|| black_box(public_key_to_address(&kp.public_key)))
     });
+    group.bench_function("cold", |b| {
+        b.iter(|| {
+            clear_public_key_cache();
+            black_box(public_key_to_address(&kp.public_key))
+        })
+    });
+    group.finish();
+}
+
+fn bench_verify_key_cache(c: &mut Criterion) {
+    let kp = generate_keypair();
+    let message = vec![0xAB; 256];
+    let sig = sign_message(&message, &kp.secret_key).unwrap();
+
+    // Repeat sender (key cached) vs first-seen sender (key parsed)
+    let mut group = c.benchmark_group("crypto/verify_key_cache");
+    group.bench_function("cached", |b| {
+        b.iter(|| black_box(verify_signature(&message, &sig, &kp.public_key)))
+    });
+    group.bench_function("cold", |b| {
+        b.iter(|| {
+            clear_public_key_cache();
+            black_box(verify_signature(&message, &sig, &kp.public_key))
+        })
+    });
+    group.finish();
 }
 
 fn bench_address_validation(c: &mut Criterion) {
@@ -116,6 +143,7 @@ criterion_group!(
     bench_deterministic_keygen,
     bench_sign,
     bench_verify,
+    bench_verify_key_cache,
     bench_address_derivation,
     bench_address_validation,
     bench_sign_verify_roundtrip,
@@ -178,13 +178,17 @@ pub fn sign_message(message: &[u8], secret_key_bytes: &[u8]) -> Result<Vec<u8>,
 /// - Ed25519:    public_key = 32 bytes,   signature = 64 bytes
 ///
 /// MAINNET SECURITY: Ed25519 is NOT post-quantum secure and is disabled on
-/// mainnet builds (`--features mainnet`). Only Dilithium5 is accepted.
-/// Ed25519 is a testnet-only fallback for Flutter desktop wallets where
-/// native Dilithium5 FFI is not yet available.
+/// mainnet builds (`--features mainnet`) and, at runtime, by
+/// [`disable_ed25519`] when the node runs a chain spec that forbids it.
+/// Only Dilithium5 is accepted then. Ed25519 is a testnet-only fallback for
+/// Flutter desktop wallets where native Dilithium5 FFI is not yet available.
 pub fn verify_signature(message: &[u8], signature_bytes: &[u8], public_key_bytes: &[u8]) -> bool {
     // MAINNET: Only Dilithium5 signatures accepted (post-quantum enforcement)
     #[cfg(not(feature = "mainnet"))]
-    if public_key_bytes.len() == 32 && signature_bytes.len() == 64 {
+    if public_key_bytes.len() == 32
+        && signature_bytes.len() == 64
+        && !ED25519_DISABLED.load(std::sync::atomic::Ordering::Acquire)
+    {
         // Ed25519 verification (TESTNET fallback for Flutter desktop)
         return verify_ed25519(message, signature_bytes, public_key_bytes);
     }
@@ -193,12 +197,22 @@ pub fn verify_signature(message: &[u8], signature_bytes: &[u8], public_key_bytes
     verify_dilithium5(message, signature_bytes, public_key_bytes)
 }
 
+/// Runtime Ed25519 kill switch (one-way: there is no way to re-enable it)
+static ED25519_DISABLED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Reject Ed25519 signatures for the rest of the process. Called when the
+/// node installs a chain spec whose signature policy forbids them.
+pub fn disable_ed25519() {
+    ED25519_DISABLED.store(true, std::sync::atomic::Ordering::Release);
+}
+
 /// Dilithium5 signature verification (primary, post-quantum)
 fn verify_dilithium5(message: &[u8], signature_bytes: &[u8], public_key_bytes: &[u8]) -> bool {
-    let pk = match DilithiumPublicKey::from_bytes(public_key_bytes) {
-        Ok(k) => k,
-        Err(_) => return false,
+    let cached = match cached_public_key(public_key_bytes) {
+        Some(k) => k,
+        None => return false,
     };
+    let pk = &cached.key;
 
     use pqcrypto_dilithium::dilithium5::DetachedSignature as DilithiumSig;
 
@@ -207,7 +221,96 @@ fn verify_dilithium5(message: &[u8], signature_bytes: &[u8], public_key_bytes: &
         Err(_) => return false,
     };
 
-    verify_detached_signature(&sig, message, &pk).is_ok()
+    verify_detached_signature(&sig, message, pk).is_ok()
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// PUBLIC KEY CACHE
+//
+// Validators verify blocks from the same senders over and over. Parsed
+// Dilithium5 keys and their derived addresses are kept in a process-wide
+// LRU keyed by SHA-256(public key), so repeat senders skip key parsing and
+// the BLAKE2b/SHA-256/Base58 address derivation.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Keys held by the cache (~2.6 KB each → ~10 MB when full)
+pub const PUBLIC_KEY_CACHE_CAPACITY: usize = 4_096;
+
+/// A parsed Dilithium5 public key and its LOS address
+pub struct CachedPublicKey {
+    key: DilithiumPublicKey,
+    pub address: String,
+}
+
+/// Hit/miss counters of the public key cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PublicKeyCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+struct PublicKeyCache {
+    keys: lru::LruCache<[u8; 32], std::sync::Arc<CachedPublicKey>>,
+    hits: u64,
+    misses: u64,
+}
+
+fn public_key_cache() -> &'static std::sync::Mutex<PublicKeyCache> {
+    static CACHE: std::sync::OnceLock<std::sync::Mutex<PublicKeyCache>> =
+        std::sync::OnceLock::new();
+    CACHE.get_or_init(|| {
+        let cap = std::num::NonZeroUsize::new(PUBLIC_KEY_CACHE_CAPACITY)
+            .unwrap_or(std::num::NonZeroUsize::MIN);
+        std::sync::Mutex::new(PublicKeyCache {
+            keys: lru::LruCache::new(cap),
+            hits: 0,
+            misses: 0,
+        })
+    })
+}
+
+/// Parsed key + address for Dilithium5 public key bytes, from the cache when
+/// seen before. None if the bytes are not a Dilithium5 public key.
+pub fn cached_public_key(public_key_bytes: &[u8]) -> Option<std::sync::Arc<CachedPublicKey>> {
+    let id: [u8; 32] = Sha256::digest(public_key_bytes).into();
+    {
+        let mut cache = public_key_cache().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(hit) = cache.keys.get(&id).cloned() {
+            cache.hits += 1;
+            return Some(hit);
+        }
+        cache.misses += 1;
+    }
+    // Parse outside the lock; a concurrent miss for the same key just parses twice
+    let entry = std::sync::Arc::new(CachedPublicKey {
+        key: DilithiumPublicKey::from_bytes(public_key_bytes).ok()?,
+        address: derive_address(public_key_bytes),
+    });
+    public_key_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys
+        .put(id, entry.clone());
+    Some(entry)
+}
+
+pub fn public_key_cache_stats() -> PublicKeyCacheStats {
+    let cache = public_key_cache().lock().unwrap_or_else(|e| e.into_inner());
+    PublicKeyCacheStats {
+        hits: cache.hits,
+        misses: cache.misses,
+        entries: cache.keys.len(),
+    }
+}
+
+/// Drop every cached key (counters are kept)
+pub fn clear_public_key_cache() {
+    public_key_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys
+        .clear();
 }
 
 /// Ed25519 signature verification (TESTNET fallback for Flutter desktop)
@@ -232,6 +335,34 @@ fn verify_ed25519(message: &[u8], signature_bytes: &[u8], public_key_bytes: &[u8
     vk.verify(message, &sig).is_ok()
 }
 
+/// Ed25519 keypair of a testnet Flutter wallet: the secret key is
+/// BIP39 seed[0:32], exactly as the desktop wallet derives it.
+/// Only needed to migrate such accounts to Dilithium5 (key_migration.rs).
+#[cfg(not(feature = "mainnet"))]
+pub fn ed25519_keypair_from_seed(bip39_seed: &[u8]) -> Result<KeyPair, CryptoError> {
+    let secret: [u8; 32] = bip39_seed
+        .get(..32)
+        .and_then(|s| s.try_into().ok())
+        .ok_or(CryptoError::InvalidKey)?;
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
+    Ok(KeyPair {
+        public_key: signing_key.verifying_key().to_bytes().to_vec(),
+        secret_key: secret.to_vec(),
+    })
+}
+
+/// Sign a message with a 32-byte Ed25519 secret key (TESTNET fallback)
+#[cfg(not(feature = "mainnet"))]
+pub fn sign_ed25519(message: &[u8], secret_key_bytes: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    use ed25519_dalek::Signer;
+
+    let secret: [u8; 32] = secret_key_bytes
+        .try_into()
+        .map_err(|_| CryptoError::InvalidKey)?;
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
+    Ok(signing_key.sign(message).to_bytes().to_vec())
+}
+
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 // ADDRESS DERIVATION MODULE (Base58Check Format - Like Bitcoin)
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
@@ -254,6 +385,16 @@ use blake2::Blake2b512;
 /// // Result: "LOSHjvLcaLZpKcRvHoEKtYdbQbMZECzNp3gh9LJ7Y9ZPTqH"
 /// ```
 pub fn public_key_to_address(public_key_bytes: &[u8]) -> String {
+    // Dilithium5 keys go through the cache; anything else is derived directly
+    if public_key_bytes.len() == pqcrypto_dilithium::dilithium5::public_key_bytes() {
+        if let Some(cached) = cached_public_key(public_key_bytes) {
+            return cached.address.clone();
+        }
+    }
+    derive_address(public_key_bytes)
+}
+
+fn derive_address(public_key_bytes: &[u8]) -> String {
     const VERSION_BYTE: u8 = 0x4A; // 74 = "LOS" identifier
 
     // 1. Hash public key with BLAKE2b-512, take first 20 bytes (160-bit)
@@ -482,6 +623,49 @@ mod tests {
         assert!(verify_signature(msg, &sig, &keys.public_key));
     }
 
+    #[test]
+    fn test_public_key_cache() {
+        let keys = generate_keypair();
+        let msg = b"repeat sender";
+        let sig = sign_message(msg, &keys.secret_key).unwrap();
+
+        let before = public_key_cache_stats();
+        assert!(verify_signature(msg, &sig, &keys.public_key));
+        assert!(verify_signature(msg, &sig, &keys.public_key));
+        let after = public_key_cache_stats();
+        // Other tests share the cache: counters only ever grow
+        assert!(after.misses > before.misses);
+        assert!(after.hits > before.hits);
+
+        let cached = cached_public_key(&keys.public_key).unwrap();
+        assert_eq!(cached.address, derive_address(&keys.public_key));
+        assert_eq!(public_key_to_address(&keys.public_key), cached.address);
+        assert!(!verify_signature(b"other", &sig, &keys.public_key));
+
+        // Bytes that are not a Dilithium5 key are never cached
+        assert!(cached_public_key(&[1u8; 100]).is_none());
+        assert_eq!(
+            public_key_to_address(&[1u8; 100]),
+            derive_address(&[1u8; 100])
+        );
+    }
+
+    #[cfg(not(feature = "mainnet"))]
+    #[test]
+    fn test_ed25519_seed_sign_verify() {
+        let seed = [7u8; 64];
+        let keys = ed25519_keypair_from_seed(&seed).unwrap();
+        assert_eq!(
+            (keys.public_key.len(), keys.secret_key.clone()),
+            (32, seed[..32].to_vec())
+        );
+        let sig = sign_ed25519(b"migrate", &keys.secret_key).unwrap();
+        assert_eq!(sig.len(), 64);
+        assert!(verify_signature(b"migrate", &sig, &keys.public_key));
+        assert!(!verify_signature(b"other", &sig, &keys.public_key));
+        assert!(ed25519_keypair_from_seed(&seed[..31]).is_err());
+    }
+
     // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
     // KEY ENCRYPTION TESTS (RISK-002 Validation)
     // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
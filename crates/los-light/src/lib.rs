@@ -0,0 +1,324 @@
+//! # LOS Light — Standalone Proof Verifiers
+//!
+//! Checks proofs served by a LOS node without running one: no ledger, no
+//! networking, no los-core. An auditor, exchange or wallet links only this
+//! crate.
+//!
+//! ## Account commitment
+//! Accounts are committed in a binary Merkle tree (SHA3-256, domain
+//! separated). The leaves are sorted by address, which is the ledger's BTreeMap order:
+//!
+//! ```text
+//! leaf = SHA3-256("LOS-ACCT-LEAF-V1" ‖ len(addr) u32 LE ‖ addr
+//!                 ‖ balance_cil u128 LE ‖ block_count u64 LE
+//!                 ‖ len(head) u32 LE ‖ head)
+//! node = SHA3-256("LOS-ACCT-NODE-V1" ‖ left ‖ right)
+//! ```
+//!
+//! A level with an odd number of nodes promotes the last one unchanged, so
+//! no leaf is ever duplicated. Leaves cover the same fields as
+//! `Ledger::compute_state_root`, in a form that supports one-account proofs.
+//!
+//! ## Proof of reserves
+//! ```rust,ignore
+//! let proof: los_light::ReservesProof = serde_json::from_str(&body)?;
+//! let total_cil = los_light::verify_reserves(&proof, Some(&trusted_root))?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashSet;
+
+const LEAF_DOMAIN: &[u8] = b"LOS-ACCT-LEAF-V1";
+const NODE_DOMAIN: &[u8] = b"LOS-ACCT-NODE-V1";
+/// Root of a tree with no accounts
+pub const EMPTY_ROOT: [u8; 32] = [0u8; 32];
+/// Upper bound on proof length (2^64 leaves), rejects garbage early
+const MAX_PROOF_DEPTH: usize = 64;
+
+/// Leaf hash for one account
+pub fn account_leaf_hash(
+    address: &str,
+    balance_cil: u128,
+    block_count: u64,
+    head: &str,
+) -> [u8; 32] {
+    let mut h = Sha3_256::new();
+    h.update(LEAF_DOMAIN);
+    h.update((address.len() as u32).to_le_bytes());
+    h.update(address.as_bytes());
+    h.update(balance_cil.to_le_bytes());
+    h.update(block_count.to_le_bytes());
+    h.update((head.len() as u32).to_le_bytes());
+    h.update(head.as_bytes());
+    h.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut h = Sha3_256::new();
+    h.update(NODE_DOMAIN);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// Merkle tree over account leaves (all levels kept for proof generation)
+#[derive(Debug, Clone)]
+pub struct AccountTree {
+    /// levels[0] = leaves, last level = [root]
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AccountTree {
+    /// Build from leaves in address order
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|l| l.len() > 1) {
+            let next = levels
+                .last()
+                .map(|level| {
+                    level
+                        .chunks(2)
+                        .map(|pair| match pair {
+                            [l, r] => node_hash(l, r),
+                            [lone] => *lone,
+                            _ => unreachable!("chunks(2) yields 1 or 2 items"),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|l| l.first().copied())
+            .unwrap_or(EMPTY_ROOT)
+    }
+
+    /// Sibling hashes from leaf to root (promoted levels contribute none)
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut idx = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = idx ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            idx /= 2;
+        }
+        Some(siblings)
+    }
+}
+
+/// Balance of one account plus its path to the account root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountProof {
+    pub address: String,
+    pub balance_cil: u128,
+    pub block_count: u64,
+    pub head: String,
+    pub leaf_index: u64,
+    /// Hex sibling hashes, leaf level first
+    pub siblings: Vec<String>,
+}
+
+/// Balances of several accounts against one account root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReservesProof {
+    /// Hex account Merkle root
+    pub account_root: String,
+    pub leaf_count: u64,
+    /// Sum of `accounts[*].balance_cil`
+    pub total_cil: u128,
+    pub accounts: Vec<AccountProof>,
+}
+
+fn decode_hash(hex_str: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hex_str).map_err(|e| format!("Invalid hash hex: {}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| "Hash must be 32 bytes".to_string())
+}
+
+/// Recompute the root from `proof` and compare with `account_root` (hex).
+pub fn verify_account_proof(
+    proof: &AccountProof,
+    leaf_count: u64,
+    account_root: &str,
+) -> Result<(), String> {
+    if proof.leaf_index >= leaf_count {
+        return Err(format!(
+            "Leaf index {} out of range ({} leaves)",
+            proof.leaf_index, leaf_count
+        ));
+    }
+    if proof.siblings.len() > MAX_PROOF_DEPTH {
+        return Err("Proof too long".to_string());
+    }
+    let root = decode_hash(account_root)?;
+    let mut hash = account_leaf_hash(
+        &proof.address,
+        proof.balance_cil,
+        proof.block_count,
+        &proof.head,
+    );
+    let mut siblings = proof.siblings.iter();
+    let (mut idx, mut n) = (proof.leaf_index, leaf_count);
+    while n > 1 {
+        // Last node of an odd level is promoted without a sibling
+        if !(idx == n - 1 && n % 2 == 1) {
+            let sibling = decode_hash(
+                siblings
+                    .next()
+                    .ok_or_else(|| "Proof too short".to_string())?,
+            )?;
+            hash = if idx % 2 == 0 {
+                node_hash(&hash, &sibling)
+            } else {
+                node_hash(&sibling, &hash)
+            };
+        }
+        idx /= 2;
+        n = n.div_ceil(2);
+    }
+    if siblings.next().is_some() {
+        return Err("Proof has unused siblings".to_string());
+    }
+    if hash != root {
+        return Err(format!("Proof for {} does not match root", proof.address));
+    }
+    Ok(())
+}
+
+/// Verify every account proof and the stated total. When `expected_root` is
+/// given (e.g. from a source the auditor trusts) the proof must use it.
+/// Returns the proven total in CIL.
+pub fn verify_reserves(proof: &ReservesProof, expected_root: Option<&str>) -> Result<u128, String> {
+    if let Some(expected) = expected_root {
+        if !expected.eq_ignore_ascii_case(&proof.account_root) {
+            return Err("Account root does not match the expected root".to_string());
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut total: u128 = 0;
+    for account in &proof.accounts {
+        if !seen.insert(account.address.as_str()) {
+            return Err(format!("Duplicate account {}", account.address));
+        }
+        verify_account_proof(account, proof.leaf_count, &proof.account_root)?;
+        total = total
+            .checked_add(account.balance_cil)
+            .ok_or_else(|| "Total overflows u128".to_string())?;
+    }
+    if total != proof.total_cil {
+        return Err(format!(
+            "Stated total {} does not match proven total {}",
+            proof.total_cil, total
+        ));
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accounts(n: usize) -> Vec<(String, u128)> {
+        (0..n)
+            .map(|i| (format!("LOSW{:03}", i), (i as u128 + 1) * 1_000))
+            .collect()
+    }
+
+    fn build(accts: &[(String, u128)]) -> AccountTree {
+        AccountTree::new(
+            accts
+                .iter()
+                .map(|(a, b)| account_leaf_hash(a, *b, 1, "head"))
+                .collect(),
+        )
+    }
+
+    fn proof_for(tree: &AccountTree, accts: &[(String, u128)], i: usize) -> AccountProof {
+        AccountProof {
+            address: accts[i].0.clone(),
+            balance_cil: accts[i].1,
+            block_count: 1,
+            head: "head".to_string(),
+            leaf_index: i as u64,
+            siblings: tree.proof(i).unwrap().iter().map(hex::encode).collect(),
+        }
+    }
+
+    #[test]
+    fn test_every_leaf_verifies_for_odd_and_even_sizes() {
+        for n in [1, 2, 3, 5, 8, 13] {
+            let accts = accounts(n);
+            let tree = build(&accts);
+            let root = hex::encode(tree.root());
+            for i in 0..n {
+                let p = proof_for(&tree, &accts, i);
+                assert_eq!(
+                    verify_account_proof(&p, n as u64, &root),
+                    Ok(()),
+                    "n={n} i={i}"
+                );
+            }
+        }
+        assert_eq!(AccountTree::new(Vec::new()).root(), EMPTY_ROOT);
+    }
+
+    #[test]
+    fn test_tampered_proofs_rejected() {
+        let accts = accounts(5);
+        let tree = build(&accts);
+        let root = hex::encode(tree.root());
+
+        let mut p = proof_for(&tree, &accts, 2);
+        p.balance_cil += 1;
+        assert!(verify_account_proof(&p, 5, &root).is_err());
+
+        let mut p = proof_for(&tree, &accts, 2);
+        p.siblings.pop();
+        assert!(verify_account_proof(&p, 5, &root).is_err());
+
+        let p = proof_for(&tree, &accts, 4);
+        assert!(verify_account_proof(&p, 4, &root).is_err());
+    }
+
+    #[test]
+    fn test_reserves_total_and_json_roundtrip() {
+        let accts = accounts(6);
+        let tree = build(&accts);
+        let root = hex::encode(tree.root());
+        let proof = ReservesProof {
+            account_root: root.clone(),
+            leaf_count: 6,
+            total_cil: 1_000 + 4_000,
+            accounts: vec![proof_for(&tree, &accts, 0), proof_for(&tree, &accts, 3)],
+        };
+        let json = serde_json::to_string(&proof).unwrap();
+        let parsed: ReservesProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(verify_reserves(&parsed, Some(&root)), Ok(5_000));
+        assert!(verify_reserves(&parsed, Some(&hex::encode([1u8; 32]))).is_err());
+
+        let mut inflated = parsed.clone();
+        inflated.total_cil += 1;
+        assert!(verify_reserves(&inflated, None).is_err());
+
+        let mut dup = parsed;
+        dup.accounts.push(dup.accounts[0].clone());
+        dup.total_cil += 1_000;
+        assert!(verify_reserves(&dup, None).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - BANDWIDTH PROFILE
+//
+// `--low-bandwidth` (or LOS_LOW_BANDWIDTH=1) lets a validator on a
+// residential connection take part without saturating its uplink:
+//
+//   - Smaller gossipsub mesh: each message is forwarded to 3 peers instead
+//     of 6, and fewer IHAVE ids are advertised per heartbeat
+//   - Peer cap: connections beyond LOS_MAX_PEERS (default 8) are refused
+//   - Every gossip payload worth it is gzip-compressed (ENVZ1 envelope, see
+//     gossip_envelope.rs)
+//   - No bulk sync serving: full-state SYNC_GZIP, delta sync pages and
+//     GET /sync are not served. Peers asking for state are pointed at the
+//     archive nodes in LOS_SYNC_DELEGATES (host:port, comma-separated)
+//
+// Voting, block production and receiving sync are unchanged. Selected once
+// at startup, before the network task starts.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static LOW_BANDWIDTH: AtomicBool = AtomicBool::new(false);
+
+/// Peer cap in low-bandwidth mode unless LOS_MAX_PEERS says otherwise
+pub const LOW_BANDWIDTH_MAX_PEERS: usize = 8;
+
+/// Turn on low-bandwidth mode (from --low-bandwidth or LOS_LOW_BANDWIDTH=1)
+pub fn enable() {
+    LOW_BANDWIDTH.store(true, Ordering::Release);
+}
+
+/// Whether this node runs the low-bandwidth profile
+pub fn is_enabled() -> bool {
+    LOW_BANDWIDTH.load(Ordering::Acquire)
+}
+
+/// Low-bandwidth mode requested via the environment
+pub fn enabled_by_env() -> bool {
+    std::env::var("LOS_LOW_BANDWIDTH").unwrap_or_default() == "1"
+}
+
+/// Gossipsub mesh parameters (see libp2p gossipsub::ConfigBuilder)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshParams {
+    pub mesh_n: usize,
+    pub mesh_n_low: usize,
+    pub mesh_n_high: usize,
+    pub mesh_outbound_min: usize,
+    /// Peers outside the mesh that receive IHAVE gossip each heartbeat
+    pub gossip_lazy: usize,
+    /// Heartbeats of message ids advertised in IHAVE
+    pub history_gossip: usize,
+    pub heartbeat: Duration,
+}
+
+impl MeshParams {
+    /// libp2p defaults with the node's 1 s heartbeat
+    pub const DEFAULT: MeshParams = MeshParams {
+        mesh_n: 6,
+        mesh_n_low: 5,
+        mesh_n_high: 12,
+        mesh_outbound_min: 2,
+        gossip_lazy: 6,
+        history_gossip: 3,
+        heartbeat: Duration::from_secs(1),
+    };
+
+    pub const LOW_BANDWIDTH: MeshParams = MeshParams {
+        mesh_n: 3,
+        mesh_n_low: 2,
+        mesh_n_high: 4,
+        mesh_outbound_min: 1,
+        gossip_lazy: 2,
+        history_gossip: 2,
+        heartbeat: Duration::from_secs(2),
+    };
+
+    /// Parameters for the active profile
+    pub fn current() -> Self {
+        if is_enabled() {
+            Self::LOW_BANDWIDTH
+        } else {
+            Self::DEFAULT
+        }
+    }
+
+    /// Apply to a gossipsub config builder
+    pub fn apply<'a>(
+        &self,
+        builder: &'a mut libp2p::gossipsub::ConfigBuilder,
+    ) -> &'a mut libp2p::gossipsub::ConfigBuilder {
+        builder
+            .mesh_n(self.mesh_n)
+            .mesh_n_low(self.mesh_n_low)
+            .mesh_n_high(self.mesh_n_high)
+            .mesh_outbound_min(self.mesh_outbound_min)
+            .gossip_lazy(self.gossip_lazy)
+            .history_gossip(self.history_gossip)
+            .heartbeat_interval(self.heartbeat)
+    }
+}
+
+/// Maximum connected peers: LOS_MAX_PEERS if set (any mode), else
+/// LOW_BANDWIDTH_MAX_PEERS in low-bandwidth mode, else unlimited (None)
+pub fn max_peers() -> Option<usize> {
+    parse_max_peers(std::env::var("LOS_MAX_PEERS").ok().as_deref(), is_enabled())
+}
+
+fn parse_max_peers(env: Option<&str>, low_bandwidth: bool) -> Option<usize> {
+    match env.and_then(|v| v.trim().parse::<usize>().ok()) {
+        Some(0) => None,
+        Some(n) => Some(n),
+        None => low_bandwidth.then_some(LOW_BANDWIDTH_MAX_PEERS),
+    }
+}
+
+/// Whether gossip payloads are compressed before publishing
+pub fn compress_gossip() -> bool {
+    is_enabled()
+}
+
+/// Whether this node serves full-state sync (SYNC_GZIP, delta sync, GET /sync)
+pub fn serves_bulk_sync() -> bool {
+    !is_enabled()
+}
+
+/// REST hosts (host:port) of archive nodes that serve sync in our place
+pub fn sync_delegates() -> Vec<String> {
+    parse_delegates(&std::env::var("LOS_SYNC_DELEGATES").unwrap_or_default())
+}
+
+fn parse_delegates(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mesh_profiles_are_valid_gossipsub_configs() {
+        for params in [MeshParams::DEFAULT, MeshParams::LOW_BANDWIDTH] {
+            let mut builder = libp2p::gossipsub::ConfigBuilder::default();
+            assert!(params.apply(&mut builder).build().is_ok(), "{:?}", params);
+        }
+        assert_eq!(MeshParams::current(), MeshParams::DEFAULT);
+    }
+
+    #[test]
+    fn test_max_peers_and_delegates() {
+        assert_eq!(parse_max_peers(None, false), None);
+        assert_eq!(parse_max_peers(None, true), Some(LOW_BANDWIDTH_MAX_PEERS));
+        assert_eq!(parse_max_peers(Some("12"), false), Some(12));
+        assert_eq!(parse_max_peers(Some("0"), true), None);
+        assert_eq!(
+            parse_max_peers(Some("junk"), true),
+            Some(LOW_BANDWIDTH_MAX_PEERS)
+        );
+        assert_eq!(
+            parse_delegates(" archive1:3030, ,archive2.onion:3030"),
+            vec!["archive1:3030", "archive2.onion:3030"]
+        );
+    }
+}
@@ -0,0 +1,221 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CHECKPOINT SIGNATURE COLLECTION
+//
+// Gossiping CHECKPOINT_PROPOSE / CHECKPOINT_SIGN to the whole mesh scales with
+// validators² and reveals to every peer exactly when each validator signs.
+// Instead the proposer asks validators directly:
+//
+//   proposer ──/los/checkpoint-sign/1──▶ peer   (CheckpointSignRequest)
+//   proposer ◀───────────────────────── peer   (CheckpointSignResponse)
+//
+// - Requests go point-to-point to every connected peer; non-validators and
+//   peers whose state root differs decline with `signature: None`
+// - Retries carry `signed_by`, so validators that already signed skip work
+// - Only the final aggregated FinalityCheckpoint is gossiped (CHECKPOINT_FINAL:)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::checkpoint::{CheckpointSignature, FinalityCheckpoint};
+use serde::{Deserialize, Serialize};
+
+/// libp2p protocol name for checkpoint signature requests
+pub const CHECKPOINT_SIGN_PROTOCOL: &str = "/los/checkpoint-sign/1";
+/// Out-channel prefix: CHECKPOINT_REQ:{json} → send the request to every connected peer
+pub const CHECKPOINT_REQ_PREFIX: &str = "CHECKPOINT_REQ:";
+/// Out-channel prefix: CHECKPOINT_RESP:{inbound_id}:{json} → answer an inbound request
+pub const CHECKPOINT_RESP_PREFIX: &str = "CHECKPOINT_RESP:";
+/// Gossip prefix for the finalized, aggregated checkpoint
+pub const CHECKPOINT_FINAL_PREFIX: &str = "CHECKPOINT_FINAL:";
+/// Seconds between signature request retries for a pending checkpoint
+pub const CHECKPOINT_RETRY_SECS: u64 = 15;
+/// Give up re-requesting after this many rounds (the pending GC drops it later)
+pub const CHECKPOINT_MAX_ATTEMPTS: u32 = 8;
+/// Max unanswered inbound requests held by the network task
+pub const MAX_INBOUND_SIGN_REQUESTS: usize = 256;
+
+/// Request body for `/los/checkpoint-sign/1`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointSignRequest {
+    pub height: u64,
+    pub block_hash: String,
+    pub state_root: String,
+    pub proposer: String,
+    /// Hex-encoded proposer signature over `FinalityCheckpoint::signing_data()`
+    pub proposer_signature: String,
+    /// Validators whose signatures the proposer already holds
+    #[serde(default)]
+    pub signed_by: Vec<String>,
+}
+
+impl CheckpointSignRequest {
+    /// Build a request for a pending checkpoint. The proposer's signature must
+    /// be the first entry (it is the one created in the save task).
+    pub fn for_checkpoint(checkpoint: &FinalityCheckpoint) -> Option<Self> {
+        let proposer = checkpoint.signatures.first()?;
+        Some(Self {
+            height: checkpoint.height,
+            block_hash: checkpoint.block_hash.clone(),
+            state_root: checkpoint.state_root.clone(),
+            proposer: proposer.validator_address.clone(),
+            proposer_signature: hex::encode(&proposer.signature),
+            signed_by: checkpoint
+                .signatures
+                .iter()
+                .map(|s| s.validator_address.clone())
+                .collect(),
+        })
+    }
+
+    /// Unsigned checkpoint skeleton, used to recompute `signing_data()`
+    pub fn checkpoint(&self) -> FinalityCheckpoint {
+        FinalityCheckpoint::new(
+            self.height,
+            self.block_hash.clone(),
+            1,
+            self.state_root.clone(),
+            vec![],
+        )
+    }
+
+    pub fn proposer_signature_bytes(&self) -> Option<Vec<u8>> {
+        hex::decode(&self.proposer_signature).ok()
+    }
+
+    /// Encode for the out channel: CHECKPOINT_REQ:{json}
+    pub fn to_message(&self) -> String {
+        format!(
+            "{}{}",
+            CHECKPOINT_REQ_PREFIX,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    pub fn from_message(msg: &str) -> Option<Self> {
+        serde_json::from_str(msg.strip_prefix(CHECKPOINT_REQ_PREFIX)?).ok()
+    }
+}
+
+/// Response body for `/los/checkpoint-sign/1` — `signature: None` means declined
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointSignResponse {
+    pub height: u64,
+    pub signer: String,
+    /// Hex-encoded signature over `FinalityCheckpoint::signing_data()`
+    pub signature: Option<String>,
+}
+
+impl CheckpointSignResponse {
+    pub fn declined(height: u64, signer: &str) -> Self {
+        Self {
+            height,
+            signer: signer.to_string(),
+            signature: None,
+        }
+    }
+
+    pub fn signed(height: u64, signer: &str, signature: &[u8]) -> Self {
+        Self {
+            height,
+            signer: signer.to_string(),
+            signature: Some(hex::encode(signature)),
+        }
+    }
+
+    /// Decode into a CheckpointSignature (None if declined or malformed)
+    pub fn to_signature(&self) -> Option<CheckpointSignature> {
+        let signature = hex::decode(self.signature.as_deref()?).ok()?;
+        Some(CheckpointSignature {
+            validator_address: self.signer.clone(),
+            signature,
+        })
+    }
+
+    /// Encode for the out channel: CHECKPOINT_RESP:{inbound_id}:{json}
+    pub fn to_message(&self, inbound_id: u64) -> String {
+        format!(
+            "{}{}:{}",
+            CHECKPOINT_RESP_PREFIX,
+            inbound_id,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    pub fn from_message(msg: &str) -> Option<(u64, Self)> {
+        let (id, json) = msg.strip_prefix(CHECKPOINT_RESP_PREFIX)?.split_once(':')?;
+        Some((id.parse().ok()?, serde_json::from_str(json).ok()?))
+    }
+}
+
+/// Encode a finalized checkpoint for gossip: CHECKPOINT_FINAL:{json}
+pub fn final_checkpoint_message(checkpoint: &FinalityCheckpoint) -> String {
+    format!(
+        "{}{}",
+        CHECKPOINT_FINAL_PREFIX,
+        serde_json::to_string(checkpoint).unwrap_or_default()
+    )
+}
+
+/// Decode a CHECKPOINT_FINAL: gossip message
+pub fn parse_final_checkpoint(msg: &str) -> Option<FinalityCheckpoint> {
+    serde_json::from_str(msg.strip_prefix(CHECKPOINT_FINAL_PREFIX)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending() -> FinalityCheckpoint {
+        FinalityCheckpoint::new(
+            2_000,
+            "ab".repeat(32),
+            4,
+            "cd".repeat(32),
+            vec![CheckpointSignature {
+                validator_address: "LOSproposer".to_string(),
+                signature: vec![1, 2, 3],
+            }],
+        )
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let req = CheckpointSignRequest::for_checkpoint(&pending()).unwrap();
+        assert_eq!(req.proposer, "LOSproposer");
+        assert_eq!(req.signed_by, vec!["LOSproposer".to_string()]);
+        assert_eq!(req.proposer_signature_bytes(), Some(vec![1, 2, 3]));
+        assert_eq!(
+            CheckpointSignRequest::from_message(&req.to_message()),
+            Some(req.clone())
+        );
+        assert_eq!(req.checkpoint().signing_data(), pending().signing_data());
+    }
+
+    #[test]
+    fn test_request_requires_proposer_signature() {
+        let cp = FinalityCheckpoint::new(1_000, "h".into(), 1, "r".into(), vec![]);
+        assert!(CheckpointSignRequest::for_checkpoint(&cp).is_none());
+    }
+
+    #[test]
+    fn test_response_roundtrip() {
+        let resp = CheckpointSignResponse::signed(2_000, "LOSv", &[0xde, 0xad]);
+        let (id, decoded) = CheckpointSignResponse::from_message(&resp.to_message(7)).unwrap();
+        assert_eq!(id, 7);
+        assert_eq!(decoded, resp);
+        let sig = decoded.to_signature().unwrap();
+        assert_eq!(sig.validator_address, "LOSv");
+        assert_eq!(sig.signature, vec![0xde, 0xad]);
+        assert!(CheckpointSignResponse::declined(2_000, "LOSv")
+            .to_signature()
+            .is_none());
+    }
+
+    #[test]
+    fn test_final_checkpoint_roundtrip() {
+        let cp = pending();
+        assert_eq!(
+            parse_final_checkpoint(&final_checkpoint_message(&cp)),
+            Some(cp)
+        );
+        assert!(parse_final_checkpoint("CHECKPOINT_SIGN:1:a:b:c:d").is_none());
+    }
+}
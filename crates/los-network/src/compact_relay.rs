@@ -0,0 +1,294 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - COMPACT BLOCK RELAY
+//
+// Every Block carries a ~4.6 KB Dilithium5 signature + ~2.6 KB public key.
+// Gossiping full blocks means every peer receives every body from every mesh
+// neighbour, even when it already has the block.
+//
+// Compact relay:
+// - Publisher gossips `COMPACT_BLOCK:{json}` (hash + short header, ~250 bytes)
+// - Peers that lack the hash fetch the body once via the
+//   `/los/block-fetch/1` request-response protocol
+// - Fetched bodies are re-verified against the announced hash and cached so
+//   this node can serve the body to its own neighbours
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::{Block, BlockType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Gossip prefix for compact block announcements
+pub const COMPACT_PREFIX: &str = "COMPACT_BLOCK:";
+/// Out-channel prefix asking the network task to fetch a body: FETCH_BLOCK:{peer_id}:{hash}
+pub const FETCH_PREFIX: &str = "FETCH_BLOCK:";
+/// libp2p protocol name for block body fetches
+pub const BLOCK_FETCH_PROTOCOL: &str = "/los/block-fetch/1";
+/// Max block bodies kept for serving fetch requests (~8 KB each → ~16 MB)
+pub const BODY_CACHE_CAPACITY: usize = 2_048;
+/// Max announced hashes remembering a fallback peer for their fetch
+pub const FETCH_FALLBACK_CAPACITY: usize = 1_024;
+
+/// Short block header gossiped instead of the full body.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompactBlock {
+    pub hash: String,
+    pub account: String,
+    pub previous: String,
+    pub block_type: BlockType,
+    pub amount: u128,
+    pub timestamp: u64,
+}
+
+impl CompactBlock {
+    pub fn from_block(block: &Block) -> Self {
+        Self {
+            hash: block.calculate_hash(),
+            account: block.account.clone(),
+            previous: block.previous.clone(),
+            block_type: block.block_type.clone(),
+            amount: block.amount,
+            timestamp: block.timestamp,
+        }
+    }
+
+    /// Encode as a gossip message: COMPACT_BLOCK:{json}
+    pub fn to_message(&self) -> String {
+        format!(
+            "{}{}",
+            COMPACT_PREFIX,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    /// Decode a COMPACT_BLOCK: gossip message. Rejects malformed hashes.
+    pub fn from_message(msg: &str) -> Option<Self> {
+        let json = msg.strip_prefix(COMPACT_PREFIX)?;
+        let cb: Self = serde_json::from_str(json).ok()?;
+        if cb.hash.len() != 64 || !cb.hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(cb)
+    }
+}
+
+/// Request body for `/los/block-fetch/1`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockFetchRequest {
+    pub hash: String,
+}
+
+/// Response body for `/los/block-fetch/1` — `None` when the peer no longer has it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockFetchResponse {
+    pub block_json: Option<String>,
+}
+
+/// Compact relay is on by default; LOS_COMPACT_RELAY=0 falls back to full-block gossip
+/// (for meshes that still contain nodes without block-fetch support).
+pub fn compact_relay_enabled() -> bool {
+    std::env::var("LOS_COMPACT_RELAY").unwrap_or_default() != "0"
+}
+
+/// If `msg` is a raw Block JSON gossip message, return the parsed block and its hash.
+/// Prefixed protocol messages (CONFIRM_REQ:, MINE_BLOCK:, ...) are never compacted.
+pub fn parse_raw_block(msg: &str) -> Option<(Block, String)> {
+    if !msg.starts_with('{') {
+        return None;
+    }
+    let block: Block = serde_json::from_str(msg).ok()?;
+    let hash = block.calculate_hash();
+    Some((block, hash))
+}
+
+/// Verify a fetched body matches the hash we asked for (peers cannot substitute blocks).
+pub fn verify_fetched_body(expected_hash: &str, block_json: &str) -> bool {
+    parse_raw_block(block_json)
+        .map(|(_, h)| h == expected_hash)
+        .unwrap_or(false)
+}
+
+/// Bounded FIFO cache of block bodies (hash → raw JSON) used to answer fetches.
+#[derive(Debug, Default)]
+pub struct BodyCache {
+    bodies: HashMap<String, String>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl BodyCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bodies: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn insert(&mut self, hash: String, block_json: String) {
+        if self.bodies.contains_key(&hash) {
+            return;
+        }
+        while self.order.len() >= self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.bodies.remove(&old);
+            }
+        }
+        self.order.push_back(hash.clone());
+        self.bodies.insert(hash, block_json);
+    }
+
+    pub fn get(&self, hash: &str) -> Option<&String> {
+        self.bodies.get(hash)
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.bodies.contains_key(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+}
+
+/// Bounded FIFO map of announced hash → fallback peer for its body fetch.
+/// Announcements of blocks this node already has are never fetched, so
+/// their entries are dropped when the map is full.
+#[derive(Debug)]
+pub struct FetchFallbacks<P> {
+    peers: HashMap<String, P>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl<P> FetchFallbacks<P> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            peers: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn insert(&mut self, hash: String, peer: P) {
+        if let Some(existing) = self.peers.get_mut(&hash) {
+            *existing = peer;
+            return;
+        }
+        while self.order.len() >= self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.peers.remove(&old);
+            }
+        }
+        self.order.push_back(hash.clone());
+        self.peers.insert(hash, peer);
+    }
+
+    pub fn remove(&mut self, hash: &str) -> Option<P> {
+        let peer = self.peers.remove(hash)?;
+        self.order.retain(|h| h != hash);
+        Some(peer)
+    }
+
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_block() -> Block {
+        Block {
+            account: "LOSabc".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Send,
+            amount: 5,
+            link: "LOSdef".to_string(),
+            signature: "aa".repeat(2300),
+            public_key: "bb".repeat(1300),
+            work: 7,
+            timestamp: 1_700_000_000,
+            fee: 1,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_compact_roundtrip_and_size() {
+        let blk = sample_block();
+        let full = serde_json::to_string(&blk).unwrap();
+        let cb = CompactBlock::from_block(&blk);
+        let msg = cb.to_message();
+        assert!(
+            msg.len() * 10 < full.len(),
+            "compact announce must be much smaller"
+        );
+        assert_eq!(CompactBlock::from_message(&msg), Some(cb));
+    }
+
+    #[test]
+    fn test_from_message_rejects_bad_hash() {
+        let mut cb = CompactBlock::from_block(&sample_block());
+        cb.hash = "not-a-hash".to_string();
+        assert!(CompactBlock::from_message(&cb.to_message()).is_none());
+        assert!(CompactBlock::from_message("CONFIRM_REQ:x").is_none());
+    }
+
+    #[test]
+    fn test_parse_raw_block_ignores_prefixed() {
+        let blk = sample_block();
+        let json = serde_json::to_string(&blk).unwrap();
+        assert_eq!(parse_raw_block(&json).unwrap().1, blk.calculate_hash());
+        assert!(parse_raw_block(&format!("MINE_BLOCK:{}", json)).is_none());
+    }
+
+    #[test]
+    fn test_verify_fetched_body() {
+        let blk = sample_block();
+        let json = serde_json::to_string(&blk).unwrap();
+        assert!(verify_fetched_body(&blk.calculate_hash(), &json));
+        assert!(!verify_fetched_body(&"0".repeat(64), &json));
+        assert!(!verify_fetched_body(&blk.calculate_hash(), "garbage"));
+    }
+
+    #[test]
+    fn test_body_cache_evicts_oldest() {
+        let mut cache = BodyCache::new(2);
+        cache.insert("a".into(), "1".into());
+        cache.insert("b".into(), "2".into());
+        cache.insert("a".into(), "ignored".into());
+        cache.insert("c".into(), "3".into());
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains("a"));
+        assert_eq!(cache.get("c").map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn test_fetch_fallbacks_are_bounded() {
+        let mut fallbacks = FetchFallbacks::new(2);
+        fallbacks.insert("a".into(), 1);
+        fallbacks.insert("b".into(), 2);
+        fallbacks.insert("c".into(), 3);
+        assert_eq!(fallbacks.len(), 2);
+        assert_eq!(fallbacks.remove("a"), None);
+
+        // A removed hash leaves no stale slot behind
+        assert_eq!(fallbacks.remove("b"), Some(2));
+        fallbacks.insert("b".into(), 4);
+        fallbacks.insert("d".into(), 5);
+        assert_eq!(fallbacks.len(), 2);
+        assert_eq!(fallbacks.remove("b"), Some(4));
+        assert_eq!(fallbacks.remove("d"), Some(5));
+        assert!(fallbacks.is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - FRONTIER DELTA SYNC PROTOCOL
+//
+// SYNC_GZIP ships the whole ledger whenever two nodes disagree. Delta sync
+// moves only what is missing, point-to-point:
+//
+//   node ──/los/delta-sync/1──▶ peer   Frontiers { after, root }  (paged by account)
+//   node ◀───────────────────── peer   (account, head, block_count) × ≤ FRONTIER_PAGE
+//   node ──/los/delta-sync/1──▶ peer   Chain { account, after_head }
+//   node ◀───────────────────── peer   blocks after `after_head`, oldest first
+//
+// The node compares each frontier page with its own accounts, queues the
+// chains where the peer is ahead and fetches them page by page. The session
+// (cursor + queue) lives in the node (los-node delta_sync.rs); this module is
+// the wire format and the out-channel encoding.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::Block;
+use serde::{Deserialize, Serialize};
+
+/// libp2p protocol name for delta sync requests
+pub const DELTA_SYNC_PROTOCOL: &str = "/los/delta-sync/1";
+/// Out-channel prefix: DELTA_REQ:{peer_id}:{json} → send a request
+/// (empty peer_id = any connected peer)
+pub const DELTA_REQ_PREFIX: &str = "DELTA_REQ:";
+/// Out-channel prefix: DELTA_RESP:{inbound_id}:{json} → answer an inbound request
+pub const DELTA_RESP_PREFIX: &str = "DELTA_RESP:";
+/// Accounts per frontier page
+pub const FRONTIER_PAGE: usize = 1_000;
+/// Blocks per chain page (Dilithium5 blocks are ~15 KB of JSON)
+pub const CHAIN_PAGE: usize = 128;
+/// Max unanswered inbound requests held by the network task
+pub const MAX_INBOUND_DELTA_REQUESTS: usize = 64;
+
+/// Head of one account chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountFrontier {
+    pub account: String,
+    pub head: String,
+    pub block_count: u64,
+}
+
+/// Request body for `/los/delta-sync/1`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeltaSyncRequest {
+    /// Frontiers of the accounts sorting after `after` (None = from the start).
+    /// `root` is the requester's state root (first page only): a responder
+    /// with the same root answers with no entries.
+    Frontiers {
+        after: Option<String>,
+        #[serde(default)]
+        root: Option<String>,
+    },
+    /// Blocks of `account` after `after_head` ("0" = the whole chain)
+    Chain { account: String, after_head: String },
+}
+
+/// Response body for `/los/delta-sync/1`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeltaSyncResponse {
+    Frontiers {
+        /// The `after` of the request (pages are matched to requests by it)
+        after: Option<String>,
+        entries: Vec<AccountFrontier>,
+        /// Cursor for the next page; None = last page
+        next: Option<String>,
+        /// Responder's state root and account count (first page only)
+        #[serde(default)]
+        state_root: Option<String>,
+        #[serde(default)]
+        accounts: u64,
+    },
+    Chain {
+        account: String,
+        after_head: String,
+        /// Oldest first, at most CHAIN_PAGE
+        blocks: Vec<Block>,
+        /// More blocks follow the last one
+        more: bool,
+    },
+    /// The responder cannot serve delta sync right now
+    Declined,
+}
+
+impl DeltaSyncRequest {
+    /// Encode for the out channel: DELTA_REQ:{peer_id}:{json}
+    pub fn to_message(&self, peer: Option<&str>) -> String {
+        format!(
+            "{}{}:{}",
+            DELTA_REQ_PREFIX,
+            peer.unwrap_or(""),
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    /// Decode DELTA_REQ:{peer_id}:{json} → (peer_id or None, request)
+    pub fn from_message(msg: &str) -> Option<(Option<String>, Self)> {
+        let (peer, json) = msg.strip_prefix(DELTA_REQ_PREFIX)?.split_once(':')?;
+        let peer = (!peer.is_empty()).then(|| peer.to_string());
+        Some((peer, serde_json::from_str(json).ok()?))
+    }
+}
+
+impl DeltaSyncResponse {
+    /// Encode for the out channel: DELTA_RESP:{inbound_id}:{json}
+    pub fn to_message(&self, inbound_id: u64) -> String {
+        format!(
+            "{}{}:{}",
+            DELTA_RESP_PREFIX,
+            inbound_id,
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+
+    pub fn from_message(msg: &str) -> Option<(u64, Self)> {
+        let (id, json) = msg.strip_prefix(DELTA_RESP_PREFIX)?.split_once(':')?;
+        Some((id.parse().ok()?, serde_json::from_str(json).ok()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_roundtrip() {
+        let req = DeltaSyncRequest::Chain {
+            account: "LOSabc".to_string(),
+            after_head: "0".to_string(),
+        };
+        let msg = req.to_message(Some("12D3KooWpeer"));
+        assert_eq!(
+            DeltaSyncRequest::from_message(&msg),
+            Some((Some("12D3KooWpeer".to_string()), req))
+        );
+
+        let any_peer = DeltaSyncRequest::Frontiers {
+            after: None,
+            root: Some("cd".repeat(32)),
+        };
+        assert_eq!(
+            DeltaSyncRequest::from_message(&any_peer.to_message(None)),
+            Some((None, any_peer))
+        );
+        assert!(DeltaSyncRequest::from_message("DELTA_REQ:peer:{bad").is_none());
+    }
+
+    #[test]
+    fn test_response_roundtrip() {
+        let resp = DeltaSyncResponse::Frontiers {
+            after: Some("LOSa".to_string()),
+            entries: vec![AccountFrontier {
+                account: "LOSb".to_string(),
+                head: "ab".repeat(32),
+                block_count: 7,
+            }],
+            next: None,
+            state_root: None,
+            accounts: 0,
+        };
+        let (id, decoded) = DeltaSyncResponse::from_message(&resp.to_message(9)).unwrap();
+        assert_eq!(id, 9);
+        match decoded {
+            DeltaSyncResponse::Frontiers {
+                after,
+                entries,
+                next,
+                ..
+            } => {
+                assert_eq!(after.as_deref(), Some("LOSa"));
+                assert_eq!(entries[0].block_count, 7);
+                assert!(next.is_none());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            DeltaSyncResponse::from_message(&DeltaSyncResponse::Declined.to_message(1)),
+            Some((1, DeltaSyncResponse::Declined))
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - GOSSIP ENVELOPE
+//
+// Every gossipsub message this node publishes is wrapped as
+//
+//   ENV1:{origin_ms}:{payload}
+//
+// origin_ms is the publisher's wall clock (ms since the UNIX epoch) at publish
+// time. Receivers subtract it from their own clock to measure propagation
+// delay (los-node GET /netstats). The envelope is not signed: it only feeds
+// statistics and never changes how the payload is handled.
+//
+// Bare payloads (older nodes) are still accepted; open() returns them with no
+// origin time.
+//
+// Low-bandwidth nodes (bandwidth.rs) publish larger payloads compressed:
+//
+//   ENVZ1:{origin_ms}:{gzip(payload)}
+//
+// open_bytes() accepts both forms. Message ids hash the decompressed payload,
+// so a compressed and a plain copy of the same message deduplicate.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Envelope prefix (version 1)
+pub const ENVELOPE_PREFIX: &str = "ENV1:";
+
+/// Compressed envelope prefix (version 1)
+pub const COMPRESSED_PREFIX: &str = "ENVZ1:";
+
+/// Payloads shorter than this are sent plain (gzip framing costs ~20 bytes)
+pub const MIN_COMPRESS_SIZE: usize = 256;
+
+/// Largest accepted decompressed payload (gossipsub max_transmit_size is 10 MB)
+pub const MAX_DECOMPRESSED_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Longest accepted origin timestamp field (u64::MAX has 20 digits)
+const MAX_ORIGIN_DIGITS: usize = 20;
+
+/// Wrap `payload` with the publish time `origin_ms`
+pub fn seal(payload: &str, origin_ms: u64) -> String {
+    format!("{}{}:{}", ENVELOPE_PREFIX, origin_ms, payload)
+}
+
+/// Split a received message into (origin_ms, payload). Messages without a
+/// well-formed envelope are returned unchanged with no origin.
+pub fn open(message: &str) -> (Option<u64>, &str) {
+    let Some(rest) = message.strip_prefix(ENVELOPE_PREFIX) else {
+        return (None, message);
+    };
+    match rest.split_once(':') {
+        Some((ms, payload))
+            if !ms.is_empty()
+                && ms.len() <= MAX_ORIGIN_DIGITS
+                && ms.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            (ms.parse().ok(), payload)
+        }
+        _ => (None, message),
+    }
+}
+
+/// Like [`seal`], gzip-compressing the payload when that makes it smaller
+pub fn seal_compressed(payload: &str, origin_ms: u64) -> Vec<u8> {
+    use std::io::Write;
+    if payload.len() >= MIN_COMPRESS_SIZE {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        if encoder.write_all(payload.as_bytes()).is_ok() {
+            if let Ok(compressed) = encoder.finish() {
+                if compressed.len() < payload.len() {
+                    let mut out = format!("{}{}:", COMPRESSED_PREFIX, origin_ms).into_bytes();
+                    out.extend_from_slice(&compressed);
+                    return out;
+                }
+            }
+        }
+    }
+    seal(payload, origin_ms).into_bytes()
+}
+
+/// Split a received gossip message (plain, enveloped or compressed) into
+/// (origin_ms, payload). A compressed message that does not inflate is
+/// returned as lossy text with no origin, like any malformed message.
+pub fn open_bytes(data: &[u8]) -> (Option<u64>, String) {
+    if let Some((origin_ms, payload)) = open_compressed(data) {
+        return (Some(origin_ms), payload);
+    }
+    let text = String::from_utf8_lossy(data);
+    let (origin_ms, payload) = open(&text);
+    (origin_ms, payload.to_string())
+}
+
+fn open_compressed(data: &[u8]) -> Option<(u64, String)> {
+    use std::io::Read;
+    let rest = data.strip_prefix(COMPRESSED_PREFIX.as_bytes())?;
+    let colon = rest
+        .iter()
+        .take(MAX_ORIGIN_DIGITS + 1)
+        .position(|b| *b == b':')?;
+    let origin_ms = std::str::from_utf8(&rest[..colon]).ok()?.parse().ok()?;
+    let mut payload = String::new();
+    flate2::read::GzDecoder::new(&rest[colon + 1..])
+        .take(MAX_DECOMPRESSED_SIZE + 1)
+        .read_to_string(&mut payload)
+        .ok()?;
+    (payload.len() as u64 <= MAX_DECOMPRESSED_SIZE).then_some((origin_ms, payload))
+}
+
+/// Gossip prefixes whose second `:` field is the block hash they carry
+const BLOCK_KEYED_PREFIXES: &[&str] = &["CONFIRM_REQ:", "CONFIRM_RES:"];
+
+/// Block hash carried by a CONFIRM_REQ / CONFIRM_RES payload
+pub fn block_key(payload: &str) -> Option<&str> {
+    let rest = BLOCK_KEYED_PREFIXES
+        .iter()
+        .find_map(|p| payload.strip_prefix(p))?;
+    let hash = rest.split(':').next()?;
+    (hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())).then_some(hash)
+}
+
+/// Current wall clock in ms since the UNIX epoch
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let msg = seal("CONFIRM_REQ:abc:LOSx:5:1:e30=", 1_771_000_000_123);
+        assert_eq!(
+            open(&msg),
+            (Some(1_771_000_000_123), "CONFIRM_REQ:abc:LOSx:5:1:e30=")
+        );
+        // Bare and malformed envelopes pass through untouched
+        assert_eq!(open("ID:LOSa:1:2"), (None, "ID:LOSa:1:2"));
+        assert_eq!(open("ENV1:12x:ID:a"), (None, "ENV1:12x:ID:a"));
+        assert_eq!(open("ENV1:"), (None, "ENV1:"));
+        let long = format!("ENV1:{}:x", "9".repeat(21));
+        assert_eq!(open(&long).0, None);
+    }
+
+    #[test]
+    fn test_compressed_envelope() {
+        let big = format!("SYNC_GZIP:{}", "a".repeat(4_000));
+        let sealed = seal_compressed(&big, 42);
+        assert!(sealed.starts_with(COMPRESSED_PREFIX.as_bytes()));
+        assert!(sealed.len() < big.len() / 4);
+        assert_eq!(open_bytes(&sealed), (Some(42), big));
+
+        // Short payloads stay plain; plain messages still open
+        let short = seal_compressed("ID:LOSa:1:2", 7);
+        assert_eq!(short, seal("ID:LOSa:1:2", 7).into_bytes());
+        assert_eq!(open_bytes(&short), (Some(7), "ID:LOSa:1:2".to_string()));
+        assert_eq!(open_bytes(b"ID:LOSa"), (None, "ID:LOSa".to_string()));
+        // Corrupt compressed data is not an error, just an unknown message
+        assert_eq!(open_bytes(b"ENVZ1:5:notgzip").0, None);
+    }
+
+    #[test]
+    fn test_block_key() {
+        let h = "ab".repeat(32);
+        assert_eq!(
+            block_key(&format!("CONFIRM_REQ:{}:LOSx:5:1:e30=", h)),
+            Some(h.as_str())
+        );
+        assert_eq!(
+            block_key(&format!("CONFIRM_RES:{}:LOSx:YES", h)),
+            Some(h.as_str())
+        );
+        assert_eq!(block_key("CONFIRM_REQ:short:LOSx"), None);
+        assert_eq!(block_key(&format!("ID:{}", h)), None);
+    }
+}
@@ -10,28 +10,86 @@
 
 use libp2p::{
     futures::StreamExt,
-    gossipsub, mdns, noise,
+    gossipsub, mdns, noise, request_response,
     swarm::{behaviour::toggle::Toggle, NetworkBehaviour, SwarmEvent},
-    tcp, yamux,
+    tcp, yamux, StreamProtocol,
 };
 use std::error::Error;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 // Public modules
+pub mod bandwidth;
+pub mod checkpoint_sign;
+pub mod compact_relay;
+pub mod delta_sync;
 pub mod fee_scaling;
+pub mod gossip_envelope;
+pub mod node_identity;
 pub mod p2p_encryption;
 pub mod p2p_integration;
 pub mod slashing_integration;
 pub mod tor_transport;
 pub mod validator_rewards;
 
+pub use compact_relay::CompactBlock;
+pub use node_identity::NetworkKeypair;
 pub use tor_transport::{load_bootstrap_nodes, BootstrapNode, TorConfig, TorDialer};
 
+use checkpoint_sign::{CheckpointSignRequest, CheckpointSignResponse};
+use compact_relay::{BlockFetchRequest, BlockFetchResponse, BodyCache, FetchFallbacks};
+use delta_sync::{DeltaSyncRequest, DeltaSyncResponse};
+
 #[derive(Debug)]
 pub enum NetworkEvent {
     NewBlock(String),
     PeerDiscovered(String),
+    /// Compact announcement; the node replies with FETCH_BLOCK:{from_peer}:{hash}
+    /// on the out channel if it does not have the block yet.
+    CompactBlock {
+        from_peer: String,
+        block: CompactBlock,
+    },
+    /// A proposer asks this node to sign a checkpoint; the node answers with
+    /// CHECKPOINT_RESP:{inbound_id}:{json} on the out channel.
+    CheckpointSignRequest {
+        inbound_id: u64,
+        from_peer: String,
+        request: CheckpointSignRequest,
+    },
+    /// Connected peer count changed (min_peers = bootstrap reconnect threshold).
+    PeerCount {
+        connected: usize,
+        min_peers: usize,
+    },
+    /// A validator answered one of our CHECKPOINT_REQ requests.
+    CheckpointSignResponse {
+        from_peer: String,
+        response: CheckpointSignResponse,
+    },
+    /// A peer asks for frontiers or an account chain; the node answers with
+    /// DELTA_RESP:{inbound_id}:{json} on the out channel.
+    DeltaSyncRequest {
+        inbound_id: u64,
+        from_peer: String,
+        request: DeltaSyncRequest,
+    },
+    /// A peer answered one of our DELTA_REQ requests.
+    DeltaSyncResponse {
+        from_peer: String,
+        response: DeltaSyncResponse,
+    },
+    /// A DELTA_REQ request failed (timeout, unsupported protocol, disconnect).
+    DeltaSyncFailed {
+        from_peer: String,
+    },
+    /// Propagation delay of an enveloped gossip message (receive time −
+    /// publisher's origin time). `block_hash` is set for block-carrying messages.
+    GossipDelay {
+        from_peer: String,
+        block_hash: Option<String>,
+        delay_ms: u64,
+    },
 }
 
 #[derive(NetworkBehaviour)]
@@ -39,6 +97,13 @@ pub struct LosBehaviour {
     pub gossipsub: gossipsub::Behaviour,
     /// mDNS is disabled when Tor is enabled to prevent LAN presence leaks.
     pub mdns: Toggle<mdns::tokio::Behaviour>,
+    /// Point-to-point block body fetches for compact relay.
+    pub block_fetch: request_response::json::Behaviour<BlockFetchRequest, BlockFetchResponse>,
+    /// Direct proposer ↔ validator checkpoint signature collection.
+    pub checkpoint_sign:
+        request_response::json::Behaviour<CheckpointSignRequest, CheckpointSignResponse>,
+    /// Frontier comparison + missing-chain fetches (delta sync).
+    pub delta_sync: request_response::json::Behaviour<DeltaSyncRequest, DeltaSyncResponse>,
 }
 
 pub struct LosNode;
@@ -47,6 +112,7 @@ impl LosNode {
     pub async fn start(
         tx: mpsc::Sender<NetworkEvent>,
         mut rx_out: mpsc::Receiver<String>,
+        network_key: NetworkKeypair,
     ) -> Result<(), Box<dyn Error>> {
         // Load Tor configuration from environment
         let tor_config = TorConfig::from_env();
@@ -72,7 +138,7 @@ impl LosNode {
             println!("📡 Bootstrap nodes: {}", bootstrap_nodes.len());
         }
 
-        let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+        let mut swarm = libp2p::SwarmBuilder::with_existing_identity(network_key)
             .with_tokio()
             .with_tcp(
                 tcp::Config::default(),
@@ -80,14 +146,17 @@ impl LosNode {
                 yamux::Config::default,
             )?
             .with_behaviour(|key| {
+                // blake3 of the payload (envelope stripped) — same ID the node's
+                // persistent seen-cache uses
                 let message_id_fn = |message: &gossipsub::Message| {
-                    let mut s = std::collections::hash_map::DefaultHasher::new();
-                    std::hash::Hash::hash(&message.data, &mut s);
-                    gossipsub::MessageId::from(std::hash::Hasher::finish(&s).to_string())
+                    let (_, payload) = gossip_envelope::open_bytes(&message.data);
+                    gossipsub::MessageId::from(
+                        blake3::hash(payload.as_bytes()).to_hex().to_string(),
+                    )
                 };
 
-                let gossipsub_config = gossipsub::ConfigBuilder::default()
-                    .heartbeat_interval(Duration::from_secs(1))
+                let gossipsub_config = bandwidth::MeshParams::current()
+                    .apply(&mut gossipsub::ConfigBuilder::default())
                     .validation_mode(gossipsub::ValidationMode::Strict)
                     .message_id_fn(message_id_fn)
                     .max_transmit_size(10 * 1024 * 1024)
@@ -111,7 +180,40 @@ impl LosNode {
                     )?))
                 };
 
-                Ok(LosBehaviour { gossipsub, mdns })
+                let block_fetch = request_response::json::Behaviour::new(
+                    [(
+                        StreamProtocol::new(compact_relay::BLOCK_FETCH_PROTOCOL),
+                        request_response::ProtocolSupport::Full,
+                    )],
+                    request_response::Config::default()
+                        .with_request_timeout(Duration::from_secs(30)),
+                );
+
+                let checkpoint_sign = request_response::json::Behaviour::new(
+                    [(
+                        StreamProtocol::new(checkpoint_sign::CHECKPOINT_SIGN_PROTOCOL),
+                        request_response::ProtocolSupport::Full,
+                    )],
+                    request_response::Config::default()
+                        .with_request_timeout(Duration::from_secs(30)),
+                );
+
+                let delta_sync = request_response::json::Behaviour::new(
+                    [(
+                        StreamProtocol::new(delta_sync::DELTA_SYNC_PROTOCOL),
+                        request_response::ProtocolSupport::Full,
+                    )],
+                    request_response::Config::default()
+                        .with_request_timeout(Duration::from_secs(60)),
+                );
+
+                Ok(LosBehaviour {
+                    gossipsub,
+                    mdns,
+                    block_fetch,
+                    checkpoint_sign,
+                    delta_sync,
+                })
             })?
             .with_swarm_config(|cfg| cfg.with_idle_connection_timeout(Duration::from_secs(300)))
             .build();
@@ -178,12 +280,58 @@ impl LosNode {
         reconnect_timer.tick().await; // Consume the first immediate tick
         let mut connected_peers: std::collections::HashSet<libp2p::PeerId> =
             std::collections::HashSet::new();
-        let min_peers: usize = bootstrap_nodes.len().max(1);
+        // Low-bandwidth profile / LOS_MAX_PEERS: refuse connections past the cap
+        let max_peers = bandwidth::max_peers();
+        let compress_gossip = bandwidth::compress_gossip();
+        if bandwidth::is_enabled() {
+            println!(
+                "🐢 Low-bandwidth mode: mesh {} peers, max {} connections, compressed gossip",
+                bandwidth::MeshParams::LOW_BANDWIDTH.mesh_n,
+                max_peers
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "unlimited".to_string())
+            );
+        }
+        let min_peers: usize = bootstrap_nodes
+            .len()
+            .min(max_peers.unwrap_or(usize::MAX))
+            .max(1);
+        // Validator endpoints learned via signed peer exchange (PEX_PEERS: from
+        // the node), re-dialed together with the bootstrap list
+        let mut pex_nodes: Vec<BootstrapNode> = Vec::new();
+
+        // Compact relay state: bodies we can serve + in-flight fetches (request → hash, fallback peer)
+        let compact_relay = compact_relay::compact_relay_enabled();
+        let mut body_cache = BodyCache::new(compact_relay::BODY_CACHE_CAPACITY);
+        let mut pending_fetches: std::collections::HashMap<
+            request_response::OutboundRequestId,
+            (String, Option<libp2p::PeerId>),
+        > = std::collections::HashMap::new();
+        let mut fetch_fallback: FetchFallbacks<libp2p::PeerId> =
+            FetchFallbacks::new(compact_relay::FETCH_FALLBACK_CAPACITY);
+
+        // Checkpoint signing: inbound requests awaiting the node's answer (inbound_id → channel)
+        let mut next_inbound_id: u64 = 0;
+        let mut inbound_sign_requests: std::collections::BTreeMap<
+            u64,
+            request_response::ResponseChannel<CheckpointSignResponse>,
+        > = std::collections::BTreeMap::new();
+        // Delta sync: inbound requests awaiting the node's answer (shares next_inbound_id)
+        let mut inbound_delta_requests: std::collections::BTreeMap<
+            u64,
+            request_response::ResponseChannel<DeltaSyncResponse>,
+        > = std::collections::BTreeMap::new();
 
         loop {
             tokio::select! {
                 Some(msg_to_send) = rx_out.recv() => {
-                    if let Some(addr_str) = msg_to_send.strip_prefix("DIAL:") {
+                    if let Some(list) = msg_to_send.strip_prefix("PEX_PEERS:") {
+                        pex_nodes = list
+                            .split(',')
+                            .filter(|s| !s.trim().is_empty())
+                            .map(tor_transport::parse_bootstrap_node)
+                            .collect();
+                    } else if let Some(addr_str) = msg_to_send.strip_prefix("DIAL:") {
                         // Check if it's a .onion address
                         if addr_str.contains(".onion") {
                             if let Some(ref dialer) = tor_dialer {
@@ -206,9 +354,57 @@ impl LosNode {
                             println!("📡 Swarm: Dialing {}...", maddr);
                             let _ = swarm.dial(maddr);
                         }
+                    } else if let Some(rest) = msg_to_send.strip_prefix(compact_relay::FETCH_PREFIX) {
+                        // FETCH_BLOCK:{peer_id}:{hash} — request the body from the announcer
+                        if let Some((peer_str, hash)) = rest.split_once(':') {
+                            if let Ok(peer) = peer_str.parse::<libp2p::PeerId>() {
+                                let request_id = swarm.behaviour_mut().block_fetch.send_request(
+                                    &peer,
+                                    BlockFetchRequest { hash: hash.to_string() },
+                                );
+                                let fallback = fetch_fallback.remove(hash).filter(|p| *p != peer);
+                                pending_fetches.insert(request_id, (hash.to_string(), fallback));
+                            }
+                        }
+                    } else if let Some(req) = CheckpointSignRequest::from_message(&msg_to_send) {
+                        // CHECKPOINT_REQ:{json} — ask every connected peer directly (never gossiped)
+                        for peer in &connected_peers {
+                            swarm.behaviour_mut().checkpoint_sign.send_request(peer, req.clone());
+                        }
+                    } else if let Some((inbound_id, response)) = CheckpointSignResponse::from_message(&msg_to_send) {
+                        if let Some(channel) = inbound_sign_requests.remove(&inbound_id) {
+                            let _ = swarm.behaviour_mut().checkpoint_sign.send_response(channel, response);
+                        }
+                    } else if let Some((peer, req)) = DeltaSyncRequest::from_message(&msg_to_send) {
+                        // DELTA_REQ:{peer_id}:{json} — point-to-point, never gossiped.
+                        // No peer_id: any connected peer (resumed or fresh sessions)
+                        let target = peer
+                            .and_then(|p| p.parse::<libp2p::PeerId>().ok())
+                            .filter(|p| connected_peers.contains(p))
+                            .or_else(|| connected_peers.iter().next().copied());
+                        if let Some(target) = target {
+                            swarm.behaviour_mut().delta_sync.send_request(&target, req);
+                        }
+                    } else if let Some((inbound_id, response)) = DeltaSyncResponse::from_message(&msg_to_send) {
+                        if let Some(channel) = inbound_delta_requests.remove(&inbound_id) {
+                            let _ = swarm.behaviour_mut().delta_sync.send_response(channel, response);
+                        }
                     } else {
+                        // Compact relay: keep the body locally, gossip only hash + header
+                        let msg_to_send = match compact_relay::parse_raw_block(&msg_to_send) {
+                            Some((block, hash)) if compact_relay => {
+                                body_cache.insert(hash, msg_to_send);
+                                CompactBlock::from_block(&block).to_message()
+                            }
+                            _ => msg_to_send,
+                        };
                         let msg_prefix = if msg_to_send.len() > 20 { &msg_to_send[..20] } else { &msg_to_send };
-                        match swarm.behaviour_mut().gossipsub.publish(topic.clone(), msg_to_send.as_bytes()) {
+                        let sealed = if compress_gossip {
+                            gossip_envelope::seal_compressed(&msg_to_send, gossip_envelope::now_ms())
+                        } else {
+                            gossip_envelope::seal(&msg_to_send, gossip_envelope::now_ms()).into_bytes()
+                        };
+                        match swarm.behaviour_mut().gossipsub.publish(topic.clone(), sealed) {
                             Ok(_mid) => {
                                 if msg_to_send.starts_with("CONFIRM_REQ:") || msg_to_send.starts_with("CONFIRM_RES:") {
                                     println!("📤 Gossip PUBLISHED OK: {} ({} bytes, mesh_peers={})", msg_prefix, msg_to_send.len(), connected_peers.len());
@@ -240,8 +436,8 @@ impl LosNode {
                 _ = reconnect_timer.tick() => {
                     let peer_count = connected_peers.len();
                     if peer_count < min_peers {
-                        println!("🔄 P2P reconnect: only {}/{} peers connected, re-dialing bootstrap nodes...", peer_count, min_peers);
-                        for node in &bootstrap_nodes {
+                        println!("🔄 P2P reconnect: only {}/{} peers connected, re-dialing {} bootstrap + {} PEX nodes...", peer_count, min_peers, bootstrap_nodes.len(), pex_nodes.len());
+                        for node in bootstrap_nodes.iter().chain(&pex_nodes) {
                             match node {
                                 BootstrapNode::Multiaddr(addr) => {
                                     if let Ok(maddr) = addr.parse::<libp2p::Multiaddr>() {
@@ -273,21 +469,124 @@ impl LosNode {
                             let _ = tx.send(NetworkEvent::PeerDiscovered(peer_id.to_string())).await;
                         }
                     },
-                    SwarmEvent::Behaviour(LosBehaviourEvent::Gossipsub(gossipsub::Event::Message { message, .. })) => {
-                        let content = String::from_utf8_lossy(&message.data).to_string();
-                        let _ = tx.send(NetworkEvent::NewBlock(content)).await;
+                    SwarmEvent::Behaviour(LosBehaviourEvent::Gossipsub(gossipsub::Event::Message { propagation_source, message, .. })) => {
+                        let (origin_ms, content) = gossip_envelope::open_bytes(&message.data);
+                        let from_peer = message.source.unwrap_or(propagation_source);
+                        let compact = CompactBlock::from_message(&content);
+                        // Cache raw bodies from legacy full-block gossip so we can serve fetches
+                        let raw_hash = match compact {
+                            Some(_) => None,
+                            None => compact_relay::parse_raw_block(&content).map(|(_, hash)| hash),
+                        };
+                        if let Some(origin_ms) = origin_ms {
+                            let block_hash = compact
+                                .as_ref()
+                                .map(|b| b.hash.clone())
+                                .or_else(|| raw_hash.clone())
+                                .or_else(|| gossip_envelope::block_key(&content).map(str::to_string));
+                            let delay_ms = gossip_envelope::now_ms().saturating_sub(origin_ms);
+                            let _ = tx.send(NetworkEvent::GossipDelay { from_peer: from_peer.to_string(), block_hash, delay_ms }).await;
+                        }
+                        if let Some(block) = compact {
+                            // Ask the original publisher first (it always has the body);
+                            // the forwarding peer is the fallback.
+                            if from_peer != propagation_source {
+                                fetch_fallback.insert(block.hash.clone(), propagation_source);
+                            }
+                            let _ = tx.send(NetworkEvent::CompactBlock { from_peer: from_peer.to_string(), block }).await;
+                        } else {
+                            if let Some(hash) = raw_hash {
+                                fetch_fallback.remove(&hash);
+                                body_cache.insert(hash, content.clone());
+                            }
+                            let _ = tx.send(NetworkEvent::NewBlock(content)).await;
+                        }
+                    },
+                    SwarmEvent::Behaviour(LosBehaviourEvent::BlockFetch(request_response::Event::Message { message, .. })) => match message {
+                        request_response::Message::Request { request, channel, .. } => {
+                            let block_json = body_cache.get(&request.hash).cloned();
+                            let _ = swarm.behaviour_mut().block_fetch.send_response(channel, BlockFetchResponse { block_json });
+                        }
+                        request_response::Message::Response { request_id, response } => {
+                            if let Some((hash, fallback)) = pending_fetches.remove(&request_id) {
+                                match response.block_json {
+                                    Some(json) if compact_relay::verify_fetched_body(&hash, &json) => {
+                                        body_cache.insert(hash, json.clone());
+                                        let _ = tx.send(NetworkEvent::NewBlock(json)).await;
+                                    }
+                                    Some(_) => eprintln!("🚫 Block fetch: body does not match hash {}", &hash[..16]),
+                                    None => {
+                                        if let Some(peer) = fallback {
+                                            let rid = swarm.behaviour_mut().block_fetch.send_request(&peer, BlockFetchRequest { hash: hash.clone() });
+                                            pending_fetches.insert(rid, (hash, None));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    SwarmEvent::Behaviour(LosBehaviourEvent::BlockFetch(request_response::Event::OutboundFailure { request_id, error, .. })) => {
+                        if let Some((hash, fallback)) = pending_fetches.remove(&request_id) {
+                            match fallback {
+                                Some(peer) => {
+                                    let rid = swarm.behaviour_mut().block_fetch.send_request(&peer, BlockFetchRequest { hash: hash.clone() });
+                                    pending_fetches.insert(rid, (hash, None));
+                                }
+                                None => eprintln!("⚠️ Block fetch failed for {}: {:?}", &hash[..16], error),
+                            }
+                        }
+                    },
+                    SwarmEvent::Behaviour(LosBehaviourEvent::CheckpointSign(request_response::Event::Message { peer, message })) => match message {
+                        request_response::Message::Request { request, channel, .. } => {
+                            // Bound unanswered requests — drop the oldest channel (its peer times out)
+                            while inbound_sign_requests.len() >= checkpoint_sign::MAX_INBOUND_SIGN_REQUESTS {
+                                inbound_sign_requests.pop_first();
+                            }
+                            let inbound_id = next_inbound_id;
+                            next_inbound_id += 1;
+                            inbound_sign_requests.insert(inbound_id, channel);
+                            let _ = tx.send(NetworkEvent::CheckpointSignRequest { inbound_id, from_peer: peer.to_string(), request }).await;
+                        }
+                        request_response::Message::Response { response, .. } => {
+                            let _ = tx.send(NetworkEvent::CheckpointSignResponse { from_peer: peer.to_string(), response }).await;
+                        }
+                    },
+                    SwarmEvent::Behaviour(LosBehaviourEvent::DeltaSync(request_response::Event::Message { peer, message })) => match message {
+                        request_response::Message::Request { request, channel, .. } => {
+                            while inbound_delta_requests.len() >= delta_sync::MAX_INBOUND_DELTA_REQUESTS {
+                                inbound_delta_requests.pop_first();
+                            }
+                            let inbound_id = next_inbound_id;
+                            next_inbound_id += 1;
+                            inbound_delta_requests.insert(inbound_id, channel);
+                            let _ = tx.send(NetworkEvent::DeltaSyncRequest { inbound_id, from_peer: peer.to_string(), request }).await;
+                        }
+                        request_response::Message::Response { response, .. } => {
+                            let _ = tx.send(NetworkEvent::DeltaSyncResponse { from_peer: peer.to_string(), response }).await;
+                        }
+                    },
+                    SwarmEvent::Behaviour(LosBehaviourEvent::DeltaSync(request_response::Event::OutboundFailure { peer, error, .. })) => {
+                        eprintln!("⚠️ Delta sync request to {} failed: {:?}", peer, error);
+                        let _ = tx.send(NetworkEvent::DeltaSyncFailed { from_peer: peer.to_string() }).await;
                     },
                     SwarmEvent::NewListenAddr { address, .. } => {
                         println!("📍 P2P listening on: {:?}", address);
                     },
                     SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                        if max_peers.is_some_and(|max| connected_peers.len() >= max && !connected_peers.contains(&peer_id)) {
+                            println!("🐢 P2P peer cap reached ({}), refusing {:?}", connected_peers.len(), peer_id);
+                            let _ = swarm.disconnect_peer_id(peer_id);
+                            continue;
+                        }
                         swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
                         connected_peers.insert(peer_id);
                         println!("🤝 P2P connected: {:?} (total: {})", peer_id, connected_peers.len());
+                        let _ = tx.send(NetworkEvent::PeerCount { connected: connected_peers.len(), min_peers }).await;
                     },
                     SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
                         connected_peers.remove(&peer_id);
                         println!("🔌 P2P disconnected: {:?} (reason: {:?}, remaining: {})", peer_id, cause, connected_peers.len());
+                        let _ = tx.send(NetworkEvent::PeerCount { connected: connected_peers.len(), min_peers }).await;
                     },
                     SwarmEvent::OutgoingConnectionError { error, .. } => {
                         eprintln!("❌ P2P dial error: {:?}", error);
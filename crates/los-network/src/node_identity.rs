@@ -0,0 +1,128 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - NETWORK IDENTITY
+//
+// A node has two identities:
+// - validator key (Dilithium5, wallet.json): signs blocks, votes, checkpoints
+// - network key (Ed25519, <data_dir>/network_key): libp2p PeerId, Noise
+//   handshakes and gossipsub message signing
+//
+// The network key is created on first start and reused afterwards, so the
+// PeerId is stable and can be pinned by peers. It is unrelated to the
+// validator key: leaking or rotating one does not affect the other.
+// Rotation (`los-node --rotate-network-key`) keeps the previous key as
+// network_key.prev.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use libp2p::identity::Keypair;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub use libp2p::identity::Keypair as NetworkKeypair;
+
+/// File name of the network key inside the data directory
+pub const NETWORK_KEY_FILE: &str = "network_key";
+/// Previous key, kept by rotate()
+pub const PREVIOUS_NETWORK_KEY_FILE: &str = "network_key.prev";
+
+/// PeerId string of a network key
+pub fn peer_id(keypair: &Keypair) -> String {
+    keypair.public().to_peer_id().to_string()
+}
+
+fn key_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(NETWORK_KEY_FILE)
+}
+
+fn read_key(path: &Path) -> Result<Keypair, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Keypair::from_protobuf_encoding(&bytes)
+        .map_err(|e| format!("Invalid network key {}: {}", path.display(), e))
+}
+
+/// Write through a temp file + rename, owner-only permissions on Unix
+fn write_key(path: &Path, keypair: &Keypair) -> Result<(), String> {
+    let bytes = keypair
+        .to_protobuf_encoding()
+        .map_err(|e| format!("Failed to encode network key: {}", e))?;
+    let tmp = path.with_extension("tmp");
+    let mut opts = fs::OpenOptions::new();
+    opts.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    let mut file = opts
+        .open(&tmp)
+        .map_err(|e| format!("Failed to create {}: {}", tmp.display(), e))?;
+    file.write_all(&bytes)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to store {}: {}", path.display(), e))
+}
+
+/// Load the network key from `data_dir`, generating it on first start.
+/// Returns the key and whether it was just created. A corrupt key file is
+/// an error (not silently replaced, which would change the PeerId).
+pub fn load_or_create(data_dir: &Path) -> Result<(Keypair, bool), String> {
+    let path = key_path(data_dir);
+    if path.exists() {
+        return read_key(&path).map(|k| (k, false));
+    }
+    let keypair = Keypair::generate_ed25519();
+    write_key(&path, &keypair)?;
+    Ok((keypair, true))
+}
+
+/// Replace the network key with a fresh one. The current key (if any) is
+/// moved to network_key.prev; returns the new key and the old PeerId.
+pub fn rotate(data_dir: &Path) -> Result<(Keypair, Option<String>), String> {
+    let path = key_path(data_dir);
+    let old = if path.exists() {
+        let old = read_key(&path).ok().map(|k| peer_id(&k));
+        let prev = data_dir.join(PREVIOUS_NETWORK_KEY_FILE);
+        fs::rename(&path, &prev)
+            .map_err(|e| format!("Failed to keep previous network key: {}", e))?;
+        old
+    } else {
+        None
+    };
+    let keypair = Keypair::generate_ed25519();
+    write_key(&path, &keypair)?;
+    Ok((keypair, old))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_key_persists_and_rotates() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, created) = load_or_create(dir.path()).unwrap();
+        assert!(created);
+        let (again, created) = load_or_create(dir.path()).unwrap();
+        assert!(!created);
+        assert_eq!(peer_id(&first), peer_id(&again));
+
+        let (rotated, old) = rotate(dir.path()).unwrap();
+        assert_eq!(old, Some(peer_id(&first)));
+        assert_ne!(peer_id(&rotated), peer_id(&first));
+        let (loaded, _) = load_or_create(dir.path()).unwrap();
+        assert_eq!(peer_id(&loaded), peer_id(&rotated));
+        let prev = read_key(&dir.path().join(PREVIOUS_NETWORK_KEY_FILE)).unwrap();
+        assert_eq!(peer_id(&prev), peer_id(&first));
+    }
+
+    #[test]
+    fn test_corrupt_network_key_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(NETWORK_KEY_FILE), b"not a key").unwrap();
+        assert!(load_or_create(dir.path()).is_err());
+        // Rotation still works (recovery path) and reports no old PeerId
+        let (_, old) = rotate(dir.path()).unwrap();
+        assert!(old.is_none());
+        assert!(load_or_create(dir.path()).is_ok());
+    }
+}
@@ -258,7 +258,7 @@ impl P2PNetworkManager {
     /// Process outbound queue (sort by priority)
     pub fn flush_outbound_queue(&mut self) -> Vec<QueuedMessage> {
         self.outbound_queue
-            .sort_by(|a, b| b.priority.cmp(&a.priority));
+            .sort_by_key(|m| std::cmp::Reverse(m.priority));
         self.outbound_queue.drain(..).collect()
     }
 
@@ -8,11 +8,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build_client(true) // Generate client code (for testing)
         .compile_protos(
             // Updated method name (not deprecated)
-            &["../../los.proto"], // Proto file path
-            &["../../"],          // Include directory
+            &["../../los.proto", "../../signer.proto"], // Proto file paths
+            &["../../"],                                // Include directory
         )?;
 
     println!("cargo:rerun-if-changed=../../los.proto");
+    println!("cargo:rerun-if-changed=../../signer.proto");
 
     Ok(())
 }
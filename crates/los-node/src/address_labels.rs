@@ -0,0 +1,140 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ADDRESS BOOK LABELS
+//
+// The address book maps short addresses (los_XXXXXXXX) to full addresses and
+// is persisted in sled tree "known_peers". Operators can also give addresses
+// a label ("exchange hot wallet", "faucet"), stored in tree "address_labels"
+// (full address → label).
+//
+// Both are managed on /admin/address-book (X-Admin-Token, see api_access.rs).
+// /history, /transactions and the explorer endpoints add a `*_label` field
+// next to every labelled address they return.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::BTreeMap;
+
+/// Longest accepted label, in bytes
+pub const MAX_LABEL_LEN: usize = 64;
+/// Labels kept at most
+pub const MAX_LABELS: usize = 10_000;
+
+/// Trimmed label, or why it is not acceptable
+pub fn validate_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Label must not be empty".to_string());
+    }
+    if label.len() > MAX_LABEL_LEN {
+        return Err(format!("Label longer than {} bytes", MAX_LABEL_LEN));
+    }
+    if label.chars().any(char::is_control) {
+        return Err("Label must not contain control characters".to_string());
+    }
+    Ok(label.to_string())
+}
+
+#[derive(Debug, Default)]
+pub struct AddressLabels {
+    labels: BTreeMap<String, String>,
+    /// Bumped on every change (part of cached responses' ETags)
+    version: u64,
+}
+
+impl AddressLabels {
+    pub fn load(labels: BTreeMap<String, String>) -> Self {
+        Self { labels, version: 0 }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn get(&self, address: &str) -> Option<&str> {
+        self.labels.get(address).map(String::as_str)
+    }
+
+    /// Set or replace the label of `address`. Returns the stored label.
+    pub fn set(&mut self, address: &str, label: &str) -> Result<String, String> {
+        let label = validate_label(label)?;
+        if self.labels.len() >= MAX_LABELS && !self.labels.contains_key(address) {
+            return Err(format!("Address book holds {} labels already", MAX_LABELS));
+        }
+        self.labels.insert(address.to_string(), label.clone());
+        self.version += 1;
+        Ok(label)
+    }
+
+    pub fn remove(&mut self, address: &str) -> Option<String> {
+        let removed = self.labels.remove(address);
+        if removed.is_some() {
+            self.version += 1;
+        }
+        removed
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.labels.iter()
+    }
+
+    /// For each (address field, label field) pair, add the label field to
+    /// `obj` when the address field holds a labelled address.
+    pub fn annotate(&self, obj: &mut serde_json::Value, fields: &[(&str, &str)]) {
+        if self.labels.is_empty() {
+            return;
+        }
+        for (address_field, label_field) in fields {
+            let label = obj
+                .get(address_field)
+                .and_then(|v| v.as_str())
+                .and_then(|addr| self.labels.get(addr));
+            if let Some(label) = label.cloned() {
+                obj[*label_field] = serde_json::Value::String(label);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_are_validated() {
+        assert_eq!(validate_label("  faucet ").unwrap(), "faucet");
+        assert!(validate_label("   ").is_err());
+        assert!(validate_label(&"x".repeat(MAX_LABEL_LEN + 1)).is_err());
+        assert!(validate_label("hot\nwallet").is_err());
+
+        let mut labels = AddressLabels::default();
+        assert!(labels.set("LOSa", "").is_err());
+        assert_eq!(
+            labels.set("LOSa", " exchange hot wallet").unwrap(),
+            "exchange hot wallet"
+        );
+        assert_eq!(labels.get("LOSa"), Some("exchange hot wallet"));
+        assert_eq!(labels.version(), 1);
+        assert_eq!(
+            labels.remove("LOSa").as_deref(),
+            Some("exchange hot wallet")
+        );
+        assert_eq!(labels.get("LOSa"), None);
+        assert!(labels.remove("LOSa").is_none());
+        assert_eq!(labels.version(), 2);
+    }
+
+    #[test]
+    fn test_annotate_adds_label_fields() {
+        let mut labels = AddressLabels::default();
+        labels.set("LOSfaucet", "faucet").unwrap();
+        let mut tx = serde_json::json!({"from": "LOSfaucet", "to": "LOSbob", "amount": 5});
+        labels.annotate(&mut tx, &[("from", "from_label"), ("to", "to_label")]);
+        assert_eq!(tx["from_label"], "faucet");
+        assert!(tx.get("to_label").is_none());
+        // Non-string and missing fields are ignored
+        labels.annotate(
+            &mut tx,
+            &[("amount", "amount_label"), ("nope", "nope_label")],
+        );
+        assert!(tx.get("amount_label").is_none() && tx.get("nope_label").is_none());
+    }
+}
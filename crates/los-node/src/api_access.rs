@@ -0,0 +1,563 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - API ACCESS CONTROL (CORS + API KEY TIERS)
+//
+// For operators running public RPC endpoints:
+//   - LOS_CORS_ORIGINS: comma-separated allowed origins
+//     ("https://wallet.example,https://explorer.example"). Unset or "*"
+//     keeps the default of allowing any origin.
+//   - API keys (optional for clients): requests carrying `X-API-Key` are
+//     rate-limited per key at their tier instead of per IP. Unknown or
+//     revoked keys are rejected with 401; requests without a key keep the
+//     per-IP limit.
+//   - Tiers: free / paid, limits from LOS_API_TIER_FREE and
+//     LOS_API_TIER_PAID ("requests_per_second[:burst]").
+//   - Per-key usage (requests, rate-limited) is counted in memory and
+//     exported as los_api_key_requests_total / los_api_key_rate_limited_total.
+//   - Key management on /admin/api-keys, enabled by LOS_ADMIN_TOKEN and
+//     authenticated with the `X-Admin-Token` header.
+//
+// Only the blake3 hash of a key is stored (sled tree "api_keys"); the key
+// itself is returned once, when it is created.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::rate_limiter::RateLimiter;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// Request header carrying a client API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// Request header carrying the admin token
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+/// Prefix of generated API keys
+const KEY_PREFIX: &str = "los_";
+/// Hex characters of the key hash used as the public key ID
+const KEY_ID_LEN: usize = 12;
+/// Longest accepted key label
+const MAX_LABEL_LEN: usize = 64;
+
+/// Default free tier: requests per second, burst
+const DEFAULT_FREE_LIMITS: TierLimits = TierLimits {
+    requests_per_second: 100,
+    burst: 200,
+};
+/// Default paid tier: requests per second, burst
+const DEFAULT_PAID_LIMITS: TierLimits = TierLimits {
+    requests_per_second: 1_000,
+    burst: 2_000,
+};
+
+fn safe_lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Rate-limit tier of an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTier {
+    Free,
+    Paid,
+}
+
+impl ApiTier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiTier::Free => "free",
+            ApiTier::Paid => "paid",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "free" => Ok(ApiTier::Free),
+            "paid" => Ok(ApiTier::Paid),
+            other => Err(format!("Unknown tier '{}' (expected free or paid)", other)),
+        }
+    }
+}
+
+/// Token bucket parameters of a tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TierLimits {
+    pub requests_per_second: u32,
+    pub burst: u32,
+}
+
+impl TierLimits {
+    /// Parse "rps" or "rps:burst" (burst defaults to 2 × rps)
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (rps, burst) = match s.trim().split_once(':') {
+            Some((r, b)) => (r, Some(b)),
+            None => (s.trim(), None),
+        };
+        let requests_per_second: u32 = rps
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid requests per second '{}'", rps))?;
+        let burst = match burst {
+            Some(b) => b
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid burst '{}'", b))?,
+            None => requests_per_second.saturating_mul(2),
+        };
+        if requests_per_second == 0 || burst == 0 {
+            return Err("Tier limits must be greater than zero".to_string());
+        }
+        Ok(TierLimits {
+            requests_per_second,
+            burst,
+        })
+    }
+}
+
+/// Persisted API key (the key itself is never stored)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    /// Public identifier: first hex characters of `key_hash`
+    pub id: String,
+    pub label: String,
+    pub tier: ApiTier,
+    /// blake3 hex of the full key
+    pub key_hash: String,
+    pub created_at: u64,
+}
+
+/// In-memory usage counters of one key (reset on restart)
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ApiKeyUsage {
+    pub requests: u64,
+    pub rate_limited: u64,
+    pub last_used: u64,
+}
+
+/// Outcome of checking a request against the access policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyCheck {
+    /// Within the key's tier limit
+    Allowed { id: String, tier: ApiTier },
+    /// Key valid, tier limit exceeded
+    RateLimited { id: String, tier: ApiTier },
+    /// Key unknown or revoked
+    Invalid,
+}
+
+/// CORS and API key configuration, read from the environment
+#[derive(Debug, Clone)]
+pub struct AccessConfig {
+    /// None = allow any origin
+    pub cors_origins: Option<Vec<String>>,
+    pub free: TierLimits,
+    pub paid: TierLimits,
+    /// None = admin API disabled
+    pub admin_token: Option<String>,
+}
+
+impl AccessConfig {
+    /// Read LOS_CORS_ORIGINS, LOS_API_TIER_FREE, LOS_API_TIER_PAID and
+    /// LOS_ADMIN_TOKEN. Invalid values are reported and replaced by defaults.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let tier = |name: &str, default: TierLimits| match var(name) {
+            Some(v) => TierLimits::parse(&v).unwrap_or_else(|e| {
+                eprintln!("⚠️  {}: {} — using default", name, e);
+                default
+            }),
+            None => default,
+        };
+        AccessConfig {
+            cors_origins: var("LOS_CORS_ORIGINS").and_then(|v| parse_cors_origins(&v)),
+            free: tier("LOS_API_TIER_FREE", DEFAULT_FREE_LIMITS),
+            paid: tier("LOS_API_TIER_PAID", DEFAULT_PAID_LIMITS),
+            admin_token: var("LOS_ADMIN_TOKEN").map(|t| t.trim().to_string()),
+        }
+    }
+}
+
+impl Default for AccessConfig {
+    fn default() -> Self {
+        AccessConfig {
+            cors_origins: None,
+            free: DEFAULT_FREE_LIMITS,
+            paid: DEFAULT_PAID_LIMITS,
+            admin_token: None,
+        }
+    }
+}
+
+/// Parse a comma-separated origin list. None means "any origin" ("*").
+/// Malformed origins are skipped with a warning, since warp panics on them;
+/// a list without any valid origin refuses all cross-origin requests.
+pub fn parse_cors_origins(list: &str) -> Option<Vec<String>> {
+    let mut origins = Vec::new();
+    for raw in list.split(',').map(str::trim).filter(|o| !o.is_empty()) {
+        if raw == "*" {
+            return None;
+        }
+        let origin = raw.trim_end_matches('/');
+        let host = origin
+            .strip_prefix("https://")
+            .or_else(|| origin.strip_prefix("http://"));
+        match host {
+            Some(h) if !h.is_empty() && !h.contains(['/', ' ', '?', '#']) => {
+                origins.push(origin.to_string())
+            }
+            _ => eprintln!(
+                "⚠️  LOS_CORS_ORIGINS: ignoring invalid origin '{}' (expected scheme://host[:port])",
+                raw
+            ),
+        }
+    }
+    if origins.is_empty() {
+        eprintln!("⚠️  LOS_CORS_ORIGINS has no valid origin — cross-origin requests are refused");
+    }
+    Some(origins)
+}
+
+/// blake3 hex of an API key
+fn hash_key(key: &str) -> String {
+    blake3::hash(key.as_bytes()).to_hex().to_string()
+}
+
+/// Constant-time string comparison (admin token)
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// API keys, their tier limiters and usage counters
+pub struct ApiAccess {
+    config: AccessConfig,
+    /// key_hash → record
+    keys: Mutex<BTreeMap<String, ApiKeyRecord>>,
+    /// key id → usage
+    usage: Mutex<HashMap<String, ApiKeyUsage>>,
+    free_limiter: RateLimiter<String>,
+    paid_limiter: RateLimiter<String>,
+}
+
+impl ApiAccess {
+    pub fn new(config: AccessConfig, records: Vec<ApiKeyRecord>) -> Self {
+        let free_limiter =
+            RateLimiter::new(config.free.requests_per_second, Some(config.free.burst));
+        let paid_limiter =
+            RateLimiter::new(config.paid.requests_per_second, Some(config.paid.burst));
+        ApiAccess {
+            config,
+            keys: Mutex::new(
+                records
+                    .into_iter()
+                    .map(|r| (r.key_hash.clone(), r))
+                    .collect(),
+            ),
+            usage: Mutex::new(HashMap::new()),
+            free_limiter,
+            paid_limiter,
+        }
+    }
+
+    pub fn config(&self) -> &AccessConfig {
+        &self.config
+    }
+
+    pub fn admin_enabled(&self) -> bool {
+        self.config.admin_token.is_some()
+    }
+
+    /// Whether `token` matches LOS_ADMIN_TOKEN (always false when disabled)
+    pub fn check_admin_token(&self, token: Option<&str>) -> bool {
+        match (&self.config.admin_token, token) {
+            (Some(expected), Some(given)) => constant_time_eq(expected, given.trim()),
+            _ => false,
+        }
+    }
+
+    /// Error body for an admin request with `token`, None when authorized
+    pub fn admin_denied(&self, token: Option<&str>) -> Option<serde_json::Value> {
+        if !self.admin_enabled() {
+            return Some(serde_json::json!({
+                "status": "error", "code": 404,
+                "msg": "Admin API disabled (set LOS_ADMIN_TOKEN to enable)"
+            }));
+        }
+        if !self.check_admin_token(token) {
+            return Some(serde_json::json!({
+                "status": "error", "code": 401,
+                "msg": "Missing or invalid X-Admin-Token"
+            }));
+        }
+        None
+    }
+
+    /// Check one request made with `key` against its tier and count it
+    pub fn check_key(&self, key: &str) -> KeyCheck {
+        let (id, tier) = {
+            let keys = safe_lock(&self.keys);
+            match keys.get(&hash_key(key.trim())) {
+                Some(r) => (r.id.clone(), r.tier),
+                None => return KeyCheck::Invalid,
+            }
+        };
+        let limiter = match tier {
+            ApiTier::Free => &self.free_limiter,
+            ApiTier::Paid => &self.paid_limiter,
+        };
+        let allowed = limiter.check_rate_limit(id.clone());
+        let mut usage = safe_lock(&self.usage);
+        let u = usage.entry(id.clone()).or_default();
+        u.last_used = now_secs();
+        if allowed {
+            u.requests += 1;
+            KeyCheck::Allowed { id, tier }
+        } else {
+            u.rate_limited += 1;
+            KeyCheck::RateLimited { id, tier }
+        }
+    }
+
+    /// Create a key. Returns the record to persist and the key, which is
+    /// not recoverable afterwards.
+    pub fn create_key(&self, label: &str, tier: ApiTier) -> Result<(ApiKeyRecord, String), String> {
+        let label = label.trim();
+        if label.len() > MAX_LABEL_LEN {
+            return Err(format!("Label longer than {} bytes", MAX_LABEL_LEN));
+        }
+        use rand::RngCore;
+        let mut secret = [0u8; 20];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let key = format!("{}{}", KEY_PREFIX, hex::encode(secret));
+        let key_hash = hash_key(&key);
+        let record = ApiKeyRecord {
+            id: key_hash[..KEY_ID_LEN].to_string(),
+            label: label.to_string(),
+            tier,
+            key_hash: key_hash.clone(),
+            created_at: now_secs(),
+        };
+        let mut keys = safe_lock(&self.keys);
+        if keys.values().any(|r| r.id == record.id) {
+            return Err("Key ID collision, retry".to_string());
+        }
+        keys.insert(key_hash, record.clone());
+        Ok((record, key))
+    }
+
+    /// Change the tier of key `id`. Returns the updated record.
+    pub fn set_tier(&self, id: &str, tier: ApiTier) -> Result<ApiKeyRecord, String> {
+        let mut keys = safe_lock(&self.keys);
+        let record = keys
+            .values_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| format!("API key {} not found", id))?;
+        record.tier = tier;
+        Ok(record.clone())
+    }
+
+    /// Revoke key `id`; requests using it are rejected from now on
+    pub fn revoke(&self, id: &str) -> Result<ApiKeyRecord, String> {
+        let mut keys = safe_lock(&self.keys);
+        let hash = keys
+            .values()
+            .find(|r| r.id == id)
+            .map(|r| r.key_hash.clone())
+            .ok_or_else(|| format!("API key {} not found", id))?;
+        safe_lock(&self.usage).remove(id);
+        keys.remove(&hash)
+            .ok_or_else(|| format!("API key {} not found", id))
+    }
+
+    /// All keys with their usage, ordered by ID
+    pub fn list(&self) -> Vec<(ApiKeyRecord, ApiKeyUsage)> {
+        let keys = safe_lock(&self.keys);
+        let usage = safe_lock(&self.usage);
+        let mut out: Vec<_> = keys
+            .values()
+            .map(|r| (r.clone(), usage.get(&r.id).copied().unwrap_or_default()))
+            .collect();
+        out.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+        out
+    }
+}
+
+/// Warp filter enforcing API keys and the per-IP limit
+pub mod filters {
+    use super::{ApiAccess, KeyCheck, API_KEY_HEADER};
+    use crate::metrics::LosMetrics;
+    use crate::rate_limiter::{filters::client_ip, filters::RateLimitExceeded, RateLimiter};
+    use std::net::IpAddr;
+    use std::sync::Arc;
+    use warp::Filter;
+
+    /// Unknown or revoked API key
+    #[derive(Debug)]
+    pub struct InvalidApiKey;
+
+    impl warp::reject::Reject for InvalidApiKey {}
+
+    /// API key over its tier limit
+    #[derive(Debug)]
+    pub struct ApiKeyRateLimited {
+        pub key_id: String,
+        pub tier: &'static str,
+    }
+
+    impl warp::reject::Reject for ApiKeyRateLimited {}
+
+    /// Requests with `X-API-Key` are limited per key at its tier; requests
+    /// without one by the per-IP `limiter`.
+    pub fn access_control(
+        access: Arc<ApiAccess>,
+        limiter: RateLimiter,
+        metrics: Arc<LosMetrics>,
+    ) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+        client_ip()
+            .and(warp::header::optional::<String>(API_KEY_HEADER))
+            .and(warp::any().map(move || (access.clone(), limiter.clone(), metrics.clone())))
+            .and_then(
+                |ip: IpAddr,
+                 key: Option<String>,
+                 (access, limiter, metrics): (Arc<ApiAccess>, RateLimiter, Arc<LosMetrics>)| async move {
+                    let Some(key) = key.filter(|k| !k.trim().is_empty()) else {
+                        if limiter.check_rate_limit(ip) {
+                            return Ok(());
+                        }
+                        metrics.rate_limit_rejections_total.inc();
+                        return Err(warp::reject::custom(RateLimitExceeded { ip }));
+                    };
+                    match access.check_key(&key) {
+                        KeyCheck::Allowed { id, tier } => {
+                            metrics
+                                .api_key_requests_total
+                                .with_label_values(&[id.as_str(), tier.as_str()])
+                                .inc();
+                            Ok(())
+                        }
+                        KeyCheck::RateLimited { id, tier } => {
+                            metrics
+                                .api_key_rate_limited_total
+                                .with_label_values(&[id.as_str(), tier.as_str()])
+                                .inc();
+                            Err(warp::reject::custom(ApiKeyRateLimited {
+                                key_id: id,
+                                tier: tier.as_str(),
+                            }))
+                        }
+                        KeyCheck::Invalid => Err(warp::reject::custom(InvalidApiKey)),
+                    }
+                },
+            )
+            .untuple_one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(free: &str, paid: &str) -> ApiAccess {
+        let config = AccessConfig {
+            free: TierLimits::parse(free).unwrap(),
+            paid: TierLimits::parse(paid).unwrap(),
+            admin_token: Some("s3cret".to_string()),
+            ..AccessConfig::default()
+        };
+        ApiAccess::new(config, Vec::new())
+    }
+
+    #[test]
+    fn test_keys_limited_per_tier_with_usage() {
+        let access = access("1:2", "5:5");
+        let (free, free_key) = access.create_key("explorer", ApiTier::Free).unwrap();
+        let (paid, paid_key) = access.create_key("wallet", ApiTier::Paid).unwrap();
+        assert!(free_key.starts_with(KEY_PREFIX));
+        assert_ne!(free.key_hash, free_key); // only the hash is kept
+
+        for _ in 0..2 {
+            assert!(matches!(
+                access.check_key(&free_key),
+                KeyCheck::Allowed { .. }
+            ));
+        }
+        assert_eq!(
+            access.check_key(&free_key),
+            KeyCheck::RateLimited {
+                id: free.id.clone(),
+                tier: ApiTier::Free
+            }
+        );
+        for _ in 0..5 {
+            assert!(matches!(
+                access.check_key(&paid_key),
+                KeyCheck::Allowed { .. }
+            ));
+        }
+        assert_eq!(access.check_key("los_unknown"), KeyCheck::Invalid);
+
+        let usage: HashMap<_, _> = access
+            .list()
+            .into_iter()
+            .map(|(r, u)| (r.id, (u.requests, u.rate_limited)))
+            .collect();
+        assert_eq!(usage[&free.id], (2, 1));
+        assert_eq!(usage[&paid.id], (5, 0));
+
+        // Upgrading moves the key to the paid bucket
+        access.set_tier(&free.id, ApiTier::Paid).unwrap();
+        assert!(matches!(
+            access.check_key(&free_key),
+            KeyCheck::Allowed {
+                tier: ApiTier::Paid,
+                ..
+            }
+        ));
+
+        access.revoke(&paid.id).unwrap();
+        assert_eq!(access.check_key(&paid_key), KeyCheck::Invalid);
+        assert!(access.revoke(&paid.id).is_err());
+    }
+
+    #[test]
+    fn test_access_config_parsing() {
+        assert_eq!(
+            TierLimits::parse("50").unwrap(),
+            TierLimits {
+                requests_per_second: 50,
+                burst: 100
+            }
+        );
+        assert_eq!(TierLimits::parse("10:15").unwrap().burst, 15);
+        assert!(TierLimits::parse("0").is_err());
+        assert!(TierLimits::parse("fast").is_err());
+        assert_eq!(ApiTier::parse("PAID").unwrap(), ApiTier::Paid);
+        assert!(ApiTier::parse("gold").is_err());
+
+        assert_eq!(parse_cors_origins("*"), None);
+        assert_eq!(
+            parse_cors_origins("https://a.example, http://localhost:3000/ ,ftp://x,https://b/path"),
+            Some(vec![
+                "https://a.example".to_string(),
+                "http://localhost:3000".to_string()
+            ])
+        );
+        assert_eq!(parse_cors_origins("not-an-origin"), Some(Vec::new()));
+
+        let a = access("1", "1");
+        assert!(a.check_admin_token(Some("s3cret")));
+        assert!(!a.check_admin_token(Some("s3cre")));
+        assert!(!a.check_admin_token(None));
+        assert!(!ApiAccess::new(AccessConfig::default(), Vec::new()).check_admin_token(Some("")));
+    }
+}
@@ -0,0 +1,233 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ARCHIVE MODE (--archive / LOS_ARCHIVE=1)
+//
+// A regular node only keeps the latest state. An archive node also keeps a
+// state snapshot per checkpoint height (every CHECKPOINT_INTERVAL blocks) so
+// explorers and auditors can query history:
+//
+//   GET /bal/{addr}?at_block=H                  → balance at snapshot ≤ H
+//   GET /contract/{addr}/state?at_checkpoint=H  → contract state at snapshot ≤ H
+//
+// Snapshots are taken by the save loop when the block count crosses the next
+// checkpoint height, from the same ledger copy the checkpoint proposal uses
+// (so "height H" means the state when the node reached H blocks). Only
+// entries that changed since the previous snapshot are written: the value at
+// H is the newest entry at or below the snapshot height. The first snapshot
+// after a (re)start writes everything.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::db::LosDatabase;
+use los_consensus::checkpoint::CHECKPOINT_INTERVAL;
+use los_core::{AccountState, Ledger};
+use los_vm::WasmEngine;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ARCHIVE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Turn on archive mode (from --archive or LOS_ARCHIVE=1, before startup)
+pub fn enable() {
+    ARCHIVE_MODE.store(true, Ordering::Release);
+}
+
+/// Whether this node keeps historical snapshots
+pub fn is_enabled() -> bool {
+    ARCHIVE_MODE.load(Ordering::Acquire)
+}
+
+/// Archive mode requested via the environment
+pub fn enabled_by_env() -> bool {
+    std::env::var("LOS_ARCHIVE").unwrap_or_default() == "1"
+}
+
+/// Archived contract state
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContractSnapshot {
+    pub balance: u128,
+    pub state: BTreeMap<String, String>,
+}
+
+/// Checkpoint height a block count belongs to
+pub fn snapshot_height(block_count: u64) -> u64 {
+    block_count / CHECKPOINT_INTERVAL * CHECKPOINT_INTERVAL
+}
+
+/// Changed accounts and contracts of one snapshot
+pub type SnapshotChanges = (Vec<(String, AccountState)>, Vec<(String, ContractSnapshot)>);
+
+/// Takes snapshots and remembers what the previous one contained
+pub struct Archiver {
+    last_height: u64,
+    account_heads: HashMap<String, (String, u128)>,
+    contract_roots: HashMap<String, blake3::Hash>,
+}
+
+impl Archiver {
+    /// `last_height`: newest snapshot already in the database (0 = none)
+    pub fn new(last_height: u64) -> Self {
+        Self {
+            last_height,
+            account_heads: HashMap::new(),
+            contract_roots: HashMap::new(),
+        }
+    }
+
+    /// Accounts and contracts that changed since the previous call
+    pub fn changes(
+        &mut self,
+        ledger: &Ledger,
+        contracts: Vec<(String, ContractSnapshot)>,
+    ) -> SnapshotChanges {
+        let mut accounts = Vec::new();
+        for (addr, state) in &ledger.accounts {
+            let key = (state.head.clone(), state.balance);
+            if self.account_heads.get(addr) != Some(&key) {
+                self.account_heads.insert(addr.clone(), key);
+                accounts.push((addr.clone(), state.clone()));
+            }
+        }
+        let mut changed = Vec::new();
+        for (addr, snap) in contracts {
+            let root = blake3::hash(&serde_json::to_vec(&snap).unwrap_or_default());
+            if self.contract_roots.get(&addr) != Some(&root) {
+                self.contract_roots.insert(addr.clone(), root);
+                changed.push((addr, snap));
+            }
+        }
+        (accounts, changed)
+    }
+
+    /// Snapshot if `block_count` reached a checkpoint height not archived yet.
+    /// Returns the new snapshot height.
+    pub fn maybe_snapshot(
+        &mut self,
+        block_count: u64,
+        ledger: &Ledger,
+        engine: &WasmEngine,
+        db: &LosDatabase,
+    ) -> Result<Option<u64>, String> {
+        let height = snapshot_height(block_count);
+        if height == 0 || height <= self.last_height {
+            return Ok(None);
+        }
+        let mut contracts = Vec::new();
+        for addr in engine.list_contracts()? {
+            let contract = engine.get_contract(&addr)?;
+            contracts.push((
+                addr,
+                ContractSnapshot {
+                    balance: contract.balance,
+                    state: contract.state,
+                },
+            ));
+        }
+        let (accounts, contracts) = self.changes(ledger, contracts);
+        db.save_archive_snapshot(height, &accounts, &contracts)?;
+        self.last_height = height;
+        Ok(Some(height))
+    }
+}
+
+/// Response for a historical query: `render(value)` plus `snapshot_height`,
+/// or an error when archive mode is off or no snapshot is old enough
+pub fn historical_json<T>(
+    lookup: Result<Option<(u64, Option<T>)>, String>,
+    at: u64,
+    render: impl FnOnce(Option<T>) -> serde_json::Value,
+) -> serde_json::Value {
+    if !is_enabled() {
+        return serde_json::json!({
+            "status": "error", "code": 400,
+            "msg": "Historical queries need an archive node (start with --archive)"
+        });
+    }
+    match lookup {
+        Ok(Some((snapshot, value))) => {
+            let mut json = render(value);
+            json["snapshot_height"] = serde_json::json!(snapshot);
+            json
+        }
+        Ok(None) => serde_json::json!({
+            "status": "error", "code": 404,
+            "msg": format!("No archive snapshot at or before height {}", at)
+        }),
+        Err(e) => serde_json::json!({"status": "error", "code": 500, "msg": e}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(head: &str, balance: u128) -> AccountState {
+        AccountState {
+            head: head.to_string(),
+            balance,
+            block_count: 1,
+            is_validator: false,
+        }
+    }
+
+    #[test]
+    fn test_archive_diffs_and_historical_lookup() {
+        let path = "test_db_archive";
+        std::fs::remove_dir_all(path).ok();
+        let db = LosDatabase::open(path).unwrap();
+        let engine = WasmEngine::new();
+        let mut ledger = Ledger::new();
+        ledger
+            .accounts
+            .insert("LOSa".to_string(), account("h1", 100));
+        ledger.accounts.insert("LOSb".to_string(), account("h1", 5));
+
+        let mut archiver = Archiver::new(db.latest_archive_height().unwrap().unwrap_or(0));
+        assert_eq!(
+            archiver.maybe_snapshot(999, &ledger, &engine, &db),
+            Ok(None)
+        );
+        assert_eq!(
+            archiver.maybe_snapshot(1_200, &ledger, &engine, &db),
+            Ok(Some(1_000))
+        );
+        assert_eq!(
+            archiver.maybe_snapshot(1_999, &ledger, &engine, &db),
+            Ok(None)
+        );
+
+        // A fresh archiver writes everything, later calls only what changed
+        ledger
+            .accounts
+            .insert("LOSa".to_string(), account("h2", 40));
+        let mut probe = Archiver::new(0);
+        assert_eq!(probe.changes(&ledger, Vec::new()).0.len(), 2);
+        ledger
+            .accounts
+            .insert("LOSa".to_string(), account("h3", 40));
+        assert_eq!(probe.changes(&ledger, Vec::new()).0.len(), 1);
+        assert_eq!(
+            archiver.maybe_snapshot(2_000, &ledger, &engine, &db),
+            Ok(Some(2_000))
+        );
+
+        let bal = |addr: &str, at: u64| {
+            db.archive_account_at(addr, at)
+                .unwrap()
+                .map(|(h, s)| (h, s.map(|s| s.balance)))
+        };
+        assert_eq!(bal("LOSa", 500), None); // before the first snapshot
+        assert_eq!(bal("LOSa", 1_500), Some((1_000, Some(100))));
+        assert_eq!(bal("LOSa", 2_000), Some((2_000, Some(40))));
+        assert_eq!(bal("LOSb", 9_999), Some((2_000, Some(5))));
+        assert_eq!(bal("LOSnew", 1_000), Some((1_000, None)));
+        assert_eq!(db.latest_archive_height().unwrap(), Some(2_000));
+        assert!(db
+            .archive_contract_at("LOSConX", 2_000)
+            .unwrap()
+            .unwrap()
+            .1
+            .is_none());
+
+        std::fs::remove_dir_all(path).ok();
+    }
+}
@@ -0,0 +1,210 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - REST REQUEST BODY LIMITS
+//
+// Every POST route that parses a JSON body declares a BodyLimits:
+//
+//   max_bytes   enforced by warp (content_length_limit) before the body is
+//               read: 413 when larger, 411 without a Content-Length
+//   max_depth   object/array nesting, checked on the raw bytes before serde
+//               builds anything
+//   max_fields  object members + array elements in the whole document
+//
+// A deploy carries up to 1 MB of WASM as base64, so it gets a far larger
+// byte budget than a send; a send has a dozen flat fields. The depth and
+// field scan is a single pass that skips string contents, so a hostile
+// body costs O(len) before it is rejected. Errors are api_json bodies:
+// {"status": "error", "code": 413 | 400, "msg": ...}.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::de::DeserializeOwned;
+use warp::Filter;
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * KIB;
+
+/// Size and shape caps of one route's JSON body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    pub max_bytes: u64,
+    pub max_depth: usize,
+    pub max_fields: usize,
+}
+
+/// POST /send, /register-validator, /unregister-validator: flat bodies with
+/// one Dilithium5 key + signature (~14 KB hex)
+pub const SEND: BodyLimits = BodyLimits {
+    max_bytes: 64 * KIB,
+    max_depth: 4,
+    max_fields: 64,
+};
+
+/// POST /call-contract, /dry-run-contract: SEND plus an args array
+pub const CALL: BodyLimits = BodyLimits {
+    max_bytes: 256 * KIB,
+    max_depth: 4,
+    max_fields: 1_024,
+};
+
+/// POST /deploy-contract: base64 of a 1 MB module plus initial_state
+pub const DEPLOY: BodyLimits = BodyLimits {
+    max_bytes: 2 * MIB,
+    max_depth: 4,
+    max_fields: 4_096,
+};
+
+/// POST /send-batch: up to MAX_BATCH_SIZE signed blocks
+pub const SEND_BATCH: BodyLimits = BodyLimits {
+    max_bytes: 16 * MIB,
+    max_depth: 6,
+    max_fields: 16 * crate::send_batch::MAX_BATCH_SIZE + 16,
+};
+
+/// Body of a route with `limits`: rejected by warp above max_bytes
+pub fn body(
+    limits: BodyLimits,
+) -> impl Filter<Extract = (bytes::Bytes,), Error = warp::Rejection> + Clone {
+    warp::body::content_length_limit(limits.max_bytes).and(warp::body::bytes())
+}
+
+/// Parse a JSON body after the depth/field scan. Err is the api_json body.
+pub fn parse_json<T: DeserializeOwned>(
+    body: &[u8],
+    limits: BodyLimits,
+) -> Result<T, serde_json::Value> {
+    if body.len() as u64 > limits.max_bytes {
+        return Err(too_large(limits.max_bytes));
+    }
+    check_shape(body, limits)
+        .map_err(|msg| serde_json::json!({"status": "error", "code": 400, "msg": msg}))?;
+    serde_json::from_slice(body).map_err(|e| {
+        serde_json::json!({
+            "status": "error",
+            "code": 400,
+            "msg": format!("Invalid request body: {}", e)
+        })
+    })
+}
+
+/// 413 body for a request above `max_bytes`
+pub fn too_large(max_bytes: u64) -> serde_json::Value {
+    serde_json::json!({
+        "status": "error",
+        "code": 413,
+        "msg": format!("Request body too large (max {} bytes)", max_bytes)
+    })
+}
+
+/// Nesting depth and member/element count of a JSON document, without
+/// parsing it. Malformed JSON is left to serde.
+fn check_shape(body: &[u8], limits: BodyLimits) -> Result<(), String> {
+    let mut depth = 0usize;
+    let mut fields = 0usize;
+    // Per open container: has it seen a value yet (counts its first element)
+    let mut non_empty: Vec<bool> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for &b in body {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if !b.is_ascii_whitespace() && !matches!(b, b']' | b'}') {
+            // First value or key inside a container
+            if let Some(seen) = non_empty.last_mut() {
+                if !*seen {
+                    *seen = true;
+                    fields += 1;
+                }
+            }
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > limits.max_depth {
+                    return Err(format!(
+                        "Request body nested too deeply (max depth {})",
+                        limits.max_depth
+                    ));
+                }
+                non_empty.push(false);
+            }
+            b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                non_empty.pop();
+            }
+            b',' => fields += 1,
+            _ => {}
+        }
+        if fields > limits.max_fields {
+            return Err(format!(
+                "Request body has too many fields (max {})",
+                limits.max_fields
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIGHT: BodyLimits = BodyLimits {
+        max_bytes: 1_024,
+        max_depth: 3,
+        max_fields: 6,
+    };
+
+    #[test]
+    fn test_counts_fields_and_depth() {
+        assert!(check_shape(br#"{"a": 1, "b": [1, 2], "c": {"d": "x,{["}}"#, TIGHT).is_ok());
+        // a, b, c, d + 2 array elements = 6; one more is too many
+        assert!(
+            check_shape(br#"{"a": 1, "b": [1, 2, 3], "c": {"d": 1}}"#, TIGHT)
+                .unwrap_err()
+                .contains("too many fields")
+        );
+        assert!(check_shape(br#"{"a": [[[1]]]}"#, TIGHT)
+            .unwrap_err()
+            .contains("nested too deeply"));
+        assert!(check_shape(br#"{"a": [], "b": {}}"#, TIGHT).is_ok());
+        // Brackets and escaped quotes inside strings do not count
+        assert!(check_shape(br#"{"a": "\"[[[[[[,,,,,,,\""}"#, TIGHT).is_ok());
+    }
+
+    #[test]
+    fn test_parse_json_errors() {
+        #[derive(serde::Deserialize)]
+        struct Req {
+            a: u32,
+        }
+        assert_eq!(parse_json::<Req>(br#"{"a": 7}"#, TIGHT).unwrap().a, 7);
+
+        let deep = parse_json::<Req>(&[b'['; 64], TIGHT).err().unwrap();
+        assert_eq!(deep["code"], 400);
+        let bad = parse_json::<Req>(br#"{"a": -1}"#, TIGHT).err().unwrap();
+        assert_eq!(bad["code"], 400);
+        assert!(bad["msg"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid request body"));
+        let big = parse_json::<Req>(&vec![b' '; 2_000], TIGHT).err().unwrap();
+        assert_eq!(big["code"], 413);
+    }
+
+    #[test]
+    fn test_route_limits_fit_their_payloads() {
+        // base64 of the largest module (4/3 of 1 MB) fits the deploy budget
+        assert!(DEPLOY.max_bytes > 1_048_576 * 4 / 3 + 64 * KIB);
+        assert!(DEPLOY.max_bytes > CALL.max_bytes && CALL.max_bytes > SEND.max_bytes);
+        // A client-signed send: 5 KB key + 9 KB signature in hex
+        assert!(SEND.max_bytes > 2 * (2_592 + 4_627) as u64 + 4 * KIB);
+    }
+}
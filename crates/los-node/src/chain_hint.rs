@@ -0,0 +1,232 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - STALE `previous` DIAGNOSTICS
+//
+// A client-signed block whose `previous` is not the account's current tip used
+// to fail with a bare "Chain Error". Wallets could not tell whether to drop the
+// block (already applied) or rebuild it. check_previous() classifies the
+// mismatch so /send, /deploy-contract and /call-contract can answer with a
+// structured 409:
+//
+//   duplicate         — this exact block is already applied or pending
+//   fork              — `previous` is an older block of this account: another
+//                       block already extends it (blocks_behind says how far)
+//   unknown_previous  — `previous` is not on this account's chain at all
+//
+// The expected previous is the ledger head extended by the account's sends
+// still awaiting consensus (same as GET /account/{address}/next).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::{Block, Ledger};
+use serde::Serialize;
+
+/// How far back a fork is traced before it is reported as unknown
+const MAX_WALK: u64 = 10_000;
+
+/// Why a block's `previous` does not extend its account's chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviousConflict {
+    Duplicate,
+    Fork,
+    UnknownPrevious,
+}
+
+/// Structured "stale previous" rejection
+#[derive(Debug, Clone, Serialize)]
+pub struct StalePrevious {
+    pub conflict: PreviousConflict,
+    pub account: String,
+    pub submitted_previous: String,
+    /// Ledger head of the account
+    pub current_head: String,
+    /// What `previous` must be (head + pending sends)
+    pub expected_previous: String,
+    /// Blocks already built on `submitted_previous` (fork only)
+    pub blocks_behind: Option<u64>,
+}
+
+impl StalePrevious {
+    /// What the wallet should do next
+    pub fn hint(&self) -> &'static str {
+        match self.conflict {
+            PreviousConflict::Duplicate => {
+                "Block already accepted; do not resubmit. Poll its status instead."
+            }
+            PreviousConflict::Fork => {
+                "Rebuild the block with previous = expected_previous, re-sign and resubmit."
+            }
+            PreviousConflict::UnknownPrevious => {
+                "previous is not on this account's chain. Fetch GET /account/{address}/next and rebuild."
+            }
+        }
+    }
+
+    /// API error body (HTTP 409)
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "error",
+            "code": 409,
+            "error": "stale_previous",
+            "msg": format!(
+                "Chain Error: Invalid block sequence ({:?}). Expected {}, got {}",
+                self.conflict, self.expected_previous, self.submitted_previous
+            ),
+            "conflict": self.conflict,
+            "account": self.account,
+            "submitted_previous": self.submitted_previous,
+            "current_head": self.current_head,
+            "expected_previous": self.expected_previous,
+            "blocks_behind": self.blocks_behind,
+            "hint": self.hint(),
+        })
+    }
+}
+
+/// Check that `block` (hash `block_hash`) extends its account's chain.
+/// `pending` holds the account's blocks awaiting consensus (empty for
+/// blocks applied straight to the ledger).
+pub fn check_previous(
+    ledger: &Ledger,
+    pending: &[(String, Block)],
+    block: &Block,
+    block_hash: &str,
+) -> Result<(), StalePrevious> {
+    let head = ledger
+        .accounts
+        .get(&block.account)
+        .map(|a| a.head.clone())
+        .unwrap_or_else(|| "0".to_string());
+
+    // Pending chain on top of the head, oldest first
+    let mut chain: Vec<&str> = Vec::new();
+    let mut tip = head.as_str();
+    while chain.len() < pending.len() {
+        match pending.iter().find(|(_, b)| b.previous == tip) {
+            Some((hash, _)) => {
+                chain.push(hash);
+                tip = hash;
+            }
+            None => break,
+        }
+    }
+    if block.previous == tip {
+        return Ok(());
+    }
+
+    let mut stale = StalePrevious {
+        conflict: PreviousConflict::UnknownPrevious,
+        account: block.account.clone(),
+        submitted_previous: block.previous.clone(),
+        current_head: head.clone(),
+        expected_previous: tip.to_string(),
+        blocks_behind: None,
+    };
+    if ledger.blocks.contains_key(block_hash) || chain.contains(&block_hash) {
+        stale.conflict = PreviousConflict::Duplicate;
+        return Err(stale);
+    }
+
+    // Walk back from the tip: pending blocks, then the ledger chain
+    let mut behind = 0u64;
+    for hash in chain.iter().rev() {
+        if *hash == block.previous {
+            stale.conflict = PreviousConflict::Fork;
+            stale.blocks_behind = Some(behind);
+            return Err(stale);
+        }
+        behind += 1;
+    }
+    let mut cur = head.as_str();
+    while behind < MAX_WALK {
+        if cur == block.previous {
+            stale.conflict = PreviousConflict::Fork;
+            stale.blocks_behind = Some(behind);
+            return Err(stale);
+        }
+        match ledger.blocks.get(cur) {
+            Some(b) if b.account == block.account => cur = &b.previous,
+            _ => break,
+        }
+        behind += 1;
+    }
+    Err(stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::{AccountState, BlockType};
+
+    fn block(previous: &str, amount: u128) -> Block {
+        Block {
+            account: "LOSalice".to_string(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount,
+            link: "LOSbob".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_classifies_stale_previous() {
+        // Ledger chain: 0 ← b1 ← b2 (head); pending: b3 on b2
+        let mut ledger = Ledger::new();
+        let b1 = block("0", 1);
+        let h1 = b1.calculate_hash();
+        let b2 = block(&h1, 2);
+        let h2 = b2.calculate_hash();
+        ledger.blocks.insert(h1.clone(), b1);
+        ledger.blocks.insert(h2.clone(), b2.clone());
+        ledger.accounts.insert(
+            "LOSalice".to_string(),
+            AccountState {
+                head: h2.clone(),
+                balance: 100,
+                block_count: 2,
+                is_validator: false,
+            },
+        );
+        let b3 = block(&h2, 3);
+        let h3 = b3.calculate_hash();
+        let pending = vec![(h3.clone(), b3.clone())];
+
+        let next = block(&h3, 4);
+        assert!(check_previous(&ledger, &pending, &next, &next.calculate_hash()).is_ok());
+        // Without pending sends the head is expected
+        let err = check_previous(&ledger, &[], &next, &next.calculate_hash()).unwrap_err();
+        assert_eq!(err.conflict, PreviousConflict::UnknownPrevious);
+        assert_eq!(err.expected_previous, h2);
+
+        let dup = check_previous(&ledger, &[], &b2, &h2).unwrap_err();
+        assert_eq!(dup.conflict, PreviousConflict::Duplicate);
+        assert_eq!(
+            check_previous(&ledger, &pending, &b3, &h3)
+                .unwrap_err()
+                .conflict,
+            PreviousConflict::Duplicate
+        );
+
+        // Built on b1 / genesis while b2 + b3 exist
+        let fork = block(&h1, 9);
+        let err = check_previous(&ledger, &pending, &fork, &fork.calculate_hash()).unwrap_err();
+        assert_eq!(err.conflict, PreviousConflict::Fork);
+        assert_eq!(err.blocks_behind, Some(2));
+        assert_eq!(err.expected_previous, h3);
+        assert_eq!(err.current_head, h2);
+        let genesis = block("0", 9);
+        let err = check_previous(&ledger, &[], &genesis, &genesis.calculate_hash()).unwrap_err();
+        assert_eq!(err.blocks_behind, Some(2));
+
+        let json = err.to_json();
+        assert_eq!(json["code"], 409);
+        assert_eq!(json["conflict"], "fork");
+        assert!(json["hint"].as_str().unwrap().contains("expected_previous"));
+    }
+}
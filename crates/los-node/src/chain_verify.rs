@@ -0,0 +1,117 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - OFFLINE CHAIN VERIFICATION
+//
+// los-node verify --data-dir <DIR> [--mainnet] (<address>... | --all)
+//   Opens the node's database read-only (stop the node first: sled holds an
+//   exclusive lock). Runs Ledger::verify_account_chain on each account and
+//   reports the first inconsistency per account. Those are hash, signature,
+//   PoW, timestamp order, Receive links and balance arithmetic. Exit code 1
+//   if any account fails.
+//
+// Signatures commit to the chain ID, so pass --mainnet for mainnet data. The
+// chain spec is resolved the same way as at node startup.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::db::LosDatabase;
+
+const USAGE: &str = "Usage: los-node verify --data-dir <DIR> [--mainnet] (<address>... | --all)";
+
+/// Entry point for `los-node verify ...`. Returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let mut data_dir: Option<String> = None;
+    let mut mainnet = false;
+    let mut all = false;
+    let mut addresses: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--data-dir" => {
+                data_dir = args.get(i + 1).cloned();
+                i += 1;
+            }
+            "--mainnet" => mainnet = true,
+            "--all" => all = true,
+            other if other.starts_with("--") => {
+                eprintln!("❌ Unknown option {}\n{}", other, USAGE);
+                return 2;
+            }
+            addr => addresses.push(addr.to_string()),
+        }
+        i += 1;
+    }
+    let Some(data_dir) = data_dir else {
+        eprintln!("❌ --data-dir is required\n{}", USAGE);
+        return 2;
+    };
+    if !all && addresses.is_empty() {
+        eprintln!("❌ Give one or more addresses, or --all\n{}", USAGE);
+        return 2;
+    }
+
+    if let Err(e) = crate::genesis::load_chain_spec(mainnet).and_then(los_core::chain_spec::install)
+    {
+        eprintln!("❌ Chain spec: {}", e);
+        return 2;
+    }
+
+    let db_path = format!("{}/los_database", data_dir);
+    let db = match LosDatabase::open(&db_path) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!(
+                "❌ Cannot open {}: {} (is the node still running?)",
+                db_path, e
+            );
+            return 2;
+        }
+    };
+    let ledger = match db.load_ledger() {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("❌ Failed to load ledger: {}", e);
+            return 2;
+        }
+    };
+
+    if all {
+        addresses = ledger.accounts.keys().cloned().collect();
+    }
+    println!(
+        "🔍 Verifying {} account chain(s) in {} (chain_id {})",
+        addresses.len(),
+        db_path,
+        los_core::chain_id()
+    );
+
+    let mut failed = 0usize;
+    let mut blocks = 0u64;
+    for addr in &addresses {
+        match ledger.verify_account_chain(addr) {
+            Ok(report) => {
+                blocks += report.blocks_checked;
+                if !all {
+                    println!(
+                        "✅ {} — {} blocks, opening balance {} CIL, balance {} CIL",
+                        addr, report.blocks_checked, report.opening_balance, report.final_balance
+                    );
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("❌ {} — {}", addr, e);
+            }
+        }
+    }
+    println!(
+        "{} {}/{} accounts consistent, {} blocks checked",
+        if failed == 0 { "✅" } else { "⚠️" },
+        addresses.len() - failed,
+        addresses.len(),
+        blocks
+    );
+    if failed == 0 {
+        0
+    } else {
+        1
+    }
+}
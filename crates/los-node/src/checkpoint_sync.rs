@@ -0,0 +1,437 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - HISTORICAL CHECKPOINTS & CHECKPOINT COLD SYNC
+//
+// GET /checkpoints?from=<height>&limit=<n>
+//   Finalized checkpoints (ascending height) with every validator signature,
+//   so light clients and new nodes can audit finality themselves.
+//
+// los-node --trust-checkpoint <checkpoint_id>
+//   Operators who do not want to replay history from genesis pin a checkpoint
+//   ID (FinalityCheckpoint::calculate_id, obtained out-of-band from a source
+//   they trust). On startup the node:
+//   1. Finds that checkpoint on a peer via GET /checkpoints
+//   2. Downloads the peer's ledger via GET /sync/full, with the VM and reward
+//      pool roots of the same state (X-VM-Root / X-Rewards-Root)
+//   3. Accepts the state only if it contains the checkpoint block, the
+//      checkpoint's signatures reach quorum among that state's staked
+//      validators, and the snapshot's recomputed full state root equals the
+//      checkpoint's state_root. A peer whose state has moved past the
+//      checkpoint cannot serve it: pin the latest checkpoint, the node
+//      retries every 30s.
+//   4. Stores the checkpoint as its finality floor
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::checkpoint::{full_state_root, CheckpointSignature, FinalityCheckpoint};
+use los_core::Ledger;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::validator_public_key;
+
+/// Default page size for GET /checkpoints
+pub const DEFAULT_CHECKPOINT_PAGE: usize = 20;
+/// Max page size for GET /checkpoints (each signature is ~9 KB hex)
+pub const MAX_CHECKPOINT_PAGE: usize = 100;
+/// Max pages scanned on one peer while looking for the trusted checkpoint
+const MAX_PAGES_PER_PEER: usize = 1_000;
+/// GET /sync/full response header: VM state root of the served snapshot
+pub const VM_ROOT_HEADER: &str = "X-VM-Root";
+/// GET /sync/full response header: reward pool root of the served snapshot
+pub const REWARDS_ROOT_HEADER: &str = "X-Rewards-Root";
+
+/// Non-ledger components of a snapshot's full state root, as the serving
+/// peer reported them. Only trusted through a checkpoint's state_root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRoots {
+    pub vm_root: String,
+    pub rewards_root: String,
+}
+
+impl SnapshotRoots {
+    /// Read both roots from a GET /sync/full response (None if either is missing)
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let get = |name: &str| headers.get(name)?.to_str().ok().map(str::to_string);
+        Some(Self {
+            vm_root: get(VM_ROOT_HEADER)?,
+            rewards_root: get(REWARDS_ROOT_HEADER)?,
+        })
+    }
+}
+
+/// Parse `from` / `limit` query parameters (limit clamped to MAX_CHECKPOINT_PAGE)
+pub fn parse_page(params: &HashMap<String, String>) -> (u64, usize) {
+    let from = params.get("from").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CHECKPOINT_PAGE)
+        .clamp(1, MAX_CHECKPOINT_PAGE);
+    (from, limit)
+}
+
+/// Validator signature as returned by the API (hex-encoded)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignatureView {
+    pub validator_address: String,
+    pub signature: String,
+}
+
+/// JSON view of a finalized checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointView {
+    pub id: String,
+    pub height: u64,
+    pub block_hash: String,
+    pub state_root: String,
+    pub timestamp: u64,
+    pub validator_count: u32,
+    pub signature_count: u32,
+    pub signatures: Vec<SignatureView>,
+}
+
+impl CheckpointView {
+    pub fn from_checkpoint(cp: &FinalityCheckpoint) -> Self {
+        Self {
+            id: cp.calculate_id(),
+            height: cp.height,
+            block_hash: cp.block_hash.clone(),
+            state_root: cp.state_root.clone(),
+            timestamp: cp.timestamp,
+            validator_count: cp.validator_count,
+            signature_count: cp.signature_count,
+            signatures: cp
+                .signatures
+                .iter()
+                .map(|s| SignatureView {
+                    validator_address: s.validator_address.clone(),
+                    signature: hex::encode(&s.signature),
+                })
+                .collect(),
+        }
+    }
+
+    /// Rebuild the checkpoint. Fails on malformed signature hex.
+    pub fn into_checkpoint(self) -> Result<FinalityCheckpoint, String> {
+        let signatures = self
+            .signatures
+            .into_iter()
+            .map(|s| {
+                hex::decode(&s.signature)
+                    .map(|signature| CheckpointSignature {
+                        validator_address: s.validator_address,
+                        signature,
+                    })
+                    .map_err(|e| format!("Invalid checkpoint signature hex: {}", e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut cp = FinalityCheckpoint::new(
+            self.height,
+            self.block_hash,
+            self.validator_count,
+            self.state_root,
+            signatures,
+        );
+        cp.timestamp = self.timestamp;
+        Ok(cp)
+    }
+}
+
+/// Response body of GET /checkpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointPage {
+    pub checkpoints: Vec<CheckpointView>,
+    /// `from` value for the next page (None when this was the last page)
+    pub next_from: Option<u64>,
+}
+
+impl CheckpointPage {
+    pub fn new(checkpoints: &[FinalityCheckpoint], limit: usize) -> Self {
+        let next_from = if checkpoints.len() == limit {
+            checkpoints.last().map(|cp| cp.height + 1)
+        } else {
+            None
+        };
+        Self {
+            checkpoints: checkpoints
+                .iter()
+                .map(CheckpointView::from_checkpoint)
+                .collect(),
+            next_from,
+        }
+    }
+}
+
+/// Trimmed, lowercase checkpoint ID (ids are compared as lowercase hex);
+/// None when blank
+pub fn normalize_checkpoint_id(raw: &str) -> Option<String> {
+    let id = raw.trim().to_lowercase();
+    (!id.is_empty()).then_some(id)
+}
+
+/// Checkpoint pinned with LOS_TRUST_CHECKPOINT (--trust-checkpoint overrides it)
+pub fn trusted_checkpoint_from_env() -> Option<String> {
+    std::env::var("LOS_TRUST_CHECKPOINT")
+        .ok()
+        .as_deref()
+        .and_then(normalize_checkpoint_id)
+}
+
+/// Page through a peer's GET /checkpoints until `trusted_id` is found.
+pub async fn fetch_trusted_checkpoint(
+    client: &reqwest::Client,
+    peer_host: &str,
+    trusted_id: &str,
+) -> Result<FinalityCheckpoint, String> {
+    let mut from = 0u64;
+    for _ in 0..MAX_PAGES_PER_PEER {
+        let url = format!(
+            "http://{}/checkpoints?from={}&limit={}",
+            peer_host, from, MAX_CHECKPOINT_PAGE
+        );
+        let page: CheckpointPage = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid /checkpoints response: {}", e))?;
+        if let Some(view) = page.checkpoints.into_iter().find(|v| v.id == trusted_id) {
+            let cp = view.into_checkpoint()?;
+            // Never trust the peer-reported id — recompute it
+            if cp.calculate_id() != trusted_id {
+                return Err("Peer returned a checkpoint whose id does not match".to_string());
+            }
+            return Ok(cp);
+        }
+        match page.next_from {
+            Some(next) if next > from => from = next,
+            _ => break,
+        }
+    }
+    Err(format!(
+        "Checkpoint {} not found on peer",
+        &trusted_id[..trusted_id.len().min(16)]
+    ))
+}
+
+/// Check a downloaded ledger against the trusted checkpoint.
+///
+/// On top of `verify_branch`, the snapshot must be the checkpointed state
+/// itself: its accounts root, combined with the peer's VM and reward pool
+/// roots, must hash to `cp.state_root`. The signed root binds all three, so
+/// a peer cannot alter the ledger and report other component roots.
+pub fn verify_anchor(
+    ledger: &Ledger,
+    cp: &FinalityCheckpoint,
+    roots: Option<&SnapshotRoots>,
+) -> Result<(), String> {
+    verify_branch(ledger, cp)?;
+    let roots = roots.ok_or("Peer did not report the VM and reward pool roots of its state")?;
+    let root = full_state_root(
+        &ledger.compute_state_root(),
+        &roots.vm_root,
+        &roots.rewards_root,
+    );
+    if root != cp.state_root {
+        return Err(format!(
+            "Synced state root {} does not match checkpoint state root {} (peer state has moved past the checkpoint?)",
+            &root[..root.len().min(16)],
+            &cp.state_root[..cp.state_root.len().min(16)]
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a downloaded ledger is on the branch of a checkpoint.
+///
+/// The checkpoint block must be part of the ledger, and the checkpoint
+/// signatures must reach quorum among the staked validators of that ledger
+/// (Ledger::reward_validator_set), verified with their keys from it. A peer
+/// cannot satisfy both without the real validators having signed.
+pub fn verify_branch(ledger: &Ledger, cp: &FinalityCheckpoint) -> Result<(), String> {
+    if !cp.is_valid_interval() {
+        return Err(format!(
+            "Checkpoint height {} is not interval-aligned",
+            cp.height
+        ));
+    }
+    if !ledger.blocks.contains_key(&cp.block_hash) {
+        return Err(format!(
+            "Synced state does not contain checkpoint block {}",
+            &cp.block_hash[..cp.block_hash.len().min(16)]
+        ));
+    }
+    // A deflated validator_count would lower the quorum threshold
+    let validators = ledger.reward_validator_set();
+    if (cp.validator_count as usize) < validators.len().max(1) {
+        return Err(format!(
+            "Checkpoint validator_count {} is below the {} staked validators",
+            cp.validator_count,
+            validators.len()
+        ));
+    }
+    let valid = cp.verify_signatures(
+        &|addr: &str| validator_public_key(ledger, addr),
+        &los_crypto::verify_signature,
+    );
+    if valid as usize != cp.signatures.len()
+        || !cp.verify_quorum()
+        || !cp.has_validator_quorum(&validators)
+    {
+        return Err(format!(
+            "Checkpoint signatures do not reach quorum ({}/{} valid, {} staked validators)",
+            valid,
+            cp.signatures.len(),
+            validators.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(height: u64) -> FinalityCheckpoint {
+        FinalityCheckpoint::new(
+            height,
+            "ab".repeat(32),
+            3,
+            "cd".repeat(32),
+            vec![CheckpointSignature {
+                validator_address: "LOSv1".to_string(),
+                signature: vec![0xde, 0xad, 0xbe, 0xef],
+            }],
+        )
+    }
+
+    #[test]
+    fn test_parse_page_defaults_and_clamp() {
+        let mut params = HashMap::new();
+        assert_eq!(parse_page(&params), (0, DEFAULT_CHECKPOINT_PAGE));
+        params.insert("from".to_string(), "5000".to_string());
+        params.insert("limit".to_string(), "100000".to_string());
+        assert_eq!(parse_page(&params), (5000, MAX_CHECKPOINT_PAGE));
+        params.insert("limit".to_string(), "0".to_string());
+        assert_eq!(parse_page(&params).1, 1);
+    }
+
+    #[test]
+    fn test_view_roundtrip_preserves_id() {
+        let cp = sample(2000);
+        let view = CheckpointView::from_checkpoint(&cp);
+        assert_eq!(view.id, cp.calculate_id());
+        let json = serde_json::to_string(&view).unwrap();
+        let back: CheckpointView = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.into_checkpoint().unwrap(), cp);
+    }
+
+    #[test]
+    fn test_page_next_from() {
+        let full = vec![sample(1000), sample(2000)];
+        assert_eq!(CheckpointPage::new(&full, 2).next_from, Some(2001));
+        assert_eq!(CheckpointPage::new(&full, 5).next_from, None);
+    }
+
+    #[test]
+    fn test_trusted_checkpoint_from_env_is_normalized() {
+        // SAFETY: no other test reads LOS_TRUST_CHECKPOINT
+        unsafe {
+            std::env::set_var("LOS_TRUST_CHECKPOINT", "  AbCdEf01\n");
+        }
+        assert_eq!(trusted_checkpoint_from_env().as_deref(), Some("abcdef01"));
+        unsafe {
+            std::env::set_var("LOS_TRUST_CHECKPOINT", "   ");
+        }
+        assert_eq!(trusted_checkpoint_from_env(), None);
+        unsafe {
+            std::env::remove_var("LOS_TRUST_CHECKPOINT");
+        }
+        assert_eq!(trusted_checkpoint_from_env(), None);
+        // The CLI value goes through the same normalization
+        assert_eq!(
+            normalize_checkpoint_id("ABCDEF01").as_deref(),
+            Some("abcdef01")
+        );
+    }
+
+    #[test]
+    fn test_verify_anchor_requires_checkpoint_block() {
+        let ledger = Ledger::new();
+        let err = verify_anchor(&ledger, &sample(2000), None).unwrap_err();
+        assert!(err.contains("does not contain"));
+        assert!(verify_anchor(&ledger, &sample(1500), None).is_err());
+    }
+
+    #[test]
+    fn test_verify_anchor_needs_staked_quorum_and_state_root() {
+        let key = los_crypto::generate_keypair_from_seed(&[3; 64]);
+        let validator = los_crypto::public_key_to_address(&key.public_key);
+        let head = los_core::Block {
+            account: validator.clone(),
+            previous: "0".to_string(),
+            block_type: los_core::BlockType::Mint,
+            amount: los_core::MIN_VALIDATOR_STAKE_CIL,
+            link: "GENESIS".to_string(),
+            signature: String::new(),
+            public_key: hex::encode(&key.public_key),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let head_hash = head.calculate_hash();
+        let mut ledger = Ledger::new();
+        ledger.blocks.insert(head_hash.clone(), head);
+        ledger.accounts.insert(
+            validator.clone(),
+            los_core::AccountState {
+                head: head_hash.clone(),
+                balance: los_core::MIN_VALIDATOR_STAKE_CIL,
+                block_count: 1,
+                is_validator: true,
+            },
+        );
+        let roots = SnapshotRoots {
+            vm_root: "vm".to_string(),
+            rewards_root: "rewards".to_string(),
+        };
+        let signed = |ledger: &Ledger| {
+            let root = full_state_root(&ledger.compute_state_root(), "vm", "rewards");
+            let mut cp = FinalityCheckpoint::new(2000, head_hash.clone(), 1, root, vec![]);
+            cp.signatures.push(CheckpointSignature {
+                validator_address: validator.clone(),
+                signature: los_crypto::sign_message(&cp.signing_data(), &key.secret_key).unwrap(),
+            });
+            cp
+        };
+        let cp = signed(&ledger);
+        assert_eq!(verify_anchor(&ledger, &cp, Some(&roots)), Ok(()));
+        assert!(verify_anchor(&ledger, &cp, None).is_err());
+
+        // Component roots other than the checkpointed ones
+        let other = SnapshotRoots {
+            vm_root: "vm2".to_string(),
+            ..roots.clone()
+        };
+        assert!(verify_anchor(&ledger, &cp, Some(&other))
+            .unwrap_err()
+            .contains("does not match"));
+
+        // State that moved past the checkpoint is still on its branch
+        let mut later = ledger.clone();
+        later.accounts.get_mut(&validator).unwrap().balance += 1;
+        assert_eq!(verify_branch(&later, &cp), Ok(()));
+        assert!(verify_anchor(&later, &cp, Some(&roots)).is_err());
+
+        // An unstaked signer does not count
+        let mut unstaked = ledger.clone();
+        unstaked.accounts.get_mut(&validator).unwrap().is_validator = false;
+        let cp = signed(&unstaked);
+        assert!(verify_branch(&unstaked, &cp)
+            .unwrap_err()
+            .contains("quorum"));
+    }
+}
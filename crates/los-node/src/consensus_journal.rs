@@ -0,0 +1,257 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - IN-FLIGHT CONSENSUS JOURNAL
+//
+// pending_sends (block + accumulated power) and send_voters (who already
+// voted) live in memory. Without a journal a restart mid-consensus drops
+// every partial tally, and validators that already voted do not vote again,
+// so the send stalls until it times out.
+//
+// Every pending Send and every counted vote is also written to sled tree
+// "inflight_sends":
+//   tx_hash ‖ 0x00           → Block JSON
+//   tx_hash ‖ 0x01 ‖ voter   → VoteRecord JSON (power + the signed message)
+// Entries are removed on finalization and on PENDING_TTL_SECS expiry. At
+// startup the journal is replayed: every stored vote signature is verified
+// again, so a tampered database cannot fake a quorum. The peer handshake
+// resends CONFIRM_REQ for restored sends; repeat votes from validators that
+// already voted are dropped by send_voters.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::vote_batch::VoteEnvelope;
+use los_core::Block;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Pending sends older than this are dropped (memory and journal)
+pub const PENDING_TTL_SECS: u64 = 300;
+
+/// Signed message that carried a vote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VoteProof {
+    /// CONFIRM_RES: signature over "{tx_hash}:{requester}:YES:{voter}:{timestamp}"
+    Single {
+        requester: String,
+        timestamp: String,
+        signature: String,
+        public_key: String,
+    },
+    /// VOTE_BATCH envelope (one signature covers all its votes)
+    Batch(VoteEnvelope),
+}
+
+impl VoteProof {
+    /// Whether this message is a valid YES vote by `voter` for `tx_hash`.
+    /// Freshness is not checked: the vote was fresh when it was counted.
+    pub fn verify<V, D>(&self, tx_hash: &str, voter: &str, verify: V, derive_address: D) -> bool
+    where
+        V: Fn(&[u8], &[u8], &[u8]) -> bool,
+        D: Fn(&[u8]) -> String,
+    {
+        match self {
+            VoteProof::Single {
+                requester,
+                timestamp,
+                signature,
+                public_key,
+            } => {
+                let (Ok(sig), Ok(pk)) = (hex::decode(signature), hex::decode(public_key)) else {
+                    return false;
+                };
+                let payload = format!("{}:{}:YES:{}:{}", tx_hash, requester, voter, timestamp);
+                derive_address(&pk) == voter && verify(payload.as_bytes(), &sig, &pk)
+            }
+            VoteProof::Batch(env) => {
+                env.voter == voter
+                    && env.votes.iter().any(|v| v.tx_hash == tx_hash)
+                    && env.verify(env.timestamp, verify, derive_address).is_ok()
+            }
+        }
+    }
+}
+
+/// One counted vote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteRecord {
+    pub voter: String,
+    /// Power added to the tally (same units as SEND_CONSENSUS_THRESHOLD)
+    pub power: u128,
+    pub proof: VoteProof,
+}
+
+/// A journaled pending send with its votes
+#[derive(Debug, Clone)]
+pub struct InFlightSend {
+    pub tx_hash: String,
+    pub block: Block,
+    pub votes: Vec<VoteRecord>,
+}
+
+/// pending_sends + send_voters entries rebuilt from the journal
+#[derive(Debug, Default)]
+pub struct Restored {
+    /// tx_hash → (block, accumulated power)
+    pub pending: Vec<(String, Block, u128)>,
+    /// tx_hash → voters
+    pub voters: Vec<(String, HashSet<String>)>,
+    /// Expired or already finalized entries to delete from the journal
+    pub stale: Vec<String>,
+    /// Votes whose signature did not verify (not counted)
+    pub rejected_votes: usize,
+}
+
+/// Rebuild in-flight state from journal entries. `is_final(tx_hash)` reports
+/// sends already in the ledger (e.g. finalized by sync while we were down).
+pub fn restore<V, D, F>(
+    entries: Vec<InFlightSend>,
+    now: u64,
+    is_final: F,
+    verify: V,
+    derive_address: D,
+) -> Restored
+where
+    V: Fn(&[u8], &[u8], &[u8]) -> bool + Copy,
+    D: Fn(&[u8]) -> String + Copy,
+    F: Fn(&str) -> bool,
+{
+    let mut out = Restored::default();
+    for entry in entries {
+        if now.saturating_sub(entry.block.valid_from()) >= PENDING_TTL_SECS
+            || is_final(&entry.tx_hash)
+        {
+            out.stale.push(entry.tx_hash);
+            continue;
+        }
+        let mut power: u128 = 0;
+        let mut voters = HashSet::new();
+        for vote in &entry.votes {
+            if !vote
+                .proof
+                .verify(&entry.tx_hash, &vote.voter, verify, derive_address)
+            {
+                out.rejected_votes += 1;
+                continue;
+            }
+            if voters.insert(vote.voter.clone()) {
+                power = power.saturating_add(vote.power);
+            }
+        }
+        if !voters.is_empty() {
+            out.voters.push((entry.tx_hash.clone(), voters));
+        }
+        out.pending.push((entry.tx_hash, entry.block, power));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_consensus::vote_batch::BatchedVote;
+    use los_core::BlockType;
+
+    // Toy scheme: signature = payload bytes, address = "LOS" + hex(pk)
+    fn verify(msg: &[u8], sig: &[u8], _pk: &[u8]) -> bool {
+        msg == sig
+    }
+    fn derive(pk: &[u8]) -> String {
+        format!("LOS{}", hex::encode(pk))
+    }
+
+    fn send_block(timestamp: u64) -> Block {
+        Block {
+            account: "LOSsender".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Send,
+            amount: 5,
+            link: "LOSrecipient".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    fn single_vote(tx_hash: &str, pk: &[u8], power: u128) -> VoteRecord {
+        let voter = derive(pk);
+        let payload = format!("{}:LOSsender:YES:{}:1000", tx_hash, voter);
+        VoteRecord {
+            voter,
+            power,
+            proof: VoteProof::Single {
+                requester: "LOSsender".to_string(),
+                timestamp: "1000".to_string(),
+                signature: hex::encode(payload.as_bytes()),
+                public_key: hex::encode(pk),
+            },
+        }
+    }
+
+    #[test]
+    fn test_restore_counts_verified_votes_once() {
+        let tx = "aa".repeat(32);
+        let envelope = VoteEnvelope::seal(
+            &derive(b"v2"),
+            1_000,
+            vec![BatchedVote {
+                tx_hash: tx.clone(),
+                requester: "LOSsender".to_string(),
+            }],
+            b"v2",
+            |payload| Ok(payload.to_vec()),
+        )
+        .unwrap();
+        let mut forged = single_vote(&tx, b"v3", 7_000);
+        forged.voter = derive(b"v4");
+        let entries = vec![InFlightSend {
+            tx_hash: tx.clone(),
+            block: send_block(1_000),
+            votes: vec![
+                single_vote(&tx, b"v1", 1_000),
+                single_vote(&tx, b"v1", 1_000),
+                VoteRecord {
+                    voter: derive(b"v2"),
+                    power: 2_000,
+                    proof: VoteProof::Batch(envelope),
+                },
+                forged,
+            ],
+        }];
+
+        // Envelope long past MAX_ENVELOPE_AGE_MS still verifies on restore
+        let restored = restore(entries, 1_100, |_| false, verify, derive);
+        assert_eq!(restored.pending.len(), 1);
+        assert_eq!(restored.pending[0].2, 3_000);
+        assert_eq!(restored.voters[0].1.len(), 2);
+        assert_eq!(restored.rejected_votes, 1);
+        assert!(restored.stale.is_empty());
+    }
+
+    #[test]
+    fn test_restore_drops_expired_and_final_sends() {
+        let entry = |tx: &str, ts: u64| InFlightSend {
+            tx_hash: tx.to_string(),
+            block: send_block(ts),
+            votes: Vec::new(),
+        };
+        let entries = vec![
+            entry("expired", 1_000),
+            entry("final", 1_200),
+            entry("live", 1_200),
+        ];
+        let restored = restore(
+            entries,
+            1_000 + PENDING_TTL_SECS,
+            |tx| tx == "final",
+            verify,
+            derive,
+        );
+        assert_eq!(restored.stale, vec!["expired", "final"]);
+        assert_eq!(restored.pending.len(), 1);
+        assert_eq!(restored.pending[0].0, "live");
+        assert!(restored.voters.is_empty());
+    }
+}
@@ -0,0 +1,332 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ATOMIC CONTRACTCALL APPLICATION
+//
+// A ContractCall block has two halves: the ledger debit (fee + value) and the
+// WASM execution in WasmEngine. Applying them separately let one succeed while
+// the other failed. apply_contract_block() is the single path used by
+// POST /call-contract and CONTRACT_CALLED replication:
+//
+//   1. Parse the CALL: link, cheap ledger pre-checks (sequence, balance)
+//   2. Execution with the block amount attached (msg_value), staged in the VM
+//      journal under the block hash
+//   3. Ledger::process_block (PoW, signature, debit, fees)
+//   4. Credit host_transfer payouts as system Receive blocks
+//      (Ledger::apply_contract_transfers), then commit the journal
+//
+// Any failure rolls the VM back and leaves the ledger untouched, so a block is
+// either fully applied or not at all. A call that fails in the VM is rejected
+// (no fee is charged). A call that returns a non-zero SDK status is applied
+// like any other: its fee is paid and result.success is false; the VM returns
+// the attached value as a payout to the caller.
+//
+// The gas limit is derived from the block (fee / GAS_PRICE_CIL), so every node
+// executes with the same limit; it is checked against the consensus gas caps
+// (los_core::contract_gas) before execution. The caller holds the ledger lock for the whole
+// call; execution is bounded by the VM's wall-clock limit.
+//
+// apply_deploy_block() is the matching path for ContractDeploy blocks
+// (POST /deploy-contract and CONTRACT_DEPLOYED replication): ledger debit,
+// then the VM deploy at the block timestamp — never the local clock, or
+// every node would derive a different unsalted contract address.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+use base64::Engine as _;
+use los_core::contract_gas::call_gas_limit;
+use los_core::deploy_link::DeployLink;
+use los_core::{Block, BlockType, Ledger};
+use los_vm::{ContractCall, ContractResult, WasmEngine};
+use std::collections::BTreeMap;
+
+/// Contract call encoded in a ContractCall block's link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallLink {
+    pub contract: String,
+    pub function: String,
+    pub args: Vec<String>,
+}
+
+impl CallLink {
+    /// Parse "CALL:{contract}:{function}:{base64(json args)}" (args optional)
+    pub fn parse(link: &str) -> Result<Self, String> {
+        let data = link
+            .strip_prefix("CALL:")
+            .ok_or("ContractCall link must start with 'CALL:'")?;
+        let parts: Vec<&str> = data.splitn(3, ':').collect();
+        if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
+            return Err("ContractCall link must contain contract address and function".to_string());
+        }
+        let args = match parts.get(2) {
+            Some(b64) => base64::engine::general_purpose::STANDARD
+                .decode(b64)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                .ok_or("ContractCall link args are not base64 JSON")?,
+            None => Vec::new(),
+        };
+        Ok(CallLink {
+            contract: parts[0].to_string(),
+            function: parts[1].to_string(),
+            args,
+        })
+    }
+}
+
+/// Outcome of a fully applied ContractCall block
+#[derive(Debug, Clone)]
+pub struct ContractBlockOutcome {
+    pub block_hash: String,
+    pub call: CallLink,
+    pub result: ContractResult,
+}
+
+/// Execute, debit, credit transfers and commit VM state for one ContractCall
+/// block, or change nothing (see module header).
+pub fn apply_contract_block(
+    ledger: &mut Ledger,
+    engine: &WasmEngine,
+    block: &Block,
+) -> Result<ContractBlockOutcome, String> {
+    if block.block_type != BlockType::ContractCall {
+        return Err("Not a ContractCall block".to_string());
+    }
+    let call = CallLink::parse(&block.link)?;
+    let block_hash = block.calculate_hash();
+    if ledger.blocks.contains_key(&block_hash) {
+        return Err(format!("Duplicate block {}", block_hash));
+    }
+    // Cheap checks first so invalid blocks never reach the VM
+    let caller = ledger
+        .accounts
+        .get(&block.account)
+        .ok_or("Caller account not found")?;
+    if caller.head != block.previous {
+        return Err(format!(
+            "Chain Error: Invalid block sequence. Expected {}, got {}",
+            caller.head, block.previous
+        ));
+    }
+    if caller.balance < block.amount.saturating_add(block.fee) {
+        return Err("Insufficient Funds: balance < call fee + value transfer".to_string());
+    }
+    ledger.check_contract_gas(block)?;
+    if !engine.contract_exists(&call.contract)? {
+        return Err(format!("Contract {} not found", call.contract));
+    }
+
+    let executed = (|| {
+        let result = engine.call_contract_staged(
+            &block_hash,
+            ContractCall {
+                contract: call.contract.clone(),
+                function: call.function.clone(),
+                args: call.args.clone(),
+                gas_limit: call_gas_limit(block),
+                caller: block.account.clone(),
+                block_timestamp: block.timestamp,
+                amount: block.amount,
+            },
+        )?;
+        // Payouts come out of CIL held by contracts (incl. this call's value)
+        let payout = result
+            .transfers
+            .iter()
+            .fold(0u128, |acc, (_, a)| acc.saturating_add(*a));
+        if payout > ledger.contract_held_cil.saturating_add(block.amount) {
+            return Err(format!(
+                "Contract payout {} CIL exceeds CIL held by contracts",
+                payout
+            ));
+        }
+        ledger.process_block(block)?;
+        Ok::<_, String>(result)
+    })();
+    let mut result = match executed {
+        Ok(r) => r,
+        Err(e) => {
+            let _ = engine.rollback(&block_hash);
+            return Err(e);
+        }
+    };
+    for event in &mut result.events {
+        event.block_hash = block_hash.clone();
+    }
+
+    if !result.transfers.is_empty() {
+        // Cannot fail: call block applied, payout within contract_held_cil
+        if let Err(e) = ledger.apply_contract_transfers(&block_hash, &result.transfers) {
+            eprintln!("⚠️ Contract payout for {} not credited: {}", block_hash, e);
+        }
+    }
+    engine.commit(&block_hash);
+    Ok(ContractBlockOutcome {
+        block_hash,
+        call,
+        result,
+    })
+}
+
+/// Debit the deployer, deploy `bytecode` and fund the contract with the block
+/// amount. Returns the contract address. Bytecode that does not match the
+/// link or a taken salted address is rejected before the ledger changes.
+pub fn apply_deploy_block(
+    ledger: &mut Ledger,
+    engine: &WasmEngine,
+    block: &Block,
+    bytecode: Vec<u8>,
+    initial_state: BTreeMap<String, String>,
+) -> Result<String, String> {
+    if block.block_type != BlockType::ContractDeploy {
+        return Err("Not a ContractDeploy block".to_string());
+    }
+    let link = DeployLink::parse(&block.link)?;
+    let code_hash = WasmEngine::compute_code_hash(&bytecode);
+    if !code_hash.starts_with(link.code_hash) {
+        return Err("Bytecode does not match the code hash in the deploy link".to_string());
+    }
+    if let Some(salt) = &link.salt {
+        let address = WasmEngine::salted_address(&block.account, salt, &code_hash);
+        if engine.contract_exists(&address)? {
+            return Err(format!("Contract address {} is already taken", address));
+        }
+    }
+    let block_hash = block.calculate_hash();
+    if ledger.blocks.contains_key(&block_hash) {
+        return Err(format!("Duplicate block {}", block_hash));
+    }
+    ledger.process_block(block)?;
+
+    let owner = block.account.clone();
+    let contract = match &link.salt {
+        Some(salt) => {
+            engine.deploy_contract_salted(owner, bytecode, initial_state, block.timestamp, salt)
+        }
+        None => engine.deploy_contract(owner, bytecode, initial_state, block.timestamp),
+    }
+    .map_err(|e| format!("VM deploy failed: {}", e))?;
+    if block.amount > 0 {
+        if let Err(e) = engine.send_to_contract(&contract, block.amount) {
+            eprintln!("⚠️ Failed to fund contract {}: {}", contract, e);
+        }
+    }
+    Ok(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::AccountState;
+
+    fn call_block(account: &str, link: String) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::ContractCall,
+            amount: 0,
+            link,
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: los_core::MIN_CALL_FEE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    fn link(contract: &str, function: &str, args: &[&str]) -> String {
+        let args = serde_json::to_vec(args).unwrap();
+        format!(
+            "CALL:{}:{}:{}",
+            contract,
+            function,
+            base64::engine::general_purpose::STANDARD.encode(args)
+        )
+    }
+
+    #[test]
+    fn test_call_link_parse() {
+        let parsed = CallLink::parse(&link("LOSConX", "set", &["a", "b"])).unwrap();
+        assert_eq!(parsed.contract, "LOSConX");
+        assert_eq!(parsed.function, "set");
+        assert_eq!(parsed.args, vec!["a", "b"]);
+        assert!(CallLink::parse("CALL:LOSConX:get").unwrap().args.is_empty());
+        assert!(CallLink::parse("DEPLOY:x").is_err());
+        assert!(CallLink::parse("CALL:LOSConX").is_err());
+        assert!(CallLink::parse("CALL:LOSConX:f:!!").is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_ledger_rejection_rolls_back_vm() {
+        let engine = WasmEngine::new();
+        let contract = engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        let mut ledger = Ledger::new();
+        ledger.accounts.insert(
+            "LOScaller".to_string(),
+            AccountState {
+                head: "0".to_string(),
+                balance: 10 * los_core::MIN_CALL_FEE_CIL,
+                block_count: 0,
+                is_validator: false,
+            },
+        );
+
+        // Executes in the VM (mock set_state), then fails signature checks
+        let block = call_block("LOScaller", link(&contract, "set_state", &["k", "v"]));
+        let err = apply_contract_block(&mut ledger, &engine, &block).unwrap_err();
+        assert!(err.contains("PoW") || err.contains("Signature"), "{}", err);
+        assert!(engine.get_contract_state(&contract).unwrap().is_empty());
+        assert!(engine.staged_blocks().is_empty());
+        assert_eq!(
+            ledger.accounts["LOScaller"].balance,
+            10 * los_core::MIN_CALL_FEE_CIL
+        );
+
+        // Pre-checks reject before execution
+        let mut wrong_prev = block.clone();
+        wrong_prev.previous = "abc".to_string();
+        assert!(apply_contract_block(&mut ledger, &engine, &wrong_prev)
+            .unwrap_err()
+            .contains("sequence"));
+        let missing = call_block("LOScaller", link("LOSConMissing", "get", &[]));
+        assert!(apply_contract_block(&mut ledger, &engine, &missing).is_err());
+        let mut greedy = block.clone();
+        greedy.fee = (los_core::MAX_GAS_PER_CALL as u128 + 1) * los_core::GAS_PRICE_CIL;
+        ledger.accounts.get_mut("LOScaller").unwrap().balance = greedy.fee;
+        assert!(apply_contract_block(&mut ledger, &engine, &greedy)
+            .unwrap_err()
+            .contains("per-call cap"));
+        assert!(engine.staged_blocks().is_empty());
+    }
+
+    #[test]
+    fn test_deploy_rejects_mismatched_bytecode() {
+        let engine = WasmEngine::new();
+        let mut ledger = Ledger::new();
+        let wasm = b"\0asm\x01\x00\x00\x00".to_vec();
+        let mut block = call_block("LOSowner", DeployLink::format("deadbeef", None));
+        block.block_type = BlockType::ContractDeploy;
+
+        assert!(
+            apply_deploy_block(&mut ledger, &engine, &block, wasm, BTreeMap::new())
+                .unwrap_err()
+                .contains("code hash")
+        );
+        assert!(ledger.blocks.is_empty());
+        let not_deploy = call_block("LOSowner", link("LOSConX", "get", &[]));
+        assert!(
+            apply_deploy_block(&mut ledger, &engine, &not_deploy, vec![], BTreeMap::new()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_gas_cap_matches_vm() {
+        assert_eq!(los_core::MAX_GAS_PER_CALL, los_vm::MAX_GAS_PER_CALL);
+    }
+}
@@ -5,8 +5,20 @@
 // Provides ACID-compliant atomic operations for blocks, accounts, and metadata.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
-use los_core::{AccountState, Block, Ledger};
+use crate::api_access::ApiKeyRecord;
+use crate::archive::ContractSnapshot;
+use crate::checkpoint_conflict::ReplayJournal;
+use crate::consensus_journal::{InFlightSend, VoteRecord};
+use crate::delta_sync::DeltaSyncSession;
+use crate::invoices::Invoice;
+use crate::source_registry::SourceRecord;
+use los_core::validator_rewards::ValidatorRewardPool;
+use los_core::{AccountState, Block, BlockType, Ledger};
+use los_vm::oracle_connector::OracleObservation;
+use los_vm::ContractEvent;
+use serde::{Deserialize, Serialize};
 use sled::{Db, Tree};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -17,9 +29,94 @@ const DB_PATH: &str = "los_database";
 const TREE_BLOCKS: &str = "blocks";
 const TREE_ACCOUNTS: &str = "accounts";
 const TREE_META: &str = "metadata";
-const TREE_FAUCET_COOLDOWNS: &str = "faucet_cooldowns";
 const TREE_PEERS: &str = "known_peers";
 const TREE_CONTRACTS: &str = "contracts"; // Smart contract VM state
+const TREE_SEEN_IDS: &str = "seen_ids"; // Gossip dedup: blake3 id → seq ‖ ts
+const TREE_SEEN_RING: &str = "seen_ring"; // Gossip dedup: seq → blake3 id (eviction order)
+const TREE_ORACLE: &str = "oracle_history"; // symbol ‖ 0x00 ‖ ts (BE) → OracleObservation JSON
+const TREE_TX_INDEX: &str = "tx_index"; // 'f'|'t' ‖ addr ‖ 0x00 ‖ ts (BE) ‖ hash → counterparty
+const TREE_EVENTS: &str = "contract_events"; // contract ‖ 0x00 ‖ event position (see event_key) → ContractEvent JSON
+const TREE_API_KEYS: &str = "api_keys"; // key id → ApiKeyRecord JSON
+const TREE_CONTRACT_SOURCES: &str = "contract_sources"; // contract address → SourceRecord JSON
+const TREE_INVOICES: &str = "invoices"; // invoice id → Invoice JSON
+const TREE_ARCHIVE_HEIGHTS: &str = "archive_heights"; // height (BE) → unix ts of the snapshot
+const TREE_ARCHIVE_ACCOUNTS: &str = "archive_accounts"; // addr ‖ 0x00 ‖ height (BE) → AccountState JSON
+const TREE_ARCHIVE_CONTRACTS: &str = "archive_contracts"; // contract ‖ 0x00 ‖ height (BE) → ContractSnapshot JSON
+const TREE_ADDRESS_LABELS: &str = "address_labels"; // full address → operator label (UTF-8)
+const TREE_EVENT_TOPICS: &str = "event_topics"; // contract ‖ 0x00 ‖ idx ‖ topic ‖ 0x00 ‖ event position → event key
+const TREE_INFLIGHT: &str = "inflight_sends"; // tx_hash ‖ 0x00 → Block JSON, tx_hash ‖ 0x01 ‖ voter → VoteRecord JSON
+const TREE_SCHEDULED: &str = "scheduled_sends"; // tx_hash → time-locked Send Block JSON (held in the mempool)
+const TREE_EVENT_SUMMARIES: &str = "event_summaries"; // contract ‖ 0x00 ‖ epoch (BE) → EventSummary JSON
+/// Metadata: events older than this timestamp (u64 LE) were rolled up into summaries
+const META_EVENT_GC_CUTOFF: &[u8] = b"event_gc_cutoff";
+/// Events removed per atomic rollup transaction
+const EVENT_GC_CHUNK: usize = 1_000;
+/// Metadata flag: every persisted block has counterparty index entries
+const META_TX_INDEX_READY: &[u8] = b"tx_index_v1";
+
+/// Sender and recipient of a value-moving block, for the counterparty index.
+/// Receive blocks are skipped: the matching Send already records the pair.
+pub fn tx_parties(block: &Block) -> Option<(String, String)> {
+    match block.block_type {
+        BlockType::Send => Some((block.account.clone(), block.link.clone())),
+        BlockType::Mint | BlockType::Claim => Some(("SYSTEM".to_string(), block.account.clone())),
+        // link = "CALL:{contract_addr}:{function}:{args_b64}"
+        BlockType::ContractCall => block
+            .link
+            .strip_prefix("CALL:")
+            .and_then(|rest| rest.split(':').next())
+            .map(|contract| (block.account.clone(), contract.to_string())),
+        BlockType::Migrate => block
+            .migration_destination()
+            .map(|to| (block.account.clone(), to)),
+        _ => None,
+    }
+}
+
+/// Rolled-up counts of a contract's pruned events in one epoch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSummary {
+    pub contract: String,
+    pub epoch: u64,
+    /// Epoch length the summary was made with (epoch × epoch_secs = start)
+    pub epoch_secs: u64,
+    pub events: u64,
+    /// Event count per event type
+    pub by_type: BTreeMap<String, u64>,
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+}
+
+impl EventSummary {
+    fn add(&mut self, event: &ContractEvent) {
+        if self.events == 0 || event.timestamp < self.first_timestamp {
+            self.first_timestamp = event.timestamp;
+        }
+        self.last_timestamp = self.last_timestamp.max(event.timestamp);
+        self.events += 1;
+        *self.by_type.entry(event.event_type.clone()).or_insert(0) += 1;
+    }
+
+    fn merge(&mut self, other: &EventSummary) {
+        if self.events == 0 || other.first_timestamp < self.first_timestamp {
+            self.first_timestamp = other.first_timestamp;
+        }
+        self.last_timestamp = self.last_timestamp.max(other.last_timestamp);
+        self.events += other.events;
+        for (kind, n) in &other.by_type {
+            *self.by_type.entry(kind.clone()).or_insert(0) += n;
+        }
+    }
+}
+
+/// Filters for [`LosDatabase::query_contract_events`]
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub event_type: Option<String>,
+    /// Required value per topic position (`None` = any)
+    pub topics: [Option<String>; los_vm::host::MAX_EVENT_TOPICS],
+    pub since: u64,
+}
 
 /// Database wrapper with ACID guarantees
 pub struct LosDatabase {
@@ -207,6 +304,25 @@ impl LosDatabase {
         Ok(())
     }
 
+    /// Underlying sled handle (storage maintenance: snapshots, compaction)
+    pub fn raw(&self) -> &Db {
+        &self.db
+    }
+
+    /// Key + value bytes stored across all trees (live data, excluding the
+    /// dead space sled has not reclaimed)
+    pub fn live_bytes(&self) -> u64 {
+        let mut total = 0u64;
+        for name in self.db.tree_names() {
+            if let Ok(tree) = self.db.open_tree(&name) {
+                for (k, v) in tree.iter().flatten() {
+                    total += (k.len() + v.len()) as u64;
+                }
+            }
+        }
+        total
+    }
+
     /// Get blocks tree
     fn blocks_tree(&self) -> Result<Tree, String> {
         self.db
@@ -238,12 +354,18 @@ impl LosDatabase {
         let blocks_tree = self.blocks_tree()?;
         let accounts_tree = self.accounts_tree()?;
         let meta_tree = self.meta_tree()?;
+        let index_tree = self.tx_index_tree()?;
 
         // Pre-serialize all data outside the transaction (transactions should be fast)
         let mut block_entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(ledger.blocks.len());
+        let mut index_entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
         for (hash, block) in &ledger.blocks {
             let block_json = serde_json::to_vec(block)
                 .map_err(|e| format!("Failed to serialize block: {}", e))?;
+            // Only blocks applied since the last save need index entries
+            if !blocks_tree.contains_key(hash.as_bytes()).unwrap_or(false) {
+                index_entries.extend(Self::tx_index_entries(hash, block));
+            }
             block_entries.push((hash.as_bytes().to_vec(), block_json));
         }
 
@@ -259,11 +381,14 @@ impl LosDatabase {
             .map_err(|e| format!("Failed to serialize distribution: {}", e))?;
 
         // Atomic cross-tree transaction: all-or-nothing commit
-        (&blocks_tree, &accounts_tree, &meta_tree)
-            .transaction(|(tx_blocks, tx_accounts, tx_meta)| {
+        (&blocks_tree, &accounts_tree, &meta_tree, &index_tree)
+            .transaction(|(tx_blocks, tx_accounts, tx_meta, tx_index)| {
                 for (key, value) in &block_entries {
                     tx_blocks.insert(key.as_slice(), value.as_slice())?;
                 }
+                for (key, value) in &index_entries {
+                    tx_index.insert(key.as_slice(), value.as_slice())?;
+                }
                 for (key, value) in &account_entries {
                     tx_accounts.insert(key.as_slice(), value.as_slice())?;
                 }
@@ -367,6 +492,15 @@ impl LosDatabase {
             }
         }
 
+        // 5. Rebuild per-account contract gas budgets from ContractCall blocks
+        ledger.rebuild_gas_usage();
+
+        // 6. Rebuild claimed validator rewards from Claim blocks
+        ledger.rebuild_reward_claims();
+
+        // 7. Rebuild session key grants from Change/ContractCall blocks
+        ledger.rebuild_session_keys();
+
         Ok(ledger)
     }
 
@@ -505,6 +639,9 @@ impl LosDatabase {
             .map_err(|e| format!("Failed to clear accounts: {}", e))?;
         meta.clear()
             .map_err(|e| format!("Failed to clear metadata: {}", e))?;
+        self.tx_index_tree()?
+            .clear()
+            .map_err(|e| format!("Failed to clear tx index: {}", e))?;
 
         self.db
             .flush()
@@ -543,50 +680,227 @@ impl LosDatabase {
         }
     }
 
-    // --- Faucet Cooldown Persistence ---
+    // --- Reward Accruals (claim payout model) ---
+
+    /// Save the reward pool's accrual accounts. The rest of the pool is rebuilt
+    /// from the ledger at startup, but accruals come from gossiped certificates.
+    pub fn save_reward_accruals(&self, pool: &ValidatorRewardPool) -> Result<(), String> {
+        let json = serde_json::to_vec(&(
+            pool.last_accrued_epoch,
+            &pool.accruals,
+            &pool.accrued_certificates,
+        ))
+        .map_err(|e| format!("Failed to serialize reward accruals: {}", e))?;
+        self.meta_tree()?
+            .insert(b"reward_accruals", json)
+            .map_err(|e| format!("Failed to save reward accruals: {}", e))?;
+        Ok(())
+    }
+
+    /// Restore accrual accounts saved by `save_reward_accruals` into `pool`
+    pub fn load_reward_accruals(&self, pool: &mut ValidatorRewardPool) -> Result<(), String> {
+        let Some(bytes) = self
+            .meta_tree()?
+            .get(b"reward_accruals")
+            .map_err(|e| format!("Failed to read reward accruals: {}", e))?
+        else {
+            return Ok(());
+        };
+        let (last_epoch, accruals, certificates) = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Failed to deserialize reward accruals: {}", e))?;
+        pool.last_accrued_epoch = last_epoch;
+        pool.accruals = accruals;
+        pool.accrued_certificates = certificates;
+        Ok(())
+    }
+
+    /// Save the running delta sync session (resumed after a restart)
+    pub fn save_delta_sync(&self, session: &DeltaSyncSession) -> Result<(), String> {
+        let json = serde_json::to_vec(session)
+            .map_err(|e| format!("Failed to serialize delta sync session: {}", e))?;
+        self.meta_tree()?
+            .insert(b"delta_sync_session", json)
+            .map_err(|e| format!("Failed to save delta sync session: {}", e))?;
+        Ok(())
+    }
+
+    /// Session saved by `save_delta_sync`, if one was running
+    pub fn load_delta_sync(&self) -> Result<Option<DeltaSyncSession>, String> {
+        let Some(bytes) = self
+            .meta_tree()?
+            .get(b"delta_sync_session")
+            .map_err(|e| format!("Failed to read delta sync session: {}", e))?
+        else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| format!("Failed to deserialize delta sync session: {}", e))
+    }
+
+    pub fn clear_delta_sync(&self) -> Result<(), String> {
+        self.meta_tree()?
+            .remove(b"delta_sync_session")
+            .map_err(|e| format!("Failed to clear delta sync session: {}", e))?;
+        Ok(())
+    }
+
+    /// Save the replay journal left by `los-node resolve-checkpoint`
+    pub fn save_checkpoint_replay(&self, journal: &ReplayJournal) -> Result<(), String> {
+        let json = serde_json::to_vec(journal)
+            .map_err(|e| format!("Failed to serialize replay journal: {}", e))?;
+        self.meta_tree()?
+            .insert(b"checkpoint_replay", json)
+            .map_err(|e| format!("Failed to save replay journal: {}", e))?;
+        self.flush()
+    }
+
+    /// Pending replay journal, if a resolution still has to be applied
+    pub fn load_checkpoint_replay(&self) -> Result<Option<ReplayJournal>, String> {
+        let Some(bytes) = self
+            .meta_tree()?
+            .get(b"checkpoint_replay")
+            .map_err(|e| format!("Failed to read replay journal: {}", e))?
+        else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| format!("Failed to deserialize replay journal: {}", e))
+    }
 
-    /// Get faucet cooldowns tree
-    fn faucet_tree(&self) -> Result<Tree, String> {
+    pub fn clear_checkpoint_replay(&self) -> Result<(), String> {
+        self.meta_tree()?
+            .remove(b"checkpoint_replay")
+            .map_err(|e| format!("Failed to clear replay journal: {}", e))?;
+        self.flush()
+    }
+
+    // --- In-flight consensus journal (consensus_journal.rs) ---
+
+    fn inflight_tree(&self) -> Result<Tree, String> {
         self.db
-            .open_tree(TREE_FAUCET_COOLDOWNS)
-            .map_err(|e| format!("Failed to open faucet cooldowns tree: {}", e))
+            .open_tree(TREE_INFLIGHT)
+            .map_err(|e| format!("Failed to open in-flight sends tree: {}", e))
     }
 
-    /// Record faucet claim timestamp for an address (persistent across restarts)
-    pub fn record_faucet_claim(&self, address: &str) -> Result<(), String> {
-        let tree = self.faucet_tree()?;
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        tree.insert(address.as_bytes(), &now.to_le_bytes())
-            .map_err(|e| format!("Failed to record faucet claim: {}", e))?;
+    fn inflight_key(tx_hash: &str, tag: u8) -> Vec<u8> {
+        let mut key = Vec::with_capacity(tx_hash.len() + 1);
+        key.extend_from_slice(tx_hash.as_bytes());
+        key.push(tag);
+        key
+    }
+
+    /// Journal a Send that is waiting for votes
+    pub fn save_inflight_send(&self, tx_hash: &str, block: &Block) -> Result<(), String> {
+        let json = serde_json::to_vec(block)
+            .map_err(|e| format!("Failed to serialize in-flight send: {}", e))?;
+        self.inflight_tree()?
+            .insert(Self::inflight_key(tx_hash, 0), json)
+            .map_err(|e| format!("Failed to save in-flight send: {}", e))?;
         Ok(())
     }
 
-    /// Check if address is in faucet cooldown period
-    /// Returns Ok(()) if allowed, Err(seconds_remaining) if in cooldown
-    pub fn check_faucet_cooldown(&self, address: &str, cooldown_secs: u64) -> Result<(), u64> {
-        let tree = self.faucet_tree().map_err(|_| 0u64)?;
+    /// Journal a vote counted for an in-flight Send
+    pub fn save_inflight_vote(&self, tx_hash: &str, vote: &VoteRecord) -> Result<(), String> {
+        let json = serde_json::to_vec(vote)
+            .map_err(|e| format!("Failed to serialize in-flight vote: {}", e))?;
+        let mut key = Self::inflight_key(tx_hash, 1);
+        key.extend_from_slice(vote.voter.as_bytes());
+        self.inflight_tree()?
+            .insert(key, json)
+            .map_err(|e| format!("Failed to save in-flight vote: {}", e))?;
+        Ok(())
+    }
 
-        if let Ok(Some(bytes)) = tree.get(address.as_bytes()) {
-            if bytes.len() == 8 {
-                let last_claim = u64::from_le_bytes(bytes.as_ref().try_into().unwrap_or([0u8; 8]));
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs();
+    /// Drop a finalized or expired Send and its votes
+    pub fn remove_inflight_send(&self, tx_hash: &str) -> Result<(), String> {
+        let tree = self.inflight_tree()?;
+        let mut batch = sled::Batch::default();
+        for item in tree.scan_prefix(tx_hash.as_bytes()) {
+            let (key, _) = item.map_err(|e| format!("Failed to read in-flight send: {}", e))?;
+            // Exact hash only: the byte after it is the 0x00/0x01 tag
+            if matches!(key.get(tx_hash.len()), Some(0) | Some(1)) {
+                batch.remove(key);
+            }
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| format!("Failed to remove in-flight send: {}", e))?;
+        Ok(())
+    }
 
-                let elapsed = now.saturating_sub(last_claim);
-                if elapsed < cooldown_secs {
-                    return Err(cooldown_secs - elapsed);
+    /// All journaled Sends with their votes. Votes without a Send and
+    /// undecodable entries are skipped.
+    pub fn load_inflight_sends(&self) -> Result<Vec<InFlightSend>, String> {
+        let mut out: Vec<InFlightSend> = Vec::new();
+        for item in self.inflight_tree()?.iter() {
+            let (key, value) = item.map_err(|e| format!("Failed to read in-flight send: {}", e))?;
+            // Hashes are hex, so the first 0x00/0x01 byte is the tag
+            let Some(split) = key.iter().position(|b| *b <= 1) else {
+                continue;
+            };
+            let Ok(tx_hash) = std::str::from_utf8(&key[..split]) else {
+                continue;
+            };
+            if key[split] == 0 {
+                if let Ok(block) = serde_json::from_slice(&value) {
+                    out.push(InFlightSend {
+                        tx_hash: tx_hash.to_string(),
+                        block,
+                        votes: Vec::new(),
+                    });
+                }
+            } else if let Ok(vote) = serde_json::from_slice::<VoteRecord>(&value) {
+                // Keys sort the Send entry (tag 0) before its votes (tag 1)
+                match out.last_mut() {
+                    Some(send) if send.tx_hash == tx_hash => send.votes.push(vote),
+                    _ => {}
                 }
             }
         }
+        Ok(out)
+    }
+
+    // --- Time-locked sends held in the mempool ---
+
+    fn scheduled_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_SCHEDULED)
+            .map_err(|e| format!("Failed to open scheduled sends tree: {}", e))
+    }
 
+    /// Keep a time-locked Send across restarts until it is released
+    pub fn save_scheduled_send(&self, tx_hash: &str, block: &Block) -> Result<(), String> {
+        let json = serde_json::to_vec(block)
+            .map_err(|e| format!("Failed to serialize scheduled send: {}", e))?;
+        self.scheduled_tree()?
+            .insert(tx_hash.as_bytes(), json)
+            .map_err(|e| format!("Failed to save scheduled send: {}", e))?;
         Ok(())
     }
 
+    pub fn remove_scheduled_send(&self, tx_hash: &str) -> Result<(), String> {
+        self.scheduled_tree()?
+            .remove(tx_hash.as_bytes())
+            .map_err(|e| format!("Failed to remove scheduled send: {}", e))?;
+        Ok(())
+    }
+
+    /// All held sends as (tx_hash, block); undecodable entries are skipped
+    pub fn load_scheduled_sends(&self) -> Result<Vec<(String, Block)>, String> {
+        let mut out = Vec::new();
+        for item in self.scheduled_tree()?.iter() {
+            let (key, value) = item.map_err(|e| format!("Failed to read scheduled send: {}", e))?;
+            if let (Ok(hash), Ok(block)) = (
+                std::str::from_utf8(&key),
+                serde_json::from_slice::<Block>(&value),
+            ) {
+                out.push((hash.to_string(), block));
+            }
+        }
+        Ok(out)
+    }
+
     // --- Persistent Peer Storage ---
 
     /// Get known peers tree
@@ -621,8 +935,770 @@ impl LosDatabase {
         Ok(peers)
     }
 
+    // --- Gossip Seen-Cache ---
+
+    /// Open the (ids, ring) tree pair backing the persistent gossip seen-cache
+    pub fn seen_cache_trees(&self) -> Result<(Tree, Tree), String> {
+        let ids = self
+            .db
+            .open_tree(TREE_SEEN_IDS)
+            .map_err(|e| format!("Failed to open seen ids tree: {}", e))?;
+        let ring = self
+            .db
+            .open_tree(TREE_SEEN_RING)
+            .map_err(|e| format!("Failed to open seen ring tree: {}", e))?;
+        Ok((ids, ring))
+    }
+
+    // --- Oracle Price History ---
+
+    /// Get oracle history tree
+    fn oracle_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_ORACLE)
+            .map_err(|e| format!("Failed to open oracle tree: {}", e))
+    }
+
+    /// Key: symbol ‖ 0x00 ‖ timestamp (big-endian) — sorts by time within a symbol
+    fn oracle_key(symbol: &str, timestamp: u64) -> Vec<u8> {
+        let mut key = Vec::with_capacity(symbol.len() + 9);
+        key.extend_from_slice(symbol.as_bytes());
+        key.push(0);
+        key.extend_from_slice(&timestamp.to_be_bytes());
+        key
+    }
+
+    /// Persist one oracle observation (same symbol+timestamp overwrites)
+    pub fn save_oracle_observation(&self, obs: &OracleObservation) -> Result<(), String> {
+        let tree = self.oracle_tree()?;
+        let value = serde_json::to_vec(obs)
+            .map_err(|e| format!("Failed to serialize oracle observation: {}", e))?;
+        tree.insert(Self::oracle_key(&obs.symbol, obs.timestamp), value)
+            .map_err(|e| format!("Failed to save oracle observation: {}", e))?;
+        Ok(())
+    }
+
+    /// Observations for `symbol` with timestamp >= `since`, oldest first,
+    /// keeping the newest `limit`.
+    pub fn get_oracle_history(
+        &self,
+        symbol: &str,
+        since: u64,
+        limit: usize,
+    ) -> Result<Vec<OracleObservation>, String> {
+        let tree = self.oracle_tree()?;
+        let start = Self::oracle_key(symbol, since);
+        let end = Self::oracle_key(symbol, u64::MAX);
+        let mut out = Vec::new();
+        for item in tree.range(start..=end).rev() {
+            if out.len() >= limit {
+                break;
+            }
+            let (_, value) = item.map_err(|e| format!("Failed to read oracle history: {}", e))?;
+            if let Ok(obs) = serde_json::from_slice::<OracleObservation>(&value) {
+                out.push(obs);
+            }
+        }
+        out.reverse();
+        Ok(out)
+    }
+
+    /// Delete observations for `symbol` older than `before`. Returns count removed.
+    pub fn prune_oracle_history(&self, symbol: &str, before: u64) -> Result<usize, String> {
+        let tree = self.oracle_tree()?;
+        let start = Self::oracle_key(symbol, 0);
+        let end = Self::oracle_key(symbol, before);
+        let mut removed = 0;
+        for item in tree.range(start..end) {
+            let (key, _) = item.map_err(|e| format!("Failed to read oracle history: {}", e))?;
+            tree.remove(key)
+                .map_err(|e| format!("Failed to prune oracle history: {}", e))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    // --- Counterparty Transaction Index ---
+
+    /// Get counterparty index tree
+    fn tx_index_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_TX_INDEX)
+            .map_err(|e| format!("Failed to open tx index tree: {}", e))
+    }
+
+    /// Key: direction ‖ addr ‖ 0x00 ‖ timestamp (BE) [‖ hash] — sorts by time within an address
+    fn tx_index_key(direction: u8, addr: &str, timestamp: u64, hash: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(addr.len() + hash.len() + 10);
+        key.push(direction);
+        key.extend_from_slice(addr.as_bytes());
+        key.push(0);
+        key.extend_from_slice(&timestamp.to_be_bytes());
+        key.extend_from_slice(hash.as_bytes());
+        key
+    }
+
+    /// Outgoing ('f') and incoming ('t') entries for one block
+    fn tx_index_entries(hash: &str, block: &Block) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let Some((from, to)) = tx_parties(block) else {
+            return Vec::new();
+        };
+        vec![
+            (
+                Self::tx_index_key(b'f', &from, block.timestamp, hash),
+                to.as_bytes().to_vec(),
+            ),
+            (
+                Self::tx_index_key(b't', &to, block.timestamp, hash),
+                from.as_bytes().to_vec(),
+            ),
+        ]
+    }
+
+    /// One-time backfill for databases created before the index existed.
+    /// Returns the number of blocks indexed (0 if already complete).
+    pub fn ensure_tx_index(&self, ledger: &Ledger) -> Result<usize, String> {
+        let meta = self.meta_tree()?;
+        if meta
+            .contains_key(META_TX_INDEX_READY)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?
+        {
+            return Ok(0);
+        }
+        let tree = self.tx_index_tree()?;
+        let mut batch = sled::Batch::default();
+        let mut indexed = 0;
+        for (hash, block) in &ledger.blocks {
+            let entries = Self::tx_index_entries(hash, block);
+            if !entries.is_empty() {
+                indexed += 1;
+            }
+            for (key, value) in entries {
+                batch.insert(key, value);
+            }
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| format!("Failed to build tx index: {}", e))?;
+        meta.insert(META_TX_INDEX_READY, &[1u8])
+            .map_err(|e| format!("Failed to save metadata: {}", e))?;
+        Ok(indexed)
+    }
+
+    /// Block hashes sent by `from` and/or received by `to` with
+    /// timestamp >= `since`, newest first, at most `limit`.
+    pub fn query_transactions(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+        since: u64,
+        limit: usize,
+    ) -> Result<Vec<String>, String> {
+        // Scan one side of the index, filter on the counterparty for the other
+        let (direction, addr, counterparty) = match (from, to) {
+            (Some(f), t) => (b'f', f, t),
+            (None, Some(t)) => (b't', t, None),
+            (None, None) => return Err("from or to is required".to_string()),
+        };
+        let tree = self.tx_index_tree()?;
+        let start = Self::tx_index_key(direction, addr, since, "");
+        let mut end = Self::tx_index_key(direction, addr, u64::MAX, "");
+        end.push(0xFF);
+        let prefix_len = start.len();
+        let mut out = Vec::new();
+        for item in tree.range(start..end).rev() {
+            if out.len() >= limit {
+                break;
+            }
+            let (key, value) = item.map_err(|e| format!("Failed to read tx index: {}", e))?;
+            if counterparty.is_some_and(|c| c.as_bytes() != value.as_ref()) {
+                continue;
+            }
+            if let Ok(hash) = String::from_utf8(key[prefix_len..].to_vec()) {
+                out.push(hash);
+            }
+        }
+        Ok(out)
+    }
+
+    // --- Contract Event Store ---
+
+    /// Get contract event trees (events, topic index)
+    fn event_trees(&self) -> Result<(Tree, Tree), String> {
+        let events = self
+            .db
+            .open_tree(TREE_EVENTS)
+            .map_err(|e| format!("Failed to open event tree: {}", e))?;
+        let topics = self
+            .db
+            .open_tree(TREE_EVENT_TOPICS)
+            .map_err(|e| format!("Failed to open event topic tree: {}", e))?;
+        Ok((events, topics))
+    }
+
+    /// Key: contract ‖ 0x00 ‖ timestamp (BE) ‖ block_hash ‖ 0x00 ‖ call_index (BE)
+    /// ‖ event_index (BE). Sorts a contract's events in their total order and
+    /// is the same on every node, so re-saving a block's events overwrites them.
+    fn event_key(event: &ContractEvent) -> Vec<u8> {
+        let mut key = Self::event_time_key(&event.contract, event.timestamp);
+        key.extend_from_slice(event.block_hash.as_bytes());
+        key.push(0);
+        key.extend_from_slice(&event.call_index.to_be_bytes());
+        key.extend_from_slice(&event.event_index.to_be_bytes());
+        key
+    }
+
+    /// Key prefix of all events of `contract`
+    fn event_contract_prefix(contract: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(contract.len() + 90);
+        key.extend_from_slice(contract.as_bytes());
+        key.push(0);
+        key
+    }
+
+    /// First key of `contract`'s events at or after `timestamp`
+    fn event_time_key(contract: &str, timestamp: u64) -> Vec<u8> {
+        let mut key = Self::event_contract_prefix(contract);
+        key.extend_from_slice(&timestamp.to_be_bytes());
+        key
+    }
+
+    /// Exclusive upper bound of the keys under a 0x00-terminated `prefix`
+    fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+        let mut end = prefix.to_vec();
+        if let Some(last) = end.last_mut() {
+            *last = 1;
+        }
+        end
+    }
+
+    /// Key prefix of topic `index` = `topic` (topics never contain 0x00)
+    fn event_topic_prefix(contract: &str, index: u8, topic: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(contract.len() + topic.len() + 3);
+        key.extend_from_slice(contract.as_bytes());
+        key.push(0);
+        key.push(index);
+        key.extend_from_slice(topic.as_bytes());
+        key.push(0);
+        key
+    }
+
+    /// Persist the events of one applied ContractCall block and index their
+    /// topics. Saving the same block again (replay, re-sync) adds nothing.
+    pub fn save_contract_events(
+        &self,
+        block_hash: &str,
+        events: &[ContractEvent],
+    ) -> Result<(), String> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        // Already rolled up (event_retention.rs): re-saving would count them twice
+        let cutoff = self.event_gc_cutoff()?;
+        let (tree, topic_tree) = self.event_trees()?;
+        let mut batch = sled::Batch::default();
+        let mut topic_batch = sled::Batch::default();
+        for event in events.iter().filter(|e| e.timestamp >= cutoff) {
+            let event = ContractEvent {
+                block_hash: block_hash.to_string(),
+                ..event.clone()
+            };
+            let key = Self::event_key(&event);
+            let value = serde_json::to_vec(&event)
+                .map_err(|e| format!("Failed to serialize event: {}", e))?;
+            for (index, topic) in event.topics.iter().enumerate() {
+                let mut topic_key = Self::event_topic_prefix(&event.contract, index as u8, topic);
+                topic_key.extend_from_slice(&key[event.contract.len() + 1..]);
+                topic_batch.insert(topic_key, key.clone());
+            }
+            batch.insert(key, value);
+        }
+        tree.apply_batch(batch)
+            .map_err(|e| format!("Failed to save events: {}", e))?;
+        topic_tree
+            .apply_batch(topic_batch)
+            .map_err(|e| format!("Failed to index event topics: {}", e))?;
+        Ok(())
+    }
+
+    /// Events of `contract` matching `filter`, newest first (reverse of the
+    /// event order), at most `limit`. With a topic filter the scan walks that
+    /// topic's index only.
+    pub fn query_contract_events(
+        &self,
+        contract: &str,
+        filter: &EventFilter,
+        limit: usize,
+    ) -> Result<Vec<ContractEvent>, String> {
+        let (tree, topic_tree) = self.event_trees()?;
+        let matches =
+            |event: &ContractEvent| {
+                filter
+                    .event_type
+                    .as_ref()
+                    .is_none_or(|t| *t == event.event_type)
+                    && filter.topics.iter().enumerate().all(|(i, want)| {
+                        want.as_ref().is_none_or(|w| event.topics.get(i) == Some(w))
+                    })
+            };
+        let mut out = Vec::new();
+        let mut take = |value: &[u8]| {
+            if let Ok(event) = serde_json::from_slice::<ContractEvent>(value) {
+                if matches(&event) {
+                    out.push(event);
+                }
+            }
+            out.len() >= limit
+        };
+
+        let indexed = filter
+            .topics
+            .iter()
+            .enumerate()
+            .find_map(|(i, t)| t.as_ref().map(|t| (i as u8, t)));
+        if let Some((index, topic)) = indexed {
+            let prefix = Self::event_topic_prefix(contract, index, topic);
+            let mut start = prefix.clone();
+            start.extend_from_slice(&filter.since.to_be_bytes());
+            let end = Self::prefix_end(&prefix);
+            for item in topic_tree.range(start..end).rev() {
+                let (_, event_key) =
+                    item.map_err(|e| format!("Failed to read event index: {}", e))?;
+                let value = tree
+                    .get(&event_key)
+                    .map_err(|e| format!("Failed to read event: {}", e))?;
+                if value.is_some_and(|v| take(&v)) {
+                    break;
+                }
+            }
+        } else {
+            let start = Self::event_time_key(contract, filter.since);
+            let end = Self::prefix_end(&Self::event_contract_prefix(contract));
+            for item in tree.range(start..end).rev() {
+                let (_, value) = item.map_err(|e| format!("Failed to read events: {}", e))?;
+                if take(&value) {
+                    break;
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn event_summaries_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_EVENT_SUMMARIES)
+            .map_err(|e| format!("Failed to open event summary tree: {}", e))
+    }
+
+    /// Events older than this were rolled up into summaries (0 = none)
+    pub fn event_gc_cutoff(&self) -> Result<u64, String> {
+        let value = self
+            .meta_tree()?
+            .get(META_EVENT_GC_CUTOFF)
+            .map_err(|e| format!("Failed to read event GC cutoff: {}", e))?;
+        Ok(value
+            .and_then(|v| v.as_ref().try_into().ok())
+            .map(u64::from_le_bytes)
+            .unwrap_or(0))
+    }
+
+    /// Roll up every event older than `cutoff` into per-epoch summaries
+    /// (`epoch_secs` long) and delete it with its topic index entries.
+    /// Each chunk is one cross-tree transaction, so an interrupted run
+    /// neither loses nor double-counts events. Returns the events removed.
+    pub fn prune_contract_events(&self, cutoff: u64, epoch_secs: u64) -> Result<u64, String> {
+        use sled::Transactional;
+
+        let epoch_secs = epoch_secs.max(1);
+        if cutoff <= self.event_gc_cutoff()? {
+            return Ok(0);
+        }
+        // Recorded first: events re-saved from now on below it are dropped
+        self.meta_tree()?
+            .insert(META_EVENT_GC_CUTOFF, &cutoff.to_le_bytes())
+            .map_err(|e| format!("Failed to save event GC cutoff: {}", e))?;
+
+        let (tree, topic_tree) = self.event_trees()?;
+        let summary_tree = self.event_summaries_tree()?;
+        let mut pruned = 0u64;
+        let mut cursor = Vec::new();
+        while let Some(item) = tree.range(cursor.clone()..).next() {
+            let (key, _) = item.map_err(|e| format!("Failed to read events: {}", e))?;
+            let Some(nul) = key.iter().position(|b| *b == 0) else {
+                cursor = key.to_vec();
+                cursor.push(0);
+                continue;
+            };
+            let contract = String::from_utf8_lossy(&key[..nul]).to_string();
+            let prefix = Self::event_contract_prefix(&contract);
+            let chunk: Vec<(sled::IVec, ContractEvent)> = tree
+                .range(prefix.clone()..Self::event_time_key(&contract, cutoff))
+                .take(EVENT_GC_CHUNK)
+                .filter_map(|item| item.ok())
+                .filter_map(|(k, v)| Some((k, serde_json::from_slice(&v).ok()?)))
+                .collect();
+            if chunk.is_empty() {
+                cursor = Self::prefix_end(&prefix);
+                continue;
+            }
+
+            let mut summaries: BTreeMap<Vec<u8>, EventSummary> = BTreeMap::new();
+            let mut topic_keys = Vec::new();
+            for (key, event) in &chunk {
+                let epoch = event.timestamp / epoch_secs;
+                let mut summary_key = prefix.clone();
+                summary_key.extend_from_slice(&epoch.to_be_bytes());
+                summaries
+                    .entry(summary_key)
+                    .or_insert_with(|| EventSummary {
+                        contract: contract.clone(),
+                        epoch,
+                        epoch_secs,
+                        ..Default::default()
+                    })
+                    .add(event);
+                for (index, topic) in event.topics.iter().enumerate() {
+                    let mut topic_key = Self::event_topic_prefix(&contract, index as u8, topic);
+                    topic_key.extend_from_slice(&key[nul + 1..]);
+                    topic_keys.push(topic_key);
+                }
+            }
+            (&tree, &topic_tree, &summary_tree)
+                .transaction(|(tx_events, tx_topics, tx_summaries)| {
+                    for (key, _) in &chunk {
+                        tx_events.remove(key)?;
+                    }
+                    for key in &topic_keys {
+                        tx_topics.remove(key.as_slice())?;
+                    }
+                    for (key, delta) in &summaries {
+                        let stored = tx_summaries
+                            .get(key)?
+                            .and_then(|v| serde_json::from_slice::<EventSummary>(&v).ok());
+                        let summary = match stored {
+                            Some(mut summary) => {
+                                summary.merge(delta);
+                                summary
+                            }
+                            None => delta.clone(),
+                        };
+                        let json = serde_json::to_vec(&summary).unwrap_or_default();
+                        tx_summaries.insert(key.as_slice(), json)?;
+                    }
+                    Ok(())
+                })
+                .map_err(|e: sled::transaction::TransactionError<()>| {
+                    format!("Event rollup failed: {:?}", e)
+                })?;
+            pruned += chunk.len() as u64;
+        }
+        Ok(pruned)
+    }
+
+    /// Per-epoch summaries of `contract`'s pruned events, oldest first
+    pub fn event_summaries(&self, contract: &str) -> Result<Vec<EventSummary>, String> {
+        let prefix = Self::event_contract_prefix(contract);
+        self.event_summaries_tree()?
+            .scan_prefix(&prefix)
+            .map(|item| {
+                let (_, value) =
+                    item.map_err(|e| format!("Failed to read event summaries: {}", e))?;
+                serde_json::from_slice(&value).map_err(|e| format!("Invalid event summary: {}", e))
+            })
+            .collect()
+    }
+
+    // --- API Keys (public RPC tiers) ---
+
+    fn api_keys_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_API_KEYS)
+            .map_err(|e| format!("Failed to open api keys tree: {}", e))
+    }
+
+    /// Insert or update an API key record
+    pub fn save_api_key(&self, record: &ApiKeyRecord) -> Result<(), String> {
+        let json =
+            serde_json::to_vec(record).map_err(|e| format!("Failed to encode api key: {}", e))?;
+        let tree = self.api_keys_tree()?;
+        tree.insert(record.id.as_bytes(), json)
+            .map_err(|e| format!("Failed to save api key: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush api keys: {}", e))?;
+        Ok(())
+    }
+
+    /// Delete a revoked API key
+    pub fn remove_api_key(&self, id: &str) -> Result<(), String> {
+        let tree = self.api_keys_tree()?;
+        tree.remove(id.as_bytes())
+            .map_err(|e| format!("Failed to remove api key: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush api keys: {}", e))?;
+        Ok(())
+    }
+
+    /// All stored API keys (undecodable entries are skipped)
+    pub fn load_api_keys(&self) -> Result<Vec<ApiKeyRecord>, String> {
+        let tree = self.api_keys_tree()?;
+        let mut out = Vec::new();
+        for item in tree.iter() {
+            let (_, value) = item.map_err(|e| format!("Failed to read api key: {}", e))?;
+            if let Ok(record) = serde_json::from_slice(&value) {
+                out.push(record);
+            }
+        }
+        Ok(out)
+    }
+
+    // --- Contract source verification (source_registry.rs) ---
+
+    fn contract_sources_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_CONTRACT_SOURCES)
+            .map_err(|e| format!("Failed to open contract sources tree: {}", e))
+    }
+
+    /// Insert or replace the source record of a contract
+    pub fn save_contract_source(&self, record: &SourceRecord) -> Result<(), String> {
+        let json = serde_json::to_vec(record)
+            .map_err(|e| format!("Failed to encode source record: {}", e))?;
+        let tree = self.contract_sources_tree()?;
+        tree.insert(record.address.as_bytes(), json)
+            .map_err(|e| format!("Failed to save source record: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush contract sources: {}", e))?;
+        Ok(())
+    }
+
+    pub fn get_contract_source(&self, address: &str) -> Result<Option<SourceRecord>, String> {
+        let tree = self.contract_sources_tree()?;
+        match tree
+            .get(address.as_bytes())
+            .map_err(|e| format!("Failed to read source record: {}", e))?
+        {
+            Some(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| format!("Failed to decode source record: {}", e)),
+            None => Ok(None),
+        }
+    }
+
+    /// All stored source records (undecodable entries are skipped)
+    pub fn load_contract_sources(&self) -> Result<Vec<SourceRecord>, String> {
+        let tree = self.contract_sources_tree()?;
+        let mut out = Vec::new();
+        for item in tree.iter() {
+            let (_, value) = item.map_err(|e| format!("Failed to read source record: {}", e))?;
+            if let Ok(record) = serde_json::from_slice(&value) {
+                out.push(record);
+            }
+        }
+        Ok(out)
+    }
+
+    // --- Invoices (invoices.rs) ---
+
+    fn invoices_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_INVOICES)
+            .map_err(|e| format!("Failed to open invoices tree: {}", e))
+    }
+
+    /// Insert or update an invoice
+    pub fn save_invoice(&self, invoice: &Invoice) -> Result<(), String> {
+        let json =
+            serde_json::to_vec(invoice).map_err(|e| format!("Failed to encode invoice: {}", e))?;
+        let tree = self.invoices_tree()?;
+        tree.insert(invoice.id.as_bytes(), json)
+            .map_err(|e| format!("Failed to save invoice: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush invoices: {}", e))?;
+        Ok(())
+    }
+
+    pub fn remove_invoice(&self, id: &str) -> Result<(), String> {
+        self.invoices_tree()?
+            .remove(id.as_bytes())
+            .map_err(|e| format!("Failed to remove invoice: {}", e))?;
+        Ok(())
+    }
+
+    /// All stored invoices (undecodable entries are skipped)
+    pub fn load_invoices(&self) -> Result<Vec<Invoice>, String> {
+        let tree = self.invoices_tree()?;
+        let mut out = Vec::new();
+        for item in tree.iter() {
+            let (_, value) = item.map_err(|e| format!("Failed to read invoice: {}", e))?;
+            if let Ok(invoice) = serde_json::from_slice(&value) {
+                out.push(invoice);
+            }
+        }
+        Ok(out)
+    }
+
+    // --- Address book labels (address_labels.rs) ---
+
+    fn address_labels_tree(&self) -> Result<Tree, String> {
+        self.db
+            .open_tree(TREE_ADDRESS_LABELS)
+            .map_err(|e| format!("Failed to open address labels tree: {}", e))
+    }
+
+    pub fn save_address_label(&self, address: &str, label: &str) -> Result<(), String> {
+        let tree = self.address_labels_tree()?;
+        tree.insert(address.as_bytes(), label.as_bytes())
+            .map_err(|e| format!("Failed to save address label: {}", e))?;
+        tree.flush()
+            .map_err(|e| format!("Failed to flush address labels: {}", e))?;
+        Ok(())
+    }
+
+    pub fn remove_address_label(&self, address: &str) -> Result<(), String> {
+        self.address_labels_tree()?
+            .remove(address.as_bytes())
+            .map_err(|e| format!("Failed to remove address label: {}", e))?;
+        Ok(())
+    }
+
+    /// All stored labels (non-UTF-8 entries are skipped)
+    pub fn load_address_labels(
+        &self,
+    ) -> Result<std::collections::BTreeMap<String, String>, String> {
+        let tree = self.address_labels_tree()?;
+        let mut out = std::collections::BTreeMap::new();
+        for item in tree.iter() {
+            let (key, value) = item.map_err(|e| format!("Failed to read address label: {}", e))?;
+            if let (Ok(address), Ok(label)) = (
+                String::from_utf8(key.to_vec()),
+                String::from_utf8(value.to_vec()),
+            ) {
+                out.insert(address, label);
+            }
+        }
+        Ok(out)
+    }
+
+    // --- Archive snapshots (archive.rs) ---
+
+    fn archive_tree(&self, name: &str) -> Result<Tree, String> {
+        self.db
+            .open_tree(name)
+            .map_err(|e| format!("Failed to open {} tree: {}", name, e))
+    }
+
+    /// Key: id ‖ 0x00 ‖ height (big-endian) — sorts by height within an id
+    fn archive_key(id: &str, height: u64) -> Vec<u8> {
+        let mut key = Vec::with_capacity(id.len() + 9);
+        key.extend_from_slice(id.as_bytes());
+        key.push(0);
+        key.extend_from_slice(&height.to_be_bytes());
+        key
+    }
+
+    /// Store the entries that changed at `height`. The height is recorded
+    /// last, so a crash mid-write leaves no visible partial snapshot.
+    pub fn save_archive_snapshot(
+        &self,
+        height: u64,
+        accounts: &[(String, AccountState)],
+        contracts: &[(String, ContractSnapshot)],
+    ) -> Result<(), String> {
+        let mut batch = sled::Batch::default();
+        for (addr, state) in accounts {
+            let json = serde_json::to_vec(state)
+                .map_err(|e| format!("Failed to encode archived account: {}", e))?;
+            batch.insert(Self::archive_key(addr, height), json);
+        }
+        self.archive_tree(TREE_ARCHIVE_ACCOUNTS)?
+            .apply_batch(batch)
+            .map_err(|e| format!("Failed to archive accounts: {}", e))?;
+
+        let mut batch = sled::Batch::default();
+        for (addr, snap) in contracts {
+            let json = serde_json::to_vec(snap)
+                .map_err(|e| format!("Failed to encode archived contract: {}", e))?;
+            batch.insert(Self::archive_key(addr, height), json);
+        }
+        self.archive_tree(TREE_ARCHIVE_CONTRACTS)?
+            .apply_batch(batch)
+            .map_err(|e| format!("Failed to archive contracts: {}", e))?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let heights = self.archive_tree(TREE_ARCHIVE_HEIGHTS)?;
+        heights
+            .insert(height.to_be_bytes(), &now.to_be_bytes())
+            .map_err(|e| format!("Failed to record archive height: {}", e))?;
+        heights
+            .flush()
+            .map_err(|e| format!("Failed to flush archive: {}", e))?;
+        Ok(())
+    }
+
+    /// Newest snapshot height, if any
+    pub fn latest_archive_height(&self) -> Result<Option<u64>, String> {
+        self.archive_snapshot_at(u64::MAX)
+    }
+
+    /// Greatest snapshot height ≤ `height`
+    fn archive_snapshot_at(&self, height: u64) -> Result<Option<u64>, String> {
+        let last = self
+            .archive_tree(TREE_ARCHIVE_HEIGHTS)?
+            .range(..=height.to_be_bytes())
+            .next_back()
+            .transpose()
+            .map_err(|e| format!("Failed to read archive heights: {}", e))?;
+        Ok(last.and_then(|(key, _)| key.as_ref().try_into().ok().map(u64::from_be_bytes)))
+    }
+
+    /// Value of `id` at the snapshot ≤ `height`: None if no snapshot is that
+    /// old, Some((snapshot, None)) if `id` did not exist yet
+    fn archive_lookup<T: serde::de::DeserializeOwned>(
+        &self,
+        tree: &str,
+        id: &str,
+        height: u64,
+    ) -> Result<Option<(u64, Option<T>)>, String> {
+        let Some(snapshot) = self.archive_snapshot_at(height)? else {
+            return Ok(None);
+        };
+        let entry = self
+            .archive_tree(tree)?
+            .range(Self::archive_key(id, 0)..=Self::archive_key(id, snapshot))
+            .next_back()
+            .transpose()
+            .map_err(|e| format!("Failed to read archive: {}", e))?;
+        let value = match entry {
+            Some((_, json)) => Some(
+                serde_json::from_slice(&json)
+                    .map_err(|e| format!("Corrupt archive entry: {}", e))?,
+            ),
+            None => None,
+        };
+        Ok(Some((snapshot, value)))
+    }
+
+    /// Account state at the snapshot ≤ `height` (see archive_lookup)
+    pub fn archive_account_at(
+        &self,
+        addr: &str,
+        height: u64,
+    ) -> Result<Option<(u64, Option<AccountState>)>, String> {
+        self.archive_lookup(TREE_ARCHIVE_ACCOUNTS, addr, height)
+    }
+
+    /// Contract balance + state at the snapshot ≤ `height` (see archive_lookup)
+    pub fn archive_contract_at(
+        &self,
+        addr: &str,
+        height: u64,
+    ) -> Result<Option<(u64, Option<ContractSnapshot>)>, String> {
+        self.archive_lookup(TREE_ARCHIVE_CONTRACTS, addr, height)
+    }
+
     /// Remove a peer from persistent storage
-    #[allow(dead_code)]
     pub fn remove_peer(&self, short_addr: &str) -> Result<(), String> {
         let tree = self.peers_tree()?;
         tree.remove(short_addr.as_bytes())
@@ -699,6 +1775,8 @@ mod tests {
             work: 0,
             timestamp: 1234567890,
             fee: 0,
+            valid_after: 0,
+            expires_at: 0,
         };
 
         // Save
@@ -767,4 +1845,264 @@ mod tests {
         // Cleanup
         std::fs::remove_dir_all("test_db_stats").ok();
     }
+
+    #[test]
+    fn test_oracle_history_roundtrip_and_prune() {
+        let db = LosDatabase::open("test_db_oracle").unwrap();
+        let obs = |symbol: &str, price: u64, timestamp: u64| OracleObservation {
+            symbol: symbol.to_string(),
+            price_micro_usd: price,
+            confidence_bps: 9_900,
+            source_count: 3,
+            timestamp,
+        };
+        for t in [100, 200, 300] {
+            db.save_oracle_observation(&obs("LOS", t * 10, t)).unwrap();
+        }
+        // Different symbol sharing a prefix must not leak into "LOS" ranges
+        db.save_oracle_observation(&obs("LOSX", 1, 250)).unwrap();
+
+        let all = db.get_oracle_history("LOS", 0, 100).unwrap();
+        assert_eq!(
+            all.iter().map(|o| o.timestamp).collect::<Vec<_>>(),
+            vec![100, 200, 300]
+        );
+        // since + limit keep the newest entries, oldest first
+        let recent = db.get_oracle_history("LOS", 150, 1).unwrap();
+        assert_eq!(recent, vec![obs("LOS", 3_000, 300)]);
+
+        assert_eq!(db.prune_oracle_history("LOS", 250).unwrap(), 2);
+        assert_eq!(db.get_oracle_history("LOS", 0, 100).unwrap().len(), 1);
+        assert_eq!(db.get_oracle_history("LOSX", 0, 100).unwrap().len(), 1);
+
+        std::fs::remove_dir_all("test_db_oracle").ok();
+    }
+
+    #[test]
+    fn test_counterparty_index() {
+        let db = LosDatabase::open("test_db_tx_index").unwrap();
+        let send = |from: &str, to: &str, ts: u64| Block {
+            account: from.to_string(),
+            previous: "0".to_string(),
+            link: to.to_string(),
+            block_type: BlockType::Send,
+            amount: 1,
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: ts,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let mut ledger = Ledger::new();
+        ledger
+            .blocks
+            .insert("h1".to_string(), send("alice", "bob", 100));
+        ledger
+            .blocks
+            .insert("h2".to_string(), send("alice", "carol", 200));
+        db.save_ledger(&ledger).unwrap();
+        // Second save indexes only the new block (no duplicate entries)
+        ledger
+            .blocks
+            .insert("h3".to_string(), send("dave", "bob", 300));
+        let mut receive = send("bob", "h1", 400);
+        receive.block_type = BlockType::Receive;
+        ledger.blocks.insert("h4".to_string(), receive);
+        db.save_ledger(&ledger).unwrap();
+
+        let q = |from, to, since| db.query_transactions(from, to, since, 100).unwrap();
+        assert_eq!(q(Some("alice"), None, 0), vec!["h2", "h1"]);
+        assert_eq!(q(None, Some("bob"), 0), vec!["h3", "h1"]);
+        assert_eq!(q(Some("alice"), Some("bob"), 0), vec!["h1"]);
+        assert_eq!(q(None, Some("bob"), 150), vec!["h3"]);
+        assert_eq!(
+            db.query_transactions(None, Some("bob"), 0, 1).unwrap(),
+            vec!["h3"]
+        );
+        assert!(db.query_transactions(None, None, 0, 10).is_err());
+
+        // Backfill is a no-op once the index is complete
+        assert_eq!(db.ensure_tx_index(&ledger).unwrap(), 3);
+        assert_eq!(db.ensure_tx_index(&ledger).unwrap(), 0);
+        assert_eq!(q(Some("dave"), None, 0), vec!["h3"]);
+
+        std::fs::remove_dir_all("test_db_tx_index").ok();
+    }
+
+    #[test]
+    fn test_contract_event_topic_queries() {
+        let db = LosDatabase::open("test_db_events").unwrap();
+        let event = |contract: &str, kind: &str, topics: &[&str], ts: u64| ContractEvent {
+            contract: contract.to_string(),
+            event_type: kind.to_string(),
+            data: Default::default(),
+            timestamp: ts,
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        db.save_contract_events(
+            "call1",
+            &[
+                event("LOSConA", "Transfer", &["alice", "bob"], 100),
+                ContractEvent {
+                    event_index: 1,
+                    ..event("LOSConA", "Approval", &["alice", "carol"], 100)
+                },
+            ],
+        )
+        .unwrap();
+        db.save_contract_events(
+            "call2",
+            &[
+                event("LOSConA", "Transfer", &["carol", "bob"], 200),
+                event("LOSConAB", "Transfer", &["alice", "bob"], 300),
+            ],
+        )
+        .unwrap();
+
+        let q = |filter: EventFilter, limit| {
+            db.query_contract_events("LOSConA", &filter, limit)
+                .unwrap()
+                .into_iter()
+                .map(|e| (e.block_hash, e.event_type, e.topics.join(",")))
+                .collect::<Vec<_>>()
+        };
+        let topics = |t0: Option<&str>, t1: Option<&str>| EventFilter {
+            topics: [t0.map(String::from), t1.map(String::from), None, None],
+            ..Default::default()
+        };
+
+        assert_eq!(q(EventFilter::default(), 10).len(), 3);
+        let to_bob = q(topics(None, Some("bob")), 10);
+        assert_eq!(to_bob.len(), 2);
+        assert_eq!(to_bob[0].2, "carol,bob"); // newest first
+        let from_alice = q(topics(Some("alice"), None), 10);
+        assert_eq!(from_alice.len(), 2);
+        let transfer_from_alice = EventFilter {
+            event_type: Some("Transfer".to_string()),
+            ..topics(Some("alice"), Some("bob"))
+        };
+        assert_eq!(
+            q(transfer_from_alice, 10),
+            vec![(
+                "call1".to_string(),
+                "Transfer".to_string(),
+                "alice,bob".to_string()
+            )]
+        );
+        let since = EventFilter {
+            since: 150,
+            ..topics(None, Some("bob"))
+        };
+        assert_eq!(q(since, 10).len(), 1);
+        assert_eq!(q(EventFilter::default(), 1).len(), 1);
+
+        std::fs::remove_dir_all("test_db_events").ok();
+    }
+
+    #[test]
+    fn test_contract_events_keep_total_order_and_ignore_replays() {
+        let db = LosDatabase::open("test_db_event_order").unwrap();
+        let event = |kind: &str, call_index: u32, event_index: u32| ContractEvent {
+            contract: "LOSConA".to_string(),
+            event_type: kind.to_string(),
+            timestamp: 100,
+            topics: vec!["t".to_string()],
+            call_index,
+            event_index,
+            ..Default::default()
+        };
+        // Same second, two blocks; hook events follow the call's events
+        let block_b = [event("b0", 0, 0), event("b1", 0, 1), event("b2", 1, 0)];
+        let block_a = [event("a0", 0, 0), event("a1", 0, 1)];
+        db.save_contract_events("bbbb", &block_b).unwrap();
+        db.save_contract_events("aaaa", &block_a).unwrap();
+        // Replayed blocks do not duplicate events
+        db.save_contract_events("bbbb", &block_b).unwrap();
+        db.save_contract_events("aaaa", &block_a).unwrap();
+
+        let newest_first = vec!["b2", "b1", "b0", "a1", "a0"];
+        let kinds = |filter: &EventFilter| {
+            db.query_contract_events("LOSConA", filter, 100)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.event_type)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kinds(&EventFilter::default()), newest_first);
+        let by_topic = EventFilter {
+            topics: [Some("t".to_string()), None, None, None],
+            ..Default::default()
+        };
+        assert_eq!(kinds(&by_topic), newest_first);
+
+        let stored = db
+            .query_contract_events("LOSConA", &EventFilter::default(), 1)
+            .unwrap();
+        assert_eq!(
+            (stored[0].block_hash.as_str(), stored[0].call_index),
+            ("bbbb", 1)
+        );
+
+        std::fs::remove_dir_all("test_db_event_order").ok();
+    }
+
+    #[test]
+    fn test_inflight_journal_roundtrip() {
+        use crate::consensus_journal::VoteProof;
+        let db = LosDatabase::open("test_db_inflight").unwrap();
+        let block = |ts: u64| Block {
+            account: "LOSsender".to_string(),
+            previous: "0".to_string(),
+            block_type: BlockType::Send,
+            amount: 5,
+            link: "LOSrecipient".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: ts,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let vote = |voter: &str| VoteRecord {
+            voter: voter.to_string(),
+            power: 1_000,
+            proof: VoteProof::Single {
+                requester: "LOSsender".to_string(),
+                timestamp: "1".to_string(),
+                signature: "ab".to_string(),
+                public_key: "cd".to_string(),
+            },
+        };
+        let (a, b) = ("aa".repeat(32), "bb".repeat(32));
+        db.save_inflight_send(&a, &block(1)).unwrap();
+        db.save_inflight_send(&b, &block(2)).unwrap();
+        db.save_inflight_vote(&a, &vote("LOSv1")).unwrap();
+        db.save_inflight_vote(&a, &vote("LOSv2")).unwrap();
+        db.save_inflight_vote(&b, &vote("LOSv1")).unwrap();
+        // Same voter again overwrites, it does not add a vote
+        db.save_inflight_vote(&a, &vote("LOSv1")).unwrap();
+
+        let loaded = db.load_inflight_sends().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            (loaded[0].tx_hash.as_str(), loaded[0].votes.len()),
+            (a.as_str(), 2)
+        );
+        assert_eq!(
+            (loaded[1].tx_hash.as_str(), loaded[1].votes.len()),
+            (b.as_str(), 1)
+        );
+        assert_eq!(loaded[1].block.timestamp, 2);
+
+        db.remove_inflight_send(&a).unwrap();
+        let loaded = db.load_inflight_sends().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].tx_hash, b);
+
+        std::fs::remove_dir_all("test_db_inflight").ok();
+    }
 }
@@ -0,0 +1,700 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ADAPTIVE DELTA SYNC
+//
+// Every 15s the node checks its delta sync session (los_network::delta_sync
+// is the wire format):
+//
+//   1. Frontiers — page through a peer's (account, head, block_count) list.
+//      The first request carries our state root; a peer with the same root
+//      answers with nothing and the session ends. Accounts where the peer has
+//      more blocks are queued, with the number of blocks we are missing.
+//   2. Chains — fetch each queued chain after our head, CHAIN_PAGE blocks at
+//      a time, and apply them with process_block(). Blocks that fail (often a
+//      Receive whose Send sits in a chain not fetched yet) are deferred and
+//      retried once every chain is in.
+//
+// The session (cursor, queue, counters) is saved to sled after every page,
+// so a restarted node resumes where it stopped, with whichever peer answers.
+// A request unanswered for STALL_SECS is re-sent to any peer; after
+// MAX_ATTEMPTS the session is dropped and the node falls back to a full
+// SYNC_REQUEST (peers without /los/delta-sync/1 end up there too).
+// Progress is served by GET /sync/progress.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::collections::VecDeque;
+
+use los_core::{Block, Ledger};
+use los_network::delta_sync::{AccountFrontier, DeltaSyncRequest, DeltaSyncResponse};
+use los_network::delta_sync::{CHAIN_PAGE, FRONTIER_PAGE};
+use serde::{Deserialize, Serialize};
+
+/// Seconds without an answer before the request is re-sent to any peer
+pub const STALL_SECS: u64 = 45;
+/// Failed or declined requests before falling back to a full SYNC_REQUEST
+pub const MAX_ATTEMPTS: u32 = 3;
+/// Minimum seconds between the end of one session and the start of the next
+pub const SESSION_INTERVAL_SECS: u64 = 30;
+/// Deferred blocks kept for the final retry (the rest count as rejected)
+const MAX_DEFERRED: usize = 10_000;
+
+// ─────────────────────────────────────────────────────────────
+// RESPONDER
+// ─────────────────────────────────────────────────────────────
+
+/// Answer a delta sync request from the local ledger
+pub fn answer(ledger: &Ledger, request: &DeltaSyncRequest) -> DeltaSyncResponse {
+    match request {
+        DeltaSyncRequest::Frontiers { after, root } => {
+            frontier_page(ledger, after.as_deref(), root.as_deref())
+        }
+        DeltaSyncRequest::Chain {
+            account,
+            after_head,
+        } => chain_page(ledger, account, after_head),
+    }
+}
+
+/// Up to FRONTIER_PAGE frontiers of the accounts sorting after `after`.
+/// The first page carries our state root and account count; it is empty when
+/// `requester_root` matches ours.
+pub fn frontier_page(
+    ledger: &Ledger,
+    after: Option<&str>,
+    requester_root: Option<&str>,
+) -> DeltaSyncResponse {
+    let (state_root, accounts) = match after {
+        None => (
+            Some(ledger.compute_state_root()),
+            ledger.accounts.len() as u64,
+        ),
+        Some(_) => (None, 0),
+    };
+    if state_root.is_some() && state_root.as_deref() == requester_root {
+        return DeltaSyncResponse::Frontiers {
+            after: None,
+            entries: Vec::new(),
+            next: None,
+            state_root,
+            accounts,
+        };
+    }
+    let range = match after {
+        Some(a) => ledger
+            .accounts
+            .range::<str, _>((std::ops::Bound::Excluded(a), std::ops::Bound::Unbounded)),
+        None => ledger.accounts.range::<str, _>(..),
+    };
+    let mut entries: Vec<AccountFrontier> = range
+        .take(FRONTIER_PAGE + 1)
+        .map(|(account, state)| AccountFrontier {
+            account: account.clone(),
+            head: state.head.clone(),
+            block_count: state.block_count,
+        })
+        .collect();
+    let next = if entries.len() > FRONTIER_PAGE {
+        entries.truncate(FRONTIER_PAGE);
+        entries.last().map(|f| f.account.clone())
+    } else {
+        None
+    };
+    DeltaSyncResponse::Frontiers {
+        after: after.map(str::to_string),
+        entries,
+        next,
+        state_root,
+        accounts,
+    }
+}
+
+/// Blocks of `account` after `after_head`, oldest first, at most CHAIN_PAGE.
+/// If `after_head` is not in the chain (unknown or forked), the page starts
+/// at the open block.
+pub fn chain_page(ledger: &Ledger, account: &str, after_head: &str) -> DeltaSyncResponse {
+    let mut hashes = Vec::new();
+    if let Some(state) = ledger.accounts.get(account) {
+        let mut current = state.head.as_str();
+        while current != after_head && current != "0" && !current.is_empty() {
+            let Some(block) = ledger.blocks.get(current) else {
+                break; // Chain broken (pruned) — serve what we have
+            };
+            if hashes.len() as u64 > state.block_count {
+                break; // Cycle guard
+            }
+            hashes.push(current);
+            current = block.previous.as_str();
+        }
+    }
+    hashes.reverse();
+    let more = hashes.len() > CHAIN_PAGE;
+    let blocks = hashes
+        .into_iter()
+        .take(CHAIN_PAGE)
+        .filter_map(|h| ledger.blocks.get(h).cloned())
+        .collect();
+    DeltaSyncResponse::Chain {
+        account: account.to_string(),
+        after_head: after_head.to_string(),
+        blocks,
+        more,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// REQUESTER
+// ─────────────────────────────────────────────────────────────
+
+/// One delta sync run against a peer. Persisted so a restart resumes it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeltaSyncSession {
+    /// Peer serving the session (None = any connected peer)
+    pub peer: Option<String>,
+    pub started_at: u64,
+    /// Last account of the previous frontier page (None = first page)
+    pub cursor: Option<String>,
+    pub frontiers_done: bool,
+    pub accounts_compared: u64,
+    /// Account count the peer reported
+    pub peer_accounts: u64,
+    /// Chains where the peer is ahead, in fetch order
+    pub queue: VecDeque<String>,
+    /// Paging within the chain at the front of the queue: last block received
+    pub chain_cursor: Option<String>,
+    pub chains_done: u64,
+    /// Blocks we lack, from the frontier block counts
+    pub blocks_missing: u64,
+    pub blocks_applied: u64,
+    pub blocks_rejected: u64,
+    /// Blocks that did not apply yet (retried when every chain is in)
+    pub deferred: Vec<Block>,
+    /// Failed/declined requests in a row
+    pub attempts: u32,
+}
+
+impl DeltaSyncSession {
+    /// The request that moves the session forward (None = all chains fetched)
+    pub fn next_request(&self, ledger: &Ledger) -> Option<DeltaSyncRequest> {
+        if !self.frontiers_done {
+            let root = self.cursor.is_none().then(|| ledger.compute_state_root());
+            return Some(DeltaSyncRequest::Frontiers {
+                after: self.cursor.clone(),
+                root,
+            });
+        }
+        let account = self.queue.front()?;
+        let after_head = match &self.chain_cursor {
+            Some(last) => last.clone(),
+            None => ledger
+                .accounts
+                .get(account)
+                .map(|a| a.head.clone())
+                .unwrap_or_else(|| "0".to_string()),
+        };
+        Some(DeltaSyncRequest::Chain {
+            account: account.clone(),
+            after_head,
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.frontiers_done && self.queue.is_empty()
+    }
+}
+
+/// What the node should do after a tick or a response
+#[derive(Debug)]
+pub enum DeltaStep {
+    /// Nothing to send (request in flight, or between sessions)
+    Idle,
+    /// Send this DELTA_REQ: message on the out channel
+    Send(String),
+    /// Every chain is in: retry these deferred blocks, then call finish()
+    Finish(Vec<Block>),
+    /// Delta sync failed — send a full SYNC_REQUEST instead
+    FullSync,
+}
+
+/// Outcome of the last finished session (GET /sync/progress)
+#[derive(Debug, Clone, Serialize)]
+pub struct DeltaSyncSummary {
+    pub finished_at: u64,
+    pub result: &'static str,
+    pub peer: Option<String>,
+    pub accounts_compared: u64,
+    pub chains_fetched: u64,
+    pub blocks_applied: u64,
+    pub blocks_rejected: u64,
+    pub duration_secs: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct DeltaSync {
+    session: Option<DeltaSyncSession>,
+    /// When the request in flight was sent
+    waiting_since: Option<u64>,
+    last: Option<DeltaSyncSummary>,
+    last_finished_at: u64,
+    sessions_completed: u64,
+    full_sync_fallbacks: u64,
+}
+
+impl DeltaSync {
+    /// Continue a session saved before a restart (its peer may be gone)
+    pub fn resume(session: DeltaSyncSession) -> Self {
+        Self {
+            session: Some(DeltaSyncSession {
+                peer: None,
+                attempts: 0,
+                ..session
+            }),
+            ..Self::default()
+        }
+    }
+
+    pub fn session(&self) -> Option<&DeltaSyncSession> {
+        self.session.as_ref()
+    }
+
+    /// Outcome of the last finished session
+    pub fn last(&self) -> Option<&DeltaSyncSummary> {
+        self.last.as_ref()
+    }
+
+    /// Periodic check: start a session, re-send a stalled request, or give up
+    pub fn tick(&mut self, ledger: &Ledger, now: u64) -> DeltaStep {
+        if self.session.is_none() {
+            if now.saturating_sub(self.last_finished_at) < SESSION_INTERVAL_SECS {
+                return DeltaStep::Idle;
+            }
+            self.session = Some(DeltaSyncSession {
+                started_at: now,
+                ..DeltaSyncSession::default()
+            });
+            self.waiting_since = None;
+        }
+        if let Some(sent) = self.waiting_since {
+            if now.saturating_sub(sent) < STALL_SECS {
+                return DeltaStep::Idle;
+            }
+            if self.note_failure(None, now) {
+                return DeltaStep::FullSync;
+            }
+        }
+        self.advance(ledger, now)
+    }
+
+    /// A request failed or was declined. Returns true when the session was
+    /// abandoned (caller falls back to a full sync).
+    pub fn note_failure(&mut self, from_peer: Option<&str>, now: u64) -> bool {
+        let Some(session) = self.session.as_mut() else {
+            return false;
+        };
+        if let (Some(from), Some(peer)) = (from_peer, session.peer.as_deref()) {
+            if from != peer {
+                return false; // Stale answer from an earlier peer
+            }
+        }
+        self.waiting_since = None;
+        session.attempts += 1;
+        session.peer = None; // Next attempt goes to any peer
+        if session.attempts < MAX_ATTEMPTS {
+            return false;
+        }
+        self.full_sync_fallbacks += 1;
+        self.end(now, "fallback_full_sync");
+        true
+    }
+
+    /// Frontier page from `from_peer`: queue the chains where it is ahead.
+    /// `local` gives our (head, block_count) of an account.
+    pub fn on_frontiers(
+        &mut self,
+        from_peer: &str,
+        response: &DeltaSyncResponse,
+        our_root: &str,
+        local: impl Fn(&str) -> Option<(String, u64)>,
+        now: u64,
+    ) -> bool {
+        let DeltaSyncResponse::Frontiers {
+            after,
+            entries,
+            next,
+            state_root,
+            accounts,
+        } = response
+        else {
+            return false;
+        };
+        let Some(session) = self.session.as_mut() else {
+            return false;
+        };
+        if session.frontiers_done || *after != session.cursor {
+            return false; // Not the page we asked for
+        }
+        self.waiting_since = None;
+        session.peer = Some(from_peer.to_string());
+        session.attempts = 0;
+        if after.is_none() {
+            session.peer_accounts = *accounts;
+            if state_root.as_deref() == Some(our_root) {
+                self.end(now, "in_sync");
+                return true;
+            }
+        }
+        for frontier in entries {
+            session.accounts_compared += 1;
+            let ours = local(&frontier.account);
+            let our_count = ours.as_ref().map(|(_, c)| *c).unwrap_or(0);
+            let same_head = ours.as_ref().is_some_and(|(h, _)| *h == frontier.head);
+            if frontier.block_count > our_count && !same_head {
+                session.blocks_missing += frontier.block_count - our_count;
+                session.queue.push_back(frontier.account.clone());
+            }
+        }
+        match next {
+            Some(cursor) => session.cursor = Some(cursor.clone()),
+            None => session.frontiers_done = true,
+        }
+        true
+    }
+
+    /// Chain page from `from_peer`: returns the blocks to apply, oldest first
+    /// (None = not the page we asked for)
+    pub fn on_chain(&mut self, from_peer: &str, response: DeltaSyncResponse) -> Option<Vec<Block>> {
+        let DeltaSyncResponse::Chain {
+            account,
+            blocks,
+            more,
+            ..
+        } = response
+        else {
+            return None;
+        };
+        let session = self.session.as_mut()?;
+        if session.queue.front() != Some(&account) {
+            return None;
+        }
+        self.waiting_since = None;
+        session.peer = Some(from_peer.to_string());
+        session.attempts = 0;
+        let last = blocks.last().map(Block::calculate_hash);
+        match last {
+            Some(last) if more => session.chain_cursor = Some(last),
+            _ => {
+                session.queue.pop_front();
+                session.chain_cursor = None;
+                session.chains_done += 1;
+            }
+        }
+        Some(blocks)
+    }
+
+    /// Count the result of applying a chain page; failed blocks are deferred
+    pub fn record(&mut self, applied: u64, failed: Vec<Block>) {
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        session.blocks_applied += applied;
+        for block in failed {
+            if session.deferred.len() < MAX_DEFERRED {
+                session.deferred.push(block);
+            } else {
+                session.blocks_rejected += 1;
+            }
+        }
+    }
+
+    /// Next request of the session, or Finish when every chain is in
+    pub fn advance(&mut self, ledger: &Ledger, now: u64) -> DeltaStep {
+        let Some(session) = self.session.as_mut() else {
+            return DeltaStep::Idle;
+        };
+        if session.is_done() {
+            return DeltaStep::Finish(std::mem::take(&mut session.deferred));
+        }
+        match session.next_request(ledger) {
+            Some(request) => {
+                let msg = request.to_message(session.peer.as_deref());
+                self.waiting_since = Some(now);
+                DeltaStep::Send(msg)
+            }
+            None => DeltaStep::Idle,
+        }
+    }
+
+    /// Close the session after the deferred retry
+    pub fn finish(&mut self, applied: u64, rejected: u64, now: u64) {
+        if let Some(session) = self.session.as_mut() {
+            session.blocks_applied += applied;
+            session.blocks_rejected += rejected;
+        }
+        self.sessions_completed += 1;
+        self.end(now, "complete");
+    }
+
+    fn end(&mut self, now: u64, result: &'static str) {
+        if let Some(s) = self.session.take() {
+            self.last = Some(DeltaSyncSummary {
+                finished_at: now,
+                result,
+                peer: s.peer,
+                accounts_compared: s.accounts_compared,
+                chains_fetched: s.chains_done,
+                blocks_applied: s.blocks_applied,
+                blocks_rejected: s.blocks_rejected,
+                duration_secs: now.saturating_sub(s.started_at),
+            });
+        }
+        self.waiting_since = None;
+        self.last_finished_at = now;
+    }
+
+    /// JSON for GET /sync/progress
+    pub fn progress(&self, now: u64) -> serde_json::Value {
+        let current = self.session.as_ref().map(|s| {
+            let fetched = s.blocks_applied + s.deferred.len() as u64 + s.blocks_rejected;
+            serde_json::json!({
+                "peer": s.peer,
+                "phase": if s.frontiers_done { "chains" } else { "frontiers" },
+                "started_at": s.started_at,
+                "elapsed_secs": now.saturating_sub(s.started_at),
+                "accounts_compared": s.accounts_compared,
+                "peer_accounts": s.peer_accounts,
+                "chains_queued": s.queue.len(),
+                "chains_fetched": s.chains_done,
+                "blocks_missing": s.blocks_missing,
+                "blocks_fetched": fetched,
+                "blocks_applied": s.blocks_applied,
+                "blocks_deferred": s.deferred.len(),
+                "blocks_rejected": s.blocks_rejected,
+                "percent": (fetched.min(s.blocks_missing) * 100)
+                    .checked_div(s.blocks_missing)
+                    .unwrap_or(0),
+                "attempts": s.attempts,
+            })
+        });
+        serde_json::json!({
+            "active": current.is_some(),
+            "current": current,
+            "last": self.last,
+            "sessions_completed": self.sessions_completed,
+            "full_sync_fallbacks": self.full_sync_fallbacks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::{AccountState, BlockType};
+
+    fn block(account: &str, previous: &str, ts: u64) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount: 1,
+            link: "LOSdest".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: ts,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    /// Ledger with one chain of `len` blocks per account (no validation)
+    fn ledger(chains: &[(&str, usize)]) -> Ledger {
+        let mut l = Ledger::new();
+        for (account, len) in chains {
+            let mut head = "0".to_string();
+            for i in 0..*len {
+                let b = block(account, &head, 1_000 + i as u64);
+                head = b.calculate_hash();
+                l.blocks.insert(head.clone(), b);
+            }
+            l.accounts.insert(
+                account.to_string(),
+                AccountState {
+                    head,
+                    balance: 0,
+                    block_count: *len as u64,
+                    is_validator: false,
+                },
+            );
+        }
+        l
+    }
+
+    fn local(l: &Ledger) -> impl Fn(&str) -> Option<(String, u64)> + '_ {
+        |a| l.accounts.get(a).map(|s| (s.head.clone(), s.block_count))
+    }
+
+    #[test]
+    fn test_chain_page_returns_blocks_after_head_in_order() {
+        let peer = ledger(&[("LOSa", 5)]);
+        let ours = ledger(&[("LOSa", 2)]);
+        let our_head = ours.accounts["LOSa"].head.clone();
+        let DeltaSyncResponse::Chain { blocks, more, .. } = chain_page(&peer, "LOSa", &our_head)
+        else {
+            panic!("expected chain page");
+        };
+        assert!(!more);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].previous, our_head);
+        assert_eq!(blocks[2].calculate_hash(), peer.accounts["LOSa"].head);
+
+        // Unknown head (fork) → from the open block; long chains are paged
+        let long = ledger(&[("LOSa", CHAIN_PAGE + 3)]);
+        let DeltaSyncResponse::Chain { blocks, more, .. } = chain_page(&long, "LOSa", "ff") else {
+            panic!("expected chain page");
+        };
+        assert!(more);
+        assert_eq!(blocks.len(), CHAIN_PAGE);
+        assert_eq!(blocks[0].previous, "0");
+    }
+
+    #[test]
+    fn test_frontier_pages_cover_all_accounts() {
+        let names: Vec<String> = (0..FRONTIER_PAGE + 5)
+            .map(|i| format!("LOS{:05}", i))
+            .collect();
+        let chains: Vec<(&str, usize)> = names.iter().map(|n| (n.as_str(), 1)).collect();
+        let l = ledger(&chains);
+        let DeltaSyncResponse::Frontiers {
+            entries,
+            next,
+            state_root,
+            accounts,
+            ..
+        } = frontier_page(&l, None, None)
+        else {
+            panic!("expected frontiers");
+        };
+        assert_eq!(entries.len(), FRONTIER_PAGE);
+        assert_eq!(accounts, names.len() as u64);
+        assert!(state_root.is_some());
+        let DeltaSyncResponse::Frontiers { entries, next, .. } =
+            frontier_page(&l, next.as_deref(), None)
+        else {
+            panic!("expected frontiers");
+        };
+        assert_eq!(entries.len(), 5);
+        assert!(next.is_none());
+
+        // Same root → empty first page
+        let root = l.compute_state_root();
+        let DeltaSyncResponse::Frontiers { entries, .. } = frontier_page(&l, None, Some(&root))
+        else {
+            panic!("expected frontiers");
+        };
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_session_fetches_only_missing_chains() {
+        let peer = ledger(&[("LOSa", 4), ("LOSb", 2), ("LOSc", 1)]);
+        let ours = ledger(&[("LOSa", 1), ("LOSb", 2)]);
+        let our_root = ours.compute_state_root();
+        let mut ds = DeltaSync::default();
+
+        let DeltaStep::Send(msg) = ds.tick(&ours, 1_000) else {
+            panic!("session should start");
+        };
+        let (_, req) = DeltaSyncRequest::from_message(&msg).unwrap();
+        // Request in flight → nothing new until it stalls
+        assert!(matches!(ds.tick(&ours, 1_010), DeltaStep::Idle));
+
+        let resp = answer(&peer, &req);
+        assert!(ds.on_frontiers("peer1", &resp, &our_root, local(&ours), 1_011));
+        let s = ds.session().unwrap();
+        assert_eq!(s.queue, ["LOSa", "LOSc"]); // LOSb is level
+        assert_eq!(s.blocks_missing, 4);
+
+        let mut fetched = 0;
+        loop {
+            match ds.advance(&ours, 1_012) {
+                DeltaStep::Send(msg) => {
+                    let (peer_id, req) = DeltaSyncRequest::from_message(&msg).unwrap();
+                    assert_eq!(peer_id.as_deref(), Some("peer1"));
+                    let blocks = ds.on_chain("peer1", answer(&peer, &req)).unwrap();
+                    fetched += blocks.len();
+                    ds.record(blocks.len() as u64, Vec::new());
+                }
+                DeltaStep::Finish(deferred) => {
+                    assert!(deferred.is_empty());
+                    ds.finish(0, 0, 1_013);
+                    break;
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        assert_eq!(fetched, 4);
+        let progress = ds.progress(1_013);
+        assert_eq!(progress["active"], false);
+        assert_eq!(progress["last"]["result"], "complete");
+        assert_eq!(progress["last"]["chains_fetched"], 2);
+        // Next session waits SESSION_INTERVAL_SECS
+        assert!(matches!(ds.tick(&ours, 1_020), DeltaStep::Idle));
+    }
+
+    #[test]
+    fn test_matching_roots_end_the_session() {
+        let l = ledger(&[("LOSa", 3)]);
+        let mut ds = DeltaSync::default();
+        let DeltaStep::Send(msg) = ds.tick(&l, 100) else {
+            panic!("session should start");
+        };
+        let (_, req) = DeltaSyncRequest::from_message(&msg).unwrap();
+        let resp = answer(&l, &req);
+        assert!(ds.on_frontiers("p", &resp, &l.compute_state_root(), local(&l), 101));
+        assert!(ds.session().is_none());
+        assert_eq!(ds.progress(101)["last"]["result"], "in_sync");
+    }
+
+    #[test]
+    fn test_stalls_fall_back_to_full_sync_and_sessions_resume() {
+        let l = ledger(&[("LOSa", 1)]);
+        let mut ds = DeltaSync::default();
+        let t = 1_000;
+        assert!(matches!(ds.tick(&l, t), DeltaStep::Send(_)));
+        assert!(matches!(ds.tick(&l, t + STALL_SECS), DeltaStep::Send(_)));
+        assert!(!ds.note_failure(None, t + STALL_SECS + 1));
+        assert!(matches!(
+            ds.tick(&l, t + STALL_SECS + 2),
+            DeltaStep::Send(_)
+        ));
+        assert!(matches!(
+            ds.tick(&l, t + 3 * STALL_SECS),
+            DeltaStep::FullSync
+        ));
+        assert!(ds.session().is_none());
+        assert_eq!(ds.progress(0)["full_sync_fallbacks"], 1);
+
+        // A saved session resumes at its cursor with any peer
+        let saved = DeltaSyncSession {
+            peer: Some("gone".to_string()),
+            frontiers_done: true,
+            queue: VecDeque::from(["LOSa".to_string()]),
+            chain_cursor: Some("abc".to_string()),
+            attempts: 2,
+            ..DeltaSyncSession::default()
+        };
+        let json = serde_json::to_string(&saved).unwrap();
+        let mut ds = DeltaSync::resume(serde_json::from_str(&json).unwrap());
+        let DeltaStep::Send(msg) = ds.tick(&l, 10) else {
+            panic!("resumed session should send");
+        };
+        let (peer, req) = DeltaSyncRequest::from_message(&msg).unwrap();
+        assert!(peer.is_none());
+        assert_eq!(
+            req,
+            DeltaSyncRequest::Chain {
+                account: "LOSa".to_string(),
+                after_head: "abc".to_string()
+            }
+        );
+    }
+}
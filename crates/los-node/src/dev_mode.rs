@@ -0,0 +1,182 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - LOCAL DEV CHAIN (los-node --dev)
+//
+// Single-node chain for dapp and contract development:
+// - Level 1 testnet config: no consensus round, blocks finalize on submission
+// - Node identity + pre-funded accounts derived from a PUBLIC dev mnemonic
+// - PoW mining difficulty pinned to 0 (every mining nonce is valid)
+// - No bootstrap dialing — the node never joins testnet
+//
+// POST /dev/fork?from=<host:port> replaces local state with a snapshot of a
+// running testnet/mainnet node (ledger via GET /sync/full, contracts via
+// GET /sync/contracts), then re-funds the dev accounts, so contracts can be
+// tested against real DEX/token state.
+//
+// ⚠️ The dev mnemonic is public. Never send real funds to these addresses.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::{AccountState, Ledger, CIL_PER_LOS};
+
+/// Well-known dev mnemonic (BIP39 test vector — PUBLIC, dev chains only)
+pub const DEV_MNEMONIC: &str = "test test test test test test test test test test test junk";
+/// Number of pre-funded dev accounts
+pub const DEV_ACCOUNT_COUNT: u32 = 10;
+/// Balance of each dev account
+pub const DEV_ACCOUNT_BALANCE_CIL: u128 = 1_000_000 * CIL_PER_LOS;
+/// Mining difficulty on the dev chain
+pub const DEV_MINING_DIFFICULTY_BITS: u32 = 0;
+
+/// Keypair for dev account `index`.
+///
+/// Account 0 uses the standard empty BIP39 passphrase, so importing
+/// DEV_MNEMONIC into the wallet yields the node's own (pre-funded) account.
+/// Account N > 0 uses passphrase `los-dev-N`.
+pub fn dev_keypair(index: u32) -> Result<los_crypto::KeyPair, String> {
+    let mnemonic = bip39::Mnemonic::parse_normalized(DEV_MNEMONIC)
+        .map_err(|e| format!("Invalid dev mnemonic: {}", e))?;
+    let passphrase = if index == 0 {
+        String::new()
+    } else {
+        format!("los-dev-{}", index)
+    };
+    Ok(los_crypto::generate_keypair_from_seed(
+        &mnemonic.to_seed(&passphrase),
+    ))
+}
+
+/// Addresses of all dev accounts (index order)
+pub fn dev_addresses() -> Result<Vec<String>, String> {
+    (0..DEV_ACCOUNT_COUNT)
+        .map(|i| dev_keypair(i).map(|kp| los_crypto::public_key_to_address(&kp.public_key)))
+        .collect()
+}
+
+/// Ensure every address holds at least DEV_ACCOUNT_BALANCE_CIL.
+/// Missing accounts are created (like genesis wallets); poorer ones are
+/// topped up. Returns how many accounts changed.
+pub fn fund_accounts(ledger: &mut Ledger, addresses: &[String]) -> usize {
+    let mut changed = 0;
+    for addr in addresses {
+        match ledger.accounts.get_mut(addr) {
+            Some(acc) if acc.balance >= DEV_ACCOUNT_BALANCE_CIL => {}
+            Some(acc) => {
+                acc.balance = DEV_ACCOUNT_BALANCE_CIL;
+                changed += 1;
+            }
+            None => {
+                ledger.accounts.insert(
+                    addr.clone(),
+                    AccountState {
+                        head: "0".to_string(),
+                        balance: DEV_ACCOUNT_BALANCE_CIL,
+                        block_count: 0,
+                        is_validator: false,
+                    },
+                );
+                changed += 1;
+            }
+        }
+    }
+    changed
+}
+
+/// Normalize the `from` parameter of POST /dev/fork into `host:port`.
+pub fn parse_fork_source(from: &str) -> Result<String, String> {
+    let host = from
+        .trim()
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    if host.is_empty() {
+        return Err("Missing 'from' (host:port of the node to fork)".to_string());
+    }
+    if host.contains('/') || host.contains(char::is_whitespace) || host.contains("://") {
+        return Err(format!(
+            "Invalid fork source '{}': expected host:port",
+            from
+        ));
+    }
+    Ok(host.to_string())
+}
+
+/// Download a node's contract state via GET /sync/contracts.
+/// Returns `None` if the node does not serve it (older version).
+pub async fn fetch_peer_contracts(peer_host: &str) -> Result<Option<Vec<u8>>, String> {
+    let client = crate::peer_http_client(peer_host)?;
+    let url = format!("http://{}/sync/contracts", peer_host);
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {} from peer", resp.status()));
+    }
+    let body = resp
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read response body: {}", e))?;
+
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    const MAX_DECOMPRESSED: u64 = 500 * 1024 * 1024;
+    let mut data = Vec::new();
+    GzDecoder::new(&body[..])
+        .take(MAX_DECOMPRESSED)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Decompression failed: {}", e))?;
+    Ok(Some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dev_accounts_deterministic_and_distinct() {
+        let a = dev_addresses().unwrap();
+        assert_eq!(a.len(), DEV_ACCOUNT_COUNT as usize);
+        assert_eq!(a, dev_addresses().unwrap());
+        let unique: std::collections::BTreeSet<&String> = a.iter().collect();
+        assert_eq!(unique.len(), a.len());
+        assert!(a.iter().all(|addr| addr.starts_with("LOS")));
+    }
+
+    #[test]
+    fn test_fund_accounts_inserts_and_tops_up() {
+        let mut ledger = Ledger::new();
+        let addrs = vec!["LOSdevA".to_string(), "LOSdevB".to_string()];
+        ledger.accounts.insert(
+            "LOSdevB".to_string(),
+            AccountState {
+                head: "h".to_string(),
+                balance: 5,
+                block_count: 3,
+                is_validator: false,
+            },
+        );
+        assert_eq!(fund_accounts(&mut ledger, &addrs), 2);
+        assert_eq!(ledger.accounts["LOSdevA"].balance, DEV_ACCOUNT_BALANCE_CIL);
+        assert_eq!(ledger.accounts["LOSdevB"].balance, DEV_ACCOUNT_BALANCE_CIL);
+        assert_eq!(ledger.accounts["LOSdevB"].block_count, 3);
+        // Idempotent
+        assert_eq!(fund_accounts(&mut ledger, &addrs), 0);
+    }
+
+    #[test]
+    fn test_parse_fork_source() {
+        assert_eq!(
+            parse_fork_source("http://node.example:3030/").unwrap(),
+            "node.example:3030"
+        );
+        assert_eq!(
+            parse_fork_source("abc.onion:3030").unwrap(),
+            "abc.onion:3030"
+        );
+        assert!(parse_fork_source("").is_err());
+        assert!(parse_fork_source("https://x:1").is_err());
+        assert!(parse_fork_source("host:1/sync/full").is_err());
+    }
+}
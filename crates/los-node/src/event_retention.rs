@@ -0,0 +1,204 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CONTRACT EVENT RETENTION
+//
+// Every applied ContractCall adds its events (and topic index entries) to
+// sled, forever. Explorer nodes want that history; lean validators do not.
+//
+// With LOS_EVENT_KEEP_CHECKPOINTS=N (> 0) a background task (every
+// LOS_EVENT_GC_SECS) keeps full events only from the timestamp of the N-th
+// newest finalized checkpoint on. Older events are rolled up into
+// per-contract, per-epoch summaries (event count per type, first/last
+// timestamp; epoch length LOS_EVENT_ROLLUP_EPOCH_SECS, default the reward
+// epoch) and deleted together with their topic index entries.
+//
+// The default (0) keeps every event. Summaries are served by
+// GET /contract/:addr/events/summary; GET /contract/:addr/events reports the
+// cutoff as `pruned_before`.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::checkpoint::CheckpointManager;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::db::LosDatabase;
+use crate::metrics::LosMetrics;
+
+const DEFAULT_GC_SECS: u64 = 3_600;
+
+/// Event retention settings (from environment)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRetentionConfig {
+    /// Checkpoints of full event detail to keep (0 = keep everything)
+    pub keep_checkpoints: usize,
+    pub gc_secs: u64,
+    /// Length of a summary epoch
+    pub epoch_secs: u64,
+}
+
+impl EventRetentionConfig {
+    /// Read LOS_EVENT_KEEP_CHECKPOINTS, LOS_EVENT_GC_SECS and
+    /// LOS_EVENT_ROLLUP_EPOCH_SECS
+    pub fn from_env() -> Self {
+        Self::from_vars(|k| std::env::var(k).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Self {
+        let num = |k: &str| get(k).and_then(|v| v.trim().parse::<u64>().ok());
+        Self {
+            keep_checkpoints: num("LOS_EVENT_KEEP_CHECKPOINTS").unwrap_or(0) as usize,
+            gc_secs: num("LOS_EVENT_GC_SECS")
+                .filter(|s| *s > 0)
+                .unwrap_or(DEFAULT_GC_SECS),
+            epoch_secs: num("LOS_EVENT_ROLLUP_EPOCH_SECS")
+                .filter(|s| *s > 0)
+                .unwrap_or_else(|| los_core::chain_spec::active().reward_epoch_secs.max(1)),
+        }
+    }
+}
+
+/// Timestamp before which events are rolled up: that of the `keep`-th
+/// newest checkpoint (`checkpoint_times` ascending). None while fewer
+/// checkpoints exist or retention is off.
+pub fn cutoff(checkpoint_times: &[u64], keep: usize) -> Option<u64> {
+    if keep == 0 || checkpoint_times.len() < keep {
+        return None;
+    }
+    Some(checkpoint_times[checkpoint_times.len() - keep])
+}
+
+/// One retention pass. Returns the number of events rolled up.
+pub fn run_pass(
+    cfg: &EventRetentionConfig,
+    database: &LosDatabase,
+    checkpoints: &Mutex<CheckpointManager>,
+) -> Result<u64, String> {
+    let times: Vec<u64> = crate::safe_lock(checkpoints)
+        .get_all_checkpoints()
+        .map_err(|e| format!("Failed to read checkpoints: {}", e))?
+        .iter()
+        .map(|cp| cp.timestamp)
+        .collect();
+    match cutoff(&times, cfg.keep_checkpoints) {
+        Some(ts) => database.prune_contract_events(ts, cfg.epoch_secs),
+        None => Ok(0),
+    }
+}
+
+/// Background task; returns immediately when retention is off
+pub async fn run_event_retention(
+    cfg: EventRetentionConfig,
+    database: Arc<LosDatabase>,
+    checkpoints: Arc<Mutex<CheckpointManager>>,
+    metrics: Arc<LosMetrics>,
+) {
+    if cfg.keep_checkpoints == 0 {
+        return;
+    }
+    println!(
+        "🗂️  Event retention: full events for the last {} checkpoints, older ones rolled up per {}s epoch (every {}s)",
+        cfg.keep_checkpoints, cfg.epoch_secs, cfg.gc_secs
+    );
+    let mut interval = tokio::time::interval(Duration::from_secs(cfg.gc_secs));
+    loop {
+        interval.tick().await;
+        let (cfg_b, db_b, cp_b) = (cfg.clone(), database.clone(), checkpoints.clone());
+        match tokio::task::spawn_blocking(move || run_pass(&cfg_b, &db_b, &cp_b)).await {
+            Ok(Ok(0)) => {}
+            Ok(Ok(pruned)) => {
+                metrics.contract_events_pruned_total.inc_by(pruned);
+                println!(
+                    "🗂️  Rolled up {} contract events into epoch summaries",
+                    pruned
+                );
+            }
+            Ok(Err(e)) => eprintln!("⚠️ Event retention pass failed: {}", e),
+            Err(e) => eprintln!("⚠️ Event retention task panicked: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_vm::ContractEvent;
+
+    #[test]
+    fn test_config_and_cutoff() {
+        let cfg = EventRetentionConfig::from_vars(|k| match k {
+            "LOS_EVENT_KEEP_CHECKPOINTS" => Some("2".to_string()),
+            "LOS_EVENT_ROLLUP_EPOCH_SECS" => Some("100".to_string()),
+            _ => None,
+        });
+        assert_eq!(cfg.keep_checkpoints, 2);
+        assert_eq!(cfg.epoch_secs, 100);
+        assert_eq!(cfg.gc_secs, DEFAULT_GC_SECS);
+
+        assert_eq!(cutoff(&[10, 20, 30], 2), Some(20));
+        assert_eq!(cutoff(&[10, 20, 30], 3), Some(10));
+        assert_eq!(cutoff(&[10, 20, 30], 4), None);
+        assert_eq!(cutoff(&[10, 20, 30], 0), None);
+    }
+
+    #[test]
+    fn test_rollup_prunes_old_events_once() {
+        let path = "test_db_event_rollup";
+        let db = LosDatabase::open(path).unwrap();
+        let event = |kind: &str, ts: u64, idx: u32| ContractEvent {
+            contract: "LOSConA".to_string(),
+            event_type: kind.to_string(),
+            timestamp: ts,
+            topics: vec!["alice".to_string()],
+            event_index: idx,
+            ..Default::default()
+        };
+        let old = [
+            event("Transfer", 100, 0),
+            event("Transfer", 150, 1),
+            event("Approval", 250, 2),
+        ];
+        db.save_contract_events("old", &old).unwrap();
+        db.save_contract_events("new", &[event("Transfer", 400, 0)])
+            .unwrap();
+
+        assert_eq!(db.prune_contract_events(300, 200).unwrap(), 3);
+        // A second pass (or an older cutoff) finds nothing left
+        assert_eq!(db.prune_contract_events(300, 200).unwrap(), 0);
+        assert_eq!(db.event_gc_cutoff().unwrap(), 300);
+
+        let remaining = db
+            .query_contract_events("LOSConA", &Default::default(), 10)
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        let by_topic = crate::db::EventFilter {
+            topics: [Some("alice".to_string()), None, None, None],
+            ..Default::default()
+        };
+        assert_eq!(
+            db.query_contract_events("LOSConA", &by_topic, 10)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let summaries = db.event_summaries("LOSConA").unwrap();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!((summaries[0].epoch, summaries[0].events), (0, 2));
+        assert_eq!(summaries[0].by_type["Transfer"], 2);
+        assert_eq!(
+            (summaries[0].first_timestamp, summaries[0].last_timestamp),
+            (100, 150)
+        );
+        assert_eq!(
+            (summaries[1].epoch, summaries[1].by_type["Approval"]),
+            (1, 1)
+        );
+
+        // Replaying the old block does not resurrect (and later re-count) events
+        db.save_contract_events("old", &old).unwrap();
+        assert_eq!(db.prune_contract_events(301, 200).unwrap(), 0);
+        assert_eq!(db.event_summaries("LOSConA").unwrap()[0].events, 2);
+
+        drop(db);
+        std::fs::remove_dir_all(path).ok();
+    }
+}
@@ -0,0 +1,364 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - EXPLORER STATS AGGREGATOR
+//
+// Backs GET /stats/overview, GET /blocks/recent and GET /accounts/top.
+// A background task folds new blocks into hourly buckets every
+// EXPLORER_REFRESH_SECS. It never rescans history: it remembers the last
+// head it saw for each account and walks back only to that head. Handlers
+// read the cached views. None of them walks the ledger on a request.
+//
+// - tx counts and active accounts come from block timestamps (per-account
+//   chain blocks only; orphans are never visited)
+// - gas comes from deltas of the VM's in-memory ContractStats, so it
+//   counts from node start and is bucketed by the time it is sampled
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::ledger_view::LedgerView;
+use los_core::{BlockType, Ledger};
+use los_vm::{StatsSort, WasmEngine};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Aggregator tick interval
+pub const EXPLORER_REFRESH_SECS: u64 = 10;
+/// Blocks kept for GET /blocks/recent
+pub const MAX_RECENT_BLOCKS: usize = 500;
+/// Accounts kept per ordering for GET /accounts/top
+pub const MAX_TOP_ACCOUNTS: usize = 100;
+/// Hourly buckets kept (7 days → daily series length)
+const BUCKET_RETENTION_HOURS: u64 = 7 * 24;
+
+/// One block as shown in the recent-blocks list
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecentBlock {
+    pub hash: String,
+    pub account: String,
+    pub block_type: String,
+    pub amount: u128,
+    pub timestamp: u64,
+}
+
+/// Activity over a time range
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ActivitySummary {
+    /// Start of the range (UNIX seconds)
+    pub start: u64,
+    pub tx_count: u64,
+    pub active_accounts: usize,
+    pub gas_used: u64,
+}
+
+/// Cached response body for GET /stats/overview
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatsOverview {
+    pub updated_at: u64,
+    pub total_accounts: usize,
+    pub total_blocks: u64,
+    pub total_contracts: usize,
+    /// Rolling 24 hours (whole hourly buckets)
+    pub last_24h: ActivitySummary,
+    /// One entry per UTC day, oldest first (up to 7)
+    pub daily: Vec<ActivitySummary>,
+}
+
+/// Row in GET /accounts/top
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopAccount {
+    pub address: String,
+    pub balance_cil: u128,
+    pub block_count: u64,
+    pub is_validator: bool,
+}
+
+/// Ordering for GET /accounts/top
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopBy {
+    Balance,
+    Blocks,
+}
+
+impl TopBy {
+    /// Parse `balance` / `blocks` (unknown → None)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "balance" => Some(TopBy::Balance),
+            "blocks" | "activity" => Some(TopBy::Blocks),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct HourBucket {
+    tx_count: u64,
+    gas_used: u64,
+    active: HashSet<String>,
+}
+
+/// Incrementally maintained explorer views
+#[derive(Debug, Default)]
+pub struct ExplorerStats {
+    /// account → head already folded into the buckets
+    heads: HashMap<String, String>,
+    /// hour index (timestamp / 3600) → activity
+    hours: BTreeMap<u64, HourBucket>,
+    /// (timestamp, hash) → block, newest MAX_RECENT_BLOCKS only
+    recent: BTreeMap<(u64, String), RecentBlock>,
+    /// Total VM gas at the previous tick (None before the first tick)
+    last_gas_total: Option<u64>,
+    overview: StatsOverview,
+    top_by_balance: Vec<TopAccount>,
+    top_by_blocks: Vec<TopAccount>,
+}
+
+impl ExplorerStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold everything that changed since the last call.
+    pub fn update(&mut self, ledger: &Ledger, gas_total: u64, total_contracts: usize, now: u64) {
+        let oldest_hour = (now / 3_600).saturating_sub(BUCKET_RETENTION_HOURS - 1);
+
+        // 1. New blocks: walk each moved head back to the one seen last tick
+        let mut accounts: Vec<TopAccount> = Vec::with_capacity(ledger.accounts.len());
+        for (addr, acct) in &ledger.accounts {
+            accounts.push(TopAccount {
+                address: addr.clone(),
+                balance_cil: acct.balance,
+                block_count: acct.block_count,
+                is_validator: acct.is_validator,
+            });
+            let seen = self.heads.get(addr);
+            if seen == Some(&acct.head) {
+                continue;
+            }
+            let mut current = acct.head.clone();
+            while current != "0" && !current.is_empty() && Some(&current) != seen {
+                let Some(blk) = ledger.blocks.get(&current) else {
+                    break;
+                };
+                let hour = blk.timestamp / 3_600;
+                if hour >= oldest_hour {
+                    let bucket = self.hours.entry(hour).or_default();
+                    bucket.tx_count += 1;
+                    bucket.active.insert(blk.account.clone());
+                }
+                self.recent.insert(
+                    (blk.timestamp, current.clone()),
+                    RecentBlock {
+                        hash: current.clone(),
+                        account: blk.account.clone(),
+                        block_type: block_type_name(&blk.block_type),
+                        amount: blk.amount,
+                        timestamp: blk.timestamp,
+                    },
+                );
+                current = blk.previous.clone();
+            }
+            self.heads.insert(addr.clone(), acct.head.clone());
+        }
+        while self.recent.len() > MAX_RECENT_BLOCKS {
+            self.recent.pop_first();
+        }
+
+        // 2. Gas used since the previous tick lands in the current hour
+        let gas_delta = match self.last_gas_total {
+            Some(prev) => gas_total.saturating_sub(prev),
+            None => gas_total,
+        };
+        self.last_gas_total = Some(gas_total);
+        if gas_delta > 0 {
+            self.hours.entry(now / 3_600).or_default().gas_used += gas_delta;
+        }
+        self.hours.retain(|h, _| *h >= oldest_hour);
+
+        // 3. Cached views
+        let first_day = (oldest_hour * 3_600) / 86_400;
+        let daily = (first_day..=now / 86_400)
+            .map(|day| self.summarize(day * 24, day * 24 + 24))
+            .filter(|s| s.tx_count > 0 || s.gas_used > 0)
+            .collect();
+        let this_hour = now / 3_600;
+        self.overview = StatsOverview {
+            updated_at: now,
+            total_accounts: ledger.accounts.len(),
+            total_blocks: ledger.total_chain_blocks(),
+            total_contracts,
+            last_24h: self.summarize(this_hour.saturating_sub(23), this_hour + 1),
+            daily,
+        };
+
+        accounts.sort_by(|a, b| {
+            b.balance_cil
+                .cmp(&a.balance_cil)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        self.top_by_balance = accounts.iter().take(MAX_TOP_ACCOUNTS).cloned().collect();
+        accounts.sort_by(|a, b| {
+            b.block_count
+                .cmp(&a.block_count)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        accounts.truncate(MAX_TOP_ACCOUNTS);
+        self.top_by_blocks = accounts;
+    }
+
+    /// Sum hourly buckets in [from_hour, to_hour)
+    fn summarize(&self, from_hour: u64, to_hour: u64) -> ActivitySummary {
+        let mut summary = ActivitySummary {
+            start: from_hour * 3_600,
+            ..Default::default()
+        };
+        let mut active: HashSet<&str> = HashSet::new();
+        for bucket in self.hours.range(from_hour..to_hour).map(|(_, b)| b) {
+            summary.tx_count += bucket.tx_count;
+            summary.gas_used += bucket.gas_used;
+            active.extend(bucket.active.iter().map(String::as_str));
+        }
+        summary.active_accounts = active.len();
+        summary
+    }
+
+    pub fn overview(&self) -> &StatsOverview {
+        &self.overview
+    }
+
+    /// Newest first
+    pub fn recent_blocks(&self, limit: usize) -> Vec<RecentBlock> {
+        self.recent.values().rev().take(limit).cloned().collect()
+    }
+
+    pub fn top_accounts(&self, by: TopBy, limit: usize) -> Vec<TopAccount> {
+        let list = match by {
+            TopBy::Balance => &self.top_by_balance,
+            TopBy::Blocks => &self.top_by_blocks,
+        };
+        list.iter().take(limit).cloned().collect()
+    }
+}
+
+fn block_type_name(t: &BlockType) -> String {
+    format!("{:?}", t).to_lowercase()
+}
+
+/// Refresh the shared stats every EXPLORER_REFRESH_SECS, forever.
+pub async fn run_stats_aggregator(
+    stats: Arc<Mutex<ExplorerStats>>,
+    view: Arc<LedgerView>,
+    engine: Arc<WasmEngine>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(EXPLORER_REFRESH_SECS));
+    loop {
+        interval.tick().await;
+        let gas_total = engine
+            .top_contracts(StatsSort::Gas, usize::MAX)
+            .iter()
+            .fold(0u64, |acc, (_, s)| acc.saturating_add(s.total_gas));
+        let total_contracts = engine.contract_count().unwrap_or(0);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        // Walk a snapshot: the live ledger is never locked here
+        let l = view.current();
+        crate::safe_lock(&stats).update(&l, gas_total, total_contracts, now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::{AccountState, Block};
+
+    const NOW: u64 = 1_771_200_000;
+
+    fn push(ledger: &mut Ledger, account: &str, hash: &str, ts: u64, balance: u128) {
+        let previous = ledger
+            .accounts
+            .get(account)
+            .map(|a| a.head.clone())
+            .unwrap_or_else(|| "0".to_string());
+        ledger.blocks.insert(
+            hash.to_string(),
+            Block {
+                account: account.to_string(),
+                previous,
+                block_type: BlockType::Send,
+                amount: 1,
+                link: String::new(),
+                signature: String::new(),
+                public_key: String::new(),
+                work: 0,
+                timestamp: ts,
+                fee: 0,
+                valid_after: 0,
+                expires_at: 0,
+            },
+        );
+        let acct = ledger
+            .accounts
+            .entry(account.to_string())
+            .or_insert(AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            });
+        acct.head = hash.to_string();
+        acct.balance = balance;
+        acct.block_count += 1;
+    }
+
+    #[test]
+    fn test_incremental_update_counts_each_block_once() {
+        let mut ledger = Ledger::new();
+        push(&mut ledger, "alice", "a1", NOW - 2 * 86_400, 50);
+        push(&mut ledger, "alice", "a2", NOW - 60, 40);
+        push(&mut ledger, "bob", "b1", NOW - 30, 90);
+
+        let mut stats = ExplorerStats::new();
+        stats.update(&ledger, 1_000, 2, NOW);
+        let o = stats.overview().clone();
+        assert_eq!(o.total_blocks, 3);
+        assert_eq!(o.total_contracts, 2);
+        assert_eq!(o.last_24h.tx_count, 2);
+        assert_eq!(o.last_24h.active_accounts, 2);
+        assert_eq!(o.last_24h.gas_used, 1_000);
+        assert_eq!(o.daily.iter().map(|d| d.tx_count).sum::<u64>(), 3);
+
+        // Second tick: one new block, gas delta only
+        push(&mut ledger, "alice", "a3", NOW + 5, 30);
+        stats.update(&ledger, 1_500, 2, NOW + 10);
+        let o = stats.overview();
+        assert_eq!(o.last_24h.tx_count, 3);
+        assert_eq!(o.last_24h.gas_used, 1_500);
+
+        // Unchanged ledger → nothing new
+        stats.update(&ledger, 1_500, 2, NOW + 20);
+        assert_eq!(stats.overview().last_24h.tx_count, 3);
+
+        let recent: Vec<String> = stats.recent_blocks(2).into_iter().map(|b| b.hash).collect();
+        assert_eq!(recent, vec!["a3", "b1"]);
+    }
+
+    #[test]
+    fn test_top_accounts_orderings() {
+        let mut ledger = Ledger::new();
+        push(&mut ledger, "alice", "a1", NOW, 10);
+        push(&mut ledger, "alice", "a2", NOW, 10);
+        push(&mut ledger, "bob", "b1", NOW, 99);
+        let mut stats = ExplorerStats::new();
+        stats.update(&ledger, 0, 0, NOW);
+
+        let by_balance = stats.top_accounts(TopBy::Balance, 10);
+        assert_eq!(by_balance[0].address, "bob");
+        let by_blocks = stats.top_accounts(TopBy::Blocks, 1);
+        assert_eq!(by_blocks.len(), 1);
+        assert_eq!(by_blocks[0].address, "alice");
+        assert_eq!(TopBy::parse("activity"), Some(TopBy::Blocks));
+        assert_eq!(TopBy::parse("gas"), None);
+    }
+}
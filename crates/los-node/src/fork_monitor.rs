@@ -0,0 +1,276 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ACCOUNT CHAIN FORK DETECTION
+//
+// Each account chain is a linked list: every block names its predecessor in
+// `previous`. Two validly signed blocks with the same account and the same
+// `previous` are a fork. The owner signed two different successors, which is
+// what a double-spend looks like. The ledger keeps whichever arrived first;
+// this module records the pair so the conflict is not silently dropped.
+//
+//   - detect(): incoming block vs. the local chain (BLOCK_CONFIRMED, CONFIRM_REQ
+//     and plain block gossip all call observe())
+//   - FORK_ALERT:<base64 json>: both signed blocks, gossiped once per new
+//     conflict. Receivers re-verify the evidence before recording it, so an
+//     alert cannot be forged against an honest account.
+//   - GET /forks lists recorded conflicts (newest first)
+//
+// Both blocks must carry a valid signature and PoW from a key that derives
+// to the account. That makes the pair self-contained slashing evidence.
+// The registry is in-memory and bounded (MAX_TRACKED_FORKS).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use base64::Engine as _;
+use los_core::{Block, Ledger};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Gossip prefix for fork evidence
+pub const FORK_ALERT_PREFIX: &str = "FORK_ALERT:";
+/// Registry cap; the oldest conflict is evicted beyond this
+pub const MAX_TRACKED_FORKS: usize = 1_000;
+
+/// Two conflicting successors of one block, ordered by hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkEvidence {
+    pub account: String,
+    pub previous: String,
+    pub blocks: [Block; 2],
+}
+
+impl ForkEvidence {
+    /// Validate a pair of blocks as fork evidence
+    pub fn new(a: Block, b: Block) -> Result<Self, String> {
+        if a.account != b.account {
+            return Err("Blocks belong to different accounts".to_string());
+        }
+        if a.previous != b.previous {
+            return Err("Blocks do not share a previous hash".to_string());
+        }
+        let (hash_a, hash_b) = (a.calculate_hash(), b.calculate_hash());
+        if hash_a == hash_b {
+            return Err("Blocks are identical".to_string());
+        }
+        for (hash, block) in [(&hash_a, &a), (&hash_b, &b)] {
+            let pk = hex::decode(&block.public_key).unwrap_or_default();
+            if los_crypto::public_key_to_address(&pk) != block.account {
+                return Err(format!("Block {} is not signed by the account owner", hash));
+            }
+            if !block.verify_signature() {
+                return Err(format!("Block {} has an invalid signature", hash));
+            }
+            if !block.verify_pow() {
+                return Err(format!("Block {} has invalid PoW", hash));
+            }
+        }
+        let blocks = if hash_a < hash_b { [a, b] } else { [b, a] };
+        Ok(Self {
+            account: blocks[0].account.clone(),
+            previous: blocks[0].previous.clone(),
+            blocks,
+        })
+    }
+
+    /// Registry key: one conflict per (account, previous)
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.account, self.previous)
+    }
+
+    /// Gossip form: FORK_ALERT:<base64(json)>
+    pub fn to_message(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        format!(
+            "{}{}",
+            FORK_ALERT_PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(json)
+        )
+    }
+
+    /// Parse and fully re-verify a FORK_ALERT message
+    pub fn from_message(data: &str) -> Result<Self, String> {
+        let payload = data
+            .strip_prefix(FORK_ALERT_PREFIX)
+            .ok_or("Not a FORK_ALERT message")?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| format!("Invalid base64: {}", e))?;
+        let claimed: ForkEvidence =
+            serde_json::from_slice(&bytes).map_err(|e| format!("Invalid evidence: {}", e))?;
+        let [a, b] = claimed.blocks;
+        Self::new(a, b)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForkConflict {
+    #[serde(flatten)]
+    pub evidence: ForkEvidence,
+    /// Unix seconds when this node first saw the conflict
+    pub detected_at: u64,
+    /// Where the second block came from (block_confirmed, confirm_req, gossip, fork_alert)
+    pub source: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ForkMonitor {
+    conflicts: BTreeMap<String, ForkConflict>,
+}
+
+impl ForkMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a conflict. Returns false if this (account, previous) is already known.
+    pub fn record(&mut self, evidence: ForkEvidence, source: &str, now: u64) -> bool {
+        let key = evidence.key();
+        if self.conflicts.contains_key(&key) {
+            return false;
+        }
+        if self.conflicts.len() >= MAX_TRACKED_FORKS {
+            let oldest = self
+                .conflicts
+                .iter()
+                .min_by_key(|(_, c)| c.detected_at)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.conflicts.remove(&oldest);
+            }
+        }
+        self.conflicts.insert(
+            key,
+            ForkConflict {
+                evidence,
+                detected_at: now,
+                source: source.to_string(),
+            },
+        );
+        true
+    }
+
+    /// Conflicts newest first, optionally for one account
+    pub fn list(&self, account: Option<&str>, limit: usize) -> Vec<&ForkConflict> {
+        let mut out: Vec<&ForkConflict> = self
+            .conflicts
+            .values()
+            .filter(|c| account.is_none_or(|a| c.evidence.account == a))
+            .collect();
+        out.sort_by_key(|c| std::cmp::Reverse(c.detected_at));
+        out.truncate(limit);
+        out
+    }
+
+    pub fn count(&self) -> usize {
+        self.conflicts.len()
+    }
+}
+
+/// Check an incoming block against the local chain of its account
+pub fn detect(ledger: &Ledger, incoming: &Block) -> Option<ForkEvidence> {
+    let (_, existing) = ledger.find_fork(incoming)?;
+    ForkEvidence::new(existing.clone(), incoming.clone()).ok()
+}
+
+/// detect() + record(). Returns the FORK_ALERT message to gossip when the
+/// conflict is new to this node.
+pub fn observe(
+    monitor: &Mutex<ForkMonitor>,
+    ledger: &Ledger,
+    incoming: &Block,
+    source: &str,
+) -> Option<String> {
+    let evidence = detect(ledger, incoming)?;
+    let msg = evidence.to_message();
+    let (account, previous) = (evidence.account.clone(), evidence.previous.clone());
+    if !crate::safe_lock(monitor).record(evidence, source, now_secs()) {
+        return None;
+    }
+    println!(
+        "🚨 FORK RECORDED: account {} signed two successors of {} (via {})",
+        crate::get_short_addr(&account),
+        crate::get_short_addr(&previous),
+        source
+    );
+    Some(msg)
+}
+
+/// Handle a gossiped FORK_ALERT. Ok(true) when the conflict was new here.
+pub fn accept_alert(monitor: &Mutex<ForkMonitor>, data: &str) -> Result<bool, String> {
+    let evidence = ForkEvidence::from_message(data)?;
+    let account = evidence.account.clone();
+    let is_new = crate::safe_lock(monitor).record(evidence, "fork_alert", now_secs());
+    if is_new {
+        println!(
+            "🚨 FORK ALERT from peer: account {} has conflicting signed blocks",
+            crate::get_short_addr(&account)
+        );
+    }
+    Ok(is_new)
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::BlockType;
+
+    fn unsigned(previous: &str, amount: u128) -> Block {
+        Block {
+            account: "LOSforker".to_string(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount,
+            link: "LOSvictim".to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: 1_700_000_000,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_evidence_rejects_non_forks_and_unsigned_pairs() {
+        let a = unsigned("prev", 1);
+        // Same block twice is not a fork
+        assert!(ForkEvidence::new(a.clone(), a.clone()).is_err());
+        // Different predecessors are not a fork
+        assert!(ForkEvidence::new(a.clone(), unsigned("other", 2)).is_err());
+        // A real conflict without owner signatures is not evidence
+        let err = ForkEvidence::new(a, unsigned("prev", 2)).unwrap_err();
+        assert!(err.contains("not signed by the account owner"));
+        assert!(ForkEvidence::from_message("FORK_ALERT:!!").is_err());
+    }
+
+    #[test]
+    fn test_registry_dedups_and_evicts_oldest() {
+        let evidence = |previous: &str| ForkEvidence {
+            account: "LOSforker".to_string(),
+            previous: previous.to_string(),
+            blocks: [unsigned(previous, 1), unsigned(previous, 2)],
+        };
+        let mut monitor = ForkMonitor::new();
+        assert!(monitor.record(evidence("p0"), "gossip", 10));
+        assert!(!monitor.record(evidence("p0"), "fork_alert", 11));
+        for i in 1..MAX_TRACKED_FORKS {
+            assert!(monitor.record(evidence(&format!("p{}", i)), "gossip", 10 + i as u64));
+        }
+        assert_eq!(monitor.count(), MAX_TRACKED_FORKS);
+        assert!(monitor.record(evidence("newest"), "gossip", 99_999));
+        assert_eq!(monitor.count(), MAX_TRACKED_FORKS);
+        let listed = monitor.list(Some("LOSforker"), 2);
+        assert_eq!(listed[0].evidence.previous, "newest");
+        assert!(monitor
+            .list(None, usize::MAX)
+            .iter()
+            .all(|c| c.evidence.previous != "p0"));
+    }
+}
@@ -7,6 +7,7 @@
 #![allow(dead_code)]
 
 use crate::{AccountState, CIL_PER_LOS};
+use los_core::units::{Los, Rounding};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -91,6 +92,30 @@ pub fn load_genesis_from_file(path: &str) -> Result<HashMap<String, AccountState
     load_genesis_from_config(&genesis_config)
 }
 
+/// Genesis file for a node started with (or without) `--mainnet`
+pub fn genesis_path_for(mainnet: bool) -> &'static str {
+    if mainnet {
+        "genesis_config.json"
+    } else {
+        "testnet-genesis/testnet_wallets.json"
+    }
+}
+
+/// Resolve the chain spec from `--mainnet` and the optional `chain_spec`
+/// object in the genesis file. A missing file or field means the embedded
+/// mainnet / testnet defaults.
+pub fn load_chain_spec(mainnet: bool) -> Result<los_core::chain_spec::ChainSpec, String> {
+    let path = genesis_path_for(mainnet);
+    let declared = match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str::<serde_json::Value>(&json)
+            .map_err(|e| format!("Failed to parse {}: {}", path, e))?
+            .get("chain_spec")
+            .cloned(),
+        Err(_) => None,
+    };
+    los_core::chain_spec::ChainSpec::resolve(mainnet, declared.as_ref())
+}
+
 /// Resolve the CIL balance from a GenesisWallet.
 /// Prefers balance_cil (integer), falls back to stake_cil, then balance_los (parsed).
 fn resolve_wallet_balance(wallet: &GenesisWallet) -> Result<u128, String> {
@@ -144,30 +169,10 @@ pub fn load_genesis_from_config(
 }
 
 /// Parse LOS amount string to CIL (integer) without f64 precision loss
-/// Handles both integer ("191942") and decimal ("191942.50000000000") formats
+/// Handles both integer ("191942") and decimal ("191942.50000000000") formats;
+/// digits beyond the 11th are truncated
 pub fn parse_los_to_cil(los_str: &str) -> Result<u128, String> {
-    let trimmed = los_str.trim();
-    if let Some(dot_pos) = trimmed.find('.') {
-        // Has decimal part: "123.456" → 123 LOS + fractional
-        let integer_part: u128 = trimmed[..dot_pos]
-            .parse()
-            .map_err(|e| format!("Invalid integer part: {}", e))?;
-        let decimal_str = &trimmed[dot_pos + 1..];
-
-        // Pad or truncate to 11 decimal places (CIL_PER_LOS = 10^11)
-        let padded = format!("{:0<11}", decimal_str);
-        let decimal_cil: u128 = padded[..11]
-            .parse()
-            .map_err(|e| format!("Invalid decimal part: {}", e))?;
-
-        Ok(integer_part * CIL_PER_LOS + decimal_cil)
-    } else {
-        // Integer only: "191942" → 191942 * CIL_PER_LOS
-        let integer_part: u128 = trimmed
-            .parse()
-            .map_err(|e| format!("Invalid amount: {}", e))?;
-        Ok(integer_part * CIL_PER_LOS)
-    }
+    Los::parse(los_str, Rounding::Down).map(|los| los.cil().0)
 }
 
 /// Validate genesis configuration.
@@ -201,11 +206,11 @@ pub fn validate_genesis(config: &GenesisConfig) -> Result<(), String> {
         (Some(n), _) if n == "testnet"
     ) || config.network_id == Some(2);
 
-    if los_core::is_mainnet_build() && is_testnet_genesis {
-        return Err("Cannot load testnet genesis on mainnet build".to_string());
+    if los_core::is_mainnet() && is_testnet_genesis {
+        return Err("Cannot load testnet genesis on a mainnet node".to_string());
     }
-    if !los_core::is_mainnet_build() && is_mainnet_genesis {
-        return Err("Cannot load mainnet genesis on testnet build".to_string());
+    if !los_core::is_mainnet() && is_mainnet_genesis {
+        return Err("Cannot load mainnet genesis on a testnet node".to_string());
     }
 
     // Check timestamp is reasonable (after 2020, before 2100)
@@ -379,7 +384,7 @@ mod tests {
 
     /// Helper: return the network_id matching the current build target
     fn current_network_id() -> u64 {
-        if los_core::is_mainnet_build() {
+        if los_core::is_mainnet() {
             1
         } else {
             2
@@ -388,7 +393,7 @@ mod tests {
 
     /// Helper: return the network string matching the current build target
     fn current_network_str() -> &'static str {
-        if los_core::is_mainnet_build() {
+        if los_core::is_mainnet() {
             "mainnet"
         } else {
             "testnet"
@@ -397,7 +402,7 @@ mod tests {
 
     /// Helper: return the opposite network_id (for mismatch tests)
     fn opposite_network_id() -> u64 {
-        if los_core::is_mainnet_build() {
+        if los_core::is_mainnet() {
             2
         } else {
             1
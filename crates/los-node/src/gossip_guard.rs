@@ -0,0 +1,315 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - GOSSIP PRE-PARSE GUARD
+//
+// Gossipsub accepts messages up to 10 MB (SYNC_GZIP needs it), and most
+// handlers feed the payload straight into serde_json. This guard runs before
+// any handler, on the raw string, in O(n) without allocating:
+// - per-message-type size caps (longest matching prefix; unknown prefixes
+//   get DEFAULT_MAX_BYTES)
+// - shallow schema sniffing: JSON payloads must be an object carrying the
+//   type's required top-level keys; colon/base64 text payloads must not
+//   contain a JSON object
+// - a streaming nesting-depth limit (MAX_JSON_DEPTH) for JSON payloads
+//
+// A rejected message is dropped and counted (los_gossip_rejected_total).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// Deepest JSON nesting any gossip type legitimately uses (+ headroom)
+pub const MAX_JSON_DEPTH: usize = 16;
+/// Cap for message types not listed in GOSSIP_RULES
+pub const DEFAULT_MAX_BYTES: usize = 64 * 1024;
+
+const KIB: usize = 1024;
+const MIB: usize = 1024 * KIB;
+
+/// Keys every serialized Block carries
+const BLOCK_KEYS: &[&str] = &["account", "previous", "block_type", "signature"];
+
+/// Expected payload after the prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Colon/pipe separated fields, hex or base64 — no JSON object
+    Text,
+    /// JSON object with these top-level keys
+    Json(&'static [&'static str]),
+    /// `<tag>:<JSON object>` with these top-level keys
+    TaggedJson(&'static [&'static str]),
+}
+
+/// Size and shape of one gossip message type
+#[derive(Debug, Clone, Copy)]
+pub struct GossipRule {
+    pub prefix: &'static str,
+    pub max_bytes: usize,
+    pub shape: Shape,
+}
+
+const fn rule(prefix: &'static str, max_bytes: usize, shape: Shape) -> GossipRule {
+    GossipRule {
+        prefix,
+        max_bytes,
+        shape,
+    }
+}
+
+/// Known gossip types. Dilithium5 keys/signatures are ~5 KB / ~9 KB in hex.
+pub const GOSSIP_RULES: &[GossipRule] = &[
+    rule("ID:", 4 * KIB, Shape::Text),
+    rule("SYNC_REQUEST:", 4 * KIB, Shape::Text),
+    rule("SYNC_VIA_REST:", 4 * KIB, Shape::Text),
+    rule("SYNC_GZIP:", 10 * MIB, Shape::Text),
+    rule("SYNC_BIN:", 10 * MIB, Shape::Text),
+    rule("SLASH_REQ:", 32 * KIB, Shape::Text),
+    rule("CONFIRM_REQ:", 64 * KIB, Shape::Text),
+    rule("CONFIRM_RES:", 32 * KIB, Shape::Text),
+    rule(
+        "VOTE_BATCH:",
+        128 * KIB,
+        Shape::Json(&["voter", "timestamp", "votes", "signature", "public_key"]),
+    ),
+    rule("VALIDATOR_HEARTBEAT:", 32 * KIB, Shape::Text),
+    rule("VALIDATOR_HEARTBEAT_PROXY:", 32 * KIB, Shape::Text),
+    rule(
+        "VALIDATOR_REG:",
+        32 * KIB,
+        Shape::Json(&["address", "public_key", "signature"]),
+    ),
+    rule("VALIDATOR_UNREG:", 32 * KIB, Shape::Json(&["address"])),
+    rule(
+        "PEX:",
+        64 * KIB,
+        Shape::Json(&["sender", "public_key", "timestamp", "entries", "signature"]),
+    ),
+    rule("PEER_LIST:", 256 * KIB, Shape::Json(&["endpoints"])),
+    rule(
+        "TELEMETRY:",
+        32 * KIB,
+        Shape::Json(&["node", "public_key", "signature"]),
+    ),
+    rule("BLOCK_CONFIRMED:", 128 * KIB, Shape::Text),
+    rule("FORK_ALERT:", 128 * KIB, Shape::Text),
+    // Bytecode (≤ 1 MB) travels base64-encoded next to the deploy block
+    rule("CONTRACT_DEPLOYED:", 2 * MIB, Shape::Text),
+    rule("CONTRACT_CALLED:", 64 * KIB, Shape::Text),
+    rule(
+        "CHECKPOINT_FINAL:",
+        MIB,
+        Shape::Json(&["height", "block_hash"]),
+    ),
+    rule("CHECKPOINT_PROPOSE:", 32 * KIB, Shape::Text),
+    rule("CHECKPOINT_SIGN:", 32 * KIB, Shape::Text),
+    rule(
+        "REWARD_PROPOSE:",
+        MIB,
+        Shape::Json(&["distribution", "signatures"]),
+    ),
+    rule("REWARD_SIGN:", 32 * KIB, Shape::TaggedJson(&["signature"])),
+    rule(
+        "REWARD_ACCRUE:",
+        MIB,
+        Shape::Json(&["distribution", "signatures"]),
+    ),
+    rule("MINE_BLOCK:", 32 * KIB, Shape::Json(BLOCK_KEYS)),
+];
+
+/// Rule for a raw block (JSON without a prefix)
+const RAW_BLOCK: GossipRule = rule("", 32 * KIB, Shape::Json(BLOCK_KEYS));
+
+/// Rule for `data`: longest matching prefix, raw block, or None (unknown)
+pub fn rule_for(data: &str) -> Option<&'static GossipRule> {
+    if data.trim_start().starts_with('{') {
+        return Some(&RAW_BLOCK);
+    }
+    GOSSIP_RULES
+        .iter()
+        .filter(|r| data.starts_with(r.prefix))
+        .max_by_key(|r| r.prefix.len())
+}
+
+/// Check a gossip message before any handler parses it
+pub fn check(data: &str) -> Result<(), String> {
+    let Some(rule) = rule_for(data) else {
+        if data.len() > DEFAULT_MAX_BYTES {
+            return Err(format!(
+                "unknown message type, {} bytes > {}",
+                data.len(),
+                DEFAULT_MAX_BYTES
+            ));
+        }
+        return Ok(());
+    };
+    let kind = rule.prefix.trim_end_matches(':');
+    let kind = if kind.is_empty() { "block" } else { kind };
+    if data.len() > rule.max_bytes {
+        return Err(format!(
+            "{}: {} bytes > {}",
+            kind,
+            data.len(),
+            rule.max_bytes
+        ));
+    }
+    let payload = &data[rule.prefix.len()..];
+    match rule.shape {
+        Shape::Text => {
+            if payload.contains('{') {
+                return Err(format!("{}: unexpected JSON in text payload", kind));
+            }
+            Ok(())
+        }
+        Shape::Json(keys) => sniff_json(payload, MAX_JSON_DEPTH, keys),
+        Shape::TaggedJson(keys) => match payload.split_once(':') {
+            Some((_, json)) => sniff_json(json, MAX_JSON_DEPTH, keys),
+            None => Err("missing tag".to_string()),
+        },
+    }
+    .map_err(|e| {
+        if e.starts_with(kind) {
+            e
+        } else {
+            format!("{}: {}", kind, e)
+        }
+    })
+}
+
+/// One pass over `json`: must be a complete object nested at most
+/// `max_depth` deep, with every key of `required` at the top level.
+/// Keys are compared raw (escaped keys never match).
+pub fn sniff_json(json: &str, max_depth: usize, required: &[&str]) -> Result<(), String> {
+    let bytes = json.as_bytes();
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .ok_or("empty JSON payload")?;
+    if bytes[start] != b'{' {
+        return Err("JSON payload is not an object".to_string());
+    }
+
+    // Up to 64 required keys tracked in a bitmask
+    let mut found: u64 = 0;
+    let all: u64 = if required.len() >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << required.len()) - 1
+    };
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_start = 0usize;
+    // At depth 1, the next string is a key (after '{' or ',')
+    let mut expect_key = false;
+    let mut string_is_key = false;
+
+    for (i, &b) in bytes.iter().enumerate().skip(start) {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                if string_is_key {
+                    let key = &json[string_start..i];
+                    if let Some(k) = required.iter().position(|r| *r == key) {
+                        found |= 1u64 << k.min(63);
+                    }
+                }
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                string_start = i + 1;
+                string_is_key = depth == 1 && expect_key;
+                expect_key = false;
+            }
+            b'{' | b'[' => {
+                if depth == 0 && i != start {
+                    return Err("trailing data after JSON object".to_string());
+                }
+                depth += 1;
+                if depth > max_depth {
+                    return Err(format!("JSON nested deeper than {}", max_depth));
+                }
+                expect_key = depth == 1;
+            }
+            b'}' | b']' => {
+                if depth == 0 {
+                    return Err("unbalanced JSON".to_string());
+                }
+                depth -= 1;
+            }
+            b',' => expect_key = depth == 1,
+            _ => {}
+        }
+    }
+    if in_string || depth != 0 {
+        return Err("truncated JSON".to_string());
+    }
+    if found & all != all {
+        let missing: Vec<&str> = required
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| found & (1u64 << (*k).min(63)) == 0)
+            .map(|(_, r)| *r)
+            .collect();
+        return Err(format!("missing field(s): {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_caps_and_longest_prefix() {
+        assert!(check("ID:LOSabc:100:1771000000").is_ok());
+        let big_id = format!("ID:{}", "a".repeat(5 * KIB));
+        assert!(check(&big_id).unwrap_err().starts_with("ID: "));
+
+        // VALIDATOR_HEARTBEAT_PROXY wins over VALIDATOR_HEARTBEAT
+        let proxy = rule_for("VALIDATOR_HEARTBEAT_PROXY:a:b:c:d:e").unwrap();
+        assert_eq!(proxy.prefix, "VALIDATOR_HEARTBEAT_PROXY:");
+
+        // Unknown types only get the default cap
+        assert!(check("SOMETHING_NEW:x").is_ok());
+        assert!(check(&format!("X:{}", "a".repeat(DEFAULT_MAX_BYTES))).is_err());
+
+        // Text payloads must not smuggle JSON
+        assert!(check("SLASH_REQ:{\"a\":1}").is_err());
+        assert!(check("REWARD_SIGN:abcd")
+            .unwrap_err()
+            .contains("missing tag"));
+    }
+
+    #[test]
+    fn test_schema_sniffing_and_depth_limit() {
+        let pex = r#"PEX:{"sender":"LOSa","public_key":"00","timestamp":1,"entries":[{"address":"LOSb","host":"h","last_seen":1}],"signature":"00"}"#;
+        assert!(check(pex).is_ok());
+        // Nested keys do not satisfy top-level requirements
+        let err = check(
+            r#"PEX:{"x":{"sender":1,"public_key":1,"timestamp":1,"entries":1,"signature":1}}"#,
+        )
+        .unwrap_err();
+        assert!(err.starts_with("PEX: missing field(s): sender"), "{}", err);
+        assert!(check("PEX:[1,2]").unwrap_err().contains("not an object"));
+        assert!(check(r#"PEER_LIST:{"endpoints":[{"a":"}{"}]"#)
+            .unwrap_err()
+            .contains("truncated"));
+        assert!(check(r#"PEER_LIST:{"endpoints":[]}{}"#)
+            .unwrap_err()
+            .contains("trailing"));
+        assert!(check(r#"REWARD_SIGN:ab12:{"signer":"x","signature":"y"}"#).is_ok());
+
+        let deep = format!(
+            r#"{{"account":"a","previous":"0","block_type":"Send","signature":"s","x":{}1{}}}"#,
+            "[".repeat(MAX_JSON_DEPTH),
+            "]".repeat(MAX_JSON_DEPTH)
+        );
+        assert!(check(&deep).unwrap_err().contains("nested deeper"));
+        // Brackets and escaped quotes inside strings are not structure
+        let block = r#"{"account":"a[[[{","previous":"\"}","block_type":"Send","signature":"s"}"#;
+        assert!(check(block).is_ok());
+    }
+}
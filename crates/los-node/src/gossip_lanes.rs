@@ -0,0 +1,249 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - GOSSIP PRIORITY LANES
+//
+// The event loop used to take network events strictly FIFO, so a burst of
+// sync payloads (SYNC_GZIP, delta sync) queued ahead of CONFIRM_RES votes
+// and checkpoint signatures stalled confirmations. Events are now sorted
+// into three bounded lanes and the loop always takes from the most urgent
+// non-empty one:
+//
+// - Consensus: votes, confirm requests, slashing, checkpoint and reward
+//   co-signing
+// - Blocks: confirmed/raw blocks, contract gossip, compact relay and
+//   everything not listed elsewhere (heartbeats, peer exchange, ...)
+// - Bulk: state sync requests and payloads, delta sync
+//
+// A full lane drops its oldest event (counted per lane). A lower lane that
+// was passed over MAX_SKIPS times in a row gets the next turn, so bulk sync
+// still progresses under sustained vote traffic.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::metrics::LosMetrics;
+use los_network::NetworkEvent;
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+
+/// Pops a waiting lower lane may be passed over before it is served
+pub const MAX_SKIPS: u32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Lane {
+    Consensus = 0,
+    Blocks = 1,
+    Bulk = 2,
+}
+
+impl Lane {
+    pub const ALL: [Lane; 3] = [Lane::Consensus, Lane::Blocks, Lane::Bulk];
+
+    /// Metric label
+    pub fn name(self) -> &'static str {
+        match self {
+            Lane::Consensus => "consensus",
+            Lane::Blocks => "blocks",
+            Lane::Bulk => "bulk",
+        }
+    }
+
+    /// Events a lane holds before dropping its oldest
+    pub fn capacity(self) -> usize {
+        match self {
+            Lane::Consensus => 4096,
+            Lane::Blocks => 2048,
+            // SYNC_GZIP / SYNC_BIN payloads are up to 10 MB each
+            Lane::Bulk => 64,
+        }
+    }
+}
+
+/// Gossip prefixes served before everything else
+const CONSENSUS_PREFIXES: &[&str] = &[
+    "CONFIRM_REQ:",
+    "CONFIRM_RES:",
+    "VOTE_BATCH:",
+    "SLASH_REQ:",
+    "FORK_ALERT:",
+    "CHECKPOINT_PROPOSE:",
+    "CHECKPOINT_SIGN:",
+    "CHECKPOINT_FINAL:",
+    "REWARD_PROPOSE:",
+    "REWARD_SIGN:",
+    "REWARD_ACCRUE:",
+];
+
+/// Gossip prefixes served last
+const BULK_PREFIXES: &[&str] = &["SYNC_REQUEST:", "SYNC_VIA_REST:", "SYNC_GZIP:", "SYNC_BIN:"];
+
+/// Lane of a gossip payload (NetworkEvent::NewBlock)
+pub fn lane_for_gossip(data: &str) -> Lane {
+    if CONSENSUS_PREFIXES.iter().any(|p| data.starts_with(p)) {
+        Lane::Consensus
+    } else if BULK_PREFIXES.iter().any(|p| data.starts_with(p)) {
+        Lane::Bulk
+    } else {
+        Lane::Blocks
+    }
+}
+
+/// Lane of any network event
+pub fn lane_for(event: &NetworkEvent) -> Lane {
+    match event {
+        NetworkEvent::NewBlock(data) => lane_for_gossip(data),
+        NetworkEvent::CheckpointSignRequest { .. }
+        | NetworkEvent::CheckpointSignResponse { .. } => Lane::Consensus,
+        NetworkEvent::DeltaSyncRequest { .. }
+        | NetworkEvent::DeltaSyncResponse { .. }
+        | NetworkEvent::DeltaSyncFailed { .. } => Lane::Bulk,
+        _ => Lane::Blocks,
+    }
+}
+
+/// Bounded per-lane queues between the network channel and the event loop
+pub struct LaneQueue<T = NetworkEvent> {
+    lanes: [VecDeque<T>; 3],
+    skipped: [u32; 3],
+}
+
+impl<T> Default for LaneQueue<T> {
+    fn default() -> Self {
+        Self {
+            lanes: [VecDeque::new(), VecDeque::new(), VecDeque::new()],
+            skipped: [0; 3],
+        }
+    }
+}
+
+impl<T> LaneQueue<T> {
+    /// Queue `item`; returns false if the lane was full and its oldest
+    /// event was dropped to make room
+    pub fn push(&mut self, lane: Lane, item: T) -> bool {
+        let queue = &mut self.lanes[lane as usize];
+        let dropped = queue.len() >= lane.capacity() && queue.pop_front().is_some();
+        queue.push_back(item);
+        !dropped
+    }
+
+    /// Next event: most urgent non-empty lane, unless a lower lane has
+    /// been passed over MAX_SKIPS times
+    pub fn pop(&mut self) -> Option<(Lane, T)> {
+        let starved = Lane::ALL.into_iter().rev().find(|l| {
+            self.skipped[*l as usize] >= MAX_SKIPS && !self.lanes[*l as usize].is_empty()
+        });
+        let lane = starved.or_else(|| {
+            Lane::ALL
+                .into_iter()
+                .find(|l| !self.lanes[*l as usize].is_empty())
+        })?;
+        for other in Lane::ALL {
+            let i = other as usize;
+            if other == lane || self.lanes[i].is_empty() {
+                self.skipped[i] = 0;
+            } else if other > lane {
+                self.skipped[i] += 1;
+            }
+        }
+        self.lanes[lane as usize]
+            .pop_front()
+            .map(|item| (lane, item))
+    }
+
+    pub fn len(&self, lane: Lane) -> usize {
+        self.lanes[lane as usize].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(VecDeque::is_empty)
+    }
+}
+
+/// Next event for the node's event loop. Moves everything already waiting
+/// on `rx` into the lanes, then takes the most urgent event; only waits on
+/// `rx` when all lanes are empty (cancel-safe inside `tokio::select!`).
+/// None = channel closed and nothing queued.
+pub async fn next_event(
+    rx: &mut mpsc::Receiver<NetworkEvent>,
+    queue: &mut LaneQueue,
+    metrics: &LosMetrics,
+) -> Option<NetworkEvent> {
+    if queue.is_empty() {
+        let event = rx.recv().await?;
+        enqueue(queue, event, metrics);
+    }
+    while let Ok(event) = rx.try_recv() {
+        enqueue(queue, event, metrics);
+    }
+    let (lane, event) = queue.pop()?;
+    metrics
+        .gossip_lane_processed_total
+        .with_label_values(&[lane.name()])
+        .inc();
+    for l in Lane::ALL {
+        metrics
+            .gossip_lane_depth
+            .with_label_values(&[l.name()])
+            .set(queue.len(l) as i64);
+    }
+    Some(event)
+}
+
+fn enqueue(queue: &mut LaneQueue, event: NetworkEvent, metrics: &LosMetrics) {
+    let lane = lane_for(&event);
+    if !queue.push(lane, event) {
+        metrics
+            .gossip_lane_dropped_total
+            .with_label_values(&[lane.name()])
+            .inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gossip_classification() {
+        assert_eq!(lane_for_gossip("CONFIRM_RES:abc:1"), Lane::Consensus);
+        assert_eq!(lane_for_gossip("CHECKPOINT_SIGN:1:ab"), Lane::Consensus);
+        assert_eq!(lane_for_gossip("SYNC_GZIP:H4sI"), Lane::Bulk);
+        assert_eq!(lane_for_gossip("SYNC_BIN:TE9TUw"), Lane::Bulk);
+        assert_eq!(lane_for_gossip("BLOCK_CONFIRMED:e30="), Lane::Blocks);
+        assert_eq!(lane_for_gossip("{\"account\":\"LOS\"}"), Lane::Blocks);
+        assert_eq!(
+            lane_for(&NetworkEvent::DeltaSyncFailed {
+                from_peer: "p".to_string()
+            }),
+            Lane::Bulk
+        );
+    }
+
+    #[test]
+    fn test_priority_with_starvation_guard() {
+        let mut q = LaneQueue::default();
+        q.push(Lane::Bulk, "sync");
+        q.push(Lane::Blocks, "block");
+        for i in 0..(MAX_SKIPS + 5) {
+            q.push(Lane::Consensus, if i == 0 { "vote0" } else { "vote" });
+        }
+        assert_eq!(q.pop(), Some((Lane::Consensus, "vote0")));
+
+        // Votes go first until the waiting lanes have been skipped MAX_SKIPS times
+        let order: Vec<Lane> = std::iter::from_fn(|| q.pop().map(|(l, _)| l)).collect();
+        let first_lower = order.iter().position(|l| *l != Lane::Consensus).unwrap();
+        assert_eq!(first_lower as u32, MAX_SKIPS - 1);
+        assert_eq!(order[first_lower], Lane::Bulk);
+        assert_eq!(order.len() as u32, MAX_SKIPS + 6);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn test_full_lane_drops_oldest() {
+        let mut q = LaneQueue::default();
+        let cap = Lane::Bulk.capacity();
+        for i in 0..cap {
+            assert!(q.push(Lane::Bulk, i));
+        }
+        assert!(!q.push(Lane::Bulk, cap));
+        assert_eq!(q.len(Lane::Bulk), cap);
+        assert_eq!(q.pop(), Some((Lane::Bulk, 1)));
+    }
+}
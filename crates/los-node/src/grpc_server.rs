@@ -1,3 +1,4 @@
+use crate::validator_directory::{ValidatorDirectory, ValidatorQuery};
 /// Unauthority gRPC Server Implementation
 ///
 /// Provides 8 core gRPC services for external integration:
@@ -7,11 +8,11 @@
 /// 4. GetLatestBlock - Get latest finalized block
 /// 5. SendTransaction - Broadcast LOS transaction
 /// 6. GetNodeInfo - Get node/oracle/supply info
-/// 7. GetValidators - List all active validators
+/// 7. GetValidators - Paginated, sortable validator listing (shared with REST)
 /// 8. GetBlockHeight - Get current blockchain height
 use los_consensus::voting::calculate_voting_power;
 use los_core::{
-    validator_rewards::ValidatorRewardPool, Ledger, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL,
+    units::Cil, validator_rewards::ValidatorRewardPool, Ledger, MIN_VALIDATOR_STAKE_CIL,
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -39,16 +40,14 @@ pub struct LosGrpcService {
     tx_sender: mpsc::Sender<String>, // For broadcasting transactions
     /// Peer address book — provides real peer count
     address_book: Arc<Mutex<HashMap<String, String>>>,
-    /// Bootstrap validator addresses for active status checks
-    bootstrap_validators: Vec<String>,
     /// Local REST API port for forwarding SendTransaction
     rest_api_port: u16,
     /// Local REST API host (respects LOS_BIND_ALL for Tor)
     rest_bind_host: String,
     /// Shared HTTP client for REST forwarding (connection pooling, keep-alive)
     http_client: reqwest::Client,
-    /// Validator reward pool — provides real uptime percentages
-    reward_pool: Arc<Mutex<ValidatorRewardPool>>,
+    /// Validator listing — same selection, sorting and paging as REST /validators
+    directory: Arc<ValidatorDirectory>,
 }
 
 impl LosGrpcService {
@@ -68,19 +67,32 @@ impl LosGrpcService {
             .pool_max_idle_per_host(4)
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
+        let directory = Arc::new(ValidatorDirectory::new(
+            Arc::clone(&ledger),
+            reward_pool,
+            Arc::clone(&address_book),
+            bootstrap_validators,
+            my_address.clone(),
+        ));
         Self {
             ledger,
             my_address,
             tx_sender,
             address_book,
-            bootstrap_validators,
             rest_api_port,
             rest_bind_host,
             http_client,
-            reward_pool,
+            directory,
         }
     }
 
+    /// Use the node's full directory (slashing status, endpoints, liveness)
+    /// instead of the ledger + reward pool view built by `new`.
+    pub fn with_directory(mut self, directory: Arc<ValidatorDirectory>) -> Self {
+        self.directory = directory;
+        self
+    }
+
     /// Helper: Convert short address to full address
     fn resolve_address(&self, addr: &str) -> Option<String> {
         let ledger = self.ledger.lock().ok()?;
@@ -132,26 +144,23 @@ impl LosNode for LosGrpcService {
             .get(&full_addr)
             .ok_or_else(|| Status::not_found("Account not found"))?;
 
-        // Use string formatting to avoid u128→u64 truncation
-        let balance_los = account.balance / CIL_PER_LOS;
-        let balance_remainder = account.balance % CIL_PER_LOS;
+        let balance = Cil(account.balance).to_los();
 
         let response = GetBalanceResponse {
             address: full_addr,
             balance_cil: account.balance.min(u64::MAX as u128) as u64, // Cap to u64::MAX (no silent truncation)
             // PROTO BOUNDARY: `double balance_los` required by los.proto.
             // Integer `balance_cil_str` below is authoritative. This f64 is display-only.
-            balance_los: balance_los as f64 + (balance_remainder as f64 / CIL_PER_LOS as f64),
+            balance_los: balance.to_f64_lossy(),
             block_count: account.block_count,
             head_block: account.head.clone(),
             balance_cil_str: account.balance.to_string(), // Full-precision u128 as string
         };
 
         println!(
-            "📊 gRPC GetBalance: {} -> {}.{} LOS",
+            "📊 gRPC GetBalance: {} -> {} LOS",
             get_short_addr(&response.address),
-            balance_los,
-            balance_remainder
+            balance
         );
 
         Ok(Response::new(response))
@@ -187,8 +196,7 @@ impl LosNode for LosGrpcService {
             balance_cil: account.balance.min(u64::MAX as u128) as u64, // Cap to u64::MAX (no silent truncation)
             // PROTO BOUNDARY: `double balance_los` required by los.proto.
             // Integer `balance_cil_str` below is authoritative. This f64 is display-only.
-            balance_los: (account.balance / CIL_PER_LOS) as f64
-                + (account.balance % CIL_PER_LOS) as f64 / CIL_PER_LOS as f64,
+            balance_los: Cil(account.balance).to_los().to_f64_lossy(),
             block_count: account.block_count,
             head_block: account.head.clone(),
             is_validator,
@@ -418,7 +426,7 @@ impl LosNode for LosGrpcService {
 
         let response = GetNodeInfoResponse {
             node_address: self.my_address.clone(),
-            network_id: los_core::CHAIN_ID as u32, // CHAIN_ID: 1=mainnet, 2=testnet
+            network_id: los_core::chain_id() as u32, // chain spec: 1=mainnet, 2=testnet
             chain_name: "Unauthority".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             // Use .min() saturation instead of hard-coding 0
@@ -447,84 +455,65 @@ impl LosNode for LosGrpcService {
     /// 7. Get validators list
     async fn get_validators(
         &self,
-        _request: Request<GetValidatorsRequest>,
+        request: Request<GetValidatorsRequest>,
     ) -> Result<Response<GetValidatorsResponse>, Status> {
-        let ledger = self
-            .ledger
-            .lock()
-            .map_err(|_| Status::internal("Failed to lock ledger"))?;
-
-        let min_stake = MIN_VALIDATOR_STAKE_CIL;
-
-        let peer_addresses: std::collections::HashSet<String> = self
-            .address_book
-            .lock()
-            .map(|ab| ab.keys().cloned().collect())
-            .unwrap_or_default();
-
-        // Real uptime data from reward pool (heartbeat-based tracking)
-        let uptime_data: HashMap<String, u64> = self
-            .reward_pool
-            .lock()
-            .map(|rp| {
-                rp.validators
-                    .iter()
-                    .map(|(addr, vs)| (addr.clone(), vs.display_uptime_pct()))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        // Pre-compute cumulative rewards per validator from REWARD Mint blocks
-        let mut reward_totals: HashMap<String, u128> = HashMap::new();
-        for blk in ledger.blocks.values() {
-            if blk.block_type == los_core::BlockType::Mint && blk.link.starts_with("REWARD:") {
-                *reward_totals.entry(blk.account.clone()).or_insert(0) += blk.amount;
-            }
-        }
-
-        // Filter accounts with minimum stake
-        let validators: Vec<ValidatorInfo> = ledger
-            .accounts
-            .iter()
-            .filter(|(_, acc)| acc.balance >= min_stake)
-            .map(|(addr, acc)| {
+        let req = request.into_inner();
+        let offset = req.offset.to_string();
+        let limit = req.limit.to_string();
+        let query = ValidatorQuery::parse(
+            Some(&req.sort),
+            Some(&req.order),
+            Some(&offset),
+            Some(&limit),
+            req.active_only,
+        )
+        .map_err(Status::invalid_argument)?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (page, total) = self.directory.page(&query, now);
+
+        let validators: Vec<ValidatorInfo> = page
+            .into_iter()
+            .map(|v| ValidatorInfo {
                 // Linear voting power: 1 CIL = 1 vote
-                // Changed from √stake to linear.
                 // PROTO BOUNDARY: `double voting_power` required by los.proto.
                 // Linear CIL is authoritative; cast to f64 only for proto serialization.
-                let voting_power = calculate_voting_power(acc.balance) as f64;
-
-                // Active = is self OR is in address book (has live P2P connection)
-                let is_active = addr == &self.my_address
-                    || peer_addresses.contains(addr)
-                    || self.bootstrap_validators.contains(addr);
-
-                let earned = reward_totals.get(addr).copied().unwrap_or(0);
-
-                ValidatorInfo {
-                    address: addr.clone(),
-                    // .min() guard prevents wrapping on balances > u64::MAX
-                    stake_cil: acc.balance.min(u64::MAX as u128) as u64,
-                    is_active,
-                    voting_power,
-                    rewards_earned: earned.min(u64::MAX as u128) as u64,
-                    // Real uptime from heartbeat tracking (100 for self if not yet recorded)
-                    uptime_percent: uptime_data
-                        .get(addr)
-                        .copied()
-                        .unwrap_or(if addr == &self.my_address { 100 } else { 0 })
-                        as f64,
-                }
+                voting_power: calculate_voting_power(v.stake_cil) as f64,
+                // .min() guard prevents wrapping on balances > u64::MAX
+                stake_cil: v.stake_cil.min(u64::MAX as u128) as u64,
+                stake_cil_str: v.stake_cil.to_string(),
+                is_active: v.active,
+                rewards_earned: v.rewards_earned_cil.min(u64::MAX as u128) as u64,
+                rewards_earned_str: v.rewards_earned_cil.to_string(),
+                uptime_percent: v.uptime_pct as f64,
+                last_heartbeat: v.last_heartbeat.unwrap_or(0),
+                in_probation: v.in_probation,
+                probation_epochs_left: v.probation_epochs_left,
+                host_address: v.host_address.unwrap_or_default(),
+                is_genesis: v.is_genesis,
+                connected: v.connected,
+                delegated_stake_cil_str: v
+                    .delegated_stake_cil
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                address: v.address,
             })
             .collect();
 
-        let total_count = validators.len() as u32;
-
-        println!("👥 gRPC GetValidators: {} active validators", total_count);
+        println!(
+            "👥 gRPC GetValidators: {} of {} validators",
+            validators.len(),
+            total
+        );
 
         let response = GetValidatorsResponse {
             validators,
-            total_count,
+            total_count: total as u32,
+            offset: query.offset as u32,
+            limit: query.limit as u32,
         };
 
         Ok(Response::new(response))
@@ -573,6 +562,7 @@ pub async fn start_grpc_server(
     bootstrap_validators: Vec<String>,
     rest_api_port: u16,
     reward_pool: Arc<Mutex<ValidatorRewardPool>>,
+    directory: Arc<ValidatorDirectory>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Respect LOS_BIND_ALL env for Tor safety (same as REST API)
     let bind_addr = if std::env::var("LOS_BIND_ALL").unwrap_or_default() == "1" {
@@ -590,7 +580,8 @@ pub async fn start_grpc_server(
         bootstrap_validators,
         rest_api_port,
         reward_pool,
-    );
+    )
+    .with_directory(directory);
 
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("🚀 gRPC Server STARTED");
@@ -611,7 +602,7 @@ pub async fn start_grpc_server(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use los_core::{validator_rewards::ValidatorRewardPool, AccountState};
+    use los_core::{validator_rewards::ValidatorRewardPool, AccountState, CIL_PER_LOS};
     use std::collections::HashMap;
 
     fn mock_reward_pool() -> Arc<Mutex<ValidatorRewardPool>> {
@@ -704,7 +695,7 @@ mod tests {
             mock_reward_pool(),
         );
 
-        let request = Request::new(GetValidatorsRequest {});
+        let request = Request::new(GetValidatorsRequest::default());
         let response = service.get_validators(request).await.unwrap();
         let validators = response.into_inner();
 
@@ -717,4 +708,70 @@ mod tests {
         assert!(val1.voting_power > 0.0);
         assert!(val1.is_active);
     }
+
+    #[tokio::test]
+    async fn test_grpc_get_validators_paging() {
+        let mut ledger = Ledger::new();
+        for (i, stake) in [3000u128, 1000, 2000].iter().enumerate() {
+            ledger.accounts.insert(
+                format!("validator{}", i),
+                AccountState {
+                    head: "genesis".to_string(),
+                    balance: stake * CIL_PER_LOS,
+                    block_count: 0,
+                    is_validator: true,
+                },
+            );
+        }
+        let pool = mock_reward_pool();
+        safe_register(&pool, "validator1");
+        let (tx, _rx) = mpsc::channel(1);
+        let service = LosGrpcService::new(
+            Arc::new(Mutex::new(ledger)),
+            "validator0".to_string(),
+            tx,
+            Arc::new(Mutex::new(HashMap::new())),
+            vec![],
+            3030,
+            pool,
+        );
+
+        let request = Request::new(GetValidatorsRequest {
+            offset: 1,
+            limit: 1,
+            ..Default::default()
+        });
+        let resp = service.get_validators(request).await.unwrap().into_inner();
+        assert_eq!(resp.total_count, 3);
+        assert_eq!(resp.validators.len(), 1);
+        assert_eq!(resp.validators[0].address, "validator2");
+        assert_eq!(
+            resp.validators[0].stake_cil_str,
+            (2000 * CIL_PER_LOS).to_string()
+        );
+
+        let request = Request::new(GetValidatorsRequest {
+            sort: "address".to_string(),
+            active_only: true,
+            ..Default::default()
+        });
+        let resp = service.get_validators(request).await.unwrap().into_inner();
+        let addrs: Vec<&str> = resp.validators.iter().map(|v| v.address.as_str()).collect();
+        assert_eq!(addrs, ["validator0", "validator1"]);
+        assert!(resp.validators[1].in_probation);
+        assert!(resp.validators[1].delegated_stake_cil_str.is_empty());
+
+        let request = Request::new(GetValidatorsRequest {
+            sort: "votes".to_string(),
+            ..Default::default()
+        });
+        let err = service.get_validators(request).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    fn safe_register(pool: &Arc<Mutex<ValidatorRewardPool>>, addr: &str) {
+        pool.lock()
+            .unwrap()
+            .register_validator(addr, false, 1000 * CIL_PER_LOS);
+    }
 }
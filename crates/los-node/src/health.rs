@@ -0,0 +1,216 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - NODE HEALTH EVALUATION
+//
+// Backs GET /health. Collects subsystem readings (ledger, checkpoints, P2P,
+// mempool, WASM VM, persistence) and folds them into one status that load
+// balancers and uptime monitors can act on:
+// - healthy   → HTTP 200
+// - degraded  → HTTP 200 (serving, but an operator should look)
+// - unhealthy → HTTP 503 (take the node out of rotation)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::Serialize;
+
+/// Last finalized checkpoint older than this → degraded
+pub const CHECKPOINT_STALE_SECS: u64 = 3_600;
+/// Unsaved changes older than this → degraded
+pub const SAVE_LAG_DEGRADED_SECS: u64 = 300;
+/// Unsaved changes older than this → unhealthy (persistence is failing)
+pub const SAVE_LAG_UNHEALTHY_SECS: u64 = 900;
+/// Mempool fill ratio (percent) above which the node is degraded
+pub const MEMPOOL_DEGRADED_PCT: usize = 80;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl HealthStatus {
+    pub fn http_code(self) -> u16 {
+        match self {
+            HealthStatus::Unhealthy => 503,
+            _ => 200,
+        }
+    }
+}
+
+/// Raw readings taken by the /health handler
+#[derive(Debug, Clone, Default)]
+pub struct HealthInputs {
+    pub accounts: usize,
+    /// Seconds since the latest finalized checkpoint (None = no checkpoint yet)
+    pub checkpoint_age_secs: Option<u64>,
+    pub connected_peers: usize,
+    pub min_peers: usize,
+    pub mempool_depth: usize,
+    pub mempool_capacity: usize,
+    pub leaked_vm_threads: usize,
+    pub max_leaked_vm_threads: usize,
+    /// Seconds since the last successful save (None = no save since startup)
+    pub last_save_age_secs: Option<u64>,
+    /// Whether the ledger has changes not yet written to disk
+    pub save_pending: bool,
+    /// State root diverged from the network's checkpoints (quarantine.rs)
+    pub quarantined: bool,
+    /// Conflicting finalized checkpoints await operator resolution
+    /// (checkpoint_conflict.rs)
+    pub finality_halted: bool,
+}
+
+/// Evaluate subsystem readings. Returns the overall status plus one
+/// human-readable reason per failing check.
+pub fn evaluate(h: &HealthInputs) -> (HealthStatus, Vec<String>) {
+    let mut status = HealthStatus::Healthy;
+    let mut issues = Vec::new();
+    let mut flag = |level: HealthStatus, msg: String| {
+        status = status.max(level);
+        issues.push(msg);
+    };
+
+    if h.accounts == 0 {
+        flag(HealthStatus::Unhealthy, "ledger not loaded".to_string());
+    }
+    if h.quarantined {
+        flag(
+            HealthStatus::Unhealthy,
+            "state root diverged from network checkpoints (quarantined, resyncing)".to_string(),
+        );
+    }
+    if h.finality_halted {
+        flag(
+            HealthStatus::Unhealthy,
+            "conflicting finalized checkpoints: finality halted until an operator resolves them"
+                .to_string(),
+        );
+    }
+    if h.max_leaked_vm_threads > 0 && h.leaked_vm_threads >= h.max_leaked_vm_threads {
+        flag(
+            HealthStatus::Unhealthy,
+            format!(
+                "WASM VM disabled: {} leaked threads (restart required)",
+                h.leaked_vm_threads
+            ),
+        );
+    } else if h.leaked_vm_threads > 0 {
+        flag(
+            HealthStatus::Degraded,
+            format!("{} leaked WASM timeout threads", h.leaked_vm_threads),
+        );
+    }
+    if h.connected_peers < h.min_peers {
+        flag(
+            HealthStatus::Degraded,
+            format!("peers {}/{} below minimum", h.connected_peers, h.min_peers),
+        );
+    }
+    if let Some(age) = h.checkpoint_age_secs.filter(|a| *a > CHECKPOINT_STALE_SECS) {
+        flag(
+            HealthStatus::Degraded,
+            format!("last checkpoint finalized {}s ago", age),
+        );
+    }
+    if h.mempool_capacity > 0 && h.mempool_depth * 100 >= h.mempool_capacity * MEMPOOL_DEGRADED_PCT
+    {
+        flag(
+            HealthStatus::Degraded,
+            format!(
+                "mempool {}/{} near capacity",
+                h.mempool_depth, h.mempool_capacity
+            ),
+        );
+    }
+    if h.save_pending {
+        match h.last_save_age_secs {
+            Some(age) if age > SAVE_LAG_UNHEALTHY_SECS => flag(
+                HealthStatus::Unhealthy,
+                format!("unsaved changes, last successful save {}s ago", age),
+            ),
+            Some(age) if age > SAVE_LAG_DEGRADED_SECS => flag(
+                HealthStatus::Degraded,
+                format!("unsaved changes, last successful save {}s ago", age),
+            ),
+            _ => {}
+        }
+    }
+
+    (status, issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn good() -> HealthInputs {
+        HealthInputs {
+            accounts: 10,
+            checkpoint_age_secs: Some(60),
+            connected_peers: 4,
+            min_peers: 2,
+            mempool_depth: 5,
+            mempool_capacity: 10_000,
+            leaked_vm_threads: 0,
+            max_leaked_vm_threads: 16,
+            last_save_age_secs: Some(10),
+            save_pending: false,
+            quarantined: false,
+            finality_halted: false,
+        }
+    }
+
+    #[test]
+    fn test_healthy_node() {
+        let (status, issues) = evaluate(&good());
+        assert_eq!(status, HealthStatus::Healthy);
+        assert!(issues.is_empty());
+        assert_eq!(status.http_code(), 200);
+    }
+
+    #[test]
+    fn test_degraded_checks() {
+        let mut h = good();
+        h.connected_peers = 1;
+        h.checkpoint_age_secs = Some(CHECKPOINT_STALE_SECS + 1);
+        h.mempool_depth = 9_000;
+        h.leaked_vm_threads = 1;
+        let (status, issues) = evaluate(&h);
+        assert_eq!(status, HealthStatus::Degraded);
+        assert_eq!(issues.len(), 4);
+    }
+
+    #[test]
+    fn test_unhealthy_wins() {
+        let mut h = good();
+        h.connected_peers = 0;
+        h.leaked_vm_threads = 16;
+        let (status, _) = evaluate(&h);
+        assert_eq!(status, HealthStatus::Unhealthy);
+        assert_eq!(status.http_code(), 503);
+    }
+
+    #[test]
+    fn test_quarantine_is_unhealthy() {
+        let mut h = good();
+        h.quarantined = true;
+        let (status, issues) = evaluate(&h);
+        assert_eq!(status, HealthStatus::Unhealthy);
+        assert!(issues[0].contains("quarantined"));
+
+        let mut h = good();
+        h.finality_halted = true;
+        assert_eq!(evaluate(&h).0, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_save_lag_only_counts_with_pending_changes() {
+        let mut h = good();
+        h.last_save_age_secs = Some(SAVE_LAG_UNHEALTHY_SECS + 1);
+        assert_eq!(evaluate(&h).0, HealthStatus::Healthy);
+        h.save_pending = true;
+        assert_eq!(evaluate(&h).0, HealthStatus::Unhealthy);
+        h.last_save_age_secs = Some(SAVE_LAG_DEGRADED_SECS + 1);
+        assert_eq!(evaluate(&h).0, HealthStatus::Degraded);
+    }
+}
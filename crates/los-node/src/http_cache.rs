@@ -0,0 +1,302 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - RESPONSE COMPRESSION + ETAG CACHING
+//
+// Heavy read endpoints (/history, /peers, /tokens) are polled by wallets and
+// explorers that mostly see the same answer again. They reply through
+// `cached_reply`:
+//
+//   - ETag: W/"<blake3>" of a cache key. /history keys on the account head,
+//     /tokens on the ledger generation (plus a per-process nonce, since the
+//     generation restarts at 0), /peers on the response itself. A request
+//     whose If-None-Match matches gets 304 Not Modified with no body, and
+//     keyed endpoints answer before building the JSON at all.
+//   - Compression: brotli or gzip, negotiated from Accept-Encoding (q-values
+//     honoured, br preferred on ties), for bodies of at least
+//     MIN_COMPRESS_BYTES. Responses carry Vary: Accept-Encoding.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use std::io::Write;
+use std::sync::OnceLock;
+use warp::http::{header, HeaderValue, StatusCode};
+use warp::reply::Response;
+use warp::Filter;
+
+/// Smaller bodies are sent uncompressed (framing overhead beats the gain)
+pub const MIN_COMPRESS_BYTES: usize = 1024;
+/// Brotli quality (0-11): 5 keeps per-request CPU low for dynamic JSON
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Content coding chosen for a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+    Identity,
+}
+
+impl ContentEncoding {
+    fn header_value(self) -> Option<&'static str> {
+        match self {
+            ContentEncoding::Brotli => Some("br"),
+            ContentEncoding::Gzip => Some("gzip"),
+            ContentEncoding::Identity => None,
+        }
+    }
+}
+
+/// Conditional / negotiation headers of a request
+#[derive(Debug, Clone, Default)]
+pub struct CacheHeaders {
+    pub if_none_match: Option<String>,
+    pub accept_encoding: Option<String>,
+}
+
+/// Extract If-None-Match and Accept-Encoding
+pub fn cache_headers() -> impl Filter<Extract = (CacheHeaders,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("if-none-match")
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .map(|if_none_match, accept_encoding| CacheHeaders {
+            if_none_match,
+            accept_encoding,
+        })
+}
+
+/// Pick the best supported coding from an Accept-Encoding header
+pub fn negotiate(accept_encoding: Option<&str>) -> ContentEncoding {
+    let Some(accept) = accept_encoding else {
+        return ContentEncoding::Identity;
+    };
+    let mut best = (ContentEncoding::Identity, 0u16);
+    for item in accept.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        // q in thousandths; absent = 1.000, unparsable = 0
+        let q = parts
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .map_or(1000, |v| {
+                v.trim()
+                    .parse::<f32>()
+                    .map(|f| (f.clamp(0.0, 1.0) * 1000.0) as u16)
+                    .unwrap_or(0)
+            });
+        let candidate = match coding.as_str() {
+            "br" => ContentEncoding::Brotli,
+            "gzip" | "x-gzip" => ContentEncoding::Gzip,
+            _ => continue,
+        };
+        let better = q > best.1 || (q == best.1 && q > 0 && candidate == ContentEncoding::Brotli);
+        if better {
+            best = (candidate, q);
+        }
+    }
+    best.0
+}
+
+/// Compress `body` with `encoding` (Identity returns a copy)
+pub fn encode(body: &[u8], encoding: ContentEncoding) -> Result<Vec<u8>, String> {
+    match encoding {
+        ContentEncoding::Identity => Ok(body.to_vec()),
+        ContentEncoding::Gzip => {
+            let mut enc = flate2::write::GzEncoder::new(
+                Vec::with_capacity(body.len() / 4),
+                flate2::Compression::fast(),
+            );
+            enc.write_all(body).map_err(|e| format!("gzip: {}", e))?;
+            enc.finish().map_err(|e| format!("gzip: {}", e))
+        }
+        ContentEncoding::Brotli => {
+            let mut out = Vec::with_capacity(body.len() / 4);
+            {
+                let mut enc =
+                    brotli::CompressorWriter::new(&mut out, 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+                enc.write_all(body).map_err(|e| format!("brotli: {}", e))?;
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Random per-process value mixed into keys built from in-memory counters
+fn process_nonce() -> u64 {
+    static NONCE: OnceLock<u64> = OnceLock::new();
+    *NONCE.get_or_init(rand::random)
+}
+
+/// Weak ETag for a cache key (weak: the representation varies by coding)
+pub fn etag_for(key: &str) -> String {
+    let hash = blake3::hash(key.as_bytes()).to_hex();
+    format!("W/\"{}\"", &hash[..32])
+}
+
+/// ETag keyed on the ledger generation (changes on every ledger mutation)
+pub fn generation_etag(scope: &str, generation: u64) -> String {
+    etag_for(&format!("{}:{}:{}", scope, process_nonce(), generation))
+}
+
+/// Whether If-None-Match matches `etag` (weak comparison, lists and `*`)
+pub fn etag_matches(if_none_match: Option<&str>, etag: &str) -> bool {
+    let Some(inm) = if_none_match else {
+        return false;
+    };
+    let opaque = |t: &str| t.trim().trim_start_matches("W/").to_string();
+    let want = opaque(etag);
+    inm.split(',').any(|t| t.trim() == "*" || opaque(t) == want)
+}
+
+fn not_modified_response(etag: &str) -> Response {
+    let mut resp = Response::new(warp::hyper::Body::empty());
+    *resp.status_mut() = StatusCode::NOT_MODIFIED;
+    set_cache_headers(&mut resp, etag);
+    resp
+}
+
+fn set_cache_headers(resp: &mut Response, etag: &str) {
+    let headers = resp.headers_mut();
+    if let Ok(v) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, v);
+    }
+    // Clients may store the body but must revalidate before reuse
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    headers.insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+}
+
+/// 304 response when the client already holds `etag`. Lets keyed endpoints
+/// skip building the body.
+pub fn not_modified(etag: &str, h: &CacheHeaders) -> Option<Response> {
+    etag_matches(h.if_none_match.as_deref(), etag).then(|| not_modified_response(etag))
+}
+
+/// JSON response with status, ETag and negotiated compression. With
+/// `etag == None` the ETag is derived from the body.
+pub fn cached_reply(
+    body: &serde_json::Value,
+    status: StatusCode,
+    etag: Option<String>,
+    h: &CacheHeaders,
+) -> Response {
+    let bytes = serde_json::to_vec(body).unwrap_or_else(|_| b"{}".to_vec());
+    let etag = etag.unwrap_or_else(|| etag_for(&format!("body:{}", blake3::hash(&bytes).to_hex())));
+    if status.is_success() {
+        if let Some(resp) = not_modified(&etag, h) {
+            return resp;
+        }
+    }
+
+    let encoding = if bytes.len() >= MIN_COMPRESS_BYTES {
+        negotiate(h.accept_encoding.as_deref())
+    } else {
+        ContentEncoding::Identity
+    };
+    let (bytes, encoding) = match encoding {
+        ContentEncoding::Identity => (bytes, encoding),
+        _ => match encode(&bytes, encoding) {
+            Ok(out) => (out, encoding),
+            Err(e) => {
+                eprintln!("⚠️ Response compression failed: {}", e);
+                (bytes, ContentEncoding::Identity)
+            }
+        },
+    };
+
+    let mut resp = Response::new(warp::hyper::Body::from(bytes));
+    *resp.status_mut() = status;
+    let headers = resp.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    if let Some(coding) = encoding.header_value() {
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(coding));
+    }
+    if status.is_success() {
+        set_cache_headers(&mut resp, &etag);
+    }
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_accept_encoding_negotiation() {
+        assert_eq!(negotiate(None), ContentEncoding::Identity);
+        assert_eq!(
+            negotiate(Some("gzip, deflate, br")),
+            ContentEncoding::Brotli
+        );
+        assert_eq!(negotiate(Some("gzip")), ContentEncoding::Gzip);
+        assert_eq!(
+            negotiate(Some("br;q=0.5, gzip;q=0.8")),
+            ContentEncoding::Gzip
+        );
+        assert_eq!(
+            negotiate(Some("br;q=0, gzip;q=0")),
+            ContentEncoding::Identity
+        );
+        assert_eq!(
+            negotiate(Some("deflate, identity")),
+            ContentEncoding::Identity
+        );
+    }
+
+    #[test]
+    fn test_compressed_body_round_trips() {
+        let body = serde_json::json!({
+            "transactions": (0..200).map(|i| serde_json::json!({"hash": format!("{:064}", i)})).collect::<Vec<_>>()
+        });
+        let raw = serde_json::to_vec(&body).unwrap();
+
+        let gz = encode(&raw, ContentEncoding::Gzip).unwrap();
+        let mut out = Vec::new();
+        flate2::read::GzDecoder::new(&gz[..])
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, raw);
+        assert!(gz.len() < raw.len() / 4);
+
+        let br = encode(&raw, ContentEncoding::Brotli).unwrap();
+        let mut out = Vec::new();
+        brotli::Decompressor::new(&br[..], 4096)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, raw);
+
+        let h = CacheHeaders {
+            if_none_match: None,
+            accept_encoding: Some("br".to_string()),
+        };
+        let resp = cached_reply(&body, StatusCode::OK, None, &h);
+        assert_eq!(resp.headers()[header::CONTENT_ENCODING], "br");
+        let small = cached_reply(&serde_json::json!({"ok": true}), StatusCode::OK, None, &h);
+        assert!(small.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+
+    #[test]
+    fn test_if_none_match_returns_304() {
+        let etag = etag_for("acct:LOSalice:head1");
+        let h = CacheHeaders {
+            if_none_match: Some(format!("\"other\", {}", etag.trim_start_matches("W/"))),
+            accept_encoding: None,
+        };
+        let resp = not_modified(&etag, &h).expect("weak match on opaque tag");
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers()[header::ETAG], etag.as_str());
+
+        let changed = etag_for("acct:LOSalice:head2");
+        assert!(not_modified(&changed, &h).is_none());
+        assert!(etag_matches(Some("*"), &changed));
+        assert_ne!(generation_etag("tokens", 1), generation_etag("tokens", 2));
+
+        // Errors are never answered with 304
+        let err = cached_reply(
+            &serde_json::json!({"status": "error"}),
+            StatusCode::NOT_FOUND,
+            Some(etag.clone()),
+            &h,
+        );
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,433 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - INVOICES
+//
+// POST /invoice creates a payment request (los: URI, see
+// los_core::payment_uri) for an address and amount. GET /invoice/{id}/status
+// looks for the payment and reports pending → paid | expired:
+//   - LOS invoices: a Receive on the recipient's chain at or after creation
+//     whose linked Send pays at least the amount
+//   - token invoices (`contract`): a USP-01 Transfer event to the recipient
+//     (topic 1) of at least the amount, from the contract event store
+// Each payment settles at most one invoice, so two invoices for the same
+// amount need two payments. Invoices live in the `invoices` tree; expired
+// ones are dropped after INVOICE_RETENTION_SECS.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::payment_uri::PaymentRequest;
+use los_core::{BlockType, Ledger};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Lifetime of an invoice when the request does not set one
+pub const DEFAULT_INVOICE_TTL_SECS: u64 = 3_600;
+/// Longest lifetime a request may ask for (30 days)
+pub const MAX_INVOICE_TTL_SECS: u64 = 30 * 24 * 3_600;
+/// Expired and paid invoices are kept this long after they end (7 days)
+pub const INVOICE_RETENTION_SECS: u64 = 7 * 24 * 3_600;
+/// Cap on stored invoices (anti-spam)
+pub const MAX_INVOICES: usize = 100_000;
+/// Recipient chain blocks scanned per status check
+const MAX_SCAN_BLOCKS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    Pending,
+    Paid,
+    Expired,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Invoice {
+    pub id: String,
+    pub request: PaymentRequest,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub status: InvoiceStatus,
+    /// Send block (LOS) or ContractCall block (token) that paid it
+    pub paid_by: Option<String>,
+    pub paid_amount: Option<u128>,
+    pub paid_at: Option<u64>,
+}
+
+impl Invoice {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "uri": self.request.to_uri(),
+            "address": self.request.address,
+            "amount": self.request.amount.unwrap_or(0).to_string(),
+            "contract": self.request.contract,
+            "label": self.request.label,
+            "message": self.request.message,
+            "status": self.status,
+            "created_at": self.created_at,
+            "expires_at": self.expires_at,
+            "paid_by": self.paid_by,
+            "paid_amount": self.paid_amount.map(|a| a.to_string()),
+            "paid_at": self.paid_at,
+        })
+    }
+}
+
+/// Body of POST /invoice: a `uri`, or the fields of one
+#[derive(Debug, Default, Deserialize)]
+pub struct InvoiceRequest {
+    pub uri: Option<String>,
+    pub address: Option<String>,
+    /// Decimal LOS, or token base units with `contract`
+    pub amount: Option<String>,
+    pub amount_cil: Option<u128>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub contract: Option<String>,
+    pub expires_in_secs: Option<u64>,
+}
+
+impl InvoiceRequest {
+    pub fn payment_request(&self) -> Result<PaymentRequest, String> {
+        if let Some(uri) = &self.uri {
+            return PaymentRequest::parse(uri);
+        }
+        let address = self
+            .address
+            .as_deref()
+            .ok_or("address or uri is required")?;
+        let amount = match (&self.amount, self.amount_cil) {
+            (Some(_), Some(_)) => return Err("give amount or amount_cil, not both".to_string()),
+            (Some(a), None) if self.contract.is_some() => Some(
+                a.parse::<u128>()
+                    .map_err(|_| format!("Invalid token amount '{}'", a))?,
+            ),
+            (Some(a), None) => Some(los_core::payment_uri::parse_los(a)?),
+            (None, cil) => cil,
+        };
+        let request = PaymentRequest {
+            address: address.to_string(),
+            amount,
+            label: self.label.clone(),
+            message: self.message.clone(),
+            contract: self.contract.clone(),
+        };
+        // Same validation as a scanned URI (address, contract)
+        PaymentRequest::parse(&request.to_uri())
+    }
+}
+
+/// A candidate payment to a recipient
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payment {
+    pub id: String,
+    pub amount: u128,
+    pub timestamp: u64,
+}
+
+/// Incoming LOS payments to `address` received at or after `since`, oldest
+/// first. Identified by the Send block hash.
+pub fn incoming_payments(ledger: &Ledger, address: &str, since: u64) -> Vec<Payment> {
+    let mut out = Vec::new();
+    let mut current = match ledger.accounts.get(address) {
+        Some(a) => a.head.clone(),
+        None => return out,
+    };
+    for _ in 0..MAX_SCAN_BLOCKS {
+        let Some(blk) = ledger.blocks.get(&current) else {
+            break;
+        };
+        if blk.timestamp < since {
+            break;
+        }
+        let is_send = ledger
+            .blocks
+            .get(&blk.link)
+            .is_some_and(|s| s.block_type == BlockType::Send);
+        if blk.block_type == BlockType::Receive && is_send {
+            out.push(Payment {
+                id: blk.link.clone(),
+                amount: blk.amount,
+                timestamp: blk.timestamp,
+            });
+        }
+        current = blk.previous.clone();
+    }
+    out.reverse();
+    out
+}
+
+/// Token payments from stored USP-01 `Transfer` events (topics = [from, to])
+pub fn token_payments(events: &[los_vm::ContractEvent], address: &str) -> Vec<Payment> {
+    let mut out: Vec<Payment> = events
+        .iter()
+        .filter(|e| e.topics.get(1).map(String::as_str) == Some(address))
+        .filter_map(|e| {
+            Some(Payment {
+                id: e.block_hash.clone(),
+                amount: e.data.get("amount")?.parse().ok()?,
+                timestamp: e.timestamp,
+            })
+        })
+        .collect();
+    out.sort_by_key(|p| p.timestamp);
+    out
+}
+
+#[derive(Default)]
+pub struct InvoiceBook {
+    invoices: BTreeMap<String, Invoice>,
+    /// Payments that already settled an invoice
+    used_payments: BTreeSet<String>,
+}
+
+impl InvoiceBook {
+    pub fn load(invoices: Vec<Invoice>) -> Self {
+        let mut book = Self::default();
+        for inv in invoices {
+            if let Some(p) = &inv.paid_by {
+                book.used_payments.insert(p.clone());
+            }
+            book.invoices.insert(inv.id.clone(), inv);
+        }
+        book
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Invoice> {
+        self.invoices.get(id)
+    }
+
+    /// Create an invoice. Returns it with the ids of pruned invoices
+    /// (for the caller to delete from storage).
+    pub fn create(
+        &mut self,
+        request: PaymentRequest,
+        ttl_secs: Option<u64>,
+        now: u64,
+    ) -> Result<(Invoice, Vec<String>), String> {
+        if request.amount.unwrap_or(0) == 0 {
+            return Err("amount must be greater than zero".to_string());
+        }
+        let ttl = ttl_secs.unwrap_or(DEFAULT_INVOICE_TTL_SECS);
+        if ttl == 0 || ttl > MAX_INVOICE_TTL_SECS {
+            return Err(format!(
+                "expires_in_secs must be between 1 and {}",
+                MAX_INVOICE_TTL_SECS
+            ));
+        }
+        let pruned = self.prune(now);
+        if self.invoices.len() >= MAX_INVOICES {
+            return Err("Too many open invoices on this node".to_string());
+        }
+        let mut hasher = Sha3_256::new();
+        hasher.update(request.to_uri().as_bytes());
+        hasher.update(now.to_le_bytes());
+        hasher.update((self.invoices.len() as u64).to_le_bytes());
+        let mut id = hex::encode(&hasher.finalize()[..12]);
+        while self.invoices.contains_key(&id) {
+            id = hex::encode(&Sha3_256::digest(id.as_bytes())[..12]);
+        }
+        let invoice = Invoice {
+            id: id.clone(),
+            request,
+            created_at: now,
+            expires_at: now + ttl,
+            status: InvoiceStatus::Pending,
+            paid_by: None,
+            paid_amount: None,
+            paid_at: None,
+        };
+        self.invoices.insert(id, invoice.clone());
+        Ok((invoice, pruned))
+    }
+
+    /// Settle a pending invoice with the first unused payment that covers it
+    /// (made before expiry), or expire it. Returns the invoice and whether
+    /// it changed.
+    pub fn update(&mut self, id: &str, payments: &[Payment], now: u64) -> Option<(Invoice, bool)> {
+        let inv = self.invoices.get_mut(id)?;
+        if inv.status != InvoiceStatus::Pending {
+            return Some((inv.clone(), false));
+        }
+        let want = inv.request.amount.unwrap_or(0);
+        let hit = payments.iter().find(|p| {
+            p.amount >= want
+                && p.timestamp >= inv.created_at
+                && p.timestamp <= inv.expires_at
+                && !self.used_payments.contains(&p.id)
+        });
+        if let Some(p) = hit {
+            inv.status = InvoiceStatus::Paid;
+            inv.paid_by = Some(p.id.clone());
+            inv.paid_amount = Some(p.amount);
+            inv.paid_at = Some(p.timestamp);
+            self.used_payments.insert(p.id.clone());
+        } else if now > inv.expires_at {
+            inv.status = InvoiceStatus::Expired;
+        } else {
+            return Some((inv.clone(), false));
+        }
+        Some((inv.clone(), true))
+    }
+
+    /// Drop invoices that ended more than INVOICE_RETENTION_SECS ago
+    fn prune(&mut self, now: u64) -> Vec<String> {
+        let stale: Vec<String> = self
+            .invoices
+            .values()
+            .filter(|i| i.expires_at.saturating_add(INVOICE_RETENTION_SECS) < now)
+            .map(|i| i.id.clone())
+            .collect();
+        for id in &stale {
+            if let Some(inv) = self.invoices.remove(id) {
+                if let Some(p) = inv.paid_by {
+                    self.used_payments.remove(&p);
+                }
+            }
+        }
+        stale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::{AccountState, Block};
+
+    const ADDR: &str = "LOSX7dStdPkS9U4MFCmDQfpmvrbMa5WAZfQX1";
+
+    fn request(amount: u128) -> PaymentRequest {
+        PaymentRequest {
+            amount: Some(amount),
+            ..PaymentRequest::new(ADDR)
+        }
+    }
+
+    fn pay(id: &str, amount: u128, timestamp: u64) -> Payment {
+        Payment {
+            id: id.to_string(),
+            amount,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_each_payment_settles_one_invoice() {
+        let mut book = InvoiceBook::default();
+        let (a, _) = book.create(request(500), None, 1_000).unwrap();
+        let (b, _) = book.create(request(500), None, 1_000).unwrap();
+        assert_ne!(a.id, b.id);
+        assert!(book.create(request(0), None, 1_000).is_err());
+        assert!(book.create(request(1), Some(0), 1_000).is_err());
+
+        let payments = [
+            pay("early", 900, 999),
+            pay("short", 499, 1_010),
+            pay("s1", 600, 1_020),
+        ];
+        let (paid, changed) = book.update(&a.id, &payments, 1_030).unwrap();
+        assert!(changed);
+        assert_eq!(paid.status, InvoiceStatus::Paid);
+        assert_eq!(
+            (paid.paid_by.as_deref(), paid.paid_amount),
+            (Some("s1"), Some(600))
+        );
+
+        // s1 is used: b stays pending until another payment arrives
+        let (b_now, changed) = book.update(&b.id, &payments, 1_030).unwrap();
+        assert_eq!((b_now.status, changed), (InvoiceStatus::Pending, false));
+        let (b_now, _) = book.update(&b.id, &[pay("s2", 500, 1_040)], 1_050).unwrap();
+        assert_eq!(b_now.status, InvoiceStatus::Paid);
+
+        // Reloaded books remember used payments
+        let mut reloaded = InvoiceBook::load(vec![paid]);
+        let (c, _) = reloaded.create(request(500), None, 1_000).unwrap();
+        let (c, _) = reloaded.update(&c.id, &payments, 1_030).unwrap();
+        assert_eq!(c.status, InvoiceStatus::Pending);
+    }
+
+    #[test]
+    fn test_invoice_request_forms() {
+        let fields = InvoiceRequest {
+            address: Some(ADDR.to_string()),
+            amount: Some("1.25".to_string()),
+            label: Some("Shop".to_string()),
+            ..Default::default()
+        };
+        let req = fields.payment_request().unwrap();
+        assert_eq!(req.amount, Some(125_000_000_000));
+        let from_uri = InvoiceRequest {
+            uri: Some(req.to_uri()),
+            ..Default::default()
+        };
+        assert_eq!(from_uri.payment_request().unwrap(), req);
+
+        let both = InvoiceRequest {
+            amount_cil: Some(1),
+            ..fields
+        };
+        assert!(both.payment_request().is_err());
+        assert!(InvoiceRequest::default().payment_request().is_err());
+    }
+
+    #[test]
+    fn test_expiry_and_pruning() {
+        let mut book = InvoiceBook::default();
+        let (inv, _) = book.create(request(5), Some(60), 1_000).unwrap();
+        let (late, _) = book
+            .update(&inv.id, &[pay("late", 5, 1_061)], 1_061)
+            .unwrap();
+        assert_eq!(late.status, InvoiceStatus::Expired);
+
+        let (_, pruned) = book
+            .create(request(5), None, 1_060 + INVOICE_RETENTION_SECS + 1)
+            .unwrap();
+        assert_eq!(pruned, vec![inv.id.clone()]);
+        assert!(book.get(&inv.id).is_none());
+    }
+
+    #[test]
+    fn test_incoming_payments_follow_receives() {
+        let mut ledger = Ledger::new();
+        let block = |account: &str, previous: &str, block_type, link: &str, ts| Block {
+            account: account.to_string(),
+            previous: previous.to_string(),
+            block_type,
+            amount: 700,
+            link: link.to_string(),
+            signature: String::new(),
+            public_key: String::new(),
+            work: 0,
+            timestamp: ts,
+            fee: 0,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        let send = block("LOSpayer", "0", BlockType::Send, ADDR, 2_000);
+        let send_hash = send.calculate_hash();
+        let old = block(ADDR, "0", BlockType::Mint, "FAUCET", 1_000);
+        let recv = block(
+            ADDR,
+            &old.calculate_hash(),
+            BlockType::Receive,
+            &send_hash,
+            2_001,
+        );
+        for b in [&send, &old, &recv] {
+            ledger.blocks.insert(b.calculate_hash(), b.clone());
+        }
+        ledger.accounts.insert(
+            ADDR.to_string(),
+            AccountState {
+                head: recv.calculate_hash(),
+                balance: 1_400,
+                block_count: 2,
+                is_validator: false,
+            },
+        );
+        assert_eq!(
+            incoming_payments(&ledger, ADDR, 1_500),
+            vec![pay(&send_hash, 700, 2_001)]
+        );
+        assert!(incoming_payments(&ledger, ADDR, 2_002).is_empty());
+        assert!(incoming_payments(&ledger, "LOSnobody", 0).is_empty());
+    }
+}
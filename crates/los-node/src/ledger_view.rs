@@ -0,0 +1,118 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - READ-ONLY LEDGER SNAPSHOTS FOR THE REST API
+//
+// Block processing keeps exclusive ownership of `Arc<Mutex<Ledger>>` (single
+// writer). Read endpoints (/bal, /history, /account, /block, /search, ...)
+// use an immutable `Arc<Ledger>` snapshot instead. Holding one costs nothing,
+// so a long /history walk never holds up consensus, and a burst of readers
+// never queues behind a block.
+//
+// Every ledger mutation calls mark_changed() (via main.rs mark_dirty()). The
+// publisher clones the ledger at most once per PUBLISH_INTERVAL_MS when the
+// generation moved, then swaps the new snapshot in. Readers see state that
+// is at most one interval old. Write endpoints still lock the live ledger.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::Ledger;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// Snapshot refresh cadence while the ledger is changing
+pub const PUBLISH_INTERVAL_MS: u64 = 250;
+
+/// Bumped on every ledger mutation
+static LEDGER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Record that the live ledger changed (snapshot is now stale)
+pub fn mark_changed() {
+    LEDGER_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Live ledger generation (cache key for responses derived from ledger or
+/// contract state; restarts at 0 with the process)
+pub fn current_generation() -> u64 {
+    LEDGER_GENERATION.load(Ordering::Acquire)
+}
+
+pub struct LedgerView {
+    snapshot: RwLock<Arc<Ledger>>,
+    /// Generation the current snapshot was taken at
+    generation: AtomicU64,
+}
+
+impl LedgerView {
+    pub fn new(ledger: &Ledger) -> Self {
+        Self {
+            snapshot: RwLock::new(Arc::new(ledger.clone())),
+            generation: AtomicU64::new(LEDGER_GENERATION.load(Ordering::Acquire)),
+        }
+    }
+
+    /// Latest published snapshot (never blocks on block processing)
+    pub fn current(&self) -> Arc<Ledger> {
+        match self.snapshot.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Re-snapshot if the ledger changed since the last publish.
+    /// Returns true when a new snapshot was published.
+    pub fn refresh(&self, ledger: &Mutex<Ledger>) -> bool {
+        // Read the generation BEFORE cloning: a write that lands during the
+        // clone bumps it again and is picked up on the next refresh.
+        let generation = LEDGER_GENERATION.load(Ordering::Acquire);
+        if generation == self.generation.load(Ordering::Acquire) {
+            return false;
+        }
+        let fresh = Arc::new(crate::safe_lock(ledger).clone());
+        match self.snapshot.write() {
+            Ok(mut guard) => *guard = fresh,
+            Err(poisoned) => *poisoned.into_inner() = fresh,
+        }
+        self.generation.store(generation, Ordering::Release);
+        true
+    }
+}
+
+/// Background publisher for the API snapshot
+pub async fn run_publisher(view: Arc<LedgerView>, ledger: Arc<Mutex<Ledger>>) {
+    let mut interval = tokio::time::interval(Duration::from_millis(PUBLISH_INTERVAL_MS));
+    loop {
+        interval.tick().await;
+        view.refresh(&ledger);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::AccountState;
+
+    #[test]
+    fn test_snapshot_is_isolated_until_refresh() {
+        let ledger = Mutex::new(Ledger::new());
+        let view = LedgerView::new(&crate::safe_lock(&ledger));
+        let before = view.current();
+
+        crate::safe_lock(&ledger).accounts.insert(
+            "LOSreader".to_string(),
+            AccountState {
+                head: "0".to_string(),
+                balance: 7,
+                block_count: 0,
+                is_validator: false,
+            },
+        );
+        mark_changed();
+
+        // Readers holding the old snapshot are unaffected
+        assert!(!view.current().accounts.contains_key("LOSreader"));
+        assert!(view.refresh(&ledger));
+        assert_eq!(view.current().accounts["LOSreader"].balance, 7);
+        assert!(!before.accounts.contains_key("LOSreader"));
+        // No change → no re-clone
+        assert!(!view.refresh(&ledger));
+    }
+}
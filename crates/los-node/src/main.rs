@@ -8,29 +8,48 @@
 
 #![recursion_limit = "512"]
 
+use api_access::{filters::access_control, AccessConfig, ApiAccess, ApiTier};
 use base64::Engine as _;
+// REST bodies shared with los-client
+use los_api_types::{
+    AccountNextResponse, BalanceResponse, CallContractRequest, DeployContractRequest,
+    DryRunContractRequest, FeeEstimateResponse, SendRequest, TimestampWindow,
+};
 use los_consensus::abft::ABFTConsensus; // aBFT engine for consensus stats & safety validation
 use los_consensus::checkpoint::{
     CheckpointManager, CheckpointSignature, FinalityCheckpoint, PendingCheckpoint,
     CHECKPOINT_INTERVAL,
 }; // Finality checkpoints
 use los_consensus::slashing::SlashingManager; // Slashing enforcement
+use los_consensus::vote_batch::{
+    VoteBatcher, VoteEnvelope, VOTE_BATCH_INTERVAL_MS, VOTE_BATCH_PREFIX,
+}; // Batched vote envelopes
 use los_consensus::voting::calculate_voting_power; // Linear voting: Power = Stake
+use los_core::chain_spec::RewardPayout;
 use los_core::pow_mint::{verify_mining_hash, MiningState}; // PoW Mint distribution engine
-use los_core::validator_rewards::ValidatorRewardPool;
+use los_core::reward_claims;
+use los_core::reward_cosign::{
+    self, CoSignature, DistributionCertificate, DistributionKind, EpochDistribution,
+};
+use los_core::validator_rewards::{pro_rata_shares, ValidatorRewardPool};
 use los_core::{
     AccountState, Block, BlockType, Ledger, CIL_PER_LOS, MIN_VALIDATOR_REGISTER_CIL,
     MIN_VALIDATOR_STAKE_CIL,
 };
+use los_network::checkpoint_sign::{
+    final_checkpoint_message, parse_final_checkpoint, CheckpointSignRequest,
+    CheckpointSignResponse, CHECKPOINT_FINAL_PREFIX, CHECKPOINT_MAX_ATTEMPTS,
+    CHECKPOINT_RETRY_SECS,
+}; // Direct checkpoint signature collection
+use los_network::delta_sync::DeltaSyncResponse; // Frontier delta sync wire format
 use los_network::{LosNode, NetworkEvent};
-use los_vm::{dex_registry, token_registry, ContractCall, WasmEngine};
-use rate_limiter::{filters::rate_limit, RateLimiter};
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::sync::atomic::{AtomicBool, Ordering};
+use los_vm::{dex_registry, token_registry, WasmEngine};
+use rate_limiter::RateLimiter;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::io::{self, AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
-use zeroize::Zeroizing;
 
 /// Safe mutex lock that recovers from poisoned state instead of panicking.
 /// When a thread panics while holding a lock, the Mutex becomes "poisoned".
@@ -83,22 +102,60 @@ const TESTNET_INITIAL_BALANCE: u128 = 1000 * CIL_PER_LOS;
 /// Total supply: 21,936,236 LOS (protocol constant, validated against genesis on mainnet)
 const TOTAL_SUPPLY_LOS: u128 = 21_936_236;
 const TOTAL_SUPPLY_CIL: u128 = TOTAL_SUPPLY_LOS * CIL_PER_LOS;
-/// Testnet faucet payout per request (5,000 LOS).
-/// MAINNET: Faucet endpoint is disabled on mainnet builds — this value is never used.
-const FAUCET_AMOUNT_CIL: u128 = 5_000 * CIL_PER_LOS;
-
+/// Gas ceiling for POST /dry-run-contract: the consensus per-call cap, since
+/// the VM refuses anything larger
+const MAX_DRY_RUN_GAS: u64 = los_core::MAX_GAS_PER_CALL;
+
+mod address_labels; // Operator address labels (/admin/address-book) shown by /history + explorer
+mod api_access; // CORS origins, API key tiers, /admin/api-keys
+mod archive; // --archive: per-checkpoint state snapshots for historical queries
+mod body_limits; // Per-route REST body size, JSON depth and field-count caps (413/400)
+mod chain_hint; // Structured stale-`previous` errors (duplicate / fork / unknown) with recovery hints
+mod chain_verify; // los-node verify: offline account chain consistency check
+mod checkpoint_conflict; // Conflicting finalized checkpoints: halt, alert, los-node resolve-checkpoint + replay
+mod checkpoint_sync; // GET /checkpoints + --trust-checkpoint cold sync
+mod consensus_journal; // Pending sends + signed votes journaled to sled, replayed after a restart
+mod contract_apply; // Atomic ContractCall: ledger debit + VM execution + transfer credits
 mod db; // Sled database persistence
+mod delta_sync; // Frontier delta sync: compare peer frontiers, fetch only missing chains (GET /sync/progress)
+mod dev_mode; // los-node --dev local chain + POST /dev/fork
+mod event_retention; // Checkpoint-based rollup of old contract events into epoch summaries
+mod explorer_stats; // Incremental aggregates for /stats/overview, /blocks/recent, /accounts/top
+mod fork_monitor; // Account chain fork detection, FORK_ALERT gossip, GET /forks
 mod genesis;
+mod gossip_guard; // Pre-parse gossip checks: per-type size caps, schema sniffing, JSON depth limit
+mod gossip_lanes; // Priority lanes for network events: consensus > blocks > bulk sync
 mod grpc_server;
+mod health; // GET /health subsystem status evaluation
+mod http_cache; // ETag/If-None-Match + gzip/brotli for heavy read endpoints
+mod invoices; // POST /invoice + GET /invoice/:id/status: payment requests watched against incoming sends
+mod ledger_view; // Immutable ledger snapshots for REST reads (single writer)
 mod mempool; // Transaction mempool
 mod metrics; // Prometheus metrics
+mod mining_server; // Stratum-like job server for external miners
+mod netstats; // Gossip propagation delay + time-to-quorum percentiles (GET /netstats)
+mod oracle_feed; // Oracle price history (sled) + deviation alerts
+mod pex; // Signed peer exchange: validator endpoint gossip + P2P re-dial list
+mod quarantine; // Full checkpoint state root + self-quarantine on divergence
 mod rate_limiter; // Anti-spam rate limiter
+mod read_cache; // LRU of pure dry-run results keyed by contract state version
+mod reserves_proof; // GET /proof/reserves (account Merkle proofs, verified by los-light)
+mod seen_cache; // Persistent gossip dedup (blake3 seen-cache)
+mod send_batch; // POST /send-batch: grouped validation of client-signed sends
+mod signer; // Validator key behind a Signer: local key or remote signing daemon (los-node signer)
+mod source_registry; // POST /contract/:addr/verify: reproducible rebuilds, verified source bundles
+mod storage; // sled size reporting, restart-time compaction, snapshot retention, GET /storage
+mod sync_codec; // Full-ledger sync payloads: v1 gzip JSON, v2 bincode + zstd, per-peer negotiation
+mod telemetry; // Opt-in signed node telemetry + aggregation mode (GET /telemetry/network)
 mod testnet_config;
 mod tor_service; // Automatic Tor Hidden Service generation
 mod validator_api; // Validator key management (generate, import)
+mod validator_directory; // GET /validators + gRPC GetValidators: stake, uptime, probation, rewards, paging
 mod validator_rewards;
+mod verify_pool; // Parallel PoW + signature checks for sync batches (LOS_VERIFY_THREADS)
 use db::LosDatabase;
 use metrics::LosMetrics;
+use signer::{SharedSigner, Signer};
 use warp::Filter;
 
 const LEDGER_FILE: &str = "ledger_state.json";
@@ -106,6 +163,14 @@ const LEDGER_FILE: &str = "ledger_state.json";
 // Race condition protection: Atomic flags for save state
 static SAVE_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 static SAVE_DIRTY: AtomicBool = AtomicBool::new(false);
+/// UNIX seconds of the last successful database save (0 = none since startup)
+static LAST_SAVE_SECS: AtomicU64 = AtomicU64::new(0);
+
+/// Ledger changed: schedule a save and a new API snapshot
+fn mark_dirty() {
+    SAVE_DIRTY.store(true, Ordering::Release);
+    ledger_view::mark_changed();
+}
 
 /// Create a JSON API reply with automatic HTTP status code based on body content.
 ///
@@ -118,6 +183,22 @@ static SAVE_DIRTY: AtomicBool = AtomicBool::new(false);
 /// This replaces bare `warp::reply::json()` calls that always return HTTP 200,
 /// ensuring error responses get proper HTTP 4xx/5xx status codes.
 fn api_json(body: serde_json::Value) -> warp::reply::WithStatus<warp::reply::Json> {
+    let status = api_status(&body);
+    warp::reply::with_status(warp::reply::json(&body), status)
+}
+
+/// Like `api_json`, with ETag/If-None-Match and Accept-Encoding compression
+/// (heavy polled endpoints). `etag == None` derives the ETag from the body.
+fn api_cached(
+    body: serde_json::Value,
+    etag: Option<String>,
+    h: &http_cache::CacheHeaders,
+) -> warp::reply::Response {
+    http_cache::cached_reply(&body, api_status(&body), etag, h)
+}
+
+/// HTTP status of an API body: its "code", else 400 for errors, else 200
+fn api_status(body: &serde_json::Value) -> warp::http::StatusCode {
     let code = body
         .get("code")
         .and_then(|c| c.as_u64())
@@ -131,9 +212,7 @@ fn api_json(body: serde_json::Value) -> warp::reply::WithStatus<warp::reply::Jso
                 200
             }
         });
-    let status = warp::http::StatusCode::from_u16(code)
-        .unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR);
-    warp::reply::with_status(warp::reply::json(&body), status)
+    warp::http::StatusCode::from_u16(code).unwrap_or(warp::http::StatusCode::INTERNAL_SERVER_ERROR)
 }
 
 /// Format seconds into human-readable uptime string (e.g. "3d 12h 5m").
@@ -260,11 +339,7 @@ fn get_bootstrap_nodes() -> Vec<String> {
     }
 
     // Priority 2: Auto-discover from genesis config bootstrap_nodes[].host_address or onion_address
-    let genesis_path = if los_core::is_mainnet_build() {
-        "genesis_config.json"
-    } else {
-        "testnet-genesis/testnet_wallets.json"
-    };
+    let genesis_path = genesis::genesis_path_for(los_core::is_mainnet());
     if let Ok(json_data) = std::fs::read_to_string(genesis_path) {
         if let Ok(config) = serde_json::from_str::<genesis::GenesisConfig>(&json_data) {
             if let Some(ref nodes) = config.bootstrap_nodes {
@@ -308,51 +383,6 @@ fn get_bootstrap_nodes() -> Vec<String> {
     Vec::new()
 }
 
-// Request body structure for sending LOS
-#[derive(serde::Deserialize, serde::Serialize)]
-struct SendRequest {
-    from: Option<String>, // Sender address (if empty, use node's address)
-    target: String,
-    amount: u128,
-    amount_cil: Option<u128>, // Amount already in CIL (skips ×CIL_PER_LOS). Used by client-signed blocks.
-    signature: Option<String>, // Client-provided signature (if present, validate instead of signing)
-    public_key: Option<String>, // Sender's public key (hex-encoded, REQUIRED for signature verification)
-    previous: Option<String>,   // Previous block hash (for client-side signing)
-    work: Option<u64>,          // PoW nonce (if client pre-computed)
-    timestamp: Option<u64>,     // Client timestamp (used when client_signed to match signing_hash)
-    fee: Option<u128>,          // Client fee (used when client_signed to match signing_hash)
-}
-
-#[derive(serde::Deserialize, serde::Serialize)]
-struct DeployContractRequest {
-    owner: String,
-    bytecode: String, // base64 encoded WASM
-    initial_state: Option<BTreeMap<String, String>>,
-    amount_cil: Option<u128>,   // Initial CIL funding for contract
-    signature: Option<String>,  // Client-signed: Dilithium5 sig
-    public_key: Option<String>, // Client-signed: deployer's pubkey (hex)
-    previous: Option<String>,   // Client-signed: previous block hash
-    work: Option<u64>,          // Client-signed: PoW nonce
-    timestamp: Option<u64>,     // Client-signed: block timestamp
-    fee: Option<u128>,          // Client-signed: fee in CIL
-}
-
-#[derive(serde::Deserialize, serde::Serialize)]
-struct CallContractRequest {
-    contract_address: String,
-    function: String,
-    args: Vec<String>,
-    gas_limit: Option<u64>,
-    caller: Option<String>,    // Caller address (if empty, use node's address)
-    amount_cil: Option<u128>,  // CIL to send to contract (msg.value)
-    signature: Option<String>, // Client-signed: Dilithium5 sig
-    public_key: Option<String>, // Client-signed: caller's pubkey (hex)
-    previous: Option<String>,  // Client-signed: previous block hash
-    work: Option<u64>,         // Client-signed: PoW nonce
-    timestamp: Option<u64>,    // Client-signed: block timestamp
-    fee: Option<u128>,         // Client-signed: fee in CIL
-}
-
 /// Per-address endpoint rate limiter
 /// Tracks request timestamps per address for each endpoint type
 #[derive(Clone)]
@@ -459,11 +489,9 @@ impl EndpointRateLimiter {
 }
 
 // Helper: sign message and hex-encode — returns Result instead of panicking.
-// MAINNET SAFETY: A signing failure (corrupted key) no longer crashes the node.
-fn try_sign_hex(msg: &[u8], sk: &[u8]) -> Result<String, String> {
-    los_crypto::sign_message(msg, sk)
-        .map(hex::encode)
-        .map_err(|e| format!("Signing failed (key corrupted?): {:?}", e))
+// MAINNET SAFETY: A signing failure (corrupted key, remote signer down) no longer crashes the node.
+fn try_sign_hex(msg: &[u8], signer: &dyn Signer) -> Result<String, String> {
+    signer.sign(msg).map(hex::encode)
 }
 
 // Helper to inject state into route handlers
@@ -482,12 +510,15 @@ pub struct ApiServerConfig {
     pub pending_sends: Arc<Mutex<HashMap<String, (Block, u128)>>>,
     pub address_book: Arc<Mutex<HashMap<String, String>>>,
     pub my_address: String,
-    pub secret_key: Zeroizing<Vec<u8>>,
+    /// Validator key (local or remote signer)
+    pub signer: SharedSigner,
     pub api_port: u16,
     pub metrics: Arc<LosMetrics>,
     pub database: Arc<LosDatabase>,
     pub slashing_manager: Arc<Mutex<SlashingManager>>,
     pub node_public_key: Vec<u8>,
+    /// libp2p PeerId of the persisted network key (distinct from the validator key).
+    pub network_peer_id: String,
     /// Bootstrap validator addresses loaded from genesis config (NOT hardcoded).
     /// On mainnet: from genesis_config.json bootstrap_nodes.
     /// On testnet: from testnet_wallets.json wallets with role="validator".
@@ -514,6 +545,24 @@ pub struct ApiServerConfig {
     pub enable_mining: bool,
     /// Number of mining threads (--mine-threads N).
     pub mining_threads: usize,
+    /// Finality checkpoints — served via GET /checkpoints.
+    pub checkpoint_manager: Arc<Mutex<CheckpointManager>>,
+    /// Recorded account chain forks — served via GET /forks.
+    pub fork_monitor: Arc<Mutex<fork_monitor::ForkMonitor>>,
+    /// Latest storage maintenance pass — served via GET /storage.
+    pub storage_report: Arc<Mutex<storage::StorageReport>>,
+    /// State root divergence quarantine — reported by GET /health.
+    pub state_quarantine: Arc<Mutex<quarantine::StateQuarantine>>,
+    /// Validator listing shared with gRPC — served via GET /validators.
+    pub validator_directory: Arc<validator_directory::ValidatorDirectory>,
+    /// Contract source verification — POST /contract/:addr/verify, GET /contract/:addr/source.
+    pub source_registry: Arc<source_registry::SourceRegistry>,
+    /// Collected telemetry reports (aggregation mode only) — POST /telemetry, GET /telemetry/network.
+    pub telemetry_aggregator: Option<Arc<Mutex<telemetry::TelemetryAggregator>>>,
+    /// Block propagation delays and time to quorum — served via GET /netstats.
+    pub netstats: Arc<Mutex<netstats::NetStats>>,
+    /// Frontier delta sync session — served via GET /sync/progress.
+    pub delta_sync: Arc<Mutex<delta_sync::DeltaSync>>,
 }
 
 #[allow(clippy::type_complexity)]
@@ -524,12 +573,13 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         pending_sends,
         address_book,
         my_address,
-        secret_key,
+        signer,
         api_port,
         metrics,
         database,
         slashing_manager,
         node_public_key,
+        network_peer_id,
         bootstrap_validators,
         reward_pool,
         validator_endpoints,
@@ -540,28 +590,70 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         mining_state,
         enable_mining,
         mining_threads,
+        checkpoint_manager,
+        fork_monitor,
+        storage_report,
+        state_quarantine,
+        validator_directory,
+        source_registry,
+        telemetry_aggregator,
+        netstats,
+        delta_sync,
     } = cfg;
-    // Rate Limiter: 100 req/sec per IP, burst 200
+    // Rate Limiter: 100 req/sec per IP, burst 200 (requests without an API key)
     let limiter = RateLimiter::new(100, Some(200));
-    let rate_limit_filter = rate_limit(limiter.clone());
+
+    // CORS origins + API key tiers (LOS_CORS_ORIGINS, LOS_API_TIER_*, LOS_ADMIN_TOKEN)
+    let api_access = Arc::new(ApiAccess::new(
+        AccessConfig::from_env(),
+        database.load_api_keys().unwrap_or_else(|e| {
+            eprintln!("⚠️  Failed to load API keys: {}", e);
+            Vec::new()
+        }),
+    ));
+    let access_filter = access_control(api_access.clone(), limiter.clone(), metrics.clone());
+    {
+        let cfg = api_access.config();
+        println!(
+            "🔑 API access: {} key(s), free {}/s, paid {}/s, CORS {}, admin API {}",
+            api_access.list().len(),
+            cfg.free.requests_per_second,
+            cfg.paid.requests_per_second,
+            cfg.cors_origins
+                .as_ref()
+                .map_or("any origin".to_string(), |o| o.join(", ")),
+            if api_access.admin_enabled() {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+    }
+
+    // Operator labels shown next to addresses (/admin/address-book)
+    let labels = Arc::new(Mutex::new(address_labels::AddressLabels::load(
+        database.load_address_labels().unwrap_or_else(|e| {
+            eprintln!("⚠️  Failed to load address labels: {}", e);
+            Default::default()
+        }),
+    )));
 
     // Track node startup time for uptime calculation
     let start_time = std::time::Instant::now();
 
     // Per-address endpoint rate limiters
     let send_limiter = Arc::new(EndpointRateLimiter::new(10, 60)); // /send: 10 tx per 60 seconds
-    let faucet_limiter = Arc::new(EndpointRateLimiter::new(1, 120)); // /faucet: 1 per 2 minutes (testnet)
+    let batch_limiter = Arc::new(EndpointRateLimiter::new(6, 60)); // /send-batch: 6 batches per sender per 60 seconds
 
     // aBFT Consensus Engine — passed from main() via ApiServerConfig, shared with event loop
     // Initialize shared secret and validator set
     {
         let mut abft = safe_lock(&abft_consensus);
         // Set shared secret for MAC authentication (SHA3-256 of node's secret key)
-        use sha3::{Digest as Sha3Digest, Sha3_256 as Sha3256Hasher};
-        let mut hasher = Sha3256Hasher::new();
-        hasher.update(&*secret_key);
-        hasher.update(b"LOS_CONSENSUS_MAC_V1");
-        abft.set_shared_secret(hasher.finalize().to_vec());
+        match signer.derive_secret(signer::CONSENSUS_MAC_CONTEXT) {
+            Ok(secret) => abft.set_shared_secret(secret),
+            Err(e) => eprintln!("⚠️ Consensus MAC secret unavailable: {}", e),
+        }
 
         // Populate validator set with real addresses for leader selection
         let l = safe_lock(&ledger);
@@ -584,105 +676,248 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         );
     }
 
-    // 1. GET /bal/:address
-    let l_bal = ledger.clone();
-    let balance_route = warp::path!("bal" / String).and(with_state(l_bal)).map(
-        |addr: String, l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
-            let full_addr = l_guard
-                .accounts
-                .keys()
-                .find(|k| get_short_addr(k) == addr || **k == addr)
-                .cloned()
-                .unwrap_or(addr);
-            let acct = l_guard.accounts.get(&full_addr);
-            let bal = acct.map(|a| a.balance).unwrap_or(0);
-            let head = acct.map(|a| a.head.as_str()).unwrap_or("0");
-            let block_count = acct.map(|a| a.block_count).unwrap_or(0);
-            api_json(serde_json::json!({
-                "address": full_addr,
-                "balance_los": format_balance_precise(bal),
-                "balance_cil": bal,
-                "balance_cil_str": bal.to_string(),
-                "head": head,
-                "block_count": block_count
-            }))
-        },
-    );
+    // Read endpoints use immutable snapshots; block processing keeps the live ledger
+    let ledger_view = Arc::new(ledger_view::LedgerView::new(&safe_lock(&ledger)));
+    tokio::spawn(ledger_view::run_publisher(
+        ledger_view.clone(),
+        ledger.clone(),
+    ));
+
+    // 1. GET /bal/:address (?at_block=H on archive nodes)
+    let l_bal = ledger_view.clone();
+    let db_bal = database.clone();
+    let balance_route = warp::path!("bal" / String)
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((l_bal, db_bal)))
+        .map(
+            |addr: String,
+             params: HashMap<String, String>,
+             (l, db): (Arc<ledger_view::LedgerView>, Arc<LosDatabase>)| {
+                let l_guard = l.current();
+                let full_addr = l_guard
+                    .accounts
+                    .keys()
+                    .find(|k| get_short_addr(k) == addr || **k == addr)
+                    .cloned()
+                    .unwrap_or(addr);
+                if let Some(at) = params.get("at_block") {
+                    let Ok(at) = at.parse::<u64>() else {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 400, "msg": "at_block must be a block height"
+                        }));
+                    };
+                    return api_json(archive::historical_json(
+                        db.archive_account_at(&full_addr, at),
+                        at,
+                        |state: Option<AccountState>| {
+                            let bal = state.as_ref().map(|a| a.balance).unwrap_or(0);
+                            serde_json::json!({
+                                "address": full_addr,
+                                "at_block": at,
+                                "balance_los": format_balance_precise(bal),
+                                "balance_cil": bal,
+                                "balance_cil_str": bal.to_string(),
+                                "head": state.as_ref().map(|a| a.head.as_str()).unwrap_or("0"),
+                                "block_count": state.as_ref().map(|a| a.block_count).unwrap_or(0)
+                            })
+                        },
+                    ));
+                }
+                let acct = l_guard.accounts.get(&full_addr);
+                let bal = acct.map(|a| a.balance).unwrap_or(0);
+                let head = acct.map(|a| a.head.as_str()).unwrap_or("0");
+                let block_count = acct.map(|a| a.block_count).unwrap_or(0);
+                api_json(serde_json::json!({
+                    "address": full_addr,
+                    "balance_los": format_balance_precise(bal),
+                    "balance_cil": bal,
+                    "balance_cil_str": bal.to_string(),
+                    "head": head,
+                    "block_count": block_count
+                }))
+            },
+        );
 
     // 2. GET /supply
-    let l_sup = ledger.clone();
-    let supply_route = warp::path("supply")
-        .and(with_state(l_sup))
-        .map(|l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
-            let total_supply_cil = TOTAL_SUPPLY_CIL;
-            let remaining_cil = l_guard.distribution.remaining_supply;
-            let circulating_cil = total_supply_cil.saturating_sub(remaining_cil);
-            api_json(serde_json::json!({
-                "total_supply": format_balance_precise(total_supply_cil),
-                "total_supply_cil": total_supply_cil,
-                "circulating_supply": format_balance_precise(circulating_cil),
-                "circulating_supply_cil": circulating_cil,
-                "remaining_supply": format_balance_precise(remaining_cil),
-                "remaining_supply_cil": remaining_cil
-            }))
-        });
+    let l_sup = ledger_view.clone();
+    let supply_route =
+        warp::path("supply")
+            .and(with_state(l_sup))
+            .map(|l: Arc<ledger_view::LedgerView>| {
+                let l_guard = l.current();
+                let total_supply_cil = TOTAL_SUPPLY_CIL;
+                let remaining_cil = l_guard.distribution.remaining_supply;
+                let circulating_cil = total_supply_cil.saturating_sub(remaining_cil);
+                api_json(serde_json::json!({
+                    "total_supply": format_balance_precise(total_supply_cil),
+                    "total_supply_cil": total_supply_cil,
+                    "circulating_supply": format_balance_precise(circulating_cil),
+                    "circulating_supply_cil": circulating_cil,
+                    "remaining_supply": format_balance_precise(remaining_cil),
+                    "remaining_supply_cil": remaining_cil
+                }))
+            });
 
     // 3. GET /history/:address
-    let l_his = ledger.clone();
+    let l_his = ledger_view.clone();
     let ab_his = address_book.clone();
+    let labels_his = labels.clone();
     let history_route = warp::path!("history" / String)
-        .and(with_state((l_his, ab_his)))
-        .map(#[allow(clippy::type_complexity)] |addr: String, (l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
-            let l_guard = safe_lock(&l);
-            let target_full = if l_guard.accounts.contains_key(&addr) {
-                Some(addr)
-            } else {
-                let ab_guard = safe_lock(&ab);
-                if let Some(full) = ab_guard.get(&addr) {
-                    Some(full.clone())
+        .and(http_cache::cache_headers())
+        .and(with_state((l_his, ab_his, labels_his)))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |addr: String,
+             h: http_cache::CacheHeaders,
+             (l, ab, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let l_guard = l.current();
+                let target_full = if l_guard.accounts.contains_key(&addr) {
+                    Some(addr)
                 } else {
-                    l_guard.accounts.keys().find(|k| get_short_addr(k) == addr).cloned()
-                }
-            };
+                    let ab_guard = safe_lock(&ab);
+                    if let Some(full) = ab_guard.get(&addr) {
+                        Some(full.clone())
+                    } else {
+                        l_guard
+                            .accounts
+                            .keys()
+                            .find(|k| get_short_addr(k) == addr)
+                            .cloned()
+                    }
+                };
 
-            let mut history = Vec::new();
-            if let Some(full) = target_full {
-                if let Some(acct) = l_guard.accounts.get(&full) {
-                    let mut curr = acct.head.clone();
-                    while curr != "0" {
-                        if let Some(blk) = l_guard.blocks.get(&curr) {
-                            // Resolve actual sender for Receive blocks
-                            let from_addr = match blk.block_type {
-                                BlockType::Send => blk.account.clone(),
-                                BlockType::Receive => {
-                                    l_guard.blocks.get(&blk.link)
+                let mut history = Vec::new();
+                let mut etag = None;
+                let labels = safe_lock(&labels);
+                if let Some(full) = target_full {
+                    if let Some(acct) = l_guard.accounts.get(&full) {
+                        // History only changes when the account head moves (or a label does)
+                        let tag = http_cache::etag_for(&format!(
+                            "history:{}:{}:{}",
+                            full,
+                            acct.head,
+                            labels.version()
+                        ));
+                        if let Some(not_modified) = http_cache::not_modified(&tag, &h) {
+                            return not_modified;
+                        }
+                        etag = Some(tag);
+                        let mut curr = acct.head.clone();
+                        while curr != "0" {
+                            if let Some(blk) = l_guard.blocks.get(&curr) {
+                                // Resolve actual sender for Receive blocks
+                                let from_addr = match blk.block_type {
+                                    BlockType::Send => blk.account.clone(),
+                                    BlockType::Receive => l_guard
+                                        .blocks
+                                        .get(&blk.link)
                                         .map(|send_blk| send_blk.account.clone())
-                                        .unwrap_or_else(|| "SYSTEM".to_string())
-                                },
-                                _ => "SYSTEM".to_string(),
-                            };
-                            let to_addr = match blk.block_type {
-                                BlockType::Receive => blk.account.clone(),
-                                _ => blk.link.clone(),
-                            };
-                            history.push(serde_json::json!({
-                                "hash": curr,
-                                "from": from_addr,
-                                "to": to_addr,
-                                "amount": format!("{}.{:011}", blk.amount / CIL_PER_LOS, blk.amount % CIL_PER_LOS),
-                                "timestamp": blk.timestamp,
-                                "type": format!("{:?}", blk.block_type).to_lowercase(),
-                                "fee": blk.fee
-                            }));
-                            curr = blk.previous.clone();
-                        } else { break; }
+                                        .unwrap_or_else(|| "SYSTEM".to_string()),
+                                    _ => "SYSTEM".to_string(),
+                                };
+                                let to_addr = match blk.block_type {
+                                    BlockType::Receive => blk.account.clone(),
+                                    _ => blk.link.clone(),
+                                };
+                                let mut tx = serde_json::json!({
+                                    "hash": curr,
+                                    "from": from_addr,
+                                    "to": to_addr,
+                                    "amount": format_balance_precise(blk.amount),
+                                    "timestamp": blk.timestamp,
+                                    "type": format!("{:?}", blk.block_type).to_lowercase(),
+                                    "fee": blk.fee
+                                });
+                                labels.annotate(
+                                    &mut tx,
+                                    &[("from", "from_label"), ("to", "to_label")],
+                                );
+                                history.push(tx);
+                                curr = blk.previous.clone();
+                            } else {
+                                break;
+                            }
+                        }
                     }
                 }
-            }
-            api_json(serde_json::json!({"transactions": history}))
-        });
+                api_cached(serde_json::json!({"transactions": history}), etag, &h)
+            },
+        );
+
+    // 3b. GET /transactions?from=&to=&since=&limit= — counterparty index lookup
+    let l_txs = ledger_view.clone();
+    let db_txs = database.clone();
+    let labels_txs = labels.clone();
+    let transactions_route = warp::path("transactions")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((l_txs, db_txs, labels_txs)))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |params: HashMap<String, String>,
+             (l, db, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<LosDatabase>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let from = params.get("from").filter(|v| !v.is_empty());
+                let to = params.get("to").filter(|v| !v.is_empty());
+                let since = params
+                    .get("since")
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(0);
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(100)
+                    .clamp(1, 1_000);
+                let hashes = match db.query_transactions(
+                    from.map(String::as_str),
+                    to.map(String::as_str),
+                    since,
+                    limit,
+                ) {
+                    Ok(h) => h,
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": e
+                        }))
+                    }
+                };
+                let l_guard = l.current();
+                let labels = safe_lock(&labels);
+                let transactions: Vec<serde_json::Value> = hashes
+                    .iter()
+                    .filter_map(|hash| {
+                        let blk = l_guard.blocks.get(hash)?;
+                        let (from_addr, to_addr) = db::tx_parties(blk)?;
+                        let mut tx = serde_json::json!({
+                            "hash": hash,
+                            "from": from_addr,
+                            "to": to_addr,
+                            "amount": format_balance_precise(blk.amount),
+                            "amount_cil": blk.amount,
+                            "timestamp": blk.timestamp,
+                            "type": format!("{:?}", blk.block_type).to_lowercase(),
+                            "fee": blk.fee
+                        });
+                        labels.annotate(&mut tx, &[("from", "from_label"), ("to", "to_label")]);
+                        Some(tx)
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "count": transactions.len(),
+                    "transactions": transactions
+                }))
+            },
+        );
 
     // 4. GET /peers — enhanced with validator endpoint discovery
     let ab_peer = address_book.clone();
@@ -691,9 +926,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let bv_peer = bootstrap_validators.clone();
     let my_addr_peer = my_address.clone();
     let peers_route = warp::path("peers")
+        .and(http_cache::cache_headers())
         .and(with_state((ab_peer, ve_peer, l_peer)))
         .map(
-            move |(ab, ve, l): (
+            move |h: http_cache::CacheHeaders,
+                  (ab, ve, l): (
                 Arc<Mutex<HashMap<String, String>>>,
                 Arc<Mutex<HashMap<String, String>>>,
                 Arc<Mutex<Ledger>>,
@@ -726,6 +963,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         entry
                     })
                     .collect();
+                // Stable order, so an unchanged peer set keeps its ETag
+                peers.sort_by(|a, b| a["address"].as_str().cmp(&b["address"].as_str()));
 
                 // Include THIS node (self) in the peers list so the operator
                 // can see their own node listed alongside remote peers.
@@ -755,8 +994,10 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }
 
                 // Collect all known validator endpoints for discovery
-                let validator_endpoints: Vec<serde_json::Value> = ve_guard
-                    .iter()
+                let mut validator_endpoints: Vec<(&String, &String)> = ve_guard.iter().collect();
+                validator_endpoints.sort();
+                let validator_endpoints: Vec<serde_json::Value> = validator_endpoints
+                    .into_iter()
                     .map(|(addr, host)| {
                         serde_json::json!({
                             "address": addr,
@@ -766,12 +1007,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     })
                     .collect();
 
-                api_json(serde_json::json!({
-                    "peers": peers,
-                    "peer_count": peers.len(),
-                    "validator_endpoints": validator_endpoints,
-                    "validator_endpoint_count": validator_endpoints.len(),
-                }))
+                // Peers change without ledger writes: ETag from the body
+                api_cached(
+                    serde_json::json!({
+                        "peers": peers,
+                        "peer_count": peers.len(),
+                        "validator_endpoints": validator_endpoints,
+                        "validator_endpoint_count": validator_endpoints.len(),
+                    }),
+                    None,
+                    &h,
+                )
             },
         );
 
@@ -782,21 +1028,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let sl_send = send_limiter.clone();
     let pk_send = node_public_key.clone();
     let mp_send = mempool_pool.clone();
+    let ns_send = netstats.clone();
+    let db_send = database.clone();
     let send_route = warp::path("send")
         .and(warp::post())
-        .and(warp::body::bytes())
-        .and(with_state((l_send, tx_send, p_send, my_address.clone(), secret_key.clone(), sl_send, pk_send, mp_send)))
-        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, tx, p, my_addr, key, rate_lim, node_pk, mp): (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Arc<Mutex<HashMap<String, (Block, u128)>>>, String, Zeroizing<Vec<u8>>, Arc<EndpointRateLimiter>, Vec<u8>, Arc<Mutex<mempool::Mempool>>)| async move {
+        .and(body_limits::body(body_limits::SEND))
+        .and(with_state((l_send, tx_send, p_send, my_address.clone(), signer.clone(), sl_send, pk_send, mp_send, ns_send, db_send)))
+        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, tx, p, my_addr, key, rate_lim, node_pk, mp, ns, db): (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Arc<Mutex<HashMap<String, (Block, u128)>>>, String, SharedSigner, Arc<EndpointRateLimiter>, Vec<u8>, Arc<Mutex<mempool::Mempool>>, Arc<Mutex<netstats::NetStats>>, Arc<LosDatabase>)| async move {
             // Parse JSON manually to return proper 400 instead of 500
-            let req: SendRequest = match serde_json::from_slice(&body) {
+            let req: SendRequest = match body_limits::parse_json(&body, body_limits::SEND) {
                 Ok(r) => r,
-                Err(e) => {
-                    return api_json(serde_json::json!({
-                        "status": "error",
-                        "code": 400,
-                        "msg": format!("Invalid request body: {}", e)
-                    }));
-                }
+                Err(e) => return api_json(e),
             };
             // Determine sender: use req.from if provided, otherwise node's address
             let sender_addr = req.from.clone().unwrap_or(my_addr.clone());
@@ -911,7 +1153,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     // When client-signed, use client's fee (part of signing_hash)
                     // Server still validates the fee is >= base_fee
                     fee: if client_signed { req.fee.unwrap_or(0) } else { 0 },
+                    valid_after: req.valid_after.unwrap_or(0),
+                    expires_at: req.expires_at.unwrap_or(0),
                 };
+                if blk.expires_at != 0 && blk.expires_at <= blk.valid_from() {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": "expires_at must be after the send's timestamp/valid_after"
+                    }));
+                }
 
                 let initial_power: u128;
                 let base_fee = los_core::BASE_FEE_CIL; // Protocol constant from los-core
@@ -1008,7 +1258,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         eprintln!("❌ [SIGN_FAIL] work={}", blk.work);
                         eprintln!("❌ [SIGN_FAIL] timestamp={}", blk.timestamp);
                         eprintln!("❌ [SIGN_FAIL] fee={} CIL", blk.fee);
-                        eprintln!("❌ [SIGN_FAIL] chain_id={} (CHAIN_ID constant)", los_core::CHAIN_ID);
+                        eprintln!("❌ [SIGN_FAIL] chain_id={} (active chain spec)", los_core::chain_id());
                         eprintln!("❌ [SIGN_FAIL] signing_hash={}", sh);
                         return api_json(serde_json::json!({
                             "status": "error",
@@ -1020,7 +1270,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     // MAINNET SAFETY: On Production level, ALL transactions MUST be client-signed.
                     // Node auto-signing (even for its own address) is a testnet convenience only.
                     // On mainnet, the API caller must prove key ownership via signature.
-                    if los_core::is_mainnet_build() {
+                    if los_core::chain_spec::active().signature_policy.require_client_signatures {
                         return api_json(serde_json::json!({
                             "status": "error",
                             "msg": "Mainnet requires client-side signature. Provide signature + public_key fields."
@@ -1040,7 +1290,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     } else {
                         println!("🔑 Node auto-signing for own address (testnet convenience)");
                     }
-                    blk.signature = match try_sign_hex(blk.signing_hash().as_bytes(), &key) {
+                    blk.signature = match try_sign_hex(blk.signing_hash().as_bytes(), &*key) {
                         Ok(sig) => sig,
                         Err(e) => return api_json(serde_json::json!({"status": "error", "msg": e})),
                     };
@@ -1049,6 +1299,50 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // Block ID sekarang mencakup signature
                 let hash = blk.calculate_hash();
 
+                // Client-chosen previous: tell the wallet how to recover if it is stale
+                let skip_consensus = !testnet_config::get_testnet_config().should_enable_consensus();
+                if req.previous.is_some() {
+                    let pending: Vec<(String, Block)> = if skip_consensus {
+                        Vec::new()
+                    } else {
+                        safe_lock(&p)
+                            .iter()
+                            .filter(|(_, (b, _))| b.account == sender_addr)
+                            .map(|(h, (b, _))| (h.clone(), b.clone()))
+                            .collect()
+                    }; // PS dropped before L
+                    let checked = chain_hint::check_previous(&safe_lock(&l), &pending, &blk, &hash);
+                    if let Err(stale) = checked {
+                        return api_json(stale.to_json());
+                    }
+                }
+
+                // Time-locked send: held in the mempool (and on disk) until valid_after,
+                // then the release task starts the vote. Never finalized immediately.
+                if mempool::awaits_release(&blk, los_core::consensus_clock::now()) {
+                    let admission = safe_lock(&mp).admit(blk.clone(), blk.fee as u64, initial_power as u64);
+                    return match admission {
+                        Ok(admission) => {
+                            if let Err(e) = db.save_scheduled_send(&hash, &blk) {
+                                eprintln!("⚠️ Scheduled send write failed: {}", e);
+                            }
+                            for old in admission.replaced.iter().chain(admission.evicted.iter()) {
+                                let _ = db.remove_scheduled_send(old);
+                            }
+                            println!("⏰ Send {} scheduled for {} ({} → {})",
+                                get_short_addr(&hash), blk.valid_after, get_short_addr(&sender_addr), get_short_addr(&target));
+                            api_json(serde_json::json!({
+                                "status": "success",
+                                "tx_hash": hash,
+                                "scheduled": true,
+                                "valid_after": blk.valid_after,
+                                "fee_paid_cil": blk.fee
+                            }))
+                        }
+                        Err(e) => api_json(serde_json::json!({"status": "error", "msg": e})),
+                    };
+                }
+
                 // Finalize immediately when:
                 //   (a) Functional testnet (no consensus needed)
                 //
@@ -1062,7 +1356,6 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 //
                 // On mainnet: ALL sends go through CONFIRM_REQ/CONFIRM_RES voting.
                 // On testnet (functional mode): skip consensus for rapid testing.
-                let skip_consensus = !testnet_config::get_testnet_config().should_enable_consensus();
                 if skip_consensus {
                     {
                         let mut l_guard = safe_lock(&l);
@@ -1070,20 +1363,16 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         // Use blk.fee (not final_fee) because for client-signed blocks,
                         // blk.fee is what's in the signed block (may be >= final_fee)
                         let actual_fee = blk.fee;
+                        // Chain-sequence validation — prevents double-spend.
+                        // In block-lattice, each block references its predecessor. If two
+                        // blocks claim the same `previous`, only the first can be applied.
+                        // Without this check, a malicious client could submit conflicting
+                        // sends to the same node and both would succeed (balance check
+                        // alone is insufficient if the first tx hasn't been processed yet).
+                        if let Err(stale) = chain_hint::check_previous(&l_guard, &[], &blk, &hash) {
+                            return api_json(stale.to_json());
+                        }
                         if let Some(sender_state) = l_guard.accounts.get_mut(&sender_addr) {
-                            // Chain-sequence validation — prevents double-spend.
-                            // In block-lattice, each block references its predecessor. If two
-                            // blocks claim the same `previous`, only the first can be applied.
-                            // Without this check, a malicious client could submit conflicting
-                            // sends to the same node and both would succeed (balance check
-                            // alone is insufficient if the first tx hasn't been processed yet).
-                            if sender_state.head != blk.previous {
-                                return api_json(serde_json::json!({
-                                    "status": "error",
-                                    "msg": format!("Chain sequence error: expected previous={}, got={}",
-                                        sender_state.head, blk.previous)
-                                }));
-                            }
                             let total_debit = amt.saturating_add(actual_fee);
                             if sender_state.balance < total_debit {
                                 return api_json(serde_json::json!({
@@ -1104,7 +1393,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         // Accumulate fees
                         l_guard.accumulated_fees_cil = l_guard.accumulated_fees_cil.saturating_add(actual_fee);
                     }
-                    SAVE_DIRTY.store(true, Ordering::Release);
+                    mark_dirty();
                     let reason = if client_signed { "client-signed" } else { "functional testnet" };
                     println!("✅ Send finalized immediately ({}): {} → {} ({} LOS, fee {} CIL)",
                         reason, get_short_addr(&sender_addr), get_short_addr(&target), amt / CIL_PER_LOS, blk.fee);
@@ -1122,7 +1411,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         if let Some(sender_acct) = l_guard.accounts.get_mut(&sender_addr) {
                             if sender_acct.is_validator && sender_acct.balance < MIN_VALIDATOR_REGISTER_CIL {
                                 sender_acct.is_validator = false;
-                                SAVE_DIRTY.store(true, Ordering::Release);
+                                mark_dirty();
                                 println!("⚠️ Auto-unregistered validator {}: balance {} < minimum registration stake {} LOS",
                                     get_short_addr(&sender_addr),
                                     sender_acct.balance / CIL_PER_LOS,
@@ -1136,50 +1425,16 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     // The node creates it and gossips to all peers.
                     let recv_gossip: Option<String> = {
                         let mut l_guard = safe_lock(&l);
-                        if !l_guard.accounts.contains_key(&target) {
-                            l_guard.accounts.insert(target.clone(), AccountState {
-                                head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                            });
-                        }
-                        if let Some(recv_state) = l_guard.accounts.get(&target).cloned() {
-                            let mut recv_blk = Block {
-                                account: target.clone(),
-                                previous: recv_state.head,
-                                block_type: BlockType::Receive,
-                                amount: amt,
-                                link: hash.clone(),
-                                signature: "".to_string(),
-                                public_key: hex::encode(&node_pk),
-                                work: 0,
-                                timestamp: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
-                                fee: 0,
-                            };
-                            solve_pow(&mut recv_blk);
-                            recv_blk.signature = match try_sign_hex(recv_blk.signing_hash().as_bytes(), &key) {
-                                Ok(sig) => sig,
-                                Err(e) => { eprintln!("❌ Auto-Receive signing failed: {}", e); return api_json(serde_json::json!({"status": "error", "msg": e})); }
-                            };
-                            // Direct ledger manipulation for Receive block — bypass process_block()
-                            // because the node's public_key doesn't match the target's account address.
-                            let recv_hash = recv_blk.calculate_hash();
-                            if let Some(recv_acct) = l_guard.accounts.get_mut(&target) {
-                                recv_acct.balance = recv_acct.balance.saturating_add(amt);
-                                recv_acct.head = recv_hash.clone();
-                                recv_acct.block_count += 1;
+                        match create_auto_receive(&mut l_guard, &target, amt, &hash, &node_pk, &*key) {
+                            Ok(recv_blk) => {
+                                let recv_json = serde_json::to_string(&recv_blk).unwrap_or_default();
+                                Some(base64::engine::general_purpose::STANDARD.encode(recv_json.as_bytes()))
                             }
-                            l_guard.blocks.insert(recv_hash.clone(), recv_blk.clone());
-                            // Track claimed Send for double-receive prevention.
-                            // Direct ledger manipulation bypasses process_block() which normally
-                            // inserts into claimed_sends. Without this, a second Receive referencing
-                            // the same Send could pass the claimed_sends check in process_block().
-                            l_guard.claimed_sends.insert(hash.clone());
-                            SAVE_DIRTY.store(true, Ordering::Release);
-                            println!("✅ Auto-Receive created for {} ({} CIL)", get_short_addr(&target), amt);
-                            let recv_json = serde_json::to_string(&recv_blk).unwrap_or_default();
-                            let recv_b64_for_gossip = base64::engine::general_purpose::STANDARD.encode(recv_json.as_bytes());
-                            Some(recv_b64_for_gossip)
-                        } else { None }
+                            Err(e) => {
+                                eprintln!("❌ Auto-Receive signing failed: {}", e);
+                                return api_json(serde_json::json!({"status": "error", "msg": e}));
+                            }
+                        }
                     }; // l_guard dropped
 
                     // Gossip as BLOCK_CONFIRMED:send_b64:recv_b64 so peers apply via P2P handler.
@@ -1194,7 +1449,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "tx_hash":hash,
                         "initial_power": initial_power,
                         "fee_paid_cil": blk.fee,
-                        "fee_multiplier_bps": if base_fee > 0 { blk.fee * 10_000 / base_fee } else { 10_000 }
+                        "fee_multiplier_bps": (blk.fee * 10_000).checked_div(base_fee).unwrap_or(10_000)
                     }));
                 }
 
@@ -1202,6 +1457,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // The sender doesn't self-vote — only distinct external validators contribute
                 // voting power via CONFIRM_RES. initial_power is kept for API response & mempool.
                 safe_lock(&p).insert(hash.clone(), (blk.clone(), 0u128));
+                if let Err(e) = db.save_inflight_send(&hash, &blk) {
+                    eprintln!("⚠️ In-flight journal write failed: {}", e);
+                }
 
                 // Serialize block BEFORE mempool takes ownership.
                 // Include block data (base64) so peers can validate and vote.
@@ -1214,28 +1472,163 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 {
                     let fee_u64 = blk.fee as u64;
                     let priority = initial_power as u64;
-                    let _ = safe_lock(&mp).add_transaction(blk, fee_u64, priority);
+                    if let Ok(admission) = safe_lock(&mp).admit(blk, fee_u64, priority) {
+                        if let Some(old) = admission.replaced {
+                            println!("♻️  Mempool: {} replaced {} (higher fee)",
+                                get_short_addr(&admission.tx_hash), get_short_addr(&old));
+                        }
+                    }
                 }
 
                 let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                safe_lock(&ns).note_published(&hash, ts as u64);
                 let _ = tx.send(format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, sender_addr, amt, ts, block_b64)).await;
                 api_json(serde_json::json!({
                     "status":"success",
                     "tx_hash":hash,
                     "initial_power": initial_power,
                     "fee_paid_cil": final_fee,
-                    "fee_multiplier_bps": if base_fee > 0 { final_fee * 10_000 / base_fee } else { 10_000 }
+                    "fee_multiplier_bps": (final_fee * 10_000).checked_div(base_fee).unwrap_or(10_000)
                 }))
             } else {
                 api_json(serde_json::json!({"status":"error","msg":"Address not found"}))
             }
         });
 
+    // 6b. POST /send-batch — many client-signed Send blocks in one request
+    let l_batch = ledger.clone();
+    let tx_batch = tx_out.clone();
+    let p_batch = pending_sends.clone();
+    let mp_batch = mempool_pool.clone();
+    let pk_batch = node_public_key.clone();
+    let ns_batch = netstats.clone();
+    let db_batch = database.clone();
+    let send_batch_route = warp::path("send-batch")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(body_limits::body(body_limits::SEND_BATCH))
+        .and(with_state((l_batch, tx_batch, p_batch, mp_batch, batch_limiter, pk_batch, signer.clone(), ns_batch, db_batch)))
+        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, tx, p, mp, rate_lim, node_pk, key, ns, db): (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Arc<Mutex<HashMap<String, (Block, u128)>>>, Arc<Mutex<mempool::Mempool>>, Arc<EndpointRateLimiter>, Vec<u8>, SharedSigner, Arc<Mutex<netstats::NetStats>>, Arc<LosDatabase>)| async move {
+            let req: send_batch::SendBatchRequest = match body_limits::parse_json(&body, body_limits::SEND_BATCH) {
+                Ok(r) => r,
+                Err(e) => return api_json(e),
+            };
+            if req.blocks.is_empty() || req.blocks.len() > send_batch::MAX_BATCH_SIZE {
+                return api_json(serde_json::json!({
+                    "status": "error",
+                    "code": 400,
+                    "msg": format!("Batch must contain 1-{} blocks", send_batch::MAX_BATCH_SIZE)
+                }));
+            }
+
+            // RATE LIMIT: per sender address, counted once per batch
+            let senders: BTreeSet<String> = req.blocks.iter().map(|b| b.account.clone()).collect();
+            for sender in &senders {
+                if let Err(wait_secs) = rate_lim.check_and_record(sender) {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "code": 429,
+                        "msg": format!("Rate limit exceeded for {}: try again in {} seconds.", get_short_addr(sender), wait_secs)
+                    }));
+                }
+            }
+
+            // Signatures + PoW in parallel, off the async runtime
+            let blocks = req.blocks;
+            let (blocks, crypto_ok) = match tokio::task::spawn_blocking(move || {
+                let ok = send_batch::verify_crypto(&blocks);
+                (blocks, ok)
+            }).await {
+                Ok(r) => r,
+                Err(e) => return api_json(serde_json::json!({"status": "error", "code": 500, "msg": format!("Verification task failed: {}", e)})),
+            };
+
+            // DEADLOCK Never hold PS and L simultaneously: snapshot pending first.
+            let pending_snapshot = safe_lock(&p).clone();
+            let verdicts = {
+                let l_guard = safe_lock(&l);
+                send_batch::validate_batch(&l_guard, &pending_snapshot, &blocks, &crypto_ok, los_core::consensus_clock::now())
+            };
+
+            let skip_consensus = !testnet_config::get_testnet_config().should_enable_consensus();
+            let mut results = Vec::with_capacity(blocks.len());
+            let mut gossip: Vec<String> = Vec::new();
+            let mut failed: HashMap<String, usize> = HashMap::new();
+            for (i, (blk, verdict)) in blocks.into_iter().zip(verdicts).enumerate() {
+                let hash = match verdict {
+                    Ok(h) => h,
+                    Err(e) => {
+                        results.push(send_batch::BatchItemStatus::rejected(i, Some(blk.calculate_hash()), e));
+                        continue;
+                    }
+                };
+                if let Some(j) = failed.get(&blk.previous) {
+                    let e = format!("Depends on rejected item #{}", j);
+                    failed.insert(hash.clone(), i);
+                    results.push(send_batch::BatchItemStatus::rejected(i, Some(hash), e));
+                    continue;
+                }
+                let block_b64 = base64::engine::general_purpose::STANDARD
+                    .encode(serde_json::to_string(&blk).unwrap_or_default().as_bytes());
+                if skip_consensus {
+                    // Functional testnet: finalize immediately, like POST /send
+                    let mut l_guard = safe_lock(&l);
+                    if let Err(e) = l_guard.process_block(&blk) {
+                        failed.insert(hash.clone(), i);
+                        results.push(send_batch::BatchItemStatus::rejected(i, Some(hash), e));
+                        continue;
+                    }
+                    mark_dirty();
+                    match create_auto_receive(&mut l_guard, &blk.link, blk.amount, &hash, &node_pk, &*key) {
+                        Ok(recv_blk) => {
+                            let recv_b64 = base64::engine::general_purpose::STANDARD
+                                .encode(serde_json::to_string(&recv_blk).unwrap_or_default().as_bytes());
+                            gossip.push(format!("BLOCK_CONFIRMED:{}:{}", block_b64, recv_b64));
+                        }
+                        Err(e) => eprintln!("❌ Auto-Receive signing failed: {}", e),
+                    }
+                } else {
+                    if let Err(e) = safe_lock(&mp).admit(blk.clone(), blk.fee as u64, blk.fee as u64) {
+                        failed.insert(hash.clone(), i);
+                        results.push(send_batch::BatchItemStatus::rejected(i, Some(hash), e));
+                        continue;
+                    }
+                    safe_lock(&p).insert(hash.clone(), (blk.clone(), 0u128));
+                    if let Err(e) = db.save_inflight_send(&hash, &blk) {
+                        eprintln!("⚠️ In-flight journal write failed: {}", e);
+                    }
+                    let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                    safe_lock(&ns).note_published(&hash, ts as u64);
+                    gossip.push(format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, blk.account, blk.amount, ts, block_b64));
+                }
+                results.push(send_batch::BatchItemStatus::accepted(i, hash));
+            }
+
+            let accepted = results.iter().filter(|r| r.status == "accepted").count();
+            println!("📦 Send batch: {}/{} accepted from {} sender(s)", accepted, results.len(), senders.len());
+
+            // Pipelined publication in batch order; the response does not wait on gossip
+            if !gossip.is_empty() {
+                tokio::spawn(async move {
+                    for msg in gossip {
+                        let _ = tx.send(msg).await;
+                    }
+                });
+            }
+
+            api_json(serde_json::json!({
+                "status": if accepted > 0 { "success" } else { "error" },
+                "accepted": accepted,
+                "rejected": results.len() - accepted,
+                "results": results
+            }))
+        });
+
     // 7. POST /deploy-contract (PERMISSIONLESS — create ContractDeploy block)
     let deploy_route = {
         let l_deploy = ledger.clone();
         let tx_deploy = tx_out.clone();
-        let sk_deploy = secret_key.clone();
+        let signer_deploy = signer.clone();
         let pk_deploy = node_public_key.clone();
         let addr_deploy = my_address.clone();
         let engine_deploy = wasm_engine.clone();
@@ -1243,18 +1636,13 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         let m_deploy = metrics.clone();
         let deploy = warp::path("deploy-contract")
             .and(warp::post())
-            .and(warp::body::bytes())
-            .and(with_state((l_deploy, tx_deploy, sk_deploy, pk_deploy, addr_deploy, engine_deploy, db_deploy, m_deploy)))
-            .then(|body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Zeroizing<Vec<u8>>, Vec<u8>, String, Arc<WasmEngine>, Arc<LosDatabase>, Arc<LosMetrics>)| async move {
-                let (l, tx, sk, pk, my_addr, engine, db, metrics) = state;
-                let req: DeployContractRequest = match serde_json::from_slice(&body) {
+            .and(body_limits::body(body_limits::DEPLOY))
+            .and(with_state((l_deploy, tx_deploy, signer_deploy, pk_deploy, addr_deploy, engine_deploy, db_deploy, m_deploy)))
+            .then(|body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, mpsc::Sender<String>, SharedSigner, Vec<u8>, String, Arc<WasmEngine>, Arc<LosDatabase>, Arc<LosMetrics>)| async move {
+                let (l, tx, signer, pk, my_addr, engine, db, metrics) = state;
+                let req: DeployContractRequest = match body_limits::parse_json(&body, body_limits::DEPLOY) {
                     Ok(r) => r,
-                    Err(e) => {
-                        return api_json(serde_json::json!({
-                            "status": "error", "code": 400,
-                            "msg": format!("Invalid request body: {}", e)
-                        }))
-                    }
+                    Err(e) => return api_json(e),
                 };
                 // Decode base64 WASM bytecode
                 let bytecode = match base64::engine::general_purpose::STANDARD.decode(&req.bytecode) {
@@ -1263,16 +1651,37 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         return api_json(serde_json::json!({"status":"error","msg":"Invalid base64 bytecode"}))
                     }
                 };
+                // Optional size pass: the optimized bytes are hashed, stored and gossiped
+                let (bytecode, optimization) = if req.optimize.unwrap_or(false) {
+                    match los_vm::optimize::optimize(&bytecode) {
+                        Ok((optimized, report)) => (optimized, Some(report)),
+                        Err(e) => {
+                            return api_json(serde_json::json!({"status":"error","code":400,"msg":e}))
+                        }
+                    }
+                } else {
+                    (bytecode, None)
+                };
                 // Compute code hash for block link
                 let code_hash = WasmEngine::compute_code_hash(&bytecode);
-                let link = format!("DEPLOY:{}", code_hash);
+                // Optional salt: deterministic address blake3(owner ‖ salt ‖ code_hash)
+                let salt = match req.salt.as_deref().map(los_core::deploy_link::parse_salt) {
+                    None => None,
+                    Some(Ok(s)) => Some(s),
+                    Some(Err(e)) => {
+                        return api_json(serde_json::json!({"status":"error","code":400,"msg":e}))
+                    }
+                };
+                let link = los_core::deploy_link::DeployLink::format(&code_hash, salt.as_ref());
                 let amount_cil = req.amount_cil.unwrap_or(0);
                 let is_client_signed = req.signature.is_some() && req.public_key.is_some();
 
                 // MAINNET GUARD: Server-signed deploys are disabled on mainnet.
                 // All contract deployments on mainnet MUST be client-signed (with signature + public_key).
                 // This prevents the node from deploying contracts with its own key on behalf of anonymous callers.
-                if los_core::is_mainnet_build() && !is_client_signed {
+                if los_core::chain_spec::active().signature_policy.require_client_signatures
+                    && !is_client_signed
+                {
                     return api_json(serde_json::json!({
                         "status": "error",
                         "code": 403,
@@ -1290,7 +1699,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     let pk_hex = req.public_key.clone().unwrap_or_default();
                     let pk_bytes = hex::decode(&pk_hex).unwrap_or_default();
                     let derived = los_crypto::public_key_to_address(&pk_bytes);
-                    (derived, pk_hex)
+                    // A session key signs for the account that granted it
+                    // (session_keys.rs); the ledger checks the grant
+                    (req.caller.clone().unwrap_or(derived), pk_hex)
                 } else {
                     (my_addr.clone(), hex::encode(&pk))
                 };
@@ -1316,6 +1727,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     work: req.work.unwrap_or(0),
                     timestamp: req.timestamp.unwrap_or(now_ts),
                     fee,
+                    valid_after: 0,
+                    expires_at: 0,
                 };
 
                 // PoW + Signing
@@ -1323,7 +1736,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     block.signature = req.signature.unwrap_or_default();
                 } else {
                     solve_pow(&mut block);
-                    block.signature = match try_sign_hex(block.signing_hash().as_bytes(), &sk) {
+                    block.signature = match try_sign_hex(block.signing_hash().as_bytes(), &*signer) {
                         Ok(sig) => sig,
                         Err(e) => {
                             return api_json(serde_json::json!({"status":"error","msg":format!("Signing failed: {}", e)}))
@@ -1331,37 +1744,33 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     };
                 }
 
-                // Process block through ledger (debit fees + optional funding)
-                let block_hash = {
+                // A taken salted address would fail in the VM after the fee is paid
+                if let Some(salt) = &salt {
+                    let predicted = WasmEngine::salted_address(&account, salt, &code_hash);
+                    if engine.contract_exists(&predicted).unwrap_or(false) {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 409,
+                            "msg": format!("Contract address {} is already taken (salt reused)", predicted)
+                        }));
+                    }
+                }
+
+                // Debit fees + optional funding, deploy and fund (contract_apply.rs)
+                let block_hash = block.calculate_hash();
+                let contract_addr = {
                     let mut l_guard = safe_lock(&l);
-                    match l_guard.process_block(&block) {
-                        Ok(result) => result.into_hash(),
+                    if let Err(stale) = chain_hint::check_previous(&l_guard, &[], &block, &block_hash) {
+                        return api_json(stale.to_json());
+                    }
+                    let initial_state = req.initial_state.unwrap_or_default();
+                    match contract_apply::apply_deploy_block(&mut l_guard, &engine, &block, bytecode.clone(), initial_state) {
+                        Ok(addr) => addr,
                         Err(e) => {
                             return api_json(serde_json::json!({"status":"error","msg":e}))
                         }
                     }
                 };
 
-                // Deploy bytecode to WASM engine
-                let contract_addr = match engine.deploy_contract(
-                    account.clone(),
-                    bytecode.clone(),
-                    req.initial_state.unwrap_or_default(),
-                    now_ts,
-                ) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        return api_json(serde_json::json!({"status":"error","msg":format!("VM deploy failed: {}", e)}))
-                    }
-                };
-
-                // Fund contract if amount > 0
-                if amount_cil > 0 {
-                    if let Err(e) = engine.send_to_contract(&contract_addr, amount_cil) {
-                        eprintln!("Warning: Failed to fund contract: {}", e);
-                    }
-                }
-
                 // Persist VM state to DB
                 if let Ok(vm_data) = engine.serialize_all() {
                     let _ = db.save_contracts(&vm_data);
@@ -1375,7 +1784,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let gossip = format!("CONTRACT_DEPLOYED:{}:{}:{}", block_b64, bytecode_b64, contract_addr);
                 let _ = tx.send(gossip).await;
 
-                SAVE_DIRTY.store(true, Ordering::Release);
+                mark_dirty();
                 metrics.contracts_deployed_total.inc();
 
                 api_json(serde_json::json!({
@@ -1385,14 +1794,16 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "block_hash": block_hash,
                     "owner": account,
                     "fee_cil": fee,
-                    "deployed_at": now_ts
+                    "deployed_at": now_ts,
+                    "salt": salt.map(hex::encode),
+                    "optimization": optimization
                 }))
             });
 
         // 8. POST /call-contract (create ContractCall block + execute)
         let l_call = ledger.clone();
         let tx_call = tx_out.clone();
-        let sk_call = secret_key.clone();
+        let signer_call = signer.clone();
         let pk_call = node_public_key.clone();
         let addr_call = my_address.clone();
         let engine_call = wasm_engine.clone();
@@ -1400,20 +1811,21 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         let m_call = metrics.clone();
         let call = warp::path("call-contract")
             .and(warp::post())
-            .and(warp::body::bytes())
-            .and(with_state((l_call, tx_call, sk_call, pk_call, addr_call, engine_call, db_call, m_call)))
-            .then(|body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, mpsc::Sender<String>, Zeroizing<Vec<u8>>, Vec<u8>, String, Arc<WasmEngine>, Arc<LosDatabase>, Arc<LosMetrics>)| async move {
-                let (l, tx, sk, pk, my_addr, engine, db, metrics) = state;
-                let req: CallContractRequest = match serde_json::from_slice(&body) {
+            .and(body_limits::body(body_limits::CALL))
+            .and(with_state((l_call, tx_call, signer_call, pk_call, addr_call, engine_call, db_call, m_call)))
+            .then(|body: bytes::Bytes, state: (Arc<Mutex<Ledger>>, mpsc::Sender<String>, SharedSigner, Vec<u8>, String, Arc<WasmEngine>, Arc<LosDatabase>, Arc<LosMetrics>)| async move {
+                let (l, tx, signer, pk, my_addr, engine, db, metrics) = state;
+                let req: CallContractRequest = match body_limits::parse_json(&body, body_limits::CALL) {
                     Ok(r) => r,
-                    Err(e) => {
-                        return api_json(serde_json::json!({
-                            "status": "error", "code": 400,
-                            "msg": format!("Invalid request body: {}", e)
-                        }))
-                    }
+                    Err(e) => return api_json(e),
                 };
                 let gas_limit = req.gas_limit.unwrap_or(los_core::DEFAULT_GAS_LIMIT);
+                if gas_limit > los_core::MAX_GAS_PER_CALL {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": format!("gas_limit {} exceeds per-call cap {}", gas_limit, los_core::MAX_GAS_PER_CALL)
+                    }));
+                }
                 let amount_cil = req.amount_cil.unwrap_or(0);
                 let fee = req.fee.unwrap_or(los_core::MIN_CALL_FEE_CIL.max(
                     (gas_limit as u128).saturating_mul(los_core::GAS_PRICE_CIL)
@@ -1423,7 +1835,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 // MAINNET GUARD: Server-signed contract calls are disabled on mainnet.
                 // All contract calls on mainnet MUST be client-signed (with signature + public_key).
                 // This prevents the node from signing transactions on behalf of anonymous callers.
-                if los_core::is_mainnet_build() && !is_client_signed {
+                if los_core::chain_spec::active().signature_policy.require_client_signatures
+                    && !is_client_signed
+                {
                     return api_json(serde_json::json!({
                         "status": "error",
                         "code": 403,
@@ -1445,7 +1859,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     let pk_hex = req.public_key.clone().unwrap_or_default();
                     let pk_bytes = hex::decode(&pk_hex).unwrap_or_default();
                     let derived = los_crypto::public_key_to_address(&pk_bytes);
-                    (derived, pk_hex)
+                    // A session key signs for the account that granted it
+                    // (session_keys.rs); the ledger checks the grant
+                    (req.caller.clone().unwrap_or(derived), pk_hex)
                 } else {
                     let caller = req.caller.clone().unwrap_or_else(|| my_addr.clone());
                     // If caller != node, still use node's key (node-signed on behalf)
@@ -1473,13 +1889,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     work: req.work.unwrap_or(0),
                     timestamp: req.timestamp.unwrap_or(now_ts),
                     fee,
+                    valid_after: 0,
+                    expires_at: 0,
                 };
 
                 if is_client_signed {
                     block.signature = req.signature.unwrap_or_default();
                 } else {
                     solve_pow(&mut block);
-                    block.signature = match try_sign_hex(block.signing_hash().as_bytes(), &sk) {
+                    block.signature = match try_sign_hex(block.signing_hash().as_bytes(), &*signer) {
                         Ok(sig) => sig,
                         Err(e) => {
                             return api_json(serde_json::json!({"status":"error","msg":format!("Signing failed: {}", e)}))
@@ -1487,64 +1905,34 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     };
                 }
 
-                // Process block through ledger (debit fee + value)
-                let block_hash = {
+                // Execution waits for a VM slot while holding the ledger
+                // lock: when the VM is saturated, answer 429 before taking it
+                if let Err(e) = engine.wait_for_exec_slot() {
+                    return api_json(serde_json::json!({"status":"error","code":429,"msg":e}));
+                }
+
+                // Execute, debit fee + value, credit transfers and commit VM
+                // state as one unit (contract_apply.rs). Gas limit = fee / GAS_PRICE_CIL,
+                // the same limit every replicating node uses.
+                let (block_hash, exec_result) = {
                     let mut l_guard = safe_lock(&l);
-                    match l_guard.process_block(&block) {
-                        Ok(result) => result.into_hash(),
+                    if let Err(stale) = chain_hint::check_previous(&l_guard, &[], &block, &block.calculate_hash()) {
+                        return api_json(stale.to_json());
+                    }
+                    match contract_apply::apply_contract_block(&mut l_guard, &engine, &block) {
+                        Ok(outcome) => (outcome.block_hash, outcome.result),
                         Err(e) => {
                             return api_json(serde_json::json!({"status":"error","msg":e}))
                         }
                     }
                 };
 
-                // Send CIL to contract if amount > 0
-                if amount_cil > 0 {
-                    if let Err(e) = engine.send_to_contract(&req.contract_address, amount_cil) {
-                        return api_json(serde_json::json!({"status":"error","msg":format!("Value transfer failed: {}", e)}));
-                    }
-                }
-
-                // Execute contract call on WASM engine
-                let call = ContractCall {
-                    contract: req.contract_address.clone(),
-                    function: req.function.clone(),
-                    args: req.args.clone(),
-                    gas_limit,
-                    caller: account.clone(),
-                    block_timestamp: block.timestamp,
-                };
-
-                let exec_result = match engine.call_contract(call) {
-                    Ok(result) => result,
-                    Err(e) => {
-                        return api_json(serde_json::json!({"status":"error","msg":format!("Execution failed: {}", e)}))
-                    }
-                };
-
-                // Persist VM state to DB
-                if let Ok(vm_data) = engine.serialize_all() {
-                    let _ = db.save_contracts(&vm_data);
-                }
-
-                // CRITICAL: Credit recipients from contract transfers.
-                // host_transfer() already decremented the contract's balance in the VM.
-                // Without this, transferred CIL is burned (never credited to recipients).
-                if !exec_result.transfers.is_empty() {
-                    let mut l_guard = safe_lock(&l);
-                    for (recipient, amount) in &exec_result.transfers {
-                        if let Some(recv_acc) = l_guard.accounts.get_mut(recipient) {
-                            recv_acc.balance = recv_acc.balance.saturating_add(*amount);
-                        } else {
-                            // Create new account if recipient doesn't exist yet
-                            l_guard.accounts.insert(recipient.clone(), los_core::AccountState {
-                                head: "0".to_string(),
-                                balance: *amount,
-                                block_count: 0,
-                                is_validator: false,
-                            });
-                        }
-                    }
+                // Persist VM state and the call's events to DB
+                if let Ok(vm_data) = engine.serialize_all() {
+                    let _ = db.save_contracts(&vm_data);
+                }
+                if let Err(e) = db.save_contract_events(&block_hash, &exec_result.events) {
+                    eprintln!("⚠️ Failed to store events of {}: {}", block_hash, e);
                 }
 
                 // Gossip to peers
@@ -1554,7 +1942,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let gossip = format!("CONTRACT_CALLED:{}", block_b64);
                 let _ = tx.send(gossip).await;
 
-                SAVE_DIRTY.store(true, Ordering::Release);
+                mark_dirty();
                 metrics.contract_executions_total.inc();
 
                 api_json(serde_json::json!({
@@ -1567,6 +1955,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "state_changes": exec_result.state_changes,
                         "events": exec_result.events,
                         "transfers": exec_result.transfers.iter()
+                            .map(|(addr, amt)| serde_json::json!({"recipient": addr, "amount_cil": amt}))
+                            .collect::<Vec<_>>(),
+                        "contract_transfers": exec_result.contract_transfers.iter()
                             .map(|(addr, amt)| serde_json::json!({"recipient": addr, "amount_cil": amt}))
                             .collect::<Vec<_>>()
                     },
@@ -1575,12 +1966,116 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 }))
             });
 
+        // 8a. POST /dry-run-contract (execute against a snapshot, apply nothing;
+        //     "trace": true adds host calls + instruction histogram)
+        //     Pure reads are answered from read_cache until the contract changes.
+        let engine_dry = wasm_engine.clone();
+        let addr_dry = my_address.clone();
+        let read_cache = Arc::new(read_cache::ReadCache::default());
+        let metrics_dry = metrics.clone();
+        let dry_run = warp::path("dry-run-contract")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(body_limits::body(body_limits::CALL))
+            .and(with_state((engine_dry, addr_dry, read_cache, metrics_dry)))
+            .then(
+                #[allow(clippy::type_complexity)]
+                |body: bytes::Bytes,
+                 state: (
+                    Arc<WasmEngine>,
+                    String,
+                    Arc<read_cache::ReadCache>,
+                    Arc<LosMetrics>,
+                )| async move {
+                let (engine, my_addr, cache, metrics) = state;
+                let req: DryRunContractRequest = match body_limits::parse_json(&body, body_limits::CALL) {
+                    Ok(r) => r,
+                    Err(e) => return api_json(e),
+                };
+                let call = los_vm::ContractCall {
+                    contract: req.contract_address,
+                    function: req.function,
+                    args: req.args,
+                    gas_limit: req
+                        .gas_limit
+                        .unwrap_or(los_core::DEFAULT_GAS_LIMIT)
+                        .min(MAX_DRY_RUN_GAS),
+                    caller: req.caller.filter(|c| !c.is_empty()).unwrap_or(my_addr),
+                    block_timestamp: 0,
+                    amount: req.amount_cil.unwrap_or(0),
+                };
+                let trace = req.trace;
+                // Version taken before executing: a concurrent write makes the
+                // stored entry stale instead of caching a post-write result
+                // under the pre-write version.
+                let version = if trace {
+                    None
+                } else {
+                    engine.contract_state_version(&call.contract).ok()
+                };
+                let cached = version.as_deref().and_then(|v| cache.lookup(&call, v));
+                let hit = cached.is_some();
+                let run = match cached {
+                    Some(dry) => {
+                        metrics.contract_read_cache_hits_total.inc();
+                        Ok(Ok(dry))
+                    }
+                    None => {
+                        tokio::task::spawn_blocking(move || {
+                            let dry = engine.dry_run(&call, trace);
+                            if let (Ok(d), Some(v)) = (&dry, version) {
+                                if read_cache::is_cacheable(d) {
+                                    metrics.contract_read_cache_misses_total.inc();
+                                    cache.store(&call, v, d);
+                                    metrics.contract_read_cache_entries.set(cache.len() as i64);
+                                }
+                            }
+                            dry
+                        })
+                        .await
+                    }
+                };
+                match run {
+                    Ok(Ok(dry)) => {
+                        let r = dry.result;
+                        let mut json = serde_json::json!({
+                            "status": "success",
+                            "result": {
+                                "success": r.success,
+                                "output": r.output,
+                                "gas_used": r.gas_used,
+                                "state_changes": r.state_changes,
+                                "events": r.events,
+                                "transfers": r.transfers.iter()
+                                    .map(|(addr, amt)| serde_json::json!({"recipient": addr, "amount_cil": amt}))
+                                    .collect::<Vec<_>>()
+                            },
+                            "logs": dry.logs,
+                            "cached": hit
+                        });
+                        if let Some(trace) = dry.trace {
+                            json["trace"] = serde_json::json!(trace);
+                        }
+                        api_json(json)
+                    }
+                    Ok(Err(e)) if los_vm::exec_limit::is_busy_error(&e) => {
+                        api_json(serde_json::json!({"status":"error","code":429,"msg":e}))
+                    }
+                    Ok(Err(e)) => api_json(serde_json::json!({"status":"error","msg":e})),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error", "code": 500,
+                        "msg": format!("Dry run task failed: {}", e)
+                    })),
+                }
+            });
+
         // 9. GET /contract/:address
         let engine_get = wasm_engine.clone();
+        let sources_get = source_registry.clone();
         let get_contract = warp::path!("contract" / String)
-            .and(with_state(engine_get))
+            .and(with_state((engine_get, sources_get)))
             .map(
-                |addr: String, engine: Arc<WasmEngine>| match engine.get_contract(&addr) {
+                |addr: String, (engine, sources): (Arc<WasmEngine>, Arc<source_registry::SourceRegistry>)| match engine.get_contract(&addr) {
                     Ok(contract) => api_json(serde_json::json!({
                         "status": "success",
                         "contract": {
@@ -1588,7 +2083,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "code_hash": contract.code_hash,
                             "balance": contract.balance,
                             "owner": contract.owner,
+                            "pending_owner": contract.pending_owner,
+                            "ownership_renounced_at": contract.ownership_renounced_at,
                             "created_at_block": contract.created_at_block,
+                            "paused": contract.paused,
+                            "info": contract.info,
+                            "verified": sources
+                                .get(&contract.address)
+                                .is_some_and(|r| r.is_verified(&contract.code_hash)),
                             "state": contract.state
                         }
                     })),
@@ -1599,6 +2101,261 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 },
             );
 
+        // 9d. POST /contract/:address/verify (source bundle → reproducible rebuild)
+        let engine_verify = wasm_engine.clone();
+        let sources_verify = source_registry.clone();
+        let verify_source = warp::path!("contract" / String / "verify")
+            .and(warp::post())
+            .and(warp::body::content_length_limit(
+                los_verify::MAX_BUNDLE_BYTES as u64 + 1_048_576,
+            ))
+            .and(warp::body::bytes())
+            .and(with_state((engine_verify, sources_verify)))
+            .map(
+                |addr: String,
+                 body: bytes::Bytes,
+                 (engine, sources): (Arc<WasmEngine>, Arc<source_registry::SourceRegistry>)| {
+                    let bundle: los_verify::SourceBundle = match serde_json::from_slice(&body) {
+                        Ok(b) => b,
+                        Err(e) => {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": format!("Invalid source bundle: {}", e)
+                            }))
+                        }
+                    };
+                    let contract = match engine.get_contract(&addr) {
+                        Ok(c) => c,
+                        Err(e) => {
+                            return api_json(serde_json::json!({"status":"error","code":404,"msg":e}))
+                        }
+                    };
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs();
+                    match sources.submit(&addr, &contract.code_hash, bundle, now) {
+                        Ok(record) => {
+                            let json = record.to_json();
+                            tokio::task::spawn_blocking(move || sources.run_build(record));
+                            api_json(serde_json::json!({
+                                "status": "success",
+                                "code": 202,
+                                "msg": "Verification build started; poll GET /contract/{address}/source",
+                                "source": json
+                            }))
+                        }
+                        Err((code, msg)) => api_json(serde_json::json!({
+                            "status": "error", "code": code, "msg": msg
+                        })),
+                    }
+                },
+            );
+
+        // 9e. GET /contract/:address/source (verification status + verified sources)
+        let sources_get = source_registry.clone();
+        let contract_source = warp::path!("contract" / String / "source")
+            .and(warp::get())
+            .and(with_state(sources_get))
+            .map(
+                |addr: String, sources: Arc<source_registry::SourceRegistry>| match sources
+                    .get(&addr)
+                {
+                    Some(record) => api_json(serde_json::json!({
+                        "status": "success",
+                        "source": record.to_json()
+                    })),
+                    None => api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": format!("No source submitted for {}", addr)
+                    })),
+                },
+            );
+
+        // 9b. GET /contract/:address/state (?at_checkpoint=H on archive nodes)
+        let engine_state = wasm_engine.clone();
+        let db_state = database.clone();
+        let contract_state_route = warp::path!("contract" / String / "state")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(with_state((engine_state, db_state)))
+            .map(
+                |addr: String,
+                 params: HashMap<String, String>,
+                 (engine, db): (Arc<WasmEngine>, Arc<LosDatabase>)| {
+                    let Some(at) = params.get("at_checkpoint") else {
+                        return match engine.get_contract(&addr) {
+                            Ok(c) => api_json(serde_json::json!({
+                                "status": "success",
+                                "address": addr,
+                                "balance": c.balance,
+                                "state": c.state
+                            })),
+                            Err(e) => api_json(serde_json::json!({
+                                "status": "error", "code": 404, "msg": e
+                            })),
+                        };
+                    };
+                    let Ok(at) = at.parse::<u64>() else {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 400,
+                            "msg": "at_checkpoint must be a block height"
+                        }));
+                    };
+                    api_json(archive::historical_json(
+                        db.archive_contract_at(&addr, at),
+                        at,
+                        |snap: Option<archive::ContractSnapshot>| match snap {
+                            Some(snap) => serde_json::json!({
+                                "status": "success",
+                                "address": addr,
+                                "at_checkpoint": at,
+                                "balance": snap.balance,
+                                "state": snap.state
+                            }),
+                            None => serde_json::json!({
+                                "status": "error", "code": 404,
+                                "msg": format!("Contract {} did not exist at height {}", addr, at)
+                            }),
+                        },
+                    ))
+                },
+            );
+
+        // 9c. GET /contract/:address/events?type=&topic0..topic3=&since=&limit=
+        let db_events = database.clone();
+        let contract_events_route = warp::path!("contract" / String / "events")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(with_state(db_events))
+            .map(
+                |addr: String, params: HashMap<String, String>, db: Arc<LosDatabase>| {
+                    let mut filter = db::EventFilter {
+                        event_type: params.get("type").filter(|v| !v.is_empty()).cloned(),
+                        since: params
+                            .get("since")
+                            .and_then(|v| v.parse::<u64>().ok())
+                            .unwrap_or(0),
+                        ..Default::default()
+                    };
+                    for (i, topic) in filter.topics.iter_mut().enumerate() {
+                        *topic = params
+                            .get(&format!("topic{}", i))
+                            .filter(|v| !v.is_empty())
+                            .cloned();
+                    }
+                    let limit = params
+                        .get("limit")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(100)
+                        .clamp(1, 1_000);
+                    match db.query_contract_events(&addr, &filter, limit) {
+                        Ok(events) => api_json(serde_json::json!({
+                            "status": "success",
+                            "contract": addr,
+                            "count": events.len(),
+                            "events": events,
+                            "pruned_before": db.event_gc_cutoff().unwrap_or(0)
+                        })),
+                        Err(e) => api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": e
+                        })),
+                    }
+                },
+            );
+
+        // 9c'. GET /contract/:address/events/summary (epoch rollups of pruned events)
+        let db_summary = database.clone();
+        let event_summary_route = warp::path!("contract" / String / "events" / "summary")
+            .and(warp::get())
+            .and(with_state(db_summary))
+            .map(
+                |addr: String, db: Arc<LosDatabase>| match db.event_summaries(&addr) {
+                    Ok(summaries) => api_json(serde_json::json!({
+                        "status": "success",
+                        "contract": addr,
+                        "pruned_before": db.event_gc_cutoff().unwrap_or(0),
+                        "summaries": summaries
+                    })),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": e
+                    })),
+                },
+            );
+
+        // 9a. GET /contracts/stats?sort=gas&limit=50 (top consumers)
+        let engine_stats = wasm_engine.clone();
+        let contract_stats_route = warp::path!("contracts" / "stats")
+            .and(warp::query::<HashMap<String, String>>())
+            .and(with_state(engine_stats))
+            .map(|params: HashMap<String, String>, engine: Arc<WasmEngine>| {
+                let sort_key = params.get("sort").map(String::as_str).unwrap_or("gas");
+                let Some(sort) = los_vm::StatsSort::parse(sort_key) else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "sort must be one of: gas, calls, failures, time"
+                    }));
+                };
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(50)
+                    .clamp(1, 200);
+                let contracts: Vec<serde_json::Value> = engine
+                    .top_contracts(sort, limit)
+                    .into_iter()
+                    .map(|(addr, s)| {
+                        serde_json::json!({
+                            "address": addr,
+                            "total_gas": s.total_gas,
+                            "calls": s.calls,
+                            "failures": s.failures,
+                            "avg_exec_micros": s.avg_exec_micros()
+                        })
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "sort": sort_key,
+                    "count": contracts.len(),
+                    "contracts": contracts
+                }))
+            });
+
+        // 9a'. GET /contracts/address?owner=&code_hash=&salt= — address of a salted
+        // deploy before it happens (counterfactual deployment)
+        let engine_predict = wasm_engine.clone();
+        let contract_address_route = warp::path!("contracts" / "address")
+            .and(warp::get())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(with_state(engine_predict))
+            .map(|params: HashMap<String, String>, engine: Arc<WasmEngine>| {
+                let (Some(owner), Some(code_hash), Some(salt)) = (
+                    params.get("owner"),
+                    params.get("code_hash"),
+                    params.get("salt"),
+                ) else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "owner, code_hash and salt are required"
+                    }));
+                };
+                let salt = match los_core::deploy_link::parse_salt(salt) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        return api_json(serde_json::json!({"status":"error","code":400,"msg":e}))
+                    }
+                };
+                let address = WasmEngine::salted_address(owner, &salt, code_hash);
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "address": address,
+                    "taken": engine.contract_exists(&address).unwrap_or(false)
+                }))
+            });
+
         // 9b. GET /contracts (list all deployed contracts)
         let engine_list = wasm_engine.clone();
         let list_contracts_route =
@@ -1616,7 +2373,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         deploy
             .boxed()
             .or(call.boxed())
+            .or(dry_run.boxed())
             .or(get_contract.boxed())
+            .or(verify_source.boxed())
+            .or(contract_source.boxed())
+            .or(contract_state_route.boxed())
+            .or(contract_events_route.boxed())
+            .or(event_summary_route.boxed())
+            .or(contract_stats_route.boxed())
+            .or(contract_address_route.boxed())
             .or(list_contracts_route.boxed())
             .boxed()
     };
@@ -1628,14 +2393,24 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let list_tokens_route = warp::path("tokens")
         .and(warp::path::end())
         .and(warp::get())
+        .and(http_cache::cache_headers())
         .and(with_state(engine_tokens))
-        .map(|engine: Arc<WasmEngine>| {
+        .map(|h: http_cache::CacheHeaders, engine: Arc<WasmEngine>| {
+            // Token state only changes with contract blocks (ledger writes)
+            let etag = http_cache::generation_etag("tokens", ledger_view::current_generation());
+            if let Some(not_modified) = http_cache::not_modified(&etag, &h) {
+                return not_modified;
+            }
             let tokens = token_registry::list_usp01_tokens(&engine);
-            api_json(serde_json::json!({
-                "status": "success",
-                "count": tokens.len(),
-                "tokens": tokens
-            }))
+            api_cached(
+                serde_json::json!({
+                    "status": "success",
+                    "count": tokens.len(),
+                    "tokens": tokens
+                }),
+                Some(etag),
+                &h,
+            )
         });
 
     // GET /token/:address — Get USP-01 token metadata
@@ -1701,13 +2476,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
     // ── DEX Routes ──
 
-    // GET /dex/pools — List all DEX pools across all contracts
+    // GET /dex/pools — List all DEX pools with spot price and 24h volume/fees
     let engine_dex_pools = wasm_engine.clone();
     let dex_list_pools_route = warp::path!("dex" / "pools")
         .and(warp::get())
         .and(with_state(engine_dex_pools))
         .map(|engine: Arc<WasmEngine>| {
-            let pools = dex_registry::list_all_dex_pools(&engine);
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let pools = dex_registry::list_pool_analytics(&engine, now);
             api_json(serde_json::json!({
                 "status": "success",
                 "count": pools.len(),
@@ -1715,6 +2494,53 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }))
         });
 
+    // GET /dex/pools/:pool_id/chart?interval=1h&limit=24&contract=LOSCon... — OHLC + volume
+    let engine_dex_chart = wasm_engine.clone();
+    let dex_chart_route = warp::path!("dex" / "pools" / String / "chart")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state(engine_dex_chart))
+        .map(
+            |pool_id: String, params: HashMap<String, String>, engine: Arc<WasmEngine>| {
+                let interval_str = params.get("interval").map(String::as_str).unwrap_or("1h");
+                let Some(interval) = dex_registry::parse_chart_interval(interval_str) else {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": "Invalid interval (expected 5m, 15m, 1h, 4h or 1d)"
+                    }));
+                };
+                let limit = params
+                    .get("limit")
+                    .and_then(|l| l.parse::<usize>().ok())
+                    .unwrap_or(24)
+                    .clamp(1, dex_registry::MAX_CHART_CANDLES);
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                match dex_registry::pool_chart(
+                    &engine,
+                    params.get("contract").map(String::as_str),
+                    &pool_id,
+                    interval,
+                    limit,
+                    now,
+                ) {
+                    Ok((pool, candles)) => api_json(serde_json::json!({
+                        "status": "success",
+                        "contract": pool.contract,
+                        "pool_id": pool.pool_id,
+                        "interval": interval_str,
+                        "candles": candles
+                    })),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": e
+                    })),
+                }
+            },
+        );
+
     // GET /dex/pool/:contract/:pool_id — Get pool info
     let engine_dex_pool = wasm_engine.clone();
     let dex_pool_info_route = warp::path!("dex" / "pool" / String / String)
@@ -1787,14 +2613,70 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             },
         );
 
+    // ── Oracle Routes ──
+
+    // GET /oracle/:symbol/history?since=<unix>&limit=288 — persisted consensus prices
+    let db_oracle = database.clone();
+    let oracle_history_route = warp::path!("oracle" / String / "history")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state(db_oracle))
+        .map(
+            |symbol: String, params: HashMap<String, String>, db: Arc<LosDatabase>| {
+                let symbol = symbol.to_uppercase();
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let since = params
+                    .get("since")
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or_else(|| now.saturating_sub(86_400));
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(288)
+                    .clamp(1, 5_000);
+                match db.get_oracle_history(&symbol, since, limit) {
+                    Ok(history) => api_json(serde_json::json!({
+                        "status": "success",
+                        "symbol": symbol,
+                        "count": history.len(),
+                        "history": history
+                    })),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": e
+                    })),
+                }
+            },
+        );
+
     // 10. GET /metrics (Prometheus endpoint)
     let metrics_clone = metrics.clone();
     let ledger_metrics = ledger.clone();
     let db_metrics = database.clone();
+    let engine_metrics = wasm_engine.clone();
+    let mp_metrics = mempool_pool.clone();
+    let rp_metrics = reward_pool.clone();
     let metrics_route = warp::path("metrics")
-        .and(with_state((metrics_clone, ledger_metrics, db_metrics)))
+        .and(with_state((
+            metrics_clone,
+            ledger_metrics,
+            db_metrics,
+            engine_metrics,
+            mp_metrics,
+            rp_metrics,
+        )))
         .map(
-            |(m, l, db): (Arc<LosMetrics>, Arc<Mutex<Ledger>>, Arc<LosDatabase>)| {
+            |(m, l, db, engine, mp, rp): (
+                Arc<LosMetrics>,
+                Arc<Mutex<Ledger>>,
+                Arc<LosDatabase>,
+                Arc<WasmEngine>,
+                Arc<Mutex<mempool::Mempool>>,
+                Arc<Mutex<ValidatorRewardPool>>,
+            )| {
                 // Update blockchain metrics before export
                 {
                     let ledger_guard = safe_lock(&l);
@@ -1805,6 +2687,15 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let stats = db.stats();
                 m.update_db_metrics(&stats);
 
+                // Per-contract gauges for the top 50 gas consumers
+                m.update_contract_metrics(&engine.top_contracts(los_vm::StatsSort::Gas, 50));
+
+                // Mempool size, evictions, replacements, expirations
+                m.update_mempool_metrics(&safe_lock(&mp).stats());
+
+                // Validator reward pool balance and fee-only phase
+                m.update_reward_metrics(&safe_lock(&rp));
+
                 // Export all metrics
                 match m.export() {
                     Ok(output) => warp::reply::with_header(
@@ -1825,6 +2716,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let l_info = ledger.clone();
     let ab_info = address_book.clone();
     let my_addr_info = my_address.clone();
+    let validator_pk_info = hex::encode(&node_public_key);
     let node_info_route = warp::path("node-info")
         .and(with_state((l_info, ab_info)))
         .map(
@@ -1843,7 +2735,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     .filter(|acc| acc.is_validator)
                     .count();
                 let peer_count = safe_lock(&ab).len();
-                let network = if los_core::CHAIN_ID == 1 {
+                let network = if los_core::chain_id() == 1 {
                     "los-mainnet"
                 } else {
                     "los-testnet"
@@ -1860,11 +2752,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     .values()
                     .filter(|b| b.timestamp > now_ts.saturating_sub(window_secs))
                     .count() as u64;
-                let network_tps = if window_secs > 0 {
-                    recent_tx_count / window_secs
-                } else {
-                    0
-                };
+                let network_tps = recent_tx_count.checked_div(window_secs).unwrap_or(0);
 
                 api_json(serde_json::json!({
                     "chain_id": network,
@@ -1877,11 +2765,23 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "total_supply": format_balance_precise(total_supply),
                     "circulating_supply": format_balance_precise(circulating),
                     "network_tps": network_tps,
+                    // Validator key signs blocks and votes; network key is the libp2p identity
+                    "identity": {
+                        "validator": {
+                            "address": my_addr_info,
+                            "public_key": validator_pk_info,
+                            "scheme": "dilithium5"
+                        },
+                        "network": {
+                            "peer_id": network_peer_id,
+                            "scheme": "ed25519"
+                        }
+                    },
                     "protocol": {
                         "base_fee_cil": los_core::BASE_FEE_CIL,
                         "pow_difficulty_bits": los_core::MIN_POW_DIFFICULTY_BITS,
                         "cil_per_los": los_core::CIL_PER_LOS,
-                        "chain_id_numeric": los_core::CHAIN_ID
+                        "chain_id_numeric": los_core::chain_id()
                     }
                 }))
             },
@@ -1890,124 +2790,44 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     // 12. GET /validators (List ALL registered validators — genesis + dynamically registered)
     // Active status is determined by actual connectivity (is_self || in_peers),
     // NOT just by having sufficient balance. Uptime comes from real heartbeat data.
-    let l_validators = ledger.clone();
-    let ab_validators = address_book.clone();
-    let my_addr_validators = my_address.clone();
-    let bv_validators = bootstrap_validators.clone();
-    let sm_validators = slashing_manager.clone();
-    let rp_validators = reward_pool.clone();
-    let ve_validators = validator_endpoints.clone();
+    // ?sort=stake|uptime|rewards|last_heartbeat|address&order=asc|desc&offset=&limit=&active=true
     let validators_route = warp::path("validators")
-        .and(with_state((l_validators, ab_validators)))
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state(validator_directory))
         .map(
-            move |(l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
-                let l_guard = safe_lock(&l);
-                let ab_guard = safe_lock(&ab);
-
-                // Collect ALL validator addresses: genesis bootstrap + slashing (active only) + ledger is_validator
-                let mut all_validator_addrs: Vec<String> = bv_validators.clone();
-                {
-                    let sm_guard = safe_lock(&sm_validators);
-                    for addr in sm_guard.get_all_validator_addresses() {
-                        // Skip validators that have been unstaked or banned — they should not appear
-                        if let Some(status) = sm_guard.get_status(&addr) {
-                            if status == los_consensus::slashing::ValidatorStatus::Unstaking
-                                || status == los_consensus::slashing::ValidatorStatus::Banned
-                            {
-                                continue;
-                            }
-                        }
-                        if !all_validator_addrs.contains(&addr) {
-                            all_validator_addrs.push(addr);
-                        }
-                    }
-                }
-                // Also include accounts explicitly marked as validators (user-registered)
-                for (addr, acc) in &l_guard.accounts {
-                    if acc.is_validator && !all_validator_addrs.contains(addr) {
-                        all_validator_addrs.push(addr.clone());
+            |params: HashMap<String, String>, dir: Arc<validator_directory::ValidatorDirectory>| {
+                let query = match validator_directory::ValidatorQuery::from_params(&params) {
+                    Ok(q) => q,
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": e
+                        }))
                     }
-                }
-
-                // Get real uptime data from reward pool
-                let rp_guard = safe_lock(&rp_validators);
-                let ve_guard = safe_lock(&ve_validators);
-
-                let validators: Vec<serde_json::Value> = all_validator_addrs
-                    .iter()
-                    .filter_map(|addr| {
-                        l_guard.accounts.get(addr.as_str()).and_then(|acc| {
-                            // Skip non-validators that are no longer active (e.g. unstaked)
-                            // Bootstrap validators are always shown regardless of flag
-                            let is_genesis = bv_validators.contains(addr);
-                            if !acc.is_validator && !is_genesis {
-                                return None;
-                            }
-                            // ACTIVE = verified validator with sufficient stake
-                            let is_self = addr == &my_addr_validators;
-                            let in_peers = ab_guard.values().any(|v| v.contains(addr.as_str()));
-                            // Also consider validator as connected if their address is
-                            // known in validator_endpoints (announced via VALIDATOR_REG
-                            // or seeded from genesis onion data)
-                            let in_endpoints = ve_guard.contains_key(addr.as_str());
-                            let has_min_stake = acc.balance >= MIN_VALIDATOR_STAKE_CIL;
-                            // Connected = evidence of P2P liveness (online indicator)
-                            let connected = is_self || in_peers || in_endpoints;
-                            // in_reward_pool = registered via verified Dilithium5 signature
-                            // (registration checks: valid sig + address match + min stake)
-                            let in_reward_pool = rp_guard.validators.contains_key(addr.as_str());
-                            // ACTIVE requires: registered + staked + evidence of legitimacy
-                            // - connected: appeared in P2P address book → online
-                            // - is_genesis: infrastructure bootstrap nodes → assumed active
-                            // - in_reward_pool: verified registration (Dilithium5 proof) → active
-                            let active = has_min_stake
-                                && acc.is_validator
-                                && (connected || is_genesis || in_reward_pool);
-
-                            // Real uptime from heartbeat data (not hardcoded)
-                            // Uses display_uptime_pct() which shows max(current_epoch, last_epoch)
-                            // to avoid misleading 0% at epoch start.
-                            // MAINNET SAFETY: Integer only (u64 percent)
-                            let uptime_pct: u64 = rp_guard
-                                .validators
-                                .get(addr.as_str())
-                                .map(|vs| vs.display_uptime_pct())
-                                .unwrap_or(if is_self { 100 } else { 0 });
-
-                            // Include host endpoint if known (for peer discovery)
-                            let host_ep = ve_guard.get(addr.as_str()).cloned();
-
-                            let mut entry = serde_json::json!({
-                                "address": addr,
-                                "stake": acc.balance / CIL_PER_LOS,
-                                "is_active": active,
-                                "active": active,
-                                "connected": connected,
-                                "is_genesis": is_genesis,
-                                "uptime_percentage": uptime_pct,
-                                "has_min_stake": has_min_stake,
-                            });
-                            if let Some(h) = host_ep {
-                                entry["host_address"] = serde_json::json!(&h);
-                                entry["onion_address"] = serde_json::json!(h); // backward compat
-                            }
-                            Some(entry)
-                        })
-                    })
-                    .collect();
-
+                };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let (page, total) = dir.page(&query, now);
+                let validators: Vec<serde_json::Value> = page.iter().map(|v| v.to_json()).collect();
                 api_json(serde_json::json!({
-                    "validators": validators
+                    "validators": validators,
+                    "total": total,
+                    "offset": query.offset,
+                    "limit": query.limit,
+                    "sort": query.sort.as_str(),
+                    "order": if query.descending { "desc" } else { "asc" },
                 }))
             },
         );
 
     // 13. GET /balance/:address (Check balance - alias for CLI compatibility)
-    let l_balance_alias = ledger.clone();
+    let l_balance_alias = ledger_view.clone();
     let balance_alias_route = warp::path!("balance" / String)
         .and(with_state(l_balance_alias))
-        .map(|addr: String, l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
+        .map(|addr: String, l: Arc<ledger_view::LedgerView>| {
+            let l_guard = l.current();
             let full_addr = l_guard
                 .accounts
                 .keys()
@@ -2018,14 +2838,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             let bal = acct.map(|a| a.balance).unwrap_or(0);
             let head = acct.map(|a| a.head.as_str()).unwrap_or("0");
             let block_count = acct.map(|a| a.block_count).unwrap_or(0);
-            api_json(serde_json::json!({
-                "address": full_addr,
-                "balance": format_balance_precise(bal),
-                "balance_los": format_balance_precise(bal),
-                "balance_cil": bal,
-                "balance_cil_str": bal.to_string(),
-                "head": head,
-                "block_count": block_count
+            api_json(serde_json::json!(BalanceResponse {
+                address: full_addr,
+                balance: format_balance_precise(bal),
+                balance_los: format_balance_precise(bal),
+                balance_cil: bal,
+                balance_cil_str: bal.to_string(),
+                head: head.to_string(),
+                block_count,
             }))
         });
 
@@ -2040,12 +2860,14 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }));
         }
         let base_fee = los_core::BASE_FEE_CIL;
-        api_json(serde_json::json!({
-            "address": addr,
-            "base_fee_cil": base_fee,
-            "estimated_fee_cil": base_fee,
-            "fee_multiplier": 1,
-            "fee_multiplier_bps": 10_000
+        api_json(serde_json::json!(FeeEstimateResponse {
+            address: addr,
+            base_fee_cil: base_fee,
+            base_fee_los: format_balance_precise(base_fee),
+            estimated_fee_cil: base_fee,
+            estimated_fee_los: format_balance_precise(base_fee),
+            fee_multiplier: 1,
+            fee_multiplier_bps: 10_000,
         }))
     });
 
@@ -2071,9 +2893,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "epoch": info.epoch,
                 "difficulty_bits": info.difficulty_bits,
                 "reward_per_epoch_cil": info.reward_per_epoch_cil.to_string(),
-                "reward_per_epoch_los": info.reward_per_epoch_cil / CIL_PER_LOS,
+                "reward_per_epoch_los": format_balance_precise(info.reward_per_epoch_cil),
                 "remaining_supply_cil": info.remaining_supply_cil.to_string(),
-                "remaining_supply_los": info.remaining_supply_cil / CIL_PER_LOS,
+                "remaining_supply_los": format_balance_precise(info.remaining_supply_cil),
                 "epoch_remaining_secs": info.epoch_remaining_secs,
                 "miners_this_epoch": info.miners_this_epoch,
                 "chain_id": info.chain_id
@@ -2088,12 +2910,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     // ──────────────────────────────────────────────────────────────────
 
     // 14. GET /block (Latest block) — path::end() prevents /block/{hash} route conflict
-    let l_block = ledger.clone();
+    let l_block = ledger_view.clone();
     let block_route = warp::path("block")
         .and(warp::path::end())
         .and(with_state(l_block))
-        .map(|l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
+        .map(|l: Arc<ledger_view::LedgerView>| {
+            let l_guard = l.current();
             // Get latest block by timestamp (HashMap has no guaranteed order)
             let latest = l_guard.blocks.values().max_by_key(|b| b.timestamp);
             if let Some(b) = latest {
@@ -2103,6 +2925,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "account": b.account,
                     "previous": b.previous,
                     "amount": b.amount / CIL_PER_LOS,
+                    "amount_cil": b.amount,
+                    "amount_los": format_balance_precise(b.amount),
                     "block_type": format!("{:?}", b.block_type)
                 }))
             } else {
@@ -2110,232 +2934,138 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }
         });
 
-    // 15. POST /faucet (TESTNET ONLY - Free LOS for testing)
-    // GOSSIP: Faucet Mint blocks are now broadcast to all peers so every
-    // node sees the faucet balance. This is testnet-only — faucet is disabled
-    // on mainnet by should_enable_faucet() which returns false (compiler DCE).
-    let l_faucet = ledger.clone();
-    let db_faucet = database.clone();
-    let fl_faucet = faucet_limiter.clone();
-    let pk_faucet = node_public_key.clone();
-    let sk_faucet = secret_key.clone();
-    let tx_faucet = tx_out.clone();
-    let faucet_route = warp::path("faucet")
-        .and(warp::post())
-        .and(warp::body::bytes())
-        .and(with_state((l_faucet, db_faucet, fl_faucet, pk_faucet, sk_faucet, tx_faucet)))
-        .then(#[allow(clippy::type_complexity)] |body: bytes::Bytes, (l, db, rate_lim, node_pk, node_sk, tx): (Arc<Mutex<Ledger>>, Arc<LosDatabase>, Arc<EndpointRateLimiter>, Vec<u8>, Zeroizing<Vec<u8>>, mpsc::Sender<String>)| async move {
-            // Parse JSON manually to return proper error instead of 500
-            let req: serde_json::Value = match serde_json::from_slice(&body) {
-                Ok(r) => r,
-                Err(e) => {
+    // 16. GET /blocks/recent?limit=10 (Recent blocks for validator dashboard)
+    // Served from the explorer aggregator cache — no chain walk per request.
+    let explorer = Arc::new(Mutex::new(explorer_stats::ExplorerStats::new()));
+    tokio::spawn(explorer_stats::run_stats_aggregator(
+        explorer.clone(),
+        ledger_view.clone(),
+        wasm_engine.clone(),
+    ));
+    let l_blocks = ledger_view.clone();
+    let ex_blocks = explorer.clone();
+    let labels_blocks = labels.clone();
+    let blocks_recent_route = warp::path!("blocks" / "recent")
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((l_blocks, ex_blocks, labels_blocks)))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |params: HashMap<String, String>,
+             (l, ex, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<Mutex<explorer_stats::ExplorerStats>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(10)
+                    .clamp(1, explorer_stats::MAX_RECENT_BLOCKS);
+                let (recent, total_blocks) = {
+                    let ex_guard = safe_lock(&ex);
+                    (
+                        ex_guard.recent_blocks(limit),
+                        ex_guard.overview().total_blocks,
+                    )
+                };
+                let l_guard = l.current();
+                let labels = safe_lock(&labels);
+                let blocks: Vec<serde_json::Value> = recent
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        // Per-account block count as individual height (block-lattice = per-account chain)
+                        let account_block_count = l_guard
+                            .accounts
+                            .get(&b.account)
+                            .map(|a| a.block_count)
+                            .unwrap_or(0);
+                        let mut block = serde_json::json!({
+                            "hash": b.hash,
+                            "height": account_block_count,
+                            "global_index": total_blocks.saturating_sub(i as u64),
+                            "timestamp": b.timestamp,
+                            "transactions_count": 1,
+                            "account": b.account,
+                            "amount": b.amount,
+                            "amount_cil": b.amount,
+                            "amount_los": format_balance_precise(b.amount),
+                            "block_type": b.block_type
+                        });
+                        labels.annotate(&mut block, &[("account", "account_label")]);
+                        block
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "blocks": blocks,
+                    "total_blocks": total_blocks
+                }))
+            },
+        );
+
+    // 16b. GET /stats/overview (tx/day, active accounts, contracts, gas/day)
+    let ex_overview = explorer.clone();
+    let stats_overview_route = warp::path!("stats" / "overview")
+        .and(warp::get())
+        .and(with_state(ex_overview))
+        .map(|ex: Arc<Mutex<explorer_stats::ExplorerStats>>| {
+            let overview = safe_lock(&ex).overview().clone();
+            api_json(serde_json::json!({
+                "status": "success",
+                "overview": overview
+            }))
+        });
+
+    // 16c. GET /accounts/top?by=balance|blocks&limit=20
+    let ex_top = explorer.clone();
+    let labels_top = labels.clone();
+    let accounts_top_route = warp::path!("accounts" / "top")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((ex_top, labels_top)))
+        .map(
+            |params: HashMap<String, String>,
+             (ex, labels): (
+                Arc<Mutex<explorer_stats::ExplorerStats>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let by_str = params.get("by").map(String::as_str).unwrap_or("balance");
+                let Some(by) = explorer_stats::TopBy::parse(by_str) else {
                     return api_json(serde_json::json!({
                         "status": "error",
-                        "code": 400,
-                        "msg": format!("Invalid request body: {}", e)
+                        "msg": format!("Unknown ordering '{}': use balance or blocks", by_str)
                     }));
-                }
-            };
-            // BELT-AND-SUSPENDERS: Explicit compile-time mainnet guard.
-            if los_core::is_mainnet_build() {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 403,
-                    "msg": "Faucet is permanently disabled on mainnet"
-                }));
-            }
-
-            if !testnet_config::get_testnet_config().should_enable_faucet() {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 403,
-                    "msg": "Faucet only available in Functional/Consensus testnet modes"
-                }));
-            }
-
-            let address = req["address"].as_str().unwrap_or("");
-            if address.is_empty() {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 400,
-                    "msg": "Address required"
-                }));
-            }
-
-            // Validate address format (Base58Check with LOS prefix)
-            if !los_crypto::validate_address(address) {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 400,
-                    "msg": "Invalid address format. Must be Base58Check with LOS prefix."
-                }));
-            }
-
-            // PERSISTENT cooldown: 1 faucet claim per 2 minutes per address (survives restart)
-            const FAUCET_COOLDOWN_SECS: u64 = 120; // 2 minutes (testnet-friendly)
-            if let Err(remaining) = db.check_faucet_cooldown(address, FAUCET_COOLDOWN_SECS) {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 429,
-                    "msg": format!("Faucet cooldown active: try again in {} seconds", remaining)
-                }));
-            }
-
-            // In-memory rate limit as secondary protection
-            if let Err(wait_secs) = rate_lim.check_and_record(address) {
-                return api_json(serde_json::json!({
-                    "status": "error",
-                    "code": 429,
-                    "msg": format!("Rate limit exceeded: max 1 faucet claim per 2 minutes. Try again in {} seconds.", wait_secs)
-                }));
-            }
-
-            let faucet_amount = FAUCET_AMOUNT_CIL; // 5k LOS per faucet request (testnet only)
-
-            // All ledger work in a contained scope so MutexGuard is dropped before .await
-            let faucet_result: Result<(String, String, u128, u128), String> = {
-                let mut l_guard = safe_lock(&l);
-
-                // Ensure account exists
-                if !l_guard.accounts.contains_key(address) {
-                    l_guard.accounts.insert(address.to_string(), AccountState {
-                        head: "0".to_string(),
-                        balance: 0,
-                        block_count: 0,
-                        is_validator: false,
-                    });
-                }
-
-                let state = l_guard.accounts.get(address).cloned().unwrap_or(AccountState {
-                    head: "0".to_string(),
-                    balance: 0,
-                    block_count: 0,
-                    is_validator: false,
-                });
-
-                // Create proper Mint block with PoW + signature, use process_block()
-                // This ensures remaining_supply is properly deducted
-                let mut faucet_block = Block {
-                    account: address.to_string(),
-                    previous: state.head.clone(),
-                    block_type: BlockType::Mint,
-                    amount: faucet_amount,
-                    link: format!("FAUCET:TESTNET:{}", std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()),
-                    signature: "".to_string(),
-                    public_key: hex::encode(&node_pk),
-                    work: 0,
-                    timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
-                    fee: 0,
-                };
-
-                solve_pow(&mut faucet_block);
-                faucet_block.signature = match try_sign_hex(faucet_block.signing_hash().as_bytes(), &node_sk) {
-                    Ok(sig) => sig,
-                    Err(e) => {
-                        let _err_msg = format!("Faucet signing failed: {}", e);
-                        return api_json(serde_json::json!({"status": "error", "code": 500, "msg": _err_msg}));
-                    }
                 };
-
-                match l_guard.process_block(&faucet_block) {
-                    Ok(result) => {
-                        let hash = result.into_hash();
-                        let new_balance = l_guard.accounts.get(address)
-                            .map(|a| a.balance).unwrap_or(0);
-                        let _ = db.record_faucet_claim(address);
-                        SAVE_DIRTY.store(true, Ordering::Release);
-                        let gossip_msg = serde_json::to_string(&faucet_block).unwrap_or_default();
-                        Ok((hash, gossip_msg, faucet_amount / CIL_PER_LOS, new_balance / CIL_PER_LOS))
-                    }
-                    Err(e) => Err(format!("Faucet mint failed: {}", e))
-                }
-            }; // l_guard dropped here — safe to .await below
-
-            match faucet_result {
-                Ok((hash, gossip_msg, amount_los, balance_los)) => {
-                    // GOSSIP: Broadcast faucet Mint block to all peers
-                    let _ = tx.send(gossip_msg).await;
-
-                    api_json(serde_json::json!({
-                        "status": "success",
-                        "msg": "Faucet claim successful",
-                        "amount": amount_los,
-                        "new_balance": balance_los,
-                        "block_hash": hash
-                    }))
-                }
-                Err(e) => {
-                    api_json(serde_json::json!({
-                        "status": "error",
-                        "code": 500,
-                        "msg": e
-                    }))
-                }
-            }
-        });
-
-    // 16. GET /blocks/recent (Recent blocks for validator dashboard)
-    let l_blocks = ledger.clone();
-    let blocks_recent_route = warp::path!("blocks" / "recent")
-        .and(with_state(l_blocks))
-        .map(|l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
-            // Collect only blocks that are part of valid account chains.
-            // Walk each account's chain from head backwards to gather chain block hashes.
-            let mut chain_hashes: std::collections::HashSet<String> =
-                std::collections::HashSet::new();
-            for acct in l_guard.accounts.values() {
-                let mut current = acct.head.clone();
-                while current != "0" && !current.is_empty() {
-                    if !chain_hashes.insert(current.clone()) {
-                        break;
-                    }
-                    if let Some(blk) = l_guard.blocks.get(&current) {
-                        current = blk.previous.clone();
-                    } else {
-                        break;
-                    }
-                }
-            }
-            // Filter and sort chain blocks by timestamp descending
-            let mut block_list: Vec<(&String, &Block)> = l_guard
-                .blocks
-                .iter()
-                .filter(|(hash, _)| chain_hashes.contains(*hash))
-                .collect();
-            block_list.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
-            // total_blocks = sum of all account chain block counts (excludes orphans)
-            let total_blocks = l_guard.total_chain_blocks();
-            let blocks: Vec<serde_json::Value> = block_list
-                .iter()
-                .take(10) // Last 10 blocks by timestamp
-                .enumerate()
-                .map(|(i, (hash, b))| {
-                    // Per-account block count as individual height (block-lattice = per-account chain)
-                    let account_block_count = l_guard
-                        .accounts
-                        .get(&b.account)
-                        .map(|a| a.block_count)
-                        .unwrap_or(0);
-                    serde_json::json!({
-                        "hash": hash,
-                        "height": account_block_count,
-                        "global_index": total_blocks as usize - i,
-                        "timestamp": b.timestamp,
-                        "transactions_count": 1,
-                        "account": b.account,
-                        "amount": b.amount,
-                        "amount_los": b.amount / CIL_PER_LOS,
-                        "block_type": format!("{:?}", b.block_type).to_lowercase()
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(20)
+                    .clamp(1, explorer_stats::MAX_TOP_ACCOUNTS);
+                let top = safe_lock(&ex).top_accounts(by, limit);
+                let labels = safe_lock(&labels);
+                let accounts: Vec<serde_json::Value> = top
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, a)| {
+                        let mut account = serde_json::json!({
+                            "rank": i + 1,
+                            "address": a.address,
+                            "balance": format_balance_precise(a.balance_cil),
+                            "balance_cil": a.balance_cil,
+                            "block_count": a.block_count,
+                            "is_validator": a.is_validator
+                        });
+                        labels.annotate(&mut account, &[("address", "label")]);
+                        account
                     })
-                })
-                .collect();
-            api_json(serde_json::json!({
-                "blocks": blocks,
-                "total_blocks": total_blocks
-            }))
-        });
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "by": by_str,
+                    "accounts": accounts
+                }))
+            },
+        );
 
     // 17. GET /whoami (Get node's internal signing address)
     let whoami_route = warp::path("whoami")
@@ -2349,11 +3079,11 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         });
 
     // 18. GET /account/:address (Account details - balance + history combined)
-    let l_account = ledger.clone();
+    let l_account = ledger_view.clone();
     let account_route = warp::path!("account" / String)
         .and(with_state(l_account))
-        .map(|addr: String, l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
+        .map(|addr: String, l: Arc<ledger_view::LedgerView>| {
+            let l_guard = l.current();
             let state = l_guard
                 .accounts
                 .get(&addr)
@@ -2389,10 +3119,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         BlockType::Send => block.account.clone(),
                         BlockType::Receive => {
                             // block.link = hash of the Send block that funded this Receive
-                            l_guard.blocks.get(&block.link)
+                            l_guard
+                                .blocks
+                                .get(&block.link)
                                 .map(|send_blk| send_blk.account.clone())
                                 .unwrap_or_else(|| "SYSTEM".to_string())
-                        },
+                        }
                         _ => "SYSTEM".to_string(), // Mint, Slash, Change
                     };
                     // Resolve `to` address
@@ -2405,7 +3137,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "from": from_addr,
                         "to": to_addr,
                         "type": format!("{:?}", block.block_type).to_lowercase(),
-                        "amount": format!("{}.{:011}", block.amount / CIL_PER_LOS, block.amount % CIL_PER_LOS),
+                        "amount": format_balance_precise(block.amount),
                         "timestamp": block.timestamp,
                         "link": block.link,
                         "previous": block.previous,
@@ -2428,9 +3160,101 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }))
         });
 
+    // 18b. GET /account/:address/next — everything a wallet needs to build its next block.
+    // Reads the live ledger (brief lock), not the API snapshot: a head that is
+    // even one block stale makes the client-signed block fail the chain check.
+    let l_next = ledger.clone();
+    let p_next = pending_sends.clone();
+    let account_next_route = warp::path!("account" / String / "next")
+        .and(warp::get())
+        .and(with_state((l_next, p_next)))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |addr: String,
+             (l, p): (
+                Arc<Mutex<Ledger>>,
+                Arc<Mutex<HashMap<String, (Block, u128)>>>,
+            )| {
+                if !los_crypto::validate_address(&addr) {
+                    return api_json(serde_json::json!({
+                        "status": "error",
+                        "code": 400,
+                        "msg": "Invalid address format. Must be Base58Check with LOS prefix."
+                    }));
+                }
+                // DEADLOCK Never hold PS and L simultaneously.
+                let (state, head_timestamp) = {
+                    let l_guard = safe_lock(&l);
+                    let state = l_guard.accounts.get(&addr).cloned();
+                    let ts = state
+                        .as_ref()
+                        .and_then(|s| l_guard.blocks.get(&s.head))
+                        .map(|b| b.timestamp)
+                        .unwrap_or(0);
+                    (state, ts)
+                };
+                let state = state.unwrap_or(AccountState {
+                    head: "0".to_string(),
+                    balance: 0,
+                    block_count: 0,
+                    is_validator: false,
+                });
+
+                // Sends awaiting consensus extend the chain beyond the ledger head
+                let (mut next_previous, mut pending_count, mut pending_debit) =
+                    (state.head.clone(), 0u64, 0u128);
+                let mut next_min_ts = head_timestamp;
+                {
+                    let ps = safe_lock(&p);
+                    let mine: Vec<(&String, &Block)> = ps
+                        .iter()
+                        .filter(|(_, (b, _))| b.account == addr)
+                        .map(|(h, (b, _))| (h, b))
+                        .collect();
+                    while let Some((hash, blk)) =
+                        mine.iter().find(|(_, b)| b.previous == next_previous)
+                    {
+                        next_previous = (*hash).clone();
+                        next_min_ts = next_min_ts.max(blk.timestamp);
+                        pending_count += 1;
+                        pending_debit =
+                            pending_debit.saturating_add(blk.amount.saturating_add(blk.fee));
+                        if pending_count as usize > mine.len() {
+                            break;
+                        }
+                    }
+                }
+
+                let now = los_core::consensus_clock::now();
+                let ts_min = next_min_ts;
+                let ts_max = now.saturating_add(los_core::MAX_TIMESTAMP_DRIFT_SECS);
+                let spendable = state.balance.saturating_sub(pending_debit);
+                api_json(serde_json::json!(AccountNextResponse {
+                    status: "success".to_string(),
+                    address: addr,
+                    chain_id: los_core::chain_id(),
+                    previous: next_previous,
+                    head: state.head,
+                    block_count: state.block_count,
+                    pending_sends: pending_count,
+                    balance_cil: state.balance,
+                    balance_los: format_balance_precise(state.balance),
+                    spendable_cil: spendable,
+                    spendable_los: format_balance_precise(spendable),
+                    timestamp: TimestampWindow {
+                        suggested: now.max(ts_min),
+                        min: ts_min,
+                        max: ts_max,
+                    },
+                    pow_difficulty_bits: los_core::MIN_POW_DIFFICULTY_BITS,
+                    min_fee_cil: los_core::BASE_FEE_CIL,
+                }))
+            },
+        );
+
     // 19. GET / (Root endpoint - API welcome)
     let root_route = warp::path::end().map(|| {
-        let network_label = if los_core::is_mainnet_build() {
+        let network_label = if los_core::is_mainnet() {
             "mainnet"
         } else {
             "testnet"
@@ -2443,15 +3267,16 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             "endpoints": {
                 "health": "GET /health - Health check",
                 "node_info": "GET /node-info - Node information",
-                "bal": "GET /bal/{address} - Account balance (short alias)",
+                "bal": "GET /bal/{address}?at_block= - Account balance (short alias; at_block on archive nodes)",
                 "balance": "GET /balance/{address} - Account balance",
                 "supply": "GET /supply - Total supply, circulating, remaining",
                 "fee_estimate": "GET /fee-estimate/{address} - Fee estimate (flat base fee)",
                 "mining_info": "GET /mining-info - PoW mining epoch, difficulty, reward info",
 
                 "account": "GET /account/{address} - Account details + history",
+                "account_next": "GET /account/{address}/next - Head, timestamp window, PoW and fee for the next client-signed block",
                 "history": "GET /history/{address} - Transaction history",
-                "validators": "GET /validators - Active validators",
+                "validators": "GET /validators?sort=&order=&offset=&limit=&active= - Validators with stake, uptime, probation, rewards",
                 "peers": "GET /peers - Connected peers + validator endpoints",
                 "network_peers": "GET /network/peers - Validator .onion endpoint discovery",
                 "block": "GET /block - Latest block",
@@ -2462,18 +3287,33 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "whoami": "GET /whoami - Node's signing address",
                 "consensus": "GET /consensus - aBFT consensus parameters and safety status",
                 "reward_info": "GET /reward-info - Validator reward pool status and epoch info",
+                "reward_fees": "GET /rewards/fees?epoch=N&validator= - Fee redistribution per epoch (shares and FEE_REWARD blocks)",
+                "reward_claim": "GET /rewards/claim/{address}, POST /rewards/claim - Accrued validator rewards and Claim blocks (claim payout model)",
+                "admin_api_keys": "GET|POST /admin/api-keys, PUT|DELETE /admin/api-keys/:id - API key management (X-Admin-Token)",
+                "admin_address_book": "GET|POST /admin/address-book, PUT|DELETE /admin/address-book/:address - Address book and operator labels (X-Admin-Token)",
                 "slashing": "GET /slashing - Slashing statistics",
                 "slashing_profile": "GET /slashing/{address} - Validator slashing profile",
                 "sync": "GET /sync - Node sync status",
+                "sync_progress": "GET /sync/progress - Delta sync session progress (frontiers, chains, blocks)",
                 "metrics": "GET /metrics - Prometheus metrics",
+                "storage": "GET /storage - Database size, fragmentation, snapshots and disk headroom",
+                "netstats": "GET /netstats - Gossip propagation delay percentiles, per-block arrival and time to quorum",
                 "mempool_stats": "GET /mempool/stats - Mempool statistics",
+                "telemetry": "POST /telemetry {signed report} - Submit node telemetry (aggregation mode)",
+                "telemetry_network": "GET /telemetry/network - Network dashboard from collected telemetry (aggregation mode)",
                 "send": "POST /send {from, target, amount} - Send transaction",
-                "faucet": "POST /faucet {address} - Claim testnet tokens",
+                "send_batch": "POST /send-batch {blocks: [signed Send blocks]} - Batched client-signed sends",
                 "register_validator": "POST /register-validator - Register as validator",
                 "unregister_validator": "POST /unregister-validator - Unregister validator",
-                "deploy_contract": "POST /deploy-contract - Deploy WASM smart contract",
+                "deploy_contract": "POST /deploy-contract - Deploy WASM smart contract (salt: deterministic address)",
+                "contract_address": "GET /contracts/address?owner=&code_hash=&salt= - Address of a salted deploy, before deploying",
                 "call_contract": "POST /call-contract - Call smart contract method",
+                "verify_contract": "POST /contract/:address/verify - Submit source bundle for reproducible-build verification",
+                "contract_source": "GET /contract/:address/source - Verification status and verified source files",
+                "dry_run_contract": "POST /dry-run-contract - Execute a contract call without applying it (trace: true for host-call + instruction trace)",
                 "contract": "GET /contract/{address} - Contract info and state",
+                "contract_state": "GET /contract/{address}/state?at_checkpoint= - Contract balance and state (at_checkpoint on archive nodes)",
+                "contract_events": "GET /contract/{address}/events?type=&topic0=&since=&limit= - Stored contract events, filterable by indexed topics",
                 "tokens": "GET /tokens - List all USP-01 tokens",
                 "token_info": "GET /token/{address} - USP-01 token metadata",
                 "token_balance": "GET /token/{address}/balance/{holder} - Token balance",
@@ -2570,38 +3410,111 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         });
 
     // 22. GET /health (Health check endpoint)
-    let l_health = ledger.clone();
+    // Subsystem status for load balancers: HTTP 503 when unhealthy.
+    let l_health = ledger_view.clone();
     let db_health = database.clone();
+    let cm_health = checkpoint_manager.clone();
+    let mp_health = mempool_pool.clone();
+    let m_health = metrics.clone();
+    let q_health = state_quarantine.clone();
+    let engine_health = wasm_engine.clone();
     let health_route = warp::path("health")
         .and(with_state((l_health, db_health)))
-        .map(move |(l, db): (Arc<Mutex<Ledger>>, Arc<LosDatabase>)| {
-            let l_guard = safe_lock(&l);
-            let db_stats = db.stats();
-
-            // Check system health
-            let is_healthy = !l_guard.accounts.is_empty() && db_stats.accounts_count > 0;
-            let status = if is_healthy { "healthy" } else { "degraded" };
-
-            api_json(serde_json::json!({
-                "status": status,
-                "uptime_seconds": start_time.elapsed().as_secs(),
-                "chain": {
-                    "id": if los_core::is_mainnet_build() { "los-mainnet" } else { "los-testnet" },
-                    "accounts": l_guard.accounts.len(),
-                    "blocks": l_guard.total_chain_blocks()
-                },
-                "database": {
-                    "accounts_count": db_stats.accounts_count,
-                    "blocks_count": db_stats.blocks_count,
-                    "size_on_disk": db_stats.size_on_disk
-                },
-                "version": env!("CARGO_PKG_VERSION"),
-                "timestamp": std::time::SystemTime::now()
+        .map(
+            move |(l, db): (Arc<ledger_view::LedgerView>, Arc<LosDatabase>)| {
+                let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
-                    .as_secs()
-            }))
-        });
+                    .as_secs();
+                let uptime = start_time.elapsed().as_secs();
+                let (accounts, blocks, state_root) = {
+                    let l_guard = l.current();
+                    (
+                        l_guard.accounts.len(),
+                        l_guard.total_chain_blocks(),
+                        l_guard.compute_state_root(),
+                    )
+                };
+                let db_stats = db.stats();
+                let latest_cp = safe_lock(&cm_health).get_latest_checkpoint().ok().flatten();
+                let (mempool_depth, mempool_capacity) = {
+                    let mp = safe_lock(&mp_health);
+                    (mp.len(), mp.config().max_size)
+                };
+                let last_save = LAST_SAVE_SECS.load(Ordering::Acquire);
+                let last_save_age = (last_save > 0).then(|| now.saturating_sub(last_save));
+                let save_pending = SAVE_DIRTY.load(Ordering::Acquire);
+                let quarantine = safe_lock(&q_health).status();
+
+                let inputs = health::HealthInputs {
+                    accounts,
+                    checkpoint_age_secs: latest_cp
+                        .as_ref()
+                        .map(|cp| now.saturating_sub(cp.timestamp)),
+                    connected_peers: m_health.connected_peers.get().max(0) as usize,
+                    min_peers: m_health.min_peers.get().max(0) as usize,
+                    mempool_depth,
+                    mempool_capacity,
+                    leaked_vm_threads: los_vm::leaked_thread_count(),
+                    max_leaked_vm_threads: los_vm::MAX_LEAKED_THREADS,
+                    // Never saved since startup → count unsaved time from startup
+                    last_save_age_secs: last_save_age.or(Some(uptime)),
+                    save_pending,
+                    quarantined: quarantine.quarantined,
+                    finality_halted: checkpoint_conflict::halted(&cm_health),
+                };
+                let (status, issues) = health::evaluate(&inputs);
+                let (consensus_now, clock_offset, clock_peers, median_time_past) =
+                    los_core::consensus_clock::status();
+
+                api_json(serde_json::json!({
+                    "status": status,
+                    "code": status.http_code(),
+                    "issues": issues,
+                    "uptime_seconds": uptime,
+                    "chain": {
+                        "id": if los_core::is_mainnet() { "los-mainnet" } else { "los-testnet" },
+                        "accounts": accounts,
+                        "blocks": blocks,
+                        "state_root": state_root
+                    },
+                    "checkpoint": {
+                        "height": latest_cp.as_ref().map(|cp| cp.height),
+                        "age_seconds": inputs.checkpoint_age_secs,
+                        "finality_halted": inputs.finality_halted
+                    },
+                    "network": {
+                        "peers": inputs.connected_peers,
+                        "min_peers": inputs.min_peers
+                    },
+                    "mempool": {
+                        "depth": mempool_depth,
+                        "capacity": inputs.mempool_capacity
+                    },
+                    "vm": {
+                        "leaked_threads": inputs.leaked_vm_threads,
+                        "max_leaked_threads": inputs.max_leaked_vm_threads,
+                        "executions": engine_health.exec_limit_stats()
+                    },
+                    "database": {
+                        "accounts_count": db_stats.accounts_count,
+                        "blocks_count": db_stats.blocks_count,
+                        "size_on_disk": db_stats.size_on_disk,
+                        "last_save_seconds_ago": last_save_age,
+                        "save_pending": save_pending
+                    },
+                    "clock": {
+                        "consensus_time": consensus_now,
+                        "network_offset_secs": clock_offset,
+                        "validator_samples": clock_peers,
+                        "checkpoint_median_time": median_time_past
+                    },
+                    "quarantine": quarantine,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "timestamp": now
+                }))
+            },
+        );
 
     // 22b. GET /tor-health (Tor Hidden Service reachability status)
     let tor_health_m = metrics.clone();
@@ -2636,44 +3549,61 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             });
 
     // 23. GET /block/:hash (Block explorer - get block by hash)
-    let l_block_hash = ledger.clone();
+    let l_block_hash = ledger_view.clone();
+    let labels_block = labels.clone();
     let block_by_hash_route = warp::path!("block" / String)
-        .and(with_state(l_block_hash))
-        .map(|hash: String, l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
-            if let Some(block) = l_guard.blocks.get(&hash) {
-                api_json(serde_json::json!({
-                    "status": "success",
-                    "block": {
-                        "hash": hash,
-                        "account": block.account,
-                        "previous": block.previous,
-                        "type": format!("{:?}", block.block_type),
-                        "amount": block.amount / CIL_PER_LOS,
-                        "amount_cil": block.amount,
-                        "link": block.link,
-                        "signature": block.signature,
-                        "public_key": block.public_key,
-                        "work": block.work,
-                        "timestamp": block.timestamp
-                    }
-                }))
-            } else {
-                api_json(serde_json::json!({
-                    "status": "error",
-                    "msg": format!("Block not found: {}", hash)
-                }))
-            }
-        });
+        .and(with_state((l_block_hash, labels_block)))
+        .map(
+            |hash: String,
+             (l, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let l_guard = l.current();
+                if let Some(block) = l_guard.blocks.get(&hash) {
+                    let mut body = serde_json::json!({
+                        "status": "success",
+                        "block": {
+                            "hash": hash,
+                            "account": block.account,
+                            "previous": block.previous,
+                            "type": format!("{:?}", block.block_type),
+                            "amount": block.amount / CIL_PER_LOS,
+                            "amount_cil": block.amount,
+                            "amount_los": format_balance_precise(block.amount),
+                            "link": block.link,
+                            "signature": block.signature,
+                            "public_key": block.public_key,
+                            "work": block.work,
+                            "timestamp": block.timestamp
+                        }
+                    });
+                    safe_lock(&labels)
+                        .annotate(&mut body["block"], &[("account", "account_label")]);
+                    api_json(body)
+                } else {
+                    api_json(serde_json::json!({
+                        "status": "error",
+                        "msg": format!("Block not found: {}", hash)
+                    }))
+                }
+            },
+        );
 
     // 24. GET /transaction/:hash (Alias for block by hash - block explorer compatibility)
-    let l_tx_hash = ledger.clone();
+    let l_tx_hash = ledger_view.clone();
+    let labels_tx = labels.clone();
     let tx_by_hash_route = warp::path!("transaction" / String)
-        .and(with_state(l_tx_hash))
-        .map(|hash: String, l: Arc<Mutex<Ledger>>| {
-            let l_guard = safe_lock(&l);
+        .and(with_state((l_tx_hash, labels_tx)))
+        .map(
+            |hash: String,
+             (l, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+            let l_guard = l.current();
             if let Some(block) = l_guard.blocks.get(&hash) {
-                api_json(serde_json::json!({
+                let mut body = serde_json::json!({
                     "status": "success",
                     "transaction": {
                         "hash": hash,
@@ -2682,11 +3612,17 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         "type": format!("{:?}", block.block_type),
                         "amount": block.amount / CIL_PER_LOS,
                         "amount_cil": block.amount,
+                        "amount_los": format_balance_precise(block.amount),
                         "timestamp": block.timestamp,
                         "signature": block.signature,
                         "confirmed": true
                     }
-                }))
+                });
+                safe_lock(&labels).annotate(
+                    &mut body["transaction"],
+                    &[("from", "from_label"), ("to", "to_label")],
+                );
+                api_json(body)
             } else {
                 api_json(serde_json::json!({
                     "status": "error",
@@ -2696,14 +3632,20 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         });
 
     // 25. GET /search/:query (Block explorer - search for address, block, or transaction)
-    let l_search = ledger.clone();
+    let l_search = ledger_view.clone();
     let ab_search = address_book.clone();
+    let labels_search = labels.clone();
     let search_route = warp::path!("search" / String)
-        .and(with_state((l_search, ab_search)))
+        .and(with_state((l_search, ab_search, labels_search)))
         .map(
             #[allow(clippy::type_complexity)]
-            |query: String, (l, ab): (Arc<Mutex<Ledger>>, Arc<Mutex<HashMap<String, String>>>)| {
-                let l_guard = safe_lock(&l);
+            |query: String,
+             (l, ab, labels): (
+                Arc<ledger_view::LedgerView>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                let l_guard = l.current();
                 let mut results = Vec::new();
 
                 // Check if it's a full address
@@ -2713,6 +3655,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "type": "account",
                             "address": query,
                             "balance": acc.balance / CIL_PER_LOS,
+                            "balance_cil": acc.balance,
+                            "balance_los": format_balance_precise(acc.balance),
                             "block_count": acc.block_count
                         }));
                     }
@@ -2735,6 +3679,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                             "address": full,
                             "short_address": query,
                             "balance": acc.balance / CIL_PER_LOS,
+                            "balance_cil": acc.balance,
+                            "balance_los": format_balance_precise(acc.balance),
                             "block_count": acc.block_count
                         }));
                     }
@@ -2748,6 +3694,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                                 "type": "account",
                                 "address": addr,
                                 "balance": acc.balance / CIL_PER_LOS,
+                                "balance_cil": acc.balance,
+                                "balance_los": format_balance_precise(acc.balance),
                                 "block_count": acc.block_count
                             }));
                             if results.len() >= 10 {
@@ -2757,6 +3705,10 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     }
                 }
 
+                let labels = safe_lock(&labels);
+                for result in &mut results {
+                    labels.annotate(result, &[("address", "label")]);
+                }
                 api_json(serde_json::json!({
                     "query": query,
                     "results": results,
@@ -2767,18 +3719,21 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
 
     // CORS configuration
     // SECURITY: Behind Tor hidden service, browser requests come from .onion origin.
-    // Allow any origin since Tor hidden services are already access-controlled by
-    // the .onion address itself. Same-origin would block legitimate Tor Browser users.
-    let cors = if los_core::is_mainnet_build() {
+    // Allow any origin by default since Tor hidden services are already access-controlled
+    // by the .onion address itself. Same-origin would block legitimate Tor Browser users.
+    // Public RPC providers restrict origins with LOS_CORS_ORIGINS.
+    let cors = if los_core::is_mainnet() {
         warp::cors()
-            .allow_any_origin() // .onion addresses serve as access control
             .allow_methods(vec!["GET", "POST", "OPTIONS"])
-            .allow_headers(vec!["Content-Type", "Accept"])
+            .allow_headers(vec!["Content-Type", "Accept", "X-API-Key"])
     } else {
         warp::cors()
-            .allow_any_origin()
             .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
-            .allow_headers(vec!["Content-Type", "Authorization", "Accept"])
+            .allow_headers(vec!["Content-Type", "Authorization", "Accept", "X-API-Key"])
+    };
+    let cors = match &api_access.config().cors_origins {
+        Some(origins) => cors.allow_origins(origins.iter().map(String::as_str)),
+        None => cors.allow_any_origin(), // .onion addresses serve as access control
     };
 
     // 26. GET /sync (HTTP-based state sync for Tor peers)
@@ -2790,6 +3745,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         .and(with_state(l_sync))
         .map(
             |params: std::collections::HashMap<String, String>, l: Arc<Mutex<Ledger>>| {
+                if !los_network::bandwidth::serves_bulk_sync() {
+                    return api_json(sync_delegated_json());
+                }
                 let their_blocks: usize = params
                     .get("blocks")
                     .and_then(|s| s.parse().ok())
@@ -2831,14 +3789,32 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     // 26b. GET /sync/full — Streaming gzip-compressed full ledger state.
     // Unlike SYNC_GZIP (gossip, capped at 8MB), this has NO size limit.
     // Used by REST-based sync fallback when state exceeds gossip capacity.
-    // Returns: Content-Encoding: gzip, Content-Type: application/octet-stream
+    // Returns: Content-Encoding: gzip, Content-Type: application/octet-stream,
+    // or with ?format=v2 the bincode + zstd payload (sync_codec.rs)
+    // X-VM-Root / X-Rewards-Root carry the other two components of the full
+    // state root, so a cold-syncing node can check the snapshot against a
+    // checkpoint's state_root (checkpoint_sync::verify_anchor).
     let l_sync_full = ledger.clone();
+    let vm_sync_full = wasm_engine.clone();
+    let rp_sync_full = reward_pool.clone();
     let sync_full_route = warp::path!("sync" / "full")
         .and(warp::get())
         .and(warp::query::<std::collections::HashMap<String, String>>())
-        .and(with_state(l_sync_full))
+        .and(with_state((l_sync_full, vm_sync_full, rp_sync_full)))
         .map(
-            |params: std::collections::HashMap<String, String>, l: Arc<Mutex<Ledger>>| {
+            |params: std::collections::HashMap<String, String>,
+             (l, vm, rp): (
+                Arc<Mutex<Ledger>>,
+                Arc<WasmEngine>,
+                Arc<Mutex<ValidatorRewardPool>>,
+            )| {
+                if !los_network::bandwidth::serves_bulk_sync() {
+                    return warp::http::Response::builder()
+                        .status(503)
+                        .header("Content-Type", "application/json")
+                        .body(sync_delegated_json().to_string().into_bytes())
+                        .unwrap_or_default();
+                }
                 let their_blocks: usize = params
                     .get("blocks")
                     .and_then(|s| s.parse().ok())
@@ -2857,72 +3833,672 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                         .unwrap_or_default();
                 }
 
-                // Serialize full ledger state and gzip compress
-                let json = serde_json::to_string(&*l_guard).unwrap_or_default();
-                drop(l_guard); // Release lock before compression
-
-                use flate2::write::GzEncoder;
-                use flate2::Compression;
-                use std::io::Write;
-                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-                let _ = encoder.write_all(json.as_bytes());
-                let compressed = encoder.finish().unwrap_or_default();
+                // v2 (bincode + zstd) when asked via ?format=v2, else gzip JSON
+                let format =
+                    sync_codec::SyncFormat::negotiate(params.get("format").map(|s| s.as_str()));
+                let compressed = sync_codec::encode(&l_guard, format).unwrap_or_default();
+                // Same state as the snapshot. Lock order: ledger → reward pool
+                let vm_root = vm.state_root().unwrap_or_default();
+                let rewards_root = safe_lock(&rp).state_root();
+                drop(l_guard);
 
                 println!(
-                    "📤 REST /sync/full: {} blocks, {:.1} KB compressed",
+                    "📤 REST /sync/full: {} blocks, {:.1} KB compressed ({:?})",
                     our_blocks,
-                    compressed.len() as f64 / 1024.0
+                    compressed.len() as f64 / 1024.0,
+                    format
                 );
 
-                warp::http::Response::builder()
-                    .header("Content-Type", "application/octet-stream")
-                    .header("Content-Encoding", "gzip")
+                let builder = warp::http::Response::builder();
+                let builder = match format {
+                    sync_codec::SyncFormat::JsonGzip => builder
+                        .header("Content-Type", "application/octet-stream")
+                        .header("Content-Encoding", "gzip"),
+                    sync_codec::SyncFormat::BincodeZstd => {
+                        builder.header("Content-Type", sync_codec::CONTENT_TYPE_V2)
+                    }
+                };
+                builder
                     .header("X-Block-Count", our_blocks.to_string())
+                    .header(checkpoint_sync::VM_ROOT_HEADER, vm_root)
+                    .header(checkpoint_sync::REWARDS_ROOT_HEADER, rewards_root)
                     .body(compressed)
                     .unwrap_or_default()
             },
         );
 
-    // 27. GET /consensus (aBFT consensus parameters and safety status)
-    let abft_consensus_route = abft_consensus.clone();
-    let l_consensus = ledger.clone();
-    let consensus_route = warp::path("consensus")
-        .and(with_state((abft_consensus_route, l_consensus)))
-        .map(
-            |(abft, l): (Arc<Mutex<ABFTConsensus>>, Arc<Mutex<Ledger>>)| {
-                let abft_guard = safe_lock(&abft);
-                let l_guard = safe_lock(&l);
-                let stats = abft_guard.get_statistics();
-                // active_validators must only count accounts that are BOTH
-                // registered validators AND have sufficient stake. Previously this
-                // counted all accounts with enough balance, inflating the number.
-                let active_validators = l_guard
-                    .accounts
-                    .iter()
-                    .filter(|(_, a)| a.is_validator && a.balance >= MIN_VALIDATOR_STAKE_CIL)
-                    .count();
+    // 26d. GET /sync/progress — delta sync session (phase, chains, blocks) + last result
+    let sync_progress_route = warp::path!("sync" / "progress")
+        .and(warp::get())
+        .and(with_state(delta_sync))
+        .map(|ds: Arc<Mutex<delta_sync::DeltaSync>>| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let mut body = safe_lock(&ds).progress(now);
+            body["status"] = serde_json::json!("ok");
+            api_json(body)
+        });
 
-                api_json(serde_json::json!({
-                    "protocol": "aBFT (Weighted Confirmation)",
-                    "safety": {
-                        "byzantine_safe": abft_guard.is_byzantine_safe(0),
-                        "total_validators": stats.total_validators,
-                        "active_validators": active_validators,
-                        "max_faulty": stats.max_faulty_validators,
-                        "quorum_threshold": stats.quorum_threshold,
-                        "formula": "f < n/3, quorum = 2f+1"
-                    },
-                    "confirmation": {
-                        "send_threshold": SEND_CONSENSUS_THRESHOLD,
-                        "voting_model": "linear (stake_cil)",
-                        "signature_scheme": "Dilithium5 (post-quantum)"
-                    },
-                    "finality": {
-                        "target_ms": 3000,
-                        "blocks_finalized": stats.blocks_finalized,
-                        "current_view": stats.current_view,
-                        "current_sequence": stats.current_sequence
+    // 26c. GET /sync/contracts — gzip-compressed WASM VM state (code + storage).
+    // Served so dev chains can fork real contract state (POST /dev/fork).
+    let engine_sync = wasm_engine.clone();
+    let sync_contracts_route = warp::path!("sync" / "contracts")
+        .and(warp::get())
+        .and(with_state(engine_sync))
+        .map(|engine: Arc<WasmEngine>| {
+            let data = match engine.serialize_all() {
+                Ok(d) => d,
+                Err(e) => {
+                    return warp::http::Response::builder()
+                        .status(500)
+                        .header("Content-Type", "application/json")
+                        .body(
+                            serde_json::json!({"status": "error", "msg": e})
+                                .to_string()
+                                .into_bytes(),
+                        )
+                        .unwrap_or_default()
+                }
+            };
+
+            use flate2::write::GzEncoder;
+            use flate2::Compression;
+            use std::io::Write;
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            let _ = encoder.write_all(&data);
+            let compressed = encoder.finish().unwrap_or_default();
+
+            warp::http::Response::builder()
+                .header("Content-Type", "application/octet-stream")
+                .header("Content-Encoding", "gzip")
+                .body(compressed)
+                .unwrap_or_default()
+        });
+
+    // 26d. POST /dev/fork?from=<host:port> — load a live node's state (--dev only)
+    let l_fork = ledger.clone();
+    let engine_fork = wasm_engine.clone();
+    let db_fork = database.clone();
+    let p_fork = pending_sends.clone();
+    let dev_fork_route = warp::path!("dev" / "fork")
+        .and(warp::post())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((l_fork, engine_fork, db_fork, p_fork)))
+        .then(
+            #[allow(clippy::type_complexity)]
+            |params: HashMap<String, String>,
+             (l, engine, db, p): (
+                Arc<Mutex<Ledger>>,
+                Arc<WasmEngine>,
+                Arc<LosDatabase>,
+                Arc<Mutex<HashMap<String, (Block, u128)>>>,
+            )| async move {
+                if !testnet_config::is_dev_mode() {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": "POST /dev/fork is only available with los-node --dev"
+                    }));
+                }
+                let host = match dev_mode::parse_fork_source(
+                    params.get("from").map(String::as_str).unwrap_or(""),
+                ) {
+                    Ok(h) => h,
+                    Err(e) => return api_json(serde_json::json!({"status": "error", "msg": e})),
+                };
+                println!("🍴 Dev fork: loading state from {}", host);
+
+                let incoming = match fetch_peer_ledger(&host, 0).await {
+                    Ok(Some(l)) => l,
+                    Ok(None) => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 502,
+                            "msg": "Source node returned no ledger state"
+                        }))
+                    }
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 502,
+                            "msg": format!("Ledger fetch failed: {}", e)
+                        }))
+                    }
+                };
+                let vm_data = match dev_mode::fetch_peer_contracts(&host).await {
+                    Ok(d) => d,
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 502,
+                            "msg": format!("Contract state fetch failed: {}", e)
+                        }))
+                    }
+                };
+
+                // Validate the VM snapshot on a scratch engine before touching live state
+                let contracts = match &vm_data {
+                    Some(data) => match WasmEngine::new().deserialize_all(data) {
+                        Ok(n) => n,
+                        Err(e) => {
+                            return api_json(
+                                serde_json::json!({"status": "error", "code": 502, "msg": e}),
+                            )
+                        }
+                    },
+                    None => 0,
+                };
+
+                let dev_addrs = dev_mode::dev_addresses().unwrap_or_default();
+                let (blocks, accounts, state_root) = {
+                    let mut l_guard = safe_lock(&l);
+                    *l_guard = incoming;
+                    dev_mode::fund_accounts(&mut l_guard, &dev_addrs);
+                    mark_dirty();
+                    (
+                        l_guard.blocks.len(),
+                        l_guard.accounts.len(),
+                        l_guard.compute_state_root(),
+                    )
+                };
+                safe_lock(&p).clear();
+                if let Some(data) = &vm_data {
+                    if engine.deserialize_all(data).is_ok() {
+                        let _ = db.save_contracts(data);
                     }
+                } else {
+                    println!(
+                        "⚠️ Dev fork: {} does not serve /sync/contracts — ledger only",
+                        host
+                    );
+                }
+
+                println!(
+                    "🍴 Dev fork complete: {} blocks, {} accounts, {} contracts",
+                    blocks, accounts, contracts
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "from": host,
+                    "blocks": blocks,
+                    "accounts": accounts,
+                    "contracts": contracts,
+                    "contracts_forked": vm_data.is_some(),
+                    "state_root": state_root,
+                    "dev_accounts": dev_addrs
+                }))
+            },
+        );
+
+    // 27. GET /consensus (aBFT consensus parameters and safety status)
+    let abft_consensus_route = abft_consensus.clone();
+    let l_consensus = ledger.clone();
+    let consensus_route = warp::path("consensus")
+        .and(with_state((abft_consensus_route, l_consensus)))
+        .map(
+            |(abft, l): (Arc<Mutex<ABFTConsensus>>, Arc<Mutex<Ledger>>)| {
+                let abft_guard = safe_lock(&abft);
+                let l_guard = safe_lock(&l);
+                let stats = abft_guard.get_statistics();
+                // active_validators must only count accounts that are BOTH
+                // registered validators AND have sufficient stake. Previously this
+                // counted all accounts with enough balance, inflating the number.
+                let active_validators = l_guard
+                    .accounts
+                    .iter()
+                    .filter(|(_, a)| a.is_validator && a.balance >= MIN_VALIDATOR_STAKE_CIL)
+                    .count();
+
+                api_json(serde_json::json!({
+                    "protocol": "aBFT (Weighted Confirmation)",
+                    "safety": {
+                        "byzantine_safe": abft_guard.is_byzantine_safe(0),
+                        "total_validators": stats.total_validators,
+                        "active_validators": active_validators,
+                        "max_faulty": stats.max_faulty_validators,
+                        "quorum_threshold": stats.quorum_threshold,
+                        "formula": "f < n/3, quorum = 2f+1"
+                    },
+                    "confirmation": {
+                        "send_threshold": SEND_CONSENSUS_THRESHOLD,
+                        "voting_model": "linear (stake_cil)",
+                        "signature_scheme": "Dilithium5 (post-quantum)"
+                    },
+                    "finality": {
+                        "target_ms": 3000,
+                        "blocks_finalized": stats.blocks_finalized,
+                        "current_view": stats.current_view,
+                        "current_sequence": stats.current_sequence
+                    }
+                }))
+            },
+        );
+
+    // 27a. /admin/api-keys (API key management, requires LOS_ADMIN_TOKEN)
+    //   GET                      list keys + usage
+    //   POST   {label, tier}     create a key (returned once)
+    //   PUT    /:id {tier}       change tier
+    //   DELETE /:id              revoke
+    let admin_token = warp::header::optional::<String>(api_access::ADMIN_TOKEN_HEADER);
+    let admin_list_keys_route = warp::path!("admin" / "api-keys")
+        .and(warp::get())
+        .and(admin_token)
+        .and(with_state(api_access.clone()))
+        .map(|token: Option<String>, access: Arc<ApiAccess>| {
+            if let Some(err) = access.admin_denied(token.as_deref()) {
+                return api_json(err);
+            }
+            let keys: Vec<serde_json::Value> = access
+                .list()
+                .into_iter()
+                .map(|(r, u)| {
+                    serde_json::json!({
+                        "id": r.id,
+                        "label": r.label,
+                        "tier": r.tier,
+                        "created_at": r.created_at,
+                        "requests": u.requests,
+                        "rate_limited": u.rate_limited,
+                        "last_used": u.last_used
+                    })
+                })
+                .collect();
+            let cfg = access.config();
+            api_json(serde_json::json!({
+                "status": "success",
+                "tiers": { "free": cfg.free, "paid": cfg.paid },
+                "count": keys.len(),
+                "keys": keys
+            }))
+        });
+
+    let admin_create_key_route = warp::path!("admin" / "api-keys")
+        .and(warp::post())
+        .and(admin_token)
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json::<serde_json::Value>())
+        .and(with_state((api_access.clone(), database.clone())))
+        .map(
+            |token: Option<String>,
+             body: serde_json::Value,
+             (access, db): (Arc<ApiAccess>, Arc<LosDatabase>)| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                let tier = match ApiTier::parse(body["tier"].as_str().unwrap_or("free")) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        return api_json(
+                            serde_json::json!({"status": "error", "code": 400, "msg": e}),
+                        )
+                    }
+                };
+                let label = body["label"].as_str().unwrap_or_default();
+                let (record, key) = match access.create_key(label, tier) {
+                    Ok(created) => created,
+                    Err(e) => {
+                        return api_json(
+                            serde_json::json!({"status": "error", "code": 400, "msg": e}),
+                        )
+                    }
+                };
+                if let Err(e) = db.save_api_key(&record) {
+                    let _ = access.revoke(&record.id);
+                    return api_json(serde_json::json!({"status": "error", "code": 500, "msg": e}));
+                }
+                println!(
+                    "🔑 API key {} created ({}, {})",
+                    record.id,
+                    record.tier.as_str(),
+                    record.label
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "id": record.id,
+                    "label": record.label,
+                    "tier": record.tier,
+                    "api_key": key,
+                    "msg": "Store this key now — it cannot be shown again"
+                }))
+            },
+        );
+
+    let admin_update_key_route = warp::path!("admin" / "api-keys" / String)
+        .and(warp::put())
+        .and(admin_token)
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json::<serde_json::Value>())
+        .and(with_state((api_access.clone(), database.clone())))
+        .map(
+            |id: String,
+             token: Option<String>,
+             body: serde_json::Value,
+             (access, db): (Arc<ApiAccess>, Arc<LosDatabase>)| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                let tier = match body["tier"].as_str().map(ApiTier::parse) {
+                    Some(Ok(t)) => t,
+                    Some(Err(e)) => return api_json(serde_json::json!({"status": "error", "code": 400, "msg": e})),
+                    None => return api_json(serde_json::json!({"status": "error", "code": 400, "msg": "Missing tier"})),
+                };
+                match access.set_tier(&id, tier).and_then(|r| db.save_api_key(&r).map(|_| r)) {
+                    Ok(r) => api_json(serde_json::json!({"status": "success", "id": r.id, "tier": r.tier})),
+                    Err(e) => api_json(serde_json::json!({"status": "error", "code": 404, "msg": e})),
+                }
+            },
+        );
+
+    let admin_revoke_key_route = warp::path!("admin" / "api-keys" / String)
+        .and(warp::delete())
+        .and(admin_token)
+        .and(with_state((api_access.clone(), database.clone())))
+        .map(
+            |id: String,
+             token: Option<String>,
+             (access, db): (Arc<ApiAccess>, Arc<LosDatabase>)| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                match access.revoke(&id) {
+                    Ok(r) => {
+                        if let Err(e) = db.remove_api_key(&r.id) {
+                            eprintln!(
+                                "⚠️  API key {} revoked but not removed from disk: {}",
+                                r.id, e
+                            );
+                        }
+                        println!("🔑 API key {} revoked", r.id);
+                        api_json(
+                            serde_json::json!({"status": "success", "id": r.id, "revoked": true}),
+                        )
+                    }
+                    Err(e) => {
+                        api_json(serde_json::json!({"status": "error", "code": 404, "msg": e}))
+                    }
+                }
+            },
+        );
+
+    // 27b. /admin/address-book (address book + operator labels, requires LOS_ADMIN_TOKEN)
+    //   GET                          list entries
+    //   POST   {address, label?}     add an address (resolvable by its short form)
+    //   PUT    /:address {label}     set or replace the label of any address
+    //   DELETE /:address             remove the address and its label
+    let admin_list_book_route = warp::path!("admin" / "address-book")
+        .and(warp::get())
+        .and(admin_token)
+        .and(with_state((
+            api_access.clone(),
+            address_book.clone(),
+            labels.clone(),
+        )))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |token: Option<String>,
+             (access, ab, labels): (
+                Arc<ApiAccess>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+            )| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                // address → (short address, in address book)
+                let mut entries: BTreeMap<String, (String, bool)> = safe_lock(&ab)
+                    .iter()
+                    .map(|(short, full)| (full.clone(), (short.clone(), true)))
+                    .collect();
+                let labels = safe_lock(&labels);
+                for (address, _) in labels.iter() {
+                    entries
+                        .entry(address.clone())
+                        .or_insert_with(|| (get_short_addr(address), false));
+                }
+                let entries: Vec<serde_json::Value> = entries
+                    .into_iter()
+                    .map(|(address, (short, in_book))| {
+                        serde_json::json!({
+                            "address": address,
+                            "short_address": short,
+                            "in_address_book": in_book,
+                            "label": labels.get(&address)
+                        })
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "count": entries.len(),
+                    "entries": entries
+                }))
+            },
+        );
+
+    let admin_add_book_route = warp::path!("admin" / "address-book")
+        .and(warp::post())
+        .and(admin_token)
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json::<serde_json::Value>())
+        .and(with_state((
+            api_access.clone(),
+            address_book.clone(),
+            labels.clone(),
+            database.clone(),
+        )))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |token: Option<String>,
+             body: serde_json::Value,
+             (access, ab, labels, db): (
+                Arc<ApiAccess>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+                Arc<LosDatabase>,
+            )| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                let address = body["address"].as_str().unwrap_or_default().trim();
+                if !los_crypto::validate_address(address) {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400, "msg": "Invalid address"
+                    }));
+                }
+                let label = match body["label"].as_str() {
+                    Some(l) => match address_labels::validate_label(l) {
+                        Ok(l) => Some(l),
+                        Err(e) => {
+                            return api_json(
+                                serde_json::json!({"status": "error", "code": 400, "msg": e}),
+                            )
+                        }
+                    },
+                    None => None,
+                };
+                let short = get_short_addr(address);
+                {
+                    let mut ab = safe_lock(&ab);
+                    if let Some(existing) = ab.get(&short).filter(|f| *f != address) {
+                        return api_json(serde_json::json!({
+                            "status": "error",
+                            "code": 409,
+                            "msg": format!("{} already maps to {}", short, existing)
+                        }));
+                    }
+                    ab.insert(short.clone(), address.to_string());
+                }
+                if let Err(e) = db.save_peer(&short, address) {
+                    return api_json(serde_json::json!({"status": "error", "code": 500, "msg": e}));
+                }
+                if let Some(label) = &label {
+                    let stored = safe_lock(&labels).set(address, label);
+                    if let Err(e) = stored.and_then(|l| db.save_address_label(address, &l)) {
+                        return api_json(
+                            serde_json::json!({"status": "error", "code": 500, "msg": e}),
+                        );
+                    }
+                }
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "address": address,
+                    "short_address": short,
+                    "label": label
+                }))
+            },
+        );
+
+    let admin_label_book_route = warp::path!("admin" / "address-book" / String)
+        .and(warp::put())
+        .and(admin_token)
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json::<serde_json::Value>())
+        .and(with_state((
+            api_access.clone(),
+            labels.clone(),
+            database.clone(),
+        )))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |address: String,
+             token: Option<String>,
+             body: serde_json::Value,
+             (access, labels, db): (
+                Arc<ApiAccess>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+                Arc<LosDatabase>,
+            )| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                if !los_crypto::validate_address(&address) {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400, "msg": "Invalid address"
+                    }));
+                }
+                let Some(label) = body["label"].as_str() else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400, "msg": "Missing label"
+                    }));
+                };
+                let stored = safe_lock(&labels).set(&address, label);
+                match stored.and_then(|l| db.save_address_label(&address, &l).map(|_| l)) {
+                    Ok(label) => api_json(serde_json::json!({
+                        "status": "success", "address": address, "label": label
+                    })),
+                    Err(e) => {
+                        api_json(serde_json::json!({"status": "error", "code": 400, "msg": e}))
+                    }
+                }
+            },
+        );
+
+    let admin_remove_book_route = warp::path!("admin" / "address-book" / String)
+        .and(warp::delete())
+        .and(admin_token)
+        .and(with_state((
+            api_access.clone(),
+            address_book.clone(),
+            labels.clone(),
+            database.clone(),
+        )))
+        .map(
+            #[allow(clippy::type_complexity)]
+            |address: String,
+             token: Option<String>,
+             (access, ab, labels, db): (
+                Arc<ApiAccess>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<address_labels::AddressLabels>>,
+                Arc<LosDatabase>,
+            )| {
+                if let Some(err) = access.admin_denied(token.as_deref()) {
+                    return api_json(err);
+                }
+                let short = get_short_addr(&address);
+                let in_book = {
+                    let mut ab = safe_lock(&ab);
+                    if ab.get(&short) == Some(&address) {
+                        ab.remove(&short);
+                        true
+                    } else {
+                        false
+                    }
+                };
+                let label = safe_lock(&labels).remove(&address);
+                if !in_book && label.is_none() {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404, "msg": "Address not in address book"
+                    }));
+                }
+                let persisted = (if in_book {
+                    db.remove_peer(&short)
+                } else {
+                    Ok(())
+                })
+                .and_then(|_| db.remove_address_label(&address));
+                if let Err(e) = persisted {
+                    eprintln!("⚠️  Address {} removed but not from disk: {}", address, e);
+                }
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "address": address,
+                    "removed": true
+                }))
+            },
+        );
+
+    // 28a. GET /rewards/fees?epoch=N&validator=LOS... (fee redistribution per epoch)
+    let rp_fees = reward_pool.clone();
+    let reward_fees_route = warp::path!("rewards" / "fees")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state(rp_fees))
+        .map(
+            |params: HashMap<String, String>, rp: Arc<Mutex<ValidatorRewardPool>>| {
+                let pool = safe_lock(&rp);
+                let epoch = match params.get("epoch").filter(|v| !v.is_empty()) {
+                    Some(v) => match v.parse::<u64>() {
+                        Ok(e) => Some(e),
+                        Err(_) => {
+                            return api_json(serde_json::json!({
+                                "status": "error", "code": 400,
+                                "msg": "epoch must be a non-negative integer"
+                            }))
+                        }
+                    },
+                    // Default: most recent epoch with a fee distribution
+                    None => pool.fee_history.keys().next_back().copied(),
+                };
+                let Some(record) = epoch.and_then(|e| pool.fee_distribution(e)) else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": "No fee distribution recorded for this epoch",
+                        "epoch": epoch,
+                        "available_epochs": pool.fee_history.keys().rev().take(20).collect::<Vec<_>>()
+                    }));
+                };
+                let validator = params.get("validator").filter(|v| !v.is_empty());
+                let shares: Vec<serde_json::Value> = record
+                    .shares
+                    .iter()
+                    .filter(|s| validator.is_none_or(|v| *v == s.validator))
+                    .map(|s| {
+                        serde_json::json!({
+                            "validator": s.validator,
+                            "amount_cil": s.amount_cil,
+                            "amount_los": format_balance_precise(s.amount_cil),
+                            "share_bps": (s.amount_cil.saturating_mul(10_000))
+                                .checked_div(record.total_fees_cil)
+                                .unwrap_or(0),
+                            "block_hash": s.block_hash
+                        })
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "epoch": record.epoch,
+                    "total_fees_cil": record.total_fees_cil,
+                    "total_fees_los": format_balance_precise(record.total_fees_cil),
+                    "validator_count": record.shares.len(),
+                    "shares": shares
                 }))
             },
         );
@@ -2962,38 +4538,220 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 })
                 .collect();
 
-            api_json(serde_json::json!({
-                "pool": {
-                    "remaining_cil": summary.remaining_cil,
-                    "remaining_los": format_balance_precise(summary.remaining_cil),
-                    "total_distributed_cil": summary.total_distributed_cil,
-                    "total_distributed_los": format_balance_precise(summary.total_distributed_cil),
-                    "pool_exhaustion_bps": summary.pool_exhaustion_bps,
-                },
-                "epoch": {
-                    "current_epoch": summary.current_epoch,
-                    "epoch_reward_rate_cil": summary.epoch_reward_rate_cil,
-                    "epoch_reward_rate_los": format_balance_precise(summary.epoch_reward_rate_cil),
-                    "halvings_occurred": summary.halvings_occurred,
-                    "epoch_elapsed_secs": epoch_elapsed_secs,
-                    "epoch_remaining_secs": remaining_secs,
-                    "epoch_duration_secs": pool.epoch_duration_secs,
-                },
-                "validators": {
-                    "total": summary.total_validators,
-                    "eligible": summary.eligible_validators,
-                    "details": validators_json,
-                },
-                "config": {
-                    "min_uptime_pct": los_core::REWARD_MIN_UPTIME_PCT,
-                    "probation_epochs": los_core::REWARD_PROBATION_EPOCHS,
-                    "halving_interval_epochs": los_core::REWARD_HALVING_INTERVAL_EPOCHS,
-                    "distribution_model": "linear stake-weighted proportional",
-                    "genesis_excluded": false,
+            api_json(serde_json::json!({
+                "pool": {
+                    "remaining_cil": summary.remaining_cil,
+                    "remaining_los": format_balance_precise(summary.remaining_cil),
+                    "total_distributed_cil": summary.total_distributed_cil,
+                    "total_distributed_los": format_balance_precise(summary.total_distributed_cil),
+                    "pool_exhaustion_bps": summary.pool_exhaustion_bps,
+                    "phase": summary.phase,
+                    "fee_only_since_epoch": summary.fee_only_since_epoch,
+                },
+                "epoch": {
+                    "current_epoch": summary.current_epoch,
+                    "epoch_reward_rate_cil": summary.epoch_reward_rate_cil,
+                    "epoch_reward_rate_los": format_balance_precise(summary.epoch_reward_rate_cil),
+                    "halvings_occurred": summary.halvings_occurred,
+                    "epoch_elapsed_secs": epoch_elapsed_secs,
+                    "epoch_remaining_secs": remaining_secs,
+                    "epoch_duration_secs": pool.epoch_duration_secs,
+                },
+                "validators": {
+                    "total": summary.total_validators,
+                    "eligible": summary.eligible_validators,
+                    "details": validators_json,
+                },
+                "config": {
+                    "min_uptime_pct": los_core::REWARD_MIN_UPTIME_PCT,
+                    "probation_epochs": los_core::effective_reward_probation_epochs(),
+                    "halving_interval_epochs": los_core::effective_reward_halving_interval(),
+                    "distribution_model": "linear stake-weighted proportional",
+                    "reward_payout": los_core::chain_spec::active().reward_payout,
+                    "genesis_excluded": false,
+                }
+            }))
+        },
+    );
+
+    // GET /rewards/claim/{address} — claim payout model: accrued, claimed and
+    // claimable validator rewards (los_core::reward_claims)
+    let reward_claim_info_route = warp::path!("rewards" / "claim" / String)
+        .and(warp::get())
+        .and(with_state((ledger.clone(), reward_pool.clone())))
+        .map(
+            |address: String, (l, rp): (Arc<Mutex<Ledger>>, Arc<Mutex<ValidatorRewardPool>>)| {
+                // Lock order: ledger → pool
+                let l_guard = safe_lock(&l);
+                let claimed = l_guard.reward_claimed_cil(&address);
+                let pool = safe_lock(&rp);
+                // One Claim block per accrued epoch: amount + link to sign
+                let claims: Vec<serde_json::Value> = pool
+                    .unclaimed_payouts(&address, |d| l_guard.is_payout_claimed(d, &address))
+                    .map(|(epoch, amount, cert)| {
+                        serde_json::json!({
+                            "epoch": epoch,
+                            "amount_cil": amount,
+                            "link": reward_claims::claim_link(cert),
+                        })
+                    })
+                    .collect();
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "address": address,
+                    "reward_payout": los_core::chain_spec::active().reward_payout,
+                    "accrued_cil": pool.accruals.get(&address).copied().unwrap_or(0),
+                    "claimed_cil": claimed,
+                    "claimable_cil": pool.claimable_cil(&address, claimed),
+                    "last_accrued_epoch": pool.last_accrued_epoch,
+                    "claims": claims,
+                }))
+            },
+        );
+
+    // POST /rewards/claim — client-signed Claim block minting an accrued epoch
+    // payout; the link carries the epoch's certificate (see GET above).
+    let reward_claim_route = warp::path!("rewards" / "claim")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::json())
+        .and(with_state((
+            ledger.clone(),
+            reward_pool.clone(),
+            tx_out.clone(),
+        )))
+        .then(
+            |block: Block,
+             (l, rp, tx): (
+                Arc<Mutex<Ledger>>,
+                Arc<Mutex<ValidatorRewardPool>>,
+                mpsc::Sender<String>,
+            )| async move {
+                if block.block_type != BlockType::Claim {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "block_type must be Claim"
+                    }));
+                }
+                let (block_hash, claimable) = {
+                    let mut l_guard = safe_lock(&l);
+                    match l_guard.process_block(&block) {
+                        Ok(result) => {
+                            let claimed = l_guard.reward_claimed_cil(&block.account);
+                            let claimable = safe_lock(&rp).claimable_cil(&block.account, claimed);
+                            (result.into_hash(), claimable)
+                        }
+                        Err(e) => {
+                            return api_json(serde_json::json!({"status": "error", "msg": e}))
+                        }
+                    }
+                };
+                let _ = tx
+                    .send(serde_json::to_string(&block).unwrap_or_default())
+                    .await;
+                mark_dirty();
+                println!(
+                    "🏆 Rewards claimed: {} → {} CIL",
+                    get_short_addr(&block.account),
+                    block.amount
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "block_hash": block_hash,
+                    "account": block.account,
+                    "amount_cil": block.amount,
+                    "claimable_cil": claimable
+                }))
+            },
+        );
+
+    // POST /reclaim — client-signed Reclaim block returning an expired,
+    // unreceived Send (expires_at) to its sender.
+    let reclaim_route = warp::path("reclaim")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::json())
+        .and(with_state((ledger.clone(), tx_out.clone())))
+        .then(
+            |block: Block, (l, tx): (Arc<Mutex<Ledger>>, mpsc::Sender<String>)| async move {
+                if block.block_type != BlockType::Reclaim {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "block_type must be Reclaim"
+                    }));
+                }
+                let block_hash = match safe_lock(&l).process_block(&block) {
+                    Ok(result) => result.into_hash(),
+                    Err(e) => return api_json(serde_json::json!({"status": "error", "msg": e})),
+                };
+                let _ = tx
+                    .send(serde_json::to_string(&block).unwrap_or_default())
+                    .await;
+                mark_dirty();
+                println!(
+                    "↩️ Expired send {} reclaimed by {} ({} CIL)",
+                    get_short_addr(&block.link),
+                    get_short_addr(&block.account),
+                    block.amount
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "block_hash": block_hash,
+                    "account": block.account,
+                    "send_hash": block.link,
+                    "amount_cil": block.amount
+                }))
+            },
+        );
+
+    // POST /session-key — client-signed Change block granting or revoking a
+    // session key (link SESSION_GRANT:… / SESSION_REVOKE:…, session_keys.rs)
+    let session_key_route = warp::path("session-key")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::json())
+        .and(with_state((ledger.clone(), tx_out.clone())))
+        .then(
+            |block: Block, (l, tx): (Arc<Mutex<Ledger>>, mpsc::Sender<String>)| async move {
+                if !los_core::session_keys::is_session_change(&block) {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "block must be a Change block with a SESSION_GRANT: or SESSION_REVOKE: link"
+                    }));
                 }
-            }))
-        },
-    );
+                let block_hash = match safe_lock(&l).process_block(&block) {
+                    Ok(result) => result.into_hash(),
+                    Err(e) => return api_json(serde_json::json!({"status": "error", "msg": e})),
+                };
+                let _ = tx
+                    .send(serde_json::to_string(&block).unwrap_or_default())
+                    .await;
+                mark_dirty();
+                let revoked = block
+                    .link
+                    .strip_prefix(los_core::session_keys::SESSION_REVOKE_PREFIX);
+                let session_address = match revoked {
+                    Some(addr) => addr.to_string(),
+                    None => los_core::session_keys::parse_grant_link(&block.link)
+                        .map(|(addr, _)| addr)
+                        .unwrap_or_default(),
+                };
+                println!(
+                    "🗝️ Session key {} {} for {}",
+                    get_short_addr(&session_address),
+                    if revoked.is_some() { "revoked" } else { "granted" },
+                    get_short_addr(&block.account)
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "block_hash": block_hash,
+                    "account": block.account,
+                    "session_address": session_address,
+                    "revoked": revoked.is_some()
+                }))
+            },
+        );
 
     // 29. POST /register-validator (Register as an active validator)
     // Requires proof of ownership via Dilithium5 signature + minimum stake.
@@ -3010,7 +4768,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let lrv_regval = local_registered_validators.clone();
     let register_validator_route = warp::path("register-validator")
         .and(warp::post())
-        .and(warp::body::bytes())
+        .and(body_limits::body(body_limits::SEND))
         .and(with_state((l_regval, sm_regval, rp_regval, tx_regval, db_regval)))
         .then(#[allow(clippy::type_complexity)] move |body: bytes::Bytes, (l, sm, rp, tx, db): (Arc<Mutex<Ledger>>, Arc<Mutex<SlashingManager>>, Arc<Mutex<ValidatorRewardPool>>, mpsc::Sender<String>, Arc<LosDatabase>)| {
             let bv_inner = bv_regval.clone();
@@ -3019,15 +4777,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             let lrv_inner = lrv_regval.clone();
             async move {
             // Parse JSON manually to return proper 400 instead of 500
-            let req: serde_json::Value = match serde_json::from_slice(&body) {
+            let req: serde_json::Value = match body_limits::parse_json(&body, body_limits::SEND) {
                 Ok(r) => r,
-                Err(e) => {
-                    return api_json(serde_json::json!({
-                        "status": "error",
-                        "code": 400,
-                        "msg": format!("Invalid request body: {}", e)
-                    }));
-                }
+                Err(e) => return api_json(e),
             };
             // Parse required fields
             let address = match req["address"].as_str() {
@@ -3167,7 +4919,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
             }
 
             // 9. Mark ledger dirty for persistence
-            SAVE_DIRTY.store(true, Ordering::Release);
+            mark_dirty();
 
             // 9b. Dynamically update aBFT validator set so new validator
             // participates in consensus immediately (no restart required).
@@ -3223,7 +4975,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "status": "ok",
                 "msg": "Validator registered successfully",
                 "address": address,
-                "stake_los": balance / CIL_PER_LOS,
+                "stake_cil": balance,
+                "stake_los": format_balance_precise(balance),
                 "is_validator": true,
                 "is_genesis": false,
             }))
@@ -3252,15 +5005,9 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
         let ve_inner = ve_unregval.clone();
         async move {
             // Parse JSON
-            let req: serde_json::Value = match serde_json::from_slice(&body) {
+            let req: serde_json::Value = match body_limits::parse_json(&body, body_limits::SEND) {
                 Ok(r) => r,
-                Err(e) => {
-                    return api_json(serde_json::json!({
-                        "status": "error",
-                        "code": 400,
-                        "msg": format!("Invalid request body: {}", e)
-                    }));
-                }
+                Err(e) => return api_json(e),
             };
 
             let address = match req["address"].as_str() {
@@ -3417,7 +5164,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 safe_lock(&abft_inner).update_validator_set(validators);
             }
 
-            SAVE_DIRTY.store(true, Ordering::Release);
+            mark_dirty();
 
             // 11. Broadcast to peers
             let unreg_msg = serde_json::json!({
@@ -3442,7 +5189,8 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 "status": "ok",
                 "msg": "Validator unregistered successfully",
                 "address": address,
-                "balance_los": balance / CIL_PER_LOS,
+                "balance_cil": balance,
+                "balance_los": format_balance_precise(balance),
                 "is_validator": false,
             }))
         }
@@ -3457,7 +5205,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let handler1 = unregister_handler.clone();
     let unregister_validator_route = warp::path("unregister-validator")
         .and(warp::post())
-        .and(warp::body::bytes())
+        .and(body_limits::body(body_limits::SEND))
         .and(with_state((
             l_unregval1,
             sm_unregval1,
@@ -3475,7 +5223,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
     let db_unregval2 = database.clone();
     let unregister_validator_underscore_route = warp::path("unregister_validator")
         .and(warp::post())
-        .and(warp::body::bytes())
+        .and(body_limits::body(body_limits::SEND))
         .and(with_state((
             l_unregval2,
             sm_unregval2,
@@ -3571,8 +5319,12 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                     "total_accepted": stats.total_accepted,
                     "total_rejected": stats.total_rejected,
                     "total_expired": stats.total_expired,
+                    "total_evicted": stats.total_evicted,
+                    "total_replaced": stats.total_replaced,
                     "unique_senders": stats.unique_senders,
+                    "scheduled": stats.scheduled,
                     "just_expired": expired,
+                    "limits": mp.config(),
                 }
             }))
         });
@@ -3607,7 +5359,7 @@ pub async fn start_api_server(cfg: ApiServerConfig) {
                 let ve_guard = safe_lock(&ve);
                 let l_guard = safe_lock(&l);
 
-                let network = if los_core::is_mainnet_build() { "Mainnet" } else { "Testnet" };
+                let network = if los_core::is_mainnet() { "Mainnet" } else { "Testnet" };
                 let uptime_secs = start_time.elapsed().as_secs();
                 let uptime_str = format_uptime(uptime_secs);
 
@@ -3773,7 +5525,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                 let ve_guard = safe_lock(&ve);
                 let l_guard = safe_lock(&l);
 
-                let network = if los_core::is_mainnet_build() { "mainnet" } else { "testnet" };
+                let network = if los_core::is_mainnet() { "mainnet" } else { "testnet" };
                 let mut peers: Vec<serde_json::Value> = Vec::new();
 
                 for (addr, host) in ve_guard.iter() {
@@ -3799,61 +5551,453 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                         "is_bootstrap": is_bootstrap,
                     }));
                 }
-
-                let active_count = peers.iter().filter(|p| p["active"].as_bool().unwrap_or(false)).count();
-                api_json(serde_json::json!({
-                    "network": network,
-                    "active_count": active_count,
-                    "total_count": peers.len(),
-                    "peers": peers,
-                    "updated_at": chrono::Utc::now().to_rfc3339(),
-                }))
+
+                let active_count = peers.iter().filter(|p| p["active"].as_bool().unwrap_or(false)).count();
+                api_json(serde_json::json!({
+                    "network": network,
+                    "active_count": active_count,
+                    "total_count": peers.len(),
+                    "peers": peers,
+                    "updated_at": chrono::Utc::now().to_rfc3339(),
+                }))
+            },
+        );
+
+    // GET /directory/api/active — Active peers only JSON (for app bootstrapping)
+    let ve_dir_active = validator_endpoints.clone();
+    let ab_dir_active = address_book.clone();
+    let l_dir_active = ledger.clone();
+    let my_addr_dir_active = my_address.clone();
+    let directory_api_active_route = warp::path!("directory" / "api" / "active")
+        .and(with_state((ve_dir_active, ab_dir_active, l_dir_active)))
+        .map(
+            move |(ve, ab, l): (
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<HashMap<String, String>>>,
+                Arc<Mutex<Ledger>>,
+            )| {
+                let ab_guard = safe_lock(&ab);
+                let ve_guard = safe_lock(&ve);
+                let _l_guard = safe_lock(&l);
+
+                let network = if los_core::is_mainnet() { "mainnet" } else { "testnet" };
+                let mut active_peers: Vec<serde_json::Value> = Vec::new();
+
+                for (addr, host) in ve_guard.iter() {
+                    let in_peers = ab_guard.values().any(|v| v == addr);
+                    let is_self = addr == &my_addr_dir_active;
+                    if !is_self && !in_peers { continue; }
+
+                    let rest_port: u16 = host.rsplit(':').next()
+                        .and_then(|p| p.parse().ok())
+                        .unwrap_or(3030);
+
+                    active_peers.push(serde_json::json!({
+                        "host": if host.contains("://") { host.clone() } else { format!("http://{}", host) },
+                        "address": addr,
+                        "transport": if host.contains(".onion") { "onion" } else { "clearnet" },
+                        "rest_port": rest_port,
+                    }));
+                }
+
+                api_json(serde_json::json!({
+                    "network": network,
+                    "active_count": active_peers.len(),
+                    "peers": active_peers,
+                    "updated_at": chrono::Utc::now().to_rfc3339(),
+                }))
+            },
+        );
+
+    // GET /checkpoints/conflicts — conflicting finalized checkpoints + operator resolutions
+    let cm_conflicts = checkpoint_manager.clone();
+    let checkpoint_conflicts_route = warp::path!("checkpoints" / "conflicts")
+        .and(warp::get())
+        .and(with_state(cm_conflicts))
+        .map(|cm: Arc<Mutex<CheckpointManager>>| {
+            api_json(checkpoint_conflict::conflicts_report(&cm))
+        });
+
+    // GET /checkpoints?from=<height>&limit=<n> — finalized checkpoints with validator signatures
+    let cm_reserves = checkpoint_manager.clone();
+    let checkpoints_route = warp::path("checkpoints")
+        .and(warp::get())
+        .and(warp::query::<std::collections::HashMap<String, String>>())
+        .and(with_state(checkpoint_manager))
+        .map(
+            |params: std::collections::HashMap<String, String>,
+             cm: Arc<Mutex<CheckpointManager>>| {
+                let (from, limit) = checkpoint_sync::parse_page(&params);
+                match safe_lock(&cm).get_checkpoints_range(from, limit) {
+                    Ok(cps) => api_json(serde_json::json!(checkpoint_sync::CheckpointPage::new(
+                        &cps, limit
+                    ))),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error",
+                        "code": 500,
+                        "msg": format!("Failed to read checkpoints: {}", e)
+                    })),
+                }
+            },
+        );
+
+    // GET /proof/reserves?addresses=a,b,c&at_checkpoint=H — Merkle balance proofs
+    let l_reserves = ledger_view.clone();
+    let vm_reserves = wasm_engine.clone();
+    let rp_reserves = reward_pool.clone();
+    let reserves_route = warp::path!("proof" / "reserves")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state((l_reserves, cm_reserves)))
+        .map(
+            move |params: HashMap<String, String>,
+             (l, cm): (Arc<ledger_view::LedgerView>, Arc<Mutex<CheckpointManager>>)| {
+                let addresses = match reserves_proof::parse_addresses(
+                    params.get("addresses").map(String::as_str).unwrap_or(""),
+                ) {
+                    Ok(a) => a,
+                    Err(e) => return api_json(serde_json::json!({"status": "error", "msg": e})),
+                };
+                let checkpoint = match params.get("at_checkpoint") {
+                    None => None,
+                    Some(h) => {
+                        let Ok(height) = h.parse::<u64>() else {
+                            return api_json(serde_json::json!({
+                                "status": "error",
+                                "msg": "at_checkpoint must be a checkpoint height"
+                            }));
+                        };
+                        match safe_lock(&cm).get_checkpoint(height) {
+                            Ok(Some(cp)) => Some(cp),
+                            Ok(None) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("No finalized checkpoint at height {}", height)
+                                }))
+                            }
+                            Err(e) => {
+                                return api_json(serde_json::json!({
+                                    "status": "error",
+                                    "msg": format!("Failed to read checkpoint: {}", e)
+                                }))
+                            }
+                        }
+                    }
+                };
+                let l_guard = l.current();
+                if let Some(cp) = &checkpoint {
+                    let live_root = {
+                        let pool = safe_lock(&rp_reserves);
+                        quarantine::full_root(&l_guard, &vm_reserves, &pool).unwrap_or_default()
+                    };
+                    if live_root != cp.state_root {
+                        return api_json(serde_json::json!({
+                            "status": "error",
+                            "msg": format!(
+                                "State has changed since checkpoint {}; historical account state is not retained",
+                                cp.height
+                            )
+                        }));
+                    }
+                }
+                match reserves_proof::build_reserves_proof(&l_guard, &addresses) {
+                    Ok(proof) => api_json(serde_json::json!({
+                        "status": "success",
+                        "total": format_balance_precise(proof.total_cil),
+                        "checkpoint": checkpoint.as_ref().map(|cp| serde_json::json!({
+                            "id": cp.calculate_id(),
+                            "height": cp.height,
+                            "state_root": cp.state_root,
+                            "signature_count": cp.signature_count
+                        })),
+                        "proof": proof
+                    })),
+                    Err(e) => api_json(serde_json::json!({"status": "error", "msg": e})),
+                }
+            },
+        );
+
+    // GET /forks?account=LOS...&limit=N — recorded account chain conflicts
+    let forks_route = warp::path("forks")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state(fork_monitor))
+        .map(
+            |params: HashMap<String, String>, fm: Arc<Mutex<fork_monitor::ForkMonitor>>| {
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(100)
+                    .min(fork_monitor::MAX_TRACKED_FORKS);
+                let fm = safe_lock(&fm);
+                let forks = fm.list(params.get("account").map(String::as_str), limit);
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "total": fm.count(),
+                    "count": forks.len(),
+                    "forks": forks
+                }))
+            },
+        );
+
+    // GET /storage — database size, fragmentation, snapshots, disk headroom
+    let storage_route = warp::path("storage")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(storage_report))
+        .map(|report: Arc<Mutex<storage::StorageReport>>| {
+            let report = safe_lock(&report).clone();
+            if report.checked_at == 0 {
+                return api_json(serde_json::json!({
+                    "status": "error", "code": 503,
+                    "msg": "Storage check has not run yet"
+                }));
+            }
+            let mut body = serde_json::to_value(&report).unwrap_or_default();
+            body["status"] = serde_json::json!(if report.alerts.is_empty() {
+                "ok"
+            } else {
+                "warning"
+            });
+            api_json(body)
+        });
+
+    // GET /netstats — gossip propagation delay, per-block arrival spread, time to quorum
+    let netstats_route = warp::path("netstats")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(netstats))
+        .map(|ns: Arc<Mutex<netstats::NetStats>>| {
+            let mut body = safe_lock(&ns).report();
+            body["status"] = serde_json::json!("ok");
+            api_json(body)
+        });
+
+    // POST /migrate-account — client-signed Migrate block (Ed25519 → Dilithium5 sweep).
+    // Applied directly and gossiped as a raw block; every node derives the
+    // destination's MIGRATED: credit record itself (los_core::key_migration).
+    let l_migrate = ledger.clone();
+    let tx_migrate = tx_out.clone();
+    let migrate_route = warp::path("migrate-account")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::json())
+        .and(with_state((l_migrate, tx_migrate)))
+        .then(
+            |block: Block, (l, tx): (Arc<Mutex<Ledger>>, mpsc::Sender<String>)| async move {
+                if block.block_type != BlockType::Migrate {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 400,
+                        "msg": "block_type must be Migrate"
+                    }));
+                }
+                let new_address = block.migration_destination().unwrap_or_default();
+                let (block_hash, credit_hash) = {
+                    let mut l_guard = safe_lock(&l);
+                    match l_guard.process_block(&block) {
+                        Ok(result) => {
+                            let credit = l_guard
+                                .accounts
+                                .get(&new_address)
+                                .map(|a| a.head.clone())
+                                .unwrap_or_default();
+                            (result.into_hash(), credit)
+                        }
+                        Err(e) => {
+                            return api_json(serde_json::json!({"status": "error", "msg": e}))
+                        }
+                    }
+                };
+                let _ = tx
+                    .send(serde_json::to_string(&block).unwrap_or_default())
+                    .await;
+                mark_dirty();
+                println!(
+                    "🔑 Account {} migrated to {} ({} CIL)",
+                    get_short_addr(&block.account),
+                    get_short_addr(&new_address),
+                    block.amount
+                );
+                api_json(serde_json::json!({
+                    "status": "success",
+                    "block_hash": block_hash,
+                    "from": block.account,
+                    "to": new_address,
+                    "amount_cil": block.amount,
+                    "fee_cil": block.fee,
+                    "credit_block_hash": credit_hash
+                }))
+            },
+        );
+
+    // POST /invoice — payment request (los: URI) watched for a matching payment
+    let invoice_book = Arc::new(Mutex::new(invoices::InvoiceBook::load(
+        database.load_invoices().unwrap_or_else(|e| {
+            eprintln!("⚠️  Failed to load invoices: {}", e);
+            Vec::new()
+        }),
+    )));
+    let invoice_create_route = warp::path("invoice")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(16 * 1024))
+        .and(warp::body::json())
+        .and(with_state((invoice_book.clone(), database.clone())))
+        .map(
+            |req: invoices::InvoiceRequest,
+             (book, db): (Arc<Mutex<invoices::InvoiceBook>>, Arc<LosDatabase>)| {
+                let request = match req.payment_request() {
+                    Ok(r) => r,
+                    Err(e) => {
+                        return api_json(serde_json::json!({
+                            "status": "error", "code": 400, "msg": e
+                        }))
+                    }
+                };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let created = safe_lock(&book).create(request, req.expires_in_secs, now);
+                match created {
+                    Ok((invoice, pruned)) => {
+                        for id in pruned {
+                            let _ = db.remove_invoice(&id);
+                        }
+                        if let Err(e) = db.save_invoice(&invoice) {
+                            eprintln!("⚠️  Failed to save invoice {}: {}", invoice.id, e);
+                        }
+                        let mut body = invoice.to_json();
+                        body["status_url"] =
+                            serde_json::json!(format!("/invoice/{}/status", invoice.id));
+                        api_json(serde_json::json!({"status": "success", "invoice": body}))
+                    }
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error", "code": 400, "msg": e
+                    })),
+                }
+            },
+        );
+
+    // GET /invoice/{id}/status — pending | paid | expired
+    let invoice_status_route = warp::path!("invoice" / String / "status")
+        .and(warp::get())
+        .and(with_state((invoice_book, database.clone(), ledger.clone())))
+        .map(
+            |id: String,
+             (book, db, l): (
+                Arc<Mutex<invoices::InvoiceBook>>,
+                Arc<LosDatabase>,
+                Arc<Mutex<Ledger>>,
+            )| {
+                let Some(invoice) = safe_lock(&book).get(&id).cloned() else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404, "msg": "Invoice not found"
+                    }));
+                };
+                let address = &invoice.request.address;
+                let payments = match &invoice.request.contract {
+                    Some(contract) => {
+                        let mut filter = db::EventFilter {
+                            event_type: Some("Transfer".to_string()),
+                            since: invoice.created_at,
+                            ..Default::default()
+                        };
+                        filter.topics[1] = Some(address.clone());
+                        let events = db
+                            .query_contract_events(contract, &filter, 500)
+                            .unwrap_or_default();
+                        invoices::token_payments(&events, address)
+                    }
+                    None => {
+                        invoices::incoming_payments(&safe_lock(&l), address, invoice.created_at)
+                    }
+                };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let updated = safe_lock(&book).update(&id, &payments, now);
+                match updated {
+                    Some((invoice, changed)) => {
+                        if changed {
+                            if let Err(e) = db.save_invoice(&invoice) {
+                                eprintln!("⚠️  Failed to save invoice {}: {}", invoice.id, e);
+                            }
+                        }
+                        api_json(
+                            serde_json::json!({"status": "success", "invoice": invoice.to_json()}),
+                        )
+                    }
+                    None => api_json(serde_json::json!({
+                        "status": "error", "code": 404, "msg": "Invoice not found"
+                    })),
+                }
+            },
+        );
+
+    // POST /telemetry — signed node summary (aggregation mode only)
+    let telemetry_post_route = warp::path("telemetry")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(32 * 1024))
+        .and(warp::body::json())
+        .and(with_state(telemetry_aggregator.clone()))
+        .map(
+            |report: telemetry::TelemetryReport,
+             agg: Option<Arc<Mutex<telemetry::TelemetryAggregator>>>| {
+                let Some(agg) = agg else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": "Telemetry aggregation is disabled (start with --telemetry-aggregate)"
+                    }));
+                };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let accepted = safe_lock(&agg).accept(&report, now);
+                match accepted {
+                    Ok(()) => api_json(serde_json::json!({"status": "success"})),
+                    Err(e) => api_json(serde_json::json!({
+                        "status": "error",
+                        "code": if e == "rate limited" { 429 } else { 400 },
+                        "msg": e
+                    })),
+                }
             },
         );
 
-    // GET /directory/api/active — Active peers only JSON (for app bootstrapping)
-    let ve_dir_active = validator_endpoints.clone();
-    let ab_dir_active = address_book.clone();
-    let l_dir_active = ledger.clone();
-    let my_addr_dir_active = my_address.clone();
-    let directory_api_active_route = warp::path!("directory" / "api" / "active")
-        .and(with_state((ve_dir_active, ab_dir_active, l_dir_active)))
+    // GET /telemetry/network — dashboard of collected reports
+    let telemetry_network_route = warp::path!("telemetry" / "network")
+        .and(warp::get())
+        .and(with_state((telemetry_aggregator, ledger.clone())))
         .map(
-            move |(ve, ab, l): (
-                Arc<Mutex<HashMap<String, String>>>,
-                Arc<Mutex<HashMap<String, String>>>,
+            |(agg, l): (
+                Option<Arc<Mutex<telemetry::TelemetryAggregator>>>,
                 Arc<Mutex<Ledger>>,
             )| {
-                let ab_guard = safe_lock(&ab);
-                let ve_guard = safe_lock(&ve);
-                let _l_guard = safe_lock(&l);
-
-                let network = if los_core::is_mainnet_build() { "mainnet" } else { "testnet" };
-                let mut active_peers: Vec<serde_json::Value> = Vec::new();
-
-                for (addr, host) in ve_guard.iter() {
-                    let in_peers = ab_guard.values().any(|v| v == addr);
-                    let is_self = addr == &my_addr_dir_active;
-                    if !is_self && !in_peers { continue; }
-
-                    let rest_port: u16 = host.rsplit(':').next()
-                        .and_then(|p| p.parse().ok())
-                        .unwrap_or(3030);
-
-                    active_peers.push(serde_json::json!({
-                        "host": if host.contains("://") { host.clone() } else { format!("http://{}", host) },
-                        "address": addr,
-                        "transport": if host.contains(".onion") { "onion" } else { "clearnet" },
-                        "rest_port": rest_port,
+                let Some(agg) = agg else {
+                    return api_json(serde_json::json!({
+                        "status": "error", "code": 404,
+                        "msg": "Telemetry aggregation is disabled (start with --telemetry-aggregate)"
                     }));
-                }
-
-                api_json(serde_json::json!({
-                    "network": network,
-                    "active_count": active_peers.len(),
-                    "peers": active_peers,
-                    "updated_at": chrono::Utc::now().to_rfc3339(),
-                }))
+                };
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let validators: HashSet<String> = safe_lock(&l)
+                    .accounts
+                    .iter()
+                    .filter(|(_, a)| a.is_validator)
+                    .map(|(addr, _)| addr.clone())
+                    .collect();
+                let mut dash = safe_lock(&agg).dashboard(now, |n| validators.contains(n));
+                dash["status"] = serde_json::json!("success");
+                api_json(dash)
             },
         );
 
@@ -3865,8 +6009,10 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         .or(balance_route.boxed())
         .or(supply_route.boxed())
         .or(history_route.boxed())
+        .or(transactions_route.boxed())
         .or(peers_route.boxed())
         .or(send_route.boxed())
+        .or(send_batch_route.boxed())
         .boxed();
 
     let group2 = deploy_route
@@ -3881,13 +6027,15 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         .or(fee_estimate_route.boxed())
         .or(mining_info_route.boxed())
         .or(block_route.boxed())
-        .or(faucet_route.boxed())
         .or(blocks_recent_route.boxed())
+        .or(stats_overview_route.boxed())
+        .or(accounts_top_route.boxed())
         .or(whoami_route.boxed())
         .boxed();
 
     let group4 = account_route
         .boxed()
+        .or(account_next_route.boxed())
         .or(health_route.boxed())
         .or(tor_health_route.boxed())
         .or(slashing_route.boxed())
@@ -3896,14 +6044,41 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         .or(tx_by_hash_route.boxed())
         .or(search_route.boxed())
         .or(sync_full_route.boxed())
+        .or(sync_contracts_route.boxed())
+        .or(sync_progress_route.boxed())
+        .or(dev_fork_route.boxed())
         .or(sync_route.boxed())
         .or(consensus_route.boxed())
         .or(reward_info_route.boxed())
+        .or(reward_fees_route.boxed())
+        .or(reward_claim_info_route.boxed())
+        .or(reward_claim_route.boxed())
+        .or(reclaim_route.boxed())
+        .or(session_key_route.boxed())
+        .or(admin_list_keys_route.boxed())
+        .or(admin_create_key_route.boxed())
+        .or(admin_update_key_route.boxed())
+        .or(admin_revoke_key_route.boxed())
+        .or(admin_list_book_route.boxed())
+        .or(admin_add_book_route.boxed())
+        .or(admin_label_book_route.boxed())
+        .or(admin_remove_book_route.boxed())
         .or(register_validator_route.boxed())
         .or(unregister_validator_route.boxed())
         .or(unregister_validator_underscore_route.boxed())
         .or(network_peers_route.boxed())
         .or(mempool_stats_route.boxed())
+        .or(checkpoint_conflicts_route.boxed())
+        .or(checkpoints_route.boxed())
+        .or(reserves_route.boxed())
+        .or(forks_route.boxed())
+        .or(storage_route.boxed())
+        .or(netstats_route.boxed())
+        .or(migrate_route.boxed())
+        .or(invoice_create_route.boxed())
+        .or(invoice_status_route.boxed())
+        .or(telemetry_post_route.boxed())
+        .or(telemetry_network_route.boxed())
         .or(validator_api::validator_routes().boxed())
         .boxed();
 
@@ -3915,12 +6090,14 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         .or(token_info_route.boxed())
         .boxed();
 
-    // DEX routes
+    // DEX + oracle routes
     let group6 = dex_list_pools_route
         .boxed()
         .or(dex_pool_info_route.boxed())
         .or(dex_quote_route.boxed())
         .or(dex_position_route.boxed())
+        .or(dex_chart_route.boxed())
+        .or(oracle_history_route.boxed())
         .boxed();
 
     // Peer Directory routes (embedded in every validator)
@@ -3941,8 +6118,8 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         .with(warp::log("api"))
         .recover(handle_rejection);
 
-    // Apply rate limiting globally
-    let routes_with_limit = rate_limit_filter.and(routes);
+    // Apply rate limiting (per API key tier, or per IP without a key) globally
+    let routes_with_limit = access_filter.and(routes).recover(handle_rejection);
 
     // ── PoW MINING BACKGROUND THREAD ──────────────────────────────────
     // When --mine is set, spawn background threads that grind SHA3 hashes
@@ -3961,7 +6138,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
         let l_bg = ledger.clone();
         let db_bg = database.clone();
         let pk_bg = node_public_key.clone();
-        let sk_bg = secret_key.clone();
+        let signer_bg = signer.clone();
         let tx_bg = tx_out.clone();
         let my_addr_bg = my_address.clone();
         // Arcs for auto self-registration as validator after first mine
@@ -4089,9 +6266,10 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                             .unwrap_or_default()
                             .as_secs();
 
+                        // Lock order: ledger → mining_state — read the supply first
+                        let remaining = safe_lock(&l_bg).distribution.remaining_supply;
                         let reward_cil = {
                             let mut ms: std::sync::MutexGuard<'_, MiningState> = safe_lock(&ms_bg);
-                            let remaining = safe_lock(&l_bg).distribution.remaining_supply;
                             match ms.verify_proof(&proof, now_secs, remaining) {
                                 Ok(r) => r,
                                 Err(e) => {
@@ -4124,6 +6302,8 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                             work: 0,
                             timestamp: now_secs,
                             fee: 0,
+                            valid_after: 0,
+                            expires_at: 0,
                         };
 
                         // Anti-spam PoW on block
@@ -4131,7 +6311,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
 
                         // Sign block
                         mint_block.signature =
-                            match try_sign_hex(mint_block.signing_hash().as_bytes(), &sk_bg) {
+                            match try_sign_hex(mint_block.signing_hash().as_bytes(), &*signer_bg) {
                                 Ok(sig) => sig,
                                 Err(e) => {
                                     eprintln!("⛏️  Signing failed: {} — skipping", e);
@@ -4144,7 +6324,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                             let mut l = safe_lock(&l_bg);
                             match l.process_block(&mint_block) {
                                 Ok(_) => {
-                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                    mark_dirty();
                                     true
                                 }
                                 Err(e) => {
@@ -4253,9 +6433,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                                     .as_secs();
                                 let reg_message =
                                     format!("REGISTER_VALIDATOR:{}:{}", my_addr_bg, ts);
-                                if let Ok(sig) =
-                                    los_crypto::sign_message(reg_message.as_bytes(), &sk_bg)
-                                {
+                                if let Ok(sig) = signer_bg.sign(reg_message.as_bytes()) {
                                     let reg_msg = serde_json::json!({
                                         "type": "VALIDATOR_REG",
                                         "address": my_addr_bg,
@@ -4268,7 +6446,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                                     });
                                     let _ = tx_bg.send(format!("VALIDATOR_REG:{}", reg_msg)).await;
                                 }
-                                SAVE_DIRTY.store(true, Ordering::Release);
+                                mark_dirty();
                                 let stake_los = safe_lock(&l_bg)
                                     .accounts
                                     .get(&my_addr_bg)
@@ -4298,9 +6476,7 @@ function copyText(text){{navigator.clipboard.writeText(text).then(function(){{va
                                         .as_secs();
                                     let reg_message =
                                         format!("REGISTER_VALIDATOR:{}:{}", my_addr_bg, ts);
-                                    if let Ok(sig) =
-                                        los_crypto::sign_message(reg_message.as_bytes(), &sk_bg)
-                                    {
+                                    if let Ok(sig) = signer_bg.sign(reg_message.as_bytes()) {
                                         let reg_msg = serde_json::json!({
                                             "type": "VALIDATOR_REG",
                                             "address": my_addr_bg,
@@ -4376,6 +6552,27 @@ async fn handle_rejection(
             json,
             warp::http::StatusCode::TOO_MANY_REQUESTS,
         ))
+    } else if let Some(api_access::filters::ApiKeyRateLimited { key_id, tier }) = err.find() {
+        let json = warp::reply::json(&serde_json::json!({
+            "status": "error",
+            "code": 429,
+            "msg": format!("Rate limit exceeded for API key {} ({} tier).", key_id, tier),
+            "key_id": key_id
+        }));
+        Ok(warp::reply::with_status(
+            json,
+            warp::http::StatusCode::TOO_MANY_REQUESTS,
+        ))
+    } else if err.find::<api_access::filters::InvalidApiKey>().is_some() {
+        let json = warp::reply::json(&serde_json::json!({
+            "status": "error",
+            "code": 401,
+            "msg": "Invalid or revoked API key"
+        }));
+        Ok(warp::reply::with_status(
+            json,
+            warp::http::StatusCode::UNAUTHORIZED,
+        ))
     } else if err.is_not_found() {
         let json = warp::reply::json(&serde_json::json!({
             "status": "error",
@@ -4399,6 +6596,27 @@ async fn handle_rejection(
             json,
             warp::http::StatusCode::BAD_REQUEST,
         ))
+    } else if err.find::<warp::reject::PayloadTooLarge>().is_some() {
+        // Above the route's body_limits::BodyLimits::max_bytes
+        let json = warp::reply::json(&serde_json::json!({
+            "status": "error",
+            "code": 413,
+            "msg": "Request body too large for this endpoint"
+        }));
+        Ok(warp::reply::with_status(
+            json,
+            warp::http::StatusCode::PAYLOAD_TOO_LARGE,
+        ))
+    } else if err.find::<warp::reject::LengthRequired>().is_some() {
+        let json = warp::reply::json(&serde_json::json!({
+            "status": "error",
+            "code": 411,
+            "msg": "Content-Length header required"
+        }));
+        Ok(warp::reply::with_status(
+            json,
+            warp::http::StatusCode::LENGTH_REQUIRED,
+        ))
     } else if err.find::<warp::reject::MethodNotAllowed>().is_some() {
         let json = warp::reply::json(&serde_json::json!({
             "status": "error",
@@ -4446,19 +6664,24 @@ async fn handle_rejection(
 //   - State only adopted if <10% of blocks fail validation
 //   - Rate limited: one REST sync attempt per 60 seconds
 //   - Decompression capped at 500MB to prevent decompression bombs
+//
+// Low-bandwidth nodes (--low-bandwidth) do not serve this: SYNC_REQUEST is
+// answered with SYNC_VIA_REST pointing at an archive node from
+// LOS_SYNC_DELEGATES, and GET /sync, /sync/full return 503 with that list.
+
+/// 503 body of the sync endpoints on a low-bandwidth node
+fn sync_delegated_json() -> serde_json::Value {
+    serde_json::json!({
+        "status": "error",
+        "code": 503,
+        "msg": "Low-bandwidth node: bulk sync is served by archive nodes",
+        "delegates": los_network::bandwidth::sync_delegates(),
+    })
+}
 
-/// Perform REST-based state sync from a specific peer.
-/// Returns the number of new blocks merged on success.
-async fn rest_sync_from_peer(
-    peer_host: &str,
-    our_blocks: usize,
-    ledger: &Arc<Mutex<Ledger>>,
-    reward_pool: &Arc<Mutex<ValidatorRewardPool>>,
-    slashing_mgr: &Arc<Mutex<los_consensus::slashing::SlashingManager>>,
-    _database: &Arc<LosDatabase>,
-) -> Result<usize, String> {
-    // Build HTTP client (with SOCKS5 proxy for .onion addresses)
-    let client = if peer_host.contains(".onion") {
+/// HTTP client for peer REST calls (SOCKS5 proxy for .onion addresses)
+fn peer_http_client(peer_host: &str) -> Result<reqwest::Client, String> {
+    if peer_host.contains(".onion") {
         let socks_url = std::env::var("LOS_SOCKS5_PROXY")
             .or_else(|_| std::env::var("LOS_TOR_SOCKS5"))
             .unwrap_or_else(|_| "socks5h://127.0.0.1:9050".to_string());
@@ -4468,15 +6691,37 @@ async fn rest_sync_from_peer(
             .proxy(proxy)
             .timeout(Duration::from_secs(120))
             .build()
-            .map_err(|e| format!("HTTP client error: {}", e))?
+            .map_err(|e| format!("HTTP client error: {}", e))
     } else {
         reqwest::Client::builder()
             .timeout(Duration::from_secs(60))
             .build()
-            .map_err(|e| format!("HTTP client error: {}", e))?
-    };
+            .map_err(|e| format!("HTTP client error: {}", e))
+    }
+}
 
-    let url = format!("http://{}/sync/full?blocks={}", peer_host, our_blocks);
+/// Download a peer's full ledger state via GET /sync/full.
+/// Returns `None` when the peer reports we are already up to date.
+async fn fetch_peer_ledger(peer_host: &str, our_blocks: usize) -> Result<Option<Ledger>, String> {
+    Ok(fetch_peer_snapshot(peer_host, our_blocks)
+        .await?
+        .map(|(ledger, _)| ledger))
+}
+
+/// Like `fetch_peer_ledger`, with the VM and reward pool roots the peer
+/// reported for the same state (None from peers that do not send them).
+async fn fetch_peer_snapshot(
+    peer_host: &str,
+    our_blocks: usize,
+) -> Result<Option<(Ledger, Option<checkpoint_sync::SnapshotRoots>)>, String> {
+    let client = peer_http_client(peer_host)?;
+
+    let url = format!(
+        "http://{}/sync/full?blocks={}&format={}",
+        peer_host,
+        our_blocks,
+        sync_codec::V2_CAPABILITY
+    );
     println!("📡 REST sync: fetching {}", url);
 
     let resp = client
@@ -4496,6 +6741,7 @@ async fn rest_sync_from_peer(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("")
         .to_string();
+    let roots = checkpoint_sync::SnapshotRoots::from_headers(resp.headers());
 
     let body_bytes = resp
         .bytes()
@@ -4504,24 +6750,43 @@ async fn rest_sync_from_peer(
 
     if content_type.contains("application/json") {
         // Peer says we're up-to-date
-        return Ok(0);
+        return Ok(None);
     }
 
-    // Decompress gzip body
-    use flate2::read::GzDecoder;
-    use std::io::Read;
-
+    // v2 peers answer with CONTENT_TYPE_V2; anything else is gzip JSON
+    let format = if content_type.contains(sync_codec::CONTENT_TYPE_V2) {
+        sync_codec::SyncFormat::BincodeZstd
+    } else {
+        sync_codec::SyncFormat::JsonGzip
+    };
     const MAX_DECOMPRESSED: u64 = 500 * 1024 * 1024; // 500 MB max
-    let decoder = GzDecoder::new(&body_bytes[..]);
-    let mut limited = decoder.take(MAX_DECOMPRESSED);
-    let mut json_str = String::new();
-    limited
-        .read_to_string(&mut json_str)
-        .map_err(|e| format!("Decompression failed: {}", e))?;
+    sync_codec::decode(&body_bytes, format, MAX_DECOMPRESSED).map(|l| Some((l, roots)))
+}
 
-    let incoming: Ledger =
-        serde_json::from_str(&json_str).map_err(|e| format!("JSON parse failed: {}", e))?;
+/// Perform REST-based state sync from a specific peer.
+/// Returns the number of new blocks merged on success.
+async fn rest_sync_from_peer(
+    peer_host: &str,
+    our_blocks: usize,
+    ledger: &Arc<Mutex<Ledger>>,
+    reward_pool: &Arc<Mutex<ValidatorRewardPool>>,
+    slashing_mgr: &Arc<Mutex<los_consensus::slashing::SlashingManager>>,
+    _database: &Arc<LosDatabase>,
+) -> Result<usize, String> {
+    match fetch_peer_ledger(peer_host, our_blocks).await? {
+        Some(incoming) => merge_synced_ledger(incoming, ledger, reward_pool, slashing_mgr),
+        None => Ok(0),
+    }
+}
 
+/// Merge a peer's ledger snapshot into ours (same fast-path as SYNC_GZIP).
+/// Returns the number of new blocks merged.
+fn merge_synced_ledger(
+    incoming: Ledger,
+    ledger: &Arc<Mutex<Ledger>>,
+    reward_pool: &Arc<Mutex<ValidatorRewardPool>>,
+    slashing_mgr: &Arc<Mutex<los_consensus::slashing::SlashingManager>>,
+) -> Result<usize, String> {
     // Compare state roots — skip if identical
     let incoming_root = incoming.compute_state_root();
     let our_root = {
@@ -4533,14 +6798,9 @@ async fn rest_sync_from_peer(
         return Ok(0);
     }
 
-    // Validate incoming blocks cryptographically
+    // Validate incoming blocks cryptographically (verification pool)
     let incoming_block_count = incoming.blocks.len();
-    let mut crypto_invalid = 0usize;
-    for blk in incoming.blocks.values() {
-        if !blk.verify_pow() || !blk.verify_signature() {
-            crypto_invalid += 1;
-        }
-    }
+    let crypto_invalid = verify_pool::global().count_invalid(&incoming.blocks);
 
     let max_invalid = (incoming_block_count / 10).max(3);
     if crypto_invalid > max_invalid {
@@ -4579,65 +6839,373 @@ async fn rest_sync_from_peer(
             l.distribution = incoming.distribution.clone();
         }
 
-        // Merge claimed sends
-        for claimed in &incoming.claimed_sends {
-            l.claimed_sends.insert(claimed.clone());
-        }
+        // Merge claimed sends
+        for claimed in &incoming.claimed_sends {
+            l.claimed_sends.insert(claimed.clone());
+        }
+
+        // Adopt accumulated fees
+        if incoming.accumulated_fees_cil > l.accumulated_fees_cil {
+            l.accumulated_fees_cil = incoming.accumulated_fees_cil;
+        }
+
+        // Sanitize: remove orphaned blocks after merging
+        let orphans = l.remove_orphaned_blocks();
+        // Gas budgets follow the merged ContractCall blocks
+        l.rebuild_gas_usage();
+        // Claimed-reward totals follow the merged Claim blocks
+        l.rebuild_reward_claims();
+        // Session grants follow the merged Change/ContractCall blocks
+        l.rebuild_session_keys();
+        if orphans > 0 {
+            println!("🧹 REST sync: removed {} orphaned block(s)", orphans);
+        }
+
+        mark_dirty();
+    }
+
+    // Sync reward pool for reward/fee blocks
+    for blk in incoming.blocks.values() {
+        if blk.block_type == BlockType::Mint
+            && (blk.link.starts_with("REWARD:EPOCH:") || blk.link.starts_with("FEE_REWARD:EPOCH:"))
+        {
+            let mut pool = safe_lock(reward_pool);
+            if pool.sync_reward_mint(blk) {
+                log_fee_only_transition(&pool);
+            }
+        }
+    }
+
+    // Update slashing participation
+    {
+        let l = safe_lock(ledger);
+        let mut sm = safe_lock(slashing_mgr);
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        for (addr, acc) in &l.accounts {
+            if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
+                if sm.get_profile(addr).is_none() {
+                    sm.register_validator(addr.clone());
+                }
+                let _ = sm.record_block_participation(addr, l.blocks.len() as u64, timestamp);
+            }
+        }
+    }
+
+    if crypto_invalid > 0 {
+        println!(
+            "⚠️ REST sync: {} blocks failed crypto validation (skipped)",
+            crypto_invalid
+        );
+    }
+
+    Ok(added_count)
+}
+
+/// Apply one block received through sync (SYNC_GZIP slow path, delta sync)
+/// with process_block(). Ok(true) = applied, Ok(false) = already known.
+/// `verified` is the verification pool's result for `blk`, if it passed.
+fn apply_synced_block(
+    l: &mut Ledger,
+    blk: &Block,
+    verified: Option<los_core::CryptoVerified<'_>>,
+    reward_pool: &Arc<Mutex<ValidatorRewardPool>>,
+    slashing_mgr: &Arc<Mutex<los_consensus::slashing::SlashingManager>>,
+) -> Result<bool, String> {
+    // Accept Mint/Slash blocks in SYNC if validly signed
+    // by a staked validator. Blanket-reject caused new nodes to
+    // permanently miss all minted balances.
+    if verified.is_none() && matches!(blk.block_type, BlockType::Mint | BlockType::Slash) {
+        let sig_ok = hex::decode(&blk.signature)
+            .ok()
+            .and_then(|sig| {
+                hex::decode(&blk.public_key).ok().map(|pk| {
+                    let sh = blk.signing_hash();
+                    los_crypto::verify_signature(sh.as_bytes(), &sig, &pk)
+                })
+            })
+            .unwrap_or(false);
+        if !sig_ok || !blk.verify_pow() {
+            return Err("invalid Mint/Slash signature or PoW".to_string());
+        }
+    }
+
+    let hash = blk.calculate_hash();
+    if l.blocks.contains_key(&hash) {
+        return Ok(false);
+    }
+
+    if !l.accounts.contains_key(&blk.account) {
+        l.accounts.insert(
+            blk.account.clone(),
+            AccountState {
+                head: "0".to_string(),
+                balance: 0,
+                block_count: 0,
+                is_validator: false,
+            },
+        );
+    }
+
+    // FEE_REWARD supply handling is in process_block() itself:
+    // it skips the remaining_supply deduction for FEE_REWARD: Mint blocks.
+    // Blocks checked by the verification pool skip the PoW/signature re-check.
+    match verified {
+        Some(v) => l.process_verified_block(v)?,
+        None => l.process_block(blk)?,
+    };
+
+    // Sync reward pool when receiving
+    // REWARD:EPOCH or FEE_REWARD:EPOCH Mint blocks from leader.
+    // This keeps non-leader pool stats consistent.
+    if blk.block_type == BlockType::Mint
+        && (blk.link.starts_with("REWARD:EPOCH:") || blk.link.starts_with("FEE_REWARD:EPOCH:"))
+    {
+        let mut pool = safe_lock(reward_pool);
+        if pool.sync_reward_mint(blk) {
+            log_fee_only_transition(&pool);
+        }
+    }
+    // SLASHING: Record participation during sync
+    if let Some(acc) = l.accounts.get(&blk.account) {
+        if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
+            let mut sm = safe_lock(slashing_mgr);
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            if sm.get_profile(&blk.account).is_none() {
+                sm.register_validator(blk.account.clone());
+            }
+            let _ = sm.record_block_participation(&blk.account, l.blocks.len() as u64, timestamp);
+        }
+    }
+    Ok(true)
+}
+
+/// Carry out a delta sync step: send the next request, retry the deferred
+/// blocks of a finished session, or fall back to a full SYNC_REQUEST.
+#[allow(clippy::too_many_arguments)]
+async fn run_delta_step(
+    step: delta_sync::DeltaStep,
+    ds: &Arc<Mutex<delta_sync::DeltaSync>>,
+    ledger: &Arc<Mutex<Ledger>>,
+    reward_pool: &Arc<Mutex<ValidatorRewardPool>>,
+    slashing_mgr: &Arc<Mutex<los_consensus::slashing::SlashingManager>>,
+    db: &LosDatabase,
+    tx_out: &mpsc::Sender<String>,
+    my_address: &str,
+) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    match step {
+        delta_sync::DeltaStep::Idle => {}
+        delta_sync::DeltaStep::Send(msg) => {
+            let _ = tx_out.send(msg).await;
+        }
+        delta_sync::DeltaStep::Finish(mut deferred) => {
+            // Two passes: a Receive may need a Send deferred after it
+            deferred.sort_by_key(|b| b.timestamp);
+            let checked = verify_pool::global().verify(&deferred);
+            let (applied, rejected) = {
+                let mut l = safe_lock(ledger);
+                let mut pending = vec![true; deferred.len()];
+                let mut applied = 0u64;
+                for _pass in 0..2 {
+                    for (i, blk) in deferred.iter().enumerate() {
+                        if !pending[i] {
+                            continue;
+                        }
+                        if let Ok(new) =
+                            apply_synced_block(&mut l, blk, checked[i], reward_pool, slashing_mgr)
+                        {
+                            applied += new as u64;
+                            pending[i] = false;
+                        }
+                    }
+                }
+                (applied, pending.iter().filter(|p| **p).count() as u64)
+            };
+            if applied > 0 {
+                mark_dirty();
+            }
+            let mut d = safe_lock(ds);
+            d.finish(applied, rejected, now);
+            let _ = db.clear_delta_sync();
+            if let Some(last) = d.last() {
+                println!(
+                    "📚 Delta sync complete: {} chains, {} blocks applied, {} rejected ({}s)",
+                    last.chains_fetched,
+                    last.blocks_applied,
+                    last.blocks_rejected,
+                    last.duration_secs
+                );
+            }
+        }
+        delta_sync::DeltaStep::FullSync => {
+            let _ = db.clear_delta_sync();
+            let block_count = safe_lock(ledger).blocks.len();
+            println!("⚠️ Delta sync failed — falling back to full state sync");
+            let _ = tx_out
+                .send(sync_codec::sync_request(&my_address, block_count))
+                .await;
+        }
+    }
+}
+
+// --- UTILS & FORMATTING ---
+
+/// Load the latest finalized checkpoint timestamps into the consensus clock
+/// (median-time floor for block timestamp validation).
+fn refresh_consensus_clock(cm: &CheckpointManager) {
+    let latest = cm.latest_finalized_height();
+    let times: Vec<u64> = (0..los_core::consensus_clock::CHECKPOINT_WINDOW as u64)
+        .filter_map(|i| latest.checked_sub(i * CHECKPOINT_INTERVAL))
+        .filter(|h| *h > 0)
+        .filter_map(|h| cm.get_checkpoint(h).ok().flatten())
+        .map(|cp| cp.timestamp)
+        .collect();
+    los_core::consensus_clock::set_checkpoint_times(times);
+}
+
+/// Log the switch to fee-only validator rewards once the emission pool is spent
+fn log_fee_only_transition(pool: &ValidatorRewardPool) {
+    if let Some(epoch) = pool.fee_only_since_epoch {
+        println!(
+            "🏁 Validator reward pool exhausted — fee-only rewards from epoch {}",
+            epoch
+        );
+    }
+}
+
+/// Mint the payouts of a quorum-certified epoch distribution: one Mint block
+/// per recipient, the certificate in the link (see los_core::reward_cosign).
+/// Returns the applied blocks as JSON for gossip.
+fn mint_certified_distribution(
+    cert: &DistributionCertificate,
+    ledger: &Mutex<Ledger>,
+    reward_pool: &Mutex<ValidatorRewardPool>,
+    public_key: &[u8],
+    signer: &dyn Signer,
+) -> Vec<String> {
+    let dist = &cert.distribution;
+    let link = cert.link();
+    let now_ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
 
-        // Adopt accumulated fees
-        if incoming.accumulated_fees_cil > l.accumulated_fees_cil {
-            l.accumulated_fees_cil = incoming.accumulated_fees_cil;
-        }
+    // Collect account heads (brief ledger lock)
+    let templates: Vec<Block> = {
+        let l = safe_lock(ledger);
+        dist.payouts
+            .iter()
+            .map(|(addr, amount)| Block {
+                block_type: BlockType::Mint,
+                account: addr.clone(),
+                previous: l
+                    .accounts
+                    .get(addr)
+                    .map(|a| a.head.clone())
+                    .unwrap_or_else(|| "0".to_string()),
+                link: link.clone(),
+                amount: *amount,
+                fee: 0,
+                valid_after: 0,
+                expires_at: 0,
+                timestamp: now_ts,
+                public_key: hex::encode(public_key),
+                signature: String::new(),
+                work: 0,
+            })
+            .collect()
+    };
 
-        // Sanitize: remove orphaned blocks after merging
-        let orphans = l.remove_orphaned_blocks();
-        if orphans > 0 {
-            println!("🧹 REST sync: removed {} orphaned block(s)", orphans);
+    // PoW + signing (NO LOCKS HELD — CPU intensive)
+    let mut signed: Vec<Block> = Vec::new();
+    for mut blk in templates {
+        compute_pow_inline(&mut blk, 0);
+        match try_sign_hex(blk.signing_hash().as_bytes(), signer) {
+            Ok(sig) => {
+                blk.signature = sig;
+                signed.push(blk);
+            }
+            Err(e) => eprintln!(
+                "❌ Failed to sign distribution block for {}: {}",
+                get_short_addr(&blk.account),
+                e
+            ),
         }
-
-        SAVE_DIRTY.store(true, Ordering::Release);
     }
 
-    // Sync reward pool for reward/fee blocks
-    for blk in incoming.blocks.values() {
-        if blk.block_type == BlockType::Mint
-            && (blk.link.starts_with("REWARD:EPOCH:") || blk.link.starts_with("FEE_REWARD:EPOCH:"))
+    // Apply (ledger lock, fast)
+    let mut l = safe_lock(ledger);
+    let mut gossip = Vec::new();
+    let mut total_credited: u128 = 0;
+    for blk in &signed {
+        // Skip if another block landed on the account during signing
+        if l.accounts
+            .get(&blk.account)
+            .is_some_and(|a| a.head != blk.previous)
         {
-            let mut pool = safe_lock(reward_pool);
-            pool.sync_reward_from_gossip(&blk.account, blk.amount);
+            eprintln!(
+                "⚠️ Distribution block stale for {} (head changed) — skipped",
+                get_short_addr(&blk.account)
+            );
+            continue;
         }
-    }
-
-    // Update slashing participation
-    {
-        let l = safe_lock(ledger);
-        let mut sm = safe_lock(slashing_mgr);
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        for (addr, acc) in &l.accounts {
-            if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
-                if sm.get_profile(addr).is_none() {
-                    sm.register_validator(addr.clone());
+        match l.process_block(blk) {
+            Ok(result) => {
+                let hash = result.into_hash();
+                total_credited += blk.amount;
+                gossip.push(serde_json::to_string(blk).unwrap_or_default());
+                match dist.kind {
+                    DistributionKind::Reward => println!(
+                        "💰 Reward Mint: {} → {} LOS (block: {})",
+                        get_short_addr(&blk.account),
+                        blk.amount / CIL_PER_LOS,
+                        &hash[..12]
+                    ),
+                    DistributionKind::Fee => {
+                        safe_lock(reward_pool).record_fee_reward(blk);
+                        println!(
+                            "💸 Fee Reward: {} → {} CIL (block: {})",
+                            get_short_addr(&blk.account),
+                            blk.amount,
+                            &hash[..12]
+                        );
+                    }
                 }
-                let _ = sm.record_block_participation(addr, l.blocks.len() as u64, timestamp);
             }
+            Err(e) => eprintln!(
+                "❌ Distribution block failed for {}: {}",
+                get_short_addr(&blk.account),
+                e
+            ),
         }
     }
-
-    if crypto_invalid > 0 {
+    if total_credited > 0 {
+        if dist.kind == DistributionKind::Fee {
+            l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_sub(total_credited);
+        }
+        mark_dirty();
         println!(
-            "⚠️ REST sync: {} blocks failed crypto validation (skipped)",
-            crypto_invalid
+            "🏆 Epoch {} {:?} distribution: {} CIL ({} LOS) to {} validators, {} co-signers",
+            dist.epoch,
+            dist.kind,
+            total_credited,
+            total_credited / CIL_PER_LOS,
+            gossip.len(),
+            cert.signatures.len()
         );
     }
-
-    Ok(added_count)
+    gossip
 }
 
-// --- UTILS & FORMATTING ---
-
 fn get_short_addr(full_addr: &str) -> String {
     if full_addr.len() < 12 {
         return full_addr.to_string();
@@ -4646,14 +7214,170 @@ fn get_short_addr(full_addr: &str) -> String {
     format!("los_{}", &full_addr[3..11])
 }
 
+/// Public key of an account, taken from its head block (None for unknown accounts)
+/// Node-signed Receive for the recipient of an immediately finalized Send
+/// (functional testnet). Applied by direct ledger manipulation, bypassing
+/// process_block(), because the node's public key does not match the
+/// recipient's address.
+fn create_auto_receive(
+    l: &mut Ledger,
+    target: &str,
+    amount: u128,
+    send_hash: &str,
+    node_pk: &[u8],
+    key: &dyn Signer,
+) -> Result<Block, String> {
+    let head = l
+        .accounts
+        .entry(target.to_string())
+        .or_insert_with(|| AccountState {
+            head: "0".to_string(),
+            balance: 0,
+            block_count: 0,
+            is_validator: false,
+        })
+        .head
+        .clone();
+    let mut recv_blk = Block {
+        account: target.to_string(),
+        previous: head,
+        block_type: BlockType::Receive,
+        amount,
+        link: send_hash.to_string(),
+        signature: "".to_string(),
+        public_key: hex::encode(node_pk),
+        work: 0,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        fee: 0,
+        valid_after: 0,
+        expires_at: 0,
+    };
+    solve_pow(&mut recv_blk);
+    recv_blk.signature = try_sign_hex(recv_blk.signing_hash().as_bytes(), key)?;
+    let recv_hash = recv_blk.calculate_hash();
+    if let Some(recv_acct) = l.accounts.get_mut(target) {
+        recv_acct.balance = recv_acct.balance.saturating_add(amount);
+        recv_acct.head = recv_hash.clone();
+        recv_acct.block_count += 1;
+    }
+    l.blocks.insert(recv_hash, recv_blk.clone());
+    // Track claimed Send for double-receive prevention.
+    // Direct ledger manipulation bypasses process_block() which normally
+    // inserts into claimed_sends. Without this, a second Receive referencing
+    // the same Send could pass the claimed_sends check in process_block().
+    l.claimed_sends.insert(send_hash.to_string());
+    mark_dirty();
+    println!(
+        "✅ Auto-Receive created for {} ({} CIL)",
+        get_short_addr(target),
+        amount
+    );
+    Ok(recv_blk)
+}
+
+fn account_public_key(ledger: &Ledger, address: &str) -> Option<Vec<u8>> {
+    ledger
+        .accounts
+        .get(address)
+        .and_then(|acc| ledger.blocks.get(&acc.head))
+        .and_then(|blk| hex::decode(&blk.public_key).ok())
+}
+
+/// Key of a staked validator (Ledger::is_staked_validator); None for any
+/// other account. Checkpoint signatures only count from these.
+fn validator_public_key(ledger: &Ledger, address: &str) -> Option<Vec<u8>> {
+    if !ledger.is_staked_validator(address) {
+        return None;
+    }
+    account_public_key(ledger, address)
+}
+
+/// Our tip and full state root for checkpoint comparison.
+/// Lock order: ledger → reward pool (VM has its own locks).
+fn local_checkpoint_state(
+    ledger: &Mutex<Ledger>,
+    vm: &WasmEngine,
+    reward_pool: &Mutex<ValidatorRewardPool>,
+) -> Option<quarantine::LocalState> {
+    let l = safe_lock(ledger);
+    let pool = safe_lock(reward_pool);
+    match quarantine::full_root(&l, vm, &pool) {
+        Ok(root) => Some(quarantine::LocalState {
+            tip: quarantine::latest_block_hash(&l),
+            root,
+        }),
+        Err(e) => {
+            eprintln!("⚠️ State root unavailable: {}", e);
+            None
+        }
+    }
+}
+
+/// Compare a checkpoint with our state, counting and alerting on divergence.
+/// Resync requests are sent by the quarantine task.
+#[allow(clippy::too_many_arguments)]
+fn check_checkpoint_root(
+    cp: &FinalityCheckpoint,
+    proposer: Option<&str>,
+    ledger: &Mutex<Ledger>,
+    vm: &WasmEngine,
+    reward_pool: &Mutex<ValidatorRewardPool>,
+    state_quarantine: &Mutex<quarantine::StateQuarantine>,
+    metrics: &LosMetrics,
+) -> quarantine::Verdict {
+    let Some(local) = local_checkpoint_state(ledger, vm, reward_pool) else {
+        return quarantine::Verdict::NotComparable;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // Only staked validators count as diverging proposers (quarantine.rs)
+    let validators = proposer.map(|_| safe_lock(ledger).reward_validator_set());
+    let mut q = safe_lock(state_quarantine);
+    let verdict = match (proposer, &validators) {
+        (Some(p), Some(set)) => q.check_proposal(cp, &local, p, set, now),
+        _ => q.check(cp, &local, None, now),
+    };
+    let source = proposer.map_or("finalized".to_string(), get_short_addr);
+    match verdict {
+        quarantine::Verdict::Diverged { entered } => {
+            metrics.state_root_mismatches_total.inc();
+            eprintln!(
+                "⚠️ State root DIVERGED at height {} ({}): same tip {}, ours={} theirs={}",
+                cp.height,
+                source,
+                &cp.block_hash[..cp.block_hash.len().min(16)],
+                &local.root[..16],
+                &cp.state_root[..cp.state_root.len().min(16)]
+            );
+            if entered {
+                eprintln!(
+                    "🚨 QUARANTINED: local state diverged from the network — voting and checkpoint signing stopped, resyncing"
+                );
+            }
+        }
+        quarantine::Verdict::NotComparable => {
+            println!(
+                "⚠️ Checkpoint at height {} not comparable ({}): tip {} differs from ours",
+                cp.height,
+                source,
+                &cp.block_hash[..cp.block_hash.len().min(16)]
+            );
+        }
+        quarantine::Verdict::Agree => {}
+    }
+    metrics.state_quarantined.set(q.is_quarantined() as i64);
+    verdict
+}
+
 /// Format CIL balance as precise LOS string
 /// Prevents integer division hiding sub-LOS amounts (e.g., 0.5 LOS → "0" with integer division)
 fn format_balance_precise(cil_amount: u128) -> String {
-    format!(
-        "{}.{:011}",
-        cil_amount / CIL_PER_LOS,
-        cil_amount % CIL_PER_LOS
-    )
+    los_core::units::cil_to_los_string(cil_amount)
 }
 
 fn format_u128(n: u128) -> String {
@@ -4709,10 +7433,19 @@ fn save_to_disk_internal(ledger: &Ledger, db: &LosDatabase, force: bool) {
         SAVE_IN_PROGRESS.store(true, Ordering::SeqCst);
     }
 
-    if let Err(e) = db.save_ledger(ledger) {
-        eprintln!("❌ Database save failed: {}", e);
-        // Fallback to JSON backup
-        save_to_disk_legacy(ledger);
+    match db.save_ledger(ledger) {
+        Ok(()) => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            LAST_SAVE_SECS.store(now, Ordering::Release);
+        }
+        Err(e) => {
+            eprintln!("❌ Database save failed: {}", e);
+            // Fallback to JSON backup
+            save_to_disk_legacy(ledger);
+        }
     }
 
     SAVE_IN_PROGRESS.store(false, Ordering::SeqCst);
@@ -4886,6 +7619,24 @@ fn print_history_table(blocks: Vec<&Block>) {
                 format!("-{}", amt_str),
                 format!("Contract: {}", &b.link[..16.min(b.link.len())]),
             ),
+            BlockType::Migrate => (
+                "🔑 MIGRATE",
+                format!("-{}", amt_str),
+                format!(
+                    "To: {}",
+                    get_short_addr(&b.migration_destination().unwrap_or_default())
+                ),
+            ),
+            BlockType::Claim => (
+                "🏆 CLAIM",
+                format!("+{}", amt_str),
+                "Accrued rewards".to_string(),
+            ),
+            BlockType::Reclaim => (
+                "↩️ RECLAIM",
+                format!("+{}", amt_str),
+                format!("Expired Send: {}", &b.link[..8.min(b.link.len())]),
+            ),
         };
 
         let hash_short = if b.calculate_hash().len() > 8 {
@@ -4915,6 +7666,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
 
+    // Offline subcommand: los-node verify ...
+    if args.get(1).map(String::as_str) == Some("verify") {
+        std::process::exit(chain_verify::run(&args[2..]));
+    }
+    // Offline subcommand: los-node resolve-checkpoint ...
+    if args.get(1).map(String::as_str) == Some("resolve-checkpoint") {
+        std::process::exit(checkpoint_conflict::run(&args[2..]));
+    }
+    // Remote signing daemon: los-node signer
+    if args.get(1).map(String::as_str) == Some("signer") {
+        return signer::run_daemon().await;
+    }
+
     // Extended CLI arguments for Flutter Validator launcher
     let mut api_port: u16 = 3030;
     let mut data_dir_override: Option<String> = None;
@@ -4923,6 +7687,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut mainnet_flag = false; // Runtime --mainnet flag
     let mut enable_mining = false; // --mine: enable background PoW mining
     let mut mining_threads: usize = 1; // --mine-threads N: parallel mining threads
+    let mut stratum_port: Option<u16> = std::env::var("LOS_STRATUM_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok()); // --stratum-port N: external miner job server
+    let mut trust_checkpoint: Option<String> = checkpoint_sync::trusted_checkpoint_from_env(); // --trust-checkpoint <id>: cold sync anchor
+    let mut dev_flag = false; // --dev: local single-node dev chain
+    let mut compact_db_flag = false; // --compact-db: compact sled before opening it
+    let mut archive_flag = archive::enabled_by_env(); // --archive: keep historical snapshots
+    let mut telemetry_flag = false; // --telemetry: publish signed node summaries
+    let mut telemetry_aggregate_flag = false; // --telemetry-aggregate: collect them, serve a dashboard
+    let mut rotate_network_key = false; // --rotate-network-key: new libp2p identity (PeerId)
+    let mut low_bandwidth_flag = los_network::bandwidth::enabled_by_env(); // --low-bandwidth: home validator profile
 
     {
         let mut i = 1;
@@ -4931,6 +7706,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "--mainnet" => {
                     mainnet_flag = true;
                 }
+                "--dev" => {
+                    dev_flag = true;
+                }
                 "--port" => {
                     if let Some(v) = args.get(i + 1) {
                         match v.parse::<u16>() {
@@ -4955,6 +7733,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         i += 1;
                     }
                 }
+                "--compact-db" => {
+                    compact_db_flag = true;
+                }
+                "--archive" => {
+                    archive_flag = true;
+                }
+                "--telemetry" => {
+                    telemetry_flag = true;
+                }
+                "--telemetry-aggregate" => {
+                    telemetry_aggregate_flag = true;
+                }
+                "--rotate-network-key" => {
+                    rotate_network_key = true;
+                }
+                "--low-bandwidth" => {
+                    low_bandwidth_flag = true;
+                }
                 "--json-log" => {
                     json_log = true;
                 }
@@ -4992,6 +7788,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         i += 1;
                     }
                 }
+                "--stratum-port" => {
+                    if let Some(v) = args.get(i + 1) {
+                        match v.parse::<u16>() {
+                            Ok(p) => stratum_port = Some(p),
+                            Err(_) => eprintln!("⚠️  Invalid --stratum-port value '{}'", v),
+                        }
+                        i += 1;
+                    }
+                }
+                "--trust-checkpoint" => {
+                    if let Some(v) = args.get(i + 1) {
+                        trust_checkpoint = checkpoint_sync::normalize_checkpoint_id(v);
+                        i += 1;
+                    }
+                }
                 _ => {
                     // Legacy: bare port number as first arg
                     if i == 1 {
@@ -5007,24 +7818,82 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // ── MAINNET / TESTNET SAFETY GATE ──────────────────────────────────
     // Prevent accidental mismatches between binary build and runtime flag.
-    if mainnet_flag && !los_core::is_mainnet_build() {
-        eprintln!(
-            "❌ FATAL: --mainnet flag passed but binary was NOT compiled with --features mainnet"
-        );
-        eprintln!("   Rebuild with: cargo build --release -p los-node --features mainnet");
-        std::process::exit(1);
-    }
+    // A mainnet-only build (--features mainnet) still requires --mainnet;
+    // a default build selects the network at runtime.
     if los_core::is_mainnet_build() && !mainnet_flag {
         eprintln!("❌ FATAL: Binary was compiled for MAINNET but --mainnet flag is missing");
         eprintln!("   Run with: los-node --mainnet --port <PORT> ...");
         eprintln!("   This safety check prevents accidental mainnet deployment.");
         std::process::exit(1);
     }
-    if los_core::is_mainnet_build() {
+    // Install the chain spec before anything reads chain_id, epochs or policy.
+    // Mainnet parameters are embedded; a genesis file can only restate them.
+    match genesis::load_chain_spec(mainnet_flag)
+        .and_then(|spec| los_core::chain_spec::install(spec.clone()).map(|_| spec))
+    {
+        Ok(spec) => {
+            if !spec.allow_test_stubs {
+                los_vm::disable_test_stubs();
+            }
+            println!(
+                "⛓️  Chain spec: {} (chain_id {})",
+                spec.network, spec.chain_id
+            );
+        }
+        Err(e) => {
+            eprintln!("❌ FATAL: {}", e);
+            std::process::exit(1);
+        }
+    }
+    if dev_flag {
+        if los_core::is_mainnet() {
+            eprintln!("❌ FATAL: --dev is not available on mainnet builds");
+            std::process::exit(1);
+        }
+        // Must run before anything reads the testnet config
+        testnet_config::enable_dev_mode();
+        println!("═══════════════════════════════════════════════════════");
+        println!("  🧪 UNAUTHORITY DEV CHAIN (single node, local only)  ");
+        println!("  Instant finalization | Mining difficulty: 0         ");
+        println!("  Dev mnemonic is PUBLIC — never use it for real funds");
+        println!("═══════════════════════════════════════════════════════");
+    }
+    if archive_flag {
+        archive::enable();
+        println!(
+            "🗄️  Archive mode: state snapshot every {} blocks",
+            CHECKPOINT_INTERVAL
+        );
+    }
+    if low_bandwidth_flag {
+        los_network::bandwidth::enable();
+        let delegates = los_network::bandwidth::sync_delegates();
+        println!(
+            "🐢 Low-bandwidth mode: bulk sync not served{}",
+            if delegates.is_empty() {
+                String::new()
+            } else {
+                format!(" (delegated to {})", delegates.join(", "))
+            }
+        );
+    }
+    let telemetry_cfg =
+        telemetry::TelemetryConfig::from_env(telemetry_flag, telemetry_aggregate_flag);
+    if telemetry_cfg.publish {
+        println!(
+            "📊 Telemetry: publishing signed summary every {}s via {}",
+            telemetry_cfg.interval_secs,
+            telemetry_cfg.url.as_deref().unwrap_or("gossip")
+        );
+    }
+    if telemetry_cfg.aggregate {
+        println!("📊 Telemetry aggregation: GET /telemetry/network");
+    }
+    if los_core::is_mainnet() {
         println!("═══════════════════════════════════════════════════════");
         println!(
             "  🔒 UNAUTHORITY MAINNET (Chain ID: {})              ",
-            los_core::CHAIN_ID
+            los_core::chain_id()
         );
         println!("  All security enforced: consensus, signatures, PoW  ");
         println!("  Faucet: DISABLED | Mint Cap: ENFORCED             ");
@@ -5062,7 +7931,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // MAINNET: Validators are identified by their public key/address, not port.
     let node_id = node_id_override.unwrap_or_else(|| {
         std::env::var("LOS_NODE_ID").unwrap_or_else(|_| {
-            if los_core::is_testnet_build() {
+            if dev_flag {
+                format!("dev-{}", api_port)
+            } else if los_core::is_testnet() {
                 match api_port {
                     3030 => "validator-1".to_string(),
                     3031 => "validator-2".to_string(),
@@ -5086,6 +7957,53 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let db_path = format!("{}/los_database", base_data_dir);
     std::fs::create_dir_all(&base_data_dir)?;
 
+    // P2P identity: persisted Ed25519 key, separate from the Dilithium5 validator key
+    let network_key = {
+        let dir = std::path::Path::new(&base_data_dir);
+        let loaded = if rotate_network_key {
+            los_network::node_identity::rotate(dir).map(|(key, old)| {
+                if let Some(old) = old {
+                    println!("🔄 Network key rotated (previous PeerId {})", old);
+                }
+                key
+            })
+        } else {
+            los_network::node_identity::load_or_create(dir).map(|(key, created)| {
+                if created {
+                    println!("🔑 Generated network key ({}/network_key)", base_data_dir);
+                }
+                key
+            })
+        };
+        match loaded {
+            Ok(key) => key,
+            Err(e) => {
+                eprintln!("❌ FATAL: {}", e);
+                eprintln!("   Restore the file or start once with --rotate-network-key");
+                std::process::exit(1);
+            }
+        }
+    };
+    let network_peer_id = los_network::node_identity::peer_id(&network_key);
+    println!("🌐 Network PeerId: {}", network_peer_id);
+
+    // Scheduled (.compact-pending) or --compact-db compaction — only possible
+    // while the database is closed
+    let last_compaction = match storage::compact_if_requested(
+        std::path::Path::new(&base_data_dir),
+        std::path::Path::new(&db_path),
+        compact_db_flag,
+    ) {
+        Ok(done) => done,
+        Err(e) => {
+            eprintln!(
+                "⚠️ Database compaction failed (continuing uncompacted): {}",
+                e
+            );
+            None
+        }
+    };
+
     let database = match LosDatabase::open(&db_path) {
         Ok(db) => {
             let stats = db.stats();
@@ -5159,123 +8077,133 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    // Use node-specific wallet file path
-    // SECURITY: Wallet keys are encrypted at rest using age encryption.
-    // The encryption password is derived from the node ID (for automated startup).
-    // MAINNET: operators MUST set LOS_WALLET_PASSWORD — weak auto-key is rejected.
-    let wallet_path = format!("{}/wallet.json", &base_data_dir);
-    let wallet_password =
-        match stdin_wallet_pw.or_else(|| std::env::var("LOS_WALLET_PASSWORD").ok()) {
-            Some(pw) if pw.len() >= 12 => pw,
-            Some(pw) if !pw.is_empty() => {
-                if los_core::is_mainnet_build() {
-                    eprintln!(
+    // Validator key: with LOS_REMOTE_SIGNER it stays on a signing host and the
+    // node only learns the public key; otherwise it is loaded into a LocalSigner.
+    let signer: SharedSigner = if let Some(remote) = signer::remote_from_env() {
+        let remote = remote.map_err(Box::<dyn std::error::Error>::from)?;
+        println!("🔏 Validator key held by {}", remote.describe());
+        Arc::new(remote)
+    } else {
+        // Use node-specific wallet file path
+        // SECURITY: Wallet keys are encrypted at rest using age encryption.
+        // The encryption password is derived from the node ID (for automated startup).
+        // MAINNET: operators MUST set LOS_WALLET_PASSWORD — weak auto-key is rejected.
+        let wallet_path = format!("{}/wallet.json", &base_data_dir);
+        let wallet_password =
+            match stdin_wallet_pw.or_else(|| std::env::var("LOS_WALLET_PASSWORD").ok()) {
+                Some(pw) if pw.len() >= 12 => pw,
+                Some(pw) if !pw.is_empty() => {
+                    if los_core::is_mainnet() {
+                        eprintln!(
                         "❌ FATAL: LOS_WALLET_PASSWORD must be at least 12 characters on mainnet."
                     );
-                    return Err(Box::<dyn std::error::Error>::from(
-                        "LOS_WALLET_PASSWORD too short for mainnet (min 12 chars)",
-                    ));
+                        return Err(Box::<dyn std::error::Error>::from(
+                            "LOS_WALLET_PASSWORD too short for mainnet (min 12 chars)",
+                        ));
+                    }
+                    pw // Testnet: allow shorter passwords
                 }
-                pw // Testnet: allow shorter passwords
-            }
-            _ => {
-                if los_core::is_mainnet_build() {
+                _ => {
+                    if los_core::is_mainnet() {
+                        eprintln!(
+                        "❌ FATAL: LOS_WALLET_PASSWORD environment variable is REQUIRED on mainnet."
+                    );
+                        eprintln!("   export LOS_WALLET_PASSWORD='<strong-password-here>'");
+                        return Err(Box::<dyn std::error::Error>::from(
+                            "LOS_WALLET_PASSWORD required for mainnet build",
+                        ));
+                    }
+                    // Testnet: auto-generate weak password (acceptable for testing)
+                    let auto = format!("los-node-{}-autokey", &node_id);
+                    println!("⚠️  Using auto-generated wallet password (testnet only)");
+                    auto
+                }
+            };
+        let keys: los_crypto::KeyPair = if let Some(seed_phrase) = stdin_seed_phrase
+            .or_else(|| std::env::var("LOS_SEED_PHRASE").ok())
+            .or_else(|| dev_flag.then(|| dev_mode::DEV_MNEMONIC.to_string()))
+        {
+            // DETERMINISTIC KEYPAIR: Derive from BIP39 mnemonic (genesis validator identity)
+            // This ensures the node's runtime address matches its genesis address.
+            // SECURITY: Prefer stdin pipe over env var to avoid /proc/[pid]/environ exposure.
+            let mnemonic = match bip39::Mnemonic::parse_normalized(&seed_phrase) {
+                Ok(m) => m,
+                Err(e) => {
+                    eprintln!("FATAL: Seed phrase contains invalid BIP39 mnemonic: {e}");
                     eprintln!(
-                    "❌ FATAL: LOS_WALLET_PASSWORD environment variable is REQUIRED on mainnet."
-                );
-                    eprintln!("   export LOS_WALLET_PASSWORD='<strong-password-here>'");
-                    return Err(Box::<dyn std::error::Error>::from(
-                        "LOS_WALLET_PASSWORD required for mainnet build",
-                    ));
+                        "Please check the seed phrase (stdin or LOS_SEED_PHRASE env) and try again."
+                    );
+                    std::process::exit(1);
                 }
-                // Testnet: auto-generate weak password (acceptable for testing)
-                let auto = format!("los-node-{}-autokey", &node_id);
-                println!("⚠️  Using auto-generated wallet password (testnet only)");
-                auto
-            }
-        };
-    let keys: los_crypto::KeyPair = if let Some(seed_phrase) =
-        stdin_seed_phrase.or_else(|| std::env::var("LOS_SEED_PHRASE").ok())
-    {
-        // DETERMINISTIC KEYPAIR: Derive from BIP39 mnemonic (genesis validator identity)
-        // This ensures the node's runtime address matches its genesis address.
-        // SECURITY: Prefer stdin pipe over env var to avoid /proc/[pid]/environ exposure.
-        let mnemonic = match bip39::Mnemonic::parse_normalized(&seed_phrase) {
-            Ok(m) => m,
-            Err(e) => {
-                eprintln!("FATAL: Seed phrase contains invalid BIP39 mnemonic: {e}");
-                eprintln!(
-                    "Please check the seed phrase (stdin or LOS_SEED_PHRASE env) and try again."
+            };
+            let bip39_seed = mnemonic.to_seed("");
+            let kp = los_crypto::generate_keypair_from_seed(&bip39_seed);
+            let derived_addr = los_crypto::public_key_to_address(&kp.public_key);
+            println!(
+                "🔑 Derived keypair from LOS_SEED_PHRASE → {}",
+                get_short_addr(&derived_addr)
+            );
+            // Save/overwrite wallet.json so subsequent restarts without seed phrase still work
+            fs::create_dir_all(&base_data_dir).ok();
+            if let Ok(encrypted) = los_crypto::migrate_to_encrypted(&kp, &wallet_password) {
+                let _ = fs::write(
+                    &wallet_path,
+                    serde_json::to_string(&encrypted).unwrap_or_default(),
                 );
-                std::process::exit(1);
             }
-        };
-        let bip39_seed = mnemonic.to_seed("");
-        let kp = los_crypto::generate_keypair_from_seed(&bip39_seed);
-        let derived_addr = los_crypto::public_key_to_address(&kp.public_key);
-        println!(
-            "🔑 Derived keypair from LOS_SEED_PHRASE → {}",
-            get_short_addr(&derived_addr)
-        );
-        // Save/overwrite wallet.json so subsequent restarts without seed phrase still work
-        fs::create_dir_all(&base_data_dir).ok();
-        if let Ok(encrypted) = los_crypto::migrate_to_encrypted(&kp, &wallet_password) {
-            let _ = fs::write(
-                &wallet_path,
-                serde_json::to_string(&encrypted).unwrap_or_default(),
-            );
-        }
-        kp
-    } else if let Ok(data) = fs::read_to_string(&wallet_path) {
-        // Try parsing as encrypted key first, fall back to legacy plaintext
-        if let Ok(encrypted) = serde_json::from_str::<los_crypto::EncryptedKey>(&data) {
-            let sk =
-                los_crypto::decrypt_private_key(&encrypted, &wallet_password).map_err(|e| {
-                    Box::<dyn std::error::Error>::from(format!(
-                        "Wallet decrypt failed: {}. Set LOS_WALLET_PASSWORD if changed.",
-                        e
-                    ))
-                })?;
-            los_crypto::KeyPair {
-                public_key: encrypted.public_key,
-                secret_key: sk,
+            kp
+        } else if let Ok(data) = fs::read_to_string(&wallet_path) {
+            // Try parsing as encrypted key first, fall back to legacy plaintext
+            if let Ok(encrypted) = serde_json::from_str::<los_crypto::EncryptedKey>(&data) {
+                let sk =
+                    los_crypto::decrypt_private_key(&encrypted, &wallet_password).map_err(|e| {
+                        Box::<dyn std::error::Error>::from(format!(
+                            "Wallet decrypt failed: {}. Set LOS_WALLET_PASSWORD if changed.",
+                            e
+                        ))
+                    })?;
+                los_crypto::KeyPair {
+                    public_key: encrypted.public_key,
+                    secret_key: sk,
+                }
+            } else if let Ok(plain_key) = serde_json::from_str::<los_crypto::KeyPair>(&data) {
+                // Legacy plaintext wallet — auto-migrate to encrypted
+                eprintln!("⚠️  Migrating plaintext wallet to encrypted format...");
+                let encrypted = los_crypto::migrate_to_encrypted(&plain_key, &wallet_password)
+                    .map_err(|e| {
+                        Box::<dyn std::error::Error>::from(format!("Migration failed: {}", e))
+                    })?;
+                fs::write(&wallet_path, serde_json::to_string(&encrypted)?)?;
+                println!("🔒 Wallet migrated to encrypted storage");
+                plain_key
+            } else {
+                return Err(Box::from(
+                    "Failed to parse wallet file — corrupted or invalid format",
+                ));
             }
-        } else if let Ok(plain_key) = serde_json::from_str::<los_crypto::KeyPair>(&data) {
-            // Legacy plaintext wallet — auto-migrate to encrypted
-            eprintln!("⚠️  Migrating plaintext wallet to encrypted format...");
-            let encrypted = los_crypto::migrate_to_encrypted(&plain_key, &wallet_password)
-                .map_err(|e| {
-                    Box::<dyn std::error::Error>::from(format!("Migration failed: {}", e))
+        } else {
+            let new_k = los_crypto::generate_keypair();
+            fs::create_dir_all(&base_data_dir)?;
+            // Store encrypted from the start
+            let encrypted =
+                los_crypto::migrate_to_encrypted(&new_k, &wallet_password).map_err(|e| {
+                    Box::<dyn std::error::Error>::from(format!("Encryption failed: {}", e))
                 })?;
             fs::write(&wallet_path, serde_json::to_string(&encrypted)?)?;
-            println!("🔒 Wallet migrated to encrypted storage");
-            plain_key
-        } else {
-            return Err(Box::from(
-                "Failed to parse wallet file — corrupted or invalid format",
-            ));
-        }
-    } else {
-        let new_k = los_crypto::generate_keypair();
-        fs::create_dir_all(&base_data_dir)?;
-        // Store encrypted from the start
-        let encrypted = los_crypto::migrate_to_encrypted(&new_k, &wallet_password)
-            .map_err(|e| Box::<dyn std::error::Error>::from(format!("Encryption failed: {}", e)))?;
-        fs::write(&wallet_path, serde_json::to_string(&encrypted)?)?;
-        println!("🔑 Generated new encrypted keypair for {}", node_id);
-        new_k
+            println!("🔑 Generated new encrypted keypair for {}", node_id);
+            new_k
+        };
+        Arc::new(signer::LocalSigner::new(keys))
     };
-
-    let my_address = los_crypto::public_key_to_address(&keys.public_key);
+    let node_public_key = signer.public_key().to_vec();
+    let my_address = los_crypto::public_key_to_address(&node_public_key);
     let my_short = get_short_addr(&my_address);
-    // MAINNET SAFETY (W1): Wrap secret key in Zeroizing so it's zeroed on drop
-    let secret_key = Zeroizing::new(keys.secret_key.clone());
     json_event!("wallet_ready", "address" => &my_address, "short" => &my_short);
 
     // ══════════════════════════════════════════════════════════════════════
     // MAINNET SAFETY (M-6): Tor & network security enforcement
     // ══════════════════════════════════════════════════════════════════════
-    if los_core::is_mainnet_build() {
+    if los_core::is_mainnet() {
         // T-1: Mainnet MUST have Tor SOCKS5 proxy configured
         let has_tor = std::env::var("LOS_SOCKS5_PROXY")
             .or_else(|_| std::env::var("LOS_TOR_SOCKS5"))
@@ -5304,6 +8232,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load ledger and genesis BEFORE wrapping in Arc to prevent race condition
     let mut ledger_state = load_from_disk(&database);
+    match database.ensure_tx_index(&ledger_state) {
+        Ok(0) => {}
+        Ok(n) => println!("🗂️  Counterparty index built for {} block(s)", n),
+        Err(e) => eprintln!("⚠️ Counterparty index backfill failed: {}", e),
+    }
 
     // Sanitize: remove orphaned blocks from l.blocks that aren't part of any account chain.
     // This cleans up ghost blocks caused by failed process_block() insertions or sync artifacts.
@@ -5337,14 +8270,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut bootstrap_validators: Vec<String> = Vec::new();
     let mut genesis_ts_from_config: Option<u64> = None;
     {
-        let genesis_path = if los_core::is_mainnet_build() {
-            "genesis_config.json"
-        } else {
-            "testnet-genesis/testnet_wallets.json"
-        };
+        let genesis_path = genesis::genesis_path_for(los_core::is_mainnet());
 
         // MAINNET: genesis_config.json is REQUIRED — refuse to start without it
-        if los_core::is_mainnet_build() && !std::path::Path::new(genesis_path).exists() {
+        if los_core::is_mainnet() && !std::path::Path::new(genesis_path).exists() {
             eprintln!("❌ FATAL: genesis_config.json not found!");
             eprintln!("   Mainnet requires genesis_config.json at the working directory root.");
             eprintln!("   Generate with: cargo run -p genesis --bin genesis");
@@ -5357,7 +8286,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             if let Ok(genesis_json) = std::fs::read_to_string(genesis_path) {
                 // Mainnet: use validated GenesisConfig parser
                 // Testnet: use the raw JSON wallets parser (legacy format)
-                if los_core::is_mainnet_build() {
+                if los_core::is_mainnet() {
                     // Validate genesis config BEFORE loading accounts.
                     // Prevents tampered genesis files from silently loading invalid state.
                     {
@@ -5562,7 +8491,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Uses genesis_timestamp from validated GenesisConfig (mainnet) or system time (testnet).
     // Bootstrap validators are registered as is_genesis=true (eligible for rewards).
     // Pool is initialized from VALIDATOR_REWARD_POOL_CIL constant.
-    let genesis_ts: u64 = if los_core::is_mainnet_build() {
+    let genesis_ts: u64 = if los_core::is_mainnet() {
         // Use timestamp stored during genesis validation above (no redundant file I/O)
         genesis_ts_from_config.unwrap_or_else(|| {
             eprintln!(
@@ -5629,7 +8558,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Set expected heartbeats using the correct interval for testnet/mainnet
-    let initial_heartbeat_secs: u64 = if los_core::is_testnet_build() { 10 } else { 60 };
+    let initial_heartbeat_secs: u64 = if los_core::is_testnet() { 10 } else { 60 };
     reward_pool_state.set_expected_heartbeats(initial_heartbeat_secs);
 
     // ── STARTUP AUTO SELF-REGISTER ──────────────────────────────────
@@ -5664,13 +8593,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         false
     };
 
+    // Fee redistribution history is derived from FEE_REWARD blocks in the ledger
+    for blk in ledger_state.blocks.values() {
+        reward_pool_state.record_fee_reward(blk);
+    }
+
+    // Claim-model accrual accounts are not derived from ledger blocks
+    if let Err(e) = database.load_reward_accruals(&mut reward_pool_state) {
+        eprintln!("⚠️  Failed to load reward accruals: {}", e);
+    }
+
     let reward_pool = Arc::new(Mutex::new(reward_pool_state));
     println!(
-        "🏆 Validator reward pool initialized: {} LOS, epoch rate {} LOS/month",
+        "🏆 Validator reward pool initialized: {} LOS, epoch rate {} LOS/epoch",
         los_core::VALIDATOR_REWARD_POOL_CIL / CIL_PER_LOS,
-        los_core::REWARD_RATE_INITIAL_CIL / CIL_PER_LOS
+        los_core::effective_reward_rate_initial_cil() / CIL_PER_LOS
     );
 
+    // DEV CHAIN: pre-fund the dev mnemonic accounts (account 0 = this node)
+    if dev_flag {
+        match dev_mode::dev_addresses() {
+            Ok(addrs) => {
+                let funded = dev_mode::fund_accounts(&mut ledger_state, &addrs);
+                if funded > 0 {
+                    save_to_disk_internal(&ledger_state, &database, true);
+                }
+                println!("💰 Dev accounts (mnemonic: \"{}\")", dev_mode::DEV_MNEMONIC);
+                for (i, addr) in addrs.iter().enumerate() {
+                    println!(
+                        "   [{}] {} — {} LOS",
+                        i,
+                        addr,
+                        dev_mode::DEV_ACCOUNT_BALANCE_CIL / CIL_PER_LOS
+                    );
+                }
+            }
+            Err(e) => eprintln!("⚠️ Dev accounts unavailable: {}", e),
+        }
+    }
+
+    // Claim payout model: validators mint certified payouts with Claim blocks
+    if los_core::chain_spec::active().reward_payout == RewardPayout::Claim {
+        println!("🏆 Validator rewards accrue in the reward pool (reward_payout = claim)");
+    }
+
     // Now wrap in Arc after all initialization is complete
     let ledger = Arc::new(Mutex::new(ledger_state));
 
@@ -5695,6 +8661,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // This prevents dead validators from earning rewards via stale address_book entries.
     let live_peers: Arc<Mutex<HashMap<String, u64>>> = Arc::new(Mutex::new(HashMap::new()));
 
+    // Epoch distributions this node proposed as leader, keyed by digest, with the
+    // proposal time. REWARD_SIGN gossip adds co-signatures; the reward task mints
+    // each one once it holds a validator quorum (los_core::reward_cosign).
+    let pending_distributions: Arc<Mutex<BTreeMap<String, (DistributionCertificate, u64)>>> =
+        Arc::new(Mutex::new(BTreeMap::new()));
+    // (kind, epoch) → digest of the distribution we co-signed. Refuses to sign a
+    // second, different distribution for the same epoch.
+    let mut signed_distributions: BTreeMap<(DistributionKind, u64), String> = BTreeMap::new();
+
     // LOCAL REGISTERED VALIDATORS — Tracks wallet addresses registered as validators
     // through THIS node's API. The heartbeat loop records heartbeats for these addresses
     // because this node's liveness proves the registered validator's liveness.
@@ -5707,20 +8682,77 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Mempool: tracks pending transactions with priority ordering and expiration.
     // Runs alongside pending_sends (shadow mode) to provide stats and future block assembly.
-    let mempool_pool = Arc::new(Mutex::new(mempool::Mempool::new()));
+    let mempool_pool = Arc::new(Mutex::new(mempool::Mempool::with_config(
+        mempool::MempoolConfig::from_env(),
+    )));
+    {
+        // Expire stuck transactions even when nobody polls /mempool/stats
+        let mp_expiry = mempool_pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let expired = safe_lock(&mp_expiry).remove_expired();
+                if expired > 0 {
+                    println!("🧹 Mempool: expired {} stale transaction(s)", expired);
+                }
+            }
+        });
+    }
 
     // Vote deduplication — track which validators have already voted
     // Prevents a single validator from reaching consensus alone by sending multiple votes
     let send_voters = Arc::new(Mutex::new(HashMap::<String, HashSet<String>>::new()));
 
+    // Replay the in-flight consensus journal: sends that were waiting for votes
+    // when the node stopped keep their verified partial tallies.
+    match database.load_inflight_sends() {
+        Ok(entries) if !entries.is_empty() => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let restored = {
+                let l = safe_lock(&ledger);
+                consensus_journal::restore(
+                    entries,
+                    now,
+                    |tx_hash| l.blocks.contains_key(tx_hash),
+                    los_crypto::verify_signature,
+                    los_crypto::public_key_to_address,
+                )
+            };
+            for tx_hash in &restored.stale {
+                let _ = database.remove_inflight_send(tx_hash);
+            }
+            println!(
+                "♻️  Restored {} in-flight send(s) with {} vote(s) ({} stale dropped, {} invalid vote(s) ignored)",
+                restored.pending.len(),
+                restored.voters.iter().map(|(_, v)| v.len()).sum::<usize>(),
+                restored.stale.len(),
+                restored.rejected_votes
+            );
+            safe_lock(&pending_sends).extend(
+                restored
+                    .pending
+                    .into_iter()
+                    .map(|(hash, blk, power)| (hash, (blk, power))),
+            );
+            safe_lock(&send_voters).extend(restored.voters);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️ In-flight consensus journal unreadable: {}", e),
+    }
+
     // DESIGN Pending checkpoints accumulating multi-validator signatures.
     // Keyed by checkpoint height → PendingCheckpoint with accumulated signatures.
     // Once 2f+1 sigs collected, finalized via CheckpointManager::store_checkpoint().
     let pending_checkpoints: Arc<Mutex<HashMap<u64, PendingCheckpoint>>> =
         Arc::new(Mutex::new(HashMap::new()));
 
-    // DESIGN Outbox for checkpoint gossip messages.
-    // The save task pushes CHECKPOINT_PROPOSE messages here; a gossip consumer drains them.
+    // DESIGN Outbox for checkpoint network messages.
+    // The save task pushes CHECKPOINT_REQ requests here; a consumer drains them to the
+    // network task, which sends them point-to-point to validators (not gossiped).
     let checkpoint_outbox: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
 
     // ══════════════════════════════════════════════════════════════════════
@@ -5732,7 +8764,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 1. This node's own LOS_HOST_ADDRESS or LOS_ONION_ADDRESS
     // 2. Genesis validator host_address/onion_address fields
     // 3. VALIDATOR_REG gossip messages
-    // 4. PEER_LIST exchange messages
+    // 4. Signed PEX messages (and legacy PEER_LIST from older nodes)
     let mut initial_endpoints = HashMap::<String, String>::new();
     // Register this node's own host address (with port)
     if let Some(raw_host) = get_node_host_address() {
@@ -5753,6 +8785,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
     let validator_endpoints = Arc::new(Mutex::new(initial_endpoints));
+    // Last-seen times + per-sender rate limits for signed PEX (pex.rs)
+    let pex_book = Arc::new(Mutex::new(pex::PexBook::new()));
+    // Telemetry: vote propagation latency window + collected reports (aggregation mode)
+    let telemetry_latency = Arc::new(Mutex::new(telemetry::LatencyTracker::default()));
+    let telemetry_aggregator = telemetry_cfg
+        .aggregate
+        .then(|| Arc::new(Mutex::new(telemetry::TelemetryAggregator::new())));
+    // Gossip propagation + time-to-quorum stats (GET /netstats)
+    let netstats = Arc::new(Mutex::new(netstats::NetStats::default()));
+    // Sync batches are verified in parallel (LOS_VERIFY_THREADS)
+    println!(
+        "🔐 Block verification pool: {} thread(s)",
+        verify_pool::global().threads()
+    );
+    // Frontier delta sync — resume a session interrupted by a restart
+    let delta_sync_state = Arc::new(Mutex::new(match database.load_delta_sync() {
+        Ok(Some(session)) => {
+            println!(
+                "📡 Resuming delta sync ({} chains queued, {} blocks applied so far)",
+                session.queue.len(),
+                session.blocks_applied
+            );
+            delta_sync::DeltaSync::resume(session)
+        }
+        Ok(None) => delta_sync::DeltaSync::default(),
+        Err(e) => {
+            eprintln!(
+                "⚠️ Saved delta sync session unreadable, starting fresh: {}",
+                e
+            );
+            delta_sync::DeltaSync::default()
+        }
+    }));
 
     // PoW MINT ENGINE — Fair token distribution via SHA3 proof-of-work
     // miners compute SHA3-256(address || epoch || nonce) and submit proofs.
@@ -5760,6 +8825,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mining_state = Arc::new(Mutex::new(MiningState::new(genesis_ts)));
     {
         let mut ms = safe_lock(&mining_state);
+        if dev_flag {
+            ms.pin_difficulty(dev_mode::DEV_MINING_DIFFICULTY_BITS);
+        }
         let now_secs = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
@@ -5862,6 +8930,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    // Consensus clock: checkpoint median floor, refreshed as checkpoints finalize
+    refresh_consensus_clock(&safe_lock(&checkpoint_manager));
+    {
+        let cm_clock = checkpoint_manager.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                refresh_consensus_clock(&safe_lock(&cm_clock));
+            }
+        });
+    }
+
     // Init own account in ledger if not exists
     {
         let mut l = safe_lock(&ledger);
@@ -5892,18 +8973,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             .as_secs()
                     ),
                     signature: "".to_string(),
-                    public_key: hex::encode(&keys.public_key),
+                    public_key: hex::encode(&node_public_key),
                     work: 0,
                     timestamp: std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_secs(),
                     fee: 0,
+                    valid_after: 0,
+                    expires_at: 0,
                 };
 
                 solve_pow(&mut init_block);
                 init_block.signature =
-                    match try_sign_hex(init_block.signing_hash().as_bytes(), &secret_key) {
+                    match try_sign_hex(init_block.signing_hash().as_bytes(), &*signer) {
                         Ok(sig) => sig,
                         Err(e) => {
                             eprintln!(
@@ -5916,7 +8999,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 match l.process_block(&init_block) {
                     Ok(_) => {
-                        SAVE_DIRTY.store(true, Ordering::Release);
+                        mark_dirty();
                         println!("🎁 TESTNET (Functional): Node initialized with 1000 LOS via Mint block (supply deducted)");
                     }
                     Err(e) => {
@@ -5955,17 +9038,67 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    // --- WASM Smart Contract Engine (shared between API + P2P) ---
+    let wasm_engine = Arc::new(WasmEngine::new());
+    wasm_engine.set_emergency_guardians(
+        los_core::chain_spec::active()
+            .emergency_guardians
+            .iter()
+            .cloned(),
+    );
+    let wasm_max_in_flight = std::env::var("LOS_WASM_MAX_IN_FLIGHT")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(los_vm::exec_limit::DEFAULT_MAX_IN_FLIGHT);
+    let wasm_queue_timeout = std::env::var("LOS_WASM_QUEUE_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(los_vm::exec_limit::DEFAULT_QUEUE_TIMEOUT);
+    wasm_engine.set_exec_limits(wasm_max_in_flight, wasm_queue_timeout);
+    // Restore contract state from DB (if any contracts were previously deployed)
+    match database.load_contracts() {
+        Ok(Some(vm_data)) => match wasm_engine.deserialize_all(&vm_data) {
+            Ok(count) => println!("✅ Restored {} smart contracts from database", count),
+            Err(e) => eprintln!("⚠️ Failed to restore contracts: {}", e),
+        },
+        Ok(None) => { /* No contracts deployed yet */ }
+        Err(e) => eprintln!("⚠️ Failed to load contracts from DB: {}", e),
+    }
+    // Ledgers saved before contract_held_cil existed: adopt the VM's total so
+    // contract payouts and the supply audit see the CIL contracts already hold.
+    {
+        let mut l = safe_lock(&ledger);
+        if l.contract_held_cil == 0 {
+            if let Ok(held) = wasm_engine.total_contract_balance() {
+                l.contract_held_cil = held;
+            }
+        }
+    }
+
+    // --- State root quarantine (see quarantine.rs) ---
+    let state_quarantine = Arc::new(Mutex::new(quarantine::StateQuarantine::new(
+        quarantine::QuarantineConfig::from_env(),
+    )));
+
     // Background task for debounced disk saves (prevents race conditions)
     // Clone ledger snapshot THEN release lock BEFORE disk I/O
     let save_ledger = Arc::clone(&ledger);
     let save_database = Arc::clone(&database);
     let save_checkpoint_mgr = Arc::clone(&checkpoint_manager);
     // Clone signing credentials for checkpoint signatures
-    let save_secret_key = secret_key.clone();
+    let save_signer = signer.clone();
     let save_my_address = my_address.clone();
     // DESIGN Clone pending checkpoints for multi-validator coordination
     let save_pending_checkpoints = Arc::clone(&pending_checkpoints);
     let save_checkpoint_outbox = Arc::clone(&checkpoint_outbox);
+    let save_wasm_engine = Arc::clone(&wasm_engine);
+    let save_reward_pool = Arc::clone(&reward_pool);
+    let save_quarantine = Arc::clone(&state_quarantine);
+    let mut save_archiver = archive::is_enabled().then(|| {
+        archive::Archiver::new(database.latest_archive_height().ok().flatten().unwrap_or(0))
+    });
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(5));
         loop {
@@ -5974,17 +9107,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Only save if dirty and not currently saving
             if SAVE_DIRTY.load(Ordering::Acquire) && !SAVE_IN_PROGRESS.load(Ordering::Acquire) {
                 // Clone ledger under lock, then release lock BEFORE disk I/O
-                let (ledger_snapshot, block_count, validator_count) = {
+                let (ledger_snapshot, block_count, validator_count, staked) = {
                     let l = safe_lock(&save_ledger);
                     let bc = l.blocks.len() as u64;
-                    let vc = l
-                        .accounts
-                        .iter()
-                        .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL)
-                        .count() as u32;
-                    (l.clone(), bc, vc)
+                    // Checkpoint quorum is counted over the staked validator set
+                    let vc = l.reward_validator_set().len() as u32;
+                    let staked = l.is_staked_validator(&save_my_address);
+                    (l.clone(), bc, vc, staked)
                 }; // Lock released — API requests can proceed during save
                 save_to_disk_internal(&ledger_snapshot, &save_database, false);
+                {
+                    let pool = safe_lock(&save_reward_pool);
+                    if !pool.accruals.is_empty() {
+                        if let Err(e) = save_database.save_reward_accruals(&pool) {
+                            eprintln!("⚠️ Failed to save reward accruals: {}", e);
+                        }
+                    }
+                }
+
+                // ARCHIVE: snapshot state at each checkpoint height (--archive)
+                if let Some(archiver) = save_archiver.as_mut() {
+                    match archiver.maybe_snapshot(
+                        block_count,
+                        &ledger_snapshot,
+                        &save_wasm_engine,
+                        &save_database,
+                    ) {
+                        Ok(Some(height)) => println!("🗄️  Archive snapshot at height {}", height),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("⚠️ Archive snapshot failed: {}", e),
+                    }
+                }
 
                 // CHECKPOINT: Create finality checkpoint when block_count crosses next interval
                 // Use >= instead of == to handle block-lattice where exact multiples may be skipped
@@ -5999,7 +9152,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let next_checkpoint =
                         ((latest_height / CHECKPOINT_INTERVAL) + 1) * CHECKPOINT_INTERVAL;
 
-                    if block_count >= next_checkpoint {
+                    // A quarantined node's state is suspect — it must not propose
+                    if block_count >= next_checkpoint
+                        && safe_lock(&save_quarantine).is_quarantined()
+                    {
+                        println!(
+                            "🚨 Quarantined — skipping checkpoint proposal at block {}",
+                            block_count
+                        );
+                    } else if block_count >= next_checkpoint
+                        // Only staked validators propose (their signature must count)
+                        && staked
+                        // Finality halted by a checkpoint conflict (checkpoint_conflict.rs)
+                        && !cm.has_conflicts()
+                        && !checkpoint_conflict::replay_pending()
+                    {
                         // Snap block_count DOWN to aligned interval.
                         // In a block-lattice, block_count rarely lands exactly on a
                         // multiple of CHECKPOINT_INTERVAL. Without snapping, every
@@ -6007,23 +9174,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         let checkpoint_height =
                             (block_count / CHECKPOINT_INTERVAL) * CHECKPOINT_INTERVAL;
 
-                        // Calculate simple state root from account balances
-                        // DESIGN Use Ledger::compute_state_root() for consistency
-                        let state_root = ledger_snapshot.compute_state_root();
+                        // Full state root: accounts + VM + reward pool
+                        let state_root = {
+                            let pool = safe_lock(&save_reward_pool);
+                            match quarantine::full_root(&ledger_snapshot, &save_wasm_engine, &pool)
+                            {
+                                Ok(root) => root,
+                                Err(e) => {
+                                    eprintln!("⚠️ Checkpoint state root failed: {} — skipping", e);
+                                    continue;
+                                }
+                            }
+                        };
 
                         // Find latest block hash
-                        let latest_block_hash = ledger_snapshot
-                            .blocks
-                            .values()
-                            .max_by_key(|b| b.timestamp)
-                            .map(|b| b.calculate_hash())
-                            .unwrap_or_else(|| "genesis".to_string());
+                        let latest_block_hash = quarantine::latest_block_hash(&ledger_snapshot);
 
                         // Sign checkpoint data with this node's key.
                         // DESIGN Create checkpoint proposal with our signature,
-                        // store in pending_checkpoints map. The gossip broadcast task
-                        // will handle broadcasting CHECKPOINT_PROPOSE to peers for
-                        // multi-validator signature collection.
+                        // store in pending_checkpoints map. The outbox drainer sends
+                        // CHECKPOINT_REQ directly to validators for signature collection.
                         let checkpoint = FinalityCheckpoint::new(
                             checkpoint_height,
                             latest_block_hash,
@@ -6032,8 +9202,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             vec![], // placeholder — filled below
                         );
                         let signing_data = checkpoint.signing_data();
-                        let my_sig = match los_crypto::sign_message(&signing_data, &save_secret_key)
-                        {
+                        let my_sig = match save_signer.sign(&signing_data) {
                             Ok(sig) => sig,
                             Err(e) => {
                                 eprintln!("⚠️ Checkpoint signing failed: {} — skipping", e);
@@ -6053,7 +9222,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                         // DESIGN Store as pending checkpoint, awaiting peer signatures.
                         // For single-validator networks, this will immediately pass quorum (1/1).
-                        // For multi-validator: gossip task will broadcast CHECKPOINT_PROPOSE.
+                        // For multi-validator: signatures are requested via CHECKPOINT_REQ.
                         let pending_cp = PendingCheckpoint::new(checkpoint.clone());
                         if pending_cp.has_quorum() {
                             // Single validator — can finalize immediately
@@ -6066,19 +9235,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             // Multi-validator — store as pending, await peer signatures
                             let mut pcp = safe_lock(&save_pending_checkpoints);
                             pcp.insert(checkpoint_height, pending_cp);
-                            // Queue CHECKPOINT_PROPOSE for gossip broadcast
-                            // Format: CHECKPOINT_PROPOSE:<height>:<block_hash>:<state_root>:<proposer>:<sig_hex>
-                            let sig_hex = hex::encode(&checkpoint.signatures[0].signature);
-                            let propose_msg = format!(
-                                "CHECKPOINT_PROPOSE:{}:{}:{}:{}:{}",
-                                checkpoint_height,
-                                checkpoint.block_hash,
-                                checkpoint.state_root,
-                                save_my_address,
-                                sig_hex,
-                            );
-                            let mut outbox = safe_lock(&save_checkpoint_outbox);
-                            outbox.push(propose_msg);
+                            // Queue a direct signature request (request-response, not gossip)
+                            if let Some(req) = CheckpointSignRequest::for_checkpoint(&checkpoint) {
+                                safe_lock(&save_checkpoint_outbox).push(req.to_message());
+                            }
                             println!("🏁 Checkpoint proposed at height {} (sig_count=1/{}, awaiting peer sigs)",
                                 checkpoint_height, validator_count);
                         }
@@ -6092,6 +9252,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Pending sends older than 5 minutes are removed to prevent memory leaks
     let cleanup_pending_sends = Arc::clone(&pending_sends);
     let cleanup_send_voters = Arc::clone(&send_voters);
+    let cleanup_db = Arc::clone(&database);
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(60));
         loop {
@@ -6100,7 +9261,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs();
-            const PENDING_TTL_SECS: u64 = 300; // 5 minute TTL for pending transactions
+            use consensus_journal::PENDING_TTL_SECS; // 5 minute TTL for pending transactions
 
             // Clean stale pending sends AND their corresponding vote trackers.
             // Without cleaning send_voters, entries for timed-out txs leak memory forever.
@@ -6110,11 +9271,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 let stale_hashes: Vec<String> = ps
                     .iter()
                     .filter(|(_, (block, _))| {
-                        now.saturating_sub(block.timestamp) >= PENDING_TTL_SECS
+                        now.saturating_sub(block.valid_from()) >= PENDING_TTL_SECS
                     })
                     .map(|(hash, _)| hash.clone())
                     .collect();
-                ps.retain(|_, (block, _)| now.saturating_sub(block.timestamp) < PENDING_TTL_SECS);
+                ps.retain(|_, (block, _)| {
+                    now.saturating_sub(block.valid_from()) < PENDING_TTL_SECS
+                });
                 let removed = before - ps.len();
                 if removed > 0 {
                     // Also clean the vote tracker for these stale transactions
@@ -6123,6 +9286,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             sv.remove(hash);
                         }
                     }
+                    for hash in &stale_hashes {
+                        let _ = cleanup_db.remove_inflight_send(hash);
+                    }
                     println!(
                         "🧹 Cleaned {} stale pending sends + vote trackers (TTL: {}s)",
                         removed, PENDING_TTL_SECS
@@ -6258,14 +9424,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (tx_in, mut rx_in) = mpsc::channel(32);
 
     tokio::spawn(async move {
-        match LosNode::start(tx_in, rx_out).await {
+        match LosNode::start(tx_in, rx_out, network_key).await {
             Ok(()) => eprintln!("⚠️ P2P network task exited normally (unexpected)"),
             Err(e) => eprintln!("❌ P2P network task failed: {}", e),
         }
     });
 
-    // DESIGN Checkpoint gossip outbox drainer.
-    // Periodically checks for pending CHECKPOINT_PROPOSE messages and sends them via gossip.
+    // Time-locked sends: re-admit the ones held before a restart, then start
+    // the vote for each send whose valid_after has passed (mempool.rs).
+    let sched_tx = tx_out.clone();
+    let sched_mp = Arc::clone(&mempool_pool);
+    let sched_pending = Arc::clone(&pending_sends);
+    let sched_db = Arc::clone(&database);
+    let sched_ns = Arc::clone(&netstats);
+    tokio::spawn(async move {
+        let mut due: Vec<(String, Block)> = Vec::new();
+        match sched_db.load_scheduled_sends() {
+            Ok(held) => {
+                let mut mp = safe_lock(&sched_mp);
+                let count = held.len();
+                for (hash, blk) in held {
+                    match mp.admit(blk.clone(), blk.fee as u64, 0) {
+                        Ok(_) if mp.is_held(&hash) => {}
+                        Ok(_) => due.push((hash, blk)),
+                        Err(e) => {
+                            println!(
+                                "⏰ Dropping scheduled send {}: {}",
+                                get_short_addr(&hash),
+                                e
+                            );
+                            let _ = sched_db.remove_scheduled_send(&hash);
+                        }
+                    }
+                }
+                if count > 0 {
+                    println!("⏰ Restored {} scheduled send(s)", count);
+                }
+            }
+            Err(e) => eprintln!("⚠️ Failed to load scheduled sends: {}", e),
+        }
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            interval.tick().await;
+            due.extend(safe_lock(&sched_mp).release_due(los_core::consensus_clock::now()));
+            for (hash, blk) in due.drain(..) {
+                safe_lock(&sched_pending).insert(hash.clone(), (blk.clone(), 0u128));
+                if let Err(e) = sched_db.save_inflight_send(&hash, &blk) {
+                    eprintln!("⚠️ In-flight journal write failed: {}", e);
+                }
+                let _ = sched_db.remove_scheduled_send(&hash);
+                let ts = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis();
+                let block_json = serde_json::to_string(&blk).unwrap_or_default();
+                let block_b64 =
+                    base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
+                safe_lock(&sched_ns).note_published(&hash, ts as u64);
+                println!(
+                    "⏰ Time lock over, requesting votes for {}",
+                    get_short_addr(&hash)
+                );
+                let _ = sched_tx
+                    .send(format!(
+                        "CONFIRM_REQ:{}:{}:{}:{}:{}",
+                        hash, blk.account, blk.amount, ts, block_b64
+                    ))
+                    .await;
+            }
+        }
+    });
+
+    // DESIGN Checkpoint outbox drainer.
+    // Periodically hands queued CHECKPOINT_REQ messages to the network task.
     let cp_outbox_tx = tx_out.clone();
     let cp_outbox_drain = Arc::clone(&checkpoint_outbox);
     tokio::spawn(async move {
@@ -6282,18 +9513,56 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // Checkpoint signature retries: re-request our own pending checkpoints that have not
+    // reached quorum. `signed_by` lets validators that already answered skip the work.
+    let cp_retry_tx = tx_out.clone();
+    let cp_retry_pending = Arc::clone(&pending_checkpoints);
+    let cp_retry_addr = my_address.clone();
+    tokio::spawn(async move {
+        let mut attempts: HashMap<u64, u32> = HashMap::new();
+        let mut interval = tokio::time::interval(Duration::from_secs(CHECKPOINT_RETRY_SECS));
+        interval.tick().await; // The first request goes out via the outbox
+        loop {
+            interval.tick().await;
+            let requests: Vec<CheckpointSignRequest> = {
+                let pcp = safe_lock(&cp_retry_pending);
+                attempts.retain(|height, _| pcp.contains_key(height));
+                pcp.iter()
+                    .filter(|(_, p)| !p.has_quorum())
+                    .filter_map(|(height, p)| {
+                        let req = CheckpointSignRequest::for_checkpoint(&p.checkpoint)?;
+                        let tries = attempts.entry(*height).or_insert(0);
+                        if req.proposer != cp_retry_addr || *tries >= CHECKPOINT_MAX_ATTEMPTS {
+                            return None;
+                        }
+                        *tries += 1;
+                        Some(req)
+                    })
+                    .collect()
+            };
+            for req in requests {
+                println!(
+                    "🔁 Re-requesting checkpoint signatures at height {} ({} signed)",
+                    req.height,
+                    req.signed_by.len()
+                );
+                let _ = cp_retry_tx.send(req.to_message()).await;
+            }
+        }
+    });
+
     // --- Start HTTP API server ---
     let api_ledger = Arc::clone(&ledger);
     let api_tx = tx_out.clone();
     let api_pending_sends = Arc::clone(&pending_sends);
     let api_address_book = Arc::clone(&address_book);
     let api_addr = my_address.clone();
-    let api_key = Zeroizing::new(keys.secret_key.clone());
+    let api_signer = signer.clone();
     let api_metrics = Arc::clone(&metrics);
     let api_database = Arc::clone(&database);
 
     let api_slashing = Arc::clone(&slashing_manager);
-    let api_pk = keys.public_key.clone();
+    let api_pk = node_public_key.clone();
     let api_bootstrap = bootstrap_validators.clone();
     let api_reward_pool = Arc::clone(&reward_pool);
     let api_validator_endpoints = Arc::clone(&validator_endpoints);
@@ -6317,19 +9586,44 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
     let api_abft = Arc::clone(&abft_consensus);
 
-    // --- WASM Smart Contract Engine (shared between API + P2P) ---
-    let wasm_engine = Arc::new(WasmEngine::new());
-    // Restore contract state from DB (if any contracts were previously deployed)
-    match database.load_contracts() {
-        Ok(Some(vm_data)) => match wasm_engine.deserialize_all(&vm_data) {
-            Ok(count) => println!("✅ Restored {} smart contracts from database", count),
-            Err(e) => eprintln!("⚠️ Failed to restore contracts: {}", e),
-        },
-        Ok(None) => { /* No contracts deployed yet */ }
-        Err(e) => eprintln!("⚠️ Failed to load contracts from DB: {}", e),
-    }
     let api_wasm_engine = Arc::clone(&wasm_engine);
     let api_mining_state = Arc::clone(&mining_state);
+    let api_checkpoint_manager = Arc::clone(&checkpoint_manager);
+    let fork_registry = Arc::new(Mutex::new(fork_monitor::ForkMonitor::new()));
+    let api_fork_monitor = Arc::clone(&fork_registry);
+    let storage_report = Arc::new(Mutex::new(storage::StorageReport {
+        last_compaction,
+        ..Default::default()
+    }));
+    let api_storage_report = Arc::clone(&storage_report);
+    let api_state_quarantine = Arc::clone(&state_quarantine);
+    let validator_directory = Arc::new(
+        validator_directory::ValidatorDirectory::new(
+            Arc::clone(&ledger),
+            Arc::clone(&reward_pool),
+            Arc::clone(&address_book),
+            bootstrap_validators.clone(),
+            my_address.clone(),
+        )
+        .with_slashing(Arc::clone(&slashing_manager))
+        .with_endpoints(Arc::clone(&validator_endpoints))
+        .with_live_peers(Arc::clone(&live_peers)),
+    );
+    let api_validator_directory = Arc::clone(&validator_directory);
+    let api_telemetry_aggregator = telemetry_aggregator.clone();
+    let api_netstats = Arc::clone(&netstats);
+    let api_delta_sync = Arc::clone(&delta_sync_state);
+    let api_source_registry = Arc::new(source_registry::SourceRegistry::new(
+        source_registry::SourceVerifyConfig::from_env(std::path::Path::new(&base_data_dir)),
+        Arc::clone(&database),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    ));
+    if api_source_registry.builds_enabled() {
+        println!("🔨 Contract source verification builds enabled (LOS_SOURCE_VERIFY)");
+    }
 
     tokio::spawn(async move {
         start_api_server(ApiServerConfig {
@@ -6338,12 +9632,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             pending_sends: api_pending_sends,
             address_book: api_address_book,
             my_address: api_addr,
-            secret_key: api_key,
+            signer: api_signer,
             api_port,
             metrics: api_metrics,
             database: api_database,
             slashing_manager: api_slashing,
             node_public_key: api_pk,
+            network_peer_id: network_peer_id.clone(),
             bootstrap_validators: api_bootstrap,
             reward_pool: api_reward_pool,
             validator_endpoints: api_validator_endpoints,
@@ -6354,6 +9649,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             mining_state: api_mining_state,
             enable_mining,
             mining_threads,
+            checkpoint_manager: api_checkpoint_manager,
+            fork_monitor: api_fork_monitor,
+            storage_report: api_storage_report,
+            state_quarantine: api_state_quarantine,
+            validator_directory: api_validator_directory,
+            source_registry: api_source_registry,
+            telemetry_aggregator: api_telemetry_aggregator,
+            netstats: api_netstats,
+            delta_sync: api_delta_sync,
         })
         .await;
     });
@@ -6367,6 +9671,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let grpc_bv = bootstrap_validators.clone();
     let grpc_rest_port = api_port;
     let grpc_reward_pool = Arc::clone(&reward_pool);
+    let grpc_directory = Arc::clone(&validator_directory);
 
     tokio::spawn(async move {
         println!("🔧 Starting gRPC server on port {}...", grpc_port);
@@ -6384,6 +9689,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             grpc_bv,
             grpc_rest_port,
             grpc_reward_pool,
+            grpc_directory,
         )
         .await
         {
@@ -6391,7 +9697,78 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
-    // (Oracle price broadcaster removed — prices fetched on-demand)
+    // --- Start mining job server (external miners, opt-in) ---
+    if let Some(port) = stratum_port {
+        let stratum_cfg = mining_server::MiningServerConfig {
+            port,
+            ledger: Arc::clone(&ledger),
+            mining_state: Arc::clone(&mining_state),
+            database: Arc::clone(&database),
+            tx_out: tx_out.clone(),
+            node_public_key: node_public_key.clone(),
+            signer: signer.clone(),
+            bootstrap_validators: bootstrap_validators.clone(),
+        };
+        tokio::spawn(mining_server::start_mining_server(stratum_cfg));
+    }
+
+    // --- Storage maintenance (size, compaction scheduling, snapshots, disk alerts) ---
+    tokio::spawn(storage::run_storage_maintenance(
+        storage::StorageConfig::from_env(),
+        Arc::clone(&database),
+        std::path::PathBuf::from(&base_data_dir),
+        Arc::clone(&metrics),
+        storage_report,
+    ));
+
+    // --- Contract event retention (opt-in: LOS_EVENT_KEEP_CHECKPOINTS) ---
+    tokio::spawn(event_retention::run_event_retention(
+        event_retention::EventRetentionConfig::from_env(),
+        Arc::clone(&database),
+        Arc::clone(&checkpoint_manager),
+        Arc::clone(&metrics),
+    ));
+
+    // --- Oracle price feed (opt-in: LOS_ORACLE_INTERVAL_SECS) ---
+    if let Some(oracle_cfg) = oracle_feed::OracleFeedConfig::from_env() {
+        tokio::spawn(oracle_feed::run_oracle_feed(
+            oracle_cfg,
+            Arc::clone(&database),
+            Arc::clone(&metrics),
+        ));
+    }
+
+    // --- Quarantine resync: pull full state from peers while diverged ---
+    {
+        let q = Arc::clone(&state_quarantine);
+        let q_ledger = Arc::clone(&ledger);
+        let q_tx = tx_out.clone();
+        let q_addr = my_address.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let attempt = {
+                    let mut q = safe_lock(&q);
+                    q.take_resync(now).then(|| q.status().resync_attempts)
+                };
+                if let Some(attempt) = attempt {
+                    // Advertise 0 blocks: peers only answer requesters that are behind,
+                    // and a diverged node usually has as many blocks as they do
+                    println!(
+                        "🚨 Quarantined — requesting full state resync (attempt {}, local blocks: {})",
+                        attempt,
+                        safe_lock(&q_ledger).blocks.len()
+                    );
+                    let _ = q_tx.send(sync_codec::sync_request(&q_addr, 0)).await;
+                }
+            }
+        });
+    }
 
     // ══════════════════════════════════════════════════════════════════════
     // VALIDATOR REWARD SYSTEM — Heartbeat recording + Epoch distribution
@@ -6410,14 +9787,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let reward_my_addr = my_address.clone();
     let reward_live_peers = Arc::clone(&live_peers);
     let reward_local_validators = Arc::clone(&local_registered_validators);
-    let reward_sk = Zeroizing::new(keys.secret_key.clone());
-    let reward_pk = keys.public_key.clone();
+    let reward_signer = signer.clone();
+    let reward_pk = node_public_key.clone();
     let reward_tx = tx_out.clone(); // For gossiping reward/fee Mint blocks + heartbeat broadcasts
+    let reward_pending = Arc::clone(&pending_distributions);
     let reward_ve = Arc::clone(&validator_endpoints); // For HTTP heartbeat fallback
     tokio::spawn(async move {
         // Testnet: shorter heartbeat interval (10s) for 2-minute epochs
         // Mainnet: 60s heartbeat for 30-day epochs
-        let heartbeat_secs = if los_core::is_testnet_build() { 10 } else { 60 };
+        let heartbeat_secs = if los_core::is_testnet() { 10 } else { 60 };
         let mut interval = tokio::time::interval(Duration::from_secs(heartbeat_secs));
 
         // HTTP heartbeat fallback: when gossipsub is down, directly ping
@@ -6442,7 +9820,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Format: VALIDATOR_HEARTBEAT:<address>:<timestamp>:<pk_hex>:<sig_hex>
             {
                 let message = format!("VALIDATOR_HEARTBEAT:{}:{}", reward_my_addr, now);
-                if let Ok(sig) = los_crypto::sign_message(message.as_bytes(), &reward_sk) {
+                if let Ok(sig) = reward_signer.sign(message.as_bytes()) {
                     let sig_hex = hex::encode(&sig);
                     let pk_hex = hex::encode(&reward_pk);
                     let hb_msg = format!(
@@ -6471,7 +9849,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         "VALIDATOR_HEARTBEAT_PROXY:{}:{}:{}",
                         addr, reward_my_addr, now
                     );
-                    if let Ok(sig) = los_crypto::sign_message(message.as_bytes(), &reward_sk) {
+                    if let Ok(sig) = reward_signer.sign(message.as_bytes()) {
                         let sig_hex = hex::encode(&sig);
                         let proxy_msg = format!(
                             "VALIDATOR_HEARTBEAT_PROXY:{}:{}:{}:{}:{}",
@@ -6613,11 +9991,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Split epoch processing into phases to minimize lock hold time.
             // Holding reward_pool for the full ~280 lines including CPU-intensive PoW + signing
             // blocks ALL HTTP routes that touch ledger or reward_pool for seconds.
-            // Phase 1 (pool lock) → Phase 2 (no lock, CPU work) → Phase 3 (ledger lock, write)
-            let (gossip_queue, fee_gossip_queue) = {
+            // Phase 1 (pool lock) → Phase 2 (propose for co-signing) → Phase 3 (quorum
+            // reached: PoW + signing without locks, then a brief ledger lock to write)
+            let mut gossip_queue = {
                 // ═══════════════════════════════════════════════════════════════════
                 // PHASE 1: Epoch check + reward calculation (pool lock only, fast)
                 // ═══════════════════════════════════════════════════════════════════
+                // Ledger inputs of a leader's distribution (stake weights, fee
+                // shares) are read before the pool lock: the lock order is
+                // ledger → pool.
+                let due_validators: Option<Vec<String>> = {
+                    let pool = safe_lock(&reward_pool_bg);
+                    pool.is_epoch_complete(now)
+                        .then(|| pool.validators.keys().cloned().collect())
+                };
+                let mut ledger_inputs = due_validators.map(|addrs| {
+                    let l = safe_lock(&reward_ledger);
+                    let stakes: Vec<(String, u128)> = addrs
+                        .into_iter()
+                        .filter_map(|addr| {
+                            let balance = l.accounts.get(&addr)?.balance;
+                            Some((addr, balance))
+                        })
+                        .collect();
+                    let fees = l.accumulated_fees_cil;
+                    let fee_data = if fees > 0 {
+                        let eligible: Vec<(String, u128)> = l
+                            .accounts
+                            .iter()
+                            .filter(|(_, s)| s.is_validator && s.balance >= MIN_VALIDATOR_STAKE_CIL)
+                            .map(|(addr, s)| {
+                                let weight = calculate_voting_power(s.balance);
+                                (addr.clone(), weight)
+                            })
+                            .collect();
+                        let total_weight: u128 = eligible.iter().map(|(_, w)| *w).sum();
+                        if total_weight > 0 && !eligible.is_empty() {
+                            Some((fees, eligible))
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+                    (stakes, fee_data)
+                }); // ledger released
+
                 let (rewards, is_leader, completed_epoch, fee_data) = {
                     let mut pool = safe_lock(&reward_pool_bg);
 
@@ -6665,6 +10084,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         };
 
                         pool.set_expected_heartbeats(heartbeat_secs);
+                        let was_fee_only = pool.fee_only_since_epoch.is_some();
 
                         // Only the leader distributes rewards.
                         // Non-leaders just advance the epoch (reset heartbeats, increment counter)
@@ -6676,48 +10096,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         // causing each to deduct different amounts (based on local heartbeat data)
                         // and create conflicting reward blocks → chain divergence → blacklisting.
                         let (rewards, completed_epoch, fee_data) = if is_leader {
-                            // Refresh stake weights (brief ledger lock)
-                            {
-                                let l = safe_lock(&reward_ledger);
-                                let addrs: Vec<String> = pool.validators.keys().cloned().collect();
-                                for addr in &addrs {
-                                    if let Some(acct) = l.accounts.get(addr) {
-                                        pool.update_stake(addr, acct.balance);
-                                    }
-                                }
-                            } // ledger released
+                            let (stakes, fee_data) = ledger_inputs.take().unwrap_or_default();
+                            // Refresh stake weights
+                            for (addr, balance) in &stakes {
+                                pool.update_stake(addr, *balance);
+                            }
 
                             let rewards = pool.distribute_epoch_rewards();
                             pool.set_expected_heartbeats(heartbeat_secs);
                             let completed_epoch = pool.current_epoch.saturating_sub(1);
 
-                            // Collect fee distribution data
-                            let fee_data = {
-                                let l = safe_lock(&reward_ledger);
-                                let fees = l.accumulated_fees_cil;
-                                if fees > 0 {
-                                    let eligible: Vec<(String, u128)> = l
-                                        .accounts
-                                        .iter()
-                                        .filter(|(_, s)| {
-                                            s.is_validator && s.balance >= MIN_VALIDATOR_STAKE_CIL
-                                        })
-                                        .map(|(addr, s)| {
-                                            let weight = calculate_voting_power(s.balance);
-                                            (addr.clone(), weight)
-                                        })
-                                        .collect();
-                                    let total_weight: u128 = eligible.iter().map(|(_, w)| *w).sum();
-                                    if total_weight > 0 && !eligible.is_empty() {
-                                        Some((fees, eligible, total_weight))
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                }
-                            };
-
                             if rewards.is_empty() {
                                 println!(
                                     "🏆 Epoch {} complete: no eligible validators for rewards",
@@ -6729,275 +10117,158 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         } else {
                             // NON-LEADER: Just advance epoch, don't distribute
                             let completed_epoch = pool.current_epoch;
-                            pool.advance_epoch_only();
-                            pool.set_expected_heartbeats(heartbeat_secs);
-                            println!(
-                                "🏆 Epoch {} complete: not leader, waiting for reward gossip",
-                                completed_epoch
-                            );
-                            (Vec::new(), completed_epoch, None)
-                        };
-
-                        (rewards, is_leader, completed_epoch, fee_data)
-                    }
-                }; // pool lock RELEASED here — all HTTP routes unblocked
-
-                let mut gossip_queue: Vec<String> = Vec::new();
-                let mut fee_gossip_queue: Vec<String> = Vec::new();
-
-                if !rewards.is_empty() && is_leader {
-                    println!("👑 This node is the epoch leader — creating reward blocks");
-
-                    // ═══════════════════════════════════════════════════════════
-                    // PHASE 2a: Collect account states (brief ledger lock)
-                    // ═══════════════════════════════════════════════════════════
-                    let now_ts = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-
-                    let mut block_templates: Vec<(String, u128, Block)> = Vec::new();
-                    {
-                        let l = safe_lock(&reward_ledger);
-                        for (addr, reward_cil) in &rewards {
-                            if l.distribution.remaining_supply < *reward_cil {
-                                eprintln!(
-                                    "⚠️ Reward skipped for {}: insufficient remaining supply",
-                                    get_short_addr(addr)
-                                );
-                                continue;
-                            }
-                            let state = l.accounts.get(addr).cloned().unwrap_or(AccountState {
-                                head: "0".to_string(),
-                                balance: 0,
-                                block_count: 0,
-                                is_validator: false,
-                            });
-                            block_templates.push((
-                                addr.clone(),
-                                *reward_cil,
-                                Block {
-                                    block_type: BlockType::Mint,
-                                    account: addr.clone(),
-                                    previous: state.head.clone(),
-                                    link: format!("REWARD:EPOCH:{}", completed_epoch),
-                                    amount: *reward_cil,
-                                    fee: 0,
-                                    timestamp: now_ts,
-                                    public_key: hex::encode(&reward_pk),
-                                    signature: String::new(),
-                                    work: 0,
-                                },
-                            ));
-                        }
-                    } // ledger released — HTTP routes unblocked during CPU work
-
-                    // ═══════════════════════════════════════════════════════════
-                    // PHASE 2b: PoW + Signing (NO LOCKS HELD — CPU intensive)
-                    // ═══════════════════════════════════════════════════════════
-                    let mut signed_blocks: Vec<(String, u128, Block)> = Vec::new();
-                    for (addr, reward_cil, mut blk) in block_templates {
-                        compute_pow_inline(&mut blk, 0);
-                        let signing_hash = blk.signing_hash();
-                        blk.signature = match try_sign_hex(signing_hash.as_bytes(), &reward_sk) {
-                            Ok(sig) => sig,
-                            Err(e) => {
-                                eprintln!(
-                                    "❌ Failed to sign reward block for {}: {}",
-                                    get_short_addr(&addr),
-                                    e
-                                );
-                                continue;
-                            }
-                        };
-                        signed_blocks.push((addr, reward_cil, blk));
-                    }
-
-                    // ═══════════════════════════════════════════════════════════
-                    // PHASE 3a: Process signed reward blocks (ledger lock, fast)
-                    // ═══════════════════════════════════════════════════════════
-                    {
-                        let mut l = safe_lock(&reward_ledger);
-                        let mut total_credited: u128 = 0;
-                        for (addr, reward_cil, reward_blk) in &signed_blocks {
-                            // Re-check previous hash in case ledger changed during signing
-                            // (another block may have been processed for this account).
-                            // If the previous hash is stale, skip — next epoch will retry.
-                            if let Some(acct) = l.accounts.get(addr) {
-                                if acct.head != reward_blk.previous {
-                                    eprintln!(
-                                        "⚠️ Reward block stale for {} (head changed) — will retry next epoch",
-                                        get_short_addr(addr)
-                                    );
-                                    continue;
-                                }
-                            }
-                            match l.process_block(reward_blk) {
-                                Ok(result) => {
-                                    let hash = result.into_hash();
-                                    total_credited += reward_cil;
-                                    gossip_queue.push(
-                                        serde_json::to_string(reward_blk).unwrap_or_default(),
-                                    );
-                                    println!(
-                                        "💰 Reward Mint: {} → {} LOS (block: {})",
-                                        get_short_addr(addr),
-                                        reward_cil / CIL_PER_LOS,
-                                        &hash[..12]
-                                    );
-                                }
-                                Err(e) => {
-                                    eprintln!(
-                                        "❌ Reward block failed for {}: {}",
-                                        get_short_addr(addr),
-                                        e
-                                    );
-                                }
-                            }
-                        }
-                        if total_credited > 0 {
-                            SAVE_DIRTY.store(true, Ordering::Release);
-                            println!(
-                                "🏆 Epoch {} rewards: {} LOS distributed to {} validators",
-                                completed_epoch,
-                                total_credited / CIL_PER_LOS,
-                                rewards.len()
-                            );
-                        }
-                    } // ledger released
-                }
-
-                // ═══════════════════════════════════════════════════════════════════
-                // FEE DISTRIBUTION (same phase split: collect → sign → write)
-                // ═══════════════════════════════════════════════════════════════════
-                if is_leader {
-                    if let Some((fees_to_distribute, eligible, total_weight)) = fee_data {
-                        let now_ts = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs();
-
-                        // Phase A: Collect account states (brief lock)
-                        let mut fee_templates: Vec<(String, u128, Block)> = Vec::new();
-                        {
-                            let l = safe_lock(&reward_ledger);
-                            for (addr, weight) in &eligible {
-                                let fee_share =
-                                    fees_to_distribute.checked_mul(*weight).unwrap_or(0)
-                                        / total_weight;
-                                if fee_share == 0 {
-                                    continue;
-                                }
-                                let state = l.accounts.get(addr).cloned().unwrap_or(AccountState {
-                                    head: "0".to_string(),
-                                    balance: 0,
-                                    block_count: 0,
-                                    is_validator: false,
-                                });
-                                fee_templates.push((
-                                    addr.clone(),
-                                    fee_share,
-                                    Block {
-                                        block_type: BlockType::Mint,
-                                        account: addr.clone(),
-                                        previous: state.head.clone(),
-                                        link: format!("FEE_REWARD:EPOCH:{}", completed_epoch),
-                                        amount: fee_share,
-                                        fee: 0,
-                                        timestamp: now_ts,
-                                        public_key: hex::encode(&reward_pk),
-                                        signature: String::new(),
-                                        work: 0,
-                                    },
-                                ));
-                            }
-                        } // ledger released
-
-                        // Phase B: PoW + Sign (NO LOCKS)
-                        let mut signed_fee_blocks: Vec<(String, u128, Block)> = Vec::new();
-                        for (addr, fee_share, mut blk) in fee_templates {
-                            compute_pow_inline(&mut blk, 0);
-                            let signing_hash = blk.signing_hash();
-                            blk.signature = match try_sign_hex(signing_hash.as_bytes(), &reward_sk)
-                            {
-                                Ok(sig) => sig,
-                                Err(e) => {
-                                    eprintln!(
-                                        "❌ Fee reward sign failed for {}: {}",
-                                        get_short_addr(&addr),
-                                        e
-                                    );
-                                    continue;
-                                }
-                            };
-                            signed_fee_blocks.push((addr, fee_share, blk));
+                            pool.advance_epoch_only();
+                            pool.set_expected_heartbeats(heartbeat_secs);
+                            println!(
+                                "🏆 Epoch {} complete: not leader, waiting for reward gossip",
+                                completed_epoch
+                            );
+                            (Vec::new(), completed_epoch, None)
+                        };
+                        if !was_fee_only && pool.fee_only_since_epoch.is_some() {
+                            log_fee_only_transition(&pool);
                         }
 
-                        // Phase C: Process signed fee blocks (brief lock)
-                        {
-                            let mut l = safe_lock(&reward_ledger);
-                            let mut total_fee_credited: u128 = 0;
-                            for (addr, fee_share, fee_blk) in &signed_fee_blocks {
-                                // Re-check previous hash for staleness
-                                if let Some(acct) = l.accounts.get(addr) {
-                                    if acct.head != fee_blk.previous {
-                                        eprintln!(
-                                            "⚠️ Fee block stale for {} — will retry next epoch",
-                                            get_short_addr(addr)
-                                        );
-                                        continue;
-                                    }
-                                }
-                                // FEE_REWARD supply handling is now in process_block() itself.
-                                // No need for save/restore — process_block skips remaining_supply
-                                // deduction for FEE_REWARD: Mint blocks automatically.
-                                match l.process_block(fee_blk) {
-                                    Ok(result) => {
-                                        let hash = result.into_hash();
-                                        total_fee_credited += fee_share;
-                                        fee_gossip_queue.push(
-                                            serde_json::to_string(fee_blk).unwrap_or_default(),
-                                        );
-                                        println!(
-                                            "💸 Fee Reward: {} → {} CIL (block: {})",
-                                            get_short_addr(addr),
-                                            fee_share,
-                                            &hash[..12]
-                                        );
-                                    }
-                                    Err(e) => {
-                                        eprintln!(
-                                            "❌ Fee reward block failed for {}: {}",
-                                            get_short_addr(addr),
-                                            e
-                                        );
-                                    }
-                                }
+                        (rewards, is_leader, completed_epoch, fee_data)
+                    }
+                }; // pool lock RELEASED here — all HTTP routes unblocked
+
+                // ═══════════════════════════════════════════════════════════════════
+                // PHASE 2: Propose the epoch's distributions for co-signing
+                // ═══════════════════════════════════════════════════════════════════
+                // Payouts are minted only after a validator quorum signed them
+                // (los_core::reward_cosign). With a single validator our own
+                // signature is the quorum and PHASE 3 mints on this same tick.
+                let mut gossip_queue: Vec<String> = Vec::new();
+                if is_leader {
+                    let mut distributions: Vec<EpochDistribution> = Vec::new();
+                    if !rewards.is_empty() {
+                        let l = safe_lock(&reward_ledger);
+                        let payouts = rewards.iter().filter(|(addr, reward_cil)| {
+                            let covered = l.distribution.remaining_supply >= *reward_cil;
+                            if !covered {
+                                eprintln!(
+                                    "⚠️ Reward skipped for {}: insufficient remaining supply",
+                                    get_short_addr(addr)
+                                );
                             }
-                            if total_fee_credited > 0 {
-                                l.accumulated_fees_cil =
-                                    l.accumulated_fees_cil.saturating_sub(total_fee_credited);
-                                SAVE_DIRTY.store(true, Ordering::Release);
+                            covered
+                        });
+                        distributions.push(EpochDistribution::new(
+                            DistributionKind::Reward,
+                            completed_epoch,
+                            payouts.cloned(),
+                        ));
+                    } // ledger released
+                    if let Some((fees_to_distribute, eligible)) = fee_data {
+                        // Pro rata by voting power; rounding dust stays for the next epoch
+                        distributions.push(EpochDistribution::new(
+                            DistributionKind::Fee,
+                            completed_epoch,
+                            pro_rata_shares(fees_to_distribute, &eligible),
+                        ));
+                    }
+
+                    let mut pending = safe_lock(&reward_pending);
+                    for dist in distributions.into_iter().filter(|d| !d.payouts.is_empty()) {
+                        match CoSignature::sign_with(&dist, &reward_my_addr, &reward_pk, |data| {
+                            reward_signer.sign(data)
+                        }) {
+                            Ok(own) => {
+                                let cert = DistributionCertificate {
+                                    distribution: dist,
+                                    signatures: vec![own],
+                                };
                                 println!(
-                                    "💸 Epoch {} fee distribution: {} CIL ({} LOS) to {} validators",
+                                    "📝 Epoch {} {:?} distribution proposed: {} payouts, awaiting validator co-signatures",
                                     completed_epoch,
-                                    total_fee_credited,
-                                    total_fee_credited / CIL_PER_LOS,
-                                    eligible.len()
+                                    cert.distribution.kind,
+                                    cert.distribution.payouts.len()
                                 );
+                                gossip_queue.push(format!(
+                                    "{}{}",
+                                    reward_cosign::PROPOSE_PREFIX,
+                                    serde_json::to_string(&cert).unwrap_or_default()
+                                ));
+                                pending.insert(cert.distribution.digest(), (cert, now));
                             }
-                        } // ledger released
+                            Err(e) => eprintln!(
+                                "❌ Failed to sign epoch {} distribution: {}",
+                                completed_epoch, e
+                            ),
+                        }
                     }
                 }
 
-                (gossip_queue, fee_gossip_queue)
+                gossip_queue
             };
 
-            // Send all queued gossip messages (reward + fee blocks) after all locks released
-            for msg in &gossip_queue {
-                let _ = reward_tx.send(msg.clone()).await;
+            // ═══════════════════════════════════════════════════════════════════
+            // PHASE 3: Mint the distributions that reached a validator quorum
+            // ═══════════════════════════════════════════════════════════════════
+            // Co-signatures arrive via REWARD_SIGN gossip between ticks. A proposal
+            // that gathers no quorum within an epoch is dropped — like an offline
+            // leader, its epoch is simply not paid out.
+            let certified: Vec<DistributionCertificate> = {
+                let validators = safe_lock(&reward_ledger).reward_validator_set();
+                let max_age = safe_lock(&reward_pool_bg).epoch_duration_secs;
+                let mut pending = safe_lock(&reward_pending);
+                pending.retain(|_, (cert, proposed_at)| {
+                    let expired = now.saturating_sub(*proposed_at) > max_age;
+                    if expired {
+                        eprintln!(
+                            "⚠️ Epoch {} {:?} distribution expired without a validator quorum ({} signatures)",
+                            cert.distribution.epoch,
+                            cert.distribution.kind,
+                            cert.signatures.len()
+                        );
+                    }
+                    !expired
+                });
+                let ready: Vec<String> = pending
+                    .iter()
+                    .filter(|(_, (cert, _))| cert.verify(&validators).is_ok())
+                    .map(|(digest, _)| digest.clone())
+                    .collect();
+                ready
+                    .iter()
+                    .filter_map(|digest| pending.remove(digest))
+                    .map(|(cert, _)| cert)
+                    .collect()
+            }; // locks released — PoW + signing below runs without them
+            let claim_payout = los_core::chain_spec::active().reward_payout == RewardPayout::Claim;
+            for cert in &certified {
+                if claim_payout && cert.distribution.kind == DistributionKind::Reward {
+                    // Claim model: accrue instead of minting (reward_claims.rs).
+                    // Our pool was already debited by distribute_epoch_rewards.
+                    if safe_lock(&reward_pool_bg).accrue_distribution(cert) {
+                        println!(
+                            "🏆 Epoch {} rewards accrued: {} CIL to {} validators, {} co-signers",
+                            cert.distribution.epoch,
+                            cert.distribution.total().unwrap_or(0),
+                            cert.distribution.payouts.len(),
+                            cert.signatures.len()
+                        );
+                        gossip_queue.push(format!(
+                            "{}{}",
+                            reward_claims::ACCRUE_PREFIX,
+                            serde_json::to_string(cert).unwrap_or_default()
+                        ));
+                        mark_dirty();
+                    }
+                    continue;
+                }
+                gossip_queue.extend(mint_certified_distribution(
+                    cert,
+                    &reward_ledger,
+                    &reward_pool_bg,
+                    &reward_pk,
+                    &*reward_signer,
+                ));
             }
-            for msg in &fee_gossip_queue {
+
+            // Send all queued gossip messages (proposals + minted blocks) after all locks released
+            for msg in &gossip_queue {
                 let _ = reward_tx.send(msg.clone()).await;
             }
         }
@@ -7010,6 +10281,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_secs(3)).await; // Wait for P2P to initialize
+        if testnet_config::is_dev_mode() {
+            println!("🧪 Dev mode: bootstrap dialing disabled (single-node chain)");
+            return;
+        }
         let bootstrap_list = get_bootstrap_nodes();
         if bootstrap_list.is_empty() {
             println!(
@@ -7059,7 +10334,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             tokio::time::sleep(Duration::from_secs(3)).await;
             let block_count = safe_lock(&ledger_boot).blocks.len();
             let _ = tx_boot
-                .send(format!("SYNC_REQUEST:{}:{}", my_addr_boot, block_count))
+                .send(sync_codec::sync_request(&my_addr_boot, block_count))
                 .await;
             println!(
                 "📡 Requesting state sync from peers (local blocks: {})",
@@ -7068,15 +10343,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         // Periodic ID re-announce (every 15s) so late-joining peers discover us.
+        // Gaps left by dropped gossip are filled by delta sync (driven from
+        // the event loop, see delta_sync.rs), with SYNC_REQUEST as fallback.
         let mut interval = tokio::time::interval(Duration::from_secs(15));
-        let mut sync_counter: u64 = 0;
         loop {
             interval.tick().await;
-            sync_counter += 1;
-            let (s, block_count) = {
-                let l = safe_lock(&ledger_boot);
-                (l.distribution.remaining_supply, l.blocks.len())
-            };
+            let s = safe_lock(&ledger_boot).distribution.remaining_supply;
             let ts = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -7084,64 +10356,280 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let _ = tx_boot
                 .send(format!("ID:{}:{}:{}", my_addr_boot, s, ts))
                 .await;
-
-            // Periodic state sync request every 30s (2 × 15s ticks)
-            // to catch any gossip messages dropped by GossipSub. Without this,
-            // a node that misses a gossip block will have a permanently stale
-            // view of that account's chain. The sync mechanism fills in gaps
-            // by comparing full state with peers.
-            if sync_counter.is_multiple_of(2) {
-                let _ = tx_boot
-                    .send(format!("SYNC_REQUEST:{}:{}", my_addr_boot, block_count))
-                    .await;
-            }
         }
     });
 
     // ══════════════════════════════════════════════════════════════════════
     // PEX: Peer Exchange — Periodically broadcast known validator endpoints
     // ══════════════════════════════════════════════════════════════════════
-    // Every 5 minutes, broadcast our known validator endpoints to all
-    // connected peers via gossipsub. This enables network-wide discovery of
-    // validator endpoints beyond the hardcoded bootstrap list.
-    // Endpoints can be .onion, IP, or domain — Tor is optional.
+    // Every 5 minutes (1 on testnet), broadcast a signed subset of recently
+    // seen validator endpoints and hand the freshest ones to the P2P layer,
+    // which re-dials them with the bootstrap list when the mesh is thin.
+    // Endpoints can be .onion, IP, or domain — Tor is optional. See pex.rs.
     let pex_tx = tx_out.clone();
     let pex_ve = Arc::clone(&validator_endpoints);
+    let pex_live = Arc::clone(&live_peers);
+    let pex_book_send = Arc::clone(&pex_book);
+    let pex_addr = my_address.clone();
+    let pex_signer = signer.clone();
     tokio::spawn(async move {
         // Wait for initial bootstrapping to complete
         tokio::time::sleep(Duration::from_secs(30)).await;
-        let pex_interval_secs = if los_core::is_testnet_build() {
-            60
-        } else {
-            300
-        };
+        let pex_interval_secs = if los_core::is_testnet() { 60 } else { 300 };
         let mut interval = tokio::time::interval(Duration::from_secs(pex_interval_secs));
         loop {
             interval.tick().await;
-            let endpoints: Vec<serde_json::Value> = {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let (entries, targets) = {
                 let ve = safe_lock(&pex_ve);
-                ve.iter()
-                    .map(|(addr, host)| {
-                        serde_json::json!({
-                            "address": addr,
-                            "host_address": host,
-                            "onion_address": host, // backward compat for older nodes
-                        })
-                    })
-                    .collect()
+                let live = safe_lock(&pex_live);
+                let mut book = safe_lock(&pex_book_send);
+                if let Some(host) = ve.get(&pex_addr) {
+                    book.observe(&pex_addr, host, now);
+                }
+                // Heartbeats prove liveness of the validator behind an endpoint
+                for (addr, seen) in live.iter() {
+                    if let Some(host) = ve.get(addr) {
+                        book.observe(addr, host, *seen);
+                    }
+                }
+                (book.subset(now), book.dial_targets(now, &pex_addr))
             };
-            if !endpoints.is_empty() {
-                let msg = serde_json::json!({
-                    "endpoints": endpoints,
-                    "timestamp": std::time::SystemTime::now()
+            if !entries.is_empty() {
+                match pex::PexMessage::signed(&pex_addr, &*pex_signer, now, entries) {
+                    Ok(msg) => {
+                        if let Ok(json) = serde_json::to_string(&msg) {
+                            let _ = pex_tx.send(format!("{}{}", pex::PEX_PREFIX, json)).await;
+                        }
+                    }
+                    Err(e) => eprintln!("⚠️ {}", e),
+                }
+            }
+            if !targets.is_empty() {
+                let _ = pex_tx
+                    .send(format!("{}{}", pex::PEX_PEERS_PREFIX, targets.join(",")))
+                    .await;
+            }
+        }
+    });
+
+    // ══════════════════════════════════════════════════════════════════════
+    // TELEMETRY — opt-in signed node summary (--telemetry / LOS_TELEMETRY=1)
+    // ══════════════════════════════════════════════════════════════════════
+    // POSTed to LOS_TELEMETRY_URL or gossiped as TELEMETRY:{json}. See telemetry.rs.
+    if telemetry_cfg.publish {
+        let tm_cfg = telemetry_cfg.clone();
+        let tm_tx = tx_out.clone();
+        let tm_ledger = Arc::clone(&ledger);
+        let tm_metrics = Arc::clone(&metrics);
+        let tm_latency = Arc::clone(&telemetry_latency);
+        let tm_addr = my_address.clone();
+        let tm_signer = signer.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            let mut interval = tokio::time::interval(Duration::from_secs(tm_cfg.interval_secs));
+            loop {
+                interval.tick().await;
+                let (height, state_root) = {
+                    let l = safe_lock(&tm_ledger);
+                    (l.blocks.len() as u64, l.compute_state_root())
+                };
+                let summary = telemetry::TelemetrySummary {
+                    node: tm_addr.clone(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    network: if los_core::chain_id() == 1 {
+                        "los-mainnet".to_string()
+                    } else {
+                        "los-testnet".to_string()
+                    },
+                    height,
+                    peer_count: tm_metrics.connected_peers.get().max(0) as u64,
+                    state_root,
+                    latency: safe_lock(&tm_latency).take_stats(),
+                    timestamp: std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_secs(),
-                });
-                let _ = pex_tx.send(format!("PEER_LIST:{}", msg)).await;
+                };
+                let report = match telemetry::TelemetryReport::signed(summary, &*tm_signer) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        eprintln!("⚠️ {}", e);
+                        continue;
+                    }
+                };
+                match &tm_cfg.url {
+                    Some(url) => {
+                        let sent = match peer_http_client(url) {
+                            Ok(client) => client
+                                .post(url)
+                                .json(&report)
+                                .send()
+                                .await
+                                .map_err(|e| e.to_string()),
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = sent {
+                            eprintln!("⚠️ Telemetry POST to {} failed: {}", url, e);
+                        }
+                    }
+                    None => {
+                        if let Ok(json) = serde_json::to_string(&report) {
+                            let _ = tm_tx
+                                .send(format!("{}{}", telemetry::TELEMETRY_PREFIX, json))
+                                .await;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    // ══════════════════════════════════════════════════════════════════════
+    // CHECKPOINT COLD SYNC — --trust-checkpoint <id>
+    // ══════════════════════════════════════════════════════════════════════
+    // Bootstraps trust from an operator-supplied checkpoint instead of genesis:
+    // find the checkpoint on a peer, download that peer's state, and adopt it
+    // only if the state contains the checkpoint block and the checkpoint's
+    // signatures reach quorum against it. Retries every 30s until it succeeds.
+    if let Some(trusted_id) = trust_checkpoint.clone() {
+        let cs_ledger = Arc::clone(&ledger);
+        let cs_ve = Arc::clone(&validator_endpoints);
+        let cs_rp = Arc::clone(&reward_pool);
+        let cs_sm = Arc::clone(&slashing_manager);
+        let cs_cm = Arc::clone(&checkpoint_manager);
+        let cs_my_addr = my_address.clone();
+
+        tokio::spawn(async move {
+            let already_trusted = safe_lock(&cs_cm)
+                .get_all_checkpoints()
+                .unwrap_or_default()
+                .iter()
+                .any(|cp| cp.calculate_id() == trusted_id);
+            if already_trusted {
+                println!(
+                    "🔐 Trusted checkpoint {} already stored — skipping cold sync",
+                    &trusted_id[..trusted_id.len().min(16)]
+                );
+                return;
             }
+            println!(
+                "🔐 Cold sync: bootstrapping from trusted checkpoint {}",
+                &trusted_id[..trusted_id.len().min(16)]
+            );
+
+            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                let peers: Vec<String> = {
+                    let ve = safe_lock(&cs_ve);
+                    ve.iter()
+                        .filter(|(addr, _)| **addr != cs_my_addr)
+                        .map(|(_, host)| ensure_host_port(host, api_port))
+                        .collect()
+                };
+                if peers.is_empty() {
+                    println!("⚠️ Cold sync: no peer endpoints known yet");
+                    continue;
+                }
+                for peer_host in &peers {
+                    let client = match peer_http_client(peer_host) {
+                        Ok(c) => c,
+                        Err(e) => {
+                            println!("⚠️ Cold sync: {}", e);
+                            continue;
+                        }
+                    };
+                    let cp = match checkpoint_sync::fetch_trusted_checkpoint(
+                        &client,
+                        peer_host,
+                        &trusted_id,
+                    )
+                    .await
+                    {
+                        Ok(cp) => cp,
+                        Err(e) => {
+                            println!("⚠️ Cold sync from {}: {}", peer_host, e);
+                            continue;
+                        }
+                    };
+                    let (incoming, roots) = match fetch_peer_snapshot(peer_host, 0).await {
+                        Ok(Some(snapshot)) => snapshot,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            println!("⚠️ Cold sync from {}: {}", peer_host, e);
+                            continue;
+                        }
+                    };
+                    if let Err(e) = checkpoint_sync::verify_anchor(&incoming, &cp, roots.as_ref()) {
+                        println!("🚫 Cold sync: rejected state from {}: {}", peer_host, e);
+                        continue;
+                    }
+                    let height = cp.height;
+                    match merge_synced_ledger(incoming, &cs_ledger, &cs_rp, &cs_sm) {
+                        Ok(added) => {
+                            if let Err(e) = safe_lock(&cs_cm).store_checkpoint(cp) {
+                                eprintln!(
+                                    "⚠️ Cold sync: failed to store trusted checkpoint: {}",
+                                    e
+                                );
+                            }
+                            println!("✅ Cold sync from {} complete: {} blocks merged, finality anchored at height {}",
+                                peer_host, added, height);
+                            return;
+                        }
+                        Err(e) => println!("⚠️ Cold sync merge from {} failed: {}", peer_host, e),
+                    }
+                }
+            }
+        });
+    }
+
+    // ══════════════════════════════════════════════════════════════════════
+    // CHECKPOINT CONFLICT — halt state + pending resolution replay
+    // ══════════════════════════════════════════════════════════════════════
+    // An unresolved conflict keeps finality halted across restarts. A replay
+    // journal left by `los-node resolve-checkpoint` is applied once a peer on
+    // the canonical branch is reachable (see checkpoint_conflict.rs).
+    {
+        let open = safe_lock(&checkpoint_manager)
+            .get_conflicts()
+            .unwrap_or_default();
+        for c in &open {
+            eprintln!(
+                "🚨 Finality HALTED: unresolved checkpoint conflict at height {} — see GET /checkpoints/conflicts",
+                c.height
+            );
         }
-    });
+        metrics.finality_halted.set(!open.is_empty() as i64);
+        match database.load_checkpoint_replay() {
+            Ok(Some(journal)) => {
+                let rp_ve = Arc::clone(&validator_endpoints);
+                let rp_my_addr = my_address.clone();
+                let peers = move || -> Vec<String> {
+                    safe_lock(&rp_ve)
+                        .iter()
+                        .filter(|(addr, _)| **addr != rp_my_addr)
+                        .map(|(_, host)| ensure_host_port(host, api_port))
+                        .collect()
+                };
+                tokio::spawn(checkpoint_conflict::run_pending_replay(
+                    journal,
+                    Arc::clone(&ledger),
+                    Arc::clone(&wasm_engine),
+                    Arc::clone(&checkpoint_manager),
+                    Arc::clone(&database),
+                    peers,
+                    base_data_dir.clone(),
+                ));
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("⚠️ {}", e),
+        }
+    }
 
     // ══════════════════════════════════════════════════════════════════════
     // BACKGROUND REST SYNC — Stale state detector & auto-recovery
@@ -7291,11 +10779,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // Wait for node to fully start before first self-ping
                 tokio::time::sleep(Duration::from_secs(60)).await;
 
-                let check_interval_secs: u64 = if los_core::is_testnet_build() {
-                    60
-                } else {
-                    120
-                };
+                let check_interval_secs: u64 = if los_core::is_testnet() { 60 } else { 120 };
                 let mut interval = tokio::time::interval(Duration::from_secs(check_interval_secs));
                 let mut consecutive_failures: u32 = 0;
 
@@ -7467,8 +10951,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let sr_ve = Arc::clone(&validator_endpoints);
         let sr_lrv = Arc::clone(&local_registered_validators);
         let sr_addr = my_address.clone();
-        let sr_sk = Zeroizing::new(keys.secret_key.clone());
-        let sr_pk = keys.public_key.clone();
+        let sr_signer = signer.clone();
+        let sr_pk = node_public_key.clone();
         let sr_tx = tx_out.clone();
         let sr_api_port = api_port;
         tokio::spawn(async move {
@@ -7533,7 +11017,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .unwrap_or_default()
                 .as_secs();
             let reg_message = format!("REGISTER_VALIDATOR:{}:{}", sr_addr, ts);
-            match los_crypto::sign_message(reg_message.as_bytes(), &sr_sk) {
+            match sr_signer.sign(reg_message.as_bytes()) {
                 Ok(sig) => {
                     let reg_msg = serde_json::json!({
                         "type": "VALIDATOR_REG",
@@ -7552,7 +11036,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     eprintln!("❌ [6/6] Failed to sign VALIDATOR_REG: {:?}", e);
                 }
             }
-            SAVE_DIRTY.store(true, Ordering::Release);
+            mark_dirty();
             println!(
                 "📡 Startup validator broadcast complete: {} (host: {})",
                 get_short_addr(&sr_addr),
@@ -7633,16 +11117,129 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Clone database, metrics, and slashing_manager for event loop
     let db_clone = Arc::clone(&database);
-    let _metrics_clone = Arc::clone(&metrics);
+    let metrics_clone = Arc::clone(&metrics);
     let slashing_clone = Arc::clone(&slashing_manager);
     let send_voters_clone = Arc::clone(&send_voters);
     let ve_event = Arc::clone(&validator_endpoints);
+    let pex_book_event = Arc::clone(&pex_book);
     let abft_event = Arc::clone(&abft_consensus);
     let live_peers = Arc::clone(&live_peers); // Shadow for event loop usage
     let rp_sync = Arc::clone(&reward_pool); // For syncing reward pool on incoming REWARD Mint blocks
 
+    // VOTE AGGREGATION: YES votes for CONFIRM_REQ are queued here and flushed once
+    // per batch interval as a single signed envelope (one Dilithium5 signature for
+    // all votes) instead of one signed CONFIRM_RES per send.
+    let vote_batcher = Arc::new(Mutex::new(VoteBatcher::new()));
+    {
+        let vb = Arc::clone(&vote_batcher);
+        let vb_tx = tx_out.clone();
+        let vb_addr = my_address.clone();
+        let vb_signer = signer.clone();
+        let vb_pk = node_public_key.clone();
+        let vb_quarantine = Arc::clone(&state_quarantine);
+        let vb_checkpoints = Arc::clone(&checkpoint_manager);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(VOTE_BATCH_INTERVAL_MS));
+            loop {
+                interval.tick().await;
+                loop {
+                    let votes = safe_lock(&vb).drain();
+                    if votes.is_empty() {
+                        break;
+                    }
+                    // QUARANTINE: a diverged node must not vote — drop queued votes.
+                    // Same while finality is halted by a checkpoint conflict.
+                    if safe_lock(&vb_quarantine).is_quarantined()
+                        || checkpoint_conflict::halted(&vb_checkpoints)
+                    {
+                        continue;
+                    }
+                    let ts = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis();
+                    let msg = if let [vote] = votes.as_slice() {
+                        // Single vote: legacy CONFIRM_RES (same size, understood by all peers)
+                        // SECURITY P0-1: Sign CONFIRM_RES with Dilithium5
+                        let payload =
+                            format!("{}:{}:YES:{}:{}", vote.tx_hash, vote.requester, vb_addr, ts);
+                        match vb_signer.sign(payload.as_bytes()) {
+                            Ok(sig) => format!(
+                                "CONFIRM_RES:{}:{}:YES:{}:{}:{}:{}",
+                                vote.tx_hash,
+                                vote.requester,
+                                vb_addr,
+                                ts,
+                                hex::encode(&sig),
+                                hex::encode(&vb_pk)
+                            ),
+                            Err(e) => {
+                                eprintln!("⚠️ Signing failed for CONFIRM_RES — skipping: {:?}", e);
+                                continue;
+                            }
+                        }
+                    } else {
+                        match VoteEnvelope::seal(&vb_addr, ts, votes, &vb_pk, |payload| {
+                            vb_signer.sign(payload).map_err(|e| format!("{:?}", e))
+                        }) {
+                            Ok(env) => env.to_message(),
+                            Err(e) => {
+                                eprintln!("⚠️ Vote batch sealing failed — skipping: {}", e);
+                                continue;
+                            }
+                        }
+                    };
+                    let _ = vb_tx.send(msg).await;
+                }
+            }
+        });
+    }
+
+    // Persistent seen-cache: suppresses replayed gossip (also across restarts)
+    let mut seen_cache = match database.seen_cache_trees().and_then(|(ids, ring)| {
+        seen_cache::SeenCache::open(
+            ids,
+            ring,
+            seen_cache::DEFAULT_SEEN_TTL_SECS,
+            seen_cache::DEFAULT_SEEN_CAPACITY,
+        )
+    }) {
+        Ok(c) => {
+            println!("🧹 Gossip seen-cache loaded ({} entries)", c.len());
+            Some(c)
+        }
+        Err(e) => {
+            eprintln!("⚠️ Gossip seen-cache unavailable (dedup disabled): {}", e);
+            None
+        }
+    };
+
+    // DELTA SYNC: start sessions / re-send stalled requests (first check after
+    // the bootstrap SYNC_REQUEST had its chance)
+    let mut delta_timer = tokio::time::interval_at(
+        tokio::time::Instant::now() + Duration::from_secs(30),
+        Duration::from_secs(15),
+    );
+
+    // Votes and checkpoint signatures are processed ahead of queued sync data
+    let mut event_lanes = gossip_lanes::LaneQueue::default();
+
     loop {
         tokio::select! {
+            _ = delta_timer.tick() => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                // A diverged node resyncs through quarantine (full state), not deltas
+                if safe_lock(&state_quarantine).is_quarantined() {
+                    continue;
+                }
+                let step = {
+                    let l = safe_lock(&ledger);
+                    safe_lock(&delta_sync_state).tick(&l, now)
+                };
+                run_delta_step(step, &delta_sync_state, &ledger, &rp_sync, &slashing_clone,
+                    &database, &tx_out, &my_address).await;
+            },
             result = stdin.next_line(), if !stdin_closed => {
                 match result {
                     Ok(Some(line)) => {
@@ -7705,98 +11302,100 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         println!("👥 Peers: {}", ab.len());
                         for (s, f) in ab.iter() { println!("  - {}: {}", s, f); }
                     },
-                    "dial" => {
-                        if p.len() == 2 {
-                            let tx = tx_out.clone();
-                            let ma = my_address.clone();
-                            let s = { let l = safe_lock(&ledger); l.distribution.remaining_supply };
-                            let target = p[1].to_string();
-                            tokio::spawn(async move {
-                                let _ = tx.send(format!("DIAL:{}", target)).await;
-                                tokio::time::sleep(Duration::from_secs(2)).await;
-                                let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                                let _ = tx.send(format!("ID:{}:{}:{}", ma, s, ts)).await;
-                            });
-                        }
+                    "dial" if p.len() == 2 => {
+                        let tx = tx_out.clone();
+                        let ma = my_address.clone();
+                        let s = { let l = safe_lock(&ledger); l.distribution.remaining_supply };
+                        let target = p[1].to_string();
+                        tokio::spawn(async move {
+                            let _ = tx.send(format!("DIAL:{}", target)).await;
+                            tokio::time::sleep(Duration::from_secs(2)).await;
+                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                            let _ = tx.send(format!("ID:{}:{}:{}", ma, s, ts)).await;
+                        });
                     },
-                    "send" => {
-                        if p.len() == 3 {
-                            let target_short = p[1];
-                            let amt_raw = match p[2].parse::<u128>() {
-                                Ok(v) if v > 0 => v,
-                                Ok(_) => {
-                                    println!("❌ Send amount must be greater than 0!");
-                                    continue;
-                                }
-                                Err(_) => {
-                                    println!("❌ Invalid amount: '{}' — must be a positive integer (LOS)", p[2]);
-                                    continue;
-                                }
-                            };
-                            let amt = amt_raw * CIL_PER_LOS;
+                    "send" if p.len() == 3 => {
+                        let target_short = p[1];
+                        let amt_raw = match p[2].parse::<u128>() {
+                            Ok(v) if v > 0 => v,
+                            Ok(_) => {
+                                println!("❌ Send amount must be greater than 0!");
+                                continue;
+                            }
+                            Err(_) => {
+                                println!("❌ Invalid amount: '{}' — must be a positive integer (LOS)", p[2]);
+                                continue;
+                            }
+                        };
+                        let amt = amt_raw * CIL_PER_LOS;
 
-                            let target_full = safe_lock(&address_book).get(target_short).cloned();
+                        let target_full = safe_lock(&address_book).get(target_short).cloned();
 
-                            if let Some(d) = target_full {
-                                // DEADLOCK Never hold L and PS simultaneously.
-                                // Step 1: Get state from Ledger (L lock only)
-                                let state = {
-                                    let l = safe_lock(&ledger);
-                                    l.accounts.get(&my_address).cloned().unwrap_or(AccountState {
-                                        head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                    })
-                                }; // L dropped
-
-                                // Step 2: Check pending total (PS lock only)
-                                // Only sum THIS sender's pending txs, not all
-                                let pending_total: u128 = safe_lock(&pending_sends).values()
-                                    .filter(|(b, _)| b.account == my_address)
-                                    .map(|(b, _)| b.amount).sum();
-
-                                if state.balance < (amt + pending_total) {
-                                    println!("❌ Insufficient balance! (Balance: {} LOS, In process: {} LOS)",
-                                        format_u128(state.balance / CIL_PER_LOS),
-                                        format_u128(pending_total / CIL_PER_LOS));
-                                    continue;
-                                }
+                        if let Some(d) = target_full {
+                            // DEADLOCK Never hold L and PS simultaneously.
+                            // Step 1: Get state from Ledger (L lock only)
+                            let state = {
+                                let l = safe_lock(&ledger);
+                                l.accounts.get(&my_address).cloned().unwrap_or(AccountState {
+                                    head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
+                                })
+                            }; // L dropped
+
+                            // Step 2: Check pending total (PS lock only)
+                            // Only sum THIS sender's pending txs, not all
+                            let pending_total: u128 = safe_lock(&pending_sends).values()
+                                .filter(|(b, _)| b.account == my_address)
+                                .map(|(b, _)| b.amount).sum();
+
+                            if state.balance < (amt + pending_total) {
+                                println!("❌ Insufficient balance! (Balance: {} LOS, In process: {} LOS)",
+                                    format_u128(state.balance / CIL_PER_LOS),
+                                    format_u128(pending_total / CIL_PER_LOS));
+                                continue;
+                            }
 
-                                // Create Send block draft
-                                let mut blk = Block {
-                                    account: my_address.clone(),
-                                    previous: state.head.clone(),
-                                    block_type: BlockType::Send,
-                                    amount: amt,
-                                    link: d.clone(),
-                                    signature: "".to_string(),
-                                    public_key: hex::encode(&keys.public_key), // Node's public key
-                                    work: 0,
-                                    timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
-                                    fee: los_core::BASE_FEE_CIL, // Protocol constant from los-core
-                                };
+                            // Create Send block draft
+                            let mut blk = Block {
+                                account: my_address.clone(),
+                                previous: state.head.clone(),
+                                block_type: BlockType::Send,
+                                amount: amt,
+                                link: d.clone(),
+                                signature: "".to_string(),
+                                public_key: hex::encode(&node_public_key), // Node's public key
+                                work: 0,
+                                timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
+                                fee: los_core::BASE_FEE_CIL, // Protocol constant from los-core
+                                valid_after: 0,
+                                expires_at: 0,
+                            };
 
-                                solve_pow(&mut blk);
-                                let signing_hash = blk.signing_hash();
-                                blk.signature = match try_sign_hex(signing_hash.as_bytes(), &secret_key) {
-                                    Ok(sig) => sig,
-                                    Err(e) => { eprintln!("❌ Signing failed: {}", e); continue; }
-                                };
-                                let hash = blk.calculate_hash();
+                            solve_pow(&mut blk);
+                            let signing_hash = blk.signing_hash();
+                            blk.signature = match try_sign_hex(signing_hash.as_bytes(), &*signer) {
+                                Ok(sig) => sig,
+                                Err(e) => { eprintln!("❌ Signing failed: {}", e); continue; }
+                            };
+                            let hash = blk.calculate_hash();
 
-                                // Save to confirmation queue
-                                safe_lock(&pending_sends).insert(hash.clone(), (blk.clone(), 0));
+                            // Save to confirmation queue
+                            safe_lock(&pending_sends).insert(hash.clone(), (blk.clone(), 0));
+                            if let Err(e) = database.save_inflight_send(&hash, &blk) {
+                                eprintln!("⚠️ In-flight journal write failed: {}", e);
+                            }
 
-                                // Broadcast confirmation request (REQ) to network
-                                let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                                // Include block data (base64) so peers can validate
-                                let block_json = serde_json::to_string(&blk).unwrap_or_default();
-                                let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
-                                let req_msg = format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, my_address, amt, ts, block_b64);
-                                let _ = tx_out.send(req_msg).await;
+                            // Broadcast confirmation request (REQ) to network
+                            let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                            // Include block data (base64) so peers can validate
+                            let block_json = serde_json::to_string(&blk).unwrap_or_default();
+                            let block_b64 = base64::engine::general_purpose::STANDARD.encode(block_json.as_bytes());
+                            let req_msg = format!("CONFIRM_REQ:{}:{}:{}:{}:{}", hash, my_address, amt, ts, block_b64);
+                            safe_lock(&netstats).note_published(&hash, ts as u64);
+                            let _ = tx_out.send(req_msg).await;
 
-                                println!("⏳ Transaction created. Requesting network confirmation (Anti Double-Spend)...");
-                            } else {
-                                println!("❌ ID {} not found. Peer must connect first.", target_short);
-                            }
+                            println!("⏳ Transaction created. Requesting network confirmation (Anti Double-Spend)...");
+                        } else {
+                            println!("❌ ID {} not found. Peer must connect first.", target_short);
                         }
                     },
                     "exit" => break,
@@ -7817,14 +11416,229 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     },
                 }
             },
-            event = rx_in.recv() => {
+            event = gossip_lanes::next_event(&mut rx_in, &mut event_lanes, &metrics_clone) => {
                 let Some(event) = event else {
                     // Network channel closed — P2P task exited/crashed
                     eprintln!("⚠️ Network channel closed, node running in offline mode");
                     // Keep node alive (API server still works) but just sleep
                     loop { tokio::time::sleep(Duration::from_secs(60)).await; }
                 };
+                // COMPACT RELAY: fetch the body only if we don't already have the block
+                if let NetworkEvent::CompactBlock { from_peer, block } = &event {
+                    let have = safe_lock(&ledger).blocks.contains_key(&block.hash);
+                    if !have {
+                        let _ = tx_out.send(format!("FETCH_BLOCK:{}:{}", from_peer, block.hash)).await;
+                    }
+                    continue;
+                }
+                if let NetworkEvent::PeerCount { connected, min_peers } = &event {
+                    metrics_clone.connected_peers.set(*connected as i64);
+                    metrics_clone.min_peers.set(*min_peers as i64);
+                    continue;
+                }
+                // PROPAGATION: receive − origin time from the gossip envelope
+                if let NetworkEvent::GossipDelay { from_peer, block_hash, delay_ms } = &event {
+                    if safe_lock(&netstats).record(from_peer, block_hash.as_deref(), *delay_ms) {
+                        metrics_clone.gossip_propagation_seconds.observe(*delay_ms as f64 / 1000.0);
+                    }
+                    continue;
+                }
+                // CHECKPOINT SIGNING: a proposer asks us directly for our signature
+                if let NetworkEvent::CheckpointSignRequest { inbound_id, from_peer, request } = &event {
+                    let already_signed = request.signed_by.iter().any(|a| a == &my_address);
+                    // Only staked validators sign: any other signature would not count
+                    let staked = safe_lock(&ledger).is_staked_validator(&my_address);
+                    let response = if request.proposer == my_address || already_signed || !staked {
+                        CheckpointSignResponse::declined(request.height, &my_address)
+                    } else {
+                        let cp = request.checkpoint();
+                        let signing_data = cp.signing_data();
+                        // SECURITY: Only sign proposals from staked validators with a valid
+                        // signature whose full state root (accounts + VM + rewards) matches our own.
+                        let proposer_pk = validator_public_key(&safe_lock(&ledger), &request.proposer);
+                        let proposer_verified = match (proposer_pk, request.proposer_signature_bytes()) {
+                            (Some(pk), Some(sig)) => los_crypto::verify_signature(&signing_data, &sig, &pk),
+                            _ => false,
+                        };
+                        let verdict = if proposer_verified {
+                            check_checkpoint_root(&cp, Some(&request.proposer), &ledger, &wasm_engine,
+                                &reward_pool, &state_quarantine, &metrics_clone)
+                        } else {
+                            quarantine::Verdict::NotComparable
+                        };
+                        if !proposer_verified {
+                            println!("🚫 Rejected checkpoint sign request: unverified proposer sig from {} (peer {})",
+                                get_short_addr(&request.proposer), from_peer);
+                            CheckpointSignResponse::declined(request.height, &my_address)
+                        } else if verdict != quarantine::Verdict::Agree {
+                            // Refuse to co-sign a root we cannot reproduce
+                            CheckpointSignResponse::declined(request.height, &my_address)
+                        } else {
+                            match signer.sign(&signing_data) {
+                                Ok(sig) => {
+                                    println!("✍️ Signed checkpoint at height {} for {}",
+                                        request.height, get_short_addr(&request.proposer));
+                                    CheckpointSignResponse::signed(request.height, &my_address, &sig)
+                                }
+                                Err(e) => {
+                                    eprintln!("⚠️ Checkpoint signing failed: {}", e);
+                                    CheckpointSignResponse::declined(request.height, &my_address)
+                                }
+                            }
+                        }
+                    };
+                    let _ = tx_out.send(response.to_message(*inbound_id)).await;
+                    continue;
+                }
+                // CHECKPOINT SIGNING: a validator answered our request
+                if let NetworkEvent::CheckpointSignResponse { from_peer, response } = &event {
+                    let Some(sig) = response.to_signature() else {
+                        continue; // Declined (not a validator, already signed, or state mismatch)
+                    };
+                    let signer_pk = validator_public_key(&safe_lock(&ledger), &sig.validator_address);
+                    let finalized = {
+                        let mut pcp = safe_lock(&pending_checkpoints);
+                        let Some(pending) = pcp.get_mut(&response.height) else {
+                            continue;
+                        };
+                        let sig_ok = signer_pk
+                            .map(|pk| los_crypto::verify_signature(&pending.signing_data, &sig.signature, &pk))
+                            .unwrap_or(false);
+                        if !sig_ok {
+                            println!("🚫 Rejected checkpoint signature from {} (peer {}): invalid signature or not a staked validator",
+                                get_short_addr(&sig.validator_address), from_peer);
+                            continue;
+                        }
+                        if !pending.add_signature(sig) {
+                            continue;
+                        }
+                        if !pending.has_quorum() {
+                            println!("✍️ Collected checkpoint signature at height {} ({}/{})",
+                                response.height, pending.checkpoint.signature_count, pending.checkpoint.validator_count);
+                            continue;
+                        }
+                        pending.checkpoint.clone()
+                    }; // Release lock before acquiring checkpoint_manager
+                    // Quorum reached — finalize locally, then gossip only the aggregate
+                    let (sc, vc) = (finalized.signature_count, finalized.validator_count);
+                    let validators = safe_lock(&ledger).reward_validator_set();
+                    let stored = checkpoint_conflict::store_finalized(&checkpoint_manager, finalized.clone(), &validators, &metrics_clone, &base_data_dir);
+                    match stored {
+                        Ok(()) => {
+                            println!("🏁 Checkpoint FINALIZED at height {} (sig_count={}/{}, quorum reached!)",
+                                response.height, sc, vc);
+                            safe_lock(&pending_checkpoints).remove(&response.height);
+                            let _ = tx_out.send(final_checkpoint_message(&finalized)).await;
+                        }
+                        Err(e) => eprintln!("⚠️ Checkpoint finalization failed at {}: {}", response.height, e),
+                    }
+                    continue;
+                }
+                // DELTA SYNC: a peer asks for our frontiers or one of our chains
+                if let NetworkEvent::DeltaSyncRequest { inbound_id, request, .. } = &event {
+                    // Do not spread a state we cannot vouch for; low-bandwidth
+                    // nodes leave bulk sync to others (the requester tries another peer)
+                    let response = if safe_lock(&state_quarantine).is_quarantined()
+                        || !los_network::bandwidth::serves_bulk_sync()
+                    {
+                        DeltaSyncResponse::Declined
+                    } else {
+                        delta_sync::answer(&safe_lock(&ledger), request)
+                    };
+                    let _ = tx_out.send(response.to_message(*inbound_id)).await;
+                    continue;
+                }
+                if let NetworkEvent::DeltaSyncFailed { from_peer } = &event {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                    if safe_lock(&delta_sync_state).note_failure(Some(from_peer), now) {
+                        run_delta_step(delta_sync::DeltaStep::FullSync, &delta_sync_state, &ledger,
+                            &rp_sync, &slashing_clone, &database, &tx_out, &my_address).await;
+                    }
+                    continue;
+                }
+                // DELTA SYNC: a frontier page or chain page for our session
+                if let NetworkEvent::DeltaSyncResponse { from_peer, response } = event {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+                    let step = {
+                        let mut ds = safe_lock(&delta_sync_state);
+                        let accepted = match response {
+                            DeltaSyncResponse::Declined => {
+                                if ds.note_failure(Some(&from_peer), now) {
+                                    Some(delta_sync::DeltaStep::FullSync)
+                                } else {
+                                    None
+                                }
+                            }
+                            frontiers @ DeltaSyncResponse::Frontiers { .. } => {
+                                let l = safe_lock(&ledger);
+                                let our_root = l.compute_state_root();
+                                let local = |a: &str| l.accounts.get(a).map(|s| (s.head.clone(), s.block_count));
+                                ds.on_frontiers(&from_peer, &frontiers, &our_root, local, now)
+                                    .then(|| ds.advance(&l, now))
+                            }
+                            chain @ DeltaSyncResponse::Chain { .. } => {
+                                ds.on_chain(&from_peer, chain).map(|blocks| {
+                                    let checked = verify_pool::global().verify(&blocks);
+                                    let mut l = safe_lock(&ledger);
+                                    let mut applied = 0u64;
+                                    let mut failed = Vec::new();
+                                    for (blk, verified) in blocks.iter().zip(checked) {
+                                        match apply_synced_block(&mut l, blk, verified, &rp_sync, &slashing_clone) {
+                                            Ok(new) => applied += new as u64,
+                                            Err(_) => failed.push(blk.clone()),
+                                        }
+                                    }
+                                    if applied > 0 {
+                                        mark_dirty();
+                                    }
+                                    ds.record(applied, failed);
+                                    ds.advance(&l, now)
+                                })
+                            }
+                        };
+                        // Persist progress (resumable); a finished session leaves nothing behind
+                        let saved = match ds.session() {
+                            Some(session) => database.save_delta_sync(session),
+                            None => database.clear_delta_sync(),
+                        };
+                        if let Err(e) = saved {
+                            eprintln!("⚠️ Failed to save delta sync progress: {}", e);
+                        }
+                        accepted.unwrap_or(delta_sync::DeltaStep::Idle)
+                    };
+                    run_delta_step(step, &delta_sync_state, &ledger, &rp_sync, &slashing_clone,
+                        &database, &tx_out, &my_address).await;
+                    continue;
+                }
                 if let NetworkEvent::NewBlock(data) = event {
+                        metrics_clone.p2p_messages_received_total.inc();
+                        metrics_clone.p2p_bytes_received_total.inc_by(data.len() as f64);
+                        // Size/schema/depth guard before any handler deserializes the payload
+                        if let Err(e) = gossip_guard::check(&data) {
+                            metrics_clone.gossip_rejected_total.inc();
+                            eprintln!("🚫 Dropped gossip message: {}", e);
+                            continue;
+                        }
+                        if let Some(cache) = seen_cache
+                            .as_mut()
+                            .filter(|_| !seen_cache::is_dedup_exempt(&data))
+                        {
+                            let now = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_secs();
+                            let is_new = cache.check_and_insert(data.as_bytes(), now);
+                            metrics_clone.gossip_dedup_checked_total.inc();
+                            metrics_clone
+                                .gossip_duplicate_rate_bps
+                                .set(cache.stats.duplicate_rate_bps() as i64);
+                            if !is_new {
+                                metrics_clone.gossip_duplicates_suppressed_total.inc();
+                                continue;
+                            }
+                        }
                         if data.starts_with("ID:") {
                             let parts: Vec<&str> = data.split(':').collect();
                             if parts.len() >= 3 {
@@ -7889,7 +11703,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             if rem_s >= calculated_remaining.saturating_sub(tolerance)
                                                 && rem_s <= calculated_remaining.saturating_add(tolerance) {
                                                 l.distribution.remaining_supply = calculated_remaining;
-                                                                SAVE_DIRTY.store(true, Ordering::Release);
+                                                                mark_dirty();
                                                 println!("🔄 Supply Verified & Synced with Peer: {} (calculated: {})", short, calculated_remaining);
                                             } else {
                                                 println!("⚠️ Supply sync rejected from {}: peer claims {} but we calculated {}",
@@ -7900,7 +11714,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         println!("🤝 Handshake: {}", short);
 
                                         let supply = l.distribution.remaining_supply;
-                                        let json = if is_new { serde_json::to_string(&*l).ok() } else { None };
+                                        let json = if is_new && los_network::bandwidth::serves_bulk_sync() {
+                                            serde_json::to_string(&*l).ok()
+                                        } else {
+                                            None
+                                        };
                                         (supply, json)
                                     }; // L dropped
 
@@ -7962,8 +11780,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     } // end is_new scope
                                 }
                             }
-                        } else if let Some(encoded_data) = data.strip_prefix("SYNC_GZIP:") {
-                            // Rate limit SYNC_GZIP to prevent DDoS via large payloads
+                        } else if let Some((encoded_data, sync_format)) = data
+                            .strip_prefix("SYNC_GZIP:")
+                            .map(|d| (d, sync_codec::SyncFormat::JsonGzip))
+                            .or_else(|| data.strip_prefix(sync_codec::BIN_PREFIX).map(|d| (d, sync_codec::SyncFormat::BincodeZstd)))
+                        {
+                            // Rate limit SYNC_GZIP / SYNC_BIN to prevent DDoS via large payloads
                             static LAST_SYNC: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
                             let now_secs = std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
@@ -7975,17 +11797,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             LAST_SYNC.store(now_secs, Ordering::Relaxed);
 
                             if let Ok(compressed_bytes) = base64::engine::general_purpose::STANDARD.decode(encoded_data) {
-                                use flate2::read::GzDecoder;
-                                use std::io::Read;
-
                                 // Limit decompressed size to prevent decompression bomb
                                 const MAX_DECOMPRESSED_SIZE: u64 = 50 * 1024 * 1024; // 50 MB max
-                                let decoder = GzDecoder::new(&compressed_bytes[..]);
-                                let mut limited_decoder = decoder.take(MAX_DECOMPRESSED_SIZE);
-                                let mut decompressed_json = String::new();
-
-                                if limited_decoder.read_to_string(&mut decompressed_json).is_ok() {
-                                    if let Ok(incoming_ledger) = serde_json::from_str::<Ledger>(&decompressed_json) {
+                                {
+                                    if let Ok(incoming_ledger) = sync_codec::decode(&compressed_bytes, sync_format, MAX_DECOMPRESSED_SIZE) {
                                         // DESIGN State root comparison — skip sync if states match.
                                         // Prevents redundant O(n) block-by-block processing when two nodes
                                         // already have identical state (common after initial sync).
@@ -8016,20 +11831,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         if block_gap > 5 {
                                             println!("📦 SYNC: Large gap detected ({} blocks behind). Using direct state adoption.", block_gap);
 
-                                            // Validate ALL incoming blocks cryptographically before adopting
-                                            let mut crypto_invalid = 0usize;
-                                            for blk in incoming_ledger.blocks.values() {
-                                                // Verify PoW
-                                                if !blk.verify_pow() {
-                                                    crypto_invalid += 1;
-                                                    continue;
-                                                }
-                                                // Verify signature
-                                                if !blk.verify_signature() {
-                                                    crypto_invalid += 1;
-                                                    continue;
-                                                }
-                                            }
+                                            // Validate ALL incoming blocks cryptographically (PoW + signature)
+                                            // before adopting, on the verification pool
+                                            let crypto_invalid = verify_pool::global()
+                                                .count_invalid(&incoming_ledger.blocks);
 
                                             // Only adopt if <10% of blocks are invalid (allows for minor
                                             // differences in block validation rules across versions)
@@ -8074,7 +11879,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             || blk.link.starts_with("FEE_REWARD:EPOCH:"))
                                                     {
                                                         let mut pool = safe_lock(&rp_sync);
-                                                        pool.sync_reward_from_gossip(&blk.account, blk.amount);
+                                                        if pool.sync_reward_mint(blk) {
+                                                            log_fee_only_transition(&pool);
+                                                        }
                                                     }
                                                 }
                                                 // Record participation for slashing
@@ -8097,6 +11904,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     added_count, crypto_invalid);
                                                 // Sanitize: remove orphaned blocks after state adoption
                                                 let orphans = l.remove_orphaned_blocks();
+                                                // Gas budgets follow the merged ContractCall blocks
+                                                l.rebuild_gas_usage();
+                                                // Claimed-reward totals follow the merged Claim blocks
+                                                l.rebuild_reward_claims();
+                                                // Session grants follow the merged Change/ContractCall blocks
+                                                l.rebuild_session_keys();
                                                 if orphans > 0 {
                                                     println!("🧹 Removed {} orphaned block(s) after sync", orphans);
                                                 }
@@ -8112,70 +11925,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             .cloned()
                                             .collect();
                                         incoming_blocks.sort_by_key(|b| b.timestamp);
+                                        let checked = verify_pool::global().verify(&incoming_blocks);
 
                                         // Two-pass: first pass processes ordered blocks, second catches stragglers
                                         for pass in 0..2 {
-                                            for blk in &incoming_blocks {
-                                                // Accept Mint/Slash blocks in SYNC if validly signed
-                                                // by a staked validator. Blanket-reject caused new nodes to
-                                                // permanently miss all minted balances.
-                                                if matches!(blk.block_type, BlockType::Mint | BlockType::Slash) {
-                                                    let sig_ok = hex::decode(&blk.signature).ok().and_then(|sig| {
-                                                        hex::decode(&blk.public_key).ok().map(|pk| {
-                                                            let sh = blk.signing_hash();
-                                                            los_crypto::verify_signature(sh.as_bytes(), &sig, &pk)
-                                                        })
-                                                    }).unwrap_or(false);
-                                                    if !sig_ok || !blk.verify_pow() {
-                                                        invalid_count += 1;
-                                                        continue;
-                                                    }
-                                                }
-
-                                                let hash = blk.calculate_hash();
-                                                if l.blocks.contains_key(&hash) { continue; }
-
-                                                if !l.accounts.contains_key(&blk.account) {
-                                                    l.accounts.insert(blk.account.clone(), AccountState {
-                                                        head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                                    });
-                                                }
-
-                                                // FEE_REWARD supply handling is now in process_block() itself.
-                                                // No need for save/restore — process_block skips remaining_supply
-                                                // deduction for FEE_REWARD: Mint blocks automatically.
-
-                                                match l.process_block(blk) {
-                                                    Ok(_) => {
-                                                        // Sync reward pool when receiving
-                                                        // REWARD:EPOCH or FEE_REWARD:EPOCH Mint blocks from leader.
-                                                        // This keeps non-leader pool stats consistent.
-                                                        if blk.block_type == BlockType::Mint
-                                                            && (blk.link.starts_with("REWARD:EPOCH:")
-                                                                || blk.link.starts_with("FEE_REWARD:EPOCH:"))
-                                                        {
-                                                            let mut pool = safe_lock(&rp_sync);
-                                                            pool.sync_reward_from_gossip(&blk.account, blk.amount);
-                                                        }
-                                                        // SLASHING: Record participation during sync
-                                                        {
-                                                            let mut sm = safe_lock(&slashing_clone);
-                                                            let timestamp = std::time::SystemTime::now()
-                                                                .duration_since(std::time::UNIX_EPOCH)
-                                                                .unwrap_or_default()
-                                                                .as_secs();
-
-                                                            if let Some(acc) = l.accounts.get(&blk.account) {
-                                                                if acc.balance >= MIN_VALIDATOR_STAKE_CIL {
-                                                                    if sm.get_profile(&blk.account).is_none() {
-                                                                        sm.register_validator(blk.account.clone());
-                                                                    }
-                                                                    let _ = sm.record_block_participation(&blk.account, l.blocks.len() as u64, timestamp);
-                                                                }
-                                                            }
-                                                        }
-                                                        added_count += 1;
-                                                    },
+                                            for (blk, verified) in incoming_blocks.iter().zip(&checked) {
+                                                match apply_synced_block(&mut l, blk, *verified, &rp_sync, &slashing_clone) {
+                                                    Ok(true) => added_count += 1,
+                                                    Ok(false) => {}
                                                     Err(_) => {
                                                         if pass == 1 { invalid_count += 1; }
                                                     }
@@ -8191,10 +11948,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         } // end slow-path else
 
                                         if added_count > 0 {
-                                            SAVE_DIRTY.store(true, Ordering::Release);
+                                            mark_dirty();
                                             // Sanitize: remove orphaned blocks after slow-path sync
                                             // NOTE: reuse existing `l` — do NOT re-acquire ledger lock (deadlock)
                                             let orphans = l.remove_orphaned_blocks();
+                                            // Gas budgets follow the merged ContractCall blocks
+                                            l.rebuild_gas_usage();
+                                            // Claimed-reward totals follow the merged Claim blocks
+                                            l.rebuild_reward_claims();
+                                            // Session grants follow the merged Change/ContractCall blocks
+                                            l.rebuild_session_keys();
                                             if orphans > 0 {
                                                 println!("🧹 Sync: removed {} orphaned block(s)", orphans);
                                             }
@@ -8205,7 +11968,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         } else if data.starts_with("SYNC_REQUEST:") {
                             // SECURITY P0-4: Rate-limited, per-requester sync response
-                            // FORMAT: SYNC_REQUEST:<requester_address>:<their_block_count>
+                            // FORMAT: SYNC_REQUEST:<requester_address>:<their_block_count>[:v2]
                             static SYNC_RESP_TIMES: std::sync::LazyLock<Mutex<HashMap<String, u64>>> =
                                 std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 
@@ -8213,6 +11976,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             if parts.len() >= 3 {
                                 let requester = parts[1].to_string();
                                 let their_count: usize = parts[2].parse().unwrap_or(0);
+                                let format = sync_codec::SyncFormat::negotiate(parts.get(3).copied());
 
                                 // Per-requester rate limit: max 1 sync response per 15 seconds per peer
                                 let now_secs = std::time::SystemTime::now()
@@ -8230,29 +11994,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 // Only respond if we have more blocks than the requester
                                 let our_count = safe_lock(&ledger).blocks.len();
+                                if !los_network::bandwidth::serves_bulk_sync() {
+                                    // Low-bandwidth: point the requester at an archive node
+                                    // instead of gossiping our whole ledger
+                                    let delegates = los_network::bandwidth::sync_delegates();
+                                    if our_count > their_count && requester != my_address && !delegates.is_empty() {
+                                        let delegate = &delegates[now_secs as usize % delegates.len()];
+                                        let _ = tx_out.send(format!("SYNC_VIA_REST:{}|{}", delegate, our_count)).await;
+                                        println!("🐢 Sync request from {} delegated to {}", get_short_addr(&requester), delegate);
+                                    }
+                                    continue;
+                                }
                                 if our_count > their_count && requester != my_address {
                                     println!("📡 Sync request from {} (they have {} blocks, we have {})",
                                         get_short_addr(&requester), their_count, our_count);
 
-                                    let sync_json = {
+                                    let encoded_sync = {
                                         let l = safe_lock(&ledger);
-                                        serde_json::to_string(&*l).ok()
+                                        sync_codec::encode(&l, format)
                                     };
 
-                                    if let Some(json) = sync_json {
-                                        use flate2::write::GzEncoder;
-                                        use flate2::Compression;
-                                        use std::io::Write;
-
-                                        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-                                        let _ = encoder.write_all(json.as_bytes());
-                                        if let Ok(compressed) = encoder.finish() {
+                                    if let Ok(compressed) = encoded_sync {
+                                        {
                                             const MAX_GOSSIP_SYNC: usize = 8 * 1024 * 1024;
                                             if compressed.len() <= MAX_GOSSIP_SYNC {
-                                                // Small enough for gossip — send via SYNC_GZIP
+                                                // Small enough for gossip — SYNC_GZIP (v1) or SYNC_BIN (v2)
                                                 let encoded = base64::engine::general_purpose::STANDARD.encode(&compressed);
-                                                let _ = tx_out.send(format!("SYNC_GZIP:{}", encoded)).await;
-                                                println!("📤 Sent state sync via gossip ({} blocks, {}KB compressed)", our_count, compressed.len() / 1024);
+                                                let _ = tx_out.send(format!("{}{}", format.gossip_prefix(), encoded)).await;
+                                                println!("📤 Sent state sync via gossip ({} blocks, {}KB compressed, {:?})", our_count, compressed.len() / 1024, format);
                                             } else {
                                                 // State too large for gossip — tell peer to use REST sync
                                                 if let Some(our_host) = get_node_host_address() {
@@ -8323,7 +12092,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 }
                             } else if parts.len() == 3 {
                                 // Legacy unsigned format — reject on mainnet, warn on testnet
-                                if los_core::is_mainnet_build() {
+                                if los_core::is_mainnet() {
                                     println!("🚨 Rejected unsigned SLASH_REQ (mainnet requires signed messages)");
                                     continue;
                                 }
@@ -8417,21 +12186,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     amount: penalty_amount,
                                                     link: format!("PENALTY:FAKE_TXID:{}", fake_txid),
                                                     signature: "".to_string(),
-                                                    public_key: hex::encode(&keys.public_key),
+                                                    public_key: hex::encode(&node_public_key),
                                                     work: 0,
                                                     timestamp: std::time::SystemTime::now()
                                                         .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                     fee: 0,
+                                                    valid_after: 0,
+                                                    expires_at: 0,
                                                 };
 
                                                 solve_pow(&mut slash_blk);
-                                                if let Ok(sig) = los_crypto::sign_message(slash_blk.signing_hash().as_bytes(), &secret_key) {
+                                                if let Ok(sig) = signer.sign(slash_blk.signing_hash().as_bytes()) {
                                                     slash_blk.signature = hex::encode(sig);
 
                                                     match l.process_block(&slash_blk) {
                                                         Ok(result) => {
                                                             let hash = result.into_hash();
-                                                            SAVE_DIRTY.store(true, Ordering::Release);
+                                                            mark_dirty();
                                                             gossip = Some(serde_json::to_string(&slash_blk).unwrap_or_default());
                                                             println!("🔨 SLASHED (consensus 2/3+1)! {} penalized {} LOS (block: {})",
                                                                 get_short_addr(&cheater_addr),
@@ -8473,18 +12244,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     None
                                 };
 
-                                let tx_confirm = tx_out.clone();
                                 let ledger_ref = Arc::clone(&ledger);
-                                let my_addr_clone = my_address.clone();
-                                // Zeroize cloned secret key on async task drop
-                                let confirm_sk = secret_key.clone();
-                                let confirm_pk = keys.public_key.clone();
+                                let vote_queue = Arc::clone(&vote_batcher);
+                                let fork_reg = Arc::clone(&fork_registry);
+                                let fork_tx = tx_out.clone();
 
                                 tokio::spawn(async move {
                                     // SECURITY P0-2: Verify the block exists and matches claims.
                                     // First check ledger (for re-gossipped blocks), then validate
                                     // the embedded block from the CONFIRM_REQ message (consensus fix).
-                                    let (sender_balance, block_valid) = {
+                                    let (sender_balance, block_valid, fork_alert) = {
                                         let l_guard = safe_lock(&ledger_ref);
                                         let bal = l_guard.accounts.get(&sender_addr).map(|a| a.balance).unwrap_or(0);
 
@@ -8515,19 +12284,42 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let pk_bytes = hex::decode(&b.public_key).unwrap_or_default();
                                                 let derived = los_crypto::public_key_to_address(&pk_bytes);
                                                 let pk_ok = derived == sender_addr;
+                                                // 8. Time lock must be over (consensus time)
+                                                let lock_ok = !b.is_time_locked(los_core::consensus_clock::now());
 
-                                                if !hash_ok || !type_ok || !sender_ok || !amount_ok || !sig_ok || !pow_ok || !pk_ok {
-                                                    println!("⚠️ CONFIRM_REQ block validation failed: hash={} type={} sender={} amount={} sig={} pow={} pk={}",
-                                                        hash_ok, type_ok, sender_ok, amount_ok, sig_ok, pow_ok, pk_ok);
+                                                if !hash_ok || !type_ok || !sender_ok || !amount_ok || !sig_ok || !pow_ok || !pk_ok || !lock_ok {
+                                                    println!("⚠️ CONFIRM_REQ block validation failed: hash={} type={} sender={} amount={} sig={} pow={} pk={} time_lock={}",
+                                                        hash_ok, type_ok, sender_ok, amount_ok, sig_ok, pow_ok, pk_ok, lock_ok);
                                                 }
 
-                                                hash_ok && type_ok && sender_ok && amount_ok && sig_ok && pow_ok && pk_ok
+                                                hash_ok && type_ok && sender_ok && amount_ok && sig_ok && pow_ok && pk_ok && lock_ok
                                             }).unwrap_or(false)
                                         } else { false };
 
-                                        (bal, ledger_valid || msg_valid)
+                                        // A valid block that conflicts with our copy of the sender's
+                                        // chain is a double-spend attempt: never vote for it.
+                                        let forked = msg_valid && block_from_msg.as_ref()
+                                            .is_some_and(|b| l_guard.find_fork(b).is_some());
+                                        if forked {
+                                            println!("🚫 CONFIRM_REQ rejected: {} conflicts with {}'s chain",
+                                                &tx_hash[..8.min(tx_hash.len())], get_short_addr(&sender_addr));
+                                        }
+                                        let fork_alert = if forked {
+                                            block_from_msg.as_ref().and_then(|b| {
+                                                fork_monitor::observe(&fork_reg, &l_guard, b, "confirm_req")
+                                            })
+                                        } else {
+                                            None
+                                        };
+
+                                        (bal, (ledger_valid || msg_valid) && !forked, fork_alert)
                                     };
 
+                                    if let Some(alert) = fork_alert {
+                                        let _ = fork_tx.send(alert).await;
+                                        return;
+                                    }
+
                                     if !block_valid {
                                         // P0-2: Block doesn't exist/match and no valid embedded block — don't vote
                                         println!("⚠️ CONFIRM_REQ rejected: block_valid=false for hash={}", &tx_hash[..8.min(tx_hash.len())]);
@@ -8537,25 +12329,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     // BALANCE CHECK: Verify sender has sufficient funds before voting YES.
                                     // This is the mainnet-safe path — no shortcuts.
                                     if sender_balance >= amount {
-                                        let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
-                                        // SECURITY P0-1: Sign CONFIRM_RES with Dilithium5
-                                        let payload = format!("{}:{}:YES:{}:{}", tx_hash, sender_addr, my_addr_clone, ts);
-                                        if let Ok(sig) = los_crypto::sign_message(payload.as_bytes(), &confirm_sk) {
-                                            let res = format!("CONFIRM_RES:{}:{}:YES:{}:{}:{}:{}", tx_hash, sender_addr, my_addr_clone, ts, hex::encode(&sig), hex::encode(&confirm_pk));
-                                            let _ = tx_confirm.send(res).await;
-                                        } else {
-                                            eprintln!("\u{26a0}\u{fe0f} Signing failed for CONFIRM_RES \u{2014} skipping");
-                                        }
+                                        // Queue YES vote — the vote batch task signs and gossips it
+                                        // (single vote → CONFIRM_RES, multiple → one VOTE_BATCH envelope)
+                                        safe_lock(&vote_queue).queue(&tx_hash, &sender_addr);
                                     } else {
                                         println!("\u{26a0}\u{fe0f} CONFIRM_REQ rejected: sender {} has insufficient balance ({} CIL < {} CIL)",
                                             get_short_addr(&sender_addr), sender_balance, amount);
                                     }
                                 });
                             }
-                        } else if data.starts_with("CONFIRM_RES:") {
-                            let parts: Vec<&str> = data.split(':').collect();
-                            // FORMAT: CONFIRM_RES:tx_hash:sender:YES:voter:timestamp:signature:pubkey (8 parts)
-                            if parts.len() == 8 {
+                        } else if data.starts_with("CONFIRM_RES:") || data.starts_with(VOTE_BATCH_PREFIX) {
+                            // Collect signature-verified (tx_hash, voter) pairs from either a single
+                            // CONFIRM_RES or a batched VOTE_BATCH envelope, then tally each vote.
+                            // The signed message is kept as proof for the in-flight journal.
+                            let verified_votes: Vec<(String, String, Arc<consensus_journal::VoteProof>)> = if data.starts_with(VOTE_BATCH_PREFIX) {
+                                let now_ms = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis();
+                                let envelope = VoteEnvelope::from_message(&data).and_then(|env| {
+                                    env.verify(now_ms, los_crypto::verify_signature, los_crypto::public_key_to_address)?;
+                                    Ok(env)
+                                });
+                                match envelope {
+                                    Ok(env) => {
+                                        // Gossip propagation delay for telemetry latency stats
+                                        safe_lock(&telemetry_latency).record(
+                                            u64::try_from(now_ms.saturating_sub(env.timestamp)).unwrap_or(u64::MAX),
+                                        );
+                                        let voter = env.voter.clone();
+                                        let tx_hashes: Vec<String> = env.votes.iter().map(|v| v.tx_hash.clone()).collect();
+                                        let proof = Arc::new(consensus_journal::VoteProof::Batch(env));
+                                        tx_hashes.into_iter().map(|h| (h, voter.clone(), Arc::clone(&proof))).collect()
+                                    }
+                                    Err(e) => {
+                                        println!("🚨 Rejected VOTE_BATCH: {}", e);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                let parts: Vec<&str> = data.split(':').collect();
+                                // FORMAT: CONFIRM_RES:tx_hash:sender:YES:voter:timestamp:signature:pubkey (8 parts)
+                                if parts.len() != 8 {
+                                    continue;
+                                }
                                 let tx_hash = parts[1].to_string();
                                 let _requester = parts[2].to_string();
                                 let voter_addr = parts[4].to_string();
@@ -8576,7 +12390,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     println!("🚨 Rejected CONFIRM_RES: pubkey mismatch for {}", get_short_addr(&voter_addr));
                                     continue;
                                 }
+                                let proof = consensus_journal::VoteProof::Single {
+                                    requester: parts[2].to_string(),
+                                    timestamp: parts[5].to_string(),
+                                    signature: sig_hex.to_string(),
+                                    public_key: pk_hex.to_string(),
+                                };
+                                vec![(tx_hash, voter_addr, Arc::new(proof))]
+                            };
 
+                            for (tx_hash, voter_addr, proof) in verified_votes {
                                 // Removed `requester == my_address` guard.
                                 // When a user wallet sends through a node, requester = wallet address ≠ node address,
                                 // causing ALL votes to be silently dropped. The tx_exists check in pending_sends
@@ -8622,11 +12445,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                         let mut pending = safe_lock(&pending_sends);
                                         if let Some((blk, total_power_votes)) = pending.get_mut(&tx_hash) {
+                                            let mut vote_power: u128 = 0;
                                             if voter_power_linear > 0 {
                                                 // Normalize CIL→LOS before * 1000 scaling (matches SEND_CONSENSUS_THRESHOLD units).
                                                 let voter_power_los = voter_power_linear / CIL_PER_LOS;
                                                 let power_scaled = voter_power_los * 1000;
                                                 *total_power_votes += power_scaled;
+                                                vote_power = power_scaled;
                                                 let min_voters = if !testnet_config::get_testnet_config().should_enable_consensus() { 1 } else { min_distinct_voters(active_vc) };
                                                 println!("📩 Konfirmasi Power: {} (Stake: {} LOS, Power: {}) | Total: {}/{} (Voters: {}/{})",
                                                     get_short_addr(&voter_addr), voter_power_display, voter_power_los, total_power_votes, SEND_CONSENSUS_THRESHOLD, distinct_count, min_voters
@@ -8636,6 +12461,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     get_short_addr(&voter_addr), voter_balance, voter_power_display);
                                             }
 
+                                            // Journal the vote so a restart keeps this partial tally
+                                            let record = consensus_journal::VoteRecord {
+                                                voter: voter_addr.clone(),
+                                                power: vote_power,
+                                                proof: (*proof).clone(),
+                                            };
+                                            if let Err(e) = database.save_inflight_vote(&tx_hash, &record) {
+                                                eprintln!("⚠️ In-flight journal write failed: {}", e);
+                                            }
+
                                             let min_voters = if !testnet_config::get_testnet_config().should_enable_consensus() { 1 } else { min_distinct_voters(active_vc) };
                                             let threshold: u128 = if !testnet_config::get_testnet_config().should_enable_consensus() { TESTNET_FUNCTIONAL_THRESHOLD } else { SEND_CONSENSUS_THRESHOLD };
                                             if *total_power_votes >= threshold && distinct_count >= min_voters {
@@ -8667,7 +12502,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             }
                                                         }
                                                     }
-                                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                                    mark_dirty();
                                                     true
                                                 },
                                                 Err(e) => {
@@ -8699,7 +12534,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 if let Some(sender_acct) = l.accounts.get_mut(&blk_to_finalize.account) {
                                                     if sender_acct.is_validator && sender_acct.balance < MIN_VALIDATOR_REGISTER_CIL {
                                                         sender_acct.is_validator = false;
-                                                        SAVE_DIRTY.store(true, Ordering::Release);
+                                                        mark_dirty();
                                                         println!("⚠️ Auto-unregistered validator {}: balance {} < minimum registration stake {} LOS",
                                                             get_short_addr(&blk_to_finalize.account),
                                                             sender_acct.balance / CIL_PER_LOS,
@@ -8734,14 +12569,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                             amount: blk_to_finalize.amount,
                                                             link: send_hash,
                                                             signature: "".to_string(),
-                                                            public_key: hex::encode(&keys.public_key),
+                                                            public_key: hex::encode(&node_public_key),
                                                             work: 0,
                                                             timestamp: std::time::SystemTime::now()
                                                                 .duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                             fee: 0,
+                                                            valid_after: 0,
+                                                            expires_at: 0,
                                                         };
                                                         solve_pow(&mut recv_blk);
-                                                        recv_blk.signature = match try_sign_hex(recv_blk.signing_hash().as_bytes(), &secret_key) {
+                                                        recv_blk.signature = match try_sign_hex(recv_blk.signing_hash().as_bytes(), &*signer) {
                                                             Ok(sig) => sig,
                                                             Err(e) => { eprintln!("⚠️ Auto-Receive signing failed: {}", e); String::new() }
                                                         };
@@ -8773,7 +12610,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                                     }
                                                                     l.blocks.insert(recv_hash.clone(), recv_blk.clone());
                                                                     l.claimed_sends.insert(recv_blk.link.clone());
-                                                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                                                    mark_dirty();
                                                                     println!("📨 Auto-Receive created for {} (+{} CIL)",
                                                                         get_short_addr(&target), blk_to_finalize.amount);
                                                                     let send_b64 = base64::engine::general_purpose::STANDARD.encode(
@@ -8793,7 +12630,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                                 }
                                                                 l.blocks.insert(recv_hash.clone(), recv_blk.clone());
                                                                 l.claimed_sends.insert(recv_blk.link.clone());
-                                                                SAVE_DIRTY.store(true, Ordering::Release);
+                                                                mark_dirty();
                                                                 println!("📨 Auto-Receive created for {} (+{} CIL)",
                                                                     get_short_addr(&target), blk_to_finalize.amount);
                                                                 let send_b64 = base64::engine::general_purpose::STANDARD.encode(
@@ -8814,6 +12651,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         }
                                         // Step 5: Remove from pending (PS lock only)
                                         safe_lock(&pending_sends).remove(&tx_hash);
+                                        let _ = database.remove_inflight_send(&tx_hash);
+                                        let now_ms = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+                                        if let Some(took) = safe_lock(&netstats).note_quorum(&tx_hash, now_ms) {
+                                            metrics_clone.consensus_latency_seconds.observe(took as f64 / 1000.0);
+                                        }
                                         safe_lock(&send_voters_clone).remove(&tx_hash);
                                         // Clean from mempool on confirmation
                                         safe_lock(&mempool_pool).remove_transaction(&tx_hash);
@@ -8838,7 +12680,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 if addr != my_address && los_crypto::validate_address(addr) {
                                     if let Ok(ts) = ts_str.parse::<u64>() {
                                         // Timestamp freshness: within 2× heartbeat interval
-                                        let hb_interval = if los_core::is_testnet_build() { 10u64 } else { 60u64 };
+                                        let hb_interval = if los_core::is_testnet() { 10u64 } else { 60u64 };
                                         let now_ts = std::time::SystemTime::now()
                                             .duration_since(std::time::UNIX_EPOCH)
                                             .unwrap_or_default()
@@ -8858,6 +12700,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                         if is_registered {
                                                             let mut lp = safe_lock(&live_peers);
                                                             lp.insert(addr.to_string(), now_ts);
+                                                            // Signed validator time feeds the consensus clock
+                                                            los_core::consensus_clock::record_peer_time(addr, ts);
                                                         }
                                                     }
                                                 }
@@ -8881,7 +12725,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 if wallet_addr != my_address && los_crypto::validate_address(wallet_addr) && los_crypto::validate_address(node_addr) {
                                     if let Ok(ts) = ts_str.parse::<u64>() {
-                                        let hb_interval = if los_core::is_testnet_build() { 10u64 } else { 60u64 };
+                                        let hb_interval = if los_core::is_testnet() { 10u64 } else { 60u64 };
                                         let now_ts = std::time::SystemTime::now()
                                             .duration_since(std::time::UNIX_EPOCH)
                                             .unwrap_or_default()
@@ -9002,7 +12846,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     get_short_addr(&addr),
                                                     host_with_port
                                                 );
-                                                SAVE_DIRTY.store(true, Ordering::Release);
+                                                mark_dirty();
                                             }
                                         }
                                         continue;
@@ -9032,7 +12876,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         rp.register_validator(&addr, false, balance);
                                     }
 
-                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                    mark_dirty();
                                     println!("✅ Validator registered via P2P: {} (stake: {} LOS)",
                                         get_short_addr(&addr), balance / CIL_PER_LOS);
 
@@ -9052,7 +12896,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     // Add to address_book so heartbeats are recorded for this validator
                                     {
                                         let short = get_short_addr(&addr);
-                                        safe_lock(&address_book).entry(short).or_insert(addr.clone());
+                                        let is_new = {
+                                            let mut ab = safe_lock(&address_book);
+                                            let is_new = !ab.contains_key(&short);
+                                            ab.entry(short.clone()).or_insert(addr.clone());
+                                            is_new
+                                        };
+                                        if is_new {
+                                            if let Err(e) = db_clone.save_peer(&short, &addr) {
+                                                eprintln!("⚠️ Failed to persist peer {}: {}", short, e);
+                                            }
+                                        }
                                     }
 
                                     // Extract and store host address for peer discovery
@@ -9165,15 +13019,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         safe_lock(&abft_event).update_validator_set(validators);
                                     }
 
-                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                    mark_dirty();
                                     println!("🔻 Validator unregistered via P2P: {}", get_short_addr(&addr));
                                 },
                                 Err(e) => {
                                     println!("⚠️ VALIDATOR_UNREG: invalid JSON from peer: {}", e);
                                 }
                             }
+                        } else if let Some(json_str) = data.strip_prefix(telemetry::TELEMETRY_PREFIX) {
+                            // Opt-in node telemetry — only aggregating nodes keep it
+                            let Some(agg) = &telemetry_aggregator else {
+                                continue;
+                            };
+                            if let Ok(report) = serde_json::from_str::<telemetry::TelemetryReport>(json_str) {
+                                let now = std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .unwrap_or_default()
+                                    .as_secs();
+                                if let Err(e) = safe_lock(agg).accept(&report, now) {
+                                    if e != "rate limited" {
+                                        println!("🚫 TELEMETRY from {}: {}", get_short_addr(&report.summary.node), e);
+                                    }
+                                }
+                            }
+                        } else if let Some(json_str) = data.strip_prefix(pex::PEX_PREFIX) {
+                            // Signed Peer Exchange — rate-limited, verified against the
+                            // ledger's validator set before any endpoint is merged
+                            let msg = match serde_json::from_str::<pex::PexMessage>(json_str) {
+                                Ok(m) => m,
+                                Err(_) => continue,
+                            };
+                            let now = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_secs();
+                            let accepted = {
+                                let l = safe_lock(&ledger);
+                                let is_validator = |addr: &str| {
+                                    l.accounts.get(addr).is_some_and(|a| {
+                                        a.is_validator && a.balance >= MIN_VALIDATOR_REGISTER_CIL
+                                    })
+                                };
+                                safe_lock(&pex_book_event).accept(&msg, now, is_validator)
+                            };
+                            match accepted {
+                                Ok(entries) => {
+                                    let mut ve = safe_lock(&ve_event);
+                                    let mut added = 0u32;
+                                    for e in entries {
+                                        if e.address == my_address {
+                                            continue;
+                                        }
+                                        if ve.get(&e.address) != Some(&e.host) {
+                                            insert_validator_endpoint(&mut ve, e.address, e.host);
+                                            added += 1;
+                                        }
+                                    }
+                                    if added > 0 {
+                                        println!("🔄 PEX: merged {} validator endpoint(s) from {}", added, get_short_addr(&msg.sender));
+                                    }
+                                }
+                                Err(e) if e == "rate limited" => {}
+                                Err(e) => println!("🚫 PEX from {}: {}", get_short_addr(&msg.sender), e),
+                            }
                         } else if let Some(json_str) = data.strip_prefix("PEER_LIST:") {
-                            // Handle Peer Exchange (PEX) — merge validator endpoints from peers
+                            // Legacy unsigned Peer Exchange from older nodes — merge
+                            // validator endpoints (never added to the PEX dial list)
                             if let Ok(peer_list) = serde_json::from_str::<serde_json::Value>(json_str) {
                                 if let Some(endpoints) = peer_list["endpoints"].as_array() {
                                     let mut ve = safe_lock(&ve_event);
@@ -9224,6 +13135,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 if let (Some(send_blk), Some(recv_blk)) = (send_block, recv_block) {
                                     let send_hash = send_blk.calculate_hash();
+                                    let mut fork_alert: Option<String> = None;
 
                                     // Validate Send block: signature + PoW + must be Send type
                                     let send_valid = send_blk.block_type == BlockType::Send
@@ -9289,6 +13201,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                         "incoming_prev" => get_short_addr(&send_blk.previous),
                                                         "canonical_winner" => canonical_winner
                                                     );
+                                                    fork_alert = fork_monitor::observe(&fork_registry, &l, &send_blk, "block_confirmed");
                                                     true
                                                 } else if sender.balance < total_debit {
                                                     println!("🚫 Rejected BLOCK_CONFIRMED: insufficient sender \
@@ -9365,7 +13278,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 // same Send (claimed_sends check would return false).
                                                 l.claimed_sends.insert(send_hash.clone());
 
-                                                SAVE_DIRTY.store(true, Ordering::Release);
+                                                mark_dirty();
                                                 println!("✅ Applied BLOCK_CONFIRMED: {} → {} ({} CIL)",
                                                     get_short_addr(&send_blk.account), get_short_addr(&recv_blk.account), send_blk.amount);
                                             } else {
@@ -9382,8 +13295,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         } // end if !send_rejected
                                         }
                                     }
+                                    if let Some(alert) = fork_alert {
+                                        let _ = tx_out.send(alert).await;
+                                    }
                                 }
                             }
+                        } else if data.starts_with(fork_monitor::FORK_ALERT_PREFIX) {
+                            // Peer-reported fork evidence: both blocks are re-verified before recording
+                            if let Err(e) = fork_monitor::accept_alert(&fork_registry, &data) {
+                                println!("🚫 Rejected FORK_ALERT: {}", e);
+                            }
                         } else if data.starts_with("CONTRACT_DEPLOYED:") {
                             // CROSS-NODE CONTRACT REPLICATION
                             // Format: CONTRACT_DEPLOYED:{block_b64}:{bytecode_b64}:{contract_addr}
@@ -9400,87 +13321,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     let valid = deploy_blk.block_type == BlockType::ContractDeploy
                                         && deploy_blk.verify_signature()
                                         && deploy_blk.verify_pow()
-                                        && deploy_blk.link.starts_with("DEPLOY:");
+                                        && los_core::deploy_link::DeployLink::parse(&deploy_blk.link).is_ok();
 
                                     if !valid {
                                         println!("🚫 Rejected CONTRACT_DEPLOYED: validation failed");
                                     } else {
+                                        // Chain sequence, balance, debit, deploy at the block
+                                        // timestamp and funding in one place (contract_apply.rs)
                                         let deploy_hash = deploy_blk.calculate_hash();
                                         let mut l = safe_lock(&ledger);
                                         if !l.blocks.contains_key(&deploy_hash) {
-                                            // Ensure deployer account exists
-                                            if !l.accounts.contains_key(&deploy_blk.account) {
-                                                l.accounts.insert(deploy_blk.account.clone(), AccountState {
-                                                    head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                                });
-                                            }
-                                            // Chain-sequence + balance validation
-                                            // for CONTRACT_DEPLOYED — same pattern as BLOCK_CONFIRMED fix.
-                                            let deploy_rejected = if let Some(deployer) = l.accounts.get(&deploy_blk.account) {
-                                                let total_debit = deploy_blk.amount.saturating_add(deploy_blk.fee);
-                                                if deployer.head != deploy_blk.previous {
-                                                    println!("🚫 Rejected CONTRACT_DEPLOYED: chain fork \
-                                                        (deployer={}, head={}, block.previous={})",
-                                                        get_short_addr(&deploy_blk.account),
-                                                        get_short_addr(&deployer.head),
-                                                        get_short_addr(&deploy_blk.previous));
-                                                    true
-                                                } else if deployer.balance < total_debit {
-                                                    println!("🚫 Rejected CONTRACT_DEPLOYED: insufficient \
-                                                        deployer balance ({} < {})",
-                                                        deployer.balance, total_debit);
-                                                    true
-                                                } else {
-                                                    false
-                                                }
-                                            } else {
-                                                false
-                                            };
-                                            if !deploy_rejected {
-                                                if let Some(deployer) = l.accounts.get_mut(&deploy_blk.account) {
-                                                    let total_debit = deploy_blk.amount.saturating_add(deploy_blk.fee);
-                                                    deployer.balance -= total_debit; // Safe: checked above
-                                                    deployer.head = deploy_hash.clone();
-                                                    deployer.block_count += 1;
-                                                }
-                                                l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(deploy_blk.fee);
-                                                l.blocks.insert(deploy_hash, deploy_blk.clone());
-                                                drop(l); // Release ledger lock before VM operations
-
-                                                // Deploy to local WASM engine
-                                                let code_hash = WasmEngine::compute_code_hash(&bytecode);
-                                                let expected_hash = &deploy_blk.link[7..]; // After "DEPLOY:"
-                                                if code_hash.starts_with(expected_hash) || expected_hash.starts_with(&code_hash[..expected_hash.len().min(code_hash.len())]) {
-                                                    let now_ts = std::time::SystemTime::now()
-                                                        .duration_since(std::time::UNIX_EPOCH)
-                                                        .unwrap_or_default()
-                                                        .as_secs();
-                                                    match wasm_engine.deploy_contract(
-                                                        deploy_blk.account.clone(),
-                                                        bytecode,
-                                                        BTreeMap::new(),
-                                                        now_ts,
-                                                    ) {
-                                                        Ok(addr) => {
-                                                            // Fund contract if amount > 0
-                                                            if deploy_blk.amount > 0 {
-                                                                let _ = wasm_engine.send_to_contract(&addr, deploy_blk.amount);
-                                                            }
-                                                            // Persist VM state
-                                                            if let Ok(vm_data) = wasm_engine.serialize_all() {
-                                                                let _ = database.save_contracts(&vm_data);
-                                                            }
-                                                            println!("✅ Replicated CONTRACT_DEPLOYED: {} (owner: {})",
-                                                                addr, get_short_addr(&deploy_blk.account));
-                                                        }
-                                                        Err(e) => eprintln!("⚠️ Failed to replicate contract deploy: {}", e),
+                                            let applied = contract_apply::apply_deploy_block(
+                                                &mut l, &wasm_engine, &deploy_blk, bytecode, BTreeMap::new());
+                                            drop(l); // Release ledger lock before persisting VM state
+                                            match applied {
+                                                Ok(addr) => {
+                                                    // Persist VM state
+                                                    if let Ok(vm_data) = wasm_engine.serialize_all() {
+                                                        let _ = database.save_contracts(&vm_data);
                                                     }
-                                                } else {
-                                                    eprintln!("🚫 CONTRACT_DEPLOYED: code hash mismatch");
+                                                    println!("✅ Replicated CONTRACT_DEPLOYED: {} (owner: {})",
+                                                        addr, get_short_addr(&deploy_blk.account));
+                                                    mark_dirty();
                                                 }
-
-                                                SAVE_DIRTY.store(true, Ordering::Release);
-                                            } // end if !deploy_rejected
+                                                Err(e) => println!("🚫 Rejected CONTRACT_DEPLOYED from {}: {}",
+                                                    get_short_addr(&deploy_blk.account), e),
+                                            }
                                         }
                                     }
                                 }
@@ -9503,98 +13369,181 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     if !valid {
                                         println!("🚫 Rejected CONTRACT_CALLED: validation failed");
                                     } else {
+                                        // Sequence, balance, debit, execution and transfer
+                                        // credits are applied atomically (contract_apply.rs)
                                         let call_hash = call_blk.calculate_hash();
                                         let mut l = safe_lock(&ledger);
                                         if !l.blocks.contains_key(&call_hash) {
-                                            // Ensure caller account exists
-                                            if !l.accounts.contains_key(&call_blk.account) {
-                                                l.accounts.insert(call_blk.account.clone(), AccountState {
-                                                    head: "0".to_string(), balance: 0, block_count: 0, is_validator: false,
-                                                });
-                                            }
-                                            // Chain-sequence + balance validation
-                                            // for CONTRACT_CALLED — same pattern as BLOCK_CONFIRMED fix.
-                                            let call_rejected = if let Some(caller) = l.accounts.get(&call_blk.account) {
-                                                let total_debit = call_blk.amount.saturating_add(call_blk.fee);
-                                                if caller.head != call_blk.previous {
-                                                    println!("🚫 Rejected CONTRACT_CALLED: chain fork \
-                                                        (caller={}, head={}, block.previous={})",
-                                                        get_short_addr(&call_blk.account),
-                                                        get_short_addr(&caller.head),
-                                                        get_short_addr(&call_blk.previous));
-                                                    true
-                                                } else if caller.balance < total_debit {
-                                                    println!("🚫 Rejected CONTRACT_CALLED: insufficient \
-                                                        caller balance ({} < {})",
-                                                        caller.balance, total_debit);
-                                                    true
-                                                } else {
-                                                    false
-                                                }
-                                            } else {
-                                                false
-                                            };
-                                            if !call_rejected {
-                                                if let Some(caller_acct) = l.accounts.get_mut(&call_blk.account) {
-                                                    let total_debit = call_blk.amount.saturating_add(call_blk.fee);
-                                                    caller_acct.balance -= total_debit; // Safe: checked above
-                                                    caller_acct.head = call_hash.clone();
-                                                    caller_acct.block_count += 1;
-                                                }
-                                                l.accumulated_fees_cil = l.accumulated_fees_cil.saturating_add(call_blk.fee);
-                                                l.blocks.insert(call_hash, call_blk.clone());
-                                                drop(l);
-
-                                                // Parse call data from link: "CALL:{addr}:{func}:{args_b64}"
-                                                let call_data = &call_blk.link[5..];
-                                                let call_parts: Vec<&str> = call_data.splitn(3, ':').collect();
-                                                if call_parts.len() >= 2 {
-                                                    let contract_addr = call_parts[0];
-                                                    let function = call_parts[1];
-                                                    let args: Vec<String> = if call_parts.len() == 3 {
-                                                        base64::engine::general_purpose::STANDARD
-                                                            .decode(call_parts[2]).ok()
-                                                            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
-                                                            .unwrap_or_default()
-                                                    } else {
-                                                        Vec::new()
-                                                    };
-                                                    let gas_limit = call_blk.fee / los_core::GAS_PRICE_CIL.max(1);
-
-                                                    // Value transfer to contract
-                                                    if call_blk.amount > 0 {
-                                                        let _ = wasm_engine.send_to_contract(contract_addr, call_blk.amount);
+                                            let applied = contract_apply::apply_contract_block(&mut l, &wasm_engine, &call_blk);
+                                            drop(l);
+                                            match applied {
+                                                Ok(outcome) => {
+                                                    if let Ok(vm_data) = wasm_engine.serialize_all() {
+                                                        let _ = database.save_contracts(&vm_data);
                                                     }
-
-                                                    // Execute deterministically (same result on all nodes)
-                                                    let call = ContractCall {
-                                                        contract: contract_addr.to_string(),
-                                                        function: function.to_string(),
-                                                        args,
-                                                        gas_limit: gas_limit as u64,
-                                                        caller: call_blk.account.clone(),
-                                                        block_timestamp: call_blk.timestamp,
-                                                    };
-                                                    match wasm_engine.call_contract(call) {
-                                                        Ok(result) => {
-                                                            if let Ok(vm_data) = wasm_engine.serialize_all() {
-                                                                let _ = database.save_contracts(&vm_data);
-                                                            }
-                                                            println!("✅ Replicated CONTRACT_CALLED: {}::{} → {}",
-                                                                contract_addr, function,
-                                                                if result.success { "OK" } else { "FAIL" });
-                                                        }
-                                                        Err(e) => eprintln!("⚠️ Failed to replicate contract call: {}", e),
+                                                    if let Err(e) = database.save_contract_events(&outcome.block_hash, &outcome.result.events) {
+                                                        eprintln!("⚠️ Failed to store events of {}: {}", outcome.block_hash, e);
                                                     }
+                                                    println!("✅ Replicated CONTRACT_CALLED: {}::{} → {}",
+                                                        outcome.call.contract, outcome.call.function,
+                                                        if outcome.result.success { "OK" } else { "FAIL" });
+                                                    mark_dirty();
                                                 }
-
-                                                SAVE_DIRTY.store(true, Ordering::Release);
-                                            } // end if !call_rejected
+                                                Err(e) => println!("🚫 Rejected CONTRACT_CALLED from {}: {}",
+                                                    get_short_addr(&call_blk.account), e),
+                                            }
+                                        }
+                                    }
+                                }
+                            }                        } else if data.starts_with(CHECKPOINT_FINAL_PREFIX) {
+                            // ── Finalized checkpoint aggregated by its proposer ──
+                            // Verify quorum with cryptographically valid signatures from staked
+                            // validators before storing it.
+                            let Some(cp) = parse_final_checkpoint(&data) else {
+                                continue;
+                            };
+                            // A different checkpoint at a finalized height is verified like any
+                            // other: with a valid quorum it is a conflict (checkpoint_conflict.rs)
+                            let stored_id = safe_lock(&checkpoint_manager).get_checkpoint(cp.height).ok().flatten()
+                                .map(|stored| stored.calculate_id());
+                            if stored_id.as_deref() == Some(cp.calculate_id().as_str()) || !cp.is_valid_interval() {
+                                continue;
+                            }
+                            let (valid_sigs, validators) = {
+                                let l = safe_lock(&ledger);
+                                (cp.verify_signatures(&|addr: &str| validator_public_key(&l, addr), &los_crypto::verify_signature),
+                                    l.reward_validator_set())
+                            };
+                            let local_vc = validators.len() as u32;
+                            // SECURITY: a deflated validator_count would lower the quorum threshold
+                            if cp.validator_count < local_vc.max(1) {
+                                println!("🚫 Rejected CHECKPOINT_FINAL at height {}: validator_count {} < local {}",
+                                    cp.height, cp.validator_count, local_vc);
+                                continue;
+                            }
+                            if valid_sigs as usize != cp.signatures.len() || !cp.verify_quorum()
+                                || !cp.has_validator_quorum(&validators)
+                            {
+                                println!("🚫 Rejected CHECKPOINT_FINAL at height {}: {}/{} valid signatures",
+                                    cp.height, valid_sigs, cp.signatures.len());
+                                continue;
+                            }
+                            // A quorum attested this root — if we disagree at the same tip,
+                            // we are the ones who diverged (quarantine, keep the checkpoint)
+                            if stored_id.is_none() {
+                                check_checkpoint_root(&cp, None, &ledger, &wasm_engine, &reward_pool,
+                                    &state_quarantine, &metrics_clone);
+                            }
+                            let height = cp.height;
+                            let sc = cp.signature_count;
+                            let vc = cp.validator_count;
+                            match checkpoint_conflict::store_finalized(&checkpoint_manager, cp, &validators, &metrics_clone, &base_data_dir) {
+                                Ok(()) => {
+                                    println!("🏁 Checkpoint FINALIZED at height {} from peer aggregate (sig_count={}/{})", height, sc, vc);
+                                    safe_lock(&pending_checkpoints).remove(&height);
+                                }
+                                Err(e) => eprintln!("⚠️ Failed to store peer checkpoint at {}: {}", height, e),
+                            }
+                        } else if let Some(json_str) = data.strip_prefix(reward_cosign::PROPOSE_PREFIX) {
+                            // ── Co-sign the epoch leader's reward / fee distribution ──
+                            // Only the leader elected for that epoch may propose, and we sign
+                            // only payouts that match our own pool and ledger, at most one
+                            // distribution per (kind, epoch).
+                            if let Ok(cert) = serde_json::from_str::<DistributionCertificate>(json_str) {
+                                let dist = &cert.distribution;
+                                let leader = {
+                                    let rp = safe_lock(&reward_pool);
+                                    let registered: Vec<&String> = rp.validators.keys().collect();
+                                    (!registered.is_empty())
+                                        .then(|| registered[(dist.epoch as usize) % registered.len()].clone())
+                                };
+                                let proposer = cert.signatures.first();
+                                let from_leader = proposer.is_some_and(|p| {
+                                    Some(&p.validator) == leader.as_ref() && p.verify(dist)
+                                });
+                                if !from_leader {
+                                    println!("🚫 Rejected REWARD_PROPOSE for epoch {}: not signed by the epoch leader", dist.epoch);
+                                } else if leader.as_deref() != Some(my_address.as_str()) {
+                                    let check = {
+                                        let l = safe_lock(&ledger);
+                                        let rp = safe_lock(&reward_pool);
+                                        if l.reward_validator_set().contains(&my_address) {
+                                            dist.check_proposal(&l, &rp)
+                                        } else {
+                                            Err("this node is not a staked validator".to_string())
+                                        }
+                                    };
+                                    let digest = dist.digest();
+                                    let key = (dist.kind, dist.epoch);
+                                    match check {
+                                        Err(e) => println!("⚠️ Not co-signing epoch {} {:?} distribution: {}", dist.epoch, dist.kind, e),
+                                        Ok(()) if signed_distributions.get(&key).is_some_and(|d| *d != digest) => {
+                                            println!("🚫 Conflicting epoch {} {:?} distribution from leader — already signed another", dist.epoch, dist.kind);
+                                        }
+                                        Ok(()) => match CoSignature::sign_with(dist, &my_address, &node_public_key, |data| signer.sign(data)) {
+                                            Ok(sig) => {
+                                                signed_distributions.insert(key, digest.clone());
+                                                // Keep the equivocation guard bounded
+                                                signed_distributions.retain(|(_, e), _| e + 64 >= dist.epoch);
+                                                let msg = format!(
+                                                    "{}{}:{}",
+                                                    reward_cosign::SIGN_PREFIX,
+                                                    digest,
+                                                    serde_json::to_string(&sig).unwrap_or_default()
+                                                );
+                                                let _ = tx_out.send(msg).await;
+                                                println!("✍️ Co-signed epoch {} {:?} distribution ({} payouts)",
+                                                    dist.epoch, dist.kind, dist.payouts.len());
+                                            }
+                                            Err(e) => eprintln!("❌ {}", e),
+                                        },
+                                    }
+                                }
+                            }
+                        } else if let Some(rest) = data.strip_prefix(reward_cosign::SIGN_PREFIX) {
+                            // ── Collect co-signatures for a distribution we proposed ──
+                            // The reward task mints it once the signatures reach quorum.
+                            if let Some((digest, json_str)) = rest.split_once(':') {
+                                if let Ok(sig) = serde_json::from_str::<CoSignature>(json_str) {
+                                    let mut pending = safe_lock(&pending_distributions);
+                                    if let Some((cert, _)) = pending.get_mut(digest) {
+                                        if sig.verify(&cert.distribution) && cert.add_signature(sig) {
+                                            println!("✍️ Collected distribution co-signature for epoch {} ({} signatures)",
+                                                cert.distribution.epoch, cert.signatures.len());
                                         }
                                     }
                                 }
-                            }                        } else if let Some(rest) = data.strip_prefix("CHECKPOINT_PROPOSE:") {
+                            }
+                        } else if let Some(json_str) = data.strip_prefix(reward_claims::ACCRUE_PREFIX) {
+                            // ── Claim payout model: accrue a certified reward distribution ──
+                            // Verified like the certificate of a REWARD mint; validators
+                            // then mint their share with Claim blocks (los_core::reward_claims).
+                            if let Ok(cert) = serde_json::from_str::<DistributionCertificate>(json_str) {
+                                let dist = &cert.distribution;
+                                let validators = safe_lock(&ledger).reward_validator_set();
+                                if los_core::chain_spec::active().reward_payout != RewardPayout::Claim {
+                                    println!("🚫 Ignored REWARD_ACCRUE: this network pushes validator rewards");
+                                } else if let Err(e) = cert.verify(&validators) {
+                                    println!("🚫 Rejected REWARD_ACCRUE for epoch {}: {}", dist.epoch, e);
+                                } else {
+                                    let mut rp = safe_lock(&reward_pool);
+                                    if rp.accrue_distribution(&cert) {
+                                        for (addr, amount) in &dist.payouts {
+                                            rp.sync_reward_from_gossip(addr, *amount);
+                                        }
+                                        if rp.enter_fee_only(dist.epoch + 1) {
+                                            log_fee_only_transition(&rp);
+                                        }
+                                        mark_dirty();
+                                        println!("🏆 Epoch {} rewards accrued: {} CIL to {} validators",
+                                            dist.epoch, dist.total().unwrap_or(0), dist.payouts.len());
+                                    }
+                                }
+                            }
+                        } else if let Some(rest) = data.strip_prefix("CHECKPOINT_PROPOSE:") {
                             // ── DESIGN Multi-validator checkpoint coordination ──
+                            // LEGACY: kept for peers that still gossip proposals; new nodes use
+                            // direct CHECKPOINT_REQ request-response instead.
                             // Format: CHECKPOINT_PROPOSE:<height>:<block_hash>:<state_root>:<proposer>:<sig_hex>
                             // When we receive a checkpoint proposal, verify our state matches,
                             // sign the checkpoint data, and broadcast CHECKPOINT_SIGN back.
@@ -9611,13 +13560,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         continue;
                                     }
 
-                                    // Verify our state root matches the proposal
-                                    let our_state_root = {
-                                        let l = safe_lock(&ledger);
-                                        l.compute_state_root()
-                                    };
+                                    // Verify our full state root matches the proposal.
+                                    // Gossiped proposals are unauthenticated at this point,
+                                    // so they never count towards quarantine.
+                                    let our_state_root = local_checkpoint_state(&ledger, &wasm_engine, &reward_pool)
+                                        .map(|local| local.root)
+                                        .unwrap_or_default();
+                                    // Neither a diverged nor a halted node co-signs
+                                    let quarantined = safe_lock(&state_quarantine).is_quarantined()
+                                        || checkpoint_conflict::halted(&checkpoint_manager);
 
-                                    if our_state_root == state_root {
+                                    let staked = safe_lock(&ledger).is_staked_validator(&my_address);
+                                    if our_state_root == state_root && !quarantined && staked {
                                         // State matches — sign the checkpoint
                                         let cp = FinalityCheckpoint::new(
                                             height,
@@ -9627,7 +13581,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             vec![],
                                         );
                                         let signing_data = cp.signing_data();
-                                        if let Ok(my_sig) = los_crypto::sign_message(&signing_data, &secret_key) {
+                                        if let Ok(my_sig) = signer.sign(&signing_data) {
                                             let my_sig_hex = hex::encode(&my_sig);
                                             let sign_msg = format!(
                                                 "CHECKPOINT_SIGN:{}:{}:{}:{}:{}",
@@ -9641,14 +13595,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         // Also add the proposer's signature to our pending map
                                         // SECURITY: Verify proposer's Dilithium5 signature first
                                         if let Ok(proposer_sig) = hex::decode(sig_hex) {
-                                            let proposer_pk: Option<Vec<u8>> = {
-                                                let l = safe_lock(&ledger);
-                                                l.accounts.get(proposer).and_then(|acc| {
-                                                    l.blocks.get(&acc.head).and_then(|blk| {
-                                                        hex::decode(&blk.public_key).ok()
-                                                    })
-                                                })
-                                            };
+                                            let proposer_pk = validator_public_key(&safe_lock(&ledger), proposer);
 
                                             let proposer_verified = if let Some(pk_bytes) = proposer_pk {
                                                 let cp_verify = FinalityCheckpoint::new(
@@ -9668,12 +13615,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             } else {
                                                 let mut pcp = safe_lock(&pending_checkpoints);
                                             let pending = pcp.entry(height).or_insert_with(|| {
-                                                let vc = {
-                                                    let l = safe_lock(&ledger);
-                                                    l.accounts.iter()
-                                                        .filter(|(_, a)| a.balance >= MIN_VALIDATOR_STAKE_CIL)
-                                                        .count() as u32
-                                                };
+                                                let vc = safe_lock(&ledger).reward_validator_set().len() as u32;
                                                 PendingCheckpoint::new(FinalityCheckpoint::new(
                                                     height,
                                                     block_hash.to_string(),
@@ -9688,9 +13630,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             });
                                             } // end proposer_verified
                                         }
-                                    } else {
+                                    } else if !quarantined {
                                         println!("⚠️ Checkpoint proposal state mismatch at height {} (ours={}, theirs={})",
-                                            height, &our_state_root[..16], &state_root[..state_root.len().min(16)]);
+                                            height, &our_state_root[..our_state_root.len().min(16)], &state_root[..state_root.len().min(16)]);
                                     }
                                 }
                             }
@@ -9714,21 +13656,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         // Without this, an attacker can forge signatures for any signer
                                         // and reach quorum trivially — enabling checkpoint manipulation.
                                         //
-                                        // Step 1: Look up signer's public key from their head block
-                                        let signer_pk: Option<Vec<u8>> = {
-                                            let l = safe_lock(&ledger);
-                                            l.accounts.get(signer).and_then(|acc| {
-                                                l.blocks.get(&acc.head).and_then(|blk| {
-                                                    hex::decode(&blk.public_key).ok()
-                                                })
-                                            })
-                                        };
+                                        // Step 1: Look up the signer's key (staked validators only)
+                                        let signer_pk = validator_public_key(&safe_lock(&ledger), signer);
 
                                         let pk_bytes = match signer_pk {
                                             Some(pk) => pk,
                                             None => {
-                                                // Unknown signer (no blocks) — reject
-                                                println!("🚫 Rejected CHECKPOINT_SIGN: unknown signer {} (no blocks found)", &signer[..signer.len().min(16)]);
+                                                // Unknown signer or not a staked validator — reject
+                                                println!("🚫 Rejected CHECKPOINT_SIGN: {} is not a staked validator", &signer[..signer.len().min(16)]);
                                                 continue;
                                             }
                                         };
@@ -9759,8 +13694,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let sig_count = finalized.signature_count;
                                                 let vc = finalized.validator_count;
                                                 drop(pcp); // Release lock before acquiring checkpoint_manager
-                                                let mut cm = safe_lock(&checkpoint_manager);
-                                                match cm.store_checkpoint(finalized) {
+                                                let validators = safe_lock(&ledger).reward_validator_set();
+                                                match checkpoint_conflict::store_finalized(&checkpoint_manager, finalized, &validators, &metrics_clone, &base_data_dir) {
                                                     Ok(()) => {
                                                         println!("🏁 Checkpoint FINALIZED at height {} (sig_count={}/{}, quorum reached!)",
                                                             height, sig_count, vc);
@@ -9876,7 +13811,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                                 // Verify reward amount is within bounds (max 1,000 LOS per block,
                                 // and no more than the epoch reward)
-                                let max_mint_cil = 1_000 * CIL_PER_LOS;
+                                let max_mint_cil = los_core::chain_spec::active().max_mint_per_block_cil;
                                 let epoch_reward = MiningState::epoch_reward_cil(proof_epoch);
                                 if mint_blk.amount > max_mint_cil || mint_blk.amount > epoch_reward {
                                     println!("🚫 Rejected MINE_BLOCK: reward {} exceeds max (cap: {}, epoch: {})",
@@ -9920,7 +13855,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     }
                                     match l.process_block(&mint_blk) {
                                         Ok(_) => {
-                                            SAVE_DIRTY.store(true, Ordering::Release);
+                                            mark_dirty();
                                             let reward_los = mint_blk.amount / CIL_PER_LOS;
                                             println!("⛏️  Replicated MINE_BLOCK: {} → {} LOS (epoch {})",
                                                 get_short_addr(&mint_blk.account), reward_los, proof_epoch);
@@ -10078,13 +14013,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             amount: slashed,
                                             link: format!("PENALTY:DOUBLE_SIGN:{}", block_hash),
                                             signature: "".to_string(),
-                                            public_key: hex::encode(&keys.public_key),
+                                            public_key: hex::encode(&node_public_key),
                                             work: 0,
                                             timestamp,
                                             fee: 0,
+                                            valid_after: 0,
+                                            expires_at: 0,
                                         };
                                         solve_pow(&mut slash_blk);
-                                        slash_blk.signature = match try_sign_hex(slash_blk.signing_hash().as_bytes(), &secret_key) {
+                                        slash_blk.signature = match try_sign_hex(slash_blk.signing_hash().as_bytes(), &*signer) {
                                             Ok(sig) => sig,
                                             Err(e) => { eprintln!("⚠️ Slash signing failed: {}", e); String::new() }
                                         };
@@ -10097,7 +14034,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             Err(e) => eprintln!("⚠️ Slash block failed: {}", e),
                                         }
                                         }
-                                        SAVE_DIRTY.store(true, Ordering::Release);
+                                        mark_dirty();
                                     }
                                 }
                                 (double_sign_detected, gossip)
@@ -10146,13 +14083,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                         amount: slashed,
                                                         link: format!("PENALTY:DOWNTIME:{}", global_height),
                                                         signature: "".to_string(),
-                                                        public_key: hex::encode(&keys.public_key),
+                                                        public_key: hex::encode(&node_public_key),
                                                         work: 0,
                                                         timestamp,
                                                         fee: 0,
+                                                        valid_after: 0,
+                                                        expires_at: 0,
                                                     };
                                                     solve_pow(&mut dt_slash);
-                                                    dt_slash.signature = match try_sign_hex(dt_slash.signing_hash().as_bytes(), &secret_key) {
+                                                    dt_slash.signature = match try_sign_hex(dt_slash.signing_hash().as_bytes(), &*signer) {
                                                         Ok(sig) => sig,
                                                         Err(e) => { eprintln!("⚠️ Downtime slash signing failed: {}", e); String::new() }
                                                     };
@@ -10164,10 +14103,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         }
 
                                         if inc.block_type == BlockType::Mint {
+                                            safe_lock(&rp_sync).record_fee_reward(&inc);
                                             let mint_val = inc.amount / CIL_PER_LOS;
                                             println!("✅ Network Mint Verified: +{} LOS", format_u128(mint_val));
                                         }
-                                        SAVE_DIRTY.store(true, Ordering::Release);
+                                        mark_dirty();
                                         println!("✅ Block Verified: {:?} from {}", inc.block_type, get_short_addr(&inc.account));
 
                                         // AUTO-UNREGISTER: If a Send block caused sender's balance
@@ -10192,18 +14132,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 let mut rb = Block {
                                                     account: my_address.clone(), previous: state.head, block_type: BlockType::Receive,
                                                     amount: inc.amount, link: block_hash, signature: "".to_string(),
-                                                    public_key: hex::encode(&keys.public_key), // Node's public key
+                                                    public_key: hex::encode(&node_public_key), // Node's public key
                                                     work: 0,
                                                     timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs(),
                                                     fee: 0,
+                                                    valid_after: 0,
+                                                    expires_at: 0,
                                                 };
                                                 solve_pow(&mut rb);
-                                                rb.signature = match try_sign_hex(rb.signing_hash().as_bytes(), &secret_key) {
+                                                rb.signature = match try_sign_hex(rb.signing_hash().as_bytes(), &*signer) {
                                                     Ok(sig) => sig,
                                                     Err(e) => { eprintln!("⚠️ Auto-Receive signing failed: {}", e); String::new() }
                                                 };
                                                 if !rb.signature.is_empty() && l.process_block(&rb).is_ok() {
-                                                    SAVE_DIRTY.store(true, Ordering::Release);
+                                                    mark_dirty();
                                                     msgs.push(serde_json::to_string(&rb).unwrap_or_default());
                                                     println!("📥 Incoming Transfer Received Automatically!");
                                                 }
@@ -10212,6 +14154,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     },
                                     Err(e) => {
                                         println!("❌ Block Rejected: {:?} (Sender: {})", e, get_short_addr(&inc.account));
+                                        msgs.extend(fork_monitor::observe(&fork_registry, &l, &inc, "gossip"));
                                     }
                                 }
                                 msgs
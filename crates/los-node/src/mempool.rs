@@ -4,20 +4,97 @@
 // Manages pending transactions before inclusion in blocks.
 // - Priority queue based on fees and stake
 // - Anti-spam protection with duplicate detection
-// - Automatic transaction expiration
+// - Size limits (total and per sender). When full, the lowest-fee transaction
+//   is evicted, but only for a newcomer that pays strictly more.
+// - Replace-by-fee: a transaction with the same (account, previous) as a
+//   pending one replaces it only with a strictly higher fee. Only one of the
+//   two could ever be applied to the account chain.
+// - Expiration by the block's own timestamp (consensus clock), so a stuck
+//   transaction ages out the same way on every node
+// - Time-locked sends (Block::valid_after in the future) are held until
+//   release_due() hands them to consensus; their TTL starts at valid_after
+//
+// Limits come from MempoolConfig::from_env():
+//   LOS_MEMPOOL_MAX_SIZE, LOS_MEMPOOL_MAX_PER_SENDER, LOS_MEMPOOL_TX_TTL_SECS,
+//   LOS_MEMPOOL_MAX_SCHEDULE_SECS
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use los_core::Block;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Maximum transactions in mempool
-const MAX_MEMPOOL_SIZE: usize = 10_000;
+pub const MAX_MEMPOOL_SIZE: usize = 10_000;
+
+/// Maximum pending transactions per sender
+pub const MAX_TXS_PER_SENDER: usize = 64;
 
-/// Transaction expires after 24 hours
+/// Transaction expires 24 hours after its block timestamp
 const TX_EXPIRATION_SECS: u64 = 86_400;
 
+/// Time-locked sends may be scheduled at most 31 days ahead
+pub const MAX_SCHEDULE_AHEAD_SECS: u64 = 31 * 86_400;
+
+/// Held sends are released this long after valid_after, so validators whose
+/// consensus clock lags slightly do not refuse the vote
+pub const TIME_LOCK_RELEASE_SLACK_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolConfig {
+    pub max_size: usize,
+    pub max_per_sender: usize,
+    pub tx_ttl_secs: u64,
+    /// Furthest valid_after accepted, relative to now
+    pub max_schedule_ahead_secs: u64,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: MAX_MEMPOOL_SIZE,
+            max_per_sender: MAX_TXS_PER_SENDER,
+            tx_ttl_secs: TX_EXPIRATION_SECS,
+            max_schedule_ahead_secs: MAX_SCHEDULE_AHEAD_SECS,
+        }
+    }
+}
+
+impl MempoolConfig {
+    /// Defaults overridden by LOS_MEMPOOL_* environment variables
+    pub fn from_env() -> Self {
+        fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+            match std::env::var(name) {
+                Ok(v) => v.parse().unwrap_or_else(|_| {
+                    eprintln!("⚠️  Invalid {}='{}', using default", name, v);
+                    default
+                }),
+                Err(_) => default,
+            }
+        }
+        let d = Self::default();
+        Self {
+            max_size: env_or("LOS_MEMPOOL_MAX_SIZE", d.max_size).max(1),
+            max_per_sender: env_or("LOS_MEMPOOL_MAX_PER_SENDER", d.max_per_sender).max(1),
+            tx_ttl_secs: env_or("LOS_MEMPOOL_TX_TTL_SECS", d.tx_ttl_secs).max(1),
+            max_schedule_ahead_secs: env_or(
+                "LOS_MEMPOOL_MAX_SCHEDULE_SECS",
+                d.max_schedule_ahead_secs,
+            ),
+        }
+    }
+}
+
+/// Outcome of a successful admission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Admission {
+    pub tx_hash: String,
+    /// Pending transaction displaced by replace-by-fee
+    pub replaced: Option<String>,
+    /// Lowest-fee transaction evicted to make room
+    pub evicted: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MempoolTransaction {
     pub block: Block,
@@ -38,26 +115,63 @@ pub struct Mempool {
     /// Track transactions by sender address
     by_sender: HashMap<String, Vec<String>>,
 
+    /// "account:previous" → tx_hash (at most one pending successor per block)
+    by_previous: HashMap<String, String>,
+
+    /// Time-locked sends not yet released: (valid_after, tx_hash)
+    held: BTreeSet<(u64, String)>,
+
+    config: MempoolConfig,
+
     /// Statistics
     pub total_received: u64,
     pub total_accepted: u64,
     pub total_rejected: u64,
     pub total_expired: u64,
+    pub total_evicted: u64,
+    pub total_replaced: u64,
+}
+
+/// Whether a time-locked send must still be held at `now` (lock not over,
+/// or over for less than TIME_LOCK_RELEASE_SLACK_SECS)
+pub fn awaits_release(block: &Block, now: u64) -> bool {
+    block.valid_after != 0
+        && block
+            .valid_after
+            .saturating_add(TIME_LOCK_RELEASE_SLACK_SECS)
+            > now
+}
+
+fn previous_key(block: &Block) -> String {
+    format!("{}:{}", block.account, block.previous)
 }
 
 impl Mempool {
     pub fn new() -> Self {
+        Self::with_config(MempoolConfig::default())
+    }
+
+    pub fn with_config(config: MempoolConfig) -> Self {
         Self {
             transactions: HashMap::new(),
             priority_queue: BTreeMap::new(),
             by_sender: HashMap::new(),
+            by_previous: HashMap::new(),
+            held: BTreeSet::new(),
+            config,
             total_received: 0,
             total_accepted: 0,
             total_rejected: 0,
             total_expired: 0,
+            total_evicted: 0,
+            total_replaced: 0,
         }
     }
 
+    pub fn config(&self) -> MempoolConfig {
+        self.config
+    }
+
     /// Add transaction to mempool
     /// Returns Ok(tx_hash) if accepted, Err(reason) if rejected
     pub fn add_transaction(
@@ -66,38 +180,102 @@ impl Mempool {
         fee: u64,
         priority: u64,
     ) -> Result<String, String> {
+        self.admit(block, fee, priority).map(|a| a.tx_hash)
+    }
+
+    /// Add transaction, reporting any replaced or evicted transaction
+    pub fn admit(&mut self, block: Block, fee: u64, priority: u64) -> Result<Admission, String> {
+        let now = los_core::consensus_clock::now();
+        self.admit_at(block, fee, priority, now)
+    }
+
+    fn reject(&mut self, reason: String) -> Result<Admission, String> {
+        self.total_rejected += 1;
+        Err(reason)
+    }
+
+    /// admit() against an explicit consensus time
+    pub fn admit_at(
+        &mut self,
+        block: Block,
+        fee: u64,
+        priority: u64,
+        now: u64,
+    ) -> Result<Admission, String> {
         self.total_received += 1;
 
         let tx_hash = block.calculate_hash();
 
         // Check if already in mempool
         if self.transactions.contains_key(&tx_hash) {
-            self.total_rejected += 1;
-            return Err("Transaction already in mempool".to_string());
+            return self.reject("Transaction already in mempool".to_string());
+        }
+
+        // Validate basic block structure
+        if block.account.is_empty() {
+            return self.reject("Invalid block: empty account".to_string());
+        }
+
+        if block.signature.is_empty() {
+            return self.reject("Invalid block: missing signature".to_string());
+        }
+
+        if now.saturating_sub(block.valid_from()) > self.config.tx_ttl_secs {
+            return self.reject(format!(
+                "Transaction expired (timestamp older than {}s)",
+                self.config.tx_ttl_secs
+            ));
+        }
+
+        if block.valid_after > now.saturating_add(self.config.max_schedule_ahead_secs) {
+            return self.reject(format!(
+                "valid_after {} is more than {}s ahead",
+                block.valid_after, self.config.max_schedule_ahead_secs
+            ));
         }
 
-        // Check mempool size limit
-        if self.transactions.len() >= MAX_MEMPOOL_SIZE {
-            // Try to evict lowest priority transaction
-            if let Some(lowest_priority) = self.priority_queue.keys().next().cloned() {
-                if lowest_priority < priority {
-                    self.evict_lowest_priority();
-                } else {
-                    self.total_rejected += 1;
-                    return Err("Mempool full and transaction priority too low".to_string());
+        // Replace-by-fee: same account + previous → only one can ever be applied
+        let replaced = match self.by_previous.get(&previous_key(&block)) {
+            Some(existing) => {
+                let existing_fee = self.transactions.get(existing).map_or(0, |tx| tx.fee);
+                if fee <= existing_fee {
+                    return self.reject(format!(
+                        "Conflicts with pending {} — replacement fee must exceed {}",
+                        existing, existing_fee
+                    ));
                 }
+                Some(existing.clone())
             }
+            None => None,
+        };
+
+        // Per-sender limit (a replacement does not add to the sender's count)
+        let sender_count = self.by_sender.get(&block.account).map_or(0, Vec::len);
+        if replaced.is_none() && sender_count >= self.config.max_per_sender {
+            return self.reject(format!(
+                "Sender has {} pending transactions (limit {})",
+                sender_count, self.config.max_per_sender
+            ));
         }
 
-        // Validate basic block structure
-        if block.account.is_empty() {
-            self.total_rejected += 1;
-            return Err("Invalid block: empty account".to_string());
+        // Check mempool size limit: evict the cheapest only for a better-paying newcomer
+        let mut evicted = None;
+        if replaced.is_none() && self.transactions.len() >= self.config.max_size {
+            match self.lowest_fee_transaction() {
+                Some((hash, lowest_fee)) if lowest_fee < fee => evicted = Some(hash),
+                _ => {
+                    return self.reject("Mempool full and transaction fee too low".to_string());
+                }
+            }
         }
 
-        if block.signature.is_empty() {
-            self.total_rejected += 1;
-            return Err("Invalid block: missing signature".to_string());
+        if let Some(hash) = &replaced {
+            self.remove_transaction(hash);
+            self.total_replaced += 1;
+        }
+        if let Some(hash) = &evicted {
+            self.remove_transaction(hash);
+            self.total_evicted += 1;
         }
 
         // Create mempool transaction
@@ -126,9 +304,20 @@ impl Mempool {
             .or_default()
             .push(tx_hash.clone());
 
+        self.by_previous
+            .insert(previous_key(&block), tx_hash.clone());
+
+        if awaits_release(&block, now) {
+            self.held.insert((block.valid_after, tx_hash.clone()));
+        }
+
         self.total_accepted += 1;
 
-        Ok(tx_hash)
+        Ok(Admission {
+            tx_hash,
+            replaced,
+            evicted,
+        })
     }
 
     /// Get transaction by hash
@@ -155,18 +344,29 @@ impl Mempool {
                 }
             }
 
+            let key = previous_key(&tx.block);
+            if self.by_previous.get(&key).map(String::as_str) == Some(tx_hash) {
+                self.by_previous.remove(&key);
+            }
+
+            self.held
+                .remove(&(tx.block.valid_after, tx_hash.to_string()));
+
             return Some(tx);
         }
         None
     }
 
-    /// Get next N transactions with highest priority
+    /// Get next N transactions with highest priority (held sends excluded)
     pub fn get_next_transactions(&self, count: usize) -> Vec<String> {
         let mut result = Vec::new();
 
         // Iterate priority queue from highest to lowest
         for (_, hashes) in self.priority_queue.iter().rev() {
             for hash in hashes {
+                if self.is_held(hash) {
+                    continue;
+                }
                 result.push(hash.clone());
                 if result.len() >= count {
                     return result;
@@ -182,17 +382,44 @@ impl Mempool {
         self.by_sender.get(address).cloned().unwrap_or_default()
     }
 
-    /// Remove expired transactions (older than 24 hours)
+    /// Whether `tx_hash` is a time-locked send still waiting for release
+    pub fn is_held(&self, tx_hash: &str) -> bool {
+        self.transactions.get(tx_hash).is_some_and(|tx| {
+            self.held
+                .contains(&(tx.block.valid_after, tx_hash.to_string()))
+        })
+    }
+
+    /// Held sends whose time lock ended (plus TIME_LOCK_RELEASE_SLACK_SECS)
+    /// at `now`, earliest first. They stay in the mempool as ordinary
+    /// pending transactions until finalized or expired.
+    pub fn release_due(&mut self, now: u64) -> Vec<(String, Block)> {
+        let cutoff = now.saturating_sub(TIME_LOCK_RELEASE_SLACK_SECS);
+        let mut due = Vec::new();
+        while let Some((valid_after, hash)) = self.held.first().cloned() {
+            if valid_after > cutoff {
+                break;
+            }
+            self.held.pop_first();
+            if let Some(tx) = self.transactions.get(&hash) {
+                due.push((hash, tx.block.clone()));
+            }
+        }
+        due
+    }
+
+    /// Remove transactions whose block timestamp is older than the TTL
     pub fn remove_expired(&mut self) -> usize {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        self.remove_expired_at(los_core::consensus_clock::now())
+    }
 
+    /// remove_expired() against an explicit consensus time
+    pub fn remove_expired_at(&mut self, now: u64) -> usize {
+        let ttl = self.config.tx_ttl_secs;
         let expired: Vec<String> = self
             .transactions
             .iter()
-            .filter(|(_, tx)| now.saturating_sub(tx.received_at) > TX_EXPIRATION_SECS)
+            .filter(|(_, tx)| now.saturating_sub(tx.block.valid_from()) > ttl)
             .map(|(hash, _)| hash.clone())
             .collect();
 
@@ -205,14 +432,16 @@ impl Mempool {
         count
     }
 
-    /// Evict lowest priority transaction
-    fn evict_lowest_priority(&mut self) {
-        if let Some((_, hashes)) = self.priority_queue.iter().next() {
-            if let Some(hash) = hashes.first() {
-                let hash = hash.clone();
-                self.remove_transaction(&hash);
-            }
-        }
+    /// Cheapest transaction (oldest first on equal fee)
+    fn lowest_fee_transaction(&self) -> Option<(String, u64)> {
+        self.transactions
+            .iter()
+            .min_by(|(ha, a), (hb, b)| {
+                (a.fee, a.received_at)
+                    .cmp(&(b.fee, b.received_at))
+                    .then_with(|| ha.cmp(hb))
+            })
+            .map(|(hash, tx)| (hash.clone(), tx.fee))
     }
 
     /// Get mempool statistics
@@ -223,7 +452,10 @@ impl Mempool {
             total_accepted: self.total_accepted,
             total_rejected: self.total_rejected,
             total_expired: self.total_expired,
+            total_evicted: self.total_evicted,
+            total_replaced: self.total_replaced,
             unique_senders: self.by_sender.len(),
+            scheduled: self.held.len(),
         }
     }
 
@@ -247,6 +479,8 @@ impl Mempool {
         self.transactions.clear();
         self.priority_queue.clear();
         self.by_sender.clear();
+        self.by_previous.clear();
+        self.held.clear();
     }
 }
 
@@ -257,7 +491,12 @@ pub struct MempoolStats {
     pub total_accepted: u64,
     pub total_rejected: u64,
     pub total_expired: u64,
+    pub total_evicted: u64,
+    pub total_replaced: u64,
     pub unique_senders: usize,
+    /// Time-locked sends waiting for their valid_after
+    #[serde(default)]
+    pub scheduled: usize,
 }
 
 #[cfg(test)]
@@ -275,8 +514,11 @@ mod tests {
             signature: "test_signature".to_string(),
             public_key: "test_pubkey".to_string(),
             work: 0,
-            timestamp: 1234567890,
+            // Expiry is judged by block timestamp, so test blocks are fresh
+            timestamp: los_core::consensus_clock::now(),
             fee: 0,
+            valid_after: 0,
+            expires_at: 0,
         }
     }
 
@@ -340,7 +582,8 @@ mod tests {
         let mut mempool = Mempool::new();
 
         let block1 = create_test_block("sender1", 1000);
-        let block2 = create_test_block("sender1", 2000);
+        let mut block2 = create_test_block("sender1", 2000);
+        block2.previous = block1.calculate_hash(); // chained, not conflicting
         let block3 = create_test_block("sender2", 3000);
 
         mempool.add_transaction(block1, 100, 1000).unwrap();
@@ -353,4 +596,115 @@ mod tests {
         let sender2_txs = mempool.get_transactions_by_sender("sender2");
         assert_eq!(sender2_txs.len(), 1);
     }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let mut mempool = Mempool::new();
+        let original = create_test_block("sender1", 1000);
+        let hash = mempool.add_transaction(original, 100, 1000).unwrap();
+
+        // Same previous, same fee → rejected
+        let err = mempool
+            .add_transaction(create_test_block("sender1", 999), 100, 1000)
+            .unwrap_err();
+        assert!(err.contains("replacement fee must exceed 100"));
+
+        // Strictly higher fee replaces
+        let admission = mempool
+            .admit(create_test_block("sender1", 998), 101, 1000)
+            .unwrap();
+        assert_eq!(admission.replaced.as_deref(), Some(hash.as_str()));
+        assert!(!mempool.contains(&hash));
+        assert_eq!(mempool.len(), 1);
+        assert_eq!(mempool.stats().total_replaced, 1);
+        assert_eq!(mempool.get_transactions_by_sender("sender1").len(), 1);
+    }
+
+    #[test]
+    fn test_full_mempool_evicts_lowest_fee() {
+        let mut mempool = Mempool::with_config(MempoolConfig {
+            max_size: 2,
+            max_per_sender: 1,
+            tx_ttl_secs: 60,
+            ..MempoolConfig::default()
+        });
+        let cheap = mempool
+            .add_transaction(create_test_block("a", 1), 10, 1)
+            .unwrap();
+        mempool
+            .add_transaction(create_test_block("b", 1), 50, 1)
+            .unwrap();
+
+        // Not better than the cheapest → rejected
+        assert!(mempool
+            .add_transaction(create_test_block("c", 1), 10, 999)
+            .is_err());
+        let admission = mempool.admit(create_test_block("c", 1), 11, 1).unwrap();
+        assert_eq!(admission.evicted.as_deref(), Some(cheap.as_str()));
+        assert_eq!(mempool.len(), 2);
+        assert_eq!(mempool.stats().total_evicted, 1);
+
+        // Per-sender limit
+        let mut next = create_test_block("c", 1);
+        next.previous = admission.tx_hash.clone();
+        assert!(mempool
+            .add_transaction(next, 1_000, 1)
+            .unwrap_err()
+            .contains("limit 1"));
+    }
+
+    #[test]
+    fn test_expiry_uses_block_timestamp() {
+        let mut mempool = Mempool::with_config(MempoolConfig {
+            tx_ttl_secs: 60,
+            ..MempoolConfig::default()
+        });
+        let mut block = create_test_block("sender1", 1);
+        block.timestamp = 1_000;
+        assert!(mempool.admit_at(block.clone(), 1, 1, 2_000).is_err());
+        mempool.admit_at(block, 1, 1, 1_050).unwrap();
+        assert_eq!(mempool.remove_expired_at(1_060), 0);
+        assert_eq!(mempool.remove_expired_at(1_061), 1);
+        assert_eq!(mempool.stats().total_expired, 1);
+    }
+
+    #[test]
+    fn test_time_locked_send_is_held_until_due() {
+        let mut mempool = Mempool::with_config(MempoolConfig {
+            tx_ttl_secs: 60,
+            max_schedule_ahead_secs: 10_000,
+            ..MempoolConfig::default()
+        });
+        let mut block = create_test_block("payroll", 1);
+        block.timestamp = 1_000;
+        block.valid_after = 5_000;
+
+        let mut too_far = block.clone();
+        too_far.valid_after = 1_000 + 10_001;
+        assert!(mempool
+            .admit_at(too_far, 1, 1, 1_000)
+            .unwrap_err()
+            .contains("ahead"));
+
+        let hash = mempool.admit_at(block, 1, 1, 1_000).unwrap().tx_hash;
+        let ready = mempool
+            .admit_at(create_test_block("other", 1), 1, 1, 1_000)
+            .unwrap()
+            .tx_hash;
+        assert!(mempool.is_held(&hash));
+        assert_eq!(mempool.get_next_transactions(10), vec![ready]);
+        assert_eq!(mempool.stats().scheduled, 1);
+
+        // Not expired while waiting: TTL runs from valid_after
+        assert_eq!(mempool.remove_expired_at(4_000), 0);
+        assert!(mempool.release_due(5_000).is_empty());
+        let due = mempool.release_due(5_000 + TIME_LOCK_RELEASE_SLACK_SECS);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, hash);
+        assert!(!mempool.is_held(&hash));
+        assert!(mempool.release_due(6_000).is_empty());
+        assert!(mempool.contains(&hash));
+        assert_eq!(mempool.remove_expired_at(5_061), 1);
+        assert!(!mempool.contains(&hash));
+    }
 }
@@ -6,8 +6,8 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use prometheus::{
-    Counter, Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntGauge, Opts, Registry,
-    TextEncoder,
+    Counter, Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
 };
 use std::sync::Arc;
 
@@ -31,6 +31,12 @@ pub struct LosMetrics {
     pub db_accounts_count: IntGauge,
     pub db_save_duration_seconds: Histogram,
     pub db_load_duration_seconds: Histogram,
+    /// Size on disk as a percentage of live data (storage maintenance)
+    pub db_fragmentation_pct: IntGauge,
+    /// Free bytes on the data directory's filesystem
+    pub disk_free_bytes: IntGauge,
+    /// 1 while free disk is below LOS_DISK_MIN_FREE_PCT
+    pub storage_low_disk: IntGauge,
 
     // Consensus metrics
     pub consensus_rounds_total: IntCounter,
@@ -38,16 +44,48 @@ pub struct LosMetrics {
     pub consensus_latency_seconds: Histogram,
     pub active_validators: IntGauge,
     pub validator_votes_total: IntCounter,
+    /// Checkpoints whose root differed from ours at the same tip block
+    pub state_root_mismatches_total: IntCounter,
+    /// 1 while quarantined after a state root divergence
+    pub state_quarantined: IntGauge,
+    /// Conflicting finalized checkpoints detected
+    pub checkpoint_conflicts_total: IntCounter,
+    /// 1 while finality is halted by an unresolved checkpoint conflict
+    pub finality_halted: IntGauge,
 
     // Distribution metrics (PoW mining)
     pub mint_remaining_supply: Gauge,
 
+    // Validator reward metrics
+    /// CIL left in the validator reward pool
+    pub reward_pool_remaining_cil: Gauge,
+    /// 1 once the pool is exhausted and validators earn fees only
+    pub reward_fee_only: IntGauge,
+
     // Network metrics
     pub connected_peers: IntGauge,
+    /// Peer count below which the network task re-dials bootstrap nodes
+    pub min_peers: IntGauge,
     pub p2p_messages_received_total: IntCounter,
     pub p2p_messages_sent_total: IntCounter,
     pub p2p_bytes_received_total: Counter,
     pub p2p_bytes_sent_total: Counter,
+    /// Gossip messages checked against the persistent seen-cache
+    pub gossip_dedup_checked_total: IntCounter,
+    /// Gossip messages dropped as duplicates (replays within the TTL window)
+    pub gossip_duplicates_suppressed_total: IntCounter,
+    /// Duplicate suppression rate since startup, basis points (10000 = 100%)
+    pub gossip_duplicate_rate_bps: IntGauge,
+    /// Gossip messages dropped by the pre-parse guard (size, schema, depth)
+    pub gossip_rejected_total: IntCounter,
+    /// Receive time − publisher's envelope origin time (clock-skewed samples excluded)
+    pub gossip_propagation_seconds: Histogram,
+    /// Network events waiting per priority lane (consensus, blocks, bulk)
+    pub gossip_lane_depth: IntGaugeVec,
+    /// Network events taken from each lane by the event loop
+    pub gossip_lane_processed_total: IntCounterVec,
+    /// Oldest events dropped because their lane was full
+    pub gossip_lane_dropped_total: IntCounterVec,
 
     // API metrics
     pub api_requests_total: IntCounter,
@@ -56,9 +94,19 @@ pub struct LosMetrics {
     pub grpc_requests_total: IntCounter,
     pub grpc_errors_total: IntCounter,
 
+    // Mempool metrics
+    pub mempool_size: IntGauge,
+    pub mempool_evictions_total: IntCounter,
+    pub mempool_replacements_total: IntCounter,
+    pub mempool_expired_total: IntCounter,
+
     // Rate limiter metrics
     pub rate_limit_rejections_total: IntCounter,
     pub rate_limit_active_ips: IntGauge,
+    /// Requests accepted per API key (X-API-Key)
+    pub api_key_requests_total: IntCounterVec,
+    /// Requests rejected per API key for exceeding its tier limit
+    pub api_key_rate_limited_total: IntCounterVec,
 
     // Slashing metrics
     pub slashing_events_total: IntCounter,
@@ -68,6 +116,18 @@ pub struct LosMetrics {
     pub contracts_deployed_total: IntCounter,
     pub contract_executions_total: IntCounter,
     pub contract_gas_used_total: Counter,
+    /// Per-contract cumulative gas (top consumers only, refreshed on scrape)
+    pub contract_gas_used: IntGaugeVec,
+    pub contract_calls: IntGaugeVec,
+    pub contract_failures: IntGaugeVec,
+    pub contract_avg_exec_micros: IntGaugeVec,
+    /// POST /dry-run-contract calls answered from the read-call cache
+    pub contract_read_cache_hits_total: IntCounter,
+    /// Cacheable dry runs that had to execute (no entry, or state changed)
+    pub contract_read_cache_misses_total: IntCounter,
+    pub contract_read_cache_entries: IntGauge,
+    /// Contract events rolled up into epoch summaries and deleted (event_retention.rs)
+    pub contract_events_pruned_total: IntCounter,
 
     // Tor Hidden Service Health metrics
     /// 1 = own .onion address is reachable via Tor SOCKS5, 0 = unreachable
@@ -78,6 +138,14 @@ pub struct LosMetrics {
     pub tor_self_ping_total: IntCounter,
     /// Total self-ping failures
     pub tor_self_ping_failures_total: IntCounter,
+
+    // Oracle price feed metrics
+    /// Latest consensus price per symbol, micro-USD
+    pub oracle_price_micro_usd: IntGaugeVec,
+    /// Latest source agreement per symbol, basis points
+    pub oracle_confidence_bps: IntGaugeVec,
+    /// Deviation alerts fired per symbol (price moved > threshold within window)
+    pub oracle_deviation_alerts_total: IntCounterVec,
 }
 
 impl LosMetrics {
@@ -165,6 +233,24 @@ impl LosMetrics {
         )?;
         registry.register(Box::new(db_load_duration_seconds.clone()))?;
 
+        let db_fragmentation_pct = IntGauge::with_opts(Opts::new(
+            "los_db_fragmentation_pct",
+            "Database size on disk as a percentage of live data",
+        ))?;
+        registry.register(Box::new(db_fragmentation_pct.clone()))?;
+
+        let disk_free_bytes = IntGauge::with_opts(Opts::new(
+            "los_disk_free_bytes",
+            "Free bytes on the data directory filesystem",
+        ))?;
+        registry.register(Box::new(disk_free_bytes.clone()))?;
+
+        let storage_low_disk = IntGauge::with_opts(Opts::new(
+            "los_storage_low_disk",
+            "1 when free disk space is below the alert threshold",
+        ))?;
+        registry.register(Box::new(storage_low_disk.clone()))?;
+
         // Consensus metrics
         let consensus_rounds_total = IntCounter::with_opts(Opts::new(
             "los_consensus_rounds_total",
@@ -199,6 +285,30 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(validator_votes_total.clone()))?;
 
+        let state_root_mismatches_total = IntCounter::with_opts(Opts::new(
+            "los_state_root_mismatches_total",
+            "Checkpoints whose state root diverged from the local state",
+        ))?;
+        registry.register(Box::new(state_root_mismatches_total.clone()))?;
+
+        let state_quarantined = IntGauge::with_opts(Opts::new(
+            "los_state_quarantined",
+            "1 while the node is quarantined after a state root divergence",
+        ))?;
+        registry.register(Box::new(state_quarantined.clone()))?;
+
+        let checkpoint_conflicts_total = IntCounter::with_opts(Opts::new(
+            "los_checkpoint_conflicts_total",
+            "Quorum-signed checkpoints contradicting a finalized checkpoint at the same height",
+        ))?;
+        registry.register(Box::new(checkpoint_conflicts_total.clone()))?;
+
+        let finality_halted = IntGauge::with_opts(Opts::new(
+            "los_finality_halted",
+            "1 while finality is halted by an unresolved checkpoint conflict",
+        ))?;
+        registry.register(Box::new(finality_halted.clone()))?;
+
         // Distribution metrics (PoW mining)
         let mint_remaining_supply = Gauge::with_opts(Opts::new(
             "los_mint_remaining_supply",
@@ -206,6 +316,19 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(mint_remaining_supply.clone()))?;
 
+        // Validator reward metrics
+        let reward_pool_remaining_cil = Gauge::with_opts(Opts::new(
+            "los_reward_pool_remaining_cil",
+            "CIL left in the validator reward pool",
+        ))?;
+        registry.register(Box::new(reward_pool_remaining_cil.clone()))?;
+
+        let reward_fee_only = IntGauge::with_opts(Opts::new(
+            "los_reward_fee_only",
+            "1 once the reward pool is exhausted and validators earn fees only",
+        ))?;
+        registry.register(Box::new(reward_fee_only.clone()))?;
+
         // Network metrics
         let connected_peers = IntGauge::with_opts(Opts::new(
             "los_connected_peers",
@@ -213,6 +336,12 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(connected_peers.clone()))?;
 
+        let min_peers = IntGauge::with_opts(Opts::new(
+            "los_min_peers",
+            "Minimum healthy P2P peer count (bootstrap reconnect threshold)",
+        ))?;
+        registry.register(Box::new(min_peers.clone()))?;
+
         let p2p_messages_received_total = IntCounter::with_opts(Opts::new(
             "los_p2p_messages_received_total",
             "Total P2P messages received",
@@ -237,6 +366,66 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(p2p_bytes_sent_total.clone()))?;
 
+        let gossip_dedup_checked_total = IntCounter::with_opts(Opts::new(
+            "los_gossip_dedup_checked_total",
+            "Gossip messages checked against the persistent seen-cache",
+        ))?;
+        registry.register(Box::new(gossip_dedup_checked_total.clone()))?;
+
+        let gossip_duplicates_suppressed_total = IntCounter::with_opts(Opts::new(
+            "los_gossip_duplicates_suppressed_total",
+            "Gossip messages suppressed as duplicates by the seen-cache",
+        ))?;
+        registry.register(Box::new(gossip_duplicates_suppressed_total.clone()))?;
+
+        let gossip_rejected_total = IntCounter::with_opts(Opts::new(
+            "los_gossip_rejected_total",
+            "Gossip messages dropped before parsing (oversized, malformed or too deeply nested)",
+        ))?;
+        registry.register(Box::new(gossip_rejected_total.clone()))?;
+
+        let gossip_duplicate_rate_bps = IntGauge::with_opts(Opts::new(
+            "los_gossip_duplicate_rate_bps",
+            "Gossip duplicate suppression rate in basis points",
+        ))?;
+        registry.register(Box::new(gossip_duplicate_rate_bps.clone()))?;
+
+        let gossip_propagation_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "los_gossip_propagation_seconds",
+                "Gossip propagation delay from publisher origin time to local receipt",
+            )
+            .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0]),
+        )?;
+        registry.register(Box::new(gossip_propagation_seconds.clone()))?;
+
+        let gossip_lane_depth = IntGaugeVec::new(
+            Opts::new(
+                "los_gossip_lane_depth",
+                "Network events waiting in each priority lane",
+            ),
+            &["lane"],
+        )?;
+        registry.register(Box::new(gossip_lane_depth.clone()))?;
+
+        let gossip_lane_processed_total = IntCounterVec::new(
+            Opts::new(
+                "los_gossip_lane_processed_total",
+                "Network events processed per priority lane",
+            ),
+            &["lane"],
+        )?;
+        registry.register(Box::new(gossip_lane_processed_total.clone()))?;
+
+        let gossip_lane_dropped_total = IntCounterVec::new(
+            Opts::new(
+                "los_gossip_lane_dropped_total",
+                "Network events dropped because their priority lane was full",
+            ),
+            &["lane"],
+        )?;
+        registry.register(Box::new(gossip_lane_dropped_total.clone()))?;
+
         // API metrics
         let api_requests_total = IntCounter::with_opts(Opts::new(
             "los_api_requests_total",
@@ -278,6 +467,24 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(rate_limit_active_ips.clone()))?;
 
+        let api_key_requests_total = IntCounterVec::new(
+            Opts::new(
+                "los_api_key_requests_total",
+                "API requests accepted per API key",
+            ),
+            &["key_id", "tier"],
+        )?;
+        registry.register(Box::new(api_key_requests_total.clone()))?;
+
+        let api_key_rate_limited_total = IntCounterVec::new(
+            Opts::new(
+                "los_api_key_rate_limited_total",
+                "API requests rejected per API key (tier limit exceeded)",
+            ),
+            &["key_id", "tier"],
+        )?;
+        registry.register(Box::new(api_key_rate_limited_total.clone()))?;
+
         // Slashing metrics
         let slashing_events_total = IntCounter::with_opts(Opts::new(
             "los_slashing_events_total",
@@ -310,6 +517,63 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(contract_gas_used_total.clone()))?;
 
+        let contract_gas_used = IntGaugeVec::new(
+            Opts::new(
+                "los_contract_gas_used",
+                "Cumulative gas used per contract (top consumers)",
+            ),
+            &["contract"],
+        )?;
+        registry.register(Box::new(contract_gas_used.clone()))?;
+
+        let contract_calls = IntGaugeVec::new(
+            Opts::new("los_contract_calls", "Calls per contract (top consumers)"),
+            &["contract"],
+        )?;
+        registry.register(Box::new(contract_calls.clone()))?;
+
+        let contract_failures = IntGaugeVec::new(
+            Opts::new(
+                "los_contract_failures",
+                "Failed calls per contract (top consumers)",
+            ),
+            &["contract"],
+        )?;
+        registry.register(Box::new(contract_failures.clone()))?;
+
+        let contract_avg_exec_micros = IntGaugeVec::new(
+            Opts::new(
+                "los_contract_avg_exec_micros",
+                "Average execution time per contract in microseconds (top consumers)",
+            ),
+            &["contract"],
+        )?;
+        registry.register(Box::new(contract_avg_exec_micros.clone()))?;
+
+        let contract_read_cache_hits_total = IntCounter::with_opts(Opts::new(
+            "los_contract_read_cache_hits_total",
+            "Dry-run contract calls served from the read-call result cache",
+        ))?;
+        registry.register(Box::new(contract_read_cache_hits_total.clone()))?;
+
+        let contract_read_cache_misses_total = IntCounter::with_opts(Opts::new(
+            "los_contract_read_cache_misses_total",
+            "Dry-run contract calls that missed the read-call result cache",
+        ))?;
+        registry.register(Box::new(contract_read_cache_misses_total.clone()))?;
+
+        let contract_read_cache_entries = IntGauge::with_opts(Opts::new(
+            "los_contract_read_cache_entries",
+            "Entries in the read-call result cache",
+        ))?;
+        registry.register(Box::new(contract_read_cache_entries.clone()))?;
+
+        let contract_events_pruned_total = IntCounter::with_opts(Opts::new(
+            "los_contract_events_pruned_total",
+            "Contract events rolled up into per-epoch summaries and deleted",
+        ))?;
+        registry.register(Box::new(contract_events_pruned_total.clone()))?;
+
         // Tor Hidden Service Health metrics
         let tor_onion_reachable = IntGauge::with_opts(Opts::new(
             "los_tor_onion_reachable",
@@ -335,6 +599,59 @@ impl LosMetrics {
         ))?;
         registry.register(Box::new(tor_self_ping_failures_total.clone()))?;
 
+        // Mempool metrics
+        let mempool_size = IntGauge::with_opts(Opts::new(
+            "los_mempool_size",
+            "Pending transactions in the mempool",
+        ))?;
+        registry.register(Box::new(mempool_size.clone()))?;
+
+        let mempool_evictions_total = IntCounter::with_opts(Opts::new(
+            "los_mempool_evictions_total",
+            "Lowest-fee transactions evicted from a full mempool",
+        ))?;
+        registry.register(Box::new(mempool_evictions_total.clone()))?;
+
+        let mempool_replacements_total = IntCounter::with_opts(Opts::new(
+            "los_mempool_replacements_total",
+            "Pending transactions replaced by a higher-fee conflict",
+        ))?;
+        registry.register(Box::new(mempool_replacements_total.clone()))?;
+
+        let mempool_expired_total = IntCounter::with_opts(Opts::new(
+            "los_mempool_expired_total",
+            "Pending transactions dropped after their TTL",
+        ))?;
+        registry.register(Box::new(mempool_expired_total.clone()))?;
+
+        // Oracle price feed metrics
+        let oracle_price_micro_usd = IntGaugeVec::new(
+            Opts::new(
+                "los_oracle_price_micro_usd",
+                "Latest oracle consensus price in micro-USD",
+            ),
+            &["symbol"],
+        )?;
+        registry.register(Box::new(oracle_price_micro_usd.clone()))?;
+
+        let oracle_confidence_bps = IntGaugeVec::new(
+            Opts::new(
+                "los_oracle_confidence_bps",
+                "Latest oracle source agreement in basis points",
+            ),
+            &["symbol"],
+        )?;
+        registry.register(Box::new(oracle_confidence_bps.clone()))?;
+
+        let oracle_deviation_alerts_total = IntCounterVec::new(
+            Opts::new(
+                "los_oracle_deviation_alerts_total",
+                "Oracle price deviation alerts fired",
+            ),
+            &["symbol"],
+        )?;
+        registry.register(Box::new(oracle_deviation_alerts_total.clone()))?;
+
         Ok(Arc::new(Self {
             registry,
             blocks_total,
@@ -350,33 +667,68 @@ impl LosMetrics {
             db_accounts_count,
             db_save_duration_seconds,
             db_load_duration_seconds,
+            db_fragmentation_pct,
+            disk_free_bytes,
+            storage_low_disk,
             consensus_rounds_total,
             consensus_failures_total,
             consensus_latency_seconds,
             active_validators,
             validator_votes_total,
+            state_root_mismatches_total,
+            state_quarantined,
+            checkpoint_conflicts_total,
+            finality_halted,
             mint_remaining_supply,
+            reward_pool_remaining_cil,
+            reward_fee_only,
             connected_peers,
+            min_peers,
             p2p_messages_received_total,
             p2p_messages_sent_total,
             p2p_bytes_received_total,
             p2p_bytes_sent_total,
+            gossip_dedup_checked_total,
+            gossip_duplicates_suppressed_total,
+            gossip_duplicate_rate_bps,
+            gossip_rejected_total,
+            gossip_propagation_seconds,
+            gossip_lane_depth,
+            gossip_lane_processed_total,
+            gossip_lane_dropped_total,
             api_requests_total,
             api_errors_total,
             api_request_duration_seconds,
             grpc_requests_total,
             grpc_errors_total,
+            mempool_size,
+            mempool_evictions_total,
+            mempool_replacements_total,
+            mempool_expired_total,
             rate_limit_rejections_total,
             rate_limit_active_ips,
+            api_key_requests_total,
+            api_key_rate_limited_total,
             slashing_events_total,
             slashing_total_amount,
             contracts_deployed_total,
             contract_executions_total,
             contract_gas_used_total,
+            contract_gas_used,
+            contract_calls,
+            contract_failures,
+            contract_avg_exec_micros,
+            contract_read_cache_hits_total,
+            contract_read_cache_misses_total,
+            contract_read_cache_entries,
+            contract_events_pruned_total,
             tor_onion_reachable,
             tor_consecutive_failures,
             tor_self_ping_total,
             tor_self_ping_failures_total,
+            oracle_price_micro_usd,
+            oracle_confidence_bps,
+            oracle_deviation_alerts_total,
         }))
     }
 
@@ -418,6 +770,9 @@ impl LosMetrics {
                 los_core::BlockType::Slash => {} // Slash blocks counted separately via slashing manager
                 los_core::BlockType::ContractDeploy => {} // Counted via contracts_deployed_total
                 los_core::BlockType::ContractCall => {} // Counted via contract_executions_total
+                los_core::BlockType::Migrate => send_count += 1, // Sweep to the new key
+                los_core::BlockType::Claim => mint_count += 1, // Accrued validator rewards
+                los_core::BlockType::Reclaim => receive_count += 1, // Expired Send refunded
             }
         }
 
@@ -441,6 +796,49 @@ impl LosMetrics {
         self.db_blocks_count.set(stats.blocks_count as i64);
         self.db_accounts_count.set(stats.accounts_count as i64);
     }
+
+    /// Update validator reward pool metrics
+    pub fn update_reward_metrics(&self, pool: &los_core::validator_rewards::ValidatorRewardPool) {
+        self.reward_pool_remaining_cil
+            .set(pool.remaining_cil as f64);
+        self.reward_fee_only
+            .set(pool.fee_only_since_epoch.is_some() as i64);
+    }
+
+    /// Update mempool metrics from mempool stats
+    pub fn update_mempool_metrics(&self, stats: &crate::mempool::MempoolStats) {
+        self.mempool_size.set(stats.size as i64);
+        self.mempool_evictions_total.reset();
+        self.mempool_evictions_total.inc_by(stats.total_evicted);
+        self.mempool_replacements_total.reset();
+        self.mempool_replacements_total.inc_by(stats.total_replaced);
+        self.mempool_expired_total.reset();
+        self.mempool_expired_total.inc_by(stats.total_expired);
+    }
+
+    /// Replace per-contract gauges with the given top consumers.
+    /// Reset first so contracts that dropped out of the top list disappear.
+    pub fn update_contract_metrics(&self, top: &[(String, los_vm::ContractStats)]) {
+        self.contract_gas_used.reset();
+        self.contract_calls.reset();
+        self.contract_failures.reset();
+        self.contract_avg_exec_micros.reset();
+        for (addr, s) in top {
+            let label = [addr.as_str()];
+            self.contract_gas_used
+                .with_label_values(&label)
+                .set(s.total_gas as i64);
+            self.contract_calls
+                .with_label_values(&label)
+                .set(s.calls as i64);
+            self.contract_failures
+                .with_label_values(&label)
+                .set(s.failures as i64);
+            self.contract_avg_exec_micros
+                .with_label_values(&label)
+                .set(s.avg_exec_micros() as i64);
+        }
+    }
 }
 
 #[cfg(test)]
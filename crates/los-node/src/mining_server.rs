@@ -0,0 +1,538 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - MINING JOB SERVER (Stratum-like)
+//
+// Lets external miners point dedicated hardware at a node without holding
+// the node's keys. Protocol: newline-delimited JSON-RPC over TCP.
+//
+//   → {"id":1,"method":"mining.subscribe","params":[]}
+//   ← {"id":1,"result":{"session_id":"..."},"error":null}
+//   → {"id":2,"method":"mining.authorize","params":["LOS...miner_address"]}
+//   ← {"id":2,"result":true,"error":null}
+//   ← {"id":null,"method":"mining.notify","params":[{job}]}
+//   → {"id":3,"method":"mining.submit","params":["LOS...","job_id","nonce"]}
+//   ← {"id":3,"result":{"block_hash":"...","reward_cil":"..."},"error":null}
+//
+// The job template is SHA3-256("LOS_MINE_V1" || chain_id || address || epoch || nonce)
+// — the same preimage as `los_core::pow_mint::compute_mining_hash`. Shares are
+// validated with `verify_mining_hash`; a valid share becomes a MINE: Mint block
+// credited to the miner's address and signed by this node.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::pow_mint::{verify_mining_hash, MiningProof, MiningState};
+use los_core::{Block, BlockType, Ledger};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use crate::db::LosDatabase;
+use crate::signer::SharedSigner;
+use crate::{get_short_addr, safe_lock, solve_pow, try_sign_hex, SAVE_DIRTY};
+
+/// Maximum length of a single JSON-RPC line (prevents memory exhaustion)
+const MAX_LINE_BYTES: usize = 4_096;
+/// Maximum concurrent miner connections per node
+const MAX_CONNECTIONS: usize = 256;
+/// How often each session checks for a new epoch (new job)
+const JOB_REFRESH_SECS: u64 = 5;
+/// Domain separator used by `compute_mining_hash` (published in every job)
+const MINING_DOMAIN: &str = "LOS_MINE_V1";
+
+/// Live connection counter (bounded by MAX_CONNECTIONS)
+static ACTIVE_CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// A mining job handed out to an authorized miner.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MiningJob {
+    /// Opaque job identifier: "{epoch}-{difficulty_bits}"
+    pub job_id: String,
+    /// Miner address bound into the hash preimage
+    pub address: String,
+    /// Mining epoch the job is valid for
+    pub epoch: u64,
+    /// Required leading zero bits
+    pub difficulty_bits: u32,
+    /// 256-bit target as hex: hash <= target ⇔ enough leading zero bits
+    pub target: String,
+    /// Chain ID mixed into the preimage (LE u64)
+    pub chain_id: u64,
+    /// Domain separator prefix of the preimage
+    pub domain: String,
+    /// Total epoch reward in CIL (split among all miners of the epoch)
+    pub epoch_reward_cil: String,
+    /// Seconds until the epoch (and this job) expires
+    pub expires_in_secs: u64,
+}
+
+impl MiningJob {
+    /// Build the current job for `address` from the node's mining state.
+    pub fn for_address(ms: &MiningState, address: &str, now_secs: u64) -> Self {
+        let epoch = ms.epoch_from_time(now_secs);
+        Self {
+            job_id: format!("{}-{}", epoch, ms.difficulty_bits),
+            address: address.to_string(),
+            epoch,
+            difficulty_bits: ms.difficulty_bits,
+            target: difficulty_target_hex(ms.difficulty_bits),
+            chain_id: los_core::chain_id(),
+            domain: MINING_DOMAIN.to_string(),
+            epoch_reward_cil: MiningState::epoch_reward_cil(epoch).to_string(),
+            expires_in_secs: ms.epoch_remaining_secs(now_secs),
+        }
+    }
+}
+
+/// Convert leading-zero-bit difficulty into a 256-bit big-endian target (hex).
+/// Example: 16 bits → "0000ffff…ff".
+pub fn difficulty_target_hex(difficulty_bits: u32) -> String {
+    let bits = difficulty_bits.min(256) as usize;
+    let mut out = String::with_capacity(64);
+    out.push_str(&"0".repeat(bits / 4));
+    if bits < 256 {
+        let partial = 0xFu8 >> (bits % 4);
+        out.push_str(&format!("{:x}", partial));
+        out.push_str(&"f".repeat(63 - bits / 4));
+    }
+    out
+}
+
+/// Incoming JSON-RPC request line.
+#[derive(Deserialize, Debug)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: serde_json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
+}
+
+/// Parse one request line. Rejects oversized or malformed input.
+pub fn parse_request(line: &str) -> Result<RpcRequest, String> {
+    if line.len() > MAX_LINE_BYTES {
+        return Err(format!("Request too large (max {} bytes)", MAX_LINE_BYTES));
+    }
+    serde_json::from_str(line).map_err(|e| format!("Malformed JSON-RPC request: {}", e))
+}
+
+/// Parse a nonce submitted as a decimal string, "0x"-prefixed hex, or JSON number.
+pub fn parse_nonce(value: &serde_json::Value) -> Option<u64> {
+    if let Some(n) = value.as_u64() {
+        return Some(n);
+    }
+    let s = value.as_str()?;
+    match s.strip_prefix("0x") {
+        Some(hex_part) => u64::from_str_radix(hex_part, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// Stateless share check against a job: job must match the current one and the
+/// nonce must satisfy its difficulty. Does NOT register the miner — see `submit_share`.
+pub fn validate_share(job: &MiningJob, job_id: &str, nonce: u64) -> Result<(), String> {
+    if job.job_id != job_id {
+        return Err(format!(
+            "Stale job: submitted {} but current job is {}",
+            job_id, job.job_id
+        ));
+    }
+    if !verify_mining_hash(&job.address, job.epoch, nonce, job.difficulty_bits) {
+        return Err(format!(
+            "Low difficulty share: hash does not meet {} leading zero bits",
+            job.difficulty_bits
+        ));
+    }
+    Ok(())
+}
+
+fn rpc_result(id: &serde_json::Value, result: serde_json::Value) -> String {
+    serde_json::json!({"id": id, "result": result, "error": null}).to_string()
+}
+
+fn rpc_error(id: &serde_json::Value, code: i32, message: &str) -> String {
+    serde_json::json!({"id": id, "result": null, "error": {"code": code, "message": message}})
+        .to_string()
+}
+
+fn rpc_notify(job: &MiningJob) -> String {
+    serde_json::json!({"id": null, "method": "mining.notify", "params": [job]}).to_string()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Dependencies for the mining job server (same shape as ApiServerConfig).
+#[derive(Clone)]
+pub struct MiningServerConfig {
+    pub port: u16,
+    pub ledger: Arc<Mutex<Ledger>>,
+    pub mining_state: Arc<Mutex<MiningState>>,
+    pub database: Arc<LosDatabase>,
+    pub tx_out: mpsc::Sender<String>,
+    pub node_public_key: Vec<u8>,
+    pub signer: SharedSigner,
+    /// Genesis bootstrap validators cannot receive mining rewards.
+    pub bootstrap_validators: Vec<String>,
+}
+
+/// Verify a share, register the miner for the epoch, then assemble, apply,
+/// persist and gossip the MINE: Mint block. Returns (block_hash, reward_cil).
+pub async fn submit_share(
+    cfg: &MiningServerConfig,
+    address: &str,
+    job_id: &str,
+    nonce: u64,
+) -> Result<(String, u128), String> {
+    if cfg.bootstrap_validators.iter().any(|v| v == address) {
+        return Err("Genesis bootstrap validators cannot mine".to_string());
+    }
+
+    let now = now_secs();
+    let job = MiningJob::for_address(&safe_lock(&cfg.mining_state), address, now);
+    validate_share(&job, job_id, nonce)?;
+
+    // verify_proof re-checks the hash + enforces 1 mint per address per epoch.
+    // Lock order: ledger → mining_state (the rejection path below unregisters
+    // the miner under the ledger lock), so read the supply before.
+    let remaining = safe_lock(&cfg.ledger).distribution.remaining_supply;
+    let reward_cil = {
+        let mut ms = safe_lock(&cfg.mining_state);
+        let proof = MiningProof {
+            address: address.to_string(),
+            epoch: job.epoch,
+            nonce,
+        };
+        ms.verify_proof(&proof, now, remaining)?
+    };
+
+    let head = safe_lock(&cfg.ledger)
+        .accounts
+        .get(address)
+        .map(|a| a.head.clone())
+        .unwrap_or_else(|| "0".to_string());
+
+    let mut mint_block = Block {
+        account: address.to_string(),
+        previous: head,
+        block_type: BlockType::Mint,
+        amount: reward_cil,
+        link: format!("MINE:{}:{}", job.epoch, nonce),
+        signature: String::new(),
+        public_key: hex::encode(&cfg.node_public_key),
+        work: 0,
+        timestamp: now,
+        fee: 0,
+        valid_after: 0,
+        expires_at: 0,
+    };
+
+    // Anti-spam PoW is CPU-bound — keep it off the async executor
+    mint_block = tokio::task::spawn_blocking(move || {
+        solve_pow(&mut mint_block);
+        mint_block
+    })
+    .await
+    .map_err(|e| format!("PoW task failed: {}", e))?;
+
+    let unregister = |reason: String| {
+        safe_lock(&cfg.mining_state)
+            .current_epoch_miners
+            .remove(address);
+        reason
+    };
+
+    mint_block.signature =
+        try_sign_hex(mint_block.signing_hash().as_bytes(), &*cfg.signer).map_err(unregister)?;
+
+    let hash = {
+        let mut l = safe_lock(&cfg.ledger);
+        l.process_block(&mint_block)
+            .map_err(|e| unregister(format!("Mint block rejected: {}", e)))?
+            .hash()
+            .to_string()
+    };
+    SAVE_DIRTY.store(true, Ordering::Release);
+
+    if let Err(e) = cfg.database.save_block(&hash, &mint_block) {
+        eprintln!("⚠️ DB save error for pool-mined block: {}", e);
+    }
+    if let Ok(json) = serde_json::to_string(&mint_block) {
+        let _ = cfg.tx_out.send(format!("MINE_BLOCK:{}", json)).await;
+    }
+
+    Ok((hash, reward_cil))
+}
+
+/// Start the mining job server. Binds 127.0.0.1 unless LOS_BIND_ALL=1
+/// (same policy as the REST API).
+pub async fn start_mining_server(cfg: MiningServerConfig) {
+    let bind_ip = if std::env::var("LOS_BIND_ALL").unwrap_or_default() == "1" {
+        "0.0.0.0"
+    } else {
+        "127.0.0.1"
+    };
+    let listener = match TcpListener::bind((bind_ip, cfg.port)).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!(
+                "❌ Mining server failed to bind {}:{}: {}",
+                bind_ip, cfg.port, e
+            );
+            return;
+        }
+    };
+    println!(
+        "⛏️  Mining job server listening on {}:{}",
+        bind_ip, cfg.port
+    );
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("⚠️ Mining server accept error: {}", e);
+                continue;
+            }
+        };
+        if ACTIVE_CONNECTIONS.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+            eprintln!("⚠️ Mining server full — dropping connection from {}", peer);
+            continue;
+        }
+        ACTIVE_CONNECTIONS.fetch_add(1, Ordering::Relaxed);
+        let session_cfg = cfg.clone();
+        tokio::spawn(async move {
+            handle_session(stream, session_cfg).await;
+            ACTIVE_CONNECTIONS.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+}
+
+async fn handle_session(stream: TcpStream, cfg: MiningServerConfig) {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+    let mut refresh = tokio::time::interval(Duration::from_secs(JOB_REFRESH_SECS));
+    let mut authorized: Option<String> = None;
+    let mut last_job_id = String::new();
+
+    loop {
+        let reply = tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(l)) => l,
+                    _ => return, // EOF or I/O error — close session
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let req = match parse_request(&line) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        let _ = write_half
+                            .write_all(format!("{}\n", rpc_error(&serde_json::Value::Null, -32700, &e)).as_bytes())
+                            .await;
+                        if line.len() > MAX_LINE_BYTES {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+                handle_request(&cfg, &req, &mut authorized, &mut last_job_id).await
+            },
+            _ = refresh.tick() => {
+                // Push a new job when the epoch or difficulty changes
+                match authorized.as_deref() {
+                    Some(addr) => {
+                        let job = MiningJob::for_address(&safe_lock(&cfg.mining_state), addr, now_secs());
+                        if job.job_id == last_job_id {
+                            continue;
+                        }
+                        last_job_id = job.job_id.clone();
+                        vec![rpc_notify(&job)]
+                    }
+                    None => continue,
+                }
+            }
+        };
+        for msg in reply {
+            if write_half
+                .write_all(format!("{}\n", msg).as_bytes())
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+async fn handle_request(
+    cfg: &MiningServerConfig,
+    req: &RpcRequest,
+    authorized: &mut Option<String>,
+    last_job_id: &mut String,
+) -> Vec<String> {
+    match req.method.as_str() {
+        "mining.subscribe" => {
+            let session_id = hex::encode(random_session_id());
+            vec![rpc_result(
+                &req.id,
+                serde_json::json!({"session_id": session_id, "protocol": "los-stratum/1"}),
+            )]
+        }
+        "mining.authorize" => {
+            let address = req
+                .params
+                .first()
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if !los_crypto::validate_address(&address) {
+                return vec![rpc_error(&req.id, 20, "Invalid LOS address")];
+            }
+            if cfg.bootstrap_validators.contains(&address) {
+                return vec![rpc_error(
+                    &req.id,
+                    24,
+                    "Genesis bootstrap validators cannot mine",
+                )];
+            }
+            println!("⛏️  Pool miner authorized: {}", get_short_addr(&address));
+            let job = MiningJob::for_address(&safe_lock(&cfg.mining_state), &address, now_secs());
+            *last_job_id = job.job_id.clone();
+            *authorized = Some(address);
+            vec![
+                rpc_result(&req.id, serde_json::json!(true)),
+                rpc_notify(&job),
+            ]
+        }
+        "mining.submit" => {
+            let Some(address) = authorized.clone() else {
+                return vec![rpc_error(
+                    &req.id,
+                    24,
+                    "Unauthorized: call mining.authorize first",
+                )];
+            };
+            // Params: [address, job_id, nonce] — address must match the session
+            if req.params.first().and_then(|v| v.as_str()) != Some(address.as_str()) {
+                return vec![rpc_error(
+                    &req.id,
+                    24,
+                    "Address does not match authorized session",
+                )];
+            }
+            let job_id = req
+                .params
+                .get(1)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let Some(nonce) = req.params.get(2).and_then(parse_nonce) else {
+                return vec![rpc_error(&req.id, 20, "Invalid nonce")];
+            };
+            match submit_share(cfg, &address, job_id, nonce).await {
+                Ok((hash, reward)) => {
+                    println!(
+                        "⛏️  Pool share accepted from {} → block {} ({} CIL)",
+                        get_short_addr(&address),
+                        &hash[..hash.len().min(16)],
+                        reward
+                    );
+                    vec![rpc_result(
+                        &req.id,
+                        serde_json::json!({"block_hash": hash, "reward_cil": reward.to_string()}),
+                    )]
+                }
+                Err(e) => vec![rpc_error(&req.id, 23, &e)],
+            }
+        }
+        "mining.get_job" => match authorized.as_deref() {
+            Some(addr) => {
+                let job = MiningJob::for_address(&safe_lock(&cfg.mining_state), addr, now_secs());
+                *last_job_id = job.job_id.clone();
+                vec![rpc_result(&req.id, serde_json::json!(job))]
+            }
+            None => vec![rpc_error(
+                &req.id,
+                24,
+                "Unauthorized: call mining.authorize first",
+            )],
+        },
+        other => vec![rpc_error(
+            &req.id,
+            -32601,
+            &format!("Unknown method: {}", other),
+        )],
+    }
+}
+
+/// Random 8-byte session identifier (not security-relevant, only for miner logs).
+fn random_session_id() -> [u8; 8] {
+    rand::random()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_target_hex() {
+        assert_eq!(difficulty_target_hex(0), "f".repeat(64));
+        assert_eq!(difficulty_target_hex(16), format!("0000{}", "f".repeat(60)));
+        assert_eq!(
+            difficulty_target_hex(18),
+            format!("00003{}", "f".repeat(59))
+        );
+        assert_eq!(difficulty_target_hex(256), "0".repeat(64));
+        assert_eq!(difficulty_target_hex(20).len(), 64);
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let req =
+            parse_request(r#"{"id":7,"method":"mining.submit","params":["a","b",3]}"#).unwrap();
+        assert_eq!(req.method, "mining.submit");
+        assert_eq!(req.params.len(), 3);
+        assert!(parse_request("not json").is_err());
+        let huge = format!(r#"{{"method":"{}"}}"#, "x".repeat(MAX_LINE_BYTES));
+        assert!(parse_request(&huge).is_err());
+    }
+
+    #[test]
+    fn test_parse_nonce_formats() {
+        assert_eq!(parse_nonce(&serde_json::json!(42)), Some(42));
+        assert_eq!(parse_nonce(&serde_json::json!("42")), Some(42));
+        assert_eq!(parse_nonce(&serde_json::json!("0x2a")), Some(42));
+        assert_eq!(parse_nonce(&serde_json::json!("zz")), None);
+    }
+
+    #[test]
+    fn test_job_matches_mining_state() {
+        let genesis = 1_000_000u64;
+        let ms = MiningState::new(genesis);
+        let job = MiningJob::for_address(&ms, "LOS_miner", genesis + 10);
+        assert_eq!(job.epoch, 0);
+        assert_eq!(job.difficulty_bits, ms.difficulty_bits);
+        assert_eq!(job.job_id, format!("0-{}", ms.difficulty_bits));
+        assert_eq!(job.chain_id, los_core::chain_id());
+    }
+
+    #[test]
+    fn test_validate_share() {
+        let mut ms = MiningState::new(0);
+        ms.difficulty_bits = 0; // Any nonce satisfies 0 bits
+        let job = MiningJob::for_address(&ms, "LOS_miner", 10);
+        assert!(validate_share(&job, &job.job_id, 123).is_ok());
+        assert!(validate_share(&job, "999-0", 123).is_err());
+
+        // Impossible difficulty must reject
+        let mut hard = job.clone();
+        hard.difficulty_bits = 256;
+        assert!(validate_share(&hard, &hard.job_id, 123).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - BLOCK PROPAGATION STATS (GET /netstats)
+//
+// Every gossip message carries its publisher's origin time (los-network
+// gossip_envelope). The network task reports receive − origin for each
+// enveloped message; this tracker keeps:
+// - a rolling window of all propagation delays
+// - per block (compact/raw block, CONFIRM_REQ, CONFIRM_RES): first and
+//   slowest arrival and the distinct publishers it was heard from
+// - time to quorum of blocks this node published (CONFIRM_REQ → finalized)
+//
+// Delays depend on both clocks. Samples above MAX_PLAUSIBLE_DELAY_MS are
+// counted as clock skew and left out of the percentiles.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// Delays above this are attributed to clock skew, not propagation
+pub const MAX_PLAUSIBLE_DELAY_MS: u64 = 60_000;
+/// Delay samples kept in the rolling window
+const MAX_DELAY_SAMPLES: usize = 4_096;
+/// Blocks tracked individually (oldest dropped first)
+const MAX_TRACKED_BLOCKS: usize = 1_024;
+/// Own blocks awaiting quorum (oldest dropped first)
+const MAX_PENDING_QUORUM: usize = 1_024;
+/// Time-to-quorum samples kept
+const MAX_QUORUM_SAMPLES: usize = 1_024;
+/// Blocks listed in `recent_blocks`
+const RECENT_BLOCKS: usize = 20;
+
+/// Percentile summary (milliseconds)
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Percentiles {
+    pub samples: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Percentiles {
+    pub fn of<I: IntoIterator<Item = u64>>(values: I) -> Self {
+        let mut sorted: Vec<u64> = values.into_iter().collect();
+        sorted.sort_unstable();
+        let pct = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+        match sorted.last() {
+            None => Self::default(),
+            Some(max) => Self {
+                samples: sorted.len() as u64,
+                p50_ms: pct(50),
+                p90_ms: pct(90),
+                p99_ms: pct(99),
+                max_ms: *max,
+            },
+        }
+    }
+}
+
+/// Propagation of one block as seen by this node
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockPropagation {
+    pub hash: String,
+    /// Fastest delay (usually the first arrival)
+    pub first_delay_ms: u64,
+    /// Slowest delay
+    pub max_delay_ms: u64,
+    /// Distinct publishers this block was heard from (proposer + voters)
+    pub peers: usize,
+    /// CONFIRM_REQ → local finalization, for blocks this node published
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_ms: Option<u64>,
+    #[serde(skip)]
+    publishers: BTreeSet<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct NetStats {
+    delays: VecDeque<u64>,
+    blocks: HashMap<String, BlockPropagation>,
+    block_order: VecDeque<String>,
+    published: HashMap<String, u64>,
+    published_order: VecDeque<String>,
+    quorum: VecDeque<u64>,
+    samples_total: u64,
+    skewed_total: u64,
+}
+
+fn push_bounded(q: &mut VecDeque<u64>, v: u64, cap: usize) {
+    if q.len() >= cap {
+        q.pop_front();
+    }
+    q.push_back(v);
+}
+
+impl NetStats {
+    /// Record one enveloped gossip arrival. Returns false for skewed samples.
+    pub fn record(&mut self, from_peer: &str, block_hash: Option<&str>, delay_ms: u64) -> bool {
+        self.samples_total += 1;
+        if delay_ms > MAX_PLAUSIBLE_DELAY_MS {
+            self.skewed_total += 1;
+            return false;
+        }
+        push_bounded(&mut self.delays, delay_ms, MAX_DELAY_SAMPLES);
+        if let Some(hash) = block_hash {
+            let entry = self.block_entry(hash);
+            if entry.publishers.is_empty() {
+                entry.first_delay_ms = delay_ms;
+            }
+            entry.first_delay_ms = entry.first_delay_ms.min(delay_ms);
+            entry.max_delay_ms = entry.max_delay_ms.max(delay_ms);
+            entry.publishers.insert(from_peer.to_string());
+            entry.peers = entry.publishers.len();
+        }
+        true
+    }
+
+    /// A block of ours was sent for confirmation at `now_ms` (first call wins)
+    pub fn note_published(&mut self, hash: &str, now_ms: u64) {
+        if self.published.contains_key(hash) {
+            return;
+        }
+        if self.published_order.len() >= MAX_PENDING_QUORUM {
+            if let Some(old) = self.published_order.pop_front() {
+                self.published.remove(&old);
+            }
+        }
+        self.published.insert(hash.to_string(), now_ms);
+        self.published_order.push_back(hash.to_string());
+    }
+
+    /// A block of ours reached quorum; returns the time it took
+    pub fn note_quorum(&mut self, hash: &str, now_ms: u64) -> Option<u64> {
+        let published = self.published.remove(hash)?;
+        self.published_order.retain(|h| h != hash);
+        let took = now_ms.saturating_sub(published);
+        push_bounded(&mut self.quorum, took, MAX_QUORUM_SAMPLES);
+        self.block_entry(hash).quorum_ms = Some(took);
+        Some(took)
+    }
+
+    fn block_entry(&mut self, hash: &str) -> &mut BlockPropagation {
+        if !self.blocks.contains_key(hash) {
+            if self.block_order.len() >= MAX_TRACKED_BLOCKS {
+                if let Some(old) = self.block_order.pop_front() {
+                    self.blocks.remove(&old);
+                }
+            }
+            self.block_order.push_back(hash.to_string());
+        }
+        self.blocks
+            .entry(hash.to_string())
+            .or_insert_with(|| BlockPropagation {
+                hash: hash.to_string(),
+                ..Default::default()
+            })
+    }
+
+    /// GET /netstats body
+    pub fn report(&self) -> serde_json::Value {
+        let heard: Vec<&BlockPropagation> = self
+            .block_order
+            .iter()
+            .filter_map(|h| self.blocks.get(h))
+            .filter(|b| b.peers > 0)
+            .collect();
+        let recent: Vec<&BlockPropagation> = self
+            .block_order
+            .iter()
+            .rev()
+            .filter_map(|h| self.blocks.get(h))
+            .take(RECENT_BLOCKS)
+            .collect();
+        serde_json::json!({
+            "gossip_delay": Percentiles::of(self.delays.iter().copied()),
+            "block_first_seen": Percentiles::of(heard.iter().map(|b| b.first_delay_ms)),
+            "block_last_seen": Percentiles::of(heard.iter().map(|b| b.max_delay_ms)),
+            "block_quorum": Percentiles::of(self.quorum.iter().copied()),
+            "blocks_tracked": self.blocks.len(),
+            "awaiting_quorum": self.published.len(),
+            "samples_total": self.samples_total,
+            "clock_skewed_samples": self.skewed_total,
+            "max_plausible_delay_ms": MAX_PLAUSIBLE_DELAY_MS,
+            "recent_blocks": recent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        assert_eq!(Percentiles::of(Vec::new()), Percentiles::default());
+        let p = Percentiles::of((1..=100).rev());
+        assert_eq!(
+            (p.samples, p.p50_ms, p.p90_ms, p.p99_ms, p.max_ms),
+            (100, 50, 90, 99, 100)
+        );
+    }
+
+    #[test]
+    fn test_block_propagation_and_quorum() {
+        let mut s = NetStats::default();
+        s.note_published("h1", 1_000);
+        assert!(s.record("peerA", Some("h1"), 120));
+        assert!(s.record("peerB", Some("h1"), 80));
+        assert!(s.record("peerB", Some("h1"), 300));
+        assert!(s.record("peerC", None, 40));
+        // Skewed clock: counted but excluded
+        assert!(!s.record("peerD", Some("h1"), MAX_PLAUSIBLE_DELAY_MS + 1));
+        assert_eq!(s.note_quorum("h1", 1_750), Some(750));
+        assert_eq!(s.note_quorum("h1", 2_000), None);
+        assert_eq!(s.note_quorum("unknown", 2_000), None);
+
+        let r = s.report();
+        assert_eq!(r["gossip_delay"]["samples"], 4);
+        assert_eq!(r["clock_skewed_samples"], 1);
+        assert_eq!(r["block_quorum"]["p50_ms"], 750);
+        let b = &r["recent_blocks"][0];
+        assert_eq!(b["hash"], "h1");
+        assert_eq!(b["first_delay_ms"], 80);
+        assert_eq!(b["max_delay_ms"], 300);
+        assert_eq!(b["peers"], 2);
+        assert_eq!(b["quorum_ms"], 750);
+        assert_eq!(r["awaiting_quorum"], 0);
+    }
+
+    #[test]
+    fn test_tracking_is_bounded() {
+        let mut s = NetStats::default();
+        for i in 0..(MAX_TRACKED_BLOCKS + 10) {
+            s.record("p", Some(&format!("b{}", i)), 5);
+            s.note_published(&format!("b{}", i), 0);
+        }
+        assert_eq!(s.blocks.len(), MAX_TRACKED_BLOCKS);
+        assert_eq!(s.published.len(), MAX_PENDING_QUORUM);
+        assert!(!s.blocks.contains_key("b0"));
+        assert_eq!(
+            s.report()["recent_blocks"].as_array().unwrap().len(),
+            RECENT_BLOCKS
+        );
+    }
+}
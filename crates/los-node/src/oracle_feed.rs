@@ -0,0 +1,214 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - ORACLE PRICE FEED
+//
+// Opt-in background task (LOS_ORACLE_INTERVAL_SECS > 0) that polls the
+// exchange oracle, persists every consensus reading to sled (served by
+// GET /oracle/:symbol/history) and fires deviation alerts when the price
+// moves more than LOS_ORACLE_ALERT_BPS within LOS_ORACLE_ALERT_WINDOW_SECS.
+//
+// Alerts are published three ways:
+//   - los_oracle_deviation_alerts_total{symbol} on /metrics
+//   - a 🚨 line in the node log
+//   - an HTTP POST of the alert JSON to LOS_ORACLE_ALERT_WEBHOOK (optional,
+//     .onion URLs go through the Tor SOCKS5 proxy)
+//
+// Observations older than ORACLE_RETENTION_SECS are pruned every tick.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_vm::oracle_connector::{
+    check_deviation, DeviationAlert, DeviationRule, ExchangeOracle, OracleObservation, PriceOracle,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::db::LosDatabase;
+use crate::metrics::LosMetrics;
+
+/// Symbol recorded by the exchange oracle (LOS/USD median)
+pub const ORACLE_SYMBOL: &str = "LOS";
+/// How long observations are kept in sled
+pub const ORACLE_RETENTION_SECS: u64 = 30 * 86_400;
+/// Default alert threshold: 5%
+const DEFAULT_ALERT_BPS: u64 = 500;
+/// Default alert window: 10 minutes
+const DEFAULT_ALERT_WINDOW_SECS: u64 = 600;
+
+/// Oracle feed settings (from environment)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OracleFeedConfig {
+    pub interval_secs: u64,
+    pub rule: DeviationRule,
+    pub webhook_url: Option<String>,
+}
+
+impl OracleFeedConfig {
+    /// Read LOS_ORACLE_* variables. None if the feed is disabled.
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|k| std::env::var(k).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let num = |k: &str| get(k).and_then(|v| v.trim().parse::<u64>().ok());
+        let interval_secs = num("LOS_ORACLE_INTERVAL_SECS").filter(|s| *s > 0)?;
+        Some(Self {
+            interval_secs,
+            rule: DeviationRule {
+                threshold_bps: num("LOS_ORACLE_ALERT_BPS").unwrap_or(DEFAULT_ALERT_BPS),
+                window_secs: num("LOS_ORACLE_ALERT_WINDOW_SECS")
+                    .unwrap_or(DEFAULT_ALERT_WINDOW_SECS),
+            },
+            webhook_url: get("LOS_ORACLE_ALERT_WEBHOOK").filter(|u| !u.trim().is_empty()),
+        })
+    }
+}
+
+/// Poll → persist → alert, forever.
+pub async fn run_oracle_feed(
+    cfg: OracleFeedConfig,
+    database: Arc<LosDatabase>,
+    metrics: Arc<LosMetrics>,
+) {
+    println!(
+        "📈 Oracle feed started: every {}s, alert on >{} bps within {}s{}",
+        cfg.interval_secs,
+        cfg.rule.threshold_bps,
+        cfg.rule.window_secs,
+        if cfg.webhook_url.is_some() {
+            " (webhook enabled)"
+        } else {
+            ""
+        }
+    );
+    let mut oracle = ExchangeOracle::new();
+    let mut last_alert_ts: Option<u64> = None;
+    let mut interval = tokio::time::interval(Duration::from_secs(cfg.interval_secs));
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = oracle.fetch_exchange_prices().await {
+            eprintln!("⚠️ Oracle fetch failed: {}", e);
+            continue;
+        }
+        let consensus = match oracle.get_oracle_consensus() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("⚠️ Oracle consensus unavailable: {}", e);
+                continue;
+            }
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let obs = OracleObservation::from_consensus(ORACLE_SYMBOL, &consensus, now);
+
+        let window = database
+            .get_oracle_history(
+                ORACLE_SYMBOL,
+                now.saturating_sub(cfg.rule.window_secs),
+                usize::MAX,
+            )
+            .unwrap_or_default();
+        if let Err(e) = database.save_oracle_observation(&obs) {
+            eprintln!("⚠️ Failed to persist oracle observation: {}", e);
+        }
+        let _ =
+            database.prune_oracle_history(ORACLE_SYMBOL, now.saturating_sub(ORACLE_RETENTION_SECS));
+
+        metrics
+            .oracle_price_micro_usd
+            .with_label_values(&[ORACLE_SYMBOL])
+            .set(obs.price_micro_usd as i64);
+        metrics
+            .oracle_confidence_bps
+            .with_label_values(&[ORACLE_SYMBOL])
+            .set(obs.confidence_bps as i64);
+
+        // One alert per window: a sustained move should not page every tick
+        let cooling_down =
+            last_alert_ts.is_some_and(|t| now < t.saturating_add(cfg.rule.window_secs));
+        if cooling_down {
+            continue;
+        }
+        if let Some(alert) = check_deviation(&window, &obs, &cfg.rule) {
+            last_alert_ts = Some(now);
+            println!(
+                "🚨 Oracle deviation: {} moved {:+} bps in {}s ({} → {} micro-USD)",
+                alert.symbol,
+                alert.change_bps,
+                alert.timestamp - alert.reference_timestamp,
+                alert.reference_price_micro_usd,
+                alert.price_micro_usd
+            );
+            metrics
+                .oracle_deviation_alerts_total
+                .with_label_values(&[ORACLE_SYMBOL])
+                .inc();
+            if let Some(url) = cfg.webhook_url.clone() {
+                tokio::spawn(async move {
+                    if let Err(e) = post_alert(&url, &alert).await {
+                        eprintln!("⚠️ Oracle alert webhook failed: {}", e);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// POST the alert as JSON: {"event":"oracle_deviation", ...alert fields}
+async fn post_alert(url: &str, alert: &DeviationAlert) -> Result<(), String> {
+    let mut body = serde_json::to_value(alert).map_err(|e| e.to_string())?;
+    body["event"] = serde_json::json!("oracle_deviation");
+    let resp = crate::peer_http_client(url)?
+        .post(url)
+        .json(&body)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("request error: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn cfg(vars: &[(&str, &str)]) -> Option<OracleFeedConfig> {
+        let map: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        OracleFeedConfig::from_vars(|k| map.get(k).cloned())
+    }
+
+    #[test]
+    fn test_config_disabled_without_interval() {
+        assert!(cfg(&[]).is_none());
+        assert!(cfg(&[("LOS_ORACLE_INTERVAL_SECS", "0")]).is_none());
+        assert!(cfg(&[("LOS_ORACLE_INTERVAL_SECS", "soon")]).is_none());
+    }
+
+    #[test]
+    fn test_config_defaults_and_overrides() {
+        let c = cfg(&[("LOS_ORACLE_INTERVAL_SECS", "30")]).unwrap();
+        assert_eq!(c.interval_secs, 30);
+        assert_eq!(c.rule.threshold_bps, DEFAULT_ALERT_BPS);
+        assert_eq!(c.rule.window_secs, DEFAULT_ALERT_WINDOW_SECS);
+        assert!(c.webhook_url.is_none());
+
+        let c = cfg(&[
+            ("LOS_ORACLE_INTERVAL_SECS", "60"),
+            ("LOS_ORACLE_ALERT_BPS", "250"),
+            ("LOS_ORACLE_ALERT_WINDOW_SECS", "3600"),
+            ("LOS_ORACLE_ALERT_WEBHOOK", "https://ops.example/hook"),
+        ])
+        .unwrap();
+        assert_eq!(c.rule.threshold_bps, 250);
+        assert_eq!(c.rule.window_secs, 3_600);
+        assert_eq!(c.webhook_url.as_deref(), Some("https://ops.example/hook"));
+    }
+}
@@ -0,0 +1,332 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - PEER EXCHANGE (PEX)
+//
+// Bootstrap nodes are only the first contact. Every PEX round a node gossips
+//
+//   PEX:{"sender","public_key","timestamp","entries":[{address,host,last_seen}],"signature"}
+//
+// holding the MAX_PEX_ENTRIES most recently seen validator endpoints it knows
+// (seen = heartbeat, own announcement, or a newer PEX entry), signed with its
+// Dilithium5 key. Receivers
+//   - drop the message if the same sender was accepted < PEX_MIN_INTERVAL_SECS
+//     ago (checked before the signature to keep verification cheap),
+//   - verify pk → sender, the signature and the timestamp (±5 minutes),
+//   - require the sender AND every entry to be in the ledger's validator set,
+//   - ignore entries not seen for PEX_MAX_ENTRY_AGE_SECS.
+//
+// Accepted entries feed validator_endpoints and the dial list handed to the
+// P2P layer (PEX_PEERS:), which re-dials it alongside the bootstrap list
+// whenever the mesh is thin — so the network heals when bootstrap nodes go
+// down. The P2P address is derived from the REST host with the default
+// LOS_P2P_PORT convention (REST port + 1000); .onion and IPv4 hosts only.
+//
+// Legacy unsigned PEER_LIST messages from older nodes are still merged into
+// validator_endpoints but never reach the dial list.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::signer::Signer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Gossip prefix of signed PEX messages
+pub const PEX_PREFIX: &str = "PEX:";
+/// Out-channel command that replaces the P2P layer's PEX dial list
+pub const PEX_PEERS_PREFIX: &str = "PEX_PEERS:";
+/// Endpoints per message
+pub const MAX_PEX_ENTRIES: usize = 32;
+/// Minimum seconds between two accepted messages of one sender
+pub const PEX_MIN_INTERVAL_SECS: u64 = 30;
+/// Allowed clock skew of a message timestamp
+pub const PEX_MAX_CLOCK_SKEW_SECS: u64 = 300;
+/// Endpoints not seen for this long are neither shared nor dialed
+pub const PEX_MAX_ENTRY_AGE_SECS: u64 = 3_600;
+/// Endpoints handed to the P2P layer for re-dialing
+pub const MAX_PEX_DIAL: usize = 8;
+/// LOS_P2P_PORT default offset from the REST port
+pub const P2P_PORT_OFFSET: u16 = 1000;
+
+/// One shared validator endpoint
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PexEntry {
+    pub address: String,
+    pub host: String,
+    pub last_seen: u64,
+}
+
+/// Signed endpoint subset
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PexMessage {
+    pub sender: String,
+    /// Hex Dilithium5 public key of `sender`
+    pub public_key: String,
+    pub timestamp: u64,
+    pub entries: Vec<PexEntry>,
+    /// Hex signature over `signing_data()`
+    pub signature: String,
+}
+
+impl PexMessage {
+    /// Sign `entries` as `sender`
+    pub fn signed(
+        sender: &str,
+        signer: &dyn Signer,
+        timestamp: u64,
+        entries: Vec<PexEntry>,
+    ) -> Result<Self, String> {
+        let mut msg = Self {
+            sender: sender.to_string(),
+            public_key: hex::encode(signer.public_key()),
+            timestamp,
+            entries,
+            signature: String::new(),
+        };
+        let sig = signer
+            .sign(msg.signing_data().as_bytes())
+            .map_err(|e| format!("PEX signing failed: {}", e))?;
+        msg.signature = hex::encode(sig);
+        Ok(msg)
+    }
+
+    /// `PEX:{sender}:{timestamp}:{blake3(entries JSON)}`
+    pub fn signing_data(&self) -> String {
+        let entries = serde_json::to_vec(&self.entries).unwrap_or_default();
+        format!(
+            "PEX:{}:{}:{}",
+            self.sender,
+            self.timestamp,
+            blake3::hash(&entries).to_hex()
+        )
+    }
+
+    /// Check size, timestamp, key binding and signature
+    pub fn verify(&self, now: u64) -> Result<(), String> {
+        if self.entries.len() > MAX_PEX_ENTRIES {
+            return Err(format!(
+                "{} entries > max {}",
+                self.entries.len(),
+                MAX_PEX_ENTRIES
+            ));
+        }
+        if now.abs_diff(self.timestamp) > PEX_MAX_CLOCK_SKEW_SECS {
+            return Err("stale timestamp".to_string());
+        }
+        let pk = hex::decode(&self.public_key).map_err(|_| "invalid pk hex".to_string())?;
+        if los_crypto::public_key_to_address(&pk) != self.sender {
+            return Err("pk does not match sender".to_string());
+        }
+        let sig = hex::decode(&self.signature).map_err(|_| "invalid sig hex".to_string())?;
+        if !los_crypto::verify_signature(self.signing_data().as_bytes(), &sig, &pk) {
+            return Err("signature verification failed".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Last-seen times of validator endpoints and per-sender rate limits
+#[derive(Debug, Default)]
+pub struct PexBook {
+    /// address → (host, last_seen)
+    seen: HashMap<String, (String, u64)>,
+    /// sender → timestamp of its last accepted message
+    last_accepted: HashMap<String, u64>,
+}
+
+impl PexBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `address` was seen at `host`; older sightings are ignored
+    pub fn observe(&mut self, address: &str, host: &str, last_seen: u64) {
+        match self.seen.get_mut(address) {
+            Some((_, seen)) if *seen >= last_seen => {}
+            Some(entry) => *entry = (host.to_string(), last_seen),
+            None => {
+                self.seen
+                    .insert(address.to_string(), (host.to_string(), last_seen));
+            }
+        }
+    }
+
+    /// Recently seen endpoints, newest first (at most `limit`)
+    fn recent(&self, now: u64, limit: usize) -> Vec<PexEntry> {
+        let mut entries: Vec<PexEntry> = self
+            .seen
+            .iter()
+            .filter(|(_, (_, t))| now.saturating_sub(*t) <= PEX_MAX_ENTRY_AGE_SECS)
+            .map(|(address, (host, last_seen))| PexEntry {
+                address: address.clone(),
+                host: host.clone(),
+                last_seen: *last_seen,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.last_seen
+                .cmp(&a.last_seen)
+                .then_with(|| a.address.cmp(&b.address))
+        });
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Subset to share in the next message
+    pub fn subset(&self, now: u64) -> Vec<PexEntry> {
+        self.recent(now, MAX_PEX_ENTRIES)
+    }
+
+    /// Validate a received message; returns the entries to merge.
+    /// `is_validator` answers from the ledger's validator set.
+    pub fn accept(
+        &mut self,
+        msg: &PexMessage,
+        now: u64,
+        is_validator: impl Fn(&str) -> bool,
+    ) -> Result<Vec<PexEntry>, String> {
+        if let Some(last) = self.last_accepted.get(&msg.sender) {
+            if now.saturating_sub(*last) < PEX_MIN_INTERVAL_SECS {
+                return Err("rate limited".to_string());
+            }
+        }
+        if !is_validator(&msg.sender) {
+            return Err("sender is not a registered validator".to_string());
+        }
+        msg.verify(now)?;
+        self.last_accepted.insert(msg.sender.clone(), now);
+
+        let mut accepted = Vec::new();
+        for entry in &msg.entries {
+            // Entries from the future are clamped to the message time
+            let last_seen = entry.last_seen.min(msg.timestamp);
+            if entry.host.is_empty()
+                || now.saturating_sub(last_seen) > PEX_MAX_ENTRY_AGE_SECS
+                || !los_crypto::validate_address(&entry.address)
+                || !is_validator(&entry.address)
+            {
+                continue;
+            }
+            self.observe(&entry.address, &entry.host, last_seen);
+            accepted.push(PexEntry {
+                last_seen,
+                ..entry.clone()
+            });
+        }
+        Ok(accepted)
+    }
+
+    /// P2P dial targets of the most recently seen endpoints, except `own`
+    pub fn dial_targets(&self, now: u64, own: &str) -> Vec<String> {
+        self.recent(now, MAX_PEX_DIAL + 1)
+            .into_iter()
+            .filter(|e| e.address != own)
+            .filter_map(|e| p2p_dial_target(&e.host))
+            .take(MAX_PEX_DIAL)
+            .collect()
+    }
+}
+
+/// P2P address of a REST host (`host:port` → P2P port = port + 1000).
+/// Onion hosts stay `host:port`, IPv4 hosts become multiaddrs; domain
+/// names cannot be dialed by the swarm and are skipped.
+pub fn p2p_dial_target(host: &str) -> Option<String> {
+    let host = host
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .trim_end_matches('/');
+    let (name, port) = host.rsplit_once(':')?;
+    let port = port.parse::<u16>().ok()?.checked_add(P2P_PORT_OFFSET)?;
+    if name.is_empty() {
+        return None;
+    }
+    if name.ends_with(".onion") {
+        Some(format!("{}:{}", name, port))
+    } else if name.parse::<std::net::Ipv4Addr>().is_ok() {
+        Some(format!("/ip4/{}/tcp/{}", name, port))
+    } else {
+        None // the swarm has no DNS transport
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+
+    const NOW: u64 = 1_771_000_000;
+
+    fn entry(address: &str, host: &str, last_seen: u64) -> PexEntry {
+        PexEntry {
+            address: address.to_string(),
+            host: host.to_string(),
+            last_seen,
+        }
+    }
+
+    #[test]
+    fn test_pex_sign_verify_rate_limit_and_validator_filter() {
+        let kp = los_crypto::generate_keypair();
+        let sender = los_crypto::public_key_to_address(&kp.public_key);
+        let other = los_crypto::public_key_to_address(&los_crypto::generate_keypair().public_key);
+        let stranger =
+            los_crypto::public_key_to_address(&los_crypto::generate_keypair().public_key);
+        let validators = [sender.clone(), other.clone()];
+        let is_validator = |a: &str| validators.iter().any(|v| v == a);
+
+        let entries = vec![
+            entry(&other, "1.2.3.4:3030", NOW - 10),
+            entry(&stranger, "5.6.7.8:3030", NOW - 10), // not a validator
+            entry(&sender, "abc.onion:3031", NOW + 999), // clamped to msg time
+        ];
+        let msg = PexMessage::signed(&sender, &LocalSigner::new(kp.clone()), NOW, entries).unwrap();
+        assert!(msg.verify(NOW).is_ok());
+        assert!(msg.verify(NOW + PEX_MAX_CLOCK_SKEW_SECS + 1).is_err());
+
+        // Tampering breaks the signature
+        let mut forged = msg.clone();
+        forged.entries[0].host = "6.6.6.6:3030".to_string();
+        assert!(forged
+            .verify(NOW)
+            .unwrap_err()
+            .contains("signature verification failed"));
+
+        let mut book = PexBook::new();
+        assert!(book
+            .accept(&msg, NOW, |a| a == other)
+            .unwrap_err()
+            .contains("not a registered validator"));
+        let accepted = book.accept(&msg, NOW, is_validator).unwrap();
+        assert_eq!(
+            accepted,
+            vec![
+                entry(&other, "1.2.3.4:3030", NOW - 10),
+                entry(&sender, "abc.onion:3031", NOW),
+            ]
+        );
+        assert!(book.subset(NOW).iter().all(|e| e.address != stranger));
+        assert_eq!(
+            book.accept(&msg, NOW + 1, is_validator).unwrap_err(),
+            "rate limited"
+        );
+
+        // Newest first; own endpoint is never dialed
+        assert_eq!(book.subset(NOW)[0].address, sender);
+        assert_eq!(
+            book.dial_targets(NOW, &sender),
+            vec!["/ip4/1.2.3.4/tcp/4030"]
+        );
+        assert!(book
+            .dial_targets(NOW + PEX_MAX_ENTRY_AGE_SECS + 11, &sender)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_p2p_dial_target() {
+        assert_eq!(p2p_dial_target("abc.onion:3030").unwrap(), "abc.onion:4030");
+        assert_eq!(
+            p2p_dial_target("http://10.0.0.7:3031/").unwrap(),
+            "/ip4/10.0.0.7/tcp/4031"
+        );
+        assert_eq!(p2p_dial_target("node.example.org:3030"), None);
+        assert_eq!(p2p_dial_target("abc.onion"), None);
+        assert_eq!(p2p_dial_target("1.2.3.4:65000"), None);
+    }
+}
@@ -0,0 +1,405 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CHECKPOINT STATE ROOT + DIVERGENCE QUARANTINE
+//
+// Every FinalityCheckpoint commits to the full state root:
+//   full_state_root(accounts root, VM state root, reward pool root)
+// A validator asked to co-sign compares the checkpoint with its own state:
+//
+//   - same root              → agree, sign
+//   - different tip block    → not comparable (we are behind or ahead), decline
+//   - same tip, other root   → divergence: same blocks, different state
+//
+// Divergence is never signed. Once LOS_QUARANTINE_AFTER distinct proposers
+// reported a diverging root since our last agreement, or a single
+// quorum-finalized checkpoint diverges, the node quarantines itself: it
+// stops voting, co-signing and proposing checkpoints, raises a 🚨 alert
+// (log, metric, /health unhealthy) and requests a state sync every LOS_QUARANTINE_RESYNC_SECS. The first
+// checkpoint whose root matches ours again lifts the quarantine. Counting
+// distinct proposers keeps one misbehaving validator from quarantining the
+// rest with bogus roots; only staked validators count as proposers, so
+// Sybil accounts cannot either.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::checkpoint::{full_state_root, FinalityCheckpoint};
+use los_core::validator_rewards::ValidatorRewardPool;
+use los_core::Ledger;
+use los_vm::WasmEngine;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Distinct proposers with diverging roots before quarantine
+pub const DEFAULT_QUARANTINE_AFTER: u32 = 2;
+/// Seconds between resync requests while quarantined
+pub const DEFAULT_RESYNC_SECS: u64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantineConfig {
+    pub threshold: u32,
+    pub resync_secs: u64,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_QUARANTINE_AFTER,
+            resync_secs: DEFAULT_RESYNC_SECS,
+        }
+    }
+}
+
+impl QuarantineConfig {
+    pub fn from_env() -> Self {
+        Self::from_vars(|k| std::env::var(k).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Self {
+        let num = |k: &str| get(k).and_then(|v| v.trim().parse::<u64>().ok());
+        Self {
+            threshold: num("LOS_QUARANTINE_AFTER")
+                .filter(|n| *n > 0)
+                .map_or(DEFAULT_QUARANTINE_AFTER, |n| n.min(u32::MAX as u64) as u32),
+            resync_secs: num("LOS_QUARANTINE_RESYNC_SECS")
+                .filter(|s| *s > 0)
+                .unwrap_or(DEFAULT_RESYNC_SECS),
+        }
+    }
+}
+
+/// Hash of the most recent block (by timestamp) — the tip a checkpoint names
+pub fn latest_block_hash(ledger: &Ledger) -> String {
+    ledger
+        .blocks
+        .values()
+        .max_by_key(|b| b.timestamp)
+        .map(|b| b.calculate_hash())
+        .unwrap_or_else(|| "genesis".to_string())
+}
+
+/// Root a checkpoint commits to: accounts + VM + reward pool
+pub fn full_root(
+    ledger: &Ledger,
+    vm: &WasmEngine,
+    rewards: &ValidatorRewardPool,
+) -> Result<String, String> {
+    Ok(full_state_root(
+        &ledger.compute_state_root(),
+        &vm.state_root()?,
+        &rewards.state_root(),
+    ))
+}
+
+/// Our side of a checkpoint comparison
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalState {
+    pub tip: String,
+    pub root: String,
+}
+
+/// Outcome of comparing a checkpoint with local state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Roots match — safe to co-sign
+    Agree,
+    /// Different tip block: we are behind or ahead, nothing to conclude
+    NotComparable,
+    /// Same tip, different root. `entered` = this pushed us into quarantine
+    Diverged { entered: bool },
+}
+
+/// A recorded divergence
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RootMismatch {
+    pub height: u64,
+    pub block_hash: String,
+    pub ours: String,
+    pub theirs: String,
+    /// Proposer that asked us to co-sign (None = quorum-finalized checkpoint)
+    pub proposer: Option<String>,
+    pub at: u64,
+}
+
+/// Quarantine state reported by /health
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct QuarantineStatus {
+    pub quarantined: bool,
+    pub since: Option<u64>,
+    /// Distinct proposers with a diverging root since our last agreement
+    pub diverging_proposers: usize,
+    pub total_mismatches: u64,
+    pub resync_attempts: u64,
+    pub last_mismatch: Option<RootMismatch>,
+}
+
+pub struct StateQuarantine {
+    cfg: QuarantineConfig,
+    diverging: BTreeSet<String>,
+    total: u64,
+    since: Option<u64>,
+    last_mismatch: Option<RootMismatch>,
+    resync_attempts: u64,
+    last_resync: Option<u64>,
+}
+
+impl StateQuarantine {
+    pub fn new(cfg: QuarantineConfig) -> Self {
+        Self {
+            cfg,
+            diverging: BTreeSet::new(),
+            total: 0,
+            since: None,
+            last_mismatch: None,
+            resync_attempts: 0,
+            last_resync: None,
+        }
+    }
+
+    pub fn config(&self) -> &QuarantineConfig {
+        &self.cfg
+    }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.since.is_some()
+    }
+
+    /// Compare `cp` with local state and record any divergence.
+    /// `proposer` is the verified proposer of a checkpoint we were asked to
+    /// co-sign, or None for a quorum-finalized checkpoint. An agreeing root
+    /// lifts an active quarantine.
+    pub fn check(
+        &mut self,
+        cp: &FinalityCheckpoint,
+        local: &LocalState,
+        proposer: Option<&str>,
+        now: u64,
+    ) -> Verdict {
+        if cp.state_root == local.root {
+            self.diverging.clear();
+            if self.since.take().is_some() {
+                println!(
+                    "✅ State root matches checkpoint at height {} again — quarantine lifted",
+                    cp.height
+                );
+            }
+            return Verdict::Agree;
+        }
+        if cp.block_hash != local.tip {
+            return Verdict::NotComparable;
+        }
+
+        if let Some(p) = proposer {
+            self.diverging.insert(p.to_string());
+        }
+        self.total += 1;
+        self.last_mismatch = Some(RootMismatch {
+            height: cp.height,
+            block_hash: cp.block_hash.clone(),
+            ours: local.root.clone(),
+            theirs: cp.state_root.clone(),
+            proposer: proposer.map(str::to_string),
+            at: now,
+        });
+        let entered = self.since.is_none()
+            && (proposer.is_none() || self.diverging.len() >= self.cfg.threshold as usize);
+        if entered {
+            self.since = Some(now);
+            // Resync right away, then every resync_secs
+            self.last_resync = None;
+        }
+        Verdict::Diverged { entered }
+    }
+
+    /// `check` for a proposal from `proposer`. Only staked validators count
+    /// towards quarantine: any other proposer's root is NotComparable.
+    pub fn check_proposal(
+        &mut self,
+        cp: &FinalityCheckpoint,
+        local: &LocalState,
+        proposer: &str,
+        validators: &BTreeSet<String>,
+        now: u64,
+    ) -> Verdict {
+        if !validators.contains(proposer) {
+            return Verdict::NotComparable;
+        }
+        self.check(cp, local, Some(proposer), now)
+    }
+
+    /// Whether a resync request is due; records the attempt when it is
+    pub fn take_resync(&mut self, now: u64) -> bool {
+        let due = self
+            .last_resync
+            .is_none_or(|last| now >= last.saturating_add(self.cfg.resync_secs));
+        if !self.is_quarantined() || !due {
+            return false;
+        }
+        self.last_resync = Some(now);
+        self.resync_attempts += 1;
+        true
+    }
+
+    pub fn status(&self) -> QuarantineStatus {
+        QuarantineStatus {
+            quarantined: self.is_quarantined(),
+            since: self.since,
+            diverging_proposers: self.diverging.len(),
+            total_mismatches: self.total,
+            resync_attempts: self.resync_attempts,
+            last_mismatch: self.last_mismatch.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn cp(height: u64, tip: &str, root: &str) -> FinalityCheckpoint {
+        FinalityCheckpoint::new(height, tip.to_string(), 4, root.to_string(), vec![])
+    }
+
+    fn local(tip: &str, root: &str) -> LocalState {
+        LocalState {
+            tip: tip.to_string(),
+            root: root.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        let vars: HashMap<&str, &str> = [
+            ("LOS_QUARANTINE_AFTER", "3"),
+            ("LOS_QUARANTINE_RESYNC_SECS", "0"),
+        ]
+        .into_iter()
+        .collect();
+        let cfg = QuarantineConfig::from_vars(|k| vars.get(k).map(|v| v.to_string()));
+        assert_eq!(cfg.threshold, 3);
+        assert_eq!(cfg.resync_secs, DEFAULT_RESYNC_SECS);
+        assert_eq!(
+            QuarantineConfig::from_vars(|_| None),
+            QuarantineConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_divergence_quarantines_after_threshold() {
+        let mut q = StateQuarantine::new(QuarantineConfig::default());
+        let ours = local("tip", "root-a");
+
+        // Different tip: we are lagging, not diverged
+        assert_eq!(
+            q.check(&cp(1000, "other-tip", "root-b"), &ours, Some("LOSp1"), 10),
+            Verdict::NotComparable
+        );
+        assert_eq!(
+            q.check(&cp(1000, "tip", "root-b"), &ours, Some("LOSp1"), 10),
+            Verdict::Diverged { entered: false }
+        );
+        // The same proposer repeating itself is not more evidence
+        assert_eq!(
+            q.check(&cp(1000, "tip", "root-b"), &ours, Some("LOSp1"), 15),
+            Verdict::Diverged { entered: false }
+        );
+        assert!(!q.is_quarantined());
+        assert_eq!(
+            q.check(&cp(1000, "tip", "root-b"), &ours, Some("LOSp2"), 20),
+            Verdict::Diverged { entered: true }
+        );
+        assert!(q.is_quarantined());
+        let status = q.status();
+        assert_eq!(status.since, Some(20));
+        assert_eq!(status.total_mismatches, 3);
+        assert_eq!(status.diverging_proposers, 2);
+        assert_eq!(status.last_mismatch.unwrap().theirs, "root-b");
+
+        // Resync immediately, then only every resync_secs
+        assert!(q.take_resync(20));
+        assert!(!q.take_resync(25));
+        assert!(q.take_resync(20 + DEFAULT_RESYNC_SECS));
+        assert_eq!(q.status().resync_attempts, 2);
+
+        // Matching root lifts the quarantine
+        assert_eq!(
+            q.check(
+                &cp(2000, "tip2", "root-c"),
+                &local("tip2", "root-c"),
+                None,
+                90
+            ),
+            Verdict::Agree
+        );
+        assert!(!q.is_quarantined());
+        assert!(!q.take_resync(1_000));
+    }
+
+    #[test]
+    fn test_unstaked_proposers_never_quarantine() {
+        let mut q = StateQuarantine::new(QuarantineConfig::default());
+        let ours = local("tip", "root-a");
+        let validators: BTreeSet<String> = ["LOSp1".to_string(), "LOSp2".to_string()].into();
+        for sybil in ["LOSs1", "LOSs2", "LOSs3"] {
+            assert_eq!(
+                q.check_proposal(&cp(1000, "tip", "root-b"), &ours, sybil, &validators, 10),
+                Verdict::NotComparable
+            );
+        }
+        assert!(!q.is_quarantined());
+        assert_eq!(q.status().diverging_proposers, 0);
+
+        q.check_proposal(&cp(1000, "tip", "root-b"), &ours, "LOSp1", &validators, 11);
+        assert_eq!(
+            q.check_proposal(&cp(1000, "tip", "root-b"), &ours, "LOSp2", &validators, 12),
+            Verdict::Diverged { entered: true }
+        );
+    }
+
+    #[test]
+    fn test_finalized_divergence_quarantines_immediately() {
+        let mut q = StateQuarantine::new(QuarantineConfig {
+            threshold: 5,
+            resync_secs: 10,
+        });
+        let ours = local("tip", "root-a");
+        assert_eq!(
+            q.check(&cp(1000, "tip", "root-b"), &ours, None, 7),
+            Verdict::Diverged { entered: true }
+        );
+        assert_eq!(q.status().last_mismatch.unwrap().proposer, None);
+        // Already quarantined: further divergence does not re-enter
+        assert_eq!(
+            q.check(&cp(1000, "tip", "root-b"), &ours, Some("LOSp1"), 8),
+            Verdict::Diverged { entered: false }
+        );
+        // An agreeing check resets the evidence
+        q.check(&cp(1000, "tip", "root-a"), &ours, Some("LOSp1"), 9);
+        assert_eq!(q.status().diverging_proposers, 0);
+    }
+
+    #[test]
+    fn test_full_root_covers_vm_and_rewards() {
+        let ledger = Ledger::new();
+        let vm = WasmEngine::new();
+        let mut pool = ValidatorRewardPool::new(1_770_580_908);
+        let root = full_root(&ledger, &vm, &pool).unwrap();
+        assert_eq!(root, full_root(&ledger, &vm, &pool).unwrap());
+
+        pool.register_validator("LOSval1", false, 1);
+        pool.validators
+            .get_mut("LOSval1")
+            .unwrap()
+            .cumulative_rewards_cil = 5;
+        let with_rewards = full_root(&ledger, &vm, &pool).unwrap();
+        assert_ne!(root, with_rewards);
+
+        vm.deploy_contract(
+            "LOSowner".to_string(),
+            b"\0asm\x01\x00\x00\x00".to_vec(),
+            Default::default(),
+            1,
+        )
+        .unwrap();
+        assert_ne!(with_rewards, full_root(&ledger, &vm, &pool).unwrap());
+        assert_eq!(latest_block_hash(&ledger), "genesis");
+    }
+}
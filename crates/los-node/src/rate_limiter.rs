@@ -1,12 +1,14 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 // UNAUTHORITY (LOS) - RATE LIMITER (DDoS Protection)
 //
-// Token Bucket Algorithm with IP-based tracking.
+// Token Bucket Algorithm with IP-based tracking (or any other key, e.g. API
+// key IDs for tiered limits).
 // MAINNET SAFETY: Uses integer math (millitokens) instead of f64
 // for deterministic behavior across platforms.
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::net::IpAddr;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
@@ -27,8 +29,8 @@ const MILLITOKEN: u64 = 1000;
 /// Allows burst traffic but limits average rate over time
 /// MAINNET SAFETY: Uses integer math (millitokens) — no f64 in production.
 #[derive(Clone)]
-pub struct RateLimiter {
-    buckets: Arc<Mutex<HashMap<IpAddr, TokenBucket>>>,
+pub struct RateLimiter<K = IpAddr> {
+    buckets: Arc<Mutex<HashMap<K, TokenBucket>>>,
     max_tokens_milli: u64, // Maximum tokens in millitokens (burst capacity)
     refill_rate: u32,      // Tokens per second
     cleanup_interval: Duration, // How often to cleanup old entries
@@ -40,7 +42,7 @@ struct TokenBucket {
     last_refill: Instant,
 }
 
-impl RateLimiter {
+impl<K: Hash + Eq> RateLimiter<K> {
     /// Create new rate limiter
     ///
     /// # Arguments
@@ -60,7 +62,7 @@ impl RateLimiter {
 
     /// Check if request is allowed for given IP
     /// Returns true if request can proceed, false if rate limit exceeded
-    pub fn check_rate_limit(&self, ip: IpAddr) -> bool {
+    pub fn check_rate_limit(&self, ip: K) -> bool {
         // Periodic cleanup
         self.cleanup_if_needed();
 
@@ -94,7 +96,7 @@ impl RateLimiter {
     /// Get current token count for IP (for monitoring)
     /// Returns millitokens (divide by 1000 for whole tokens)
     #[allow(dead_code)]
-    pub fn get_tokens_milli(&self, ip: IpAddr) -> Option<u64> {
+    pub fn get_tokens_milli(&self, ip: K) -> Option<u64> {
         let buckets = safe_lock(&self.buckets);
         buckets.get(&ip).map(|b| b.tokens_milli)
     }
@@ -124,7 +126,7 @@ impl RateLimiter {
 
     /// Reset rate limit for specific IP (admin tool)
     #[allow(dead_code)]
-    pub fn reset_ip(&self, ip: IpAddr) {
+    pub fn reset_ip(&self, ip: K) {
         let mut buckets = safe_lock(&self.buckets);
         buckets.remove(&ip);
     }
@@ -139,7 +141,6 @@ impl RateLimiter {
 
 /// Warp filter for rate limiting
 pub mod filters {
-    use super::RateLimiter;
     use std::net::IpAddr;
     use warp::Filter;
 
@@ -152,22 +153,6 @@ pub mod filters {
         })
     }
 
-    /// Rate limit filter
-    pub fn rate_limit(
-        limiter: RateLimiter,
-    ) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
-        client_ip()
-            .and(warp::any().map(move || limiter.clone()))
-            .and_then(|ip: IpAddr, limiter: RateLimiter| async move {
-                if limiter.check_rate_limit(ip) {
-                    Ok(())
-                } else {
-                    Err(warp::reject::custom(RateLimitExceeded { ip }))
-                }
-            })
-            .untuple_one()
-    }
-
     /// Rate limit exceeded rejection
     #[derive(Debug)]
     pub struct RateLimitExceeded {
@@ -0,0 +1,204 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - READ-CALL RESULT CACHE
+//
+// Dapps poll the same view functions (token name, decimals, pool reserves)
+// through POST /dry-run-contract. A call whose result only depends on the
+// contract's own state, its args, the caller and the attached amount is
+// cached here, keyed by (contract, function, args, caller, amount).
+//
+// Each entry records the contract's state version
+// (WasmEngine::contract_state_version, a hash of code, state, balance and
+// pause flag) at execution time. A lookup under a different version is a
+// miss and drops the entry, so any state change invalidates every cached
+// read of that contract without hooking the mutation paths.
+//
+// Only pure reads are stored: successful, no state changes, events or
+// transfers, and no timestamp / random seed / cross-contract reads
+// (DryRunResult::env_reads). Traced runs always execute.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_vm::{ContractCall, DryRunResult};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+/// Cached read results kept per node
+pub const READ_CACHE_CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReadCallKey {
+    contract: String,
+    function: String,
+    args: Vec<String>,
+    caller: String,
+    amount: u128,
+}
+
+impl ReadCallKey {
+    fn of(call: &ContractCall) -> Self {
+        Self {
+            contract: call.contract.clone(),
+            function: call.function.clone(),
+            args: call.args.clone(),
+            caller: call.caller.clone(),
+            amount: call.amount,
+        }
+    }
+}
+
+struct CachedRead {
+    state_version: String,
+    result: DryRunResult,
+}
+
+pub struct ReadCache {
+    entries: Mutex<LruCache<ReadCallKey, CachedRead>>,
+}
+
+/// Whether repeating `dry` on the same contract state gives the same result
+pub fn is_cacheable(dry: &DryRunResult) -> bool {
+    let r = &dry.result;
+    r.success
+        && !dry.env_reads
+        && dry.trace.is_none()
+        && r.state_changes.is_empty()
+        && r.events.is_empty()
+        && r.transfers.is_empty()
+        && r.contract_transfers.is_empty()
+}
+
+impl ReadCache {
+    pub fn new(capacity: usize) -> Self {
+        let cap = NonZeroUsize::new(capacity.max(1)).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(cap)),
+        }
+    }
+
+    /// Cached result of `call` at `state_version`. Entries from an older
+    /// version are dropped; a gas limit below the cached gas use misses
+    /// (the call would have run out of gas).
+    pub fn lookup(&self, call: &ContractCall, state_version: &str) -> Option<DryRunResult> {
+        let key = ReadCallKey::of(call);
+        let mut entries = self.entries.lock().ok()?;
+        let entry = entries.get(&key)?;
+        if entry.state_version != state_version {
+            entries.pop(&key);
+            return None;
+        }
+        (entry.result.result.gas_used <= call.gas_limit).then(|| entry.result.clone())
+    }
+
+    /// Remember `dry` if it is a pure read. Returns whether it was stored.
+    pub fn store(&self, call: &ContractCall, state_version: String, dry: &DryRunResult) -> bool {
+        if !is_cacheable(dry) {
+            return false;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return false;
+        };
+        entries.put(
+            ReadCallKey::of(call),
+            CachedRead {
+                state_version,
+                result: dry.clone(),
+            },
+        );
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+}
+
+impl Default for ReadCache {
+    fn default() -> Self {
+        Self::new(READ_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_vm::ContractResult;
+    use std::collections::BTreeMap;
+
+    fn call(function: &str, gas_limit: u64) -> ContractCall {
+        ContractCall {
+            contract: "LOSConToken".to_string(),
+            function: function.to_string(),
+            args: vec!["LOSalice".to_string()],
+            gas_limit,
+            caller: "LOSbob".to_string(),
+            block_timestamp: 0,
+            amount: 0,
+        }
+    }
+
+    fn read(output: &str, gas_used: u64) -> DryRunResult {
+        DryRunResult {
+            result: ContractResult {
+                success: true,
+                output: output.to_string(),
+                gas_used,
+                state_changes: BTreeMap::new(),
+                events: Vec::new(),
+                transfers: Vec::new(),
+                contract_transfers: Vec::new(),
+            },
+            logs: Vec::new(),
+            trace: None,
+            env_reads: false,
+        }
+    }
+
+    #[test]
+    fn test_hit_until_state_version_changes() {
+        let cache = ReadCache::new(8);
+        assert!(cache.lookup(&call("balance_of", 1_000), "v1").is_none());
+        assert!(cache.store(
+            &call("balance_of", 1_000),
+            "v1".to_string(),
+            &read("42", 300)
+        ));
+
+        // Gas limit is not part of the key, as long as it covers the cached use
+        let hit = cache.lookup(&call("balance_of", 5_000), "v1").unwrap();
+        assert_eq!(hit.result.output, "42");
+        assert!(cache.lookup(&call("balance_of", 299), "v1").is_none());
+        assert_eq!(cache.len(), 1);
+
+        let mut other_caller = call("balance_of", 1_000);
+        other_caller.caller = "LOScarol".to_string();
+        assert!(cache.lookup(&other_caller, "v1").is_none());
+
+        // State changed: stale entry is dropped
+        assert!(cache.lookup(&call("balance_of", 1_000), "v2").is_none());
+        assert_eq!(cache.len(), 0);
+        assert!(cache.lookup(&call("balance_of", 1_000), "v1").is_none());
+    }
+
+    #[test]
+    fn test_only_pure_reads_are_stored() {
+        let cache = ReadCache::new(8);
+        let c = call("mint", 1_000);
+
+        let mut failed = read("", 10);
+        failed.result.success = false;
+        let mut writes = read("ok", 10);
+        writes
+            .result
+            .state_changes
+            .insert("k".to_string(), "v".to_string());
+        let mut clock = read("ok", 10);
+        clock.env_reads = true;
+        let mut pays = read("ok", 10);
+        pays.result.transfers.push(("LOSalice".to_string(), 1));
+
+        for dry in [failed, writes, clock, pays] {
+            assert!(!cache.store(&c, "v1".to_string(), &dry));
+        }
+        assert_eq!(cache.len(), 0);
+    }
+}
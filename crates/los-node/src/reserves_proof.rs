@@ -0,0 +1,131 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - PROOF OF RESERVES
+//
+// GET /proof/reserves?addresses=a,b,c[&at_checkpoint=H]
+//   Balances of the listed accounts plus Merkle proofs against the account
+//   root (los_light::AccountTree over every account, address order). Anyone
+//   can check the response offline with los_light::verify_reserves.
+//
+// Anchoring: finality checkpoints sign the full state root (flat
+// Ledger::compute_state_root + VM + reward pool), not the account Merkle
+// root. With at_checkpoint=H the node serves proofs only if its live state
+// still hashes to checkpoint H's state_root. That is the exact state the validators
+// signed. The link between account_root and state_root is asserted by this
+// node, not proven. Closing that gap needs checkpoints to commit to the
+// account root (consensus change). Historical state is not kept, so older
+// checkpoints cannot be served.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::Ledger;
+use los_light::{account_leaf_hash, AccountProof, AccountTree, ReservesProof};
+use std::collections::HashSet;
+
+/// Max addresses per request (each proof is ~log2(accounts) hashes)
+pub const MAX_PROOF_ADDRESSES: usize = 100;
+
+/// Parse the comma-separated `addresses` parameter (deduplicated, order kept)
+pub fn parse_addresses(raw: &str) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let addresses: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty() && seen.insert(*a))
+        .map(str::to_string)
+        .collect();
+    if addresses.is_empty() {
+        return Err("addresses is required (comma-separated)".to_string());
+    }
+    if addresses.len() > MAX_PROOF_ADDRESSES {
+        return Err(format!(
+            "Too many addresses: {} (max {})",
+            addresses.len(),
+            MAX_PROOF_ADDRESSES
+        ));
+    }
+    Ok(addresses)
+}
+
+/// Build proofs for `addresses` against the current account root.
+/// Unknown addresses are an error: a reserves proof must not silently drop one.
+pub fn build_reserves_proof(
+    ledger: &Ledger,
+    addresses: &[String],
+) -> Result<ReservesProof, String> {
+    let leaves: Vec<[u8; 32]> = ledger
+        .accounts
+        .iter()
+        .map(|(addr, a)| account_leaf_hash(addr, a.balance, a.block_count, &a.head))
+        .collect();
+    let tree = AccountTree::new(leaves);
+
+    let mut accounts = Vec::with_capacity(addresses.len());
+    let mut total_cil: u128 = 0;
+    for address in addresses {
+        // BTreeMap order = leaf order
+        let index = ledger
+            .accounts
+            .keys()
+            .position(|k| k == address)
+            .ok_or_else(|| format!("Unknown account {}", address))?;
+        let state = &ledger.accounts[address];
+        let siblings = tree
+            .proof(index)
+            .ok_or_else(|| format!("No proof for {}", address))?;
+        total_cil = total_cil.saturating_add(state.balance);
+        accounts.push(AccountProof {
+            address: address.clone(),
+            balance_cil: state.balance,
+            block_count: state.block_count,
+            head: state.head.clone(),
+            leaf_index: index as u64,
+            siblings: siblings.iter().map(hex::encode).collect(),
+        });
+    }
+    Ok(ReservesProof {
+        account_root: hex::encode(tree.root()),
+        leaf_count: tree.leaf_count() as u64,
+        total_cil,
+        accounts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::AccountState;
+
+    fn ledger(balances: &[(&str, u128)]) -> Ledger {
+        let mut l = Ledger::new();
+        for (addr, balance) in balances {
+            l.accounts.insert(
+                addr.to_string(),
+                AccountState {
+                    head: format!("head-{}", addr),
+                    balance: *balance,
+                    block_count: 1,
+                    is_validator: false,
+                },
+            );
+        }
+        l
+    }
+
+    #[test]
+    fn test_proof_verifies_with_los_light() {
+        let l = ledger(&[
+            ("LOSa", 10),
+            ("LOSb", 20),
+            ("LOSc", 30),
+            ("LOSd", 40),
+            ("LOSe", 50),
+        ]);
+        let addrs = parse_addresses("LOSe, LOSb,LOSe,").unwrap();
+        assert_eq!(addrs, vec!["LOSe", "LOSb"]);
+        let proof = build_reserves_proof(&l, &addrs).unwrap();
+        assert_eq!(proof.total_cil, 70);
+        assert_eq!(los_light::verify_reserves(&proof, None), Ok(70));
+
+        assert!(build_reserves_proof(&l, &["LOSz".to_string()]).is_err());
+        assert!(parse_addresses(" , ").is_err());
+    }
+}
@@ -0,0 +1,261 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - PERSISTENT GOSSIP SEEN-CACHE
+//
+// Gossipsub only remembers message IDs in memory for ~1 minute, so a peer
+// that replays an old CONFIRM_RES / BLOCK_CONFIRMED blob (or any message
+// after we restart) gets it reprocessed. This cache sits between the network
+// channel and the node event loop:
+// - Message ID = blake3(payload), stable across restarts and Rust versions
+// - sled-backed ring buffer: `seen_ids` (id → seq ‖ ts) + `seen_ring` (seq → id)
+// - Entries expire after a TTL; the ring is capped at a fixed capacity
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use sled::Tree;
+
+/// Default dedup window: replays within 1 hour are suppressed
+pub const DEFAULT_SEEN_TTL_SECS: u64 = 3_600;
+/// Default ring capacity (~48 bytes/entry on disk → ~10 MB)
+pub const DEFAULT_SEEN_CAPACITY: u64 = 200_000;
+
+/// Messages that legitimately repeat with an identical payload:
+/// - SYNC_REQUEST:{addr}:{block_count}, every 30s while a node is behind
+/// - SYNC_GZIP: / SYNC_BIN: answers, identical while the ledger is unchanged
+///   (a node that asks again still needs the answer)
+/// - ID: state announcements, resent on every heartbeat
+const DEDUP_EXEMPT_PREFIXES: &[&str] = &["SYNC_REQUEST:", "SYNC_GZIP:", "SYNC_BIN:", "ID:"];
+
+/// Whether a gossip payload bypasses the persistent seen-cache
+pub fn is_dedup_exempt(data: &str) -> bool {
+    DEDUP_EXEMPT_PREFIXES.iter().any(|p| data.starts_with(p))
+}
+
+/// Counters for duplicate suppression (exported via Prometheus)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SeenCacheStats {
+    pub checked: u64,
+    pub duplicates: u64,
+}
+
+impl SeenCacheStats {
+    /// Duplicate suppression rate in basis points (10000 = 100%)
+    pub fn duplicate_rate_bps(&self) -> u64 {
+        (self.duplicates * 10_000)
+            .checked_div(self.checked)
+            .unwrap_or(0)
+    }
+}
+
+pub struct SeenCache {
+    ids: Tree,
+    ring: Tree,
+    next_seq: u64,
+    /// Cached ring length (sled `Tree::len` is O(n))
+    count: u64,
+    ttl_secs: u64,
+    capacity: u64,
+    pub stats: SeenCacheStats,
+}
+
+/// blake3 message ID of a gossip payload
+pub fn message_id(payload: &[u8]) -> [u8; 32] {
+    *blake3::hash(payload).as_bytes()
+}
+
+fn encode_entry(seq: u64, ts: u64) -> [u8; 16] {
+    let mut v = [0u8; 16];
+    v[..8].copy_from_slice(&seq.to_be_bytes());
+    v[8..].copy_from_slice(&ts.to_be_bytes());
+    v
+}
+
+fn decode_entry(bytes: &[u8]) -> Option<(u64, u64)> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let seq = u64::from_be_bytes(bytes[..8].try_into().ok()?);
+    let ts = u64::from_be_bytes(bytes[8..].try_into().ok()?);
+    Some((seq, ts))
+}
+
+impl SeenCache {
+    /// Open the cache on top of the node database trees.
+    pub fn open(ids: Tree, ring: Tree, ttl_secs: u64, capacity: u64) -> Result<Self, String> {
+        // Resume sequence numbering after the highest persisted entry
+        let next_seq = match ring.last() {
+            Ok(Some((k, _))) if k.len() == 8 => {
+                u64::from_be_bytes(k.as_ref().try_into().unwrap_or([0u8; 8])) + 1
+            }
+            Ok(_) => 0,
+            Err(e) => return Err(format!("Failed to read seen-cache ring: {}", e)),
+        };
+        let count = ring.len() as u64;
+        Ok(Self {
+            ids,
+            ring,
+            next_seq,
+            count,
+            ttl_secs,
+            capacity: capacity.max(1),
+            stats: SeenCacheStats::default(),
+        })
+    }
+
+    /// Record `payload` as seen. Returns `true` if the message is new (or its
+    /// previous sighting expired) and should be processed, `false` if it is a
+    /// duplicate within the TTL window.
+    pub fn check_and_insert(&mut self, payload: &[u8], now: u64) -> bool {
+        self.stats.checked += 1;
+        let id = message_id(payload);
+
+        if let Ok(Some(existing)) = self.ids.get(id) {
+            if let Some((old_seq, ts)) = decode_entry(&existing) {
+                if now.saturating_sub(ts) < self.ttl_secs {
+                    self.stats.duplicates += 1;
+                    return false;
+                }
+                // Expired — re-insert with a fresh sequence number
+                if let Ok(Some(_)) = self.ring.remove(old_seq.to_be_bytes()) {
+                    self.count = self.count.saturating_sub(1);
+                }
+            }
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let _ = self.ids.insert(id, &encode_entry(seq, now));
+        if let Ok(None) = self.ring.insert(seq.to_be_bytes(), &id) {
+            self.count += 1;
+        }
+        self.evict(now);
+        true
+    }
+
+    /// Drop the oldest entries while over capacity or past TTL.
+    fn evict(&mut self, now: u64) {
+        loop {
+            let over_capacity = self.count > self.capacity;
+            let Ok(Some((seq_key, id))) = self.ring.first() else {
+                return;
+            };
+            let expired = self
+                .ids
+                .get(&id)
+                .ok()
+                .flatten()
+                .and_then(|v| decode_entry(&v))
+                .map(|(_, ts)| now.saturating_sub(ts) >= self.ttl_secs)
+                .unwrap_or(true);
+            if !over_capacity && !expired {
+                return;
+            }
+            let _ = self.ring.remove(&seq_key);
+            self.count = self.count.saturating_sub(1);
+            // Only drop the id if it still points at this ring slot
+            let slot = seq_key.as_ref().try_into().ok().map(u64::from_be_bytes);
+            if let Ok(Some(v)) = self.ids.get(&id) {
+                if decode_entry(&v).map(|(s, _)| s) == slot {
+                    let _ = self.ids.remove(&id);
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_cache(ttl: u64, cap: u64) -> (sled::Db, SeenCache) {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let cache = SeenCache::open(
+            db.open_tree("seen_ids").unwrap(),
+            db.open_tree("seen_ring").unwrap(),
+            ttl,
+            cap,
+        )
+        .unwrap();
+        (db, cache)
+    }
+
+    #[test]
+    fn test_duplicate_suppressed_within_ttl() {
+        let (_db, mut cache) = open_cache(60, 100);
+        assert!(cache.check_and_insert(b"CONFIRM_RES:abc", 1_000));
+        assert!(!cache.check_and_insert(b"CONFIRM_RES:abc", 1_030));
+        assert!(cache.check_and_insert(b"CONFIRM_RES:abd", 1_030));
+        assert_eq!(cache.stats.checked, 3);
+        assert_eq!(cache.stats.duplicates, 1);
+        assert_eq!(cache.stats.duplicate_rate_bps(), 3_333);
+    }
+
+    #[test]
+    fn test_expired_entry_reprocessed() {
+        let (_db, mut cache) = open_cache(60, 100);
+        assert!(cache.check_and_insert(b"msg", 1_000));
+        assert!(cache.check_and_insert(b"msg", 1_060));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_ring_capacity_evicts_oldest() {
+        let (_db, mut cache) = open_cache(3_600, 2);
+        assert!(cache.check_and_insert(b"a", 10));
+        assert!(cache.check_and_insert(b"b", 10));
+        assert!(cache.check_and_insert(b"c", 10));
+        assert_eq!(cache.len(), 2);
+        // "a" was evicted, so it is accepted again
+        assert!(cache.check_and_insert(b"a", 10));
+        assert!(!cache.check_and_insert(b"c", 10));
+    }
+
+    #[test]
+    fn test_sync_request_exempt() {
+        assert!(is_dedup_exempt("SYNC_REQUEST:LOSabc:120"));
+        assert!(is_dedup_exempt("ID:LOSabc:1000:1771000000"));
+        assert!(!is_dedup_exempt("CONFIRM_RES:{}"));
+    }
+
+    #[test]
+    fn test_repeated_sync_response_processed() {
+        // The same peer answers two SYNC_REQUESTs with an unchanged ledger;
+        // the gate in the event loop must let both answers through
+        let (_db, mut cache) = open_cache(3_600, 100);
+        for response in ["SYNC_GZIP:H4sIAAAA", "SYNC_BIN:TE9TUwIA"] {
+            for now in [1_000, 1_030] {
+                assert!(
+                    is_dedup_exempt(response) || cache.check_and_insert(response.as_bytes(), now)
+                );
+            }
+        }
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_persists_across_reopen() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        {
+            let mut cache = SeenCache::open(
+                db.open_tree("seen_ids").unwrap(),
+                db.open_tree("seen_ring").unwrap(),
+                3_600,
+                100,
+            )
+            .unwrap();
+            assert!(cache.check_and_insert(b"replayed", 100));
+        }
+        let mut reopened = SeenCache::open(
+            db.open_tree("seen_ids").unwrap(),
+            db.open_tree("seen_ring").unwrap(),
+            3_600,
+            100,
+        )
+        .unwrap();
+        assert!(!reopened.check_and_insert(b"replayed", 200));
+        assert!(reopened.check_and_insert(b"fresh", 200));
+        assert_eq!(reopened.len(), 2);
+    }
+}
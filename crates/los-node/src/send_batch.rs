@@ -0,0 +1,306 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - BATCHED SEND SUBMISSION (POST /send-batch)
+//
+// Exchanges submit withdrawals by the thousand. POST /send handles one block
+// per request and waits for PoW and consensus initiation each time. Here a
+// caller submits up to MAX_BATCH_SIZE client-signed Send blocks (PoW already
+// solved) in one request:
+//
+//   1. Signatures and PoW are verified in parallel (verify_crypto)
+//   2. The group is validated against the ledger snapshot (validate_batch).
+//      Blocks from one sender must form a chain in batch order: the first
+//      extends the account head, each next one extends the previous item.
+//      Balance is checked cumulatively, including sends already pending.
+//   3. Accepted blocks enter the mempool and pending consensus. Their
+//      CONFIRM_REQs are published by a background task in batch order, so
+//      the HTTP response does not wait on gossip.
+//
+// Every item gets its own status. An item that builds on a rejected item is
+// rejected too, since it could never be applied.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_core::{Block, BlockType, Ledger, BASE_FEE_CIL, MAX_TIMESTAMP_DRIFT_SECS};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum blocks per POST /send-batch request
+pub const MAX_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct SendBatchRequest {
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct BatchItemStatus {
+    pub index: usize,
+    /// "accepted" or "rejected"
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchItemStatus {
+    pub fn accepted(index: usize, tx_hash: String) -> Self {
+        Self {
+            index,
+            status: "accepted",
+            tx_hash: Some(tx_hash),
+            error: None,
+        }
+    }
+
+    pub fn rejected(index: usize, tx_hash: Option<String>, error: String) -> Self {
+        Self {
+            index,
+            status: "rejected",
+            tx_hash,
+            error: Some(error),
+        }
+    }
+}
+
+/// Verify signature + PoW of every block, spread over the available cores
+pub fn verify_crypto(blocks: &[Block]) -> Vec<bool> {
+    if blocks.is_empty() {
+        return Vec::new();
+    }
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let chunk = blocks.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = blocks
+            .chunks(chunk)
+            .map(|part| {
+                scope.spawn(move || {
+                    part.iter()
+                        .map(|b| b.verify_pow() && b.verify_signature())
+                        .collect::<Vec<bool>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Checks that need no ledger state
+fn check_block(block: &Block, crypto_ok: bool, now: u64) -> Result<(), String> {
+    if block.block_type != BlockType::Send {
+        return Err("Only Send blocks can be batched".to_string());
+    }
+    if block.amount == 0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+    if !los_crypto::validate_address(&block.account) {
+        return Err("Invalid sender address format".to_string());
+    }
+    if !los_crypto::validate_address(&block.link) {
+        return Err("Invalid target address format".to_string());
+    }
+    if block.link == block.account {
+        return Err("Cannot send to your own address".to_string());
+    }
+    if block.fee < BASE_FEE_CIL {
+        return Err(format!(
+            "Fee {} CIL is below minimum required fee {} CIL",
+            block.fee, BASE_FEE_CIL
+        ));
+    }
+    if block.timestamp > now.saturating_add(MAX_TIMESTAMP_DRIFT_SECS) {
+        return Err("Timestamp too far in the future".to_string());
+    }
+    let pk = hex::decode(&block.public_key).unwrap_or_default();
+    if los_crypto::public_key_to_address(&pk) != block.account {
+        return Err("public_key does not match sender address".to_string());
+    }
+    if !crypto_ok {
+        return Err("Invalid signature or PoW".to_string());
+    }
+    Ok(())
+}
+
+/// Validate a batch as a group. `crypto_ok[i]` comes from verify_crypto().
+/// Returns per item Ok(tx_hash) or Err(reason), in batch order.
+pub fn validate_batch(
+    ledger: &Ledger,
+    pending: &HashMap<String, (Block, u128)>,
+    blocks: &[Block],
+    crypto_ok: &[bool],
+    now: u64,
+) -> Vec<Result<String, String>> {
+    // Per sender: expected previous hash and spendable balance
+    let mut tips: HashMap<&str, (String, u128)> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut rejected: HashMap<String, usize> = HashMap::new();
+    let mut out = Vec::with_capacity(blocks.len());
+
+    for (i, block) in blocks.iter().enumerate() {
+        let hash = block.calculate_hash();
+        let verdict = (|| {
+            if let Some(j) = rejected.get(&block.previous) {
+                return Err(format!("Depends on rejected item #{}", j));
+            }
+            check_block(block, crypto_ok.get(i).copied().unwrap_or(false), now)?;
+            if !seen.insert(hash.clone())
+                || ledger.blocks.contains_key(&hash)
+                || pending.contains_key(&hash)
+            {
+                return Err("Duplicate transaction".to_string());
+            }
+            if !tips.contains_key(block.account.as_str()) {
+                let state = ledger
+                    .accounts
+                    .get(&block.account)
+                    .ok_or("Sender account not found")?;
+                let pending_debit: u128 = pending
+                    .values()
+                    .filter(|(b, _)| b.account == block.account)
+                    .map(|(b, _)| b.amount.saturating_add(b.fee))
+                    .sum();
+                tips.insert(
+                    block.account.as_str(),
+                    (
+                        state.head.clone(),
+                        state.balance.saturating_sub(pending_debit),
+                    ),
+                );
+            }
+            if let Some((other, _)) = pending
+                .iter()
+                .find(|(_, (b, _))| b.account == block.account && b.previous == block.previous)
+            {
+                return Err(format!("Conflicts with pending transaction {}", other));
+            }
+            let (expected_prev, available) = tips
+                .get_mut(block.account.as_str())
+                .ok_or("Sender account not found")?;
+            if block.previous != *expected_prev {
+                return Err(format!(
+                    "Chain sequence error: expected previous={}, got={}",
+                    expected_prev, block.previous
+                ));
+            }
+            let debit = block
+                .amount
+                .checked_add(block.fee)
+                .ok_or("Overflow: total transaction cost exceeds maximum")?;
+            if *available < debit {
+                return Err(format!(
+                    "Insufficient balance (need {} CIL, {} CIL available after earlier items and pending sends)",
+                    debit, available
+                ));
+            }
+            *available -= debit;
+            *expected_prev = hash.clone();
+            Ok(hash.clone())
+        })();
+        if verdict.is_err() {
+            rejected.insert(hash, i);
+        }
+        out.push(verdict);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::AccountState;
+
+    fn keypair_address() -> (String, String) {
+        let kp = los_crypto::generate_keypair();
+        (
+            los_crypto::public_key_to_address(&kp.public_key),
+            hex::encode(&kp.public_key),
+        )
+    }
+
+    fn send(account: &str, pk: &str, previous: &str, amount: u128, target: &str) -> Block {
+        Block {
+            account: account.to_string(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount,
+            link: target.to_string(),
+            signature: "sig".to_string(),
+            public_key: pk.to_string(),
+            work: 0,
+            timestamp: 1_771_000_000,
+            fee: BASE_FEE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_group_validation_chains_and_budgets_per_sender() {
+        let (sender, pk) = keypair_address();
+        let (target, _) = keypair_address();
+        let mut ledger = Ledger::new();
+        ledger.accounts.insert(
+            sender.clone(),
+            AccountState {
+                head: "h0".to_string(),
+                balance: 3 * (1_000 + BASE_FEE_CIL),
+                block_count: 1,
+                is_validator: false,
+            },
+        );
+
+        let b0 = send(&sender, &pk, "h0", 1_000, &target);
+        let b1 = send(&sender, &pk, &b0.calculate_hash(), 1_000, &target);
+        // Bad crypto → rejected, and the item chained on it too
+        let b2 = send(&sender, &pk, &b1.calculate_hash(), 1_000, &target);
+        let b3 = send(&sender, &pk, &b2.calculate_hash(), 1, &target);
+        // Unknown sender
+        let b4 = send(&target, &pk, "0", 1, &sender);
+        let blocks = vec![b0.clone(), b1, b2, b3, b4];
+        let crypto = vec![true, true, false, true, true];
+
+        let pending = HashMap::new();
+        let res = validate_batch(&ledger, &pending, &blocks, &crypto, 1_771_000_000);
+        assert_eq!(res[0].as_deref(), Ok(b0.calculate_hash().as_str()));
+        assert!(res[1].is_ok());
+        assert_eq!(res[2].as_ref().unwrap_err(), "Invalid signature or PoW");
+        assert_eq!(res[3].as_ref().unwrap_err(), "Depends on rejected item #2");
+        assert!(res[4].is_err());
+
+        // A pending send from the sender reduces the budget and blocks its slot
+        let mut pending = HashMap::new();
+        pending.insert("p".to_string(), (b0.clone(), 0u128));
+        let res = validate_batch(
+            &ledger,
+            &pending,
+            &[send(&sender, &pk, "h0", 5, &target)],
+            &[true],
+            1_771_000_000,
+        );
+        assert!(res[0]
+            .as_ref()
+            .unwrap_err()
+            .contains("Conflicts with pending"));
+
+        // Cumulative balance: the third full-size send does not fit
+        let c0 = send(&sender, &pk, "h0", 1_000, &target);
+        let c1 = send(&sender, &pk, &c0.calculate_hash(), 1_000, &target);
+        let c2 = send(&sender, &pk, &c1.calculate_hash(), 1_001, &target);
+        let res = validate_batch(
+            &ledger,
+            &HashMap::new(),
+            &[c0, c1, c2],
+            &[true; 3],
+            1_771_000_000,
+        );
+        assert!(res[0].is_ok() && res[1].is_ok());
+        assert!(res[2]
+            .as_ref()
+            .unwrap_err()
+            .contains("Insufficient balance"));
+    }
+}
@@ -0,0 +1,665 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - VALIDATOR SIGNER
+//
+// Everything the node signs with its validator key (server-signed blocks,
+// votes, checkpoints, reward co-signatures, registrations, heartbeats,
+// telemetry, PEX) goes through the `Signer` trait:
+//
+// - LocalSigner: Dilithium5 key from wallet.json / LOS_SEED_PHRASE, held in
+//   node memory (default)
+// - RemoteSigner: LOS_REMOTE_SIGNER=http://host:port. The node only knows
+//   the public key; every signature is requested over gRPC (signer.proto)
+//   from a signing daemon (`los-node signer`) on a separate host or in
+//   front of an HSM. The node never holds the secret key.
+//
+// The daemon does not trust the node: it classifies each message by its
+// shape (SignKind::classify) and refuses anything that is not a known
+// validator message of a whitelisted kind (LOS_SIGNER_ALLOW). Requests must
+// carry LOS_SIGNER_TOKEN when it is set; it is required when the daemon
+// listens on a non-loopback address.
+//
+// Calls are blocking (signing sites are synchronous); a remote request is
+// bounded by REMOTE_TIMEOUT and its signature is verified before use.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use sha3::{Digest, Sha3_256};
+use std::net::SocketAddr;
+use std::sync::{mpsc as std_mpsc, Arc};
+use std::time::Duration;
+use tonic::transport::{Endpoint, Server};
+use tonic::{Request, Response, Status};
+use zeroize::Zeroizing;
+
+pub mod proto {
+    tonic::include_proto!("unauthority.signer");
+}
+
+use proto::los_signer_client::LosSignerClient;
+use proto::los_signer_server::{LosSigner, LosSignerServer};
+use proto::{
+    DeriveSecretRequest, DeriveSecretResponse, PublicKeyRequest, PublicKeyResponse, SignRequest,
+    SignResponse,
+};
+
+/// Context of the consensus MAC secret (ABFTConsensus::set_shared_secret)
+pub const CONSENSUS_MAC_CONTEXT: &str = "LOS_CONSENSUS_MAC_V1";
+/// Contexts the daemon derives secrets for
+const DERIVE_CONTEXTS: &[&str] = &[CONSENSUS_MAC_CONTEXT];
+/// Default daemon listen address
+pub const DEFAULT_SIGNER_LISTEN: &str = "127.0.0.1:50070";
+/// Largest message the daemon signs (reward distributions grow with payouts)
+pub const MAX_SIGN_MESSAGE: usize = 1024 * 1024;
+/// Bound on one remote signing round trip
+pub const REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Signs with the validator key
+pub trait Signer: Send + Sync {
+    /// Dilithium5 public key
+    fn public_key(&self) -> &[u8];
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, String>;
+
+    /// SHA3-256(secret key ‖ context), for secrets bound to the validator key
+    fn derive_secret(&self, context: &str) -> Result<Vec<u8>, String>;
+
+    /// "local" or "remote <endpoint>"
+    fn describe(&self) -> String;
+}
+
+pub type SharedSigner = Arc<dyn Signer>;
+
+fn derive(secret_key: &[u8], context: &str) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(secret_key);
+    hasher.update(context.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// LOCAL
+// ─────────────────────────────────────────────────────────────────
+
+/// Key held in node memory
+pub struct LocalSigner {
+    public_key: Vec<u8>,
+    secret_key: Zeroizing<Vec<u8>>,
+}
+
+impl LocalSigner {
+    pub fn new(mut keys: los_crypto::KeyPair) -> Self {
+        Self {
+            public_key: std::mem::take(&mut keys.public_key),
+            secret_key: Zeroizing::new(std::mem::take(&mut keys.secret_key)),
+        }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        los_crypto::sign_message(message, &self.secret_key)
+            .map_err(|e| format!("Signing failed (key corrupted?): {:?}", e))
+    }
+
+    fn derive_secret(&self, context: &str) -> Result<Vec<u8>, String> {
+        Ok(derive(&self.secret_key, context))
+    }
+
+    fn describe(&self) -> String {
+        "local".to_string()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// WHITELIST
+// ─────────────────────────────────────────────────────────────────
+
+/// Validator message kinds the daemon can sign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignKind {
+    /// Block signing hash (64 hex chars)
+    Block,
+    /// CONFIRM_RES payload or VOTE_BATCH envelope
+    Vote,
+    /// FinalityCheckpoint::signing_data (height LE ‖ block hash ‖ state root)
+    Checkpoint,
+    /// Epoch reward / fee distribution co-signature
+    Distribution,
+    /// REGISTER_VALIDATOR:<address>:<ts>
+    Registration,
+    /// VALIDATOR_HEARTBEAT[_PROXY]:...
+    Heartbeat,
+    /// TELEMETRY:<node>:<ts>:<digest>
+    Telemetry,
+    /// PEX:<sender>:<ts>:<digest>
+    Pex,
+}
+
+impl SignKind {
+    pub const ALL: [SignKind; 8] = [
+        SignKind::Block,
+        SignKind::Vote,
+        SignKind::Checkpoint,
+        SignKind::Distribution,
+        SignKind::Registration,
+        SignKind::Heartbeat,
+        SignKind::Telemetry,
+        SignKind::Pex,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SignKind::Block => "block",
+            SignKind::Vote => "vote",
+            SignKind::Checkpoint => "checkpoint",
+            SignKind::Distribution => "distribution",
+            SignKind::Registration => "registration",
+            SignKind::Heartbeat => "heartbeat",
+            SignKind::Telemetry => "telemetry",
+            SignKind::Pex => "pex",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<SignKind> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    /// Kind of `message`, None if it is not a validator message
+    pub fn classify(message: &[u8]) -> Option<SignKind> {
+        let is_hex = |b: &[u8]| !b.is_empty() && b.iter().all(u8::is_ascii_hexdigit);
+        if message.len() == 64 && is_hex(message) {
+            return Some(SignKind::Block);
+        }
+        if message.starts_with(los_core::reward_cosign::DISTRIBUTION_DOMAIN) {
+            return Some(SignKind::Distribution);
+        }
+        if message.len() > 8 && is_hex(&message[8..]) && !is_hex(&message[..8]) {
+            return Some(SignKind::Checkpoint);
+        }
+        let text = std::str::from_utf8(message).ok()?;
+        let prefixed = [
+            ("REGISTER_VALIDATOR:", SignKind::Registration),
+            ("VALIDATOR_HEARTBEAT:", SignKind::Heartbeat),
+            ("VALIDATOR_HEARTBEAT_PROXY:", SignKind::Heartbeat),
+            ("TELEMETRY:", SignKind::Telemetry),
+            ("PEX:", SignKind::Pex),
+        ];
+        if let Some((_, kind)) = prefixed.iter().find(|(p, _)| text.starts_with(p)) {
+            return Some(*kind);
+        }
+        let batch_prefix = format!("{}|", los_consensus::vote_batch::VOTE_BATCH_DOMAIN);
+        if text.starts_with(&batch_prefix) {
+            return Some(SignKind::Vote);
+        }
+        // CONFIRM_RES: {tx_hash}:{requester}:YES:{voter}:{ts}
+        let parts: Vec<&str> = text.split(':').collect();
+        (parts.len() == 5 && parts[2] == "YES" && is_hex(parts[0].as_bytes()))
+            .then_some(SignKind::Vote)
+    }
+}
+
+/// What the daemon agrees to sign
+#[derive(Debug, Clone)]
+pub struct SignerPolicy {
+    pub allowed: Vec<SignKind>,
+}
+
+impl Default for SignerPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: SignKind::ALL.to_vec(),
+        }
+    }
+}
+
+impl SignerPolicy {
+    /// Comma-separated kind names ("block,vote,checkpoint"); empty = all
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut allowed = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let kind = SignKind::from_name(name).ok_or_else(|| {
+                let known: Vec<&str> = SignKind::ALL.iter().map(|k| k.name()).collect();
+                format!(
+                    "Unknown signing kind '{}' (known: {})",
+                    name,
+                    known.join(", ")
+                )
+            })?;
+            if !allowed.contains(&kind) {
+                allowed.push(kind);
+            }
+        }
+        if allowed.is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(Self { allowed })
+    }
+
+    /// Kind of `message` if the daemon may sign it
+    pub fn check(&self, message: &[u8]) -> Result<SignKind, String> {
+        if message.len() > MAX_SIGN_MESSAGE {
+            return Err(format!(
+                "message of {} bytes > max {}",
+                message.len(),
+                MAX_SIGN_MESSAGE
+            ));
+        }
+        let kind = SignKind::classify(message).ok_or("not a validator message")?;
+        if !self.allowed.contains(&kind) {
+            return Err(format!("kind '{}' is not allowed", kind.name()));
+        }
+        Ok(kind)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// REMOTE (node side)
+// ─────────────────────────────────────────────────────────────────
+
+enum Job {
+    Sign(Vec<u8>, std_mpsc::Sender<Result<Vec<u8>, String>>),
+    Derive(String, std_mpsc::Sender<Result<Vec<u8>, String>>),
+}
+
+/// Signatures from a `los-node signer` daemon
+pub struct RemoteSigner {
+    endpoint: String,
+    public_key: Vec<u8>,
+    jobs: std_mpsc::Sender<Job>,
+}
+
+fn authed<T>(message: T, token: &Option<String>) -> Request<T> {
+    let mut request = Request::new(message);
+    if let Some(value) = token
+        .as_ref()
+        .and_then(|t| format!("Bearer {}", t).parse().ok())
+    {
+        request.metadata_mut().insert("authorization", value);
+    }
+    request
+}
+
+impl RemoteSigner {
+    /// Connect to the daemon at `endpoint` and fetch the validator public key.
+    /// Requests run on a dedicated runtime thread, so `sign` can be called
+    /// from sync code and from inside the node's runtime.
+    pub fn connect(endpoint: &str, token: Option<String>) -> Result<Self, String> {
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|e| format!("Invalid remote signer URL '{}': {}", endpoint, e))?
+            .connect_timeout(REMOTE_TIMEOUT)
+            .timeout(REMOTE_TIMEOUT);
+        let (jobs, rx) = std_mpsc::channel::<Job>();
+        let (key_tx, key_rx) = std_mpsc::channel::<Result<Vec<u8>, String>>();
+        std::thread::Builder::new()
+            .name("los-remote-signer".to_string())
+            .spawn(move || {
+                let rt = match tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(2)
+                    .enable_all()
+                    .build()
+                {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        let _ = key_tx.send(Err(format!("Signer runtime: {}", e)));
+                        return;
+                    }
+                };
+                let client = {
+                    let _guard = rt.enter();
+                    LosSignerClient::new(channel.connect_lazy())
+                };
+                let mut first = client.clone();
+                let key = rt.block_on(async {
+                    first
+                        .get_public_key(authed(PublicKeyRequest {}, &token))
+                        .await
+                        .map(|r| r.into_inner().public_key)
+                        .map_err(|e| format!("Remote signer unavailable: {}", e.message()))
+                });
+                let _ = key_tx.send(key);
+                while let Ok(job) = rx.recv() {
+                    let (mut client, token) = (client.clone(), token.clone());
+                    rt.spawn(async move {
+                        match job {
+                            Job::Sign(message, reply) => {
+                                let res = client
+                                    .sign(authed(SignRequest { message }, &token))
+                                    .await
+                                    .map(|r| r.into_inner().signature)
+                                    .map_err(|e| e.message().to_string());
+                                let _ = reply.send(res);
+                            }
+                            Job::Derive(context, reply) => {
+                                let res = client
+                                    .derive_secret(authed(DeriveSecretRequest { context }, &token))
+                                    .await
+                                    .map(|r| r.into_inner().secret)
+                                    .map_err(|e| e.message().to_string());
+                                let _ = reply.send(res);
+                            }
+                        }
+                    });
+                }
+            })
+            .map_err(|e| format!("Failed to start remote signer thread: {}", e))?;
+
+        let public_key = key_rx
+            .recv_timeout(REMOTE_TIMEOUT * 2)
+            .map_err(|_| format!("Remote signer {} did not answer", endpoint))??;
+        if public_key.is_empty() {
+            return Err(format!("Remote signer {} returned no public key", endpoint));
+        }
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            public_key,
+            jobs,
+        })
+    }
+
+    fn call(
+        &self,
+        job: impl FnOnce(std_mpsc::Sender<Result<Vec<u8>, String>>) -> Job,
+    ) -> Result<Vec<u8>, String> {
+        let (reply, answer) = std_mpsc::channel();
+        self.jobs
+            .send(job(reply))
+            .map_err(|_| "Remote signer thread stopped".to_string())?;
+        answer
+            .recv_timeout(REMOTE_TIMEOUT + Duration::from_secs(1))
+            .map_err(|_| format!("Remote signer {} timed out", self.endpoint))?
+            .map_err(|e| format!("Remote signer refused: {}", e))
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, String> {
+        let signature = self.call(|reply| Job::Sign(message.to_vec(), reply))?;
+        if !los_crypto::verify_signature(message, &signature, &self.public_key) {
+            return Err("Remote signer returned an invalid signature".to_string());
+        }
+        Ok(signature)
+    }
+
+    fn derive_secret(&self, context: &str) -> Result<Vec<u8>, String> {
+        self.call(|reply| Job::Derive(context.to_string(), reply))
+    }
+
+    fn describe(&self) -> String {
+        format!("remote {}", self.endpoint)
+    }
+}
+
+/// Remote signer from LOS_REMOTE_SIGNER / LOS_REMOTE_SIGNER_TOKEN, if set
+pub fn remote_from_env() -> Option<Result<RemoteSigner, String>> {
+    let url = std::env::var("LOS_REMOTE_SIGNER")
+        .ok()
+        .filter(|u| !u.trim().is_empty())?;
+    let token = std::env::var("LOS_REMOTE_SIGNER_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    Some(RemoteSigner::connect(url.trim(), token))
+}
+
+// ─────────────────────────────────────────────────────────────────
+// DAEMON (`los-node signer`)
+// ─────────────────────────────────────────────────────────────────
+
+struct SignerService {
+    key: LocalSigner,
+    address: String,
+    policy: SignerPolicy,
+    token: Option<String>,
+}
+
+impl SignerService {
+    fn authorized<T>(&self, request: &Request<T>) -> bool {
+        let Some(token) = &self.token else {
+            return true;
+        };
+        let given = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or("");
+        // blake3::Hash compares in constant time
+        blake3::hash(given.as_bytes()) == blake3::hash(token.as_bytes())
+    }
+}
+
+#[tonic::async_trait]
+impl LosSigner for SignerService {
+    async fn get_public_key(
+        &self,
+        request: Request<PublicKeyRequest>,
+    ) -> Result<Response<PublicKeyResponse>, Status> {
+        if !self.authorized(&request) {
+            return Err(Status::unauthenticated("invalid signer token"));
+        }
+        Ok(Response::new(PublicKeyResponse {
+            public_key: self.key.public_key().to_vec(),
+            address: self.address.clone(),
+        }))
+    }
+
+    async fn sign(&self, request: Request<SignRequest>) -> Result<Response<SignResponse>, Status> {
+        if !self.authorized(&request) {
+            return Err(Status::unauthenticated("invalid signer token"));
+        }
+        let message = request.into_inner().message;
+        let kind = self.policy.check(&message).map_err(|e| {
+            eprintln!("🚫 Refused signing request: {}", e);
+            Status::permission_denied(e)
+        })?;
+        let signature = self.key.sign(&message).map_err(Status::internal)?;
+        Ok(Response::new(SignResponse {
+            signature,
+            kind: kind.name().to_string(),
+        }))
+    }
+
+    async fn derive_secret(
+        &self,
+        request: Request<DeriveSecretRequest>,
+    ) -> Result<Response<DeriveSecretResponse>, Status> {
+        if !self.authorized(&request) {
+            return Err(Status::unauthenticated("invalid signer token"));
+        }
+        let context = request.into_inner().context;
+        if !DERIVE_CONTEXTS.contains(&context.as_str()) {
+            eprintln!("🚫 Refused secret derivation for context '{}'", context);
+            return Err(Status::permission_denied("context is not allowed"));
+        }
+        let secret = self.key.derive_secret(&context).map_err(Status::internal)?;
+        Ok(Response::new(DeriveSecretResponse { secret }))
+    }
+}
+
+/// Load the validator key for the daemon: LOS_SEED_PHRASE, or an encrypted
+/// (or legacy plaintext) wallet.json at `wallet_path`
+fn load_daemon_key(
+    wallet_path: Option<&str>,
+    password: &str,
+) -> Result<los_crypto::KeyPair, String> {
+    if let Ok(phrase) = std::env::var("LOS_SEED_PHRASE") {
+        let mnemonic = bip39::Mnemonic::parse_normalized(&phrase)
+            .map_err(|e| format!("Invalid LOS_SEED_PHRASE: {}", e))?;
+        return Ok(los_crypto::generate_keypair_from_seed(
+            &mnemonic.to_seed(""),
+        ));
+    }
+    let path = wallet_path.ok_or("Set LOS_SIGNER_WALLET (or LOS_SEED_PHRASE)")?;
+    let data =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if let Ok(encrypted) = serde_json::from_str::<los_crypto::EncryptedKey>(&data) {
+        let secret_key = los_crypto::decrypt_private_key(&encrypted, password)
+            .map_err(|e| format!("Wallet decrypt failed: {:?}", e))?;
+        return Ok(los_crypto::KeyPair {
+            public_key: encrypted.public_key,
+            secret_key,
+        });
+    }
+    serde_json::from_str::<los_crypto::KeyPair>(&data)
+        .map_err(|_| format!("{} is not a wallet file", path))
+}
+
+/// `los-node signer`: serve signatures for one validator key. Configured by
+/// LOS_SIGNER_LISTEN, LOS_SIGNER_WALLET + LOS_WALLET_PASSWORD (or
+/// LOS_SEED_PHRASE), LOS_SIGNER_ALLOW and LOS_SIGNER_TOKEN.
+pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
+    let var = |k: &str| std::env::var(k).ok().filter(|v| !v.trim().is_empty());
+    let listen: SocketAddr = var("LOS_SIGNER_LISTEN")
+        .unwrap_or_else(|| DEFAULT_SIGNER_LISTEN.to_string())
+        .parse()
+        .map_err(|e| format!("Invalid LOS_SIGNER_LISTEN: {}", e))?;
+    let token = var("LOS_SIGNER_TOKEN");
+    if token.is_none() && !listen.ip().is_loopback() {
+        return Err(format!(
+            "LOS_SIGNER_TOKEN is required when listening on {} (non-loopback)",
+            listen
+        )
+        .into());
+    }
+    let policy = SignerPolicy::parse(&var("LOS_SIGNER_ALLOW").unwrap_or_default())?;
+    let password = var("LOS_WALLET_PASSWORD").unwrap_or_default();
+    let key = LocalSigner::new(load_daemon_key(
+        var("LOS_SIGNER_WALLET").as_deref(),
+        &password,
+    )?);
+    let address = los_crypto::public_key_to_address(key.public_key());
+
+    let allowed: Vec<&str> = policy.allowed.iter().map(|k| k.name()).collect();
+    println!("🔏 LOS remote signer for {}", address);
+    println!(
+        "   Listening on {} (token {})",
+        listen,
+        if token.is_some() { "required" } else { "off" }
+    );
+    println!("   Signs: {}", allowed.join(", "));
+
+    Server::builder()
+        .add_service(LosSignerServer::new(SignerService {
+            key,
+            address,
+            policy,
+            token,
+        }))
+        .serve(listen)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_validator_messages() {
+        let hash = "ab".repeat(32);
+        let mut checkpoint = 42u64.to_le_bytes().to_vec();
+        checkpoint.extend_from_slice(hash.as_bytes());
+        checkpoint.extend_from_slice(hash.as_bytes());
+        let cases: Vec<(Vec<u8>, Option<SignKind>)> = vec![
+            (hash.clone().into_bytes(), Some(SignKind::Block)),
+            (checkpoint, Some(SignKind::Checkpoint)),
+            (
+                format!("{}:LOSreq:YES:LOSme:1700000000000", hash).into_bytes(),
+                Some(SignKind::Vote),
+            ),
+            (
+                b"LOS_VOTE_BATCH_V1|LOSme|1700000000000|aa:LOSreq".to_vec(),
+                Some(SignKind::Vote),
+            ),
+            (
+                b"LOS-EPOCH-DISTRIBUTION-V1\x01\x00".to_vec(),
+                Some(SignKind::Distribution),
+            ),
+            (
+                b"REGISTER_VALIDATOR:LOSme:1".to_vec(),
+                Some(SignKind::Registration),
+            ),
+            (
+                b"VALIDATOR_HEARTBEAT_PROXY:a:b:1".to_vec(),
+                Some(SignKind::Heartbeat),
+            ),
+            (b"PEX:LOSme:1:abcd".to_vec(), Some(SignKind::Pex)),
+            // A transfer authorization or arbitrary text is never signed
+            (b"please sign me".to_vec(), None),
+            (format!("{}:LOSreq:NO:LOSme:1", hash).into_bytes(), None),
+            ("ab".repeat(33).into_bytes(), None),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(
+                SignKind::classify(&message),
+                expected,
+                "{}",
+                String::from_utf8_lossy(&message)
+            );
+        }
+    }
+
+    #[test]
+    fn test_policy_whitelist() {
+        let policy = SignerPolicy::parse("vote, checkpoint").unwrap();
+        assert_eq!(policy.allowed, vec![SignKind::Vote, SignKind::Checkpoint]);
+        assert!(policy.check("ab".repeat(32).as_bytes()).is_err());
+        assert!(SignerPolicy::parse("blocks").is_err());
+        assert_eq!(SignerPolicy::parse("").unwrap().allowed.len(), 8);
+        assert!(SignerPolicy::default()
+            .check(&vec![b'a'; MAX_SIGN_MESSAGE + 1])
+            .is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_remote_signer_round_trip() {
+        let keys = los_crypto::generate_keypair_from_seed(&[9u8; 64]);
+        let public_key = keys.public_key.clone();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let service = SignerService {
+            address: los_crypto::public_key_to_address(&keys.public_key),
+            key: LocalSigner::new(keys),
+            policy: SignerPolicy::parse("block").unwrap(),
+            token: Some("s3cret".to_string()),
+        };
+        tokio::spawn(
+            Server::builder()
+                .add_service(LosSignerServer::new(service))
+                .serve(addr),
+        );
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let url = format!("http://{}", addr);
+        let remote = tokio::task::spawn_blocking(move || {
+            assert!(RemoteSigner::connect(&url, Some("wrong".to_string())).is_err());
+            RemoteSigner::connect(&url, Some("s3cret".to_string()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(remote.public_key(), public_key.as_slice());
+
+        let hash = "cd".repeat(32);
+        let sig = remote.sign(hash.as_bytes()).unwrap();
+        assert!(los_crypto::verify_signature(
+            hash.as_bytes(),
+            &sig,
+            &public_key
+        ));
+        // Not whitelisted on this daemon
+        assert!(remote.sign(b"REGISTER_VALIDATOR:LOSme:1").is_err());
+        assert_eq!(
+            remote.derive_secret(CONSENSUS_MAC_CONTEXT).unwrap().len(),
+            32
+        );
+        assert!(remote.derive_secret("anything else").is_err());
+    }
+}
@@ -0,0 +1,386 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CONTRACT SOURCE VERIFICATION REGISTRY
+//
+// Developers submit a source bundle (cargo project + pinned toolchain, see
+// the los-verify crate) to POST /contract/{address}/verify. With
+// LOS_SOURCE_VERIFY=1 the node rebuilds the WASM with the canonical build
+// and compares its blake3 hash with the deployed code_hash:
+//   pending → verified   build matches, the source is published
+//           → mismatch   build differs (built hash is recorded)
+//           → failed     bundle did not build (compiler output is recorded)
+//
+// Records (with the bundle) live in the `contract_sources` tree and are
+// served by GET /contract/{address}/source; GET /contract/{address} reports
+// `verified`. One build runs at a time, killed after
+// LOS_SOURCE_VERIFY_TIMEOUT_SECS. A verified contract cannot be
+// resubmitted. Builds run untrusted code (dependencies' build scripts):
+// enable them only on a sandboxed node. Nodes without builds can still serve
+// records, and anyone can check a bundle offline with `los-verify check`.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_verify::{BuildOptions, SourceBundle, Toolchain, Verification};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::db::LosDatabase;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 900;
+/// Build scratch space inside the data dir
+const WORK_DIR: &str = "verify-builds";
+
+/// Source verification settings (from environment)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceVerifyConfig {
+    /// Run verification builds on this node
+    pub builds_enabled: bool,
+    pub timeout_secs: u64,
+    pub work_dir: PathBuf,
+}
+
+impl SourceVerifyConfig {
+    /// Read LOS_SOURCE_VERIFY and LOS_SOURCE_VERIFY_TIMEOUT_SECS
+    pub fn from_env(data_dir: &Path) -> Self {
+        Self::from_vars(data_dir, |k| std::env::var(k).ok())
+    }
+
+    fn from_vars(data_dir: &Path, get: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            builds_enabled: get("LOS_SOURCE_VERIFY")
+                .is_some_and(|v| matches!(v.trim(), "1" | "true" | "yes")),
+            timeout_secs: get("LOS_SOURCE_VERIFY_TIMEOUT_SECS")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|s| *s > 0)
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+            work_dir: data_dir.join(WORK_DIR),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceStatus {
+    Pending,
+    Verified,
+    Mismatch,
+    Failed,
+}
+
+/// One contract's submitted source and the outcome of its rebuild
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceRecord {
+    pub address: String,
+    /// Deployed code hash the build was compared with
+    pub code_hash: String,
+    pub status: SourceStatus,
+    pub bundle_hash: String,
+    pub submitted_at: u64,
+    pub finished_at: Option<u64>,
+    pub built_hash: Option<String>,
+    /// Build error (failed) or reason (interrupted)
+    pub error: Option<String>,
+    pub bundle: SourceBundle,
+}
+
+impl SourceRecord {
+    pub fn toolchain(&self) -> &Toolchain {
+        &self.bundle.toolchain
+    }
+
+    /// Verified against the contract's current code hash
+    pub fn is_verified(&self, code_hash: &str) -> bool {
+        self.status == SourceStatus::Verified && self.code_hash == code_hash
+    }
+
+    /// API view; source files are published only once verified
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "address": self.address,
+            "code_hash": self.code_hash,
+            "status": self.status,
+            "verified": self.status == SourceStatus::Verified,
+            "bundle_hash": self.bundle_hash,
+            "toolchain": self.toolchain(),
+            "submitted_at": self.submitted_at,
+            "finished_at": self.finished_at,
+            "built_hash": self.built_hash,
+            "error": self.error,
+            "file_count": self.bundle.files.len(),
+        });
+        if self.status == SourceStatus::Verified {
+            json["files"] = serde_json::json!(self.bundle.files);
+        }
+        json
+    }
+}
+
+/// Submissions, builds and stored records
+pub struct SourceRegistry {
+    config: SourceVerifyConfig,
+    db: Arc<LosDatabase>,
+    /// A verification build is running
+    building: AtomicBool,
+}
+
+impl SourceRegistry {
+    /// Records left pending by a previous run (node stopped mid-build) are
+    /// marked failed so they can be resubmitted
+    pub fn new(config: SourceVerifyConfig, db: Arc<LosDatabase>, now: u64) -> Self {
+        for mut record in db.load_contract_sources().unwrap_or_default() {
+            if record.status == SourceStatus::Pending {
+                record.status = SourceStatus::Failed;
+                record.finished_at = Some(now);
+                record.error = Some("Build interrupted (node restarted)".to_string());
+                let _ = db.save_contract_source(&record);
+            }
+        }
+        Self {
+            config,
+            db,
+            building: AtomicBool::new(false),
+        }
+    }
+
+    pub fn builds_enabled(&self) -> bool {
+        self.config.builds_enabled
+    }
+
+    pub fn get(&self, address: &str) -> Option<SourceRecord> {
+        self.db.get_contract_source(address).ok().flatten()
+    }
+
+    /// Accept a bundle for `address` (deployed with `code_hash`) and claim
+    /// the build slot. Errors carry the HTTP status to answer with.
+    pub fn submit(
+        &self,
+        address: &str,
+        code_hash: &str,
+        bundle: SourceBundle,
+        now: u64,
+    ) -> Result<SourceRecord, (u16, String)> {
+        if !self.config.builds_enabled {
+            return Err((
+                503,
+                "Verification builds are disabled on this node (LOS_SOURCE_VERIFY); \
+                 check the bundle offline with los-verify"
+                    .to_string(),
+            ));
+        }
+        bundle.validate().map_err(|e| (400, e))?;
+        match self.get(address) {
+            Some(r) if r.is_verified(code_hash) => {
+                return Err((409, format!("Contract {} is already verified", address)))
+            }
+            Some(r) if r.status == SourceStatus::Pending => {
+                return Err((
+                    409,
+                    format!("Contract {} is already being verified", address),
+                ))
+            }
+            _ => {}
+        }
+        if self.building.swap(true, Ordering::SeqCst) {
+            return Err((
+                429,
+                "Another verification build is running; retry later".to_string(),
+            ));
+        }
+
+        let record = SourceRecord {
+            address: address.to_string(),
+            code_hash: code_hash.to_string(),
+            status: SourceStatus::Pending,
+            bundle_hash: bundle.bundle_hash(),
+            submitted_at: now,
+            finished_at: None,
+            built_hash: None,
+            error: None,
+            bundle,
+        };
+        if let Err(e) = self.db.save_contract_source(&record) {
+            self.building.store(false, Ordering::SeqCst);
+            return Err((500, e));
+        }
+        Ok(record)
+    }
+
+    /// Rebuild a submitted record (blocking, minutes) and store the outcome
+    pub fn run_build(&self, record: SourceRecord) -> SourceRecord {
+        println!(
+            "🔨 Verifying source of {} (rustc {}, bundle {})",
+            record.address,
+            record.toolchain().rustc,
+            &record.bundle_hash[..16]
+        );
+        let opts = BuildOptions {
+            work_dir: self.config.work_dir.join(&record.address),
+            timeout: Duration::from_secs(self.config.timeout_secs),
+        };
+        let outcome = los_verify::verify(&record.bundle, &record.code_hash, &opts);
+        self.finish(record, outcome, now_secs())
+    }
+
+    fn finish(
+        &self,
+        mut record: SourceRecord,
+        outcome: Result<Verification, String>,
+        now: u64,
+    ) -> SourceRecord {
+        record.finished_at = Some(now);
+        match outcome {
+            Ok(v) if v.matched => {
+                record.status = SourceStatus::Verified;
+                record.built_hash = Some(v.built_hash);
+                println!("✅ Source of {} verified", record.address);
+            }
+            Ok(v) => {
+                println!(
+                    "❌ Source of {} does not match: built {}",
+                    record.address, v.built_hash
+                );
+                record.status = SourceStatus::Mismatch;
+                record.built_hash = Some(v.built_hash);
+            }
+            Err(e) => {
+                eprintln!("⚠️ Source verification of {} failed", record.address);
+                record.status = SourceStatus::Failed;
+                record.error = Some(e);
+            }
+        }
+        if let Err(e) = self.db.save_contract_source(&record) {
+            eprintln!("⚠️ Failed to store source record: {}", e);
+        }
+        self.building.store(false, Ordering::SeqCst);
+        record
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn bundle() -> SourceBundle {
+        let files: BTreeMap<String, String> = [
+            ("Cargo.toml", "[package]\nname = \"token\"\n"),
+            ("Cargo.lock", "version = 3\n"),
+            ("src/lib.rs", "#![no_std]\n"),
+        ]
+        .iter()
+        .map(|(p, c)| (p.to_string(), c.to_string()))
+        .collect();
+        SourceBundle {
+            toolchain: Toolchain {
+                rustc: "1.85.0".to_string(),
+                target: los_verify::WASM_TARGET.to_string(),
+                package: None,
+                features: vec![],
+                no_default_features: false,
+            },
+            files,
+        }
+    }
+
+    fn registry(path: &str, enabled: bool) -> SourceRegistry {
+        let _ = std::fs::remove_dir_all(path);
+        let db = Arc::new(LosDatabase::open(path).unwrap());
+        let mut config = SourceVerifyConfig::from_vars(Path::new(path), |_| None);
+        config.builds_enabled = enabled;
+        SourceRegistry::new(config, db, 1)
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        let vars: HashMap<&str, &str> = [
+            ("LOS_SOURCE_VERIFY", "1"),
+            ("LOS_SOURCE_VERIFY_TIMEOUT_SECS", "60"),
+        ]
+        .into_iter()
+        .collect();
+        let cfg = SourceVerifyConfig::from_vars(Path::new("/data"), |k| {
+            vars.get(k).map(|v| v.to_string())
+        });
+        assert!(cfg.builds_enabled);
+        assert_eq!(cfg.timeout_secs, 60);
+        assert_eq!(cfg.work_dir, Path::new("/data/verify-builds"));
+        let off = SourceVerifyConfig::from_vars(Path::new("/data"), |_| None);
+        assert!(!off.builds_enabled);
+        assert_eq!(off.timeout_secs, DEFAULT_TIMEOUT_SECS);
+    }
+
+    #[test]
+    fn test_submit_and_finish() {
+        let path = "test_db_source_registry";
+        let disabled = registry(path, false);
+        assert_eq!(
+            disabled.submit("LOSCon1", "aa", bundle(), 5).unwrap_err().0,
+            503
+        );
+        drop(disabled);
+
+        let reg = registry(path, true);
+        let mut bad = bundle();
+        bad.files.insert("build.rs".to_string(), String::new());
+        assert_eq!(reg.submit("LOSCon1", "aa", bad, 5).unwrap_err().0, 400);
+
+        let pending = reg.submit("LOSCon1", "aa", bundle(), 5).unwrap();
+        assert_eq!(reg.get("LOSCon1").unwrap().status, SourceStatus::Pending);
+        assert!(reg.get("LOSCon1").unwrap().to_json().get("files").is_none());
+        // Same contract while pending; another contract while the slot is taken
+        assert_eq!(reg.submit("LOSCon1", "aa", bundle(), 6).unwrap_err().0, 409);
+        assert_eq!(reg.submit("LOSCon2", "bb", bundle(), 6).unwrap_err().0, 429);
+
+        let failed = reg.finish(pending, Err("error[E0425]".to_string()), 7);
+        assert_eq!(failed.status, SourceStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("error[E0425]"));
+
+        // Resubmission after a failure, then a matching build
+        let pending = reg.submit("LOSCon1", "aa", bundle(), 8).unwrap();
+        let verified = reg.finish(
+            pending,
+            Ok(Verification {
+                expected_hash: "aa".to_string(),
+                built_hash: "aa".to_string(),
+                matched: true,
+            }),
+            9,
+        );
+        assert!(verified.is_verified("aa"));
+        assert!(!verified.is_verified("bb"));
+        let json = reg.get("LOSCon1").unwrap().to_json();
+        assert_eq!(json["verified"], true);
+        assert_eq!(json["files"]["src/lib.rs"], "#![no_std]\n");
+        assert_eq!(
+            reg.submit("LOSCon1", "aa", bundle(), 10).unwrap_err().0,
+            409
+        );
+        drop(reg);
+        std::fs::remove_dir_all(path).ok();
+    }
+
+    #[test]
+    fn test_pending_records_fail_on_restart() {
+        let path = "test_db_source_restart";
+        let reg = registry(path, true);
+        reg.submit("LOSCon1", "aa", bundle(), 5).unwrap();
+        let db = Arc::clone(&reg.db);
+        drop(reg);
+
+        let config = SourceVerifyConfig::from_vars(Path::new(path), |_| None);
+        let reg = SourceRegistry::new(config, db, 42);
+        let record = reg.get("LOSCon1").unwrap();
+        assert_eq!(record.status, SourceStatus::Failed);
+        assert_eq!(record.finished_at, Some(42));
+        drop(reg);
+        std::fs::remove_dir_all(path).ok();
+    }
+}
@@ -0,0 +1,552 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - STORAGE MAINTENANCE (sled size, compaction, snapshots)
+//
+// sled never shrinks its files while open: overwritten and deleted values
+// leave dead space in the segment log. A background task (every
+// LOS_STORAGE_CHECK_SECS) measures:
+//   - size on disk vs. live data (key + value bytes of every tree). When the
+//     ratio passes LOS_COMPACT_RATIO_PCT the task schedules a compaction by
+//     writing `.compact-pending` in the data dir.
+//   - free space on the data dir's filesystem. Below LOS_DISK_MIN_FREE_PCT a
+//     🚨 alert is logged (once per transition) and los_storage_low_disk = 1.
+//
+// Compaction runs at startup, before the database is opened (sled cannot
+// swap an open database): the pending marker or `--compact-db` copies the
+// live data into a fresh database and replaces the old one.
+//
+// With LOS_SNAPSHOT_INTERVAL_SECS > 0 the task also writes full copies to
+// `<data_dir>/snapshots/snap-<unix_ts>`. Snapshots beyond LOS_SNAPSHOT_KEEP or
+// older than LOS_SNAPSHOT_MAX_AGE_DAYS are pruned; the newest is always kept.
+//
+// The latest report is served by GET /storage.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::db::LosDatabase;
+use crate::metrics::LosMetrics;
+
+/// Marker file: compact the database on next startup
+pub const COMPACT_MARKER: &str = ".compact-pending";
+/// Snapshot directory inside the data dir
+pub const SNAPSHOT_DIR: &str = "snapshots";
+const SNAPSHOT_PREFIX: &str = "snap-";
+/// Don't compact for less dead space than this, whatever the ratio
+const MIN_COMPACT_WASTE_BYTES: u64 = 64 * 1_048_576;
+
+const DEFAULT_CHECK_SECS: u64 = 600;
+const DEFAULT_SNAPSHOT_KEEP: usize = 3;
+const DEFAULT_SNAPSHOT_MAX_AGE_DAYS: u64 = 14;
+const DEFAULT_MIN_FREE_PCT: u64 = 10;
+/// Size on disk ≥ 3× live data
+const DEFAULT_COMPACT_RATIO_PCT: u64 = 300;
+
+/// Which snapshots to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Keep at most this many snapshots (newest first)
+    pub keep: usize,
+    /// Drop snapshots older than this (0 = no age limit)
+    pub max_age_secs: u64,
+}
+
+/// Storage maintenance settings (from environment)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageConfig {
+    pub check_secs: u64,
+    /// 0 = no periodic snapshots
+    pub snapshot_interval_secs: u64,
+    pub retention: RetentionPolicy,
+    pub min_free_pct: u64,
+    pub compact_ratio_pct: u64,
+}
+
+impl StorageConfig {
+    /// Read LOS_STORAGE_CHECK_SECS, LOS_SNAPSHOT_*, LOS_DISK_MIN_FREE_PCT and
+    /// LOS_COMPACT_RATIO_PCT
+    pub fn from_env() -> Self {
+        Self::from_vars(|k| std::env::var(k).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Self {
+        let num = |k: &str| get(k).and_then(|v| v.trim().parse::<u64>().ok());
+        Self {
+            check_secs: num("LOS_STORAGE_CHECK_SECS")
+                .filter(|s| *s > 0)
+                .unwrap_or(DEFAULT_CHECK_SECS),
+            snapshot_interval_secs: num("LOS_SNAPSHOT_INTERVAL_SECS").unwrap_or(0),
+            retention: RetentionPolicy {
+                keep: num("LOS_SNAPSHOT_KEEP")
+                    .map(|k| (k as usize).max(1))
+                    .unwrap_or(DEFAULT_SNAPSHOT_KEEP),
+                max_age_secs: num("LOS_SNAPSHOT_MAX_AGE_DAYS")
+                    .unwrap_or(DEFAULT_SNAPSHOT_MAX_AGE_DAYS)
+                    .saturating_mul(86_400),
+            },
+            min_free_pct: num("LOS_DISK_MIN_FREE_PCT")
+                .unwrap_or(DEFAULT_MIN_FREE_PCT)
+                .min(100),
+            compact_ratio_pct: num("LOS_COMPACT_RATIO_PCT")
+                .filter(|r| *r > 100)
+                .unwrap_or(DEFAULT_COMPACT_RATIO_PCT),
+        }
+    }
+}
+
+/// One snapshot directory
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub created_at: u64,
+    pub size_bytes: u64,
+}
+
+/// Result of the last maintenance pass (GET /storage)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageReport {
+    pub checked_at: u64,
+    pub db_size_bytes: u64,
+    /// Key + value bytes of all trees
+    pub live_bytes: u64,
+    /// Size on disk as a percentage of live data (300 = 3×)
+    pub fragmentation_pct: u64,
+    pub compaction_pending: bool,
+    /// (unix ts, bytes before, bytes after) of the startup compaction
+    pub last_compaction: Option<(u64, u64, u64)>,
+    pub snapshots: Vec<SnapshotInfo>,
+    pub snapshots_size_bytes: u64,
+    pub disk_total_bytes: Option<u64>,
+    pub disk_free_bytes: Option<u64>,
+    pub disk_free_pct: Option<u64>,
+    pub low_disk: bool,
+    pub alerts: Vec<String>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Total size of the files under `path`
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.metadata() {
+            Ok(m) if m.is_dir() => dir_size(&e.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// (total, available) bytes of the filesystem holding `path`
+#[cfg(unix)]
+pub fn disk_space(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid out-pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let frsize = stat.f_frsize as u64;
+    Some((
+        (stat.f_blocks as u64).saturating_mul(frsize),
+        (stat.f_bavail as u64).saturating_mul(frsize),
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn disk_space(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Snapshots under `<data_dir>/snapshots`, newest first
+pub fn list_snapshots(data_dir: &Path) -> Vec<SnapshotInfo> {
+    let dir = data_dir.join(SNAPSHOT_DIR);
+    let mut out: Vec<SnapshotInfo> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let created_at = name.strip_prefix(SNAPSHOT_PREFIX)?.parse().ok()?;
+            Some(SnapshotInfo {
+                size_bytes: dir_size(&e.path()),
+                name,
+                created_at,
+            })
+        })
+        .collect();
+    out.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    out
+}
+
+/// Names of snapshots to delete under `policy`. `snapshots` is newest
+/// first; the newest one is never selected.
+pub fn select_prunable(
+    snapshots: &[SnapshotInfo],
+    now: u64,
+    policy: RetentionPolicy,
+) -> Vec<String> {
+    snapshots
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(i, s)| {
+            *i >= policy.keep
+                || (policy.max_age_secs > 0
+                    && now.saturating_sub(s.created_at) > policy.max_age_secs)
+        })
+        .map(|(_, s)| s.name.clone())
+        .collect()
+}
+
+/// Copy every tree of `src` into the empty database `dst`
+fn copy_database(src: &sled::Db, dst: &sled::Db) -> Result<(), String> {
+    dst.import(src.export());
+    dst.flush()
+        .map_err(|e| format!("Failed to flush copy: {}", e))?;
+    Ok(())
+}
+
+/// Open a sled database, retrying while a just-closed handle (whose flusher
+/// thread has not exited yet) still holds the file lock
+fn open_with_retry(path: &Path) -> Result<sled::Db, sled::Error> {
+    let mut delay_ms = 50;
+    loop {
+        match sled::open(path) {
+            Err(e) if delay_ms <= 800 && e.to_string().contains("could not acquire lock") => {
+                std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+                delay_ms *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Compact `db_path` if `.compact-pending` exists in `data_dir` or `force`.
+/// Must run before the database is opened. Returns (ts, before, after).
+pub fn compact_if_requested(
+    data_dir: &Path,
+    db_path: &Path,
+    force: bool,
+) -> Result<Option<(u64, u64, u64)>, String> {
+    let marker = data_dir.join(COMPACT_MARKER);
+    if !(force || marker.exists()) || !db_path.exists() {
+        return Ok(None);
+    }
+    let before = dir_size(db_path);
+    println!(
+        "🧹 Compacting database {} ({:.2} MB)...",
+        db_path.display(),
+        before as f64 / 1_048_576.0
+    );
+    let tmp = PathBuf::from(format!("{}.compact", db_path.display()));
+    let old = PathBuf::from(format!("{}.old", db_path.display()));
+    let _ = std::fs::remove_dir_all(&tmp);
+    {
+        let src =
+            open_with_retry(db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+        let dst =
+            sled::open(&tmp).map_err(|e| format!("Failed to create compacted copy: {}", e))?;
+        copy_database(&src, &dst)?;
+        src.flush()
+            .map_err(|e| format!("Failed to flush database: {}", e))?;
+    } // both closed here
+    std::fs::rename(db_path, &old).map_err(|e| format!("Failed to move old database: {}", e))?;
+    if let Err(e) = std::fs::rename(&tmp, db_path) {
+        // Put the original back before giving up
+        let _ = std::fs::rename(&old, db_path);
+        return Err(format!("Failed to install compacted database: {}", e));
+    }
+    let _ = std::fs::remove_dir_all(&old);
+    let _ = std::fs::remove_file(&marker);
+    let after = dir_size(db_path);
+    println!(
+        "✅ Database compacted: {:.2} MB → {:.2} MB",
+        before as f64 / 1_048_576.0,
+        after as f64 / 1_048_576.0
+    );
+    Ok(Some((now_secs(), before, after)))
+}
+
+/// Write a snapshot of `database` to `<data_dir>/snapshots/snap-<now>`
+pub fn create_snapshot(
+    database: &LosDatabase,
+    data_dir: &Path,
+    now: u64,
+) -> Result<PathBuf, String> {
+    let dir = data_dir.join(SNAPSHOT_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create snapshot dir: {}", e))?;
+    let target = dir.join(format!("{}{}", SNAPSHOT_PREFIX, now));
+    let partial = dir.join(format!("{}{}.partial", SNAPSHOT_PREFIX, now));
+    let _ = std::fs::remove_dir_all(&partial);
+    {
+        let dst = sled::open(&partial).map_err(|e| format!("Failed to create snapshot: {}", e))?;
+        copy_database(database.raw(), &dst)?;
+    }
+    std::fs::rename(&partial, &target)
+        .map_err(|e| format!("Failed to finalize snapshot: {}", e))?;
+    Ok(target)
+}
+
+/// Measure the database and disk; schedule compaction and raise alerts
+pub fn check_storage(
+    cfg: &StorageConfig,
+    database: &LosDatabase,
+    data_dir: &Path,
+    last_compaction: Option<(u64, u64, u64)>,
+) -> StorageReport {
+    let db_size_bytes = database.stats().size_on_disk;
+    let live_bytes = database.live_bytes();
+    let fragmentation_pct = (db_size_bytes.saturating_mul(100))
+        .checked_div(live_bytes)
+        .unwrap_or(100);
+    let mut alerts = Vec::new();
+
+    let marker = data_dir.join(COMPACT_MARKER);
+    if fragmentation_pct >= cfg.compact_ratio_pct
+        && db_size_bytes.saturating_sub(live_bytes) >= MIN_COMPACT_WASTE_BYTES
+        && !marker.exists()
+    {
+        match std::fs::write(&marker, now_secs().to_string()) {
+            Ok(()) => println!(
+                "🧹 Database is {}% of live data — compaction scheduled for next restart",
+                fragmentation_pct
+            ),
+            Err(e) => eprintln!("⚠️ Failed to schedule compaction: {}", e),
+        }
+    }
+    let compaction_pending = marker.exists();
+    if compaction_pending {
+        alerts.push(format!(
+            "compaction pending: {:.1} MB on disk for {:.1} MB of data (restart to compact)",
+            db_size_bytes as f64 / 1_048_576.0,
+            live_bytes as f64 / 1_048_576.0
+        ));
+    }
+
+    let (disk_total_bytes, disk_free_bytes) = disk_space(data_dir).unzip();
+    let disk_free_pct = disk_total_bytes
+        .zip(disk_free_bytes)
+        .and_then(|(total, free)| free.saturating_mul(100).checked_div(total));
+    let low_disk = disk_free_pct.is_some_and(|p| p < cfg.min_free_pct);
+    if low_disk {
+        alerts.push(format!(
+            "low disk headroom: {}% free (threshold {}%)",
+            disk_free_pct.unwrap_or(0),
+            cfg.min_free_pct
+        ));
+    }
+
+    let snapshots = list_snapshots(data_dir);
+    StorageReport {
+        checked_at: now_secs(),
+        db_size_bytes,
+        live_bytes,
+        fragmentation_pct,
+        compaction_pending,
+        last_compaction,
+        snapshots_size_bytes: snapshots.iter().map(|s| s.size_bytes).sum(),
+        snapshots,
+        disk_total_bytes,
+        disk_free_bytes,
+        disk_free_pct,
+        low_disk,
+        alerts,
+    }
+}
+
+/// Snapshot → prune → measure, forever
+pub async fn run_storage_maintenance(
+    cfg: StorageConfig,
+    database: Arc<LosDatabase>,
+    data_dir: PathBuf,
+    metrics: Arc<LosMetrics>,
+    report: Arc<Mutex<StorageReport>>,
+) {
+    println!(
+        "💾 Storage maintenance: check every {}s, snapshots {}, compact at {}% of live data, alert below {}% free",
+        cfg.check_secs,
+        if cfg.snapshot_interval_secs > 0 {
+            format!(
+                "every {}s (keep {})",
+                cfg.snapshot_interval_secs, cfg.retention.keep
+            )
+        } else {
+            "off".to_string()
+        },
+        cfg.compact_ratio_pct,
+        cfg.min_free_pct
+    );
+    let last_compaction = crate::safe_lock(&report).last_compaction;
+    let mut was_low = false;
+    let mut interval = tokio::time::interval(Duration::from_secs(cfg.check_secs));
+    loop {
+        interval.tick().await;
+        let (cfg_b, db_b, dir_b) = (cfg.clone(), database.clone(), data_dir.clone());
+        let pass = tokio::task::spawn_blocking(move || {
+            let now = now_secs();
+            if cfg_b.snapshot_interval_secs > 0 {
+                let newest = list_snapshots(&dir_b).first().map(|s| s.created_at);
+                if newest.is_none_or(|ts| now.saturating_sub(ts) >= cfg_b.snapshot_interval_secs) {
+                    match create_snapshot(&db_b, &dir_b, now) {
+                        Ok(path) => println!("📸 Database snapshot written: {}", path.display()),
+                        Err(e) => eprintln!("⚠️ Snapshot failed: {}", e),
+                    }
+                }
+            }
+            for name in select_prunable(&list_snapshots(&dir_b), now, cfg_b.retention) {
+                match std::fs::remove_dir_all(dir_b.join(SNAPSHOT_DIR).join(&name)) {
+                    Ok(()) => println!("🗑️  Pruned snapshot {}", name),
+                    Err(e) => eprintln!("⚠️ Failed to prune snapshot {}: {}", name, e),
+                }
+            }
+            check_storage(&cfg_b, &db_b, &dir_b, last_compaction)
+        })
+        .await;
+        let Ok(fresh) = pass else {
+            eprintln!("⚠️ Storage maintenance pass panicked");
+            continue;
+        };
+
+        metrics.db_size_bytes.set(fresh.db_size_bytes as f64);
+        metrics
+            .db_fragmentation_pct
+            .set(fresh.fragmentation_pct as i64);
+        if let Some(free) = fresh.disk_free_bytes {
+            metrics.disk_free_bytes.set(free as i64);
+        }
+        metrics.storage_low_disk.set(fresh.low_disk as i64);
+        if fresh.low_disk && !was_low {
+            eprintln!(
+                "🚨 STORAGE: only {}% disk free at {} ({:.1} MB) — prune snapshots or grow the volume",
+                fresh.disk_free_pct.unwrap_or(0),
+                data_dir.display(),
+                fresh.disk_free_bytes.unwrap_or(0) as f64 / 1_048_576.0
+            );
+        } else if !fresh.low_disk && was_low {
+            println!("✅ STORAGE: disk headroom recovered");
+        }
+        was_low = fresh.low_disk;
+        *crate::safe_lock(&report) = fresh;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(ts: u64) -> SnapshotInfo {
+        SnapshotInfo {
+            name: format!("{}{}", SNAPSHOT_PREFIX, ts),
+            created_at: ts,
+            size_bytes: 1,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_retention_policy() {
+        let day = 86_400;
+        let now = 100 * day;
+        // Newest first
+        let snaps = vec![
+            snap(now - day),
+            snap(now - 2 * day),
+            snap(now - 3 * day),
+            snap(now - 30 * day),
+        ];
+        let policy = RetentionPolicy {
+            keep: 3,
+            max_age_secs: 14 * day,
+        };
+        assert_eq!(
+            select_prunable(&snaps, now, policy),
+            vec![snaps[3].name.clone()]
+        );
+
+        let by_count = RetentionPolicy {
+            keep: 1,
+            max_age_secs: 0,
+        };
+        assert_eq!(select_prunable(&snaps, now, by_count).len(), 3);
+
+        // The newest snapshot survives even when it is too old
+        let stale = vec![snap(now - 60 * day)];
+        assert!(select_prunable(&stale, now, policy).is_empty());
+    }
+
+    #[test]
+    fn test_storage_config_from_vars() {
+        let cfg = |vars: &[(&str, &str)]| {
+            let vars: Vec<(String, String)> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            StorageConfig::from_vars(|k| vars.iter().find(|(n, _)| n == k).map(|(_, v)| v.clone()))
+        };
+        let d = cfg(&[]);
+        assert_eq!(
+            (d.check_secs, d.snapshot_interval_secs),
+            (DEFAULT_CHECK_SECS, 0)
+        );
+        assert_eq!(d.retention.max_age_secs, 14 * 86_400);
+        let c = cfg(&[
+            ("LOS_SNAPSHOT_KEEP", "0"),
+            ("LOS_COMPACT_RATIO_PCT", "50"),
+            ("LOS_DISK_MIN_FREE_PCT", "20"),
+        ]);
+        assert_eq!(c.retention.keep, 1);
+        assert_eq!(c.compact_ratio_pct, DEFAULT_COMPACT_RATIO_PCT);
+        assert_eq!(c.min_free_pct, 20);
+    }
+
+    #[test]
+    fn test_snapshot_and_compaction_preserve_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("los_database");
+        {
+            let db = LosDatabase::open(&db_path).unwrap();
+            for i in 0..200u32 {
+                db.raw().insert(i.to_be_bytes(), vec![7u8; 512]).unwrap();
+            }
+            for i in 0..150u32 {
+                db.raw().remove(i.to_be_bytes()).unwrap();
+            }
+            assert_eq!(db.live_bytes(), 50 * (4 + 512));
+
+            let snap = create_snapshot(&db, dir.path(), 1_000).unwrap();
+            assert!(snap.ends_with("snap-1000"));
+            assert_eq!(list_snapshots(dir.path())[0].created_at, 1_000);
+
+            let report = check_storage(&StorageConfig::from_vars(|_| None), &db, dir.path(), None);
+            assert_eq!(report.live_bytes, 50 * 516);
+            assert_eq!(report.snapshots.len(), 1);
+            db.flush().unwrap();
+        }
+
+        // Nothing requested → no compaction
+        assert!(compact_if_requested(dir.path(), &db_path, false)
+            .unwrap()
+            .is_none());
+        std::fs::write(dir.path().join(COMPACT_MARKER), "1").unwrap();
+        let (_, before, after) = compact_if_requested(dir.path(), &db_path, false)
+            .unwrap()
+            .expect("marker triggers compaction");
+        assert!(before > 0 && after > 0);
+        assert!(!dir.path().join(COMPACT_MARKER).exists());
+
+        let db = LosDatabase::open(&db_path).unwrap();
+        assert_eq!(db.live_bytes(), 50 * 516);
+        assert!(db.raw().get(199u32.to_be_bytes()).unwrap().is_some());
+    }
+}
@@ -0,0 +1,350 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - FULL-LEDGER SYNC PAYLOADS
+//
+// Two encodings of a whole ledger, used by gossip sync (SYNC_REQUEST →
+// SYNC_GZIP / SYNC_BIN) and by GET /sync/full (REST sync, checkpoint cold
+// sync):
+//
+//   v1  gzip(JSON of Ledger)                          SYNC_GZIP:{base64}
+//   v2  "LOSS" ‖ schema u16 LE ‖ zstd(bincode(WireLedger))   SYNC_BIN:{base64}
+//
+// v2 is roughly a third of the size and several times faster to parse. Its
+// layout is explicit (WireLedger / WireBlock below): bincode is positional,
+// so every field is always written and any layout change needs a new
+// SYNC_SCHEMA_VERSION.
+//
+// Negotiation, per peer, keeps v1 for nodes that predate v2:
+//   - gossip: the requester appends its capability, SYNC_REQUEST:{addr}:{n}:v2.
+//     Older responders ignore the extra field and answer SYNC_GZIP
+//   - REST: the requester adds ?format=v2; a v2 answer has Content-Type
+//     application/x-los-sync-v2, anything else is decoded as v1
+//   - the unsolicited sync sent to a new peer stays v1 (capability unknown)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use bincode::Options;
+use los_core::contract_gas::GasUsage;
+use los_core::session_keys::SessionGrant;
+use los_core::{AccountState, Block, BlockType, Ledger};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Write};
+
+/// Layout version of the v2 payload
+pub const SYNC_SCHEMA_VERSION: u16 = 3;
+/// v2 payload magic
+const MAGIC: &[u8; 4] = b"LOSS";
+/// Gossip prefix of a v2 payload (v1 is SYNC_GZIP:)
+pub const BIN_PREFIX: &str = "SYNC_BIN:";
+/// SYNC_REQUEST capability field / ?format= value asking for v2
+pub const V2_CAPABILITY: &str = "v2";
+/// Content-Type of a v2 GET /sync/full answer
+pub const CONTENT_TYPE_V2: &str = "application/x-los-sync-v2";
+/// zstd level: fast to produce on every SYNC_REQUEST, still far smaller than gzip JSON
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncFormat {
+    /// gzip(JSON)
+    JsonGzip,
+    /// zstd(bincode), schema SYNC_SCHEMA_VERSION
+    BincodeZstd,
+}
+
+impl SyncFormat {
+    /// Format for a peer that announced `capability` (SYNC_REQUEST 4th field
+    /// or ?format=); unknown or missing = v1
+    pub fn negotiate(capability: Option<&str>) -> Self {
+        match capability {
+            Some(c) if c.trim() == V2_CAPABILITY => Self::BincodeZstd,
+            _ => Self::JsonGzip,
+        }
+    }
+
+    /// Gossip prefix carrying this format
+    pub fn gossip_prefix(self) -> &'static str {
+        match self {
+            Self::JsonGzip => "SYNC_GZIP:",
+            Self::BincodeZstd => BIN_PREFIX,
+        }
+    }
+}
+
+/// SYNC_REQUEST announcing that we read v2 answers
+pub fn sync_request(address: &str, block_count: usize) -> String {
+    format!("SYNC_REQUEST:{}:{}:{}", address, block_count, V2_CAPABILITY)
+}
+
+/// Block with every field written: the JSON form skips zero valid_after /
+/// expires_at, which a positional encoding cannot do
+#[derive(Serialize, Deserialize)]
+struct WireBlock<'a> {
+    account: Cow<'a, str>,
+    previous: Cow<'a, str>,
+    block_type: BlockType,
+    amount: u128,
+    link: Cow<'a, str>,
+    signature: Cow<'a, str>,
+    public_key: Cow<'a, str>,
+    work: u64,
+    timestamp: u64,
+    fee: u128,
+    valid_after: u64,
+    expires_at: u64,
+}
+
+impl<'a> WireBlock<'a> {
+    fn of(b: &'a Block) -> Self {
+        Self {
+            account: Cow::Borrowed(&b.account),
+            previous: Cow::Borrowed(&b.previous),
+            block_type: b.block_type.clone(),
+            amount: b.amount,
+            link: Cow::Borrowed(&b.link),
+            signature: Cow::Borrowed(&b.signature),
+            public_key: Cow::Borrowed(&b.public_key),
+            work: b.work,
+            timestamp: b.timestamp,
+            fee: b.fee,
+            valid_after: b.valid_after,
+            expires_at: b.expires_at,
+        }
+    }
+
+    fn into_block(self) -> Block {
+        Block {
+            account: self.account.into_owned(),
+            previous: self.previous.into_owned(),
+            block_type: self.block_type,
+            amount: self.amount,
+            link: self.link.into_owned(),
+            signature: self.signature.into_owned(),
+            public_key: self.public_key.into_owned(),
+            work: self.work,
+            timestamp: self.timestamp,
+            fee: self.fee,
+            valid_after: self.valid_after,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+/// Schema 3 of the serialized ledger (2 lacked session_keys)
+#[derive(Serialize, Deserialize)]
+struct WireLedger<'a> {
+    accounts: Cow<'a, BTreeMap<String, AccountState>>,
+    blocks: Vec<(Cow<'a, str>, WireBlock<'a>)>,
+    remaining_supply: u128,
+    claimed_sends: Cow<'a, BTreeSet<String>>,
+    accumulated_fees_cil: u128,
+    total_slashed_cil: u128,
+    contract_held_cil: u128,
+    contract_gas_used: Cow<'a, BTreeMap<String, GasUsage>>,
+    reward_claims_cil: Cow<'a, BTreeMap<String, u128>>,
+    session_keys: Cow<'a, BTreeMap<String, BTreeMap<String, SessionGrant>>>,
+}
+
+fn bincode_options(limit: u64) -> impl Options {
+    bincode::DefaultOptions::new().with_limit(limit)
+}
+
+/// Encode `ledger` in `format`
+pub fn encode(ledger: &Ledger, format: SyncFormat) -> Result<Vec<u8>, String> {
+    match format {
+        SyncFormat::JsonGzip => {
+            let json = serde_json::to_vec(ledger).map_err(|e| format!("encode: {}", e))?;
+            let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            enc.write_all(&json).map_err(|e| format!("gzip: {}", e))?;
+            enc.finish().map_err(|e| format!("gzip: {}", e))
+        }
+        SyncFormat::BincodeZstd => {
+            let wire = WireLedger {
+                accounts: Cow::Borrowed(&ledger.accounts),
+                blocks: ledger
+                    .blocks
+                    .iter()
+                    .map(|(hash, b)| (Cow::Borrowed(hash.as_str()), WireBlock::of(b)))
+                    .collect(),
+                remaining_supply: ledger.distribution.remaining_supply,
+                claimed_sends: Cow::Borrowed(&ledger.claimed_sends),
+                accumulated_fees_cil: ledger.accumulated_fees_cil,
+                total_slashed_cil: ledger.total_slashed_cil,
+                contract_held_cil: ledger.contract_held_cil,
+                contract_gas_used: Cow::Borrowed(&ledger.contract_gas_used),
+                reward_claims_cil: Cow::Borrowed(&ledger.reward_claims_cil),
+                session_keys: Cow::Borrowed(&ledger.session_keys),
+            };
+            let raw = bincode_options(u64::MAX)
+                .serialize(&wire)
+                .map_err(|e| format!("encode: {}", e))?;
+            let mut out = Vec::with_capacity(raw.len() / 3 + 6);
+            out.extend_from_slice(MAGIC);
+            out.extend_from_slice(&SYNC_SCHEMA_VERSION.to_le_bytes());
+            let mut enc = zstd::stream::write::Encoder::new(out, ZSTD_LEVEL)
+                .map_err(|e| format!("zstd: {}", e))?;
+            enc.write_all(&raw).map_err(|e| format!("zstd: {}", e))?;
+            enc.finish().map_err(|e| format!("zstd: {}", e))
+        }
+    }
+}
+
+/// Decode a `format` payload, refusing to inflate it past `max_decompressed` bytes
+pub fn decode(bytes: &[u8], format: SyncFormat, max_decompressed: u64) -> Result<Ledger, String> {
+    match format {
+        SyncFormat::JsonGzip => {
+            let mut json = Vec::new();
+            flate2::read::GzDecoder::new(bytes)
+                .take(max_decompressed)
+                .read_to_end(&mut json)
+                .map_err(|e| format!("Decompression failed: {}", e))?;
+            serde_json::from_slice(&json).map_err(|e| format!("JSON parse failed: {}", e))
+        }
+        SyncFormat::BincodeZstd => {
+            let body = bytes
+                .strip_prefix(MAGIC.as_slice())
+                .ok_or("not a v2 sync payload")?;
+            if body.len() < 2 {
+                return Err("truncated v2 sync payload".to_string());
+            }
+            let schema = u16::from_le_bytes([body[0], body[1]]);
+            if schema != SYNC_SCHEMA_VERSION {
+                return Err(format!(
+                    "unsupported sync schema {} (this node reads {})",
+                    schema, SYNC_SCHEMA_VERSION
+                ));
+            }
+            let mut raw = Vec::new();
+            zstd::stream::read::Decoder::new(&body[2..])
+                .map_err(|e| format!("zstd: {}", e))?
+                .take(max_decompressed)
+                .read_to_end(&mut raw)
+                .map_err(|e| format!("Decompression failed: {}", e))?;
+            let wire: WireLedger = bincode_options(max_decompressed)
+                .deserialize(&raw)
+                .map_err(|e| format!("v2 decode failed: {}", e))?;
+
+            let mut ledger = Ledger::new();
+            ledger.accounts = wire.accounts.into_owned();
+            ledger.blocks = wire
+                .blocks
+                .into_iter()
+                .map(|(hash, b)| (hash.into_owned(), b.into_block()))
+                .collect();
+            ledger.distribution.remaining_supply = wire.remaining_supply;
+            ledger.claimed_sends = wire.claimed_sends.into_owned();
+            ledger.accumulated_fees_cil = wire.accumulated_fees_cil;
+            ledger.total_slashed_cil = wire.total_slashed_cil;
+            ledger.contract_held_cil = wire.contract_held_cil;
+            ledger.contract_gas_used = wire.contract_gas_used.into_owned();
+            ledger.reward_claims_cil = wire.reward_claims_cil.into_owned();
+            ledger.session_keys = wire.session_keys.into_owned();
+            Ok(ledger)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_ledger() -> Ledger {
+        let mut l = Ledger::new();
+        for i in 0..50u64 {
+            let acct = format!("LOSacct{:03}", i);
+            l.accounts.insert(
+                acct.clone(),
+                AccountState {
+                    head: format!("{:064x}", i),
+                    balance: 1_000_000_000_000 + i as u128,
+                    block_count: i,
+                    is_validator: i % 7 == 0,
+                },
+            );
+            l.blocks.insert(
+                format!("{:064x}", i),
+                Block {
+                    account: acct,
+                    previous: "0".repeat(64),
+                    block_type: if i % 2 == 0 {
+                        BlockType::Send
+                    } else {
+                        BlockType::Receive
+                    },
+                    amount: i as u128 * 100_000_000_000,
+                    link: format!("LOSdest{}", i),
+                    signature: "ab".repeat(2_000),
+                    public_key: "cd".repeat(1_000),
+                    work: i * 31,
+                    timestamp: 1_771_000_000 + i,
+                    fee: 100_000,
+                    valid_after: if i == 3 { 1_771_000_100 } else { 0 },
+                    expires_at: 0,
+                },
+            );
+        }
+        l.claimed_sends.insert("f".repeat(64));
+        l.accumulated_fees_cil = 42;
+        l.contract_gas_used
+            .insert("LOSacct001".to_string(), GasUsage { epoch: 3, used: 9 });
+        l.reward_claims_cil.insert("LOSacct007".to_string(), 5);
+        l.session_keys
+            .entry("LOSacct002".to_string())
+            .or_default()
+            .insert(
+                "LOSsession".to_string(),
+                SessionGrant {
+                    public_key: "ef".repeat(32),
+                    expires_at: 1_771_000_600,
+                    max_amount_cil: 3_000,
+                    contract: "LOSConGame".to_string(),
+                    spent_cil: 1_000,
+                },
+            );
+        l
+    }
+
+    #[test]
+    fn test_v2_roundtrip_matches_v1() {
+        let ledger = sample_ledger();
+        let v1 = encode(&ledger, SyncFormat::JsonGzip).unwrap();
+        let v2 = encode(&ledger, SyncFormat::BincodeZstd).unwrap();
+        assert!(v2.len() < v1.len(), "v2 {} vs v1 {}", v2.len(), v1.len());
+
+        let from_v1 = decode(&v1, SyncFormat::JsonGzip, 1 << 30).unwrap();
+        let from_v2 = decode(&v2, SyncFormat::BincodeZstd, 1 << 30).unwrap();
+        assert_eq!(from_v2.compute_state_root(), ledger.compute_state_root());
+        assert_eq!(from_v2.compute_state_root(), from_v1.compute_state_root());
+        assert_eq!(
+            serde_json::to_value(&from_v2).unwrap(),
+            serde_json::to_value(&from_v1).unwrap()
+        );
+        assert!(!from_v2.session_keys.is_empty());
+        assert_eq!(from_v2.session_keys, ledger.session_keys);
+    }
+
+    #[test]
+    fn test_v2_rejects_bad_payloads() {
+        let v2 = encode(&sample_ledger(), SyncFormat::BincodeZstd).unwrap();
+        let mut future = v2.clone();
+        future[4] = 4;
+        assert!(decode(&future, SyncFormat::BincodeZstd, 1 << 30)
+            .unwrap_err()
+            .contains("unsupported sync schema 4"));
+        assert!(decode(b"H4sIjunk", SyncFormat::BincodeZstd, 1 << 30).is_err());
+        assert!(decode(&v2[..v2.len() / 2], SyncFormat::BincodeZstd, 1 << 30).is_err());
+        // Inflating past the cap fails instead of allocating
+        assert!(decode(&v2, SyncFormat::BincodeZstd, 1_024).is_err());
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(SyncFormat::negotiate(Some("v2")), SyncFormat::BincodeZstd);
+        assert_eq!(SyncFormat::negotiate(Some("v9")), SyncFormat::JsonGzip);
+        assert_eq!(SyncFormat::negotiate(None), SyncFormat::JsonGzip);
+        assert_eq!(SyncFormat::BincodeZstd.gossip_prefix(), BIN_PREFIX);
+        // Older responders read fields 1 and 2 and ignore the capability
+        let req = sync_request("LOSa", 12);
+        let parts: Vec<&str> = req.split(':').collect();
+        assert_eq!(parts, vec!["SYNC_REQUEST", "LOSa", "12", "v2"]);
+    }
+}
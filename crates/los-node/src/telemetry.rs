@@ -0,0 +1,368 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - OPT-IN NODE TELEMETRY + NETWORK DASHBOARD
+//
+// Publishing (off by default; --telemetry or LOS_TELEMETRY=1):
+//   every LOS_TELEMETRY_INTERVAL_SECS (default 300) the node signs a summary
+//     {node, version, network, height, peer_count, state_root, latency, timestamp}
+//   with its Dilithium5 key and sends it
+//     - to LOS_TELEMETRY_URL (HTTP POST, e.g. http://dash.example:3030/telemetry), or
+//     - as TELEMETRY:{json} gossip when no URL is set.
+//   Latency = gossip propagation delay of verified VOTE_BATCH envelopes
+//   (receive time − sealed time) over the report window. No IPs, hosts or
+//   geolocation are included.
+//
+// Aggregation (--telemetry-aggregate or LOS_TELEMETRY_AGGREGATE=1):
+//   the node collects verified reports from gossip and POST /telemetry and
+//   serves GET /telemetry/network — so any operator can run a public
+//   dashboard without a central service. Reports are verified (pk → node,
+//   signature, ±5 min timestamp), rate-limited per node, capped at
+//   MAX_TELEMETRY_NODES and dropped after TELEMETRY_STALE_SECS.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::signer::Signer;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Gossip prefix of signed telemetry reports
+pub const TELEMETRY_PREFIX: &str = "TELEMETRY:";
+/// Default publishing interval
+pub const DEFAULT_TELEMETRY_INTERVAL_SECS: u64 = 300;
+/// Minimum seconds between two accepted reports of one node
+pub const MIN_REPORT_INTERVAL_SECS: u64 = 60;
+/// Allowed clock skew of a report timestamp
+pub const MAX_CLOCK_SKEW_SECS: u64 = 300;
+/// Nodes without a report for this long leave the dashboard
+pub const TELEMETRY_STALE_SECS: u64 = 3_600;
+/// Nodes tracked by an aggregator
+pub const MAX_TELEMETRY_NODES: usize = 1_000;
+/// Latency samples kept per report window
+const MAX_LATENCY_SAMPLES: usize = 1_024;
+
+/// Telemetry switches from CLI flags and environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    pub publish: bool,
+    pub url: Option<String>,
+    pub aggregate: bool,
+    pub interval_secs: u64,
+}
+
+impl TelemetryConfig {
+    pub fn from_env(publish_flag: bool, aggregate_flag: bool) -> Self {
+        let env_on = |k: &str| std::env::var(k).unwrap_or_default() == "1";
+        Self {
+            publish: publish_flag || env_on("LOS_TELEMETRY"),
+            url: std::env::var("LOS_TELEMETRY_URL")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            aggregate: aggregate_flag || env_on("LOS_TELEMETRY_AGGREGATE"),
+            interval_secs: std::env::var("LOS_TELEMETRY_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_TELEMETRY_INTERVAL_SECS)
+                .max(MIN_REPORT_INTERVAL_SECS),
+        }
+    }
+}
+
+/// Latency summary of one report window (milliseconds)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub samples: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Collects latency samples between reports
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    samples: VecDeque<u64>,
+}
+
+impl LatencyTracker {
+    pub fn record(&mut self, ms: u64) {
+        if self.samples.len() >= MAX_LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+    }
+
+    /// Stats of the current window; starts a new one
+    pub fn take_stats(&mut self) -> LatencyStats {
+        let mut sorted: Vec<u64> = self.samples.drain(..).collect();
+        sorted.sort_unstable();
+        let pct = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+        match sorted.last() {
+            None => LatencyStats::default(),
+            Some(max) => LatencyStats {
+                samples: sorted.len() as u64,
+                p50_ms: pct(50),
+                p95_ms: pct(95),
+                max_ms: *max,
+            },
+        }
+    }
+}
+
+/// Unsigned node summary
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TelemetrySummary {
+    pub node: String,
+    pub version: String,
+    pub network: String,
+    pub height: u64,
+    pub peer_count: u64,
+    pub state_root: String,
+    pub latency: LatencyStats,
+    pub timestamp: u64,
+}
+
+/// Signed summary as published
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TelemetryReport {
+    #[serde(flatten)]
+    pub summary: TelemetrySummary,
+    /// Hex Dilithium5 public key of `node`
+    pub public_key: String,
+    /// Hex signature over `signing_data()`
+    pub signature: String,
+}
+
+impl TelemetryReport {
+    pub fn signed(summary: TelemetrySummary, signer: &dyn Signer) -> Result<Self, String> {
+        let data = Self::signing_data(&summary);
+        let sig = signer
+            .sign(data.as_bytes())
+            .map_err(|e| format!("Telemetry signing failed: {}", e))?;
+        Ok(Self {
+            summary,
+            public_key: hex::encode(signer.public_key()),
+            signature: hex::encode(sig),
+        })
+    }
+
+    /// `TELEMETRY:{node}:{timestamp}:{blake3(summary JSON)}`
+    pub fn signing_data(summary: &TelemetrySummary) -> String {
+        let json = serde_json::to_vec(summary).unwrap_or_default();
+        format!(
+            "TELEMETRY:{}:{}:{}",
+            summary.node,
+            summary.timestamp,
+            blake3::hash(&json).to_hex()
+        )
+    }
+
+    /// Check timestamp, key binding and signature
+    pub fn verify(&self, now: u64) -> Result<(), String> {
+        if now.abs_diff(self.summary.timestamp) > MAX_CLOCK_SKEW_SECS {
+            return Err("stale timestamp".to_string());
+        }
+        if self.summary.version.len() > 32 || self.summary.state_root.len() > 128 {
+            return Err("oversized field".to_string());
+        }
+        let pk = hex::decode(&self.public_key).map_err(|_| "invalid pk hex".to_string())?;
+        if los_crypto::public_key_to_address(&pk) != self.summary.node {
+            return Err("pk does not match node".to_string());
+        }
+        let sig = hex::decode(&self.signature).map_err(|_| "invalid sig hex".to_string())?;
+        let data = Self::signing_data(&self.summary);
+        if !los_crypto::verify_signature(data.as_bytes(), &sig, &pk) {
+            return Err("signature verification failed".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Latest verified report per node (aggregation mode)
+#[derive(Debug, Default)]
+pub struct TelemetryAggregator {
+    /// node → (summary, received_at)
+    reports: HashMap<String, (TelemetrySummary, u64)>,
+}
+
+impl TelemetryAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify and store a report
+    pub fn accept(&mut self, report: &TelemetryReport, now: u64) -> Result<(), String> {
+        let node = &report.summary.node;
+        if let Some((_, received)) = self.reports.get(node) {
+            if now.saturating_sub(*received) < MIN_REPORT_INTERVAL_SECS {
+                return Err("rate limited".to_string());
+            }
+        }
+        report.verify(now)?;
+        self.prune(now);
+        if self.reports.len() >= MAX_TELEMETRY_NODES && !self.reports.contains_key(node) {
+            // Evict the node heard from least recently
+            if let Some(oldest) = self
+                .reports
+                .iter()
+                .min_by_key(|(_, (_, received))| *received)
+                .map(|(n, _)| n.clone())
+            {
+                self.reports.remove(&oldest);
+            }
+        }
+        self.reports
+            .insert(node.clone(), (report.summary.clone(), now));
+        Ok(())
+    }
+
+    fn prune(&mut self, now: u64) {
+        self.reports
+            .retain(|_, (_, received)| now.saturating_sub(*received) <= TELEMETRY_STALE_SECS);
+    }
+
+    /// Dashboard for GET /telemetry/network
+    pub fn dashboard(
+        &mut self,
+        now: u64,
+        is_validator: impl Fn(&str) -> bool,
+    ) -> serde_json::Value {
+        self.prune(now);
+        let mut nodes: Vec<&(TelemetrySummary, u64)> = self.reports.values().collect();
+        nodes.sort_by(|a, b| {
+            b.0.height
+                .cmp(&a.0.height)
+                .then_with(|| a.0.node.cmp(&b.0.node))
+        });
+
+        let mut versions: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut roots: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut heights: Vec<u64> = Vec::new();
+        let mut p50s: Vec<u64> = Vec::new();
+        let mut p95_max = 0u64;
+        for (s, _) in &nodes {
+            *versions.entry(&s.version).or_default() += 1;
+            *roots.entry(&s.state_root).or_default() += 1;
+            heights.push(s.height);
+            if s.latency.samples > 0 {
+                p50s.push(s.latency.p50_ms);
+                p95_max = p95_max.max(s.latency.p95_ms);
+            }
+        }
+        heights.sort_unstable();
+        p50s.sort_unstable();
+        let median = |v: &[u64]| v.get(v.len() / 2).copied().unwrap_or(0);
+        let mut root_list: Vec<(&str, u64)> = roots.into_iter().collect();
+        root_list.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let agreeing = root_list.first().map(|(_, n)| *n).unwrap_or(0);
+
+        serde_json::json!({
+            "node_count": nodes.len(),
+            "validator_count": nodes.iter().filter(|(s, _)| is_validator(&s.node)).count(),
+            "max_height": heights.last().copied().unwrap_or(0),
+            "median_height": median(&heights),
+            "versions": versions,
+            "state_roots": root_list
+                .iter()
+                .map(|(root, n)| serde_json::json!({"state_root": root, "nodes": n}))
+                .collect::<Vec<_>>(),
+            "state_root_agreement_pct": (agreeing * 100).checked_div(nodes.len() as u64).unwrap_or(0),
+            "latency": {
+                "median_p50_ms": median(&p50s),
+                "max_p95_ms": p95_max,
+            },
+            "nodes": nodes
+                .iter()
+                .map(|(s, received)| {
+                    let mut json = serde_json::to_value(s).unwrap_or_default();
+                    json["is_validator"] = serde_json::json!(is_validator(&s.node));
+                    json["received_at"] = serde_json::json!(received);
+                    json
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+
+    const NOW: u64 = 1_771_000_000;
+
+    fn summary(node: &str, height: u64, root: &str) -> TelemetrySummary {
+        TelemetrySummary {
+            node: node.to_string(),
+            version: "2.2.1".to_string(),
+            network: "los-testnet".to_string(),
+            height,
+            peer_count: 4,
+            state_root: root.to_string(),
+            latency: LatencyStats::default(),
+            timestamp: NOW,
+        }
+    }
+
+    #[test]
+    fn test_latency_tracker_percentiles() {
+        let mut t = LatencyTracker::default();
+        assert_eq!(t.take_stats(), LatencyStats::default());
+        for ms in (1..=100).rev() {
+            t.record(ms);
+        }
+        let stats = t.take_stats();
+        assert_eq!(
+            (stats.samples, stats.p50_ms, stats.p95_ms, stats.max_ms),
+            (100, 50, 95, 100)
+        );
+        assert_eq!(t.take_stats().samples, 0); // window restarted
+    }
+
+    #[test]
+    fn test_signed_reports_aggregate_into_dashboard() {
+        let kp_a = los_crypto::generate_keypair();
+        let kp_b = los_crypto::generate_keypair();
+        let a = los_crypto::public_key_to_address(&kp_a.public_key);
+        let b = los_crypto::public_key_to_address(&kp_b.public_key);
+
+        let mut sa = summary(&a, 120, "root1");
+        sa.latency = LatencyStats {
+            samples: 3,
+            p50_ms: 80,
+            p95_ms: 300,
+            max_ms: 400,
+        };
+        let ra = TelemetryReport::signed(sa, &LocalSigner::new(kp_a.clone())).unwrap();
+        let rb =
+            TelemetryReport::signed(summary(&b, 100, "root2"), &LocalSigner::new(kp_b.clone()))
+                .unwrap();
+
+        // Round-trips through the gossip JSON and still verifies
+        let json = serde_json::to_string(&ra).unwrap();
+        assert!(crate::gossip_guard::check(&format!("{}{}", TELEMETRY_PREFIX, json)).is_ok());
+        let ra: TelemetryReport = serde_json::from_str(&json).unwrap();
+
+        let mut forged = rb.clone();
+        forged.summary.height = 999;
+        assert!(forged.verify(NOW).unwrap_err().contains("signature"));
+        assert!(ra.verify(NOW + MAX_CLOCK_SKEW_SECS + 1).is_err());
+
+        let mut agg = TelemetryAggregator::new();
+        agg.accept(&ra, NOW).unwrap();
+        agg.accept(&rb, NOW).unwrap();
+        assert_eq!(agg.accept(&ra, NOW + 1).unwrap_err(), "rate limited");
+        assert!(agg.accept(&forged, NOW + MIN_REPORT_INTERVAL_SECS).is_err());
+
+        let dash = agg.dashboard(NOW, |n| n == a);
+        assert_eq!(dash["node_count"], 2);
+        assert_eq!(dash["validator_count"], 1);
+        assert_eq!(dash["max_height"], 120);
+        assert_eq!(dash["versions"]["2.2.1"], 2);
+        assert_eq!(dash["state_root_agreement_pct"], 50);
+        assert_eq!(dash["latency"]["max_p95_ms"], 300);
+        assert_eq!(dash["nodes"][0]["node"], a.as_str());
+
+        // Silent nodes age out
+        assert_eq!(
+            agg.dashboard(NOW + TELEMETRY_STALE_SECS + 1, |_| false)["node_count"],
+            0
+        );
+    }
+}
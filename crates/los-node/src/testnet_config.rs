@@ -32,6 +32,8 @@ pub enum TestnetLevel {
 #[allow(dead_code)]
 pub struct TestnetConfig {
     pub level: TestnetLevel,
+    /// Dev-mode relay of testnet Mint blocks (faucet drips come from the
+    /// faucet contract in los-contracts)
     pub enable_faucet: bool,
     /// Consensus quorum threshold in basis points (6700 = 67%)
     pub consensus_threshold_bps: u32,
@@ -46,7 +48,7 @@ impl TestnetConfig {
     /// MUST never be called on mainnet builds.
     pub fn functional() -> Self {
         assert!(
-            !los_core::is_mainnet_build(),
+            !los_core::is_mainnet(),
             "BUG: functional() testnet config used on mainnet build — signature validation disabled!"
         );
         Self {
@@ -96,10 +98,6 @@ impl TestnetConfig {
         self.signature_validation
     }
 
-    pub fn should_enable_faucet(&self) -> bool {
-        self.enable_faucet
-    }
-
     #[allow(dead_code)]
     pub fn should_test_byzantine_behavior(&self) -> bool {
         self.byzantine_testing
@@ -111,6 +109,24 @@ impl TestnetConfig {
     }
 }
 
+/// Set by `los-node --dev` before the config is first read.
+static DEV_MODE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Enable local dev mode (single-node chain, Level 1 config).
+/// MUST be called before the first `get_testnet_config()` — the config is read once.
+pub fn enable_dev_mode() {
+    assert!(
+        !los_core::is_mainnet(),
+        "BUG: dev mode requested on mainnet build"
+    );
+    DEV_MODE.store(true, std::sync::atomic::Ordering::Release);
+}
+
+/// Whether the node was started with `--dev` (always false on mainnet builds)
+pub fn is_dev_mode() -> bool {
+    !los_core::is_mainnet() && DEV_MODE.load(std::sync::atomic::Ordering::Acquire)
+}
+
 /// Global testnet configuration
 ///
 /// MAINNET BUILD: Always returns Production config regardless of environment variables.
@@ -118,18 +134,23 @@ impl TestnetConfig {
 /// so forcing Production level here eliminates ALL testnet bypasses at once:
 ///   - should_enable_consensus() → true (no immediate finalization)
 ///   - should_validate_signatures() → true (no unsigned blocks)
-///   - should_enable_faucet() → false (no free tokens)
 ///   - Mint cap → enforced (no TESTNET: prefix bypass)
 ///
-/// TESTNET BUILD: Reads LOS_TESTNET_LEVEL env var, defaults to Consensus (Level 2).
+/// TESTNET BUILD: `--dev` forces Functional (Level 1). Otherwise reads
+/// LOS_TESTNET_LEVEL env var, defaults to Consensus (Level 2).
 static TESTNET_CONFIG: std::sync::LazyLock<TestnetConfig> = std::sync::LazyLock::new(|| {
     // MAINNET: Hardcoded to Production. No env var can weaken this.
-    if los_core::is_mainnet_build() {
+    if los_core::is_mainnet() {
         println!("🔒 MAINNET BUILD: All security enforced (consensus, signatures, mint cap)");
         println!("   Faucet: DISABLED | Consensus: ENABLED | Signatures: REQUIRED");
         return TestnetConfig::production_simulation();
     }
 
+    if is_dev_mode() {
+        println!("🧪 DEV MODE: Level 1 single-node chain (instant finalization)");
+        return TestnetConfig::functional();
+    }
+
     // TESTNET: Allow level selection via environment variable
     match std::env::var("LOS_TESTNET_LEVEL").as_deref() {
         Ok("functional") => {
@@ -170,7 +191,7 @@ pub fn get_testnet_config() -> &'static TestnetConfig {
 /// On testnet builds, returns `false` only when `LOS_NETWORK=mainnet` is set.
 #[allow(dead_code)]
 pub fn is_testnet() -> bool {
-    if los_core::is_mainnet_build() {
+    if los_core::is_mainnet() {
         return false; // Compile-time: mainnet build can never be testnet
     }
     match std::env::var("LOS_NETWORK").as_deref() {
@@ -0,0 +1,507 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - VALIDATOR DIRECTORY
+//
+// One view of every validator for GET /validators and gRPC GetValidators:
+// stake, uptime, last heartbeat, probation, host endpoint and rewards earned,
+// joined from the ledger, reward pool, slashing manager, endpoint registry
+// and liveness map. Both APIs share the listing, sorting and paging below so
+// they never disagree on who is a validator.
+//
+// Listed: genesis bootstrap validators, validators known to the slashing
+// manager (minus unstaking/banned) and ledger accounts flagged is_validator.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::slashing::{SlashingManager, ValidatorStatus};
+use los_core::validator_rewards::ValidatorRewardPool;
+use los_core::{Ledger, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Page size when the request does not set one
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+/// Largest page served
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// One validator as served by the APIs
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ValidatorRecord {
+    pub address: String,
+    pub stake_cil: u128,
+    /// Stake delegated by other accounts. None until delegation exists.
+    pub delegated_stake_cil: Option<u128>,
+    pub active: bool,
+    pub connected: bool,
+    pub is_genesis: bool,
+    pub has_min_stake: bool,
+    pub slashed: bool,
+    pub uptime_pct: u64,
+    /// Unix time this node last saw the validator alive (None = not recently)
+    pub last_heartbeat: Option<u64>,
+    pub in_probation: bool,
+    pub probation_epochs_left: u64,
+    pub host_address: Option<String>,
+    pub rewards_earned_cil: u128,
+}
+
+impl ValidatorRecord {
+    /// REST form. Keeps the pre-pagination keys (`stake` in LOS,
+    /// `is_active`, `uptime_percentage`, `onion_address`) for old clients.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut entry = serde_json::json!({
+            "address": self.address,
+            "stake": self.stake_cil / CIL_PER_LOS,
+            "stake_cil": self.stake_cil,
+            "delegated_stake_cil": self.delegated_stake_cil,
+            "is_active": self.active,
+            "active": self.active,
+            "connected": self.connected,
+            "is_genesis": self.is_genesis,
+            "has_min_stake": self.has_min_stake,
+            "slashed": self.slashed,
+            "uptime_percentage": self.uptime_pct,
+            "last_heartbeat": self.last_heartbeat,
+            "in_probation": self.in_probation,
+            "probation_epochs_left": self.probation_epochs_left,
+            "rewards_earned_cil": self.rewards_earned_cil,
+        });
+        if let Some(h) = &self.host_address {
+            entry["host_address"] = serde_json::json!(h);
+            entry["onion_address"] = serde_json::json!(h); // backward compat
+        }
+        entry
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorSort {
+    Stake,
+    Uptime,
+    Rewards,
+    LastHeartbeat,
+    Address,
+}
+
+impl ValidatorSort {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "stake" => Some(Self::Stake),
+            "uptime" => Some(Self::Uptime),
+            "rewards" => Some(Self::Rewards),
+            "last_heartbeat" => Some(Self::LastHeartbeat),
+            "address" => Some(Self::Address),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stake => "stake",
+            Self::Uptime => "uptime",
+            Self::Rewards => "rewards",
+            Self::LastHeartbeat => "last_heartbeat",
+            Self::Address => "address",
+        }
+    }
+}
+
+/// Sorting, filtering and paging of the listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorQuery {
+    pub sort: ValidatorSort,
+    pub descending: bool,
+    pub offset: usize,
+    pub limit: usize,
+    pub active_only: bool,
+}
+
+impl Default for ValidatorQuery {
+    fn default() -> Self {
+        Self {
+            sort: ValidatorSort::Stake,
+            descending: true,
+            offset: 0,
+            limit: DEFAULT_PAGE_LIMIT,
+            active_only: false,
+        }
+    }
+}
+
+impl ValidatorQuery {
+    /// Build from raw request fields. Empty strings and a zero limit fall
+    /// back to defaults; address sorts ascending unless `order` says otherwise.
+    pub fn parse(
+        sort: Option<&str>,
+        order: Option<&str>,
+        offset: Option<&str>,
+        limit: Option<&str>,
+        active_only: bool,
+    ) -> Result<Self, String> {
+        fn non_empty(v: Option<&str>) -> Option<&str> {
+            v.map(str::trim).filter(|v| !v.is_empty())
+        }
+        let sort = match non_empty(sort) {
+            None => ValidatorSort::Stake,
+            Some(s) => ValidatorSort::parse(s).ok_or_else(|| {
+                "sort must be one of: stake, uptime, rewards, last_heartbeat, address".to_string()
+            })?,
+        };
+        let descending = match non_empty(order) {
+            None => sort != ValidatorSort::Address,
+            Some("desc") => true,
+            Some("asc") => false,
+            Some(_) => return Err("order must be asc or desc".to_string()),
+        };
+        let number = |v: Option<&str>, name: &str| -> Result<Option<usize>, String> {
+            non_empty(v)
+                .map(|v| {
+                    v.parse::<usize>()
+                        .map_err(|_| format!("{} must be a non-negative integer", name))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            sort,
+            descending,
+            offset: number(offset, "offset")?.unwrap_or(0),
+            limit: number(limit, "limit")?
+                .filter(|l| *l > 0)
+                .unwrap_or(DEFAULT_PAGE_LIMIT)
+                .min(MAX_PAGE_LIMIT),
+            active_only,
+        })
+    }
+
+    pub fn from_params(params: &HashMap<String, String>) -> Result<Self, String> {
+        let get = |k: &str| params.get(k).map(String::as_str);
+        Self::parse(
+            get("sort"),
+            get("order"),
+            get("offset"),
+            get("limit"),
+            matches!(get("active"), Some("true" | "1")),
+        )
+    }
+
+    /// Sort, filter and cut one page. Returns (page, total matching).
+    pub fn apply(&self, mut records: Vec<ValidatorRecord>) -> (Vec<ValidatorRecord>, usize) {
+        if self.active_only {
+            records.retain(|r| r.active);
+        }
+        records.sort_by(|a, b| {
+            let primary = match self.sort {
+                ValidatorSort::Stake => a.stake_cil.cmp(&b.stake_cil),
+                ValidatorSort::Uptime => a.uptime_pct.cmp(&b.uptime_pct),
+                ValidatorSort::Rewards => a.rewards_earned_cil.cmp(&b.rewards_earned_cil),
+                ValidatorSort::LastHeartbeat => a.last_heartbeat.cmp(&b.last_heartbeat),
+                ValidatorSort::Address => std::cmp::Ordering::Equal,
+            };
+            let primary = if self.descending {
+                primary.reverse()
+            } else {
+                primary
+            };
+            // Address breaks ties so pages are stable between requests
+            let by_address = a.address.cmp(&b.address);
+            primary.then(if self.sort == ValidatorSort::Address && self.descending {
+                by_address.reverse()
+            } else {
+                by_address
+            })
+        });
+        let total = records.len();
+        let page = records
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit)
+            .collect();
+        (page, total)
+    }
+}
+
+/// Shared handles the listing is assembled from
+pub struct ValidatorDirectory {
+    ledger: Arc<Mutex<Ledger>>,
+    reward_pool: Arc<Mutex<ValidatorRewardPool>>,
+    address_book: Arc<Mutex<HashMap<String, String>>>,
+    bootstrap_validators: Vec<String>,
+    my_address: String,
+    slashing_manager: Option<Arc<Mutex<SlashingManager>>>,
+    validator_endpoints: Option<Arc<Mutex<HashMap<String, String>>>>,
+    live_peers: Option<Arc<Mutex<HashMap<String, u64>>>>,
+}
+
+impl ValidatorDirectory {
+    pub fn new(
+        ledger: Arc<Mutex<Ledger>>,
+        reward_pool: Arc<Mutex<ValidatorRewardPool>>,
+        address_book: Arc<Mutex<HashMap<String, String>>>,
+        bootstrap_validators: Vec<String>,
+        my_address: String,
+    ) -> Self {
+        Self {
+            ledger,
+            reward_pool,
+            address_book,
+            bootstrap_validators,
+            my_address,
+            slashing_manager: None,
+            validator_endpoints: None,
+            live_peers: None,
+        }
+    }
+
+    pub fn with_slashing(mut self, sm: Arc<Mutex<SlashingManager>>) -> Self {
+        self.slashing_manager = Some(sm);
+        self
+    }
+
+    pub fn with_endpoints(mut self, endpoints: Arc<Mutex<HashMap<String, String>>>) -> Self {
+        self.validator_endpoints = Some(endpoints);
+        self
+    }
+
+    pub fn with_live_peers(mut self, live_peers: Arc<Mutex<HashMap<String, u64>>>) -> Self {
+        self.live_peers = Some(live_peers);
+        self
+    }
+
+    /// Every listed validator, unsorted. Lock order: ledger → address book →
+    /// slashing → reward pool (same as the block processing paths).
+    pub fn snapshot(&self, now: u64) -> Vec<ValidatorRecord> {
+        let l = lock(&self.ledger);
+        let ab = lock(&self.address_book);
+
+        let mut addrs: Vec<String> = self.bootstrap_validators.clone();
+        let mut slashed = Vec::new();
+        if let Some(sm) = &self.slashing_manager {
+            let sm = lock(sm);
+            for addr in sm.get_all_validator_addresses() {
+                match sm.get_status(&addr) {
+                    Some(ValidatorStatus::Unstaking | ValidatorStatus::Banned) => continue,
+                    Some(ValidatorStatus::Slashed) => slashed.push(addr.clone()),
+                    _ => {}
+                }
+                if !addrs.contains(&addr) {
+                    addrs.push(addr);
+                }
+            }
+        }
+        for (addr, acc) in &l.accounts {
+            if acc.is_validator && !addrs.contains(addr) {
+                addrs.push(addr.clone());
+            }
+        }
+
+        let pool = lock(&self.reward_pool);
+        let endpoints = self
+            .validator_endpoints
+            .as_ref()
+            .map(|ve| lock(ve).clone())
+            .unwrap_or_default();
+        let live = self
+            .live_peers
+            .as_ref()
+            .map(|lp| lock(lp).clone())
+            .unwrap_or_default();
+
+        addrs
+            .iter()
+            .filter_map(|addr| {
+                let acc = l.accounts.get(addr)?;
+                let is_genesis = self.bootstrap_validators.contains(addr);
+                // Unstaked accounts drop out; bootstrap validators always show
+                if !acc.is_validator && !is_genesis {
+                    return None;
+                }
+                let is_self = *addr == self.my_address;
+                let in_peers = ab.values().any(|v| v.contains(addr.as_str()));
+                let host_address = endpoints.get(addr).cloned();
+                let connected = is_self || in_peers || host_address.is_some();
+                let reward_state = pool.validators.get(addr);
+                let has_min_stake = acc.balance >= MIN_VALIDATOR_STAKE_CIL;
+                // ACTIVE: staked validator with evidence of liveness or legitimacy
+                // (connected, bootstrap node, or verified reward pool registration)
+                let active = has_min_stake
+                    && acc.is_validator
+                    && (connected || is_genesis || reward_state.is_some());
+                let probation_epochs_left = reward_state
+                    .map(|vs| vs.probation_epochs_left(pool.current_epoch))
+                    .unwrap_or(0);
+                Some(ValidatorRecord {
+                    address: addr.clone(),
+                    stake_cil: acc.balance,
+                    delegated_stake_cil: None,
+                    active,
+                    connected,
+                    is_genesis,
+                    has_min_stake,
+                    slashed: slashed.contains(addr),
+                    // Integer uptime: max(current epoch, last epoch)
+                    uptime_pct: reward_state
+                        .map(|vs| vs.display_uptime_pct())
+                        .unwrap_or(if is_self { 100 } else { 0 }),
+                    last_heartbeat: if is_self {
+                        Some(now)
+                    } else {
+                        live.get(addr).copied()
+                    },
+                    in_probation: probation_epochs_left > 0,
+                    probation_epochs_left,
+                    host_address,
+                    rewards_earned_cil: reward_state
+                        .map(|vs| vs.cumulative_rewards_cil)
+                        .unwrap_or(0),
+                })
+            })
+            .collect()
+    }
+
+    /// One page of the listing. Returns (page, total matching).
+    pub fn page(&self, query: &ValidatorQuery, now: u64) -> (Vec<ValidatorRecord>, usize) {
+        query.apply(self.snapshot(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_core::AccountState;
+
+    fn record(address: &str, stake: u128, uptime: u64, hb: Option<u64>) -> ValidatorRecord {
+        ValidatorRecord {
+            address: address.to_string(),
+            stake_cil: stake,
+            delegated_stake_cil: None,
+            active: stake >= MIN_VALIDATOR_STAKE_CIL,
+            connected: true,
+            is_genesis: false,
+            has_min_stake: stake >= MIN_VALIDATOR_STAKE_CIL,
+            slashed: false,
+            uptime_pct: uptime,
+            last_heartbeat: hb,
+            in_probation: false,
+            probation_epochs_left: 0,
+            host_address: None,
+            rewards_earned_cil: 0,
+        }
+    }
+
+    #[test]
+    fn test_query_parsing() {
+        assert_eq!(
+            ValidatorQuery::parse(None, None, None, None, false).unwrap(),
+            ValidatorQuery::default()
+        );
+        let q =
+            ValidatorQuery::parse(Some("address"), None, Some("20"), Some("9999"), true).unwrap();
+        assert_eq!(q.sort, ValidatorSort::Address);
+        assert!(!q.descending);
+        assert_eq!((q.offset, q.limit), (20, MAX_PAGE_LIMIT));
+        assert!(q.active_only);
+        // gRPC sends empty strings and 0 for unset fields
+        let q = ValidatorQuery::parse(Some(""), Some(""), Some("0"), Some("0"), false).unwrap();
+        assert_eq!(q, ValidatorQuery::default());
+        assert!(ValidatorQuery::parse(Some("votes"), None, None, None, false).is_err());
+        assert!(ValidatorQuery::parse(None, Some("up"), None, None, false).is_err());
+        assert!(ValidatorQuery::parse(None, None, Some("-1"), None, false).is_err());
+    }
+
+    #[test]
+    fn test_sort_and_paginate() {
+        let min = MIN_VALIDATOR_STAKE_CIL;
+        let records = vec![
+            record("LOSc", 3 * min, 90, Some(30)),
+            record("LOSa", 3 * min, 100, None),
+            record("LOSb", 5 * min, 50, Some(10)),
+            record("LOSd", min / 2, 100, Some(40)),
+        ];
+
+        // Default: stake desc, ties by address
+        let (page, total) = ValidatorQuery::default().apply(records.clone());
+        let order: Vec<&str> = page.iter().map(|r| r.address.as_str()).collect();
+        assert_eq!(order, ["LOSb", "LOSa", "LOSc", "LOSd"]);
+        assert_eq!(total, 4);
+
+        let q = ValidatorQuery::parse(Some("uptime"), Some("asc"), Some("1"), Some("2"), false)
+            .unwrap();
+        let (page, total) = q.apply(records.clone());
+        let order: Vec<&str> = page.iter().map(|r| r.address.as_str()).collect();
+        assert_eq!(order, ["LOSc", "LOSa"]);
+        assert_eq!(total, 4);
+
+        let q = ValidatorQuery::parse(Some("last_heartbeat"), None, None, None, true).unwrap();
+        let (page, total) = q.apply(records.clone());
+        let order: Vec<&str> = page.iter().map(|r| r.address.as_str()).collect();
+        assert_eq!(order, ["LOSc", "LOSb", "LOSa"]);
+        assert_eq!(total, 3);
+
+        // Offset past the end: empty page, total still reported
+        let q = ValidatorQuery::parse(None, None, Some("10"), None, false).unwrap();
+        assert_eq!(q.apply(records).0.len(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_joins_sources() {
+        let mut ledger = Ledger::new();
+        for (addr, stake, is_validator) in [
+            ("LOSgenesis", 2 * MIN_VALIDATOR_STAKE_CIL, true),
+            ("LOSnew", MIN_VALIDATOR_STAKE_CIL, true),
+            ("LOSuser", MIN_VALIDATOR_STAKE_CIL, false),
+        ] {
+            ledger.accounts.insert(
+                addr.to_string(),
+                AccountState {
+                    head: "0".to_string(),
+                    balance: stake,
+                    block_count: 1,
+                    is_validator,
+                },
+            );
+        }
+        let mut pool = ValidatorRewardPool::new(0);
+        pool.register_validator("LOSnew", false, MIN_VALIDATOR_STAKE_CIL);
+        pool.sync_reward_from_gossip("LOSnew", 42);
+        let endpoints: HashMap<String, String> =
+            [("LOSnew".to_string(), "abc.onion".to_string())].into();
+        let live: HashMap<String, u64> = [("LOSnew".to_string(), 1_000)].into();
+
+        let dir = ValidatorDirectory::new(
+            Arc::new(Mutex::new(ledger)),
+            Arc::new(Mutex::new(pool)),
+            Arc::new(Mutex::new(HashMap::new())),
+            vec!["LOSgenesis".to_string()],
+            "LOSgenesis".to_string(),
+        )
+        .with_endpoints(Arc::new(Mutex::new(endpoints)))
+        .with_live_peers(Arc::new(Mutex::new(live)));
+
+        let (page, total) = dir.page(&ValidatorQuery::default(), 2_000);
+        assert_eq!(total, 2, "non-validator accounts are not listed");
+        let genesis = &page[0];
+        assert_eq!(genesis.address, "LOSgenesis");
+        assert!(genesis.active && genesis.is_genesis);
+        assert_eq!(genesis.last_heartbeat, Some(2_000)); // self
+        assert_eq!(genesis.uptime_pct, 100);
+
+        let new = &page[1];
+        assert!(new.in_probation);
+        assert_eq!(new.probation_epochs_left, 1);
+        assert_eq!(new.rewards_earned_cil, 42);
+        assert_eq!(new.last_heartbeat, Some(1_000));
+        assert_eq!(new.host_address.as_deref(), Some("abc.onion"));
+        assert_eq!(new.delegated_stake_cil, None);
+
+        let json = new.to_json();
+        assert_eq!(
+            json["stake"],
+            (MIN_VALIDATOR_STAKE_CIL / CIL_PER_LOS) as u64
+        );
+        assert_eq!(json["onion_address"], "abc.onion");
+        assert_eq!(json["is_active"], true);
+    }
+}
@@ -0,0 +1,131 @@
+//! Shims that let common `no_std` crates build and run inside UVM contracts
+//! (enabled with the `compat` feature).
+//!
+//! ## Randomness (`getrandom`)
+//! `getrandom` 0.2 refuses to compile for `wasm32-unknown-unknown` unless a
+//! backend is registered, which breaks `hashbrown`, `uuid`, `rand_core` and
+//! anything else that pulls it in. With `compat` the SDK registers a backend
+//! that expands [`crypto::random_seed`](crate::crypto::random_seed) with
+//! blake3. The bytes are DETERMINISTIC (identical on every validator) and
+//! predictable by the caller — good for hash seeds and IDs, never for
+//! lotteries. If a dependency uses getrandom, enable its `custom` feature in
+//! your contract's `Cargo.toml`:
+//!
+//! ```toml
+//! [dependencies]
+//! los-sdk = { path = "../los-sdk", features = ["compat"] }
+//! getrandom = { version = "0.2", features = ["custom"] }
+//! ```
+//!
+//! ## Time
+//! There is no wall clock in the UVM. [`now`] and [`Instant`] read the block
+//! timestamp (`host_get_timestamp`) instead: constant within one execution,
+//! and [`Instant::duration_since`] saturates at zero, so code written against
+//! `std::time::Instant` keeps working without ever seeing negative time.
+//!
+//! ## JSON (`serde-json-core`)
+//! `serde_json` needs `std`; `serde-json-core` is `no_std`, allocation-free
+//! and works unchanged:
+//!
+//! ```rust,ignore
+//! // Cargo.toml: serde = { version = "1", default-features = false, features = ["derive"] }
+//! //             serde-json-core = "0.6"
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Order<'a> { side: &'a str, amount: u64 }
+//!
+//! let raw = arg(0).unwrap_or_default();
+//! let (order, _): (Order, usize) = serde_json_core::from_str(&raw)
+//!     .map_err(|_| ContractError::InvalidArgument)?;
+//! let mut buf = [0u8; 256];
+//! let n = serde_json_core::to_slice(&order, &mut buf)
+//!     .map_err(|_| ContractError::Overflow)?;
+//! set_return(&buf[..n]);
+//! ```
+
+use core::time::Duration;
+
+/// Fill `dest` with deterministic pseudo-random bytes:
+/// `blake3(seed ‖ block_index)` for each 32-byte block, using a fresh host
+/// seed per call.
+pub fn fill_deterministic(dest: &mut [u8]) {
+    let seed = crate::crypto::random_seed();
+    let mut input = [0u8; 40];
+    input[..32].copy_from_slice(&seed);
+    for (i, chunk) in dest.chunks_mut(32).enumerate() {
+        input[32..].copy_from_slice(&(i as u64).to_le_bytes());
+        let block = crate::crypto::blake3(&input);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn getrandom_backend(dest: &mut [u8]) -> Result<(), getrandom::Error> {
+    fill_deterministic(dest);
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+getrandom::register_custom_getrandom!(getrandom_backend);
+
+/// Block timestamp as time since the UNIX epoch (stand-in for
+/// `SystemTime::now().duration_since(UNIX_EPOCH)`).
+pub fn now() -> Duration {
+    Duration::from_secs(crate::timestamp())
+}
+
+/// Second-resolution instant backed by the block timestamp.
+/// Store it with `state::set_u64(key, t.as_secs())`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(u64);
+
+impl Instant {
+    /// Current block time
+    pub fn now() -> Self {
+        Instant(crate::timestamp())
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Instant(secs)
+    }
+
+    pub const fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// Time from `earlier` to `self`; zero if `earlier` is later.
+    pub fn duration_since(self, earlier: Instant) -> Duration {
+        Duration::from_secs(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time from `self` to the current block
+    pub fn elapsed(self) -> Duration {
+        Instant::now().duration_since(self)
+    }
+
+    pub fn checked_add(self, d: Duration) -> Option<Instant> {
+        self.0.checked_add(d.as_secs()).map(Instant)
+    }
+
+    pub fn checked_sub(self, d: Duration) -> Option<Instant> {
+        self.0.checked_sub(d.as_secs()).map(Instant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instant_saturates_and_adds() {
+        let a = Instant::from_secs(100);
+        let b = Instant::from_secs(160);
+        assert_eq!(b.duration_since(a), Duration::from_secs(60));
+        assert_eq!(a.duration_since(b), Duration::ZERO);
+        assert_eq!(a.checked_add(Duration::from_secs(60)), Some(b));
+        assert_eq!(a.checked_sub(Duration::from_secs(101)), None);
+        assert_eq!(
+            Instant::from_secs(u64::MAX).checked_add(Duration::from_secs(1)),
+            None
+        );
+    }
+}
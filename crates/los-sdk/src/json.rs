@@ -0,0 +1,396 @@
+//! Minimal `no_std` JSON writer for return data and event payloads.
+//!
+//! - [`Object`] / [`Array`] — builders with typed push methods that always
+//!   produce well-formed JSON (keys and strings are escaped)
+//! - [`ToJson`] — implemented for strings, integers, `bool`, `Option`, slices
+//!   and `Vec`; implement it (or use [`impl_to_json!`](crate::impl_to_json))
+//!   for contract structs
+//! - [`escape`] — RFC 8259 string escaping, including control characters
+//!
+//! `u128` / `i128` are written as quoted decimal strings: JSON numbers lose
+//! precision above 2^53 in most clients, and CIL amounts routinely exceed it.
+//!
+//! ```rust,ignore
+//! use los_sdk::json::Object;
+//!
+//! let out = Object::new()
+//!     .str("owner", &caller())
+//!     .u128("supply", 1_000_000)
+//!     .bool("paused", false)
+//!     .finish();
+//! set_return_str(&out); // {"owner":"LOS...","supply":"1000000","paused":false}
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Output buffer type (lets [`impl_to_json!`](crate::impl_to_json) avoid naming `alloc`).
+pub type JsonBuf = String;
+
+/// Append `s` to `out` with JSON string escaping (no surrounding quotes).
+pub fn escape_into(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
+/// JSON-escape `s` (no surrounding quotes).
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    escape_into(&mut out, s);
+    out
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    escape_into(out, s);
+    out.push('"');
+}
+
+// ─────────────────────────────────────────────────────────────────
+// ToJson
+// ─────────────────────────────────────────────────────────────────
+
+/// A value that can serialize itself as JSON.
+pub trait ToJson {
+    /// Append this value's JSON to `out`.
+    fn write_json(&self, out: &mut String);
+
+    /// This value as a JSON string.
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+}
+
+impl ToJson for str {
+    fn write_json(&self, out: &mut String) {
+        write_str(out, self);
+    }
+}
+
+impl ToJson for String {
+    fn write_json(&self, out: &mut String) {
+        write_str(out, self);
+    }
+}
+
+impl ToJson for bool {
+    fn write_json(&self, out: &mut String) {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+macro_rules! number_to_json {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t {
+            fn write_json(&self, out: &mut String) {
+                let _ = write!(out, "{}", self);
+            }
+        }
+    )*};
+}
+number_to_json!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl ToJson for u128 {
+    fn write_json(&self, out: &mut String) {
+        let _ = write!(out, "\"{}\"", self);
+    }
+}
+
+impl ToJson for i128 {
+    fn write_json(&self, out: &mut String) {
+        let _ = write!(out, "\"{}\"", self);
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn write_json(&self, out: &mut String) {
+        (**self).write_json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Some(v) => v.write_json(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn write_json(&self, out: &mut String) {
+        out.push('[');
+        for (i, v) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            v.write_json(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn write_json(&self, out: &mut String) {
+        self.as_slice().write_json(out);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Builders
+// ─────────────────────────────────────────────────────────────────
+
+/// JSON object builder. Keys are written in insertion order.
+#[derive(Debug, Clone)]
+pub struct Object {
+    buf: String,
+    empty: bool,
+}
+
+impl Default for Object {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Object {
+            buf: String::from("{"),
+            empty: true,
+        }
+    }
+
+    fn key(&mut self, key: &str) {
+        if !self.empty {
+            self.buf.push(',');
+        }
+        self.empty = false;
+        write_str(&mut self.buf, key);
+        self.buf.push(':');
+    }
+
+    /// Any [`ToJson`] value.
+    pub fn value<T: ToJson + ?Sized>(mut self, key: &str, v: &T) -> Self {
+        self.key(key);
+        v.write_json(&mut self.buf);
+        self
+    }
+
+    pub fn str(self, key: &str, v: &str) -> Self {
+        self.value(key, v)
+    }
+
+    pub fn u64(self, key: &str, v: u64) -> Self {
+        self.value(key, &v)
+    }
+
+    pub fn i64(self, key: &str, v: i64) -> Self {
+        self.value(key, &v)
+    }
+
+    /// Written as a quoted decimal string.
+    pub fn u128(self, key: &str, v: u128) -> Self {
+        self.value(key, &v)
+    }
+
+    /// Written as a quoted decimal string.
+    pub fn i128(self, key: &str, v: i128) -> Self {
+        self.value(key, &v)
+    }
+
+    pub fn bool(self, key: &str, v: bool) -> Self {
+        self.value(key, &v)
+    }
+
+    pub fn null(mut self, key: &str) -> Self {
+        self.key(key);
+        self.buf.push_str("null");
+        self
+    }
+
+    /// Nested object.
+    pub fn object(mut self, key: &str, v: Object) -> Self {
+        self.key(key);
+        self.buf.push_str(&v.finish());
+        self
+    }
+
+    /// Nested array.
+    pub fn array(mut self, key: &str, v: Array) -> Self {
+        self.key(key);
+        self.buf.push_str(&v.finish());
+        self
+    }
+
+    /// Pre-serialized JSON, inserted verbatim. The caller guarantees it is valid.
+    pub fn raw(mut self, key: &str, json: &str) -> Self {
+        self.key(key);
+        self.buf.push_str(json);
+        self
+    }
+
+    /// Close the object and return the JSON text.
+    pub fn finish(mut self) -> String {
+        self.buf.push('}');
+        self.buf
+    }
+}
+
+/// JSON array builder.
+#[derive(Debug, Clone)]
+pub struct Array {
+    buf: String,
+    empty: bool,
+}
+
+impl Default for Array {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Array {
+    pub fn new() -> Self {
+        Array {
+            buf: String::from("["),
+            empty: true,
+        }
+    }
+
+    fn sep(&mut self) {
+        if !self.empty {
+            self.buf.push(',');
+        }
+        self.empty = false;
+    }
+
+    /// Any [`ToJson`] value.
+    pub fn push<T: ToJson + ?Sized>(mut self, v: &T) -> Self {
+        self.sep();
+        v.write_json(&mut self.buf);
+        self
+    }
+
+    pub fn object(mut self, v: Object) -> Self {
+        self.sep();
+        self.buf.push_str(&v.finish());
+        self
+    }
+
+    /// Close the array and return the JSON text.
+    pub fn finish(mut self) -> String {
+        self.buf.push(']');
+        self.buf
+    }
+}
+
+/// Implement [`ToJson`] for a struct by listing the fields to serialize.
+/// Every listed field must itself implement `ToJson`; field names become keys.
+///
+/// ```rust,ignore
+/// struct Listing { seller: String, price: u128, active: bool }
+/// los_sdk::impl_to_json!(Listing { seller, price, active });
+///
+/// set_return_json(&listing); // {"seller":"LOS...","price":"500","active":true}
+/// ```
+#[macro_export]
+macro_rules! impl_to_json {
+    ($ty:ty { $($field:ident),* $(,)? }) => {
+        impl $crate::json::ToJson for $ty {
+            fn write_json(&self, out: &mut $crate::json::JsonBuf) {
+                let obj = $crate::json::Object::new()
+                    $(.value(stringify!($field), &self.$field))*;
+                out.push_str(&obj.finish());
+            }
+        }
+    };
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_escape_quotes_backslashes_and_control_chars() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a\"b\\c"), "a\\\"b\\\\c");
+        assert_eq!(escape("l1\nl2\r\t"), "l1\\nl2\\r\\t");
+        assert_eq!(escape("\u{0}\u{1f}"), "\\u0000\\u001f");
+        // Non-ASCII passes through unchanged
+        assert_eq!(escape("ünï"), "ünï");
+    }
+
+    #[test]
+    fn test_object_and_array_builders() {
+        let inner = Object::new().u64("n", 1);
+        let list = Array::new().push("x").push(&2u32).object(Object::new());
+        let json = Object::new()
+            .str("name", "a\"b")
+            .u128("amount", u128::MAX)
+            .i64("delta", -5)
+            .bool("ok", true)
+            .null("none")
+            .object("inner", inner)
+            .array("list", list)
+            .raw("pre", "[1,2]")
+            .finish();
+        assert_eq!(
+            json,
+            "{\"name\":\"a\\\"b\",\"amount\":\"340282366920938463463374607431768211455\",\
+             \"delta\":-5,\"ok\":true,\"none\":null,\"inner\":{\"n\":1},\
+             \"list\":[\"x\",2,{}],\"pre\":[1,2]}"
+        );
+        assert_eq!(Object::new().finish(), "{}");
+        assert_eq!(Array::new().finish(), "[]");
+    }
+
+    struct Listing {
+        seller: String,
+        price: u128,
+        tags: Vec<String>,
+        expires: Option<u64>,
+    }
+    impl_to_json!(Listing {
+        seller,
+        price,
+        tags,
+        expires,
+    });
+
+    #[test]
+    fn test_impl_to_json_macro() {
+        let l = Listing {
+            seller: String::from("LOSabc"),
+            price: 500,
+            tags: vec![String::from("nft")],
+            expires: None,
+        };
+        assert_eq!(
+            l.to_json(),
+            "{\"seller\":\"LOSabc\",\"price\":\"500\",\"tags\":[\"nft\"],\"expires\":null}"
+        );
+        assert_eq!(vec![Some(-1i128), None].to_json(), "[\"-1\",null]");
+    }
+}
@@ -7,10 +7,21 @@
 //! ## Features
 //! - `#![no_std]` — compiles to `wasm32-unknown-unknown` without libstd
 //! - Key-value state storage via [`state::set`] / [`state::get`]
-//! - Structured event emission via [`event::emit`]
+//! - Per-call read cache, buffered writes under [`entry`], size-hinted reads via [`state::get_with_hint`]
+//! - Structured event emission via [`event::emit`], indexed topics via [`event::emit_indexed`]
 //! - Native CIL transfers via [`transfer`]
+//! - CIL attached to the call via [`msg_value`]
+//! - Originating block and per-invocation idempotency key via [`block_hash`] / [`call_id`]
 //! - Caller/contract context via [`caller`], [`self_address`], [`balance`]
-//! - Blake3 hashing via [`crypto::blake3`]
+//! - Existence and code hash of other contracts via [`contract::exists`] / [`contract::code_hash`]
+//! - Blake3 hashing via [`crypto::blake3`], deterministic seeds via [`crypto::random_seed`]
+//! - Typed status codes via [`ContractError`] and the [`entry`] adapter
+//! - Well-formed JSON return data and events via [`json::Object`] and [`impl_to_json!`]
+//! - Overflow-safe `mul_div`, 64.64 fixed point and bps helpers in [`math`]
+//! - Storage schema migrations via [`migrate::run_migrations`]
+//! - Name / version / build hash introspection via [`contract_info!`]
+//! - `compat` feature: getrandom backend + time shim for third-party crates
+//! - Host ABI version embedded as a `los_abi` custom section ([`HOST_ABI_VERSION`])
 //! - Custom global allocator for WASM heap
 //!
 //! ## Quick Start
@@ -167,6 +178,14 @@ extern "C" {
     fn host_del_state(key_ptr: *const u8, key_len: u32);
 
     fn host_emit_event(type_ptr: *const u8, type_len: u32, data_ptr: *const u8, data_len: u32);
+    fn host_emit_event_indexed(
+        type_ptr: *const u8,
+        type_len: u32,
+        topics_ptr: *const u8,
+        topics_len: u32,
+        data_ptr: *const u8,
+        data_len: u32,
+    ) -> i32;
 
     fn host_transfer(addr_ptr: *const u8, addr_len: u32, amount_lo: i64, amount_hi: i64) -> i32;
 
@@ -175,6 +194,9 @@ extern "C" {
     fn host_get_balance_lo() -> i64;
     fn host_get_balance_hi() -> i64;
     fn host_get_timestamp() -> i64;
+    fn host_get_attached_value(out_ptr: *mut u8) -> i32;
+    fn host_get_block_hash(out_ptr: *mut u8, out_max: u32) -> i32;
+    fn host_get_call_id(out_ptr: *mut u8) -> i32;
 
     fn host_get_arg_count() -> i32;
     fn host_get_arg(idx: i32, out_ptr: *mut u8, out_max: u32) -> i32;
@@ -182,29 +204,194 @@ extern "C" {
     fn host_set_return(ptr: *const u8, len: u32);
 
     fn host_blake3(data_ptr: *const u8, data_len: u32, out_ptr: *mut u8) -> i32;
+
+    fn host_random_seed(out_ptr: *mut u8) -> i32;
+
+    fn host_contract_exists(addr_ptr: *const u8, addr_len: u32) -> i32;
+    fn host_get_code_hash(
+        addr_ptr: *const u8,
+        addr_len: u32,
+        out_ptr: *mut u8,
+        out_max: u32,
+    ) -> i32;
 }
 
+// ─────────────────────────────────────────────────────────────────
+// Host ABI version (custom section read by the UVM)
+// ─────────────────────────────────────────────────────────────────
+
+/// Host ABI version this SDK is built against. Bumped whenever a host
+/// function is added; the UVM refuses contracts declaring a newer version
+/// than it implements (instead of failing with an opaque link error).
+pub const HOST_ABI_VERSION: u32 = 3;
+
+/// Name of the WASM custom section carrying [`HOST_ABI_VERSION`]
+/// (4 bytes, little-endian).
+pub const ABI_SECTION: &str = "los_abi";
+
+/// Embedded into every contract that links the SDK.
+#[cfg(target_arch = "wasm32")]
+#[used]
+#[link_section = "los_abi"]
+static LOS_ABI_VERSION: [u8; 4] = HOST_ABI_VERSION.to_le_bytes();
+
 // ─────────────────────────────────────────────────────────────────
 // Safe wrappers — State management
 // ─────────────────────────────────────────────────────────────────
 
 /// Contract state (persistent key-value storage).
+///
+/// Reads go through a per-execution cache: a key is fetched from the host at
+/// most once per call, and later reads (including `exists`) are served
+/// from memory. Inside the [`entry`](crate::entry) adapter writes are
+/// buffered too and reach the host once, in key order, when the closure
+/// returns `Ok` — a key set five times costs one host write. A failed call
+/// is reverted by the host anyway, so its buffered writes are dropped.
+/// Outside `entry` every write goes straight to the host.
 pub mod state {
     use super::*;
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use core::cell::RefCell;
+
+    /// Largest value the host stores (`MAX_STATE_VALUE_SIZE`).
+    pub const MAX_VALUE_SIZE: usize = 262_144;
+
+    /// Initial read buffer of [`get`]. Longer values cost a second host read.
+    pub const DEFAULT_READ_HINT: usize = 256;
+
+    struct Cache {
+        /// Value as last read or written; `None` = known to be absent
+        entries: BTreeMap<String, Option<Vec<u8>>>,
+        /// Keys whose cached entry has not reached the host yet
+        dirty: BTreeSet<String>,
+        /// Writes are held back until [`flush`]
+        buffering: bool,
+    }
 
-    /// Write a key-value pair to the contract's persistent state.
-    /// Both key and value are arbitrary bytes. Overwrites existing values.
-    pub fn set(key: &str, value: &[u8]) {
+    impl Cache {
+        const fn new() -> Self {
+            Self {
+                entries: BTreeMap::new(),
+                dirty: BTreeSet::new(),
+                buffering: false,
+            }
+        }
+    }
+
+    // One execution per WASM instance, and the instance is single-threaded.
+    #[cfg(target_arch = "wasm32")]
+    struct CacheCell(RefCell<Cache>);
+
+    #[cfg(target_arch = "wasm32")]
+    unsafe impl Sync for CacheCell {}
+
+    #[cfg(target_arch = "wasm32")]
+    static CACHE: CacheCell = CacheCell(RefCell::new(Cache::new()));
+
+    #[cfg(target_arch = "wasm32")]
+    fn with_cache<R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+        f(&mut CACHE.0.borrow_mut())
+    }
+
+    // Natively (los-testing) every call runs on its own thread.
+    #[cfg(not(target_arch = "wasm32"))]
+    extern crate std;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    std::thread_local! {
+        static CACHE: RefCell<Cache> = const { RefCell::new(Cache::new()) };
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_cache<R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+        CACHE.with(|cell| f(&mut cell.borrow_mut()))
+    }
+
+    fn host_write(key: &str, value: Option<&[u8]>) {
         unsafe {
-            host_set_state(
+            match value {
+                Some(value) => host_set_state(
+                    key.as_ptr(),
+                    key.len() as u32,
+                    value.as_ptr(),
+                    value.len() as u32,
+                ),
+                None => host_del_state(key.as_ptr(), key.len() as u32),
+            }
+        }
+    }
+
+    /// One host read into a `max`-byte buffer. The host reports how many
+    /// bytes it copied, not the full length, so `len == max` may be truncated.
+    fn host_read(key: &str, max: usize) -> Option<Vec<u8>> {
+        let mut buf = vec![0u8; max];
+        let len = unsafe {
+            host_get_state(
                 key.as_ptr(),
                 key.len() as u32,
-                value.as_ptr(),
-                value.len() as u32,
-            );
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+            )
+        };
+        if len < 0 {
+            return None;
+        }
+        buf.truncate(len as usize);
+        Some(buf)
+    }
+
+    fn store(key: &str, value: Option<&[u8]>) {
+        let write_through = with_cache(|c| {
+            c.entries.insert(key.into(), value.map(<[u8]>::to_vec));
+            if c.buffering {
+                c.dirty.insert(key.into());
+            }
+            !c.buffering
+        });
+        if write_through {
+            host_write(key, value);
+        }
+    }
+
+    /// Start buffering writes. `false` if an outer `entry` already did.
+    pub(crate) fn begin_buffering() -> bool {
+        with_cache(|c| !core::mem::replace(&mut c.buffering, true))
+    }
+
+    /// Write every buffered change to the host and stop buffering.
+    pub(crate) fn flush() {
+        let pending: Vec<(String, Option<Vec<u8>>)> = with_cache(|c| {
+            c.buffering = false;
+            let dirty = core::mem::take(&mut c.dirty);
+            dirty
+                .into_iter()
+                .map(|k| {
+                    let v = c.entries.get(&k).cloned().flatten();
+                    (k, v)
+                })
+                .collect()
+        });
+        for (key, value) in &pending {
+            host_write(key, value.as_deref());
         }
     }
 
+    /// Drop buffered writes (the host reverts the failed call anyway).
+    pub(crate) fn discard() {
+        with_cache(|c| {
+            c.buffering = false;
+            for key in core::mem::take(&mut c.dirty) {
+                c.entries.remove(&key);
+            }
+        });
+    }
+
+    /// Write a key-value pair to the contract's persistent state.
+    /// Both key and value are arbitrary bytes. Overwrites existing values.
+    pub fn set(key: &str, value: &[u8]) {
+        store(key, Some(value));
+    }
+
     /// Write a UTF-8 string value to state.
     pub fn set_str(key: &str, value: &str) {
         set(key, value.as_bytes());
@@ -224,20 +411,23 @@ pub mod state {
 
     /// Read a value from the contract's state. Returns `None` if key not found.
     pub fn get(key: &str) -> Option<Vec<u8>> {
-        let mut buf = vec![0u8; 262_144]; // MAX_STATE_VALUE_SIZE
-        let len = unsafe {
-            host_get_state(
-                key.as_ptr(),
-                key.len() as u32,
-                buf.as_mut_ptr(),
-                buf.len() as u32,
-            )
-        };
-        if len < 0 {
-            return None;
+        get_with_hint(key, DEFAULT_READ_HINT)
+    }
+
+    /// [`get`] with the expected value size: the first host read allocates
+    /// `hint + 1` bytes instead of [`MAX_VALUE_SIZE`], and only a longer
+    /// value is read again in full.
+    pub fn get_with_hint(key: &str, hint: usize) -> Option<Vec<u8>> {
+        if let Some(cached) = with_cache(|c| c.entries.get(key).cloned()) {
+            return cached;
         }
-        buf.truncate(len as usize);
-        Some(buf)
+        let first = hint.min(MAX_VALUE_SIZE - 1) + 1;
+        let value = match host_read(key, first) {
+            Some(v) if v.len() == first => host_read(key, MAX_VALUE_SIZE),
+            other => other,
+        };
+        with_cache(|c| c.entries.insert(key.into(), value.clone()));
+        value
     }
 
     /// Read a UTF-8 string from state. Returns `None` if key not found or invalid UTF-8.
@@ -248,7 +438,7 @@ pub mod state {
 
     /// Read a u128 value from state. Returns 0 if key not found or data too short.
     pub fn get_u128(key: &str) -> u128 {
-        match get(key) {
+        match get_with_hint(key, 16) {
             Some(bytes) if bytes.len() >= 16 => {
                 let mut arr = [0u8; 16];
                 arr.copy_from_slice(&bytes[..16]);
@@ -260,7 +450,7 @@ pub mod state {
 
     /// Read a u64 value from state. Returns 0 if key not found or data too short.
     pub fn get_u64(key: &str) -> u64 {
-        match get(key) {
+        match get_with_hint(key, 8) {
             Some(bytes) if bytes.len() >= 8 => {
                 let mut arr = [0u8; 8];
                 arr.copy_from_slice(&bytes[..8]);
@@ -270,16 +460,42 @@ pub mod state {
         }
     }
 
+    /// Write an i64 value to state (zig-zag, 16 hex characters; see
+    /// [`math::encode_i64`](crate::math::encode_i64)).
+    pub fn set_i64(key: &str, value: i64) {
+        set_str(key, &crate::math::encode_i64(value));
+    }
+
+    /// Write an i128 value to state (zig-zag, 32 hex characters).
+    pub fn set_i128(key: &str, value: i128) {
+        set_str(key, &crate::math::encode_i128(value));
+    }
+
+    /// Read an i64 written by `set_i64`. Returns 0 if key not found or malformed.
+    pub fn get_i64(key: &str) -> i64 {
+        get_str(key)
+            .and_then(|s| crate::math::decode_i64(&s))
+            .unwrap_or(0)
+    }
+
+    /// Read an i128 written by `set_i128`. Returns 0 if key not found or malformed.
+    pub fn get_i128(key: &str) -> i128 {
+        get_str(key)
+            .and_then(|s| crate::math::decode_i128(&s))
+            .unwrap_or(0)
+    }
+
     /// Delete a key from the contract's state.
     pub fn del(key: &str) {
-        unsafe {
-            host_del_state(key.as_ptr(), key.len() as u32);
-        }
+        store(key, None);
     }
 
     /// Check if a key exists in state.
     pub fn exists(key: &str) -> bool {
-        // Try reading 1 byte — if result >= 0, key exists
+        if let Some(cached) = with_cache(|c| c.entries.get(key).map(Option::is_some)) {
+            return cached;
+        }
+        // A zero-byte read: >= 0 means the key exists
         let mut buf = [0u8; 1];
         let len = unsafe { host_get_state(key.as_ptr(), key.len() as u32, buf.as_mut_ptr(), 0) };
         len >= 0
@@ -294,9 +510,20 @@ pub mod state {
 pub mod event {
     use super::*;
 
+    /// Maximum events per call. Emitting more aborts the call.
+    pub const MAX_EVENTS: usize = 64;
+    /// Maximum length of an event type name.
+    pub const MAX_TYPE_SIZE: usize = 128;
+    /// Maximum size of an event's JSON data (8 KB).
+    pub const MAX_DATA_SIZE: usize = 8_192;
+
     /// Emit a structured event.
     /// - `event_type`: Short identifier (e.g., "Transfer", "Approval", "Swap")
     /// - `data_json`: JSON string with event data, e.g., `{"from":"LOSW...","amount":"1000"}`
+    ///
+    /// Costs 375 gas plus 8 per byte. An event over [`MAX_TYPE_SIZE`] /
+    /// [`MAX_DATA_SIZE`] or past [`MAX_EVENTS`] aborts the call (all state
+    /// changes revert) rather than being truncated.
     pub fn emit(event_type: &str, data_json: &str) {
         unsafe {
             host_emit_event(
@@ -307,6 +534,52 @@ pub mod event {
             );
         }
     }
+
+    /// Emit an event whose data is the JSON serialization of `data`.
+    pub fn emit_json<T: crate::json::ToJson + ?Sized>(event_type: &str, data: &T) {
+        emit(event_type, &data.to_json());
+    }
+
+    /// Maximum indexed topics per event.
+    pub const MAX_TOPICS: usize = 4;
+
+    /// Emit an event with up to [`MAX_TOPICS`] indexed values. Nodes index
+    /// topic `i` so clients can filter with `topic{i}=...` (e.g. every
+    /// `Transfer` whose recipient is X). Topics may not contain NUL bytes
+    /// and are at most 256 bytes each. Topic bytes are priced like data and
+    /// each topic costs 50 gas more; size and count limits are as for [`emit`].
+    ///
+    /// ```rust,ignore
+    /// event::emit_indexed("Transfer", &[&from, &to], &format!(r#"{{"amount":"{}"}}"#, amount))?;
+    /// ```
+    pub fn emit_indexed(
+        event_type: &str,
+        topics: &[&str],
+        data_json: &str,
+    ) -> Result<(), &'static str> {
+        if topics.len() > MAX_TOPICS {
+            return Err("Too many event topics");
+        }
+        if topics.iter().any(|t| t.as_bytes().contains(&0)) {
+            return Err("Event topic contains a NUL byte");
+        }
+        let joined = topics.join("\0");
+        let code = unsafe {
+            host_emit_event_indexed(
+                event_type.as_ptr(),
+                event_type.len() as u32,
+                joined.as_ptr(),
+                joined.len() as u32,
+                data_json.as_ptr(),
+                data_json.len() as u32,
+            )
+        };
+        match code {
+            0 => Ok(()),
+            1 => Err("Invalid event topics"),
+            _ => Err("Event not recorded"),
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────
@@ -325,6 +598,18 @@ pub mod crypto {
         }
         out
     }
+
+    /// Deterministic 32-byte seed from the host; each call in the same
+    /// execution returns a different value. Every validator computes the
+    /// same bytes, so the caller can predict them — do not use for
+    /// lotteries or anything with value riding on the outcome.
+    pub fn random_seed() -> [u8; 32] {
+        let mut out = [0u8; 32];
+        unsafe {
+            host_random_seed(out.as_mut_ptr());
+        }
+        out
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────
@@ -358,11 +643,85 @@ pub fn balance() -> u128 {
     (hi << 64) | lo
 }
 
+/// CIL attached to this call by the caller (u128). Already included in
+/// [`balance`]; refunded to the caller if the call fails.
+pub fn msg_value() -> u128 {
+    let mut buf = [0u8; 16];
+    if unsafe { host_get_attached_value(buf.as_mut_ptr()) } != 16 {
+        return 0;
+    }
+    u128::from_le_bytes(buf)
+}
+
 /// Get the current block timestamp (seconds since UNIX epoch).
 pub fn timestamp() -> u64 {
     unsafe { host_get_timestamp() as u64 }
 }
 
+/// Hash of the ContractCall block being executed (hex). Empty outside a
+/// block: dry runs and read-only calls.
+pub fn block_hash() -> String {
+    let mut buf = [0u8; 128];
+    let len = unsafe { host_get_block_hash(buf.as_mut_ptr(), buf.len() as u32) };
+    if len <= 0 {
+        return String::new();
+    }
+    String::from_utf8_lossy(&buf[..len as usize]).into_owned()
+}
+
+/// Unique ID of this invocation, `blake3(block_hash || call_index)`: the
+/// call itself has index 0, the n-th `on_receive` hook it triggers index n.
+/// Use it as an idempotency key (bridge deposits, order IDs). Outside a
+/// block it depends on the call index only.
+pub fn call_id() -> [u8; 32] {
+    let mut out = [0u8; 32];
+    unsafe {
+        host_get_call_id(out.as_mut_ptr());
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Safe wrappers — Other contracts
+// ─────────────────────────────────────────────────────────────────
+
+/// Queries about other deployed contracts.
+///
+/// ```rust,ignore
+/// const TOKEN_CODE_HASH: &str = "9f2c…"; // audited USP-01 build
+///
+/// let token = arg(0).unwrap_or_default();
+/// if contract::code_hash(&token).as_deref() != Some(TOKEN_CODE_HASH) {
+///     return ContractError::InvalidArgument.code();
+/// }
+/// ```
+pub mod contract {
+    use super::*;
+
+    /// True if `addr` is a deployed contract.
+    pub fn exists(addr: &str) -> bool {
+        unsafe { host_contract_exists(addr.as_ptr(), addr.len() as u32) == 1 }
+    }
+
+    /// Hex blake3 hash of the bytecode deployed at `addr` (the `code_hash`
+    /// shown by `GET /contract/:addr`), or `None` if it is not a contract.
+    pub fn code_hash(addr: &str) -> Option<String> {
+        let mut buf = [0u8; 64];
+        let len = unsafe {
+            host_get_code_hash(
+                addr.as_ptr(),
+                addr.len() as u32,
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+            )
+        };
+        if len <= 0 {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&buf[..len as usize]).into_owned())
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Safe wrappers — Arguments
 // ─────────────────────────────────────────────────────────────────
@@ -424,6 +783,11 @@ pub fn set_return_str(s: &str) {
     set_return(s.as_bytes());
 }
 
+/// Set return data to the JSON serialization of `value`.
+pub fn set_return_json<T: json::ToJson + ?Sized>(value: &T) {
+    set_return_str(&value.to_json());
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Safe wrappers — Logging
 // ─────────────────────────────────────────────────────────────────
@@ -446,9 +810,328 @@ pub fn abort(msg: &str) -> ! {
         host_abort(msg.as_ptr(), msg.len() as u32);
         #[cfg(target_arch = "wasm32")]
         core::arch::wasm32::unreachable();
-        #[cfg(not(target_arch = "wasm32"))]
-        core::hint::unreachable_unchecked();
     }
+    // Native: los-testing's host_abort never returns; any other host gets a panic
+    #[cfg(not(target_arch = "wasm32"))]
+    panic!("contract aborted: {}", msg);
+}
+
+// Overflow-safe mul_div, 64.64 fixed point, bps helpers
+pub mod json;
+pub mod math;
+// Shims for third-party no_std crates (getrandom backend, time)
+#[cfg(feature = "compat")]
+pub mod compat;
+
+// ─────────────────────────────────────────────────────────────────
+// Errors — typed status codes
+// ─────────────────────────────────────────────────────────────────
+
+/// Highest status code reserved for the SDK (1..=99).
+pub const SDK_ERROR_MAX: i32 = 99;
+/// First status code available to contracts (`ContractError::User(n)` → 100 + n).
+pub const USER_ERROR_BASE: i32 = 100;
+
+/// Contract failure with a stable status code.
+///
+/// Codes 1-99 are reserved for the SDK; codes 100+ belong to the contract.
+/// The node maps codes to messages in the call result, so contracts only
+/// need `set_return` for success data or custom error detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractError {
+    /// Generic failure (code 1 — what hand-written contracts already return)
+    Failed,
+    /// Required argument not supplied (2)
+    MissingArgument,
+    /// Argument could not be parsed or is out of range (3)
+    InvalidArgument,
+    /// Caller is not allowed to perform this action (4)
+    Unauthorized,
+    /// Account or contract balance too low (5)
+    InsufficientBalance,
+    /// Requested key/record does not exist (6)
+    NotFound,
+    /// Record already exists (7)
+    AlreadyExists,
+    /// Arithmetic overflow or underflow (8)
+    Overflow,
+    /// `transfer()` was rejected by the host (9)
+    TransferFailed,
+    /// Contract state does not allow this call (10)
+    InvalidState,
+    /// Contract-defined error; reported as code `100 + n`
+    User(u32),
+}
+
+impl ContractError {
+    /// Status code returned from the entry point.
+    pub fn code(&self) -> i32 {
+        match self {
+            ContractError::Failed => 1,
+            ContractError::MissingArgument => 2,
+            ContractError::InvalidArgument => 3,
+            ContractError::Unauthorized => 4,
+            ContractError::InsufficientBalance => 5,
+            ContractError::NotFound => 6,
+            ContractError::AlreadyExists => 7,
+            ContractError::Overflow => 8,
+            ContractError::TransferFailed => 9,
+            ContractError::InvalidState => 10,
+            ContractError::User(n) => {
+                USER_ERROR_BASE.saturating_add((*n).min(i32::MAX as u32) as i32)
+            }
+        }
+    }
+
+    /// Inverse of [`code`](ContractError::code). `None` for 0 (success),
+    /// negative codes and unassigned SDK codes.
+    pub fn from_code(code: i32) -> Option<ContractError> {
+        Some(match code {
+            1 => ContractError::Failed,
+            2 => ContractError::MissingArgument,
+            3 => ContractError::InvalidArgument,
+            4 => ContractError::Unauthorized,
+            5 => ContractError::InsufficientBalance,
+            6 => ContractError::NotFound,
+            7 => ContractError::AlreadyExists,
+            8 => ContractError::Overflow,
+            9 => ContractError::TransferFailed,
+            10 => ContractError::InvalidState,
+            c if c >= USER_ERROR_BASE => ContractError::User((c - USER_ERROR_BASE) as u32),
+            _ => return None,
+        })
+    }
+
+    /// Short human-readable description.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ContractError::Failed => "Contract call failed",
+            ContractError::MissingArgument => "Missing required argument",
+            ContractError::InvalidArgument => "Invalid argument",
+            ContractError::Unauthorized => "Caller is not authorized",
+            ContractError::InsufficientBalance => "Insufficient balance",
+            ContractError::NotFound => "Not found",
+            ContractError::AlreadyExists => "Already exists",
+            ContractError::Overflow => "Arithmetic overflow",
+            ContractError::TransferFailed => "Transfer failed",
+            ContractError::InvalidState => "Invalid contract state",
+            ContractError::User(_) => "Contract-defined error",
+        }
+    }
+}
+
+/// Human-readable text for an entry point's status code
+/// (used by the node to fill `ContractResult.output`).
+pub fn describe_code(code: i32) -> String {
+    match ContractError::from_code(code) {
+        Some(ContractError::User(n)) => alloc::format!("Contract error {} (user code {})", code, n),
+        Some(e) => alloc::format!("{} (code {})", e.message(), code),
+        None if code == 0 => String::from("OK"),
+        None => alloc::format!("Unknown error (code {})", code),
+    }
+}
+
+/// Values an [`entry`] function can return on success.
+pub trait IntoReturn {
+    /// Write `self` as the call's return data.
+    fn set_as_return(self);
+}
+
+impl IntoReturn for () {
+    fn set_as_return(self) {}
+}
+
+impl IntoReturn for String {
+    fn set_as_return(self) {
+        set_return_str(&self);
+    }
+}
+
+impl IntoReturn for &str {
+    fn set_as_return(self) {
+        set_return_str(self);
+    }
+}
+
+impl IntoReturn for Vec<u8> {
+    fn set_as_return(self) {
+        set_return(&self);
+    }
+}
+
+impl IntoReturn for bool {
+    fn set_as_return(self) {
+        set_return_str(if self { "true" } else { "false" });
+    }
+}
+
+macro_rules! impl_into_return_int {
+    ($($t:ty),*) => {
+        $(impl IntoReturn for $t {
+            fn set_as_return(self) {
+                set_return_str(&alloc::format!("{}", self));
+            }
+        })*
+    };
+}
+impl_into_return_int!(u32, u64, u128, i32, i64, i128);
+
+/// Entry-point adapter: run `f`, publish `Ok` as return data, map `Err`
+/// to its status code. State writes made in `f` are buffered and flushed
+/// to the host on `Ok` (see [`state`]).
+///
+/// ```rust,ignore
+/// #[no_mangle]
+/// pub extern "C" fn withdraw() -> i32 {
+///     entry(|| {
+///         let amount: u128 = require_arg(0)?
+///             .parse()
+///             .map_err(|_| ContractError::InvalidArgument)?;
+///         require(caller() == state::get_str("owner").unwrap_or_default(),
+///                 ContractError::Unauthorized)?;
+///         transfer(&caller(), amount).map_err(|_| ContractError::TransferFailed)?;
+///         Ok(amount)
+///     })
+/// }
+/// ```
+pub fn entry<T, F>(f: F) -> i32
+where
+    T: IntoReturn,
+    F: FnOnce() -> Result<T, ContractError>,
+{
+    let outermost = state::begin_buffering();
+    match f() {
+        Ok(value) => {
+            if outermost {
+                state::flush();
+            }
+            value.set_as_return();
+            0
+        }
+        Err(e) => {
+            if outermost {
+                state::discard();
+            }
+            e.code()
+        }
+    }
+}
+
+/// `Err(err)` unless `cond` holds.
+pub fn require(cond: bool, err: ContractError) -> Result<(), ContractError> {
+    if cond {
+        Ok(())
+    } else {
+        Err(err)
+    }
+}
+
+/// Argument `idx`, or `ContractError::MissingArgument`.
+pub fn require_arg(idx: u32) -> Result<String, ContractError> {
+    arg(idx).ok_or(ContractError::MissingArgument)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Storage migrations
+// ─────────────────────────────────────────────────────────────────
+
+/// Versioned state-layout migrations.
+///
+/// The stored schema version lives under [`SCHEMA_VERSION_KEY`] as a decimal
+/// string. The host only accepts writes that move it forward, so each
+/// migration commits at most once: re-running an applied version aborts the
+/// call and reverts everything it wrote.
+///
+/// ```rust,ignore
+/// const MIGRATIONS: &[migrate::Migration] = &[v0_to_v1, v1_to_v2];
+///
+/// #[no_mangle]
+/// pub extern "C" fn init() -> i32 {
+///     entry(|| migrate::run_migrations(2, MIGRATIONS).map(|_| ()))
+/// }
+/// ```
+pub mod migrate {
+    use super::*;
+
+    /// Reserved state key (host-enforced: increase-only, never deleted)
+    pub const SCHEMA_VERSION_KEY: &str = "__los:schema_version";
+
+    /// Upgrades state from version `i` to `i + 1` (its index in the list).
+    pub type Migration = fn() -> Result<(), ContractError>;
+
+    /// Stored schema version (0 = never migrated / fresh contract).
+    pub fn schema_version() -> u32 {
+        state::get_str(SCHEMA_VERSION_KEY)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Bring state up to `current`, running `migrations[v]` for every
+    /// version `v` between the stored version and `current`. Returns how
+    /// many migrations ran (0 when already up to date — one state read).
+    ///
+    /// Errors with `InvalidState` if fewer than `current` migrations are
+    /// given or the stored version is newer than `current` (downgrade).
+    pub fn run_migrations(current: u32, migrations: &[Migration]) -> Result<u32, ContractError> {
+        if migrations.len() < current as usize {
+            return Err(ContractError::InvalidState);
+        }
+        let stored = schema_version();
+        if stored > current {
+            return Err(ContractError::InvalidState);
+        }
+        for v in stored..current {
+            migrations[v as usize]()?;
+            state::set_str(SCHEMA_VERSION_KEY, &alloc::format!("{}", v + 1));
+        }
+        Ok(current - stored)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Contract metadata (contract_info export)
+// ─────────────────────────────────────────────────────────────────
+
+/// JSON answer of the `contract_info` export: name, version, [`HOST_ABI_VERSION`]
+/// and build hash. The UVM reads it once at deploy and shows it at
+/// `GET /contract/:addr`.
+pub fn contract_info_json(name: &str, version: &str, build_hash: &str) -> String {
+    json::Object::new()
+        .str("name", name)
+        .str("version", version)
+        .u64("sdk_abi", HOST_ABI_VERSION as u64)
+        .str("build_hash", build_hash)
+        .finish()
+}
+
+/// Generate the standard `contract_info` export.
+///
+/// Without arguments the contract crate's `CARGO_PKG_NAME` and
+/// `CARGO_PKG_VERSION` are used. The build hash is taken from the
+/// `LOS_BUILD_HASH` environment variable at compile time (e.g. a git commit),
+/// empty if unset.
+///
+/// ```rust,ignore
+/// los_sdk::contract_info!();
+/// // or
+/// los_sdk::contract_info!("my_token", "1.2.0");
+/// ```
+#[macro_export]
+macro_rules! contract_info {
+    () => {
+        $crate::contract_info!(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    };
+    ($name:expr, $version:expr $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn contract_info() -> i32 {
+            $crate::set_return_str(&$crate::contract_info_json(
+                $name,
+                $version,
+                option_env!("LOS_BUILD_HASH").unwrap_or(""),
+            ));
+            0
+        }
+    };
 }
 
 // ─────────────────────────────────────────────────────────────────
@@ -493,6 +1176,57 @@ mod tests {
     use alloc::vec;
     use alloc::vec::Vec;
 
+    use super::{
+        contract_info_json, describe_code, ContractError, HOST_ABI_VERSION, SDK_ERROR_MAX,
+        USER_ERROR_BASE,
+    };
+
+    #[test]
+    fn test_contract_error_code_ranges_roundtrip() {
+        let sdk = [
+            ContractError::Failed,
+            ContractError::MissingArgument,
+            ContractError::InvalidArgument,
+            ContractError::Unauthorized,
+            ContractError::InsufficientBalance,
+            ContractError::NotFound,
+            ContractError::AlreadyExists,
+            ContractError::Overflow,
+            ContractError::TransferFailed,
+            ContractError::InvalidState,
+        ];
+        for e in sdk {
+            assert!((1..=SDK_ERROR_MAX).contains(&e.code()));
+            assert_eq!(ContractError::from_code(e.code()), Some(e));
+        }
+        assert_eq!(ContractError::User(0).code(), USER_ERROR_BASE);
+        assert_eq!(ContractError::User(7).code(), 107);
+        assert_eq!(ContractError::from_code(107), Some(ContractError::User(7)));
+        assert_eq!(ContractError::User(u32::MAX).code(), i32::MAX);
+        assert_eq!(ContractError::from_code(0), None);
+        assert_eq!(ContractError::from_code(-1), None);
+        assert_eq!(ContractError::from_code(50), None);
+    }
+
+    #[test]
+    fn test_describe_code() {
+        assert_eq!(describe_code(4), "Caller is not authorized (code 4)");
+        assert_eq!(describe_code(105), "Contract error 105 (user code 5)");
+        assert_eq!(describe_code(42), "Unknown error (code 42)");
+        assert_eq!(describe_code(0), "OK");
+    }
+
+    #[test]
+    fn test_contract_info_json() {
+        assert_eq!(
+            contract_info_json("token", "1.0.0", ""),
+            alloc::format!(
+                r#"{{"name":"token","version":"1.0.0","sdk_abi":{},"build_hash":""}}"#,
+                HOST_ABI_VERSION
+            )
+        );
+    }
+
     #[test]
     fn test_u128_split_reconstruct() {
         let amount: u128 = 1_000_000_000_000; // 1 trillion
@@ -0,0 +1,560 @@
+//! Overflow-safe integer math for DeFi contracts.
+//!
+//! - [`mul_div`] — `a * b / denominator` with a 256-bit intermediate, so the
+//!   product never overflows (the usual AMM / pro-rata / fee pattern)
+//! - [`Fixed64x64`] — unsigned 64.64 fixed-point number for prices and ratios
+//! - Basis-point and percentage helpers ([`bps_of`], [`apply_bps_fee`], …)
+//! - Saturating integer conversions
+//! - Signed math for PnL, funding rates and price deltas ([`mul_div_signed`],
+//!   [`delta`], [`apply_delta`], …) and String-safe state encodings for
+//!   `i64` / `i128` ([`encode_i128`], [`decode_i128`])
+//!
+//! Everything is integer-only (no `f32`/`f64`), so results are identical on
+//! every validator. Functions return `None` instead of trapping.
+
+/// Basis-point denominator (10 000 bps = 100%)
+pub const BPS_DENOMINATOR: u128 = 10_000;
+/// Percentage denominator
+pub const PERCENT_DENOMINATOR: u128 = 100;
+
+use alloc::format;
+use alloc::string::String;
+
+const LO_MASK: u128 = u64::MAX as u128;
+
+// ─────────────────────────────────────────────────────────────────
+// 256-bit intermediate
+// ─────────────────────────────────────────────────────────────────
+
+/// Full product `a * b` as (high, low) 128-bit halves.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LO_MASK);
+    let (b1, b0) = (b >> 64, b & LO_MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    // < 3 * 2^64 — cannot overflow
+    let mid = (p00 >> 64) + (p01 & LO_MASK) + (p10 & LO_MASK);
+    let lo = (p00 & LO_MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+/// (quotient, remainder) of the 256-bit value `hi:lo` divided by `d`.
+/// `None` if `d == 0` or the quotient does not fit in u128.
+fn div_wide(hi: u128, lo: u128, d: u128) -> Option<(u128, u128)> {
+    if d == 0 || hi >= d {
+        return None;
+    }
+    if hi == 0 {
+        return Some((lo / d, lo % d));
+    }
+    // Restoring long division; invariant: rem < d
+    let mut rem = hi;
+    let mut quot: u128 = 0;
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quot <<= 1;
+        if carry == 1 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            quot |= 1;
+        }
+    }
+    Some((quot, rem))
+}
+
+/// `floor(a * b / denominator)` without intermediate overflow.
+/// `None` if `denominator == 0` or the result exceeds `u128::MAX`.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    let (hi, lo) = widening_mul(a, b);
+    div_wide(hi, lo, denominator).map(|(q, _)| q)
+}
+
+/// `ceil(a * b / denominator)` without intermediate overflow.
+pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    let (hi, lo) = widening_mul(a, b);
+    let (q, r) = div_wide(hi, lo, denominator)?;
+    if r > 0 {
+        q.checked_add(1)
+    } else {
+        Some(q)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Basis points / percentages
+// ─────────────────────────────────────────────────────────────────
+
+/// `floor(amount * bps / 10_000)`
+pub fn bps_of(amount: u128, bps: u128) -> Option<u128> {
+    mul_div(amount, bps, BPS_DENOMINATOR)
+}
+
+/// `floor(amount * pct / 100)`
+pub fn percent_of(amount: u128, pct: u128) -> Option<u128> {
+    mul_div(amount, pct, PERCENT_DENOMINATOR)
+}
+
+/// Split `amount` into `(net, fee)` for a fee of `fee_bps` (rounded down,
+/// in the payer's favour). `None` if `fee_bps > 10_000`.
+pub fn apply_bps_fee(amount: u128, fee_bps: u128) -> Option<(u128, u128)> {
+    if fee_bps > BPS_DENOMINATOR {
+        return None;
+    }
+    let fee = bps_of(amount, fee_bps)?;
+    Some((amount - fee, fee))
+}
+
+/// `part / whole` in basis points (rounded down). `None` if `whole == 0`.
+pub fn ratio_bps(part: u128, whole: u128) -> Option<u128> {
+    mul_div(part, BPS_DENOMINATOR, whole)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Saturating conversions
+// ─────────────────────────────────────────────────────────────────
+
+/// u128 → u64, clamped to `u64::MAX`
+pub fn to_u64_saturating(v: u128) -> u64 {
+    if v > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        v as u64
+    }
+}
+
+/// u128 → i64, clamped to `i64::MAX`
+pub fn to_i64_saturating(v: u128) -> i64 {
+    if v > i64::MAX as u128 {
+        i64::MAX
+    } else {
+        v as i64
+    }
+}
+
+/// i128 → u128, negative values clamp to 0
+pub fn to_u128_saturating(v: i128) -> u128 {
+    if v < 0 {
+        0
+    } else {
+        v as u128
+    }
+}
+
+/// u128 → i128, clamped to `i128::MAX`
+pub fn to_i128_saturating(v: u128) -> i128 {
+    if v > i128::MAX as u128 {
+        i128::MAX
+    } else {
+        v as i128
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Signed math
+// ─────────────────────────────────────────────────────────────────
+
+/// `a * b / denominator` on signed values with a 256-bit intermediate,
+/// rounded toward zero (like `/` on integers). `None` if `denominator == 0`
+/// or the result does not fit in i128.
+pub fn mul_div_signed(a: i128, b: i128, denominator: i128) -> Option<i128> {
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let magnitude = mul_div(
+        a.unsigned_abs(),
+        b.unsigned_abs(),
+        denominator.unsigned_abs(),
+    )?;
+    with_sign(magnitude, negative)
+}
+
+/// `amount * bps / 10_000` on a signed amount, rounded toward zero
+pub fn bps_of_signed(amount: i128, bps: i128) -> Option<i128> {
+    mul_div_signed(amount, bps, BPS_DENOMINATOR as i128)
+}
+
+/// `new - old` as a signed value (price delta, PnL). `None` if the
+/// difference does not fit in i128.
+pub fn delta(new: u128, old: u128) -> Option<i128> {
+    if new >= old {
+        with_sign(new - old, false)
+    } else {
+        with_sign(old - new, true)
+    }
+}
+
+/// `value + delta`. `None` if the result is negative or exceeds u128.
+pub fn apply_delta(value: u128, delta: i128) -> Option<u128> {
+    if delta >= 0 {
+        value.checked_add(delta as u128)
+    } else {
+        value.checked_sub(delta.unsigned_abs())
+    }
+}
+
+/// Magnitude with a sign. `None` if it does not fit (i128::MIN is allowed).
+fn with_sign(magnitude: u128, negative: bool) -> Option<i128> {
+    if negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Signed state encoding
+// ─────────────────────────────────────────────────────────────────
+//
+// Contract state is stored by the node as UTF-8 text, so raw little-endian
+// bytes of a negative number do not survive. Signed values are zig-zag
+// encoded (0, -1, 1, -2, … → 0, 1, 2, 3, …) and written as fixed-width
+// lowercase hex: 16 characters for i64, 32 for i128.
+
+/// Zig-zag encode: small magnitudes of either sign become small numbers
+pub const fn zigzag_i64(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+pub const fn unzigzag_i64(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+pub const fn zigzag_i128(v: i128) -> u128 {
+    ((v << 1) ^ (v >> 127)) as u128
+}
+
+pub const fn unzigzag_i128(v: u128) -> i128 {
+    ((v >> 1) as i128) ^ -((v & 1) as i128)
+}
+
+/// i64 → 16 hex characters (zig-zag)
+pub fn encode_i64(v: i64) -> String {
+    format!("{:016x}", zigzag_i64(v))
+}
+
+/// Inverse of [`encode_i64`]. `None` unless exactly 16 hex characters.
+pub fn decode_i64(s: &str) -> Option<i64> {
+    if s.len() != 16 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u64::from_str_radix(s, 16).ok().map(unzigzag_i64)
+}
+
+/// i128 → 32 hex characters (zig-zag)
+pub fn encode_i128(v: i128) -> String {
+    format!("{:032x}", zigzag_i128(v))
+}
+
+/// Inverse of [`encode_i128`]. `None` unless exactly 32 hex characters.
+pub fn decode_i128(s: &str) -> Option<i128> {
+    if s.len() != 32 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(s, 16).ok().map(unzigzag_i128)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// 64.64 fixed point
+// ─────────────────────────────────────────────────────────────────
+
+/// Unsigned 64.64 fixed-point number: 64 integer bits, 64 fractional bits,
+/// stored in one u128 (`raw / 2^64`). Resolution ≈ 5.4e-20.
+///
+/// Store it with `state::set_u128(key, x.raw())` and read it back with
+/// `Fixed64x64::from_raw(state::get_u128(key))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Fixed64x64(u128);
+
+impl Fixed64x64 {
+    /// Number of fractional bits
+    pub const FRAC_BITS: u32 = 64;
+    pub const ZERO: Fixed64x64 = Fixed64x64(0);
+    pub const ONE: Fixed64x64 = Fixed64x64(1 << 64);
+    pub const MAX: Fixed64x64 = Fixed64x64(u128::MAX);
+
+    pub const fn from_raw(raw: u128) -> Self {
+        Fixed64x64(raw)
+    }
+
+    pub const fn raw(self) -> u128 {
+        self.0
+    }
+
+    pub const fn from_int(v: u64) -> Self {
+        Fixed64x64((v as u128) << 64)
+    }
+
+    /// `numerator / denominator` (rounded down). `None` if `denominator == 0`
+    /// or the ratio is ≥ 2^64.
+    pub fn from_ratio(numerator: u128, denominator: u128) -> Option<Self> {
+        mul_div(numerator, 1 << 64, denominator).map(Fixed64x64)
+    }
+
+    /// `bps / 10_000`
+    pub fn from_bps(bps: u128) -> Option<Self> {
+        Self::from_ratio(bps, BPS_DENOMINATOR)
+    }
+
+    /// Integer part (rounded down)
+    pub const fn floor(self) -> u64 {
+        (self.0 >> 64) as u64
+    }
+
+    /// Integer part rounded up. `None` at the top of the range.
+    pub fn ceil(self) -> Option<u64> {
+        let floor = self.floor();
+        if self.0 & LO_MASK == 0 {
+            Some(floor)
+        } else {
+            floor.checked_add(1)
+        }
+    }
+
+    /// Fractional bits (`raw mod 2^64`)
+    pub const fn frac(self) -> u64 {
+        (self.0 & LO_MASK) as u64
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Fixed64x64)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Fixed64x64)
+    }
+
+    /// Product, rounded down
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        mul_div(self.0, rhs.0, 1 << 64).map(Fixed64x64)
+    }
+
+    /// Quotient, rounded down. `None` on division by zero or overflow.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        mul_div(self.0, 1 << 64, rhs.0).map(Fixed64x64)
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Fixed64x64(self.0.saturating_add(rhs.0))
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Fixed64x64(self.0.saturating_sub(rhs.0))
+    }
+
+    /// `floor(self * v)` — e.g. price × amount
+    pub fn mul_int(self, v: u128) -> Option<u128> {
+        mul_div(v, self.0, 1 << 64)
+    }
+
+    /// `ceil(self * v)` — round against the user when charging
+    pub fn mul_int_ceil(self, v: u128) -> Option<u128> {
+        mul_div_ceil(v, self.0, 1 << 64)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Products with a closed-form 256-bit result
+    #[test]
+    fn test_widening_mul_known_values() {
+        assert_eq!(widening_mul(0, u128::MAX), (0, 0));
+        assert_eq!(widening_mul(1, u128::MAX), (0, u128::MAX));
+        assert_eq!(widening_mul(1 << 64, 1 << 64), (1, 0));
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1 → hi = 2^128 - 2, lo = 1
+        assert_eq!(widening_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        // (2^127)·2 = 2^128
+        assert_eq!(widening_mul(1 << 127, 2), (1, 0));
+    }
+
+    #[test]
+    fn test_mul_div_matches_native_when_no_overflow() {
+        let samples: [u128; 9] = [
+            0,
+            1,
+            2,
+            3,
+            7,
+            10_000,
+            1_000_000_007,
+            u64::MAX as u128,
+            (u64::MAX as u128) + 12_345,
+        ];
+        for &a in &samples {
+            for &b in &samples {
+                for &d in &samples[1..] {
+                    if let Some(p) = a.checked_mul(b) {
+                        assert_eq!(mul_div(a, b, d), Some(p / d), "{a}*{b}/{d}");
+                        let ceil = p / d + u128::from(p % d != 0);
+                        assert_eq!(mul_div_ceil(a, b, d), Some(ceil), "ceil {a}*{b}/{d}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_div_wide_intermediate() {
+        // a * b overflows u128 but the quotient fits
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 3, 3), Some(u128::MAX));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 120), Some(1 << 80));
+        assert_eq!(mul_div(u128::MAX, 2, 4), Some(u128::MAX / 2));
+        assert_eq!(mul_div_ceil(u128::MAX, 2, 4), Some(u128::MAX / 2 + 1));
+        // Symmetric
+        let (a, b, d) = (u128::MAX / 3, 1_000_000_000_000u128, 999_999_999_999u128);
+        assert_eq!(mul_div(a, b, d), mul_div(b, a, d));
+    }
+
+    #[test]
+    fn test_mul_div_errors() {
+        assert_eq!(mul_div(1, 1, 0), None);
+        assert_eq!(mul_div_ceil(1, 1, 0), None);
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+        assert_eq!(mul_div_ceil(u128::MAX, u128::MAX, u128::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_bps_and_percent() {
+        assert_eq!(bps_of(10_000, 30), Some(30));
+        assert_eq!(bps_of(100, 30), Some(0));
+        assert_eq!(bps_of(u128::MAX, BPS_DENOMINATOR), Some(u128::MAX));
+        assert_eq!(percent_of(250, 40), Some(100));
+        assert_eq!(apply_bps_fee(10_000, 30), Some((9_970, 30)));
+        assert_eq!(
+            apply_bps_fee(u128::MAX, 30).map(|(n, f)| n + f),
+            Some(u128::MAX)
+        );
+        assert_eq!(apply_bps_fee(1, 10_000), Some((0, 1)));
+        assert_eq!(apply_bps_fee(1, 10_001), None);
+        assert_eq!(ratio_bps(1, 4), Some(2_500));
+        assert_eq!(ratio_bps(1, 0), None);
+    }
+
+    #[test]
+    fn test_saturating_conversions() {
+        assert_eq!(to_u64_saturating(5), 5);
+        assert_eq!(to_u64_saturating(u128::MAX), u64::MAX);
+        assert_eq!(to_i64_saturating(u64::MAX as u128), i64::MAX);
+        assert_eq!(to_i64_saturating(7), 7);
+        assert_eq!(to_u128_saturating(-1), 0);
+        assert_eq!(to_u128_saturating(i128::MAX), i128::MAX as u128);
+        assert_eq!(to_i128_saturating(u128::MAX), i128::MAX);
+    }
+
+    #[test]
+    fn test_fixed_basic_arithmetic() {
+        let two = Fixed64x64::from_int(2);
+        let half = Fixed64x64::from_ratio(1, 2).unwrap();
+        assert_eq!(two.checked_mul(half), Some(Fixed64x64::ONE));
+        assert_eq!(Fixed64x64::ONE.checked_div(two), Some(half));
+        assert_eq!(half.checked_add(half), Some(Fixed64x64::ONE));
+        assert_eq!(half.checked_sub(two), None);
+        assert_eq!(half.saturating_sub(two), Fixed64x64::ZERO);
+        assert_eq!(Fixed64x64::MAX.checked_add(Fixed64x64::ONE), None);
+        assert_eq!(two.checked_div(Fixed64x64::ZERO), None);
+        assert_eq!(Fixed64x64::from_ratio(1, 0), None);
+        // 2^64 does not fit in the integer part
+        assert_eq!(Fixed64x64::from_ratio(1 << 64, 1), None);
+    }
+
+    #[test]
+    fn test_fixed_rounding() {
+        let third = Fixed64x64::from_ratio(1, 3).unwrap();
+        assert_eq!(third.floor(), 0);
+        assert_eq!(third.ceil(), Some(1));
+        assert_eq!(Fixed64x64::from_int(7).ceil(), Some(7));
+        assert_eq!(Fixed64x64::MAX.ceil(), None);
+        assert_eq!(third.mul_int(3_000), Some(999));
+        assert_eq!(third.mul_int_ceil(3_000), Some(1_000));
+        let price = Fixed64x64::from_ratio(3, 2).unwrap();
+        assert_eq!(price.frac(), 1 << 63);
+        assert_eq!(price.mul_int(u128::MAX / 3 * 2), Some(u128::MAX / 3 * 3));
+    }
+
+    #[test]
+    fn test_fixed_bps_matches_integer_helper() {
+        let fee = Fixed64x64::from_bps(30).unwrap();
+        for amount in [0u128, 1, 333, 10_000, 123_456_789, u64::MAX as u128] {
+            let exact = bps_of(amount, 30).unwrap();
+            let approx = fee.mul_int(amount).unwrap();
+            // 30/10_000 is not exact in binary: at most 1 unit below
+            assert!(approx == exact || approx + 1 == exact, "amount {amount}");
+        }
+    }
+
+    #[test]
+    fn test_signed_mul_div_rounds_toward_zero() {
+        assert_eq!(mul_div_signed(-7, 3, 2), Some(-10));
+        assert_eq!(mul_div_signed(7, 3, 2), Some(10));
+        assert_eq!(mul_div_signed(7, -3, -2), Some(10));
+        assert_eq!(mul_div_signed(-7, -3, 2), Some(10));
+        assert_eq!(mul_div_signed(1, 1, 0), None);
+        // Wide intermediate on signed inputs
+        assert_eq!(
+            mul_div_signed(i128::MIN, i128::MAX, i128::MAX),
+            Some(i128::MIN)
+        );
+        assert_eq!(mul_div_signed(i128::MIN, -1, 1), None);
+        assert_eq!(bps_of_signed(-10_000, 30), Some(-30));
+        assert_eq!(bps_of_signed(-100, 30), Some(0));
+    }
+
+    #[test]
+    fn test_delta_and_apply() {
+        assert_eq!(delta(150, 100), Some(50));
+        assert_eq!(delta(100, 150), Some(-50));
+        assert_eq!(delta(0, 1 << 127), Some(i128::MIN));
+        assert_eq!(delta(u128::MAX, 0), None);
+        assert_eq!(apply_delta(100, -50), Some(50));
+        assert_eq!(apply_delta(100, -101), None);
+        assert_eq!(apply_delta(u128::MAX, 1), None);
+        assert_eq!(apply_delta(0, i128::MIN), None);
+        let (old, new) = (1_000_000u128, 999_000u128);
+        assert_eq!(apply_delta(old, delta(new, old).unwrap()), Some(new));
+    }
+
+    #[test]
+    fn test_zigzag() {
+        let pairs: [(i64, u64); 5] = [(0, 0), (-1, 1), (1, 2), (-2, 3), (i64::MIN, u64::MAX)];
+        for (v, z) in pairs {
+            assert_eq!(zigzag_i64(v), z);
+            assert_eq!(unzigzag_i64(z), v);
+        }
+        assert_eq!(zigzag_i64(i64::MAX), u64::MAX - 1);
+        assert_eq!(zigzag_i128(-1), 1);
+        assert_eq!(zigzag_i128(i128::MIN), u128::MAX);
+        assert_eq!(unzigzag_i128(u128::MAX - 1), i128::MAX);
+    }
+
+    /// The node keeps contract state as `String` (lossy UTF-8): encoded
+    /// values must come back unchanged
+    #[test]
+    fn test_signed_encoding_survives_string_state() {
+        let through_state = |s: String| String::from_utf8_lossy(s.as_bytes()).into_owned();
+        for v in [0i64, 1, -1, 42, -42, i64::MAX, i64::MIN] {
+            let stored = through_state(encode_i64(v));
+            assert_eq!(stored.len(), 16);
+            assert_eq!(decode_i64(&stored), Some(v));
+        }
+        for v in [0i128, -1, 1 << 100, -(1 << 100), i128::MAX, i128::MIN] {
+            let stored = through_state(encode_i128(v));
+            assert_eq!(stored.len(), 32);
+            assert_eq!(decode_i128(&stored), Some(v));
+        }
+        // Raw little-endian bytes of a negative number do not survive
+        let raw = (-1i64).to_le_bytes();
+        assert_ne!(String::from_utf8_lossy(&raw).as_bytes(), raw);
+
+        assert_eq!(decode_i64("-42"), None);
+        assert_eq!(decode_i64(&encode_i128(5)), None);
+        assert_eq!(decode_i128("zz"), None);
+        assert_eq!(decode_i64("+00000000000002a"), None);
+    }
+}
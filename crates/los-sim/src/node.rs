@@ -0,0 +1,776 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - SIMULATED VALIDATOR
+//
+// One validator: a los-core Ledger, the ABFTConsensus engine (view, leader
+// rotation, f and the 2f+1 quorum, MAC secret, finality stats) and the
+// replica logic around it:
+//
+//   Proposal    leader of the view → all: a consensus block of client txs
+//   Prepare     the proposal extends our head and its txs apply cleanly
+//   Commit      2f+1 Prepares seen for it; the sender locks on the block
+//   finalize    2f+1 Commits → apply the txs, move to the next height
+//   ViewChange  no finality within the view timeout; carries the lock so
+//               the next leader re-proposes the highest one it collects
+//   Checkpoint  every checkpoint_interval heights, a signature over
+//               (height, block hash, state root); 2f+1 matching → final
+//
+// Votes count per (view, block hash), so two blocks at one height cannot
+// both reach a quorum without an honest validator voting twice.
+// A Behavior changes only what the node sends, never how it judges what
+// it receives.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::net::NodeId;
+use los_consensus::abft::{
+    ABFTConsensus, Block as ConsensusBlock, ConsensusMessage, ConsensusMessageType, ValidatorState,
+};
+use los_consensus::checkpoint::{CheckpointSignature, FinalityCheckpoint, PendingCheckpoint};
+use los_core::{Block, Ledger};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// MAC secret shared by the simulated validators
+pub const SIM_SECRET: &[u8] = b"los-sim-consensus-secret";
+
+/// Unix time of the simulated genesis; consensus time is this + ticks / 1000
+pub const GENESIS_TIME: u64 = 1_771_000_000;
+
+/// Longest view timeout after repeated failed views (timeout << 6)
+const MAX_BACKOFF_SHIFT: u32 = 6;
+
+/// Address of validator `id`
+pub fn node_address(id: NodeId) -> String {
+    format!("LOS_SIM_{:03}", id)
+}
+
+/// Consensus time (Unix seconds) at simulation tick `tick`
+pub fn consensus_time(tick: u64) -> u64 {
+    GENESIS_TIME + tick / 1_000
+}
+
+/// Stand-in checkpoint signature: SHA3-256(tag ‖ address ‖ data). The
+/// simulation exercises quorum logic, not Dilithium5.
+pub fn sim_signature(address: &str, data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"LOS-SIM-CHECKPOINT-SIG");
+    hasher.update(address.as_bytes());
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// What a validator does with its own messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behavior {
+    Honest,
+    /// Crashed from the start: sends nothing
+    Silent,
+    /// As leader, proposes one block to half of the validators and another
+    /// to the rest; as voter, prepares and commits everything it sees
+    Equivocate,
+    /// Follows the protocol but never sends Prepare, Commit or ViewChange
+    WithholdVotes,
+    /// Every outgoing message is delayed by this many extra ticks
+    Delay(u64),
+    /// Signs checkpoints over a corrupted state root
+    CorruptCheckpoints,
+}
+
+impl Behavior {
+    pub fn is_honest(&self) -> bool {
+        *self == Behavior::Honest
+    }
+}
+
+/// A block a validator committed to, and the view it saw the quorum in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    pub view: u64,
+    pub block: ConsensusBlock,
+}
+
+/// Consensus traffic between validators
+#[derive(Debug, Clone)]
+pub enum Message {
+    Proposal {
+        msg: ConsensusMessage,
+        block: ConsensusBlock,
+    },
+    Prepare(ConsensusMessage),
+    /// Carries the block so a validator that got the other half of an
+    /// equivocation can still finalize the quorum's choice
+    Commit {
+        msg: ConsensusMessage,
+        block: ConsensusBlock,
+    },
+    /// `msg.view` is the view voted for, `msg.sequence` the sender's height
+    ViewChange {
+        msg: ConsensusMessage,
+        locked: Option<Lock>,
+    },
+    Checkpoint {
+        checkpoint: FinalityCheckpoint,
+        signature: CheckpointSignature,
+    },
+}
+
+impl Message {
+    /// Finalized height the message is built on: handled at that height,
+    /// buffered above it, stale below it
+    fn base_height(&self) -> u64 {
+        match self {
+            Message::Proposal { msg, .. } | Message::Prepare(msg) | Message::Commit { msg, .. } => {
+                msg.sequence.saturating_sub(1)
+            }
+            Message::ViewChange { msg, .. } => msg.sequence,
+            Message::Checkpoint { checkpoint, .. } => checkpoint.height,
+        }
+    }
+}
+
+/// A message to one validator (`to: Some`) or to all, sender included
+#[derive(Debug, Clone)]
+pub struct Outgoing {
+    pub to: Option<NodeId>,
+    pub msg: Message,
+}
+
+impl Outgoing {
+    fn all(msg: Message) -> Self {
+        Self { to: None, msg }
+    }
+}
+
+/// Votes and proposals of the height being decided
+#[derive(Debug, Default)]
+struct Round {
+    /// Proposal prepared in the current view
+    accepted: Option<ConsensusBlock>,
+    /// View this node proposed in (leader only)
+    proposed_view: Option<u64>,
+    /// View this node sent its Commit in
+    committed_view: Option<u64>,
+    locked: Option<Lock>,
+    /// Valid blocks seen in proposals and commits, by hash
+    known: BTreeMap<String, ConsensusBlock>,
+    prepares: BTreeMap<(u64, String), BTreeSet<String>>,
+    commits: BTreeMap<(u64, String), BTreeSet<String>>,
+    view_changes: BTreeMap<u64, BTreeMap<String, Option<Lock>>>,
+    /// (type, view, hash) this node already voted (equivocators vote often)
+    voted: BTreeSet<(u8, u64, String)>,
+}
+
+/// One simulated validator
+#[derive(Debug)]
+pub struct SimNode {
+    pub id: NodeId,
+    pub address: String,
+    pub behavior: Behavior,
+    pub engine: ABFTConsensus,
+    pub ledger: Ledger,
+    /// Finalized consensus blocks; `finalized[h - 1]` is height h
+    pub finalized: Vec<ConsensusBlock>,
+    /// Own state root at each checkpoint height
+    pub state_roots: BTreeMap<u64, String>,
+    /// Quorum-signed checkpoints matching this node's own block and root
+    pub checkpoints: BTreeMap<u64, FinalityCheckpoint>,
+    /// Quorum-signed checkpoints contradicting this node's block or root
+    pub checkpoint_conflicts: Vec<FinalityCheckpoint>,
+    /// Committed blocks this node could not apply (a safety violation)
+    pub faults: Vec<String>,
+    /// Messages refused: bad MAC, wrong leader, invalid block, bad signature
+    pub rejected: u64,
+    mempool: Vec<Block>,
+    view_timeout: u64,
+    checkpoint_interval: u64,
+    deadline: u64,
+    /// View changes since the last finalized block (timeout backoff)
+    failed_views: u32,
+    round: Round,
+    future: Vec<Message>,
+    pending_checkpoints: BTreeMap<String, PendingCheckpoint>,
+}
+
+impl SimNode {
+    pub fn new(
+        id: NodeId,
+        validators: usize,
+        behavior: Behavior,
+        genesis: Ledger,
+        view_timeout: u64,
+        checkpoint_interval: u64,
+    ) -> Self {
+        let address = node_address(id);
+        let mut engine = ABFTConsensus::new(address.clone(), validators);
+        engine.update_validator_set((0..validators).map(node_address).collect());
+        engine.set_shared_secret(SIM_SECRET.to_vec());
+        Self {
+            id,
+            address,
+            behavior,
+            engine,
+            ledger: genesis,
+            finalized: Vec::new(),
+            state_roots: BTreeMap::new(),
+            checkpoints: BTreeMap::new(),
+            checkpoint_conflicts: Vec::new(),
+            faults: Vec::new(),
+            rejected: 0,
+            mempool: Vec::new(),
+            view_timeout,
+            checkpoint_interval,
+            deadline: view_timeout,
+            failed_views: 0,
+            round: Round::default(),
+            future: Vec::new(),
+            pending_checkpoints: BTreeMap::new(),
+        }
+    }
+
+    /// Highest finalized height
+    pub fn height(&self) -> u64 {
+        self.finalized.len() as u64
+    }
+
+    /// Hash of the last finalized block ("0" before the first)
+    pub fn head_hash(&self) -> String {
+        self.finalized
+            .last()
+            .map(|b| b.calculate_hash())
+            .unwrap_or_else(|| "0".to_string())
+    }
+
+    /// Tick at which the current view times out
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    /// Client tx gossiped to this validator
+    pub fn submit(&mut self, tx: Block) {
+        self.mempool.push(tx);
+    }
+
+    fn quorum(&self) -> usize {
+        self.engine.get_statistics().quorum_threshold as usize
+    }
+
+    fn validators(&self) -> usize {
+        self.engine.validator_set.len()
+    }
+
+    fn message(
+        &self,
+        msg_type: ConsensusMessageType,
+        view: u64,
+        sequence: u64,
+        block_hash: String,
+    ) -> ConsensusMessage {
+        ConsensusMessage::new_with_secret(
+            msg_type,
+            view,
+            sequence,
+            block_hash,
+            self.address.clone(),
+            SIM_SECRET,
+        )
+    }
+
+    /// First action at tick `now`: the view-0 leader proposes height 1
+    pub fn start(&mut self, now: u64) -> Vec<Outgoing> {
+        self.deadline = now + self.view_timeout;
+        self.propose_if_leader(now, None)
+    }
+
+    pub fn on_timeout(&mut self, now: u64) -> Vec<Outgoing> {
+        if now < self.deadline {
+            return Vec::new();
+        }
+        let target = self.engine.view + 1;
+        self.vote_view_change(target, now)
+    }
+
+    pub fn on_message(&mut self, msg: Message, now: u64) -> Vec<Outgoing> {
+        let base = msg.base_height();
+        if base > self.height() {
+            self.future.push(msg);
+            return Vec::new();
+        }
+        // A checkpoint is judged against our own block at its height
+        if let Message::Checkpoint {
+            checkpoint,
+            signature,
+        } = msg
+        {
+            self.on_checkpoint(checkpoint, signature);
+            return Vec::new();
+        }
+        if base < self.height() {
+            return Vec::new();
+        }
+
+        let cm = match &msg {
+            Message::Proposal { msg, .. }
+            | Message::Prepare(msg)
+            | Message::Commit { msg, .. }
+            | Message::ViewChange { msg, .. } => msg,
+            Message::Checkpoint { .. } => unreachable!("handled above"),
+        };
+        if !cm.verify_mac_with_secret(SIM_SECRET) || !self.engine.validator_set.contains(&cm.sender)
+        {
+            self.rejected += 1;
+            return Vec::new();
+        }
+
+        match msg {
+            Message::Proposal { msg, block } => self.on_proposal(msg, block),
+            Message::Prepare(msg) => self.on_prepare(msg),
+            Message::Commit { msg, block } => self.on_commit(msg, block, now),
+            Message::ViewChange { msg, locked } => self.on_view_change(msg, locked, now),
+            Message::Checkpoint { .. } => Vec::new(),
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────
+    // Proposals and votes
+    // ─────────────────────────────────────────────────────────────────
+
+    fn on_proposal(&mut self, msg: ConsensusMessage, block: ConsensusBlock) -> Vec<Outgoing> {
+        if msg.view > self.engine.view {
+            // Leader of a view we have not reached yet
+            self.future.push(Message::Proposal { msg, block });
+            return Vec::new();
+        }
+        if msg.view < self.engine.view || msg.sender != self.engine.get_leader(msg.view) {
+            self.rejected += 1;
+            return Vec::new();
+        }
+        if self.check_proposal(&msg, &block).is_err() {
+            self.rejected += 1;
+            return Vec::new();
+        }
+        // The new leader's proposal is the end of the view change for us
+        if self.engine.state == ValidatorState::ViewChanging {
+            let _ = self.engine.complete_view_change(msg.view);
+        }
+        let hash = msg.block_hash.clone();
+        self.round.known.insert(hash.clone(), block.clone());
+
+        if self.behavior == Behavior::Equivocate {
+            return self.vote(ConsensusMessageType::Prepare, msg.view, &hash, None);
+        }
+        if self.round.accepted.is_some() {
+            return Vec::new();
+        }
+        if let Some(lock) = &self.round.locked {
+            if lock.block.calculate_hash() != hash {
+                self.rejected += 1;
+                return Vec::new();
+            }
+        }
+        self.round.accepted = Some(block);
+        let mut out = self.vote(ConsensusMessageType::Prepare, msg.view, &hash, None);
+        // Prepares of this view may have arrived before the proposal
+        out.extend(self.after_prepares(msg.view, &hash));
+        out
+    }
+
+    /// Hash, height, parent and txs of a proposal
+    fn check_proposal(&self, msg: &ConsensusMessage, block: &ConsensusBlock) -> Result<(), String> {
+        if block.calculate_hash() != msg.block_hash {
+            return Err("block hash does not match the message".to_string());
+        }
+        if block.height != self.height() + 1 || msg.sequence != block.height {
+            return Err(format!("height {} is not next", block.height));
+        }
+        if block.parent_hash != self.head_hash() {
+            return Err("parent is not our head".to_string());
+        }
+        let mut ledger = self.ledger.clone();
+        apply_txs(&mut ledger, block).map(|_| ())
+    }
+
+    fn on_prepare(&mut self, msg: ConsensusMessage) -> Vec<Outgoing> {
+        self.round
+            .prepares
+            .entry((msg.view, msg.block_hash.clone()))
+            .or_default()
+            .insert(msg.sender);
+        self.after_prepares(msg.view, &msg.block_hash)
+    }
+
+    /// React to a (view, hash) prepare count reaching quorum
+    fn after_prepares(&mut self, view: u64, hash: &str) -> Vec<Outgoing> {
+        let count = self
+            .round
+            .prepares
+            .get(&(view, hash.to_string()))
+            .map_or(0, |s| s.len());
+        if count < self.quorum() {
+            return Vec::new();
+        }
+        let Some(block) = self.round.known.get(hash).cloned() else {
+            return Vec::new();
+        };
+
+        // A quorum in a later view than our lock supersedes it
+        if let Some(lock) = &self.round.locked {
+            if lock.view < view && lock.block.calculate_hash() != hash {
+                self.round.locked = Some(Lock {
+                    view,
+                    block: block.clone(),
+                });
+            }
+        }
+
+        if self.behavior == Behavior::Equivocate {
+            return self.vote(ConsensusMessageType::Commit, view, hash, Some(block));
+        }
+        let accepted_here = view == self.engine.view
+            && self
+                .round
+                .accepted
+                .as_ref()
+                .is_some_and(|b| b.calculate_hash() == hash);
+        if !accepted_here || self.round.committed_view == Some(view) {
+            return Vec::new();
+        }
+        self.round.committed_view = Some(view);
+        self.round.locked = Some(Lock {
+            view,
+            block: block.clone(),
+        });
+        self.vote(ConsensusMessageType::Commit, view, hash, Some(block))
+    }
+
+    fn on_commit(
+        &mut self,
+        msg: ConsensusMessage,
+        block: ConsensusBlock,
+        now: u64,
+    ) -> Vec<Outgoing> {
+        if block.calculate_hash() != msg.block_hash {
+            self.rejected += 1;
+            return Vec::new();
+        }
+        let hash = msg.block_hash.clone();
+        self.round.known.entry(hash.clone()).or_insert(block);
+        let voters = self
+            .round
+            .commits
+            .entry((msg.view, hash.clone()))
+            .or_default();
+        voters.insert(msg.sender);
+        let voters = voters.len();
+        if voters >= self.quorum() {
+            return self.finalize(&hash, voters, now);
+        }
+        Vec::new()
+    }
+
+    /// Broadcast a Prepare or Commit once per (view, hash)
+    fn vote(
+        &mut self,
+        msg_type: ConsensusMessageType,
+        view: u64,
+        hash: &str,
+        block: Option<ConsensusBlock>,
+    ) -> Vec<Outgoing> {
+        if self.behavior == Behavior::WithholdVotes {
+            return Vec::new();
+        }
+        let kind = match msg_type {
+            ConsensusMessageType::Prepare => 0,
+            _ => 1,
+        };
+        if !self.round.voted.insert((kind, view, hash.to_string())) {
+            return Vec::new();
+        }
+        let msg = self.message(msg_type, view, self.height() + 1, hash.to_string());
+        let msg = match block {
+            Some(block) => Message::Commit { msg, block },
+            None => Message::Prepare(msg),
+        };
+        vec![Outgoing::all(msg)]
+    }
+
+    // ─────────────────────────────────────────────────────────────────
+    // Finality and checkpoints
+    // ─────────────────────────────────────────────────────────────────
+
+    fn finalize(&mut self, hash: &str, voters: usize, now: u64) -> Vec<Outgoing> {
+        let Some(block) = self.round.known.get(hash).cloned() else {
+            return Vec::new();
+        };
+        let mut ledger = self.ledger.clone();
+        let included = match apply_txs(&mut ledger, &block) {
+            Ok(txs) => txs,
+            Err(e) => {
+                self.faults.push(format!(
+                    "{}: committed block {} at height {} does not apply: {}",
+                    self.address,
+                    &hash[..16.min(hash.len())],
+                    block.height,
+                    e
+                ));
+                return Vec::new();
+            }
+        };
+        self.ledger = ledger;
+        self.finalized.push(block);
+        self.engine.record_external_finalization(voters);
+        let included: BTreeSet<String> = included.iter().map(|tx| tx.calculate_hash()).collect();
+        self.mempool
+            .retain(|tx| !included.contains(&tx.calculate_hash()));
+        self.round = Round::default();
+        self.failed_views = 0;
+        self.deadline = now + self.view_timeout;
+
+        let mut out = Vec::new();
+        let height = self.height();
+        if self.checkpoint_interval > 0 && height.is_multiple_of(self.checkpoint_interval) {
+            out.extend(self.sign_checkpoint(height, hash));
+        }
+        out.extend(self.propose_if_leader(now, None));
+        out.extend(self.replay_future(now));
+        out
+    }
+
+    fn sign_checkpoint(&mut self, height: u64, block_hash: &str) -> Option<Outgoing> {
+        let root = self.ledger.compute_state_root();
+        self.state_roots.insert(height, root.clone());
+        let signed_root = if self.behavior == Behavior::CorruptCheckpoints {
+            hex::encode(Sha3_256::digest(format!("corrupt:{}", root)))
+        } else {
+            root
+        };
+        let checkpoint = FinalityCheckpoint::new(
+            height,
+            block_hash.to_string(),
+            self.validators() as u32,
+            signed_root,
+            Vec::new(),
+        );
+        let signature = CheckpointSignature {
+            validator_address: self.address.clone(),
+            signature: sim_signature(&self.address, &checkpoint.signing_data()),
+        };
+        Some(Outgoing::all(Message::Checkpoint {
+            checkpoint,
+            signature,
+        }))
+    }
+
+    fn on_checkpoint(&mut self, checkpoint: FinalityCheckpoint, signature: CheckpointSignature) {
+        // validator_count is the signer's claim: quorum is always over the full set
+        if checkpoint.validator_count as usize != self.validators()
+            || checkpoint.height == 0
+            || !checkpoint
+                .height
+                .is_multiple_of(self.checkpoint_interval.max(1))
+            || !self
+                .engine
+                .validator_set
+                .contains(&signature.validator_address)
+            || sim_signature(&signature.validator_address, &checkpoint.signing_data())
+                != signature.signature
+        {
+            self.rejected += 1;
+            return;
+        }
+        let id = checkpoint.calculate_id();
+        let pending = self
+            .pending_checkpoints
+            .entry(id.clone())
+            .or_insert_with(|| {
+                PendingCheckpoint::new(FinalityCheckpoint {
+                    signatures: Vec::new(),
+                    signature_count: 0,
+                    ..checkpoint
+                })
+            });
+        if !pending.add_signature(signature) || !pending.has_quorum() {
+            return;
+        }
+        let final_cp = pending.checkpoint.clone();
+        let height = final_cp.height;
+        let known = self
+            .checkpoints
+            .get(&height)
+            .is_some_and(|c| c.calculate_id() == id)
+            || self
+                .checkpoint_conflicts
+                .iter()
+                .any(|c| c.calculate_id() == id);
+        if known {
+            return;
+        }
+        let own_block = self.finalized[height as usize - 1].calculate_hash();
+        if final_cp.block_hash == own_block
+            && self.state_roots.get(&height) == Some(&final_cp.state_root)
+        {
+            self.checkpoints.insert(height, final_cp);
+        } else {
+            self.checkpoint_conflicts.push(final_cp);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────
+    // Leader and view changes
+    // ─────────────────────────────────────────────────────────────────
+
+    /// Propose the next height if we lead the current view. `lock` is the
+    /// highest lock collected by the view change, re-proposed as is.
+    fn propose_if_leader(&mut self, now: u64, lock: Option<Lock>) -> Vec<Outgoing> {
+        let view = self.engine.view;
+        if self.engine.get_leader(view) != self.address
+            || self.round.proposed_view == Some(view)
+            || self.behavior == Behavior::Silent
+        {
+            return Vec::new();
+        }
+        self.round.proposed_view = Some(view);
+        let first = match lock.or_else(|| self.round.locked.clone()) {
+            Some(lock) => lock.block,
+            None => self.build_block(now, false),
+        };
+        let proposal = |node: &Self, block: ConsensusBlock| Message::Proposal {
+            msg: node.message(
+                ConsensusMessageType::PrePrepare,
+                view,
+                block.height,
+                block.calculate_hash(),
+            ),
+            block,
+        };
+
+        if self.behavior != Behavior::Equivocate {
+            return vec![Outgoing::all(proposal(self, first))];
+        }
+        // Same parent, other tx order: conflicting sends land on opposite sides
+        let mut second = self.build_block(now, true);
+        if second == first {
+            second.timestamp += 1;
+        }
+        let n = self.validators();
+        let mut out: Vec<Outgoing> = (0..n)
+            .map(|to| Outgoing {
+                to: Some(to),
+                msg: proposal(
+                    self,
+                    if to < n / 2 {
+                        first.clone()
+                    } else {
+                        second.clone()
+                    },
+                ),
+            })
+            .collect();
+        for block in [first, second] {
+            let hash = block.calculate_hash();
+            self.round.known.insert(hash.clone(), block);
+            out.extend(self.vote(ConsensusMessageType::Prepare, view, &hash, None));
+        }
+        out
+    }
+
+    /// Next block from the mempool: every tx that applies on top of the
+    /// previous ones, in mempool order (`reversed`: newest first)
+    fn build_block(&self, now: u64, reversed: bool) -> ConsensusBlock {
+        let mut ledger = self.ledger.clone();
+        let consensus_now = consensus_time(now);
+        let mut candidates: Vec<&Block> = self.mempool.iter().collect();
+        if reversed {
+            candidates.reverse();
+        }
+        let txs: Vec<&Block> = candidates
+            .into_iter()
+            .filter(|tx| ledger.process_block_at(tx, consensus_now).is_ok())
+            .collect();
+        ConsensusBlock {
+            height: self.height() + 1,
+            timestamp: now,
+            data: serde_json::to_vec(&txs).unwrap_or_default(),
+            proposer: self.address.clone(),
+            parent_hash: self.head_hash(),
+        }
+    }
+
+    fn vote_view_change(&mut self, target: u64, now: u64) -> Vec<Outgoing> {
+        // initiate_view_change moves one view up; jump first when joining a later one
+        self.engine.view = target - 1;
+        let msg = match self.engine.initiate_view_change() {
+            Ok(m) => m,
+            Err(_) => return Vec::new(),
+        };
+        self.round.accepted = None;
+        self.round.committed_view = None;
+        self.deadline = now + (self.view_timeout << self.failed_views.min(MAX_BACKOFF_SHIFT));
+        self.failed_views += 1;
+        if self.behavior == Behavior::WithholdVotes {
+            return Vec::new();
+        }
+        // initiate_view_change signs with the engine's view and sequence
+        // (= our finalized height), which is what receivers bucket by
+        vec![Outgoing::all(Message::ViewChange {
+            msg,
+            locked: self.round.locked.clone(),
+        })]
+    }
+
+    fn on_view_change(
+        &mut self,
+        msg: ConsensusMessage,
+        locked: Option<Lock>,
+        now: u64,
+    ) -> Vec<Outgoing> {
+        let target = msg.view;
+        let done = target < self.engine.view
+            || (target == self.engine.view && self.engine.state != ValidatorState::ViewChanging);
+        if done {
+            return Vec::new();
+        }
+        let votes = self.round.view_changes.entry(target).or_default();
+        votes.insert(msg.sender, locked);
+        let count = votes.len();
+
+        let mut out = Vec::new();
+        // f+1 validators want a later view: at least one honest one timed out
+        if target > self.engine.view && count > self.engine.f_max_faulty {
+            out.extend(self.vote_view_change(target, now));
+            out.extend(self.replay_future(now));
+        }
+        if count < self.quorum() || self.engine.view != target {
+            return out;
+        }
+        let _ = self.engine.complete_view_change(target);
+        let highest = self.round.view_changes[&target]
+            .values()
+            .flatten()
+            .chain(self.round.locked.iter())
+            .max_by_key(|lock| lock.view)
+            .cloned();
+        out.extend(self.propose_if_leader(now, highest));
+        out.extend(self.replay_future(now));
+        out
+    }
+
+    /// Re-deliver buffered messages that became current
+    fn replay_future(&mut self, now: u64) -> Vec<Outgoing> {
+        let buffered = std::mem::take(&mut self.future);
+        let mut out = Vec::new();
+        for msg in buffered {
+            out.extend(self.on_message(msg, now));
+        }
+        out
+    }
+}
+
+/// Apply the txs of `block` in order; the applied txs on success
+fn apply_txs(ledger: &mut Ledger, block: &ConsensusBlock) -> Result<Vec<Block>, String> {
+    let txs: Vec<Block> =
+        serde_json::from_slice(&block.data).map_err(|e| format!("undecodable txs: {}", e))?;
+    let consensus_now = consensus_time(block.timestamp);
+    for tx in &txs {
+        ledger.process_block_at(tx, consensus_now)?;
+    }
+    Ok(txs)
+}
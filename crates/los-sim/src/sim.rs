@@ -0,0 +1,314 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - SIMULATION DRIVER
+//
+// Runs N SimNodes over one Network, single-threaded: the next event is the
+// earliest of the next delivery and the earliest view timeout. Silent
+// validators are never scheduled.
+//
+// Properties, checked over the honest validators only:
+//   safety    same block at every height two validators both finalized,
+//             same ledger state root at the same height, no checkpoint
+//             contradicting a validator's own state, no committed block
+//             that fails to apply, and no CIL created or destroyed
+//   liveness  every honest validator reached the target height
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::net::{Network, NodeId};
+use crate::node::{Behavior, Outgoing, SimNode};
+use los_core::{AccountState, Block, BlockType, Ledger};
+use std::collections::BTreeMap;
+
+/// Scenario parameters
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub validators: usize,
+    pub seed: u64,
+    /// One-way latency in ticks (1 tick = 1 ms)
+    pub latency: u64,
+    /// Uniform extra latency 0..=jitter per message
+    pub jitter: u64,
+    /// Ticks without finality before a validator votes for the next view
+    pub view_timeout: u64,
+    /// Heights between checkpoints (0 = none)
+    pub checkpoint_interval: u64,
+    /// Non-honest validators
+    pub behaviors: BTreeMap<NodeId, Behavior>,
+}
+
+impl SimConfig {
+    pub fn new(validators: usize) -> Self {
+        Self {
+            validators,
+            seed: 1,
+            latency: 20,
+            jitter: 30,
+            view_timeout: 1_000,
+            checkpoint_interval: 2,
+            behaviors: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_behavior(mut self, node: NodeId, behavior: Behavior) -> Self {
+        self.behaviors.insert(node, behavior);
+        self
+    }
+
+    /// f: Byzantine validators the quorum tolerates, (n - 1) / 3
+    pub fn max_faulty(&self) -> usize {
+        self.validators.saturating_sub(1) / 3
+    }
+}
+
+/// Outcome of a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ticks: u64,
+    pub messages: u64,
+    /// Lowest and highest finalized height among honest validators
+    pub min_height: u64,
+    pub max_height: u64,
+    /// Most view changes seen by an honest validator
+    pub view_changes: u64,
+    /// Fewest finalized checkpoints among honest validators
+    pub checkpoints: usize,
+}
+
+pub struct Simulation {
+    pub config: SimConfig,
+    pub nodes: Vec<SimNode>,
+    net: Network,
+    genesis_supply: u128,
+    started: bool,
+}
+
+impl Simulation {
+    /// Validators sharing a genesis ledger that funds `genesis` accounts
+    pub fn new(config: SimConfig, genesis: &[(String, u128)]) -> Self {
+        let mut ledger = Ledger::new();
+        for (address, balance) in genesis {
+            ledger.accounts.insert(
+                address.clone(),
+                AccountState {
+                    head: "0".to_string(),
+                    balance: *balance,
+                    block_count: 0,
+                    is_validator: false,
+                },
+            );
+        }
+        let nodes = (0..config.validators)
+            .map(|id| {
+                let behavior = config
+                    .behaviors
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(Behavior::Honest);
+                SimNode::new(
+                    id,
+                    config.validators,
+                    behavior,
+                    ledger.clone(),
+                    config.view_timeout,
+                    config.checkpoint_interval,
+                )
+            })
+            .collect();
+        Self {
+            net: Network::new(config.seed, config.latency, config.jitter),
+            genesis_supply: genesis.iter().map(|(_, b)| b).sum(),
+            config,
+            nodes,
+            started: false,
+        }
+    }
+
+    /// Gossip a client tx to every validator's mempool
+    pub fn submit(&mut self, tx: Block) {
+        for node in &mut self.nodes {
+            node.submit(tx.clone());
+        }
+    }
+
+    pub fn honest(&self) -> impl Iterator<Item = &SimNode> {
+        self.nodes.iter().filter(|n| n.behavior.is_honest())
+    }
+
+    /// Run until every honest validator finalized `height`, or until tick
+    /// `max_ticks`, whichever comes first
+    pub fn run_until(&mut self, height: u64, max_ticks: u64) -> Report {
+        if !self.started {
+            self.started = true;
+            for id in self.scheduled() {
+                let out = self.nodes[id].start(self.net.now());
+                self.dispatch(id, out);
+            }
+        }
+        while self.honest().any(|n| n.height() < height) {
+            let next_msg = self.net.peek_time().unwrap_or(u64::MAX);
+            let next_timer = self
+                .scheduled()
+                .into_iter()
+                .map(|id| self.nodes[id].deadline())
+                .min()
+                .unwrap_or(u64::MAX);
+            if next_msg.min(next_timer) > max_ticks {
+                break;
+            }
+            if next_timer < next_msg {
+                self.net.advance_to(next_timer);
+                let now = self.net.now();
+                for id in self.scheduled() {
+                    if self.nodes[id].deadline() <= now {
+                        let out = self.nodes[id].on_timeout(now);
+                        self.dispatch(id, out);
+                    }
+                }
+                continue;
+            }
+            let Some(env) = self.net.deliver() else {
+                break;
+            };
+            if self.nodes[env.to].behavior == Behavior::Silent {
+                continue;
+            }
+            let now = self.net.now();
+            let out = self.nodes[env.to].on_message(env.msg, now);
+            self.dispatch(env.to, out);
+        }
+        self.report()
+    }
+
+    fn scheduled(&self) -> Vec<NodeId> {
+        (0..self.nodes.len())
+            .filter(|&id| self.nodes[id].behavior != Behavior::Silent)
+            .collect()
+    }
+
+    fn dispatch(&mut self, from: NodeId, out: Vec<Outgoing>) {
+        let extra = match self.nodes[from].behavior {
+            Behavior::Delay(ticks) => ticks,
+            _ => 0,
+        };
+        for o in out {
+            match o.to {
+                Some(to) => self.net.send(from, to, o.msg, extra),
+                None => {
+                    for to in 0..self.nodes.len() {
+                        self.net.send(from, to, o.msg.clone(), extra);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn report(&self) -> Report {
+        let heights = || self.honest().map(|n| n.height());
+        Report {
+            ticks: self.net.now(),
+            messages: self.net.delivered(),
+            min_height: heights().min().unwrap_or(0),
+            max_height: heights().max().unwrap_or(0),
+            view_changes: self
+                .honest()
+                .map(|n| n.engine.view_changes)
+                .max()
+                .unwrap_or(0),
+            checkpoints: self
+                .honest()
+                .map(|n| n.checkpoints.len())
+                .min()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Safety properties over the honest validators
+    pub fn check_safety(&self) -> Result<(), String> {
+        let honest: Vec<&SimNode> = self.honest().collect();
+        for node in &honest {
+            if let Some(fault) = node.faults.first() {
+                return Err(fault.clone());
+            }
+            if let Some(cp) = node.checkpoint_conflicts.first() {
+                return Err(format!(
+                    "{}: quorum checkpoint at height {} contradicts its own state",
+                    node.address, cp.height
+                ));
+            }
+            let supply = ledger_supply(&node.ledger);
+            if supply != self.genesis_supply {
+                return Err(format!(
+                    "{}: ledger holds {} CIL, genesis had {}",
+                    node.address, supply, self.genesis_supply
+                ));
+            }
+        }
+        for (i, a) in honest.iter().enumerate() {
+            for b in &honest[i + 1..] {
+                let common = a.finalized.len().min(b.finalized.len());
+                for h in 0..common {
+                    if a.finalized[h].calculate_hash() != b.finalized[h].calculate_hash() {
+                        return Err(format!(
+                            "{} and {} finalized different blocks at height {}",
+                            a.address,
+                            b.address,
+                            h + 1
+                        ));
+                    }
+                }
+                if a.height() == b.height()
+                    && a.ledger.compute_state_root() != b.ledger.compute_state_root()
+                {
+                    return Err(format!(
+                        "{} and {} diverged: different state roots at height {}",
+                        a.address,
+                        b.address,
+                        a.height()
+                    ));
+                }
+                for (height, cp) in &a.checkpoints {
+                    if let Some(other) = b.checkpoints.get(height) {
+                        if other.calculate_id() != cp.calculate_id() {
+                            return Err(format!(
+                                "{} and {} finalized different checkpoints at height {}",
+                                a.address, b.address, height
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every honest validator finalized at least `height`
+    pub fn check_liveness(&self, height: u64) -> Result<(), String> {
+        match self.honest().find(|n| n.height() < height) {
+            Some(n) => Err(format!(
+                "{} stuck at height {} (target {})",
+                n.address,
+                n.height(),
+                height
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Balances + collected fees + sent-but-unreceived amounts
+fn ledger_supply(ledger: &Ledger) -> u128 {
+    let balances: u128 = ledger.accounts.values().map(|a| a.balance).sum();
+    let in_flight: u128 = ledger
+        .blocks
+        .iter()
+        .filter(|(hash, b)| {
+            b.block_type == BlockType::Send && !ledger.claimed_sends.contains(*hash)
+        })
+        .map(|(_, b)| b.amount)
+        .sum();
+    balances + ledger.accumulated_fees_cil + in_flight
+}
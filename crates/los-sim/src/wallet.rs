@@ -0,0 +1,48 @@
+// Client accounts of a scenario: real Dilithium5 keys, so the simulated
+// ledgers run the same PoW and signature checks as a node.
+
+use crate::node::GENESIS_TIME;
+use los_core::{Block, BlockType};
+use los_crypto::KeyPair;
+
+/// A funded client account
+pub struct SimAccount {
+    pub keypair: KeyPair,
+    pub address: String,
+}
+
+impl SimAccount {
+    /// Deterministic account `n` of a scenario
+    pub fn from_index(n: u8) -> Self {
+        let keypair = los_crypto::generate_keypair_from_seed(&[n; 64]);
+        let address = los_crypto::public_key_to_address(&keypair.public_key);
+        Self { keypair, address }
+    }
+
+    /// Signed Send on top of `previous` with PoW solved. Does not track the
+    /// head, so conflicting sends (a double spend) share one `previous`.
+    pub fn send(&self, previous: &str, to: &str, amount_cil: u128) -> Block {
+        let mut block = Block {
+            account: self.address.clone(),
+            previous: previous.to_string(),
+            block_type: BlockType::Send,
+            amount: amount_cil,
+            link: to.to_string(),
+            signature: String::new(),
+            public_key: hex::encode(&self.keypair.public_key),
+            work: 0,
+            timestamp: GENESIS_TIME,
+            fee: los_core::BASE_FEE_CIL,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        while !block.verify_pow() {
+            block.work += 1;
+        }
+        let signature =
+            los_crypto::sign_message(block.signing_hash().as_bytes(), &self.keypair.secret_key)
+                .expect("signing with a generated key");
+        block.signature = hex::encode(signature);
+        block
+    }
+}
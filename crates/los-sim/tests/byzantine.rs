@@ -0,0 +1,175 @@
+// Byzantine scenarios: up to f faulty validators must cost neither safety
+// nor liveness; f + 1 may stop progress but must never fork the honest ones.
+
+use los_core::CIL_PER_LOS;
+use los_sim::{Behavior, SimAccount, SimConfig, Simulation};
+
+const MAX_TICKS: u64 = 120_000;
+
+struct Clients {
+    alice: SimAccount,
+    bob: SimAccount,
+    carol: SimAccount,
+}
+
+fn clients() -> Clients {
+    Clients {
+        alice: SimAccount::from_index(1),
+        bob: SimAccount::from_index(2),
+        carol: SimAccount::from_index(3),
+    }
+}
+
+fn simulation(config: SimConfig, c: &Clients) -> Simulation {
+    Simulation::new(
+        config,
+        &[
+            (c.alice.address.clone(), 100 * CIL_PER_LOS),
+            (c.bob.address.clone(), 100 * CIL_PER_LOS),
+        ],
+    )
+}
+
+fn assert_safe_and_live(sim: &Simulation, height: u64) {
+    sim.check_safety().unwrap();
+    sim.check_liveness(height).unwrap();
+}
+
+#[test]
+fn test_honest_network_finalizes_and_checkpoints() {
+    let c = clients();
+    let mut sim = simulation(SimConfig::new(4), &c);
+    let first = c.alice.send("0", &c.carol.address, 5 * CIL_PER_LOS);
+    let second = c
+        .alice
+        .send(&first.calculate_hash(), &c.bob.address, CIL_PER_LOS);
+    sim.submit(first);
+    sim.submit(second);
+
+    // Checkpoint 4 is signed once height 4 is final; height 5 gives it time
+    let report = sim.run_until(5, MAX_TICKS);
+    assert_safe_and_live(&sim, 5);
+    assert_eq!(report.view_changes, 0);
+    assert_eq!(report.checkpoints, 2);
+    for node in sim.honest() {
+        assert_eq!(node.ledger.accounts[&c.alice.address].block_count, 2);
+    }
+}
+
+#[test]
+fn test_silent_leader_is_replaced() {
+    let c = clients();
+    // Validator 0 leads view 0
+    let mut sim = simulation(SimConfig::new(4).with_behavior(0, Behavior::Silent), &c);
+    sim.submit(c.alice.send("0", &c.carol.address, CIL_PER_LOS));
+
+    let report = sim.run_until(3, MAX_TICKS);
+    assert_safe_and_live(&sim, 3);
+    assert!(report.view_changes >= 1);
+}
+
+#[test]
+fn test_equivocating_leader_cannot_double_spend() {
+    let c = clients();
+    let config = SimConfig::new(4).with_behavior(0, Behavior::Equivocate);
+    let mut sim = simulation(config, &c);
+    // Two sends from one previous: at most one can ever apply
+    sim.submit(c.alice.send("0", &c.bob.address, 60 * CIL_PER_LOS));
+    sim.submit(c.alice.send("0", &c.carol.address, 60 * CIL_PER_LOS));
+
+    sim.run_until(3, MAX_TICKS);
+    assert_safe_and_live(&sim, 3);
+    for node in sim.honest() {
+        assert_eq!(node.ledger.accounts[&c.alice.address].block_count, 1);
+    }
+}
+
+#[test]
+fn test_withheld_votes_up_to_f() {
+    let c = clients();
+    let config = SimConfig::new(7)
+        .with_behavior(1, Behavior::WithholdVotes)
+        .with_behavior(4, Behavior::WithholdVotes);
+    let mut sim = simulation(config, &c);
+    sim.run_until(3, MAX_TICKS);
+    assert_safe_and_live(&sim, 3);
+}
+
+#[test]
+fn test_f_plus_one_withheld_votes_halt_but_never_fork() {
+    let c = clients();
+    let config = SimConfig::new(4)
+        .with_behavior(1, Behavior::WithholdVotes)
+        .with_behavior(2, Behavior::WithholdVotes);
+    assert_eq!(config.max_faulty(), 1);
+    let mut sim = simulation(config, &c);
+    let report = sim.run_until(1, 30_000);
+    assert_eq!(report.max_height, 0);
+    sim.check_safety().unwrap();
+    assert!(sim.check_liveness(1).is_err());
+}
+
+#[test]
+fn test_delayed_leader_and_voter() {
+    let c = clients();
+    let config = SimConfig::new(4)
+        // Slower than the view timeout: its views always fail
+        .with_behavior(0, Behavior::Delay(5_000))
+        .with_behavior(3, Behavior::Delay(300));
+    let mut sim = simulation(config, &c);
+    sim.submit(c.bob.send("0", &c.carol.address, CIL_PER_LOS));
+
+    let report = sim.run_until(3, MAX_TICKS);
+    assert_safe_and_live(&sim, 3);
+    assert!(report.view_changes >= 1);
+}
+
+#[test]
+fn test_corrupted_checkpoints_up_to_f() {
+    let c = clients();
+    let config = SimConfig::new(7)
+        .with_behavior(2, Behavior::CorruptCheckpoints)
+        .with_behavior(5, Behavior::CorruptCheckpoints);
+    let mut sim = simulation(config, &c);
+    sim.submit(c.alice.send("0", &c.carol.address, CIL_PER_LOS));
+
+    let report = sim.run_until(5, MAX_TICKS);
+    assert_safe_and_live(&sim, 5);
+    assert_eq!(report.checkpoints, 2);
+}
+
+#[test]
+fn test_f_plus_one_corrupted_checkpoints_never_finalize() {
+    let c = clients();
+    let config = SimConfig::new(4)
+        .with_behavior(0, Behavior::CorruptCheckpoints)
+        .with_behavior(1, Behavior::CorruptCheckpoints);
+    let mut sim = simulation(config, &c);
+
+    let report = sim.run_until(4, MAX_TICKS);
+    // Blocks still finalize; no checkpoint reaches 2f+1 honest signatures
+    assert_eq!(report.min_height, 4);
+    assert_eq!(report.checkpoints, 0);
+    sim.check_safety().unwrap();
+}
+
+#[test]
+fn test_mixed_faults_across_seeds() {
+    let c = clients();
+    let to_bob = c.alice.send("0", &c.bob.address, 60 * CIL_PER_LOS);
+    let to_carol = c.alice.send("0", &c.carol.address, 60 * CIL_PER_LOS);
+    for seed in 1..=4 {
+        let config = SimConfig::new(7)
+            .with_seed(seed)
+            .with_behavior(0, Behavior::Equivocate)
+            .with_behavior(3, Behavior::Silent);
+        let mut sim = simulation(config, &c);
+        sim.submit(to_bob.clone());
+        sim.submit(to_carol.clone());
+        sim.run_until(4, MAX_TICKS);
+        sim.check_safety()
+            .unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        sim.check_liveness(4)
+            .unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+    }
+}
@@ -0,0 +1,442 @@
+// Native implementations of the 23 UVM host functions imported by los-sdk.
+//
+// Signatures match the `extern "C"` block in los-sdk exactly; the linker
+// resolves the SDK's imports to these symbols when a contract's tests link
+// los-testing. Behaviour mirrors los-vm/src/host.rs (same limits, same
+// return codes). Nothing here may panic — unwinding out of an `extern "C"`
+// function aborts the test process.
+#![cfg(not(target_arch = "wasm32"))]
+// Only ever called by los-sdk, with pointers into live Rust slices.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::*;
+
+/// Borrow guest bytes, capped at `max`.
+unsafe fn read_bytes<'a>(ptr: *const u8, len: u32, max: usize) -> &'a [u8] {
+    let len = (len as usize).min(max);
+    if ptr.is_null() || len == 0 {
+        return &[];
+    }
+    std::slice::from_raw_parts(ptr, len)
+}
+
+unsafe fn read_string(ptr: *const u8, len: u32, max: usize) -> Option<String> {
+    String::from_utf8(read_bytes(ptr, len, max).to_vec()).ok()
+}
+
+/// Copy `data` into the guest buffer, capped by `out_max`.
+/// Returns bytes written (same as the UVM's write_guest_bytes).
+unsafe fn write_out(out_ptr: *mut u8, out_max: u32, data: &[u8]) -> i32 {
+    let n = data.len().min(out_max as usize);
+    if n == 0 || out_ptr.is_null() {
+        return 0;
+    }
+    std::ptr::copy_nonoverlapping(data.as_ptr(), out_ptr, n);
+    n as i32
+}
+
+fn byte_gas(len: usize) -> u64 {
+    (len as u64).saturating_mul(GAS_PER_BYTE)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_log(ptr: *const u8, len: u32) {
+    let Some(msg) = read_string(ptr, len, MAX_LOG_SIZE) else {
+        return;
+    };
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL + byte_gas(msg.len()));
+        if ex.logs.len() < MAX_LOGS {
+            ex.logs.push(msg);
+        }
+    });
+}
+
+/// Never returns inside a MockHost call: the execution is handed back to the
+/// harness and this worker thread parks forever (nothing may unwind through
+/// the contract's `extern "C"` entry point).
+#[no_mangle]
+pub unsafe extern "C" fn host_abort(ptr: *const u8, len: u32) {
+    let msg = read_string(ptr, len, MAX_LOG_SIZE).unwrap_or_default();
+    let Some(Active { mut ex, done }) = ACTIVE.with(|cell| cell.borrow_mut().take()) else {
+        eprintln!(
+            "⚠️ los-testing: host_abort called outside MockHost::call(): {}",
+            msg
+        );
+        return;
+    };
+    ex.charge(GAS_HOST_CALL);
+    ex.abort_message = Some(msg);
+    let _ = done.send((ex, -1));
+    loop {
+        std::thread::park();
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_set_state(
+    key_ptr: *const u8,
+    key_len: u32,
+    val_ptr: *const u8,
+    val_len: u32,
+) {
+    let Some(key) = read_string(key_ptr, key_len, MAX_STATE_KEY_SIZE) else {
+        return;
+    };
+    let val = read_bytes(val_ptr, val_len, MAX_STATE_VALUE_SIZE).to_vec();
+    with_execution(|ex| {
+        ex.charge(
+            GAS_HOST_CALL
+                + byte_gas(key.len())
+                + (val.len() as u64).saturating_mul(GAS_PER_STATE_BYTE_WRITTEN),
+        );
+        if key == SCHEMA_VERSION_KEY {
+            if let Err(e) = check_schema_version_write(ex.state.get(&key), Some(&val)) {
+                ex.abort_message.get_or_insert(e);
+                return;
+            }
+        }
+        if ex.dirty_keys.len() >= MAX_STATE_KEYS && !ex.dirty_keys.contains(&key) {
+            return;
+        }
+        ex.state.insert(key.clone(), val);
+        ex.dirty_keys.insert(key);
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_state(
+    key_ptr: *const u8,
+    key_len: u32,
+    out_ptr: *mut u8,
+    out_max: u32,
+) -> i32 {
+    let Some(key) = read_string(key_ptr, key_len, MAX_STATE_KEY_SIZE) else {
+        return -1;
+    };
+    let value = with_execution(|ex| {
+        let value = ex.state.get(&key).cloned();
+        let len = value.as_ref().map_or(0, Vec::len);
+        ex.charge(GAS_HOST_CALL + byte_gas(key.len() + len));
+        value
+    })
+    .flatten();
+    match value {
+        Some(v) => write_out(out_ptr, out_max, &v),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_del_state(key_ptr: *const u8, key_len: u32) {
+    let Some(key) = read_string(key_ptr, key_len, MAX_STATE_KEY_SIZE) else {
+        return;
+    };
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL + byte_gas(key.len()));
+        if key == SCHEMA_VERSION_KEY {
+            ex.abort_message
+                .get_or_insert_with(|| "schema version cannot be deleted".to_string());
+            return;
+        }
+        ex.state.remove(&key);
+        ex.dirty_keys.insert(key);
+    });
+}
+
+/// Same rules as the UVM's charge_event: charge `GAS_EVENT` plus
+/// `GAS_PER_EVENT_BYTE` per byte (sizes capped at the limits), then abort the
+/// call if the event is oversized or past MAX_EVENTS. Returns false if the
+/// event must not be recorded.
+fn charge_event(ex: &mut Execution, type_len: u32, topics_len: usize, data_len: u32) -> bool {
+    let (type_len, data_len) = (type_len as usize, data_len as usize);
+    let bytes = type_len.min(MAX_EVENT_TYPE_SIZE) + topics_len + data_len.min(MAX_EVENT_DATA_SIZE);
+    ex.charge(GAS_EVENT.saturating_add((bytes as u64).saturating_mul(GAS_PER_EVENT_BYTE)));
+    let rejection = if ex.events.len() >= MAX_EVENTS {
+        format!("too many events (max {} per call)", MAX_EVENTS)
+    } else if type_len > MAX_EVENT_TYPE_SIZE {
+        format!(
+            "event type too long: {} bytes (max {})",
+            type_len, MAX_EVENT_TYPE_SIZE
+        )
+    } else if data_len > MAX_EVENT_DATA_SIZE {
+        format!(
+            "event data too large: {} bytes (max {})",
+            data_len, MAX_EVENT_DATA_SIZE
+        )
+    } else {
+        return true;
+    };
+    ex.abort_message.get_or_insert(rejection);
+    false
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_emit_event(
+    type_ptr: *const u8,
+    type_len: u32,
+    data_ptr: *const u8,
+    data_len: u32,
+) {
+    with_execution(|ex| {
+        if !charge_event(ex, type_len, 0, data_len) {
+            return;
+        }
+        let Some(event_type) = read_string(type_ptr, type_len, MAX_EVENT_TYPE_SIZE) else {
+            return;
+        };
+        let Some(data) = read_string(data_ptr, data_len, MAX_EVENT_DATA_SIZE) else {
+            return;
+        };
+        ex.events.push(MockEvent {
+            event_type,
+            data,
+            topics: Vec::new(),
+        });
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_emit_event_indexed(
+    type_ptr: *const u8,
+    type_len: u32,
+    topics_ptr: *const u8,
+    topics_len: u32,
+    data_ptr: *const u8,
+    data_len: u32,
+) -> i32 {
+    // Longest valid list: MAX_EVENT_TOPICS values plus separators
+    let max_topics_len = MAX_EVENT_TOPICS * (MAX_TOPIC_SIZE + 1);
+    with_execution(|ex| {
+        if !charge_event(
+            ex,
+            type_len,
+            (topics_len as usize).min(max_topics_len),
+            data_len,
+        ) {
+            return 2;
+        }
+        if topics_len as usize > max_topics_len {
+            return 1;
+        }
+        let Some(event_type) = read_string(type_ptr, type_len, MAX_EVENT_TYPE_SIZE) else {
+            return 2;
+        };
+        let topics = match parse_topics(read_bytes(topics_ptr, topics_len, max_topics_len)) {
+            Some(t) => t,
+            None => return 1,
+        };
+        ex.charge(GAS_HOST_CALL * topics.len() as u64);
+        let Some(data) = read_string(data_ptr, data_len, MAX_EVENT_DATA_SIZE) else {
+            return 2;
+        };
+        ex.events.push(MockEvent {
+            event_type,
+            data,
+            topics,
+        });
+        0
+    })
+    .unwrap_or(2)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_transfer(
+    addr_ptr: *const u8,
+    addr_len: u32,
+    amount_lo: i64,
+    amount_hi: i64,
+) -> i32 {
+    let recipient = match read_string(addr_ptr, addr_len, 256) {
+        Some(a) if !a.is_empty() => a,
+        _ => return 2,
+    };
+    let amount = ((amount_hi as u64 as u128) << 64) | (amount_lo as u64 as u128);
+    with_execution(|ex| {
+        ex.charge(GAS_TRANSFER);
+        if amount == 0 {
+            return 0;
+        }
+        if ex.transfers.len() >= MAX_TRANSFERS {
+            return 3;
+        }
+        if ex.balance < amount {
+            return 1;
+        }
+        ex.balance -= amount;
+        ex.transfers.push((recipient, amount));
+        0
+    })
+    .unwrap_or(2)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_caller(out_ptr: *mut u8, out_max: u32) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.caller.clone()
+    }) {
+        Some(caller) => write_out(out_ptr, out_max, caller.as_bytes()),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_self_address(out_ptr: *mut u8, out_max: u32) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.self_address.clone()
+    }) {
+        Some(addr) => write_out(out_ptr, out_max, addr.as_bytes()),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn host_get_balance_lo() -> i64 {
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        (ex.balance & 0xFFFF_FFFF_FFFF_FFFF) as i64
+    })
+    .unwrap_or(0)
+}
+
+#[no_mangle]
+pub extern "C" fn host_get_balance_hi() -> i64 {
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        (ex.balance >> 64) as i64
+    })
+    .unwrap_or(0)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_attached_value(out_ptr: *mut u8) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.attached_value
+    }) {
+        Some(value) => write_out(out_ptr, 16, &value.to_le_bytes()),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_block_hash(out_ptr: *mut u8, out_max: u32) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.block_hash.clone()
+    }) {
+        Some(hash) => write_out(out_ptr, out_max, hash.as_bytes()),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_call_id(out_ptr: *mut u8) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_BLAKE3);
+        derive_call_id(&ex.block_hash, 0)
+    }) {
+        Some(id) => write_out(out_ptr, 32, &id),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn host_get_timestamp() -> i64 {
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.timestamp as i64
+    })
+    .unwrap_or(0)
+}
+
+#[no_mangle]
+pub extern "C" fn host_get_arg_count() -> i32 {
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL);
+        ex.args.len() as i32
+    })
+    .unwrap_or(0)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_arg(idx: i32, out_ptr: *mut u8, out_max: u32) -> i32 {
+    let arg = with_execution(|ex| {
+        let arg = usize::try_from(idx)
+            .ok()
+            .and_then(|i| ex.args.get(i))
+            .cloned();
+        ex.charge(GAS_HOST_CALL + byte_gas(arg.as_ref().map_or(0, String::len)));
+        arg
+    })
+    .flatten();
+    match arg {
+        Some(a) => write_out(out_ptr, out_max, a.as_bytes()),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_set_return(ptr: *const u8, len: u32) {
+    let data = read_bytes(ptr, len, MAX_RETURN_DATA_SIZE).to_vec();
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL + byte_gas(data.len()));
+        ex.return_data = data;
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_blake3(data_ptr: *const u8, data_len: u32, out_ptr: *mut u8) -> i32 {
+    let data = read_bytes(data_ptr, data_len, MAX_STATE_VALUE_SIZE);
+    with_execution(|ex| ex.charge(GAS_BLAKE3 + byte_gas(data.len())));
+    let hash = blake3::hash(data);
+    write_out(out_ptr, 32, hash.as_bytes())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_random_seed(out_ptr: *mut u8) -> i32 {
+    match with_execution(|ex| {
+        ex.charge(GAS_BLAKE3);
+        let n = ex.random_counter;
+        ex.random_counter = n.saturating_add(1);
+        derive_random_seed(&ex.self_address, &ex.caller, ex.timestamp, &ex.args, n)
+    }) {
+        Some(seed) => write_out(out_ptr, 32, &seed),
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_contract_exists(addr_ptr: *const u8, addr_len: u32) -> i32 {
+    let Some(addr) = read_string(addr_ptr, addr_len, 256) else {
+        return -1;
+    };
+    with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL + byte_gas(addr.len()));
+        ex.contracts.contains_key(&addr) as i32
+    })
+    .unwrap_or(-1)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn host_get_code_hash(
+    addr_ptr: *const u8,
+    addr_len: u32,
+    out_ptr: *mut u8,
+    out_max: u32,
+) -> i32 {
+    let Some(addr) = read_string(addr_ptr, addr_len, 256) else {
+        return -1;
+    };
+    match with_execution(|ex| {
+        ex.charge(GAS_HOST_CALL + byte_gas(addr.len()));
+        ex.contracts.get(&addr).cloned()
+    })
+    .flatten()
+    {
+        Some(hash) => write_out(out_ptr, out_max, hash.as_bytes()),
+        None => -1,
+    }
+}
@@ -0,0 +1,982 @@
+//! # LOS Testing — Native Unit-Testing Harness for UVM Contracts
+//!
+//! Contracts written with `los-sdk` import 23 host functions from the UVM.
+//! On `wasm32` the node provides them; on the native target this crate
+//! provides them instead, backed by an in-process [`MockHost`]. Contract
+//! entry points can then be called directly from `cargo test`:
+//!
+//! ```rust,ignore
+//! use los_testing::MockHost;
+//!
+//! #[test]
+//! fn test_set_and_get() {
+//!     let mut host = MockHost::new("LOSConTest").with_caller("LOSalice");
+//!     let res = host.call(&["greeting", "hello"], my_contract::set);
+//!     assert!(res.is_success());
+//!     assert_eq!(host.state_str("greeting").as_deref(), Some("hello"));
+//! }
+//! ```
+//!
+//! ## Semantics (mirrors `los-vm/src/host.rs`)
+//! - State changes, balance changes and transfers commit only on success
+//!   (return code 0, no abort, within gas); otherwise they are reverted,
+//!   including value attached with [`MockHost::call_with_value`]
+//! - `los_sdk::abort` ends the call (see [`MockHost::call_fn`]) and reverts
+//! - Same size/count limits as the UVM (state values, events, transfers, logs)
+//! - Same increase-only rule for the `los_sdk::migrate` schema version key
+//!
+//! ## Gas
+//! Native code is not instruction-metered, so `gas_used` is an
+//! APPROXIMATION: a fixed per-call base cost plus per-host-call and per-byte
+//! costs (see the `GAS_*` constants). The host call costs are the UVM's
+//! (`los_vm::host::GasSchedule::DEFAULT`); only instruction gas is missing. Use it to spot regressions and
+//! expensive storage patterns, not to size `gas_limit` exactly — measure on
+//! a `los-node --dev` chain for that.
+
+mod host_abi;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+
+// ─────────────────────────────────────────────────────────────────
+// Limits (same values as the UVM host)
+// ─────────────────────────────────────────────────────────────────
+
+pub(crate) const MAX_STATE_VALUE_SIZE: usize = 262_144;
+pub(crate) const MAX_STATE_KEY_SIZE: usize = 1_024;
+pub(crate) const MAX_RETURN_DATA_SIZE: usize = 262_144;
+pub(crate) const MAX_LOG_SIZE: usize = 4_096;
+pub(crate) const MAX_EVENTS: usize = 64;
+pub(crate) const MAX_EVENT_TYPE_SIZE: usize = 128;
+pub(crate) const MAX_EVENT_DATA_SIZE: usize = 8_192;
+pub(crate) const MAX_TRANSFERS: usize = 64;
+pub(crate) const MAX_STATE_KEYS: usize = 1_024;
+pub(crate) const MAX_LOGS: usize = 256;
+pub(crate) const MAX_EVENT_TOPICS: usize = 4;
+pub(crate) const MAX_TOPIC_SIZE: usize = 256;
+
+/// Same rules as `los_vm::host::parse_topics` (0x00-separated, max 4 × 256 bytes).
+pub(crate) fn parse_topics(raw: &[u8]) -> Option<Vec<String>> {
+    if raw.is_empty() {
+        return Some(Vec::new());
+    }
+    let topics: Vec<&[u8]> = raw.split(|b| *b == 0).collect();
+    if topics.len() > MAX_EVENT_TOPICS || topics.iter().any(|t| t.len() > MAX_TOPIC_SIZE) {
+        return None;
+    }
+    topics
+        .into_iter()
+        .map(|t| String::from_utf8(t.to_vec()).ok())
+        .collect()
+}
+
+/// Reserved schema-version key (see `los_sdk::migrate`)
+pub(crate) const SCHEMA_VERSION_KEY: &str = "__los:schema_version";
+
+/// Same rule as `los_vm::host::check_schema_version_write`: increase-only.
+pub(crate) fn check_schema_version_write(
+    current: Option<&Vec<u8>>,
+    new: Option<&Vec<u8>>,
+) -> Result<(), String> {
+    let parse = |b: &Vec<u8>| {
+        std::str::from_utf8(b)
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+    };
+    let current = current.and_then(parse).unwrap_or(0);
+    let new = match new {
+        Some(b) => parse(b).ok_or("schema version must be a decimal u32")?,
+        None => return Err("schema version cannot be deleted".to_string()),
+    };
+    if new <= current {
+        return Err(format!(
+            "schema version {} already applied (stored: {})",
+            new, current
+        ));
+    }
+    Ok(())
+}
+
+/// Same derivation as `los_vm::host::derive_call_id`: blake3 of the hex
+/// block hash followed by the call index (u32 LE).
+pub fn derive_call_id(block_hash: &str, call_index: u32) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(block_hash.as_bytes());
+    h.update(&call_index.to_le_bytes());
+    *h.finalize().as_bytes()
+}
+
+/// Same derivation as `los_vm::host::derive_random_seed`, so seeds seen in
+/// tests match the ones the contract gets on-chain for the same inputs.
+pub fn derive_random_seed(
+    self_address: &str,
+    caller: &str,
+    timestamp: u64,
+    args: &[String],
+    n: u32,
+) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(b"LOS-RANDOM-SEED-V1");
+    for field in [self_address, caller] {
+        h.update(&(field.len() as u64).to_le_bytes());
+        h.update(field.as_bytes());
+    }
+    h.update(&timestamp.to_le_bytes());
+    h.update(&(args.len() as u64).to_le_bytes());
+    for a in args {
+        h.update(&(a.len() as u64).to_le_bytes());
+        h.update(a.as_bytes());
+    }
+    h.update(&n.to_le_bytes());
+    *h.finalize().as_bytes()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Gas approximation
+// ─────────────────────────────────────────────────────────────────
+
+/// Default gas limit (matches los_core::DEFAULT_GAS_LIMIT)
+pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
+/// Per-call base cost (approximates instantiation + compilation of a small contract)
+pub const GAS_CALL_BASE: u64 = 1_000;
+/// Cost of any host function call
+pub const GAS_HOST_CALL: u64 = 50;
+/// Cost per byte copied across the host boundary
+pub const GAS_PER_BYTE: u64 = 1;
+/// Extra cost per byte written to persistent state
+pub const GAS_PER_STATE_BYTE_WRITTEN: u64 = 10;
+/// Cost of a blake3 hash (plus GAS_PER_BYTE per input byte)
+pub const GAS_BLAKE3: u64 = 200;
+/// Cost of a transfer request
+pub const GAS_TRANSFER: u64 = 500;
+/// Base cost of an emitted event (instead of GAS_HOST_CALL)
+pub const GAS_EVENT: u64 = 375;
+/// Cost per byte of event type, topics and data
+pub const GAS_PER_EVENT_BYTE: u64 = 8;
+
+// ─────────────────────────────────────────────────────────────────
+// Results
+// ─────────────────────────────────────────────────────────────────
+
+/// Event emitted by a contract (`data` is the raw JSON string)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockEvent {
+    pub event_type: String,
+    pub data: String,
+    /// Indexed values (`los_sdk::event::emit_indexed`)
+    pub topics: Vec<String>,
+}
+
+/// Outcome of one [`MockHost::call`]
+#[derive(Debug, Clone, Default)]
+pub struct CallResult {
+    /// Entry point return code (0 = success in SDK mode; -1 if it aborted/panicked)
+    pub code: i32,
+    pub return_data: Vec<u8>,
+    pub events: Vec<MockEvent>,
+    /// Requested transfers (recipient, amount_cil) — empty unless the call succeeded
+    pub transfers: Vec<(String, u128)>,
+    pub logs: Vec<String>,
+    /// `los_sdk::abort` message, or the panic message if the contract panicked
+    pub abort_message: Option<String>,
+    /// Approximate gas (see crate docs)
+    pub gas_used: u64,
+    pub out_of_gas: bool,
+}
+
+impl CallResult {
+    pub fn is_success(&self) -> bool {
+        self.code == 0 && self.abort_message.is_none() && !self.out_of_gas
+    }
+
+    /// Return data as UTF-8 (lossy)
+    pub fn return_str(&self) -> String {
+        String::from_utf8_lossy(&self.return_data).into_owned()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Execution context (what the host functions see)
+// ─────────────────────────────────────────────────────────────────
+
+/// Working state of the call in progress on this thread.
+pub(crate) struct Execution {
+    pub state: BTreeMap<String, Vec<u8>>,
+    pub dirty_keys: BTreeSet<String>,
+    pub events: Vec<MockEvent>,
+    pub transfers: Vec<(String, u128)>,
+    pub logs: Vec<String>,
+    pub args: Vec<String>,
+    pub caller: String,
+    pub self_address: String,
+    pub balance: u128,
+    /// CIL attached to the call (included in `balance`)
+    pub attached_value: u128,
+    pub timestamp: u64,
+    pub return_data: Vec<u8>,
+    pub abort_message: Option<String>,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub random_counter: u32,
+    /// Other contracts: address → code hash
+    pub contracts: BTreeMap<String, String>,
+    /// Hash of the block the call belongs to (call index is always 0)
+    pub block_hash: String,
+}
+
+impl Execution {
+    /// Add gas. Native code cannot be stopped mid-call, so exceeding the
+    /// limit is recorded and the call is reverted when it returns.
+    pub fn charge(&mut self, gas: u64) {
+        self.gas_used = self.gas_used.saturating_add(gas);
+    }
+}
+
+/// An execution bound to the current (worker) thread, plus the channel that
+/// hands it back to [`MockHost::call_fn`] when the call finishes or aborts.
+pub(crate) struct Active {
+    pub ex: Execution,
+    pub done: Sender<(Execution, i32)>,
+}
+
+thread_local! {
+    pub(crate) static ACTIVE: RefCell<Option<Active>> = const { RefCell::new(None) };
+}
+
+/// Run `f` against the active execution. Returns `None` (and warns) when a
+/// host function is called outside [`MockHost::call`].
+pub(crate) fn with_execution<R>(f: impl FnOnce(&mut Execution) -> R) -> Option<R> {
+    ACTIVE.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(active) => Some(f(&mut active.ex)),
+        None => {
+            eprintln!("⚠️ los-testing: host function called outside MockHost::call()");
+            None
+        }
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────
+// MockHost
+// ─────────────────────────────────────────────────────────────────
+
+/// In-process stand-in for the UVM: persistent contract state, balance and
+/// call context. Each [`call`](MockHost::call) runs one entry point.
+#[derive(Debug, Clone)]
+pub struct MockHost {
+    self_address: String,
+    caller: String,
+    timestamp: u64,
+    balance: u128,
+    gas_limit: u64,
+    state: BTreeMap<String, Vec<u8>>,
+    contracts: BTreeMap<String, String>,
+    block_hash: String,
+}
+
+impl MockHost {
+    /// New host for the contract at `self_address` (caller `LOSTestCaller`,
+    /// timestamp 1_700_000_000, zero balance, default gas limit).
+    pub fn new(self_address: &str) -> Self {
+        Self {
+            self_address: self_address.to_string(),
+            caller: "LOSTestCaller".to_string(),
+            timestamp: 1_700_000_000,
+            balance: 0,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            state: BTreeMap::new(),
+            contracts: BTreeMap::new(),
+            block_hash: String::new(),
+        }
+    }
+
+    pub fn with_caller(mut self, caller: &str) -> Self {
+        self.caller = caller.to_string();
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_balance(mut self, balance_cil: u128) -> Self {
+        self.balance = balance_cil;
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Make `address` a deployed contract with `code_hash` for
+    /// `los_sdk::contract::exists` / `code_hash`. The contract under test is
+    /// not registered unless added here.
+    pub fn with_contract(mut self, address: &str, code_hash: &str) -> Self {
+        self.contracts
+            .insert(address.to_string(), code_hash.to_string());
+        self
+    }
+
+    /// Block hash seen by `los_sdk::block_hash` / `call_id` (empty by
+    /// default, like a dry run)
+    pub fn with_block_hash(mut self, block_hash: &str) -> Self {
+        self.block_hash = block_hash.to_string();
+        self
+    }
+
+    /// Change the block hash for subsequent calls (each on-chain call has
+    /// its own block)
+    pub fn set_block_hash(&mut self, block_hash: &str) {
+        self.block_hash = block_hash.to_string();
+    }
+
+    /// Change the caller for subsequent calls
+    pub fn set_caller(&mut self, caller: &str) {
+        self.caller = caller.to_string();
+    }
+
+    /// Change the block timestamp for subsequent calls
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    /// Move the block timestamp forward
+    pub fn advance_time(&mut self, secs: u64) {
+        self.timestamp = self.timestamp.saturating_add(secs);
+    }
+
+    /// Credit the contract (simulates a Send to the contract address)
+    pub fn deposit(&mut self, amount_cil: u128) {
+        self.balance = self.balance.saturating_add(amount_cil);
+    }
+
+    pub fn balance(&self) -> u128 {
+        self.balance
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Seed a state entry (e.g. to start from a known fixture)
+    pub fn set_state(&mut self, key: &str, value: &[u8]) {
+        self.state.insert(key.to_string(), value.to_vec());
+    }
+
+    pub fn state(&self, key: &str) -> Option<&[u8]> {
+        self.state.get(key).map(Vec::as_slice)
+    }
+
+    pub fn state_str(&self, key: &str) -> Option<String> {
+        String::from_utf8(self.state.get(key)?.clone()).ok()
+    }
+
+    /// Read a value written with `los_sdk::state::set_u128`
+    pub fn state_u128(&self, key: &str) -> u128 {
+        match self.state.get(key) {
+            Some(b) if b.len() >= 16 => {
+                let mut arr = [0u8; 16];
+                arr.copy_from_slice(&b[..16]);
+                u128::from_le_bytes(arr)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Read a value written with `los_sdk::state::set_u64`
+    pub fn state_u64(&self, key: &str) -> u64 {
+        match self.state.get(key) {
+            Some(b) if b.len() >= 8 => {
+                let mut arr = [0u8; 8];
+                arr.copy_from_slice(&b[..8]);
+                u64::from_le_bytes(arr)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Full committed state
+    pub fn state_map(&self) -> &BTreeMap<String, Vec<u8>> {
+        &self.state
+    }
+
+    /// Call a contract entry point (`extern "C" fn() -> i32`) with string arguments.
+    pub fn call(&mut self, args: &[&str], entry: extern "C" fn() -> i32) -> CallResult {
+        self.call_fn(args, move || entry())
+    }
+
+    /// [`call`](MockHost::call) with a one-off caller
+    pub fn call_as(
+        &mut self,
+        caller: &str,
+        args: &[&str],
+        entry: extern "C" fn() -> i32,
+    ) -> CallResult {
+        let previous = std::mem::replace(&mut self.caller, caller.to_string());
+        let result = self.call(args, entry);
+        self.caller = previous;
+        result
+    }
+
+    /// Run arbitrary contract code (e.g. an internal helper) as one call.
+    ///
+    /// The code runs on a fresh worker thread. `los_sdk::abort` hands the
+    /// execution back and parks that thread for good (it cannot unwind
+    /// through an `extern "C"` entry point), so each aborted call leaks one
+    /// idle thread — harmless in a test process. A panic inside plain Rust
+    /// code is caught and reported as an abort; a panic inside an
+    /// `extern "C"` entry point aborts the test process (Rust semantics).
+    pub fn call_fn<F>(&mut self, args: &[&str], f: F) -> CallResult
+    where
+        F: FnOnce() -> i32 + Send + 'static,
+    {
+        self.execute(args, 0, f)
+    }
+
+    /// [`call`](MockHost::call) with `amount_cil` attached
+    /// (`los_sdk::msg_value()`). The value is credited for the duration of
+    /// the call and stays with the contract only if the call succeeds.
+    pub fn call_with_value(
+        &mut self,
+        amount_cil: u128,
+        args: &[&str],
+        entry: extern "C" fn() -> i32,
+    ) -> CallResult {
+        self.execute(args, amount_cil, move || entry())
+    }
+
+    fn execute<F>(&mut self, args: &[&str], attached_value: u128, f: F) -> CallResult
+    where
+        F: FnOnce() -> i32 + Send + 'static,
+    {
+        let execution = Execution {
+            state: self.state.clone(),
+            dirty_keys: BTreeSet::new(),
+            events: Vec::new(),
+            transfers: Vec::new(),
+            logs: Vec::new(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            caller: self.caller.clone(),
+            self_address: self.self_address.clone(),
+            balance: self.balance.saturating_add(attached_value),
+            attached_value,
+            timestamp: self.timestamp,
+            return_data: Vec::new(),
+            abort_message: None,
+            gas_used: GAS_CALL_BASE,
+            gas_limit: self.gas_limit,
+            random_counter: 0,
+            contracts: self.contracts.clone(),
+            block_hash: self.block_hash.clone(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name("los-testing-call".to_string())
+            .spawn(move || {
+                ACTIVE.with(|cell| {
+                    *cell.borrow_mut() = Some(Active {
+                        ex: execution,
+                        done: tx,
+                    })
+                });
+                let outcome = catch_unwind(AssertUnwindSafe(f));
+                if let Some(Active { mut ex, done }) = ACTIVE.with(|cell| cell.borrow_mut().take())
+                {
+                    let code = match outcome {
+                        Ok(code) => code,
+                        Err(payload) => {
+                            let msg = payload
+                                .downcast_ref::<&str>()
+                                .map(|s| s.to_string())
+                                .or_else(|| payload.downcast_ref::<String>().cloned())
+                                .unwrap_or_else(|| "contract panicked".to_string());
+                            ex.abort_message = Some(format!("panic: {}", msg));
+                            -1
+                        }
+                    };
+                    let _ = done.send((ex, code));
+                }
+            });
+        if let Err(e) = spawned {
+            panic!("los-testing: failed to spawn call thread: {}", e);
+        }
+        let (ex, code) = rx
+            .recv()
+            .expect("los-testing: call thread exited without a result");
+
+        let mut result = CallResult {
+            code,
+            return_data: ex.return_data,
+            events: ex.events,
+            transfers: Vec::new(),
+            logs: ex.logs,
+            abort_message: ex.abort_message,
+            gas_used: ex.gas_used,
+            out_of_gas: ex.gas_used > ex.gas_limit,
+        };
+        if result.is_success() {
+            self.state = ex.state;
+            self.balance = ex.balance;
+            result.transfers = ex.transfers;
+        }
+        result
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Tests — tiny contracts written against los-sdk, run on MockHost
+// ─────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use los_sdk::*;
+
+    extern "C" fn counter_inc() -> i32 {
+        let n = state::get_u64("count") + 1;
+        state::set_u64("count", n);
+        event::emit(
+            "Inc",
+            &format!(r#"{{"count":"{}","by":"{}"}}"#, n, caller()),
+        );
+        set_return_str(&n.to_string());
+        0
+    }
+
+    extern "C" fn guarded_set() -> i32 {
+        let Some(key) = arg(0) else { return 1 };
+        let value = arg(1).unwrap_or_default();
+        state::set_str(&key, &value);
+        if value == "boom" {
+            abort("value not allowed");
+        }
+        if value == "fail" {
+            return 7;
+        }
+        0
+    }
+
+    extern "C" fn payout() -> i32 {
+        let to = arg(0).unwrap_or_default();
+        let amount: u128 = arg(1).and_then(|a| a.parse().ok()).unwrap_or(0);
+        match transfer(&to, amount) {
+            Ok(()) => 0,
+            Err(_) => 2,
+        }
+    }
+
+    /// Credits the attached value to the caller; rejects more than 500 CIL
+    extern "C" fn deposit() -> i32 {
+        let value = msg_value();
+        if value == 0 || value > 500 {
+            return 1;
+        }
+        let key = format!("dep:{}", caller());
+        state::set_u128(&key, state::get_u128(&key) + value);
+        set_return_str(&balance().to_string());
+        0
+    }
+
+    /// Processes each invocation once, keyed by call_id()
+    extern "C" fn claim_once() -> i32 {
+        let id: String = call_id().iter().map(|b| format!("{:02x}", b)).collect();
+        let key = format!("seen:{}", id);
+        if state::get(&key).is_some() {
+            return 1;
+        }
+        state::set(&key, block_hash().as_bytes());
+        0
+    }
+
+    extern "C" fn require_token() -> i32 {
+        entry(|| {
+            let target = require_arg(0)?;
+            require(contract::exists(&target), ContractError::NotFound)?;
+            let hash = contract::code_hash(&target).ok_or(ContractError::NotFound)?;
+            require(hash == "aa".repeat(32), ContractError::InvalidArgument)?;
+            Ok(hash)
+        })
+    }
+
+    extern "C" fn indexed_transfer() -> i32 {
+        entry(|| {
+            let to = require_arg(0)?;
+            let from = caller();
+            event::emit_indexed("Transfer", &[&from, &to], r#"{"amount":"5"}"#)
+                .map_err(|_| ContractError::InvalidArgument)?;
+            let too_many = event::emit_indexed("X", &["a", "b", "c", "d", "e"], "{}");
+            require(too_many.is_err(), ContractError::User(9))?;
+            Ok(())
+        })
+    }
+
+    extern "C" fn event_flood() -> i32 {
+        let n: usize = arg(0).and_then(|a| a.parse().ok()).unwrap_or(0);
+        for _ in 0..n {
+            event::emit("Tick", "{}");
+        }
+        let size: usize = arg(1).and_then(|a| a.parse().ok()).unwrap_or(0);
+        if size > 0 {
+            event::emit("Blob", &format!(r#"{{"b":"{}"}}"#, "x".repeat(size)));
+        }
+        state::set_u64("done", 1);
+        0
+    }
+
+    extern "C" fn set_owner() -> i32 {
+        entry(|| {
+            let owner = state::get_str("owner");
+            require(
+                owner.is_none() || owner.as_deref() == Some(caller().as_str()),
+                ContractError::Unauthorized,
+            )?;
+            let new_owner = require_arg(0)?;
+            state::set_str("owner", &new_owner);
+            Ok(new_owner)
+        })
+    }
+
+    fn rename_name_key() -> Result<(), ContractError> {
+        let v = state::get_str("name").ok_or(ContractError::NotFound)?;
+        state::del("name");
+        state::set_str("meta:name", &v);
+        Ok(())
+    }
+
+    fn add_counter() -> Result<(), ContractError> {
+        state::set_u64("count", 0);
+        Ok(())
+    }
+
+    const MIGRATIONS: &[migrate::Migration] = &[rename_name_key, add_counter];
+
+    extern "C" fn migrate_to_v2() -> i32 {
+        entry(|| migrate::run_migrations(2, MIGRATIONS).map(|n| n as u64))
+    }
+
+    extern "C" fn replay_v1() -> i32 {
+        state::set_str(migrate::SCHEMA_VERSION_KEY, "1");
+        0
+    }
+
+    extern "C" fn roll() -> i32 {
+        let a = crypto::random_seed();
+        let b = crypto::random_seed();
+        let mut buf = [0u8; 70];
+        compat::fill_deterministic(&mut buf);
+        let elapsed = compat::Instant::from_secs(10).elapsed().as_secs();
+        set_return_str(&format!("{}|{}|{}|{}", a[0], b[0], buf[69], elapsed));
+        if a == b || buf[..32] == buf[32..64] {
+            return 1;
+        }
+        0
+    }
+
+    extern "C" fn context() -> i32 {
+        let digest = crypto::blake3(b"los");
+        log("context called");
+        set_return_str(&format!(
+            "{}|{}|{}|{}|{}|{}",
+            caller(),
+            self_address(),
+            timestamp(),
+            balance(),
+            arg_count(),
+            digest[0]
+        ));
+        if state::exists("missing") {
+            return 1;
+        }
+        state::del("temp");
+        0
+    }
+
+    fn bump(times: u64) {
+        for _ in 0..times {
+            state::set_u64("n", state::get_u64("n") + 1);
+        }
+    }
+
+    fn times_arg() -> Result<u64, ContractError> {
+        require_arg(0)?
+            .parse()
+            .map_err(|_| ContractError::InvalidArgument)
+    }
+
+    /// `arg(0)` read-modify-writes of one key; fails after writing if `arg(1)` is set
+    extern "C" fn bump_buffered() -> i32 {
+        entry(|| {
+            bump(times_arg()?);
+            require(arg(1).is_none(), ContractError::InvalidArgument)?;
+            Ok(state::get_u64("n"))
+        })
+    }
+
+    extern "C" fn bump_direct() -> i32 {
+        match times_arg() {
+            Ok(times) => {
+                bump(times);
+                0
+            }
+            Err(e) => e.code(),
+        }
+    }
+
+    /// Length of "blob" via the default and the smallest read hint
+    extern "C" fn read_blob() -> i32 {
+        entry(|| {
+            let full = state::get("blob").map_or(0, |v| v.len());
+            let tiny = state::get_with_hint("blob", 0).map_or(0, |v| v.len());
+            state::del("temp");
+            require(!state::exists("temp"), ContractError::InvalidArgument)?;
+            state::set("temp", b"x");
+            require(state::exists("temp"), ContractError::InvalidArgument)?;
+            state::del("temp");
+            Ok(format!("{}|{}", full, tiny))
+        })
+    }
+
+    #[test]
+    fn test_state_and_events_commit() {
+        let mut host = MockHost::new("LOSConCounter").with_caller("LOSalice");
+        let r1 = host.call(&[], counter_inc);
+        let r2 = host.call_as("LOSbob", &[], counter_inc);
+        assert!(r1.is_success() && r2.is_success());
+        assert_eq!(r2.return_str(), "2");
+        assert_eq!(host.state_u64("count"), 2);
+        assert_eq!(r2.events[0].event_type, "Inc");
+        assert!(r2.events[0].data.contains("LOSbob"));
+        assert!(r1.gas_used > GAS_CALL_BASE);
+    }
+
+    #[test]
+    fn test_abort_and_error_code_revert() {
+        let mut host = MockHost::new("LOSConGuard");
+        assert!(host.call(&["k", "ok"], guarded_set).is_success());
+
+        let aborted = host.call(&["k", "boom"], guarded_set);
+        assert_eq!(aborted.abort_message.as_deref(), Some("value not allowed"));
+        assert_eq!(aborted.code, -1);
+        assert_eq!(host.state_str("k").as_deref(), Some("ok"));
+
+        let failed = host.call(&["k", "fail"], guarded_set);
+        assert_eq!(failed.code, 7);
+        assert_eq!(host.state_str("k").as_deref(), Some("ok"));
+        assert_eq!(host.call(&[], guarded_set).code, 1);
+    }
+
+    #[test]
+    fn test_transfers_respect_balance() {
+        let mut host = MockHost::new("LOSConPay").with_balance(1_000);
+        let ok = host.call(&["LOSbob", "600"], payout);
+        assert!(ok.is_success());
+        assert_eq!(ok.transfers, vec![("LOSbob".to_string(), 600)]);
+        assert_eq!(host.balance(), 400);
+
+        let short = host.call(&["LOSbob", "600"], payout);
+        assert_eq!(short.code, 2);
+        assert!(short.transfers.is_empty());
+        assert_eq!(host.balance(), 400);
+    }
+
+    #[test]
+    fn test_attached_value_refunded_on_failure() {
+        let mut host = MockHost::new("LOSConVault")
+            .with_caller("LOSalice")
+            .with_balance(100);
+        let ok = host.call_with_value(300, &[], deposit);
+        assert!(ok.is_success());
+        assert_eq!(ok.return_str(), "400");
+        assert_eq!(host.state_u128("dep:LOSalice"), 300);
+        assert_eq!(host.balance(), 400);
+
+        assert_eq!(host.call_with_value(900, &[], deposit).code, 1);
+        assert_eq!(host.call(&[], deposit).code, 1);
+        assert_eq!(host.balance(), 400);
+        assert_eq!(host.state_u128("dep:LOSalice"), 300);
+    }
+
+    #[test]
+    fn test_call_id_dedupes_per_block() {
+        let h1 = "ab".repeat(32);
+        let mut host = MockHost::new("LOSConBridge").with_block_hash(&h1);
+        assert!(host.call(&[], claim_once).is_success());
+        // Replaying the same block is rejected
+        assert_eq!(host.call(&[], claim_once).code, 1);
+
+        host.set_block_hash(&"cd".repeat(32));
+        assert!(host.call(&[], claim_once).is_success());
+        let id: String = derive_call_id(&h1, 0)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(
+            host.state_str(&format!("seen:{}", id)).as_deref(),
+            Some(h1.as_str())
+        );
+    }
+
+    #[test]
+    fn test_entry_adapter_codes() {
+        let mut host = MockHost::new("LOSConOwned").with_caller("LOSalice");
+        assert_eq!(host.call(&[], set_owner).code, 2);
+        let ok = host.call(&["LOSalice"], set_owner);
+        assert!(ok.is_success());
+        assert_eq!(ok.return_str(), "LOSalice");
+        let denied = host.call_as("LOSmallory", &["LOSmallory"], set_owner);
+        assert_eq!(denied.code, ContractError::Unauthorized.code());
+        assert_eq!(host.state_str("owner").as_deref(), Some("LOSalice"));
+    }
+
+    #[test]
+    fn test_migrations_run_once() {
+        let mut host = MockHost::new("LOSConMig");
+        host.set_state("name", b"token");
+        let first = host.call(&[], migrate_to_v2);
+        assert!(first.is_success());
+        assert_eq!(first.return_str(), "2");
+        assert_eq!(host.state_str("meta:name").as_deref(), Some("token"));
+        assert_eq!(host.state_str(SCHEMA_VERSION_KEY).as_deref(), Some("2"));
+
+        // Up to date: nothing runs
+        assert_eq!(host.call(&[], migrate_to_v2).return_str(), "0");
+
+        // Host refuses to move the version backwards
+        let replay = host.call(&[], replay_v1);
+        assert!(!replay.is_success());
+        assert!(replay.abort_message.unwrap().contains("already applied"));
+        assert_eq!(host.state_str(SCHEMA_VERSION_KEY).as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_failed_migration_reverts() {
+        let mut host = MockHost::new("LOSConMig");
+        let r = host.call(&[], migrate_to_v2);
+        assert_eq!(r.code, ContractError::NotFound.code());
+        assert!(host.state(SCHEMA_VERSION_KEY).is_none());
+    }
+
+    #[test]
+    fn test_random_seed_and_compat_shims() {
+        let mut host = MockHost::new("LOSConRng").with_timestamp(70);
+        let r1 = host.call(&["x"], roll);
+        let r2 = host.call(&["x"], roll);
+        assert!(r1.is_success());
+        // Deterministic for identical inputs
+        assert_eq!(r1.return_str(), r2.return_str());
+        let seed0 = derive_random_seed("LOSConRng", "LOSTestCaller", 70, &["x".to_string()], 0);
+        assert!(r1.return_str().starts_with(&format!("{}|", seed0[0])));
+        assert!(r1.return_str().ends_with("|60"));
+        // Different args → different seeds
+        assert_ne!(host.call(&["y"], roll).return_str(), r1.return_str());
+    }
+
+    #[test]
+    fn test_context_functions() {
+        let mut host = MockHost::new("LOSConCtx")
+            .with_caller("LOScarol")
+            .with_timestamp(42)
+            .with_balance(9);
+        host.set_state("temp", b"x");
+        let r = host.call(&["a", "b"], context);
+        assert!(r.is_success());
+        let expected = format!(
+            "LOScarol|LOSConCtx|42|9|2|{}",
+            blake3::hash(b"los").as_bytes()[0]
+        );
+        assert_eq!(r.return_str(), expected);
+        assert_eq!(r.logs, vec!["context called".to_string()]);
+        assert!(host.state("temp").is_none());
+    }
+
+    #[test]
+    fn test_contract_queries() {
+        let mut host = MockHost::new("LOSConRouter")
+            .with_contract("LOSConToken", &"aa".repeat(32))
+            .with_contract("LOSConOther", &"bb".repeat(32));
+        let r = host.call(&["LOSConToken"], require_token);
+        assert!(r.is_success());
+        assert_eq!(r.return_str(), "aa".repeat(32));
+        assert_eq!(
+            host.call(&["LOSConOther"], require_token).code,
+            ContractError::InvalidArgument.code()
+        );
+        assert_eq!(
+            host.call(&["LOSalice"], require_token).code,
+            ContractError::NotFound.code()
+        );
+    }
+
+    #[test]
+    fn test_indexed_events() {
+        let mut host = MockHost::new("LOSConToken").with_caller("LOSalice");
+        let r = host.call(&["LOSbob"], indexed_transfer);
+        assert!(r.is_success(), "{:?}", r);
+        assert_eq!(r.events.len(), 1);
+        assert_eq!(r.events[0].event_type, "Transfer");
+        assert_eq!(r.events[0].topics, vec!["LOSalice", "LOSbob"]);
+        assert!(host.call(&[], indexed_transfer).code != 0);
+    }
+
+    #[test]
+    fn test_event_limits_abort() {
+        let mut host = MockHost::new("LOSConEvents");
+        let ok = host.call(&["63", "8000"], event_flood);
+        assert!(ok.is_success(), "{:?}", ok.abort_message);
+        assert_eq!(ok.events.len(), 64);
+        // 8 bytes per event byte on top of the base
+        assert!(ok.gas_used > GAS_CALL_BASE + 8_000 * GAS_PER_EVENT_BYTE);
+
+        let many = host.call(&["65"], event_flood);
+        assert!(!many.is_success());
+        assert!(many.abort_message.unwrap().contains("too many events"));
+        let big = host.call(&["1", "9000"], event_flood);
+        assert!(big.abort_message.unwrap().contains("event data too large"));
+        assert_eq!(host.state_u64("done"), 1);
+    }
+
+    #[test]
+    fn test_out_of_gas_reverts() {
+        let mut host = MockHost::new("LOSConGas").with_gas_limit(GAS_CALL_BASE + 10);
+        let r = host.call(&[], counter_inc);
+        assert!(r.out_of_gas);
+        assert!(!r.is_success());
+        assert_eq!(host.state_u64("count"), 0);
+    }
+
+    #[test]
+    fn test_entry_buffers_state_writes() {
+        let mut buffered = MockHost::new("LOSConBump");
+        let r = buffered.call(&["20"], bump_buffered);
+        assert!(r.is_success(), "{:?}", r);
+        assert_eq!(r.return_str(), "20");
+        assert_eq!(buffered.state_u64("n"), 20);
+
+        let mut direct = MockHost::new("LOSConBump");
+        let d = direct.call(&["20"], bump_direct);
+        assert!(d.is_success());
+        assert_eq!(direct.state_u64("n"), 20);
+        // One host read and one host write instead of twenty of each
+        assert!(r.gas_used < d.gas_used, "{} >= {}", r.gas_used, d.gas_used);
+
+        // A failing closure leaves nothing behind
+        let failed = buffered.call(&["5", "fail"], bump_buffered);
+        assert_eq!(failed.code, ContractError::InvalidArgument.code());
+        assert_eq!(buffered.state_u64("n"), 20);
+    }
+
+    #[test]
+    fn test_size_hinted_reads() {
+        let mut host = MockHost::new("LOSConBlob");
+        host.set_state("blob", &[9u8; 1000]);
+        host.set_state("temp", b"old");
+        let r = host.call(&[], read_blob);
+        assert!(r.is_success(), "{:?}", r);
+        assert_eq!(r.return_str(), "1000|1000");
+        assert!(host.state("temp").is_none());
+    }
+}
@@ -0,0 +1,35 @@
+// Scenario contracts, written in WAT so the harness needs no wasm32
+// toolchain. They use the same host imports as los-sdk contracts.
+
+/// Key-value store: `set(key, value)` writes state, `get(key)` returns it
+/// (empty when unset). Either fails with status 1 on a missing key.
+pub const KV_STORE_WAT: &str = r#"(module
+    (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+    (import "env" "host_get_state" (func $get (param i32 i32 i32 i32) (result i32)))
+    (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+    (import "env" "host_set_return" (func $ret (param i32 i32)))
+    (memory (export "memory") 1)
+
+    ;; key at 1024..1280, value at 2048..3072
+    (func (export "set") (result i32) (local $kl i32) (local $vl i32)
+        (local.set $kl (call $arg (i32.const 0) (i32.const 1024) (i32.const 256)))
+        (if (i32.le_s (local.get $kl) (i32.const 0)) (then (return (i32.const 1))))
+        (local.set $vl (call $arg (i32.const 1) (i32.const 2048) (i32.const 1024)))
+        (if (i32.lt_s (local.get $vl) (i32.const 0)) (then (local.set $vl (i32.const 0))))
+        (call $set (i32.const 1024) (local.get $kl) (i32.const 2048) (local.get $vl))
+        (i32.const 0))
+
+    (func (export "get") (result i32) (local $kl i32) (local $vl i32)
+        (local.set $kl (call $arg (i32.const 0) (i32.const 1024) (i32.const 256)))
+        (if (i32.le_s (local.get $kl) (i32.const 0)) (then (return (i32.const 1))))
+        (local.set $vl (call $get (i32.const 1024) (local.get $kl) (i32.const 2048) (i32.const 1024)))
+        (if (i32.lt_s (local.get $vl) (i32.const 0)) (then (local.set $vl (i32.const 0))))
+        (call $ret (i32.const 2048) (local.get $vl))
+        (i32.const 0)))"#;
+
+/// Compiled [`KV_STORE_WAT`]
+pub fn kv_store() -> Vec<u8> {
+    wasmer::wat2wasm(KV_STORE_WAT.as_bytes())
+        .expect("KV store WAT must compile")
+        .to_vec()
+}
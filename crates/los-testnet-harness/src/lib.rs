@@ -0,0 +1,56 @@
+//! # LOS Testnet Harness — In-Process Multi-Node Scenarios
+//!
+//! Starts N validator nodes in one process, connected by std channels
+//! instead of Tor, and runs scripted scenarios against them — transfers,
+//! contract deploys and calls, reward epoch rollovers, checkpoints — then
+//! asserts that every node ended up with the same state:
+//!
+//! ```rust,ignore
+//! use los_testnet_harness::{contracts, Step, Testnet};
+//!
+//! let mut net = Testnet::new(4, &["alice", "bob"]);
+//! net.run(&[
+//!     Step::Send { from: "alice".into(), to: "bob".into(), amount_cil: 5 },
+//!     Step::Receive { account: "bob".into() },
+//!     Step::Deploy { from: "alice".into(), name: "kv".into(), bytecode: contracts::kv_store() },
+//!     Step::EpochRollover,
+//!     Step::Checkpoint,
+//!     Step::AssertConverged,
+//! ])
+//! .unwrap();
+//! ```
+//!
+//! ## What runs for real
+//! - los-core `Ledger` (PoW, Dilithium5 signatures, fees, distribution
+//!   certificates) and los-vm `WasmEngine`
+//! - the reward pool with leader proposals, co-signatures and certified Mints
+//! - checkpoint quorum (2f+1 Dilithium5 signatures) over the full state root
+//! - los-node's `contract_apply.rs` and `quarantine.rs`, compiled in as-is,
+//!   so deploys, calls and divergence checks are the node's own code
+//!
+//! ## What does not
+//! The rest of los-node — Tor and gossip transport, the REST and gRPC
+//! layers, persistence, aBFT block confirmation (see los-sim for that).
+//! Every node applies a gossiped block as soon as it arrives.
+//!
+//! ## Determinism
+//! Keys come from fixed seeds, time is scripted, and inboxes are drained
+//! round-robin by node id: one script, one run.
+
+#[allow(dead_code)]
+#[path = "../../los-node/src/contract_apply.rs"]
+mod contract_apply;
+#[allow(dead_code)]
+#[path = "../../los-node/src/quarantine.rs"]
+mod quarantine;
+
+pub mod contracts;
+pub mod net;
+pub mod node;
+pub mod testnet;
+pub mod wallet;
+
+pub use net::NodeId;
+pub use node::HarnessNode;
+pub use testnet::{Step, Testnet};
+pub use wallet::HarnessAccount;
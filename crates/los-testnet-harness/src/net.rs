@@ -0,0 +1,125 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - CHANNEL NETWORK
+//
+// Every node owns the receiving end of one std mpsc channel and a sender to
+// every other node: gossip is a clone per peer, no sockets, no Tor, no
+// serialization. Channels are FIFO per sender, and the testnet drains the
+// inboxes round-robin by node id, so one script is one interleaving.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use los_consensus::checkpoint::{CheckpointSignature, FinalityCheckpoint};
+use los_core::reward_cosign::{CoSignature, DistributionCertificate};
+use los_core::Block;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// Index of a node in the testnet
+pub type NodeId = usize;
+
+/// What nodes gossip; mirrors the los-node message prefixes in comments
+#[derive(Debug, Clone)]
+pub enum Gossip {
+    /// BLOCK_CONFIRMED / CONTRACT_CALLED / reward and fee Mints
+    Block(Block),
+    /// CONTRACT_DEPLOYED: the deploy block plus its bytecode
+    Deploy { block: Block, bytecode: Vec<u8> },
+    /// Validator heartbeat (uptime for reward eligibility)
+    Heartbeat(String),
+    /// REWARD_PROPOSE: the epoch leader's distribution with its own signature
+    Propose(DistributionCertificate),
+    /// REWARD_SIGN: a co-signature, sent back to the leader
+    CoSign {
+        digest: String,
+        signature: CoSignature,
+    },
+    /// CHECKPOINT_PROPOSE
+    CheckpointPropose(FinalityCheckpoint),
+    /// CHECKPOINT_SIGN, sent back to the proposer
+    CheckpointSign {
+        height: u64,
+        signature: CheckpointSignature,
+    },
+    /// CHECKPOINT_FINAL: the aggregate with a quorum of signatures
+    CheckpointFinal(FinalityCheckpoint),
+}
+
+/// A message and who sent it
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub from: NodeId,
+    pub gossip: Gossip,
+}
+
+/// One node's connection to the others
+#[derive(Debug)]
+pub struct Endpoint {
+    pub id: NodeId,
+    inbox: Receiver<Envelope>,
+    peers: Vec<Sender<Envelope>>,
+}
+
+impl Endpoint {
+    /// Gossip to every other node
+    pub fn broadcast(&self, gossip: &Gossip) {
+        for to in (0..self.peers.len()).filter(|to| *to != self.id) {
+            self.send(to, gossip.clone());
+        }
+    }
+
+    /// Direct message to one node
+    pub fn send(&self, to: NodeId, gossip: Gossip) {
+        // A node is never dropped while the testnet runs
+        let _ = self.peers[to].send(Envelope {
+            from: self.id,
+            gossip,
+        });
+    }
+
+    /// Next queued message, if any
+    pub fn try_recv(&self) -> Option<Envelope> {
+        self.inbox.try_recv().ok()
+    }
+}
+
+/// Fully connected endpoints for `n` nodes
+pub fn connect(n: usize) -> Vec<Endpoint> {
+    let (senders, inboxes): (Vec<_>, Vec<_>) = (0..n).map(|_| channel()).unzip();
+    inboxes
+        .into_iter()
+        .enumerate()
+        .map(|(id, inbox)| Endpoint {
+            id,
+            inbox,
+            peers: senders.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_skips_sender_and_keeps_order() {
+        let nodes = connect(3);
+        nodes[0].broadcast(&Gossip::Heartbeat("a".to_string()));
+        nodes[0].broadcast(&Gossip::Heartbeat("b".to_string()));
+        nodes[2].send(1, Gossip::Heartbeat("c".to_string()));
+
+        assert!(nodes[0].try_recv().is_none());
+        let order: Vec<(NodeId, String)> = std::iter::from_fn(|| nodes[1].try_recv())
+            .map(|env| match env.gossip {
+                Gossip::Heartbeat(h) => (env.from, h),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (0, "a".to_string()),
+                (0, "b".to_string()),
+                (2, "c".to_string())
+            ]
+        );
+        assert_eq!(std::iter::from_fn(|| nodes[2].try_recv()).count(), 2);
+    }
+}
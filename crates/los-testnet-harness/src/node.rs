@@ -0,0 +1,501 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - HARNESS NODE
+//
+// One validator's application state — a los-core Ledger, a los-vm
+// WasmEngine and the validator reward pool — plus the gossip handlers of
+// los-node that change it:
+//
+//   blocks        Ledger::process_block_at; reward and fee Mints also
+//                 update the reward pool (sync_reward_mint)
+//   contracts     los-node's own contract_apply.rs (deploys and calls)
+//   epoch end     the round-robin leader distributes and proposes, every
+//                 validator co-signs what matches its pool and ledger, the
+//                 leader mints at quorum; everyone else advances the epoch
+//   checkpoints   proposer signs (tip, full state root), co-signers compare
+//                 through los-node's quarantine.rs, 2f+1 → final
+//
+// Anything a node refuses that the origin accepted is recorded in `faults`:
+// in a harness every node must agree on every valid block.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::net::{Endpoint, Envelope, Gossip, NodeId};
+use crate::quarantine::{self, LocalState, QuarantineConfig, StateQuarantine, Verdict};
+use crate::{contract_apply, wallet};
+use los_consensus::checkpoint::{CheckpointSignature, FinalityCheckpoint, PendingCheckpoint};
+use los_consensus::voting::calculate_voting_power;
+use los_core::reward_cosign::{
+    CoSignature, DistributionCertificate, DistributionKind, EpochDistribution,
+};
+use los_core::validator_rewards::{pro_rata_shares, ValidatorRewardPool};
+use los_core::{Block, BlockType, Ledger, MIN_VALIDATOR_STAKE_CIL};
+use los_crypto::KeyPair;
+use los_vm::WasmEngine;
+use std::collections::BTreeMap;
+
+pub struct HarnessNode {
+    pub id: NodeId,
+    pub address: String,
+    keypair: KeyPair,
+    /// Validator address → public key, for checkpoint signatures
+    validator_keys: BTreeMap<String, Vec<u8>>,
+    pub ledger: Ledger,
+    pub vm: WasmEngine,
+    pub rewards: ValidatorRewardPool,
+    pub quarantine: StateQuarantine,
+    /// Finalized checkpoints by height
+    pub checkpoints: BTreeMap<u64, FinalityCheckpoint>,
+    /// Rejected gossip and invalid checkpoints, in order
+    pub faults: Vec<String>,
+    /// Checkpoints this node proposed, collecting signatures
+    pending_checkpoints: BTreeMap<u64, PendingCheckpoint>,
+    /// Distributions this node proposed as leader, by digest
+    pending_distributions: BTreeMap<String, DistributionCertificate>,
+    /// (kind, epoch) → digest this node co-signed (one per epoch)
+    cosigned: BTreeMap<(DistributionKind, u64), String>,
+    endpoint: Endpoint,
+}
+
+impl HarnessNode {
+    /// Validator `keypair` on a copy of the genesis ledger. Every member of
+    /// `validator_keys` is registered in the reward pool at epoch 0.
+    pub fn new(
+        keypair: KeyPair,
+        genesis: &Ledger,
+        validator_keys: BTreeMap<String, Vec<u8>>,
+        genesis_time: u64,
+        endpoint: Endpoint,
+    ) -> Self {
+        let address = los_crypto::public_key_to_address(&keypair.public_key);
+        let mut rewards = ValidatorRewardPool::new(genesis_time);
+        for addr in validator_keys.keys() {
+            let stake = genesis.accounts.get(addr).map_or(0, |a| a.balance);
+            rewards.register_validator(addr, false, stake);
+        }
+        Self {
+            id: endpoint.id,
+            address,
+            keypair,
+            validator_keys,
+            ledger: genesis.clone(),
+            vm: WasmEngine::new(),
+            rewards,
+            quarantine: StateQuarantine::new(QuarantineConfig::default()),
+            checkpoints: BTreeMap::new(),
+            faults: Vec::new(),
+            pending_checkpoints: BTreeMap::new(),
+            pending_distributions: BTreeMap::new(),
+            cosigned: BTreeMap::new(),
+            endpoint,
+        }
+    }
+
+    /// Root a checkpoint commits to: accounts + VM + reward pool
+    pub fn state_root(&self) -> Result<String, String> {
+        quarantine::full_root(&self.ledger, &self.vm, &self.rewards)
+    }
+
+    /// Tip block a checkpoint names
+    pub fn tip(&self) -> String {
+        quarantine::latest_block_hash(&self.ledger)
+    }
+
+    /// Apply a client block here first, then gossip it (the REST submit
+    /// path). Returns the block hash, or the contract address of a deploy.
+    pub fn submit(&mut self, gossip: Gossip, now: u64) -> Result<String, String> {
+        let hash = self.apply(&gossip, now)?;
+        self.endpoint.broadcast(&gossip);
+        Ok(hash)
+    }
+
+    /// Handle every queued message; returns how many there were
+    pub fn poll(&mut self, now: u64) -> usize {
+        let mut handled = 0;
+        while let Some(env) = self.endpoint.try_recv() {
+            self.handle(env, now);
+            handled += 1;
+        }
+        handled
+    }
+
+    /// Record our own heartbeat and gossip it
+    pub fn heartbeat(&mut self) {
+        self.rewards.record_heartbeat(&self.address);
+        self.endpoint
+            .broadcast(&Gossip::Heartbeat(self.address.clone()));
+    }
+
+    fn fault(&mut self, what: String) {
+        self.faults.push(format!("{}: {}", self.address, what));
+    }
+
+    /// Contract deploys and calls through los-node's contract_apply, every
+    /// other block through the ledger (see `submit` for the result)
+    fn apply(&mut self, gossip: &Gossip, now: u64) -> Result<String, String> {
+        match gossip {
+            Gossip::Deploy { block, bytecode } => contract_apply::apply_deploy_block(
+                &mut self.ledger,
+                &self.vm,
+                block,
+                bytecode.clone(),
+                BTreeMap::new(),
+            ),
+            Gossip::Block(block) if block.block_type == BlockType::ContractCall => {
+                contract_apply::apply_contract_block(&mut self.ledger, &self.vm, block)
+                    .map(|outcome| outcome.block_hash)
+            }
+            Gossip::Block(block) => {
+                let result = self.ledger.process_block_at(block, now)?;
+                if result.is_new() && block.block_type == BlockType::Mint {
+                    self.rewards.sync_reward_mint(block);
+                }
+                Ok(result.into_hash())
+            }
+            _ => Err("not a block".to_string()),
+        }
+    }
+
+    fn handle(&mut self, env: Envelope, now: u64) {
+        match env.gossip {
+            gossip @ (Gossip::Block(_) | Gossip::Deploy { .. }) => {
+                if let Err(e) = self.apply(&gossip, now) {
+                    self.fault(format!("rejected a block from node {}: {}", env.from, e));
+                }
+            }
+            Gossip::Heartbeat(validator) => self.rewards.record_heartbeat(&validator),
+            Gossip::Propose(cert) => self.on_distribution_proposal(env.from, cert),
+            Gossip::CoSign { digest, signature } => {
+                if let Some(cert) = self.pending_distributions.get_mut(&digest) {
+                    if signature.verify(&cert.distribution) {
+                        cert.add_signature(signature);
+                    }
+                }
+                self.try_mint(&digest, now);
+            }
+            Gossip::CheckpointPropose(cp) => self.on_checkpoint_proposal(env.from, cp, now),
+            Gossip::CheckpointSign { height, signature } => {
+                self.on_checkpoint_signature(height, signature)
+            }
+            Gossip::CheckpointFinal(cp) => self.on_final_checkpoint(cp, now),
+        }
+    }
+
+    // ── Epoch rewards ────────────────────────────────────────────────────
+
+    /// Leader of `epoch`: round-robin over the sorted registered validators
+    pub fn reward_leader(&self, epoch: u64) -> Option<&str> {
+        let registered: Vec<&String> = self.rewards.validators.keys().collect();
+        (!registered.is_empty()).then(|| registered[(epoch as usize) % registered.len()].as_str())
+    }
+
+    /// End of a reward epoch, as los-node's reward loop does it: the leader
+    /// distributes and proposes, everyone else only advances the epoch.
+    /// One heartbeat per validator per epoch is full uptime.
+    pub fn epoch_tick(&mut self, now: u64) {
+        if !self.rewards.is_epoch_complete(now) {
+            return;
+        }
+        let heartbeat_secs = self.rewards.epoch_duration_secs;
+        let is_leader =
+            self.reward_leader(self.rewards.current_epoch) == Some(self.address.as_str());
+        self.rewards.set_expected_heartbeats(heartbeat_secs);
+        if !is_leader {
+            self.rewards.advance_epoch_only();
+            self.rewards.set_expected_heartbeats(heartbeat_secs);
+            return;
+        }
+
+        let stakes: Vec<(String, u128)> = self
+            .rewards
+            .validators
+            .keys()
+            .filter_map(|addr| Some((addr.clone(), self.ledger.accounts.get(addr)?.balance)))
+            .collect();
+        for (addr, balance) in &stakes {
+            self.rewards.update_stake(addr, *balance);
+        }
+        let fees = self.ledger.accumulated_fees_cil;
+        let fee_weights: Vec<(String, u128)> = self
+            .ledger
+            .accounts
+            .iter()
+            .filter(|(_, s)| s.is_validator && s.balance >= MIN_VALIDATOR_STAKE_CIL)
+            .map(|(addr, s)| (addr.clone(), calculate_voting_power(s.balance)))
+            .collect();
+
+        let rewards = self.rewards.distribute_epoch_rewards();
+        self.rewards.set_expected_heartbeats(heartbeat_secs);
+        let completed_epoch = self.rewards.current_epoch - 1;
+        let supply = self.ledger.distribution.remaining_supply;
+        let distributions = [
+            EpochDistribution::new(
+                DistributionKind::Reward,
+                completed_epoch,
+                rewards.into_iter().filter(|(_, r)| *r <= supply),
+            ),
+            EpochDistribution::new(
+                DistributionKind::Fee,
+                completed_epoch,
+                pro_rata_shares(fees, &fee_weights),
+            ),
+        ];
+        for dist in distributions.into_iter().filter(|d| !d.payouts.is_empty()) {
+            let own = match CoSignature::sign(
+                &dist,
+                &self.address,
+                &self.keypair.public_key,
+                &self.keypair.secret_key,
+            ) {
+                Ok(sig) => sig,
+                Err(e) => {
+                    self.fault(e);
+                    continue;
+                }
+            };
+            let cert = DistributionCertificate {
+                distribution: dist,
+                signatures: vec![own],
+            };
+            let digest = cert.distribution.digest();
+            self.endpoint.broadcast(&Gossip::Propose(cert.clone()));
+            self.pending_distributions.insert(digest.clone(), cert);
+            // A single validator is its own quorum
+            self.try_mint(&digest, now);
+        }
+    }
+
+    /// Co-sign the epoch leader's proposal if it matches our pool and ledger
+    fn on_distribution_proposal(&mut self, from: NodeId, cert: DistributionCertificate) {
+        let dist = &cert.distribution;
+        let from_leader = cert.signatures.first().is_some_and(|p| {
+            self.reward_leader(dist.epoch) == Some(p.validator.as_str()) && p.verify(dist)
+        });
+        if !from_leader {
+            self.fault(format!(
+                "epoch {} proposal from node {} is not signed by the epoch leader",
+                dist.epoch, from
+            ));
+            return;
+        }
+        if let Err(e) = dist.check_proposal(&self.ledger, &self.rewards) {
+            self.fault(format!(
+                "not co-signing epoch {} {:?} distribution: {}",
+                dist.epoch, dist.kind, e
+            ));
+            return;
+        }
+        let digest = dist.digest();
+        let signed = self
+            .cosigned
+            .entry((dist.kind, dist.epoch))
+            .or_insert_with(|| digest.clone());
+        if *signed != digest {
+            self.fault(format!(
+                "conflicting epoch {} {:?} distribution from the leader",
+                dist.epoch, dist.kind
+            ));
+            return;
+        }
+        match CoSignature::sign(
+            dist,
+            &self.address,
+            &self.keypair.public_key,
+            &self.keypair.secret_key,
+        ) {
+            Ok(signature) => self
+                .endpoint
+                .send(from, Gossip::CoSign { digest, signature }),
+            Err(e) => self.fault(e),
+        }
+    }
+
+    /// Mint a proposed distribution once a validator quorum signed it: one
+    /// Mint per recipient carrying the certificate, signed by the leader
+    fn try_mint(&mut self, digest: &str, now: u64) {
+        let validators = self.ledger.reward_validator_set();
+        let Some(cert) = self.pending_distributions.get(digest) else {
+            return;
+        };
+        if cert.verify(&validators).is_err() {
+            return;
+        }
+        let Some(cert) = self.pending_distributions.remove(digest) else {
+            return;
+        };
+        let link = cert.link();
+        let mut credited: u128 = 0;
+        for (account, amount) in &cert.distribution.payouts {
+            let mut block = Block {
+                account: account.clone(),
+                previous: self
+                    .ledger
+                    .accounts
+                    .get(account)
+                    .map_or_else(|| "0".to_string(), |a| a.head.clone()),
+                block_type: BlockType::Mint,
+                amount: *amount,
+                link: link.clone(),
+                signature: String::new(),
+                public_key: hex::encode(&self.keypair.public_key),
+                work: 0,
+                timestamp: now,
+                fee: 0,
+                valid_after: 0,
+                expires_at: 0,
+            };
+            wallet::sign_block(&mut block, &self.keypair);
+            match self.ledger.process_block_at(&block, now) {
+                Ok(_) => {
+                    credited += block.amount;
+                    if cert.distribution.kind == DistributionKind::Fee {
+                        self.rewards.record_fee_reward(&block);
+                    }
+                    self.endpoint.broadcast(&Gossip::Block(block));
+                }
+                Err(e) => self.fault(format!("distribution Mint for {} failed: {}", account, e)),
+            }
+        }
+        if cert.distribution.kind == DistributionKind::Fee {
+            self.ledger.accumulated_fees_cil =
+                self.ledger.accumulated_fees_cil.saturating_sub(credited);
+        }
+    }
+
+    // ── Checkpoints ──────────────────────────────────────────────────────
+
+    fn local_state(&self) -> Option<LocalState> {
+        Some(LocalState {
+            tip: self.tip(),
+            root: self.state_root().ok()?,
+        })
+    }
+
+    fn sign_checkpoint(&self, cp: &FinalityCheckpoint) -> Result<CheckpointSignature, String> {
+        let signature = los_crypto::sign_message(&cp.signing_data(), &self.keypair.secret_key)
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(CheckpointSignature {
+            validator_address: self.address.clone(),
+            signature,
+        })
+    }
+
+    /// Propose a checkpoint at `height` over our tip and full state root
+    pub fn propose_checkpoint(&mut self, height: u64) {
+        let Some(local) = self.local_state() else {
+            self.fault("state root unavailable".to_string());
+            return;
+        };
+        let validator_count = self.ledger.reward_validator_set().len() as u32;
+        let mut cp =
+            FinalityCheckpoint::new(height, local.tip, validator_count, local.root, vec![]);
+        match self.sign_checkpoint(&cp) {
+            Ok(own) => cp.signatures.push(own),
+            Err(e) => return self.fault(e),
+        }
+        cp.signature_count = 1;
+        self.endpoint
+            .broadcast(&Gossip::CheckpointPropose(cp.clone()));
+        self.pending_checkpoints
+            .insert(height, PendingCheckpoint::new(cp));
+        self.finalize_if_quorum(height);
+    }
+
+    fn on_checkpoint_proposal(&mut self, from: NodeId, cp: FinalityCheckpoint, now: u64) {
+        let Some(proposer) = cp.signatures.first().map(|s| s.validator_address.clone()) else {
+            return self.fault(format!("unsigned checkpoint proposal from node {}", from));
+        };
+        let signed = cp.verify_signatures(
+            &|addr| self.validator_keys.get(addr).cloned(),
+            &los_crypto::verify_signature,
+        );
+        if signed != 1 {
+            return self.fault(format!(
+                "checkpoint proposal from node {} has a bad signature",
+                from
+            ));
+        }
+        let Some(local) = self.local_state() else {
+            return self.fault("state root unavailable".to_string());
+        };
+        match self.quarantine.check(&cp, &local, Some(&proposer), now) {
+            Verdict::Agree => match self.sign_checkpoint(&cp) {
+                Ok(signature) => self.endpoint.send(
+                    from,
+                    Gossip::CheckpointSign {
+                        height: cp.height,
+                        signature,
+                    },
+                ),
+                Err(e) => self.fault(e),
+            },
+            verdict => self.fault(format!(
+                "not co-signing checkpoint {} from node {}: {:?}",
+                cp.height, from, verdict
+            )),
+        }
+    }
+
+    fn on_checkpoint_signature(&mut self, height: u64, signature: CheckpointSignature) {
+        let Some(pending) = self.pending_checkpoints.get_mut(&height) else {
+            return;
+        };
+        let valid = self
+            .validator_keys
+            .get(&signature.validator_address)
+            .is_some_and(|pk| {
+                los_crypto::verify_signature(&pending.signing_data, &signature.signature, pk)
+            });
+        if valid {
+            pending.add_signature(signature);
+        }
+        self.finalize_if_quorum(height);
+    }
+
+    fn finalize_if_quorum(&mut self, height: u64) {
+        if !self
+            .pending_checkpoints
+            .get(&height)
+            .is_some_and(|p| p.has_quorum())
+        {
+            return;
+        }
+        if let Some(pending) = self.pending_checkpoints.remove(&height) {
+            let cp = pending.checkpoint;
+            self.endpoint
+                .broadcast(&Gossip::CheckpointFinal(cp.clone()));
+            self.checkpoints.insert(height, cp);
+        }
+    }
+
+    /// Store a quorum checkpoint after the checks los-node runs on
+    /// CHECKPOINT_FINAL: every signature valid, 2f+1 of our validator count
+    fn on_final_checkpoint(&mut self, cp: FinalityCheckpoint, now: u64) {
+        let valid = cp.verify_signatures(
+            &|addr| self.validator_keys.get(addr).cloned(),
+            &los_crypto::verify_signature,
+        );
+        let local_count = self.ledger.reward_validator_set().len().max(1) as u32;
+        if valid as usize != cp.signatures.len()
+            || !cp.verify_quorum()
+            || cp.validator_count < local_count
+        {
+            return self.fault(format!(
+                "invalid final checkpoint at height {}: {}/{} valid signatures",
+                cp.height,
+                valid,
+                cp.signatures.len()
+            ));
+        }
+        if let Some(stored) = self.checkpoints.get(&cp.height) {
+            if stored.calculate_id() != cp.calculate_id() {
+                self.fault(format!("conflicting checkpoints at height {}", cp.height));
+            }
+            return;
+        }
+        if let Some(local) = self.local_state() {
+            if let Verdict::Diverged { .. } = self.quarantine.check(&cp, &local, None, now) {
+                self.fault(format!("state diverged from checkpoint {}", cp.height));
+            }
+        }
+        self.checkpoints.insert(cp.height, cp);
+    }
+}
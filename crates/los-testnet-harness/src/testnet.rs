@@ -0,0 +1,331 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// UNAUTHORITY (LOS) - TESTNET DRIVER
+//
+// N HarnessNodes on one channel network and a scripted clock. Client
+// operations go to one node (round-robin), which applies and gossips them;
+// `settle` then drains every inbox until the network is quiet. Time moves
+// only when a script says so, so an epoch rollover is one `EpochRollover`
+// step instead of a two-minute wait.
+//
+// Convergence: every node has the same full state root (accounts + VM +
+// reward pool) and the same tip, no node refused anything, and no node is
+// quarantined.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use crate::net::{self, Gossip, NodeId};
+use crate::node::HarnessNode;
+use crate::wallet::HarnessAccount;
+use los_consensus::checkpoint::CHECKPOINT_INTERVAL;
+use los_core::{AccountState, Block, Ledger, CIL_PER_LOS};
+use std::collections::BTreeMap;
+
+/// Genesis time of every harness network. Below the wall clock, because
+/// contract blocks are checked against the process-wide consensus clock.
+pub const GENESIS_TIME: u64 = 1_771_000_000;
+
+/// Genesis balance of each validator
+pub const VALIDATOR_STAKE_CIL: u128 = 2_000 * CIL_PER_LOS;
+
+/// Genesis balance of each scripted client account
+pub const ACCOUNT_BALANCE_CIL: u128 = 1_000 * CIL_PER_LOS;
+
+/// Upper bound on settle rounds; gossip that never quiets down is a bug
+const MAX_SETTLE_ROUNDS: usize = 1_000;
+
+/// One scripted operation. Accounts and contracts are referred to by name.
+#[derive(Debug, Clone)]
+pub enum Step {
+    Send {
+        from: String,
+        to: String,
+        amount_cil: u128,
+    },
+    /// Receive every pending Send to `account`
+    Receive {
+        account: String,
+    },
+    /// Deploy `bytecode` from `from`, remembered as contract `name`
+    Deploy {
+        from: String,
+        name: String,
+        bytecode: Vec<u8>,
+    },
+    Call {
+        from: String,
+        contract: String,
+        function: String,
+        args: Vec<String>,
+    },
+    /// Heartbeats from every validator, then past the epoch boundary
+    EpochRollover,
+    /// Next checkpoint height, proposed by the next validator in turn
+    Checkpoint,
+    AssertConverged,
+}
+
+pub struct Testnet {
+    pub nodes: Vec<HarnessNode>,
+    pub accounts: BTreeMap<String, HarnessAccount>,
+    /// Contract name → address
+    pub contracts: BTreeMap<String, String>,
+    /// Current scripted time
+    pub now: u64,
+    /// Sends not yet received, per recipient name
+    pending: BTreeMap<String, Vec<Block>>,
+    /// Node the next client operation goes to
+    entry: NodeId,
+    checkpoints: u64,
+}
+
+impl Testnet {
+    /// `validators` nodes, each with a funded validator account, and one
+    /// funded client account per name
+    pub fn new(validators: usize, account_names: &[&str]) -> Self {
+        let keypairs: Vec<_> = (0..validators)
+            .map(|i| los_crypto::generate_keypair_from_seed(&[200u8.wrapping_add(i as u8); 64]))
+            .collect();
+        let validator_keys: BTreeMap<String, Vec<u8>> = keypairs
+            .iter()
+            .map(|kp| {
+                (
+                    los_crypto::public_key_to_address(&kp.public_key),
+                    kp.public_key.clone(),
+                )
+            })
+            .collect();
+        let accounts: BTreeMap<String, HarnessAccount> = account_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), HarnessAccount::from_seed(1 + i as u8)))
+            .collect();
+
+        let mut genesis = Ledger::new();
+        let funded = validator_keys
+            .keys()
+            .map(|addr| (addr, VALIDATOR_STAKE_CIL, true))
+            .chain(
+                accounts
+                    .values()
+                    .map(|a| (&a.address, ACCOUNT_BALANCE_CIL, false)),
+            );
+        for (address, balance, is_validator) in funded {
+            genesis.accounts.insert(
+                address.clone(),
+                AccountState {
+                    head: "0".to_string(),
+                    balance,
+                    block_count: 0,
+                    is_validator,
+                },
+            );
+        }
+
+        let nodes = keypairs
+            .into_iter()
+            .zip(net::connect(validators))
+            .map(|(kp, endpoint)| {
+                HarnessNode::new(kp, &genesis, validator_keys.clone(), GENESIS_TIME, endpoint)
+            })
+            .collect();
+        Self {
+            nodes,
+            accounts,
+            contracts: BTreeMap::new(),
+            now: GENESIS_TIME,
+            pending: BTreeMap::new(),
+            entry: 0,
+            checkpoints: 0,
+        }
+    }
+
+    fn account(&self, name: &str) -> Result<&HarnessAccount, String> {
+        self.accounts
+            .get(name)
+            .ok_or_else(|| format!("unknown account {}", name))
+    }
+
+    /// Hand a client block to the next node in turn; on success move the
+    /// sender's head and let the network settle. Returns what the entry
+    /// node returned (block hash, or contract address for a deploy).
+    fn submit(&mut self, name: &str, block: &Block, gossip: Gossip) -> Result<String, String> {
+        let node = self.entry;
+        self.entry = (self.entry + 1) % self.nodes.len();
+        let result = self.nodes[node].submit(gossip, self.now)?;
+        if let Some(account) = self.accounts.get_mut(name) {
+            account.head = block.calculate_hash();
+        }
+        self.settle();
+        Ok(result)
+    }
+
+    /// Next client block timestamp; one second per block keeps them ordered
+    fn tick(&mut self) -> u64 {
+        self.now += 1;
+        self.now
+    }
+
+    pub fn send(&mut self, from: &str, to: &str, amount_cil: u128) -> Result<String, String> {
+        let ts = self.tick();
+        let to_address = self.account(to)?.address.clone();
+        let block = self.account(from)?.send(&to_address, amount_cil, ts);
+        let hash = self.submit(from, &block, Gossip::Block(block.clone()))?;
+        self.pending.entry(to.to_string()).or_default().push(block);
+        Ok(hash)
+    }
+
+    /// Receive every pending Send to `account`, oldest first
+    pub fn receive(&mut self, account: &str) -> Result<(), String> {
+        for send in self.pending.remove(account).unwrap_or_default() {
+            let ts = self.tick();
+            let block = self.account(account)?.receive(&send, ts);
+            self.submit(account, &block, Gossip::Block(block.clone()))?;
+        }
+        Ok(())
+    }
+
+    /// Deploy `bytecode` from `from`; the contract is known as `name`
+    pub fn deploy(&mut self, from: &str, name: &str, bytecode: Vec<u8>) -> Result<String, String> {
+        let ts = self.tick();
+        let block = self.account(from)?.deploy(&bytecode, ts);
+        let address = self.submit(
+            from,
+            &block,
+            Gossip::Deploy {
+                block: block.clone(),
+                bytecode,
+            },
+        )?;
+        self.contracts.insert(name.to_string(), address.clone());
+        Ok(address)
+    }
+
+    pub fn call(
+        &mut self,
+        from: &str,
+        contract: &str,
+        function: &str,
+        args: &[String],
+    ) -> Result<String, String> {
+        let ts = self.tick();
+        let address = self
+            .contracts
+            .get(contract)
+            .ok_or_else(|| format!("unknown contract {}", contract))?
+            .clone();
+        let block = self.account(from)?.call(&address, function, args, ts);
+        self.submit(from, &block, Gossip::Block(block.clone()))
+    }
+
+    /// Every validator heartbeats once, the clock moves past the current
+    /// epoch's end (grace included), and every node runs its epoch tick
+    pub fn advance_epoch(&mut self) {
+        for node in &mut self.nodes {
+            node.heartbeat();
+        }
+        self.settle();
+        let pool = &self.nodes[0].rewards;
+        let grace = if pool.epoch_duration_secs <= 300 {
+            5
+        } else {
+            300
+        };
+        self.now = self
+            .now
+            .max(pool.epoch_start_timestamp + pool.epoch_duration_secs + grace);
+        for node in &mut self.nodes {
+            node.epoch_tick(self.now);
+        }
+        self.settle();
+    }
+
+    /// Next checkpoint height, proposed by the validators in turn
+    pub fn checkpoint(&mut self) -> u64 {
+        let proposer = (self.checkpoints as usize) % self.nodes.len();
+        self.checkpoints += 1;
+        let height = self.checkpoints * CHECKPOINT_INTERVAL;
+        self.nodes[proposer].propose_checkpoint(height);
+        self.settle();
+        height
+    }
+
+    /// Deliver gossip until every inbox is empty
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_SETTLE_ROUNDS {
+            let mut handled = 0;
+            for node in &mut self.nodes {
+                handled += node.poll(self.now);
+            }
+            if handled == 0 {
+                return;
+            }
+        }
+        panic!("gossip did not settle in {} rounds", MAX_SETTLE_ROUNDS);
+    }
+
+    /// Run `steps` in order, stopping at the first failure
+    pub fn run(&mut self, steps: &[Step]) -> Result<(), String> {
+        for (i, step) in steps.iter().enumerate() {
+            let result = match step {
+                Step::Send {
+                    from,
+                    to,
+                    amount_cil,
+                } => self.send(from, to, *amount_cil).map(drop),
+                Step::Receive { account } => self.receive(account),
+                Step::Deploy {
+                    from,
+                    name,
+                    bytecode,
+                } => self.deploy(from, name, bytecode.clone()).map(drop),
+                Step::Call {
+                    from,
+                    contract,
+                    function,
+                    args,
+                } => self.call(from, contract, function, args).map(drop),
+                Step::EpochRollover => {
+                    self.advance_epoch();
+                    Ok(())
+                }
+                Step::Checkpoint => {
+                    self.checkpoint();
+                    Ok(())
+                }
+                Step::AssertConverged => self.check_converged().map(drop),
+            };
+            result.map_err(|e| format!("step {} ({:?}): {}", i, step, e))?;
+        }
+        Ok(())
+    }
+
+    /// The common full state root, or why the nodes disagree
+    pub fn check_converged(&self) -> Result<String, String> {
+        let faults: Vec<&String> = self.nodes.iter().flat_map(|n| &n.faults).collect();
+        if !faults.is_empty() {
+            return Err(format!("faults: {:?}", faults));
+        }
+        if let Some(node) = self.nodes.iter().find(|n| n.quarantine.is_quarantined()) {
+            return Err(format!("node {} is quarantined", node.id));
+        }
+        let root = self.nodes[0].state_root()?;
+        let tip = self.nodes[0].tip();
+        for node in &self.nodes[1..] {
+            if node.tip() != tip {
+                return Err(format!(
+                    "node {} tip {} != node 0 tip {}",
+                    node.id,
+                    node.tip(),
+                    tip
+                ));
+            }
+            let other = node.state_root()?;
+            if other != root {
+                return Err(format!(
+                    "node {} state root {} != node 0 root {}",
+                    node.id, other, root
+                ));
+            }
+        }
+        Ok(root)
+    }
+}
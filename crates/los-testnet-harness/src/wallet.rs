@@ -0,0 +1,112 @@
+// Client accounts of a scenario: real Dilithium5 keys and PoW, so every node
+// runs the same checks on their blocks as a deployed validator would.
+
+use base64::Engine as _;
+use los_core::deploy_link::DeployLink;
+use los_core::{Block, BlockType};
+use los_crypto::KeyPair;
+use los_vm::WasmEngine;
+
+/// A funded client account. Tracks its chain head; the testnet moves it
+/// only when the origin node accepted the block.
+pub struct HarnessAccount {
+    pub keypair: KeyPair,
+    pub address: String,
+    pub head: String,
+}
+
+impl HarnessAccount {
+    /// Deterministic account from a one-byte seed
+    pub fn from_seed(seed: u8) -> Self {
+        let keypair = los_crypto::generate_keypair_from_seed(&[seed; 64]);
+        let address = los_crypto::public_key_to_address(&keypair.public_key);
+        Self {
+            keypair,
+            address,
+            head: "0".to_string(),
+        }
+    }
+
+    /// Send `amount_cil` to `to` at `timestamp`
+    pub fn send(&self, to: &str, amount_cil: u128, timestamp: u64) -> Block {
+        self.sign(
+            BlockType::Send,
+            amount_cil,
+            to.to_string(),
+            los_core::BASE_FEE_CIL,
+            timestamp,
+        )
+    }
+
+    /// Receive the Send `send`
+    pub fn receive(&self, send: &Block, timestamp: u64) -> Block {
+        self.sign(
+            BlockType::Receive,
+            send.amount,
+            send.calculate_hash(),
+            0,
+            timestamp,
+        )
+    }
+
+    /// Deploy `bytecode` (unsalted, no initial funding)
+    pub fn deploy(&self, bytecode: &[u8], timestamp: u64) -> Block {
+        let link = DeployLink::format(&WasmEngine::compute_code_hash(bytecode), None);
+        self.sign(
+            BlockType::ContractDeploy,
+            0,
+            link,
+            los_core::MIN_DEPLOY_FEE_CIL,
+            timestamp,
+        )
+    }
+
+    /// Call `function` of `contract`; the fee buys DEFAULT_GAS_LIMIT gas
+    pub fn call(&self, contract: &str, function: &str, args: &[String], timestamp: u64) -> Block {
+        let args = serde_json::to_vec(args).unwrap_or_default();
+        let link = format!(
+            "CALL:{}:{}:{}",
+            contract,
+            function,
+            base64::engine::general_purpose::STANDARD.encode(args)
+        );
+        let fee = los_core::DEFAULT_GAS_LIMIT as u128 * los_core::GAS_PRICE_CIL;
+        self.sign(BlockType::ContractCall, 0, link, fee, timestamp)
+    }
+
+    fn sign(
+        &self,
+        block_type: BlockType,
+        amount: u128,
+        link: String,
+        fee: u128,
+        timestamp: u64,
+    ) -> Block {
+        let mut block = Block {
+            account: self.address.clone(),
+            previous: self.head.clone(),
+            block_type,
+            amount,
+            link,
+            signature: String::new(),
+            public_key: hex::encode(&self.keypair.public_key),
+            work: 0,
+            timestamp,
+            fee,
+            valid_after: 0,
+            expires_at: 0,
+        };
+        sign_block(&mut block, &self.keypair);
+        block
+    }
+}
+
+/// Solve PoW and sign with `keypair` (client blocks and validator Mints)
+pub fn sign_block(block: &mut Block, keypair: &KeyPair) {
+    while !block.verify_pow() {
+        block.work += 1;
+    }
+    let signature = los_crypto::sign_message(block.signing_hash().as_bytes(), &keypair.secret_key)
+        .expect("signing with a generated key");
+    block.signature = hex::encode(signature);
+}
@@ -0,0 +1,104 @@
+// End-to-end scenarios: every node must reach the same full state root
+// after transfers, contracts, reward epochs and checkpoints — and a node
+// whose state drifted must be caught by the next checkpoint.
+
+use los_core::CIL_PER_LOS;
+use los_testnet_harness::{contracts, Step, Testnet};
+
+fn s(v: &str) -> String {
+    v.to_string()
+}
+
+fn transfer_and_contract() -> Vec<Step> {
+    vec![
+        Step::Send {
+            from: s("alice"),
+            to: s("bob"),
+            amount_cil: 10 * CIL_PER_LOS,
+        },
+        Step::Receive { account: s("bob") },
+        Step::Deploy {
+            from: s("alice"),
+            name: s("kv"),
+            bytecode: contracts::kv_store(),
+        },
+        Step::Call {
+            from: s("bob"),
+            contract: s("kv"),
+            function: s("set"),
+            args: vec![s("greeting"), s("hello")],
+        },
+        Step::AssertConverged,
+    ]
+}
+
+#[test]
+fn test_full_scenario_converges() {
+    let mut net = Testnet::new(4, &["alice", "bob"]);
+    let mut steps = transfer_and_contract();
+    steps.extend([Step::EpochRollover, Step::Checkpoint, Step::AssertConverged]);
+    net.run(&steps).unwrap();
+
+    let kv = net.contracts["kv"].clone();
+    let bob = net.accounts["bob"].address.clone();
+    for node in &net.nodes {
+        let state = node.vm.get_contract_state(&kv).unwrap();
+        assert_eq!(state.get("greeting").map(String::as_str), Some("hello"));
+        assert_eq!(
+            node.ledger.accounts[&bob].balance,
+            net.nodes[0].ledger.accounts[&bob].balance
+        );
+    }
+}
+
+#[test]
+fn test_checkpoint_finalizes_on_every_node() {
+    let mut net = Testnet::new(4, &["alice", "bob"]);
+    net.run(&transfer_and_contract()).unwrap();
+    let height = net.checkpoint();
+    let root = net.check_converged().unwrap();
+    for node in &net.nodes {
+        let cp = &node.checkpoints[&height];
+        assert!(cp.verify_quorum());
+        assert_eq!(cp.state_root, root);
+    }
+}
+
+#[test]
+fn test_rewards_are_minted_after_probation() {
+    let mut net = Testnet::new(3, &["alice"]);
+    // Epoch 0 is everyone's probation epoch; epoch 1 pays out
+    net.run(&[
+        Step::EpochRollover,
+        Step::EpochRollover,
+        Step::AssertConverged,
+    ])
+    .unwrap();
+    for node in &net.nodes {
+        assert!(node.rewards.total_distributed_cil > 0);
+        assert!(node
+            .ledger
+            .blocks
+            .values()
+            .any(|b| b.link.starts_with("REWARD:")));
+    }
+    net.run(&[Step::Checkpoint, Step::AssertConverged]).unwrap();
+}
+
+#[test]
+fn test_diverged_node_is_caught_by_checkpoint() {
+    let mut net = Testnet::new(4, &["alice", "bob"]);
+    net.run(&transfer_and_contract()).unwrap();
+
+    // Node 3 silently credits bob: its root no longer matches at the same tip
+    let bob = net.accounts["bob"].address.clone();
+    net.nodes[3].ledger.accounts.get_mut(&bob).unwrap().balance += 1;
+    let height = net.checkpoint();
+
+    // Three honest signatures are still a quorum of four...
+    assert!(net.nodes[0].checkpoints.contains_key(&height));
+    // ...and the drifted node refused to co-sign and is quarantined
+    assert!(net.nodes[3].quarantine.is_quarantined());
+    let err = net.check_converged().unwrap_err();
+    assert!(err.contains("not co-signing checkpoint"), "{}", err);
+}
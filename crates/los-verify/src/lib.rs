@@ -0,0 +1,545 @@
+//! # LOS Verify — Reproducible Contract Builds
+//!
+//! Rebuilds a contract's WASM from a **source bundle** (every source file of
+//! the cargo project plus the pinned toolchain) and compares its blake3 hash
+//! with the `code_hash` of the deployed contract. A node with verification
+//! builds enabled runs this for `POST /contract/{address}/verify`; anyone can
+//! run the same build offline with the `los-verify` binary.
+//!
+//! ## Canonical build
+//! ```text
+//! cargo +<rustc> build --release --locked --target wasm32-unknown-unknown
+//!       [--package <package>] [--features <f,..>] [--no-default-features]
+//! RUSTFLAGS="--remap-path-prefix=<src>=/los/src --remap-path-prefix=<cargo home>=/los/cargo"
+//! ```
+//!
+//! Paths are remapped so the WASM does not depend on where it was built.
+//! Deploy the output of `los-verify build` (or of the same command) and a
+//! later verification reproduces it byte for byte.
+//!
+//! ## What a bundle may contain
+//! Building runs the compiler on untrusted input, so bundles are restricted:
+//! relative paths only, no `build.rs`, no proc-macro crates, no `.cargo/`
+//! config, no `rust-toolchain` file (the toolchain comes from the metadata),
+//! and a `Cargo.lock` whose packages all come from crates.io. Dependencies'
+//! own build scripts still run: nodes that enable builds should do so in a
+//! sandbox.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// The only target contracts are built for
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
+/// Largest bundle accepted (sum of file sizes)
+pub const MAX_BUNDLE_BYTES: usize = 4 * 1_048_576;
+pub const MAX_BUNDLE_FILES: usize = 512;
+/// Only source allowed in Cargo.lock
+const CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+/// Lines of compiler output kept in a build error
+const ERROR_TAIL_LINES: usize = 20;
+
+/// Toolchain metadata submitted with the sources
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toolchain {
+    /// Pinned rustup toolchain: `1.85.0` or `nightly-2025-01-15`
+    pub rustc: String,
+    #[serde(default = "default_target")]
+    pub target: String,
+    /// Workspace member to build (required if the build yields several .wasm)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    #[serde(default)]
+    pub no_default_features: bool,
+}
+
+fn default_target() -> String {
+    WASM_TARGET.to_string()
+}
+
+/// Sources + toolchain: everything needed to rebuild a contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceBundle {
+    pub toolchain: Toolchain,
+    /// Relative path (`/`-separated) → file contents
+    pub files: BTreeMap<String, String>,
+}
+
+/// Outcome of rebuilding a bundle against a deployed code hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Verification {
+    pub expected_hash: String,
+    pub built_hash: String,
+    pub matched: bool,
+}
+
+/// How to run a build
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    /// Scratch directory (sources + target dir), removed afterwards
+    pub work_dir: PathBuf,
+    /// The build is killed after this long
+    pub timeout: Duration,
+}
+
+/// Contract code hash, as `WasmEngine::compute_code_hash` computes it
+pub fn code_hash(wasm: &[u8]) -> String {
+    hex::encode(blake3::hash(wasm).as_bytes())
+}
+
+fn is_pinned_toolchain(v: &str) -> bool {
+    let numeric = |s: &str, parts: usize| {
+        let p: Vec<&str> = s.split('.').collect();
+        p.len() == parts
+            && p.iter()
+                .all(|x| !x.is_empty() && x.chars().all(|c| c.is_ascii_digit()))
+    };
+    if let Some(date) = v.strip_prefix("nightly-") {
+        let d: Vec<&str> = date.split('-').collect();
+        return d.len() == 3
+            && [4, 2, 2]
+                .iter()
+                .zip(&d)
+                .all(|(len, part)| part.len() == *len && part.chars().all(|c| c.is_ascii_digit()));
+    }
+    numeric(v, 3)
+}
+
+/// Package / feature name (no leading '-', so it cannot pass for a flag)
+fn is_cargo_name(v: &str) -> bool {
+    v.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        && v.len() <= 64
+        && v.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '/')
+}
+
+fn check_path(path: &str) -> Result<(), String> {
+    if path.is_empty() || path.starts_with('/') || path.contains('\\') || path.contains(':') {
+        return Err(format!("Invalid path '{}': must be relative", path));
+    }
+    for part in path.split('/') {
+        if part.is_empty() || part == "." || part == ".." {
+            return Err(format!("Invalid path '{}'", path));
+        }
+        if part == ".cargo" {
+            return Err(format!("'{}': cargo config is not allowed", path));
+        }
+    }
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if name == "build.rs" {
+        return Err(format!("'{}': build scripts are not allowed", path));
+    }
+    if name.starts_with("rust-toolchain") {
+        return Err(format!(
+            "'{}': set the toolchain in the bundle metadata instead",
+            path
+        ));
+    }
+    Ok(())
+}
+
+/// Line scan of a manifest for build scripts and proc-macro crates
+fn check_manifest(path: &str, text: &str) -> Result<(), String> {
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key == "build" && value != "false" {
+            return Err(format!("{}: build scripts are not allowed", path));
+        }
+        if (key == "proc-macro" || key == "proc_macro") && value == "true" {
+            return Err(format!("{}: proc-macro crates are not allowed", path));
+        }
+    }
+    Ok(())
+}
+
+fn check_lockfile(text: &str) -> Result<(), String> {
+    for line in text.lines() {
+        if let Some(source) = line.trim().strip_prefix("source = ") {
+            let source = source.trim_matches('"');
+            if source != CRATES_IO_SOURCE {
+                return Err(format!(
+                    "Cargo.lock: dependency source '{}' is not crates.io",
+                    source
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl SourceBundle {
+    /// Reject bundles that are too large, escape the build directory or
+    /// could run code of their own at build time (see module docs)
+    pub fn validate(&self) -> Result<(), String> {
+        let tc = &self.toolchain;
+        if !is_pinned_toolchain(&tc.rustc) {
+            return Err(format!(
+                "toolchain.rustc '{}' must be a pinned version (1.85.0 or nightly-YYYY-MM-DD)",
+                tc.rustc
+            ));
+        }
+        if tc.target != WASM_TARGET {
+            return Err(format!("toolchain.target must be {}", WASM_TARGET));
+        }
+        if let Some(p) = &tc.package {
+            if !is_cargo_name(p) {
+                return Err(format!("Invalid package name '{}'", p));
+            }
+        }
+        if let Some(f) = tc.features.iter().find(|f| !is_cargo_name(f)) {
+            return Err(format!("Invalid feature '{}'", f));
+        }
+
+        if self.files.is_empty() || self.files.len() > MAX_BUNDLE_FILES {
+            return Err(format!(
+                "Bundle must contain 1..={} files",
+                MAX_BUNDLE_FILES
+            ));
+        }
+        let total: usize = self.files.iter().map(|(p, c)| p.len() + c.len()).sum();
+        if total > MAX_BUNDLE_BYTES {
+            return Err(format!(
+                "Bundle is {} bytes (max {})",
+                total, MAX_BUNDLE_BYTES
+            ));
+        }
+        for (path, text) in &self.files {
+            check_path(path)?;
+            if path == "Cargo.toml" || path.ends_with("/Cargo.toml") {
+                check_manifest(path, text)?;
+            }
+        }
+        if !self.files.contains_key("Cargo.toml") {
+            return Err("Bundle has no top-level Cargo.toml".to_string());
+        }
+        match self.files.get("Cargo.lock") {
+            Some(lock) => check_lockfile(lock),
+            None => Err("Bundle has no Cargo.lock (needed for a reproducible build)".to_string()),
+        }
+    }
+
+    /// blake3 of the canonical JSON encoding (files are sorted by path)
+    pub fn bundle_hash(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(blake3::hash(&json).as_bytes())
+    }
+
+    /// Pack a cargo project directory (skips `target/` and dot-entries)
+    pub fn from_dir(root: &Path, toolchain: Toolchain) -> Result<Self, String> {
+        fn walk(
+            root: &Path,
+            dir: &Path,
+            files: &mut BTreeMap<String, String>,
+        ) -> Result<(), String> {
+            let entries = std::fs::read_dir(dir)
+                .map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+            for entry in entries {
+                let entry = entry.map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                let path = entry.path();
+                if name.starts_with('.') || (dir == root && name == "target") {
+                    continue;
+                }
+                if path.is_dir() {
+                    walk(root, &path, files)?;
+                    continue;
+                }
+                let rel = path
+                    .strip_prefix(root)
+                    .map_err(|e| e.to_string())?
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let text = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("{}: {} (only UTF-8 files can be bundled)", rel, e))?;
+                files.insert(rel, text);
+            }
+            Ok(())
+        }
+        let mut files = BTreeMap::new();
+        walk(root, root, &mut files)?;
+        let bundle = SourceBundle { toolchain, files };
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    /// Write the sources under `dir`
+    pub fn write_to(&self, dir: &Path) -> Result<(), String> {
+        self.validate()?;
+        for (rel, text) in &self.files {
+            let path = dir.join(rel);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+            }
+            std::fs::write(&path, text)
+                .map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Arguments of the canonical cargo invocation
+    pub fn cargo_args(&self) -> Vec<String> {
+        let tc = &self.toolchain;
+        let mut args = vec![
+            format!("+{}", tc.rustc),
+            "build".to_string(),
+            "--release".to_string(),
+            "--locked".to_string(),
+            "--target".to_string(),
+            tc.target.clone(),
+        ];
+        if let Some(p) = &tc.package {
+            args.push("--package".to_string());
+            args.push(p.clone());
+        }
+        if !tc.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(tc.features.join(","));
+        }
+        if tc.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        args
+    }
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cargo")))
+}
+
+fn tail(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(ERROR_TAIL_LINES)..].join("\n")
+}
+
+/// The single .wasm the build produced (or the one named after `package`)
+fn find_artifact(out_dir: &Path, package: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(p) = package {
+        let path = out_dir.join(format!("{}.wasm", p.replace('-', "_")));
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!("Build produced no {}", path.display()))
+        };
+    }
+    let wasm: Vec<PathBuf> = std::fs::read_dir(out_dir)
+        .map_err(|e| format!("No build output in {}: {}", out_dir.display(), e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|x| x == "wasm"))
+        .collect();
+    match wasm.as_slice() {
+        [one] => Ok(one.clone()),
+        [] => Err("Build produced no .wasm (is the crate a cdylib?)".to_string()),
+        _ => Err("Build produced several .wasm files; set toolchain.package".to_string()),
+    }
+}
+
+/// Run the canonical build of `bundle` and return the WASM
+pub fn build(bundle: &SourceBundle, opts: &BuildOptions) -> Result<Vec<u8>, String> {
+    bundle.validate()?;
+    let src = opts.work_dir.join("src");
+    let target = opts.work_dir.join("target");
+    let _ = std::fs::remove_dir_all(&opts.work_dir);
+    bundle.write_to(&src)?;
+
+    let mut rustflags = format!("--remap-path-prefix={}=/los/src", src.display());
+    if let Some(home) = cargo_home() {
+        rustflags.push_str(&format!(
+            " --remap-path-prefix={}=/los/cargo",
+            home.display()
+        ));
+    }
+    let mut child = Command::new("cargo")
+        .args(bundle.cargo_args())
+        .current_dir(&src)
+        .env("CARGO_TARGET_DIR", &target)
+        .env("RUSTFLAGS", rustflags)
+        .env("CARGO_INCREMENTAL", "0")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("RUSTC_WRAPPER")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Cannot run cargo: {}", e))?;
+
+    // Drain stderr on a thread so a chatty build cannot block on a full pipe
+    let mut stderr = child.stderr.take();
+    let reader = std::thread::spawn(move || {
+        let mut out = String::new();
+        if let Some(s) = stderr.as_mut() {
+            let _ = s.read_to_string(&mut out);
+        }
+        out
+    });
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() > opts.timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                let _ = std::fs::remove_dir_all(&opts.work_dir);
+                return Err(format!("Build timed out after {}s", opts.timeout.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+            Err(e) => return Err(format!("Build failed to run: {}", e)),
+        }
+    };
+    let log = reader.join().unwrap_or_default();
+
+    let result = if status.success() {
+        let out_dir = target.join(&bundle.toolchain.target).join("release");
+        find_artifact(&out_dir, bundle.toolchain.package.as_deref()).and_then(|path| {
+            std::fs::read(&path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+        })
+    } else {
+        Err(format!("Build failed ({}):\n{}", status, tail(&log)))
+    };
+    let _ = std::fs::remove_dir_all(&opts.work_dir);
+    result
+}
+
+/// Rebuild `bundle` and compare its hash with `expected_hash`
+pub fn verify(
+    bundle: &SourceBundle,
+    expected_hash: &str,
+    opts: &BuildOptions,
+) -> Result<Verification, String> {
+    let built_hash = code_hash(&build(bundle, opts)?);
+    Ok(Verification {
+        matched: built_hash.eq_ignore_ascii_case(expected_hash),
+        expected_hash: expected_hash.to_string(),
+        built_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+
+    fn bundle(extra: &[(&str, &str)]) -> SourceBundle {
+        let mut files: BTreeMap<String, String> = [
+            (
+                "Cargo.toml",
+                "[package]\nname = \"token\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n",
+            ),
+            ("Cargo.lock", LOCK),
+            ("src/lib.rs", "#![no_std]\n"),
+        ]
+        .iter()
+        .map(|(p, c)| (p.to_string(), c.to_string()))
+        .collect();
+        for (p, c) in extra {
+            files.insert(p.to_string(), c.to_string());
+        }
+        SourceBundle {
+            toolchain: Toolchain {
+                rustc: "1.85.0".to_string(),
+                target: WASM_TARGET.to_string(),
+                package: None,
+                features: vec![],
+                no_default_features: false,
+            },
+            files,
+        }
+    }
+
+    #[test]
+    fn test_valid_bundle_and_hash_is_stable() {
+        let b = bundle(&[]);
+        assert!(b.validate().is_ok());
+        assert_eq!(b.bundle_hash(), bundle(&[]).bundle_hash());
+        assert_ne!(b.bundle_hash(), bundle(&[("README.md", "x")]).bundle_hash());
+        assert_eq!(
+            code_hash(b"\0asm"),
+            hex::encode(blake3::hash(b"\0asm").as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsafe_bundles() {
+        for (path, text) in [
+            ("../escape.rs", ""),
+            ("/etc/passwd", ""),
+            ("src/./lib.rs", ""),
+            ("build.rs", "fn main() {}"),
+            ("sub/build.rs", "fn main() {}"),
+            (".cargo/config.toml", ""),
+            ("rust-toolchain.toml", ""),
+            ("macros/Cargo.toml", "[lib]\nproc-macro = true\n"),
+            ("sub/Cargo.toml", "[package]\nbuild = \"gen.rs\"\n"),
+        ] {
+            assert!(
+                bundle(&[(path, text)]).validate().is_err(),
+                "accepted {}",
+                path
+            );
+        }
+
+        let mut git = bundle(&[]);
+        git.files.insert(
+            "Cargo.lock".to_string(),
+            LOCK.replace(
+                "registry+https://github.com/rust-lang/crates.io-index",
+                "git+https://example.com/x",
+            ),
+        );
+        assert!(git.validate().unwrap_err().contains("not crates.io"));
+
+        let mut no_lock = bundle(&[]);
+        no_lock.files.remove("Cargo.lock");
+        assert!(no_lock.validate().is_err());
+
+        for rustc in [
+            "stable",
+            "1.85",
+            "1.85.0; rm -rf /",
+            "nightly",
+            "nightly-2025-1-15",
+        ] {
+            let mut b = bundle(&[]);
+            b.toolchain.rustc = rustc.to_string();
+            assert!(b.validate().is_err(), "accepted toolchain {}", rustc);
+        }
+        let mut flag = bundle(&[]);
+        flag.toolchain.features = vec!["--config=x".to_string()];
+        assert!(flag.validate().is_err());
+        let mut nightly = bundle(&[]);
+        nightly.toolchain.rustc = "nightly-2025-01-15".to_string();
+        assert!(nightly.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pack_and_unpack_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("los-verify-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let b = bundle(&[]);
+        b.write_to(&dir.join("proj")).unwrap();
+        std::fs::create_dir_all(dir.join("proj/target/release")).unwrap();
+        std::fs::write(dir.join("proj/target/release/junk"), "x").unwrap();
+        std::fs::write(dir.join("proj/.gitignore"), "target\n").unwrap();
+
+        let packed = SourceBundle::from_dir(&dir.join("proj"), b.toolchain.clone()).unwrap();
+        assert_eq!(packed, b);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let args = b.cargo_args();
+        assert_eq!(args[0], "+1.85.0");
+        assert!(args.contains(&"--locked".to_string()));
+    }
+}
@@ -0,0 +1,139 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// LOS-VERIFY - Reproducible contract builds (offline verifier)
+//
+//   los-verify pack <project> --rustc 1.85.0 > bundle.json
+//   los-verify build bundle.json --out contract.wasm    (deploy this file)
+//   los-verify check bundle.json --code-hash <hex>      (compare with a node)
+//
+// `pack` output is the body of POST /contract/{address}/verify.
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+use clap::{Parser, Subcommand};
+use los_verify::{BuildOptions, SourceBundle, Toolchain, WASM_TARGET};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "los-verify")]
+#[command(about = "Rebuild LOS contracts reproducibly and check them against deployed code hashes", long_about = None)]
+#[command(version)]
+struct Cli {
+    /// Build timeout in seconds
+    #[arg(long, default_value_t = 900, global = true)]
+    timeout: u64,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Pack a cargo project into a source bundle (JSON on stdout)
+    Pack {
+        /// Project directory (with Cargo.toml and Cargo.lock)
+        dir: PathBuf,
+        /// Pinned toolchain (1.85.0 or nightly-YYYY-MM-DD)
+        #[arg(long)]
+        rustc: String,
+        /// Workspace member to build
+        #[arg(long)]
+        package: Option<String>,
+        /// Comma-separated cargo features
+        #[arg(long, value_delimiter = ',')]
+        features: Vec<String>,
+        #[arg(long)]
+        no_default_features: bool,
+    },
+    /// Run the canonical build of a bundle and print its code hash
+    Build {
+        bundle: PathBuf,
+        /// Write the WASM here
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Rebuild a bundle and compare it with a deployed code hash
+    Check {
+        bundle: PathBuf,
+        #[arg(long)]
+        code_hash: String,
+    },
+}
+
+fn load(path: &Path) -> Result<SourceBundle, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid bundle {}: {}", path.display(), e))
+}
+
+fn options(timeout: u64) -> BuildOptions {
+    BuildOptions {
+        work_dir: std::env::temp_dir().join(format!("los-verify-{}", std::process::id())),
+        timeout: Duration::from_secs(timeout),
+    }
+}
+
+fn run(cli: Cli) -> Result<bool, String> {
+    match cli.command {
+        Commands::Pack {
+            dir,
+            rustc,
+            package,
+            features,
+            no_default_features,
+        } => {
+            let toolchain = Toolchain {
+                rustc,
+                target: WASM_TARGET.to_string(),
+                package,
+                features,
+                no_default_features,
+            };
+            let bundle = SourceBundle::from_dir(&dir, toolchain)?;
+            let json = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+            println!("{}", json);
+            eprintln!(
+                "📦 {} files, bundle hash {}",
+                bundle.files.len(),
+                bundle.bundle_hash()
+            );
+            Ok(true)
+        }
+        Commands::Build { bundle, out } => {
+            let bundle = load(&bundle)?;
+            eprintln!("🔨 cargo {}", bundle.cargo_args().join(" "));
+            let wasm = los_verify::build(&bundle, &options(cli.timeout))?;
+            if let Some(out) = out {
+                std::fs::write(&out, &wasm)
+                    .map_err(|e| format!("Cannot write {}: {}", out.display(), e))?;
+                eprintln!("💾 {} bytes → {}", wasm.len(), out.display());
+            }
+            println!("{}", los_verify::code_hash(&wasm));
+            Ok(true)
+        }
+        Commands::Check { bundle, code_hash } => {
+            let bundle = load(&bundle)?;
+            eprintln!("🔨 cargo {}", bundle.cargo_args().join(" "));
+            let v = los_verify::verify(&bundle, &code_hash, &options(cli.timeout))?;
+            if v.matched {
+                println!("✅ Verified: build matches code hash {}", v.expected_hash);
+            } else {
+                println!(
+                    "❌ Mismatch: built {}, deployed {}",
+                    v.built_hash, v.expected_hash
+                );
+            }
+            Ok(v.matched)
+        }
+    }
+}
+
+fn main() {
+    match run(Cli::parse()) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(2);
+        }
+    }
+}
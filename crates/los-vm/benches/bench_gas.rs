@@ -0,0 +1,37 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// BENCHMARK SUITE — los-vm (metered contract execution)
+//
+// Wall time of the canonical contracts (benches/canonical) through the full
+// call path: hosted dispatch, metering, host calls, state apply. Gas per call
+// is printed alongside; exact gas is pinned by tests/gas_regression.rs.
+// Record a release: cargo bench -p los-vm -- --save-baseline v<version>
+// Compare:          cargo bench -p los-vm -- --baseline v<previous>
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+mod canonical;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+fn bench_canonical_contracts(c: &mut Criterion) {
+    let gas = canonical::measure_gas();
+    println!("\n⛽ Gas per call (los-vm {})", env!("CARGO_PKG_VERSION"));
+    for (name, used) in &gas {
+        println!("   {:<36} {:>10}", name, used);
+    }
+
+    let mut group = c.benchmark_group("gas");
+    for scenario in canonical::SCENARIOS {
+        group.bench_function(scenario.name, |b| {
+            // Fresh engine per call: every iteration sees the same state
+            b.iter_batched(
+                scenario.prepare,
+                |(engine, call)| canonical::execute(&engine, call),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_canonical_contracts);
+criterion_main!(benches);
@@ -0,0 +1,370 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// CANONICAL CONTRACTS — gas benchmarks + gas regression test
+//
+// Hand-written WAT versions of the hot paths of the production contracts:
+//   usp01   — USP-01 balance transfer (two state reads, two writes, event)
+//   dex     — constant-product swap with 0.3% fee (reserves in state, event)
+//   storage — bulk state writes / reads (storage-heavy)
+//   compute — pure arithmetic loop, no host calls (compute-heavy)
+//
+// WAT rather than compiled Rust so the gas figures only move when the VM's
+// metering or host gas schedule moves, not when rustc changes codegen.
+// Shared by benches/bench_gas.rs (wall time) and tests/gas_regression.rs
+// (exact gas against tests/vectors/gas_baseline.json).
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#![allow(dead_code)]
+
+use los_vm::{ContractCall, ContractResult, WasmEngine};
+use std::collections::BTreeMap;
+
+pub const OWNER: &str = "LOSXbenchOwner0000000000000000001";
+pub const ALICE: &str = "LOSXbenchAlice0000000000000000001";
+pub const BOB: &str = "LOSXbenchBob000000000000000000001";
+/// Fixed block timestamp so every run is identical
+pub const BLOCK_TS: u64 = 1_771_000_000;
+pub const GAS_LIMIT: u64 = 5_000_000;
+
+// Memory map shared by all modules:
+//   0..256      constant strings (data segments)
+//   1024..1088  number scratch (state values)
+//   2048..2304  key A ("bal:" + caller, "slot:" + n, ...)
+//   2304..2560  key B ("bal:" + arg0)
+//   3072..3200  event JSON ({"amount":" preset by the modules that emit)
+//   4096..4160  argument scratch
+const IMPORTS: &str = r#"
+    (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+    (import "env" "host_get_state" (func $get (param i32 i32 i32 i32) (result i32)))
+    (import "env" "host_emit_event" (func $emit (param i32 i32 i32 i32)))
+    (import "env" "host_get_caller" (func $caller (param i32 i32) (result i32)))
+    (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+    (import "env" "host_set_return" (func $ret (param i32 i32)))
+    (memory (export "memory") 1)
+"#;
+
+/// Decimal parse/format and numeric state helpers (what the SDK's
+/// `arg_u128` / `state_u128` do, in 64 bits)
+const HELPERS: &str = r#"
+    ;; ASCII decimal at [p, p+n) -> value, -1 if empty or not a number
+    (func $parse (param $p i32) (param $n i32) (result i64) (local $v i64) (local $c i32)
+        (if (i32.le_s (local.get $n) (i32.const 0)) (then (return (i64.const -1))))
+        (block $done (loop $next
+            (br_if $done (i32.eqz (local.get $n)))
+            (local.set $c (i32.sub (i32.load8_u (local.get $p)) (i32.const 48)))
+            (if (i32.gt_u (local.get $c) (i32.const 9)) (then (return (i64.const -1))))
+            (local.set $v (i64.add (i64.mul (local.get $v) (i64.const 10))
+                (i64.extend_i32_u (local.get $c))))
+            (local.set $p (i32.add (local.get $p) (i32.const 1)))
+            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+            (br $next)))
+        (local.get $v))
+
+    ;; Write v as ASCII decimal at out, return its length
+    (func $fmt (param $v i64) (param $out i32) (result i32) (local $len i32) (local $t i64)
+        (local.set $t (local.get $v))
+        (local.set $len (i32.const 1))
+        (block $done (loop $count
+            (br_if $done (i64.lt_u (local.get $t) (i64.const 10)))
+            (local.set $t (i64.div_u (local.get $t) (i64.const 10)))
+            (local.set $len (i32.add (local.get $len) (i32.const 1)))
+            (br $count)))
+        (local.set $t (i64.extend_i32_u (local.get $len)))
+        (loop $digit
+            (local.set $t (i64.sub (local.get $t) (i64.const 1)))
+            (i32.store8 (i32.add (local.get $out) (i32.wrap_i64 (local.get $t)))
+                (i32.add (i32.const 48) (i32.wrap_i64 (i64.rem_u (local.get $v) (i64.const 10)))))
+            (local.set $v (i64.div_u (local.get $v) (i64.const 10)))
+            (br_if $digit (i64.gt_u (local.get $t) (i64.const 0))))
+        (local.get $len))
+
+    ;; Numeric state value (0 if missing)
+    (func $load (param $k i32) (param $kl i32) (result i64) (local $n i32)
+        (local.set $n (call $get (local.get $k) (local.get $kl) (i32.const 1024) (i32.const 40)))
+        (if (result i64) (i32.lt_s (local.get $n) (i32.const 0))
+            (then (i64.const 0))
+            (else (call $parse (i32.const 1024) (local.get $n)))))
+
+    (func $store (param $k i32) (param $kl i32) (param $v i64)
+        (call $set (local.get $k) (local.get $kl)
+            (i32.const 1024) (call $fmt (local.get $v) (i32.const 1024))))
+
+    ;; Numeric argument `idx`, -1 if missing or malformed
+    (func $arg_num (param $idx i32) (result i64)
+        (call $parse (i32.const 4096)
+            (call $arg (local.get $idx) (i32.const 4096) (i32.const 40))))
+
+    ;; Emit `type` with data {"amount":"<v>"} (prefix preset at 3072)
+    (func $emit_amount (param $type i32) (param $type_len i32) (param $v i64) (local $n i32)
+        (local.set $n (call $fmt (local.get $v) (i32.const 3083)))
+        (i32.store16 (i32.add (i32.const 3083) (local.get $n)) (i32.const 0x7d22))
+        (call $emit (local.get $type) (local.get $type_len)
+            (i32.const 3072) (i32.add (local.get $n) (i32.const 13))))
+
+    (func $return_num (param $v i64)
+        (call $ret (i32.const 1024) (call $fmt (local.get $v) (i32.const 1024))))
+"#;
+
+/// USP-01 balances under "bal:<address>"
+const USP01: &str = r#"
+    (data (i32.const 0) "Transfer")
+    (data (i32.const 3072) "{\"amount\":\"")
+    (data (i32.const 2048) "bal:")
+    (data (i32.const 2304) "bal:")
+
+    ;; init(supply): credit the whole supply to the caller
+    (func (export "init") (result i32) (local $supply i64) (local $kl i32)
+        (local.set $supply (call $arg_num (i32.const 0)))
+        (if (i64.lt_s (local.get $supply) (i64.const 0)) (then (return (i32.const 1))))
+        (local.set $kl (i32.add (i32.const 4) (call $caller (i32.const 2052) (i32.const 200))))
+        (call $store (i32.const 2048) (local.get $kl) (local.get $supply))
+        (i32.const 0))
+
+    ;; transfer(to, amount)
+    (func (export "transfer") (result i32)
+        (local $from_kl i32) (local $to_len i32) (local $amount i64) (local $bal i64)
+        (local.set $from_kl (i32.add (i32.const 4) (call $caller (i32.const 2052) (i32.const 200))))
+        (local.set $to_len (call $arg (i32.const 0) (i32.const 2308) (i32.const 200)))
+        (if (i32.le_s (local.get $to_len) (i32.const 0)) (then (return (i32.const 1))))
+        (local.set $amount (call $arg_num (i32.const 1)))
+        (if (i64.le_s (local.get $amount) (i64.const 0)) (then (return (i32.const 1))))
+        (local.set $bal (call $load (i32.const 2048) (local.get $from_kl)))
+        (if (i64.lt_u (local.get $bal) (local.get $amount)) (then (return (i32.const 2))))
+        (call $store (i32.const 2048) (local.get $from_kl)
+            (i64.sub (local.get $bal) (local.get $amount)))
+        (call $store (i32.const 2304) (i32.add (i32.const 4) (local.get $to_len))
+            (i64.add (call $load (i32.const 2304) (i32.add (i32.const 4) (local.get $to_len)))
+                (local.get $amount)))
+        (call $emit_amount (i32.const 0) (i32.const 8) (local.get $amount))
+        (i32.const 0))
+"#;
+
+/// Constant-product pool: reserves "ra" / "rb", swaps A for B
+const DEX: &str = r#"
+    (data (i32.const 0) "Swap")
+    (data (i32.const 8) "ra")
+    (data (i32.const 16) "rb")
+    (data (i32.const 3072) "{\"amount\":\"")
+
+    ;; init(reserve_a, reserve_b)
+    (func (export "init") (result i32) (local $a i64) (local $b i64)
+        (local.set $a (call $arg_num (i32.const 0)))
+        (local.set $b (call $arg_num (i32.const 1)))
+        (if (i32.or (i64.le_s (local.get $a) (i64.const 0)) (i64.le_s (local.get $b) (i64.const 0)))
+            (then (return (i32.const 1))))
+        (call $store (i32.const 8) (i32.const 2) (local.get $a))
+        (call $store (i32.const 16) (i32.const 2) (local.get $b))
+        (i32.const 0))
+
+    ;; swap(amount_in) -> amount_out = rb * in * 997 / (ra * 1000 + in * 997)
+    ;; (64-bit products: reserves * amount must stay below ~9.2e15)
+    (func (export "swap") (result i32)
+        (local $in i64) (local $ra i64) (local $rb i64) (local $fee_in i64) (local $out i64)
+        (local.set $in (call $arg_num (i32.const 0)))
+        (if (i64.le_s (local.get $in) (i64.const 0)) (then (return (i32.const 1))))
+        (local.set $ra (call $load (i32.const 8) (i32.const 2)))
+        (local.set $rb (call $load (i32.const 16) (i32.const 2)))
+        (local.set $fee_in (i64.mul (local.get $in) (i64.const 997)))
+        (local.set $out (i64.div_u (i64.mul (local.get $rb) (local.get $fee_in))
+            (i64.add (i64.mul (local.get $ra) (i64.const 1000)) (local.get $fee_in))))
+        (if (i64.eqz (local.get $out)) (then (return (i32.const 2))))
+        (call $store (i32.const 8) (i32.const 2) (i64.add (local.get $ra) (local.get $in)))
+        (call $store (i32.const 16) (i32.const 2) (i64.sub (local.get $rb) (local.get $out)))
+        (call $emit_amount (i32.const 0) (i32.const 4) (local.get $out))
+        (call $return_num (local.get $out))
+        (i32.const 0))
+"#;
+
+/// n state slots "slot:<i>" holding 32-byte values
+const STORAGE: &str = r#"
+    (data (i32.const 0) "0123456789abcdef0123456789abcdef")
+    (data (i32.const 2048) "slot:")
+
+    ;; fill(n): write n slots
+    (func (export "fill") (result i32) (local $n i64) (local $i i64)
+        (local.set $n (call $arg_num (i32.const 0)))
+        (if (i64.lt_s (local.get $n) (i64.const 0)) (then (return (i32.const 1))))
+        (block $done (loop $next
+            (br_if $done (i64.ge_u (local.get $i) (local.get $n)))
+            (call $set (i32.const 2048)
+                (i32.add (i32.const 5) (call $fmt (local.get $i) (i32.const 2053)))
+                (i32.const 0) (i32.const 32))
+            (local.set $i (i64.add (local.get $i) (i64.const 1)))
+            (br $next)))
+        (i32.const 0))
+
+    ;; scan(n): read n slots back, return the bytes found
+    (func (export "scan") (result i32) (local $n i64) (local $i i64) (local $total i64) (local $len i32)
+        (local.set $n (call $arg_num (i32.const 0)))
+        (if (i64.lt_s (local.get $n) (i64.const 0)) (then (return (i32.const 1))))
+        (block $done (loop $next
+            (br_if $done (i64.ge_u (local.get $i) (local.get $n)))
+            (local.set $len (call $get (i32.const 2048)
+                (i32.add (i32.const 5) (call $fmt (local.get $i) (i32.const 2053)))
+                (i32.const 1024) (i32.const 64)))
+            (if (i32.gt_s (local.get $len) (i32.const 0))
+                (then (local.set $total (i64.add (local.get $total)
+                    (i64.extend_i32_u (local.get $len))))))
+            (local.set $i (i64.add (local.get $i) (i64.const 1)))
+            (br $next)))
+        (call $return_num (local.get $total))
+        (i32.const 0))
+"#;
+
+/// xorshift64 rounds, no host calls inside the loop
+const COMPUTE: &str = r#"
+    ;; mix(rounds) -> final state
+    (func (export "mix") (result i32) (local $n i64) (local $x i64)
+        (local.set $n (call $arg_num (i32.const 0)))
+        (if (i64.lt_s (local.get $n) (i64.const 0)) (then (return (i32.const 1))))
+        (local.set $x (i64.const 0x9E3779B97F4A7C15))
+        (block $done (loop $next
+            (br_if $done (i64.eqz (local.get $n)))
+            (local.set $x (i64.xor (local.get $x) (i64.shl (local.get $x) (i64.const 13))))
+            (local.set $x (i64.xor (local.get $x) (i64.shr_u (local.get $x) (i64.const 7))))
+            (local.set $x (i64.xor (local.get $x) (i64.shl (local.get $x) (i64.const 17))))
+            (local.set $n (i64.sub (local.get $n) (i64.const 1)))
+            (br $next)))
+        (call $return_num (i64.shr_u (local.get $x) (i64.const 1)))
+        (i32.const 0))
+"#;
+
+/// Compiled WASM for one canonical contract body
+pub fn module(body: &str) -> Vec<u8> {
+    let wat = format!("(module {} {} {})", IMPORTS, HELPERS, body);
+    wasmer::wat2wasm(wat.as_bytes())
+        .expect("canonical contract WAT must compile")
+        .to_vec()
+}
+
+fn call(contract: &str, caller: &str, function: &str, args: &[&str]) -> ContractCall {
+    ContractCall {
+        contract: contract.to_string(),
+        function: function.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        gas_limit: GAS_LIMIT,
+        caller: caller.to_string(),
+        block_timestamp: BLOCK_TS,
+        amount: 0,
+    }
+}
+
+/// Run a call that must succeed
+pub fn execute(engine: &WasmEngine, c: ContractCall) -> ContractResult {
+    let name = format!("{}({})", c.function, c.args.join(","));
+    let result = engine
+        .call_contract(c)
+        .unwrap_or_else(|e| panic!("{} failed: {}", name, e));
+    assert!(result.success, "{} returned {}", name, result.output);
+    result
+}
+
+/// One measured call: a fresh engine with the contract deployed and set up,
+/// plus the call whose gas / wall time is recorded
+pub struct Scenario {
+    pub name: &'static str,
+    pub prepare: fn() -> (WasmEngine, ContractCall),
+}
+
+fn deploy(engine: &WasmEngine, body: &str) -> String {
+    engine
+        .deploy_contract(OWNER.to_string(), module(body), BTreeMap::new(), 1)
+        .expect("deploy canonical contract")
+}
+
+fn usp01_init() -> (WasmEngine, ContractCall) {
+    let engine = WasmEngine::new();
+    let token = deploy(&engine, USP01);
+    let c = call(&token, ALICE, "init", &["21936236000000"]);
+    (engine, c)
+}
+
+fn usp01_transfer() -> (WasmEngine, ContractCall) {
+    let (engine, init) = usp01_init();
+    let token = init.contract.clone();
+    execute(&engine, init);
+    let c = call(&token, ALICE, "transfer", &[BOB, "250000000"]);
+    (engine, c)
+}
+
+fn usp01_transfer_existing() -> (WasmEngine, ContractCall) {
+    let (engine, first) = usp01_transfer();
+    let token = first.contract.clone();
+    execute(&engine, first);
+    let c = call(&token, ALICE, "transfer", &[BOB, "250000000"]);
+    (engine, c)
+}
+
+fn dex_swap() -> (WasmEngine, ContractCall) {
+    let engine = WasmEngine::new();
+    let pool = deploy(&engine, DEX);
+    execute(
+        &engine,
+        call(&pool, OWNER, "init", &["100000000", "250000000"]),
+    );
+    let c = call(&pool, ALICE, "swap", &["1000000"]);
+    (engine, c)
+}
+
+fn storage_fill() -> (WasmEngine, ContractCall) {
+    let engine = WasmEngine::new();
+    let store = deploy(&engine, STORAGE);
+    let c = call(&store, ALICE, "fill", &["64"]);
+    (engine, c)
+}
+
+fn storage_scan() -> (WasmEngine, ContractCall) {
+    let (engine, fill) = storage_fill();
+    let store = fill.contract.clone();
+    execute(&engine, fill);
+    let c = call(&store, ALICE, "scan", &["64"]);
+    (engine, c)
+}
+
+fn compute_mix() -> (WasmEngine, ContractCall) {
+    let engine = WasmEngine::new();
+    let mixer = deploy(&engine, COMPUTE);
+    let c = call(&mixer, ALICE, "mix", &["10000"]);
+    (engine, c)
+}
+
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "usp01_init",
+        prepare: usp01_init,
+    },
+    Scenario {
+        name: "usp01_transfer",
+        prepare: usp01_transfer,
+    },
+    Scenario {
+        name: "usp01_transfer_existing_recipient",
+        prepare: usp01_transfer_existing,
+    },
+    Scenario {
+        name: "dex_swap",
+        prepare: dex_swap,
+    },
+    Scenario {
+        name: "storage_fill_64",
+        prepare: storage_fill,
+    },
+    Scenario {
+        name: "storage_scan_64",
+        prepare: storage_scan,
+    },
+    Scenario {
+        name: "compute_mix_10k",
+        prepare: compute_mix,
+    },
+];
+
+/// Gas used by every scenario, by name
+pub fn measure_gas() -> BTreeMap<String, u64> {
+    SCENARIOS
+        .iter()
+        .map(|s| {
+            let (engine, c) = (s.prepare)();
+            (s.name.to_string(), execute(&engine, c).gas_used)
+        })
+        .collect()
+}
@@ -0,0 +1,220 @@
+//! # Host ABI versioning
+//!
+//! Contracts built with `los-sdk` carry a `los_abi` custom section holding
+//! the host ABI version the SDK was compiled against (4 bytes, little-endian,
+//! see `los_sdk::HOST_ABI_VERSION`). Every host function has the version
+//! that introduced it ([`HOST_CAPABILITIES`]).
+//!
+//! Before deploying and before every call the UVM checks that
+//! - the declared version is not newer than [`HOST_ABI_VERSION`], and
+//! - every import is a host function this node provides.
+//!
+//! Either failure is reported with the list of missing capabilities, instead
+//! of the wasmer link error the instantiation would otherwise produce.
+//! Modules without the section (hand-written WAT, pre-versioning SDKs) are
+//! treated as version 1 and checked on their imports alone.
+
+use serde::Serialize;
+use wasmer::wasmparser::{Parser, Payload, TypeRef};
+
+pub use los_sdk::{ABI_SECTION, HOST_ABI_VERSION};
+
+/// Import module of all host functions
+pub const HOST_MODULE: &str = "env";
+
+/// Host functions provided by this node and the ABI version that added each
+pub const HOST_CAPABILITIES: &[(&str, u32)] = &[
+    ("host_log", 1),
+    ("host_abort", 1),
+    ("host_set_state", 1),
+    ("host_get_state", 1),
+    ("host_del_state", 1),
+    ("host_emit_event", 1),
+    ("host_emit_event_indexed", 1),
+    ("host_transfer", 1),
+    ("host_get_caller", 1),
+    ("host_get_self_address", 1),
+    ("host_get_balance_lo", 1),
+    ("host_get_balance_hi", 1),
+    ("host_get_timestamp", 1),
+    ("host_get_arg_count", 1),
+    ("host_get_arg", 1),
+    ("host_set_return", 1),
+    ("host_blake3", 1),
+    ("host_random_seed", 1),
+    ("host_contract_exists", 1),
+    ("host_get_code_hash", 1),
+    ("host_get_attached_value", 2),
+    ("host_get_block_hash", 3),
+    ("host_get_call_id", 3),
+];
+
+/// ABI facts read from a module's bytecode
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ModuleAbi {
+    /// Version from the `los_abi` section (None = section absent)
+    pub declared_version: Option<u32>,
+    /// Imports as `module.name` (non-function imports get a ` (kind)` suffix)
+    pub imports: Vec<String>,
+}
+
+impl ModuleAbi {
+    /// Version the module is checked against (absent section = 1)
+    pub fn version(&self) -> u32 {
+        self.declared_version.unwrap_or(1)
+    }
+
+    /// Imports this node cannot satisfy
+    pub fn missing_capabilities(&self) -> Vec<String> {
+        self.imports
+            .iter()
+            .filter(|import| {
+                !import
+                    .strip_prefix(HOST_MODULE)
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .is_some_and(|name| HOST_CAPABILITIES.iter().any(|(f, _)| *f == name))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Host functions available to contracts declaring `version`
+pub fn capabilities(version: u32) -> Vec<&'static str> {
+    HOST_CAPABILITIES
+        .iter()
+        .filter(|(_, since)| *since <= version)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Read the ABI section and the import list of `bytecode`
+pub fn inspect(bytecode: &[u8]) -> Result<ModuleAbi, String> {
+    // Module::new also accepts WAT text; binary passes through unchanged
+    let bytecode = wasmer::wat2wasm(bytecode).map_err(|e| format!("Invalid WASM: {}", e))?;
+    let mut abi = ModuleAbi::default();
+    for payload in Parser::new(0).parse_all(&bytecode) {
+        match payload.map_err(|e| format!("Invalid WASM: {}", e))? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(|e| format!("Invalid WASM import: {}", e))?;
+                    let kind = match import.ty {
+                        TypeRef::Func(_) => "",
+                        TypeRef::Memory(_) => " (memory)",
+                        TypeRef::Table(_) => " (table)",
+                        TypeRef::Global(_) => " (global)",
+                        TypeRef::Tag(_) => " (tag)",
+                    };
+                    abi.imports
+                        .push(format!("{}.{}{}", import.module, import.name, kind));
+                }
+            }
+            Payload::CustomSection(reader) if reader.name() == ABI_SECTION => {
+                let bytes: [u8; 4] = reader.data().try_into().map_err(|_| {
+                    format!("Malformed '{}' section (expected 4 bytes)", ABI_SECTION)
+                })?;
+                if abi.declared_version.is_some() {
+                    return Err(format!("Duplicate '{}' section", ABI_SECTION));
+                }
+                abi.declared_version = Some(u32::from_le_bytes(bytes));
+            }
+            _ => {}
+        }
+    }
+    Ok(abi)
+}
+
+/// Reject modules this node cannot run, naming what is missing
+pub fn check_compat(bytecode: &[u8]) -> Result<ModuleAbi, String> {
+    let abi = inspect(bytecode)?;
+    let missing = abi.missing_capabilities();
+    if abi.version() > HOST_ABI_VERSION {
+        let mut msg = format!(
+            "Contract requires host ABI v{} but this node implements v{}",
+            abi.version(),
+            HOST_ABI_VERSION
+        );
+        if !missing.is_empty() {
+            msg.push_str(&format!("; missing capabilities: {}", missing.join(", ")));
+        }
+        return Err(msg);
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "Contract imports host functions this node does not provide (host ABI v{}): {}",
+            HOST_ABI_VERSION,
+            missing.join(", ")
+        ));
+    }
+    Ok(abi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Custom section: id 0, size, name (length-prefixed), payload
+    fn abi_section(version: u32) -> Vec<u8> {
+        let mut body = vec![ABI_SECTION.len() as u8];
+        body.extend_from_slice(ABI_SECTION.as_bytes());
+        body.extend_from_slice(&version.to_le_bytes());
+        let mut section = vec![0, body.len() as u8];
+        section.extend_from_slice(&body);
+        section
+    }
+
+    fn wasm(imports: &str, abi: Option<u32>) -> Vec<u8> {
+        let wat = format!("(module {} (func (export \"f\")))", imports);
+        let mut code = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+        if let Some(version) = abi {
+            code.extend_from_slice(&abi_section(version));
+        }
+        code
+    }
+
+    #[test]
+    fn test_capability_table_matches_host_imports() {
+        assert_eq!(
+            capabilities(HOST_ABI_VERSION).len(),
+            HOST_CAPABILITIES.len()
+        );
+        let mut names: Vec<_> = HOST_CAPABILITIES.iter().map(|(n, _)| *n).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), HOST_CAPABILITIES.len());
+        assert!(HOST_CAPABILITIES
+            .iter()
+            .all(|(_, since)| (1..=HOST_ABI_VERSION).contains(since)));
+    }
+
+    #[test]
+    fn test_check_compat() {
+        let log = r#"(import "env" "host_log" (func (param i32 i32)))"#;
+        let ok = check_compat(&wasm(log, Some(HOST_ABI_VERSION))).unwrap();
+        assert_eq!(ok.declared_version, Some(HOST_ABI_VERSION));
+        assert_eq!(ok.imports, vec!["env.host_log"]);
+
+        // No section: legacy module, imports alone decide
+        assert_eq!(check_compat(&wasm(log, None)).unwrap().version(), 1);
+
+        let future = r#"(import "env" "host_teleport" (func))"#;
+        let err = check_compat(&wasm(future, Some(HOST_ABI_VERSION + 1))).unwrap_err();
+        assert!(err.contains(&format!("requires host ABI v{}", HOST_ABI_VERSION + 1)));
+        assert!(err.contains("missing capabilities: env.host_teleport"));
+
+        let err = check_compat(&wasm(
+            r#"(import "wasi" "fd_write" (func)) (import "env" "memory" (memory 1))"#,
+            None,
+        ))
+        .unwrap_err();
+        assert!(
+            err.ends_with("wasi.fd_write, env.memory (memory)"),
+            "{}",
+            err
+        );
+
+        let mut dup = wasm("", Some(1));
+        dup.extend_from_slice(&abi_section(1));
+        assert!(inspect(&dup).unwrap_err().contains("Duplicate"));
+    }
+}
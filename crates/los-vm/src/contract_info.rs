@@ -0,0 +1,78 @@
+//! # Contract metadata introspection
+//!
+//! Contracts built with `los_sdk::contract_info!` export a `contract_info`
+//! function returning a JSON object:
+//!
+//! ```json
+//! {"name":"my_token","version":"1.2.0","sdk_abi":2,"build_hash":"3f9a..."}
+//! ```
+//!
+//! `WasmEngine::deploy_contract` calls it once, with a fixed gas budget, and
+//! stores the result in [`Contract::info`](crate::Contract). Modules without
+//! the export, calls that fail or read the clock / random seed / other
+//! contracts, and malformed or oversized answers leave `info` empty: the
+//! deploy itself never fails because of it.
+
+use serde::{Deserialize, Serialize};
+use wasmer::wasmparser::{ExternalKind, Parser, Payload};
+
+/// Name of the exported metadata function
+pub const CONTRACT_INFO_EXPORT: &str = "contract_info";
+/// Gas budget for the deploy-time `contract_info` call
+pub const CONTRACT_INFO_GAS: u64 = 100_000;
+/// Longest accepted `name` / `version` / `build_hash`
+pub const MAX_INFO_FIELD_LEN: usize = 128;
+
+/// Self-reported contract metadata (not verified by the node)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractInfo {
+    pub name: String,
+    pub version: String,
+    /// Host ABI version of the SDK the contract was built with
+    pub sdk_abi: u32,
+    /// Build identifier set at compile time (`LOS_BUILD_HASH`), may be empty
+    #[serde(default)]
+    pub build_hash: String,
+}
+
+/// Whether `bytecode` exports a `contract_info` function
+pub fn exports_info(bytecode: &[u8]) -> bool {
+    for payload in Parser::new(0).parse_all(bytecode) {
+        let Ok(Payload::ExportSection(reader)) = payload else {
+            continue;
+        };
+        return reader.into_iter().flatten().any(|export| {
+            export.kind == ExternalKind::Func && export.name == CONTRACT_INFO_EXPORT
+        });
+    }
+    false
+}
+
+/// Parse and bound the return data of a `contract_info` call
+pub fn parse(return_data: &[u8]) -> Option<ContractInfo> {
+    let info: ContractInfo = serde_json::from_slice(return_data).ok()?;
+    let fits = |s: &str| s.len() <= MAX_INFO_FIELD_LEN;
+    (!info.name.is_empty() && fits(&info.name) && fits(&info.version) && fits(&info.build_hash))
+        .then_some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bounds_fields() {
+        let info = parse(br#"{"name":"token","version":"1.0.0","sdk_abi":2}"#).unwrap();
+        assert_eq!(info.name, "token");
+        assert_eq!(info.sdk_abi, 2);
+        assert!(info.build_hash.is_empty());
+
+        assert!(parse(b"not json").is_none());
+        assert!(parse(br#"{"name":"","version":"1","sdk_abi":2}"#).is_none());
+        let long = format!(
+            r#"{{"name":"t","version":"1","sdk_abi":2,"build_hash":"{}"}}"#,
+            "a".repeat(MAX_INFO_FIELD_LEN + 1)
+        );
+        assert!(parse(long.as_bytes()).is_none());
+    }
+}
@@ -16,10 +16,16 @@
 //! - `pool:{id}:last_trade`        → Last trade timestamp
 //! - `pool_list:{index}`           → Pool ID at index
 //! - `lp:{pool_id}:{address}`     → LP shares for user
+//!
+//! ## Analytics
+//! 24h volume, fee revenue and price charts come from `Swap` events, which
+//! the engine feeds into [`DexAnalytics`] after every successful call. The
+//! history is in-memory (since node start, last 7 days) and identical on
+//! every node that replays the same blocks.
 
-use crate::WasmEngine;
+use crate::{ContractEvent, WasmEngine};
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 
 /// Pool info extracted from contract state.
 #[derive(Debug, Clone, Serialize)]
@@ -246,16 +252,10 @@ pub fn compute_quote(
 
     // Price impact
     let precision: u128 = 1_000_000_000_000;
-    let spot = if reserve_in > 0 {
-        reserve_out * precision / reserve_in
-    } else {
-        0
-    };
-    let exec = if amount_in > 0 {
-        amount_out * precision / amount_in
-    } else {
-        0
-    };
+    let spot = (reserve_out * precision)
+        .checked_div(reserve_in)
+        .unwrap_or(0);
+    let exec = (amount_out * precision).checked_div(amount_in).unwrap_or(0);
     let impact_bps = if spot > 0 && spot > exec {
         ((spot - exec) * 10_000) / spot
     } else {
@@ -265,6 +265,350 @@ pub fn compute_quote(
     Ok((amount_out, fee, impact_bps))
 }
 
+// ─────────────────────────────────────────────────────────────
+// ANALYTICS (Swap event history)
+// ─────────────────────────────────────────────────────────────
+
+/// Fixed-point scale for reported prices (12 decimals, same as quotes)
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000;
+/// Swaps older than this (relative to the newest swap) are dropped
+pub const ANALYTICS_RETENTION_SECS: u64 = 7 * 86_400;
+/// Hard cap on retained swaps per pool (memory bound under heavy trading)
+pub const MAX_SWAPS_PER_POOL: usize = 50_000;
+/// Maximum candles returned by one chart query
+pub const MAX_CHART_CANDLES: usize = 500;
+
+const DAY_SECS: u64 = 86_400;
+
+/// One executed swap, as seen in a `Swap` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapRecord {
+    pub timestamp: u64,
+    /// true if token A was sold for token B
+    pub a_to_b: bool,
+    pub amount_in: u128,
+    pub amount_out: u128,
+    /// Fee charged in the input token
+    pub fee: u128,
+    /// Price of A in B (× PRICE_PRECISION) after the swap
+    pub price_after: u128,
+}
+
+impl SwapRecord {
+    /// Token A leg of the trade
+    fn volume_a(&self) -> u128 {
+        if self.a_to_b {
+            self.amount_in
+        } else {
+            self.amount_out
+        }
+    }
+
+    /// Token B leg of the trade
+    fn volume_b(&self) -> u128 {
+        if self.a_to_b {
+            self.amount_out
+        } else {
+            self.amount_in
+        }
+    }
+}
+
+/// Per-pool swap history, keyed by (contract, pool_id).
+#[derive(Debug, Default)]
+pub struct DexAnalytics {
+    swaps: BTreeMap<(String, String), VecDeque<SwapRecord>>,
+}
+
+impl DexAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a swap and prune history outside the retention window.
+    pub fn record(&mut self, contract: &str, pool_id: &str, swap: SwapRecord) {
+        let history = self
+            .swaps
+            .entry((contract.to_string(), pool_id.to_string()))
+            .or_default();
+        let cutoff = swap.timestamp.saturating_sub(ANALYTICS_RETENTION_SECS);
+        history.push_back(swap);
+        while history.len() > MAX_SWAPS_PER_POOL
+            || history.front().is_some_and(|s| s.timestamp < cutoff)
+        {
+            history.pop_front();
+        }
+    }
+
+    /// Retained swaps for one pool, oldest first.
+    pub fn swaps(&self, contract: &str, pool_id: &str) -> Vec<SwapRecord> {
+        self.swaps
+            .get(&(contract.to_string(), pool_id.to_string()))
+            .map(|h| h.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Pool info plus trading analytics (served by `GET /dex/pools`).
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolAnalytics {
+    #[serde(flatten)]
+    pub pool: PoolInfo,
+    /// Price of token A in token B (reserve_b / reserve_a), decimal string
+    pub spot_price: String,
+    /// Token A traded in the last 24h (both directions)
+    pub volume_24h_a: u128,
+    /// Token B traded in the last 24h (both directions)
+    pub volume_24h_b: u128,
+    /// Fees collected in token A in the last 24h (A → B swaps)
+    pub fees_24h_a: u128,
+    /// Fees collected in token B in the last 24h (B → A swaps)
+    pub fees_24h_b: u128,
+    pub swaps_24h: u64,
+}
+
+/// One OHLC candle of a pool's price chart. Prices are decimal strings.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Candle {
+    /// Bucket start (UNIX seconds)
+    pub time: u64,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume_a: u128,
+    pub volume_b: u128,
+    pub fees_a: u128,
+    pub fees_b: u128,
+    pub swaps: u64,
+}
+
+/// Parse a chart interval: `5m`, `15m`, `1h`, `4h`, `1d` → seconds.
+pub fn parse_chart_interval(s: &str) -> Option<u64> {
+    match s {
+        "5m" => Some(300),
+        "15m" => Some(900),
+        "1h" => Some(3_600),
+        "4h" => Some(14_400),
+        "1d" => Some(DAY_SECS),
+        _ => None,
+    }
+}
+
+/// Price of A in B (× PRICE_PRECISION); 0 for an empty pool.
+fn spot_price_scaled(reserve_a: u128, reserve_b: u128) -> u128 {
+    if reserve_a == 0 {
+        return 0;
+    }
+    los_sdk::math::mul_div(reserve_b, PRICE_PRECISION, reserve_a).unwrap_or(u128::MAX)
+}
+
+/// Render a PRICE_PRECISION-scaled price as a decimal string.
+pub fn format_price(scaled: u128) -> String {
+    format!(
+        "{}.{:012}",
+        scaled / PRICE_PRECISION,
+        scaled % PRICE_PRECISION
+    )
+}
+
+/// Build a `SwapRecord` from a `Swap` event and the post-call contract state.
+fn swap_from_event(
+    event: &ContractEvent,
+    state: &BTreeMap<String, String>,
+) -> Option<(String, SwapRecord)> {
+    let pool_id = event.data.get("pool_id")?;
+    let info = pool_info_from_state(&event.contract, pool_id, state)?;
+    let amount = |k: &str| event.data.get(k).and_then(|v| v.parse::<u128>().ok());
+    let swap = SwapRecord {
+        timestamp: event.timestamp,
+        a_to_b: event.data.get("token_in")? == &info.token_a,
+        amount_in: amount("amount_in")?,
+        amount_out: amount("amount_out")?,
+        fee: amount("fee").unwrap_or(0),
+        price_after: spot_price_scaled(info.reserve_a, info.reserve_b),
+    };
+    Some((pool_id.clone(), swap))
+}
+
+/// Feed `Swap` events from a successful call into the engine's analytics.
+/// Non-DEX contracts and malformed events are ignored.
+pub(crate) fn record_swap_events(engine: &WasmEngine, contract: &str, events: &[ContractEvent]) {
+    if !events.iter().any(|e| e.event_type == "Swap") {
+        return;
+    }
+    let swaps: Vec<(String, SwapRecord)> = {
+        let Ok(contracts) = engine.contracts.lock() else {
+            return;
+        };
+        let Some(c) = contracts.get(contract) else {
+            return;
+        };
+        if !is_dex_contract(&c.state) {
+            return;
+        }
+        events
+            .iter()
+            .filter(|e| e.event_type == "Swap" && e.contract == contract)
+            .filter_map(|e| swap_from_event(e, &c.state))
+            .collect()
+    };
+    if let Ok(mut analytics) = engine.dex_analytics.lock() {
+        for (pool_id, swap) in swaps {
+            analytics.record(contract, &pool_id, swap);
+        }
+    }
+}
+
+fn analytics_for_pool(pool: PoolInfo, swaps: &[SwapRecord], now: u64) -> PoolAnalytics {
+    let since = now.saturating_sub(DAY_SECS);
+    let mut a = PoolAnalytics {
+        spot_price: format_price(spot_price_scaled(pool.reserve_a, pool.reserve_b)),
+        pool,
+        volume_24h_a: 0,
+        volume_24h_b: 0,
+        fees_24h_a: 0,
+        fees_24h_b: 0,
+        swaps_24h: 0,
+    };
+    for s in swaps
+        .iter()
+        .filter(|s| s.timestamp > since && s.timestamp <= now)
+    {
+        a.volume_24h_a = a.volume_24h_a.saturating_add(s.volume_a());
+        a.volume_24h_b = a.volume_24h_b.saturating_add(s.volume_b());
+        if s.a_to_b {
+            a.fees_24h_a = a.fees_24h_a.saturating_add(s.fee);
+        } else {
+            a.fees_24h_b = a.fees_24h_b.saturating_add(s.fee);
+        }
+        a.swaps_24h += 1;
+    }
+    a
+}
+
+/// All DEX pools with spot price and trailing-24h volume/fees as of `now`.
+pub fn list_pool_analytics(engine: &WasmEngine, now: u64) -> Vec<PoolAnalytics> {
+    let pools = list_all_dex_pools(engine);
+    let Ok(analytics) = engine.dex_analytics.lock() else {
+        return Vec::new();
+    };
+    pools
+        .into_iter()
+        .map(|p| {
+            let swaps = analytics.swaps(&p.contract, &p.pool_id);
+            analytics_for_pool(p, &swaps, now)
+        })
+        .collect()
+}
+
+/// Bucket swaps into `limit` candles of `interval` seconds ending at `now`.
+/// Empty buckets repeat the previous close; buckets before the first known
+/// price are omitted.
+fn build_candles(swaps: &[SwapRecord], interval: u64, limit: usize, now: u64) -> Vec<Candle> {
+    let interval = interval.max(1);
+    let limit = limit.clamp(1, MAX_CHART_CANDLES) as u64;
+    let last_start = now - now % interval;
+    let first_start = last_start.saturating_sub((limit - 1) * interval);
+
+    let mut last_close = swaps
+        .iter()
+        .rev()
+        .find(|s| s.timestamp < first_start)
+        .map(|s| s.price_after);
+    let mut candles = Vec::new();
+    let mut start = first_start;
+    while start <= last_start {
+        let end = start + interval;
+        let bucket: Vec<&SwapRecord> = swaps
+            .iter()
+            .filter(|s| s.timestamp >= start && s.timestamp < end)
+            .collect();
+        if let Some(first) = bucket.first() {
+            let open = last_close.unwrap_or(first.price_after);
+            let mut c = Candle {
+                time: start,
+                open: format_price(open),
+                high: String::new(),
+                low: String::new(),
+                close: String::new(),
+                volume_a: 0,
+                volume_b: 0,
+                fees_a: 0,
+                fees_b: 0,
+                swaps: bucket.len() as u64,
+            };
+            let (mut high, mut low) = (open, open);
+            for s in &bucket {
+                high = high.max(s.price_after);
+                low = low.min(s.price_after);
+                c.volume_a = c.volume_a.saturating_add(s.volume_a());
+                c.volume_b = c.volume_b.saturating_add(s.volume_b());
+                if s.a_to_b {
+                    c.fees_a = c.fees_a.saturating_add(s.fee);
+                } else {
+                    c.fees_b = c.fees_b.saturating_add(s.fee);
+                }
+            }
+            let close = bucket.last().map_or(open, |s| s.price_after);
+            c.high = format_price(high);
+            c.low = format_price(low);
+            c.close = format_price(close);
+            last_close = Some(close);
+            candles.push(c);
+        } else if let Some(p) = last_close {
+            let price = format_price(p);
+            candles.push(Candle {
+                time: start,
+                open: price.clone(),
+                high: price.clone(),
+                low: price.clone(),
+                close: price,
+                volume_a: 0,
+                volume_b: 0,
+                fees_a: 0,
+                fees_b: 0,
+                swaps: 0,
+            });
+        }
+        start = end;
+    }
+    candles
+}
+
+/// Price/volume chart for `pool_id`. If `contract` is None the pool is
+/// looked up across all DEX contracts (error if the id is ambiguous).
+pub fn pool_chart(
+    engine: &WasmEngine,
+    contract: Option<&str>,
+    pool_id: &str,
+    interval: u64,
+    limit: usize,
+    now: u64,
+) -> Result<(PoolInfo, Vec<Candle>), String> {
+    let mut matches: Vec<PoolInfo> = list_all_dex_pools(engine)
+        .into_iter()
+        .filter(|p| p.pool_id == pool_id && contract.is_none_or(|c| p.contract == c))
+        .collect();
+    let pool = match matches.len() {
+        0 => return Err("Pool not found".to_string()),
+        1 => matches.remove(0),
+        _ => {
+            return Err(format!(
+                "Pool id '{}' exists in {} DEX contracts; pass ?contract=",
+                pool_id,
+                matches.len()
+            ))
+        }
+    };
+    let swaps = engine
+        .dex_analytics
+        .lock()
+        .map_err(|_| "Lock error".to_string())?
+        .swaps(&pool.contract, &pool.pool_id);
+    Ok((pool, build_candles(&swaps, interval, limit, now)))
+}
+
 // ─────────────────────────────────────────────────────────────
 // TESTS
 // ─────────────────────────────────────────────────────────────
@@ -384,4 +728,120 @@ mod tests {
         let pools = list_all_dex_pools(&engine);
         assert!(pools.is_empty());
     }
+
+    fn swap(timestamp: u64, a_to_b: bool, amount_in: u128, price: u128) -> SwapRecord {
+        SwapRecord {
+            timestamp,
+            a_to_b,
+            amount_in,
+            amount_out: amount_in * 2,
+            fee: amount_in * 3 / 1000,
+            price_after: price,
+        }
+    }
+
+    #[test]
+    fn test_swap_from_event_direction_and_price() {
+        let s = make_dex_state();
+        let mut data = BTreeMap::new();
+        data.insert("pool_id".to_string(), "POOL:LOS:TOKEN_A".to_string());
+        data.insert("token_in".to_string(), "TOKEN_A".to_string());
+        data.insert("amount_in".to_string(), "1000".to_string());
+        data.insert("amount_out".to_string(), "497".to_string());
+        data.insert("fee".to_string(), "3".to_string());
+        let event = ContractEvent {
+            contract: "LOSCon123".to_string(),
+            event_type: "Swap".to_string(),
+            data,
+            timestamp: 77,
+            ..Default::default()
+        };
+        let (pool_id, rec) = swap_from_event(&event, &s).unwrap();
+        assert_eq!(pool_id, "POOL:LOS:TOKEN_A");
+        assert!(!rec.a_to_b);
+        assert_eq!(rec.volume_a(), 497);
+        assert_eq!(rec.volume_b(), 1000);
+        assert_eq!(rec.price_after, 2 * PRICE_PRECISION);
+        assert_eq!(format_price(rec.price_after), "2.000000000000");
+    }
+
+    #[test]
+    fn test_analytics_retention_prunes_old_swaps() {
+        let mut a = DexAnalytics::new();
+        a.record("C", "P", swap(100, true, 10, 1));
+        a.record(
+            "C",
+            "P",
+            swap(100 + ANALYTICS_RETENTION_SECS + 1, true, 10, 1),
+        );
+        assert_eq!(a.swaps("C", "P").len(), 1);
+        assert!(a.swaps("C", "other").is_empty());
+    }
+
+    #[test]
+    fn test_pool_analytics_24h_window() {
+        let pool =
+            pool_info_from_state("LOSCon123", "POOL:LOS:TOKEN_A", &make_dex_state()).unwrap();
+        let now = 200_000;
+        let swaps = vec![
+            swap(now - DAY_SECS, true, 1_000, 1), // exactly 24h ago: excluded
+            swap(now - 10, true, 1_000, 1),
+            swap(now - 5, false, 4_000, 1),
+        ];
+        let a = analytics_for_pool(pool, &swaps, now);
+        assert_eq!(a.spot_price, "2.000000000000");
+        assert_eq!(a.swaps_24h, 2);
+        assert_eq!(a.volume_24h_a, 1_000 + 8_000);
+        assert_eq!(a.volume_24h_b, 2_000 + 4_000);
+        assert_eq!(a.fees_24h_a, 3);
+        assert_eq!(a.fees_24h_b, 12);
+    }
+
+    #[test]
+    fn test_build_candles_ohlc_and_gap_fill() {
+        let p = PRICE_PRECISION;
+        let swaps = vec![
+            swap(3_500, true, 10, 5 * p),  // before window: seeds the open
+            swap(3_700, true, 10, 4 * p),  // bucket 3600
+            swap(3_800, false, 10, 7 * p), // bucket 3600
+            swap(11_000, true, 10, 6 * p), // bucket 10800
+        ];
+        let candles = build_candles(&swaps, 3_600, 3, 11_500);
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles[0].time, 3_600);
+        assert_eq!(candles[0].open, format_price(5 * p));
+        assert_eq!(candles[0].high, format_price(7 * p));
+        assert_eq!(candles[0].low, format_price(4 * p));
+        assert_eq!(candles[0].close, format_price(7 * p));
+        assert_eq!(candles[0].swaps, 2);
+        assert_eq!(candles[0].fees_a, 0); // 10 * 3 / 1000 rounds down
+                                          // Empty bucket carries the previous close forward
+        assert_eq!(candles[1].time, 7_200);
+        assert_eq!(candles[1].swaps, 0);
+        assert_eq!(candles[1].close, format_price(7 * p));
+        assert_eq!(candles[2].open, format_price(7 * p));
+        assert_eq!(candles[2].close, format_price(6 * p));
+    }
+
+    #[test]
+    fn test_build_candles_skips_buckets_before_first_price() {
+        let swaps = vec![swap(7_300, true, 10, PRICE_PRECISION)];
+        let candles = build_candles(&swaps, 3_600, 24, 7_300);
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].time, 7_200);
+        assert!(build_candles(&[], 3_600, 24, 7_300).is_empty());
+    }
+
+    #[test]
+    fn test_parse_chart_interval() {
+        assert_eq!(parse_chart_interval("1h"), Some(3_600));
+        assert_eq!(parse_chart_interval("1d"), Some(86_400));
+        assert_eq!(parse_chart_interval("2h"), None);
+    }
+
+    #[test]
+    fn test_pool_chart_unknown_pool() {
+        let engine = WasmEngine::new();
+        assert!(pool_chart(&engine, None, "POOL:X", 3_600, 24, 0).is_err());
+    }
 }
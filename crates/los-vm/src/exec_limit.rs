@@ -0,0 +1,276 @@
+//! # Execution limits
+//!
+//! Every WASM execution compiles the module with Cranelift on its own
+//! thread. Unbounded, a burst of REST dry runs compiles dozens of modules at
+//! once and can exhaust the node's memory. [`ExecLimiter`] caps the number
+//! of executions in flight (default [`DEFAULT_MAX_IN_FLIGHT`]).
+//!
+//! Two kinds of callers wait differently for a slot:
+//!
+//! - [`Admission::Consensus`] (applying blocks, deploys, receive hooks) waits
+//!   as long as needed and goes ahead of interactive callers: a replicated
+//!   call must never fail on one node because it was busy
+//! - [`Admission::Interactive`] (dry runs) queues for at most the queue
+//!   timeout, then fails with an error starting with [`VM_BUSY`], which the
+//!   REST layer reports as HTTP 429
+//!
+//! Installed by the node with [`WasmEngine::set_exec_limits`]
+//! (`LOS_WASM_MAX_IN_FLIGHT`, `LOS_WASM_QUEUE_TIMEOUT_MS`).
+
+use crate::WasmEngine;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Executions in flight unless configured
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+/// How long an interactive call queues for a slot unless configured
+pub const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_millis(2_000);
+/// Prefix of the error returned when an interactive call times out in the queue
+pub const VM_BUSY: &str = "VM busy";
+
+/// Whether an error came from a saturated execution queue
+pub fn is_busy_error(e: &str) -> bool {
+    e.starts_with(VM_BUSY)
+}
+
+/// How a caller waits for an execution slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Wait without limit, ahead of interactive callers
+    Consensus,
+    /// Wait up to the queue timeout, then fail with [`VM_BUSY`]
+    Interactive,
+}
+
+#[derive(Debug, Default)]
+struct Slots {
+    in_flight: usize,
+    /// Interactive callers waiting
+    queued: usize,
+    /// Consensus callers waiting (interactive ones yield to them)
+    priority_waiting: usize,
+}
+
+/// Counting semaphore for WASM executions
+#[derive(Debug)]
+pub struct ExecLimiter {
+    slots: Mutex<Slots>,
+    freed: Condvar,
+    max_in_flight: Mutex<usize>,
+    queue_timeout: Mutex<Duration>,
+    busy_rejections: AtomicU64,
+}
+
+/// Snapshot for /health
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExecLimitStats {
+    pub max_in_flight: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+    pub queue_timeout_ms: u64,
+    /// Interactive calls rejected with VM busy since startup
+    pub busy_rejections: u64,
+}
+
+/// An execution slot; released on drop
+#[derive(Debug)]
+pub struct ExecPermit<'a> {
+    limiter: &'a ExecLimiter,
+}
+
+impl Drop for ExecPermit<'_> {
+    fn drop(&mut self) {
+        let mut slots = self.limiter.lock_slots();
+        slots.in_flight = slots.in_flight.saturating_sub(1);
+        drop(slots);
+        self.limiter.freed.notify_all();
+    }
+}
+
+impl Default for ExecLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IN_FLIGHT, DEFAULT_QUEUE_TIMEOUT)
+    }
+}
+
+impl ExecLimiter {
+    pub fn new(max_in_flight: usize, queue_timeout: Duration) -> Self {
+        Self {
+            slots: Mutex::new(Slots::default()),
+            freed: Condvar::new(),
+            max_in_flight: Mutex::new(max_in_flight.max(1)),
+            queue_timeout: Mutex::new(queue_timeout),
+            busy_rejections: AtomicU64::new(0),
+        }
+    }
+
+    /// Change the limits. Executions already in flight keep their slots.
+    pub fn configure(&self, max_in_flight: usize, queue_timeout: Duration) {
+        *self.max_in_flight.lock().unwrap_or_else(|e| e.into_inner()) = max_in_flight.max(1);
+        *self.queue_timeout.lock().unwrap_or_else(|e| e.into_inner()) = queue_timeout;
+        self.freed.notify_all();
+    }
+
+    fn max(&self) -> usize {
+        *self.max_in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn timeout(&self) -> Duration {
+        *self.queue_timeout.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_slots(&self) -> MutexGuard<'_, Slots> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Take a slot, waiting as `admission` allows
+    pub fn acquire(&self, admission: Admission) -> Result<ExecPermit<'_>, String> {
+        let mut slots = self.lock_slots();
+        match admission {
+            Admission::Consensus => {
+                slots.priority_waiting += 1;
+                while slots.in_flight >= self.max() {
+                    slots = self.freed.wait(slots).unwrap_or_else(|e| e.into_inner());
+                }
+                slots.priority_waiting -= 1;
+            }
+            Admission::Interactive => {
+                slots = self.wait_interactive(slots)?;
+            }
+        }
+        slots.in_flight += 1;
+        Ok(ExecPermit { limiter: self })
+    }
+
+    /// Wait (as an interactive caller) until a slot is free without taking
+    /// it. Lets a caller that holds other locks during execution fail fast
+    /// before taking them.
+    pub fn wait_available(&self) -> Result<(), String> {
+        let slots = self.lock_slots();
+        self.wait_interactive(slots).map(drop)
+    }
+
+    fn wait_interactive<'a>(
+        &'a self,
+        mut slots: MutexGuard<'a, Slots>,
+    ) -> Result<MutexGuard<'a, Slots>, String> {
+        let timeout = self.timeout();
+        let deadline = Instant::now() + timeout;
+        slots.queued += 1;
+        loop {
+            let max = self.max();
+            if slots.priority_waiting == 0 && slots.in_flight < max {
+                slots.queued -= 1;
+                return Ok(slots);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                slots.queued -= 1;
+                self.busy_rejections.fetch_add(1, Ordering::Relaxed);
+                return Err(format!(
+                    "{}: {} WASM executions in flight (max {}), no slot within {} ms",
+                    VM_BUSY,
+                    slots.in_flight,
+                    max,
+                    timeout.as_millis()
+                ));
+            }
+            slots = self
+                .freed
+                .wait_timeout(slots, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    pub fn stats(&self) -> ExecLimitStats {
+        let slots = self.lock_slots();
+        ExecLimitStats {
+            max_in_flight: self.max(),
+            in_flight: slots.in_flight,
+            queued: slots.queued,
+            queue_timeout_ms: self.timeout().as_millis() as u64,
+            busy_rejections: self.busy_rejections.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl WasmEngine {
+    /// Set the maximum number of WASM executions in flight and how long
+    /// interactive calls queue for a slot
+    pub fn set_exec_limits(&self, max_in_flight: usize, queue_timeout: Duration) {
+        self.exec_limit.configure(max_in_flight, queue_timeout);
+    }
+
+    /// Current execution slot usage
+    pub fn exec_limit_stats(&self) -> ExecLimitStats {
+        self.exec_limit.stats()
+    }
+
+    /// Wait up to the queue timeout for a free execution slot (see
+    /// [`ExecLimiter::wait_available`]). Err starts with [`VM_BUSY`].
+    pub fn wait_for_exec_slot(&self) -> Result<(), String> {
+        self.exec_limit.wait_available()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_interactive_times_out_when_saturated() {
+        let limiter = ExecLimiter::new(2, Duration::from_millis(50));
+        let a = limiter.acquire(Admission::Interactive).unwrap();
+        let _b = limiter.acquire(Admission::Consensus).unwrap();
+        assert_eq!(limiter.stats().in_flight, 2);
+
+        let err = limiter.acquire(Admission::Interactive).unwrap_err();
+        assert!(is_busy_error(&err), "{}", err);
+        assert!(limiter.wait_available().is_err());
+        assert_eq!(limiter.stats().busy_rejections, 2);
+        assert_eq!(limiter.stats().queued, 0);
+
+        drop(a);
+        assert!(limiter.wait_available().is_ok());
+        let _c = limiter.acquire(Admission::Interactive).unwrap();
+        assert_eq!(limiter.stats().in_flight, 2);
+    }
+
+    #[test]
+    fn test_consensus_waits_and_goes_first() {
+        let limiter = Arc::new(ExecLimiter::new(1, Duration::from_millis(200)));
+        let held = limiter.acquire(Admission::Interactive).unwrap();
+
+        let l = Arc::clone(&limiter);
+        let consensus = std::thread::spawn(move || {
+            let _p = l.acquire(Admission::Consensus).unwrap();
+            std::thread::sleep(Duration::from_millis(400));
+        });
+        while limiter.lock_slots().priority_waiting == 0 {
+            std::thread::yield_now();
+        }
+        drop(held);
+
+        // The freed slot goes to the consensus caller, which holds it past
+        // the interactive queue timeout
+        let err = limiter.acquire(Admission::Interactive).unwrap_err();
+        assert!(is_busy_error(&err));
+        consensus.join().unwrap();
+        assert_eq!(limiter.stats().in_flight, 0);
+        assert!(limiter.acquire(Admission::Interactive).is_ok());
+    }
+
+    #[test]
+    fn test_reconfigure_wakes_waiters() {
+        let limiter = ExecLimiter::new(1, Duration::from_secs(5));
+        let _held = limiter.acquire(Admission::Consensus).unwrap();
+        limiter.configure(2, Duration::from_millis(10));
+        assert!(limiter.acquire(Admission::Interactive).is_ok());
+        assert_eq!(limiter.stats().max_in_flight, 2);
+        assert_eq!(limiter.stats().queue_timeout_ms, 10);
+    }
+}
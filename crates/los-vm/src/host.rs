@@ -13,6 +13,7 @@
 //! | `host_get_state`             | `(i32, i32, i32, i32) -> i32`                        | Read state (-1 = not found)          |
 //! | `host_del_state`             | `(i32, i32) -> ()`                                   | Delete state key                     |
 //! | `host_emit_event`            | `(i32, i32, i32, i32) -> ()`                         | Emit event (type + JSON data)        |
+//! | `host_emit_event_indexed`    | `(i32, i32, i32, i32, i32, i32) -> i32`              | Emit event with indexed topics       |
 //! | `host_transfer`              | `(i32, i32, i64, i64) -> i32`                        | Transfer CIL (0=ok, 1=insuf, 2=err) |
 //! | `host_get_caller`            | `(i32, i32) -> i32`                                  | Get caller address                   |
 //! | `host_get_self_address`      | `(i32, i32) -> i32`                                  | Get contract address                 |
@@ -23,11 +24,26 @@
 //! | `host_get_arg`               | `(i32, i32, i32) -> i32`                             | Get argument by index                |
 //! | `host_set_return`            | `(i32, i32) -> ()`                                   | Set return data                      |
 //! | `host_blake3`                | `(i32, i32, i32) -> i32`                             | Compute blake3 hash (32 bytes)       |
+//! | `host_random_seed`           | `(i32) -> i32`                                       | Deterministic 32-byte seed           |
+//! | `host_contract_exists`       | `(i32, i32) -> i32`                                  | 1 if address is a deployed contract  |
+//! | `host_get_code_hash`         | `(i32, i32, i32, i32) -> i32`                        | Contract code hash (-1 = not found)  |
+//! | `host_get_attached_value`    | `(i32) -> i32`                                       | CIL attached to the call (16 B, LE)  |
+//! | `host_get_block_hash`        | `(i32, i32) -> i32`                                  | Originating block hash (hex)         |
+//! | `host_get_call_id`           | `(i32) -> i32`                                       | Unique 32-byte invocation ID         |
+//!
+//! ## Host call gas
+//!
+//! Every host call is charged from [`GasSchedule::DEFAULT`] on top of the
+//! per-instruction metering, by deducting points from the same metering
+//! counter *before* the host logic runs. A call that cannot be paid for does
+//! nothing and leaves the execution out of gas.
 
-use crate::ContractEvent;
+use crate::{Contract, ContractEvent};
 use std::collections::{BTreeMap, HashSet};
 use std::sync::{Arc, Mutex};
-use wasmer::{imports, Function, FunctionEnv, FunctionEnvMut, Imports, Memory, Store};
+use wasmer::{
+    imports, Function, FunctionEnv, FunctionEnvMut, Global, Imports, Instance, Memory, Store, Value,
+};
 
 // ─────────────────────────────────────────────────────────────────
 // Limits (prevent abuse from malicious contracts)
@@ -41,8 +57,16 @@ const MAX_STATE_KEY_SIZE: u32 = 1_024;
 const MAX_RETURN_DATA_SIZE: u32 = 262_144;
 /// Maximum size of a single log message (4 KB)
 const MAX_LOG_SIZE: u32 = 4_096;
-/// Maximum number of events per execution
-const MAX_EVENTS: usize = 256;
+/// Maximum number of events per execution (more aborts the call)
+pub const MAX_EVENTS: usize = 64;
+/// Maximum length of an event type name
+pub const MAX_EVENT_TYPE_SIZE: u32 = 128;
+/// Maximum size of one event's JSON data (8 KB, larger aborts the call)
+pub const MAX_EVENT_DATA_SIZE: u32 = 8_192;
+/// Maximum indexed topics per event
+pub const MAX_EVENT_TOPICS: usize = 4;
+/// Maximum length of one topic value
+pub const MAX_TOPIC_SIZE: usize = 256;
 /// Maximum number of transfers per execution
 const MAX_TRANSFERS: usize = 64;
 /// Maximum number of distinct state keys modified per execution
@@ -50,6 +74,158 @@ const MAX_STATE_KEYS: usize = 1_024;
 /// Maximum number of log lines per execution
 const MAX_LOGS: usize = 256;
 
+/// Reserved state key holding the contract's storage schema version
+/// (decimal string, maintained by `los_sdk::migrate::run_migrations`).
+pub const SCHEMA_VERSION_KEY: &str = "__los:schema_version";
+
+/// Host rule for [`SCHEMA_VERSION_KEY`]: the version may only move forward
+/// (new value must be a decimal u32 strictly above the stored one) and can
+/// never be deleted. This is what guarantees a migration for a given
+/// version runs at most once — a second attempt aborts the whole call.
+pub fn check_schema_version_write(
+    current: Option<&[u8]>,
+    new: Option<&[u8]>,
+) -> Result<(), String> {
+    let parse = |b: &[u8]| {
+        std::str::from_utf8(b)
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+    };
+    let current = current.and_then(parse).unwrap_or(0);
+    let new = match new {
+        Some(b) => parse(b).ok_or("schema version must be a decimal u32")?,
+        None => return Err("schema version cannot be deleted".to_string()),
+    };
+    if new <= current {
+        return Err(format!(
+            "schema version {} already applied (stored: {})",
+            new, current
+        ));
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Host call gas
+// ─────────────────────────────────────────────────────────────────
+
+/// Gas charged per host function call, in metering points (1 point = 1 WASM
+/// instruction). Consensus-critical: every validator must use the same values.
+/// `los-testing` charges the same amounts in its native MockHost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasSchedule {
+    /// Base cost of any host call
+    pub host_call: u64,
+    /// Per byte copied across the host boundary (keys, values read, events, logs, args)
+    pub per_byte: u64,
+    /// Per byte written to persistent state (replaces `per_byte` for set_state values)
+    pub per_state_byte_written: u64,
+    /// host_blake3 / host_random_seed (plus `per_byte` per hashed byte)
+    pub blake3: u64,
+    /// host_transfer
+    pub transfer: u64,
+    /// Base cost of an emitted event (replaces `host_call` for event host calls)
+    pub event: u64,
+    /// Per byte of event type, topics and data: events are gossiped and
+    /// stored by every node, so they cost more than a plain copy
+    pub per_event_byte: u64,
+}
+
+impl GasSchedule {
+    pub const DEFAULT: GasSchedule = GasSchedule {
+        host_call: 50,
+        per_byte: 1,
+        per_state_byte_written: 10,
+        blake3: 200,
+        transfer: 500,
+        event: 375,
+        per_event_byte: 8,
+    };
+
+    /// `host_call + len * per_byte`
+    pub fn call_with_bytes(&self, len: u64) -> u64 {
+        self.host_call
+            .saturating_add(len.saturating_mul(self.per_byte))
+    }
+
+    /// Cost of host_set_state for the given key/value sizes
+    pub fn state_write(&self, key_len: u64, val_len: u64) -> u64 {
+        self.call_with_bytes(key_len)
+            .saturating_add(val_len.saturating_mul(self.per_state_byte_written))
+    }
+
+    /// `event + len * per_event_byte` for an event of `len` bytes
+    pub fn event_cost(&self, len: u64) -> u64 {
+        self.event
+            .saturating_add(len.saturating_mul(self.per_event_byte))
+    }
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Handles to the metering middleware's exported globals, so host functions
+/// can deduct points from the running instance.
+#[derive(Clone)]
+pub struct MeteringGlobals {
+    remaining: Global,
+    exhausted: Global,
+}
+
+impl MeteringGlobals {
+    /// Look up the metering globals of an instance compiled with `Metering`.
+    /// None for instances compiled without the middleware.
+    pub fn from_instance(instance: &Instance) -> Option<Self> {
+        Some(MeteringGlobals {
+            remaining: instance
+                .exports
+                .get_global("wasmer_metering_remaining_points")
+                .ok()?
+                .clone(),
+            exhausted: instance
+                .exports
+                .get_global("wasmer_metering_points_exhausted")
+                .ok()?
+                .clone(),
+        })
+    }
+
+    /// Points left on the counter (0 once exhausted)
+    pub(crate) fn remaining_points(&self, store: &mut impl wasmer::AsStoreMut) -> u64 {
+        match self.remaining.get(store) {
+            Value::I64(x) => x as u64,
+            _ => 0,
+        }
+    }
+}
+
+/// Deduct `cost` points before a host function does any work.
+/// Returns false when the remaining points do not cover it: the counter is
+/// then marked exhausted, so the execution fails as out of gas, and the
+/// caller must return without side effects.
+fn charge_gas(env: &mut FunctionEnvMut<HostState>, cost: u64) -> bool {
+    let Some(globals) = env.data().metering.clone() else {
+        return true;
+    };
+    let exhausted = matches!(globals.exhausted.get(env), Value::I32(x) if x != 0);
+    let remaining = match globals.remaining.get(env) {
+        Value::I64(x) => x as u64,
+        _ => 0,
+    };
+    if !exhausted && remaining >= cost {
+        let _ = globals
+            .remaining
+            .set(env, Value::I64((remaining - cost) as i64));
+        return true;
+    }
+    let _ = globals.remaining.set(env, Value::I64(0));
+    let _ = globals.exhausted.set(env, Value::I32(1));
+    false
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Shared state types
 // ─────────────────────────────────────────────────────────────────
@@ -63,6 +239,11 @@ const MAX_LOGS: usize = 256;
 pub struct HostState {
     /// Reference to the guest's linear memory. Set after instantiation.
     pub memory: Option<Memory>,
+    /// Metering counter of the running instance. Set after instantiation;
+    /// host calls are free while this is None.
+    pub metering: Option<MeteringGlobals>,
+    /// Host call prices.
+    pub gas: GasSchedule,
     /// Shared mutable data accessed by host functions during execution.
     pub inner: Arc<Mutex<HostData>>,
 }
@@ -87,6 +268,8 @@ pub struct HostData {
     pub self_address: String,
     /// Contract balance in CIL (decremented locally on transfer).
     pub balance: u128,
+    /// CIL attached to this call (already included in `balance`).
+    pub attached_value: u128,
     /// Block timestamp (seconds since epoch).
     pub timestamp: u64,
     /// Function arguments (strings passed by the caller via REST/gossip).
@@ -99,6 +282,29 @@ pub struct HostData {
     pub aborted: bool,
     /// Human-readable abort reason.
     pub abort_message: String,
+    /// Number of `host_random_seed` calls so far (each call gets a new seed).
+    pub random_counter: u32,
+    /// Deployed contracts, for `host_contract_exists` / `host_get_code_hash`
+    /// (read-only; None = no other contracts visible). The engine must not
+    /// hold this lock while waiting on an execution.
+    pub contracts: Option<Arc<Mutex<BTreeMap<String, Contract>>>>,
+    /// Ledger block this execution belongs to.
+    pub context: CallContext,
+    /// Set once the call reads the block timestamp, a random seed, the call
+    /// context or another contract's registry entry: its result then depends
+    /// on more than the contract's own state, the args and the caller.
+    pub env_reads: bool,
+}
+
+/// Originating ledger block of an execution. Empty outside block processing
+/// (dry runs, read-only calls, `contract_info` at deploy).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallContext {
+    /// Hash of the ContractCall block (hex, as returned by the REST API)
+    pub block_hash: String,
+    /// 0 for the call itself, n for the n-th `on_receive` hook it triggered
+    /// (same numbering as `ContractEvent::call_index`)
+    pub call_index: u32,
 }
 
 /// Result of hosted WASM execution, returned to the caller.
@@ -125,6 +331,10 @@ pub struct HostExecResult {
     /// True if the contract was called in SDK mode (no WASM-level params).
     /// False if legacy mode (WASM function has i32 params).
     pub sdk_mode: bool,
+    /// Host-call trace + instruction histogram (dry runs with tracing only).
+    pub trace: Option<crate::trace::ExecutionTrace>,
+    /// See `HostData::env_reads`
+    pub env_reads: bool,
 }
 
 // ─────────────────────────────────────────────────────────────────
@@ -132,7 +342,11 @@ pub struct HostExecResult {
 // ─────────────────────────────────────────────────────────────────
 
 /// Read `len` bytes from WASM linear memory at `ptr`. Returns None on error.
-fn read_guest_bytes(env: &FunctionEnvMut<HostState>, ptr: u32, len: u32) -> Option<Vec<u8>> {
+pub(crate) fn read_guest_bytes(
+    env: &FunctionEnvMut<HostState>,
+    ptr: u32,
+    len: u32,
+) -> Option<Vec<u8>> {
     if len == 0 {
         return Some(Vec::new());
     }
@@ -172,9 +386,13 @@ fn write_guest_bytes(env: &FunctionEnvMut<HostState>, ptr: u32, data: &[u8], max
 // ─────────────────────────────────────────────────────────────────
 
 /// `host_log(ptr: i32, len: i32)` — Write a debug log line.
-/// Gas-metered by wasmer instruction counting. Capped at MAX_LOG_SIZE bytes.
-fn host_log_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
+/// Charged per byte. Capped at MAX_LOG_SIZE bytes.
+fn host_log_fn(mut env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
     let len = (len as u32).min(MAX_LOG_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(len as u64)) {
+        return;
+    }
     if let Some(msg) = read_guest_string(&env, ptr as u32, len) {
         if let Ok(mut inner) = env.data().inner.lock() {
             if inner.logs.len() < MAX_LOGS {
@@ -187,8 +405,12 @@ fn host_log_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
 /// `host_abort(ptr: i32, len: i32)` — Set abort flag. The SDK calls `unreachable` after
 /// this returns, causing a WASM trap that unwinds execution.
 /// All state changes are discarded on abort.
-fn host_abort_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
+fn host_abort_fn(mut env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
     let len = (len as u32).min(MAX_LOG_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return;
+    }
     let msg = read_guest_string(&env, ptr as u32, len).unwrap_or_default();
     if let Ok(mut inner) = env.data().inner.lock() {
         inner.aborted = true;
@@ -198,8 +420,9 @@ fn host_abort_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
 
 /// `host_set_state(key_ptr, key_len, val_ptr, val_len)` — Write a key-value pair to
 /// the contract's persistent state. Overwrites existing values.
+/// Writes to [`SCHEMA_VERSION_KEY`] that do not move the version forward abort the call.
 fn host_set_state_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     key_ptr: i32,
     key_len: i32,
     val_ptr: i32,
@@ -207,6 +430,10 @@ fn host_set_state_fn(
 ) {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
     let val_len = (val_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.state_write(key_len as u64, val_len as u64)) {
+        return;
+    }
 
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
@@ -218,6 +445,15 @@ fn host_set_state_fn(
     };
 
     if let Ok(mut inner) = env.data().inner.lock() {
+        if key == SCHEMA_VERSION_KEY {
+            if let Err(e) =
+                check_schema_version_write(inner.state.get(&key).map(Vec::as_slice), Some(&val))
+            {
+                inner.aborted = true;
+                inner.abort_message = e;
+                return;
+            }
+        }
         // Rate-limit: max distinct keys per execution
         if inner.dirty_keys.len() >= MAX_STATE_KEYS && !inner.dirty_keys.contains(&key) {
             return;
@@ -230,14 +466,19 @@ fn host_set_state_fn(
 /// `host_get_state(key_ptr, key_len, out_ptr, out_max) -> i32`
 /// Read a value from the contract's state. Returns actual byte length, or -1 if key not found.
 /// If actual length exceeds `out_max`, data is truncated.
+/// Charged for the key up front and for the value once its size is known.
 fn host_get_state_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     key_ptr: i32,
     key_len: i32,
     out_ptr: i32,
     out_max: i32,
 ) -> i32 {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(key_len as u64)) {
+        return -1;
+    }
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
         None => return -1,
@@ -253,18 +494,30 @@ fn host_get_state_fn(
             None => return -1,
         }
     };
+    if !charge_gas(&mut env, (data.len() as u64).saturating_mul(gas.per_byte)) {
+        return -1;
+    }
 
     write_guest_bytes(&env, out_ptr as u32, &data, out_max as u32)
 }
 
 /// `host_del_state(key_ptr, key_len)` — Delete a key from the contract's state.
-fn host_del_state_fn(env: FunctionEnvMut<HostState>, key_ptr: i32, key_len: i32) {
+fn host_del_state_fn(mut env: FunctionEnvMut<HostState>, key_ptr: i32, key_len: i32) {
     let key_len = (key_len as u32).min(MAX_STATE_KEY_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(key_len as u64)) {
+        return;
+    }
     let key = match read_guest_string(&env, key_ptr as u32, key_len) {
         Some(k) => k,
         None => return,
     };
     if let Ok(mut inner) = env.data().inner.lock() {
+        if key == SCHEMA_VERSION_KEY {
+            inner.aborted = true;
+            inner.abort_message = "schema version cannot be deleted".to_string();
+            return;
+        }
         inner.state.remove(&key);
         inner.dirty_keys.insert(key); // Mark as changed (deletion)
     }
@@ -272,15 +525,18 @@ fn host_del_state_fn(env: FunctionEnvMut<HostState>, key_ptr: i32, key_len: i32)
 
 /// `host_emit_event(type_ptr, type_len, data_ptr, data_len)` — Emit a structured event.
 /// `data` is JSON: `{"key1":"val1","key2":"val2"}`.
+/// An oversized event or one past MAX_EVENTS aborts the call.
 fn host_emit_event_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     type_ptr: i32,
     type_len: i32,
     data_ptr: i32,
     data_len: i32,
 ) {
-    let type_len = (type_len as u32).min(256);
-    let data_len = (data_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let (type_len, data_len) = (type_len as u32, data_len as u32);
+    if !charge_event(&mut env, type_len, 0, data_len) {
+        return;
+    }
 
     let event_type = match read_guest_string(&env, type_ptr as u32, type_len) {
         Some(t) => t,
@@ -290,23 +546,145 @@ fn host_emit_event_fn(
         Some(d) => d,
         None => return,
     };
+    push_event(&env, event_type, &data_str, Vec::new());
+}
 
+/// Charge for an event and check its size and the per-call event count.
+/// Sizes over the limits are charged at the limit, then abort the call
+/// instead of being truncated. Returns false if the event must not be
+/// recorded.
+fn charge_event(
+    env: &mut FunctionEnvMut<HostState>,
+    type_len: u32,
+    topics_len: u32,
+    data_len: u32,
+) -> bool {
+    let gas = env.data().gas;
+    let bytes = type_len.min(MAX_EVENT_TYPE_SIZE) as u64
+        + topics_len as u64
+        + data_len.min(MAX_EVENT_DATA_SIZE) as u64;
+    if !charge_gas(env, gas.event_cost(bytes)) {
+        return false;
+    }
+    let Ok(mut inner) = env.data().inner.lock() else {
+        return false;
+    };
+    let rejection = if inner.events.len() >= MAX_EVENTS {
+        format!("too many events (max {} per call)", MAX_EVENTS)
+    } else if type_len > MAX_EVENT_TYPE_SIZE {
+        format!(
+            "event type too long: {} bytes (max {})",
+            type_len, MAX_EVENT_TYPE_SIZE
+        )
+    } else if data_len > MAX_EVENT_DATA_SIZE {
+        format!(
+            "event data too large: {} bytes (max {})",
+            data_len, MAX_EVENT_DATA_SIZE
+        )
+    } else {
+        return true;
+    };
+    inner.aborted = true;
+    inner.abort_message = rejection;
+    false
+}
+
+/// Record an event (limits already checked by `charge_event`)
+fn push_event(
+    env: &FunctionEnvMut<HostState>,
+    event_type: String,
+    data_json: &str,
+    topics: Vec<String>,
+) {
     // Parse event data as JSON key-value pairs (gracefully defaults to empty on parse errors)
-    let data: BTreeMap<String, String> = serde_json::from_str(&data_str).unwrap_or_default();
+    let data: BTreeMap<String, String> = serde_json::from_str(data_json).unwrap_or_default();
 
-    if let Ok(mut inner) = env.data().inner.lock() {
-        if inner.events.len() >= MAX_EVENTS {
-            return;
-        }
-        let contract_addr = inner.self_address.clone();
-        let ts = inner.timestamp;
-        inner.events.push(ContractEvent {
-            contract: contract_addr,
-            event_type,
-            data,
-            timestamp: ts,
-        });
+    let Ok(mut inner) = env.data().inner.lock() else {
+        return;
+    };
+    let contract_addr = inner.self_address.clone();
+    let ts = inner.timestamp;
+    let event_index = inner.events.len() as u32;
+    inner.events.push(ContractEvent {
+        contract: contract_addr,
+        event_type,
+        data,
+        timestamp: ts,
+        topics,
+        block_hash: String::new(),
+        call_index: 0,
+        event_index,
+    });
+}
+
+/// Split a 0x00-separated topic list. Err if there are more than
+/// MAX_EVENT_TOPICS or one is longer than MAX_TOPIC_SIZE.
+pub fn parse_topics(raw: &[u8]) -> Result<Vec<String>, String> {
+    if raw.is_empty() {
+        return Ok(Vec::new());
     }
+    let topics: Vec<&[u8]> = raw.split(|b| *b == 0).collect();
+    if topics.len() > MAX_EVENT_TOPICS {
+        return Err(format!(
+            "{} topics (max {})",
+            topics.len(),
+            MAX_EVENT_TOPICS
+        ));
+    }
+    topics
+        .into_iter()
+        .map(|t| {
+            if t.len() > MAX_TOPIC_SIZE {
+                return Err(format!("topic longer than {} bytes", MAX_TOPIC_SIZE));
+            }
+            String::from_utf8(t.to_vec()).map_err(|_| "topic is not UTF-8".to_string())
+        })
+        .collect()
+}
+
+/// `host_emit_event_indexed(type_ptr, type_len, topics_ptr, topics_len, data_ptr, data_len) -> i32`
+/// Emit an event with up to MAX_EVENT_TOPICS indexed values, passed as one
+/// buffer separated by 0x00 bytes. Topic i is queryable as `topic{i}`.
+/// Returns 0 = emitted, 1 = invalid topics, 2 = error. Like host_emit_event,
+/// an oversized event or one past MAX_EVENTS aborts the call (returns 2).
+fn host_emit_event_indexed_fn(
+    mut env: FunctionEnvMut<HostState>,
+    type_ptr: i32,
+    type_len: i32,
+    topics_ptr: i32,
+    topics_len: i32,
+    data_ptr: i32,
+    data_len: i32,
+) -> i32 {
+    let (type_len, data_len) = (type_len as u32, data_len as u32);
+    // Longest valid list: MAX_EVENT_TOPICS values plus separators
+    let max_topics_len = (MAX_EVENT_TOPICS * (MAX_TOPIC_SIZE + 1)) as u32;
+    let topics_len = topics_len as u32;
+    if !charge_event(&mut env, type_len, topics_len.min(max_topics_len), data_len) {
+        return 2;
+    }
+    if topics_len > max_topics_len {
+        return 1;
+    }
+    let Some(event_type) = read_guest_string(&env, type_ptr as u32, type_len) else {
+        return 2;
+    };
+    let topics =
+        match read_guest_bytes(&env, topics_ptr as u32, topics_len).map(|raw| parse_topics(&raw)) {
+            Some(Ok(t)) => t,
+            Some(Err(_)) => return 1,
+            None => return 2,
+        };
+    // Each topic is an extra index entry on every node
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call.saturating_mul(topics.len() as u64)) {
+        return 2;
+    }
+    let Some(data_str) = read_guest_string(&env, data_ptr as u32, data_len) else {
+        return 2;
+    };
+    push_event(&env, event_type, &data_str, topics);
+    0
 }
 
 /// `host_transfer(addr_ptr, addr_len, amount_lo: i64, amount_hi: i64) -> i32`
@@ -314,12 +692,16 @@ fn host_emit_event_fn(
 /// `amount` is reconstructed as `(amount_hi << 64) | amount_lo` (u128).
 /// Returns: 0 = success, 1 = insufficient balance, 2 = invalid address, 3 = too many transfers.
 fn host_transfer_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     addr_ptr: i32,
     addr_len: i32,
     amount_lo: i64,
     amount_hi: i64,
 ) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.transfer) {
+        return 2;
+    }
     let addr_len = (addr_len as u32).min(256);
     let recipient = match read_guest_string(&env, addr_ptr as u32, addr_len) {
         Some(a) if !a.is_empty() => a,
@@ -349,7 +731,11 @@ fn host_transfer_fn(
 
 /// `host_get_caller(out_ptr, out_max) -> i32` — Write caller's LOS address to guest memory.
 /// Returns number of bytes written, or -1 on error.
-fn host_get_caller_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+fn host_get_caller_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return -1;
+    }
     let caller = {
         let inner = match env.data().inner.lock() {
             Ok(i) => i,
@@ -362,7 +748,11 @@ fn host_get_caller_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32
 
 /// `host_get_self_address(out_ptr, out_max) -> i32` — Write contract's own address to guest memory.
 /// Returns number of bytes written, or -1 on error.
-fn host_get_self_address_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+fn host_get_self_address_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return -1;
+    }
     let addr = {
         let inner = match env.data().inner.lock() {
             Ok(i) => i,
@@ -374,7 +764,11 @@ fn host_get_self_address_fn(env: FunctionEnvMut<HostState>, out_ptr: i32, out_ma
 }
 
 /// `host_get_balance_lo() -> i64` — Lower 64 bits of the contract's CIL balance.
-fn host_get_balance_lo_fn(env: FunctionEnvMut<HostState>) -> i64 {
+fn host_get_balance_lo_fn(mut env: FunctionEnvMut<HostState>) -> i64 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return 0;
+    }
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
         Err(_) => return 0,
@@ -383,7 +777,11 @@ fn host_get_balance_lo_fn(env: FunctionEnvMut<HostState>) -> i64 {
 }
 
 /// `host_get_balance_hi() -> i64` — Upper 64 bits of the contract's CIL balance.
-fn host_get_balance_hi_fn(env: FunctionEnvMut<HostState>) -> i64 {
+fn host_get_balance_hi_fn(mut env: FunctionEnvMut<HostState>) -> i64 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return 0;
+    }
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
         Err(_) => return 0,
@@ -391,17 +789,40 @@ fn host_get_balance_hi_fn(env: FunctionEnvMut<HostState>) -> i64 {
     (inner.balance >> 64) as i64
 }
 
+/// `host_get_attached_value(out_ptr) -> i32` — Write the CIL attached to this
+/// call (u128, 16 bytes little-endian) to guest memory.
+fn host_get_attached_value_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return -1;
+    }
+    let value = match env.data().inner.lock() {
+        Ok(i) => i.attached_value,
+        Err(_) => return -1,
+    };
+    write_guest_bytes(&env, out_ptr as u32, &value.to_le_bytes(), 16)
+}
+
 /// `host_get_timestamp() -> i64` — Block timestamp in seconds since UNIX epoch.
-fn host_get_timestamp_fn(env: FunctionEnvMut<HostState>) -> i64 {
-    let inner = match env.data().inner.lock() {
+fn host_get_timestamp_fn(mut env: FunctionEnvMut<HostState>) -> i64 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return 0;
+    }
+    let mut inner = match env.data().inner.lock() {
         Ok(i) => i,
         Err(_) => return 0,
     };
+    inner.env_reads = true;
     inner.timestamp as i64
 }
 
 /// `host_get_arg_count() -> i32` — Number of string arguments passed to this call.
-fn host_get_arg_count_fn(env: FunctionEnvMut<HostState>) -> i32 {
+fn host_get_arg_count_fn(mut env: FunctionEnvMut<HostState>) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return 0;
+    }
     let inner = match env.data().inner.lock() {
         Ok(i) => i,
         Err(_) => return 0,
@@ -411,7 +832,12 @@ fn host_get_arg_count_fn(env: FunctionEnvMut<HostState>) -> i32 {
 
 /// `host_get_arg(idx, out_ptr, out_max) -> i32` — Get argument by index.
 /// Returns byte length of the argument, or -1 if index is out of bounds.
-fn host_get_arg_fn(env: FunctionEnvMut<HostState>, idx: i32, out_ptr: i32, out_max: i32) -> i32 {
+fn host_get_arg_fn(
+    mut env: FunctionEnvMut<HostState>,
+    idx: i32,
+    out_ptr: i32,
+    out_max: i32,
+) -> i32 {
     let arg_data = {
         let inner = match env.data().inner.lock() {
             Ok(i) => i,
@@ -422,13 +848,21 @@ fn host_get_arg_fn(env: FunctionEnvMut<HostState>, idx: i32, out_ptr: i32, out_m
             None => return -1,
         }
     };
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(arg_data.len() as u64)) {
+        return -1;
+    }
     write_guest_bytes(&env, out_ptr as u32, arg_data.as_bytes(), out_max as u32)
 }
 
 /// `host_set_return(ptr, len)` — Set the contract's return data.
 /// Called by the contract to return structured data (e.g., JSON response).
-fn host_set_return_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
+fn host_set_return_fn(mut env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
     let len = (len as u32).min(MAX_RETURN_DATA_SIZE);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(len as u64)) {
+        return;
+    }
     if let Some(data) = read_guest_bytes(&env, ptr as u32, len) {
         if let Ok(mut inner) = env.data().inner.lock() {
             inner.return_data = data;
@@ -440,12 +874,19 @@ fn host_set_return_fn(env: FunctionEnvMut<HostState>, ptr: i32, len: i32) {
 /// Compute blake3 hash of input data, write 32 bytes to `out_ptr`.
 /// Returns 32 on success, -1 on error.
 fn host_blake3_fn(
-    env: FunctionEnvMut<HostState>,
+    mut env: FunctionEnvMut<HostState>,
     data_ptr: i32,
     data_len: i32,
     out_ptr: i32,
 ) -> i32 {
     let data_len = (data_len as u32).min(MAX_STATE_VALUE_SIZE);
+    let gas = env.data().gas;
+    let cost = gas
+        .blake3
+        .saturating_add((data_len as u64).saturating_mul(gas.per_byte));
+    if !charge_gas(&mut env, cost) {
+        return -1;
+    }
     let data = match read_guest_bytes(&env, data_ptr as u32, data_len) {
         Some(d) => d,
         None => return -1,
@@ -454,6 +895,171 @@ fn host_blake3_fn(
     write_guest_bytes(&env, out_ptr as u32, hash.as_bytes(), 32)
 }
 
+/// Seed returned by the `n`-th `host_random_seed` call of an execution:
+/// `blake3("LOS-RANDOM-SEED-V1" ‖ self_address ‖ caller ‖ timestamp ‖ args ‖ n)`
+/// with every variable-length field length-prefixed.
+///
+/// Deterministic — every validator derives the same bytes — but therefore
+/// also predictable by the caller. Fine for shuffles, IDs and hash-map
+/// seeds; never for lotteries or anything with value riding on the outcome.
+pub fn derive_random_seed(
+    self_address: &str,
+    caller: &str,
+    timestamp: u64,
+    args: &[String],
+    n: u32,
+) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(b"LOS-RANDOM-SEED-V1");
+    for field in [self_address, caller] {
+        h.update(&(field.len() as u64).to_le_bytes());
+        h.update(field.as_bytes());
+    }
+    h.update(&timestamp.to_le_bytes());
+    h.update(&(args.len() as u64).to_le_bytes());
+    for a in args {
+        h.update(&(a.len() as u64).to_le_bytes());
+        h.update(a.as_bytes());
+    }
+    h.update(&n.to_le_bytes());
+    *h.finalize().as_bytes()
+}
+
+/// `host_random_seed(out_ptr) -> i32` — Write a deterministic 32-byte seed
+/// (see [`derive_random_seed`]) to `out_ptr`. Returns 32, or -1 on error.
+/// Backs the SDK's `crypto::random_seed` and the `compat` getrandom backend.
+fn host_random_seed_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.blake3) {
+        return -1;
+    }
+    let seed = {
+        let mut inner = match env.data().inner.lock() {
+            Ok(i) => i,
+            Err(_) => return -1,
+        };
+        let n = inner.random_counter;
+        inner.random_counter = n.saturating_add(1);
+        inner.env_reads = true;
+        derive_random_seed(
+            &inner.self_address,
+            &inner.caller,
+            inner.timestamp,
+            &inner.args,
+            n,
+        )
+    };
+    write_guest_bytes(&env, out_ptr as u32, &seed, 32)
+}
+
+/// Idempotency key of one invocation: `blake3(block_hash || call_index)`,
+/// the hex block hash as UTF-8 followed by the index as 4 bytes LE. Unique
+/// per call and hook across the ledger, and the same on every validator.
+pub fn derive_call_id(block_hash: &str, call_index: u32) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(block_hash.as_bytes());
+    h.update(&call_index.to_le_bytes());
+    *h.finalize().as_bytes()
+}
+
+/// `host_get_block_hash(out_ptr, out_max) -> i32` — Write the hash of the
+/// ContractCall block being executed (hex). Returns bytes written: 0 outside
+/// a block (dry runs, read-only calls), -1 on error.
+fn host_get_block_hash_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32, out_max: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.host_call) {
+        return -1;
+    }
+    let hash = {
+        let mut inner = match env.data().inner.lock() {
+            Ok(i) => i,
+            Err(_) => return -1,
+        };
+        inner.env_reads = true;
+        inner.context.block_hash.clone()
+    };
+    write_guest_bytes(&env, out_ptr as u32, hash.as_bytes(), out_max as u32)
+}
+
+/// `host_get_call_id(out_ptr) -> i32` — Write this invocation's 32-byte ID
+/// ([`derive_call_id`]) to guest memory.
+fn host_get_call_id_fn(mut env: FunctionEnvMut<HostState>, out_ptr: i32) -> i32 {
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.blake3) {
+        return -1;
+    }
+    let id = {
+        let mut inner = match env.data().inner.lock() {
+            Ok(i) => i,
+            Err(_) => return -1,
+        };
+        inner.env_reads = true;
+        derive_call_id(&inner.context.block_hash, inner.context.call_index)
+    };
+    write_guest_bytes(&env, out_ptr as u32, &id, 32)
+}
+
+/// Code hash of deployed contract `addr` as seen by the running execution.
+/// Outer None = registry unavailable (host error), inner None = no such contract.
+fn lookup_code_hash(env: &FunctionEnvMut<HostState>, addr: &str) -> Option<Option<String>> {
+    let registry = {
+        let mut inner = env.data().inner.lock().ok()?;
+        inner.env_reads = true;
+        inner.contracts.clone()?
+    };
+    let contracts = registry.lock().ok()?;
+    Some(contracts.get(addr).map(|c| c.code_hash.clone()))
+}
+
+/// `host_contract_exists(addr_ptr, addr_len) -> i32`
+/// Returns 1 if `addr` is a deployed contract, 0 if not, -1 on error.
+fn host_contract_exists_fn(
+    mut env: FunctionEnvMut<HostState>,
+    addr_ptr: i32,
+    addr_len: i32,
+) -> i32 {
+    let addr_len = (addr_len as u32).min(256);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(addr_len as u64)) {
+        return -1;
+    }
+    let Some(addr) = read_guest_string(&env, addr_ptr as u32, addr_len) else {
+        return -1;
+    };
+    match lookup_code_hash(&env, &addr) {
+        Some(Some(_)) => 1,
+        Some(None) => 0,
+        None => -1,
+    }
+}
+
+/// `host_get_code_hash(addr_ptr, addr_len, out_ptr, out_max) -> i32`
+/// Write the hex blake3 hash of contract `addr`'s bytecode (64 bytes, same
+/// as `code_hash` in GET /contract/:addr) to `out_ptr`. Returns bytes written,
+/// or -1 if `addr` is not a deployed contract or on error.
+fn host_get_code_hash_fn(
+    mut env: FunctionEnvMut<HostState>,
+    addr_ptr: i32,
+    addr_len: i32,
+    out_ptr: i32,
+    out_max: i32,
+) -> i32 {
+    let addr_len = (addr_len as u32).min(256);
+    let gas = env.data().gas;
+    if !charge_gas(&mut env, gas.call_with_bytes(addr_len as u64)) {
+        return -1;
+    }
+    let Some(addr) = read_guest_string(&env, addr_ptr as u32, addr_len) else {
+        return -1;
+    };
+    match lookup_code_hash(&env, &addr) {
+        Some(Some(hash)) => {
+            write_guest_bytes(&env, out_ptr as u32, hash.as_bytes(), out_max as u32)
+        }
+        _ => -1,
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Import object construction
 // ─────────────────────────────────────────────────────────────────
@@ -470,16 +1076,23 @@ pub fn create_host_imports(store: &mut Store, env: &FunctionEnv<HostState>) -> I
     let f_get_state = Function::new_typed_with_env(store, env, host_get_state_fn);
     let f_del_state = Function::new_typed_with_env(store, env, host_del_state_fn);
     let f_emit_event = Function::new_typed_with_env(store, env, host_emit_event_fn);
+    let f_emit_indexed = Function::new_typed_with_env(store, env, host_emit_event_indexed_fn);
     let f_transfer = Function::new_typed_with_env(store, env, host_transfer_fn);
     let f_get_caller = Function::new_typed_with_env(store, env, host_get_caller_fn);
     let f_get_self = Function::new_typed_with_env(store, env, host_get_self_address_fn);
     let f_bal_lo = Function::new_typed_with_env(store, env, host_get_balance_lo_fn);
     let f_bal_hi = Function::new_typed_with_env(store, env, host_get_balance_hi_fn);
     let f_timestamp = Function::new_typed_with_env(store, env, host_get_timestamp_fn);
+    let f_attached = Function::new_typed_with_env(store, env, host_get_attached_value_fn);
     let f_arg_count = Function::new_typed_with_env(store, env, host_get_arg_count_fn);
     let f_get_arg = Function::new_typed_with_env(store, env, host_get_arg_fn);
     let f_set_return = Function::new_typed_with_env(store, env, host_set_return_fn);
     let f_blake3 = Function::new_typed_with_env(store, env, host_blake3_fn);
+    let f_random_seed = Function::new_typed_with_env(store, env, host_random_seed_fn);
+    let f_contract_exists = Function::new_typed_with_env(store, env, host_contract_exists_fn);
+    let f_code_hash = Function::new_typed_with_env(store, env, host_get_code_hash_fn);
+    let f_block_hash = Function::new_typed_with_env(store, env, host_get_block_hash_fn);
+    let f_call_id = Function::new_typed_with_env(store, env, host_get_call_id_fn);
 
     imports! {
         "env" => {
@@ -489,6 +1102,7 @@ pub fn create_host_imports(store: &mut Store, env: &FunctionEnv<HostState>) -> I
             "host_get_state" => f_get_state,
             "host_del_state" => f_del_state,
             "host_emit_event" => f_emit_event,
+            "host_emit_event_indexed" => f_emit_indexed,
             "host_transfer" => f_transfer,
             "host_get_caller" => f_get_caller,
             "host_get_self_address" => f_get_self,
@@ -499,6 +1113,12 @@ pub fn create_host_imports(store: &mut Store, env: &FunctionEnv<HostState>) -> I
             "host_get_arg" => f_get_arg,
             "host_set_return" => f_set_return,
             "host_blake3" => f_blake3,
+            "host_random_seed" => f_random_seed,
+            "host_contract_exists" => f_contract_exists,
+            "host_get_code_hash" => f_code_hash,
+            "host_get_attached_value" => f_attached,
+            "host_get_block_hash" => f_block_hash,
+            "host_get_call_id" => f_call_id,
         }
     }
 }
@@ -521,18 +1141,62 @@ mod tests {
             caller: "LOSWtestCaller".to_string(),
             self_address: "LOSConTestAddr".to_string(),
             balance: 1_000_000,
+            attached_value: 0,
             timestamp: 1700000000,
             args: vec!["arg0".to_string(), "arg1".to_string()],
             return_data: Vec::new(),
             logs: Vec::new(),
             aborted: false,
             abort_message: String::new(),
+            random_counter: 0,
+            contracts: None,
+            context: CallContext::default(),
+            env_reads: false,
         };
         assert_eq!(data.args.len(), 2);
         assert_eq!(data.balance, 1_000_000);
         assert!(!data.aborted);
     }
 
+    #[test]
+    fn test_schema_version_only_moves_forward() {
+        assert!(check_schema_version_write(None, Some(b"1")).is_ok());
+        assert!(check_schema_version_write(Some(b"1"), Some(b"3")).is_ok());
+        assert!(check_schema_version_write(Some(b"2"), Some(b"2")).is_err());
+        assert!(check_schema_version_write(Some(b"2"), Some(b"1")).is_err());
+        assert!(check_schema_version_write(None, Some(b"0")).is_err());
+        assert!(check_schema_version_write(None, Some(b"v2")).is_err());
+        assert!(check_schema_version_write(Some(b"1"), None).is_err());
+    }
+
+    #[test]
+    fn test_call_id_unique_per_block_and_index() {
+        let h1 = "ab".repeat(32);
+        let h2 = "cd".repeat(32);
+        let id = derive_call_id(&h1, 0);
+        assert_eq!(id, derive_call_id(&h1, 0));
+        assert_ne!(id, derive_call_id(&h1, 1));
+        assert_ne!(id, derive_call_id(&h2, 0));
+        let mut preimage = h1.into_bytes();
+        preimage.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(id, *blake3::hash(&preimage).as_bytes());
+    }
+
+    #[test]
+    fn test_random_seed_deterministic_and_distinct() {
+        let args = vec!["a".to_string()];
+        let s0 = derive_random_seed("LOSConX", "LOSalice", 100, &args, 0);
+        assert_eq!(s0, derive_random_seed("LOSConX", "LOSalice", 100, &args, 0));
+        assert_ne!(s0, derive_random_seed("LOSConX", "LOSalice", 100, &args, 1));
+        assert_ne!(s0, derive_random_seed("LOSConX", "LOSalice", 101, &args, 0));
+        assert_ne!(s0, derive_random_seed("LOSConX", "LOSbob", 100, &args, 0));
+        // Length prefixes keep field boundaries unambiguous
+        assert_ne!(
+            derive_random_seed("LOSConXa", "b", 0, &[], 0),
+            derive_random_seed("LOSConX", "ab", 0, &[], 0)
+        );
+    }
+
     #[test]
     fn test_host_state_is_send() {
         // Compile-time check: HostState must be Send + 'static for wasmer FunctionEnv
@@ -553,6 +1217,8 @@ mod tests {
             aborted: false,
             abort_message: String::new(),
             sdk_mode: true,
+            trace: None,
+            env_reads: false,
         };
         assert_eq!(result.return_code, 0);
         assert!(result.sdk_mode);
@@ -578,13 +1244,29 @@ mod tests {
         assert_eq!(recon2, large);
     }
 
+    #[test]
+    fn test_parse_topics() {
+        assert!(parse_topics(b"").unwrap().is_empty());
+        assert_eq!(
+            parse_topics(b"LOSalice\0LOSbob").unwrap(),
+            vec!["LOSalice", "LOSbob"]
+        );
+        // Empty topics keep their position
+        assert_eq!(parse_topics(b"\0x").unwrap(), vec!["", "x"]);
+        assert!(parse_topics(b"a\0b\0c\0d\0e").is_err());
+        assert!(parse_topics(&[b'x'; MAX_TOPIC_SIZE + 1]).is_err());
+        assert!(parse_topics(&[0xFF]).is_err());
+    }
+
     #[test]
     fn test_limits_constants() {
         assert_eq!(MAX_STATE_VALUE_SIZE, 262_144); // 256 KB
         assert_eq!(MAX_STATE_KEY_SIZE, 1_024);
         assert_eq!(MAX_RETURN_DATA_SIZE, 262_144);
         assert_eq!(MAX_LOG_SIZE, 4_096);
-        assert_eq!(MAX_EVENTS, 256);
+        assert_eq!(MAX_EVENTS, 64);
+        assert_eq!(MAX_EVENT_TYPE_SIZE, 128);
+        assert_eq!(MAX_EVENT_DATA_SIZE, 8_192);
         assert_eq!(MAX_TRANSFERS, 64);
         assert_eq!(MAX_STATE_KEYS, 1_024);
         assert_eq!(MAX_LOGS, 256);
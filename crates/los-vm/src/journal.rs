@@ -0,0 +1,299 @@
+//! # Block-level state journal
+//!
+//! Contract calls mutate `WasmEngine` state as soon as they execute. When the
+//! block that carried the call can still fail (consensus rejects it, or the
+//! ledger refuses the debit), those changes must be undoable. Staged calls
+//! ([`WasmEngine::call_contract_staged`], [`WasmEngine::send_to_contract_staged`])
+//! keep an undo record under the block hash: the previous value of every state
+//! key they write, the net balance change of every contract they touch and
+//! any pause flag or ownership they change.
+//!
+//! - changes apply immediately, so later blocks build on them
+//! - [`WasmEngine::commit`] forgets the undo record once the block is final
+//! - [`WasmEngine::rollback`] replays it. Blocks staged after the rolled
+//!   back one may have read its changes, so they are rolled back too
+//!   (newest first) and their hashes returned for re-execution.
+//!
+//! Blocks are normally committed in order. A block committed while an
+//! earlier one is still staged keeps its own changes on rollback of the
+//! earlier one, except for keys both wrote.
+//!
+//! The journal is in memory only and bounded by [`MAX_STAGED_BLOCKS`]
+//! (the oldest entry is committed implicitly when full).
+
+use crate::ownership::OwnershipState;
+use crate::{Contract, WasmEngine};
+use std::collections::BTreeMap;
+
+/// Maximum blocks with uncommitted changes
+pub const MAX_STAGED_BLOCKS: usize = 10_000;
+
+/// What one staged block changed in one contract
+#[derive(Debug, Clone, Default)]
+struct Undo {
+    /// Key → value before the block's first write (None = key was absent)
+    keys: BTreeMap<String, Option<String>>,
+    /// Net balance change made by the block
+    balance_delta: i128,
+    /// Pause flag before the block changed it (None = unchanged)
+    paused: Option<bool>,
+    /// Ownership before the block changed it (None = unchanged)
+    ownership: Option<OwnershipState>,
+}
+
+#[derive(Debug)]
+struct JournalEntry {
+    block_hash: String,
+    undo: BTreeMap<String, Undo>,
+}
+
+/// Staged blocks in execution order
+#[derive(Debug, Default)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Record that `block_hash` is about to write `keys` of contract `address`
+    /// (current state `state`) and change its balance by `balance_delta`. Only
+    /// the first write of a key keeps its previous value (the one to restore).
+    pub(crate) fn record<'a>(
+        &mut self,
+        block_hash: &str,
+        address: &str,
+        state: &BTreeMap<String, String>,
+        keys: impl IntoIterator<Item = &'a String>,
+        balance_delta: i128,
+    ) {
+        let undo = self.undo_mut(block_hash, address);
+        for key in keys {
+            undo.keys
+                .entry(key.clone())
+                .or_insert_with(|| state.get(key).cloned());
+        }
+        undo.balance_delta = undo.balance_delta.saturating_add(balance_delta);
+    }
+
+    /// Record that `block_hash` changes the pause flag of `address` (was `previous`)
+    pub(crate) fn record_pause(&mut self, block_hash: &str, address: &str, previous: bool) {
+        self.undo_mut(block_hash, address)
+            .paused
+            .get_or_insert(previous);
+    }
+
+    /// Record that `block_hash` changes the ownership of `address`
+    pub(crate) fn record_ownership(
+        &mut self,
+        block_hash: &str,
+        address: &str,
+        previous: OwnershipState,
+    ) {
+        self.undo_mut(block_hash, address)
+            .ownership
+            .get_or_insert(previous);
+    }
+
+    /// Undo record of `address` in `block_hash`'s entry (created if missing)
+    fn undo_mut(&mut self, block_hash: &str, address: &str) -> &mut Undo {
+        let idx = match self
+            .entries
+            .iter()
+            .rposition(|e| e.block_hash == block_hash)
+        {
+            Some(i) => i,
+            None => {
+                if self.entries.len() >= MAX_STAGED_BLOCKS {
+                    self.entries.remove(0);
+                }
+                self.entries.push(JournalEntry {
+                    block_hash: block_hash.to_string(),
+                    undo: BTreeMap::new(),
+                });
+                self.entries.len() - 1
+            }
+        };
+        self.entries[idx]
+            .undo
+            .entry(address.to_string())
+            .or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl WasmEngine {
+    /// Journal a mutation of `contract` if it belongs to a staged block.
+    /// Call with the contracts map locked, before writing the state keys.
+    pub(crate) fn journal_record<'a>(
+        &self,
+        block_hash: Option<&str>,
+        contract: &Contract,
+        keys: impl IntoIterator<Item = &'a String>,
+        balance_delta: i128,
+    ) {
+        let Some(block_hash) = block_hash else {
+            return;
+        };
+        let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        journal.record(
+            block_hash,
+            &contract.address,
+            &contract.state,
+            keys,
+            balance_delta,
+        );
+    }
+
+    /// `call_contract` as part of block `block_hash` (reversible until commit).
+    /// The contract reads `block_hash` with `host_get_block_hash`.
+    pub fn call_contract_staged(
+        &self,
+        block_hash: &str,
+        call: crate::ContractCall,
+    ) -> Result<crate::ContractResult, String> {
+        self.call_contract_in(Some(block_hash), call)
+    }
+
+    /// `send_to_contract` as part of block `block_hash` (reversible until commit)
+    pub fn send_to_contract_staged(
+        &self,
+        block_hash: &str,
+        contract_addr: &str,
+        amount: u128,
+    ) -> Result<(), String> {
+        self.send_to_contract_in(Some(block_hash), contract_addr, amount)
+    }
+
+    /// Block is final: drop its undo record. Returns false if nothing was staged.
+    pub fn commit(&self, block_hash: &str) -> bool {
+        let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        let before = journal.entries.len();
+        journal.entries.retain(|e| e.block_hash != block_hash);
+        journal.entries.len() != before
+    }
+
+    /// Undo `block_hash` and every block staged after it, newest first.
+    /// Returns the rolled back hashes in that order (empty if not staged).
+    pub fn rollback(&self, block_hash: &str) -> Result<Vec<String>, String> {
+        // Lock order: contracts → journal (same as the write paths)
+        let mut contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+        let mut journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(idx) = journal
+            .entries
+            .iter()
+            .position(|e| e.block_hash == block_hash)
+        else {
+            return Ok(Vec::new());
+        };
+        let undone: Vec<JournalEntry> = journal.entries.drain(idx..).rev().collect();
+        let mut hashes = Vec::with_capacity(undone.len());
+        for entry in undone {
+            for (addr, undo) in entry.undo {
+                let Some(c) = contracts.get_mut(&addr) else {
+                    continue;
+                };
+                for (key, prev) in undo.keys {
+                    match prev {
+                        Some(v) => c.state.insert(key, v),
+                        None => c.state.remove(&key),
+                    };
+                }
+                c.balance = if undo.balance_delta >= 0 {
+                    c.balance.saturating_sub(undo.balance_delta as u128)
+                } else {
+                    c.balance.saturating_add(undo.balance_delta.unsigned_abs())
+                };
+                if let Some(paused) = undo.paused {
+                    c.paused = paused;
+                }
+                if let Some(ownership) = undo.ownership {
+                    ownership.restore(c);
+                }
+            }
+            hashes.push(entry.block_hash);
+        }
+        Ok(hashes)
+    }
+
+    /// Hashes of blocks with uncommitted changes, oldest first
+    pub fn staged_blocks(&self) -> Vec<String> {
+        let journal = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+        journal
+            .entries
+            .iter()
+            .map(|e| e.block_hash.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ContractCall, WasmEngine};
+    use std::collections::BTreeMap;
+
+    fn set_state(contract: &str, key: &str, value: &str) -> ContractCall {
+        ContractCall {
+            contract: contract.to_string(),
+            function: "set_state".to_string(),
+            args: vec![key.to_string(), value.to_string()],
+            gas_limit: 1_000,
+            caller: "LOSalice".to_string(),
+            block_timestamp: 1,
+            amount: 0,
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_rollback_restores_block_and_later_blocks() {
+        let engine = WasmEngine::new();
+        let addr = engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        let value = |k: &str| engine.get_contract_state(&addr).unwrap().get(k).cloned();
+
+        engine.call_contract(set_state(&addr, "k", "base")).unwrap();
+        engine
+            .call_contract_staged("b1", set_state(&addr, "k", "one"))
+            .unwrap();
+        engine.send_to_contract_staged("b1", &addr, 50).unwrap();
+        engine
+            .call_contract_staged("b2", set_state(&addr, "k", "two"))
+            .unwrap();
+        engine
+            .call_contract_staged("b3", set_state(&addr, "other", "x"))
+            .unwrap();
+        assert_eq!(engine.staged_blocks(), vec!["b1", "b2", "b3"]);
+
+        // b3 is final; rolling back b2 leaves b1 in place
+        assert!(engine.commit("b3"));
+        assert_eq!(engine.rollback("b2").unwrap(), vec!["b2"]);
+        assert_eq!(value("k").as_deref(), Some("one"));
+        assert_eq!(value("other").as_deref(), Some("x"));
+
+        // b1 goes back to the unstaged state, including the value transfer
+        engine
+            .call_contract_staged("b4", set_state(&addr, "k", "four"))
+            .unwrap();
+        assert_eq!(engine.rollback("b1").unwrap(), vec!["b4", "b1"]);
+        assert_eq!(value("k").as_deref(), Some("base"));
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 0);
+        assert!(engine.staged_blocks().is_empty());
+        assert!(engine.rollback("unknown").unwrap().is_empty());
+        assert!(!engine.commit("unknown"));
+    }
+}
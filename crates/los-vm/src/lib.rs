@@ -9,9 +9,10 @@
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use wasmer::{imports, CompilerConfig, FunctionEnv, Instance, Module, Store, Value};
 use wasmer_compiler_cranelift::Cranelift;
 use wasmer_middlewares::metering::get_remaining_points;
@@ -22,7 +23,26 @@ use wasmer_middlewares::Metering;
 /// Once MAX_LEAKED_THREADS is reached, new WASM executions are rejected
 /// to prevent unbounded resource consumption from pathological contracts.
 static LEAKED_THREADS: AtomicUsize = AtomicUsize::new(0);
-const MAX_LEAKED_THREADS: usize = 16;
+pub const MAX_LEAKED_THREADS: usize = 16;
+
+/// Runtime kill switch for mock dispatch and stub oracles (one-way).
+static TEST_STUBS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Disable VM test stubs for the rest of the process. Called when the node
+/// installs a chain spec that does not allow them (e.g. mainnet on a default build).
+pub fn disable_test_stubs() {
+    TEST_STUBS_DISABLED.store(true, AtomicOrdering::Release);
+}
+
+/// Whether mock dispatch and stub oracles may run
+pub fn test_stubs_enabled() -> bool {
+    !cfg!(feature = "mainnet") && !TEST_STUBS_DISABLED.load(AtomicOrdering::Acquire)
+}
+
+/// Number of WASM timeout threads leaked since startup (exposed via /health).
+pub fn leaked_thread_count() -> usize {
+    LEAKED_THREADS.load(AtomicOrdering::Relaxed)
+}
 
 // Provide __rust_probestack stub for wasmer-vm 4.x compatibility with
 // Rust 1.85+ where this symbol was removed from compiler_builtins.
@@ -44,6 +64,26 @@ pub mod usp01;
 pub mod token_registry;
 // DEX Registry: node-level DEX pool discovery and query helpers
 pub mod dex_registry;
+// Receive hooks: settle host_transfer to other contracts (on_receive)
+pub mod receive_hook;
+// Journal: per-block undo records for staged contract calls
+pub mod journal;
+// Emergency pause: owner/guardian kill switch for exploited contracts
+pub mod pause;
+// Ownership: two-step owner transfer and renounce via reserved functions
+pub mod ownership;
+// Execution tracing: host-call trace + instruction histogram for dry runs
+pub mod trace;
+// Host ABI versioning: los_abi section + host capability checks
+pub mod abi;
+// Deploy-time bytecode optimization: custom section + linker export stripping
+pub mod optimize;
+// Contract metadata: deploy-time contract_info() call (name, version, SDK ABI)
+pub mod contract_info;
+// Execution limits: bounded in-flight WASM executions with a timed queue
+pub mod exec_limit;
+
+pub use contract_info::ContractInfo;
 
 /// Unauthority Virtual Machine (UVM)
 /// Executes WebAssembly smart contracts with permissionless deployment
@@ -54,6 +94,9 @@ const MAX_BYTECODE_SIZE: usize = 1_048_576;
 const MAX_EXECUTION_SECS: u64 = 5;
 /// Gas cost per kilobyte of bytecode (compilation cost)
 const GAS_PER_KB_BYTECODE: u64 = 100;
+/// Consensus cap on a call's gas limit (same value as los_core::MAX_GAS_PER_CALL).
+/// Larger limits are refused before execution.
+pub const MAX_GAS_PER_CALL: u64 = 10_000_000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contract {
@@ -64,7 +107,20 @@ pub struct Contract {
     pub state: BTreeMap<String, String>,
     pub balance: u128,
     pub created_at_block: u64,
+    /// Empty once ownership is renounced (see ownership)
     pub owner: String,
+    /// Proposed new owner awaiting `__accept_ownership`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_owner: Option<String>,
+    /// Block timestamp of `__renounce_ownership`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ownership_renounced_at: Option<u64>,
+    /// Emergency stop: calls are rejected while set (see pause)
+    #[serde(default)]
+    pub paused: bool,
+    /// Metadata from the contract's `contract_info` export, read at deploy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<ContractInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +139,10 @@ pub struct ContractCall {
     /// If 0, falls back to SystemTime::now() (backward-compatible, but non-deterministic).
     #[serde(default)]
     pub block_timestamp: u64,
+    /// CIL attached to the call (`los_sdk::msg_value()`). Credited to the
+    /// contract before execution; refunded to the caller if the call fails.
+    #[serde(default)]
+    pub amount: u128,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,19 +156,107 @@ pub struct ContractResult {
     pub events: Vec<ContractEvent>,
     /// Transfers initiated by `host_transfer()` during execution.
     /// Each entry is (recipient_address, amount_cil).
-    /// The contract's balance is already decremented — the caller MUST
-    /// credit these amounts to the recipient accounts in the ledger.
+    /// The contract's balance is already decremented. The node credits them
+    /// with `los_core::Ledger::apply_contract_transfers` (system Receive blocks).
+    /// Transfers to other contracts are not listed here (see `contract_transfers`).
     #[serde(default)]
     pub transfers: Vec<(String, u128)>,
+    /// Transfers to other contracts, already credited to their VM balance
+    /// after their `on_receive` hook (if any) accepted them.
+    #[serde(default)]
+    pub contract_transfers: Vec<(String, u128)>,
 }
 
-/// Contract event (emitted during execution, stored for indexing)
+/// Result of `WasmEngine::dry_run`: what the call would do, nothing applied
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    /// State changes / events / transfers the call would produce.
+    /// Transfers are listed but not settled (recipients' hooks do not run).
+    pub result: ContractResult,
+    /// `host_log` output
+    pub logs: Vec<String>,
+    /// Present when tracing was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<trace::ExecutionTrace>,
+    /// The call read the timestamp, a random seed or another contract (see
+    /// HostData::env_reads): repeating it on the same state may differ
+    #[serde(default)]
+    pub env_reads: bool,
+}
+
+/// Contract event (emitted during execution, stored for indexing)
+///
+/// Events are totally ordered by (timestamp, block_hash, call_index,
+/// event_index). Every node derives the same position for an event, so the
+/// tuple also identifies it: storing a block's events twice is a no-op.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContractEvent {
     pub contract: String,
     pub event_type: String,
     pub data: BTreeMap<String, String>,
     pub timestamp: u64,
+    /// Indexed values (at most `host::MAX_EVENT_TOPICS`), set via
+    /// `host_emit_event_indexed`. Nodes index them for filtered queries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    /// ContractCall block that emitted the event (set when the block is applied)
+    #[serde(default)]
+    pub block_hash: String,
+    /// Execution within the block: 0 = the called contract, then one per
+    /// `on_receive` hook in the order they ran
+    #[serde(default)]
+    pub call_index: u32,
+    /// Position among the events of that execution
+    #[serde(default)]
+    pub event_index: u32,
+}
+
+/// Cumulative execution statistics for one contract (operator analytics)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContractStats {
+    pub total_gas: u64,
+    pub calls: u64,
+    pub failures: u64,
+    /// Sum of wall-clock execution time, microseconds
+    pub total_exec_micros: u64,
+}
+
+impl ContractStats {
+    /// Average wall-clock execution time per call, microseconds
+    pub fn avg_exec_micros(&self) -> u64 {
+        self.total_exec_micros.checked_div(self.calls).unwrap_or(0)
+    }
+}
+
+/// Sort key for `WasmEngine::top_contracts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsSort {
+    Gas,
+    Calls,
+    Failures,
+    AvgTime,
+}
+
+impl StatsSort {
+    /// Parse `gas` / `calls` / `failures` / `time` (unknown → None)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "gas" => Some(StatsSort::Gas),
+            "calls" => Some(StatsSort::Calls),
+            "failures" => Some(StatsSort::Failures),
+            "time" | "avg_time" => Some(StatsSort::AvgTime),
+            _ => None,
+        }
+    }
+
+    fn key(self, s: &ContractStats) -> u64 {
+        match self {
+            StatsSort::Gas => s.total_gas,
+            StatsSort::Calls => s.calls,
+            StatsSort::Failures => s.failures,
+            StatsSort::AvgTime => s.avg_exec_micros(),
+        }
+    }
 }
 
 /// WASM execution environment
@@ -120,6 +268,16 @@ pub struct WasmEngine {
     /// snapshot the same state, execute independently, and overwrite each
     /// other's results. The lock ensures serialized execution per contract.
     contract_locks: Arc<Mutex<BTreeMap<String, Arc<Mutex<()>>>>>,
+    /// Per-contract gas/call/failure/time counters (in-memory, since startup).
+    stats: Arc<Mutex<BTreeMap<String, ContractStats>>>,
+    /// Swap history of DEX pools, fed from `Swap` events (see dex_registry).
+    dex_analytics: Arc<Mutex<dex_registry::DexAnalytics>>,
+    /// Undo records of staged (not yet final) blocks (see journal).
+    journal: Arc<Mutex<journal::Journal>>,
+    /// Accounts allowed to pause any contract (see pause).
+    guardians: Arc<Mutex<BTreeSet<String>>>,
+    /// Bound on concurrent executions (see exec_limit).
+    exec_limit: Arc<exec_limit::ExecLimiter>,
 }
 
 impl WasmEngine {
@@ -129,6 +287,47 @@ impl WasmEngine {
             contracts: Arc::new(Mutex::new(BTreeMap::new())),
             nonce: Arc::new(Mutex::new(BTreeMap::new())),
             contract_locks: Arc::new(Mutex::new(BTreeMap::new())),
+            stats: Arc::new(Mutex::new(BTreeMap::new())),
+            dex_analytics: Arc::new(Mutex::new(dex_registry::DexAnalytics::new())),
+            journal: Arc::new(Mutex::new(journal::Journal::default())),
+            guardians: Arc::new(Mutex::new(BTreeSet::new())),
+            exec_limit: Arc::new(exec_limit::ExecLimiter::default()),
+        }
+    }
+
+    /// Execution statistics for one contract (None if never called)
+    pub fn contract_stats(&self, address: &str) -> Option<ContractStats> {
+        self.stats.lock().ok()?.get(address).cloned()
+    }
+
+    /// Top `limit` contracts by `sort`, descending (ties broken by address)
+    pub fn top_contracts(&self, sort: StatsSort, limit: usize) -> Vec<(String, ContractStats)> {
+        let Ok(stats) = self.stats.lock() else {
+            return Vec::new();
+        };
+        let mut all: Vec<(String, ContractStats)> =
+            stats.iter().map(|(a, s)| (a.clone(), s.clone())).collect();
+        all.sort_by_key(|(_, s)| std::cmp::Reverse(sort.key(s)));
+        all.truncate(limit);
+        all
+    }
+
+    /// Record one call. Only deployed contracts are tracked, so calls to
+    /// unknown addresses cannot grow the stats map.
+    fn record_call(&self, address: &str, gas_used: u64, success: bool, elapsed: Duration) {
+        if !self.contract_exists(address).unwrap_or(false) {
+            return;
+        }
+        if let Ok(mut stats) = self.stats.lock() {
+            let entry = stats.entry(address.to_string()).or_default();
+            entry.calls += 1;
+            entry.total_gas = entry.total_gas.saturating_add(gas_used);
+            entry.total_exec_micros = entry
+                .total_exec_micros
+                .saturating_add(elapsed.as_micros() as u64);
+            if !success {
+                entry.failures += 1;
+            }
         }
     }
 
@@ -140,19 +339,7 @@ impl WasmEngine {
         initial_state: BTreeMap<String, String>,
         block_number: u64,
     ) -> Result<String, String> {
-        // Validate WASM magic bytes (0x00 0x61 0x73 0x6d)
-        if bytecode.len() < 4 || &bytecode[0..4] != b"\0asm" {
-            return Err("Invalid WASM bytecode (missing magic header)".to_string());
-        }
-
-        // Enforce bytecode size limit
-        if bytecode.len() > MAX_BYTECODE_SIZE {
-            return Err(format!(
-                "WASM bytecode too large: {} bytes (max {} bytes)",
-                bytecode.len(),
-                MAX_BYTECODE_SIZE
-            ));
-        }
+        Self::check_deployable(&bytecode)?;
 
         let mut nonce = self
             .nonce
@@ -169,8 +356,77 @@ impl WasmEngine {
         let addr_hash = blake3::hash(addr_input.as_bytes());
         let address = format!("LOSCon{}", hex::encode(&addr_hash.as_bytes()[0..16]));
 
+        drop(nonce);
+
+        self.insert_contract(address, owner, bytecode, initial_state, block_number)
+    }
+
+    /// Deploy at the salted address `salted_address(owner, salt, code_hash)`
+    /// (CREATE2-style, los_core::deploy_link). Fails if that address is taken.
+    pub fn deploy_contract_salted(
+        &self,
+        owner: String,
+        bytecode: Vec<u8>,
+        initial_state: BTreeMap<String, String>,
+        block_number: u64,
+        salt: &[u8; 32],
+    ) -> Result<String, String> {
+        Self::check_deployable(&bytecode)?;
+        let address = Self::salted_address(&owner, salt, &Self::compute_code_hash(&bytecode));
+        if self.contract_exists(&address)? {
+            return Err(format!("Contract address {} is already taken", address));
+        }
+        self.insert_contract(address, owner, bytecode, initial_state, block_number)
+    }
+
+    /// Address of a salted deploy: "LOSCon" + first 32 hex chars of
+    /// blake3(owner ‖ salt ‖ code_hash). Known before the deploy block exists.
+    pub fn salted_address(owner: &str, salt: &[u8; 32], code_hash: &str) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(owner.as_bytes());
+        hasher.update(salt);
+        match hex::decode(code_hash) {
+            Ok(bytes) => hasher.update(&bytes),
+            Err(_) => hasher.update(code_hash.as_bytes()),
+        };
+        format!(
+            "LOSCon{}",
+            hex::encode(&hasher.finalize().as_bytes()[0..16])
+        )
+    }
+
+    /// Checks shared by every deploy path
+    fn check_deployable(bytecode: &[u8]) -> Result<(), String> {
+        // Validate WASM magic bytes (0x00 0x61 0x73 0x6d)
+        if bytecode.len() < 4 || &bytecode[0..4] != b"\0asm" {
+            return Err("Invalid WASM bytecode (missing magic header)".to_string());
+        }
+
+        // Enforce bytecode size limit
+        if bytecode.len() > MAX_BYTECODE_SIZE {
+            return Err(format!(
+                "WASM bytecode too large: {} bytes (max {} bytes)",
+                bytecode.len(),
+                MAX_BYTECODE_SIZE
+            ));
+        }
+
+        // Refuse code needing host functions this node lacks (newer SDK)
+        abi::check_compat(bytecode)?;
+        Ok(())
+    }
+
+    fn insert_contract(
+        &self,
+        address: String,
+        owner: String,
+        bytecode: Vec<u8>,
+        initial_state: BTreeMap<String, String>,
+        block_number: u64,
+    ) -> Result<String, String> {
         // Calculate code hash
         let code_hash = hex::encode(&blake3::hash(&bytecode).as_bytes()[0..32]);
+        let info = self.read_contract_info(&bytecode, &owner, &address, &initial_state);
 
         let contract = Contract {
             address: address.clone(),
@@ -180,6 +436,10 @@ impl WasmEngine {
             balance: 0,
             created_at_block: block_number,
             owner,
+            pending_owner: None,
+            ownership_renounced_at: None,
+            paused: false,
+            info,
         };
 
         let mut contracts = self
@@ -187,10 +447,46 @@ impl WasmEngine {
             .lock()
             .map_err(|_| "Failed to lock contracts".to_string())?;
 
+        // A salted address can be taken only once
+        if contracts.contains_key(&address) {
+            return Err(format!("Contract address {} is already taken", address));
+        }
         contracts.insert(address.clone(), contract);
         Ok(address)
     }
 
+    /// Call `contract_info` once on freshly deployed code (contract_info.rs).
+    /// Only a successful SDK-mode call that reads no environment counts.
+    fn read_contract_info(
+        &self,
+        bytecode: &[u8],
+        owner: &str,
+        address: &str,
+        initial_state: &BTreeMap<String, String>,
+    ) -> Option<ContractInfo> {
+        if !contract_info::exports_info(bytecode) {
+            return None;
+        }
+        let exec = self
+            .execute_wasm_hosted(
+                bytecode,
+                contract_info::CONTRACT_INFO_EXPORT,
+                &[],
+                contract_info::CONTRACT_INFO_GAS,
+                owner,
+                address,
+                initial_state,
+                0,
+                0,
+                0,
+            )
+            .ok()?;
+        if !exec.sdk_mode || exec.return_code != 0 || exec.env_reads {
+            return None;
+        }
+        contract_info::parse(&exec.return_data)
+    }
+
     /// Get contract by address
     pub fn get_contract(&self, address: &str) -> Result<Contract, String> {
         let contracts = self
@@ -227,6 +523,12 @@ impl WasmEngine {
                 leaked, MAX_LEAKED_THREADS
             ));
         }
+        if gas_limit > MAX_GAS_PER_CALL {
+            return Err(format!(
+                "Gas limit {} exceeds per-call cap {}",
+                gas_limit, MAX_GAS_PER_CALL
+            ));
+        }
 
         // 1. Bytecode size limit
         if bytecode.len() > MAX_BYTECODE_SIZE {
@@ -248,6 +550,9 @@ impl WasmEngine {
 
         let remaining_gas = gas_limit - compile_gas;
 
+        // Held until the result (or timeout) is in, bounding concurrent compiles
+        let _permit = self.exec_limit.acquire(exec_limit::Admission::Consensus)?;
+
         // 3. Clone data for thread-safe execution
         let bytecode_owned = bytecode.to_vec();
         let function_owned = function.to_string();
@@ -407,7 +712,46 @@ impl WasmEngine {
         contract_addr: &str,
         contract_state: &BTreeMap<String, String>,
         balance: u128,
+        attached_value: u128,
         timestamp: u64,
+    ) -> Result<host::HostExecResult, String> {
+        self.execute_wasm_hosted_with(
+            bytecode,
+            function,
+            args,
+            gas_limit,
+            caller,
+            contract_addr,
+            contract_state,
+            balance,
+            attached_value,
+            timestamp,
+            host::CallContext::default(),
+            false,
+            exec_limit::Admission::Consensus,
+        )
+    }
+
+    /// `execute_wasm_hosted` inside the ledger block `context`, optionally
+    /// recording an execution trace (see trace). Tracing does not change
+    /// gas. `admission` decides how long the call waits for an execution
+    /// slot (see exec_limit).
+    #[allow(clippy::too_many_arguments)]
+    fn execute_wasm_hosted_with(
+        &self,
+        bytecode: &[u8],
+        function: &str,
+        args: &[String],
+        gas_limit: u64,
+        caller: &str,
+        contract_addr: &str,
+        contract_state: &BTreeMap<String, String>,
+        balance: u128,
+        attached_value: u128,
+        timestamp: u64,
+        context: host::CallContext,
+        trace: bool,
+        admission: exec_limit::Admission,
     ) -> Result<host::HostExecResult, String> {
         use host::{HostData, HostExecResult, HostState};
         use std::collections::HashSet;
@@ -420,6 +764,12 @@ impl WasmEngine {
                 leaked, MAX_LEAKED_THREADS
             ));
         }
+        if gas_limit > MAX_GAS_PER_CALL {
+            return Err(format!(
+                "Gas limit {} exceeds per-call cap {}",
+                gas_limit, MAX_GAS_PER_CALL
+            ));
+        }
         if bytecode.len() > MAX_BYTECODE_SIZE {
             return Err(format!(
                 "WASM bytecode too large: {} bytes (max {} bytes)",
@@ -434,8 +784,13 @@ impl WasmEngine {
                 compile_gas, gas_limit
             ));
         }
+        // Deployed by a newer node? Name the missing host functions.
+        abi::check_compat(bytecode)?;
         let remaining_gas = gas_limit - compile_gas;
 
+        // Held until the result (or timeout) is in, bounding concurrent compiles
+        let _permit = self.exec_limit.acquire(admission)?;
+
         // Convert contract state (String→String) to byte state (String→Vec<u8>)
         let state_bytes: BTreeMap<String, Vec<u8>> = contract_state
             .iter()
@@ -452,12 +807,17 @@ impl WasmEngine {
             caller: caller.to_string(),
             self_address: contract_addr.to_string(),
             balance,
+            attached_value,
             timestamp,
             args: args.to_vec(),
             return_data: Vec::new(),
             logs: Vec::new(),
             aborted: false,
             abort_message: String::new(),
+            random_counter: 0,
+            contracts: Some(Arc::clone(&self.contracts)),
+            context,
+            env_reads: false,
         }));
         let host_data_thread = Arc::clone(&host_data);
 
@@ -467,19 +827,29 @@ impl WasmEngine {
         let abort_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         let abort_clone = Arc::clone(&abort_flag);
 
-        let (result_tx, result_rx) = std::sync::mpsc::channel::<Result<(i32, u64, bool), String>>();
+        let host_calls = Arc::new(Mutex::new(trace::HostCallLog::default()));
+        let host_calls_thread = Arc::clone(&host_calls);
+
+        type ExecOutcome = (i32, u64, bool, Option<Vec<trace::FunctionProfile>>);
+        let (result_tx, result_rx) = std::sync::mpsc::channel::<Result<ExecOutcome, String>>();
 
         let _handle = std::thread::spawn(move || {
             if abort_clone.load(std::sync::atomic::Ordering::Relaxed) {
                 return;
             }
 
-            // Deterministic gas metering: 1 WASM instruction = 1 gas unit
+            // Deterministic gas metering: 1 WASM instruction = 1 gas unit,
+            // plus GasSchedule costs deducted by each host call
             let cost_fn = |_operator: &wasmer::wasmparser::Operator| -> u64 { 1 };
             let metering = Arc::new(wasmer_middlewares::Metering::new(remaining_gas, cost_fn));
 
             let mut compiler = Cranelift::default();
             compiler.push_middleware(metering);
+            // After metering, so the profiler's counters are not metered
+            let profiler = trace.then(|| Arc::new(trace::InstructionProfiler::default()));
+            if let Some(p) = &profiler {
+                compiler.push_middleware(p.clone());
+            }
             let mut store = Store::new(compiler);
 
             let module = match Module::new(&store, &bytecode_owned) {
@@ -497,19 +867,39 @@ impl WasmEngine {
             // Create FunctionEnv with host state (memory set after instantiation)
             let host_state = HostState {
                 memory: None,
+                metering: None,
+                gas: host::GasSchedule::DEFAULT,
                 inner: host_data_thread,
             };
             let env = FunctionEnv::new(&mut store, host_state);
 
-            // Create imports with all 16 host functions
-            let import_object = host::create_host_imports(&mut store, &env);
+            // Create imports with all host functions
+            let mut import_object = host::create_host_imports(&mut store, &env);
+            if trace {
+                import_object = trace::traced_imports(
+                    &mut store,
+                    &env,
+                    &import_object,
+                    host_calls_thread,
+                    compile_gas,
+                    remaining_gas,
+                );
+            }
 
             let instance = match Instance::new(&mut store, &module, &import_object) {
                 Ok(i) => i,
+                // Imports were checked by abi::check_compat, so this is a
+                // signature mismatch or a start-function trap. Mainnet never
+                // retries: running without host functions is not an option.
+                Err(first_err) if cfg!(feature = "mainnet") => {
+                    let _ =
+                        result_tx.send(Err(format!("Failed to instantiate WASM: {}", first_err)));
+                    return;
+                }
                 Err(first_err) => {
-                    // Module may not import "env" at all — retry with empty imports.
+                    // Testnet: retry with empty imports for pure-compute modules.
                     // WARNING: This means NO host functions (transfer, log, storage, etc.)
-                    // are available. Only pure-compute WASM modules should reach this path.
+                    // are available.
                     eprintln!(
                         "⚠️ VM: WASM module instantiation with host imports failed ({}). \
                          Retrying with empty imports (no host functions available).",
@@ -517,9 +907,9 @@ impl WasmEngine {
                     );
                     match Instance::new(&mut store, &module, &imports! {}) {
                         Ok(i) => i,
-                        Err(e) => {
-                            let _ =
-                                result_tx.send(Err(format!("Failed to instantiate WASM: {}", e)));
+                        Err(_) => {
+                            let _ = result_tx
+                                .send(Err(format!("Failed to instantiate WASM: {}", first_err)));
                             return;
                         }
                     }
@@ -530,6 +920,8 @@ impl WasmEngine {
             if let Ok(memory) = instance.exports.get_memory("memory") {
                 env.as_mut(&mut store).memory = Some(memory.clone());
             }
+            // Host calls charge gas against this instance's metering counter
+            env.as_mut(&mut store).metering = host::MeteringGlobals::from_instance(&instance);
 
             let func = match instance.exports.get_function(&function_owned) {
                 Ok(f) => f,
@@ -597,7 +989,8 @@ impl WasmEngine {
                             }
                         })
                         .unwrap_or(0);
-                    let _ = result_tx.send(Ok((return_code, exec_gas, is_sdk_mode)));
+                    let profile = profiler.map(|p| p.read(&mut store, &instance));
+                    let _ = result_tx.send(Ok((return_code, exec_gas, is_sdk_mode, profile)));
                 }
                 Err(e) => {
                     let err_str = format!("{}", e);
@@ -615,7 +1008,7 @@ impl WasmEngine {
         // Wait with timeout (safety net)
         let timeout = std::time::Duration::from_secs(MAX_EXECUTION_SECS);
         match result_rx.recv_timeout(timeout) {
-            Ok(Ok((return_code, exec_gas, is_sdk_mode))) => {
+            Ok(Ok((return_code, exec_gas, is_sdk_mode, profile))) => {
                 let total_gas = compile_gas + exec_gas;
                 if total_gas > gas_limit {
                     return Err(format!(
@@ -651,6 +1044,13 @@ impl WasmEngine {
                     aborted: false,
                     abort_message: String::new(),
                     sdk_mode: is_sdk_mode,
+                    trace: profile.map(|functions| {
+                        let log = std::mem::take(
+                            &mut *host_calls.lock().unwrap_or_else(|e| e.into_inner()),
+                        );
+                        trace::ExecutionTrace::assemble(compile_gas, log, functions)
+                    }),
+                    env_reads: data.env_reads,
                 })
             }
             Ok(Err(e)) => {
@@ -694,7 +1094,11 @@ impl WasmEngine {
     /// Without this, two concurrent calls to the same contract would snapshot the
     /// same state, execute independently, and the second write would silently
     /// overwrite the first's state changes.
-    fn try_hosted_call(&self, call: &ContractCall) -> Result<Option<ContractResult>, String> {
+    fn try_hosted_call(
+        &self,
+        journal: Option<&str>,
+        call: &ContractCall,
+    ) -> Result<Option<ContractResult>, String> {
         // C-07: Acquire per-contract execution lock (serializes concurrent calls)
         let contract_lock = self.get_contract_lock(&call.contract);
         let _guard = contract_lock
@@ -731,7 +1135,12 @@ impl WasmEngine {
                 .as_secs()
         };
 
-        match self.execute_wasm_hosted(
+        // Staged calls journal under their block hash
+        let context = host::CallContext {
+            block_hash: journal.unwrap_or_default().to_string(),
+            call_index: 0,
+        };
+        match self.execute_wasm_hosted_with(
             &contract_snapshot.bytecode,
             &call.function,
             &call.args,
@@ -740,51 +1149,56 @@ impl WasmEngine {
             &call.contract,
             &contract_snapshot.state,
             contract_snapshot.balance,
+            call.amount,
             timestamp,
+            context,
+            false,
+            exec_limit::Admission::Consensus,
         ) {
             Ok(exec_result) => {
-                // Apply state changes + transfers back to contract (short lock)
-                if !exec_result.state_changes.is_empty() || !exec_result.transfers.is_empty() {
-                    let mut contracts = self
-                        .contracts
-                        .lock()
-                        .map_err(|_| "Failed to lock contracts for state update".to_string())?;
-                    if let Some(c) = contracts.get_mut(&call.contract) {
-                        for (key, val) in &exec_result.state_changes {
-                            c.state
-                                .insert(key.clone(), String::from_utf8_lossy(val).to_string());
-                        }
-                        for (_, amount) in &exec_result.transfers {
-                            c.balance = c.balance.saturating_sub(*amount);
-                        }
-                    }
-                }
+                // Settle transfers (running recipients' on_receive hooks), then
+                // apply state changes + balances of everything involved at once
+                let mut settlement = receive_hook::Settlement {
+                    gas_used: exec_result.gas_used,
+                    ..Default::default()
+                };
+                settlement
+                    .state
+                    .insert(call.contract.clone(), exec_result.state_changes.clone());
+                let queue = settlement.debit(&call.contract, &exec_result.transfers);
+                let settlement = self.settle_transfers(
+                    journal,
+                    queue.into(),
+                    std::iter::once(call.contract.clone()).collect(),
+                    settlement,
+                    call.gas_limit,
+                    timestamp,
+                )?;
 
                 let (success, output) = if exec_result.sdk_mode {
                     (
                         exec_result.return_code == 0,
-                        if exec_result.return_data.is_empty() {
-                            exec_result.return_code.to_string()
-                        } else {
-                            String::from_utf8_lossy(&exec_result.return_data).to_string()
-                        },
+                        sdk_call_output(exec_result.return_code, &exec_result.return_data),
                     )
                 } else {
                     // Legacy: return_code IS the result, always success
                     (true, exec_result.return_code.to_string())
                 };
 
+                let mut events = exec_result.events;
+                events.extend(settlement.events);
                 Ok(Some(ContractResult {
                     success,
                     output,
-                    gas_used: exec_result.gas_used,
+                    gas_used: settlement.gas_used,
                     state_changes: exec_result
                         .state_changes
                         .iter()
                         .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).to_string()))
                         .collect(),
-                    events: exec_result.events,
-                    transfers: exec_result.transfers,
+                    events,
+                    transfers: settlement.external,
+                    contract_transfers: settlement.to_contracts,
                 }))
             }
             Err(e)
@@ -807,8 +1221,133 @@ impl WasmEngine {
     /// 2. **Legacy WASM** (i32 args, no host functions) — backward compatibility
     /// 3. **Mock dispatch** (testnet only) — disabled on mainnet
     pub fn call_contract(&self, call: ContractCall) -> Result<ContractResult, String> {
+        self.call_contract_in(None, call)
+    }
+
+    /// Execute a call against a snapshot of the contract without applying
+    /// anything: no state commit, no balance changes, no transfer settlement,
+    /// no call metrics. With `trace`, the result carries an execution trace
+    /// (host calls + instruction histogram, see trace). Hosted WASM only.
+    pub fn dry_run(&self, call: &ContractCall, trace: bool) -> Result<DryRunResult, String> {
+        if self.is_paused(&call.contract)? {
+            return Err(format!("Contract {} is paused", call.contract));
+        }
+        let contract = self.get_contract(&call.contract)?;
+        if !contract.bytecode.starts_with(b"\0asm") {
+            return Err("Dry runs need WASM bytecode".to_string());
+        }
+        let timestamp = if call.block_timestamp > 0 {
+            call.block_timestamp
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        };
+
+        let exec = self.execute_wasm_hosted_with(
+            &contract.bytecode,
+            &call.function,
+            &call.args,
+            call.gas_limit,
+            &call.caller,
+            &call.contract,
+            &contract.state,
+            contract.balance.saturating_add(call.amount),
+            call.amount,
+            timestamp,
+            host::CallContext::default(),
+            trace,
+            exec_limit::Admission::Interactive,
+        )?;
+        let (success, output) = if exec.sdk_mode {
+            (
+                exec.return_code == 0,
+                sdk_call_output(exec.return_code, &exec.return_data),
+            )
+        } else {
+            (true, exec.return_code.to_string())
+        };
+        Ok(DryRunResult {
+            result: ContractResult {
+                success,
+                output,
+                gas_used: exec.gas_used,
+                state_changes: exec
+                    .state_changes
+                    .iter()
+                    .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).to_string()))
+                    .collect(),
+                events: exec.events,
+                transfers: exec.transfers,
+                contract_transfers: Vec::new(),
+            },
+            logs: exec.logs,
+            trace: exec.trace,
+            env_reads: exec.env_reads,
+        })
+    }
+
+    /// `call_contract`, journaling changes under `journal` (a block hash) if set
+    fn call_contract_in(
+        &self,
+        journal: Option<&str>,
+        call: ContractCall,
+    ) -> Result<ContractResult, String> {
+        let started = Instant::now();
+        let contract = call.contract.clone();
+        let (caller, amount) = (call.caller.clone(), call.amount);
+        if amount > 0 {
+            self.send_to_contract_in(journal, &contract, amount)?;
+        }
+        let mut result = self.dispatch_call(journal, call);
+        if amount > 0 {
+            match &mut result {
+                // Nothing from the call applies, neither does the value
+                Err(_) => {
+                    self.withdraw_from_contract_in(journal, &contract, amount)?;
+                }
+                Ok(r) if !r.success => {
+                    let refund = self.withdraw_from_contract_in(journal, &contract, amount)?;
+                    if refund > 0 && !caller.is_empty() {
+                        r.transfers.push((caller, refund));
+                    }
+                }
+                Ok(_) => {}
+            }
+        }
+        match &result {
+            Ok(r) => {
+                self.record_call(&contract, r.gas_used, r.success, started.elapsed());
+                if r.success {
+                    dex_registry::record_swap_events(self, &contract, &r.events);
+                }
+            }
+            Err(_) => self.record_call(&contract, 0, false, started.elapsed()),
+        }
+        result
+    }
+
+    /// Route a call through hosted → legacy → mock execution (see `call_contract`).
+    fn dispatch_call(
+        &self,
+        journal: Option<&str>,
+        call: ContractCall,
+    ) -> Result<ContractResult, String> {
+        // ── Phase 0: Emergency pause (reserved functions, paused contracts) ──
+        if let Some(paused) = pause::requested_pause_state(&call.function) {
+            return self.set_paused_by_call(journal, &call, paused);
+        }
+        // Ownership changes work on paused contracts (hand over to a new owner)
+        if let Some(action) = ownership::OwnershipAction::parse(&call.function) {
+            return self.change_ownership_by_call(journal, &call, action);
+        }
+        if self.is_paused(&call.contract)? {
+            return Err(format!("Contract {} is paused", call.contract));
+        }
+
         // ── Phase 1: Try hosted WASM execution (SDK + legacy auto-detect) ──
-        if let Some(result) = self.try_hosted_call(&call)? {
+        if let Some(result) = self.try_hosted_call(journal, &call)? {
             return Ok(result);
         }
 
@@ -844,6 +1383,7 @@ impl WasmEngine {
                                 state_changes: BTreeMap::new(),
                                 events: Vec::new(),
                                 transfers: Vec::new(),
+                                contract_transfers: Vec::new(),
                             });
                         }
                         Err(e)
@@ -872,6 +1412,12 @@ impl WasmEngine {
         // Fallback to mock dispatch for testing/simple contracts (testnet only)
         #[cfg(not(feature = "mainnet"))]
         {
+            if !test_stubs_enabled() {
+                return Err(format!(
+                    "Contract function '{}' not found in WASM module. Mock dispatch disabled on this network.",
+                    call.function
+                ));
+            }
             let mut contracts = self
                 .contracts
                 .lock()
@@ -879,6 +1425,7 @@ impl WasmEngine {
             let contract = contracts
                 .get_mut(&call.contract)
                 .ok_or("Contract not found".to_string())?;
+            let balance_before = contract.balance;
 
             let (output, gas_used, state_changes) = match call.function.as_str() {
                 "transfer" => {
@@ -956,6 +1503,8 @@ impl WasmEngine {
             }
 
             // Apply state changes
+            let balance_delta = contract.balance as i128 - balance_before as i128;
+            self.journal_record(journal, contract, state_changes.keys(), balance_delta);
             for (k, v) in state_changes.iter() {
                 contract.state.insert(k.clone(), v.clone());
             }
@@ -967,12 +1516,22 @@ impl WasmEngine {
                 state_changes,
                 events: Vec::new(),
                 transfers: Vec::new(),
+                contract_transfers: Vec::new(),
             })
         } // end #[cfg(not(feature = "mainnet"))]
     }
 
     /// Send native cil to contract
     pub fn send_to_contract(&self, contract_addr: &str, amount: u128) -> Result<(), String> {
+        self.send_to_contract_in(None, contract_addr, amount)
+    }
+
+    fn send_to_contract_in(
+        &self,
+        journal: Option<&str>,
+        contract_addr: &str,
+        amount: u128,
+    ) -> Result<(), String> {
         let mut contracts = self
             .contracts
             .lock()
@@ -982,10 +1541,34 @@ impl WasmEngine {
             .get_mut(contract_addr)
             .ok_or("Contract not found")?;
 
+        self.journal_record(journal, contract, [], amount as i128);
         contract.balance = contract.balance.saturating_add(amount);
         Ok(())
     }
 
+    /// Take back up to `amount` of a call's attached value (the contract may
+    /// have paid part of it out already). Returns the amount taken.
+    fn withdraw_from_contract_in(
+        &self,
+        journal: Option<&str>,
+        contract_addr: &str,
+        amount: u128,
+    ) -> Result<u128, String> {
+        let mut contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+
+        let contract = contracts
+            .get_mut(contract_addr)
+            .ok_or("Contract not found")?;
+
+        let taken = amount.min(contract.balance);
+        self.journal_record(journal, contract, [], -(taken as i128));
+        contract.balance -= taken;
+        Ok(taken)
+    }
+
     /// Check if contract exists
     pub fn contract_exists(&self, address: &str) -> Result<bool, String> {
         let contracts = self
@@ -1016,6 +1599,18 @@ impl WasmEngine {
         Ok(contracts.len())
     }
 
+    /// Sum of all contract balances (CIL held by contracts)
+    pub fn total_contract_balance(&self) -> Result<u128, String> {
+        let contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+
+        Ok(contracts
+            .values()
+            .fold(0u128, |acc, c| acc.saturating_add(c.balance)))
+    }
+
     /// Get contract state
     pub fn get_contract_state(&self, address: &str) -> Result<BTreeMap<String, String>, String> {
         let contracts = self
@@ -1027,6 +1622,34 @@ impl WasmEngine {
 
         Ok(contract.state.clone())
     }
+
+    /// Fingerprint of everything a call to `address` can observe about the
+    /// contract itself (code, state, balance, pause flag). Changes whenever
+    /// any of them does; used to key cached read-call results.
+    pub fn contract_state_version(&self, address: &str) -> Result<String, String> {
+        let contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+        let c = contracts.get(address).ok_or("Contract not found")?;
+        let bytes = serde_json::to_vec(&(&c.code_hash, &c.state, c.balance, c.paused))
+            .map_err(|e| format!("Failed to encode contract state: {}", e))?;
+        Ok(blake3::hash(&bytes).to_hex().to_string())
+    }
+}
+
+/// `ContractResult.output` for an SDK-mode call.
+/// Return data wins when the contract set any (success payloads and the
+/// JSON error bodies existing contracts write); otherwise a failing code is
+/// mapped to its `los_sdk::ContractError` message.
+fn sdk_call_output(return_code: i32, return_data: &[u8]) -> String {
+    if !return_data.is_empty() {
+        String::from_utf8_lossy(return_data).to_string()
+    } else if return_code == 0 {
+        "0".to_string()
+    } else {
+        los_sdk::describe_code(return_code)
+    }
 }
 
 impl Default for WasmEngine {
@@ -1058,6 +1681,13 @@ impl WasmEngine {
         serde_json::to_vec(&data).map_err(|e| format!("Failed to serialize VM state: {}", e))
     }
 
+    /// Hash of the full contract state (blake3 over `serialize_all()`).
+    /// Contracts and nonces live in BTreeMaps, so equal state hashes equally
+    /// on every node. Committed to by finality checkpoints.
+    pub fn state_root(&self) -> Result<String, String> {
+        Ok(blake3::hash(&self.serialize_all()?).to_hex().to_string())
+    }
+
     /// Deserialize and restore all contracts + nonce state from persistence.
     pub fn deserialize_all(&self, data: &[u8]) -> Result<usize, String> {
         #[derive(Deserialize)]
@@ -1095,6 +1725,22 @@ impl WasmEngine {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sdk_call_output_maps_error_codes() {
+        assert_eq!(sdk_call_output(0, b""), "0");
+        assert_eq!(sdk_call_output(0, b"42"), "42");
+        assert_eq!(sdk_call_output(4, b""), "Caller is not authorized (code 4)");
+        assert_eq!(
+            sdk_call_output(103, b""),
+            "Contract error 103 (user code 3)"
+        );
+        // Contract-provided error detail is kept as-is
+        assert_eq!(
+            sdk_call_output(1, br#"{"success":false}"#),
+            r#"{"success":false}"#
+        );
+    }
+
     #[test]
     fn test_wasm_engine_creation() {
         let engine = WasmEngine::new();
@@ -1164,6 +1810,7 @@ mod tests {
             gas_limit: 1000,
             caller: "charlie".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call).unwrap();
@@ -1189,6 +1836,7 @@ mod tests {
             gas_limit: 1000,
             caller: "dave".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(set_call).unwrap();
@@ -1202,6 +1850,7 @@ mod tests {
             gas_limit: 1000,
             caller: "dave".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(get_call).unwrap();
@@ -1227,6 +1876,7 @@ mod tests {
             gas_limit: 100,
             caller: "eve".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call).unwrap();
@@ -1243,6 +1893,7 @@ mod tests {
             gas_limit: 1000,
             caller: "nobody".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call);
@@ -1265,6 +1916,85 @@ mod tests {
         assert_eq!(contract.balance, 2500);
     }
 
+    #[test]
+    fn test_attached_value_credited_and_refunded() {
+        let engine = WasmEngine::new();
+        // deposit: status 0 for 1..=500 CIL, 1 otherwise; trap: traps
+        let wat = br#"(module
+            (import "env" "host_get_attached_value" (func $value (param i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "deposit") (result i32)
+                (drop (call $value (i32.const 0)))
+                (if (result i32)
+                    (i32.and
+                        (i64.gt_u (i64.load (i32.const 0)) (i64.const 0))
+                        (i64.le_u (i64.load (i32.const 0)) (i64.const 500)))
+                    (then (i32.const 0))
+                    (else (i32.const 1))))
+            (func (export "trap") (result i32) unreachable))"#;
+        let code = wasmer::wat2wasm(wat).unwrap().to_vec();
+        let addr = engine
+            .deploy_contract("LOSowner".to_string(), code, BTreeMap::new(), 1)
+            .unwrap();
+        let call = |function: &str, amount: u128| ContractCall {
+            contract: addr.clone(),
+            function: function.to_string(),
+            args: vec![],
+            gas_limit: 100_000,
+            caller: "LOSalice".to_string(),
+            block_timestamp: 1,
+            amount,
+        };
+
+        let ok = engine.call_contract(call("deposit", 300)).unwrap();
+        assert!(ok.success && ok.transfers.is_empty());
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 300);
+
+        // Non-zero status: the value goes back to the caller as a payout
+        let rejected = engine.call_contract(call("deposit", 900)).unwrap();
+        assert!(!rejected.success);
+        assert_eq!(rejected.transfers, vec![("LOSalice".to_string(), 900)]);
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 300);
+
+        // Failed call: nothing applies
+        assert!(engine.call_contract(call("trap", 50)).is_err());
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 300);
+
+        // Staged: rollback undoes the credit
+        engine
+            .call_contract_staged("b1", call("deposit", 100))
+            .unwrap();
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 400);
+        engine.rollback("b1").unwrap();
+        assert_eq!(engine.get_contract(&addr).unwrap().balance, 300);
+    }
+
+    #[test]
+    fn test_state_root_tracks_contract_state() {
+        let engine = WasmEngine::new();
+        let empty = engine.state_root().unwrap();
+        let addr = engine
+            .deploy_contract(
+                "heidi".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        let deployed = engine.state_root().unwrap();
+        assert_ne!(empty, deployed);
+
+        // Same state restored elsewhere → same root
+        let replica = WasmEngine::new();
+        replica
+            .deserialize_all(&engine.serialize_all().unwrap())
+            .unwrap();
+        assert_eq!(replica.state_root().unwrap(), deployed);
+
+        engine.send_to_contract(&addr, 1).unwrap();
+        assert_ne!(engine.state_root().unwrap(), deployed);
+    }
+
     #[test]
     fn test_multiple_deployments_increment_nonce() {
         let engine = WasmEngine::new();
@@ -1282,6 +2012,53 @@ mod tests {
         assert_eq!(engine.contract_count().unwrap(), 2);
     }
 
+    #[test]
+    fn test_salted_deploy_address_is_predictable_and_unique() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = b"\0asm\x01\x00\x00\x00".to_vec();
+        let code_hash = WasmEngine::compute_code_hash(&wasm_bytes);
+        let salt = [9u8; 32];
+        let predicted = WasmEngine::salted_address("LOSowner", &salt, &code_hash);
+
+        // Independent of nonce and block
+        let addr = engine
+            .deploy_contract_salted(
+                "LOSowner".to_string(),
+                wasm_bytes.clone(),
+                BTreeMap::new(),
+                7,
+                &salt,
+            )
+            .unwrap();
+        assert_eq!(addr, predicted);
+
+        let again = engine.deploy_contract_salted(
+            "LOSowner".to_string(),
+            wasm_bytes.clone(),
+            BTreeMap::new(),
+            8,
+            &salt,
+        );
+        assert!(again.unwrap_err().contains("already taken"));
+
+        // Another owner or salt gets another address
+        assert_ne!(
+            WasmEngine::salted_address("LOSother", &salt, &code_hash),
+            predicted
+        );
+        let other = engine
+            .deploy_contract_salted(
+                "LOSowner".to_string(),
+                wasm_bytes,
+                BTreeMap::new(),
+                8,
+                &[1u8; 32],
+            )
+            .unwrap();
+        assert_ne!(other, predicted);
+        assert_eq!(engine.contract_count().unwrap(), 2);
+    }
+
     #[test]
     fn test_contract_list() {
         let engine = WasmEngine::new();
@@ -1316,6 +2093,7 @@ mod tests {
             gas_limit: 50, // Too low
             caller: "henry".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call);
@@ -1340,6 +2118,7 @@ mod tests {
             gas_limit: 1000,
             caller: "iris".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call);
@@ -1356,6 +2135,7 @@ mod tests {
             state_changes: BTreeMap::new(),
             events: Vec::new(),
             transfers: Vec::new(),
+            contract_transfers: Vec::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1383,6 +2163,7 @@ mod tests {
             gas_limit: 100,
             caller: "jack".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         engine.call_contract(call).unwrap();
@@ -1432,10 +2213,432 @@ mod tests {
             gas_limit: 1000,
             caller: "wasm_tester".to_string(),
             block_timestamp: 0,
+            amount: 0,
         };
 
         let result = engine.call_contract(call).unwrap();
         assert!(result.success);
         assert_eq!(result.output, "12"); // 5 + 7 = 12
     }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_contract_stats_tracking() {
+        let engine = WasmEngine::new();
+        let wasm_bytes = b"\0asm\x01\x00\x00\x00".to_vec();
+        let busy = engine
+            .deploy_contract("kate".to_string(), wasm_bytes.clone(), BTreeMap::new(), 1)
+            .unwrap();
+        let idle = engine
+            .deploy_contract("kate".to_string(), wasm_bytes, BTreeMap::new(), 2)
+            .unwrap();
+        engine.send_to_contract(&busy, 1000).unwrap();
+
+        let call = |contract: &str, function: &str| ContractCall {
+            contract: contract.to_string(),
+            function: function.to_string(),
+            args: vec!["10".to_string(), "recipient".to_string()],
+            gas_limit: 1000,
+            caller: "kate".to_string(),
+            block_timestamp: 0,
+            amount: 0,
+        };
+        engine.call_contract(call(&busy, "transfer")).unwrap();
+        engine.call_contract(call(&busy, "transfer")).unwrap();
+        assert!(engine.call_contract(call(&busy, "unknown_func")).is_err());
+        assert!(engine.call_contract(call(&idle, "unknown_func")).is_err());
+        // Unknown contracts are never tracked
+        assert!(engine
+            .call_contract(call("LOSConMissing", "transfer"))
+            .is_err());
+
+        let stats = engine.contract_stats(&busy).unwrap();
+        assert_eq!(stats.calls, 3);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.total_gas, 150);
+        assert!(engine.contract_stats("LOSConMissing").is_none());
+
+        let top = engine.top_contracts(StatsSort::Gas, 10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].0, busy);
+        let top = engine.top_contracts(StatsSort::Failures, 1);
+        assert_eq!(top.len(), 1);
+    }
+
+    #[test]
+    fn test_host_calls_are_charged_gas() {
+        let engine = WasmEngine::new();
+        let wat = br#"(module
+            (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "keyvalue")
+            (func (export "write") (result i32)
+                (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 5))
+                (i32.const 0))
+            (func (export "noop") (result i32) (i32.const 0)))"#;
+        let run = |function: &str, gas_limit: u64| {
+            engine.execute_wasm_hosted(
+                wat,
+                function,
+                &[],
+                gas_limit,
+                "LOSalice",
+                "LOSConGas",
+                &BTreeMap::new(),
+                0,
+                0,
+                0,
+            )
+        };
+
+        let noop = run("noop", 10_000).unwrap();
+        let write = run("write", 10_000).unwrap();
+        assert_eq!(write.state_changes.get("key").unwrap(), b"value");
+        let host_cost = host::GasSchedule::DEFAULT.state_write(3, 5);
+        let extra = write.gas_used - noop.gas_used;
+        // Host cost plus the handful of instructions that set up the call
+        assert!(
+            extra >= host_cost && extra < host_cost + 10,
+            "extra={}",
+            extra
+        );
+
+        // Enough gas for the instructions but not the host call: nothing is written
+        match run("write", noop.gas_used + 20) {
+            Err(e) => assert!(e.contains("Out of gas"), "{}", e),
+            Ok(_) => panic!("host call ran without enough gas"),
+        }
+    }
+
+    #[test]
+    fn test_contract_exists_and_code_hash_host_calls() {
+        let engine = WasmEngine::new();
+        let target_code = b"\0asm\x01\x00\x00\x00".to_vec();
+        let target = engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                target_code.clone(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        // Returns host_contract_exists(arg0); return data = code hash of arg0
+        let wat = br#"(module
+            (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+            (import "env" "host_contract_exists" (func $exists (param i32 i32) (result i32)))
+            (import "env" "host_get_code_hash" (func $hash (param i32 i32 i32 i32) (result i32)))
+            (import "env" "host_set_return" (func $ret (param i32 i32)))
+            (memory (export "memory") 1)
+            (func (export "probe") (result i32) (local $n i32) (local $h i32)
+                (local.set $n (call $arg (i32.const 0) (i32.const 0) (i32.const 256)))
+                (local.set $h (call $hash (i32.const 0) (local.get $n) (i32.const 512) (i32.const 128)))
+                (if (i32.ge_s (local.get $h) (i32.const 0))
+                    (then (call $ret (i32.const 512) (local.get $h))))
+                (call $exists (i32.const 0) (local.get $n))))"#;
+        let probe = |addr: &str| {
+            engine
+                .execute_wasm_hosted(
+                    wat,
+                    "probe",
+                    &[addr.to_string()],
+                    100_000,
+                    "LOSalice",
+                    "LOSConRouter",
+                    &BTreeMap::new(),
+                    0,
+                    0,
+                    0,
+                )
+                .map_err(|e| e.to_string())
+                .map(|r| (r.return_code, r.return_data))
+        };
+
+        let (exists, hash) = probe(&target).unwrap();
+        assert_eq!(exists, 1);
+        assert_eq!(
+            String::from_utf8(hash).unwrap(),
+            WasmEngine::compute_code_hash(&target_code)
+        );
+        assert_eq!(probe("LOSConMissing").unwrap(), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_block_hash_and_call_id_host_calls() {
+        let engine = WasmEngine::new();
+        let wat = br#"(module
+            (import "env" "host_get_block_hash" (func $block (param i32 i32) (result i32)))
+            (import "env" "host_get_call_id" (func $id (param i32) (result i32)))
+            (import "env" "host_set_return" (func $ret (param i32 i32)))
+            (memory (export "memory") 1)
+            (func (export "block") (result i32)
+                (call $ret (i32.const 0) (call $block (i32.const 0) (i32.const 128)))
+                (i32.const 0))
+            (func (export "id") (result i32)
+                (drop (call $id (i32.const 0)))
+                (call $ret (i32.const 0) (i32.const 32))
+                (i32.const 0)))"#;
+        let block_hash = "ab".repeat(32);
+        let run = |function: &str, context: host::CallContext| {
+            engine
+                .execute_wasm_hosted_with(
+                    wat,
+                    function,
+                    &[],
+                    100_000,
+                    "LOSalice",
+                    "LOSConBridge",
+                    &BTreeMap::new(),
+                    0,
+                    0,
+                    0,
+                    context,
+                    false,
+                    exec_limit::Admission::Consensus,
+                )
+                .unwrap()
+        };
+        let hook = host::CallContext {
+            block_hash: block_hash.clone(),
+            call_index: 2,
+        };
+        let id = run("id", hook.clone());
+        assert_eq!(id.return_data, host::derive_call_id(&block_hash, 2));
+        assert!(id.env_reads);
+        assert_eq!(run("block", hook).return_data, block_hash.as_bytes());
+        assert!(run("block", host::CallContext::default())
+            .return_data
+            .is_empty());
+
+        // A staged call sees its own block
+        let code = wasmer::wat2wasm(wat).unwrap().to_vec();
+        let addr = engine
+            .deploy_contract("LOSowner".to_string(), code, BTreeMap::new(), 1)
+            .unwrap();
+        let call = ContractCall {
+            contract: addr,
+            function: "block".to_string(),
+            args: vec![],
+            gas_limit: 100_000,
+            caller: "LOSalice".to_string(),
+            block_timestamp: 1,
+            amount: 0,
+        };
+        let staged = engine
+            .call_contract_staged(&block_hash, call.clone())
+            .unwrap();
+        assert_eq!(staged.output, block_hash);
+        assert_eq!(engine.call_contract(call).unwrap().output, "0");
+    }
+
+    #[test]
+    fn test_emit_event_indexed() {
+        let engine = WasmEngine::new();
+        let wat = br#"(module
+            (import "env" "host_emit_event_indexed"
+                (func $emit (param i32 i32 i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "TransferLOSa\00LOSb{\"amount\":\"5\"}a\00b\00c\00d\00e")
+            (func (export "ok") (result i32)
+                (call $emit (i32.const 0) (i32.const 8) (i32.const 8) (i32.const 9)
+                    (i32.const 17) (i32.const 14)))
+            (func (export "too_many") (result i32)
+                (call $emit (i32.const 0) (i32.const 8) (i32.const 31) (i32.const 9)
+                    (i32.const 17) (i32.const 14))))"#;
+        let run = |function: &str| {
+            engine
+                .execute_wasm_hosted(
+                    wat,
+                    function,
+                    &[],
+                    100_000,
+                    "LOSalice",
+                    "LOSConToken",
+                    &BTreeMap::new(),
+                    0,
+                    0,
+                    9,
+                )
+                .map_err(|e| e.to_string())
+                .unwrap()
+        };
+
+        let ok = run("ok");
+        assert_eq!(ok.return_code, 0);
+        assert_eq!(ok.events.len(), 1);
+        let event = &ok.events[0];
+        assert_eq!(event.event_type, "Transfer");
+        assert_eq!(event.topics, vec!["LOSa", "LOSb"]);
+        assert_eq!(event.data.get("amount").map(String::as_str), Some("5"));
+
+        let rejected = run("too_many");
+        assert_eq!(rejected.return_code, 1);
+        assert!(rejected.events.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_trace_accounts_for_all_gas() {
+        let engine = WasmEngine::new();
+        let wat = br#"(module
+            (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+            (import "env" "host_get_state" (func $get (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "countervalue")
+            (func $spin (param $n i32)
+                (block $done (loop $next
+                    (br_if $done (i32.eqz (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $next))))
+            (func (export "run") (result i32)
+                (call $spin (i32.const 50))
+                (call $spin (i32.const 10))
+                (drop (call $get (i32.const 0) (i32.const 7) (i32.const 64) (i32.const 16)))
+                (call $set (i32.const 0) (i32.const 7) (i32.const 7) (i32.const 5))
+                (i32.const 0)))"#;
+        let code = wasmer::wat2wasm(wat).unwrap().to_vec();
+        let addr = engine
+            .deploy_contract("LOSowner".to_string(), code, BTreeMap::new(), 1)
+            .unwrap();
+        let call = ContractCall {
+            contract: addr.clone(),
+            function: "run".to_string(),
+            args: vec![],
+            gas_limit: 100_000,
+            caller: "LOSalice".to_string(),
+            block_timestamp: 9,
+            amount: 0,
+        };
+
+        let plain = engine.dry_run(&call, false).unwrap();
+        assert!(plain.trace.is_none());
+        let traced = engine.dry_run(&call, true).unwrap();
+        // Tracing does not change gas or results
+        assert_eq!(traced.result.gas_used, plain.result.gas_used);
+        assert_eq!(traced.result.state_changes, plain.result.state_changes);
+        assert_eq!(traced.result.state_changes.get("counter").unwrap(), "value");
+
+        let trace = traced.trace.unwrap();
+        assert_eq!(
+            trace.compile_gas + trace.instruction_gas + trace.host_gas,
+            traced.result.gas_used
+        );
+        let calls: Vec<_> = trace
+            .host_calls
+            .iter()
+            .map(|c| c.function.as_str())
+            .collect();
+        assert_eq!(calls, vec!["host_get_state", "host_set_state"]);
+        assert_eq!(trace.host_calls[0].args, r#"key="counter""#);
+        assert_eq!(trace.host_calls[0].result, Some(-1));
+        assert_eq!(trace.host_calls[1].args, r#"key="counter", value_len=5"#);
+        assert_eq!(
+            trace.host_calls[1].gas_after - trace.host_calls[1].gas_before,
+            host::GasSchedule::DEFAULT.state_write(7, 5)
+        );
+        // The loop helper dominates; unexported, it is named from the name section
+        assert_eq!(trace.functions[0].function, "spin");
+        assert_eq!(trace.functions[0].calls, 2);
+        assert!(trace
+            .functions
+            .iter()
+            .any(|f| f.function == "run" && f.calls == 1));
+
+        // Nothing was applied
+        assert!(engine.get_contract(&addr).unwrap().state.is_empty());
+    }
+
+    #[test]
+    fn test_deploy_reads_contract_info() {
+        let engine = WasmEngine::new();
+        let module = |info_body: &str| {
+            let wat = format!(
+                r#"(module
+                    (import "env" "host_set_return" (func $ret (param i32 i32)))
+                    (import "env" "host_get_timestamp" (func $ts (result i64)))
+                    (memory (export "memory") 1)
+                    (data (i32.const 0) "{{\"name\":\"token\",\"version\":\"1.2.0\",\"sdk_abi\":2,\"build_hash\":\"ab12\"}}")
+                    (func (export "contract_info") (result i32) {} (i32.const 0)))"#,
+                info_body
+            );
+            wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec()
+        };
+        let deploy = |code: Vec<u8>| {
+            let addr = engine
+                .deploy_contract("LOSowner".to_string(), code, BTreeMap::new(), 1)
+                .unwrap();
+            engine.get_contract(&addr).unwrap().info
+        };
+
+        let info = deploy(module("(call $ret (i32.const 0) (i32.const 66))")).unwrap();
+        assert_eq!(info.name, "token");
+        assert_eq!(info.version, "1.2.0");
+        assert_eq!(info.sdk_abi, 2);
+        assert_eq!(info.build_hash, "ab12");
+
+        // Environment-dependent answers are not recorded
+        assert!(deploy(module(
+            "(drop (call $ts)) (call $ret (i32.const 0) (i32.const 66))"
+        ))
+        .is_none());
+        // Malformed (truncated) JSON is ignored, the deploy still succeeds
+        assert!(deploy(module("(call $ret (i32.const 0) (i32.const 20))")).is_none());
+        let plain = wasmer::wat2wasm(br#"(module (func (export "f") (result i32) (i32.const 0)))"#)
+            .unwrap()
+            .to_vec();
+        assert!(deploy(plain).is_none());
+    }
+
+    #[test]
+    fn test_dry_run_reports_env_reads_and_state_version() {
+        let engine = WasmEngine::new();
+        let wat = br#"(module
+            (import "env" "host_get_timestamp" (func $ts (result i64)))
+            (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "keyval")
+            (func (export "pure") (result i32) (i32.const 0))
+            (func (export "clock") (result i32) (drop (call $ts)) (i32.const 0))
+            (func (export "write") (result i32)
+                (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 3))
+                (i32.const 0)))"#;
+        let code = wasmer::wat2wasm(wat).unwrap().to_vec();
+        let addr = engine
+            .deploy_contract("LOSowner".to_string(), code, BTreeMap::new(), 1)
+            .unwrap();
+        let call = |function: &str| ContractCall {
+            contract: addr.clone(),
+            function: function.to_string(),
+            args: vec![],
+            gas_limit: 100_000,
+            caller: "LOSalice".to_string(),
+            block_timestamp: 9,
+            amount: 0,
+        };
+        assert!(!engine.dry_run(&call("pure"), false).unwrap().env_reads);
+        assert!(engine.dry_run(&call("clock"), false).unwrap().env_reads);
+
+        // Dry runs leave the version alone; applied state changes move it
+        let v0 = engine.contract_state_version(&addr).unwrap();
+        engine.dry_run(&call("write"), false).unwrap();
+        assert_eq!(engine.contract_state_version(&addr).unwrap(), v0);
+        engine.call_contract(call("write")).unwrap();
+        let v1 = engine.contract_state_version(&addr).unwrap();
+        assert_ne!(v1, v0);
+        engine.send_to_contract(&addr, 5).unwrap();
+        assert_ne!(engine.contract_state_version(&addr).unwrap(), v1);
+        assert!(engine.contract_state_version("LOSConMissing").is_err());
+    }
+
+    #[test]
+    fn test_stats_sort_parse_and_average() {
+        assert_eq!(StatsSort::parse("gas"), Some(StatsSort::Gas));
+        assert_eq!(StatsSort::parse("time"), Some(StatsSort::AvgTime));
+        assert_eq!(StatsSort::parse("bogus"), None);
+        let s = ContractStats {
+            calls: 4,
+            total_exec_micros: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(s.avg_exec_micros(), 250);
+        assert_eq!(ContractStats::default().avg_exec_micros(), 0);
+    }
 }
@@ -0,0 +1,205 @@
+//! # Deploy-time bytecode optimization
+//!
+//! Rust's default wasm32 output carries a lot the UVM never reads: DWARF
+//! (`.debug_*`), the `name` and `producers` sections, `target_features`, and
+//! exported linker globals such as `__data_end` / `__heap_base`. With the
+//! 1 MB bytecode cap and compile gas charged per KB, that weight matters.
+//!
+//! [`optimize`] is a pure, deterministic byte-level pass:
+//! - drops every custom section except `los_abi` (see [`crate::abi`])
+//! - drops global and table exports (contracts are only entered through
+//!   exported functions; the host only reads the `memory` export)
+//!
+//! Code, data and all other sections are copied unchanged, the output is
+//! validated, and running the pass twice gives the same bytes. Deploying with
+//! `optimize: true` hashes the optimized bytes, so the recorded `code_hash`
+//! is the hash of what is stored and replicated. Client-signed deploys must
+//! sign `DEPLOY:{hash}` of the optimized bytecode (run the same pass locally).
+
+use serde::Serialize;
+
+use crate::abi::ABI_SECTION;
+
+const SECTION_CUSTOM: u8 = 0;
+const SECTION_EXPORT: u8 = 7;
+const EXTERNAL_FUNC: u8 = 0x00;
+const EXTERNAL_MEMORY: u8 = 0x02;
+
+/// What the pass removed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OptimizeReport {
+    pub original_size: usize,
+    pub optimized_size: usize,
+    /// Names of removed custom sections
+    pub removed_sections: Vec<String>,
+    /// Names of removed exports
+    pub removed_exports: Vec<String>,
+}
+
+/// Strip custom sections and non-function exports from a binary module
+pub fn optimize(bytecode: &[u8]) -> Result<(Vec<u8>, OptimizeReport), String> {
+    if bytecode.len() < 8 || &bytecode[0..4] != b"\0asm" {
+        return Err("Invalid WASM bytecode (missing magic header)".to_string());
+    }
+    let mut report = OptimizeReport {
+        original_size: bytecode.len(),
+        ..Default::default()
+    };
+    let mut out = bytecode[0..8].to_vec();
+    let mut pos = 8;
+    while pos < bytecode.len() {
+        let id = bytecode[pos];
+        let (size, header) = read_leb_u32(bytecode, pos + 1)?;
+        let body_start = pos + 1 + header;
+        let body_end = body_start
+            .checked_add(size as usize)
+            .filter(|end| *end <= bytecode.len())
+            .ok_or("Truncated WASM section")?;
+        let body = &bytecode[body_start..body_end];
+        match id {
+            SECTION_CUSTOM => {
+                let (name, _) = read_name(body, 0)?;
+                if name == ABI_SECTION {
+                    out.extend_from_slice(&bytecode[pos..body_end]);
+                } else {
+                    report.removed_sections.push(name.to_string());
+                }
+            }
+            SECTION_EXPORT => {
+                let kept = filter_exports(body, &mut report.removed_exports)?;
+                out.push(SECTION_EXPORT);
+                write_leb_u32(&mut out, kept.len() as u32);
+                out.extend_from_slice(&kept);
+            }
+            _ => out.extend_from_slice(&bytecode[pos..body_end]),
+        }
+        pos = body_end;
+    }
+    wasmer::wasmparser::validate(&out).map_err(|e| format!("Optimized module invalid: {}", e))?;
+    report.optimized_size = out.len();
+    Ok((out, report))
+}
+
+/// Re-encode an export section body keeping function and memory exports
+fn filter_exports(body: &[u8], removed: &mut Vec<String>) -> Result<Vec<u8>, String> {
+    let (count, mut pos) = read_leb_u32(body, 0)?;
+    let mut kept_count = 0u32;
+    let mut kept = Vec::new();
+    for _ in 0..count {
+        let start = pos;
+        let (name, next) = read_name(body, pos)?;
+        let kind = *body.get(next).ok_or("Truncated export")?;
+        let (_, index_len) = read_leb_u32(body, next + 1)?;
+        pos = next + 1 + index_len;
+        if kind == EXTERNAL_FUNC || kind == EXTERNAL_MEMORY {
+            kept.extend_from_slice(&body[start..pos]);
+            kept_count += 1;
+        } else {
+            removed.push(name.to_string());
+        }
+    }
+    let mut section = Vec::with_capacity(kept.len() + 5);
+    write_leb_u32(&mut section, kept_count);
+    section.extend_from_slice(&kept);
+    Ok(section)
+}
+
+/// Length-prefixed UTF-8 name at `pos`; returns it and the offset after it
+fn read_name(bytes: &[u8], pos: usize) -> Result<(&str, usize), String> {
+    let (len, header) = read_leb_u32(bytes, pos)?;
+    let start = pos + header;
+    let end = start
+        .checked_add(len as usize)
+        .filter(|end| *end <= bytes.len())
+        .ok_or("Truncated WASM name")?;
+    let name = std::str::from_utf8(&bytes[start..end]).map_err(|_| "Invalid UTF-8 name")?;
+    Ok((name, end))
+}
+
+/// Unsigned LEB128 at `pos`; returns the value and its encoded length
+fn read_leb_u32(bytes: &[u8], pos: usize) -> Result<(u32, usize), String> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let byte = *bytes.get(pos + i).ok_or("Truncated LEB128")?;
+        value |= ((byte & 0x7f) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err("LEB128 too long".to_string())
+}
+
+fn write_leb_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_leb_u32(&mut body, name.len() as u32);
+        body.extend_from_slice(name.as_bytes());
+        body.extend_from_slice(payload);
+        let mut section = vec![SECTION_CUSTOM];
+        write_leb_u32(&mut section, body.len() as u32);
+        section.extend_from_slice(&body);
+        section
+    }
+
+    #[test]
+    fn test_optimize_strips_sections_and_linker_exports() {
+        let wat = r#"(module
+            (memory (export "memory") 1)
+            (global (export "__data_end") i32 (i32.const 1024))
+            (global (export "__heap_base") i32 (i32.const 2048))
+            (func (export "get") (result i32) i32.const 0))"#;
+        let mut code = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+        code.extend_from_slice(&custom_section("producers", &[0u8; 200]));
+        code.extend_from_slice(&custom_section(".debug_info", &[7u8; 4000]));
+        code.extend_from_slice(&custom_section(ABI_SECTION, &1u32.to_le_bytes()));
+
+        let (optimized, report) = optimize(&code).unwrap();
+        assert_eq!(report.removed_sections, vec!["producers", ".debug_info"]);
+        assert_eq!(report.removed_exports, vec!["__data_end", "__heap_base"]);
+        assert!(report.optimized_size + 4_200 < report.original_size);
+
+        // ABI section survives; the pass is idempotent
+        assert_eq!(
+            crate::abi::inspect(&optimized).unwrap().declared_version,
+            Some(1)
+        );
+        let (again, second) = optimize(&optimized).unwrap();
+        assert_eq!(again, optimized);
+        assert!(second.removed_sections.is_empty() && second.removed_exports.is_empty());
+
+        // Still runs
+        let engine = crate::WasmEngine::new();
+        let addr = engine
+            .deploy_contract("LOSowner".to_string(), optimized, Default::default(), 1)
+            .unwrap();
+        let result = engine
+            .call_contract(crate::ContractCall {
+                contract: addr,
+                function: "get".to_string(),
+                args: vec![],
+                gas_limit: 100_000,
+                caller: "LOSowner".to_string(),
+                block_timestamp: 1,
+                amount: 0,
+            })
+            .unwrap();
+        assert!(result.success, "{}", result.output);
+
+        assert!(optimize(b"\0asm\x01\0\0\0\x07\x05").is_err()); // truncated
+    }
+}
@@ -27,6 +27,88 @@ pub struct OracleConsensusPrice {
     pub confidence_bps: u16, // 0-10000 basis points (0.00%-100.00%)
 }
 
+/// One persisted consensus price reading (node-side history)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleObservation {
+    pub symbol: String,       // "LOS"
+    pub price_micro_usd: u64, // Consensus median at `timestamp`
+    pub confidence_bps: u16,  // Source agreement, 0-10000
+    pub source_count: u32,    // Exchanges that contributed
+    pub timestamp: u64,       // Unix seconds
+}
+
+impl OracleObservation {
+    pub fn from_consensus(symbol: &str, consensus: &OracleConsensusPrice, timestamp: u64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            price_micro_usd: consensus.median_price_micro_usd,
+            confidence_bps: consensus.confidence_bps,
+            source_count: consensus.sources.len() as u32,
+            timestamp,
+        }
+    }
+}
+
+/// Alert when the price moves more than `threshold_bps` within `window_secs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviationRule {
+    pub threshold_bps: u64,
+    pub window_secs: u64,
+}
+
+/// A triggered deviation alert (published to metrics and the alert webhook)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviationAlert {
+    pub symbol: String,
+    pub reference_price_micro_usd: u64,
+    pub reference_timestamp: u64,
+    pub price_micro_usd: u64,
+    pub timestamp: u64,
+    /// Signed move from reference to current, basis points
+    pub change_bps: i64,
+    pub threshold_bps: u64,
+    pub window_secs: u64,
+}
+
+/// Compare `latest` against every earlier observation of the same symbol
+/// inside the rule's window and report the largest move if it exceeds the
+/// threshold. Integer math only.
+pub fn check_deviation(
+    history: &[OracleObservation],
+    latest: &OracleObservation,
+    rule: &DeviationRule,
+) -> Option<DeviationAlert> {
+    let window_start = latest.timestamp.saturating_sub(rule.window_secs);
+    let (reference, change_bps) = history
+        .iter()
+        .filter(|o| {
+            o.symbol == latest.symbol
+                && o.price_micro_usd > 0
+                && o.timestamp >= window_start
+                && o.timestamp < latest.timestamp
+        })
+        .map(|o| {
+            let diff = latest.price_micro_usd as i128 - o.price_micro_usd as i128;
+            let bps = (diff * 10_000 / o.price_micro_usd as i128)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+            (o, bps)
+        })
+        .max_by_key(|(_, bps)| bps.unsigned_abs())?;
+    if change_bps.unsigned_abs() <= rule.threshold_bps {
+        return None;
+    }
+    Some(DeviationAlert {
+        symbol: latest.symbol.clone(),
+        reference_price_micro_usd: reference.price_micro_usd,
+        reference_timestamp: reference.timestamp,
+        price_micro_usd: latest.price_micro_usd,
+        timestamp: latest.timestamp,
+        change_bps,
+        threshold_bps: rule.threshold_bps,
+        window_secs: rule.window_secs,
+    })
+}
+
 /// Smart Contract Oracle Interface
 /// This is what payment smart contracts will call
 pub trait PriceOracle {
@@ -86,13 +168,20 @@ impl ExchangeOracle {
         return Err("VM oracle stubs disabled on mainnet. Use node-level oracle.".to_string());
 
         #[cfg(not(feature = "mainnet"))]
-        Ok(ExchangePrice {
-            exchange: "binance".to_string(),
-            pair: "LOS/USDT".to_string(),
-            price_micro_usd: 10_000, // 0.01 USD = 10,000 micro-USD
-            volume_24h_usd: 1_000_000,
-            timestamp: chrono::Utc::now().timestamp() as u64,
-        })
+        {
+            if !crate::test_stubs_enabled() {
+                return Err(
+                    "VM oracle stubs disabled on this network. Use node-level oracle.".to_string(),
+                );
+            }
+            Ok(ExchangePrice {
+                exchange: "binance".to_string(),
+                pair: "LOS/USDT".to_string(),
+                price_micro_usd: 10_000, // 0.01 USD = 10,000 micro-USD
+                volume_24h_usd: 1_000_000,
+                timestamp: chrono::Utc::now().timestamp() as u64,
+            })
+        }
     }
 
     async fn fetch_from_coinbase(&self) -> Result<ExchangePrice, String> {
@@ -100,13 +189,20 @@ impl ExchangeOracle {
         return Err("VM oracle stubs disabled on mainnet. Use node-level oracle.".to_string());
 
         #[cfg(not(feature = "mainnet"))]
-        Ok(ExchangePrice {
-            exchange: "coinbase".to_string(),
-            pair: "LOS-USD".to_string(),
-            price_micro_usd: 9_900, // 0.0099 USD
-            volume_24h_usd: 500_000,
-            timestamp: chrono::Utc::now().timestamp() as u64,
-        })
+        {
+            if !crate::test_stubs_enabled() {
+                return Err(
+                    "VM oracle stubs disabled on this network. Use node-level oracle.".to_string(),
+                );
+            }
+            Ok(ExchangePrice {
+                exchange: "coinbase".to_string(),
+                pair: "LOS-USD".to_string(),
+                price_micro_usd: 9_900, // 0.0099 USD
+                volume_24h_usd: 500_000,
+                timestamp: chrono::Utc::now().timestamp() as u64,
+            })
+        }
     }
 
     async fn fetch_from_kraken(&self) -> Result<ExchangePrice, String> {
@@ -114,13 +210,20 @@ impl ExchangeOracle {
         return Err("VM oracle stubs disabled on mainnet. Use node-level oracle.".to_string());
 
         #[cfg(not(feature = "mainnet"))]
-        Ok(ExchangePrice {
-            exchange: "kraken".to_string(),
-            pair: "LOSUSD".to_string(),
-            price_micro_usd: 10_100, // 0.0101 USD
-            volume_24h_usd: 750_000,
-            timestamp: chrono::Utc::now().timestamp() as u64,
-        })
+        {
+            if !crate::test_stubs_enabled() {
+                return Err(
+                    "VM oracle stubs disabled on this network. Use node-level oracle.".to_string(),
+                );
+            }
+            Ok(ExchangePrice {
+                exchange: "kraken".to_string(),
+                pair: "LOSUSD".to_string(),
+                price_micro_usd: 10_100, // 0.0101 USD
+                volume_24h_usd: 750_000,
+                timestamp: chrono::Utc::now().timestamp() as u64,
+            })
+        }
     }
 }
 
@@ -311,4 +414,44 @@ mod tests {
         assert!(consensus.confidence_bps > 9000); // >90% confidence
         assert_eq!(consensus.sources.len(), 2);
     }
+
+    fn obs(price: u64, timestamp: u64) -> OracleObservation {
+        OracleObservation {
+            symbol: "LOS".to_string(),
+            price_micro_usd: price,
+            confidence_bps: 10_000,
+            source_count: 3,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_deviation_alert_within_window() {
+        let rule = DeviationRule {
+            threshold_bps: 500,
+            window_secs: 600,
+        };
+        let history = vec![obs(10_000, 0), obs(10_000, 500), obs(10_200, 900)];
+        // 11_000 vs 10_000 at t=500 → +10% (the 10_000 at t=0 is outside the window)
+        let alert = check_deviation(&history, &obs(11_000, 1_000), &rule).unwrap();
+        assert_eq!(alert.change_bps, 1_000);
+        assert_eq!(alert.reference_timestamp, 500);
+
+        // Drop is reported with a negative sign, against the furthest reading
+        let alert = check_deviation(&history, &obs(9_000, 1_000), &rule).unwrap();
+        assert_eq!(alert.change_bps, -1_176);
+        assert_eq!(alert.reference_price_micro_usd, 10_200);
+    }
+
+    #[test]
+    fn test_deviation_below_threshold_or_no_history() {
+        let rule = DeviationRule {
+            threshold_bps: 500,
+            window_secs: 600,
+        };
+        assert!(check_deviation(&[obs(10_000, 900)], &obs(10_400, 1_000), &rule).is_none());
+        assert!(check_deviation(&[], &obs(10_400, 1_000), &rule).is_none());
+        // Old observations outside the window never trigger
+        assert!(check_deviation(&[obs(1, 0)], &obs(10_000, 1_000), &rule).is_none());
+    }
 }
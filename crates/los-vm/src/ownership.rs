@@ -0,0 +1,294 @@
+//! # Contract ownership
+//!
+//! `Contract.owner` (the deployer) controls owner-only operations such as
+//! the emergency pause. Ownership can be handed over in two steps, so a typo
+//! in the new address cannot lock the contract, or given up for good:
+//!
+//! - `__transfer_ownership(new_owner)` by the owner records `new_owner` as
+//!   `pending_owner` (a later proposal replaces it)
+//! - `__accept_ownership()` by the pending owner makes it the owner
+//! - `__renounce_ownership()` by the owner clears the owner (and any pending
+//!   transfer) and records the block timestamp in `ownership_renounced_at`.
+//!   Nobody can act as owner afterwards; emergency guardians can still pause.
+//!
+//! Like the pause, these are ordinary signed ContractCall blocks with
+//! reserved function names: they pay a fee, replicate, are journaled and
+//! never reach WASM. Each emits `OwnershipTransferStarted`,
+//! `OwnershipTransferred` or `OwnershipRenounced` with `previous_owner` /
+//! `new_owner` for explorers.
+
+use crate::{Contract, ContractCall, ContractEvent, ContractResult, WasmEngine};
+use std::collections::BTreeMap;
+
+/// Reserved function: propose a new owner (arg: the new owner's address)
+pub const TRANSFER_OWNERSHIP_FUNCTION: &str = "__transfer_ownership";
+/// Reserved function: the pending owner accepts
+pub const ACCEPT_OWNERSHIP_FUNCTION: &str = "__accept_ownership";
+/// Reserved function: the owner gives up ownership for good
+pub const RENOUNCE_OWNERSHIP_FUNCTION: &str = "__renounce_ownership";
+
+/// Ownership change requested by a reserved function name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnershipAction {
+    Transfer,
+    Accept,
+    Renounce,
+}
+
+impl OwnershipAction {
+    /// None = not an ownership function
+    pub fn parse(function: &str) -> Option<Self> {
+        match function {
+            TRANSFER_OWNERSHIP_FUNCTION => Some(Self::Transfer),
+            ACCEPT_OWNERSHIP_FUNCTION => Some(Self::Accept),
+            RENOUNCE_OWNERSHIP_FUNCTION => Some(Self::Renounce),
+            _ => None,
+        }
+    }
+}
+
+/// The ownership fields of a contract (journal undo record)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OwnershipState {
+    owner: String,
+    pending_owner: Option<String>,
+    renounced_at: Option<u64>,
+}
+
+impl OwnershipState {
+    pub(crate) fn of(contract: &Contract) -> Self {
+        Self {
+            owner: contract.owner.clone(),
+            pending_owner: contract.pending_owner.clone(),
+            renounced_at: contract.ownership_renounced_at,
+        }
+    }
+
+    pub(crate) fn restore(self, contract: &mut Contract) {
+        contract.owner = self.owner;
+        contract.pending_owner = self.pending_owner;
+        contract.ownership_renounced_at = self.renounced_at;
+    }
+}
+
+impl WasmEngine {
+    /// Execute `__transfer_ownership` / `__accept_ownership` /
+    /// `__renounce_ownership` (see module docs)
+    pub(crate) fn change_ownership_by_call(
+        &self,
+        journal: Option<&str>,
+        call: &ContractCall,
+        action: OwnershipAction,
+    ) -> Result<ContractResult, String> {
+        let mut contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+        let contract = contracts
+            .get_mut(&call.contract)
+            .ok_or_else(|| "Contract not found".to_string())?;
+        let is_owner = !call.caller.is_empty() && call.caller == contract.owner;
+        let previous = OwnershipState::of(contract);
+
+        let mut data = BTreeMap::new();
+        data.insert("previous_owner".to_string(), contract.owner.clone());
+        let event_type = match action {
+            OwnershipAction::Transfer => {
+                if !is_owner {
+                    return Err(format!(
+                        "Unauthorized: only the owner can transfer ownership of {}",
+                        call.contract
+                    ));
+                }
+                let new_owner = call.args.first().map(|a| a.trim()).unwrap_or_default();
+                if new_owner.is_empty() {
+                    return Err(
+                        "Missing new owner (use __renounce_ownership to give up ownership)"
+                            .to_string(),
+                    );
+                }
+                if new_owner == contract.owner {
+                    return Err(format!("{} already owns {}", new_owner, call.contract));
+                }
+                contract.pending_owner = Some(new_owner.to_string());
+                data.insert("new_owner".to_string(), new_owner.to_string());
+                "OwnershipTransferStarted"
+            }
+            OwnershipAction::Accept => {
+                if call.caller.is_empty() || contract.pending_owner.as_ref() != Some(&call.caller) {
+                    return Err(format!(
+                        "Unauthorized: only the pending owner can accept ownership of {}",
+                        call.contract
+                    ));
+                }
+                contract.owner = call.caller.clone();
+                contract.pending_owner = None;
+                data.insert("new_owner".to_string(), call.caller.clone());
+                "OwnershipTransferred"
+            }
+            OwnershipAction::Renounce => {
+                if !is_owner {
+                    return Err(format!(
+                        "Unauthorized: only the owner can renounce ownership of {}",
+                        call.contract
+                    ));
+                }
+                contract.owner = String::new();
+                contract.pending_owner = None;
+                contract.ownership_renounced_at = Some(call.block_timestamp);
+                "OwnershipRenounced"
+            }
+        };
+        if let Some(block_hash) = journal {
+            let mut j = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+            j.record_ownership(block_hash, &contract.address, previous);
+        }
+
+        Ok(ContractResult {
+            success: true,
+            output: event_type.to_string(),
+            gas_used: 0,
+            state_changes: BTreeMap::new(),
+            events: vec![ContractEvent {
+                contract: call.contract.clone(),
+                event_type: event_type.to_string(),
+                data,
+                timestamp: call.block_timestamp,
+                topics: Vec::new(),
+                block_hash: String::new(),
+                call_index: 0,
+                event_index: 0,
+            }],
+            transfers: Vec::new(),
+            contract_transfers: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pause::PAUSE_FUNCTION;
+
+    fn call(contract: &str, function: &str, caller: &str, args: &[&str]) -> ContractCall {
+        ContractCall {
+            contract: contract.to_string(),
+            function: function.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            gas_limit: 1_000,
+            caller: caller.to_string(),
+            block_timestamp: 7,
+            amount: 0,
+        }
+    }
+
+    fn deploy(engine: &WasmEngine) -> String {
+        engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_two_step_transfer() {
+        let engine = WasmEngine::new();
+        let addr = deploy(&engine);
+        let transfer = |caller: &str, to: &str| {
+            engine.call_contract(call(&addr, TRANSFER_OWNERSHIP_FUNCTION, caller, &[to]))
+        };
+
+        assert!(transfer("LOSmallory", "LOSmallory")
+            .unwrap_err()
+            .contains("Unauthorized"));
+        let started = transfer("LOSowner", "LOSalice").unwrap();
+        assert_eq!(started.events[0].event_type, "OwnershipTransferStarted");
+        assert_eq!(started.events[0].data["new_owner"], "LOSalice");
+        // Proposing does not hand over anything yet
+        let c = engine.get_contract(&addr).unwrap();
+        assert_eq!(
+            (c.owner.as_str(), c.pending_owner.as_deref()),
+            ("LOSowner", Some("LOSalice"))
+        );
+
+        let accept = |caller: &str| {
+            engine.call_contract(call(&addr, ACCEPT_OWNERSHIP_FUNCTION, caller, &[]))
+        };
+        assert!(accept("LOSbob").unwrap_err().contains("pending owner"));
+        let done = accept("LOSalice").unwrap();
+        assert_eq!(done.events[0].event_type, "OwnershipTransferred");
+        assert_eq!(done.events[0].data["previous_owner"], "LOSowner");
+        let c = engine.get_contract(&addr).unwrap();
+        assert_eq!((c.owner.as_str(), c.pending_owner), ("LOSalice", None));
+
+        // The old owner lost owner-only rights
+        assert!(engine
+            .call_contract(call(&addr, PAUSE_FUNCTION, "LOSowner", &[]))
+            .is_err());
+        assert!(engine
+            .call_contract(call(&addr, PAUSE_FUNCTION, "LOSalice", &[]))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_renounce_is_final() {
+        let engine = WasmEngine::new();
+        let addr = deploy(&engine);
+        engine
+            .call_contract(call(
+                &addr,
+                TRANSFER_OWNERSHIP_FUNCTION,
+                "LOSowner",
+                &["LOSalice"],
+            ))
+            .unwrap();
+        let renounced = engine
+            .call_contract(call(&addr, RENOUNCE_OWNERSHIP_FUNCTION, "LOSowner", &[]))
+            .unwrap();
+        assert_eq!(renounced.events[0].event_type, "OwnershipRenounced");
+
+        let c = engine.get_contract(&addr).unwrap();
+        assert_eq!(c.owner, "");
+        assert_eq!(c.pending_owner, None);
+        assert_eq!(c.ownership_renounced_at, Some(7));
+        // The cancelled proposal cannot be accepted, nobody can act as owner
+        assert!(engine
+            .call_contract(call(&addr, ACCEPT_OWNERSHIP_FUNCTION, "LOSalice", &[]))
+            .is_err());
+        assert!(engine
+            .call_contract(call(&addr, PAUSE_FUNCTION, "", &[]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_staged_ownership_rolls_back() {
+        let engine = WasmEngine::new();
+        let addr = deploy(&engine);
+        engine
+            .call_contract_staged(
+                "b1",
+                call(
+                    &addr,
+                    TRANSFER_OWNERSHIP_FUNCTION,
+                    "LOSowner",
+                    &["LOSalice"],
+                ),
+            )
+            .unwrap();
+        engine
+            .call_contract_staged(
+                "b2",
+                call(&addr, ACCEPT_OWNERSHIP_FUNCTION, "LOSalice", &[]),
+            )
+            .unwrap();
+        assert_eq!(engine.get_contract(&addr).unwrap().owner, "LOSalice");
+
+        assert_eq!(engine.rollback("b1").unwrap(), vec!["b2", "b1"]);
+        let c = engine.get_contract(&addr).unwrap();
+        assert_eq!((c.owner.as_str(), c.pending_owner), ("LOSowner", None));
+        assert_eq!(OwnershipAction::parse("transfer"), None);
+    }
+}
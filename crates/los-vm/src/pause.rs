@@ -0,0 +1,196 @@
+//! # Emergency pause
+//!
+//! Incident response for exploited contracts. A paused contract's code does
+//! not run: calls fail with `Contract ... is paused`, and contract transfers
+//! that would run its `on_receive` hook are rejected. State and balance stay
+//! readable (`GET /contract/:addr` reports `paused`).
+//!
+//! Pausing is an ordinary signed ContractCall block with a reserved function
+//! name, so it pays a fee, replicates and is journaled like any other call:
+//!
+//! - `__pause` / `__unpause` on the target contract
+//! - allowed for the contract owner and for the network's emergency
+//!   guardians (`ChainSpec::emergency_guardians`, installed by the node with
+//!   [`WasmEngine::set_emergency_guardians`])
+//! - the call emits a `Paused` / `Unpaused` event with the caller in `by`
+//!
+//! The reserved names never reach WASM, so a contract cannot export a
+//! function that overrides them.
+
+use crate::{ContractCall, ContractEvent, ContractResult, WasmEngine};
+use std::collections::BTreeMap;
+
+/// Reserved function that pauses the called contract
+pub const PAUSE_FUNCTION: &str = "__pause";
+/// Reserved function that resumes the called contract
+pub const UNPAUSE_FUNCTION: &str = "__unpause";
+
+/// Pause state requested by a reserved function name (None = ordinary call)
+pub fn requested_pause_state(function: &str) -> Option<bool> {
+    match function {
+        PAUSE_FUNCTION => Some(true),
+        UNPAUSE_FUNCTION => Some(false),
+        _ => None,
+    }
+}
+
+impl WasmEngine {
+    /// Replace the accounts allowed to pause any contract
+    pub fn set_emergency_guardians(&self, guardians: impl IntoIterator<Item = String>) {
+        let mut g = self.guardians.lock().unwrap_or_else(|e| e.into_inner());
+        *g = guardians.into_iter().filter(|a| !a.is_empty()).collect();
+    }
+
+    /// Accounts allowed to pause any contract
+    pub fn emergency_guardians(&self) -> Vec<String> {
+        let g = self.guardians.lock().unwrap_or_else(|e| e.into_inner());
+        g.iter().cloned().collect()
+    }
+
+    /// Whether `address` is paused (false for unknown contracts)
+    pub fn is_paused(&self, address: &str) -> Result<bool, String> {
+        let contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+        Ok(contracts.get(address).is_some_and(|c| c.paused))
+    }
+
+    /// Execute `__pause` / `__unpause` (see module docs)
+    pub(crate) fn set_paused_by_call(
+        &self,
+        journal: Option<&str>,
+        call: &ContractCall,
+        paused: bool,
+    ) -> Result<ContractResult, String> {
+        let is_guardian = {
+            let g = self.guardians.lock().unwrap_or_else(|e| e.into_inner());
+            g.contains(&call.caller)
+        };
+        let mut contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts".to_string())?;
+        let contract = contracts
+            .get_mut(&call.contract)
+            .ok_or_else(|| "Contract not found".to_string())?;
+        if call.caller.is_empty() || (call.caller != contract.owner && !is_guardian) {
+            return Err(format!(
+                "Unauthorized: only the owner or an emergency guardian can {} {}",
+                if paused { "pause" } else { "unpause" },
+                call.contract
+            ));
+        }
+        if contract.paused == paused {
+            return Err(format!(
+                "Contract {} is already {}",
+                call.contract,
+                if paused { "paused" } else { "active" }
+            ));
+        }
+        if let Some(block_hash) = journal {
+            let mut j = self.journal.lock().unwrap_or_else(|e| e.into_inner());
+            j.record_pause(block_hash, &contract.address, contract.paused);
+        }
+        contract.paused = paused;
+
+        let event_type = if paused { "Paused" } else { "Unpaused" };
+        let mut data = BTreeMap::new();
+        data.insert("by".to_string(), call.caller.clone());
+        Ok(ContractResult {
+            success: true,
+            output: event_type.to_lowercase(),
+            gas_used: 0,
+            state_changes: BTreeMap::new(),
+            events: vec![ContractEvent {
+                contract: call.contract.clone(),
+                event_type: event_type.to_string(),
+                data,
+                timestamp: call.block_timestamp,
+                topics: Vec::new(),
+                block_hash: String::new(),
+                call_index: 0,
+                event_index: 0,
+            }],
+            transfers: Vec::new(),
+            contract_transfers: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(contract: &str, function: &str, caller: &str) -> ContractCall {
+        ContractCall {
+            contract: contract.to_string(),
+            function: function.to_string(),
+            args: vec!["k".to_string(), "v".to_string()],
+            gas_limit: 1_000,
+            caller: caller.to_string(),
+            block_timestamp: 1,
+            amount: 0,
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "mainnet"))]
+    fn test_pause_blocks_calls_until_unpaused() {
+        let engine = WasmEngine::new();
+        let addr = engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+
+        // Only the owner or a guardian may pause
+        let denied = engine.call_contract(call(&addr, PAUSE_FUNCTION, "LOSmallory"));
+        assert!(denied.unwrap_err().contains("Unauthorized"));
+        let paused = engine
+            .call_contract(call(&addr, PAUSE_FUNCTION, "LOSowner"))
+            .unwrap();
+        assert_eq!(paused.events[0].event_type, "Paused");
+        assert!(engine.is_paused(&addr).unwrap());
+        assert!(engine
+            .call_contract(call(&addr, PAUSE_FUNCTION, "LOSowner"))
+            .unwrap_err()
+            .contains("already"));
+
+        let err = engine
+            .call_contract(call(&addr, "set_state", "LOSalice"))
+            .unwrap_err();
+        assert!(err.contains("paused"), "{}", err);
+
+        engine.set_emergency_guardians(["LOSguardian".to_string()]);
+        engine
+            .call_contract(call(&addr, UNPAUSE_FUNCTION, "LOSguardian"))
+            .unwrap();
+        assert!(engine
+            .call_contract(call(&addr, "set_state", "LOSalice"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_staged_pause_rolls_back() {
+        let engine = WasmEngine::new();
+        let addr = engine
+            .deploy_contract(
+                "LOSowner".to_string(),
+                b"\0asm\x01\x00\x00\x00".to_vec(),
+                BTreeMap::new(),
+                1,
+            )
+            .unwrap();
+        engine
+            .call_contract_staged("b1", call(&addr, PAUSE_FUNCTION, "LOSowner"))
+            .unwrap();
+        assert!(engine.is_paused(&addr).unwrap());
+        assert_eq!(engine.rollback("b1").unwrap(), vec!["b1"]);
+        assert!(!engine.is_paused(&addr).unwrap());
+        assert_eq!(requested_pause_state("transfer"), None);
+    }
+}
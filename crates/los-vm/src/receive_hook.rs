@@ -0,0 +1,425 @@
+//! # Contract receive hooks
+//!
+//! `host_transfer` to an ordinary account is settled by the node (it credits
+//! the ledger). A transfer to another **contract** is settled here: the VM
+//! credits the recipient's balance and, if the recipient exports
+//! `on_receive`, runs it first so escrow-style contracts can account for
+//! incoming funds.
+//!
+//! - `on_receive` is an SDK-mode export (no WASM params). `arg(0)` is the
+//!   sending contract, `arg(1)` the amount in CIL; `caller()` is the sender.
+//! - It runs with whatever is left of the original call's gas limit.
+//! - It sees the block of the original call; the n-th hook of a call has
+//!   call index n, so `los_sdk::call_id()` differs from the caller's.
+//! - A non-zero return code, abort, trap or out-of-gas rejects the transfer,
+//!   and with it the whole call: nothing from the call or any hook commits.
+//! - Transfers made by a hook are settled the same way (nested hooks), up to
+//!   [`MAX_RECEIVE_HOOKS`] per call. A contract runs at most once per call, so
+//!   a hook that would re-enter an already executed contract is rejected.
+//!
+//! A paused recipient (see [`crate::pause`]) that exports `on_receive`
+//! rejects the transfer.
+//!
+//! Every contract touched by a hook stays locked until the whole call
+//! commits. A recipient that is busy with another call rejects the transfer
+//! instead of waiting (two contracts paying each other could deadlock).
+
+use crate::host::CallContext;
+use crate::{exec_limit, sdk_call_output, ContractEvent, WasmEngine};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use wasmer::wasmparser::{ExternalKind, Parser, Payload};
+
+/// Name of the optional export invoked on incoming contract transfers
+pub const RECEIVE_HOOK: &str = "on_receive";
+/// Maximum `on_receive` invocations (including nested ones) per call
+pub const MAX_RECEIVE_HOOKS: usize = 16;
+
+/// Whether `bytecode` exports a function named `name`
+pub fn exports_function(bytecode: &[u8], name: &str) -> bool {
+    for payload in Parser::new(0).parse_all(bytecode) {
+        match payload {
+            Ok(Payload::ExportSection(reader)) => {
+                return reader
+                    .into_iter()
+                    .flatten()
+                    .any(|e| e.name == name && e.kind == ExternalKind::Func);
+            }
+            // The export section precedes the code section
+            Ok(Payload::CodeSectionStart { .. }) | Err(_) => return false,
+            Ok(_) => {}
+        }
+    }
+    false
+}
+
+/// Effects of a call and the hooks it triggered, applied together on commit
+#[derive(Debug, Default)]
+pub(crate) struct Settlement {
+    /// Contract → state key → new value
+    pub state: BTreeMap<String, BTreeMap<String, Vec<u8>>>,
+    /// Contract → CIL received from other contracts
+    pub credits: BTreeMap<String, u128>,
+    /// Contract → CIL sent by host_transfer
+    pub debits: BTreeMap<String, u128>,
+    /// Transfers to non-contract accounts (the node credits the ledger)
+    pub external: Vec<(String, u128)>,
+    /// Transfers credited to contract balances
+    pub to_contracts: Vec<(String, u128)>,
+    /// Events emitted by hooks
+    pub events: Vec<ContractEvent>,
+    pub hooks_run: usize,
+    pub gas_used: u64,
+}
+
+impl Settlement {
+    /// Record one contract's transfers as debits; returns them as queue items
+    pub fn debit(&mut self, from: &str, transfers: &[(String, u128)]) -> Vec<Transfer> {
+        let total: u128 = transfers.iter().map(|(_, a)| *a).sum();
+        let entry = self.debits.entry(from.to_string()).or_default();
+        *entry = entry.saturating_add(total);
+        transfers
+            .iter()
+            .map(|(to, amount)| Transfer {
+                from: from.to_string(),
+                to: to.clone(),
+                amount: *amount,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Transfer {
+    pub from: String,
+    pub to: String,
+    pub amount: u128,
+}
+
+impl WasmEngine {
+    /// Settle queued transfers (running receive hooks), then commit everything
+    /// in `settlement`. `executed` holds contracts whose state is already
+    /// staged (the original callee). Recurses once per hook so each hook's
+    /// contract lock is held until the commit at the bottom.
+    pub(crate) fn settle_transfers(
+        &self,
+        journal: Option<&str>,
+        mut queue: VecDeque<Transfer>,
+        mut executed: BTreeSet<String>,
+        mut settlement: Settlement,
+        gas_limit: u64,
+        timestamp: u64,
+    ) -> Result<Settlement, String> {
+        while let Some(t) = queue.pop_front() {
+            let target = {
+                let contracts = self
+                    .contracts
+                    .lock()
+                    .map_err(|_| "Failed to lock contracts".to_string())?;
+                contracts.get(&t.to).cloned()
+            };
+            let Some(target) = target else {
+                settlement.external.push((t.to, t.amount));
+                continue;
+            };
+            let credited = settlement.credits.entry(t.to.clone()).or_default();
+            *credited = credited.saturating_add(t.amount);
+            settlement.to_contracts.push((t.to.clone(), t.amount));
+            if !exports_function(&target.bytecode, RECEIVE_HOOK) {
+                continue;
+            }
+
+            if target.paused {
+                return Err(format!("Recipient contract {} is paused", t.to));
+            }
+            if executed.contains(&t.to) {
+                return Err(format!(
+                    "Re-entrant transfer: {} already executed in this call",
+                    t.to
+                ));
+            }
+            if settlement.hooks_run >= MAX_RECEIVE_HOOKS {
+                return Err(format!(
+                    "Too many {} hooks (max {})",
+                    RECEIVE_HOOK, MAX_RECEIVE_HOOKS
+                ));
+            }
+            let lock = self.get_contract_lock(&t.to);
+            let _guard = lock
+                .try_lock()
+                .map_err(|_| format!("Recipient contract {} is busy", t.to))?;
+            executed.insert(t.to.clone());
+            settlement.hooks_run += 1;
+
+            let balance = target
+                .balance
+                .saturating_add(settlement.credits.get(&t.to).copied().unwrap_or(0))
+                .saturating_sub(settlement.debits.get(&t.to).copied().unwrap_or(0));
+            let gas_left = gas_limit.saturating_sub(settlement.gas_used);
+            let rejected = |reason: String| {
+                format!(
+                    "{} rejected transfer of {} CIL from {}: {}",
+                    t.to, t.amount, t.from, reason
+                )
+            };
+            let context = CallContext {
+                block_hash: journal.unwrap_or_default().to_string(),
+                call_index: settlement.hooks_run as u32,
+            };
+            let res = self
+                .execute_wasm_hosted_with(
+                    &target.bytecode,
+                    RECEIVE_HOOK,
+                    &[t.from.clone(), t.amount.to_string()],
+                    gas_left,
+                    &t.from,
+                    &t.to,
+                    &target.state,
+                    balance,
+                    t.amount,
+                    timestamp,
+                    context,
+                    false,
+                    exec_limit::Admission::Consensus,
+                )
+                .map_err(rejected)?;
+            if !res.sdk_mode {
+                return Err(rejected(format!(
+                    "{} must take no parameters",
+                    RECEIVE_HOOK
+                )));
+            }
+            if res.return_code != 0 {
+                return Err(rejected(sdk_call_output(res.return_code, &res.return_data)));
+            }
+
+            settlement.gas_used = settlement.gas_used.saturating_add(res.gas_used);
+            settlement.state.insert(t.to.clone(), res.state_changes);
+            let call_index = settlement.hooks_run as u32;
+            settlement.events.extend(
+                res.events
+                    .into_iter()
+                    .map(|e| ContractEvent { call_index, ..e }),
+            );
+            queue.extend(settlement.debit(&t.to, &res.transfers));
+            return self
+                .settle_transfers(journal, queue, executed, settlement, gas_limit, timestamp);
+        }
+        self.commit_settlement(journal, &settlement)?;
+        Ok(settlement)
+    }
+
+    fn commit_settlement(
+        &self,
+        journal: Option<&str>,
+        settlement: &Settlement,
+    ) -> Result<(), String> {
+        let mut contracts = self
+            .contracts
+            .lock()
+            .map_err(|_| "Failed to lock contracts for state update".to_string())?;
+        if journal.is_some() {
+            let touched: BTreeSet<&String> = settlement
+                .state
+                .keys()
+                .chain(settlement.credits.keys())
+                .chain(settlement.debits.keys())
+                .collect();
+            for addr in touched {
+                if let Some(c) = contracts.get(addr) {
+                    let keys = settlement
+                        .state
+                        .get(addr)
+                        .into_iter()
+                        .flat_map(|m| m.keys());
+                    let credit = settlement.credits.get(addr).copied().unwrap_or(0);
+                    let debit = settlement.debits.get(addr).copied().unwrap_or(0);
+                    self.journal_record(journal, c, keys, credit as i128 - debit as i128);
+                }
+            }
+        }
+        for (addr, changes) in &settlement.state {
+            if let Some(c) = contracts.get_mut(addr) {
+                for (key, val) in changes {
+                    c.state
+                        .insert(key.clone(), String::from_utf8_lossy(val).to_string());
+                }
+            }
+        }
+        for (addr, amount) in &settlement.debits {
+            if let Some(c) = contracts.get_mut(addr) {
+                c.balance = c.balance.saturating_sub(*amount);
+            }
+        }
+        for (addr, amount) in &settlement.credits {
+            if let Some(c) = contracts.get_mut(addr) {
+                c.balance = c.balance.saturating_add(*amount);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exports_function_scans_export_section() {
+        let wasm = wasmer::wat2wasm(
+            br#"(module
+                (memory (export "on_receive_mem") 1)
+                (func (export "on_receive") (result i32) (i32.const 0))
+                (func (export "other") (result i32) (i32.const 0)))"#,
+        )
+        .unwrap();
+        assert!(exports_function(&wasm, "on_receive"));
+        assert!(exports_function(&wasm, "other"));
+        // Memory exports and missing names do not count
+        assert!(!exports_function(&wasm, "on_receive_mem"));
+        assert!(!exports_function(&wasm, "missing"));
+        assert!(!exports_function(b"\0asm\x01\x00\x00\x00", "on_receive"));
+        assert!(!exports_function(b"garbage", "on_receive"));
+    }
+
+    #[test]
+    fn test_transfers_to_contracts_run_on_receive() {
+        let engine = WasmEngine::new();
+        let deploy = |wat: &str, seq: u64| {
+            let wasm = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+            engine
+                .deploy_contract("LOSowner".to_string(), wasm, BTreeMap::new(), seq)
+                .unwrap()
+        };
+        // pay(recipient): host_transfer(recipient, 100), returns its status
+        let payer = deploy(
+            r#"(module
+                (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+                (import "env" "host_transfer" (func $xfer (param i32 i32 i64 i64) (result i32)))
+                (memory (export "memory") 1)
+                (func (export "pay") (result i32)
+                    (call $xfer (i32.const 0)
+                        (call $arg (i32.const 0) (i32.const 0) (i32.const 128))
+                        (i64.const 100) (i64.const 0))))"#,
+            1,
+        );
+        let receiver = |code: i32| {
+            format!(
+                r#"(module
+                    (import "env" "host_set_state" (func $set (param i32 i32 i32 i32)))
+                    (memory (export "memory") 1)
+                    (data (i32.const 0) "got1")
+                    (func (export "on_receive") (result i32)
+                        (call $set (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 1))
+                        (i32.const {})))"#,
+                code
+            )
+        };
+        let accepting = deploy(&receiver(0), 2);
+        let rejecting = deploy(&receiver(107), 3);
+        let plain = deploy(
+            r#"(module (func (export "noop") (result i32) (i32.const 0)))"#,
+            4,
+        );
+        engine.send_to_contract(&payer, 1_000).unwrap();
+
+        let pay = |to: &str| {
+            engine.call_contract(crate::ContractCall {
+                contract: payer.clone(),
+                function: "pay".to_string(),
+                args: vec![to.to_string()],
+                gas_limit: 100_000,
+                caller: "LOSalice".to_string(),
+                block_timestamp: 1,
+                amount: 0,
+            })
+        };
+        let balance = |addr: &str| engine.get_contract(addr).unwrap().balance;
+
+        let res = pay(&accepting).unwrap();
+        assert!(res.success);
+        assert!(res.transfers.is_empty());
+        assert_eq!(res.contract_transfers, vec![(accepting.clone(), 100)]);
+        assert_eq!(balance(&accepting), 100);
+        assert_eq!(balance(&payer), 900);
+        let state = engine.get_contract_state(&accepting).unwrap();
+        assert_eq!(state.get("got").map(String::as_str), Some("1"));
+
+        // Hook returns an error code: nothing commits, payer keeps its CIL
+        let err = pay(&rejecting).unwrap_err();
+        assert!(err.contains("rejected transfer"), "{}", err);
+        assert_eq!(balance(&payer), 900);
+        assert_eq!(balance(&rejecting), 0);
+        assert!(engine.get_contract_state(&rejecting).unwrap().is_empty());
+
+        // No hook exported: credited directly
+        pay(&plain).unwrap();
+        assert_eq!(balance(&plain), 100);
+
+        // Ordinary accounts are left to the ledger
+        let res = pay("LOSplainaccount").unwrap();
+        assert_eq!(res.transfers, vec![("LOSplainaccount".to_string(), 100)]);
+        assert_eq!(balance(&payer), 700);
+    }
+
+    #[test]
+    fn test_hook_events_follow_the_call_in_order() {
+        let engine = WasmEngine::new();
+        let deploy = |wat: &str, seq: u64| {
+            let wasm = wasmer::wat2wasm(wat.as_bytes()).unwrap().to_vec();
+            engine
+                .deploy_contract("LOSowner".to_string(), wasm, BTreeMap::new(), seq)
+                .unwrap()
+        };
+        // Emits "A" and "B", pays arg(0), then emits "C"
+        let payer = deploy(
+            r#"(module
+                (import "env" "host_get_arg" (func $arg (param i32 i32 i32) (result i32)))
+                (import "env" "host_transfer" (func $xfer (param i32 i32 i64 i64) (result i32)))
+                (import "env" "host_emit_event" (func $emit (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 200) "ABC")
+                (func (export "pay") (result i32)
+                    (call $emit (i32.const 200) (i32.const 1) (i32.const 0) (i32.const 0))
+                    (call $emit (i32.const 201) (i32.const 1) (i32.const 0) (i32.const 0))
+                    (drop (call $xfer (i32.const 0)
+                        (call $arg (i32.const 0) (i32.const 0) (i32.const 128))
+                        (i64.const 10) (i64.const 0)))
+                    (call $emit (i32.const 202) (i32.const 1) (i32.const 0) (i32.const 0))
+                    (i32.const 0)))"#,
+            1,
+        );
+        let receiver = deploy(
+            r#"(module
+                (import "env" "host_emit_event" (func $emit (param i32 i32 i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "Got")
+                (func (export "on_receive") (result i32)
+                    (call $emit (i32.const 0) (i32.const 3) (i32.const 0) (i32.const 0))
+                    (i32.const 0)))"#,
+            2,
+        );
+        engine.send_to_contract(&payer, 100).unwrap();
+
+        let res = engine
+            .call_contract(crate::ContractCall {
+                contract: payer.clone(),
+                function: "pay".to_string(),
+                args: vec![receiver.clone()],
+                gas_limit: 100_000,
+                caller: "LOSalice".to_string(),
+                block_timestamp: 1,
+                amount: 0,
+            })
+            .unwrap();
+        let order: Vec<_> = res
+            .events
+            .iter()
+            .map(|e| (e.event_type.as_str(), e.call_index, e.event_index))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("A", 0, 0), ("B", 0, 1), ("C", 0, 2), ("Got", 1, 0)]
+        );
+        assert_eq!(res.events[3].contract, receiver);
+    }
+}
@@ -0,0 +1,441 @@
+//! # Execution tracing (dry runs)
+//!
+//! Opt-in instrumentation for [`WasmEngine::dry_run`](crate::WasmEngine::dry_run),
+//! so a contract developer can see where the gas of a call goes:
+//!
+//! - **Host-call trace** — every host import the contract calls, in order,
+//!   with a short summary of its arguments (state key, event type, transfer
+//!   recipient, ...), its result and the gas used before and after it.
+//! - **Instruction histogram** — per WASM function, how often it was entered
+//!   and how many instructions it executed (each instruction costs 1 gas).
+//!
+//! Tracing never changes gas: the profiler middleware runs after the
+//! metering middleware and skips the operators metering injects, and host
+//! calls are wrapped, not re-priced. Consensus execution never enables it.
+
+use crate::host::{read_guest_bytes, HostState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use wasmer::wasmparser::Operator;
+use wasmer::{
+    ExportIndex, Extern, Function, FunctionEnv, FunctionEnvMut, FunctionMiddleware, GlobalInit,
+    GlobalType, Imports, Instance, LocalFunctionIndex, MiddlewareError, MiddlewareReaderState,
+    ModuleMiddleware, Mutability, RuntimeError, Store, Type, Value,
+};
+use wasmer_types::ModuleInfo;
+
+/// Host calls recorded per trace (later calls are counted, not listed)
+pub const MAX_TRACED_HOST_CALLS: usize = 10_000;
+/// Longest string shown in a host-call argument summary
+const MAX_SUMMARY_STR: usize = 64;
+
+const INSTR_EXPORT: &str = "__los_trace_instr_";
+const CALLS_EXPORT: &str = "__los_trace_calls_";
+const METERING_REMAINING_EXPORT: &str = "wasmer_metering_remaining_points";
+
+/// One host import invocation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HostCallTrace {
+    pub function: String,
+    /// Decoded arguments, e.g. `key="bal:LOS…", value_len=9`
+    pub args: String,
+    /// Return value (None for host calls without one)
+    pub result: Option<i64>,
+    /// Gas used by the whole execution so far, before / after this call
+    pub gas_before: u64,
+    pub gas_after: u64,
+}
+
+/// Instruction histogram entry for one WASM function
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// Export or name-section name, else `func[<index>]`
+    pub function: String,
+    pub calls: u64,
+    pub instructions: u64,
+}
+
+/// Trace of one dry-run execution
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    /// Bytecode compilation charge (per KB)
+    pub compile_gas: u64,
+    /// Instructions executed (sum of `functions[].instructions`)
+    pub instruction_gas: u64,
+    /// Gas charged by host calls (sum over `host_calls`, including dropped ones)
+    pub host_gas: u64,
+    pub host_calls: Vec<HostCallTrace>,
+    /// Host calls beyond MAX_TRACED_HOST_CALLS (charged, not listed)
+    pub host_calls_dropped: u64,
+    /// Functions that ran, most instructions first
+    pub functions: Vec<FunctionProfile>,
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Instruction histogram (compiler middleware)
+// ─────────────────────────────────────────────────────────────────
+
+/// Per-function counter globals of one module
+#[derive(Debug, Clone, Default)]
+struct ProfilerLayout {
+    /// (calls global, instructions global) per local function
+    counters: Vec<(u32, u32)>,
+    names: Vec<String>,
+    /// Metering's remaining-points global, to recognise its injected code
+    metering_global: Option<u32>,
+}
+
+/// Module middleware adding an instruction and an entry counter per local
+/// function. Push it AFTER `Metering`; one instance per module.
+#[derive(Debug, Default)]
+pub(crate) struct InstructionProfiler {
+    layout: Mutex<Option<ProfilerLayout>>,
+}
+
+impl InstructionProfiler {
+    /// Read the histogram from an instance compiled with this middleware
+    pub(crate) fn read(&self, store: &mut Store, instance: &Instance) -> Vec<FunctionProfile> {
+        let names = match self.layout.lock() {
+            Ok(l) => l.as_ref().map(|l| l.names.clone()).unwrap_or_default(),
+            Err(_) => return Vec::new(),
+        };
+        let counter = |store: &mut Store, prefix: &str, i: usize| -> u64 {
+            match instance.exports.get_global(&format!("{}{}", prefix, i)) {
+                Ok(g) => match g.get(store) {
+                    Value::I64(v) => v as u64,
+                    _ => 0,
+                },
+                Err(_) => 0,
+            }
+        };
+        let mut profile: Vec<FunctionProfile> = names
+            .into_iter()
+            .enumerate()
+            .map(|(i, function)| FunctionProfile {
+                function,
+                calls: counter(store, CALLS_EXPORT, i),
+                instructions: counter(store, INSTR_EXPORT, i),
+            })
+            .filter(|p| p.calls > 0 || p.instructions > 0)
+            .collect();
+        profile.sort_by(|a, b| {
+            b.instructions
+                .cmp(&a.instructions)
+                .then_with(|| a.function.cmp(&b.function))
+        });
+        profile
+    }
+}
+
+impl ModuleMiddleware for InstructionProfiler {
+    fn generate_function_middleware(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        let layout = self
+            .layout
+            .lock()
+            .ok()
+            .and_then(|l| l.clone())
+            .unwrap_or_default();
+        let (calls_global, instr_global) = layout
+            .counters
+            .get(local_function_index.as_u32() as usize)
+            .copied()
+            .unwrap_or((u32::MAX, u32::MAX));
+        Box::new(FunctionProfiler {
+            calls_global,
+            instr_global,
+            metering_global: layout.metering_global,
+            entered: false,
+            in_metering: false,
+            accumulated: 0,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut names_by_index: HashMap<u32, String> = module_info
+            .function_names
+            .iter()
+            .map(|(idx, name)| (idx.as_u32(), name.clone()))
+            .collect();
+        // Export names win over name-section names
+        for (name, export) in module_info.exports.iter() {
+            if let ExportIndex::Function(idx) = export {
+                names_by_index.insert(idx.as_u32(), name.clone());
+            }
+        }
+        let metering_global = match module_info.exports.get(METERING_REMAINING_EXPORT) {
+            Some(ExportIndex::Global(g)) => Some(g.as_u32()),
+            _ => None,
+        };
+
+        let local_count = module_info.functions.len() - module_info.num_imported_functions;
+        let mut layout = ProfilerLayout {
+            metering_global,
+            ..Default::default()
+        };
+        for local in 0..local_count {
+            let func_index = module_info.func_index(LocalFunctionIndex::from_u32(local as u32));
+            let mut counter = |prefix: &str| {
+                let g = module_info
+                    .globals
+                    .push(GlobalType::new(Type::I64, Mutability::Var));
+                module_info
+                    .global_initializers
+                    .push(GlobalInit::I64Const(0));
+                module_info
+                    .exports
+                    .insert(format!("{}{}", prefix, local), ExportIndex::Global(g));
+                g.as_u32()
+            };
+            let calls = counter(CALLS_EXPORT);
+            let instr = counter(INSTR_EXPORT);
+            layout.counters.push((calls, instr));
+            layout.names.push(
+                names_by_index
+                    .remove(&func_index.as_u32())
+                    .unwrap_or_else(|| format!("func[{}]", func_index.as_u32())),
+            );
+        }
+        let mut slot = self
+            .layout
+            .lock()
+            .map_err(|_| MiddlewareError::new("InstructionProfiler", "layout lock poisoned"))?;
+        if slot.is_some() {
+            return Err(MiddlewareError::new(
+                "InstructionProfiler",
+                "one profiler per module",
+            ));
+        }
+        *slot = Some(layout);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FunctionProfiler {
+    calls_global: u32,
+    instr_global: u32,
+    metering_global: Option<u32>,
+    entered: bool,
+    /// Inside an accounting block injected by `Metering`
+    in_metering: bool,
+    /// Original instructions since the last flush
+    accumulated: u64,
+}
+
+impl FunctionProfiler {
+    fn add_to(global_index: u32, value: u64) -> [Operator<'static>; 4] {
+        [
+            Operator::GlobalGet { global_index },
+            Operator::I64Const {
+                value: value as i64,
+            },
+            Operator::I64Add,
+            Operator::GlobalSet { global_index },
+        ]
+    }
+}
+
+impl FunctionMiddleware for FunctionProfiler {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        if self.calls_global == u32::MAX {
+            state.push_operator(operator);
+            return Ok(());
+        }
+        if !self.entered {
+            self.entered = true;
+            state.extend(&Self::add_to(self.calls_global, 1));
+        }
+
+        // Metering's accounting block runs from `global.get remaining` to
+        // `global.set remaining`; the original program cannot touch that
+        // global, so everything in between is injected and not counted.
+        let metering = self.metering_global;
+        match operator {
+            Operator::GlobalGet { global_index } if Some(global_index) == metering => {
+                self.in_metering = true;
+            }
+            Operator::GlobalSet { global_index } if Some(global_index) == metering => {
+                self.in_metering = false;
+                state.push_operator(operator);
+                return Ok(());
+            }
+            _ => {}
+        }
+        if self.in_metering {
+            state.push_operator(operator);
+            return Ok(());
+        }
+
+        self.accumulated += 1;
+        // Flush at the same points as metering (before control flow), so the
+        // count only includes instructions that actually ran
+        if wasmer_middlewares::metering::is_accounting(&operator) {
+            state.extend(&Self::add_to(self.instr_global, self.accumulated));
+            self.accumulated = 0;
+        }
+        state.push_operator(operator);
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Host-call trace (import wrappers)
+// ─────────────────────────────────────────────────────────────────
+
+/// Host calls recorded during one execution
+#[derive(Debug, Default)]
+pub(crate) struct HostCallLog {
+    pub calls: Vec<HostCallTrace>,
+    pub dropped: u64,
+    pub gas: u64,
+}
+
+fn quoted(env: &FunctionEnvMut<HostState>, ptr: &Value, len: &Value) -> String {
+    let (Value::I32(ptr), Value::I32(len)) = (ptr, len) else {
+        return "?".to_string();
+    };
+    let len = (*len as u32).min(MAX_SUMMARY_STR as u32 * 4);
+    let text = read_guest_bytes(env, *ptr as u32, len)
+        .map(|b| String::from_utf8_lossy(&b).into_owned())
+        .unwrap_or_else(|| "<out of bounds>".to_string());
+    if text.chars().count() > MAX_SUMMARY_STR {
+        let short: String = text.chars().take(MAX_SUMMARY_STR).collect();
+        format!("{:?}…", short)
+    } else {
+        format!("{:?}", text)
+    }
+}
+
+fn int(v: &Value) -> i64 {
+    match v {
+        Value::I32(x) => *x as i64,
+        Value::I64(x) => *x,
+        _ => 0,
+    }
+}
+
+/// Human-readable arguments of a host call
+fn summarize(env: &FunctionEnvMut<HostState>, name: &str, a: &[Value]) -> String {
+    let arg = |i: usize| a.get(i).cloned().unwrap_or(Value::I32(0));
+    match name {
+        "host_set_state" => format!(
+            "key={}, value_len={}",
+            quoted(env, &arg(0), &arg(1)),
+            int(&arg(3))
+        ),
+        "host_get_state" | "host_del_state" => format!("key={}", quoted(env, &arg(0), &arg(1))),
+        "host_emit_event" => format!(
+            "type={}, data_len={}",
+            quoted(env, &arg(0), &arg(1)),
+            int(&arg(3))
+        ),
+        "host_emit_event_indexed" => format!(
+            "type={}, topics_len={}, data_len={}",
+            quoted(env, &arg(0), &arg(1)),
+            int(&arg(3)),
+            int(&arg(5))
+        ),
+        "host_transfer" => {
+            let amount = (int(&arg(2)) as u64 as u128) | ((int(&arg(3)) as u64 as u128) << 64);
+            format!(
+                "to={}, amount_cil={}",
+                quoted(env, &arg(0), &arg(1)),
+                amount
+            )
+        }
+        "host_log" | "host_abort" => format!("msg={}", quoted(env, &arg(0), &arg(1))),
+        "host_contract_exists" | "host_get_code_hash" => {
+            format!("address={}", quoted(env, &arg(0), &arg(1)))
+        }
+        "host_get_arg" => format!("index={}", int(&arg(0))),
+        "host_set_return" | "host_blake3" => format!("len={}", int(&arg(1))),
+        _ => String::new(),
+    }
+}
+
+fn remaining_points(env: &mut FunctionEnvMut<HostState>) -> Option<u64> {
+    let metering = env.data().metering.clone()?;
+    Some(metering.remaining_points(env))
+}
+
+/// Wrap every function import so each call is recorded in `log`.
+/// `budget` is the metering counter's starting value; gas used so far is
+/// `compile_gas + budget - remaining`.
+pub(crate) fn traced_imports(
+    store: &mut Store,
+    env: &FunctionEnv<HostState>,
+    base: &Imports,
+    log: Arc<Mutex<HostCallLog>>,
+    compile_gas: u64,
+    budget: u64,
+) -> Imports {
+    let mut traced = Imports::new();
+    for ((module, name), ext) in base {
+        let Extern::Function(inner) = ext else {
+            traced.define(&module, &name, ext);
+            continue;
+        };
+        let ty = inner.ty(store);
+        let log = Arc::clone(&log);
+        let fname = name.clone();
+        let wrapper = Function::new_with_env(
+            store,
+            env,
+            ty,
+            move |mut env: FunctionEnvMut<HostState>,
+                  args: &[Value]|
+                  -> Result<Vec<Value>, RuntimeError> {
+                let used = |remaining: Option<u64>| {
+                    compile_gas + budget.saturating_sub(remaining.unwrap_or(budget))
+                };
+                let summary = summarize(&env, &fname, args);
+                let gas_before = used(remaining_points(&mut env));
+                let results = inner.call(&mut env, args)?;
+                // Exhausted metering reports 0 remaining: the call was refused
+                let gas_after = used(remaining_points(&mut env));
+                if let Ok(mut log) = log.lock() {
+                    log.gas += gas_after.saturating_sub(gas_before);
+                    if log.calls.len() < MAX_TRACED_HOST_CALLS {
+                        log.calls.push(HostCallTrace {
+                            function: fname.clone(),
+                            args: summary,
+                            result: results.first().map(int),
+                            gas_before,
+                            gas_after,
+                        });
+                    } else {
+                        log.dropped += 1;
+                    }
+                }
+                Ok(results.into_vec())
+            },
+        );
+        traced.define(&module, &name, wrapper);
+    }
+    traced
+}
+
+impl ExecutionTrace {
+    pub(crate) fn assemble(
+        compile_gas: u64,
+        log: HostCallLog,
+        functions: Vec<FunctionProfile>,
+    ) -> Self {
+        ExecutionTrace {
+            compile_gas,
+            instruction_gas: functions.iter().map(|f| f.instructions).sum(),
+            host_gas: log.gas,
+            host_calls: log.calls,
+            host_calls_dropped: log.dropped,
+            functions,
+        }
+    }
+}
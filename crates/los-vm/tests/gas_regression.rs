@@ -0,0 +1,136 @@
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// GAS REGRESSION — canonical contracts vs tests/vectors/gas_baseline.json
+//
+// Gas is consensus-critical: validators that disagree on gas disagree on
+// out-of-gas failures and fees. The baseline records gas per scenario for
+// each release; a run compares against this release's entry (or the latest
+// recorded release when this one has none yet), so any change in metering or
+// the host gas schedule fails until it is recorded on purpose:
+//   LOS_UPDATE_GAS=1 cargo test -p los-vm --test gas_regression
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+#[path = "../benches/canonical/mod.rs"]
+mod canonical;
+
+use std::collections::BTreeMap;
+
+const BASELINE_PATH: &str = "tests/vectors/gas_baseline.json";
+
+type Releases = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Numeric ordering of "major.minor.patch" release keys
+fn release_key(v: &str) -> Vec<u64> {
+    v.split('.').map(|p| p.parse().unwrap_or(0)).collect()
+}
+
+#[test]
+fn test_gas_matches_recorded_baseline() {
+    let actual = canonical::measure_gas();
+    let release = env!("CARGO_PKG_VERSION");
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(BASELINE_PATH);
+    let mut releases: Releases = std::fs::read_to_string(&path)
+        .ok()
+        .map(|text| {
+            let file: serde_json::Value = serde_json::from_str(&text).expect("parse gas baseline");
+            serde_json::from_value(file["releases"].clone()).expect("releases object")
+        })
+        .unwrap_or_default();
+
+    if std::env::var("LOS_UPDATE_GAS").is_ok_and(|v| v == "1") {
+        releases.insert(release.to_string(), actual);
+        let json = serde_json::json!({
+            "note": "gas_used per canonical contract call (crates/los-vm/benches/canonical), by los-vm release",
+            "releases": releases,
+        });
+        let text = serde_json::to_string_pretty(&json).expect("encode baseline") + "\n";
+        std::fs::write(&path, text).expect("write gas baseline");
+        println!(
+            "📝 Recorded gas for los-vm {} in {}",
+            release, BASELINE_PATH
+        );
+        return;
+    }
+
+    let (recorded, expected) = releases
+        .get_key_value(release)
+        .or_else(|| releases.iter().max_by_key(|(v, _)| release_key(v)))
+        .expect("no gas baseline recorded — run with LOS_UPDATE_GAS=1");
+    for (name, want) in expected {
+        let got = actual.get(name).copied();
+        assert_eq!(
+            got,
+            Some(*want),
+            "gas for '{}' changed from los-vm {} ({:?} vs {}). Gas is consensus-critical; \
+             if the change is intentional, record it with LOS_UPDATE_GAS=1",
+            name,
+            recorded,
+            got,
+            want
+        );
+    }
+    assert_eq!(
+        expected.len(),
+        actual.len(),
+        "canonical scenario set changed — record it with LOS_UPDATE_GAS=1"
+    );
+}
+
+#[test]
+fn test_canonical_contracts_behave() {
+    use canonical::*;
+
+    // Token balances move and the event carries the amount
+    let (engine, transfer) = (SCENARIOS[1].prepare)();
+    let token = transfer.contract.clone();
+    let result = execute(&engine, transfer);
+    let contract = engine.get_contract(&token).unwrap();
+    assert_eq!(
+        contract.state.get(&format!("bal:{}", ALICE)).unwrap(),
+        "21935986000000"
+    );
+    assert_eq!(
+        contract.state.get(&format!("bal:{}", BOB)).unwrap(),
+        "250000000"
+    );
+    assert_eq!(result.events[0].event_type, "Transfer");
+    assert_eq!(
+        result.events[0].data.get("amount").map(String::as_str),
+        Some("250000000")
+    );
+
+    // Swap output follows x*y=k with the 0.3% fee
+    let (engine, swap) = (SCENARIOS[3].prepare)();
+    let out = execute(&engine, swap).output;
+    let (ra, rb, amount_in) = (100_000_000u128, 250_000_000u128, 1_000_000u128);
+    let expected = rb * amount_in * 997 / (ra * 1000 + amount_in * 997);
+    assert_eq!(out, expected.to_string());
+
+    // Storage scan reads back every byte written
+    let (engine, scan) = (SCENARIOS[5].prepare)();
+    assert_eq!(execute(&engine, scan).output, (64 * 32).to_string());
+
+    // Same inputs, same gas (metering is deterministic)
+    let again = measure_gas();
+    assert_eq!(again, measure_gas());
+}
+
+#[test]
+fn test_tracing_does_not_change_gas() {
+    for scenario in canonical::SCENARIOS {
+        let (engine, c) = (scenario.prepare)();
+        let traced = engine.dry_run(&c, true).expect("dry run");
+        let trace = traced.trace.expect("trace requested");
+        let applied = canonical::execute(&engine, c);
+        assert_eq!(
+            traced.result.gas_used, applied.gas_used,
+            "{}",
+            scenario.name
+        );
+        assert_eq!(
+            trace.compile_gas + trace.instruction_gas + trace.host_gas,
+            applied.gas_used,
+            "{}: trace does not add up",
+            scenario.name
+        );
+    }
+}
@@ -957,30 +957,54 @@ class ApiService {
     }
   }
 
+  /// Faucet contract of the testnet (los-contracts `faucet`), set with
+  /// --dart-define=LOS_FAUCET_CONTRACT=LOScon... Mainnet has no faucet.
+  static const String _faucetContract =
+      String.fromEnvironment('LOS_FAUCET_CONTRACT');
+
   // Request Faucet
+  // Calls drip(address) on the faucet contract. The call is node-signed
+  // (testnet /call-contract without a signature), so an empty wallet can
+  // claim; the contract enforces the drip amount and per-address cooldown.
   Future<Map<String, dynamic>> requestFaucet(String address) async {
-    losLog('🚠 [API] requestFaucet -> $baseUrl/faucet  address=$address');
+    losLog('🚠 [API] requestFaucet -> $_faucetContract.drip  address=$address');
+    if (_faucetContract.isEmpty) {
+      throw Exception('No faucet contract configured for this network');
+    }
     try {
       final response = await _requestWithFailover(
         (url) => _clientFor(url).post(
-          Uri.parse('$url/faucet'),
+          Uri.parse('$url/call-contract'),
           headers: {'Content-Type': 'application/json'},
-          body: json.encode({'address': address}),
+          body: json.encode({
+            'contract_address': _faucetContract,
+            'function': 'drip',
+            'args': [address],
+          }),
         ),
-        '/faucet',
+        '/call-contract',
       );
 
       final data = json.decode(response.body);
 
-      // Critical: Check BOTH status code AND response body status
-      // Backend returns 200 on success, >= 400 on errors (400 for validation, 429 for rate limit)
-      if (response.statusCode >= 400 || data['status'] == 'error') {
-        losLog('🚠 [API] faucet FAILED: ${data['msg']}');
-        throw Exception(data['msg'] ?? 'Faucet request failed');
+      // Check the HTTP status, the response status AND the contract result:
+      // a refused drip (cooldown, empty faucet) is a failed call
+      final result = data['result'];
+      if (response.statusCode >= 400 ||
+          data['status'] == 'error' ||
+          (result is Map && result['success'] == false)) {
+        String? reason;
+        if (result is Map && result['output'] is String) {
+          try {
+            reason = json.decode(result['output'])['message'] as String?;
+          } catch (_) {
+            reason = result['output'] as String;
+          }
+        }
+        throw Exception(reason ?? data['msg'] ?? 'Faucet request failed');
       }
 
-      losLog('🚠 [API] faucet SUCCESS: $data');
-      return data;
+      return {...data, 'msg': 'Faucet claimed successfully!'};
     } catch (e) {
       losLog('❌ requestFaucet error: $e');
       rethrow;
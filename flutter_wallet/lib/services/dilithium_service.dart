@@ -13,6 +13,9 @@ import 'package:ffi/ffi.dart';
 /// - Signature verification
 /// - LOS address derivation (Base58Check, identical to backend)
 /// - Address validation
+/// - Checkpoint and balance proof verification (light client)
+/// - Cancellable PoW mining and batch signing
+/// - Encrypted keystores (sign without exposing the secret key to Dart)
 ///
 /// Falls back to a "not available" state if the native library isn't compiled.
 /// Use [DilithiumService.isAvailable] to check before calling crypto functions.
@@ -45,6 +48,28 @@ class DilithiumService {
       _losHexToBytes;
   static late int Function(Pointer<Uint8>, int, int, int, int, Pointer<Uint64>,
       Pointer<Uint8>, int) _losMinePow;
+  static late int Function(Pointer<Uint8>, int, int, int, int, Pointer<Uint8>,
+          Pointer<Uint64>, Pointer<Uint64>, Pointer<Uint8>, int)
+      _losMinePowCancellable;
+  static late int Function(Pointer<Uint8>, int, Pointer<Int32>, int,
+      Pointer<Uint8>, int, Pointer<Uint8>, int) _losSignBatch;
+  static late int Function() _losMaxKeystoreBytes;
+  static late int Function(
+          Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>, int)
+      _losEncryptKeystore;
+  static late int Function(
+          Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>, int)
+      _losDecryptKeystore;
+  static late int Function(Pointer<Uint8>, int, Pointer<Uint8>, int,
+      Pointer<Uint8>, int, Pointer<Uint8>, int) _losSignWithKeystore;
+  static late int Function(Pointer<Uint8>, int) _losZeroize;
+  static late int Function(Pointer<Uint8>, int, Pointer<Uint8>, int)
+      _losVerifyCheckpoint;
+  static late int Function(Pointer<Uint8>, int, int, Pointer<Uint8>, int,
+      Pointer<Uint8>, int) _losVerifyAccountProof;
+  static late int Function(
+          Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>, int)
+      _losVerifyReserves;
 
   // Cached sizes
   static int _pkBytes = 0;
@@ -148,6 +173,70 @@ class DilithiumService {
           int Function(Pointer<Uint8>, int, int, int, int, Pointer<Uint64>,
               Pointer<Uint8>, int)>('los_mine_pow');
 
+      _losMinePowCancellable = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Int32, Uint32, Uint64,
+              Pointer<Uint8>, Pointer<Uint64>, Pointer<Uint64>, Pointer<Uint8>,
+              Int32),
+          int Function(
+              Pointer<Uint8>,
+              int,
+              int,
+              int,
+              int,
+              Pointer<Uint8>,
+              Pointer<Uint64>,
+              Pointer<Uint64>,
+              Pointer<Uint8>,
+              int)>('los_mine_pow_cancellable');
+
+      _losSignBatch = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Int32>, Int32,
+              Pointer<Uint8>, Int32, Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Int32>, int, Pointer<Uint8>,
+              int, Pointer<Uint8>, int)>('los_sign_batch');
+
+      _losMaxKeystoreBytes = _lib!
+          .lookupFunction<Int32 Function(), int Function()>(
+              'los_max_keystore_bytes');
+
+      _losEncryptKeystore = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Uint8>, Int32,
+              Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>,
+              int)>('los_encrypt_keystore');
+
+      _losDecryptKeystore = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Uint8>, Int32,
+              Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>,
+              int)>('los_decrypt_keystore');
+
+      _losSignWithKeystore = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Uint8>, Int32,
+              Pointer<Uint8>, Int32, Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>,
+              int, Pointer<Uint8>, int)>('los_sign_with_keystore');
+
+      _losZeroize = _lib!.lookupFunction<Int32 Function(Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int)>('los_zeroize');
+
+      _losVerifyCheckpoint = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Uint8>,
+              int)>('los_verify_checkpoint');
+
+      _losVerifyAccountProof = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Uint64, Pointer<Uint8>, Int32,
+              Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, int, Pointer<Uint8>, int,
+              Pointer<Uint8>, int)>('los_verify_account_proof');
+
+      _losVerifyReserves = _lib!.lookupFunction<
+          Int32 Function(Pointer<Uint8>, Int32, Pointer<Uint8>, Int32,
+              Pointer<Uint8>, Int32),
+          int Function(Pointer<Uint8>, int, Pointer<Uint8>, int, Pointer<Uint8>,
+              int)>('los_verify_reserves');
+
       // Query sizes
       _pkBytes = _losPublicKeyBytes();
       _skBytes = _losSecretKeyBytes();
@@ -536,6 +625,307 @@ class DilithiumService {
       calloc.free(hashPtr);
     }
   }
+
+  /// [minePow] that stops when [token] is cancelled (from any isolate) and
+  /// reports progress through it. Run it in a background isolate and pass
+  /// the token along; it holds only native addresses.
+  ///
+  /// Returns `{'work': nonce, 'hash': hexHash}` on success, null on failure
+  /// or cancellation.
+  static Map<String, dynamic>? minePowCancellable({
+    required Uint8List buffer,
+    required int workOffset,
+    required int difficultyBits,
+    required PowCancelToken token,
+    int maxIterations = 50000000,
+  }) {
+    if (!_available) return null;
+
+    final bufPtr = calloc<Uint8>(buffer.length);
+    final noncePtr = calloc<Uint64>(1);
+    final hashPtr = calloc<Uint8>(64);
+
+    try {
+      bufPtr.asTypedList(buffer.length).setAll(0, buffer);
+
+      final result = _losMinePowCancellable(
+        bufPtr,
+        buffer.length,
+        workOffset,
+        difficultyBits,
+        maxIterations,
+        Pointer<Uint8>.fromAddress(token.flagAddress),
+        Pointer<Uint64>.fromAddress(token.progressAddress),
+        noncePtr,
+        hashPtr,
+        64,
+      );
+
+      if (result < 0) {
+        losLog(result == -7
+            ? '⛏️ [DilithiumService.minePowCancellable] Cancelled'
+            : '⚠️ Native PoW failed with error: $result');
+        return null;
+      }
+      return {
+        'work': noncePtr.value,
+        'hash': String.fromCharCodes(hashPtr.asTypedList(result)),
+      };
+    } finally {
+      calloc.free(bufPtr);
+      calloc.free(noncePtr);
+      calloc.free(hashPtr);
+    }
+  }
+
+  /// Sign every message in [messages] with [secretKey] in one FFI call.
+  /// Signatures come back in message order.
+  static List<Uint8List> signBatch(
+      List<Uint8List> messages, Uint8List secretKey) {
+    if (!_available) {
+      throw StateError('Dilithium5 native library not available');
+    }
+    if (messages.isEmpty) return [];
+
+    final total = messages.fold<int>(0, (n, m) => n + m.length);
+    final msgPtr = calloc<Uint8>(total == 0 ? 1 : total);
+    final lensPtr = calloc<Int32>(messages.length);
+    final skPtr = calloc<Uint8>(secretKey.length);
+    final sigsLen = _sigBytes * messages.length;
+    final sigsPtr = calloc<Uint8>(sigsLen);
+
+    try {
+      var offset = 0;
+      for (var i = 0; i < messages.length; i++) {
+        msgPtr.asTypedList(total).setAll(offset, messages[i]);
+        lensPtr[i] = messages[i].length;
+        offset += messages[i].length;
+      }
+      skPtr.asTypedList(secretKey.length).setAll(0, secretKey);
+
+      final count = _losSignBatch(msgPtr, total, lensPtr, messages.length,
+          skPtr, secretKey.length, sigsPtr, sigsLen);
+      if (count < 0) {
+        throw StateError('Batch signing failed: error $count');
+      }
+      final sigs = sigsPtr.asTypedList(sigsLen);
+      return [
+        for (var i = 0; i < count; i++)
+          Uint8List.fromList(sigs.sublist(i * _sigBytes, (i + 1) * _sigBytes)),
+      ];
+    } finally {
+      // Zero secret key memory before freeing
+      skPtr.asTypedList(secretKey.length).fillRange(0, secretKey.length, 0);
+      calloc.free(msgPtr);
+      calloc.free(lensPtr);
+      calloc.free(skPtr);
+      calloc.free(sigsPtr);
+    }
+  }
+
+  /// Encrypt [secretKey] into a password-protected keystore (age/scrypt,
+  /// same format as the CLI and node). Store the keystore instead of the
+  /// raw key and sign with [signWithKeystore]. Takes about a second (scrypt).
+  static Uint8List encryptKeystore(Uint8List secretKey, String password) {
+    if (!_available) {
+      throw StateError('Dilithium5 native library not available');
+    }
+
+    final pw = utf8.encode(password);
+    final skPtr = _toNative(secretKey);
+    final pwPtr = _toNative(pw);
+    final outLen = _losMaxKeystoreBytes();
+    final outPtr = calloc<Uint8>(outLen);
+
+    try {
+      final len = _losEncryptKeystore(
+          skPtr, secretKey.length, pwPtr, pw.length, outPtr, outLen);
+      if (len < 0) {
+        throw StateError('Keystore encryption failed: error $len');
+      }
+      return Uint8List.fromList(outPtr.asTypedList(len));
+    } finally {
+      _losZeroize(skPtr, secretKey.length);
+      _losZeroize(pwPtr, pw.length);
+      calloc.free(skPtr);
+      calloc.free(pwPtr);
+      calloc.free(outPtr);
+    }
+  }
+
+  /// Decrypt a keystore back to the raw secret key (export / migration
+  /// only). Returns null on a wrong password. Wipe the result with
+  /// `fillRange(0, length, 0)` when done.
+  static Uint8List? decryptKeystore(Uint8List keystore, String password) {
+    if (!_available) {
+      throw StateError('Dilithium5 native library not available');
+    }
+
+    final pw = utf8.encode(password);
+    final ksPtr = _toNative(keystore);
+    final pwPtr = _toNative(pw);
+    final skPtr = calloc<Uint8>(_skBytes);
+
+    try {
+      final len = _losDecryptKeystore(
+          ksPtr, keystore.length, pwPtr, pw.length, skPtr, _skBytes);
+      if (len == -8) return null;
+      if (len < 0) {
+        throw StateError('Keystore decryption failed: error $len');
+      }
+      return Uint8List.fromList(skPtr.asTypedList(len));
+    } finally {
+      _losZeroize(pwPtr, pw.length);
+      _losZeroize(skPtr, _skBytes);
+      calloc.free(ksPtr);
+      calloc.free(pwPtr);
+      calloc.free(skPtr);
+    }
+  }
+
+  /// Sign [message] with the key inside [keystore]. The secret key is
+  /// decrypted and wiped inside native code and never reaches the Dart heap.
+  /// Returns null on a wrong password.
+  static Uint8List? signWithKeystore(
+      Uint8List message, Uint8List keystore, String password) {
+    if (!_available) {
+      throw StateError('Dilithium5 native library not available');
+    }
+
+    final pw = utf8.encode(password);
+    final msgPtr = _toNative(message);
+    final ksPtr = _toNative(keystore);
+    final pwPtr = _toNative(pw);
+    final sigPtr = calloc<Uint8>(_sigBytes);
+
+    try {
+      final sigLen = _losSignWithKeystore(msgPtr, message.length, ksPtr,
+          keystore.length, pwPtr, pw.length, sigPtr, _sigBytes);
+      if (sigLen == -8) return null;
+      if (sigLen < 0) {
+        throw StateError('Keystore signing failed: error $sigLen');
+      }
+      return Uint8List.fromList(sigPtr.asTypedList(sigLen));
+    } finally {
+      _losZeroize(pwPtr, pw.length);
+      calloc.free(msgPtr);
+      calloc.free(ksPtr);
+      calloc.free(pwPtr);
+      calloc.free(sigPtr);
+    }
+  }
+
+  /// Copy [bytes] into native memory (caller frees).
+  static Pointer<Uint8> _toNative(List<int> bytes) {
+    final ptr = calloc<Uint8>(bytes.isEmpty ? 1 : bytes.length);
+    ptr.asTypedList(bytes.length).setAll(0, bytes);
+    return ptr;
+  }
+
+  /// Verify a finalized checkpoint (one entry of `GET /checkpoints`) against
+  /// a trusted validator set (address → Dilithium5 public key hex).
+  ///
+  /// Returns the number of valid signers, or null if they are below the
+  /// 2f+1 quorum or the input is malformed.
+  static int? verifyCheckpoint(
+      String checkpointJson, Map<String, String> validators) {
+    if (!_available) return null;
+
+    final cp = utf8.encode(checkpointJson);
+    final vals = utf8.encode(jsonEncode(validators));
+    final cpPtr = _toNative(cp);
+    final valPtr = _toNative(vals);
+
+    try {
+      final result =
+          _losVerifyCheckpoint(cpPtr, cp.length, valPtr, vals.length);
+      losLog('🔑 [DilithiumService.verifyCheckpoint] Result: $result');
+      return result < 0 ? null : result;
+    } finally {
+      calloc.free(cpPtr);
+      calloc.free(valPtr);
+    }
+  }
+
+  /// Verify one account proof (an entry of `proof.accounts` from
+  /// `GET /proof/reserves`) against [accountRoot].
+  ///
+  /// Returns the proven balance in CIL, or null if the proof does not match.
+  static BigInt? verifyAccountProof(
+      String proofJson, int leafCount, String accountRoot) {
+    if (!_available) return null;
+
+    final proof = utf8.encode(proofJson);
+    final root = utf8.encode(accountRoot);
+    final proofPtr = _toNative(proof);
+    final rootPtr = _toNative(root);
+    final outPtr = calloc<Uint8>(64);
+
+    try {
+      final len = _losVerifyAccountProof(
+          proofPtr, proof.length, leafCount, rootPtr, root.length, outPtr, 64);
+      if (len < 0) return null;
+      return BigInt.parse(String.fromCharCodes(outPtr.asTypedList(len)));
+    } finally {
+      calloc.free(proofPtr);
+      calloc.free(rootPtr);
+      calloc.free(outPtr);
+    }
+  }
+
+  /// Verify a `GET /proof/reserves` response body. With [stateRoot] (from a
+  /// checkpoint checked by [verifyCheckpoint]) the response must have been
+  /// served at that checkpoint.
+  ///
+  /// Returns the proven total in CIL, or null if verification fails.
+  static BigInt? verifyReserves(String responseJson, {String? stateRoot}) {
+    if (!_available) return null;
+
+    final body = utf8.encode(responseJson);
+    final root = utf8.encode(stateRoot ?? '');
+    final bodyPtr = _toNative(body);
+    final rootPtr = _toNative(root);
+    final outPtr = calloc<Uint8>(64);
+
+    try {
+      final len = _losVerifyReserves(
+          bodyPtr, body.length, rootPtr, root.length, outPtr, 64);
+      if (len < 0) {
+        losLog('⚠️ Reserves proof rejected: error $len');
+        return null;
+      }
+      return BigInt.parse(String.fromCharCodes(outPtr.asTypedList(len)));
+    } finally {
+      calloc.free(bodyPtr);
+      calloc.free(rootPtr);
+      calloc.free(outPtr);
+    }
+  }
+}
+
+/// Cancellation flag and progress counter for
+/// [DilithiumService.minePowCancellable], in native memory so a mining
+/// isolate and the UI isolate can share them. Call [dispose] once mining
+/// has returned.
+class PowCancelToken {
+  final int flagAddress;
+  final int progressAddress;
+
+  PowCancelToken._(this.flagAddress, this.progressAddress);
+
+  factory PowCancelToken() => PowCancelToken._(
+      calloc<Uint8>(1).address, calloc<Uint64>(1).address);
+
+  /// Ask the miner to stop at its next batch boundary
+  void cancel() => Pointer<Uint8>.fromAddress(flagAddress).value = 1;
+
+  /// Nonces tried so far
+  int get progress => Pointer<Uint64>.fromAddress(progressAddress).value;
+
+  void dispose() {
+    calloc.free(Pointer<Uint8>.fromAddress(flagAddress));
+    calloc.free(Pointer<Uint64>.fromAddress(progressAddress));
+  }
 }
 
 /// Dilithium5 keypair container
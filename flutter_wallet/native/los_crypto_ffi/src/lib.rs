@@ -5,7 +5,10 @@
 //! - Message signing / verification
 //! - LOS address derivation (Base58Check, matching los-crypto backend)
 //! - Address validation
-//! - PoW mining (native SHA3-256, 100-1000x faster than pure Dart)
+//! - PoW mining (native SHA3-256, 100-1000x faster than pure Dart), cancellable
+//! - Batch signing (many messages, one FFI crossing)
+//! - Encrypted keystores (los-crypto age/scrypt) and signing from them
+//! - Checkpoint signature and account balance proof verification (los-light)
 //!
 //! All functions use pre-allocated buffers and return status codes.
 //! Return values: 0 or positive = success, negative = error.
@@ -21,7 +24,11 @@ use blake2::Blake2b512;
 use sha2::Sha256;
 use sha3::Sha3_256;
 use digest::Digest;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
+use los_light::{AccountProof, ReservesProof};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
 // SIZE QUERIES — Call these first to allocate correct buffer sizes in Dart
@@ -231,6 +238,74 @@ pub extern "C" fn los_sign(
     sig_bytes.len() as i32
 }
 
+/// Maximum messages per `los_sign_batch` call
+const MAX_SIGN_BATCH: i32 = 1024;
+
+/// Sign several messages (e.g. block signing hashes) with one secret key in
+/// a single FFI crossing.
+///
+/// # Arguments
+/// - `messages`:      All messages back to back
+/// - `message_lens`:  `count` lengths, one per message
+/// - `count`:         Number of messages (1..=1024)
+/// - `signatures_out`: Output: `count` signatures of `los_signature_bytes()`
+///   each, back to back in message order
+///
+/// # Returns
+/// Number of signatures written on success, negative on error:
+/// - -1: null pointer
+/// - -2: buffer too small
+/// - -3: invalid secret key
+/// - -4: bad count, message lengths, key length or capacity
+#[no_mangle]
+pub extern "C" fn los_sign_batch(
+    messages: *const u8,
+    messages_len: i32,
+    message_lens: *const i32,
+    count: i32,
+    secret_key: *const u8,
+    sk_len: i32,
+    signatures_out: *mut u8,
+    sigs_capacity: i32,
+) -> i32 {
+    if messages.is_null() || message_lens.is_null() || secret_key.is_null() || signatures_out.is_null() {
+        return -1;
+    }
+    if count <= 0 || count > MAX_SIGN_BATCH || messages_len < 0 || sigs_capacity < 0
+        || sk_len as usize != dilithium5::secret_key_bytes()
+    {
+        return -4;
+    }
+
+    let sig_size = dilithium5::signature_bytes();
+    if (sigs_capacity as usize) < sig_size * count as usize {
+        return -2;
+    }
+
+    let lens = unsafe { std::slice::from_raw_parts(message_lens, count as usize) };
+    if lens.iter().any(|&l| l < 0) || lens.iter().map(|&l| l as i64).sum::<i64>() != messages_len as i64 {
+        return -4;
+    }
+
+    let all = unsafe { std::slice::from_raw_parts(messages, messages_len as usize) };
+    let sk_slice = unsafe { std::slice::from_raw_parts(secret_key, sk_len as usize) };
+    let sk = match DilithiumSecretKey::from_bytes(sk_slice) {
+        Ok(k) => k,
+        Err(_) => return -3,
+    };
+
+    let out = unsafe { std::slice::from_raw_parts_mut(signatures_out, sig_size * count as usize) };
+    let mut offset = 0usize;
+    for (i, &len) in lens.iter().enumerate() {
+        let msg = &all[offset..offset + len as usize];
+        offset += len as usize;
+        let signature = detached_sign(msg, &sk);
+        out[i * sig_size..(i + 1) * sig_size].copy_from_slice(signature.as_bytes());
+    }
+
+    count
+}
+
 /// Verify a Dilithium5 signature.
 ///
 /// # Returns
@@ -279,6 +354,30 @@ pub extern "C" fn los_verify(
 /// "LOS" + Base58( 0x4A | BLAKE2b-160(pubkey) | SHA256²(payload)[0..4] )
 const VERSION_BYTE: u8 = 0x4A; // 74 = LOS identifier
 
+fn derive_address(public_key: &[u8]) -> String {
+    // 1. BLAKE2b-512 hash, take first 20 bytes (160-bit)
+    let mut hasher = Blake2b512::new();
+    hasher.update(public_key);
+    let hash_result = hasher.finalize();
+    let pubkey_hash = &hash_result[..20];
+
+    // 2. Payload: version_byte + pubkey_hash
+    let mut payload = vec![VERSION_BYTE];
+    payload.extend_from_slice(pubkey_hash);
+
+    // 3. Checksum: SHA256(SHA256(payload)) first 4 bytes
+    let hash1 = Sha256::digest(&payload);
+    let hash2 = Sha256::digest(&hash1);
+    let checksum = &hash2[..4];
+
+    // 4. Full encoded bytes: payload + checksum = 25 bytes
+    let mut address_bytes = payload;
+    address_bytes.extend_from_slice(checksum);
+
+    // 5. Base58 encode, 6. "LOS" prefix
+    format!("LOS{}", bs58::encode(&address_bytes).into_string())
+}
+
 /// Derive LOS address from Dilithium5 public key.
 /// Exact same algorithm as los-crypto::public_key_to_address().
 ///
@@ -299,30 +398,7 @@ pub extern "C" fn los_public_key_to_address(
 
     let pk_slice = unsafe { std::slice::from_raw_parts(public_key, pk_len as usize) };
 
-    // 1. BLAKE2b-512 hash, take first 20 bytes (160-bit)
-    let mut hasher = Blake2b512::new();
-    hasher.update(pk_slice);
-    let hash_result = hasher.finalize();
-    let pubkey_hash = &hash_result[..20];
-
-    // 2. Payload: version_byte + pubkey_hash
-    let mut payload = vec![VERSION_BYTE];
-    payload.extend_from_slice(pubkey_hash);
-
-    // 3. Checksum: SHA256(SHA256(payload)) first 4 bytes
-    let hash1 = Sha256::digest(&payload);
-    let hash2 = Sha256::digest(&hash1);
-    let checksum = &hash2[..4];
-
-    // 4. Full encoded bytes: payload + checksum = 25 bytes
-    let mut address_bytes = payload;
-    address_bytes.extend_from_slice(checksum);
-
-    // 5. Base58 encode
-    let base58_addr = bs58::encode(&address_bytes).into_string();
-
-    // 6. "LOS" prefix
-    let full_address = format!("LOS{}", base58_addr);
+    let full_address = derive_address(pk_slice);
     let addr_bytes = full_address.as_bytes();
 
     if (addr_capacity as usize) < addr_bytes.len() {
@@ -442,6 +518,30 @@ pub extern "C" fn los_mine_pow(
 
     let buf = unsafe { std::slice::from_raw_parts_mut(buffer, buf_len) };
 
+    match mine_pow(buf, w_off, difficulty_bits, max_iterations, std::ptr::null(), std::ptr::null_mut()) {
+        Ok((nonce, hash)) => write_pow_result(nonce, &hash, nonce_out, hash_out),
+        Err(code) => code,
+    }
+}
+
+/// Nonces tried between two polls of the cancellation flag
+const POW_BATCH: u64 = 16_384;
+
+/// Search nonces `0..max_iterations`. Between batches of [`POW_BATCH`]
+/// nonces it publishes progress and stops with -7 once `cancel_flag` is
+/// nonzero (both pointers may be null). -5 if nothing was found.
+fn mine_pow(
+    buf: &mut [u8],
+    w_off: usize,
+    difficulty_bits: u32,
+    max_iterations: u64,
+    cancel_flag: *const u8,
+    progress_out: *mut u64,
+) -> Result<(u64, [u8; 32]), i32> {
+    // Another isolate flips the flag / reads progress while we run
+    let cancel = unsafe { (cancel_flag as *const AtomicU8).as_ref() };
+    let progress = unsafe { (progress_out as *const AtomicU64).as_ref() };
+
     // Precompute difficulty check parameters
     let full_zero_bytes = (difficulty_bits / 8) as usize;
     let remaining_bits = difficulty_bits % 8;
@@ -452,6 +552,15 @@ pub extern "C" fn los_mine_pow(
     };
 
     for nonce in 0u64..max_iterations {
+        if nonce % POW_BATCH == 0 {
+            if let Some(p) = progress {
+                p.store(nonce, Ordering::Relaxed);
+            }
+            if cancel.is_some_and(|c| c.load(Ordering::Relaxed) != 0) {
+                return Err(-7);
+            }
+        }
+
         // Write nonce as u64 LE into the work field
         let nonce_bytes = nonce.to_le_bytes();
         buf[w_off..w_off + 8].copy_from_slice(&nonce_bytes);
@@ -474,19 +583,517 @@ pub extern "C" fn los_mine_pow(
         }
 
         if valid {
-            // Write outputs
-            unsafe { *nonce_out = nonce; }
-
-            let hex_string = hex::encode(hash);
-            let hex_bytes = hex_string.as_bytes();
-            unsafe {
-                std::ptr::copy_nonoverlapping(hex_bytes.as_ptr(), hash_out, hex_bytes.len());
+            if let Some(p) = progress {
+                p.store(nonce + 1, Ordering::Relaxed);
             }
-            return hex_bytes.len() as i32;
+            return Ok((nonce, hash.into()));
+        }
+    }
+
+    if let Some(p) = progress {
+        p.store(max_iterations, Ordering::Relaxed);
+    }
+    Err(-5) // PoW not found
+}
+
+fn write_pow_result(nonce: u64, hash: &[u8; 32], nonce_out: *mut u64, hash_out: *mut u8) -> i32 {
+    unsafe { *nonce_out = nonce; }
+
+    let hex_string = hex::encode(hash);
+    let hex_bytes = hex_string.as_bytes();
+    unsafe {
+        std::ptr::copy_nonoverlapping(hex_bytes.as_ptr(), hash_out, hex_bytes.len());
+    }
+    hex_bytes.len() as i32
+}
+
+/// `los_mine_pow` that can be stopped and observed from another isolate.
+///
+/// Dart allocates a 1-byte cancellation flag and a u64 progress counter in
+/// native memory and passes their addresses to the mining isolate. Every
+/// 16,384 nonces the miner stores the number of nonces tried so far into
+/// `progress_out` and returns -7 if `cancel_flag` is nonzero.
+///
+/// # Arguments
+/// As `los_mine_pow`, plus:
+/// - `cancel_flag`:  Set to nonzero to stop mining (may be null)
+/// - `progress_out`: Nonces tried so far, updated between batches (may be null)
+///
+/// # Returns
+/// As `los_mine_pow`, plus:
+/// - -7: cancelled
+#[no_mangle]
+pub extern "C" fn los_mine_pow_cancellable(
+    buffer: *mut u8,
+    buffer_len: i32,
+    work_offset: i32,
+    difficulty_bits: u32,
+    max_iterations: u64,
+    cancel_flag: *const u8,
+    progress_out: *mut u64,
+    nonce_out: *mut u64,
+    hash_out: *mut u8,
+    hash_capacity: i32,
+) -> i32 {
+    if buffer.is_null() || nonce_out.is_null() || hash_out.is_null() {
+        return -1;
+    }
+    if (hash_capacity as usize) < 64 {
+        return -2;
+    }
+    let buf_len = buffer_len as usize;
+    let w_off = work_offset as usize;
+    if w_off + 8 > buf_len {
+        return -6;
+    }
+
+    let buf = unsafe { std::slice::from_raw_parts_mut(buffer, buf_len) };
+
+    match mine_pow(buf, w_off, difficulty_bits, max_iterations, cancel_flag, progress_out) {
+        Ok((nonce, hash)) => write_pow_result(nonce, &hash, nonce_out, hash_out),
+        Err(code) => code,
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// LIGHT-CLIENT VERIFICATION — Checkpoints and balance proofs (los-light)
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+/// One entry of GET /checkpoints (signatures hex-encoded)
+#[derive(Deserialize)]
+struct CheckpointJson {
+    height: u64,
+    block_hash: String,
+    state_root: String,
+    validator_count: u32,
+    #[serde(default)]
+    signatures: Vec<CheckpointSignatureJson>,
+}
+
+#[derive(Deserialize)]
+struct CheckpointSignatureJson {
+    validator_address: String,
+    signature: String,
+}
+
+/// The `checkpoint` summary of a GET /proof/reserves response
+#[derive(Deserialize)]
+struct ReservesCheckpointJson {
+    state_root: String,
+}
+
+/// GET /proof/reserves response (only the fields verified here)
+#[derive(Deserialize)]
+struct ReservesResponseJson {
+    #[serde(default)]
+    checkpoint: Option<ReservesCheckpointJson>,
+    proof: ReservesProof,
+}
+
+/// Same bytes as los-consensus FinalityCheckpoint::signing_data():
+/// height (LE) || block_hash (UTF-8) || state_root (UTF-8)
+fn checkpoint_signing_data(checkpoint: &CheckpointJson) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + checkpoint.block_hash.len() + checkpoint.state_root.len());
+    data.extend_from_slice(&checkpoint.height.to_le_bytes());
+    data.extend_from_slice(checkpoint.block_hash.as_bytes());
+    data.extend_from_slice(checkpoint.state_root.as_bytes());
+    data
+}
+
+/// BFT quorum 2f+1 with f = (n-1)/3, as FinalityCheckpoint::verify_quorum()
+fn checkpoint_quorum(validators: u64) -> u64 {
+    if validators <= 1 {
+        1
+    } else {
+        2 * ((validators - 1) / 3) + 1
+    }
+}
+
+/// Borrow a UTF-8 string from a Dart buffer (None for null or invalid UTF-8)
+fn ffi_str<'a>(ptr: *const u8, len: i32) -> Option<&'a str> {
+    if ptr.is_null() || len < 0 {
+        return None;
+    }
+    let slice = unsafe { std::slice::from_raw_parts(ptr, len as usize) };
+    std::str::from_utf8(slice).ok()
+}
+
+/// Copy `value` into a Dart buffer. Returns its length, -2 if it does not
+/// fit, or -3 for a null buffer or negative capacity.
+fn write_str_out(value: &str, out: *mut u8, capacity: i32) -> i32 {
+    if capacity < 0 || out.is_null() {
+        return -3;
+    }
+    let bytes = value.as_bytes();
+    if (capacity as usize) < bytes.len() {
+        return -2;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    }
+    bytes.len() as i32
+}
+
+/// Verify a finalized checkpoint against a trusted validator set.
+///
+/// A signature counts only if its signer is in the trusted set, the trusted
+/// public key derives to that address, and the Dilithium5 signature over the
+/// checkpoint's signing data verifies. Each validator counts once. The
+/// quorum is 2f+1 of the larger of the checkpoint's `validator_count` and
+/// the trusted set, so a node cannot lower it by under-reporting.
+///
+/// # Arguments
+/// - `checkpoint_json`: One entry of GET /checkpoints
+/// - `validators_json`: Trusted set as `{"LOS…": "<public key hex>", …}`
+///
+/// # Returns
+/// Number of valid signers (>= quorum) on success, negative on error:
+/// - -1: null pointer
+/// - -3: malformed JSON
+/// - -5: valid signatures below quorum
+#[no_mangle]
+pub extern "C" fn los_verify_checkpoint(
+    checkpoint_json: *const u8,
+    checkpoint_len: i32,
+    validators_json: *const u8,
+    validators_len: i32,
+) -> i32 {
+    if checkpoint_json.is_null() || validators_json.is_null() {
+        return -1;
+    }
+    let (Some(cp_str), Some(val_str)) = (
+        ffi_str(checkpoint_json, checkpoint_len),
+        ffi_str(validators_json, validators_len),
+    ) else {
+        return -3;
+    };
+    let checkpoint: CheckpointJson = match serde_json::from_str(cp_str) {
+        Ok(c) => c,
+        Err(_) => return -3,
+    };
+    let validators: BTreeMap<String, String> = match serde_json::from_str(val_str) {
+        Ok(v) => v,
+        Err(_) => return -3,
+    };
+
+    let signing_data = checkpoint_signing_data(&checkpoint);
+    let mut signers: HashSet<&str> = HashSet::new();
+    for sig in &checkpoint.signatures {
+        let address = sig.validator_address.as_str();
+        if signers.contains(address) {
+            continue;
+        }
+        let Some(pk_bytes) = validators.get(address).and_then(|pk| hex::decode(pk).ok()) else {
+            continue;
+        };
+        if derive_address(&pk_bytes) != address {
+            continue;
         }
+        let (Ok(pk), Some(signature)) = (
+            DilithiumPublicKey::from_bytes(&pk_bytes),
+            hex::decode(&sig.signature)
+                .ok()
+                .and_then(|b| DilithiumSignature::from_bytes(&b).ok()),
+        ) else {
+            continue;
+        };
+        if verify_detached_signature(&signature, &signing_data, &pk).is_ok() {
+            signers.insert(address);
+        }
+    }
+
+    let n = (checkpoint.validator_count as u64).max(validators.len() as u64);
+    if (signers.len() as u64) < checkpoint_quorum(n) {
+        return -5;
+    }
+    signers.len() as i32
+}
+
+/// Verify one account's balance proof against an account Merkle root.
+///
+/// # Arguments
+/// - `proof_json`: One entry of `proof.accounts` from GET /proof/reserves
+/// - `leaf_count`: `proof.leaf_count` of the same response
+/// - `account_root`: Hex account root (`proof.account_root`)
+/// - `balance_out`: Output: proven balance in CIL, decimal string
+///
+/// # Returns
+/// Balance string length on success, negative on error:
+/// - -1: null pointer
+/// - -2: balance_out buffer too small
+/// - -3: malformed JSON or negative capacity
+/// - -4: proof does not match the root
+#[no_mangle]
+pub extern "C" fn los_verify_account_proof(
+    proof_json: *const u8,
+    proof_len: i32,
+    leaf_count: u64,
+    account_root: *const u8,
+    root_len: i32,
+    balance_out: *mut u8,
+    balance_capacity: i32,
+) -> i32 {
+    if proof_json.is_null() || account_root.is_null() || balance_out.is_null() {
+        return -1;
     }
+    let (Some(proof_str), Some(root)) = (
+        ffi_str(proof_json, proof_len),
+        ffi_str(account_root, root_len),
+    ) else {
+        return -3;
+    };
+    let proof: AccountProof = match serde_json::from_str(proof_str) {
+        Ok(p) => p,
+        Err(_) => return -3,
+    };
+    if los_light::verify_account_proof(&proof, leaf_count, root).is_err() {
+        return -4;
+    }
+    write_str_out(&proof.balance_cil.to_string(), balance_out, balance_capacity)
+}
 
-    -5 // PoW not found
+/// Verify a whole GET /proof/reserves response with los-light.
+///
+/// Every account proof must match `proof.account_root` and the stated total
+/// must add up. When `state_root` is given (a checkpoint root already checked
+/// with `los_verify_checkpoint`), the response must have been served
+/// `at_checkpoint` with that same root. Checkpoints sign the combined state
+/// root, not the account root, so that last link is asserted by the node.
+///
+/// # Arguments
+/// - `response_json`: Body of GET /proof/reserves
+/// - `state_root`: Trusted checkpoint state root, or null / length 0 for none
+/// - `total_out`: Output: proven total in CIL, decimal string
+///
+/// # Returns
+/// Total string length on success, negative on error:
+/// - -1: null pointer
+/// - -2: total_out buffer too small
+/// - -3: malformed JSON or negative capacity
+/// - -4: proof does not verify, or was not served at `state_root`
+#[no_mangle]
+pub extern "C" fn los_verify_reserves(
+    response_json: *const u8,
+    response_len: i32,
+    state_root: *const u8,
+    state_root_len: i32,
+    total_out: *mut u8,
+    total_capacity: i32,
+) -> i32 {
+    if response_json.is_null() || total_out.is_null() {
+        return -1;
+    }
+    let Some(body) = ffi_str(response_json, response_len) else {
+        return -3;
+    };
+    let response: ReservesResponseJson = match serde_json::from_str(body) {
+        Ok(r) => r,
+        Err(_) => return -3,
+    };
+    if !state_root.is_null() && state_root_len > 0 {
+        let Some(trusted) = ffi_str(state_root, state_root_len) else {
+            return -3;
+        };
+        match &response.checkpoint {
+            Some(cp) if cp.state_root.eq_ignore_ascii_case(trusted) => {}
+            _ => return -4,
+        }
+    }
+    match los_light::verify_reserves(&response.proof, None) {
+        Ok(total) => write_str_out(&total.to_string(), total_out, total_capacity),
+        Err(_) => -4,
+    }
+}
+
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+// KEYSTORE — age (scrypt) encrypted secret keys via los-crypto
+// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+//
+// The keystore is the raw age ciphertext of the secret key
+// (los_crypto::EncryptedKey::ciphertext), so it is portable between the
+// wallet, the CLI and the node. Signing straight from a keystore decrypts
+// into zeroized native memory and never hands the secret key to Dart.
+
+/// age header + scrypt stanza + MAC + per-chunk tag stay well under this
+const KEYSTORE_OVERHEAD: usize = 1024;
+
+/// Returns the buffer size to allocate for `los_encrypt_keystore` output
+#[no_mangle]
+pub extern "C" fn los_max_keystore_bytes() -> i32 {
+    (dilithium5::secret_key_bytes() + KEYSTORE_OVERHEAD) as i32
+}
+
+/// Decrypt `keystore` with `password` into zeroize-on-drop memory.
+/// Err is the FFI status: -3 malformed input, -8 wrong password.
+fn open_keystore(
+    keystore: *const u8,
+    keystore_len: i32,
+    password: *const u8,
+    password_len: i32,
+) -> Result<Zeroizing<Vec<u8>>, i32> {
+    if keystore_len < 0 || password_len < 0 {
+        return Err(-3);
+    }
+    let ks_slice = unsafe { std::slice::from_raw_parts(keystore, keystore_len as usize) };
+    let Some(password) = ffi_str(password, password_len) else {
+        return Err(-3);
+    };
+    let encrypted = los_crypto::EncryptedKey {
+        ciphertext: ks_slice.to_vec(),
+        version: 1,
+        salt: vec![],
+        public_key: vec![],
+    };
+    match los_crypto::decrypt_private_key(&encrypted, password) {
+        Ok(sk) => Ok(Zeroizing::new(sk)),
+        Err(los_crypto::CryptoError::InvalidPassword) => Err(-8),
+        Err(_) => Err(-3),
+    }
+}
+
+/// Encrypt a secret key into a password-protected keystore (age, scrypt).
+///
+/// # Arguments
+/// - `secret_key`: Raw Dilithium5 secret key
+/// - `password`: UTF-8 password
+/// - `keystore_out`: Output buffer (>= los_max_keystore_bytes())
+///
+/// # Returns
+/// Keystore length on success, negative on error:
+/// - -1: null pointer
+/// - -2: buffer too small
+/// - -3: invalid secret key, non-UTF-8 password or negative capacity
+/// - -9: encryption failed
+#[no_mangle]
+pub extern "C" fn los_encrypt_keystore(
+    secret_key: *const u8,
+    sk_len: i32,
+    password: *const u8,
+    password_len: i32,
+    keystore_out: *mut u8,
+    keystore_capacity: i32,
+) -> i32 {
+    if secret_key.is_null() || password.is_null() || keystore_out.is_null() {
+        return -1;
+    }
+    if keystore_capacity < 0 {
+        return -3;
+    }
+    if sk_len as usize != dilithium5::secret_key_bytes() {
+        return -3;
+    }
+    let sk_slice = unsafe { std::slice::from_raw_parts(secret_key, sk_len as usize) };
+    let Some(password) = ffi_str(password, password_len) else {
+        return -3;
+    };
+    if DilithiumSecretKey::from_bytes(sk_slice).is_err() {
+        return -3;
+    }
+
+    let encrypted = match los_crypto::encrypt_private_key(sk_slice, password) {
+        Ok(e) => e,
+        Err(_) => return -9,
+    };
+    let bytes = &encrypted.ciphertext;
+    if (keystore_capacity as usize) < bytes.len() {
+        return -2;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), keystore_out, bytes.len());
+    }
+    bytes.len() as i32
+}
+
+/// Decrypt a keystore back to the raw secret key (e.g. for export). Prefer
+/// `los_sign_with_keystore`, which never exposes the key; wipe `sk_out`
+/// with `los_zeroize` as soon as it is no longer needed.
+///
+/// # Returns
+/// Secret key length on success, negative on error:
+/// - -1: null pointer
+/// - -2: buffer too small
+/// - -3: malformed keystore, non-UTF-8 password or negative capacity
+/// - -8: wrong password
+#[no_mangle]
+pub extern "C" fn los_decrypt_keystore(
+    keystore: *const u8,
+    keystore_len: i32,
+    password: *const u8,
+    password_len: i32,
+    sk_out: *mut u8,
+    sk_capacity: i32,
+) -> i32 {
+    if keystore.is_null() || password.is_null() || sk_out.is_null() {
+        return -1;
+    }
+    if sk_capacity < 0 {
+        return -3;
+    }
+    let sk = match open_keystore(keystore, keystore_len, password, password_len) {
+        Ok(sk) => sk,
+        Err(code) => return code,
+    };
+    if (sk_capacity as usize) < sk.len() {
+        return -2;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(sk.as_ptr(), sk_out, sk.len());
+    }
+    sk.len() as i32
+}
+
+/// Sign a message with the key inside an encrypted keystore. The secret key
+/// exists only in native memory for the duration of the call and is
+/// zeroized before returning.
+///
+/// # Returns
+/// Signature length on success, negative on error:
+/// - -1: null pointer
+/// - -2: buffer too small
+/// - -3: malformed keystore, invalid key, non-UTF-8 password or negative capacity
+/// - -8: wrong password
+#[no_mangle]
+pub extern "C" fn los_sign_with_keystore(
+    message: *const u8,
+    message_len: i32,
+    keystore: *const u8,
+    keystore_len: i32,
+    password: *const u8,
+    password_len: i32,
+    signature_out: *mut u8,
+    sig_capacity: i32,
+) -> i32 {
+    if message.is_null() || keystore.is_null() || password.is_null() || signature_out.is_null() {
+        return -1;
+    }
+    if sig_capacity < 0 {
+        return -3;
+    }
+    if (sig_capacity as usize) < dilithium5::signature_bytes() {
+        return -2;
+    }
+    let sk = match open_keystore(keystore, keystore_len, password, password_len) {
+        Ok(sk) => sk,
+        Err(code) => return code,
+    };
+    los_sign(message, message_len, sk.as_ptr(), sk.len() as i32, signature_out, sig_capacity)
+}
+
+/// Overwrite `len` bytes at `ptr` with zeros (volatile, not optimized away).
+/// For wiping native buffers that held secret keys or passwords.
+///
+/// # Returns
+/// 0 on success, -1 on null pointer
+#[no_mangle]
+pub extern "C" fn los_zeroize(ptr: *mut u8, len: i32) -> i32 {
+    if ptr.is_null() {
+        return -1;
+    }
+    if len > 0 {
+        let buf = unsafe { std::slice::from_raw_parts_mut(ptr, len as usize) };
+        buf.zeroize();
+    }
+    0
 }
 
 // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
@@ -824,4 +1431,320 @@ mod tests {
         // Verify it's fast — should be under 2 seconds for 16-bit difficulty
         assert!(elapsed.as_secs() < 5, "PoW took too long: {:?}", elapsed);
     }
+
+    fn seeded_keypair(byte: u8) -> (Vec<u8>, Vec<u8>) {
+        let pk_size = los_public_key_bytes() as usize;
+        let sk_size = los_secret_key_bytes() as usize;
+        let seed = [byte; 64];
+        let mut pk = vec![0u8; pk_size];
+        let mut sk = vec![0u8; sk_size];
+        los_generate_keypair_from_seed(
+            seed.as_ptr(), 64,
+            pk.as_mut_ptr(), pk_size as i32,
+            sk.as_mut_ptr(), sk_size as i32,
+        );
+        (pk, sk)
+    }
+
+    fn checkpoint_json(state_root: &str, signers: &[(Vec<u8>, Vec<u8>)]) -> String {
+        let mut data = 4000u64.to_le_bytes().to_vec();
+        data.extend_from_slice(b"ab12");
+        data.extend_from_slice(b"cd34");
+        let sk_size = los_secret_key_bytes() as usize;
+        let signatures: Vec<String> = signers
+            .iter()
+            .map(|(pk, sk)| {
+                let sk = DilithiumSecretKey::from_bytes(&sk[..sk_size]).unwrap();
+                let sig = detached_sign(&data, &sk);
+                format!(
+                    r#"{{"validator_address":"{}","signature":"{}"}}"#,
+                    derive_address(pk),
+                    hex::encode(sig.as_bytes())
+                )
+            })
+            .collect();
+        format!(
+            r#"{{"id":"x","height":4000,"block_hash":"ab12","state_root":"{}","timestamp":1,"validator_count":4,"signature_count":{},"signatures":[{}]}}"#,
+            state_root,
+            signers.len(),
+            signatures.join(",")
+        )
+    }
+
+    #[test]
+    fn test_verify_checkpoint_quorum() {
+        let keys: Vec<_> = (1..=5u8).map(seeded_keypair).collect();
+        // Trusted set: the first four
+        let validators = format!(
+            "{{{}}}",
+            keys[..4]
+                .iter()
+                .map(|(pk, _)| format!(r#""{}":"{}""#, derive_address(pk), hex::encode(pk)))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let verify = |cp: &str| {
+            los_verify_checkpoint(
+                cp.as_ptr(), cp.len() as i32,
+                validators.as_ptr(), validators.len() as i32,
+            )
+        };
+
+        assert_eq!(verify(&checkpoint_json("cd34", &keys[..3])), 3);
+        // 2 of 4 is below 2f+1 = 3
+        assert_eq!(verify(&checkpoint_json("cd34", &keys[..2])), -5);
+        // An outsider and a repeated signer add nothing
+        let padded = vec![keys[0].clone(), keys[0].clone(), keys[1].clone(), keys[4].clone()];
+        assert_eq!(verify(&checkpoint_json("cd34", &padded)), -5);
+        // Signatures over a different state root do not verify
+        assert_eq!(verify(&checkpoint_json("ffff", &keys[..4])), -5);
+        assert_eq!(verify("not json"), -3);
+    }
+
+    #[test]
+    fn test_verify_balance_proofs() {
+        let accounts: Vec<(String, u128)> =
+            (0..5).map(|i| (format!("LOSW{:03}", i), (i as u128 + 1) * 1_000)).collect();
+        let tree = los_light::AccountTree::new(
+            accounts
+                .iter()
+                .map(|(a, b)| los_light::account_leaf_hash(a, *b, 1, "head"))
+                .collect(),
+        );
+        let root = hex::encode(tree.root());
+        let proofs: Vec<AccountProof> = [1usize, 4]
+            .iter()
+            .map(|&i| AccountProof {
+                address: accounts[i].0.clone(),
+                balance_cil: accounts[i].1,
+                block_count: 1,
+                head: "head".to_string(),
+                leaf_index: i as u64,
+                siblings: tree.proof(i).unwrap().iter().map(hex::encode).collect(),
+            })
+            .collect();
+
+        let mut out = [0u8; 64];
+        let one = serde_json::to_string(&proofs[0]).unwrap();
+        let len = los_verify_account_proof(
+            one.as_ptr(), one.len() as i32, 5,
+            root.as_ptr(), root.len() as i32,
+            out.as_mut_ptr(), 64,
+        );
+        assert_eq!(std::str::from_utf8(&out[..len as usize]).unwrap(), "2000");
+        let other_root = "00".repeat(32);
+        let bad = los_verify_account_proof(
+            one.as_ptr(), one.len() as i32, 5,
+            other_root.as_ptr(), other_root.len() as i32,
+            out.as_mut_ptr(), 64,
+        );
+        assert_eq!(bad, -4);
+        let negative = los_verify_account_proof(
+            one.as_ptr(), one.len() as i32, 5,
+            root.as_ptr(), root.len() as i32,
+            out.as_mut_ptr(), -1,
+        );
+        assert_eq!(negative, -3);
+
+        let response = format!(
+            r#"{{"status":"success","checkpoint":{{"id":"9f","height":4000,"state_root":"A71E","signature_count":3}},"proof":{}}}"#,
+            serde_json::to_string(&ReservesProof {
+                account_root: root.clone(),
+                leaf_count: 5,
+                total_cil: 7_000,
+                accounts: proofs,
+            })
+            .unwrap()
+        );
+        let reserves = |state_root: &str, out: &mut [u8; 64]| {
+            los_verify_reserves(
+                response.as_ptr(), response.len() as i32,
+                state_root.as_ptr(), state_root.len() as i32,
+                out.as_mut_ptr(), 64,
+            )
+        };
+        let len = reserves("a71e", &mut out);
+        assert_eq!(std::str::from_utf8(&out[..len as usize]).unwrap(), "7000");
+        assert_eq!(reserves("", &mut out), 4);
+        // Served at a different checkpoint than the one the wallet trusts
+        assert_eq!(reserves("b000", &mut out), -4);
+        let negative = los_verify_reserves(
+            response.as_ptr(), response.len() as i32,
+            std::ptr::null(), 0,
+            out.as_mut_ptr(), -1,
+        );
+        assert_eq!(negative, -3);
+    }
+
+    #[test]
+    fn test_sign_batch() {
+        let (pk, sk) = seeded_keypair(11);
+        let sig_size = los_signature_bytes() as usize;
+        let msgs: [&[u8]; 3] = [b"hash-one", b"", b"a much longer signing hash"];
+        let all: Vec<u8> = msgs.concat();
+        let lens: Vec<i32> = msgs.iter().map(|m| m.len() as i32).collect();
+        let mut sigs = vec![0u8; sig_size * 3];
+
+        let n = los_sign_batch(
+            all.as_ptr(), all.len() as i32,
+            lens.as_ptr(), 3,
+            sk.as_ptr(), sk.len() as i32,
+            sigs.as_mut_ptr(), sigs.len() as i32,
+        );
+        assert_eq!(n, 3);
+        for (i, msg) in msgs.iter().enumerate() {
+            let sig = &sigs[i * sig_size..(i + 1) * sig_size];
+            let valid = los_verify(
+                msg.as_ptr(), msg.len() as i32,
+                sig.as_ptr(), sig_size as i32,
+                pk.as_ptr(), pk.len() as i32,
+            );
+            assert_eq!(valid, 1, "signature {} must verify", i);
+        }
+
+        // Lengths must cover the buffer exactly; output must fit every signature
+        let bad_lens = [8, 0, 5];
+        assert_eq!(los_sign_batch(
+            all.as_ptr(), all.len() as i32, bad_lens.as_ptr(), 3,
+            sk.as_ptr(), sk.len() as i32, sigs.as_mut_ptr(), sigs.len() as i32,
+        ), -4);
+        assert_eq!(los_sign_batch(
+            all.as_ptr(), all.len() as i32, lens.as_ptr(), 3,
+            sk.as_ptr(), sk.len() as i32, sigs.as_mut_ptr(), (sig_size * 2) as i32,
+        ), -2);
+        // Negative capacity or a truncated key never reach the raw slices
+        assert_eq!(los_sign_batch(
+            all.as_ptr(), all.len() as i32, lens.as_ptr(), 3,
+            sk.as_ptr(), sk.len() as i32, sigs.as_mut_ptr(), -1,
+        ), -4);
+        assert_eq!(los_sign_batch(
+            all.as_ptr(), all.len() as i32, lens.as_ptr(), 3,
+            sk.as_ptr(), sk.len() as i32 - 1, sigs.as_mut_ptr(), sigs.len() as i32,
+        ), -4);
+        assert_eq!(los_sign_batch(
+            all.as_ptr(), all.len() as i32, lens.as_ptr(), 3,
+            sk.as_ptr(), -1, sigs.as_mut_ptr(), sigs.len() as i32,
+        ), -4);
+    }
+
+    #[test]
+    fn test_mine_pow_cancellable() {
+        let mut buf = vec![7u8; 64];
+        let mut nonce_out = 0u64;
+        let mut hash_buf = [0u8; 64];
+        let mut progress = 0u64;
+        let no_cancel = 0u8;
+
+        // Same result as the blocking miner when left alone
+        let found = los_mine_pow_cancellable(
+            buf.as_mut_ptr(), 64, 8, 12, 10_000_000,
+            &no_cancel, &mut progress,
+            &mut nonce_out, hash_buf.as_mut_ptr(), 64,
+        );
+        assert_eq!(found, 64);
+        let (mut plain_nonce, mut plain_hash) = (0u64, [0u8; 64]);
+        los_mine_pow(
+            buf.as_mut_ptr(), 64, 8, 12, 10_000_000,
+            &mut plain_nonce, plain_hash.as_mut_ptr(), 64,
+        );
+        assert_eq!(nonce_out, plain_nonce);
+        assert_eq!(hash_buf, plain_hash);
+        assert_eq!(progress, nonce_out + 1);
+
+        // Cancelled from another thread while searching an impossible target
+        let cancel = Box::leak(Box::new(AtomicU8::new(0)));
+        let progress = Box::leak(Box::new(AtomicU64::new(0)));
+        let cancel_addr = cancel as *const AtomicU8 as usize;
+        let progress_addr = progress as *const AtomicU64 as usize;
+        let miner = std::thread::spawn(move || {
+            let mut buf = vec![7u8; 64];
+            let mut nonce_out = 0u64;
+            let mut hash_buf = [0u8; 64];
+            los_mine_pow_cancellable(
+                buf.as_mut_ptr(), 64, 8, 255, u64::MAX,
+                cancel_addr as *const u8, progress_addr as *mut u64,
+                &mut nonce_out, hash_buf.as_mut_ptr(), 64,
+            )
+        });
+        while progress.load(Ordering::Relaxed) < POW_BATCH {
+            std::thread::yield_now();
+        }
+        cancel.store(1, Ordering::Relaxed);
+        assert_eq!(miner.join().unwrap(), -7);
+        assert!(progress.load(Ordering::Relaxed) >= POW_BATCH);
+    }
+
+    #[test]
+    fn test_keystore_roundtrip_and_signing() {
+        let (pk, mut sk) = seeded_keypair(21);
+        let password = "correct horse battery staple";
+        let mut keystore = vec![0u8; los_max_keystore_bytes() as usize];
+        let ks_len = los_encrypt_keystore(
+            sk.as_ptr(), sk.len() as i32,
+            password.as_ptr(), password.len() as i32,
+            keystore.as_mut_ptr(), keystore.len() as i32,
+        );
+        assert!(ks_len > 0, "encryption failed: {}", ks_len);
+        keystore.truncate(ks_len as usize);
+        assert!(los_crypto::is_encrypted(&keystore));
+
+        let mut decrypted = vec![0u8; sk.len()];
+        let sk_len = los_decrypt_keystore(
+            keystore.as_ptr(), ks_len,
+            password.as_ptr(), password.len() as i32,
+            decrypted.as_mut_ptr(), decrypted.len() as i32,
+        );
+        assert_eq!(sk_len as usize, sk.len());
+        assert_eq!(decrypted, sk);
+
+        let message = b"signing hash";
+        let mut sig = vec![0u8; los_signature_bytes() as usize];
+        let sig_len = los_sign_with_keystore(
+            message.as_ptr(), message.len() as i32,
+            keystore.as_ptr(), ks_len,
+            password.as_ptr(), password.len() as i32,
+            sig.as_mut_ptr(), sig.len() as i32,
+        );
+        assert!(sig_len > 0);
+        assert_eq!(los_verify(
+            message.as_ptr(), message.len() as i32,
+            sig.as_ptr(), sig_len,
+            pk.as_ptr(), pk.len() as i32,
+        ), 1);
+
+        let wrong = "wrong password";
+        assert_eq!(los_sign_with_keystore(
+            message.as_ptr(), message.len() as i32,
+            keystore.as_ptr(), ks_len,
+            wrong.as_ptr(), wrong.len() as i32,
+            sig.as_mut_ptr(), sig.len() as i32,
+        ), -8);
+        assert_eq!(los_decrypt_keystore(
+            b"not a keystore".as_ptr(), 14,
+            password.as_ptr(), password.len() as i32,
+            decrypted.as_mut_ptr(), decrypted.len() as i32,
+        ), -3);
+
+        // Negative capacities are rejected before any cast to usize
+        let mut out = vec![0u8; los_max_keystore_bytes() as usize];
+        assert_eq!(los_encrypt_keystore(
+            sk.as_ptr(), sk.len() as i32,
+            password.as_ptr(), password.len() as i32,
+            out.as_mut_ptr(), -1,
+        ), -3);
+        assert_eq!(los_decrypt_keystore(
+            keystore.as_ptr(), ks_len,
+            password.as_ptr(), password.len() as i32,
+            decrypted.as_mut_ptr(), -1,
+        ), -3);
+        assert_eq!(los_sign_with_keystore(
+            message.as_ptr(), message.len() as i32,
+            keystore.as_ptr(), ks_len,
+            password.as_ptr(), password.len() as i32,
+            sig.as_mut_ptr(), i32::MIN,
+        ), -3);
+
+        assert_eq!(los_zeroize(sk.as_mut_ptr(), sk.len() as i32), 0);
+        assert!(sk.iter().all(|&b| b == 0));
+    }
 }
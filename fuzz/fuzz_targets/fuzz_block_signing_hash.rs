@@ -28,14 +28,15 @@ struct FuzzBlock {
 
 impl From<FuzzBlock> for Block {
     fn from(fb: FuzzBlock) -> Self {
-        let block_type = match fb.block_type_idx % 7 {
+        let block_type = match fb.block_type_idx % 8 {
             0 => BlockType::Send,
             1 => BlockType::Receive,
             2 => BlockType::Change,
             3 => BlockType::Mint,
             4 => BlockType::Slash,
             5 => BlockType::ContractDeploy,
-            _ => BlockType::ContractCall,
+            6 => BlockType::ContractCall,
+            _ => BlockType::Migrate,
         };
         Block {
             account: fb.account,
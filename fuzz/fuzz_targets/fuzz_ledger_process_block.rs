@@ -45,14 +45,15 @@ fuzz_target!(|input: FuzzLedgerInput| {
         }
     }
 
-    let block_type = match input.block_type_idx % 7 {
+    let block_type = match input.block_type_idx % 8 {
         0 => BlockType::Send,
         1 => BlockType::Receive,
         2 => BlockType::Change,
         3 => BlockType::Mint,
         4 => BlockType::Slash,
         5 => BlockType::ContractDeploy,
-        _ => BlockType::ContractCall,
+        6 => BlockType::ContractCall,
+        _ => BlockType::Migrate,
     };
 
     let block = Block {
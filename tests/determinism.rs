@@ -0,0 +1,319 @@
+// ========================================
+// CONSENSUS DETERMINISM TESTS FOR UNAUTHORITY (LOS)
+// ========================================
+//
+// Every validator must produce the same bytes for the same consensus state:
+// state roots, checkpoint IDs and SYNC payloads are compared across nodes.
+// A HashMap/HashSet slipped into a serialized consensus structure makes the
+// output depend on insertion order and the per-process hash seed, which
+// splits the network without any test noticing.
+//
+// Each fixture (Ledger, WasmEngine state, full checkpoint state root,
+// finality checkpoint, validator reward pool) is built on several threads, each inserting in a different
+// order, and the serialized bytes must be identical. Their blake3 digests are
+// then compared with the replay vectors in tests/vectors/determinism_v1.json,
+// so a change in serialization between versions (or platforms) fails CI.
+//
+// A deliberate format change must regenerate the vectors:
+//   LOS_UPDATE_VECTORS=1 cargo test --test determinism
+//
+// Usage:
+//   cargo test --test determinism
+//
+// ========================================
+
+use std::collections::BTreeMap;
+use std::thread;
+
+use los_consensus::checkpoint::{full_state_root, CheckpointSignature, FinalityCheckpoint};
+use los_core::validator_rewards::ValidatorRewardPool;
+use los_core::{AccountState, Block, BlockType, Ledger, CIL_PER_LOS, MIN_VALIDATOR_STAKE_CIL};
+use los_vm::WasmEngine;
+
+const VECTORS_PATH: &str = "tests/vectors/determinism_v1.json";
+/// Threads (and insertion orders) per fixture
+const BUILDERS: usize = 8;
+const GENESIS_TS: u64 = 1_771_000_000;
+
+/// Deterministic permutation of 0..n, different for each `seed`
+fn permutation(n: usize, seed: u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut x = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    for i in (1..n).rev() {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        order.swap(i, (x >> 33) as usize % (i + 1));
+    }
+    order
+}
+
+fn address(i: usize) -> String {
+    format!("LOSdet{:04}", i)
+}
+
+fn block(
+    account: &str,
+    previous: &str,
+    block_type: BlockType,
+    amount: u128,
+    link: &str,
+    ts: u64,
+) -> Block {
+    let fee = if block_type == BlockType::Send {
+        100_000
+    } else {
+        0
+    };
+    Block {
+        account: account.to_string(),
+        previous: previous.to_string(),
+        block_type,
+        amount,
+        link: link.to_string(),
+        signature: format!("sig-{}-{}", account, ts),
+        public_key: String::new(),
+        work: 0,
+        timestamp: ts,
+        fee,
+        valid_after: 0,
+        expires_at: 0,
+    }
+}
+
+// ── Fixtures ────────────────────────────────────────────────────────
+
+/// Ledger with 32 accounts, Mint → Send chains and claimed sends
+fn build_ledger(seed: u64) -> Ledger {
+    let mut ledger = Ledger::new();
+    let mut entries = Vec::new();
+    for i in 0..32 {
+        let addr = address(i);
+        let mint = block(
+            &addr,
+            "0",
+            BlockType::Mint,
+            (i as u128 + 1) * CIL_PER_LOS,
+            "GENESIS",
+            GENESIS_TS + i as u64,
+        );
+        let mint_hash = mint.calculate_hash();
+        let send = block(
+            &addr,
+            &mint_hash,
+            BlockType::Send,
+            7 * i as u128,
+            &address((i + 1) % 32),
+            GENESIS_TS + 100 + i as u64,
+        );
+        let send_hash = send.calculate_hash();
+        entries.push((addr, mint_hash, mint, send_hash, send, i));
+    }
+    for idx in permutation(entries.len(), seed) {
+        let (addr, mint_hash, mint, send_hash, send, i) = entries[idx].clone();
+        ledger.blocks.insert(mint_hash, mint);
+        ledger.blocks.insert(send_hash.clone(), send);
+        ledger.claimed_sends.insert(send_hash.clone());
+        ledger.accounts.insert(
+            addr,
+            AccountState {
+                head: send_hash,
+                balance: (i as u128 + 1) * CIL_PER_LOS - 7 * i as u128,
+                block_count: 2,
+                is_validator: i % 4 == 0,
+            },
+        );
+    }
+    ledger.accumulated_fees_cil = 3_200_000;
+    ledger.total_slashed_cil = 42;
+    ledger.contract_held_cil = 1_000;
+    ledger
+}
+
+/// VM with 6 contracts from 3 owners, each with 20 state keys and a balance
+fn build_vm(seed: u64) -> WasmEngine {
+    let engine = WasmEngine::new();
+    let mut addresses = Vec::new();
+    for c in 0..6 {
+        let keys = permutation(20, seed.wrapping_add(c));
+        let mut state = BTreeMap::new();
+        for k in keys {
+            state.insert(format!("key:{:02}", k), format!("value-{}-{}", c, k));
+        }
+        let owner = address(c as usize % 3);
+        let addr = engine
+            .deploy_contract(owner, b"\0asm\x01\x00\x00\x00".to_vec(), state, 10 + c)
+            .expect("deploy fixture contract");
+        addresses.push(addr);
+    }
+    for idx in permutation(addresses.len(), seed) {
+        engine
+            .send_to_contract(&addresses[idx], 1_000 + idx as u128)
+            .expect("fund fixture contract");
+    }
+    engine
+}
+
+/// Full state root over the ledger, VM and reward pool fixtures
+fn build_full_root(seed: u64) -> String {
+    full_state_root(
+        &build_ledger(seed).compute_state_root(),
+        &build_vm(seed).state_root().expect("vm state root"),
+        &build_rewards(seed).0.state_root(),
+    )
+}
+
+/// Checkpoint at height 2000 with 5 validator signatures
+fn build_checkpoint(_seed: u64) -> FinalityCheckpoint {
+    let signatures = (0..5)
+        .map(|i| CheckpointSignature {
+            validator_address: address(i * 4),
+            signature: blake3::hash(address(i).as_bytes()).as_bytes().to_vec(),
+        })
+        .collect();
+    let mut cp = FinalityCheckpoint::new(2_000, "ab".repeat(32), 8, build_full_root(0), signatures);
+    cp.timestamp = GENESIS_TS + 2_000; // new() stamps the wall clock
+    cp
+}
+
+/// Reward pool after one distributed epoch, plus the payouts it returned
+fn build_rewards(seed: u64) -> (ValidatorRewardPool, Vec<(String, u128)>) {
+    let mut pool = ValidatorRewardPool::with_balance(GENESIS_TS, 500_000 * CIL_PER_LOS);
+    pool.epoch_duration_secs = 120;
+    for idx in permutation(12, seed) {
+        pool.register_validator(
+            &address(idx),
+            idx == 0,
+            MIN_VALIDATOR_STAKE_CIL + idx as u128 * CIL_PER_LOS,
+        );
+    }
+    pool.advance_epoch_only(); // past probation
+    pool.set_expected_heartbeats(60);
+    for idx in permutation(12, seed.wrapping_add(1)) {
+        for _ in 0..2 {
+            pool.record_heartbeat(&address(idx));
+        }
+    }
+    let payouts = pool.distribute_epoch_rewards();
+    (pool, payouts)
+}
+
+// ── Harness ────────────────────────────────────────────────────────
+
+/// Serialize a fixture on BUILDERS threads (one insertion order each) and
+/// assert every thread produced the same bytes.
+fn identical_across_threads(name: &str, build: fn(u64) -> Vec<u8>) -> Vec<u8> {
+    let outputs: Vec<Vec<u8>> = (0..BUILDERS as u64)
+        .map(|seed| thread::spawn(move || build(seed)))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|h| h.join().expect("fixture thread panicked"))
+        .collect();
+    for (seed, out) in outputs.iter().enumerate().skip(1) {
+        assert!(
+            *out == outputs[0],
+            "{}: insertion order {} serialized differently — non-deterministic container in a consensus structure?",
+            name,
+            seed
+        );
+    }
+    outputs[0].clone()
+}
+
+fn digest(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
+
+fn compute_vectors() -> BTreeMap<String, String> {
+    let ledger = identical_across_threads("ledger", |seed| {
+        serde_json::to_vec(&build_ledger(seed)).expect("serialize ledger")
+    });
+    let state_root = identical_across_threads("state_root", |seed| {
+        build_ledger(seed).compute_state_root().into_bytes()
+    });
+    let vm = identical_across_threads("vm_state", |seed| {
+        build_vm(seed).serialize_all().expect("serialize vm")
+    });
+    let full_root =
+        identical_across_threads("full_state_root", |seed| build_full_root(seed).into_bytes());
+    let checkpoint = identical_across_threads("checkpoint", |seed| {
+        serde_json::to_vec(&build_checkpoint(seed)).expect("serialize checkpoint")
+    });
+    let checkpoint_id = identical_across_threads("checkpoint_id", |seed| {
+        build_checkpoint(seed).calculate_id().into_bytes()
+    });
+    let rewards = identical_across_threads("reward_pool", |seed| {
+        serde_json::to_vec(&build_rewards(seed).0).expect("serialize reward pool")
+    });
+    let payouts = identical_across_threads("reward_payouts", |seed| {
+        let (_, payouts) = build_rewards(seed);
+        assert!(!payouts.is_empty(), "reward fixture paid nobody");
+        serde_json::to_vec(&payouts).expect("serialize payouts")
+    });
+
+    let mut v = BTreeMap::new();
+    v.insert("ledger_json".to_string(), digest(&ledger));
+    v.insert(
+        "ledger_state_root".to_string(),
+        String::from_utf8(state_root).unwrap_or_default(),
+    );
+    v.insert("vm_state".to_string(), digest(&vm));
+    v.insert(
+        "full_state_root".to_string(),
+        String::from_utf8(full_root).unwrap_or_default(),
+    );
+    v.insert("checkpoint_json".to_string(), digest(&checkpoint));
+    v.insert(
+        "checkpoint_id".to_string(),
+        String::from_utf8(checkpoint_id).unwrap_or_default(),
+    );
+    v.insert("reward_pool_json".to_string(), digest(&rewards));
+    v.insert("reward_payouts".to_string(), digest(&payouts));
+    v
+}
+
+// ========================================
+// TEST 1: SAME BYTES ON EVERY THREAD AND INSERTION ORDER
+// ========================================
+#[test]
+fn test_consensus_structures_serialize_identically() {
+    let first = compute_vectors();
+    // A second pass in the same process must agree too (fresh hash seeds)
+    assert_eq!(first, compute_vectors());
+}
+
+// ========================================
+// TEST 2: CROSS-VERSION REPLAY VECTORS
+// ========================================
+#[test]
+fn test_replay_vectors_match() {
+    let actual = compute_vectors();
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTORS_PATH);
+    if std::env::var("LOS_UPDATE_VECTORS").is_ok_and(|v| v == "1") {
+        let json = serde_json::json!({
+            "version": 1,
+            "note": "blake3 digests of consensus fixtures in tests/determinism.rs",
+            "vectors": actual,
+        });
+        let text = serde_json::to_string_pretty(&json).expect("encode vectors") + "\n";
+        std::fs::write(&path, text).expect("write vectors");
+        println!("📝 Updated {}", VECTORS_PATH);
+        return;
+    }
+    let text = std::fs::read_to_string(&path).expect("read replay vectors");
+    let file: serde_json::Value = serde_json::from_str(&text).expect("parse replay vectors");
+    let expected: BTreeMap<String, String> =
+        serde_json::from_value(file["vectors"].clone()).expect("vectors object");
+    for (name, want) in &expected {
+        let got = actual.get(name).map(String::as_str).unwrap_or("<missing>");
+        assert_eq!(
+            got, want,
+            "replay vector '{}' changed — consensus serialization differs from {}. \
+             If the change is intentional, regenerate with LOS_UPDATE_VECTORS=1",
+            name, VECTORS_PATH
+        );
+    }
+    assert_eq!(expected.len(), actual.len(), "replay vector set changed");
+}
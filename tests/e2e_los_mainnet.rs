@@ -120,6 +120,8 @@ fn make_mint_block(
         work: 0,
         timestamp: now,
         fee: 0,
+        valid_after: 0,
+        expires_at: 0,
     };
     mine_and_sign(&mut block, secret_key);
     block
@@ -145,6 +147,8 @@ fn make_send_block(
         work: 0,
         timestamp,
         fee: fee_cil,
+        valid_after: 0,
+        expires_at: 0,
     };
     mine_and_sign(&mut block, &sender.secret_key);
     block
@@ -171,6 +175,8 @@ fn make_receive_block(
         work: 0,
         timestamp,
         fee: 0,
+        valid_after: 0,
+        expires_at: 0,
     };
     mine_and_sign(&mut block, secret_key);
     block
@@ -691,6 +697,8 @@ async fn test_distribution_supply_exhaustion() {
             work: 0,
             timestamp: ts_base + mint_count + 1,
             fee: 0,
+            valid_after: 0,
+            expires_at: 0,
         };
         let mut mint = mint;
         mine_and_sign(&mut mint, &node.secret_key);
@@ -736,6 +744,8 @@ async fn test_distribution_supply_exhaustion() {
             work: 0,
             timestamp: ts_base + mint_count + 2,
             fee: 0,
+            valid_after: 0,
+            expires_at: 0,
         };
         let mut over_mint = over_mint;
         mine_and_sign(&mut over_mint, &node.secret_key);
@@ -979,8 +989,8 @@ async fn test_linear_voting_power() {
 
     // SECURITY: Linear voting — 10x stake gives 10x power (Sybil-neutral).
     // Previously used √stake which made Sybil attacks profitable by splitting stake.
-    if powers[0] > 0 {
-        let ratio_10x = (powers[1] * 100) / powers[0]; // basis points-like
+    if let Some(ratio_10x) = (powers[1] * 100).checked_div(powers[0]) {
+        // basis points-like
         assert!(
             ratio_10x == 1000,
             "10x stake should yield exactly 10x power (linear), got ratio {}",
@@ -1142,6 +1152,8 @@ async fn test_chain_id_replay_protection() {
         work: 0,
         timestamp: now_secs(),
         fee: 0,
+        valid_after: 0,
+        expires_at: 0,
     };
     mine_and_sign(&mut block1, &kp.secret_key);
 
@@ -74,6 +74,8 @@ async fn test_three_validator_consensus() {
             .unwrap_or_default()
             .as_secs(),
         fee: 0,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     // Broadcast block to all validators (simulate consensus)
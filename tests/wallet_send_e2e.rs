@@ -141,6 +141,8 @@ fn test_wallet_send_from_mnemonic() {
         work: 0,
         timestamp,
         fee: base_fee,
+        valid_after: 0,
+        expires_at: 0,
     };
 
     // 7. Mine PoW